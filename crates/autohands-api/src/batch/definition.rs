@@ -0,0 +1,317 @@
+//! Batch definition and status.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use autohands_runloop::TaskPriority;
+
+use crate::error::InterfaceError;
+
+/// Default most tasks in one batch.
+pub const DEFAULT_MAX_BATCH_TASKS: usize = 200;
+
+/// Default seconds a batch task may take from submission to its outcome.
+pub const DEFAULT_BATCH_TASK_TIMEOUT_SECS: u64 = 3600;
+
+/// One task of a batch request.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BatchTaskSpec {
+    /// Key other tasks of the batch name in `depends_on`; defaults to the
+    /// task's position in the batch.
+    #[serde(default)]
+    pub key: Option<String>,
+    /// The task description for the agent to execute.
+    pub task: String,
+    /// Optional agent ID to use. Defaults to "general".
+    #[serde(default)]
+    pub agent_id: Option<String>,
+    /// Keys of tasks that must complete before this one starts.
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+}
+
+/// Request to submit a batch of tasks.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BatchRequest {
+    /// Tasks to run.
+    pub tasks: Vec<BatchTaskSpec>,
+    /// Priority of every task in the batch.
+    #[serde(default)]
+    pub priority: TaskPriority,
+    /// Seconds each task may take from submission to its outcome; defaults
+    /// to [`DEFAULT_BATCH_TASK_TIMEOUT_SECS`].
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+}
+
+/// Status of a task within a batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchTaskStatus {
+    /// Waiting for its dependencies.
+    Waiting,
+    /// Submitted to the RunLoop.
+    Queued,
+    /// Agent finished.
+    Completed,
+    /// Submission or the agent failed.
+    Failed,
+    /// Not run because a dependency did not complete.
+    Skipped,
+}
+
+/// A task within a batch.
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchTask {
+    /// Key within the batch.
+    pub key: String,
+    /// Task description.
+    pub task: String,
+    /// Agent to run the task.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent_id: Option<String>,
+    /// Keys of tasks this one waits for.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+    /// Agent session of the task.
+    pub session_id: String,
+    /// RunLoop task, once submitted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<Uuid>,
+    /// Current status.
+    pub status: BatchTaskStatus,
+    /// Agent's final response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
+    /// Error or reason the task was skipped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Aggregate status of a batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchStatus {
+    /// Some tasks are waiting or queued.
+    Running,
+    /// Every task completed.
+    Completed,
+    /// Finished, with at least one failed or skipped task.
+    Failed,
+}
+
+/// Number of tasks per status.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BatchCounts {
+    pub total: usize,
+    pub waiting: usize,
+    pub queued: usize,
+    pub completed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+}
+
+/// A submitted batch.
+#[derive(Debug, Clone, Serialize)]
+pub struct Batch {
+    /// Batch ID.
+    pub id: String,
+    /// Priority of the batch's tasks.
+    pub priority: TaskPriority,
+    /// Seconds each task may take; tasks without an outcome by then fail.
+    pub timeout_secs: u64,
+    /// When the batch was submitted.
+    pub created_at: DateTime<Utc>,
+    /// When the last task finished.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Tasks, in request order.
+    pub tasks: Vec<BatchTask>,
+}
+
+impl Batch {
+    /// Validate a request and create its batch.
+    ///
+    /// Rejects empty batches, batches over `max_tasks`, duplicate keys,
+    /// unknown dependencies and dependency cycles.
+    pub fn from_request(request: BatchRequest, max_tasks: usize) -> Result<Self, InterfaceError> {
+        let invalid = |msg: String| Err(InterfaceError::InvalidBatch(msg));
+        if request.tasks.is_empty() {
+            return invalid("Batch has no tasks".to_string());
+        }
+        if request.tasks.len() > max_tasks {
+            return invalid(format!("Batch has more than {} tasks", max_tasks));
+        }
+        if request.timeout_secs == Some(0) {
+            return invalid("Task timeout must be at least one second".to_string());
+        }
+
+        let id = Uuid::new_v4().to_string();
+        let mut keys = HashSet::new();
+        let mut tasks = Vec::with_capacity(request.tasks.len());
+        for (index, spec) in request.tasks.into_iter().enumerate() {
+            let key = spec.key.unwrap_or_else(|| index.to_string());
+            if spec.task.trim().is_empty() {
+                return invalid(format!("Task '{}' has no description", key));
+            }
+            if !keys.insert(key.clone()) {
+                return invalid(format!("Duplicate task key '{}'", key));
+            }
+            tasks.push(BatchTask {
+                session_id: format!("batch:{}:{}", id, key),
+                key,
+                task: spec.task,
+                agent_id: spec.agent_id,
+                depends_on: spec.depends_on,
+                task_id: None,
+                status: BatchTaskStatus::Waiting,
+                result: None,
+                error: None,
+            });
+        }
+
+        for task in &tasks {
+            if let Some(dep) = task.depends_on.iter().find(|dep| !keys.contains(*dep)) {
+                return invalid(format!("Task '{}' depends on unknown task '{}'", task.key, dep));
+            }
+        }
+        if let Some(key) = find_cycle(&tasks) {
+            return invalid(format!("Task '{}' is part of a dependency cycle", key));
+        }
+
+        Ok(Self {
+            id,
+            priority: request.priority,
+            timeout_secs: request.timeout_secs.unwrap_or(DEFAULT_BATCH_TASK_TIMEOUT_SECS),
+            created_at: Utc::now(),
+            finished_at: None,
+            tasks,
+        })
+    }
+
+    /// Number of tasks per status.
+    pub fn counts(&self) -> BatchCounts {
+        let mut counts = BatchCounts {
+            total: self.tasks.len(),
+            ..Default::default()
+        };
+        for task in &self.tasks {
+            match task.status {
+                BatchTaskStatus::Waiting => counts.waiting += 1,
+                BatchTaskStatus::Queued => counts.queued += 1,
+                BatchTaskStatus::Completed => counts.completed += 1,
+                BatchTaskStatus::Failed => counts.failed += 1,
+                BatchTaskStatus::Skipped => counts.skipped += 1,
+            }
+        }
+        counts
+    }
+
+    /// Aggregate status.
+    pub fn status(&self) -> BatchStatus {
+        let counts = self.counts();
+        if counts.waiting + counts.queued > 0 {
+            BatchStatus::Running
+        } else if counts.completed == counts.total {
+            BatchStatus::Completed
+        } else {
+            BatchStatus::Failed
+        }
+    }
+
+    /// Whether no task is waiting or queued.
+    pub fn is_finished(&self) -> bool {
+        self.status() != BatchStatus::Running
+    }
+
+    /// Indices of waiting tasks whose dependencies all completed.
+    pub(crate) fn ready(&self) -> Vec<usize> {
+        let completed: HashSet<&str> = self
+            .tasks
+            .iter()
+            .filter(|t| t.status == BatchTaskStatus::Completed)
+            .map(|t| t.key.as_str())
+            .collect();
+        self.tasks
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| {
+                t.status == BatchTaskStatus::Waiting
+                    && t.depends_on.iter().all(|dep| completed.contains(dep.as_str()))
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Skip waiting tasks that depend, directly or not, on a failed or
+    /// skipped task.
+    pub(crate) fn skip_blocked(&mut self) {
+        loop {
+            let statuses: HashMap<String, BatchTaskStatus> = self
+                .tasks
+                .iter()
+                .map(|t| (t.key.clone(), t.status))
+                .collect();
+            let mut changed = false;
+            for task in &mut self.tasks {
+                if task.status != BatchTaskStatus::Waiting {
+                    continue;
+                }
+                let blocked = task.depends_on.iter().find(|dep| {
+                    matches!(
+                        statuses.get(*dep),
+                        Some(BatchTaskStatus::Failed | BatchTaskStatus::Skipped)
+                    )
+                });
+                if let Some(dep) = blocked {
+                    task.status = BatchTaskStatus::Skipped;
+                    task.error = Some(format!("Dependency '{}' did not complete", dep));
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+    }
+}
+
+/// Key of a task on a dependency cycle, if there is one.
+fn find_cycle(tasks: &[BatchTask]) -> Option<String> {
+    // Kahn's algorithm: whatever cannot be ordered is on or behind a cycle
+    let mut remaining: HashMap<&str, usize> = tasks
+        .iter()
+        .map(|t| (t.key.as_str(), t.depends_on.len()))
+        .collect();
+    let mut ready: Vec<&str> = remaining
+        .iter()
+        .filter(|(_, deps)| **deps == 0)
+        .map(|(key, _)| *key)
+        .collect();
+    while let Some(done) = ready.pop() {
+        remaining.remove(done);
+        for task in tasks {
+            let waits = task.depends_on.iter().filter(|dep| *dep == done).count();
+            if waits == 0 {
+                continue;
+            }
+            if let Some(deps) = remaining.get_mut(task.key.as_str()) {
+                *deps -= waits;
+                if *deps == 0 {
+                    ready.push(task.key.as_str());
+                }
+            }
+        }
+    }
+    tasks
+        .iter()
+        .find(|t| remaining.contains_key(t.key.as_str()))
+        .map(|t| t.key.clone())
+}
+
+#[cfg(test)]
+#[path = "definition_tests.rs"]
+mod tests;
@@ -0,0 +1,112 @@
+use super::*;
+
+fn spec(key: &str, depends_on: &[&str]) -> BatchTaskSpec {
+    BatchTaskSpec {
+        key: Some(key.to_string()),
+        task: format!("Do {}", key),
+        agent_id: None,
+        depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+    }
+}
+
+fn request(tasks: Vec<BatchTaskSpec>) -> BatchRequest {
+    BatchRequest {
+        tasks,
+        priority: TaskPriority::Normal,
+        timeout_secs: None,
+    }
+}
+
+fn invalid(request: BatchRequest) -> String {
+    match Batch::from_request(request, DEFAULT_MAX_BATCH_TASKS) {
+        Err(InterfaceError::InvalidBatch(msg)) => msg,
+        other => panic!("expected invalid batch, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_request_deserialization() {
+    let request: BatchRequest = serde_json::from_str(
+        r#"{"priority": "High", "tasks": [{"task": "a"}, {"key": "b", "task": "b", "depends_on": ["0"]}]}"#,
+    )
+    .unwrap();
+    assert_eq!(request.priority, TaskPriority::High);
+
+    let batch = Batch::from_request(request, DEFAULT_MAX_BATCH_TASKS).unwrap();
+    assert_eq!(batch.tasks[0].key, "0");
+    assert_eq!(batch.tasks[1].depends_on, vec!["0"]);
+    assert_eq!(batch.tasks[1].session_id, format!("batch:{}:b", batch.id));
+    assert_eq!(batch.status(), BatchStatus::Running);
+    assert_eq!(batch.timeout_secs, DEFAULT_BATCH_TASK_TIMEOUT_SECS);
+}
+
+#[test]
+fn test_rejects_invalid_requests() {
+    assert!(invalid(request(vec![])).contains("no tasks"));
+    assert!(invalid(request(vec![spec("a", &[]), spec("a", &[])])).contains("Duplicate"));
+    assert!(invalid(request(vec![spec("a", &["x"])])).contains("unknown task 'x'"));
+
+    let mut empty = spec("a", &[]);
+    empty.task = "  ".to_string();
+    assert!(invalid(request(vec![empty])).contains("no description"));
+
+    let tasks = (0..3).map(|i| spec(&i.to_string(), &[])).collect();
+    assert!(Batch::from_request(request(tasks), 2).is_err());
+}
+
+#[test]
+fn test_rejects_cycles() {
+    let msg = invalid(request(vec![
+        spec("a", &[]),
+        spec("b", &["a", "d"]),
+        spec("c", &["b"]),
+        spec("d", &["c"]),
+    ]));
+    assert!(msg.contains("cycle"));
+    assert!(invalid(request(vec![spec("a", &["a"])])).contains("cycle"));
+}
+
+#[test]
+fn test_ready_and_skip_blocked() {
+    let mut batch = Batch::from_request(
+        request(vec![
+            spec("a", &[]),
+            spec("b", &[]),
+            spec("c", &["a"]),
+            spec("d", &["b", "c"]),
+        ]),
+        DEFAULT_MAX_BATCH_TASKS,
+    )
+    .unwrap();
+    assert_eq!(batch.ready(), vec![0, 1]);
+
+    batch.tasks[0].status = BatchTaskStatus::Completed;
+    batch.tasks[1].status = BatchTaskStatus::Failed;
+    assert_eq!(batch.ready(), vec![2]);
+
+    batch.skip_blocked();
+    assert_eq!(batch.tasks[3].status, BatchTaskStatus::Skipped);
+    assert!(batch.tasks[3].error.as_deref().unwrap().contains("'b'"));
+    assert_eq!(batch.tasks[2].status, BatchTaskStatus::Waiting);
+}
+
+#[test]
+fn test_counts_and_status() {
+    let mut batch = Batch::from_request(
+        request(vec![spec("a", &[]), spec("b", &["a"])]),
+        DEFAULT_MAX_BATCH_TASKS,
+    )
+    .unwrap();
+    batch.tasks[0].status = BatchTaskStatus::Queued;
+    let counts = batch.counts();
+    assert_eq!((counts.total, counts.queued, counts.waiting), (2, 1, 1));
+    assert!(!batch.is_finished());
+
+    batch.tasks[0].status = BatchTaskStatus::Completed;
+    batch.tasks[1].status = BatchTaskStatus::Completed;
+    assert_eq!(batch.status(), BatchStatus::Completed);
+
+    batch.tasks[1].status = BatchTaskStatus::Skipped;
+    assert_eq!(batch.status(), BatchStatus::Failed);
+    assert!(batch.is_finished());
+}
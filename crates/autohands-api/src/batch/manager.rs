@@ -0,0 +1,204 @@
+//! Batch execution.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use futures::stream::{FuturesUnordered, StreamExt};
+use tokio::sync::{Mutex, RwLock};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use autohands_runloop::{Task, TaskOutcome};
+
+use super::definition::{Batch, BatchRequest, BatchTaskStatus, DEFAULT_MAX_BATCH_TASKS};
+use crate::error::InterfaceError;
+use crate::runloop_bridge::RunLoopState;
+
+/// Most batches kept; the oldest finished ones are dropped beyond this.
+pub const MAX_RETAINED_BATCHES: usize = 100;
+
+/// Submits batches to the RunLoop and tracks their tasks.
+///
+/// Each batch gets a driver that submits tasks as their dependencies
+/// complete and records every task's outcome from the RunLoop's
+/// [`TaskOutcomeLog`](autohands_runloop::TaskOutcomeLog). A task without an
+/// outcome within the batch's timeout is marked failed; its RunLoop task
+/// carries the same deadline, so it is not started late.
+///
+/// Batches are kept in memory only and are lost on restart.
+pub struct BatchManager {
+    runloop: Arc<RunLoopState>,
+    batches: RwLock<HashMap<String, Arc<Mutex<Batch>>>>,
+    max_tasks: usize,
+}
+
+impl BatchManager {
+    /// Create a manager submitting to the given RunLoop.
+    pub fn new(runloop: Arc<RunLoopState>) -> Self {
+        Self {
+            runloop,
+            batches: RwLock::new(HashMap::new()),
+            max_tasks: DEFAULT_MAX_BATCH_TASKS,
+        }
+    }
+
+    /// Set the most tasks accepted in one batch.
+    pub fn with_max_tasks(mut self, max_tasks: usize) -> Self {
+        self.max_tasks = max_tasks;
+        self
+    }
+
+    /// Most tasks accepted in one batch.
+    pub fn max_tasks(&self) -> usize {
+        self.max_tasks
+    }
+
+    /// Validate and start a batch, returning it with its first tasks queued.
+    pub async fn submit(self: &Arc<Self>, request: BatchRequest) -> Result<Batch, InterfaceError> {
+        let batch = Batch::from_request(request, self.max_tasks)?;
+        let id = batch.id.clone();
+        let batch = Arc::new(Mutex::new(batch));
+
+        {
+            let mut batches = self.batches.write().await;
+            batches.insert(id.clone(), batch.clone());
+            self.evict(&mut batches).await;
+        }
+
+        let queued = self.submit_ready(&mut *batch.lock().await).await;
+        info!("Batch {} submitted", id);
+
+        let snapshot = batch.lock().await.clone();
+        let manager = self.clone();
+        tokio::spawn(async move { manager.drive(batch, queued).await });
+        Ok(snapshot)
+    }
+
+    /// Current state of a batch.
+    pub async fn get(&self, id: &str) -> Option<Batch> {
+        let batch = self.batches.read().await.get(id).cloned()?;
+        let batch = batch.lock().await.clone();
+        Some(batch)
+    }
+
+    /// Wait for queued tasks to finish, submitting their dependents.
+    async fn drive(&self, batch: Arc<Mutex<Batch>>, queued: Vec<(usize, Uuid)>) {
+        let outcomes = self.runloop.run_loop().outcome_log();
+        let timeout = Duration::from_secs(batch.lock().await.timeout_secs);
+        let mut pending = FuturesUnordered::new();
+        let wait = |(index, task_id): (usize, Uuid)| {
+            let outcomes = outcomes.clone();
+            async move {
+                let outcome = tokio::time::timeout(timeout, outcomes.wait(task_id))
+                    .await
+                    .unwrap_or_else(|_| {
+                        warn!("Batch task {} timed out after {:?}", task_id, timeout);
+                        TaskOutcome::failed(format!("Timed out after {} s", timeout.as_secs()))
+                    });
+                (index, outcome)
+            }
+        };
+        pending.extend(queued.into_iter().map(wait));
+
+        while let Some((index, outcome)) = pending.next().await {
+            let mut batch = batch.lock().await;
+            record(&mut batch, index, outcome);
+            pending.extend(self.submit_ready(&mut batch).await.into_iter().map(wait));
+        }
+
+        let mut batch = batch.lock().await;
+        batch.finished_at = Some(Utc::now());
+        let counts = batch.counts();
+        info!(
+            "Batch {} finished: {} completed, {} failed, {} skipped",
+            batch.id, counts.completed, counts.failed, counts.skipped
+        );
+    }
+
+    /// Submit the tasks whose dependencies completed, returning the queued
+    /// ones as (index, RunLoop task ID).
+    async fn submit_ready(&self, batch: &mut Batch) -> Vec<(usize, Uuid)> {
+        let mut queued = Vec::new();
+        // A failed submission may block further tasks, so repeat until none is ready
+        loop {
+            batch.skip_blocked();
+            let ready = batch.ready();
+            if ready.is_empty() {
+                break;
+            }
+            for index in ready {
+                let batch_id = batch.id.clone();
+                let priority = batch.priority;
+                let deadline = Utc::now() + chrono::Duration::seconds(batch.timeout_secs as i64);
+                let task = &mut batch.tasks[index];
+                let payload = serde_json::json!({
+                    "prompt": task.task,
+                    "session_id": task.session_id,
+                    "agent_id": task.agent_id,
+                    "batch_id": batch_id,
+                    "batch_task": task.key,
+                });
+                let mut runloop_task = Task::new("agent:execute", payload)
+                    .with_priority(priority)
+                    .with_deadline(deadline);
+                runloop_task
+                    .metadata
+                    .insert("batch_id".to_string(), serde_json::json!(batch_id));
+
+                match self.runloop.submit(runloop_task).await {
+                    Ok(task_id) => {
+                        task.task_id = Some(task_id);
+                        task.status = BatchTaskStatus::Queued;
+                        queued.push((index, task_id));
+                    }
+                    Err(e) => {
+                        error!("Failed to submit batch {} task '{}': {}", batch_id, task.key, e);
+                        task.status = BatchTaskStatus::Failed;
+                        task.error = Some(e.to_string());
+                    }
+                }
+            }
+        }
+        queued
+    }
+
+    /// Drop the oldest finished batches beyond [`MAX_RETAINED_BATCHES`].
+    async fn evict(&self, batches: &mut HashMap<String, Arc<Mutex<Batch>>>) {
+        let excess = batches.len().saturating_sub(MAX_RETAINED_BATCHES);
+        if excess == 0 {
+            return;
+        }
+        let mut finished = Vec::new();
+        for (id, batch) in batches.iter() {
+            let batch = batch.lock().await;
+            if batch.is_finished() {
+                finished.push((batch.created_at, id.clone()));
+            }
+        }
+        finished.sort();
+        for (_, id) in finished.into_iter().take(excess) {
+            batches.remove(&id);
+        }
+        if batches.len() > MAX_RETAINED_BATCHES {
+            warn!("{} batches still running", batches.len());
+        }
+    }
+}
+
+/// Record a task's outcome.
+fn record(batch: &mut Batch, index: usize, outcome: TaskOutcome) {
+    let task = &mut batch.tasks[index];
+    task.status = if outcome.success {
+        BatchTaskStatus::Completed
+    } else {
+        BatchTaskStatus::Failed
+    };
+    task.result = outcome.response;
+    task.error = outcome.error;
+}
+
+#[cfg(test)]
+#[path = "manager_tests.rs"]
+mod tests;
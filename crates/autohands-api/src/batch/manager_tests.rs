@@ -0,0 +1,148 @@
+use super::*;
+use std::time::Duration;
+
+use autohands_runloop::{RunLoop, RunLoopConfig};
+
+use super::super::definition::{BatchStatus, BatchTaskSpec};
+
+fn manager() -> (Arc<BatchManager>, Arc<RunLoop>) {
+    let run_loop = Arc::new(RunLoop::new(RunLoopConfig::default()));
+    let state = Arc::new(RunLoopState::from_runloop(run_loop.clone()));
+    (Arc::new(BatchManager::new(state)), run_loop)
+}
+
+fn spec(key: &str, depends_on: &[&str]) -> BatchTaskSpec {
+    BatchTaskSpec {
+        key: Some(key.to_string()),
+        task: format!("Do {}", key),
+        agent_id: None,
+        depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+    }
+}
+
+fn request(tasks: Vec<BatchTaskSpec>) -> BatchRequest {
+    BatchRequest {
+        tasks,
+        priority: Default::default(),
+        timeout_secs: None,
+    }
+}
+
+/// Poll a batch until `done` holds.
+async fn wait_for(manager: &BatchManager, id: &str, done: impl Fn(&Batch) -> bool) -> Batch {
+    tokio::time::timeout(Duration::from_secs(2), async {
+        loop {
+            let batch = manager.get(id).await.unwrap();
+            if done(&batch) {
+                return batch;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .expect("batch did not reach expected state")
+}
+
+#[tokio::test]
+async fn test_submit_queues_tasks_without_dependencies() {
+    let (manager, run_loop) = manager();
+    let batch = manager
+        .submit(request(vec![spec("a", &[]), spec("b", &[]), spec("c", &["a"])]))
+        .await
+        .unwrap();
+
+    let statuses: Vec<_> = batch.tasks.iter().map(|t| t.status).collect();
+    assert_eq!(
+        statuses,
+        vec![
+            BatchTaskStatus::Queued,
+            BatchTaskStatus::Queued,
+            BatchTaskStatus::Waiting
+        ]
+    );
+    assert!(batch.tasks[0].task_id.is_some());
+    assert!(batch.tasks[2].task_id.is_none());
+    assert_eq!(run_loop.task_queue().len().await, 2);
+    assert!(manager.get(&batch.id).await.is_some());
+}
+
+#[tokio::test]
+async fn test_dependents_queued_after_completion() {
+    let (manager, run_loop) = manager();
+    let batch = manager
+        .submit(request(vec![spec("a", &[]), spec("b", &["a"])]))
+        .await
+        .unwrap();
+
+    let first = batch.tasks[0].task_id.unwrap();
+    run_loop
+        .outcome_log()
+        .record(first, TaskOutcome::succeeded(Some("a done".to_string())));
+
+    let batch = wait_for(&manager, &batch.id, |b| {
+        b.tasks[1].status == BatchTaskStatus::Queued
+    })
+    .await;
+    assert_eq!(batch.tasks[0].status, BatchTaskStatus::Completed);
+    assert_eq!(batch.tasks[0].result.as_deref(), Some("a done"));
+
+    let second = batch.tasks[1].task_id.unwrap();
+    run_loop
+        .outcome_log()
+        .record(second, TaskOutcome::succeeded(None));
+
+    let batch = wait_for(&manager, &batch.id, |b| b.finished_at.is_some()).await;
+    assert_eq!(batch.status(), BatchStatus::Completed);
+}
+
+#[tokio::test]
+async fn test_failure_skips_dependents() {
+    let (manager, run_loop) = manager();
+    let batch = manager
+        .submit(request(vec![
+            spec("a", &[]),
+            spec("b", &["a"]),
+            spec("c", &["b"]),
+        ]))
+        .await
+        .unwrap();
+
+    let first = batch.tasks[0].task_id.unwrap();
+    run_loop.outcome_log().record(first, TaskOutcome::failed("boom"));
+
+    let batch = wait_for(&manager, &batch.id, |b| b.finished_at.is_some()).await;
+    assert_eq!(batch.status(), BatchStatus::Failed);
+    assert_eq!(batch.tasks[0].error.as_deref(), Some("boom"));
+    assert_eq!(batch.tasks[1].status, BatchTaskStatus::Skipped);
+    assert_eq!(batch.tasks[2].status, BatchTaskStatus::Skipped);
+    assert_eq!(run_loop.task_queue().len().await, 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_tasks_without_outcome_time_out() {
+    let (manager, run_loop) = manager();
+    let mut request = request(vec![spec("a", &[]), spec("b", &["a"])]);
+    request.timeout_secs = Some(1);
+    let batch = manager.submit(request).await.unwrap();
+
+    let task = run_loop.task_queue().dequeue().await.unwrap();
+    assert_eq!(Some(task.id), batch.tasks[0].task_id);
+    assert!(task.deadline.is_some());
+
+    let batch = wait_for(&manager, &batch.id, |b| b.finished_at.is_some()).await;
+    assert_eq!(batch.tasks[0].status, BatchTaskStatus::Failed);
+    assert_eq!(batch.tasks[0].error.as_deref(), Some("Timed out after 1 s"));
+    assert_eq!(batch.tasks[1].status, BatchTaskStatus::Skipped);
+}
+
+#[tokio::test]
+async fn test_rejects_batches_over_limit() {
+    let (_, run_loop) = manager();
+    let state = Arc::new(RunLoopState::from_runloop(run_loop));
+    let manager = Arc::new(BatchManager::new(state).with_max_tasks(1));
+    let result = manager
+        .submit(request(vec![spec("a", &[]), spec("b", &[])]))
+        .await;
+    assert!(matches!(result, Err(InterfaceError::InvalidBatch(_))));
+    assert!(manager.get("missing").await.is_none());
+}
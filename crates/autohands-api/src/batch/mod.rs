@@ -0,0 +1,19 @@
+//! Batch task module.
+//!
+//! Submits many agent tasks at once:
+//! - Batch definitions with a shared priority and inter-task dependencies
+//! - Batch manager queuing tasks as their dependencies complete
+//! - HTTP API routes for batch submission and status polling
+//!
+//! Batches live in memory only: a restart forgets them, along with the
+//! results of their tasks.
+
+mod definition;
+mod manager;
+pub mod routes;
+
+pub use definition::{
+    Batch, BatchCounts, BatchRequest, BatchStatus, BatchTask, BatchTaskSpec, BatchTaskStatus,
+    DEFAULT_BATCH_TASK_TIMEOUT_SECS, DEFAULT_MAX_BATCH_TASKS,
+};
+pub use manager::{BatchManager, MAX_RETAINED_BATCHES};
@@ -0,0 +1,93 @@
+//! Batch HTTP route handlers.
+//!
+//! Provides batch submission and polling:
+//! - POST   /tasks/batch       - Submit a batch of tasks
+//! - GET    /tasks/batch/{id}  - Get batch status and results
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Serialize;
+use tracing::{info, warn};
+
+use super::definition::{Batch, BatchCounts, BatchRequest, BatchStatus};
+use crate::error::InterfaceError;
+use crate::runloop_bridge::HybridAppState;
+
+/// Response for a batch.
+#[derive(Debug, Serialize)]
+pub struct BatchResponse {
+    /// Aggregate status.
+    pub status: BatchStatus,
+    /// Number of tasks per status.
+    pub counts: BatchCounts,
+    #[serde(flatten)]
+    pub batch: Batch,
+}
+
+impl From<Batch> for BatchResponse {
+    fn from(batch: Batch) -> Self {
+        Self {
+            status: batch.status(),
+            counts: batch.counts(),
+            batch,
+        }
+    }
+}
+
+/// Submit a batch of tasks.
+///
+/// Tasks without dependencies are queued immediately; the others are
+/// queued as the tasks they depend on complete.
+///
+/// POST /tasks/batch
+pub async fn submit_batch(
+    State(state): State<Arc<HybridAppState>>,
+    Json(request): Json<BatchRequest>,
+) -> impl IntoResponse {
+    info!("Submitting batch of {} tasks", request.tasks.len());
+
+    match state.batch_manager.submit(request).await {
+        Ok(batch) => (
+            StatusCode::ACCEPTED,
+            Json(serde_json::json!(BatchResponse::from(batch))),
+        ),
+        Err(e) => {
+            warn!("Rejected batch: {}", e);
+            (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )
+        }
+    }
+}
+
+/// Get a batch by ID.
+///
+/// GET /tasks/batch/{id}
+pub async fn get_batch(
+    State(state): State<Arc<HybridAppState>>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    match state.batch_manager.get(&id).await {
+        Some(batch) => (
+            StatusCode::OK,
+            Json(serde_json::json!(BatchResponse::from(batch))),
+        ),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({
+                "error": InterfaceError::Custom(format!("Batch not found: {}", id)).to_string()
+            })),
+        ),
+    }
+}
+
+#[cfg(test)]
+#[path = "routes_tests.rs"]
+mod tests;
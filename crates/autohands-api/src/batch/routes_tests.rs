@@ -0,0 +1,92 @@
+//! Tests for batch routes.
+
+use super::*;
+use crate::http::routes::create_router_with_hybrid_state;
+use crate::runloop_bridge::RunLoopState;
+use crate::state::AppState;
+use autohands_runloop::{RunLoop, RunLoopConfig};
+use axum::body::Body;
+use axum::http::Request;
+use tower::ServiceExt;
+
+fn test_state() -> Arc<HybridAppState> {
+    let run_loop = Arc::new(RunLoop::new(RunLoopConfig::default()));
+    Arc::new(HybridAppState::new(
+        Arc::new(AppState::default()),
+        Arc::new(RunLoopState::from_runloop(run_loop)),
+        Arc::new(crate::websocket::ApiWsChannel::new()),
+    ))
+}
+
+fn post_json(uri: &str, body: &str) -> Request<Body> {
+    Request::post(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+async fn json_body(resp: axum::response::Response) -> serde_json::Value {
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_submit_and_get_batch() {
+    let state = test_state();
+    let resp = create_router_with_hybrid_state(state.clone())
+        .oneshot(post_json(
+            "/tasks/batch",
+            r#"{"priority": "High", "tasks": [
+                {"key": "fetch", "task": "Fetch data"},
+                {"key": "report", "task": "Write report", "depends_on": ["fetch"]}
+            ]}"#,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::ACCEPTED);
+
+    let json = json_body(resp).await;
+    assert_eq!(json["status"], "running");
+    assert_eq!(json["priority"], "High");
+    assert_eq!(json["counts"]["queued"], 1);
+    assert_eq!(json["counts"]["waiting"], 1);
+    assert!(json["tasks"][0]["task_id"].is_string());
+    assert_eq!(json["tasks"][1]["status"], "waiting");
+
+    let id = json["id"].as_str().unwrap();
+    let resp = create_router_with_hybrid_state(state)
+        .oneshot(
+            Request::get(format!("/tasks/batch/{}", id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(json_body(resp).await["id"], id);
+}
+
+#[tokio::test]
+async fn test_invalid_batch_rejected() {
+    let resp = create_router_with_hybrid_state(test_state())
+        .oneshot(post_json(
+            "/tasks/batch",
+            r#"{"tasks": [{"key": "a", "task": "A", "depends_on": ["a"]}]}"#,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    assert!(json_body(resp).await["error"]
+        .as_str()
+        .unwrap()
+        .contains("cycle"));
+}
+
+#[tokio::test]
+async fn test_unknown_batch_not_found() {
+    let resp = create_router_with_hybrid_state(test_state())
+        .oneshot(Request::get("/tasks/batch/missing").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
@@ -37,6 +37,14 @@ pub enum InterfaceError {
     #[error("Failed to inject event into RunLoop: {0}")]
     RunLoopInjectionFailed(String),
 
+    /// RunLoop queue is full for the task's source.
+    #[error("RunLoop queue is full: {0}")]
+    QueueFull(String),
+
+    /// RunLoop is draining and refuses new tasks.
+    #[error("RunLoop is draining: {0}")]
+    Draining(String),
+
     /// WebSocket error.
     #[error("WebSocket error: {0}")]
     WebSocketError(String),
@@ -45,6 +53,18 @@ pub enum InterfaceError {
     #[error("Webhook error: {0}")]
     WebhookError(String),
 
+    /// Task template not found.
+    #[error("Template not found: {0}")]
+    TemplateNotFound(String),
+
+    /// Required template variables were not provided.
+    #[error("Missing template variables: {}", .0.join(", "))]
+    MissingVariables(Vec<String>),
+
+    /// Invalid batch request.
+    #[error("Invalid batch: {0}")]
+    InvalidBatch(String),
+
     /// Generic error.
     #[error("{0}")]
     Custom(String),
@@ -1,7 +1,13 @@
 //! Admin management endpoints.
 
+use autohands_core::ResourceSuspension;
+use autohands_runtime::FlagState;
+use autohands_runtime::session_bundle::{
+    export_session as export_bundle, import_session as import_bundle, BundleError,
+    ImportReport, SessionBundle,
+};
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
     Json,
@@ -147,6 +153,226 @@ pub async fn delete_session(
     }
 }
 
+/// Query parameters for importing a session bundle.
+#[derive(Debug, Default, Deserialize)]
+pub struct ImportSessionQuery {
+    /// Replace an existing session with the same ID.
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+fn bundle_error(e: BundleError) -> (StatusCode, Json<ErrorResponse>) {
+    let (status, code) = match &e {
+        BundleError::NotFound(_) => (StatusCode::NOT_FOUND, "session_not_found"),
+        BundleError::AlreadyExists(_) => (StatusCode::CONFLICT, "session_exists"),
+        BundleError::UnsupportedVersion(_) | BundleError::InvalidSessionId(_) => {
+            (StatusCode::BAD_REQUEST, "invalid_bundle")
+        }
+        _ => (StatusCode::INTERNAL_SERVER_ERROR, "bundle_failed"),
+    };
+    (status, Json(ErrorResponse::new(e.to_string(), code)))
+}
+
+/// Export a session as a portable bundle.
+///
+/// GET /admin/sessions/{id}/export
+pub async fn export_session(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<SessionBundle>, (StatusCode, Json<ErrorResponse>)> {
+    let memory = state.agent_runtime.memory_backend().map(|m| m.as_ref());
+    export_bundle(&state.transcript_manager, &id, memory)
+        .await
+        .map(Json)
+        .map_err(bundle_error)
+}
+
+/// Import a session bundle.
+///
+/// Artifacts are restored under `<transcripts>/artifacts/<session id>/`.
+///
+/// POST /admin/sessions/import?overwrite=true
+pub async fn import_session(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ImportSessionQuery>,
+    Json(bundle): Json<SessionBundle>,
+) -> Result<(StatusCode, Json<ImportReport>), (StatusCode, Json<ErrorResponse>)> {
+    let artifact_dir = state
+        .transcript_manager
+        .base_dir()
+        .join("artifacts")
+        .join(&bundle.session_id);
+    let memory = state.agent_runtime.memory_backend().map(|m| m.as_ref());
+    import_bundle(
+        &state.transcript_manager,
+        bundle,
+        &artifact_dir,
+        memory,
+        query.overwrite,
+    )
+    .await
+    .map(|report| (StatusCode::CREATED, Json(report)))
+    .map_err(bundle_error)
+}
+
+/// Query selecting the workspace feature flags are resolved for.
+#[derive(Debug, Default, Deserialize)]
+pub struct FeatureFlagQuery {
+    /// Working directory of the workspace; global when absent.
+    #[serde(default)]
+    pub workspace: Option<String>,
+}
+
+/// Feature flag override request.
+#[derive(Debug, Deserialize)]
+pub struct SetFeatureFlagRequest {
+    /// Whether the flag is on.
+    pub enabled: bool,
+    /// Workspace the override applies to; global when absent.
+    #[serde(default)]
+    pub workspace: Option<String>,
+}
+
+/// List feature flags as resolved for a workspace.
+///
+/// GET /admin/features?workspace=/path/to/repo
+pub async fn list_feature_flags(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<FeatureFlagQuery>,
+) -> Json<Vec<FlagState>> {
+    let flags = state.agent_runtime.feature_flags();
+    Json(flags.list(query.workspace.as_deref()))
+}
+
+/// Get a feature flag as resolved for a workspace.
+///
+/// GET /admin/features/{name}?workspace=/path/to/repo
+pub async fn get_feature_flag(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    Query(query): Query<FeatureFlagQuery>,
+) -> Json<FlagState> {
+    let flags = state.agent_runtime.feature_flags();
+    Json(flags.state(&name, query.workspace.as_deref()))
+}
+
+/// Override a feature flag, for a workspace or globally.
+///
+/// PUT /admin/features/{name}
+pub async fn set_feature_flag(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    Json(request): Json<SetFeatureFlagRequest>,
+) -> Result<Json<FlagState>, (StatusCode, Json<ErrorResponse>)> {
+    if name.trim().is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new("Flag name is empty", "invalid_flag")),
+        ));
+    }
+    let flags = state.agent_runtime.feature_flags();
+    let workspace = request.workspace.as_deref();
+    flags.set_override(&name, workspace, request.enabled);
+    tracing::info!(
+        "Feature flag {} set to {} ({})",
+        name,
+        request.enabled,
+        workspace.unwrap_or("global")
+    );
+    Ok(Json(flags.state(&name, workspace)))
+}
+
+/// Drop a feature flag override, falling back to the configured value.
+///
+/// DELETE /admin/features/{name}?workspace=/path/to/repo
+pub async fn clear_feature_flag(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    Query(query): Query<FeatureFlagQuery>,
+) -> StatusCode {
+    let flags = state.agent_runtime.feature_flags();
+    if flags.clear_override(&name, query.workspace.as_deref()) {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
+    }
+}
+
+/// Exclusive resources in use and suspended.
+#[derive(Debug, Serialize)]
+pub struct ResourceStatus {
+    /// Resources held by running tool calls.
+    pub held: Vec<String>,
+    /// Suspended resources; tools needing them are refused.
+    pub suspended: Vec<ResourceSuspension>,
+}
+
+/// Resource suspension request.
+#[derive(Debug, Default, Deserialize)]
+pub struct SuspendResourceRequest {
+    /// Why the resource is suspended.
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// List the exclusive resources in use and suspended.
+///
+/// GET /admin/resources
+pub async fn list_resources(State(state): State<Arc<AppState>>) -> Json<ResourceStatus> {
+    let locks = state.tool_registry.resource_locks();
+    Json(ResourceStatus {
+        held: locks.held(),
+        suspended: locks.suspensions(),
+    })
+}
+
+/// Suspend an exclusive resource, e.g. `desktop-input` to stop all mouse
+/// and keyboard tools at once. Calls in progress finish; later ones fail
+/// until the resource is resumed.
+///
+/// POST /admin/resources/{name}/suspend
+pub async fn suspend_resource(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    request: Option<Json<SuspendResourceRequest>>,
+) -> Json<ResourceSuspension> {
+    let reason = request
+        .and_then(|Json(request)| request.reason)
+        .unwrap_or_else(|| "suspended from the API".to_string());
+    Json(state.tool_registry.resource_locks().suspend(&name, &reason))
+}
+
+/// Abort an exclusive resource: suspend it and cancel the runs using it,
+/// e.g. `desktop-input` as a panic button against a runaway agent. The run
+/// whose call holds it is cancelled at once, as are those calling a tool
+/// needing it until it is resumed.
+///
+/// POST /admin/resources/{name}/abort
+pub async fn abort_resource(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    request: Option<Json<SuspendResourceRequest>>,
+) -> Json<ResourceSuspension> {
+    let reason = request
+        .and_then(|Json(request)| request.reason)
+        .unwrap_or_else(|| "aborted from the API".to_string());
+    Json(state.tool_registry.resource_locks().abort(&name, &reason))
+}
+
+/// Resume a suspended resource.
+///
+/// POST /admin/resources/{name}/resume
+pub async fn resume_resource(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> StatusCode {
+    if state.tool_registry.resource_locks().resume(&name) {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
+    }
+}
+
 /// Get system statistics.
 pub async fn system_stats(State(state): State<Arc<AppState>>) -> Json<SystemStats> {
     let uptime = state.uptime().as_secs();
@@ -194,16 +420,27 @@ impl ReloadConfigRequest {
 }
 
 /// Reload configuration.
+///
+/// Rebuilds the running agents' context (prompt template, skills, memories)
+/// when a refresher is configured.
 pub async fn reload_config(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Json(request): Json<ReloadConfigRequest>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
     // TODO: Implement actual config reload
     let path_info = request.path().unwrap_or("default");
-    Ok(Json(serde_json::json!({
+    let mut response = serde_json::json!({
         "status": "ok",
         "message": format!("Configuration reload requested from: {}", path_info)
-    })))
+    });
+    if let Some(refresher) = &state.context_refresher {
+        let changed = refresher.refresh().await;
+        response["context"] = serde_json::json!({
+            "changed": changed,
+            "generation": refresher.prompt().generation(),
+        });
+    }
+    Ok(Json(response))
 }
 
 /// Shutdown request.
@@ -20,3 +20,24 @@
         assert_eq!(err.error, "Not found");
         assert_eq!(err.code, "not_found");
     }
+
+    #[tokio::test]
+    async fn test_reload_refreshes_agent_context() {
+        use autohands_runtime::{ContextRefresher, LivePrompt, TemplateSection};
+
+        let refresher = Arc::new(
+            ContextRefresher::new(Arc::new(LivePrompt::default()))
+                .with_section(Arc::new(TemplateSection::new(None, "You are AutoHands."))),
+        );
+        let state = Arc::new(AppState::default().with_context_refresher(refresher.clone()));
+        let request = ReloadConfigRequest { config_path: None };
+
+        let Json(body) = reload_config(State(state.clone()), Json(request)).await.unwrap();
+        assert_eq!(body["context"]["changed"], true);
+        assert_eq!(body["context"]["generation"], 1);
+        assert_eq!(refresher.prompt().get().as_deref(), Some("You are AutoHands."));
+
+        let request = ReloadConfigRequest { config_path: None };
+        let Json(body) = reload_config(State(state), Json(request)).await.unwrap();
+        assert_eq!(body["context"]["changed"], false);
+    }
@@ -0,0 +1,114 @@
+//! Human approval endpoints.
+//!
+//! - GET  /approvals               - List pending approval requests
+//! - POST /approvals/{id}/approve  - Approve a pending request
+//! - POST /approvals/{id}/reject   - Reject a pending request
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use autohands_protocols::approval::{ApprovalDecision, ApprovalRequest};
+
+use crate::runloop_bridge::HybridAppState;
+
+/// Response for listing pending approvals.
+#[derive(Debug, Serialize)]
+pub struct ApprovalListResponse {
+    pub count: usize,
+    pub approvals: Vec<ApprovalRequest>,
+}
+
+/// Optional body when deciding a request.
+#[derive(Debug, Default, Deserialize)]
+pub struct DecisionRequest {
+    /// Who decided.
+    pub decided_by: Option<String>,
+    /// Reason or note.
+    pub comment: Option<String>,
+}
+
+fn unavailable() -> (StatusCode, Json<serde_json::Value>) {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(serde_json::json!({"error": "Approvals are not configured"})),
+    )
+}
+
+/// List pending approval requests.
+///
+/// GET /approvals
+pub async fn list_approvals(State(state): State<Arc<HybridAppState>>) -> impl IntoResponse {
+    let Some(gate) = &state.approval_gate else {
+        return unavailable();
+    };
+    let approvals = gate.pending().await;
+    (
+        StatusCode::OK,
+        Json(serde_json::json!(ApprovalListResponse {
+            count: approvals.len(),
+            approvals,
+        })),
+    )
+}
+
+/// Approve a pending request.
+///
+/// POST /approvals/{id}/approve
+pub async fn approve(
+    state: State<Arc<HybridAppState>>,
+    id: Path<String>,
+    req: Option<Json<DecisionRequest>>,
+) -> impl IntoResponse {
+    decide(state, id, req, ApprovalDecision::approved()).await
+}
+
+/// Reject a pending request.
+///
+/// POST /approvals/{id}/reject
+pub async fn reject(
+    state: State<Arc<HybridAppState>>,
+    id: Path<String>,
+    req: Option<Json<DecisionRequest>>,
+) -> impl IntoResponse {
+    decide(state, id, req, ApprovalDecision::rejected()).await
+}
+
+async fn decide(
+    State(state): State<Arc<HybridAppState>>,
+    Path(id): Path<String>,
+    req: Option<Json<DecisionRequest>>,
+    mut decision: ApprovalDecision,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let Some(gate) = &state.approval_gate else {
+        return unavailable();
+    };
+    let req = req.map(|Json(req)| req).unwrap_or_default();
+    decision.decided_by = req.decided_by;
+    decision.comment = req.comment;
+    let status = decision.status;
+
+    if !gate.decide(&id, decision).await {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("No pending approval '{}'", id)})),
+        );
+    }
+
+    info!("Approval {} decided via API: {:?}", id, status);
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({"id": id, "status": status})),
+    )
+}
+
+#[cfg(test)]
+#[path = "approvals_tests.rs"]
+mod tests;
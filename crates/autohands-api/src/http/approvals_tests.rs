@@ -0,0 +1,92 @@
+//! Tests for approval routes.
+
+use super::*;
+use crate::http::routes::create_router_with_hybrid_state;
+use crate::runloop_bridge::RunLoopState;
+use crate::state::AppState;
+use autohands_core::ApprovalService;
+use autohands_protocols::approval::{ApprovalGate, ApprovalStatus};
+use autohands_runloop::{RunLoop, RunLoopConfig};
+use axum::body::Body;
+use axum::http::Request;
+use tower::ServiceExt;
+
+fn test_state(gate: Option<Arc<ApprovalService>>) -> Arc<HybridAppState> {
+    let run_loop = Arc::new(RunLoop::new(RunLoopConfig::default()));
+    let state = HybridAppState::new(
+        Arc::new(AppState::default()),
+        Arc::new(RunLoopState::from_runloop(run_loop)),
+        Arc::new(crate::websocket::ApiWsChannel::new()),
+    );
+    Arc::new(match gate {
+        Some(gate) => state.with_approval_gate(gate),
+        None => state,
+    })
+}
+
+async fn wait_for_pending(gate: &ApprovalService) -> ApprovalRequest {
+    loop {
+        if let Some(request) = gate.pending().await.into_iter().next() {
+            return request;
+        }
+        tokio::task::yield_now().await;
+    }
+}
+
+#[tokio::test]
+async fn test_unconfigured_gate() {
+    let resp = create_router_with_hybrid_state(test_state(None))
+        .oneshot(Request::get("/approvals").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test]
+async fn test_list_and_reject() {
+    let gate = Arc::new(ApprovalService::new());
+    let state = test_state(Some(gate.clone()));
+    let waiter = {
+        let gate = gate.clone();
+        tokio::spawn(async move {
+            gate.request_approval(ApprovalRequest::new("Deploy", "workflow:release/confirm"))
+                .await
+        })
+    };
+    let request = wait_for_pending(&gate).await;
+
+    let resp = create_router_with_hybrid_state(state.clone())
+        .oneshot(Request::get("/approvals").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["count"], 1);
+    assert_eq!(body["approvals"][0]["title"], "Deploy");
+
+    let resp = create_router_with_hybrid_state(state.clone())
+        .oneshot(
+            Request::post(format!("/approvals/{}/reject", request.id))
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"decided_by": "bob", "comment": "not today"}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let decision = waiter.await.unwrap();
+    assert_eq!(decision.status, ApprovalStatus::Rejected);
+    assert_eq!(decision.decided_by.as_deref(), Some("bob"));
+
+    let resp = create_router_with_hybrid_state(state)
+        .oneshot(
+            Request::post(format!("/approvals/{}/approve", request.id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
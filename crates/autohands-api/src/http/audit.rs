@@ -0,0 +1,95 @@
+//! Audit log endpoints.
+//!
+//! - GET /audit         - Entries matching the query, oldest first
+//! - GET /audit/verify  - Check the hash chain of the log
+//!
+//! `GET /audit` filters by `kind`, `agent_id`, `session_id`,
+//! `correlation_id`, `name`, `outcome`, `since` and `until` (RFC 3339), and
+//! returns the latest `limit` matches (default 100).
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Serialize;
+
+use autohands_core::audit::{AuditEntry, AuditLog, AuditQuery};
+
+use crate::runloop_bridge::HybridAppState;
+
+/// Response for listing audit entries.
+#[derive(Debug, Serialize)]
+pub struct AuditListResponse {
+    pub count: usize,
+    pub entries: Vec<AuditEntry>,
+}
+
+fn unavailable() -> (StatusCode, Json<serde_json::Value>) {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(serde_json::json!({"error": "Audit log is not configured"})),
+    )
+}
+
+fn read_failed(e: impl std::fmt::Display) -> (StatusCode, Json<serde_json::Value>) {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(serde_json::json!({"error": format!("Failed to read audit log: {}", e)})),
+    )
+}
+
+/// Run a read of the log off the async runtime.
+async fn read_log<T: Send + 'static>(
+    log: &Arc<AuditLog>,
+    read: impl FnOnce(&AuditLog) -> std::io::Result<T> + Send + 'static,
+) -> Result<T, (StatusCode, Json<serde_json::Value>)> {
+    let log = log.clone();
+    match tokio::task::spawn_blocking(move || read(&log)).await {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(e)) => Err(read_failed(e)),
+        Err(e) => Err(read_failed(e)),
+    }
+}
+
+/// List audit entries.
+///
+/// GET /audit
+pub async fn list_audit(
+    State(state): State<Arc<HybridAppState>>,
+    Query(query): Query<AuditQuery>,
+) -> impl IntoResponse {
+    let Some(log) = &state.audit_log else {
+        return unavailable();
+    };
+    match read_log(log, move |log| log.query(&query)).await {
+        Ok(entries) => (
+            StatusCode::OK,
+            Json(serde_json::json!(AuditListResponse {
+                count: entries.len(),
+                entries,
+            })),
+        ),
+        Err(e) => e,
+    }
+}
+
+/// Check the hash chain of the audit log.
+///
+/// GET /audit/verify
+pub async fn verify_audit(State(state): State<Arc<HybridAppState>>) -> impl IntoResponse {
+    let Some(log) = &state.audit_log else {
+        return unavailable();
+    };
+    match read_log(log, |log| log.verify()).await {
+        Ok(verification) => (StatusCode::OK, Json(serde_json::json!(verification))),
+        Err(e) => e,
+    }
+}
+
+#[cfg(test)]
+#[path = "audit_tests.rs"]
+mod tests;
@@ -0,0 +1,69 @@
+//! Tests for audit routes.
+
+use super::*;
+use crate::http::routes::create_router_with_hybrid_state;
+use crate::runloop_bridge::RunLoopState;
+use crate::state::AppState;
+use autohands_core::audit::AuditKind;
+use autohands_runloop::{RunLoop, RunLoopConfig};
+use axum::body::Body;
+use axum::http::Request;
+use tower::ServiceExt;
+
+fn test_state(log: Option<Arc<AuditLog>>) -> Arc<HybridAppState> {
+    let run_loop = Arc::new(RunLoop::new(RunLoopConfig::default()));
+    let state = HybridAppState::new(
+        Arc::new(AppState::default()),
+        Arc::new(RunLoopState::from_runloop(run_loop)),
+        Arc::new(crate::websocket::ApiWsChannel::new()),
+    );
+    Arc::new(match log {
+        Some(log) => state.with_audit_log(log),
+        None => state,
+    })
+}
+
+async fn get_json(state: Arc<HybridAppState>, uri: &str) -> (StatusCode, serde_json::Value) {
+    let resp = create_router_with_hybrid_state(state)
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = resp.status();
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn test_unconfigured_log() {
+    let (status, _) = get_json(test_state(None), "/audit").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test]
+async fn test_list_and_verify() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = Arc::new(AuditLog::open(dir.path().join("audit.jsonl")).unwrap());
+    for (tool, session) in [("exec", "s-1"), ("read_file", "s-2"), ("exec", "s-2")] {
+        log.record(
+            AuditEntry::new(AuditKind::ToolCall, "success")
+                .with_name(tool)
+                .with_session(session),
+        );
+    }
+    log.record(AuditEntry::new(AuditKind::Approval, "approved").with_session("s-2"));
+    let state = test_state(Some(log));
+
+    let (status, body) = get_json(state.clone(), "/audit?kind=tool_call&session_id=s-2").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["count"], 2);
+    assert_eq!(body["entries"][0]["name"], "read_file");
+    assert_eq!(body["entries"][1]["seq"], 3);
+
+    let (_, body) = get_json(state.clone(), "/audit?limit=1").await;
+    assert_eq!(body["entries"][0]["kind"], "approval");
+
+    let (status, body) = get_json(state, "/audit/verify").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["valid"], true);
+    assert_eq!(body["entries"], 4);
+}
@@ -0,0 +1,229 @@
+//! Deployment capability manifest.
+//!
+//! `GET /capabilities` describes what this deployment offers: channels,
+//! tools with their risk levels, providers and models, skills, feature
+//! flags and extensions. Clients and other agents read it to adapt their
+//! requests; every list is sorted so manifests of two environments can be
+//! diffed directly.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+
+use autohands_protocols::channel::ChannelCapabilities;
+use autohands_protocols::skill::SkillDefinition;
+use autohands_protocols::types::RiskLevel;
+use autohands_runtime::FlagState;
+
+use crate::state::AppState;
+
+/// Skills available to agents of the deployment.
+#[async_trait]
+pub trait SkillCatalog: Send + Sync {
+    /// All registered skills, enabled or not.
+    async fn list_skills(&self) -> Vec<SkillDefinition>;
+}
+
+/// What a deployment offers.
+#[derive(Debug, Serialize)]
+pub struct CapabilityManifest {
+    /// AutoHands version.
+    pub version: String,
+    /// Channels messages can arrive on and be delivered to.
+    pub channels: Vec<ChannelCapability>,
+    /// Enabled tools.
+    pub tools: Vec<ToolCapability>,
+    /// Providers and their models.
+    pub providers: Vec<ProviderCapability>,
+    /// Registered skills.
+    pub skills: Vec<SkillCapability>,
+    /// Feature flags, resolved for the requested workspace.
+    pub feature_flags: Vec<FlagState>,
+    /// Loaded extensions.
+    pub extensions: Vec<ExtensionCapability>,
+}
+
+/// A channel of the manifest.
+#[derive(Debug, Serialize)]
+pub struct ChannelCapability {
+    pub id: String,
+    #[serde(flatten)]
+    pub capabilities: ChannelCapabilities,
+}
+
+/// A tool of the manifest.
+#[derive(Debug, Serialize)]
+pub struct ToolCapability {
+    pub id: String,
+    pub name: String,
+    pub risk_level: RiskLevel,
+    /// Whether calls have no side effects.
+    pub read_only: bool,
+    pub supports_streaming: bool,
+    /// Extension providing the tool.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extension_id: Option<String>,
+}
+
+/// A provider of the manifest.
+#[derive(Debug, Serialize)]
+pub struct ProviderCapability {
+    pub id: String,
+    pub models: Vec<ModelCapability>,
+}
+
+/// A model of the manifest.
+#[derive(Debug, Serialize)]
+pub struct ModelCapability {
+    pub id: String,
+    pub context_length: u32,
+    pub max_output_tokens: u32,
+    pub supports_vision: bool,
+    pub supports_tools: bool,
+}
+
+/// A skill of the manifest.
+#[derive(Debug, Serialize)]
+pub struct SkillCapability {
+    pub id: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    pub enabled: bool,
+    /// Tools the skill needs.
+    pub required_tools: Vec<String>,
+}
+
+/// An extension of the manifest.
+#[derive(Debug, Serialize)]
+pub struct ExtensionCapability {
+    pub id: String,
+    pub version: String,
+}
+
+/// Query of `GET /capabilities`.
+#[derive(Debug, Default, Deserialize)]
+pub struct CapabilityQuery {
+    /// Workspace the feature flags are resolved for; global when absent.
+    pub workspace: Option<String>,
+}
+
+/// Describe the deployment.
+///
+/// GET /capabilities?workspace=/path/to/repo
+pub async fn get_capabilities(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<CapabilityQuery>,
+) -> Json<CapabilityManifest> {
+    Json(build_manifest(&state, query.workspace.as_deref()).await)
+}
+
+/// Build the manifest of a deployment.
+pub async fn build_manifest(state: &AppState, workspace: Option<&str>) -> CapabilityManifest {
+    let mut channels: Vec<ChannelCapability> = match &state.channel_registry {
+        Some(registry) => registry
+            .list_ids()
+            .into_iter()
+            .filter_map(|id| {
+                let channel = registry.get(&id)?;
+                Some(ChannelCapability {
+                    capabilities: channel.capabilities().clone(),
+                    id,
+                })
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+    channels.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let mut tools: Vec<ToolCapability> = state
+        .tool_registry
+        .list()
+        .into_iter()
+        .map(|definition| ToolCapability {
+            id: definition.id,
+            name: definition.name,
+            risk_level: definition.risk_level,
+            read_only: definition.read_only,
+            supports_streaming: definition.supports_streaming,
+            extension_id: definition.extension_id,
+        })
+        .collect();
+    tools.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let mut providers: Vec<ProviderCapability> = state
+        .provider_registry
+        .list_ids()
+        .into_iter()
+        .map(|id| ProviderCapability {
+            id,
+            models: Vec::new(),
+        })
+        .collect();
+    for (provider_id, model) in state.provider_registry.list_models() {
+        if let Some(provider) = providers.iter_mut().find(|p| p.id == provider_id) {
+            provider.models.push(ModelCapability {
+                id: model.id,
+                context_length: model.context_length,
+                max_output_tokens: model.max_output_tokens,
+                supports_vision: model.supports_vision,
+                supports_tools: model.supports_tools,
+            });
+        }
+    }
+    providers.sort_by(|a, b| a.id.cmp(&b.id));
+    for provider in &mut providers {
+        provider.models.sort_by(|a, b| a.id.cmp(&b.id));
+    }
+
+    let mut skills: Vec<SkillCapability> = match &state.skill_catalog {
+        Some(catalog) => catalog
+            .list_skills()
+            .await
+            .into_iter()
+            .map(|skill| {
+                let mut required_tools = skill.required_tools;
+                required_tools.sort();
+                SkillCapability {
+                    id: skill.id,
+                    name: skill.name,
+                    category: skill.category,
+                    enabled: skill.enabled,
+                    required_tools,
+                }
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+    skills.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let mut extensions: Vec<ExtensionCapability> = state
+        .kernel
+        .list_extensions()
+        .iter()
+        .map(|manifest| ExtensionCapability {
+            id: manifest.id.clone(),
+            version: manifest.version.to_string(),
+        })
+        .collect();
+    extensions.sort_by(|a, b| a.id.cmp(&b.id));
+
+    CapabilityManifest {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        channels,
+        tools,
+        providers,
+        skills,
+        feature_flags: state.agent_runtime.feature_flags().list(workspace),
+        extensions,
+    }
+}
+
+#[cfg(test)]
+#[path = "capabilities_tests.rs"]
+mod tests;
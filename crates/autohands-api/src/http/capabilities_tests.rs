@@ -0,0 +1,144 @@
+use super::*;
+use autohands_core::registry::ChannelRegistry;
+use autohands_protocols::channel::{
+    Channel, ChannelId, InboundMessage, OutboundMessage, ReplyAddress, SentMessage,
+};
+use autohands_protocols::error::{ChannelError, ToolError};
+use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+use tokio::sync::broadcast;
+
+struct NamedTool(ToolDefinition);
+
+#[async_trait]
+impl Tool for NamedTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.0
+    }
+
+    async fn execute(
+        &self,
+        _params: serde_json::Value,
+        _ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        Ok(ToolResult::success(""))
+    }
+}
+
+struct QuietChannel {
+    id: ChannelId,
+    capabilities: ChannelCapabilities,
+    inbound: broadcast::Sender<InboundMessage>,
+}
+
+#[async_trait]
+impl Channel for QuietChannel {
+    fn id(&self) -> &ChannelId {
+        &self.id
+    }
+
+    fn capabilities(&self) -> &ChannelCapabilities {
+        &self.capabilities
+    }
+
+    async fn start(&self) -> Result<(), ChannelError> {
+        Ok(())
+    }
+
+    async fn stop(&self) -> Result<(), ChannelError> {
+        Ok(())
+    }
+
+    async fn send(
+        &self,
+        _target: &ReplyAddress,
+        _message: OutboundMessage,
+    ) -> Result<SentMessage, ChannelError> {
+        Err(ChannelError::Disconnected)
+    }
+
+    fn inbound(&self) -> broadcast::Receiver<InboundMessage> {
+        self.inbound.subscribe()
+    }
+}
+
+struct FixedSkills;
+
+#[async_trait]
+impl SkillCatalog for FixedSkills {
+    async fn list_skills(&self) -> Vec<SkillDefinition> {
+        let mut review = SkillDefinition::new("review", "Code review");
+        review.required_tools = vec!["read_file".to_string(), "grep".to_string()];
+        let mut deploy = SkillDefinition::new("deploy", "Deploy");
+        deploy.enabled = false;
+        vec![review, deploy]
+    }
+}
+
+fn state() -> AppState {
+    let channels = Arc::new(ChannelRegistry::new());
+    for id in ["web", "slack"] {
+        let capabilities = ChannelCapabilities {
+            supports_threads: id == "slack",
+            ..Default::default()
+        };
+        let (inbound, _) = broadcast::channel(1);
+        channels
+            .register(Arc::new(QuietChannel {
+                id: id.to_string(),
+                capabilities,
+                inbound,
+            }))
+            .unwrap();
+    }
+    let state = AppState::default()
+        .with_channels(channels)
+        .with_skill_catalog(Arc::new(FixedSkills));
+    let tools = [
+        ToolDefinition::new("write_file", "Write File", "Write a file")
+            .with_risk_level(RiskLevel::Medium),
+        ToolDefinition::new("read_file", "Read File", "Read a file").with_read_only(),
+    ];
+    for definition in tools {
+        state
+            .tool_registry
+            .register(Arc::new(NamedTool(definition)))
+            .unwrap();
+    }
+    state
+}
+
+#[tokio::test]
+async fn test_manifest_is_sorted() {
+    let manifest = build_manifest(&state(), None).await;
+
+    let channels: Vec<_> = manifest.channels.iter().map(|c| c.id.as_str()).collect();
+    assert_eq!(channels, vec!["slack", "web"]);
+    assert!(manifest.channels[0].capabilities.supports_threads);
+
+    let tools: Vec<_> = manifest.tools.iter().map(|t| t.id.as_str()).collect();
+    assert_eq!(tools, vec!["read_file", "write_file"]);
+    assert!(manifest.tools[0].read_only);
+    assert_eq!(manifest.tools[1].risk_level, RiskLevel::Medium);
+
+    let skills: Vec<_> = manifest.skills.iter().map(|s| s.id.as_str()).collect();
+    assert_eq!(skills, vec!["deploy", "review"]);
+    assert!(!manifest.skills[0].enabled);
+    assert_eq!(manifest.skills[1].required_tools, vec!["grep", "read_file"]);
+
+    assert!(manifest.providers.is_empty());
+    assert!(manifest
+        .feature_flags
+        .iter()
+        .any(|flag| flag.name == "parallel_tools"));
+}
+
+#[tokio::test]
+async fn test_manifest_without_channels_or_skills() {
+    let manifest = build_manifest(&AppState::default(), Some("/srv/repo")).await;
+    assert!(manifest.channels.is_empty());
+    assert!(manifest.skills.is_empty());
+
+    let json = serde_json::to_value(&manifest).unwrap();
+    assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(json["tools"], serde_json::json!([]));
+}
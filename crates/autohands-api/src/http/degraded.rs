@@ -0,0 +1,53 @@
+//! Degraded mode: serving without an LLM provider.
+//!
+//! When no provider is registered the server still starts. Tools, jobs
+//! that need no model (backups, page watches), webhooks registration,
+//! admin and monitoring endpoints keep working, but endpoints that start
+//! agent runs answer `503 Service Unavailable` with setup instructions
+//! instead of queueing work that can only fail. `/health` reports the
+//! condition as a degraded `providers` component.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use crate::state::AppState;
+
+/// Error code of requests refused in degraded mode.
+pub const NO_PROVIDER_CODE: &str = "no_provider_configured";
+
+/// Error of requests refused in degraded mode.
+pub const NO_PROVIDER_ERROR: &str = "No LLM provider configured";
+
+/// How to get out of degraded mode.
+pub const PROVIDER_SETUP: &str = "Set an API key for one of the providers under [providers] \
+    in the config file (anthropic, openai, gemini or ark), either as `api_key` or as the \
+    <NAME>_API_KEY environment variable (e.g. ANTHROPIC_API_KEY), then restart the server";
+
+/// Body of a response refused in degraded mode.
+pub fn no_provider_body() -> serde_json::Value {
+    serde_json::json!({
+        "status": "error",
+        "code": NO_PROVIDER_CODE,
+        "error": NO_PROVIDER_ERROR,
+        "setup": PROVIDER_SETUP,
+    })
+}
+
+/// Middleware refusing agent runs while no provider is registered.
+pub async fn require_provider(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if state.missing_provider() {
+        return (StatusCode::SERVICE_UNAVAILABLE, Json(no_provider_body())).into_response();
+    }
+    next.run(request).await
+}
@@ -3,9 +3,10 @@
 //! Provides HTTP endpoints for executing agents and managing their lifecycle.
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
     Json,
@@ -14,11 +15,20 @@ use serde::{Deserialize, Serialize};
 use tracing::{error, info};
 use uuid::Uuid;
 
-use autohands_protocols::agent::AgentConfig;
+use autohands_protocols::agent::{AgentConfig, RunOverrides};
 use autohands_protocols::types::Message;
+use autohands_runloop::TaskOutcome;
+use autohands_runtime::TaskProgress;
 
+use crate::runloop_bridge::HybridAppState;
 use crate::state::AppState;
 
+/// Longest a status request may wait for a result.
+pub const MAX_WAIT_SECONDS: u64 = 60;
+
+/// How often a waiting status request checks whether a session stopped.
+const SESSION_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
 /// Request to run an agent task.
 #[derive(Debug, Deserialize)]
 pub struct AgentRunRequest {
@@ -33,6 +43,30 @@ pub struct AgentRunRequest {
 
     /// Optional agent ID to use. Defaults to "general".
     pub agent_id: Option<String>,
+
+    /// Optional sampling temperature for this run.
+    #[serde(default)]
+    pub temperature: Option<f32>,
+
+    /// Optional output token limit per model call for this run.
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
+
+    /// Optional allowlist of tool IDs for this run.
+    #[serde(default)]
+    pub tools: Option<Vec<String>>,
+}
+
+impl AgentRunRequest {
+    /// Model, generation and tool overrides requested for this run.
+    pub fn overrides(&self) -> RunOverrides {
+        RunOverrides {
+            model: self.model.clone(),
+            temperature: self.temperature,
+            max_tokens: self.max_tokens,
+            tools: self.tools.clone(),
+        }
+    }
 }
 
 /// Response from running an agent.
@@ -88,14 +122,73 @@ pub struct AgentAbortResponse {
     pub message: String,
 }
 
+/// Query parameters for a status request.
+#[derive(Debug, Default, Deserialize)]
+pub struct AgentStatusQuery {
+    /// Seconds to wait for the result before answering (capped at
+    /// [`MAX_WAIT_SECONDS`]); 0 answers at once.
+    #[serde(default)]
+    pub wait_seconds: u64,
+}
+
 /// Agent status response.
 #[derive(Debug, Serialize)]
 pub struct AgentStatusResponse {
-    /// Session ID.
+    /// Session ID, or RunLoop task ID.
     pub session_id: String,
 
     /// Whether the agent is currently running.
     pub is_running: bool,
+
+    /// `completed`, `failed`, `running`, or `pending` when there is no
+    /// result yet.
+    pub status: String,
+
+    /// Agent's final response, once completed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
+
+    /// Error, if the task failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+
+    /// When the task finished.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<chrono::DateTime<chrono::Utc>>,
+
+    /// Turn-level progress of the agent session, once it started.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<TaskProgress>,
+}
+
+impl AgentStatusResponse {
+    /// Status of an ID from its recorded outcome, or whether it runs.
+    fn new(
+        id: String,
+        is_running: bool,
+        outcome: Option<TaskOutcome>,
+        progress: Option<TaskProgress>,
+    ) -> Self {
+        let status = match &outcome {
+            Some(outcome) if outcome.success => "completed",
+            Some(_) => "failed",
+            None if is_running => "running",
+            None => "pending",
+        };
+        let (result, error, finished_at) = match outcome {
+            Some(outcome) => (outcome.response, outcome.error, Some(outcome.finished_at)),
+            None => (None, None, None),
+        };
+        Self {
+            session_id: id,
+            is_running,
+            status: status.to_string(),
+            result,
+            error,
+            finished_at,
+            progress,
+        }
+    }
 }
 
 /// Tool information.
@@ -164,6 +257,7 @@ pub async fn agent_run(
     Json(req): Json<AgentRunRequest>,
 ) -> impl IntoResponse {
     info!("Agent run request: task={}", req.task);
+    let overrides = req.overrides();
 
     let session_id = req
         .session_id
@@ -193,6 +287,19 @@ pub async fn agent_run(
         }
     };
 
+    // Overrides must pass the runtime's policy and suit the agent
+    if let Err(e) = state.agent_runtime.check_overrides(&agent_id, &overrides) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(AgentRunResponse {
+                session_id,
+                messages: vec![],
+                status: "error".to_string(),
+                error: Some(e.to_string()),
+            }),
+        );
+    }
+
     // Create user message
     let message = Message::user(&req.task);
 
@@ -214,7 +321,7 @@ pub async fn agent_run(
     // Execute agent with transcript
     match state
         .agent_runtime
-        .execute_with_transcript(&agent_id, &session_id, message, transcript.clone())
+        .execute_with_overrides(&agent_id, &session_id, message, transcript.clone(), None, overrides)
         .await
     {
         Ok(messages) => {
@@ -254,17 +361,85 @@ pub async fn agent_run(
 
 /// Get agent execution status.
 ///
-/// GET /tasks/{session_id}
+/// GET /tasks/{id}?wait_seconds=N
+///
+/// `id` is a session ID or a RunLoop task ID (from `POST /v1/runloop/task`).
+/// With `wait_seconds`, the request long-polls: it answers as soon as the
+/// task's result is recorded or the session stops running, returning the
+/// result inline, or with the current status once the wait is over.
+///
+/// The response carries the session's `progress` (turn, last tool, tokens,
+/// ETA). RunLoop tasks run in the session named by their payload's
+/// `session_id`, or in one named after the task ID, which is looked up here.
 pub async fn agent_status(
-    State(state): State<Arc<AppState>>,
-    Path(session_id): Path<String>,
+    State(state): State<Arc<HybridAppState>>,
+    Path(id): Path<String>,
+    Query(query): Query<AgentStatusQuery>,
 ) -> impl IntoResponse {
-    let is_running = state.agent_runtime.is_running(&session_id);
+    let runtime = &state.base.agent_runtime;
+    let outcomes = state.runloop.run_loop().outcome_log();
+    let task_id = Uuid::parse_str(&id).ok();
+    let wait = Duration::from_secs(query.wait_seconds.min(MAX_WAIT_SECONDS));
+
+    let mut outcome = task_id.and_then(|task_id| outcomes.get(task_id));
+    if outcome.is_none() && !wait.is_zero() {
+        let deadline = tokio::time::Instant::now() + wait;
+        if let Some(task_id) = task_id {
+            // Queued RunLoop tasks are not running yet, so wait for the outcome
+            outcome = tokio::time::timeout_at(deadline, outcomes.wait(task_id))
+                .await
+                .ok();
+        } else {
+            while runtime.is_running(&id) && tokio::time::Instant::now() < deadline {
+                tokio::time::sleep_until(
+                    (tokio::time::Instant::now() + SESSION_POLL_INTERVAL).min(deadline),
+                )
+                .await;
+            }
+        }
+    }
 
-    Json(AgentStatusResponse {
-        session_id,
-        is_running,
-    })
+    let is_running = runtime.is_running(&id);
+    let progress = runtime.progress(&id);
+    Json(AgentStatusResponse::new(id, is_running, outcome, progress))
+}
+
+/// Timeline of a traced task.
+///
+/// GET /tasks/{id}/trace
+///
+/// `id` is a correlation ID or the ID of a RunLoop task traced under one.
+/// The timeline follows the work from the inbound message through each
+/// agent turn, provider call and tool execution to the outbound reply.
+pub async fn task_trace(
+    State(state): State<Arc<HybridAppState>>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    match state.runloop.run_loop().trace_log().get(&id) {
+        Some(trace) => (StatusCode::OK, Json(serde_json::json!(trace))),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("No trace recorded for '{}'", id)})),
+        ),
+    }
+}
+
+/// Get the task tree of a multi-agent run.
+///
+/// GET /tasks/{id}/tree
+///
+/// `id` is any task of the run, or its session.
+pub async fn task_tree(
+    State(state): State<Arc<HybridAppState>>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    match state.task_tree(&id) {
+        Some(tree) => (StatusCode::OK, Json(serde_json::json!(tree))),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("No task '{}' in the task graph", id)})),
+        ),
+    }
 }
 
 /// Abort an agent execution.
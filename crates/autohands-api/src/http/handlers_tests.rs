@@ -16,6 +16,20 @@
         assert_eq!(req.task, "list files");
         assert_eq!(req.model, Some("test-model".to_string()));
         assert!(req.session_id.is_none());
+        assert_eq!(req.overrides().model.as_deref(), Some("test-model"));
+    }
+
+    #[test]
+    fn test_agent_run_request_overrides() {
+        let json = r#"{"task": "t", "temperature": 0.3, "max_tokens": 100, "tools": ["read_file"]}"#;
+        let req: AgentRunRequest = serde_json::from_str(json).unwrap();
+        let overrides = req.overrides();
+        assert_eq!(overrides.temperature, Some(0.3));
+        assert_eq!(overrides.max_tokens, Some(100));
+        assert!(overrides.allows_tool("read_file") && !overrides.allows_tool("exec"));
+
+        let req: AgentRunRequest = serde_json::from_str(r#"{"task": "t"}"#).unwrap();
+        assert!(req.overrides().is_empty());
     }
 
     #[test]
@@ -4,12 +4,23 @@
 //! - Task submission and management
 //! - Agent execution
 //! - Admin operations
+//! - Human approvals
+//! - Clarification questions
+//! - Tool catalog with schemas
+//! - Capability manifest of the deployment
 //! - Health checks and monitoring
+//! - Degraded mode without an LLM provider
 
 pub mod handlers;
 pub mod routes;
 
 // Internal modules (not publicly exported)
 pub(crate) mod admin;
+pub(crate) mod approvals;
+pub(crate) mod audit;
+pub(crate) mod capabilities;
+pub(crate) mod degraded;
 pub(crate) mod monitoring;
 pub(crate) mod openai_compat;
+pub(crate) mod questions;
+pub(crate) mod tools;
@@ -5,6 +5,9 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::time::SystemTime;
 
+use autohands_runtime::{ProviderHealthTable, ProviderStatus};
+
+use crate::http::degraded::{NO_PROVIDER_ERROR, PROVIDER_SETUP};
 use crate::state::AppState;
 
 // ============================================================================
@@ -86,8 +89,8 @@ fn get_uptime() -> u64 {
 }
 
 /// Enhanced health check handler.
-pub async fn health_check_detailed(State(_state): State<Arc<AppState>>) -> Json<HealthResponse> {
-    let components = vec![
+pub async fn health_check_detailed(State(state): State<Arc<AppState>>) -> Json<HealthResponse> {
+    let mut components = vec![
         ComponentHealth {
             name: "api".to_string(),
             status: HealthStatus::Healthy,
@@ -99,9 +102,28 @@ pub async fn health_check_detailed(State(_state): State<Arc<AppState>>) -> Json<
             message: None,
         },
     ];
+    let providers = state
+        .provider_health
+        .as_deref()
+        .map(provider_components)
+        .unwrap_or_default();
+    // An unhealthy provider is failed over; only losing all of them is fatal
+    let providers_down =
+        !providers.is_empty() && providers.iter().all(|c| c.status != HealthStatus::Healthy);
+    components.extend(providers);
+    // Without any provider configured, only agent runs are unavailable
+    if state.missing_provider() {
+        components.push(ComponentHealth {
+            name: "providers".to_string(),
+            status: HealthStatus::Degraded,
+            message: Some(format!("{}. {}", NO_PROVIDER_ERROR, PROVIDER_SETUP)),
+        });
+    }
 
     // Determine overall status based on components
-    let overall_status = if components.iter().any(|c| c.status == HealthStatus::Unhealthy) {
+    let overall_status = if providers_down
+        || components.iter().any(|c| c.status == HealthStatus::Unhealthy)
+    {
         HealthStatus::Unhealthy
     } else if components.iter().any(|c| c.status == HealthStatus::Degraded) {
         HealthStatus::Degraded
@@ -117,6 +139,29 @@ pub async fn health_check_detailed(State(_state): State<Arc<AppState>>) -> Json<
     })
 }
 
+/// One component per provider: degraded while unhealthy, with the last
+/// error, otherwise with the last latency.
+fn provider_components(table: &ProviderHealthTable) -> Vec<ComponentHealth> {
+    table
+        .snapshot()
+        .into_iter()
+        .map(|health| {
+            let (status, message) = match health.status {
+                ProviderStatus::Unhealthy => (HealthStatus::Degraded, health.last_error),
+                _ => (
+                    HealthStatus::Healthy,
+                    health.latency_ms.map(|ms| format!("{} ms", ms)),
+                ),
+            };
+            ComponentHealth {
+                name: format!("provider:{}", health.provider_id),
+                status,
+                message,
+            }
+        })
+        .collect()
+}
+
 /// Prometheus metrics endpoint.
 pub async fn prometheus_metrics(State(_state): State<Arc<AppState>>) -> PrometheusMetrics {
     let uptime = get_uptime();
@@ -30,6 +30,49 @@
         assert!(json.contains("0.1.0"));
     }
 
+    #[tokio::test]
+    async fn test_health_reports_providers() {
+        use autohands_protocols::error::ProviderError;
+        use std::time::Duration;
+
+        let table = Arc::new(ProviderHealthTable::new(1));
+        table.record_success("anthropic", Duration::from_millis(240));
+        table.record_failure("openai", &ProviderError::Network("connection refused".to_string()));
+        let state = Arc::new(AppState::default().with_provider_health(table.clone()));
+
+        let Json(response) = health_check_detailed(State(state.clone())).await;
+        assert_eq!(response.status, HealthStatus::Degraded);
+        let openai = response
+            .components
+            .iter()
+            .find(|c| c.name == "provider:openai")
+            .unwrap();
+        assert_eq!(openai.status, HealthStatus::Degraded);
+        assert!(openai.message.as_ref().unwrap().contains("connection refused"));
+        let anthropic = &response.components[2];
+        assert_eq!(anthropic.name, "provider:anthropic");
+        assert_eq!(anthropic.message.as_deref(), Some("240 ms"));
+
+        // Without any provider left, the service is unhealthy
+        table.record_failure("anthropic", &ProviderError::Timeout(15));
+        let Json(response) = health_check_detailed(State(state)).await;
+        assert_eq!(response.status, HealthStatus::Unhealthy);
+    }
+
+    #[tokio::test]
+    async fn test_health_reports_missing_provider() {
+        let Json(response) =
+            health_check_detailed(State(Arc::new(AppState::default()))).await;
+        assert_eq!(response.status, HealthStatus::Healthy);
+
+        let state = Arc::new(AppState::default().with_provider_required());
+        let Json(response) = health_check_detailed(State(state)).await;
+        assert_eq!(response.status, HealthStatus::Degraded);
+        let providers = response.components.last().unwrap();
+        assert_eq!(providers.name, "providers");
+        assert!(providers.message.as_ref().unwrap().contains("ANTHROPIC_API_KEY"));
+    }
+
     #[tokio::test]
     async fn test_liveness_probe() {
         let response = liveness_probe().await;
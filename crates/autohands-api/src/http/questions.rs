@@ -0,0 +1,101 @@
+//! Clarification question endpoints.
+//!
+//! - GET  /questions               - List questions waiting for an answer
+//! - POST /questions/{id}/answer   - Answer a pending question
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use autohands_protocols::question::{Answer, Question};
+
+use crate::runloop_bridge::HybridAppState;
+
+/// Response for listing pending questions.
+#[derive(Debug, Serialize)]
+pub struct QuestionListResponse {
+    pub count: usize,
+    pub questions: Vec<Question>,
+}
+
+/// Body of an answer.
+#[derive(Debug, Deserialize)]
+pub struct AnswerRequest {
+    /// The reply; an option's number or text picks that option.
+    pub answer: String,
+    /// Who answered.
+    pub answered_by: Option<String>,
+}
+
+fn unavailable() -> (StatusCode, Json<serde_json::Value>) {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(serde_json::json!({"error": "Questions are not configured"})),
+    )
+}
+
+fn not_found(id: &str) -> (StatusCode, Json<serde_json::Value>) {
+    (
+        StatusCode::NOT_FOUND,
+        Json(serde_json::json!({"error": format!("No pending question '{}'", id)})),
+    )
+}
+
+/// List questions waiting for an answer.
+///
+/// GET /questions
+pub async fn list_questions(State(state): State<Arc<HybridAppState>>) -> impl IntoResponse {
+    let Some(gate) = &state.question_gate else {
+        return unavailable();
+    };
+    let questions = gate.pending().await;
+    (
+        StatusCode::OK,
+        Json(serde_json::json!(QuestionListResponse {
+            count: questions.len(),
+            questions,
+        })),
+    )
+}
+
+/// Answer a pending question.
+///
+/// POST /questions/{id}/answer
+pub async fn answer_question(
+    State(state): State<Arc<HybridAppState>>,
+    Path(id): Path<String>,
+    Json(req): Json<AnswerRequest>,
+) -> impl IntoResponse {
+    let Some(gate) = &state.question_gate else {
+        return unavailable();
+    };
+    let Some(question) = gate.pending().await.into_iter().find(|q| q.id == id) else {
+        return not_found(&id);
+    };
+    let mut answer: Answer = question.parse_reply(&req.answer);
+    if let Some(answered_by) = req.answered_by {
+        answer = answer.by(answered_by);
+    }
+    let text = answer.text.clone();
+
+    if !gate.answer(&id, answer).await {
+        return not_found(&id);
+    }
+
+    info!("Question {} answered via API", id);
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({"id": id, "answer": text})),
+    )
+}
+
+#[cfg(test)]
+#[path = "questions_tests.rs"]
+mod tests;
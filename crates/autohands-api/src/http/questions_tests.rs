@@ -0,0 +1,96 @@
+//! Tests for question routes.
+
+use super::*;
+use crate::http::routes::create_router_with_hybrid_state;
+use crate::runloop_bridge::RunLoopState;
+use crate::state::AppState;
+use autohands_core::QuestionService;
+use autohands_protocols::question::{AnswerStatus, QuestionGate};
+use autohands_runloop::{RunLoop, RunLoopConfig};
+use axum::body::Body;
+use axum::http::Request;
+use tower::ServiceExt;
+
+fn test_state(gate: Option<Arc<QuestionService>>) -> Arc<HybridAppState> {
+    let run_loop = Arc::new(RunLoop::new(RunLoopConfig::default()));
+    let state = HybridAppState::new(
+        Arc::new(AppState::default()),
+        Arc::new(RunLoopState::from_runloop(run_loop)),
+        Arc::new(crate::websocket::ApiWsChannel::new()),
+    );
+    Arc::new(match gate {
+        Some(gate) => state.with_question_gate(gate),
+        None => state,
+    })
+}
+
+async fn wait_for_pending(gate: &QuestionService) -> Question {
+    loop {
+        if let Some(question) = gate.pending().await.into_iter().next() {
+            return question;
+        }
+        tokio::task::yield_now().await;
+    }
+}
+
+#[tokio::test]
+async fn test_unconfigured_gate() {
+    let resp = create_router_with_hybrid_state(test_state(None))
+        .oneshot(Request::get("/questions").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test]
+async fn test_list_and_answer() {
+    let gate = Arc::new(QuestionService::new());
+    let state = test_state(Some(gate.clone()));
+    let asker = {
+        let gate = gate.clone();
+        tokio::spawn(async move {
+            let question = Question::new("Which environment?")
+                .with_options(vec!["staging".to_string(), "production".to_string()]);
+            gate.ask(question).await
+        })
+    };
+    let question = wait_for_pending(&gate).await;
+
+    let resp = create_router_with_hybrid_state(state.clone())
+        .oneshot(Request::get("/questions").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["count"], 1);
+    assert_eq!(body["questions"][0]["text"], "Which environment?");
+
+    let resp = create_router_with_hybrid_state(state.clone())
+        .oneshot(
+            Request::post(format!("/questions/{}/answer", question.id))
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"answer": "1", "answered_by": "bob"}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let answer = asker.await.unwrap();
+    assert_eq!(answer.status, AnswerStatus::Answered);
+    assert_eq!(answer.text.as_deref(), Some("staging"));
+    assert_eq!(answer.answered_by.as_deref(), Some("bob"));
+
+    // Already answered
+    let resp = create_router_with_hybrid_state(state)
+        .oneshot(
+            Request::post(format!("/questions/{}/answer", question.id))
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"answer": "1"}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
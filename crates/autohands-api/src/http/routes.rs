@@ -10,15 +10,26 @@
 use std::sync::Arc;
 
 use axum::{
-    routing::{delete, get, post},
+    extract::DefaultBodyLimit,
+    middleware,
+    routing::{delete, get, post, put},
     Router,
 };
 
+use crate::batch::routes as batch_routes;
 use crate::http::admin;
-use crate::http::handlers::{agent_abort, agent_run, agent_status};
+use crate::http::approvals;
+use crate::http::audit;
+use crate::http::capabilities;
+use crate::http::degraded;
+use crate::http::questions;
+use crate::http::handlers::{agent_abort, agent_run, agent_status, task_trace, task_tree};
 use crate::http::monitoring;
+use crate::http::tools;
+use crate::idempotency;
 use crate::job::routes as job_routes;
 use crate::runloop_bridge::{self, HybridAppState};
+use crate::template::routes as template_routes;
 use crate::webhook::{
     delete_webhook, get_webhook, handle_github_webhook, handle_webhook, list_webhooks,
     register_webhook,
@@ -26,18 +37,36 @@ use crate::webhook::{
 use crate::websocket::ws_handler_with_runloop;
 use crate::workflow::routes as workflow_routes;
 
+/// Request size limit for session bundle imports (bundles carry artifacts).
+const SESSION_BUNDLE_LIMIT: usize = 64 * 1024 * 1024;
+
 /// Create the main router with HybridAppState for RunLoop support.
 ///
 /// ## Route Structure
 ///
 /// ```text
+/// POST routes under /tasks and /webhook honor an `Idempotency-Key` header.
+/// Routes starting agent runs answer 503 while no LLM provider is
+/// configured (degraded mode).
+///
 /// /tasks
 ///   POST   /tasks          - Submit task (sync, backward compat)
 ///   GET    /tasks/{id}     - Query task status
+///   GET    /tasks/{id}/trace - Execution trace
+///   GET    /tasks/{id}/tree  - Parent/child graph of a multi-agent run
 ///   POST   /tasks/{id}/abort - Abort task
+///   POST   /tasks/batch    - Submit a batch of tasks with dependencies
+///   GET    /tasks/batch/{id} - Batch status and results
 ///
 /// /v1/runloop
 ///   POST   /v1/runloop/task - Submit task via RunLoop (async)
+///   GET    /v1/runloop/task/{id}/deliveries - Per-target delivery status
+///   GET    /v1/runloop/status - Dispatch state and outstanding work
+///   GET    /v1/runloop/sources - Per-source depth, throughput and queue wait
+///   GET    /v1/runloop/events - Journal of triggers, phases and dispatch decisions
+///   POST   /v1/runloop/pause  - Stop dispatching queued tasks
+///   POST   /v1/runloop/resume - Accept and dispatch tasks again
+///   POST   /v1/runloop/drain  - Refuse new tasks, finish queued and in-flight ones
 ///
 /// /webhook
 ///   GET    /webhook/list   - List registered webhooks
@@ -53,6 +82,15 @@ use crate::workflow::routes as workflow_routes;
 ///   GET    /admin/sessions        - List sessions
 ///   GET    /admin/sessions/{id}   - Get session details
 ///   DELETE /admin/sessions/{id}   - Delete session
+///   GET    /admin/sessions/{id}/export - Export session bundle
+///   POST   /admin/sessions/import - Import session bundle
+///   GET    /admin/features        - Feature flags (?workspace=)
+///   GET    /admin/features/{name} - Feature flag value (?workspace=)
+///   PUT    /admin/features/{name} - Override a feature flag
+///   DELETE /admin/features/{name} - Drop a feature flag override (?workspace=)
+///   GET    /admin/resources       - Exclusive resources held and suspended
+///   POST   /admin/resources/{name}/suspend - Refuse tools needing a resource
+///   POST   /admin/resources/{name}/resume  - Let them run again
 ///   GET    /admin/stats           - System statistics
 ///   POST   /admin/reload          - Reload configuration
 ///   POST   /admin/shutdown        - Graceful shutdown
@@ -68,8 +106,36 @@ use crate::workflow::routes as workflow_routes;
 ///   POST   /jobs       - Create job
 ///   GET    /jobs       - List jobs
 ///   GET    /jobs/{id}  - Get job
+///   GET    /jobs/{id}/history - Job run history
 ///   DELETE /jobs/{id}  - Delete job
 ///
+/// /templates
+///   POST   /templates           - Create template
+///   GET    /templates           - List templates
+///   GET    /templates/{id}      - Get template
+///   POST   /templates/{id}/run  - Render and submit template
+///   DELETE /templates/{id}      - Delete template
+///
+/// /tools
+///   GET    /tools              - List enabled tools
+///   GET    /tools/{id}/schema  - Tool parameter/result schemas and examples
+///   POST   /tools/{id}/invoke  - Run a tool directly (no LLM)
+///
+/// /approvals
+///   GET    /approvals               - List pending approvals
+///   POST   /approvals/{id}/approve  - Approve request
+///   POST   /approvals/{id}/reject   - Reject request
+///
+/// /questions
+///   GET    /questions               - List pending questions
+///   POST   /questions/{id}/answer   - Answer question
+///
+/// /audit
+///   GET    /audit         - Query the audit log
+///   GET    /audit/verify  - Check the audit log's hash chain
+///
+/// /capabilities - Deployment capability manifest (?workspace=)
+///
 /// /health  - Detailed health check
 /// /metrics - Prometheus metrics
 /// /livez   - Liveness probe (Kubernetes)
@@ -78,26 +144,63 @@ use crate::workflow::routes as workflow_routes;
 /// /ws      - WebSocket connection (injects to RunLoop)
 /// ```
 pub fn create_router_with_hybrid_state(state: Arc<HybridAppState>) -> Router {
+    let idempotency = middleware::from_fn_with_state(
+        state.base.idempotency_store.clone(),
+        idempotency::enforce,
+    );
+    let requires_provider = middleware::from_fn_with_state(
+        state.base.clone(),
+        degraded::require_provider,
+    );
+
     // Task routes need AppState for agent_runtime access (backward compat)
     let task_routes = Router::new()
-        .route("/", post(agent_run))
-        .route("/{session_id}", get(agent_status))
+        .route("/", post(agent_run).layer(requires_provider.clone()))
         .route("/{session_id}/abort", post(agent_abort))
+        .route_layer(idempotency.clone())
         .with_state(state.base.clone());
 
+    // Status and trace lookups see RunLoop task outcomes and timelines;
+    // batch routes track their tasks in HybridAppState
+    let batch_router = Router::new()
+        .route("/{session_id}", get(agent_status))
+        .route("/{session_id}/trace", get(task_trace))
+        .route("/{session_id}/tree", get(task_tree))
+        .route(
+            "/batch",
+            post(batch_routes::submit_batch).layer(requires_provider.clone()),
+        )
+        .route("/batch/{id}", get(batch_routes::get_batch))
+        .route_layer(idempotency.clone())
+        .with_state(state.clone());
+
     // RunLoop route group for async task submission
     let runloop_routes = Router::new()
-        .route("/task", post(runloop_bridge::submit_task))
+        .route(
+            "/task",
+            post(runloop_bridge::submit_task).layer(requires_provider.clone()),
+        )
+        .route("/task/{id}/deliveries", get(runloop_bridge::get_deliveries))
+        .route("/status", get(runloop_bridge::control_status))
+        .route("/sources", get(runloop_bridge::source_stats))
+        .route("/events", get(runloop_bridge::events))
+        .route("/pause", post(runloop_bridge::pause))
+        .route("/resume", post(runloop_bridge::resume))
+        .route("/drain", post(runloop_bridge::drain))
         .with_state(state.runloop.clone());
 
     // Webhook routes use HybridAppState for RunLoop integration
     let webhook_routes = Router::new()
         .route("/list", get(list_webhooks))
         .route("/register", post(register_webhook))
-        .route("/github", post(handle_github_webhook))
+        .route(
+            "/github",
+            post(handle_github_webhook).layer(requires_provider.clone()),
+        )
         .route("/{id}", get(get_webhook))
-        .route("/{id}", post(handle_webhook))
+        .route("/{id}", post(handle_webhook).layer(requires_provider.clone()))
         .route("/{id}", delete(delete_webhook))
+        .route_layer(idempotency.clone())
         .with_state(state.clone());
 
     // Admin routes for extension/session management
@@ -107,16 +210,30 @@ pub fn create_router_with_hybrid_state(state: Arc<HybridAppState>) -> Router {
         .route("/sessions", get(admin::list_sessions))
         .route("/sessions/{id}", get(admin::get_session))
         .route("/sessions/{id}", delete(admin::delete_session))
+        .route("/sessions/{id}/export", get(admin::export_session))
+        .route(
+            "/sessions/import",
+            post(admin::import_session).layer(DefaultBodyLimit::max(SESSION_BUNDLE_LIMIT)),
+        )
+        .route("/features", get(admin::list_feature_flags))
+        .route("/features/{name}", get(admin::get_feature_flag))
+        .route("/features/{name}", put(admin::set_feature_flag))
+        .route("/features/{name}", delete(admin::clear_feature_flag))
+        .route("/resources", get(admin::list_resources))
+        .route("/resources/{name}/suspend", post(admin::suspend_resource))
+        .route("/resources/{name}/abort", post(admin::abort_resource))
+        .route("/resources/{name}/resume", post(admin::resume_resource))
         .route("/stats", get(admin::system_stats))
         .route("/reload", post(admin::reload_config))
         .route("/shutdown", post(admin::shutdown))
         .with_state(state.base.clone());
 
-    // Monitoring routes (health, metrics, probes)
+    // Monitoring routes (health, metrics, probes, capabilities)
     let monitoring_routes = Router::new()
         .route("/health", get(monitoring::health_check_detailed))
         .route("/metrics", get(monitoring::prometheus_metrics))
         .route("/readyz", get(monitoring::readiness_probe))
+        .route("/capabilities", get(capabilities::get_capabilities))
         .with_state(state.base.clone());
 
     // Liveness probe has no state dependency
@@ -128,7 +245,10 @@ pub fn create_router_with_hybrid_state(state: Arc<HybridAppState>) -> Router {
         .route("/", post(workflow_routes::create_workflow))
         .route("/", get(workflow_routes::list_workflows))
         .route("/{id}", get(workflow_routes::get_workflow))
-        .route("/{id}/run", post(workflow_routes::run_workflow))
+        .route(
+            "/{id}/run",
+            post(workflow_routes::run_workflow).layer(requires_provider.clone()),
+        )
         .route("/{id}", delete(workflow_routes::delete_workflow))
         .with_state(state.clone());
 
@@ -137,9 +257,49 @@ pub fn create_router_with_hybrid_state(state: Arc<HybridAppState>) -> Router {
         .route("/", post(job_routes::create_job))
         .route("/", get(job_routes::list_jobs))
         .route("/{id}", get(job_routes::get_job))
+        .route("/{id}/history", get(job_routes::get_job_history))
         .route("/{id}", delete(job_routes::delete_job))
         .with_state(state.clone());
 
+    // Template routes for template CRUD and submission
+    let template_router = Router::new()
+        .route("/", post(template_routes::create_template))
+        .route("/", get(template_routes::list_templates))
+        .route("/{id}", get(template_routes::get_template))
+        .route(
+            "/{id}/run",
+            post(template_routes::run_template).layer(requires_provider),
+        )
+        .route("/{id}", delete(template_routes::delete_template))
+        .with_state(state.clone());
+
+    // Tool catalog routes for external orchestrators
+    let tool_router = Router::new()
+        .route("/", get(tools::list_tools))
+        .route("/{id}/schema", get(tools::get_tool_schema))
+        .route("/{id}/invoke", post(tools::invoke_tool))
+        .route_layer(idempotency)
+        .with_state(state.base.clone());
+
+    // Approval routes for deciding pending human approvals
+    let approval_router = Router::new()
+        .route("/", get(approvals::list_approvals))
+        .route("/{id}/approve", post(approvals::approve))
+        .route("/{id}/reject", post(approvals::reject))
+        .with_state(state.clone());
+
+    // Question routes for answering the agents' questions
+    let question_router = Router::new()
+        .route("/", get(questions::list_questions))
+        .route("/{id}/answer", post(questions::answer_question))
+        .with_state(state.clone());
+
+    // Audit routes for reading the tool call and approval record
+    let audit_router = Router::new()
+        .route("/", get(audit::list_audit))
+        .route("/verify", get(audit::verify_audit))
+        .with_state(state.clone());
+
     // WebSocket route uses HybridAppState for RunLoop integration
     let ws_route = Router::new()
         .route("/ws", get(ws_handler_with_runloop))
@@ -147,11 +307,16 @@ pub fn create_router_with_hybrid_state(state: Arc<HybridAppState>) -> Router {
 
     // Combine all routes
     Router::new()
-        .nest("/tasks", task_routes)
+        .nest("/tasks", task_routes.merge(batch_router))
         .nest("/v1/runloop", runloop_routes)
         .nest("/webhook", webhook_routes)
         .nest("/workflows", workflow_router)
         .nest("/jobs", job_router)
+        .nest("/templates", template_router)
+        .nest("/tools", tool_router)
+        .nest("/approvals", approval_router)
+        .nest("/questions", question_router)
+        .nest("/audit", audit_router)
         .nest("/admin", admin_routes)
         .merge(monitoring_routes)
         .merge(liveness_route)
@@ -2,13 +2,18 @@
     use super::*;
     use crate::runloop_bridge::RunLoopState;
     use crate::state::AppState;
+    use autohands_runtime::TranscriptManager;
     use autohands_runloop::{RunLoop, RunLoopConfig};
     use axum::{
         body::Body,
         http::{Request, StatusCode},
     };
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
     use tower::ServiceExt;
 
+    type HmacSha256 = Hmac<Sha256>;
+
     fn create_test_router() -> Router {
         let base = Arc::new(AppState::default());
         let run_loop = Arc::new(RunLoop::new(RunLoopConfig::default()));
@@ -41,6 +46,62 @@
         assert!(response.status().is_success() || response.status().is_client_error());
     }
 
+    /// Agent with the default override handling, which refuses overrides.
+    struct FixedAgent(autohands_protocols::agent::AgentConfig);
+
+    #[async_trait::async_trait]
+    impl autohands_protocols::agent::Agent for FixedAgent {
+        fn id(&self) -> &str {
+            &self.0.id
+        }
+
+        fn config(&self) -> &autohands_protocols::agent::AgentConfig {
+            &self.0
+        }
+
+        async fn process(
+            &self,
+            _message: autohands_protocols::types::Message,
+            _ctx: autohands_protocols::agent::AgentContext,
+        ) -> Result<autohands_protocols::agent::AgentResponse, autohands_protocols::error::AgentError>
+        {
+            unreachable!("runs with rejected overrides never start")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_task_submit_rejects_overrides() {
+        let base = Arc::new(AppState::default());
+        base.agent_runtime.register_agent(Arc::new(FixedAgent(
+            autohands_protocols::agent::AgentConfig::new("general", "General", "model"),
+        )));
+        let run_loop = Arc::new(RunLoop::new(RunLoopConfig::default()));
+        let hybrid = Arc::new(HybridAppState::new(
+            base,
+            Arc::new(RunLoopState::from_runloop(run_loop)),
+            Arc::new(crate::websocket::ApiWsChannel::new()),
+        ));
+        let app = create_router_with_hybrid_state(hybrid);
+        let body = serde_json::json!({ "task": "Hello", "temperature": 0.5 });
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/tasks")
+                    .header("content-type", "application/json")
+                    .body(Body::from(serde_json::to_string(&body).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(json["error"].as_str().unwrap().contains("does not support per-run overrides"));
+    }
+
     #[tokio::test]
     async fn test_task_status_endpoint() {
         let app = create_test_router();
@@ -58,6 +119,160 @@
         assert!(response.status() == StatusCode::OK || response.status() == StatusCode::NOT_FOUND);
     }
 
+    async fn task_status(app: Router, uri: &str) -> serde_json::Value {
+        let response = app
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_task_status_long_poll() {
+        let run_loop = Arc::new(RunLoop::new(RunLoopConfig::default()));
+        let hybrid = Arc::new(HybridAppState::new(
+            Arc::new(AppState::default()),
+            Arc::new(RunLoopState::from_runloop(run_loop.clone())),
+            Arc::new(crate::websocket::ApiWsChannel::new()),
+        ));
+        let app = create_router_with_hybrid_state(hybrid);
+        let task_id = uuid::Uuid::new_v4();
+
+        let body = task_status(app.clone(), &format!("/tasks/{}", task_id)).await;
+        assert_eq!(body["status"], "pending");
+
+        let outcomes = run_loop.outcome_log();
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            outcomes.record(
+                task_id,
+                autohands_runloop::TaskOutcome::succeeded(Some("all done".to_string())),
+            );
+        });
+        let body = task_status(app.clone(), &format!("/tasks/{}?wait_seconds=10", task_id)).await;
+        assert_eq!(body["status"], "completed");
+        assert_eq!(body["result"], "all done");
+        assert!(body["finished_at"].is_string());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_task_status_long_poll_timeout() {
+        let app = create_test_router();
+        let uri = format!("/tasks/{}?wait_seconds=5", uuid::Uuid::new_v4());
+
+        let started = tokio::time::Instant::now();
+        let body = task_status(app, &uri).await;
+        assert_eq!(body["status"], "pending");
+        assert!(body.get("result").is_none());
+        assert_eq!(started.elapsed().as_secs(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_task_status_progress() {
+        let base = Arc::new(AppState::default());
+        let tracker = base.agent_runtime.progress_tracker().clone();
+        let hybrid = Arc::new(HybridAppState::new(
+            base,
+            Arc::new(RunLoopState::from_runloop(Arc::new(RunLoop::new(
+                RunLoopConfig::default(),
+            )))),
+            Arc::new(crate::websocket::ApiWsChannel::new()),
+        ));
+        let app = create_router_with_hybrid_state(hybrid);
+
+        let body = task_status(app.clone(), "/tasks/progress-session").await;
+        assert!(body.get("progress").is_none());
+
+        tracker.start("progress-session");
+        tracker.record(
+            "progress-session",
+            &autohands_runtime::StreamEvent::TurnStart { turn: 2 },
+        );
+        tracker.record(
+            "progress-session",
+            &autohands_runtime::StreamEvent::ToolCallStart {
+                id: "call-1".to_string(),
+                name: "read_file".to_string(),
+            },
+        );
+
+        let body = task_status(app, "/tasks/progress-session").await;
+        assert_eq!(body["progress"]["turn"], 2);
+        assert_eq!(body["progress"]["last_tool"], "read_file");
+        assert_eq!(body["progress"]["finished"], false);
+    }
+
+    #[tokio::test]
+    async fn test_task_trace_endpoint() {
+        use autohands_runtime::{TraceEvent, TraceEventKind};
+
+        let run_loop = Arc::new(RunLoop::new(RunLoopConfig::default()));
+        let hybrid = Arc::new(HybridAppState::new(
+            Arc::new(AppState::default()),
+            Arc::new(RunLoopState::from_runloop(run_loop.clone())),
+            Arc::new(crate::websocket::ApiWsChannel::new()),
+        ));
+        let app = create_router_with_hybrid_state(hybrid);
+
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri("/tasks/corr-1/trace").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let task_id = uuid::Uuid::new_v4();
+        let trace_log = run_loop.trace_log();
+        trace_log.record("corr-1", TraceEvent::new(TraceEventKind::TaskStarted).with_task(task_id));
+        trace_log.record(
+            "corr-1",
+            TraceEvent::new(TraceEventKind::ToolCall).with_turn(1).with_name("read_file"),
+        );
+
+        let body = task_status(app.clone(), "/tasks/corr-1/trace").await;
+        assert_eq!(body["correlation_id"], "corr-1");
+        assert_eq!(body["events"][0]["kind"], "task_started");
+        assert_eq!(body["events"][1]["name"], "read_file");
+
+        // Looked up by task ID as well
+        let body = task_status(app, &format!("/tasks/{}/trace", task_id)).await;
+        assert_eq!(body["correlation_id"], "corr-1");
+    }
+
+    #[tokio::test]
+    async fn test_task_tree_endpoint() {
+        let run_loop = Arc::new(RunLoop::new(RunLoopConfig::default()));
+        let hybrid = Arc::new(HybridAppState::new(
+            Arc::new(AppState::default()),
+            Arc::new(RunLoopState::from_runloop(run_loop.clone())),
+            Arc::new(crate::websocket::ApiWsChannel::new()),
+        ));
+        let app = create_router_with_hybrid_state(hybrid);
+
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri("/tasks/run-1/tree").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let root = autohands_runloop::Task::new("agent:execute", serde_json::json!({}))
+            .with_correlation_id("run-1");
+        let child = autohands_runloop::Task::new("agent:subtask", serde_json::json!({}))
+            .with_parent(root.id)
+            .with_correlation_id("run-1");
+        run_loop.task_graph().queued(&root);
+        run_loop.task_graph().queued(&child);
+
+        let body = task_status(app, &format!("/tasks/{}/tree", child.id)).await;
+        assert_eq!(body["tasks"], 2);
+        assert_eq!(body["root"]["id"], root.id.to_string());
+        assert_eq!(body["root"]["status"], "queued");
+        assert_eq!(body["root"]["children"][0]["label"], "agent:subtask");
+    }
+
     #[tokio::test]
     async fn test_task_abort_endpoint() {
         let app = create_test_router();
@@ -139,6 +354,259 @@
         assert_eq!(response.status(), StatusCode::ACCEPTED);
     }
 
+    #[tokio::test]
+    async fn test_webhook_queue_full() {
+        let mut config = RunLoopConfig::default();
+        config.queue.sources.insert(
+            "webhook".to_string(),
+            autohands_runloop::SourceQueueConfig {
+                max_pending: 1,
+                overflow: autohands_runloop::OverflowPolicy::Reject,
+            },
+        );
+        let run_loop = Arc::new(RunLoop::new(config));
+        let hybrid = Arc::new(HybridAppState::new(
+            Arc::new(AppState::default()),
+            Arc::new(RunLoopState::from_runloop(run_loop.clone())),
+            Arc::new(crate::websocket::ApiWsChannel::new()),
+        ));
+        let app = create_router_with_hybrid_state(hybrid);
+        let post = || {
+            Request::builder()
+                .method("POST")
+                .uri("/webhook/test-hook")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"test": "data"}"#))
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(post()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let response = app.oneshot(post()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(run_loop.task_queue().depth("webhook"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_runloop_control_endpoints() {
+        let run_loop = Arc::new(RunLoop::new(RunLoopConfig::default()));
+        let hybrid = Arc::new(HybridAppState::new(
+            Arc::new(AppState::default()),
+            Arc::new(RunLoopState::from_runloop(run_loop.clone())),
+            Arc::new(crate::websocket::ApiWsChannel::new()),
+        ));
+        let app = create_router_with_hybrid_state(hybrid);
+        let post = |uri: &str| {
+            Request::builder()
+                .method("POST")
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap()
+        };
+        let json = |bytes: axum::body::Bytes| -> serde_json::Value {
+            serde_json::from_slice(&bytes).unwrap()
+        };
+
+        let response = app.clone().oneshot(post("/v1/runloop/pause")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = json(axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap());
+        assert_eq!(body["dispatch"], "paused");
+        assert_eq!(run_loop.dispatch_state(), autohands_runloop::DispatchState::Paused);
+
+        let response = app
+            .clone()
+            .oneshot(post("/v1/runloop/drain?wait_seconds=1"))
+            .await
+            .unwrap();
+        let body = json(axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap());
+        assert_eq!(body["dispatch"], "draining");
+        assert_eq!(body["drained"], true);
+
+        let submit = Request::builder()
+            .method("POST")
+            .uri("/v1/runloop/task")
+            .header("content-type", "application/json")
+            .body(Body::from(r#"{"task": "hello"}"#))
+            .unwrap();
+        let response = app.clone().oneshot(submit).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let response = app.clone().oneshot(post("/v1/runloop/resume")).await.unwrap();
+        let body = json(axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap());
+        assert_eq!(body["dispatch"], "active");
+
+        let status = Request::builder()
+            .uri("/v1/runloop/status")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(status).await.unwrap();
+        let body = json(axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap());
+        assert_eq!(body["dispatch"], "active");
+        assert_eq!(body["queued_tasks"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_degraded_mode_without_provider() {
+        let run_loop = Arc::new(RunLoop::new(RunLoopConfig::default()));
+        let hybrid = Arc::new(HybridAppState::new(
+            Arc::new(AppState::default().with_provider_required()),
+            Arc::new(RunLoopState::from_runloop(run_loop.clone())),
+            Arc::new(crate::websocket::ApiWsChannel::new()),
+        ));
+        let app = create_router_with_hybrid_state(hybrid);
+        let post = |uri: &str, body: &str| {
+            Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        for uri in ["/tasks", "/v1/runloop/task"] {
+            let response = app
+                .clone()
+                .oneshot(post(uri, r#"{"task": "hello"}"#))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+            assert_eq!(body["code"], "no_provider_configured");
+            assert!(body["setup"].as_str().unwrap().contains("[providers]"));
+        }
+        assert_eq!(run_loop.task_queue().len().await, 0);
+
+        // What needs no model keeps working
+        let response = app
+            .clone()
+            .oneshot(post(
+                "/webhook/register",
+                r#"{"id": "test-hook", "description": "Test", "enabled": true}"#,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let response = app
+            .oneshot(Request::builder().uri("/tools").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_runloop_source_stats() {
+        let run_loop = Arc::new(RunLoop::new(RunLoopConfig::default()));
+        let hybrid = Arc::new(HybridAppState::new(
+            Arc::new(AppState::default()),
+            Arc::new(RunLoopState::from_runloop(run_loop.clone())),
+            Arc::new(crate::websocket::ApiWsChannel::new()),
+        ));
+        let app = create_router_with_hybrid_state(hybrid);
+        run_loop.metrics().record_dispatch("webhook", 40);
+        run_loop
+            .inject_task(autohands_runloop::Task::new("hello", serde_json::Value::Null))
+            .await
+            .unwrap();
+
+        let request = Request::builder()
+            .uri("/v1/runloop/sources")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["sources"]["webhook"]["dispatched"], 1);
+        assert_eq!(body["sources"]["webhook"]["max_wait_ms"], 40);
+        assert_eq!(body["sources"]["webhook"]["pending"], 0);
+        assert_eq!(body["sources"]["user"]["pending"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_bindings() {
+        let app = create_test_router();
+        let post = |uri: &str, body: serde_json::Value| {
+            Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+        let registration = serde_json::json!({
+            "id": "issues",
+            "enabled": true,
+            "bindings": [
+                {
+                    "target": "agent",
+                    "agent_id": "triager",
+                    "prompt": "Triage {{ body.repo }}#{{ body.number }}",
+                    "when": {"body.action": "opened"},
+                },
+                {
+                    "target": "workflow",
+                    "workflow_id": "missing",
+                    "when": {"body.action": "closed"},
+                },
+            ],
+        });
+        let response = app.clone().oneshot(post("/webhook/register", registration)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let opened = serde_json::json!({"action": "opened", "repo": "acme/app", "number": 7});
+        let response = app.clone().oneshot(post("/webhook/issues", opened)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let data: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(data["triggered"][0]["target"], "agent:triager");
+
+        // A matching binding that cannot start fails the event
+        let closed = serde_json::json!({"action": "closed"});
+        let response = app.clone().oneshot(post("/webhook/issues", closed)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        // No matching binding falls back to the generic trigger task
+        let edited = serde_json::json!({"action": "edited"});
+        let response = app.oneshot(post("/webhook/issues", edited)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let data: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(data.get("triggered").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_idempotency_key() {
+        let app = create_test_router();
+        let post = |key: &str, body: &str| {
+            Request::builder()
+                .method("POST")
+                .uri("/webhook/test-hook")
+                .header("content-type", "application/json")
+                .header("idempotency-key", key)
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let first = app.clone().oneshot(post("k1", r#"{"n": 1}"#)).await.unwrap();
+        assert_eq!(first.status(), StatusCode::ACCEPTED);
+        assert!(first.headers().get("idempotent-replayed").is_none());
+        let first = axum::body::to_bytes(first.into_body(), usize::MAX).await.unwrap();
+
+        // A retry replays the original response instead of queueing again
+        let retry = app.clone().oneshot(post("k1", r#"{"n": 1}"#)).await.unwrap();
+        assert_eq!(retry.status(), StatusCode::ACCEPTED);
+        assert_eq!(retry.headers()["idempotent-replayed"], "true");
+        let retry = axum::body::to_bytes(retry.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(retry, first);
+
+        let reused = app.clone().oneshot(post("k1", r#"{"n": 2}"#)).await.unwrap();
+        assert_eq!(reused.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let blank = app.oneshot(post(" ", r#"{"n": 1}"#)).await.unwrap();
+        assert_eq!(blank.status(), StatusCode::BAD_REQUEST);
+    }
+
     #[tokio::test]
     async fn test_webhook_github_endpoint() {
         let app = create_test_router();
@@ -158,6 +626,72 @@
         assert_eq!(response.status(), StatusCode::ACCEPTED);
     }
 
+    #[tokio::test]
+    async fn test_webhook_github_typed_events() {
+        let app = create_test_router();
+        let registration = serde_json::json!({
+            "id": "github",
+            "enabled": true,
+            "secret": "s3cret",
+            "bindings": [{
+                "target": "agent",
+                "agent_id": "triager",
+                "prompt": "{{ prompt }}",
+                "when": {"trigger.trigger_type": "github:issues.opened"},
+                "inputs": {"number": "$.trigger.data.number"},
+            }],
+        });
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/webhook/register")
+                    .header("content-type", "application/json")
+                    .body(Body::from(registration.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let body = serde_json::json!({
+            "action": "opened",
+            "repository": {"full_name": "acme/app"},
+            "issue": {
+                "number": 42,
+                "title": "Crash",
+                "user": {"login": "octocat"},
+                "html_url": "https://github.com/acme/app/issues/42",
+            },
+        })
+        .to_string();
+        let mut mac = HmacSha256::new_from_slice(b"s3cret").unwrap();
+        mac.update(body.as_bytes());
+        let signature = format!("sha256={}", hex::encode(mac.finalize().into_bytes()));
+        let delivery = |signature: Option<&str>| {
+            let mut request = Request::builder()
+                .method("POST")
+                .uri("/webhook/github")
+                .header("content-type", "application/json")
+                .header("x-github-event", "issues");
+            if let Some(signature) = signature {
+                request = request.header("x-hub-signature-256", signature);
+            }
+            request.body(Body::from(body.clone())).unwrap()
+        };
+
+        // A configured secret makes the signature mandatory
+        let response = app.clone().oneshot(delivery(None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = app.oneshot(delivery(Some(&signature))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let data: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(data["triggered"][0]["target"], "agent:triager");
+    }
+
     #[tokio::test]
     async fn test_webhook_delete_endpoint() {
         let app = create_test_router();
@@ -175,3 +709,268 @@
 
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
+
+    #[tokio::test]
+    async fn test_job_history_endpoint() {
+        let app = create_test_router();
+        let job = serde_json::json!({
+            "id": "nightly",
+            "schedule": "0 0 2 * * *",
+            "agent": "general",
+            "prompt": "Clean up",
+            "description": null,
+            "enabled": true,
+            "catch_up": "run_once",
+        });
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/jobs")
+                    .header("content-type", "application/json")
+                    .body(Body::from(job.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let history = |id: &str| {
+            Request::builder()
+                .uri(format!("/jobs/{}/history?limit=10", id))
+                .body(Body::empty())
+                .unwrap()
+        };
+        let response = app.clone().oneshot(history("nightly")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let data: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(data["count"], 0);
+
+        let response = app.oneshot(history("missing")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_session_export_import_endpoints() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut base = AppState::default();
+        base.transcript_manager = Arc::new(TranscriptManager::new(dir.path().join("transcripts")));
+        let run_loop = Arc::new(RunLoop::new(RunLoopConfig::default()));
+        let runloop = Arc::new(RunLoopState::from_runloop(run_loop));
+        let api_ws_channel = Arc::new(crate::websocket::ApiWsChannel::new());
+        let hybrid = Arc::new(HybridAppState::new(Arc::new(base), runloop, api_ws_channel));
+        let app = create_router_with_hybrid_state(hybrid);
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/admin/sessions/missing/export")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let bundle = serde_json::json!({
+            "format_version": 1,
+            "session_id": "moved",
+            "exported_at": "2026-01-01T00:00:00Z",
+            "autohands_version": "0.1.0",
+            "transcript": [],
+        });
+        let import = |body: String| {
+            Request::builder()
+                .method("POST")
+                .uri("/admin/sessions/import")
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap()
+        };
+        let response = app.clone().oneshot(import(bundle.to_string())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        // A second import without overwrite conflicts
+        let response = app.clone().oneshot(import(bundle.to_string())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/admin/sessions/moved/export")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_feature_flag_endpoints() {
+        let app = create_test_router();
+        let json = |response: axum::response::Response| async move {
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("PUT")
+                    .uri("/admin/features/parallel_tools")
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"enabled": true, "workspace": "/srv/repo"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let flag = json(response).await;
+        assert_eq!(flag["enabled"], true);
+        assert_eq!(flag["source"], "override");
+        assert_eq!(flag["workspace"], "/srv/repo");
+
+        let list = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let response = app
+            .clone()
+            .oneshot(list("/admin/features?workspace=/srv/repo"))
+            .await
+            .unwrap();
+        let flags = json(response).await;
+        let parallel = flags
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|f| f["name"] == "parallel_tools")
+            .unwrap();
+        assert_eq!(parallel["enabled"], true);
+
+        let response = app
+            .clone()
+            .oneshot(list("/admin/features/parallel_tools"))
+            .await
+            .unwrap();
+        assert_eq!(json(response).await["enabled"], false);
+
+        let clear = || {
+            Request::builder()
+                .method("DELETE")
+                .uri("/admin/features/parallel_tools?workspace=/srv/repo")
+                .body(Body::empty())
+                .unwrap()
+        };
+        let response = app.clone().oneshot(clear()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let response = app.oneshot(clear()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_resource_suspension_endpoints() {
+        let app = create_test_router();
+        let json = |response: axum::response::Response| async move {
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        };
+        let post = |uri: &str, body: &'static str| {
+            Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(post(
+                "/admin/resources/desktop-input/suspend",
+                r#"{"reason": "hands off"}"#,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(json(response).await["reason"], "hands off");
+
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri("/admin/resources").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = json(response).await;
+        assert_eq!(status["held"], serde_json::json!([]));
+        assert_eq!(status["suspended"][0]["resource"], "desktop-input");
+
+        let resume = || post("/admin/resources/desktop-input/resume", "");
+        let response = app.clone().oneshot(resume()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let response = app.clone().oneshot(resume()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = app
+            .clone()
+            .oneshot(post("/admin/resources/desktop-input/abort", "{}"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let suspension = json(response).await;
+        assert_eq!(suspension["reason"], "aborted from the API");
+        assert_eq!(suspension["aborts_runs"], true);
+        let response = app.oneshot(resume()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn test_tool_catalog_endpoints() {
+        let app = create_test_router();
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri("/tools").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/tools/missing/schema")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/tools/missing/invoke")
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"params": {}}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_capabilities_endpoint() {
+        let app = create_test_router();
+        let response = app
+            .oneshot(Request::builder().uri("/capabilities").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let manifest: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(manifest["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(manifest["tools"], serde_json::json!([]));
+        assert_eq!(manifest["feature_flags"][0]["name"], "auto_approval");
+    }
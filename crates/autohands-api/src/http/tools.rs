@@ -0,0 +1,170 @@
+//! Tool catalog endpoints.
+//!
+//! Lets external orchestrators discover the enabled tools together with
+//! their parameter and result schemas and example invocations, and run a
+//! single tool directly, without a model, under the same approval, timeout
+//! and audit rules as agent tool calls.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use autohands_core::ExecutionContext;
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{ToolDefinition, ToolExample, ToolResult};
+use autohands_protocols::types::RiskLevel;
+use autohands_runtime::InvokeError;
+
+use crate::http::admin::ErrorResponse;
+use crate::state::AppState;
+
+/// Request and response schemas of a tool.
+#[derive(Debug, Serialize)]
+pub struct ToolSchema {
+    /// Tool ID.
+    pub id: String,
+    /// Human-readable name.
+    pub name: String,
+    /// What the tool does.
+    pub description: String,
+    /// Risk level.
+    pub risk_level: RiskLevel,
+    /// Whether the tool streams output.
+    pub supports_streaming: bool,
+    /// JSON Schema of the parameters.
+    pub input_schema: serde_json::Value,
+    /// JSON Schema of the result.
+    pub output_schema: serde_json::Value,
+    /// Example invocations.
+    pub examples: Vec<ToolExample>,
+}
+
+impl From<ToolDefinition> for ToolSchema {
+    fn from(definition: ToolDefinition) -> Self {
+        Self {
+            input_schema: definition.input_schema(),
+            output_schema: ToolResult::json_schema(),
+            id: definition.id,
+            name: definition.name,
+            description: definition.description,
+            risk_level: definition.risk_level,
+            supports_streaming: definition.supports_streaming,
+            examples: definition.examples,
+        }
+    }
+}
+
+/// List enabled tools, sorted by ID.
+///
+/// GET /tools
+pub async fn list_tools(State(state): State<Arc<AppState>>) -> Json<Vec<ToolDefinition>> {
+    let mut tools = state.tool_registry.list();
+    tools.sort_by(|a, b| a.id.cmp(&b.id));
+    Json(tools)
+}
+
+/// Get the schemas of an enabled tool.
+///
+/// GET /tools/{id}/schema
+pub async fn get_tool_schema(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<ToolSchema>, (StatusCode, Json<ErrorResponse>)> {
+    match state.tool_registry.get(&id) {
+        Some(tool) => Ok(Json(tool.definition().clone().into())),
+        None => Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::new(
+                format!("Tool not found: {}", id),
+                "tool_not_found",
+            )),
+        )),
+    }
+}
+
+/// Longest timeout a caller may request for one invocation.
+pub const MAX_INVOKE_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Request to run a tool.
+#[derive(Debug, Default, Deserialize)]
+pub struct InvokeToolRequest {
+    /// Tool parameters.
+    #[serde(default)]
+    pub params: serde_json::Value,
+    /// Session the call is attributed to; generated if absent.
+    #[serde(default)]
+    pub session_id: Option<String>,
+    /// Timeout in seconds, capped at [`MAX_INVOKE_TIMEOUT`].
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+}
+
+/// Result of a tool invocation.
+#[derive(Debug, Serialize)]
+pub struct InvokeToolResponse {
+    pub tool_id: String,
+    pub session_id: String,
+    pub correlation_id: String,
+    pub duration_ms: u64,
+    #[serde(flatten)]
+    pub result: ToolResult,
+}
+
+/// Run an enabled tool once.
+///
+/// POST /tools/{id}/invoke
+pub async fn invoke_tool(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(request): Json<InvokeToolRequest>,
+) -> Result<Json<InvokeToolResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let session_id = request
+        .session_id
+        .unwrap_or_else(|| format!("tool:{}:{}", id, uuid::Uuid::new_v4()));
+    let work_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let ctx = ExecutionContext::new(session_id, work_dir);
+    let timeout = request
+        .timeout_secs
+        .map(|secs| Duration::from_secs(secs).min(MAX_INVOKE_TIMEOUT));
+
+    let started = Instant::now();
+    let result = state
+        .agent_runtime
+        .tool_invoker()
+        .invoke(&id, request.params, &ctx, timeout)
+        .await
+        .map_err(invoke_error)?;
+
+    Ok(Json(InvokeToolResponse {
+        tool_id: id,
+        session_id: ctx.session_id,
+        correlation_id: ctx.correlation_id,
+        duration_ms: started.elapsed().as_millis() as u64,
+        result,
+    }))
+}
+
+fn invoke_error(error: InvokeError) -> (StatusCode, Json<ErrorResponse>) {
+    let (status, code) = match &error {
+        InvokeError::NotFound(_) => (StatusCode::NOT_FOUND, "tool_not_found"),
+        InvokeError::Refused(_) | InvokeError::Tool(ToolError::PermissionDenied(_)) => {
+            (StatusCode::FORBIDDEN, "tool_refused")
+        }
+        InvokeError::Timeout(..) => (StatusCode::GATEWAY_TIMEOUT, "tool_timeout"),
+        InvokeError::Tool(ToolError::InvalidParameters(_) | ToolError::ValidationFailed(_)) => {
+            (StatusCode::BAD_REQUEST, "invalid_parameters")
+        }
+        InvokeError::Tool(_) => (StatusCode::INTERNAL_SERVER_ERROR, "tool_failed"),
+    };
+    (status, Json(ErrorResponse::new(error.to_string(), code)))
+}
+
+#[cfg(test)]
+#[path = "tools_tests.rs"]
+mod tests;
@@ -0,0 +1,139 @@
+use super::*;
+use async_trait::async_trait;
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{Tool, ToolContext};
+
+struct EchoTool {
+    definition: ToolDefinition,
+}
+
+impl EchoTool {
+    fn new(id: &str) -> Self {
+        Self {
+            definition: ToolDefinition::new(id, "Echo", "Echo the input")
+                .with_parameters_schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {"text": {"type": "string"}},
+                    "required": ["text"]
+                }))
+                .with_example("Echo a greeting", serde_json::json!({"text": "hi"})),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for EchoTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        _ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        Ok(ToolResult::success(params["text"].as_str().unwrap_or_default()))
+    }
+}
+
+fn state_with_tools() -> Arc<AppState> {
+    let state = AppState::default();
+    state.tool_registry.register(Arc::new(EchoTool::new("echo"))).unwrap();
+    state.tool_registry.register(Arc::new(EchoTool::new("alpha"))).unwrap();
+    state.tool_registry.set_enabled("alpha", false).unwrap();
+    Arc::new(state)
+}
+
+#[tokio::test]
+async fn test_list_tools_skips_disabled() {
+    let Json(tools) = list_tools(State(state_with_tools())).await;
+    let ids: Vec<_> = tools.iter().map(|t| t.id.as_str()).collect();
+    assert_eq!(ids, vec!["echo"]);
+    assert_eq!(tools[0].examples.len(), 1);
+}
+
+#[tokio::test]
+async fn test_get_tool_schema() {
+    let Json(schema) = get_tool_schema(State(state_with_tools()), Path("echo".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(schema.id, "echo");
+    assert_eq!(schema.input_schema["required"][0], "text");
+    assert_eq!(schema.output_schema["required"][0], "success");
+    assert_eq!(schema.examples[0].input["text"], "hi");
+}
+
+#[tokio::test]
+async fn test_get_tool_schema_not_found() {
+    for id in ["missing", "alpha"] {
+        let err = get_tool_schema(State(state_with_tools()), Path(id.to_string()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.0, StatusCode::NOT_FOUND);
+        assert_eq!(err.1.code, "tool_not_found");
+    }
+}
+
+#[tokio::test]
+async fn test_invoke_tool() {
+    let request = InvokeToolRequest {
+        params: serde_json::json!({"text": "hi"}),
+        session_id: Some("ops".to_string()),
+        timeout_secs: Some(5),
+    };
+    let Json(response) = invoke_tool(
+        State(state_with_tools()),
+        Path("echo".to_string()),
+        Json(request),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.tool_id, "echo");
+    assert_eq!(response.session_id, "ops");
+    assert!(!response.correlation_id.is_empty());
+    assert!(response.result.success);
+
+    let json = serde_json::to_value(&response).unwrap();
+    assert_eq!(json["content"], "hi");
+}
+
+#[tokio::test]
+async fn test_invoke_tool_not_found() {
+    for id in ["missing", "alpha"] {
+        let err = invoke_tool(
+            State(state_with_tools()),
+            Path(id.to_string()),
+            Json(InvokeToolRequest::default()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.0, StatusCode::NOT_FOUND);
+        assert_eq!(err.1.code, "tool_not_found");
+    }
+}
+
+#[test]
+fn test_invoke_error_status() {
+    let cases = [
+        (InvokeError::Refused("no".into()), StatusCode::FORBIDDEN),
+        (
+            InvokeError::Timeout("echo".into(), Duration::from_secs(1)),
+            StatusCode::GATEWAY_TIMEOUT,
+        ),
+        (
+            InvokeError::Tool(ToolError::InvalidParameters("x".into())),
+            StatusCode::BAD_REQUEST,
+        ),
+        (
+            InvokeError::Tool(ToolError::PermissionDenied("x".into())),
+            StatusCode::FORBIDDEN,
+        ),
+        (
+            InvokeError::Tool(ToolError::ExecutionFailed("x".into())),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ),
+    ];
+    for (error, status) in cases {
+        assert_eq!(invoke_error(error).0, status);
+    }
+}
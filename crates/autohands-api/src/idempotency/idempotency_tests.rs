@@ -0,0 +1,55 @@
+use super::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use axum::{middleware, routing::post, Router};
+use tower::ServiceExt;
+
+/// Router whose handler answers each call with the next status of
+/// `statuses`, counting the calls.
+fn router(statuses: Vec<StatusCode>, calls: Arc<AtomicUsize>) -> Router {
+    let store: Arc<dyn IdempotencyStore> =
+        Arc::new(MemoryIdempotencyStore::new(Duration::from_secs(60)));
+    Router::new()
+        .route(
+            "/tasks",
+            post(move || {
+                let call = calls.fetch_add(1, Ordering::SeqCst);
+                let status = statuses[call.min(statuses.len() - 1)];
+                async move { (status, Json(serde_json::json!({"call": call}))) }
+            }),
+        )
+        .layer(middleware::from_fn_with_state(store, enforce))
+}
+
+fn request() -> Request {
+    Request::builder()
+        .method("POST")
+        .uri("/tasks")
+        .header(IDEMPOTENCY_KEY_HEADER, "k1")
+        .body(Body::from(r#"{"task": "run"}"#))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_errors_release_the_key() {
+    for error in [StatusCode::BAD_REQUEST, StatusCode::SERVICE_UNAVAILABLE] {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = router(vec![error, StatusCode::ACCEPTED], calls.clone());
+
+        let failed = app.clone().oneshot(request()).await.unwrap();
+        assert_eq!(failed.status(), error);
+
+        // The retry runs instead of replaying the error
+        let retry = app.clone().oneshot(request()).await.unwrap();
+        assert_eq!(retry.status(), StatusCode::ACCEPTED);
+        assert!(retry.headers().get(REPLAYED_HEADER).is_none());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // Its success is recorded
+        let replayed = app.oneshot(request()).await.unwrap();
+        assert_eq!(replayed.status(), StatusCode::ACCEPTED);
+        assert_eq!(replayed.headers()[REPLAYED_HEADER], "true");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,183 @@
+//! Idempotency keys for task-creating requests.
+//!
+//! A client sending `Idempotency-Key: <key>` with a POST gets the request
+//! executed at most once per key: retries with the same key and body replay
+//! the recorded response (marked `Idempotent-Replayed: true`) instead of
+//! starting another agent run. While the first request is still running,
+//! retries get `409 Conflict`; reusing a key for a different request gets
+//! `422 Unprocessable Entity`.
+//!
+//! Errors are not recorded: after a client error (4xx) nothing ran and the
+//! client is expected to fix the request, and after a server error (5xx) the
+//! request may succeed when retried, both with the same key.
+
+mod store;
+
+pub use store::{
+    Claim, FileIdempotencyStore, IdempotencyRecord, IdempotencyStore, MemoryIdempotencyStore,
+    StoredResponse, DEFAULT_IDEMPOTENCY_TTL,
+};
+
+use std::sync::Arc;
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::{header, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+
+/// Request header carrying the key.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Response header set on replayed responses.
+pub const REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// Longest accepted key.
+pub const MAX_KEY_LEN: usize = 255;
+
+/// Largest request body buffered for fingerprinting.
+const MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// Middleware enforcing idempotency keys on POST requests.
+///
+/// Requests without the header, and non-POST requests, pass through.
+pub async fn enforce(
+    State(store): State<Arc<dyn IdempotencyStore>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.method() != Method::POST {
+        return next.run(request).await;
+    }
+    let Some(key) = request.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+        return next.run(request).await;
+    };
+    let key = match key.to_str() {
+        Ok(key) if !key.trim().is_empty() && key.len() <= MAX_KEY_LEN => key.trim().to_string(),
+        _ => {
+            return error(
+                StatusCode::BAD_REQUEST,
+                format!("Idempotency-Key must be 1-{} visible ASCII characters", MAX_KEY_LEN),
+            )
+        }
+    };
+
+    let (parts, body) = request.into_parts();
+    let Ok(body) = to_bytes(body, MAX_BODY_BYTES).await else {
+        return error(StatusCode::PAYLOAD_TOO_LARGE, "Request body too large".to_string());
+    };
+    let fingerprint = fingerprint(&parts.method, parts.uri.path(), &body);
+
+    match store.claim(&key, &fingerprint).await {
+        Ok(Claim::Acquired) => {}
+        Ok(Claim::Completed(response)) => {
+            info!("Replaying response for idempotency key '{}'", key);
+            return replay(response);
+        }
+        Ok(Claim::InProgress) => {
+            return error(
+                StatusCode::CONFLICT,
+                format!("A request with idempotency key '{}' is still in progress", key),
+            )
+        }
+        Ok(Claim::Mismatch) => {
+            return error(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                format!("Idempotency key '{}' was already used for a different request", key),
+            )
+        }
+        Err(e) => {
+            warn!("Idempotency store unavailable: {}", e);
+            return error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string());
+        }
+    }
+
+    // Release the key if the client disconnects and the handler is dropped
+    let mut pending = Pending {
+        store: store.clone(),
+        key: Some(key.clone()),
+    };
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+
+    let (parts, body) = response.into_parts();
+    let body = match to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => {
+            return error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to read response: {}", e),
+            )
+        }
+    };
+
+    pending.key = None;
+    let result = if parts.status.is_client_error() || parts.status.is_server_error() {
+        store.release(&key).await
+    } else {
+        let stored = StoredResponse {
+            status: parts.status.as_u16(),
+            body: String::from_utf8_lossy(&body).into_owned(),
+        };
+        store.complete(&key, stored).await
+    };
+    if let Err(e) = result {
+        warn!("Failed to record idempotency key '{}': {}", key, e);
+    }
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+/// Releases a claimed key when dropped before the request finished.
+struct Pending {
+    store: Arc<dyn IdempotencyStore>,
+    key: Option<String>,
+}
+
+impl Drop for Pending {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            let store = self.store.clone();
+            tokio::spawn(async move {
+                if let Err(e) = store.release(&key).await {
+                    warn!("Failed to release idempotency key '{}': {}", key, e);
+                }
+            });
+        }
+    }
+}
+
+fn fingerprint(method: &Method, path: &str, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_str());
+    hasher.update(b" ");
+    hasher.update(path);
+    hasher.update(b"\n");
+    hasher.update(body);
+    hex::encode(hasher.finalize())
+}
+
+fn replay(response: StoredResponse) -> Response {
+    let status = StatusCode::from_u16(response.status).unwrap_or(StatusCode::OK);
+    (
+        status,
+        [
+            (header::CONTENT_TYPE, "application/json"),
+            (header::HeaderName::from_static(REPLAYED_HEADER), "true"),
+        ],
+        response.body,
+    )
+        .into_response()
+}
+
+fn error(status: StatusCode, message: String) -> Response {
+    (status, Json(serde_json::json!({"error": message}))).into_response()
+}
+
+#[cfg(test)]
+#[path = "idempotency_tests.rs"]
+mod tests;
@@ -0,0 +1,312 @@
+//! Idempotency key persistence.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::fs;
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+use crate::error::InterfaceError;
+
+/// How long keys are remembered by default.
+pub const DEFAULT_IDEMPOTENCY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// A response recorded for replay.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredResponse {
+    /// HTTP status code.
+    pub status: u16,
+    /// Response body (JSON).
+    pub body: String,
+}
+
+/// A key and what it was used for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdempotencyRecord {
+    /// Client-supplied key.
+    pub key: String,
+    /// Hash of the request the key was first used with.
+    pub fingerprint: String,
+    /// When the key was first used.
+    pub created_at: DateTime<Utc>,
+    /// Response, once the request has finished.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response: Option<StoredResponse>,
+}
+
+impl IdempotencyRecord {
+    fn new(key: &str, fingerprint: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            fingerprint: fingerprint.to_string(),
+            created_at: Utc::now(),
+            response: None,
+        }
+    }
+
+    fn is_expired(&self, ttl: Duration) -> bool {
+        (Utc::now() - self.created_at).to_std().unwrap_or_default() >= ttl
+    }
+
+    /// What a new request with this key gets, or `None` if the record expired.
+    fn claim(&self, fingerprint: &str, ttl: Duration) -> Option<Claim> {
+        if self.is_expired(ttl) {
+            None
+        } else if self.fingerprint != fingerprint {
+            Some(Claim::Mismatch)
+        } else {
+            Some(match &self.response {
+                Some(response) => Claim::Completed(response.clone()),
+                None => Claim::InProgress,
+            })
+        }
+    }
+}
+
+/// Outcome of claiming a key for a request.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Claim {
+    /// Key is new; the caller runs the request and then completes or releases it.
+    Acquired,
+    /// A request with this key is still running.
+    InProgress,
+    /// A request with this key finished; replay its response.
+    Completed(StoredResponse),
+    /// The key was used with a different request.
+    Mismatch,
+}
+
+/// Store mapping idempotency keys to their requests and responses.
+#[async_trait]
+pub trait IdempotencyStore: Send + Sync {
+    /// Claim a key for a request with the given fingerprint.
+    async fn claim(&self, key: &str, fingerprint: &str) -> Result<Claim, InterfaceError>;
+
+    /// Record the response of a claimed key.
+    async fn complete(&self, key: &str, response: StoredResponse) -> Result<(), InterfaceError>;
+
+    /// Forget a claimed key so the request can be retried.
+    async fn release(&self, key: &str) -> Result<(), InterfaceError>;
+
+    /// Remove expired keys, returning how many were removed.
+    async fn purge_expired(&self) -> Result<usize, InterfaceError>;
+}
+
+/// In-memory idempotency store.
+pub struct MemoryIdempotencyStore {
+    ttl: Duration,
+    records: Mutex<HashMap<String, IdempotencyRecord>>,
+}
+
+impl MemoryIdempotencyStore {
+    /// Create a store remembering keys for `ttl`.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            records: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for MemoryIdempotencyStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_IDEMPOTENCY_TTL)
+    }
+}
+
+#[async_trait]
+impl IdempotencyStore for MemoryIdempotencyStore {
+    async fn claim(&self, key: &str, fingerprint: &str) -> Result<Claim, InterfaceError> {
+        let mut records = self.records.lock().await;
+        if let Some(claim) = records.get(key).and_then(|r| r.claim(fingerprint, self.ttl)) {
+            return Ok(claim);
+        }
+        records.insert(key.to_string(), IdempotencyRecord::new(key, fingerprint));
+        Ok(Claim::Acquired)
+    }
+
+    async fn complete(&self, key: &str, response: StoredResponse) -> Result<(), InterfaceError> {
+        if let Some(record) = self.records.lock().await.get_mut(key) {
+            record.response = Some(response);
+        }
+        Ok(())
+    }
+
+    async fn release(&self, key: &str) -> Result<(), InterfaceError> {
+        self.records.lock().await.remove(key);
+        Ok(())
+    }
+
+    async fn purge_expired(&self) -> Result<usize, InterfaceError> {
+        let mut records = self.records.lock().await;
+        let before = records.len();
+        records.retain(|_, r| !r.is_expired(self.ttl));
+        Ok(before - records.len())
+    }
+}
+
+/// File system based idempotency store.
+///
+/// Each key is a JSON file under `<storage_path>/idempotency/`, named by the
+/// key's SHA-256 so arbitrary client keys are safe file names. Keys survive
+/// restarts, so a client retrying across a deploy still gets the original
+/// response. Keys whose request was still running are dropped on open: that
+/// request died with the previous process, so a retry may run it again.
+pub struct FileIdempotencyStore {
+    storage_path: PathBuf,
+    ttl: Duration,
+    // Serializes claim's read-then-write within the process
+    lock: Mutex<()>,
+}
+
+impl FileIdempotencyStore {
+    /// Create a file-based store remembering keys for `ttl`.
+    pub async fn new(
+        storage_path: impl Into<PathBuf>,
+        ttl: Duration,
+    ) -> Result<Self, InterfaceError> {
+        let storage_path = storage_path.into();
+        fs::create_dir_all(storage_path.join("idempotency"))
+            .await
+            .map_err(|e| {
+                InterfaceError::Custom(format!("Failed to create idempotency directory: {}", e))
+            })?;
+
+        debug!("FileIdempotencyStore initialized at {:?}", storage_path);
+
+        let store = Self {
+            storage_path,
+            ttl,
+            lock: Mutex::new(()),
+        };
+        let abandoned = store
+            .remove_where(|record| record.is_some_and(|r| r.response.is_none()))
+            .await?;
+        if abandoned > 0 {
+            warn!("Released {} idempotency keys of interrupted requests", abandoned);
+        }
+        Ok(store)
+    }
+
+    fn records_dir(&self) -> PathBuf {
+        self.storage_path.join("idempotency")
+    }
+
+    fn record_path(&self, key: &str) -> PathBuf {
+        let digest = hex::encode(Sha256::digest(key.as_bytes()));
+        self.records_dir().join(format!("{}.json", digest))
+    }
+
+    async fn read(&self, key: &str) -> Result<Option<IdempotencyRecord>, InterfaceError> {
+        let content = match fs::read_to_string(self.record_path(key)).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(InterfaceError::Custom(format!(
+                    "Failed to read idempotency record: {}",
+                    e
+                )))
+            }
+        };
+        match serde_json::from_str(&content) {
+            Ok(record) => Ok(Some(record)),
+            Err(e) => {
+                // A torn write; treat the key as unused
+                warn!("Discarding unreadable idempotency record for '{}': {}", key, e);
+                Ok(None)
+            }
+        }
+    }
+
+    async fn write(&self, record: &IdempotencyRecord) -> Result<(), InterfaceError> {
+        let content = serde_json::to_string(record).map_err(|e| {
+            InterfaceError::Custom(format!("Failed to serialize idempotency record: {}", e))
+        })?;
+        fs::write(self.record_path(&record.key), content)
+            .await
+            .map_err(|e| {
+                InterfaceError::Custom(format!("Failed to write idempotency record: {}", e))
+            })
+    }
+
+    /// Delete the records matching `remove`, which gets `None` for an
+    /// unreadable record; returns how many were deleted.
+    async fn remove_where(
+        &self,
+        remove: impl Fn(Option<&IdempotencyRecord>) -> bool,
+    ) -> Result<usize, InterfaceError> {
+        let mut entries = fs::read_dir(self.records_dir()).await.map_err(|e| {
+            InterfaceError::Custom(format!("Failed to read idempotency directory: {}", e))
+        })?;
+
+        let mut removed = 0;
+        while let Some(entry) = entries.next_entry().await.map_err(|e| {
+            InterfaceError::Custom(format!("Failed to read directory entry: {}", e))
+        })? {
+            let path = entry.path();
+            let matched = match fs::read_to_string(&path).await {
+                Ok(content) => {
+                    remove(serde_json::from_str::<IdempotencyRecord>(&content).ok().as_ref())
+                }
+                Err(_) => false,
+            };
+            if matched && fs::remove_file(&path).await.is_ok() {
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+}
+
+#[async_trait]
+impl IdempotencyStore for FileIdempotencyStore {
+    async fn claim(&self, key: &str, fingerprint: &str) -> Result<Claim, InterfaceError> {
+        let _guard = self.lock.lock().await;
+        if let Some(claim) = self
+            .read(key)
+            .await?
+            .and_then(|r| r.claim(fingerprint, self.ttl))
+        {
+            return Ok(claim);
+        }
+        self.write(&IdempotencyRecord::new(key, fingerprint)).await?;
+        Ok(Claim::Acquired)
+    }
+
+    async fn complete(&self, key: &str, response: StoredResponse) -> Result<(), InterfaceError> {
+        let _guard = self.lock.lock().await;
+        if let Some(mut record) = self.read(key).await? {
+            record.response = Some(response);
+            self.write(&record).await?;
+        }
+        Ok(())
+    }
+
+    async fn release(&self, key: &str) -> Result<(), InterfaceError> {
+        let _guard = self.lock.lock().await;
+        match fs::remove_file(self.record_path(key)).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(InterfaceError::Custom(format!(
+                "Failed to delete idempotency record: {}",
+                e
+            ))),
+        }
+    }
+
+    async fn purge_expired(&self) -> Result<usize, InterfaceError> {
+        let _guard = self.lock.lock().await;
+        self.remove_where(|record| record.is_none_or(|r| r.is_expired(self.ttl)))
+            .await
+    }
+}
+
+#[cfg(test)]
+#[path = "store_tests.rs"]
+mod tests;
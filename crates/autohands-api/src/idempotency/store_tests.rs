@@ -0,0 +1,94 @@
+use super::*;
+
+fn response(body: &str) -> StoredResponse {
+    StoredResponse {
+        status: 202,
+        body: body.to_string(),
+    }
+}
+
+async fn check_lifecycle(store: &dyn IdempotencyStore) {
+    assert_eq!(store.claim("k1", "fp").await.unwrap(), Claim::Acquired);
+    assert_eq!(store.claim("k1", "fp").await.unwrap(), Claim::InProgress);
+    assert_eq!(store.claim("k1", "other").await.unwrap(), Claim::Mismatch);
+
+    store.complete("k1", response("{}")).await.unwrap();
+    assert_eq!(store.claim("k1", "fp").await.unwrap(), Claim::Completed(response("{}")));
+
+    // Released keys can be claimed again
+    assert_eq!(store.claim("k2", "fp").await.unwrap(), Claim::Acquired);
+    store.release("k2").await.unwrap();
+    assert_eq!(store.claim("k2", "fp").await.unwrap(), Claim::Acquired);
+}
+
+#[tokio::test]
+async fn test_memory_store_lifecycle() {
+    check_lifecycle(&MemoryIdempotencyStore::default()).await;
+}
+
+#[tokio::test]
+async fn test_file_store_lifecycle() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = FileIdempotencyStore::new(dir.path(), DEFAULT_IDEMPOTENCY_TTL)
+        .await
+        .unwrap();
+    check_lifecycle(&store).await;
+    // Keys with path characters are stored safely
+    assert_eq!(store.claim("../../etc/passwd", "fp").await.unwrap(), Claim::Acquired);
+}
+
+#[tokio::test]
+async fn test_file_store_survives_restart() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = FileIdempotencyStore::new(dir.path(), DEFAULT_IDEMPOTENCY_TTL)
+        .await
+        .unwrap();
+    store.claim("k", "fp").await.unwrap();
+    store.complete("k", response("{\"ok\":true}")).await.unwrap();
+
+    let reopened = FileIdempotencyStore::new(dir.path(), DEFAULT_IDEMPOTENCY_TTL)
+        .await
+        .unwrap();
+    assert_eq!(
+        reopened.claim("k", "fp").await.unwrap(),
+        Claim::Completed(response("{\"ok\":true}"))
+    );
+}
+
+#[tokio::test]
+async fn test_file_store_releases_interrupted_requests() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = FileIdempotencyStore::new(dir.path(), DEFAULT_IDEMPOTENCY_TTL)
+        .await
+        .unwrap();
+    store.claim("done", "fp").await.unwrap();
+    store.complete("done", response("{}")).await.unwrap();
+    // Still running when the process stopped
+    assert_eq!(store.claim("running", "fp").await.unwrap(), Claim::Acquired);
+    drop(store);
+
+    let reopened = FileIdempotencyStore::new(dir.path(), DEFAULT_IDEMPOTENCY_TTL)
+        .await
+        .unwrap();
+    assert_eq!(reopened.claim("running", "fp").await.unwrap(), Claim::Acquired);
+    assert_eq!(
+        reopened.claim("done", "fp").await.unwrap(),
+        Claim::Completed(response("{}"))
+    );
+}
+
+#[tokio::test]
+async fn test_expired_keys() {
+    let memory = MemoryIdempotencyStore::new(Duration::ZERO);
+    memory.claim("k", "fp").await.unwrap();
+    // An expired key is reusable, even for a different request
+    assert_eq!(memory.claim("k", "other").await.unwrap(), Claim::Acquired);
+    assert_eq!(memory.purge_expired().await.unwrap(), 1);
+
+    let dir = tempfile::tempdir().unwrap();
+    let file = FileIdempotencyStore::new(dir.path(), Duration::ZERO).await.unwrap();
+    file.claim("a", "fp").await.unwrap();
+    file.claim("b", "fp").await.unwrap();
+    assert_eq!(file.purge_expired().await.unwrap(), 2);
+    assert_eq!(file.purge_expired().await.unwrap(), 0);
+}
@@ -25,21 +25,120 @@ impl Default for JobStatus {
     }
 }
 
+/// What to do with runs missed while the scheduler was not running.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CatchUpPolicy {
+    /// Drop missed runs and wait for the next scheduled time.
+    #[default]
+    Skip,
+    /// Run once to make up for any number of missed runs.
+    RunOnce,
+    /// Run once per missed occurrence.
+    RunAllMissed,
+}
+
+/// What to do when a run is due while the previous one is still in progress.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlapPolicy {
+    /// Do not start the new run.
+    #[default]
+    Skip,
+    /// Start the new run once the previous one finished.
+    Queue,
+    /// Abort the previous run and start the new one.
+    KillPrevious,
+}
+
+/// Backup taken by a job instead of running an agent.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupJob {
+    /// Directory the backups are written to (default: `~/.autohands/backups`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dir: Option<String>,
+    /// Most recent backups kept in the directory (0 = all).
+    #[serde(default)]
+    pub keep: usize,
+    /// Glob patterns of state paths to leave out.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Encrypt with the passphrase in `AUTOHANDS_BACKUP_PASSPHRASE`.
+    #[serde(default)]
+    pub encrypt: bool,
+}
+
+/// Web page watched by a job, whose agent then only runs when the page
+/// changed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchJob {
+    /// Page to watch.
+    pub url: String,
+    /// Part of the page to watch: `#id`, `.class` or a tag name (default:
+    /// the whole page).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selector: Option<String>,
+    /// Fewest added or removed lines of text that count as a change.
+    #[serde(default = "default_min_changed_lines")]
+    pub min_changed_lines: usize,
+    /// Channel told about changes, e.g. `web`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    /// Recipient on the channel.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+}
+
+fn default_min_changed_lines() -> usize {
+    1
+}
+
+impl WatchJob {
+    /// Watch the whole page at `url`.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            selector: None,
+            min_changed_lines: default_min_changed_lines(),
+            channel: None,
+            target: None,
+        }
+    }
+}
+
 /// Job definition.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JobDefinition {
     /// Unique job ID.
     pub id: String,
-    /// Cron schedule expression.
+    /// Schedule: a cron expression, `@every <interval>` or `@at <timestamp>`.
+    /// See [`JobSchedule`](super::JobSchedule).
     pub schedule: String,
     /// Agent to run the job.
+    #[serde(default)]
     pub agent: String,
     /// Prompt to execute.
+    #[serde(default)]
     pub prompt: String,
     /// Optional description.
     pub description: Option<String>,
     /// Whether job is enabled.
     pub enabled: bool,
+    /// Handling of runs missed during downtime.
+    #[serde(default)]
+    pub catch_up: CatchUpPolicy,
+    /// Upper bound of the random delay added to each run's start, in seconds.
+    #[serde(default)]
+    pub jitter_secs: u64,
+    /// Handling of runs due while the previous run is still in progress.
+    #[serde(default)]
+    pub overlap: OverlapPolicy,
+    /// Back up the state directory instead of running the agent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backup: Option<BackupJob>,
+    /// Watch a web page, running the agent only when it changed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub watch: Option<WatchJob>,
 }
 
 impl JobDefinition {
@@ -57,6 +156,19 @@ impl JobDefinition {
             prompt: prompt.into(),
             description: None,
             enabled: true,
+            catch_up: CatchUpPolicy::default(),
+            jitter_secs: 0,
+            overlap: OverlapPolicy::default(),
+            backup: None,
+            watch: None,
+        }
+    }
+
+    /// Create a job backing up the state directory.
+    pub fn backup(id: impl Into<String>, schedule: impl Into<String>, backup: BackupJob) -> Self {
+        Self {
+            backup: Some(backup),
+            ..Self::new(id, schedule, "", "")
         }
     }
 
@@ -71,6 +183,69 @@ impl JobDefinition {
         self.enabled = enabled;
         self
     }
+
+    /// Set the catch-up policy.
+    pub fn with_catch_up(mut self, catch_up: CatchUpPolicy) -> Self {
+        self.catch_up = catch_up;
+        self
+    }
+
+    /// Delay each run's start by up to `secs` seconds.
+    pub fn with_jitter(mut self, secs: u64) -> Self {
+        self.jitter_secs = secs;
+        self
+    }
+
+    /// Set the overlap policy.
+    pub fn with_overlap(mut self, overlap: OverlapPolicy) -> Self {
+        self.overlap = overlap;
+        self
+    }
+
+    /// Watch a web page; the agent runs with the changes appended to the
+    /// prompt, or not at all when the prompt is empty.
+    pub fn with_watch(mut self, watch: WatchJob) -> Self {
+        self.watch = Some(watch);
+        self
+    }
+}
+
+/// Outcome of a scheduled occurrence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobRunStatus {
+    /// Submitted to the RunLoop.
+    Submitted,
+    /// Ran to completion in the scheduler (backup jobs, and watch jobs
+    /// without an agent run).
+    Completed,
+    /// Submission failed.
+    Failed,
+    /// Not run, per the catch-up or overlap policy or the concurrency limit.
+    Skipped,
+}
+
+/// One entry of a job's run history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobRun {
+    /// Occurrence of the schedule this run is for.
+    pub scheduled_for: DateTime<Utc>,
+    /// When the scheduler handled the occurrence.
+    pub started_at: DateTime<Utc>,
+    /// Outcome.
+    pub status: JobRunStatus,
+    /// Whether the occurrence was missed during downtime.
+    #[serde(default)]
+    pub catch_up: bool,
+    /// RunLoop task executing the run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<Uuid>,
+    /// Agent session of the run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// Error or note, e.g. the file a backup was written to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
 }
 
 /// Runtime job instance.
@@ -192,4 +367,63 @@ mod tests {
         assert_eq!(job.status, JobStatus::Failed);
         assert_eq!(job.last_error, Some("Something went wrong".to_string()));
     }
+
+    #[test]
+    fn test_job_definition_run_options_default() {
+        let def: JobDefinition = serde_json::from_value(serde_json::json!({
+            "id": "job",
+            "schedule": "0 * * * * *",
+            "agent": "agent",
+            "prompt": "prompt",
+            "description": null,
+            "enabled": true
+        }))
+        .unwrap();
+        assert_eq!(def.jitter_secs, 0);
+        assert_eq!(def.overlap, OverlapPolicy::Skip);
+
+        let def = def.with_jitter(30).with_overlap(OverlapPolicy::KillPrevious);
+        let value = serde_json::to_value(&def).unwrap();
+        assert_eq!(value["jitter_secs"], 30);
+        assert_eq!(value["overlap"], "kill_previous");
+        assert!(value.get("backup").is_none());
+    }
+
+    #[test]
+    fn test_backup_job_definition() {
+        let def: JobDefinition = serde_json::from_value(serde_json::json!({
+            "id": "nightly-backup",
+            "schedule": "0 0 3 * * *",
+            "enabled": true,
+            "backup": {"keep": 7, "exclude": ["sessions"]}
+        }))
+        .unwrap();
+        let backup = def.backup.unwrap();
+        assert_eq!(backup.keep, 7);
+        assert_eq!(backup.exclude, vec!["sessions"]);
+        assert!(backup.dir.is_none());
+        assert!(!backup.encrypt);
+        assert!(def.agent.is_empty());
+    }
+
+    #[test]
+    fn test_watch_job_definition() {
+        let def: JobDefinition = serde_json::from_value(serde_json::json!({
+            "id": "pricing",
+            "schedule": "@every 1h",
+            "agent": "general",
+            "prompt": "Summarize the pricing change",
+            "enabled": true,
+            "watch": {"url": "https://example.com/pricing", "selector": "#plans", "channel": "web"}
+        }))
+        .unwrap();
+        let watch = def.watch.unwrap();
+        assert_eq!(watch.selector.as_deref(), Some("#plans"));
+        assert_eq!(watch.min_changed_lines, 1);
+        assert!(watch.target.is_none());
+
+        let def = JobDefinition::new("j", "@every 1h", "", "").with_watch(WatchJob::new("https://a"));
+        let value = serde_json::to_value(&def).unwrap();
+        assert_eq!(value["watch"], serde_json::json!({"url": "https://a", "min_changed_lines": 1}));
+    }
 }
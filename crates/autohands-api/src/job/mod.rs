@@ -1,16 +1,30 @@
 //! Job scheduling module.
 //!
-//! Provides scheduled task execution via Cron expressions:
-//! - Job definitions with cron schedules
+//! Provides scheduled task execution via Cron expressions, fixed intervals
+//! and one-shot times:
+//! - Job definitions with cron, `@every` and `@at` schedules
 //! - Persistent job store (memory and file-based)
 //! - HTTP API routes for job management
-//! - Scheduler that checks due jobs periodically
+//! - Scheduler that checks due jobs periodically, catching up on runs
+//!   missed during downtime per job policy
+//! - Per-job start jitter and overlap policy, and a global limit on
+//!   concurrently running jobs
+//! - Backup jobs snapshotting the state directory
+//! - Watch jobs running their agent and notifying a channel when a web
+//!   page changed
 
 mod definition;
 pub mod routes;
+mod schedule;
 pub mod scheduler;
 mod store;
+pub mod watch;
 
-pub use definition::{Job, JobDefinition, JobStatus};
-pub use scheduler::JobScheduler;
-pub use store::{FileJobStore, JobStore, MemoryJobStore};
+pub use definition::{
+    BackupJob, CatchUpPolicy, Job, JobDefinition, JobRun, JobRunStatus, JobStatus, OverlapPolicy,
+    WatchJob,
+};
+pub use schedule::{format_interval, parse_interval, JobSchedule, AT_PREFIX, EVERY_PREFIX};
+pub use scheduler::{BackupRunner, JobScheduler, RunTracker};
+pub use store::{FileJobStore, JobStore, MemoryJobStore, MAX_JOB_HISTORY};
+pub use watch::{PageChange, PageCheck, PageFetcher, PageWatcher};
@@ -4,20 +4,21 @@
 //! - POST   /jobs       - Create job
 //! - GET    /jobs       - List jobs
 //! - GET    /jobs/{id}  - Get job
+//! - GET    /jobs/{id}/history - Past runs, newest first
 //! - DELETE /jobs/{id}  - Delete job
 
 use std::sync::Arc;
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
     Json,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tracing::{error, info};
 
-use super::definition::{Job, JobDefinition};
+use super::definition::{Job, JobDefinition, JobRun};
 use crate::runloop_bridge::HybridAppState;
 
 /// Response for listing jobs.
@@ -33,6 +34,21 @@ pub struct JobResponse {
     pub job: Job,
 }
 
+/// Response for a job's run history.
+#[derive(Debug, Serialize)]
+pub struct JobHistoryResponse {
+    pub job_id: String,
+    pub count: usize,
+    pub runs: Vec<JobRun>,
+}
+
+/// Query parameters for job history.
+#[derive(Debug, Deserialize)]
+pub struct JobHistoryQuery {
+    /// Most runs to return.
+    pub limit: Option<usize>,
+}
+
 /// Create a new job.
 ///
 /// POST /jobs
@@ -100,6 +116,57 @@ pub async fn get_job(
     }
 }
 
+/// Get a job's past runs, newest first.
+///
+/// GET /jobs/{id}/history
+pub async fn get_job_history(
+    State(state): State<Arc<HybridAppState>>,
+    Path(id): Path<String>,
+    Query(query): Query<JobHistoryQuery>,
+) -> impl IntoResponse {
+    let job_store = &state.job_store;
+    match job_store.load(&id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": format!("Job '{}' not found", id)})),
+            )
+        }
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )
+        }
+    }
+
+    match job_store.load_history(&id).await {
+        Ok(runs) => {
+            let runs: Vec<JobRun> = runs
+                .into_iter()
+                .rev()
+                .take(query.limit.unwrap_or(usize::MAX))
+                .collect();
+            (
+                StatusCode::OK,
+                Json(serde_json::json!(JobHistoryResponse {
+                    job_id: id,
+                    count: runs.len(),
+                    runs,
+                })),
+            )
+        }
+        Err(e) => {
+            error!("Failed to load history of job '{}': {}", id, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )
+        }
+    }
+}
+
 /// Delete a job.
 ///
 /// DELETE /jobs/{id}
@@ -23,3 +23,21 @@ fn test_job_response_serialization() {
     assert_eq!(json["job"]["definition"]["id"], "test-job");
     assert_eq!(json["job"]["definition"]["schedule"], "0 * * * *");
 }
+
+#[test]
+fn test_job_definition_catch_up_defaults_to_skip() {
+    let def: JobDefinition = serde_json::from_value(serde_json::json!({
+        "id": "job",
+        "schedule": "0 0 9 * * *",
+        "agent": "general",
+        "prompt": "Morning report",
+        "description": null,
+        "enabled": true,
+    }))
+    .unwrap();
+    assert_eq!(def.catch_up, crate::job::CatchUpPolicy::Skip);
+
+    let json = serde_json::to_value(def.with_catch_up(crate::job::CatchUpPolicy::RunAllMissed))
+        .unwrap();
+    assert_eq!(json["catch_up"], "run_all_missed");
+}
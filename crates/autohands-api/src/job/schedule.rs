@@ -0,0 +1,157 @@
+//! Job schedules.
+//!
+//! A job's schedule string is one of:
+//! - a cron expression, e.g. `"0 0 9 * * MON-FRI"`
+//! - `"@every <interval>"`, e.g. `"@every 15m"` or `"@every 1h30m"`, firing
+//!   at a fixed interval like a repeating RunLoop `Timer`
+//! - `"@at <RFC 3339 timestamp>"`, firing once like a one-shot `Timer`
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+/// Prefix of interval schedules.
+pub const EVERY_PREFIX: &str = "@every ";
+
+/// Prefix of one-shot schedules.
+pub const AT_PREFIX: &str = "@at ";
+
+/// Parsed job schedule.
+#[derive(Debug, Clone)]
+pub enum JobSchedule {
+    /// Cron expression.
+    Cron(Box<cron::Schedule>),
+    /// Fixed interval.
+    Every(Duration),
+    /// Single point in time.
+    At(DateTime<Utc>),
+}
+
+impl JobSchedule {
+    /// Whether the schedule fires only once.
+    pub fn is_one_shot(&self) -> bool {
+        matches!(self, JobSchedule::At(_))
+    }
+
+    /// Occurrences strictly after `t`, oldest first.
+    ///
+    /// Interval occurrences are counted from `t`.
+    pub fn after<'a>(
+        &'a self,
+        t: &DateTime<Utc>,
+    ) -> Box<dyn Iterator<Item = DateTime<Utc>> + Send + 'a> {
+        match self {
+            JobSchedule::Cron(schedule) => Box::new(schedule.after(t)),
+            JobSchedule::Every(interval) => {
+                let step = chrono::Duration::from_std(*interval).unwrap_or(chrono::Duration::MAX);
+                Box::new(std::iter::successors(t.checked_add_signed(step), move |prev| {
+                    prev.checked_add_signed(step)
+                }))
+            }
+            JobSchedule::At(at) => Box::new((*at > *t).then_some(*at).into_iter()),
+        }
+    }
+
+    /// First occurrence after `now` in the series continuing from `occurrence`.
+    ///
+    /// Interval schedules keep their phase instead of restarting from `now`.
+    pub fn next_after(
+        &self,
+        occurrence: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        match self {
+            JobSchedule::Every(interval) if occurrence <= now => {
+                let step = i64::try_from(interval.as_millis()).ok()?.max(1);
+                let elapsed = (now - occurrence).num_milliseconds();
+                let offset = (elapsed / step + 1).checked_mul(step)?;
+                occurrence.checked_add_signed(chrono::Duration::milliseconds(offset))
+            }
+            _ => self.after(&now).next(),
+        }
+    }
+}
+
+impl FromStr for JobSchedule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(interval) = s.strip_prefix(EVERY_PREFIX) {
+            return parse_interval(interval).map(JobSchedule::Every);
+        }
+        if let Some(at) = s.strip_prefix(AT_PREFIX) {
+            return DateTime::parse_from_rfc3339(at.trim())
+                .map(|t| JobSchedule::At(t.with_timezone(&Utc)))
+                .map_err(|e| format!("Invalid timestamp '{}': {}", at.trim(), e));
+        }
+        cron::Schedule::from_str(s)
+            .map(|schedule| JobSchedule::Cron(Box::new(schedule)))
+            .map_err(|e| format!("Invalid cron expression '{}': {}", s, e))
+    }
+}
+
+impl fmt::Display for JobSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JobSchedule::Cron(schedule) => write!(f, "{}", schedule),
+            JobSchedule::Every(interval) => write!(f, "{}{}", EVERY_PREFIX, format_interval(*interval)),
+            JobSchedule::At(at) => write!(f, "{}{}", AT_PREFIX, at.to_rfc3339()),
+        }
+    }
+}
+
+/// Parse an interval such as `"90s"`, `"15m"`, `"1h30m"` or `"2d"`.
+///
+/// Units are `s`, `m`, `h` and `d`; the interval must be positive.
+pub fn parse_interval(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let invalid = || format!("Invalid interval '{}', expected e.g. '15m' or '1h30m'", s);
+    let mut total: u64 = 0;
+    let mut digits = String::new();
+    for c in s.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let unit = match c {
+            's' => 1,
+            'm' => 60,
+            'h' => 3600,
+            'd' => 86400,
+            _ => return Err(invalid()),
+        };
+        let value: u64 = digits.parse().map_err(|_| invalid())?;
+        total = value
+            .checked_mul(unit)
+            .and_then(|secs| total.checked_add(secs))
+            .ok_or_else(invalid)?;
+        digits.clear();
+    }
+    if !digits.is_empty() || total == 0 {
+        return Err(invalid());
+    }
+    Ok(Duration::from_secs(total))
+}
+
+/// Format an interval in the form accepted by [`parse_interval`].
+pub fn format_interval(interval: Duration) -> String {
+    let mut secs = interval.as_secs();
+    if secs == 0 {
+        return "0s".to_string();
+    }
+    let mut out = String::new();
+    for (unit, size) in [("d", 86400), ("h", 3600), ("m", 60), ("s", 1)] {
+        if secs >= size {
+            out.push_str(&format!("{}{}", secs / size, unit));
+            secs %= size;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+#[path = "schedule_tests.rs"]
+mod tests;
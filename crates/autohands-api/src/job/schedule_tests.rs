@@ -0,0 +1,70 @@
+use super::*;
+use chrono::TimeZone;
+
+fn at(h: u32, m: u32, s: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 1, 1, h, m, s).unwrap()
+}
+
+#[test]
+fn test_parse_interval() {
+    assert_eq!(parse_interval("90s").unwrap(), Duration::from_secs(90));
+    assert_eq!(parse_interval("15m").unwrap(), Duration::from_secs(900));
+    assert_eq!(parse_interval("1h30m").unwrap(), Duration::from_secs(5400));
+    assert_eq!(parse_interval("2d").unwrap(), Duration::from_secs(172_800));
+    for bad in ["", "15", "m", "0m", "15x", "1.5h", "-5m"] {
+        assert!(parse_interval(bad).is_err(), "{:?} should be rejected", bad);
+    }
+}
+
+#[test]
+fn test_format_interval_round_trips() {
+    for s in ["45s", "15m", "1h30m", "1d2h3m4s"] {
+        assert_eq!(format_interval(parse_interval(s).unwrap()), s);
+    }
+}
+
+#[test]
+fn test_parse_schedules() {
+    assert!(matches!(
+        "0 */5 * * * *".parse::<JobSchedule>().unwrap(),
+        JobSchedule::Cron(_)
+    ));
+    assert!(matches!(
+        "@every 15m".parse::<JobSchedule>().unwrap(),
+        JobSchedule::Every(d) if d == Duration::from_secs(900)
+    ));
+    let once: JobSchedule = "@at 2026-01-01T12:00:00+02:00".parse().unwrap();
+    assert!(once.is_one_shot());
+    assert!(matches!(once, JobSchedule::At(t) if t == at(10, 0, 0)));
+
+    assert!("@every soon".parse::<JobSchedule>().is_err());
+    assert!("@at tomorrow".parse::<JobSchedule>().is_err());
+    assert!("not a cron".parse::<JobSchedule>().is_err());
+}
+
+#[test]
+fn test_display() {
+    let every: JobSchedule = "@every 90m".parse().unwrap();
+    assert_eq!(every.to_string(), "@every 1h30m");
+    let once: JobSchedule = "@at 2026-01-01T10:00:00Z".parse().unwrap();
+    assert_eq!(once.to_string(), "@at 2026-01-01T10:00:00+00:00");
+}
+
+#[test]
+fn test_interval_occurrences() {
+    let every = JobSchedule::Every(Duration::from_secs(600));
+    let next: Vec<_> = every.after(&at(10, 0, 0)).take(2).collect();
+    assert_eq!(next, vec![at(10, 10, 0), at(10, 20, 0)]);
+
+    // Keeps the 10-minute phase after a late check
+    assert_eq!(every.next_after(at(10, 0, 0), at(10, 25, 0)), Some(at(10, 30, 0)));
+    assert_eq!(every.next_after(at(10, 0, 0), at(10, 0, 0)), Some(at(10, 10, 0)));
+}
+
+#[test]
+fn test_one_shot_occurrences() {
+    let once = JobSchedule::At(at(10, 0, 0));
+    assert_eq!(once.after(&at(9, 0, 0)).collect::<Vec<_>>(), vec![at(10, 0, 0)]);
+    assert_eq!(once.after(&at(10, 0, 0)).next(), None);
+    assert_eq!(once.next_after(at(10, 0, 0), at(10, 5, 0)), None);
+}
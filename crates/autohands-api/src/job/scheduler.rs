@@ -1,22 +1,102 @@
 //! Job scheduler that checks due jobs periodically and submits them via RunLoop.
 
+use std::collections::{HashMap, VecDeque};
+use std::ops::ControlFlow;
 use std::str::FromStr;
 use std::sync::Arc;
 
-use chrono::Utc;
-use cron::Schedule;
+use autohands_core::registry::ChannelRegistry;
+use autohands_protocols::channel::{OutboundMessage, ReplyAddress};
+use autohands_runloop::{Task, TaskSource};
+use autohands_runtime::AgentRuntime;
+use chrono::{DateTime, Utc};
+use tokio::sync::Mutex;
 use tokio::time::{self, Duration};
 use tracing::{debug, error, info, warn};
+use uuid::Uuid;
 
-use super::definition::JobStatus;
+use super::definition::{
+    BackupJob, CatchUpPolicy, Job, JobRun, JobRunStatus, JobStatus, OverlapPolicy, WatchJob,
+};
+use super::schedule::JobSchedule;
 use super::store::JobStore;
+use super::watch::{PageCheck, PageChange, PageWatcher};
 use crate::runloop_bridge::RunLoopState;
 
+/// Most catch-up runs submitted for one job in one check.
+const MAX_CATCH_UP_RUNS: usize = 100;
+
+/// Most due occurrences considered for one job in one check.
+const MAX_DUE_SCAN: usize = 10_000;
+
+/// Shortest delay after which an occurrence counts as missed.
+const MIN_GRACE: Duration = Duration::from_secs(60);
+
+/// How long a submitted run counts as in progress before its agent starts.
+const START_GRACE: chrono::Duration = chrono::Duration::seconds(30);
+
+/// Most runs waiting behind a job's in-progress run.
+const MAX_QUEUED_RUNS: usize = 10;
+
+/// Observes the agent sessions job runs execute in.
+pub trait RunTracker: Send + Sync {
+    /// Whether the session is executing.
+    fn is_running(&self, session_id: &str) -> bool;
+
+    /// Abort the session, returning whether it was running.
+    fn abort(&self, session_id: &str) -> bool;
+}
+
+impl RunTracker for AgentRuntime {
+    fn is_running(&self, session_id: &str) -> bool {
+        AgentRuntime::is_running(self, session_id)
+    }
+
+    fn abort(&self, session_id: &str) -> bool {
+        AgentRuntime::abort(self, session_id)
+    }
+}
+
+/// Takes the backups of backup jobs.
+#[async_trait::async_trait]
+pub trait BackupRunner: Send + Sync {
+    /// Back up the state directory for a job, returning a note on the
+    /// backup taken (e.g. its file).
+    async fn backup(&self, job_id: &str, backup: &BackupJob) -> Result<String, String>;
+}
+
+/// A submitted run that may still be in progress.
+#[derive(Debug, Clone)]
+struct ActiveRun {
+    session_id: String,
+    /// When the RunLoop releases the task, after jitter.
+    starts_at: DateTime<Utc>,
+}
+
+/// Runs in progress and waiting, by job ID.
+#[derive(Debug, Default)]
+struct RunState {
+    active: HashMap<String, Vec<ActiveRun>>,
+    queued: HashMap<String, VecDeque<(DateTime<Utc>, bool)>>,
+}
+
+impl RunState {
+    fn active_count(&self) -> usize {
+        self.active.values().map(Vec::len).sum()
+    }
+}
+
 /// Job scheduler that periodically checks for due jobs and submits them.
 pub struct JobScheduler {
     job_store: Arc<dyn JobStore>,
     runloop: Arc<RunLoopState>,
     check_interval: Duration,
+    tracker: Option<Arc<dyn RunTracker>>,
+    backup_runner: Option<Arc<dyn BackupRunner>>,
+    page_watcher: Option<Arc<PageWatcher>>,
+    channels: Option<Arc<ChannelRegistry>>,
+    max_concurrent: usize,
+    runs: Mutex<RunState>,
 }
 
 impl JobScheduler {
@@ -29,6 +109,12 @@ impl JobScheduler {
             job_store,
             runloop,
             check_interval: Duration::from_secs(60),
+            tracker: None,
+            backup_runner: None,
+            page_watcher: None,
+            channels: None,
+            max_concurrent: 0,
+            runs: Mutex::new(RunState::default()),
         }
     }
 
@@ -38,11 +124,45 @@ impl JobScheduler {
         self
     }
 
+    /// Track runs through their agent sessions.
+    ///
+    /// Without a tracker, a run counts as in progress only until its agent
+    /// is due to start, so overlap policies and the concurrency limit only
+    /// see runs still waiting out their jitter.
+    pub fn with_run_tracker(mut self, tracker: Arc<dyn RunTracker>) -> Self {
+        self.tracker = Some(tracker);
+        self
+    }
+
+    /// Take the backups of backup jobs, which fail without a runner.
+    pub fn with_backup_runner(mut self, runner: Arc<dyn BackupRunner>) -> Self {
+        self.backup_runner = Some(runner);
+        self
+    }
+
+    /// Check the pages of watch jobs, which fail without a watcher.
+    pub fn with_page_watcher(mut self, watcher: Arc<PageWatcher>) -> Self {
+        self.page_watcher = Some(watcher);
+        self
+    }
+
+    /// Channels watch jobs report page changes to.
+    pub fn with_channels(mut self, channels: Arc<ChannelRegistry>) -> Self {
+        self.channels = Some(channels);
+        self
+    }
+
+    /// Limit the runs in progress across all jobs (0 = unlimited).
+    pub fn with_max_concurrent(mut self, max: usize) -> Self {
+        self.max_concurrent = max;
+        self
+    }
+
     /// Start the scheduler loop. Runs until the provided cancellation token fires.
     pub async fn run(self: Arc<Self>, cancel: tokio::sync::watch::Receiver<bool>) {
         info!(
-            "Job scheduler started (check interval: {:?})",
-            self.check_interval
+            "Job scheduler started (check interval: {:?}, max concurrent: {})",
+            self.check_interval, self.max_concurrent
         );
 
         let mut interval = time::interval(self.check_interval);
@@ -64,73 +184,453 @@ impl JobScheduler {
     }
 
     /// Check for due jobs and submit them.
+    ///
+    /// Each job's next run time is persisted, so occurrences that passed
+    /// while the scheduler was down are found on the next check and handled
+    /// according to the job's [`CatchUpPolicy`]. Runs due while an earlier
+    /// run is still in progress are handled per the job's [`OverlapPolicy`].
     async fn check_due_jobs(&self) -> Result<(), crate::error::InterfaceError> {
         let jobs = self.job_store.load_all().await?;
         let now = Utc::now();
 
+        let mut runs = self.runs.lock().await;
+        self.prune(&mut runs, now);
+        runs.queued.retain(|id, _| {
+            jobs.iter().any(|job| {
+                job.definition.id == *id
+                    && job.status == JobStatus::Enabled
+                    && job.definition.enabled
+            })
+        });
+
         for mut job in jobs {
             if job.status != JobStatus::Enabled || !job.definition.enabled {
                 continue;
             }
 
-            // Parse cron schedule to determine if the job is due
-            let schedule = match Schedule::from_str(&job.definition.schedule) {
+            // Parse the schedule to determine if the job is due
+            let schedule = match JobSchedule::from_str(&job.definition.schedule) {
                 Ok(s) => s,
                 Err(e) => {
-                    warn!(
-                        "Invalid cron expression for job '{}': {} - {}",
-                        job.definition.id, job.definition.schedule, e
-                    );
+                    warn!("Invalid schedule for job '{}': {}", job.definition.id, e);
                     continue;
                 }
             };
 
-            // Find the next scheduled time after last_run (or epoch if never run)
-            let after = job.last_run.unwrap_or_else(|| {
-                chrono::DateTime::from_timestamp(0, 0).unwrap_or_else(|| Utc::now())
-            });
+            // Jobs saved before next-run tracking resume from their last run;
+            // new jobs wait for their first scheduled time
+            let next = job
+                .next_run
+                .or_else(|| schedule.after(&job.last_run.unwrap_or(now)).next());
 
-            let next = schedule.after(&after).next();
-            if let Some(next_time) = next {
-                if next_time <= now {
-                    debug!("Job '{}' is due, submitting", job.definition.id);
-                    self.submit_job(&mut job).await;
+            let mut due_runs = Vec::new();
+            let mut changed = false;
+            match next {
+                Some(next) if next <= now => {
+                    due_runs = self.plan_due(&job, &schedule, next, now).await;
+                    job.next_run = schedule.next_after(next, now);
+                    changed = true;
                 }
+                Some(next) if job.next_run != Some(next) => {
+                    job.next_run = Some(next);
+                    changed = true;
+                }
+                _ => {}
+            }
+
+            let started = self.dispatch(&mut runs, &mut job, due_runs, now).await;
+            // A one-shot job is done once its run is no longer pending
+            if schedule.is_one_shot()
+                && job.next_run.is_none()
+                && !runs.queued.contains_key(&job.definition.id)
+            {
+                info!("One-shot job '{}' finished", job.definition.id);
+                job.status = JobStatus::Completed;
+                changed = true;
+            }
+            if started || changed {
+                self.save(&job).await;
             }
         }
 
         Ok(())
     }
 
-    /// Submit a job for execution via RunLoop.
-    async fn submit_job(&self, job: &mut super::definition::Job) {
+    /// Plan the occurrences due since `next`, recording the skipped ones.
+    async fn plan_due(
+        &self,
+        job: &Job,
+        schedule: &JobSchedule,
+        next: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Vec<(DateTime<Utc>, bool)> {
+        let due: Vec<DateTime<Utc>> = std::iter::once(next)
+            .chain(schedule.after(&next))
+            .take_while(|t| *t <= now)
+            .take(MAX_DUE_SCAN)
+            .collect();
+        let grace = chrono::Duration::from_std(self.check_interval.max(MIN_GRACE))
+            .unwrap_or_else(|_| chrono::Duration::minutes(1));
+        let plan = plan_runs(job.definition.catch_up, &due, now - grace);
+
+        if !plan.skipped.is_empty() {
+            info!(
+                "Job '{}' skipping {} missed run(s) ({:?} policy)",
+                job.definition.id,
+                plan.skipped.len(),
+                job.definition.catch_up
+            );
+            let run = JobRun {
+                scheduled_for: plan.skipped[plan.skipped.len() - 1],
+                started_at: now,
+                status: JobRunStatus::Skipped,
+                catch_up: true,
+                task_id: None,
+                session_id: None,
+                message: Some(format!(
+                    "Skipped {} missed run(s) since {}",
+                    plan.skipped.len(),
+                    plan.skipped[0]
+                )),
+            };
+            self.record(job, &run).await;
+        }
+        plan.runs
+    }
+
+    /// Start, queue or skip a job's due runs and any runs queued earlier.
+    ///
+    /// The overlap policy applies to runs started by earlier checks; the runs
+    /// of one check are started together. Returns whether any run started.
+    async fn dispatch(
+        &self,
+        runs: &mut RunState,
+        job: &mut Job,
+        due: Vec<(DateTime<Utc>, bool)>,
+        now: DateTime<Utc>,
+    ) -> bool {
+        let id = job.definition.id.clone();
+        let mut pending: Vec<_> = runs.queued.remove(&id).map(Vec::from).unwrap_or_default();
+        pending.extend(due);
+        if pending.is_empty() {
+            return false;
+        }
+
+        let overlap = job.definition.overlap;
+        let mut replaced = 0;
+        if runs.active.contains_key(&id) {
+            match overlap {
+                OverlapPolicy::Skip => {
+                    self.skip(job, &pending, now, "Previous run still in progress").await;
+                    return false;
+                }
+                OverlapPolicy::Queue => {
+                    self.enqueue(runs, job, pending, now).await;
+                    return false;
+                }
+                OverlapPolicy::KillPrevious => replaced = self.abort_runs(runs, &id),
+            }
+        }
+
+        let mut started = false;
+        let mut pending = pending.into_iter();
+        while let Some((scheduled_for, catch_up)) = pending.next() {
+            if self.max_concurrent > 0 && runs.active_count() >= self.max_concurrent {
+                let mut rest = vec![(scheduled_for, catch_up)];
+                rest.extend(pending);
+                if overlap == OverlapPolicy::Queue {
+                    self.enqueue(runs, job, rest, now).await;
+                } else {
+                    let reason = format!("Concurrent job limit ({}) reached", self.max_concurrent);
+                    self.skip(job, &rest, now, &reason).await;
+                }
+                break;
+            }
+
+            debug!("Job '{}' is due, submitting", id);
+            let (mut run, starts_at) = self.submit_job(job, scheduled_for, catch_up).await;
+            if let (JobRunStatus::Submitted, Some(session_id)) = (run.status, &run.session_id) {
+                runs.active.entry(id.clone()).or_default().push(ActiveRun {
+                    session_id: session_id.clone(),
+                    starts_at,
+                });
+            }
+            if replaced > 0 {
+                run.message = Some(format!("Replaced {} run(s) still in progress", replaced));
+                replaced = 0;
+            }
+            self.record(job, &run).await;
+            started = true;
+        }
+        started
+    }
+
+    /// Submit a job for execution via RunLoop, returning the run and when
+    /// its agent is due to start.
+    ///
+    /// Backup jobs run here instead, without jitter. Watch jobs check their
+    /// page here and only submit their agent when it changed.
+    async fn submit_job(
+        &self,
+        job: &mut Job,
+        scheduled_for: DateTime<Utc>,
+        catch_up: bool,
+    ) -> (JobRun, DateTime<Utc>) {
         job.start_run();
+        if let Some(backup) = job.definition.backup.clone() {
+            let run = self.run_backup(job, &backup, scheduled_for, catch_up).await;
+            let started_at = run.started_at;
+            return (run, started_at);
+        }
+        let mut prompt = job.definition.prompt.clone();
+        let mut note = None;
+        if let Some(watch) = job.definition.watch.clone() {
+            match self.check_watch(job, &watch, scheduled_for, catch_up).await {
+                ControlFlow::Continue(change) => {
+                    prompt = format!(
+                        "{}\n\nThe watched page {} changed since the last check:\n\n{}",
+                        prompt,
+                        change.url,
+                        change.diff()
+                    );
+                    note = Some(change.summary());
+                }
+                ControlFlow::Break(run) => {
+                    let started_at = run.started_at;
+                    return (run, started_at);
+                }
+            }
+        }
 
+        let session_id = format!("job:{}:{}", job.definition.id, Uuid::new_v4());
         let payload = serde_json::json!({
-            "prompt": job.definition.prompt,
-            "agent_id": job.definition.agent,
+            "prompt": prompt,
+            "agent": job.definition.agent,
             "job_id": job.definition.id,
+            "session_id": session_id,
             "source": "scheduler",
+            "scheduled_for": scheduled_for,
+            "catch_up": catch_up,
         });
 
-        match self
-            .runloop
-            .submit_task("agent:execute", payload, None)
-            .await
-        {
-            Ok(()) => {
+        let mut run = JobRun {
+            scheduled_for,
+            started_at: Utc::now(),
+            status: JobRunStatus::Submitted,
+            catch_up,
+            task_id: None,
+            session_id: Some(session_id),
+            message: note,
+        };
+        let starts_at = run.started_at + jitter(job.definition.jitter_secs);
+        let mut task = Task::new("agent:execute", payload).with_source(TaskSource::Scheduler);
+        if starts_at > run.started_at {
+            task = task.with_scheduled_at(starts_at);
+        }
+
+        match self.runloop.submit(task).await {
+            Ok(task_id) => {
                 info!("Job '{}' submitted to RunLoop", job.definition.id);
                 job.complete_run();
                 job.re_enable();
+                run.task_id = Some(task_id);
             }
             Err(e) => {
                 error!("Failed to submit job '{}': {}", job.definition.id, e);
                 job.fail_run(e.to_string());
                 job.re_enable();
+                run.status = JobRunStatus::Failed;
+                run.message = Some(e.to_string());
             }
         }
+        (run, starts_at)
+    }
+
+    /// Take a backup job's backup.
+    async fn run_backup(
+        &self,
+        job: &mut Job,
+        backup: &BackupJob,
+        scheduled_for: DateTime<Utc>,
+        catch_up: bool,
+    ) -> JobRun {
+        let started_at = Utc::now();
+        let result = match &self.backup_runner {
+            Some(runner) => runner.backup(&job.definition.id, backup).await,
+            None => Err("No backup runner configured".to_string()),
+        };
+        let (status, message) = match result {
+            Ok(note) => {
+                info!("Backup job '{}' finished: {}", job.definition.id, note);
+                job.complete_run();
+                (JobRunStatus::Completed, note)
+            }
+            Err(e) => {
+                error!("Backup job '{}' failed: {}", job.definition.id, e);
+                job.fail_run(e.clone());
+                (JobRunStatus::Failed, e)
+            }
+        };
+        job.re_enable();
+        JobRun {
+            scheduled_for,
+            started_at,
+            status,
+            catch_up,
+            task_id: None,
+            session_id: None,
+            message: Some(message),
+        }
+    }
+
+    /// Check a watch job's page and report a change to its channel.
+    ///
+    /// Continues with the change when the job's agent should run on it;
+    /// otherwise breaks with the finished run.
+    async fn check_watch(
+        &self,
+        job: &mut Job,
+        watch: &WatchJob,
+        scheduled_for: DateTime<Utc>,
+        catch_up: bool,
+    ) -> ControlFlow<JobRun, PageChange> {
+        let started_at = Utc::now();
+        let result = match &self.page_watcher {
+            Some(watcher) => watcher.check(&job.definition.id, watch).await,
+            None => Err("No page watcher configured".to_string()),
+        };
+        let (status, message) = match result {
+            Ok(PageCheck::Changed(change)) => {
+                info!("Watch job '{}': {}", job.definition.id, change.summary());
+                self.notify_change(&job.definition.id, watch, &change).await;
+                if !job.definition.prompt.is_empty() {
+                    return ControlFlow::Continue(change);
+                }
+                job.complete_run();
+                (JobRunStatus::Completed, change.summary())
+            }
+            Ok(check) => {
+                job.complete_run();
+                let message = if check == PageCheck::First {
+                    "Took the first snapshot"
+                } else {
+                    "No change"
+                };
+                (JobRunStatus::Completed, message.to_string())
+            }
+            Err(e) => {
+                warn!("Watch job '{}' failed: {}", job.definition.id, e);
+                job.fail_run(e.clone());
+                (JobRunStatus::Failed, e)
+            }
+        };
+        job.re_enable();
+        ControlFlow::Break(JobRun {
+            scheduled_for,
+            started_at,
+            status,
+            catch_up,
+            task_id: None,
+            session_id: None,
+            message: Some(message),
+        })
+    }
+
+    /// Send a page change to the watch job's channel, if it has one.
+    async fn notify_change(&self, job_id: &str, watch: &WatchJob, change: &PageChange) {
+        let (Some(channel), Some(target)) = (&watch.channel, &watch.target) else {
+            return;
+        };
+        let Some(channels) = &self.channels else {
+            warn!("Watch job '{}' has a channel but no channels are available", job_id);
+            return;
+        };
+        let text = format!("{}\n\n{}", change.summary(), change.diff());
+        let address = ReplyAddress::new(channel, target);
+        if let Err(e) = channels.send(&address, OutboundMessage::text(text)).await {
+            warn!("Failed to notify {} of watch job '{}': {}", channel, job_id, e);
+        }
+    }
+
+    /// Queue runs behind the job's in-progress run, dropping the oldest
+    /// beyond [`MAX_QUEUED_RUNS`].
+    async fn enqueue(
+        &self,
+        runs: &mut RunState,
+        job: &Job,
+        pending: Vec<(DateTime<Utc>, bool)>,
+        now: DateTime<Utc>,
+    ) {
+        let queue = runs.queued.entry(job.definition.id.clone()).or_default();
+        queue.extend(pending);
+        let overflow = queue.len().saturating_sub(MAX_QUEUED_RUNS);
+        let dropped: Vec<_> = queue.drain(..overflow).collect();
+        debug!("Job '{}' has {} queued run(s)", job.definition.id, queue.len());
+        if !dropped.is_empty() {
+            self.skip(job, &dropped, now, "Run queue full").await;
+        }
+    }
+
+    /// Abort a job's in-progress runs, returning how many there were.
+    fn abort_runs(&self, runs: &mut RunState, id: &str) -> usize {
+        let active = runs.active.remove(id).unwrap_or_default();
+        for run in &active {
+            let aborted = self
+                .tracker
+                .as_ref()
+                .is_some_and(|tracker| tracker.abort(&run.session_id));
+            info!(
+                "Job '{}' replacing run in session {} (aborted: {})",
+                id, run.session_id, aborted
+            );
+        }
+        active.len()
+    }
 
-        // Persist updated status
+    /// Forget runs that are no longer in progress.
+    fn prune(&self, runs: &mut RunState, now: DateTime<Utc>) {
+        for active in runs.active.values_mut() {
+            active.retain(|run| {
+                now < run.starts_at + START_GRACE
+                    || self
+                        .tracker
+                        .as_ref()
+                        .is_some_and(|tracker| tracker.is_running(&run.session_id))
+            });
+        }
+        runs.active.retain(|_, active| !active.is_empty());
+    }
+
+    /// Record runs that are not started.
+    async fn skip(
+        &self,
+        job: &Job,
+        skipped: &[(DateTime<Utc>, bool)],
+        now: DateTime<Utc>,
+        reason: &str,
+    ) {
+        let Some(&(scheduled_for, _)) = skipped.last() else {
+            return;
+        };
+        info!("Job '{}' skipping {} run(s): {}", job.definition.id, skipped.len(), reason);
+        let run = JobRun {
+            scheduled_for,
+            started_at: now,
+            status: JobRunStatus::Skipped,
+            catch_up: skipped.iter().all(|(_, catch_up)| *catch_up),
+            task_id: None,
+            session_id: None,
+            message: Some(if skipped.len() == 1 {
+                reason.to_string()
+            } else {
+                format!("{} ({} runs)", reason, skipped.len())
+            }),
+        };
+        self.record(job, &run).await;
+    }
+
+    /// Persist updated status.
+    async fn save(&self, job: &Job) {
         if let Err(e) = self.job_store.update_status(job).await {
             error!(
                 "Failed to update job status for '{}': {}",
@@ -138,6 +638,67 @@ impl JobScheduler {
             );
         }
     }
+
+    async fn record(&self, job: &Job, run: &JobRun) {
+        if let Err(e) = self.job_store.record_run(&job.definition.id, run).await {
+            error!("Failed to record run of job '{}': {}", job.definition.id, e);
+        }
+    }
+}
+
+/// Random start delay of up to `max_secs` seconds.
+fn jitter(max_secs: u64) -> chrono::Duration {
+    if max_secs == 0 {
+        return chrono::Duration::zero();
+    }
+    let secs = Uuid::new_v4().as_u128() % (u128::from(max_secs) + 1);
+    chrono::Duration::seconds(secs as i64)
+}
+
+/// Occurrences to run and to skip.
+#[derive(Debug, Default, PartialEq)]
+struct RunPlan {
+    /// Occurrences to submit, with whether each is a catch-up run.
+    runs: Vec<(DateTime<Utc>, bool)>,
+    /// Missed occurrences that are not run.
+    skipped: Vec<DateTime<Utc>>,
+}
+
+/// Decide which due occurrences (oldest first) to run.
+///
+/// Occurrences at or before `missed_before` were missed during downtime; later
+/// ones are on time and the latest of them always runs.
+fn plan_runs(
+    policy: CatchUpPolicy,
+    due: &[DateTime<Utc>],
+    missed_before: DateTime<Utc>,
+) -> RunPlan {
+    let split = due.partition_point(|t| *t <= missed_before);
+    let (missed, on_time) = due.split_at(split);
+    let mut plan = RunPlan::default();
+
+    let mut missed = missed.to_vec();
+    match policy {
+        CatchUpPolicy::Skip => {}
+        CatchUpPolicy::RunOnce => {
+            // An on-time run already makes up for the missed ones
+            if on_time.is_empty() {
+                if let Some(latest) = missed.pop() {
+                    plan.runs.push((latest, true));
+                }
+            }
+        }
+        CatchUpPolicy::RunAllMissed => {
+            // Beyond the cap, the oldest occurrences are dropped
+            let skip = missed.len().saturating_sub(MAX_CATCH_UP_RUNS);
+            plan.runs.extend(missed.drain(skip..).map(|t| (t, true)));
+        }
+    }
+    plan.skipped = missed;
+    if let Some(latest) = on_time.last() {
+        plan.runs.push((*latest, false));
+    }
+    plan
 }
 
 #[cfg(test)]
@@ -67,3 +67,457 @@ async fn test_scheduler_shutdown() {
         .expect("Scheduler should shut down promptly")
         .expect("Scheduler task should not panic");
 }
+
+fn minutes_ago(now: DateTime<Utc>, minutes: &[i64]) -> Vec<DateTime<Utc>> {
+    minutes.iter().map(|m| now - chrono::Duration::minutes(*m)).collect()
+}
+
+#[test]
+fn test_plan_runs_policies() {
+    let now = Utc::now();
+    let missed_before = now - chrono::Duration::minutes(1);
+    // Three occurrences missed during downtime, one on time
+    let due = minutes_ago(now, &[30, 20, 10, 0]);
+
+    let plan = plan_runs(CatchUpPolicy::Skip, &due, missed_before);
+    assert_eq!(plan.runs, vec![(due[3], false)]);
+    assert_eq!(plan.skipped, due[..3].to_vec());
+
+    // An on-time run covers the missed ones
+    let plan = plan_runs(CatchUpPolicy::RunOnce, &due, missed_before);
+    assert_eq!(plan.runs, vec![(due[3], false)]);
+
+    let plan = plan_runs(CatchUpPolicy::RunOnce, &due[..3], missed_before);
+    assert_eq!(plan.runs, vec![(due[2], true)]);
+    assert_eq!(plan.skipped, due[..2].to_vec());
+
+    let plan = plan_runs(CatchUpPolicy::RunAllMissed, &due, missed_before);
+    assert_eq!(
+        plan.runs,
+        vec![(due[0], true), (due[1], true), (due[2], true), (due[3], false)]
+    );
+    assert!(plan.skipped.is_empty());
+}
+
+#[test]
+fn test_plan_runs_caps_catch_up() {
+    let now = Utc::now();
+    let minutes: Vec<i64> = (1..=MAX_CATCH_UP_RUNS as i64 + 5).rev().map(|m| m + 1).collect();
+    let due = minutes_ago(now, &minutes);
+    let plan = plan_runs(CatchUpPolicy::RunAllMissed, &due, now);
+    assert_eq!(plan.runs.len(), MAX_CATCH_UP_RUNS);
+    // The oldest occurrences are the ones dropped
+    assert_eq!(plan.skipped, due[..5].to_vec());
+}
+
+async fn scheduler_with(job: Job) -> (Arc<JobScheduler>, Arc<MemoryJobStore>) {
+    let store = Arc::new(MemoryJobStore::new());
+    store.save(&job).await.unwrap();
+    let run_loop = Arc::new(RunLoop::new(RunLoopConfig::default()));
+    let runloop = Arc::new(RunLoopState::from_runloop(run_loop));
+    let job_store: Arc<dyn JobStore> = store.clone();
+    (Arc::new(JobScheduler::new(job_store, runloop)), store)
+}
+
+#[tokio::test]
+async fn test_new_job_waits_for_first_occurrence() {
+    let def = JobDefinition::new("job", "0 * * * * *", "agent", "prompt");
+    let (scheduler, store) = scheduler_with(Job::new(def)).await;
+
+    scheduler.check_due_jobs().await.unwrap();
+
+    let job = store.load("job").await.unwrap().unwrap();
+    assert_eq!(job.run_count, 0);
+    assert!(job.next_run.unwrap() > Utc::now());
+    assert!(store.load_history("job").await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_catch_up_after_downtime() {
+    // Every minute, last scheduled for ten minutes ago
+    let def = JobDefinition::new("job", "0 * * * * *", "agent", "prompt")
+        .with_catch_up(CatchUpPolicy::RunAllMissed);
+    let mut job = Job::new(def);
+    job.next_run = Some(Utc::now() - chrono::Duration::minutes(10));
+    let (scheduler, store) = scheduler_with(job).await;
+
+    scheduler.check_due_jobs().await.unwrap();
+
+    let job = store.load("job").await.unwrap().unwrap();
+    let history = store.load_history("job").await.unwrap();
+    assert!(job.run_count >= 10);
+    assert_eq!(history.len() as u64, job.run_count);
+    assert!(history.iter().all(|r| r.status == JobRunStatus::Submitted && r.task_id.is_some()));
+    assert!(history[0].catch_up);
+    assert!(job.next_run.unwrap() > Utc::now());
+}
+
+#[tokio::test]
+async fn test_skip_records_missed_runs() {
+    let def = JobDefinition::new("job", "0 * * * * *", "agent", "prompt");
+    let mut job = Job::new(def);
+    job.next_run = Some(Utc::now() - chrono::Duration::minutes(10));
+    let (scheduler, store) = scheduler_with(job).await;
+
+    scheduler.check_due_jobs().await.unwrap();
+
+    let history = store.load_history("job").await.unwrap();
+    let skipped: Vec<_> = history
+        .iter()
+        .filter(|r| r.status == JobRunStatus::Skipped)
+        .collect();
+    assert_eq!(skipped.len(), 1);
+    assert!(skipped[0].message.as_deref().unwrap().starts_with("Skipped"));
+    // At most the on-time occurrence ran
+    let job = store.load("job").await.unwrap().unwrap();
+    assert!(job.run_count <= 1);
+}
+
+#[tokio::test]
+async fn test_interval_job_keeps_phase() {
+    let def = JobDefinition::new("job", "@every 10m", "agent", "prompt");
+    let mut job = Job::new(def);
+    let due = Utc::now() - chrono::Duration::seconds(30);
+    job.next_run = Some(due);
+    let (scheduler, store) = scheduler_with(job).await;
+
+    scheduler.check_due_jobs().await.unwrap();
+
+    let job = store.load("job").await.unwrap().unwrap();
+    assert_eq!(job.run_count, 1);
+    assert_eq!(job.status, JobStatus::Enabled);
+    assert_eq!(job.next_run, Some(due + chrono::Duration::minutes(10)));
+}
+
+#[tokio::test]
+async fn test_one_shot_job_runs_once() {
+    let at = Utc::now() - chrono::Duration::seconds(1);
+    let def = JobDefinition::new("job", format!("@at {}", at.to_rfc3339()), "agent", "prompt");
+    let mut job = Job::new(def);
+    job.next_run = Some(at);
+    let (scheduler, store) = scheduler_with(job).await;
+
+    scheduler.check_due_jobs().await.unwrap();
+    scheduler.check_due_jobs().await.unwrap();
+
+    let job = store.load("job").await.unwrap().unwrap();
+    assert_eq!(job.run_count, 1);
+    assert_eq!(job.status, JobStatus::Completed);
+    assert!(job.next_run.is_none());
+    assert_eq!(store.load_history("job").await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_one_shot_job_waits_for_its_time() {
+    let at = Utc::now() + chrono::Duration::minutes(20);
+    let def = JobDefinition::new("job", format!("@at {}", at.to_rfc3339()), "agent", "prompt");
+    let (scheduler, store) = scheduler_with(Job::new(def)).await;
+
+    scheduler.check_due_jobs().await.unwrap();
+
+    let job = store.load("job").await.unwrap().unwrap();
+    assert_eq!(job.run_count, 0);
+    assert_eq!(job.status, JobStatus::Enabled);
+    assert_eq!(job.next_run.map(|t| t.timestamp()), Some(at.timestamp()));
+}
+
+/// Tracker reporting every session as running until aborted.
+#[derive(Default)]
+struct FakeTracker {
+    aborted: std::sync::Mutex<Vec<String>>,
+}
+
+impl RunTracker for FakeTracker {
+    fn is_running(&self, session_id: &str) -> bool {
+        !self.aborted.lock().unwrap().iter().any(|s| s == session_id)
+    }
+
+    fn abort(&self, session_id: &str) -> bool {
+        self.aborted.lock().unwrap().push(session_id.to_string());
+        true
+    }
+}
+
+async fn tracked_scheduler(
+    jobs: Vec<Job>,
+    max_concurrent: usize,
+) -> (Arc<JobScheduler>, Arc<MemoryJobStore>, Arc<FakeTracker>) {
+    let store = Arc::new(MemoryJobStore::new());
+    for job in &jobs {
+        store.save(job).await.unwrap();
+    }
+    let run_loop = Arc::new(RunLoop::new(RunLoopConfig::default()));
+    let runloop = Arc::new(RunLoopState::from_runloop(run_loop));
+    let tracker = Arc::new(FakeTracker::default());
+    let job_store: Arc<dyn JobStore> = store.clone();
+    let scheduler = JobScheduler::new(job_store, runloop)
+        .with_run_tracker(tracker.clone())
+        .with_max_concurrent(max_concurrent);
+    (Arc::new(scheduler), store, tracker)
+}
+
+fn due_job(id: &str, overlap: OverlapPolicy) -> Job {
+    let def = JobDefinition::new(id, "0 * * * * *", "agent", "prompt").with_overlap(overlap);
+    let mut job = Job::new(def);
+    job.next_run = Some(Utc::now() - chrono::Duration::seconds(1));
+    job
+}
+
+/// Make a job due again, as if its next occurrence had arrived.
+async fn make_due(store: &MemoryJobStore, id: &str) {
+    let mut job = store.load(id).await.unwrap().unwrap();
+    job.next_run = Some(Utc::now() - chrono::Duration::seconds(1));
+    store.save(&job).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_overlap_skip() {
+    let (scheduler, store, _) = tracked_scheduler(vec![due_job("job", OverlapPolicy::Skip)], 0).await;
+
+    scheduler.check_due_jobs().await.unwrap();
+    make_due(&store, "job").await;
+    scheduler.check_due_jobs().await.unwrap();
+
+    let history = store.load_history("job").await.unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].status, JobRunStatus::Submitted);
+    assert!(history[0].session_id.as_deref().unwrap().starts_with("job:job:"));
+    assert_eq!(history[1].status, JobRunStatus::Skipped);
+    assert_eq!(history[1].message.as_deref(), Some("Previous run still in progress"));
+}
+
+#[tokio::test]
+async fn test_overlap_queue() {
+    let (scheduler, store, tracker) =
+        tracked_scheduler(vec![due_job("job", OverlapPolicy::Queue)], 0).await;
+
+    scheduler.check_due_jobs().await.unwrap();
+    make_due(&store, "job").await;
+    scheduler.check_due_jobs().await.unwrap();
+    assert_eq!(store.load_history("job").await.unwrap().len(), 1);
+    assert_eq!(scheduler.runs.lock().await.queued["job"].len(), 1);
+
+    // The first run finishes; the queued one starts on the next check
+    let first = store.load_history("job").await.unwrap()[0].session_id.clone().unwrap();
+    tracker.aborted.lock().unwrap().push(first);
+    for run in scheduler.runs.lock().await.active.values_mut().flatten() {
+        run.starts_at -= START_GRACE;
+    }
+    scheduler.check_due_jobs().await.unwrap();
+
+    let history = store.load_history("job").await.unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[1].status, JobRunStatus::Submitted);
+    assert!(scheduler.runs.lock().await.queued.is_empty());
+}
+
+#[tokio::test]
+async fn test_overlap_kill_previous() {
+    let (scheduler, store, tracker) =
+        tracked_scheduler(vec![due_job("job", OverlapPolicy::KillPrevious)], 0).await;
+
+    scheduler.check_due_jobs().await.unwrap();
+    make_due(&store, "job").await;
+    scheduler.check_due_jobs().await.unwrap();
+
+    let history = store.load_history("job").await.unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[1].status, JobRunStatus::Submitted);
+    assert_eq!(
+        history[1].message.as_deref(),
+        Some("Replaced 1 run(s) still in progress")
+    );
+    assert_eq!(
+        *tracker.aborted.lock().unwrap(),
+        vec![history[0].session_id.clone().unwrap()]
+    );
+}
+
+#[tokio::test]
+async fn test_max_concurrent() {
+    let jobs = vec![
+        due_job("a", OverlapPolicy::Skip),
+        due_job("b", OverlapPolicy::Skip),
+        due_job("c", OverlapPolicy::Queue),
+    ];
+    let (scheduler, store, _) = tracked_scheduler(jobs, 1).await;
+
+    scheduler.check_due_jobs().await.unwrap();
+
+    let mut submitted = 0;
+    for id in ["a", "b", "c"] {
+        for run in store.load_history(id).await.unwrap() {
+            match run.status {
+                JobRunStatus::Submitted => submitted += 1,
+                JobRunStatus::Skipped => {
+                    assert_eq!(run.message.as_deref(), Some("Concurrent job limit (1) reached"))
+                }
+                JobRunStatus::Failed | JobRunStatus::Completed => {
+                    panic!("unexpected {:?}", run.status)
+                }
+            }
+        }
+    }
+    assert_eq!(submitted, 1);
+    assert_eq!(scheduler.runs.lock().await.active_count(), 1);
+}
+
+#[tokio::test]
+async fn test_jitter_delays_start() {
+    let def = JobDefinition::new("job", "0 * * * * *", "agent", "prompt").with_jitter(3600);
+    let mut job = Job::new(def);
+    job.next_run = Some(Utc::now() - chrono::Duration::seconds(1));
+    let (scheduler, store, _) = tracked_scheduler(vec![job], 0).await;
+
+    scheduler.check_due_jobs().await.unwrap();
+
+    let run = &store.load_history("job").await.unwrap()[0];
+    let starts_at = scheduler.runs.lock().await.active["job"][0].starts_at;
+    assert!(starts_at >= run.started_at);
+    assert!(starts_at <= run.started_at + chrono::Duration::seconds(3600));
+}
+
+#[test]
+fn test_jitter_bounds() {
+    assert_eq!(jitter(0), chrono::Duration::zero());
+    for _ in 0..100 {
+        let delay = jitter(5);
+        assert!(delay >= chrono::Duration::zero() && delay <= chrono::Duration::seconds(5));
+    }
+}
+
+/// Backup runner recording the jobs it backed up.
+#[derive(Default)]
+struct FakeBackupRunner {
+    backups: std::sync::Mutex<Vec<String>>,
+}
+
+#[async_trait::async_trait]
+impl BackupRunner for FakeBackupRunner {
+    async fn backup(&self, job_id: &str, backup: &BackupJob) -> Result<String, String> {
+        self.backups.lock().unwrap().push(job_id.to_string());
+        Ok(format!("kept {}", backup.keep))
+    }
+}
+
+#[tokio::test]
+async fn test_backup_job() {
+    let backup = BackupJob { keep: 3, ..Default::default() };
+    let mut job = Job::new(JobDefinition::backup("nightly", "0 * * * * *", backup));
+    job.next_run = Some(Utc::now() - chrono::Duration::seconds(1));
+    let store = Arc::new(MemoryJobStore::new());
+    store.save(&job).await.unwrap();
+    let run_loop = Arc::new(RunLoop::new(RunLoopConfig::default()));
+    let runner = Arc::new(FakeBackupRunner::default());
+    let job_store: Arc<dyn JobStore> = store.clone();
+    let scheduler = JobScheduler::new(job_store, Arc::new(RunLoopState::from_runloop(run_loop.clone())))
+        .with_backup_runner(runner.clone());
+
+    scheduler.check_due_jobs().await.unwrap();
+
+    assert_eq!(*runner.backups.lock().unwrap(), vec!["nightly"]);
+    // Backups run in the scheduler, not on the RunLoop
+    assert_eq!(run_loop.pending_task_count().await, 0);
+    let history = store.load_history("nightly").await.unwrap();
+    assert_eq!(history[0].status, JobRunStatus::Completed);
+    assert_eq!(history[0].message.as_deref(), Some("kept 3"));
+    let job = store.load("nightly").await.unwrap().unwrap();
+    assert_eq!(job.run_count, 1);
+    assert_eq!(job.status, JobStatus::Enabled);
+}
+
+#[tokio::test]
+async fn test_backup_job_without_runner() {
+    let mut job = Job::new(JobDefinition::backup("nightly", "0 * * * * *", BackupJob::default()));
+    job.next_run = Some(Utc::now() - chrono::Duration::seconds(1));
+    let (scheduler, store) = scheduler_with(job).await;
+
+    scheduler.check_due_jobs().await.unwrap();
+
+    let history = store.load_history("nightly").await.unwrap();
+    assert_eq!(history[0].status, JobRunStatus::Failed);
+    let job = store.load("nightly").await.unwrap().unwrap();
+    assert_eq!(job.last_error.as_deref(), Some("No backup runner configured"));
+}
+
+/// Fetcher serving a fixed page.
+struct FixedPage(&'static str);
+
+#[async_trait::async_trait]
+impl crate::job::watch::PageFetcher for FixedPage {
+    async fn fetch(&self, _url: &str) -> Result<String, String> {
+        Ok(self.0.to_string())
+    }
+}
+
+async fn watch_scheduler(
+    job: Job,
+    page: &'static str,
+    snapshots: &std::path::Path,
+) -> (JobScheduler, Arc<MemoryJobStore>, Arc<RunLoop>) {
+    let store = Arc::new(MemoryJobStore::new());
+    store.save(&job).await.unwrap();
+    let run_loop = Arc::new(RunLoop::new(RunLoopConfig::default()));
+    let job_store: Arc<dyn JobStore> = store.clone();
+    let watcher = PageWatcher::new(Arc::new(FixedPage(page)), snapshots);
+    let scheduler = JobScheduler::new(job_store, Arc::new(RunLoopState::from_runloop(run_loop.clone())))
+        .with_page_watcher(Arc::new(watcher));
+    (scheduler, store, run_loop)
+}
+
+#[tokio::test]
+async fn test_watch_job_runs_agent_on_change() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(dir.path().join("pricing.txt"), "Basic $10").unwrap();
+    let def = JobDefinition::new("pricing", "0 * * * * *", "general", "Summarize the change")
+        .with_watch(WatchJob::new("https://example.com/pricing"));
+    let mut job = Job::new(def);
+    job.next_run = Some(Utc::now() - chrono::Duration::seconds(1));
+    let (scheduler, store, run_loop) =
+        watch_scheduler(job, "<p>Basic $12</p>", dir.path()).await;
+
+    scheduler.check_due_jobs().await.unwrap();
+
+    assert_eq!(run_loop.pending_task_count().await, 1);
+    let history = store.load_history("pricing").await.unwrap();
+    assert_eq!(history[0].status, JobRunStatus::Submitted);
+    assert_eq!(
+        history[0].message.as_deref(),
+        Some("https://example.com/pricing changed: 1 line(s) added, 1 removed")
+    );
+    assert_eq!(std::fs::read_to_string(dir.path().join("pricing.txt")).unwrap(), "Basic $12");
+}
+
+#[tokio::test]
+async fn test_watch_job_without_change_or_prompt() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let def = JobDefinition::new("pricing", "0 * * * * *", "", "")
+        .with_watch(WatchJob::new("https://example.com/pricing"));
+    let mut job = Job::new(def);
+    job.next_run = Some(Utc::now() - chrono::Duration::seconds(1));
+    let (scheduler, store, run_loop) = watch_scheduler(job, "<p>Basic $10</p>", dir.path()).await;
+
+    scheduler.check_due_jobs().await.unwrap();
+
+    // The first check only stores a snapshot; watching runs in the scheduler
+    assert_eq!(run_loop.pending_task_count().await, 0);
+    let history = store.load_history("pricing").await.unwrap();
+    assert_eq!(history[0].status, JobRunStatus::Completed);
+    assert_eq!(history[0].message.as_deref(), Some("Took the first snapshot"));
+    let job = store.load("pricing").await.unwrap().unwrap();
+    assert_eq!(job.status, JobStatus::Enabled);
+}
+
+#[tokio::test]
+async fn test_watch_job_without_watcher() {
+    let def = JobDefinition::new("pricing", "0 * * * * *", "general", "prompt")
+        .with_watch(WatchJob::new("https://example.com/pricing"));
+    let mut job = Job::new(def);
+    job.next_run = Some(Utc::now() - chrono::Duration::seconds(1));
+    let (scheduler, store) = scheduler_with(job).await;
+
+    scheduler.check_due_jobs().await.unwrap();
+
+    let job = store.load("pricing").await.unwrap().unwrap();
+    assert_eq!(job.last_error.as_deref(), Some("No page watcher configured"));
+}
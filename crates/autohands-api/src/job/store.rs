@@ -5,9 +5,12 @@ use std::path::PathBuf;
 use tokio::fs;
 use tracing::{debug, warn};
 
-use super::definition::Job;
+use super::definition::{Job, JobRun};
 use crate::error::InterfaceError;
 
+/// Most runs kept in a job's history.
+pub const MAX_JOB_HISTORY: usize = 200;
+
 /// Job store trait for persistence.
 #[async_trait]
 pub trait JobStore: Send + Sync {
@@ -25,11 +28,18 @@ pub trait JobStore: Send + Sync {
 
     /// Update job status.
     async fn update_status(&self, job: &Job) -> Result<(), InterfaceError>;
+
+    /// Append a run to a job's history, keeping the latest [`MAX_JOB_HISTORY`].
+    async fn record_run(&self, id: &str, run: &JobRun) -> Result<(), InterfaceError>;
+
+    /// Load a job's run history, oldest first.
+    async fn load_history(&self, id: &str) -> Result<Vec<JobRun>, InterfaceError>;
 }
 
 /// In-memory job store for testing.
 pub struct MemoryJobStore {
     jobs: tokio::sync::RwLock<std::collections::HashMap<String, Job>>,
+    history: tokio::sync::RwLock<std::collections::HashMap<String, Vec<JobRun>>>,
 }
 
 impl MemoryJobStore {
@@ -37,6 +47,7 @@ impl MemoryJobStore {
     pub fn new() -> Self {
         Self {
             jobs: tokio::sync::RwLock::new(std::collections::HashMap::new()),
+            history: tokio::sync::RwLock::new(std::collections::HashMap::new()),
         }
     }
 }
@@ -68,12 +79,27 @@ impl JobStore for MemoryJobStore {
     async fn delete(&self, id: &str) -> Result<(), InterfaceError> {
         let mut jobs = self.jobs.write().await;
         jobs.remove(id);
+        self.history.write().await.remove(id);
         Ok(())
     }
 
     async fn update_status(&self, job: &Job) -> Result<(), InterfaceError> {
         self.save(job).await
     }
+
+    async fn record_run(&self, id: &str, run: &JobRun) -> Result<(), InterfaceError> {
+        let mut history = self.history.write().await;
+        let runs = history.entry(id.to_string()).or_default();
+        runs.push(run.clone());
+        if runs.len() > MAX_JOB_HISTORY {
+            runs.drain(..runs.len() - MAX_JOB_HISTORY);
+        }
+        Ok(())
+    }
+
+    async fn load_history(&self, id: &str) -> Result<Vec<JobRun>, InterfaceError> {
+        Ok(self.history.read().await.get(id).cloned().unwrap_or_default())
+    }
 }
 
 /// File system based job store for persistence.
@@ -104,6 +130,10 @@ impl FileJobStore {
         self.jobs_dir().join(format!("{}.json", id))
     }
 
+    fn history_path(&self, id: &str) -> PathBuf {
+        self.jobs_dir().join(format!("{}.history.jsonl", id))
+    }
+
     fn sanitize_id(id: &str) -> String {
         id.chars()
             .map(|c| {
@@ -199,12 +229,59 @@ impl JobStore for FileJobStore {
             debug!("Deleted job '{}' from {:?}", id, path);
         }
 
+        let history_path = self.history_path(&sanitized_id);
+        if history_path.exists() {
+            fs::remove_file(&history_path).await.map_err(|e| {
+                InterfaceError::Custom(format!("Failed to delete job history: {}", e))
+            })?;
+        }
+
         Ok(())
     }
 
     async fn update_status(&self, job: &Job) -> Result<(), InterfaceError> {
         self.save(job).await
     }
+
+    async fn record_run(&self, id: &str, run: &JobRun) -> Result<(), InterfaceError> {
+        let mut runs = self.load_history(id).await?;
+        runs.push(run.clone());
+        let keep = &runs[runs.len().saturating_sub(MAX_JOB_HISTORY)..];
+
+        let mut content = String::new();
+        for run in keep {
+            let line = serde_json::to_string(run).map_err(|e| {
+                InterfaceError::Custom(format!("Failed to serialize job run: {}", e))
+            })?;
+            content.push_str(&line);
+            content.push('\n');
+        }
+
+        fs::write(self.history_path(&Self::sanitize_id(id)), content)
+            .await
+            .map_err(|e| InterfaceError::Custom(format!("Failed to write job history: {}", e)))
+    }
+
+    async fn load_history(&self, id: &str) -> Result<Vec<JobRun>, InterfaceError> {
+        let path = self.history_path(&Self::sanitize_id(id));
+
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&path)
+            .await
+            .map_err(|e| InterfaceError::Custom(format!("Failed to read job history: {}", e)))?;
+
+        let mut runs = Vec::new();
+        for line in content.lines().filter(|l| !l.trim().is_empty()) {
+            match serde_json::from_str::<JobRun>(line) {
+                Ok(run) => runs.push(run),
+                Err(e) => warn!("Skipping malformed run in {:?}: {}", path, e),
+            }
+        }
+        Ok(runs)
+    }
 }
 
 #[cfg(test)]
@@ -1,6 +1,6 @@
 
     use super::*;
-    use crate::job::JobDefinition;
+    use crate::job::{JobDefinition, JobRunStatus};
     use tempfile::TempDir;
 
     #[tokio::test]
@@ -74,6 +74,37 @@
         assert!(store.load("to-delete").await.unwrap().is_none());
     }
 
+    #[tokio::test]
+    async fn test_file_job_store_history() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = FileJobStore::new(temp_dir.path()).await.unwrap();
+        let job = Job::new(JobDefinition::new("nightly", "0 0 * * * *", "agent", "Prompt"));
+        store.save(&job).await.unwrap();
+
+        for i in 0..MAX_JOB_HISTORY + 3 {
+            let run = JobRun {
+                scheduled_for: chrono::Utc::now(),
+                started_at: chrono::Utc::now(),
+                status: JobRunStatus::Submitted,
+                catch_up: false,
+                task_id: None,
+                session_id: None,
+                message: Some(format!("run {}", i)),
+            };
+            store.record_run("nightly", &run).await.unwrap();
+        }
+
+        // History files don't show up as jobs
+        assert_eq!(store.load_all().await.unwrap().len(), 1);
+
+        let history = store.load_history("nightly").await.unwrap();
+        assert_eq!(history.len(), MAX_JOB_HISTORY);
+        assert_eq!(history[0].message.as_deref(), Some("run 3"));
+
+        store.delete("nightly").await.unwrap();
+        assert!(store.load_history("nightly").await.unwrap().is_empty());
+    }
+
     #[test]
     fn test_sanitize_id() {
         assert_eq!(FileJobStore::sanitize_id("simple-job"), "simple-job");
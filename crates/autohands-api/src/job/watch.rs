@@ -0,0 +1,324 @@
+//! Web page watching for watch jobs.
+//!
+//! A watch job fetches its page on schedule, reduces it (or the element
+//! matching its selector) to lines of text and compares them with the
+//! snapshot stored by the previous run. Markup-only edits therefore go
+//! unnoticed, and a change smaller than the job's threshold keeps the old
+//! snapshot, so small edits add up until they count.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::definition::WatchJob;
+
+/// Most diff lines shown in notifications and prompts.
+const MAX_DIFF_LINES: usize = 50;
+
+/// Tags whose content is not text.
+const SKIPPED_TAGS: &[&str] = &["script", "style", "noscript", "template"];
+
+/// Tags starting a new line of text.
+const BLOCK_TAGS: &[&str] = &[
+    "address", "article", "aside", "blockquote", "br", "dd", "div", "dl", "dt", "footer",
+    "form", "h1", "h2", "h3", "h4", "h5", "h6", "header", "hr", "li", "main", "nav", "ol",
+    "p", "pre", "section", "table", "title", "tr", "ul",
+];
+
+/// Fetches the pages of watch jobs.
+#[async_trait]
+pub trait PageFetcher: Send + Sync {
+    /// Body of the page at `url`.
+    async fn fetch(&self, url: &str) -> Result<String, String>;
+}
+
+/// Lines of a watched page added and removed since the previous run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageChange {
+    pub url: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl PageChange {
+    /// Number of added and removed lines.
+    pub fn changed_lines(&self) -> usize {
+        self.added.len() + self.removed.len()
+    }
+
+    /// One-line summary, e.g. for the job's run history.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} changed: {} line(s) added, {} removed",
+            self.url,
+            self.added.len(),
+            self.removed.len()
+        )
+    }
+
+    /// Removed lines prefixed with `-`, then added ones with `+`, cut off
+    /// after [`MAX_DIFF_LINES`].
+    pub fn diff(&self) -> String {
+        let lines: Vec<String> = self
+            .removed
+            .iter()
+            .map(|line| format!("- {}", line))
+            .chain(self.added.iter().map(|line| format!("+ {}", line)))
+            .collect();
+        let mut diff = lines[..lines.len().min(MAX_DIFF_LINES)].join("\n");
+        if lines.len() > MAX_DIFF_LINES {
+            diff.push_str(&format!("\n... {} more line(s)", lines.len() - MAX_DIFF_LINES));
+        }
+        diff
+    }
+}
+
+/// Outcome of checking a watched page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PageCheck {
+    /// There was no snapshot yet; the page's text was stored.
+    First,
+    /// The page changed less than the job's threshold.
+    Unchanged,
+    /// The page changed.
+    Changed(PageChange),
+}
+
+/// Checks watched pages against snapshots stored in a directory.
+pub struct PageWatcher {
+    fetcher: Arc<dyn PageFetcher>,
+    dir: PathBuf,
+}
+
+impl PageWatcher {
+    /// Watcher storing a snapshot per job in `dir`.
+    pub fn new(fetcher: Arc<dyn PageFetcher>, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            fetcher,
+            dir: dir.into(),
+        }
+    }
+
+    /// Fetch a watch job's page and compare it with the job's snapshot,
+    /// which is replaced when there is a change.
+    pub async fn check(&self, job_id: &str, watch: &WatchJob) -> Result<PageCheck, String> {
+        let body = self.fetcher.fetch(&watch.url).await?;
+        let text = page_text(&body, watch.selector.as_deref()).ok_or_else(|| {
+            format!(
+                "Selector '{}' matches nothing on {}",
+                watch.selector.as_deref().unwrap_or_default(),
+                watch.url
+            )
+        })?;
+
+        let path = self.snapshot_path(job_id);
+        let check = match tokio::fs::read_to_string(&path).await {
+            Ok(previous) => {
+                let (added, removed) = diff_lines(&previous, &text);
+                let change = PageChange {
+                    url: watch.url.clone(),
+                    added,
+                    removed,
+                };
+                if change.changed_lines() == 0 || change.changed_lines() < watch.min_changed_lines {
+                    return Ok(PageCheck::Unchanged);
+                }
+                PageCheck::Changed(change)
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => PageCheck::First,
+            Err(e) => return Err(format!("Failed to read snapshot {}: {}", path.display(), e)),
+        };
+
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(|e| format!("Failed to create {}: {}", self.dir.display(), e))?;
+        tokio::fs::write(&path, &text)
+            .await
+            .map_err(|e| format!("Failed to write snapshot {}: {}", path.display(), e))?;
+        Ok(check)
+    }
+
+    fn snapshot_path(&self, job_id: &str) -> PathBuf {
+        let name: String = job_id
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+            .collect();
+        self.dir.join(format!("{}.txt", name))
+    }
+}
+
+/// Text of a page, or of the first element matching `selector`, one
+/// whitespace-collapsed line per block. `None` when the selector matches
+/// nothing.
+pub fn page_text(html: &str, selector: Option<&str>) -> Option<String> {
+    let html = match selector {
+        Some(selector) => select(html, selector)?,
+        None => html,
+    };
+    Some(html_to_text(html))
+}
+
+/// Lines of `new` that are not in `old` and lines of `old` that are not in
+/// `new`. Lines that only moved do not count.
+pub fn diff_lines(old: &str, new: &str) -> (Vec<String>, Vec<String>) {
+    let mut unmatched: HashMap<&str, usize> = HashMap::new();
+    for line in old.lines() {
+        *unmatched.entry(line).or_default() += 1;
+    }
+    let mut added = Vec::new();
+    for line in new.lines() {
+        match unmatched.get_mut(line) {
+            Some(count) if *count > 0 => *count -= 1,
+            _ => added.push(line.to_string()),
+        }
+    }
+    let removed = old
+        .lines()
+        .filter(|line| match unmatched.get_mut(line) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                true
+            }
+            _ => false,
+        })
+        .map(String::from)
+        .collect();
+    (added, removed)
+}
+
+/// Inner HTML of the first element matching `selector`.
+fn select<'a>(html: &'a str, selector: &str) -> Option<&'a str> {
+    // ASCII lowercasing keeps byte offsets, so both strings share them
+    let lower = html.to_ascii_lowercase();
+    let mut pos = 0;
+    while let Some(offset) = lower[pos..].find('<') {
+        let start = pos + offset;
+        let end = start + lower[start..].find('>')?;
+        pos = end + 1;
+        let name = tag_name(&lower[start + 1..end]);
+        if !name.is_empty() && matches_selector(name, &html[start + 1..end], selector) {
+            return Some(&html[pos..closing_tag(&lower, name, pos)]);
+        }
+    }
+    None
+}
+
+fn matches_selector(name: &str, tag: &str, selector: &str) -> bool {
+    if let Some(id) = selector.strip_prefix('#') {
+        attribute(tag, "id") == Some(id)
+    } else if let Some(class) = selector.strip_prefix('.') {
+        attribute(tag, "class").is_some_and(|classes| classes.split_whitespace().any(|c| c == class))
+    } else {
+        name.eq_ignore_ascii_case(selector)
+    }
+}
+
+/// Value of an attribute of a start tag (without its brackets).
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let lower = tag.to_ascii_lowercase();
+    let mut from = 0;
+    while let Some(offset) = lower[from..].find(name) {
+        let at = from + offset;
+        from = at + name.len();
+        let rest = lower[from..].trim_start();
+        if !lower[..at].ends_with(|c: char| c.is_ascii_whitespace()) || !rest.starts_with('=') {
+            continue;
+        }
+        let value = tag[lower.len() - rest.len() + 1..].trim_start();
+        return Some(match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => value[1..].split(quote).next().unwrap_or_default(),
+            _ => value
+                .split(|c: char| c.is_ascii_whitespace() || c == '/')
+                .next()
+                .unwrap_or_default(),
+        });
+    }
+    None
+}
+
+/// Offset of the tag closing the `name` element whose content starts at
+/// `from`, or the end of the page.
+fn closing_tag(lower: &str, name: &str, from: usize) -> usize {
+    let mut depth = 1;
+    let mut pos = from;
+    while let Some(offset) = lower[pos..].find('<') {
+        let at = pos + offset;
+        pos = at + 1;
+        let (closing, rest) = match lower[pos..].strip_prefix('/') {
+            Some(rest) => (true, rest),
+            None => (false, &lower[pos..]),
+        };
+        if tag_name(rest) != name {
+            continue;
+        }
+        if closing {
+            depth -= 1;
+            if depth == 0 {
+                return at;
+            }
+        } else {
+            depth += 1;
+        }
+    }
+    lower.len()
+}
+
+/// Name at the start of a tag's content.
+fn tag_name(tag: &str) -> &str {
+    let end = tag
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(tag.len());
+    &tag[..end]
+}
+
+fn html_to_text(html: &str) -> String {
+    let lower = html.to_ascii_lowercase();
+    let mut text = String::new();
+    let mut pos = 0;
+    while let Some(offset) = lower[pos..].find('<') {
+        let start = pos + offset;
+        text.push_str(&html[pos..start]);
+        if lower[start..].starts_with("<!--") {
+            pos = lower[start..].find("-->").map_or(lower.len(), |e| start + e + 3);
+            continue;
+        }
+        let Some(end) = lower[start..].find('>').map(|e| start + e) else {
+            pos = lower.len();
+            break;
+        };
+        pos = end + 1;
+        let tag = &lower[start + 1..end];
+        let name = tag_name(tag.strip_prefix('/').unwrap_or(tag));
+        if SKIPPED_TAGS.contains(&name) && !tag.starts_with('/') {
+            let close = format!("</{}", name);
+            pos = lower[pos..].find(&close).map_or(lower.len(), |e| pos + e);
+        } else if BLOCK_TAGS.contains(&name) {
+            text.push('\n');
+        } else if name == "td" || name == "th" {
+            text.push(' ');
+        }
+    }
+    text.push_str(&html[pos..]);
+
+    decode_entities(&text)
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+#[path = "watch_tests.rs"]
+mod tests;
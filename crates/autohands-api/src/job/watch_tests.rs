@@ -0,0 +1,114 @@
+//! Tests for page watching.
+
+use super::*;
+use std::sync::Mutex;
+
+const PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<head><title>Pricing</title><style>.plan { color: red; }</style></head>
+<body>
+  <nav>Home &amp; About</nav>
+  <!-- <div id="plans">commented out</div> -->
+  <div id="plans" class="grid wide">
+    <div class="plan"><h2>Basic</h2><p>$10 / month</p></div>
+    <div class="plan"><h2>Pro</h2><p>$20&nbsp;/ month</p></div>
+  </div>
+  <script>var plans = "<div>";</script>
+  <table><tr><td>Seats</td><td>5</td></tr></table>
+</body>
+</html>"#;
+
+#[test]
+fn test_page_text() {
+    let text = page_text(PAGE, None).unwrap();
+    assert_eq!(
+        text,
+        "Pricing\nHome & About\nBasic\n$10 / month\nPro\n$20 / month\nSeats 5"
+    );
+}
+
+#[test]
+fn test_page_text_with_selector() {
+    let plans = "Basic\n$10 / month\nPro\n$20 / month";
+    assert_eq!(page_text(PAGE, Some("#plans")).unwrap(), plans);
+    assert_eq!(page_text(PAGE, Some(".wide")).unwrap(), plans);
+    assert_eq!(page_text(PAGE, Some(".plan")).unwrap(), "Basic\n$10 / month");
+    assert_eq!(page_text(PAGE, Some("NAV")).unwrap(), "Home & About");
+    assert!(page_text(PAGE, Some("#missing")).is_none());
+    assert!(page_text(PAGE, Some(".gri")).is_none());
+}
+
+#[test]
+fn test_diff_lines_ignores_moves() {
+    let (added, removed) = diff_lines("a\nb\nc\nc", "c\nb\na\nd\nc");
+    assert_eq!(added, vec!["d"]);
+    assert!(removed.is_empty());
+
+    let (added, removed) = diff_lines("a\nb\nb", "a\nc");
+    assert_eq!(added, vec!["c"]);
+    assert_eq!(removed, vec!["b", "b"]);
+}
+
+#[test]
+fn test_page_change_diff() {
+    let change = PageChange {
+        url: "https://example.com".to_string(),
+        added: vec!["$25 / month".to_string()],
+        removed: vec!["$20 / month".to_string()],
+    };
+    assert_eq!(change.changed_lines(), 2);
+    assert_eq!(change.diff(), "- $20 / month\n+ $25 / month");
+    assert_eq!(change.summary(), "https://example.com changed: 1 line(s) added, 1 removed");
+
+    let change = PageChange {
+        url: "https://example.com".to_string(),
+        added: (0..60).map(|i| i.to_string()).collect(),
+        removed: Vec::new(),
+    };
+    assert!(change.diff().ends_with("\n... 10 more line(s)"));
+}
+
+/// Fetcher serving a page that tests can change.
+struct FakeFetcher {
+    page: Mutex<Result<String, String>>,
+}
+
+#[async_trait]
+impl PageFetcher for FakeFetcher {
+    async fn fetch(&self, _url: &str) -> Result<String, String> {
+        self.page.lock().unwrap().clone()
+    }
+}
+
+#[tokio::test]
+async fn test_page_watcher() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let fetcher = Arc::new(FakeFetcher {
+        page: Mutex::new(Ok("<p>one</p><p>two</p>".to_string())),
+    });
+    let watcher = PageWatcher::new(fetcher.clone(), dir.path().join("snapshots"));
+    let mut watch = WatchJob::new("https://example.com");
+    watch.min_changed_lines = 2;
+
+    assert_eq!(watcher.check("job/1", &watch).await.unwrap(), PageCheck::First);
+    assert!(dir.path().join("snapshots/job_1.txt").exists());
+    assert_eq!(watcher.check("job/1", &watch).await.unwrap(), PageCheck::Unchanged);
+
+    // One changed line is below the threshold and keeps the old snapshot
+    *fetcher.page.lock().unwrap() = Ok("<p>one</p><p>two</p><p>three</p>".to_string());
+    assert_eq!(watcher.check("job/1", &watch).await.unwrap(), PageCheck::Unchanged);
+
+    *fetcher.page.lock().unwrap() = Ok("<p>one</p><p>three</p><p>four</p>".to_string());
+    let PageCheck::Changed(change) = watcher.check("job/1", &watch).await.unwrap() else {
+        panic!("expected a change");
+    };
+    assert_eq!(change.added, vec!["three", "four"]);
+    assert_eq!(change.removed, vec!["two"]);
+    assert_eq!(watcher.check("job/1", &watch).await.unwrap(), PageCheck::Unchanged);
+
+    watch.selector = Some("#missing".to_string());
+    let err = watcher.check("job/1", &watch).await.unwrap_err();
+    assert!(err.contains("matches nothing"));
+    *fetcher.page.lock().unwrap() = Err("HTTP 503".to_string());
+    assert_eq!(watcher.check("job/1", &watch).await.unwrap_err(), "HTTP 503");
+}
@@ -8,6 +8,8 @@
 //! - **Webhook**: Event-driven trigger system
 //! - **Workflow**: Multi-step task orchestration
 //! - **Job**: Scheduled task execution via Cron
+//! - **Template**: Saved, parameterized task prompts
+//! - **Batch**: Many tasks submitted at once, with dependencies
 //!
 //! ## Architecture
 //!
@@ -40,12 +42,16 @@
 //! 3. **Unified response routing**: Responses flow back through the interface
 //!    layer to the appropriate client connection.
 
+pub mod batch;
 pub mod error;
 pub mod http;
+pub mod idempotency;
 pub mod job;
 pub mod runloop_bridge;
 pub mod server;
 pub mod state;
+pub mod task_tree;
+pub mod template;
 pub mod webhook;
 pub mod websocket;
 pub mod workflow;
@@ -53,24 +59,45 @@ pub mod workflow;
 // Re-export core types
 pub use error::InterfaceError;
 pub use http::{
+    capabilities::{CapabilityManifest, SkillCatalog},
+    degraded::{NO_PROVIDER_ERROR, PROVIDER_SETUP},
     handlers::{AgentAbortRequest, AgentAbortResponse, AgentRunRequest, AgentRunResponse},
     routes::create_router_with_hybrid_state,
 };
+pub use idempotency::{
+    FileIdempotencyStore, IdempotencyStore, MemoryIdempotencyStore, DEFAULT_IDEMPOTENCY_TTL,
+};
 pub use runloop_bridge::{
     HybridAppState, RunLoopBridge, RunLoopState, RunLoopTaskRequest, RunLoopTaskResponse,
 };
 pub use server::{InterfaceConfig, InterfaceServer};
 pub use state::AppState;
+pub use task_tree::{
+    build_task_tree, SubAgentDirectory, SubAgentNode, TaskTree, TaskTreeNode, TaskTreeSources,
+    TokenUsage, TreeLinks, TreeNodeKind,
+};
 pub use webhook::{WebhookEvent, WebhookRegistration, WebhookRegistry, WebhookResponse};
 pub use websocket::{ApiWsChannel, WsConnectionManager, WsMessage};
 
 // Workflow module exports
 pub use workflow::{
-    ExecutionContext, ExecutionState, MemoryWorkflowStore, StepResult, StepType, Workflow,
-    WorkflowExecution, WorkflowExecutor, WorkflowStep, WorkflowStore,
+    ExecutionContext, ExecutionState, MemoryWorkflowStore, SqliteWorkflowStore, StepResult,
+    StepType, Workflow, WorkflowExecution, WorkflowExecutor, WorkflowInput, WorkflowStep,
+    WorkflowStore,
 };
 
 // Job module exports
 pub use job::{
-    FileJobStore, Job, JobDefinition, JobScheduler, JobStatus, JobStore, MemoryJobStore,
+    format_interval, parse_interval, BackupJob, BackupRunner, CatchUpPolicy, FileJobStore, Job,
+    JobDefinition, JobRun, JobRunStatus, JobSchedule, JobScheduler, JobStatus, JobStore,
+    MemoryJobStore, OverlapPolicy, PageChange, PageCheck, PageFetcher, PageWatcher, RunTracker,
+    WatchJob,
+};
+
+// Template module exports
+pub use template::{FileTemplateStore, MemoryTemplateStore, TaskTemplate, TemplateStore};
+
+// Batch module exports
+pub use batch::{
+    Batch, BatchManager, BatchRequest, BatchStatus, BatchTask, BatchTaskSpec, BatchTaskStatus,
 };
@@ -5,13 +5,21 @@
 //! for unified processing.
 
 use std::sync::Arc;
-
-use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use std::time::Duration;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use tracing::{error, info};
 use uuid::Uuid;
 
-use autohands_runloop::{RunLoop, Task};
+use autohands_protocols::agent::RunScheduling;
+use autohands_runloop::{DeliveryTarget, RunLoop, RunLoopError, Task, TaskCallback};
 
 use crate::state::AppState;
 
@@ -60,18 +68,38 @@ impl RunLoopState {
         if let Some(addr) = reply_to {
             task = task.with_reply_to(addr);
         }
+        self.submit(task).await.map(|_| ())
+    }
 
-        self.run_loop.inject_task(task).await.map_err(|e| {
-            crate::error::InterfaceError::RunLoopInjectionFailed(format!(
+    /// Submit a prepared task to the RunLoop, returning its ID.
+    pub async fn submit(&self, task: Task) -> Result<Uuid, crate::error::InterfaceError> {
+        let task_id = task.id;
+        let task_type = task.task_type.clone();
+
+        self.run_loop.inject_task(task).await.map_err(|e| match e {
+            RunLoopError::QueueFull(_) => crate::error::InterfaceError::QueueFull(e.to_string()),
+            RunLoopError::Draining => crate::error::InterfaceError::Draining(e.to_string()),
+            e => crate::error::InterfaceError::RunLoopInjectionFailed(format!(
                 "Failed to inject task: {}",
                 e
-            ))
+            )),
         })?;
 
         // Wake up the RunLoop
         self.run_loop.wakeup(format!("New task: {}", task_type));
 
-        Ok(())
+        Ok(task_id)
+    }
+}
+
+/// HTTP status for a failed submission: 503 when the RunLoop queue is full
+/// or draining, so callers can back off and retry, 500 otherwise.
+pub fn submit_error_status(e: &crate::error::InterfaceError) -> StatusCode {
+    match e {
+        crate::error::InterfaceError::QueueFull(_) | crate::error::InterfaceError::Draining(_) => {
+            StatusCode::SERVICE_UNAVAILABLE
+        }
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
     }
 }
 
@@ -86,6 +114,34 @@ pub struct RunLoopTaskRequest {
 
     /// Optional agent ID to use. Defaults to "general".
     pub agent_id: Option<String>,
+
+    /// Channels the final result is delivered to, each with its own format.
+    #[serde(default)]
+    pub deliver_to: Vec<DeliveryTarget>,
+
+    /// Endpoint receiving signed lifecycle events (started, turn completed,
+    /// needs approval, finished).
+    #[serde(default)]
+    pub callback: Option<TaskCallback>,
+
+    /// Correlation ID tracing the task; defaults to the task ID.
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+
+    /// Queue priority ("low", "normal", "high" or "critical"). Sub-agents
+    /// and follow-up tasks inherit it, capped at "high".
+    #[serde(default)]
+    pub priority: Option<String>,
+
+    /// Seconds the task, and any work it spawns, has to finish.
+    #[serde(default)]
+    pub deadline_secs: Option<u64>,
+
+    /// Preview the task: tools with side effects (file writes, shell
+    /// commands, browser input, webhooks) report what they would do
+    /// instead of doing it, while read-only tools run normally.
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 /// Response from submitting a task to RunLoop.
@@ -94,6 +150,14 @@ pub struct RunLoopTaskResponse {
     /// Session ID for tracking this task.
     pub session_id: String,
 
+    /// RunLoop task ID, for querying delivery status.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<String>,
+
+    /// ID the task is traced under (`GET /tasks/{id}/trace`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+
     /// Status of the submission.
     pub status: String,
 
@@ -125,20 +189,52 @@ pub async fn submit_task(
     );
 
     // Build task payload
-    let payload = serde_json::json!({
+    let mut payload = serde_json::json!({
         "prompt": req.task,
         "session_id": session_id.clone(),
         "agent_id": agent_id,
     });
+    RunScheduling {
+        priority: req.priority,
+        deadline: req
+            .deadline_secs
+            .map(|secs| chrono::Utc::now() + chrono::Duration::seconds(secs as i64)),
+    }
+    .apply_to(&mut payload);
+
+    let callbacks = state.run_loop.callback_dispatcher();
+    if let Some(Err(e)) = req.callback.as_ref().map(|cb| callbacks.validate(cb)) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(RunLoopTaskResponse {
+                session_id,
+                task_id: None,
+                correlation_id: None,
+                status: "error".to_string(),
+                error: Some(e),
+            }),
+        );
+    }
+
+    let mut task = Task::new("agent:execute", payload).with_payload_scheduling();
+    if req.dry_run {
+        task = task.with_dry_run();
+    }
+    task.deliver_to = req.deliver_to;
+    task.callback = req.callback;
+    task.correlation_id = req.correlation_id;
+    let correlation_id = task.trace_id();
 
-    match state.submit_task("agent:execute", payload, None).await {
-        Ok(()) => {
+    match state.submit(task).await {
+        Ok(task_id) => {
             info!("Task submitted to RunLoop: session={}", session_id);
 
             (
                 StatusCode::ACCEPTED,
                 Json(RunLoopTaskResponse {
                     session_id,
+                    task_id: Some(task_id.to_string()),
+                    correlation_id: Some(correlation_id),
                     status: "queued".to_string(),
                     error: None,
                 }),
@@ -148,9 +244,11 @@ pub async fn submit_task(
             error!("Failed to submit task to RunLoop: {}", e);
 
             (
-                StatusCode::INTERNAL_SERVER_ERROR,
+                submit_error_status(&e),
                 Json(RunLoopTaskResponse {
                     session_id,
+                    task_id: None,
+                    correlation_id: None,
                     status: "error".to_string(),
                     error: Some(e.to_string()),
                 }),
@@ -159,6 +257,165 @@ pub async fn submit_task(
     }
 }
 
+/// Per-target delivery status of a task's result.
+///
+/// GET /v1/runloop/task/{id}/deliveries
+///
+/// Answers 404 until the task has finished and its result was fanned out.
+pub async fn get_deliveries(
+    State(state): State<Arc<RunLoopState>>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let Ok(task_id) = Uuid::parse_str(&id) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": format!("Invalid task ID '{}'", id)})),
+        );
+    };
+    match state.run_loop.delivery_log().get(task_id) {
+        Some(deliveries) => (
+            StatusCode::OK,
+            Json(serde_json::json!({"task_id": id, "deliveries": deliveries})),
+        ),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("No deliveries recorded for task '{}'", id)})),
+        ),
+    }
+}
+
+/// Query parameters for a drain request.
+#[derive(Debug, Default, Deserialize)]
+pub struct DrainQuery {
+    /// Seconds to wait for the drain to finish before answering (capped at
+    /// [`MAX_WAIT_SECONDS`](crate::http::handlers::MAX_WAIT_SECONDS)); 0
+    /// answers at once.
+    #[serde(default)]
+    pub wait_seconds: u64,
+}
+
+/// Dispatch state and outstanding work of the RunLoop.
+///
+/// GET /v1/runloop/status
+pub async fn control_status(State(state): State<Arc<RunLoopState>>) -> impl IntoResponse {
+    Json(state.run_loop.control_status().await)
+}
+
+/// Per-source queue depth, dispatch throughput and queue wait.
+///
+/// GET /v1/runloop/sources
+pub async fn source_stats(State(state): State<Arc<RunLoopState>>) -> impl IntoResponse {
+    let snapshot = state.run_loop.metrics().snapshot();
+    let throughput = snapshot.source_throughput();
+    let mut names: Vec<&String> = snapshot
+        .queue_depths
+        .keys()
+        .chain(snapshot.source_stats.keys())
+        .collect();
+    names.sort();
+    names.dedup();
+
+    let sources: serde_json::Map<String, serde_json::Value> = names
+        .into_iter()
+        .map(|name| {
+            let depth = snapshot.queue_depths.get(name).copied().unwrap_or_default();
+            let stats = snapshot.source_stats.get(name).copied().unwrap_or_default();
+            let report = serde_json::json!({
+                "pending": depth.pending,
+                "spilled": depth.spilled,
+                "dispatched": stats.dispatched,
+                "dispatched_per_sec": throughput.get(name).copied().unwrap_or_default(),
+                "avg_wait_ms": stats.avg_wait_ms(),
+                "max_wait_ms": stats.max_wait_ms,
+                "last_wait_ms": stats.last_wait_ms,
+            });
+            (name.clone(), report)
+        })
+        .collect();
+    Json(serde_json::json!({"uptime_secs": snapshot.uptime_secs, "sources": sources}))
+}
+
+/// Query parameters for the event journal.
+#[derive(Debug, Default, Deserialize)]
+pub struct EventsQuery {
+    /// Only events at or after this time (RFC 3339).
+    #[serde(default)]
+    pub since: Option<DateTime<Utc>>,
+    /// Only events of this kind, e.g. `dispatched` or `rejected`.
+    #[serde(default)]
+    pub kind: Option<String>,
+    /// Only the most recent events, this many.
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// Events recorded by the RunLoop journal, oldest first.
+///
+/// GET /v1/runloop/events?since=2025-01-01T01:55:00Z&kind=dispatched&limit=100
+///
+/// Answers 404 when the journal is disabled (`runloop.journal`).
+pub async fn events(
+    State(state): State<Arc<RunLoopState>>,
+    Query(query): Query<EventsQuery>,
+) -> impl IntoResponse {
+    let Some(journal) = state.run_loop.journal() else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({
+                "error": "RunLoop journal is disabled; set runloop.journal = true"
+            })),
+        );
+    };
+    let mut events = journal.events(query.since);
+    if let Some(kind) = &query.kind {
+        events.retain(|event| event.kind.name() == kind);
+    }
+    if let Some(limit) = query.limit {
+        events.drain(..events.len().saturating_sub(limit));
+    }
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({"capacity": journal.capacity(), "events": events})),
+    )
+}
+
+/// Stop dispatching queued tasks; new tasks are still queued and in-flight
+/// ones run to completion.
+///
+/// POST /v1/runloop/pause
+pub async fn pause(State(state): State<Arc<RunLoopState>>) -> impl IntoResponse {
+    state.run_loop.pause();
+    Json(state.run_loop.control_status().await)
+}
+
+/// Accept and dispatch tasks again after a pause or drain.
+///
+/// POST /v1/runloop/resume
+pub async fn resume(State(state): State<Arc<RunLoopState>>) -> impl IntoResponse {
+    state.run_loop.resume();
+    Json(state.run_loop.control_status().await)
+}
+
+/// Refuse new tasks (503) while queued and in-flight ones finish.
+///
+/// POST /v1/runloop/drain
+///
+/// With `?wait_seconds=N`, answers once the drain has finished or the wait
+/// is over; `drained` in the response tells which.
+pub async fn drain(
+    State(state): State<Arc<RunLoopState>>,
+    Query(query): Query<DrainQuery>,
+) -> impl IntoResponse {
+    state.run_loop.drain();
+    let wait = query
+        .wait_seconds
+        .min(crate::http::handlers::MAX_WAIT_SECONDS);
+    if wait > 0 {
+        state.run_loop.wait_drained(Duration::from_secs(wait)).await;
+    }
+    Json(state.run_loop.control_status().await)
+}
+
 /// Application state for RunLoop-based execution.
 ///
 /// All external requests go through RunLoop for unified event processing.
@@ -184,6 +441,24 @@ pub struct HybridAppState {
 
     /// Job store for persistence.
     pub job_store: Arc<dyn crate::job::JobStore>,
+
+    /// Task template store.
+    pub template_store: Arc<dyn crate::template::TemplateStore>,
+
+    /// Batch task manager.
+    pub batch_manager: Arc<crate::batch::BatchManager>,
+
+    /// Gate deciding human approvals (approval steps, risky tools).
+    pub approval_gate: Option<Arc<dyn autohands_protocols::approval::ApprovalGate>>,
+
+    /// Gate holding the agents' questions for the user.
+    pub question_gate: Option<Arc<dyn autohands_protocols::question::QuestionGate>>,
+
+    /// Log of tool calls, approvals and agent runs, read through `/audit`.
+    pub audit_log: Option<Arc<autohands_core::audit::AuditLog>>,
+
+    /// Agents spawned by agents, shown in task trees.
+    pub sub_agents: Option<Arc<dyn crate::task_tree::SubAgentDirectory>>,
 }
 
 impl HybridAppState {
@@ -193,15 +468,17 @@ impl HybridAppState {
         runloop: Arc<RunLoopState>,
         api_ws_channel: Arc<crate::websocket::ApiWsChannel>,
     ) -> Self {
+        let workflow_store: Arc<dyn crate::workflow::WorkflowStore> =
+            Arc::new(crate::workflow::MemoryWorkflowStore::new());
         let workflow_executor = Arc::new(
             crate::workflow::WorkflowExecutor::new(
                 Arc::new(crate::workflow::MockAgentExecutor::new()),
-            ),
+            )
+            .with_store(workflow_store.clone()),
         );
-        let workflow_store: Arc<dyn crate::workflow::WorkflowStore> =
-            Arc::new(crate::workflow::MemoryWorkflowStore::new());
         let job_store: Arc<dyn crate::job::JobStore> =
             Arc::new(crate::job::MemoryJobStore::new());
+        let batch_manager = Arc::new(crate::batch::BatchManager::new(runloop.clone()));
 
         Self {
             base,
@@ -211,6 +488,12 @@ impl HybridAppState {
             workflow_executor,
             workflow_store,
             job_store,
+            template_store: Arc::new(crate::template::MemoryTemplateStore::new()),
+            batch_manager,
+            approval_gate: None,
+            question_gate: None,
+            audit_log: None,
+            sub_agents: None,
         }
     }
 
@@ -224,6 +507,7 @@ impl HybridAppState {
         workflow_store: Arc<dyn crate::workflow::WorkflowStore>,
         job_store: Arc<dyn crate::job::JobStore>,
     ) -> Self {
+        let batch_manager = Arc::new(crate::batch::BatchManager::new(runloop.clone()));
         Self {
             base,
             runloop,
@@ -232,9 +516,123 @@ impl HybridAppState {
             workflow_executor,
             workflow_store,
             job_store,
+            template_store: Arc::new(crate::template::MemoryTemplateStore::new()),
+            batch_manager,
+            approval_gate: None,
+            question_gate: None,
+            audit_log: None,
+            sub_agents: None,
         }
     }
 
+    /// Replace the workflow store, rebinding the executor to persist into it.
+    pub fn with_workflow_store(
+        mut self,
+        workflow_store: Arc<dyn crate::workflow::WorkflowStore>,
+    ) -> Self {
+        self.workflow_executor = Arc::new(
+            (*self.workflow_executor)
+                .clone()
+                .with_store(workflow_store.clone()),
+        );
+        self.workflow_store = workflow_store;
+        self
+    }
+
+    /// Run workflow agent steps with `executor` instead of the mock.
+    pub fn with_agent_executor(
+        mut self,
+        executor: Arc<dyn crate::workflow::AgentExecutor>,
+    ) -> Self {
+        self.workflow_executor = Arc::new(
+            (*self.workflow_executor)
+                .clone()
+                .with_agent_executor(executor),
+        );
+        self
+    }
+
+    /// Run workflow tool steps with `executor`.
+    pub fn with_tool_executor(
+        mut self,
+        executor: Arc<dyn crate::workflow::ToolExecutor>,
+    ) -> Self {
+        self.workflow_executor = Arc::new(
+            (*self.workflow_executor)
+                .clone()
+                .with_tool_executor(executor),
+        );
+        self
+    }
+
+    /// Replace the job store.
+    pub fn with_job_store(mut self, job_store: Arc<dyn crate::job::JobStore>) -> Self {
+        self.job_store = job_store;
+        self
+    }
+
+    /// Replace the task template store.
+    pub fn with_template_store(
+        mut self,
+        template_store: Arc<dyn crate::template::TemplateStore>,
+    ) -> Self {
+        self.template_store = template_store;
+        self
+    }
+
+    /// Set the approval gate, binding the workflow executor's approval steps to it.
+    pub fn with_approval_gate(
+        mut self,
+        gate: Arc<dyn autohands_protocols::approval::ApprovalGate>,
+    ) -> Self {
+        self.workflow_executor = Arc::new(
+            (*self.workflow_executor)
+                .clone()
+                .with_approval_gate(gate.clone()),
+        );
+        self.approval_gate = Some(gate);
+        self
+    }
+
+    /// Set the question gate answered through `/questions`.
+    pub fn with_question_gate(
+        mut self,
+        gate: Arc<dyn autohands_protocols::question::QuestionGate>,
+    ) -> Self {
+        self.question_gate = Some(gate);
+        self
+    }
+
+    /// Set the audit log read through `/audit`.
+    pub fn with_audit_log(mut self, audit_log: Arc<autohands_core::audit::AuditLog>) -> Self {
+        self.audit_log = Some(audit_log);
+        self
+    }
+
+    /// Set where task trees find spawned sub-agents.
+    pub fn with_sub_agents(
+        mut self,
+        sub_agents: Arc<dyn crate::task_tree::SubAgentDirectory>,
+    ) -> Self {
+        self.sub_agents = Some(sub_agents);
+        self
+    }
+
+    /// Tree of the multi-agent run holding a task, looked up by task ID or
+    /// session.
+    pub fn task_tree(&self, id: &str) -> Option<crate::task_tree::TaskTree> {
+        let graph = self.runloop.run_loop().task_graph();
+        crate::task_tree::build_task_tree(
+            &crate::task_tree::TaskTreeSources {
+                graph: &graph,
+                runtime: &self.base.agent_runtime,
+                transcripts: &self.base.transcript_manager,
+                sub_agents: self.sub_agents.as_ref(),
+            },
+            id,
+        )
+    }
+
     /// Get the RunLoop state.
     pub fn runloop_state(&self) -> &Arc<RunLoopState> {
         &self.runloop
@@ -24,6 +24,8 @@
     fn test_runloop_task_response_serialize() {
         let resp = RunLoopTaskResponse {
             session_id: "test-session".to_string(),
+            task_id: None,
+            correlation_id: None,
             status: "queued".to_string(),
             error: None,
         };
@@ -89,3 +91,141 @@
         assert_eq!(hybrid.api_ws_channel.connection_count(), 0);
         assert!(Arc::ptr_eq(&hybrid.api_ws_channel, &api_ws_channel));
     }
+
+    #[test]
+    fn test_runloop_task_request_deliver_to() {
+        let json = r##"{"task": "report", "deliver_to": [
+            {"channel_id": "slack", "target": "#reports", "format": "plain"},
+            {"channel_id": "email", "target": "ops@example.com", "format": "file", "file_name": "report.md"}
+        ]}"##;
+        let req: RunLoopTaskRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(req.deliver_to.len(), 2);
+        assert_eq!(req.deliver_to[1].file_name.as_deref(), Some("report.md"));
+    }
+
+    #[tokio::test]
+    async fn test_get_deliveries() {
+        use autohands_runloop::DeliveryStatus;
+        use axum::response::IntoResponse;
+
+        let run_loop = Arc::new(RunLoop::new(RunLoopConfig::default()));
+        let state = Arc::new(RunLoopState::from_runloop(run_loop.clone()));
+        let task_id = Uuid::new_v4();
+
+        let resp = get_deliveries(State(state.clone()), Path(task_id.to_string()))
+            .await
+            .into_response();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let resp = get_deliveries(State(state.clone()), Path("not-a-uuid".to_string()))
+            .await
+            .into_response();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let target = DeliveryTarget::new("slack", "#reports");
+        run_loop
+            .delivery_log()
+            .record(task_id, vec![DeliveryStatus::failed(&target, "Channel not found")]);
+        let resp = get_deliveries(State(state), Path(task_id.to_string()))
+            .await
+            .into_response();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["deliveries"][0]["delivered"], false);
+        assert_eq!(body["deliveries"][0]["channel_id"], "slack");
+    }
+
+    #[tokio::test]
+    async fn test_get_events() {
+        use autohands_runloop::JournalEventKind;
+        use axum::response::IntoResponse;
+
+        let run_loop = Arc::new(RunLoop::new(RunLoopConfig::default()));
+        let state = Arc::new(RunLoopState::from_runloop(run_loop));
+        let resp = events(State(state), Query(EventsQuery::default()))
+            .await
+            .into_response();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let mut config = RunLoopConfig::default();
+        config.journal.enabled = true;
+        let run_loop = Arc::new(RunLoop::new(config));
+        let journal = run_loop.journal().unwrap();
+        for reason in ["task_submitted", "stop"] {
+            journal.record(JournalEventKind::Wakeup {
+                reason: reason.to_string(),
+            });
+        }
+        journal.record(JournalEventKind::SourceTriggered {
+            source_id: "cron".to_string(),
+            tasks: 1,
+        });
+        let state = Arc::new(RunLoopState::from_runloop(run_loop));
+        let query = EventsQuery {
+            kind: Some("wakeup".to_string()),
+            limit: Some(1),
+            ..Default::default()
+        };
+        let resp = events(State(state), Query(query)).await.into_response();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        let events = body["events"].as_array().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["reason"], "stop");
+    }
+
+    #[tokio::test]
+    async fn test_submit_task_with_callback() {
+        use axum::response::IntoResponse;
+
+        let run_loop = Arc::new(RunLoop::new(RunLoopConfig::default()));
+        let state = Arc::new(RunLoopState::from_runloop(run_loop.clone()));
+
+        let req: RunLoopTaskRequest = serde_json::from_str(
+            r#"{"task": "build", "callback": {"url": "ftp://example.com/hook"}}"#,
+        )
+        .unwrap();
+        let resp = submit_task(State(state.clone()), Json(req)).await.into_response();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(run_loop.pending_task_count().await, 0);
+
+        let req: RunLoopTaskRequest = serde_json::from_str(
+            r#"{"task": "build", "callback": {"url": "https://example.com/hook", "secret": "s3cret"}}"#,
+        )
+        .unwrap();
+        let resp = submit_task(State(state), Json(req)).await.into_response();
+        assert_eq!(resp.status(), StatusCode::ACCEPTED);
+
+        let task = run_loop.task_queue().dequeue().await.unwrap();
+        let callback = task.callback.unwrap();
+        assert_eq!(callback.url, "https://example.com/hook");
+        assert_eq!(callback.secret.as_deref(), Some("s3cret"));
+    }
+
+    #[tokio::test]
+    async fn test_submit_task_with_correlation_id() {
+        use axum::response::IntoResponse;
+
+        let run_loop = Arc::new(RunLoop::new(RunLoopConfig::default()));
+        let state = Arc::new(RunLoopState::from_runloop(run_loop.clone()));
+
+        let req: RunLoopTaskRequest =
+            serde_json::from_str(r#"{"task": "build", "correlation_id": "trace-7"}"#).unwrap();
+        let resp = submit_task(State(state.clone()), Json(req)).await.into_response();
+        assert_eq!(resp.status(), StatusCode::ACCEPTED);
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["correlation_id"], "trace-7");
+
+        let task = run_loop.task_queue().dequeue().await.unwrap();
+        assert_eq!(task.correlation_id.as_deref(), Some("trace-7"));
+
+        // Without one, the task is traced under its own ID
+        let req: RunLoopTaskRequest = serde_json::from_str(r#"{"task": "build"}"#).unwrap();
+        let resp = submit_task(State(state), Json(req)).await.into_response();
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["correlation_id"], body["task_id"]);
+    }
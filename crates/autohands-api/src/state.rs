@@ -7,9 +7,13 @@ use std::time::Instant;
 
 use tokio::sync::Notify;
 
-use autohands_core::registry::{ProviderRegistry, ToolRegistry};
+use autohands_core::registry::{ChannelRegistry, ProviderRegistry, ToolRegistry};
+use autohands_core::redact::Redactor;
 use autohands_core::Kernel;
-use autohands_runtime::{AgentLoopConfig, AgentRuntime, AgentRuntimeConfig, Session, SessionManager, TranscriptManager};
+use crate::http::capabilities::SkillCatalog;
+use crate::idempotency::{IdempotencyStore, MemoryIdempotencyStore};
+
+use autohands_runtime::{AgentLoopConfig, AgentRuntime, AgentRuntimeConfig, ContextRefresher, ProviderHealthTable, Session, SessionManager, TranscriptManager};
 
 /// Application state shared across handlers.
 pub struct AppState {
@@ -19,6 +23,18 @@ pub struct AppState {
     pub kernel: Arc<Kernel>,
     pub agent_runtime: Arc<AgentRuntime>,
     pub transcript_manager: Arc<TranscriptManager>,
+    /// Idempotency keys of task-creating requests.
+    pub idempotency_store: Arc<dyn IdempotencyStore>,
+    /// Provider health reported by `/health`.
+    pub provider_health: Option<Arc<ProviderHealthTable>>,
+    /// Agent context refreshed by `/admin/reload`.
+    pub context_refresher: Option<Arc<ContextRefresher>>,
+    /// Channels listed by `/capabilities`.
+    pub channel_registry: Option<Arc<ChannelRegistry>>,
+    /// Skills listed by `/capabilities`.
+    pub skill_catalog: Option<Arc<dyn SkillCatalog>>,
+    /// Whether agent runs need a registered provider (degraded mode).
+    pub provider_required: bool,
     start_time: Instant,
     request_count: AtomicU64,
     shutdown_requested: AtomicBool,
@@ -41,6 +57,12 @@ impl AppState {
             kernel,
             agent_runtime,
             transcript_manager: Arc::new(TranscriptManager::new(transcript_dir)),
+            idempotency_store: Arc::new(MemoryIdempotencyStore::default()),
+            provider_health: None,
+            context_refresher: None,
+            channel_registry: None,
+            skill_catalog: None,
+            provider_required: false,
             start_time: Instant::now(),
             request_count: AtomicU64::new(0),
             shutdown_requested: AtomicBool::new(false),
@@ -48,6 +70,56 @@ impl AppState {
         }
     }
 
+    /// Replace the idempotency store.
+    pub fn with_idempotency_store(mut self, store: Arc<dyn IdempotencyStore>) -> Self {
+        self.idempotency_store = store;
+        self
+    }
+
+    /// Report provider health from a table.
+    pub fn with_provider_health(mut self, table: Arc<ProviderHealthTable>) -> Self {
+        self.provider_health = Some(table);
+        self
+    }
+
+    /// Redact session transcripts before they are written.
+    pub fn with_transcript_redactor(mut self, redactor: Arc<dyn Redactor>) -> Self {
+        let base_dir = self.transcript_manager.base_dir().to_path_buf();
+        self.transcript_manager =
+            Arc::new(TranscriptManager::new(base_dir).with_redactor(redactor));
+        self
+    }
+
+    /// Refresh the agents' context on `/admin/reload`.
+    pub fn with_context_refresher(mut self, refresher: Arc<ContextRefresher>) -> Self {
+        self.context_refresher = Some(refresher);
+        self
+    }
+
+    /// List the channels of a registry in `/capabilities`.
+    pub fn with_channels(mut self, registry: Arc<ChannelRegistry>) -> Self {
+        self.channel_registry = Some(registry);
+        self
+    }
+
+    /// List the skills of a catalog in `/capabilities`.
+    pub fn with_skill_catalog(mut self, catalog: Arc<dyn SkillCatalog>) -> Self {
+        self.skill_catalog = Some(catalog);
+        self
+    }
+
+    /// Refuse agent runs, and report degraded health, while the provider
+    /// registry is empty.
+    pub fn with_provider_required(mut self) -> Self {
+        self.provider_required = true;
+        self
+    }
+
+    /// Whether agent runs are refused for want of a provider.
+    pub fn missing_provider(&self) -> bool {
+        self.provider_required && self.provider_registry.list_ids().is_empty()
+    }
+
     /// Get uptime.
     pub fn uptime(&self) -> std::time::Duration {
         self.start_time.elapsed()
@@ -110,6 +182,12 @@ impl Default for AppState {
             kernel: Arc::new(Kernel::new(PathBuf::from("."))),
             agent_runtime,
             transcript_manager: Arc::new(TranscriptManager::new(transcript_dir)),
+            idempotency_store: Arc::new(MemoryIdempotencyStore::default()),
+            provider_health: None,
+            context_refresher: None,
+            channel_registry: None,
+            skill_catalog: None,
+            provider_required: false,
             start_time: Instant::now(),
             request_count: AtomicU64::new(0),
             shutdown_requested: AtomicBool::new(false),
@@ -0,0 +1,247 @@
+//! Task trees of multi-agent runs.
+//!
+//! A task started through the API may chain subtasks and spawn sub-agents,
+//! which chain their own. [`build_task_tree`] walks the RunLoop's
+//! [`TaskGraph`] from the root of a task's chain and attaches the agents
+//! spawned in each session, with their statuses, token usage and links to
+//! traces and transcripts.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use autohands_runloop::{TaskGraph, TaskNode};
+use autohands_runtime::{AgentRuntime, TranscriptManager};
+
+/// An agent spawned by another agent.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SubAgentNode {
+    /// Spawn ID.
+    pub id: String,
+    /// Agent that runs it.
+    pub agent_id: String,
+    /// Session it runs in.
+    pub session_id: String,
+    /// Status, as reported by the spawner.
+    pub status: String,
+    /// Task it was given.
+    pub task: String,
+    /// When it was spawned.
+    pub created_at: DateTime<Utc>,
+    /// When it finished.
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Error on failure.
+    pub error: Option<String>,
+}
+
+/// Agents spawned from agent sessions.
+pub trait SubAgentDirectory: Send + Sync {
+    /// Agents spawned from a session, oldest first.
+    fn spawned_by(&self, session_id: &str) -> Vec<SubAgentNode>;
+}
+
+/// What a node of the tree stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TreeNodeKind {
+    /// A RunLoop task.
+    Task,
+    /// A spawned sub-agent.
+    Agent,
+}
+
+/// Tokens used by a node's session.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct TokenUsage {
+    pub prompt: u64,
+    pub completion: u64,
+    pub total: u64,
+}
+
+/// Links to a node's details.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct TreeLinks {
+    /// Execution trace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace: Option<String>,
+    /// Transcript export, once one was written.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transcript: Option<String>,
+}
+
+/// A node of a task tree.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TaskTreeNode {
+    pub id: String,
+    pub kind: TreeNodeKind,
+    /// Task type, or the agent of a sub-agent.
+    pub label: String,
+    pub status: String,
+    /// Task given to a sub-agent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub session_id: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Tokens used by the session, if it ran an agent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tokens: Option<TokenUsage>,
+    pub links: TreeLinks,
+    pub children: Vec<TaskTreeNode>,
+}
+
+/// A task tree with its totals.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TaskTree {
+    pub root: TaskTreeNode,
+    /// Number of tasks in the tree.
+    pub tasks: usize,
+    /// Number of sub-agents in the tree.
+    pub agents: usize,
+    /// Tokens used by all sessions of the tree, each counted once.
+    pub tokens: TokenUsage,
+}
+
+/// Where a tree's details come from.
+pub struct TaskTreeSources<'a> {
+    pub graph: &'a TaskGraph,
+    pub runtime: &'a AgentRuntime,
+    pub transcripts: &'a TranscriptManager,
+    pub sub_agents: Option<&'a Arc<dyn SubAgentDirectory>>,
+}
+
+/// Build the tree holding a task, looked up by ID or session.
+///
+/// Returns `None` if the task is not in the graph.
+pub fn build_task_tree(sources: &TaskTreeSources<'_>, id: &str) -> Option<TaskTree> {
+    let task_id = Uuid::parse_str(id).ok().filter(|id| sources.graph.get(*id).is_some());
+    let root_id = match task_id {
+        Some(task_id) => sources.graph.root_of(task_id)?,
+        None => sources.graph.root_of_session(id)?,
+    };
+    let root = sources.graph.get(root_id)?;
+
+    let mut builder = TreeBuilder {
+        sources,
+        tasks: 0,
+        agents: 0,
+        tokens: TokenUsage::default(),
+        sessions: HashSet::new(),
+    };
+    let root = builder.task(root);
+    Some(TaskTree {
+        root,
+        tasks: builder.tasks,
+        agents: builder.agents,
+        tokens: builder.tokens,
+    })
+}
+
+struct TreeBuilder<'s, 'a> {
+    sources: &'s TaskTreeSources<'a>,
+    tasks: usize,
+    agents: usize,
+    tokens: TokenUsage,
+    /// Sessions visited, so shared sessions are counted and expanded once.
+    sessions: HashSet<String>,
+}
+
+impl TreeBuilder<'_, '_> {
+    fn task(&mut self, task: TaskNode) -> TaskTreeNode {
+        self.tasks += 1;
+        let (tokens, sub_agents) = self.session(&task.session_id);
+        let mut children: Vec<TaskTreeNode> = self
+            .sources
+            .graph
+            .children(task.id)
+            .into_iter()
+            .map(|child| self.task(child))
+            .collect();
+        children.extend(sub_agents);
+        TaskTreeNode {
+            id: task.id.to_string(),
+            kind: TreeNodeKind::Task,
+            label: task.task_type,
+            status: task.status.as_str().to_string(),
+            description: None,
+            links: self.links(Some(task.id.to_string()), &task.session_id),
+            session_id: task.session_id,
+            created_at: task.created_at,
+            started_at: task.started_at,
+            finished_at: task.finished_at,
+            error: task.error,
+            tokens,
+            children,
+        }
+    }
+
+    fn agent(&mut self, agent: SubAgentNode) -> TaskTreeNode {
+        self.agents += 1;
+        let (tokens, children) = self.session(&agent.session_id);
+        TaskTreeNode {
+            links: self.links(None, &agent.session_id),
+            id: agent.id,
+            kind: TreeNodeKind::Agent,
+            label: agent.agent_id,
+            status: agent.status,
+            description: Some(agent.task),
+            session_id: agent.session_id,
+            created_at: agent.created_at,
+            started_at: Some(agent.created_at),
+            finished_at: agent.finished_at,
+            error: agent.error,
+            tokens,
+            children,
+        }
+    }
+
+    /// Token usage of a session and the agents spawned from it, on its
+    /// first visit.
+    fn session(&mut self, session_id: &str) -> (Option<TokenUsage>, Vec<TaskTreeNode>) {
+        let tokens = self.sources.runtime.progress(session_id).map(|progress| TokenUsage {
+            prompt: progress.prompt_tokens,
+            completion: progress.completion_tokens,
+            total: progress.tokens_used,
+        });
+        if !self.sessions.insert(session_id.to_string()) {
+            return (tokens, Vec::new());
+        }
+        if let Some(tokens) = tokens {
+            self.tokens.prompt += tokens.prompt;
+            self.tokens.completion += tokens.completion;
+            self.tokens.total += tokens.total;
+        }
+        let agents = self
+            .sources
+            .sub_agents
+            .map(|directory| directory.spawned_by(session_id))
+            .unwrap_or_default();
+        let agents = agents.into_iter().map(|agent| self.agent(agent)).collect();
+        (tokens, agents)
+    }
+
+    fn links(&self, task_id: Option<String>, session_id: &str) -> TreeLinks {
+        let transcript = self
+            .sources
+            .transcripts
+            .transcript_path(session_id)
+            .exists()
+            .then(|| format!("/admin/sessions/{}/export", session_id));
+        TreeLinks {
+            trace: task_id.map(|id| format!("/tasks/{}/trace", id)),
+            transcript,
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "task_tree_tests.rs"]
+mod tests;
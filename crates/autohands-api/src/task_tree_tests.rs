@@ -0,0 +1,129 @@
+use super::*;
+
+use autohands_protocols::types::Usage;
+use autohands_runloop::{Task, TaskOutcome};
+use autohands_runtime::StreamEvent;
+
+use crate::state::AppState;
+
+/// Sub-agents with the session that spawned them.
+struct Spawned(Vec<(&'static str, SubAgentNode)>);
+
+impl SubAgentDirectory for Spawned {
+    fn spawned_by(&self, session_id: &str) -> Vec<SubAgentNode> {
+        self.0
+            .iter()
+            .filter(|(parent, _)| *parent == session_id)
+            .map(|(_, agent)| agent.clone())
+            .collect()
+    }
+}
+
+fn sub_agent(id: &str, session_id: &str) -> SubAgentNode {
+    SubAgentNode {
+        id: id.to_string(),
+        agent_id: "researcher".to_string(),
+        session_id: session_id.to_string(),
+        status: "completed".to_string(),
+        task: "Find sources".to_string(),
+        created_at: Utc::now(),
+        finished_at: Some(Utc::now()),
+        error: None,
+    }
+}
+
+fn use_tokens(state: &AppState, session_id: &str, prompt: u32, completion: u32) {
+    let tracker = state.agent_runtime.progress_tracker();
+    tracker.start(session_id);
+    tracker.record(
+        session_id,
+        &StreamEvent::Usage {
+            usage: Usage {
+                prompt_tokens: prompt,
+                completion_tokens: completion,
+                total_tokens: prompt + completion,
+                ..Default::default()
+            },
+        },
+    );
+}
+
+#[test]
+fn test_tree_of_chained_tasks_and_sub_agents() {
+    let state = AppState::default();
+    let graph = TaskGraph::new();
+    let root = Task::new("agent:execute", serde_json::json!({})).with_correlation_id("run-1");
+    let child = Task::new("agent:subtask", serde_json::json!({}))
+        .with_parent(root.id)
+        .with_correlation_id("run-1");
+    let follow_up = Task::new("agent:delayed", serde_json::json!({"session_id": "run-2"}))
+        .with_parent(root.id);
+    for task in [&root, &child, &follow_up] {
+        graph.queued(task);
+    }
+    graph.running(&root);
+    graph.finished(root.id, &TaskOutcome::failed("boom"));
+
+    use_tokens(&state, "run-1", 100, 20);
+    use_tokens(&state, "run-2", 10, 5);
+    use_tokens(&state, "spawn-a", 1, 1);
+    let sub_agents: Arc<dyn SubAgentDirectory> =
+        Arc::new(Spawned(vec![("run-1", sub_agent("a", "spawn-a"))]));
+    let sources = TaskTreeSources {
+        graph: &graph,
+        runtime: &state.agent_runtime,
+        transcripts: &state.transcript_manager,
+        sub_agents: Some(&sub_agents),
+    };
+
+    // Any task of the run leads to the same root
+    let tree = build_task_tree(&sources, &child.id.to_string()).unwrap();
+    assert_eq!(tree, build_task_tree(&sources, &root.id.to_string()).unwrap());
+    assert_eq!(tree.tasks, 3);
+    assert_eq!(tree.agents, 1);
+    // Sessions are counted once though two tasks share one
+    assert_eq!(tree.tokens, TokenUsage { prompt: 111, completion: 26, total: 137 });
+
+    let root_node = &tree.root;
+    assert_eq!(root_node.id, root.id.to_string());
+    assert_eq!(root_node.status, "failed");
+    assert_eq!(root_node.error.as_deref(), Some("boom"));
+    assert_eq!(root_node.links.trace, Some(format!("/tasks/{}/trace", root.id)));
+    assert_eq!(root_node.links.transcript, None);
+    let kinds: Vec<_> = root_node
+        .children
+        .iter()
+        .map(|child| (child.kind, child.label.as_str()))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            (TreeNodeKind::Task, "agent:subtask"),
+            (TreeNodeKind::Task, "agent:delayed"),
+            (TreeNodeKind::Agent, "researcher"),
+        ]
+    );
+    assert_eq!(root_node.children[1].tokens.unwrap().total, 15);
+    assert_eq!(root_node.children[2].description.as_deref(), Some("Find sources"));
+}
+
+#[test]
+fn test_tree_by_session_and_unknown() {
+    let state = AppState::default();
+    let graph = TaskGraph::new();
+    let task = Task::new("agent:execute", serde_json::json!({})).with_correlation_id("run-1");
+    graph.queued(&task);
+    let sources = TaskTreeSources {
+        graph: &graph,
+        runtime: &state.agent_runtime,
+        transcripts: &state.transcript_manager,
+        sub_agents: None,
+    };
+
+    let tree = build_task_tree(&sources, "run-1").unwrap();
+    assert_eq!(tree.root.id, task.id.to_string());
+    assert_eq!(tree.root.status, "queued");
+    assert!(tree.root.tokens.is_none());
+    assert!(build_task_tree(&sources, "run-2").is_none());
+    assert!(build_task_tree(&sources, &uuid::Uuid::new_v4().to_string()).is_none());
+}
@@ -0,0 +1,143 @@
+//! Task template definition.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::InterfaceError;
+
+/// A saved, parameterized prompt.
+///
+/// Placeholders are written as `{name}`. Braces that do not enclose a plain
+/// identifier (letters, digits, `_`, `-`) are left untouched, so JSON snippets
+/// in the prompt survive rendering.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskTemplate {
+    /// Unique template ID.
+    pub id: String,
+    /// Display name.
+    pub name: String,
+    /// Prompt with `{name}` placeholders.
+    pub prompt: String,
+    /// Agent profile used when the caller does not choose one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+    /// Variables that must be supplied to render the prompt.
+    #[serde(default)]
+    pub variables: Vec<String>,
+    /// Optional description.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Creation time.
+    #[serde(default = "Utc::now")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl TaskTemplate {
+    /// Create a template. Required variables default to the placeholders
+    /// found in the prompt.
+    pub fn new(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        prompt: impl Into<String>,
+    ) -> Self {
+        let prompt = prompt.into();
+        Self {
+            id: id.into(),
+            name: name.into(),
+            variables: placeholders(&prompt),
+            prompt,
+            agent: None,
+            description: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Set the default agent profile.
+    pub fn with_agent(mut self, agent: impl Into<String>) -> Self {
+        self.agent = Some(agent.into());
+        self
+    }
+
+    /// Override the required variables.
+    pub fn with_variables(mut self, variables: Vec<String>) -> Self {
+        self.variables = variables;
+        self
+    }
+
+    /// Add a description.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Substitute `values` into the prompt.
+    ///
+    /// Fails if any required variable is missing. Placeholders that are
+    /// neither required nor supplied are kept verbatim.
+    pub fn render(&self, values: &HashMap<String, String>) -> Result<String, InterfaceError> {
+        let missing: Vec<String> = self
+            .variables
+            .iter()
+            .filter(|v| !values.contains_key(*v))
+            .cloned()
+            .collect();
+        if !missing.is_empty() {
+            return Err(InterfaceError::MissingVariables(missing));
+        }
+
+        let mut output = String::with_capacity(self.prompt.len());
+        let mut rest = self.prompt.as_str();
+        while let Some(start) = rest.find('{') {
+            output.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            match after.find('}') {
+                Some(end) if is_identifier(&after[..end]) => {
+                    match values.get(&after[..end]) {
+                        Some(value) => output.push_str(value),
+                        None => output.push_str(&rest[start..start + end + 2]),
+                    }
+                    rest = &after[end + 1..];
+                }
+                _ => {
+                    output.push('{');
+                    rest = after;
+                }
+            }
+        }
+        output.push_str(rest);
+        Ok(output)
+    }
+}
+
+/// Collect the distinct `{name}` placeholders of a prompt, in order.
+pub fn placeholders(prompt: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    let mut rest = prompt;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        match after.find('}') {
+            Some(end) if is_identifier(&after[..end]) => {
+                let name = &after[..end];
+                if !names.iter().any(|n| n == name) {
+                    names.push(name.to_string());
+                }
+                rest = &after[end + 1..];
+            }
+            _ => rest = after,
+        }
+    }
+    names
+}
+
+fn is_identifier(s: &str) -> bool {
+    !s.is_empty()
+        && s
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+#[cfg(test)]
+#[path = "definition_tests.rs"]
+mod tests;
@@ -0,0 +1,60 @@
+//! Tests for task template definitions.
+
+use super::*;
+
+fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_new_collects_placeholders() {
+    let template = TaskTemplate::new(
+        "weekly-report",
+        "Weekly report",
+        "Generate the weekly report for {team} covering {period}. Mention {team} leads.",
+    );
+    assert_eq!(template.variables, vec!["team", "period"]);
+    assert!(template.agent.is_none());
+}
+
+#[test]
+fn test_render() {
+    let template = TaskTemplate::new("t", "T", "Report for {team} ({period})");
+    let prompt = template
+        .render(&vars(&[("team", "infra"), ("period", "W42")]))
+        .unwrap();
+    assert_eq!(prompt, "Report for infra (W42)");
+}
+
+#[test]
+fn test_render_missing_variables() {
+    let template = TaskTemplate::new("t", "T", "Report for {team} ({period})");
+    let err = template.render(&vars(&[("team", "infra")])).unwrap_err();
+    assert!(matches!(err, InterfaceError::MissingVariables(ref m) if m == &["period"]));
+    assert_eq!(err.to_string(), "Missing template variables: period");
+}
+
+#[test]
+fn test_render_leaves_non_placeholders() {
+    let template = TaskTemplate::new("t", "T", r#"Send {"team": "{team}"} and {optional}"#)
+        .with_variables(vec!["team".to_string()]);
+    let prompt = template.render(&vars(&[("team", "infra")])).unwrap();
+    assert_eq!(prompt, r#"Send {"team": "infra"} and {optional}"#);
+}
+
+#[test]
+fn test_placeholders_ignores_unclosed_brace() {
+    assert_eq!(placeholders("a { b {c}"), vec!["c"]);
+    assert!(placeholders("no placeholders").is_empty());
+}
+
+#[test]
+fn test_deserialize_defaults() {
+    let template: TaskTemplate =
+        serde_json::from_str(r#"{"id": "t", "name": "T", "prompt": "Hi {name}"}"#).unwrap();
+    assert!(template.variables.is_empty());
+    assert!(template.render(&HashMap::new()).is_ok());
+}
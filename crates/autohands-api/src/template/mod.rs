@@ -0,0 +1,13 @@
+//! Task template module.
+//!
+//! Saved, parameterized prompts for recurring requests:
+//! - Template definitions with `{name}` placeholders and required variables
+//! - Persistent template store (memory and file-based)
+//! - HTTP API routes for template management and submission
+
+mod definition;
+pub mod routes;
+mod store;
+
+pub use definition::{placeholders, TaskTemplate};
+pub use store::{FileTemplateStore, MemoryTemplateStore, TemplateStore};
@@ -0,0 +1,233 @@
+//! Task template HTTP route handlers.
+//!
+//! Provides CRUD operations and submission for task templates:
+//! - POST   /templates           - Create or replace template
+//! - GET    /templates           - List templates
+//! - GET    /templates/{id}      - Get template
+//! - DELETE /templates/{id}      - Delete template
+//! - POST   /templates/{id}/run  - Render template and submit it to the RunLoop
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info};
+use uuid::Uuid;
+
+use autohands_runloop::{DeliveryTarget, Task};
+
+use super::definition::{placeholders, TaskTemplate};
+use crate::error::InterfaceError;
+use crate::runloop_bridge::{HybridAppState, RunLoopTaskResponse};
+
+/// Response for listing templates.
+#[derive(Debug, Serialize)]
+pub struct TemplateListResponse {
+    pub count: usize,
+    pub templates: Vec<TaskTemplate>,
+}
+
+/// Response for a single template.
+#[derive(Debug, Serialize)]
+pub struct TemplateResponse {
+    pub template: TaskTemplate,
+}
+
+/// Request to run a template.
+#[derive(Debug, Default, Deserialize)]
+pub struct RunTemplateRequest {
+    /// Values for the template placeholders.
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+
+    /// Optional session ID for correlation.
+    pub session_id: Option<String>,
+
+    /// Agent to use instead of the template's default.
+    pub agent_id: Option<String>,
+
+    /// Channels the final result is delivered to, each with its own format.
+    #[serde(default)]
+    pub deliver_to: Vec<DeliveryTarget>,
+}
+
+/// Create or replace a template.
+///
+/// When no required variables are given, they are taken from the prompt's
+/// placeholders.
+///
+/// POST /templates
+pub async fn create_template(
+    State(state): State<Arc<HybridAppState>>,
+    Json(mut template): Json<TaskTemplate>,
+) -> impl IntoResponse {
+    info!("Creating task template: {}", template.id);
+
+    if template.variables.is_empty() {
+        template.variables = placeholders(&template.prompt);
+    }
+
+    if let Err(e) = state.template_store.save(&template).await {
+        error!("Failed to save template: {}", e);
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": e.to_string()})),
+        );
+    }
+
+    (
+        StatusCode::CREATED,
+        Json(serde_json::json!(TemplateResponse { template })),
+    )
+}
+
+/// List all templates.
+///
+/// GET /templates
+pub async fn list_templates(State(state): State<Arc<HybridAppState>>) -> impl IntoResponse {
+    match state.template_store.load_all().await {
+        Ok(mut templates) => {
+            templates.sort_by(|a, b| a.id.cmp(&b.id));
+            let count = templates.len();
+            (
+                StatusCode::OK,
+                Json(serde_json::json!(TemplateListResponse { count, templates })),
+            )
+        }
+        Err(e) => {
+            error!("Failed to list templates: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )
+        }
+    }
+}
+
+/// Get a template by ID.
+///
+/// GET /templates/{id}
+pub async fn get_template(
+    State(state): State<Arc<HybridAppState>>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    match state.template_store.load(&id).await {
+        Ok(Some(template)) => (
+            StatusCode::OK,
+            Json(serde_json::json!(TemplateResponse { template })),
+        ),
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": InterfaceError::TemplateNotFound(id).to_string()})),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": e.to_string()})),
+        ),
+    }
+}
+
+/// Delete a template.
+///
+/// DELETE /templates/{id}
+pub async fn delete_template(
+    State(state): State<Arc<HybridAppState>>,
+    Path(id): Path<String>,
+) -> Response {
+    info!("Deleting task template: {}", id);
+
+    match state.template_store.delete(&id).await {
+        Ok(true) => StatusCode::NO_CONTENT.into_response(),
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": InterfaceError::TemplateNotFound(id).to_string()})),
+        )
+            .into_response(),
+        Err(e) => {
+            error!("Failed to delete template: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// Render a template and submit the prompt as an agent task.
+///
+/// POST /templates/{id}/run
+pub async fn run_template(
+    State(state): State<Arc<HybridAppState>>,
+    Path(id): Path<String>,
+    Json(req): Json<RunTemplateRequest>,
+) -> impl IntoResponse {
+    let session_id = req
+        .session_id
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    let rejected = |status: StatusCode, error: String| {
+        (
+            status,
+            Json(RunLoopTaskResponse {
+                session_id: session_id.clone(),
+                task_id: None,
+                correlation_id: None,
+                status: "error".to_string(),
+                error: Some(error),
+            }),
+        )
+    };
+
+    let template = match state.template_store.load(&id).await {
+        Ok(Some(template)) => template,
+        Ok(None) => {
+            return rejected(
+                StatusCode::NOT_FOUND,
+                InterfaceError::TemplateNotFound(id).to_string(),
+            )
+        }
+        Err(e) => return rejected(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    };
+
+    let prompt = match template.render(&req.variables) {
+        Ok(prompt) => prompt,
+        Err(e) => return rejected(StatusCode::BAD_REQUEST, e.to_string()),
+    };
+
+    info!("Running task template '{}': session={}", id, session_id);
+
+    let payload = serde_json::json!({
+        "prompt": prompt,
+        "session_id": session_id.clone(),
+        "agent_id": req.agent_id.or(template.agent),
+        "template_id": template.id,
+    });
+
+    let mut task = Task::new("agent:execute", payload);
+    task.deliver_to = req.deliver_to;
+    let correlation_id = task.trace_id();
+
+    match state.runloop.submit(task).await {
+        Ok(task_id) => (
+            StatusCode::ACCEPTED,
+            Json(RunLoopTaskResponse {
+                session_id,
+                task_id: Some(task_id.to_string()),
+                correlation_id: Some(correlation_id),
+                status: "queued".to_string(),
+                error: None,
+            }),
+        ),
+        Err(e) => {
+            error!("Failed to submit template task to RunLoop: {}", e);
+            rejected(StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "routes_tests.rs"]
+mod tests;
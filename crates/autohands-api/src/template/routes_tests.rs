@@ -0,0 +1,129 @@
+//! Tests for template routes.
+
+use super::*;
+use crate::http::routes::create_router_with_hybrid_state;
+use crate::runloop_bridge::RunLoopState;
+use crate::state::AppState;
+use autohands_runloop::{RunLoop, RunLoopConfig};
+use axum::body::Body;
+use axum::http::Request;
+use tower::ServiceExt;
+
+fn test_state() -> (Arc<HybridAppState>, Arc<RunLoop>) {
+    let run_loop = Arc::new(RunLoop::new(RunLoopConfig::default()));
+    let state = HybridAppState::new(
+        Arc::new(AppState::default()),
+        Arc::new(RunLoopState::from_runloop(run_loop.clone())),
+        Arc::new(crate::websocket::ApiWsChannel::new()),
+    );
+    (Arc::new(state), run_loop)
+}
+
+fn post_json(uri: &str, body: &str) -> Request<Body> {
+    Request::post(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+async fn json_body(resp: axum::response::Response) -> serde_json::Value {
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[test]
+fn test_template_list_response_serialization() {
+    let response = TemplateListResponse {
+        count: 1,
+        templates: vec![TaskTemplate::new("t", "T", "Hi {name}")],
+    };
+    let json = serde_json::to_value(&response).unwrap();
+    assert_eq!(json["count"], 1);
+    assert_eq!(json["templates"][0]["variables"][0], "name");
+}
+
+#[tokio::test]
+async fn test_create_fills_variables_from_prompt() {
+    let (state, _) = test_state();
+    let resp = create_router_with_hybrid_state(state.clone())
+        .oneshot(post_json(
+            "/templates",
+            r#"{"id": "weekly", "name": "Weekly", "prompt": "Report for {team}"}"#,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    assert_eq!(json_body(resp).await["template"]["variables"][0], "team");
+
+    let stored = state.template_store.load("weekly").await.unwrap().unwrap();
+    assert_eq!(stored.variables, vec!["team"]);
+}
+
+#[tokio::test]
+async fn test_get_missing_template() {
+    let (state, _) = test_state();
+    let resp = create_router_with_hybrid_state(state)
+        .oneshot(Request::get("/templates/missing").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_delete_template() {
+    let (state, _) = test_state();
+    state
+        .template_store
+        .save(&TaskTemplate::new("weekly", "Weekly", "Report for {team}"))
+        .await
+        .unwrap();
+    let delete = || Request::delete("/templates/weekly").body(Body::empty()).unwrap();
+
+    let resp = create_router_with_hybrid_state(state.clone()).oneshot(delete()).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = create_router_with_hybrid_state(state).oneshot(delete()).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    assert!(json_body(resp).await["error"].as_str().unwrap().contains("weekly"));
+}
+
+#[tokio::test]
+async fn test_run_template_queues_task() {
+    let (state, run_loop) = test_state();
+    state
+        .template_store
+        .save(&TaskTemplate::new("weekly", "Weekly", "Report for {team}").with_agent("general"))
+        .await
+        .unwrap();
+
+    let resp = create_router_with_hybrid_state(state)
+        .oneshot(post_json(
+            "/templates/weekly/run",
+            r#"{"variables": {"team": "infra"}, "session_id": "s-1"}"#,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::ACCEPTED);
+    let body = json_body(resp).await;
+    assert_eq!(body["session_id"], "s-1");
+    assert_eq!(body["status"], "queued");
+    assert_eq!(run_loop.pending_task_count().await, 1);
+}
+
+#[tokio::test]
+async fn test_run_template_missing_variables() {
+    let (state, run_loop) = test_state();
+    state
+        .template_store
+        .save(&TaskTemplate::new("weekly", "Weekly", "Report for {team}"))
+        .await
+        .unwrap();
+
+    let resp = create_router_with_hybrid_state(state)
+        .oneshot(post_json("/templates/weekly/run", "{}"))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(json_body(resp).await["error"], "Missing template variables: team");
+    assert_eq!(run_loop.pending_task_count().await, 0);
+}
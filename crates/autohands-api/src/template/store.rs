@@ -0,0 +1,205 @@
+//! Task template persistence store.
+
+use async_trait::async_trait;
+use std::path::PathBuf;
+use tokio::fs;
+use tracing::{debug, warn};
+
+use super::definition::TaskTemplate;
+use crate::error::InterfaceError;
+
+/// Template store trait for persistence.
+#[async_trait]
+pub trait TemplateStore: Send + Sync {
+    /// Save a template, replacing any template with the same ID.
+    async fn save(&self, template: &TaskTemplate) -> Result<(), InterfaceError>;
+
+    /// Load a template by ID.
+    async fn load(&self, id: &str) -> Result<Option<TaskTemplate>, InterfaceError>;
+
+    /// Load all templates.
+    async fn load_all(&self) -> Result<Vec<TaskTemplate>, InterfaceError>;
+
+    /// Delete a template; returns whether it existed.
+    async fn delete(&self, id: &str) -> Result<bool, InterfaceError>;
+}
+
+/// In-memory template store for testing.
+pub struct MemoryTemplateStore {
+    templates: tokio::sync::RwLock<std::collections::HashMap<String, TaskTemplate>>,
+}
+
+impl MemoryTemplateStore {
+    /// Create a new memory store.
+    pub fn new() -> Self {
+        Self {
+            templates: tokio::sync::RwLock::new(std::collections::HashMap::new()),
+        }
+    }
+}
+
+impl Default for MemoryTemplateStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl TemplateStore for MemoryTemplateStore {
+    async fn save(&self, template: &TaskTemplate) -> Result<(), InterfaceError> {
+        let mut templates = self.templates.write().await;
+        templates.insert(template.id.clone(), template.clone());
+        Ok(())
+    }
+
+    async fn load(&self, id: &str) -> Result<Option<TaskTemplate>, InterfaceError> {
+        let templates = self.templates.read().await;
+        Ok(templates.get(id).cloned())
+    }
+
+    async fn load_all(&self) -> Result<Vec<TaskTemplate>, InterfaceError> {
+        let templates = self.templates.read().await;
+        Ok(templates.values().cloned().collect())
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, InterfaceError> {
+        let mut templates = self.templates.write().await;
+        Ok(templates.remove(id).is_some())
+    }
+}
+
+/// File system based template store.
+///
+/// Each template is a JSON file under `<storage_path>/templates/`, so the CLI
+/// and a running server can share the same directory.
+pub struct FileTemplateStore {
+    storage_path: PathBuf,
+}
+
+impl FileTemplateStore {
+    /// Create a new file-based template store.
+    pub async fn new(storage_path: impl Into<PathBuf>) -> Result<Self, InterfaceError> {
+        let storage_path = storage_path.into();
+        let templates_dir = storage_path.join("templates");
+
+        fs::create_dir_all(&templates_dir).await.map_err(|e| {
+            InterfaceError::Custom(format!("Failed to create templates directory: {}", e))
+        })?;
+
+        debug!("FileTemplateStore initialized at {:?}", storage_path);
+
+        Ok(Self { storage_path })
+    }
+
+    fn templates_dir(&self) -> PathBuf {
+        self.storage_path.join("templates")
+    }
+
+    fn template_path(&self, id: &str) -> PathBuf {
+        self.templates_dir().join(format!("{}.json", Self::sanitize_id(id)))
+    }
+
+    fn sanitize_id(id: &str) -> String {
+        id.chars()
+            .map(|c| {
+                if c.is_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl TemplateStore for FileTemplateStore {
+    async fn save(&self, template: &TaskTemplate) -> Result<(), InterfaceError> {
+        let path = self.template_path(&template.id);
+
+        let content = serde_json::to_string_pretty(template).map_err(|e| {
+            InterfaceError::Custom(format!("Failed to serialize template: {}", e))
+        })?;
+
+        fs::write(&path, content).await.map_err(|e| {
+            InterfaceError::Custom(format!("Failed to write template file: {}", e))
+        })?;
+
+        debug!("Saved template '{}' to {:?}", template.id, path);
+        Ok(())
+    }
+
+    async fn load(&self, id: &str) -> Result<Option<TaskTemplate>, InterfaceError> {
+        let path = self.template_path(id);
+
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path).await.map_err(|e| {
+            InterfaceError::Custom(format!("Failed to read template file: {}", e))
+        })?;
+
+        let template: TaskTemplate = serde_json::from_str(&content).map_err(|e| {
+            InterfaceError::Custom(format!("Failed to deserialize template: {}", e))
+        })?;
+
+        Ok(Some(template))
+    }
+
+    async fn load_all(&self) -> Result<Vec<TaskTemplate>, InterfaceError> {
+        let templates_dir = self.templates_dir();
+
+        if !templates_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut templates = Vec::new();
+        let mut entries = fs::read_dir(&templates_dir).await.map_err(|e| {
+            InterfaceError::Custom(format!("Failed to read templates directory: {}", e))
+        })?;
+
+        while let Some(entry) = entries.next_entry().await.map_err(|e| {
+            InterfaceError::Custom(format!("Failed to read directory entry: {}", e))
+        })? {
+            let path = entry.path();
+
+            if path.extension().is_some_and(|ext| ext == "json") {
+                match fs::read_to_string(&path).await {
+                    Ok(content) => match serde_json::from_str::<TaskTemplate>(&content) {
+                        Ok(template) => templates.push(template),
+                        Err(e) => {
+                            warn!("Failed to deserialize template from {:?}: {}", path, e);
+                        }
+                    },
+                    Err(e) => {
+                        warn!("Failed to read template file {:?}: {}", path, e);
+                    }
+                }
+            }
+        }
+
+        debug!("Loaded {} templates from {:?}", templates.len(), templates_dir);
+        Ok(templates)
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, InterfaceError> {
+        let path = self.template_path(id);
+
+        match fs::remove_file(&path).await {
+            Ok(()) => {
+                debug!("Deleted template '{}' from {:?}", id, path);
+                Ok(true)
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(InterfaceError::Custom(format!(
+                "Failed to delete template file: {}",
+                e
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "store_tests.rs"]
+mod tests;
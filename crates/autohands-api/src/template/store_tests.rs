@@ -0,0 +1,58 @@
+//! Tests for template stores.
+
+use super::*;
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn test_memory_template_store() {
+    let store = MemoryTemplateStore::new();
+    let template = TaskTemplate::new("weekly", "Weekly", "Report for {team}");
+
+    store.save(&template).await.unwrap();
+    let loaded = store.load("weekly").await.unwrap().unwrap();
+    assert_eq!(loaded.prompt, "Report for {team}");
+    assert_eq!(store.load_all().await.unwrap().len(), 1);
+
+    assert!(store.delete("weekly").await.unwrap());
+    assert!(store.load("weekly").await.unwrap().is_none());
+    assert!(!store.delete("weekly").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_file_template_store_roundtrip() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = FileTemplateStore::new(temp_dir.path()).await.unwrap();
+    let template = TaskTemplate::new("weekly/report", "Weekly", "Report for {team}")
+        .with_agent("general");
+
+    store.save(&template).await.unwrap();
+    assert!(temp_dir.path().join("templates/weekly_report.json").exists());
+
+    let loaded = store.load("weekly/report").await.unwrap().unwrap();
+    assert_eq!(loaded.id, "weekly/report");
+    assert_eq!(loaded.agent.as_deref(), Some("general"));
+    assert_eq!(loaded.variables, vec!["team"]);
+
+    // A second store over the same directory sees the template
+    let other = FileTemplateStore::new(temp_dir.path()).await.unwrap();
+    assert_eq!(other.load_all().await.unwrap().len(), 1);
+
+    assert!(store.delete("weekly/report").await.unwrap());
+    assert!(store.load("weekly/report").await.unwrap().is_none());
+    assert!(!store.delete("weekly/report").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_file_template_store_skips_invalid_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = FileTemplateStore::new(temp_dir.path()).await.unwrap();
+    std::fs::write(temp_dir.path().join("templates/broken.json"), "not json").unwrap();
+    store
+        .save(&TaskTemplate::new("ok", "Ok", "prompt"))
+        .await
+        .unwrap();
+
+    let all = store.load_all().await.unwrap();
+    assert_eq!(all.len(), 1);
+    assert_eq!(all[0].id, "ok");
+}
@@ -0,0 +1,166 @@
+//! Webhook trigger bindings.
+//!
+//! A binding routes a webhook event to a specific workflow or agent instead
+//! of the generic `trigger:webhook` task. Values are pulled out of the event
+//! payload (`webhook_id`, `method`, `headers`, `query`, `body`, `timestamp`)
+//! with either a JSONPath such as `$.body.issue.number`, which keeps the JSON
+//! type, or a template such as `{{ body.repository.full_name }}#{{ body.issue.number }}`,
+//! which renders a string.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Where a bound event goes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "target", rename_all = "snake_case")]
+pub enum BindingTarget {
+    /// Run a registered workflow with the extracted inputs.
+    Workflow { workflow_id: String },
+    /// Submit an agent task with a prompt rendered from the payload.
+    Agent { agent_id: String, prompt: String },
+}
+
+/// Maps a webhook's events to a workflow or agent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookBinding {
+    /// Target of the binding.
+    #[serde(flatten)]
+    pub target: BindingTarget,
+    /// Conditions on the payload, path to expected value; all must hold.
+    /// Expected strings also match numbers and booleans with the same text.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub when: HashMap<String, Value>,
+    /// Workflow inputs (or extra agent task payload), name to JSONPath or template.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub inputs: HashMap<String, String>,
+}
+
+impl WebhookBinding {
+    /// Bind to a workflow.
+    pub fn workflow(workflow_id: impl Into<String>) -> Self {
+        Self::new(BindingTarget::Workflow {
+            workflow_id: workflow_id.into(),
+        })
+    }
+
+    /// Bind to an agent with a prompt template.
+    pub fn agent(agent_id: impl Into<String>, prompt: impl Into<String>) -> Self {
+        Self::new(BindingTarget::Agent {
+            agent_id: agent_id.into(),
+            prompt: prompt.into(),
+        })
+    }
+
+    fn new(target: BindingTarget) -> Self {
+        Self {
+            target,
+            when: HashMap::new(),
+            inputs: HashMap::new(),
+        }
+    }
+
+    /// Add a condition.
+    pub fn when(mut self, path: impl Into<String>, expected: Value) -> Self {
+        self.when.insert(path.into(), expected);
+        self
+    }
+
+    /// Add an input extraction.
+    pub fn with_input(mut self, name: impl Into<String>, expr: impl Into<String>) -> Self {
+        self.inputs.insert(name.into(), expr.into());
+        self
+    }
+
+    /// Whether the event payload satisfies every condition.
+    pub fn matches(&self, payload: &Value) -> bool {
+        self.when.iter().all(|(path, expected)| {
+            match (lookup(payload, path), expected) {
+                (Some(actual @ (Value::Number(_) | Value::Bool(_))), Value::String(s)) => {
+                    serde_json::from_str::<Value>(s).is_ok_and(|parsed| parsed == *actual)
+                }
+                (Some(actual), expected) => actual == expected,
+                (None, expected) => expected.is_null(),
+            }
+        })
+    }
+
+    /// Extract the inputs from the event payload.
+    pub fn extract_inputs(&self, payload: &Value) -> HashMap<String, Value> {
+        self.inputs
+            .iter()
+            .map(|(name, expr)| (name.clone(), extract(expr, payload)))
+            .collect()
+    }
+
+    /// Short description, e.g. `workflow:triage`.
+    pub fn label(&self) -> String {
+        match &self.target {
+            BindingTarget::Workflow { workflow_id } => format!("workflow:{}", workflow_id),
+            BindingTarget::Agent { agent_id, .. } => format!("agent:{}", agent_id),
+        }
+    }
+}
+
+/// Evaluate an extraction expression against a payload.
+///
+/// Expressions starting with `$` are JSONPaths and return the value found
+/// (null if missing); anything else is rendered as a template string.
+pub fn extract(expr: &str, payload: &Value) -> Value {
+    let trimmed = expr.trim();
+    if trimmed.starts_with('$') {
+        lookup(payload, trimmed).cloned().unwrap_or(Value::Null)
+    } else {
+        Value::String(render(expr, payload))
+    }
+}
+
+/// Replace `{{ path }}` placeholders with values from the payload.
+///
+/// Strings are inserted verbatim, other values serialized; missing paths
+/// render as an empty string.
+pub fn render(template: &str, payload: &Value) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        output.push_str(&rest[..start]);
+
+        match lookup(payload, &rest[start + 2..start + 2 + end]) {
+            Some(Value::String(s)) => output.push_str(s),
+            Some(Value::Null) | None => {}
+            Some(value) => output.push_str(&value.to_string()),
+        }
+
+        rest = &rest[start + 2 + end + 2..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// Look up a path such as `$.body.issues[0].title` or `body.issues.0.title`.
+pub fn lookup<'a>(payload: &'a Value, path: &str) -> Option<&'a Value> {
+    let path = path.trim();
+    let path = path.strip_prefix('$').unwrap_or(path);
+    let mut value = payload;
+    for segment in path.replace('[', ".").replace(']', "").split('.') {
+        if segment.is_empty() {
+            continue;
+        }
+        value = match value {
+            Value::Object(map) => map.get(segment)?,
+            Value::Array(items) => items.get(segment.parse::<usize>().ok()?)?,
+            _ => return None,
+        };
+    }
+    Some(value)
+}
+
+#[cfg(test)]
+#[path = "binding_tests.rs"]
+mod tests;
@@ -0,0 +1,94 @@
+//! Tests for webhook trigger bindings.
+
+use super::*;
+use serde_json::json;
+
+fn issue_opened() -> Value {
+    json!({
+        "webhook_id": "github",
+        "headers": {"x-github-event": "issues"},
+        "body": {
+            "action": "opened",
+            "issue": {"number": 42, "title": "Crash on start", "labels": [{"name": "bug"}]},
+            "repository": {"full_name": "acme/app"},
+        },
+    })
+}
+
+#[test]
+fn test_lookup_paths() {
+    let payload = issue_opened();
+    assert_eq!(lookup(&payload, "$.body.issue.number"), Some(&json!(42)));
+    assert_eq!(lookup(&payload, "body.issue.labels[0].name"), Some(&json!("bug")));
+    assert_eq!(lookup(&payload, "body.issue.labels.0.name"), Some(&json!("bug")));
+    assert_eq!(lookup(&payload, "$"), Some(&payload));
+    assert!(lookup(&payload, "$.body.missing").is_none());
+    assert!(lookup(&payload, "$.body.issue.labels[5]").is_none());
+}
+
+#[test]
+fn test_extract_jsonpath_keeps_type() {
+    let payload = issue_opened();
+    assert_eq!(extract("$.body.issue.number", &payload), json!(42));
+    assert_eq!(extract("$.body.issue.labels", &payload), json!([{"name": "bug"}]));
+    assert_eq!(extract("$.body.nothing", &payload), Value::Null);
+}
+
+#[test]
+fn test_extract_template_renders_string() {
+    let payload = issue_opened();
+    assert_eq!(
+        extract("{{ body.repository.full_name }}#{{ $.body.issue.number }}", &payload),
+        json!("acme/app#42")
+    );
+    assert_eq!(extract("Missing: {{ body.nope }}.", &payload), json!("Missing: ."));
+}
+
+#[test]
+fn test_matches_conditions() {
+    let payload = issue_opened();
+    let binding = WebhookBinding::workflow("triage")
+        .when("headers.x-github-event", json!("issues"))
+        .when("body.action", json!("opened"));
+    assert!(binding.matches(&payload));
+    assert!(WebhookBinding::workflow("triage").matches(&payload));
+
+    let closed = binding.clone().when("body.action", json!("closed"));
+    assert!(!closed.matches(&payload));
+
+    // Strings match numbers with the same text; null matches missing paths
+    assert!(WebhookBinding::workflow("t").when("body.issue.number", json!("42")).matches(&payload));
+    assert!(WebhookBinding::workflow("t").when("body.assignee", Value::Null).matches(&payload));
+    assert!(!WebhookBinding::workflow("t").when("body.assignee", json!("x")).matches(&payload));
+}
+
+#[test]
+fn test_extract_inputs() {
+    let binding = WebhookBinding::workflow("triage")
+        .with_input("repo", "$.body.repository.full_name")
+        .with_input("issue", "$.body.issue.number")
+        .with_input("summary", "#{{ body.issue.number }}: {{ body.issue.title }}");
+    let inputs = binding.extract_inputs(&issue_opened());
+    assert_eq!(inputs["repo"], json!("acme/app"));
+    assert_eq!(inputs["issue"], json!(42));
+    assert_eq!(inputs["summary"], json!("#42: Crash on start"));
+}
+
+#[test]
+fn test_binding_serde() {
+    let binding: WebhookBinding = serde_json::from_value(json!({
+        "target": "workflow",
+        "workflow_id": "triage",
+        "when": {"body.action": "opened"},
+        "inputs": {"issue": "$.body.issue.number"},
+    }))
+    .unwrap();
+    assert_eq!(binding.label(), "workflow:triage");
+    assert_eq!(binding.inputs.len(), 1);
+
+    let agent = WebhookBinding::agent("reviewer", "Review {{ body.pull_request.url }}");
+    let value = serde_json::to_value(&agent).unwrap();
+    assert_eq!(value["target"], "agent");
+    assert!(value.get("when").is_none());
+    assert_eq!(serde_json::from_value::<WebhookBinding>(value).unwrap(), agent);
+}
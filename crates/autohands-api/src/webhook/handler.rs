@@ -17,11 +17,18 @@ use serde_json::Value;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
-use super::types::{WebhookEvent, WebhookRegistration, WebhookResponse};
-use crate::runloop_bridge::HybridAppState;
+use autohands_runloop::{GitHubEvent, Task, TaskSource};
+
+use super::binding::{render, BindingTarget};
+use super::types::{TriggeredBinding, WebhookEvent, WebhookRegistration, WebhookResponse};
+use crate::runloop_bridge::{submit_error_status, HybridAppState};
+use crate::workflow::WorkflowExecution;
 
 type HmacSha256 = Hmac<Sha256>;
 
+/// Agent for GitHub events when the registration names none.
+const DEFAULT_AGENT: &str = "general";
+
 /// Verify a GitHub webhook signature using HMAC-SHA256.
 ///
 /// The `signature_header` is expected in the format `sha256=<hex-digest>`.
@@ -45,6 +52,112 @@ fn verify_github_signature(secret: &str, signature_header: &str, body_bytes: &[u
     mac.verify_slice(&expected_bytes).is_ok()
 }
 
+/// Trigger the registration's bindings that match an event.
+///
+/// Returns `None` when no binding matches, so the caller queues the generic
+/// trigger task. Workflows run in the background and agent bindings are
+/// submitted to the RunLoop; the event is accepted if any binding started.
+async fn dispatch_bindings(
+    state: &HybridAppState,
+    registration: Option<&WebhookRegistration>,
+    event_id: &str,
+    payload: &Value,
+) -> Option<(StatusCode, Json<WebhookResponse>)> {
+    let registration = registration?;
+    let mut triggered = Vec::new();
+    let mut errors = Vec::new();
+
+    for binding in registration.bindings.iter().filter(|b| b.matches(payload)) {
+        let inputs = binding.extract_inputs(payload);
+        let started = match &binding.target {
+            BindingTarget::Workflow { workflow_id } => {
+                start_workflow(state, workflow_id, inputs).await
+            }
+            BindingTarget::Agent { agent_id, prompt } => {
+                let task = Task::new(
+                    "agent:execute",
+                    serde_json::json!({
+                        "prompt": render(prompt, payload),
+                        "session_id": Uuid::new_v4().to_string(),
+                        "agent_id": agent_id,
+                        "webhook_id": registration.id,
+                        "inputs": inputs,
+                    }),
+                )
+                .with_source(TaskSource::Webhook);
+                state
+                    .runloop
+                    .submit(task)
+                    .await
+                    .map(|id| id.to_string())
+                    .map_err(|e| e.to_string())
+            }
+        };
+        match started {
+            Ok(id) => {
+                info!("Webhook '{}' triggered {} ({})", registration.id, binding.label(), id);
+                triggered.push(TriggeredBinding {
+                    target: binding.label(),
+                    id,
+                });
+            }
+            Err(e) => {
+                warn!("Webhook '{}' failed to trigger {}: {}", registration.id, binding.label(), e);
+                errors.push(format!("{}: {}", binding.label(), e));
+            }
+        }
+    }
+
+    if triggered.is_empty() && errors.is_empty() {
+        return None;
+    }
+    if triggered.is_empty() {
+        return Some((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(WebhookResponse::rejected(event_id, errors.join("; "))),
+        ));
+    }
+    Some((
+        StatusCode::ACCEPTED,
+        Json(
+            WebhookResponse::accepted_with_message(
+                event_id,
+                format!("Webhook {} triggered {} binding(s)", registration.id, triggered.len()),
+            )
+            .with_triggered(triggered),
+        ),
+    ))
+}
+
+/// Start a workflow in the background, returning its execution ID.
+async fn start_workflow(
+    state: &HybridAppState,
+    workflow_id: &str,
+    inputs: std::collections::HashMap<String, Value>,
+) -> Result<String, String> {
+    let workflow = state
+        .workflow_store
+        .load(workflow_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Workflow '{}' not found", workflow_id))?;
+    // Fail now rather than in the background when inputs are missing
+    workflow.resolve_inputs(&inputs).map_err(|e| e.to_string())?;
+
+    let mut execution = WorkflowExecution::new(&workflow.id);
+    let execution_id = execution.id.to_string();
+    let executor = state.workflow_executor.clone();
+    tokio::spawn(async move {
+        if let Err(e) = executor
+            .execute_workflow_with_inputs(&workflow, &mut execution, &inputs)
+            .await
+        {
+            warn!("Webhook-triggered workflow '{}' failed: {}", workflow.id, e);
+        }
+    });
+    Ok(execution_id)
+}
+
 /// List registered webhooks.
 ///
 /// GET /webhook/list
@@ -164,10 +277,23 @@ pub async fn handle_webhook(
     let payload = event.to_runloop_payload();
     debug!("Webhook event payload: {:?}", payload);
 
+    if let Some(response) =
+        dispatch_bindings(&state, registration.as_ref(), &event_id, &payload).await
+    {
+        return response;
+    }
+
     // Inject event into RunLoop
     let runloop_state = state.runloop_state();
-    match runloop_state.submit_task("trigger:webhook", payload, None).await {
-        Ok(()) => {
+    let mut task = Task::new("trigger:webhook", payload).with_source(TaskSource::Webhook);
+    if let Some(correlation_id) = headers
+        .get("x-correlation-id")
+        .and_then(|v| v.to_str().ok())
+    {
+        task = task.with_correlation_id(correlation_id);
+    }
+    match runloop_state.submit(task).await {
+        Ok(_) => {
             info!("Webhook event injected into RunLoop: event_id={}", event_id);
             (
                 StatusCode::ACCEPTED,
@@ -180,7 +306,7 @@ pub async fn handle_webhook(
         Err(e) => {
             warn!("Failed to inject webhook event: {}", e);
             (
-                StatusCode::INTERNAL_SERVER_ERROR,
+                submit_error_status(&e),
                 Json(WebhookResponse::rejected(
                     event_id,
                     format!("Failed to queue event: {}", e),
@@ -196,7 +322,10 @@ pub async fn handle_webhook(
 ///
 /// This handler specifically handles GitHub webhook format.
 /// It extracts the raw body bytes for HMAC-SHA256 signature verification
-/// before parsing as JSON.
+/// before parsing as JSON; when the `github` registration has a secret,
+/// unsigned deliveries are rejected. Issue, pull request and check events
+/// are normalized into a `github:<event>.<action>` trigger whose prompt the
+/// agent runs and whose typed event is in the payload's `trigger.data`.
 pub async fn handle_github_webhook(
     State(state): State<Arc<HybridAppState>>,
     headers: HeaderMap,
@@ -220,30 +349,43 @@ pub async fn handle_github_webhook(
         event_type, delivery_id
     );
 
-    // Verify signature if the "github" webhook has a secret configured
-    if let Some(sig_header) = headers.get("x-hub-signature-256") {
-        if let Ok(sig_str) = sig_header.to_str() {
-            // Look up the GitHub webhook registration for its secret
-            let secret = state
-                .webhook_registry()
-                .get("github")
-                .and_then(|reg| reg.secret.clone());
-
-            if let Some(secret) = secret {
-                if !verify_github_signature(&secret, sig_str, &body_bytes) {
-                    warn!("GitHub webhook signature verification failed");
-                    return (
-                        StatusCode::UNAUTHORIZED,
-                        Json(WebhookResponse::rejected(
-                            event_id,
-                            "Signature verification failed".to_string(),
-                        )),
-                    );
-                }
-                debug!("GitHub webhook signature verified successfully");
-            } else {
-                debug!("No secret configured for GitHub webhook, skipping signature verification");
+    // A secret on the "github" registration makes signatures mandatory
+    let registration = state.webhook_registry().get("github");
+    if let Some(reg) = &registration {
+        if !reg.enabled {
+            warn!("GitHub webhook is disabled, rejecting event");
+            return (
+                StatusCode::FORBIDDEN,
+                Json(WebhookResponse::rejected(event_id, "Webhook 'github' is disabled")),
+            );
+        }
+    }
+    match registration.as_ref().and_then(|reg| reg.secret.as_deref()) {
+        Some(secret) => {
+            let signature = headers
+                .get("x-hub-signature-256")
+                .and_then(|v| v.to_str().ok());
+            let Some(signature) = signature else {
+                warn!("GitHub webhook signature missing");
+                return (
+                    StatusCode::UNAUTHORIZED,
+                    Json(WebhookResponse::rejected(event_id, "Missing signature")),
+                );
+            };
+            if !verify_github_signature(secret, signature, &body_bytes) {
+                warn!("GitHub webhook signature verification failed");
+                return (
+                    StatusCode::UNAUTHORIZED,
+                    Json(WebhookResponse::rejected(
+                        event_id,
+                        "Signature verification failed".to_string(),
+                    )),
+                );
             }
+            debug!("GitHub webhook signature verified successfully");
+        }
+        None => {
+            debug!("No secret configured for GitHub webhook, skipping signature verification");
         }
     }
 
@@ -267,14 +409,45 @@ pub async fn handle_github_webhook(
         .with_header("x-github-event", event_type)
         .with_header("x-github-delivery", delivery_id);
 
+    // Normalize issue, PR and check events into a typed trigger event
+    let trigger = match GitHubEvent::parse(event_type, &event.body) {
+        Ok(Some(github_event)) => {
+            let agent = registration
+                .as_ref()
+                .and_then(|reg| reg.agent.as_deref())
+                .unwrap_or(DEFAULT_AGENT);
+            Some(github_event.to_trigger_event("github", agent))
+        }
+        Ok(None) => None,
+        Err(e) => {
+            warn!("Malformed GitHub {} event: {}", event_type, e);
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(WebhookResponse::rejected(event_id, e.to_string())),
+            );
+        }
+    };
+
     // Convert to RunLoop event and inject
-    let payload = event.to_runloop_payload();
+    let mut payload = event.to_runloop_payload();
+    if let Some(trigger) = trigger {
+        payload["prompt"] = Value::String(trigger.prompt.clone());
+        payload["agent_id"] = Value::String(trigger.agent.clone());
+        payload["trigger"] = serde_json::to_value(trigger).unwrap_or_default();
+    }
     debug!("GitHub webhook payload: {:?}", payload);
 
+    if let Some(response) =
+        dispatch_bindings(&state, registration.as_ref(), &event_id, &payload).await
+    {
+        return response;
+    }
+
     // Inject event into RunLoop
     let runloop_state = state.runloop_state();
-    match runloop_state.submit_task("trigger:github", payload, None).await {
-        Ok(()) => {
+    let task = Task::new("trigger:github", payload).with_source(TaskSource::Webhook);
+    match runloop_state.submit(task).await {
+        Ok(_) => {
             info!(
                 "GitHub webhook injected into RunLoop: event={}, event_id={}",
                 event_type, event_id
@@ -290,7 +463,7 @@ pub async fn handle_github_webhook(
         Err(e) => {
             warn!("Failed to inject GitHub webhook event: {}", e);
             (
-                StatusCode::INTERNAL_SERVER_ERROR,
+                submit_error_status(&e),
                 Json(WebhookResponse::rejected(
                     event_id,
                     format!("Failed to queue event: {}", e),
@@ -3,6 +3,7 @@
 //! Provides event-driven trigger capabilities via HTTP webhooks.
 //! All webhook events are converted to RunLoop events for unified processing.
 
+pub mod binding;
 mod handler;
 pub mod registry;
 mod types;
@@ -11,5 +12,6 @@ pub use handler::{
     delete_webhook, get_webhook, handle_github_webhook, handle_webhook, list_webhooks,
     register_webhook,
 };
+pub use binding::{BindingTarget, WebhookBinding};
 pub use registry::WebhookRegistry;
-pub use types::{WebhookEvent, WebhookRegistration, WebhookResponse};
+pub use types::{TriggeredBinding, WebhookEvent, WebhookRegistration, WebhookResponse};
@@ -3,6 +3,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use super::binding::WebhookBinding;
+
 /// Webhook event data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WebhookEvent {
@@ -74,6 +76,18 @@ pub struct WebhookResponse {
     /// Optional message.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
+    /// Bindings the event triggered.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub triggered: Vec<TriggeredBinding>,
+}
+
+/// A binding triggered by a webhook event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TriggeredBinding {
+    /// Binding target, e.g. `workflow:triage`.
+    pub target: String,
+    /// Workflow execution ID or agent task ID.
+    pub id: String,
 }
 
 impl WebhookResponse {
@@ -83,6 +97,7 @@ impl WebhookResponse {
             accepted: true,
             event_id: event_id.into(),
             message: None,
+            triggered: Vec::new(),
         }
     }
 
@@ -92,6 +107,7 @@ impl WebhookResponse {
             accepted: true,
             event_id: event_id.into(),
             message: Some(message.into()),
+            triggered: Vec::new(),
         }
     }
 
@@ -101,8 +117,15 @@ impl WebhookResponse {
             accepted: false,
             event_id: event_id.into(),
             message: Some(reason.into()),
+            triggered: Vec::new(),
         }
     }
+
+    /// Set the triggered bindings.
+    pub fn with_triggered(mut self, triggered: Vec<TriggeredBinding>) -> Self {
+        self.triggered = triggered;
+        self
+    }
 }
 
 /// Webhook registration.
@@ -119,6 +142,10 @@ pub struct WebhookRegistration {
     /// Secret for signature verification (e.g., GitHub webhook secret).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub secret: Option<String>,
+    /// Workflow and agent bindings; events matching none of them are
+    /// queued as a generic trigger task.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bindings: Vec<WebhookBinding>,
 }
 
 impl WebhookRegistration {
@@ -130,6 +157,7 @@ impl WebhookRegistration {
             agent: None,
             enabled: true,
             secret: None,
+            bindings: Vec::new(),
         }
     }
 
@@ -156,6 +184,12 @@ impl WebhookRegistration {
         self.secret = Some(secret.into());
         self
     }
+
+    /// Add a trigger binding.
+    pub fn with_binding(mut self, binding: WebhookBinding) -> Self {
+        self.bindings.push(binding);
+        self
+    }
 }
 
 #[cfg(test)]
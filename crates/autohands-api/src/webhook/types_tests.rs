@@ -41,6 +41,7 @@
             accepted: true,
             event_id: "evt_123".to_string(),
             message: Some("OK".to_string()),
+            triggered: Vec::new(),
         };
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("accepted"));
@@ -53,6 +54,7 @@
             accepted: true,
             event_id: "evt_456".to_string(),
             message: None,
+            triggered: Vec::new(),
         };
         let json = serde_json::to_string(&response).unwrap();
         assert!(!json.contains("message"));
@@ -81,6 +83,7 @@
             agent: Some("deployer".to_string()),
             enabled: true,
             secret: None,
+            bindings: Vec::new(),
         };
         let json = serde_json::to_string(&reg).unwrap();
         assert!(json.contains("github"));
@@ -303,6 +303,20 @@ async fn handle_message_with_runloop(
         } => {
             let session = session_id.unwrap_or_else(|| Uuid::new_v4().to_string());
 
+            if state.base.missing_provider() {
+                tx.send(WsMessage::error(
+                    "NO_PROVIDER",
+                    format!(
+                        "{}. {}",
+                        crate::http::degraded::NO_PROVIDER_ERROR,
+                        crate::http::degraded::PROVIDER_SETUP
+                    ),
+                ))
+                .await
+                .map_err(|e| e.to_string())?;
+                return Ok(());
+            }
+
             // Create RunLoop event payload
             let payload = serde_json::json!({
                 "session_id": session,
@@ -0,0 +1,158 @@
+//! Workflows shipped with AutoHands.
+//!
+//! Registered at startup before the workflow directory is loaded, so a
+//! file with the same ID replaces a built-in workflow.
+
+use tracing::info;
+
+use super::definition::Workflow;
+use super::store::WorkflowStore;
+use super::yaml::parse_workflow_yaml;
+use crate::error::InterfaceError;
+
+/// Review a git ref range or a pull request with the `review` agent and,
+/// optionally, post its findings as review comments.
+const REVIEW_WORKFLOW: &str = r#"
+id: review
+name: Code review
+description: Review a git ref range or a GitHub pull request and report structured findings
+agent: review
+inputs:
+  target:
+    description: Git ref range (e.g. main..HEAD) or pull request URL
+  path:
+    description: Repository checkout holding the ref range
+    default: "."
+  post:
+    description: Post the findings as review comments on the pull request
+    default: false
+steps:
+  - id: findings
+    timeout_secs: 1800
+    prompt: |
+      Review the changes in {{ inputs.target }}.
+      For a git ref range, run git in the repository checkout at {{ inputs.path }}.
+      Do not post anything.
+  - id: publish
+    if: "inputs.post == true"
+    then:
+      id: post
+      tools: [github_review]
+      prompt: |
+        Post these review findings on the pull request {{ inputs.target }} with a single
+        github_review call (event "comment"), passing them unchanged as `findings`.
+        If there are none, post a short body saying no problems were found. If
+        {{ inputs.target }} is not a pull request URL, post nothing and say so.
+
+        {{ findings.data }}
+"#;
+
+/// Triage a new email: classify it, remember it for the digest and, when
+/// it needs an answer, draft a reply for approval. Run by email triggers
+/// with `workflow = "inbox-zero"`.
+const INBOX_ZERO_WORKFLOW: &str = r#"
+id: inbox-zero
+name: Inbox zero
+description: Classify a new email, remember it for the daily digest and draft a reply for approval
+agent: general
+inputs:
+  message:
+    description: The email with its headers, as passed by email triggers
+  from:
+    description: Sender address
+    default: ""
+  subject:
+    description: Subject
+    default: ""
+steps:
+  - id: triage
+    tools: [memory_store]
+    prompt: |
+      Triage this email. Classify it as one of action, reply, fyi or low
+      priority, and decide whether it needs a reply from me.
+      Store a one-line summary with memory_store (tags ["inbox", "<category>"]),
+      naming the sender and subject, so the daily digest can list it.
+      End your answer with a fenced json block:
+      {"category": "...", "needs_reply": true|false, "summary": "..."}
+
+      {{ inputs.message }}
+  - id: reply
+    if: "triage.data.needs_reply == true"
+    then:
+      id: respond
+      steps:
+        - id: draft
+          prompt: |
+            Draft a reply to this email. Answer with the reply text only.
+
+            {{ inputs.message }}
+        - id: approve
+          approval: |
+            Reply to {{ inputs.from }} about "{{ inputs.subject }}"?
+
+            {{ draft.response }}
+          timeout_secs: 86400
+          on_timeout: reject
+        - id: file
+          tools: [memory_store]
+          prompt: |
+            Store this approved reply to {{ inputs.from }} about "{{ inputs.subject }}"
+            with memory_store (tags ["inbox", "reply"]) so the daily digest lists it
+            as ready to send.
+
+            {{ draft.response }}
+"#;
+
+/// Summarize what `inbox-zero` triaged into a digest, optionally sent with
+/// `notify_send`. Meant to run once a day.
+const INBOX_DIGEST_WORKFLOW: &str = r#"
+id: inbox-digest
+name: Inbox digest
+description: Summarize the mail triaged by inbox-zero into a daily digest
+agent: general
+inputs:
+  period:
+    description: Period the digest covers
+    default: the last 24 hours
+  notify:
+    description: Send the digest with notify_send
+    default: false
+steps:
+  - id: digest
+    tools: [memory_search, memory_get]
+    prompt: |
+      Write my inbox digest for {{ inputs.period }}. Search memory for entries
+      tagged "inbox" from that period. Group them by category, action items
+      first, then replies ready to send, then the rest in one line each.
+  - id: publish
+    if: "inputs.notify == true"
+    then:
+      id: send
+      tools: [notify_send]
+      prompt: |
+        Send this digest with notify_send, titled "Inbox digest".
+
+        {{ digest.response }}
+"#;
+
+/// The built-in workflows.
+pub fn builtin_workflows() -> Vec<Workflow> {
+    [REVIEW_WORKFLOW, INBOX_ZERO_WORKFLOW, INBOX_DIGEST_WORKFLOW]
+        .into_iter()
+        .map(|source| parse_workflow_yaml(source).expect("built-in workflow is valid"))
+        .collect()
+}
+
+/// Save the built-in workflows to `store`, returning how many were saved.
+pub async fn register_builtin_workflows(store: &dyn WorkflowStore) -> Result<usize, InterfaceError> {
+    let workflows = builtin_workflows();
+    for workflow in &workflows {
+        store.save(workflow).await?;
+        info!("Registered built-in workflow '{}'", workflow.id);
+    }
+    Ok(workflows.len())
+}
+
+#[cfg(test)]
+#[path = "builtin_tests.rs"]
+mod tests;
@@ -0,0 +1,108 @@
+use super::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::workflow::definition::WorkflowExecution;
+use crate::workflow::store::MemoryWorkflowStore;
+use crate::workflow::{MockAgentExecutor, WorkflowExecutor};
+
+#[tokio::test]
+async fn test_register_builtin_workflows() {
+    let store = MemoryWorkflowStore::new();
+    assert_eq!(register_builtin_workflows(&store).await.unwrap(), 3);
+
+    let review = store.load("review").await.unwrap().unwrap();
+    let target = review.inputs.iter().find(|i| i.name == "target").unwrap();
+    assert!(target.required);
+    assert!(review.inputs.iter().all(|i| i.name == "target" || !i.required));
+
+    // Email triggers pass the message; the digest needs nothing
+    let inbox = store.load("inbox-zero").await.unwrap().unwrap();
+    assert!(inbox.inputs.iter().all(|i| i.name == "message" || !i.required));
+    assert!(inbox.root.requires_approval());
+    let digest = store.load("inbox-digest").await.unwrap().unwrap();
+    assert!(digest.inputs.iter().all(|i| !i.required));
+}
+
+#[tokio::test]
+async fn test_review_workflow_posts_only_when_asked() {
+    let review = builtin_workflows().into_iter().find(|w| w.id == "review").unwrap();
+    let agents = Arc::new(MockAgentExecutor::new());
+    agents
+        .set_response("review", serde_json::json!({"data": [{"file": "a.rs", "line": 1}]}))
+        .await;
+    let executor = WorkflowExecutor::new(agents);
+
+    let inputs = HashMap::from([(
+        "target".to_string(),
+        serde_json::json!("https://github.com/acme/app/pull/9"),
+    )]);
+    let mut execution = WorkflowExecution::new("review");
+    let context = executor
+        .execute_workflow_with_inputs(&review, &mut execution, &inputs)
+        .await
+        .unwrap();
+    assert!(context.get("findings").is_some());
+    assert!(context.get("post").is_none());
+
+    let mut inputs = inputs;
+    inputs.insert("post".to_string(), serde_json::json!(true));
+    let mut execution = WorkflowExecution::new("review");
+    let context = executor
+        .execute_workflow_with_inputs(&review, &mut execution, &inputs)
+        .await
+        .unwrap();
+    assert!(context.get("post").is_some());
+}
+
+#[tokio::test]
+async fn test_inbox_zero_drafts_reply_for_approval() {
+    use autohands_core::ApprovalService;
+    use autohands_protocols::approval::ApprovalDecision;
+
+    let inbox = builtin_workflows().into_iter().find(|w| w.id == "inbox-zero").unwrap();
+    let inputs = HashMap::from([
+        ("message".to_string(), serde_json::json!("From: bob@example.com\nSubject: Lunch?")),
+        ("from".to_string(), serde_json::json!("bob@example.com")),
+        ("subject".to_string(), serde_json::json!("Lunch?")),
+    ]);
+
+    // Mail that needs no reply is only triaged
+    let agents = Arc::new(MockAgentExecutor::new());
+    agents
+        .set_response("general", serde_json::json!({"response": "fyi", "data": {"needs_reply": false}}))
+        .await;
+    let executor = WorkflowExecutor::new(agents.clone());
+    let mut execution = WorkflowExecution::new("inbox-zero");
+    let context = executor
+        .execute_workflow_with_inputs(&inbox, &mut execution, &inputs)
+        .await
+        .unwrap();
+    assert!(context.get("triage").is_some());
+    assert!(context.get("draft").is_none());
+
+    agents
+        .set_response("general", serde_json::json!({"response": "Sure, noon works.", "data": {"needs_reply": true}}))
+        .await;
+    let gate = Arc::new(ApprovalService::new());
+    let executor = WorkflowExecutor::new(agents).with_approval_gate(gate.clone());
+    let approver = tokio::spawn(async move {
+        loop {
+            if let Some(request) = gate.pending().await.into_iter().next() {
+                gate.decide(&request.id, ApprovalDecision::approved()).await;
+                return request;
+            }
+            tokio::task::yield_now().await;
+        }
+    });
+    let mut execution = WorkflowExecution::new("inbox-zero");
+    let context = executor
+        .execute_workflow_with_inputs(&inbox, &mut execution, &inputs)
+        .await
+        .unwrap();
+
+    let request = approver.await.unwrap();
+    assert!(request.title.starts_with("Reply to bob@example.com about \"Lunch?\"?"));
+    assert!(request.title.contains("Sure, noon works."));
+    assert!(context.get("file").is_some());
+}
@@ -1,16 +1,61 @@
 //! Workflow definitions.
 
+use std::collections::{HashMap, HashSet};
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use autohands_protocols::approval::ApprovalTimeoutPolicy;
+
+use crate::error::InterfaceError;
+
+/// Default upper bound on loop iterations.
+pub const DEFAULT_MAX_ITERATIONS: u32 = 100;
+
+/// Variable holding a workflow's resolved inputs.
+pub const INPUTS_VAR: &str = "inputs";
+
+fn default_max_iterations() -> u32 {
+    DEFAULT_MAX_ITERATIONS
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn is_zero(n: &u32) -> bool {
+    *n == 0
+}
+
 /// Workflow step type.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum StepType {
     /// Execute a single agent.
-    Agent { agent: String, prompt: String },
+    Agent {
+        agent: String,
+        prompt: String,
+        /// Tools the agent may use (all tools if empty).
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        tools: Vec<String>,
+    },
+    /// Call a tool directly, without an agent.
+    Tool {
+        tool: String,
+        /// Tool parameters; string values support `{{ }}` templating.
+        #[serde(default)]
+        params: serde_json::Value,
+    },
     /// Execute steps in parallel.
-    Parallel { steps: Vec<WorkflowStep> },
+    Parallel {
+        steps: Vec<WorkflowStep>,
+        /// Maximum number of steps running at once (unbounded if `None`).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_concurrency: Option<usize>,
+        /// When the group is considered finished.
+        #[serde(default)]
+        join: JoinMode,
+    },
     /// Execute steps in sequence.
     Sequential { steps: Vec<WorkflowStep> },
     /// Conditional branch.
@@ -19,11 +64,72 @@ pub enum StepType {
         if_true: Box<WorkflowStep>,
         if_false: Option<Box<WorkflowStep>>,
     },
+    /// Pause until a person approves.
+    ///
+    /// The step's `timeout_secs` bounds the wait; `on_timeout` decides the
+    /// outcome when nobody answers (the gate's default if unset).
+    Approval {
+        /// What the reviewer is asked to approve (supports `{{ }}` templating).
+        message: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        on_timeout: Option<ApprovalTimeoutPolicy>,
+    },
     /// Wait for an event.
     WaitForEvent {
         event_type: String,
         timeout_secs: Option<u64>,
     },
+    /// Repeat the body while the condition holds.
+    Loop {
+        condition: String,
+        body: Box<WorkflowStep>,
+        #[serde(default = "default_max_iterations")]
+        max_iterations: u32,
+    },
+    /// Run the body once for every element of an array.
+    ///
+    /// `items` is a variable path (e.g. `fetch.urls`) resolving to an array;
+    /// each element is bound to `item_var` for the body.
+    ForEach {
+        items: String,
+        item_var: String,
+        body: Box<WorkflowStep>,
+        #[serde(default = "default_max_iterations")]
+        max_iterations: u32,
+    },
+    /// Run the body once for every file matching a glob, several files at
+    /// a time, then optionally reduce the results.
+    ///
+    /// `files` is a glob pattern (supports `{{ }}` templating); each
+    /// matching path is bound to `item_var` for the body. Failed items are
+    /// retried as the body's `retries` says. The `reduce` step sees the
+    /// map results as `<step id>.results`.
+    Map {
+        files: String,
+        item_var: String,
+        body: Box<WorkflowStep>,
+        /// Maximum number of items processed at once (one if `None`).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_concurrency: Option<usize>,
+        /// Succeed, and reduce, even if some items failed.
+        #[serde(default)]
+        allow_failures: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reduce: Option<Box<WorkflowStep>>,
+        #[serde(default = "default_max_iterations")]
+        max_iterations: u32,
+    },
+}
+
+/// Join semantics for parallel step groups.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JoinMode {
+    /// Wait for every step; the group fails if any step fails.
+    #[default]
+    All,
+    /// Finish as soon as one step succeeds; remaining steps are cancelled.
+    Any,
 }
 
 /// A workflow step.
@@ -37,6 +143,9 @@ pub struct WorkflowStep {
     pub step_type: StepType,
     /// Timeout in seconds.
     pub timeout_secs: Option<u64>,
+    /// Extra attempts after a failed run.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub retries: u32,
 }
 
 impl WorkflowStep {
@@ -53,8 +162,10 @@ impl WorkflowStep {
             step_type: StepType::Agent {
                 agent: agent.into(),
                 prompt: prompt.into(),
+                tools: Vec::new(),
             },
             timeout_secs: None,
+            retries: 0,
         }
     }
 
@@ -67,8 +178,34 @@ impl WorkflowStep {
         Self {
             id: id.into(),
             name: name.into(),
-            step_type: StepType::Parallel { steps },
+            step_type: StepType::Parallel {
+                steps,
+                max_concurrency: None,
+                join: JoinMode::All,
+            },
+            timeout_secs: None,
+            retries: 0,
+        }
+    }
+
+    /// Create a parallel step with a concurrency limit and join mode.
+    pub fn parallel_with(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        steps: Vec<WorkflowStep>,
+        max_concurrency: usize,
+        join: JoinMode,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            step_type: StepType::Parallel {
+                steps,
+                max_concurrency: Some(max_concurrency),
+                join,
+            },
             timeout_secs: None,
+            retries: 0,
         }
     }
 
@@ -83,6 +220,25 @@ impl WorkflowStep {
             name: name.into(),
             step_type: StepType::Sequential { steps },
             timeout_secs: None,
+            retries: 0,
+        }
+    }
+
+    /// Create an approval step.
+    pub fn approval(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            step_type: StepType::Approval {
+                message: message.into(),
+                on_timeout: None,
+            },
+            timeout_secs: None,
+            retries: 0,
         }
     }
 
@@ -103,6 +259,103 @@ impl WorkflowStep {
                 if_false: if_false.map(Box::new),
             },
             timeout_secs: None,
+            retries: 0,
+        }
+    }
+
+    /// Create a loop step.
+    pub fn loop_while(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        condition: impl Into<String>,
+        body: WorkflowStep,
+        max_iterations: u32,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            step_type: StepType::Loop {
+                condition: condition.into(),
+                body: Box::new(body),
+                max_iterations,
+            },
+            timeout_secs: None,
+            retries: 0,
+        }
+    }
+
+    /// Create a tool step.
+    pub fn tool(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        tool: impl Into<String>,
+        params: serde_json::Value,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            step_type: StepType::Tool {
+                tool: tool.into(),
+                params,
+            },
+            timeout_secs: None,
+            retries: 0,
+        }
+    }
+
+    /// Create a map step over the files matching `files`.
+    pub fn map(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        files: impl Into<String>,
+        item_var: impl Into<String>,
+        body: WorkflowStep,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            step_type: StepType::Map {
+                files: files.into(),
+                item_var: item_var.into(),
+                body: Box::new(body),
+                max_concurrency: None,
+                allow_failures: false,
+                reduce: None,
+                max_iterations: DEFAULT_MAX_ITERATIONS,
+            },
+            timeout_secs: None,
+            retries: 0,
+        }
+    }
+
+    /// Reduce the results of a map step with `step`. No effect on other
+    /// steps.
+    pub fn with_reduce(mut self, step: WorkflowStep) -> Self {
+        if let StepType::Map { reduce, .. } = &mut self.step_type {
+            *reduce = Some(Box::new(step));
+        }
+        self
+    }
+
+    /// Create a for-each step.
+    pub fn for_each(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        items: impl Into<String>,
+        item_var: impl Into<String>,
+        body: WorkflowStep,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            step_type: StepType::ForEach {
+                items: items.into(),
+                item_var: item_var.into(),
+                body: Box::new(body),
+                max_iterations: DEFAULT_MAX_ITERATIONS,
+            },
+            timeout_secs: None,
+            retries: 0,
         }
     }
 
@@ -111,6 +364,200 @@ impl WorkflowStep {
         self.timeout_secs = Some(secs);
         self
     }
+
+    /// Retry the step up to `retries` more times when it fails.
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Restrict an agent step to the given tools. No effect on other steps.
+    pub fn with_tools(mut self, allowed: Vec<String>) -> Self {
+        if let StepType::Agent { tools, .. } = &mut self.step_type {
+            *tools = allowed;
+        }
+        self
+    }
+
+    /// Collect the IDs of this step and all nested steps.
+    pub fn step_ids(&self) -> Vec<&str> {
+        let mut ids = vec![self.id.as_str()];
+        match &self.step_type {
+            StepType::Parallel { steps, .. } | StepType::Sequential { steps } => {
+                for step in steps {
+                    ids.extend(step.step_ids());
+                }
+            }
+            StepType::Conditional {
+                if_true, if_false, ..
+            } => {
+                ids.extend(if_true.step_ids());
+                if let Some(step) = if_false {
+                    ids.extend(step.step_ids());
+                }
+            }
+            StepType::Loop { body, .. } | StepType::ForEach { body, .. } => {
+                ids.extend(body.step_ids());
+            }
+            StepType::Map { body, reduce, .. } => {
+                ids.extend(body.step_ids());
+                if let Some(step) = reduce {
+                    ids.extend(step.step_ids());
+                }
+            }
+            StepType::Agent { .. }
+            | StepType::Tool { .. }
+            | StepType::Approval { .. }
+            | StepType::WaitForEvent { .. } => {}
+        }
+        ids
+    }
+
+    /// Whether this step or a nested step is an approval.
+    pub fn requires_approval(&self) -> bool {
+        matches!(self.step_type, StepType::Approval { .. })
+            || self.children().iter().any(|child| child.requires_approval())
+    }
+
+    /// Direct child steps.
+    fn children(&self) -> Vec<&WorkflowStep> {
+        match &self.step_type {
+            StepType::Parallel { steps, .. } | StepType::Sequential { steps } => {
+                steps.iter().collect()
+            }
+            StepType::Conditional {
+                if_true, if_false, ..
+            } => std::iter::once(if_true.as_ref())
+                .chain(if_false.as_deref())
+                .collect(),
+            StepType::Loop { body, .. } | StepType::ForEach { body, .. } => vec![body.as_ref()],
+            StepType::Map { body, reduce, .. } => std::iter::once(body.as_ref())
+                .chain(reduce.as_deref())
+                .collect(),
+            StepType::Agent { .. }
+            | StepType::Tool { .. }
+            | StepType::Approval { .. }
+            | StepType::WaitForEvent { .. } => Vec::new(),
+        }
+    }
+
+    fn validate_into(&self, errors: &mut Vec<String>) {
+        let at = |msg: &str| format!("step '{}': {}", self.id, msg);
+        if self.id.trim().is_empty() {
+            errors.push("step with empty id".to_string());
+        }
+        match &self.step_type {
+            StepType::Agent { agent, prompt, .. } => {
+                if agent.trim().is_empty() {
+                    errors.push(at("agent is empty"));
+                }
+                if prompt.trim().is_empty() {
+                    errors.push(at("prompt is empty"));
+                }
+            }
+            StepType::Tool { tool, .. } => {
+                if tool.trim().is_empty() {
+                    errors.push(at("tool is empty"));
+                }
+            }
+            StepType::Parallel {
+                steps,
+                max_concurrency,
+                ..
+            } => {
+                if steps.is_empty() {
+                    errors.push(at("parallel group has no steps"));
+                }
+                if *max_concurrency == Some(0) {
+                    errors.push(at("max_concurrency must be at least 1"));
+                }
+            }
+            StepType::Sequential { steps } => {
+                if steps.is_empty() {
+                    errors.push(at("sequence has no steps"));
+                }
+            }
+            StepType::Conditional { condition, .. } | StepType::Loop { condition, .. } => {
+                if condition.trim().is_empty() {
+                    errors.push(at("condition is empty"));
+                }
+            }
+            StepType::ForEach {
+                items, item_var, ..
+            } => {
+                if items.trim().is_empty() {
+                    errors.push(at("items path is empty"));
+                }
+                if item_var.trim().is_empty() {
+                    errors.push(at("item variable is empty"));
+                }
+            }
+            StepType::Map {
+                files,
+                item_var,
+                max_concurrency,
+                ..
+            } => {
+                if files.trim().is_empty() {
+                    errors.push(at("files pattern is empty"));
+                }
+                if item_var.trim().is_empty() {
+                    errors.push(at("item variable is empty"));
+                }
+                if *max_concurrency == Some(0) {
+                    errors.push(at("max_concurrency must be at least 1"));
+                }
+            }
+            StepType::Approval { message, .. } => {
+                if message.trim().is_empty() {
+                    errors.push(at("approval message is empty"));
+                }
+            }
+            StepType::WaitForEvent { event_type, .. } => {
+                if event_type.trim().is_empty() {
+                    errors.push(at("event type is empty"));
+                }
+            }
+        }
+        for child in self.children() {
+            child.validate_into(errors);
+        }
+    }
+}
+
+/// A declared workflow input.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowInput {
+    /// Input name, available to steps as `inputs.<name>`
+    /// (see [`INPUTS_VAR`]).
+    pub name: String,
+    /// Description shown to users.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Whether a value must be supplied when there is no default.
+    #[serde(default = "default_true")]
+    pub required: bool,
+    /// Value used when none is supplied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
+}
+
+impl WorkflowInput {
+    /// Create a required input.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            required: true,
+            default: None,
+        }
+    }
+
+    /// Set a default value.
+    pub fn with_default(mut self, default: serde_json::Value) -> Self {
+        self.default = Some(default);
+        self
+    }
 }
 
 /// A workflow definition.
@@ -126,6 +573,9 @@ pub struct Workflow {
     pub root: WorkflowStep,
     /// Timeout for entire workflow.
     pub timeout_secs: Option<u64>,
+    /// Declared inputs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inputs: Vec<WorkflowInput>,
 }
 
 impl Workflow {
@@ -137,6 +587,7 @@ impl Workflow {
             description: None,
             root,
             timeout_secs: None,
+            inputs: Vec::new(),
         }
     }
 
@@ -151,6 +602,76 @@ impl Workflow {
         self.timeout_secs = Some(secs);
         self
     }
+
+    /// Declare an input.
+    pub fn with_input(mut self, input: WorkflowInput) -> Self {
+        self.inputs.push(input);
+        self
+    }
+
+    /// Check the definition for structural errors.
+    ///
+    /// Reports every problem found (empty fields, duplicate step IDs,
+    /// duplicate inputs) in a single [`InterfaceError::InvalidWorkflow`].
+    pub fn validate(&self) -> Result<(), InterfaceError> {
+        let mut errors = Vec::new();
+        if self.id.trim().is_empty() {
+            errors.push("workflow id is empty".to_string());
+        }
+
+        let mut seen = HashSet::new();
+        for id in self.root.step_ids() {
+            if !seen.insert(id) {
+                errors.push(format!("duplicate step id '{}'", id));
+            }
+        }
+        self.root.validate_into(&mut errors);
+
+        let mut inputs = HashSet::new();
+        for input in &self.inputs {
+            if !inputs.insert(input.name.as_str()) {
+                errors.push(format!("duplicate input '{}'", input.name));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(InterfaceError::InvalidWorkflow(errors.join("; ")))
+        }
+    }
+
+    /// Combine supplied input values with declared defaults.
+    ///
+    /// Fails if a required input has neither a value nor a default.
+    /// Undeclared values are passed through unchanged.
+    pub fn resolve_inputs(
+        &self,
+        provided: &HashMap<String, serde_json::Value>,
+    ) -> Result<serde_json::Map<String, serde_json::Value>, InterfaceError> {
+        let mut resolved: serde_json::Map<String, serde_json::Value> = provided
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        let mut missing = Vec::new();
+        for input in &self.inputs {
+            if resolved.contains_key(&input.name) {
+                continue;
+            }
+            match &input.default {
+                Some(default) => {
+                    resolved.insert(input.name.clone(), default.clone());
+                }
+                None if input.required => missing.push(input.name.clone()),
+                None => {}
+            }
+        }
+        if missing.is_empty() {
+            Ok(resolved)
+        } else {
+            Err(InterfaceError::MissingVariables(missing))
+        }
+    }
 }
 
 /// Workflow execution state.
@@ -187,6 +708,9 @@ pub struct WorkflowExecution {
     pub step_results: serde_json::Value,
     /// Error message if failed.
     pub error: Option<String>,
+    /// Resolved input values.
+    #[serde(default)]
+    pub inputs: serde_json::Value,
 }
 
 impl WorkflowExecution {
@@ -201,6 +725,7 @@ impl WorkflowExecution {
             current_step: None,
             step_results: serde_json::json!({}),
             error: None,
+            inputs: serde_json::json!({}),
         }
     }
 }
@@ -31,3 +31,35 @@
         assert_eq!(exec.workflow_id, "wf1");
         assert_eq!(exec.state, ExecutionState::Pending);
     }
+
+    #[test]
+    fn test_validate_reports_all_errors() {
+        let root = WorkflowStep::sequential(
+            "root",
+            "Root",
+            vec![
+                WorkflowStep::agent("s1", "One", "general", "Go"),
+                WorkflowStep::agent("s1", "Two", "", "Go"),
+            ],
+        );
+        let err = Workflow::new("wf1", "Test", root).validate().unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("duplicate step id 's1'"));
+        assert!(message.contains("step 's1': agent is empty"));
+    }
+
+    #[test]
+    fn test_resolve_inputs() {
+        let root = WorkflowStep::agent("root", "Root", "general", "Start");
+        let workflow = Workflow::new("wf1", "Test", root)
+            .with_input(WorkflowInput::new("team"))
+            .with_input(WorkflowInput::new("period").with_default(serde_json::json!("week")));
+
+        let err = workflow.resolve_inputs(&HashMap::new()).unwrap_err();
+        assert!(matches!(err, InterfaceError::MissingVariables(names) if names == ["team"]));
+
+        let provided = HashMap::from([("team".to_string(), serde_json::json!("infra"))]);
+        let resolved = workflow.resolve_inputs(&provided).unwrap();
+        assert_eq!(resolved["team"], "infra");
+        assert_eq!(resolved["period"], "week");
+    }
@@ -4,6 +4,7 @@
 #[path = "executor_tests.rs"]
 mod tests;
 
+use std::collections::HashMap;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
@@ -11,17 +12,39 @@ use std::time::Duration;
 
 use tracing::{debug, error, info, warn};
 
+use autohands_protocols::approval::ApprovalGate;
+
 use crate::error::InterfaceError;
 
-use super::definition::{ExecutionState, Workflow, WorkflowExecution, WorkflowStep};
+use super::definition::{
+    ExecutionState, StepType, Workflow, WorkflowExecution, WorkflowStep, INPUTS_VAR,
+};
 use super::executor_types::{
     AgentExecutor, ConditionEvaluator, ExecutionContext, SimpleConditionEvaluator, StepResult,
+    ToolExecutor,
 };
+use super::store::WorkflowStore;
+
+/// Default time a person has to decide an approval step.
+pub const DEFAULT_APPROVAL_STEP_TIMEOUT: Duration = Duration::from_secs(3600);
+
+/// Time past an approval step's timeout left to the gate to apply the
+/// step's timeout policy before the step is cut off.
+const APPROVAL_POLICY_GRACE: Duration = Duration::from_secs(5);
 
 /// Workflow executor that runs workflow steps.
 ///
+/// When a store is attached, execution state is saved after every step so
+/// interrupted executions can be resumed with [`WorkflowExecutor::resume_incomplete`].
+///
+/// Every step and the whole workflow run under a time limit: their own
+/// `timeout_secs`, else the default timeout. Approval steps default to the
+/// approval timeout instead, and steps or workflows containing approvals to
+/// the sum of both. Approval steps are never retried: a decision is final.
+///
 /// Note: WaitForEvent steps currently use a placeholder implementation.
 /// In the future, this should integrate with RunLoop for event subscription.
+#[derive(Clone)]
 pub struct WorkflowExecutor {
     /// Agent executor for running agents.
     pub(crate) agent_executor: Arc<dyn AgentExecutor>,
@@ -29,6 +52,14 @@ pub struct WorkflowExecutor {
     pub(crate) condition_evaluator: Arc<dyn ConditionEvaluator>,
     /// Default timeout for steps.
     pub(crate) default_timeout: Duration,
+    /// Default timeout for approval steps.
+    pub(crate) approval_timeout: Duration,
+    /// Store for step-level execution state.
+    pub(crate) store: Option<Arc<dyn WorkflowStore>>,
+    /// Gate deciding approval steps.
+    pub(crate) approval_gate: Option<Arc<dyn ApprovalGate>>,
+    /// Executor for tool steps.
+    pub(crate) tool_executor: Option<Arc<dyn ToolExecutor>>,
 }
 
 impl WorkflowExecutor {
@@ -38,9 +69,37 @@ impl WorkflowExecutor {
             agent_executor,
             condition_evaluator: Arc::new(SimpleConditionEvaluator),
             default_timeout: Duration::from_secs(300),
+            approval_timeout: DEFAULT_APPROVAL_STEP_TIMEOUT,
+            store: None,
+            approval_gate: None,
+            tool_executor: None,
         }
     }
 
+    /// Run agent steps with another executor.
+    pub fn with_agent_executor(mut self, agent_executor: Arc<dyn AgentExecutor>) -> Self {
+        self.agent_executor = agent_executor;
+        self
+    }
+
+    /// Run tool steps with `executor`; without one they fail.
+    pub fn with_tool_executor(mut self, executor: Arc<dyn ToolExecutor>) -> Self {
+        self.tool_executor = Some(executor);
+        self
+    }
+
+    /// Set the gate that decides approval steps.
+    pub fn with_approval_gate(mut self, gate: Arc<dyn ApprovalGate>) -> Self {
+        self.approval_gate = Some(gate);
+        self
+    }
+
+    /// Persist execution state to a store.
+    pub fn with_store(mut self, store: Arc<dyn WorkflowStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
     /// Set a custom condition evaluator.
     pub fn with_condition_evaluator(
         mut self,
@@ -56,24 +115,122 @@ impl WorkflowExecutor {
         self
     }
 
+    /// Set the default time a person has to decide an approval step.
+    pub fn with_approval_timeout(mut self, timeout: Duration) -> Self {
+        self.approval_timeout = timeout;
+        self
+    }
+
+    /// Time limit of a step without its own timeout.
+    fn default_step_timeout(&self, step: &WorkflowStep) -> Duration {
+        match &step.step_type {
+            StepType::Approval { .. } => self.approval_timeout,
+            _ if step.requires_approval() => self.default_timeout + self.approval_timeout,
+            _ => self.default_timeout,
+        }
+    }
+
+    /// Seconds a person has to decide an approval step.
+    pub(crate) fn approval_timeout_secs(&self, step: &WorkflowStep) -> u64 {
+        step.timeout_secs
+            .unwrap_or_else(|| self.approval_timeout.as_secs())
+    }
+
     /// Execute a complete workflow.
     pub async fn execute_workflow(
         &self,
         workflow: &Workflow,
         execution: &mut WorkflowExecution,
     ) -> Result<ExecutionContext, InterfaceError> {
+        self.execute_workflow_with_inputs(workflow, execution, &HashMap::new())
+            .await
+    }
+
+    /// Execute a complete workflow with input values.
+    ///
+    /// Inputs are resolved against the workflow's declarations and exposed
+    /// to steps as the `inputs` variable (e.g. `{{ inputs.team }}`).
+    pub async fn execute_workflow_with_inputs(
+        &self,
+        workflow: &Workflow,
+        execution: &mut WorkflowExecution,
+        inputs: &HashMap<String, serde_json::Value>,
+    ) -> Result<ExecutionContext, InterfaceError> {
+        execution.inputs = serde_json::Value::Object(workflow.resolve_inputs(inputs)?);
         info!(
             "Starting workflow execution: {} ({})",
             workflow.id, execution.id
         );
+        self.run(workflow, execution, ExecutionContext::new()).await
+    }
+
+    /// Resume an interrupted execution, skipping steps that already completed.
+    pub async fn resume_workflow(
+        &self,
+        workflow: &Workflow,
+        execution: &mut WorkflowExecution,
+    ) -> Result<ExecutionContext, InterfaceError> {
+        let step_results =
+            serde_json::from_value(execution.step_results.clone()).unwrap_or_default();
+        let context = ExecutionContext::from_step_results(step_results);
+        info!(
+            "Resuming workflow execution: {} ({}) with {} completed step(s)",
+            workflow.id,
+            execution.id,
+            context.resumed.len()
+        );
+        self.run(workflow, execution, context).await
+    }
+
+    /// Resume every execution the store still records as running.
+    ///
+    /// Intended to be called once at startup. Returns the number of
+    /// executions that were resumed.
+    pub async fn resume_incomplete(&self) -> Result<usize, InterfaceError> {
+        let Some(store) = self.store.clone() else {
+            return Ok(0);
+        };
 
-        let mut context = ExecutionContext::new();
+        let mut resumed = 0;
+        for mut execution in store.list_running_executions().await? {
+            let Some(workflow) = store.load(&execution.workflow_id).await? else {
+                warn!(
+                    "Workflow {} for execution {} no longer exists",
+                    execution.workflow_id, execution.id
+                );
+                execution.state = ExecutionState::Cancelled;
+                execution.error = Some("Workflow definition removed".to_string());
+                execution.ended_at = Some(chrono::Utc::now());
+                store.save_execution(&execution).await?;
+                continue;
+            };
+
+            if let Err(e) = self.resume_workflow(&workflow, &mut execution).await {
+                error!("Failed to resume execution {}: {}", execution.id, e);
+            }
+            resumed += 1;
+        }
+        Ok(resumed)
+    }
+
+    async fn run(
+        &self,
+        workflow: &Workflow,
+        execution: &mut WorkflowExecution,
+        mut context: ExecutionContext,
+    ) -> Result<ExecutionContext, InterfaceError> {
+        context.metadata = serde_json::json!({
+            "execution_id": execution.id,
+            "workflow_id": workflow.id,
+        });
+        context.set(INPUTS_VAR, execution.inputs.clone());
         execution.state = ExecutionState::Running;
+        self.persist_execution(execution).await;
 
         let timeout = workflow
             .timeout_secs
             .map(Duration::from_secs)
-            .unwrap_or(self.default_timeout);
+            .unwrap_or_else(|| self.default_step_timeout(&workflow.root));
 
         let result = match tokio::time::timeout(
             timeout,
@@ -86,6 +243,7 @@ impl WorkflowExecutor {
                 error!("Workflow {} timed out", workflow.id);
                 execution.state = ExecutionState::Failed;
                 execution.error = Some("Workflow timeout".to_string());
+                self.persist_execution(execution).await;
                 return Err(InterfaceError::Timeout);
             }
         };
@@ -109,6 +267,7 @@ impl WorkflowExecutor {
                 execution.state = ExecutionState::Failed;
                 execution.error = Some(e.to_string());
                 error!("Workflow {} execution error: {}", workflow.id, e);
+                self.persist_execution(execution).await;
                 return Err(e);
             }
         }
@@ -116,10 +275,34 @@ impl WorkflowExecutor {
         execution.ended_at = Some(chrono::Utc::now());
         execution.step_results =
             serde_json::to_value(&context.step_results).unwrap_or_default();
+        self.persist_execution(execution).await;
 
         Ok(context)
     }
 
+    async fn persist_execution(&self, execution: &WorkflowExecution) {
+        if let Some(store) = &self.store {
+            if let Err(e) = store.save_execution(execution).await {
+                warn!("Failed to persist execution {}: {}", execution.id, e);
+            }
+        }
+    }
+
+    pub(crate) async fn persist_step_result(&self, context: &ExecutionContext, result: &StepResult) {
+        let Some(store) = &self.store else {
+            return;
+        };
+        let Some(execution_id) = context.metadata["execution_id"]
+            .as_str()
+            .and_then(|id| id.parse().ok())
+        else {
+            return;
+        };
+        if let Err(e) = store.save_step_result(execution_id, result).await {
+            warn!("Failed to persist step {}: {}", result.step_id, e);
+        }
+    }
+
     /// Execute a single step (boxed for recursion).
     pub fn execute_step<'a>(
         &'a self,
@@ -128,25 +311,50 @@ impl WorkflowExecutor {
     ) -> Pin<Box<dyn Future<Output = Result<StepResult, InterfaceError>> + Send + 'a>>
     {
         Box::pin(async move {
+            if let Some(result) = context.take_resumed(&step.id) {
+                debug!("Skipping step {} completed before restart", step.id);
+                return Ok(result);
+            }
+
             debug!("Executing step: {} ({})", step.name, step.id);
 
             let start = std::time::Instant::now();
-            let timeout = step
-                .timeout_secs
-                .map(Duration::from_secs)
-                .unwrap_or(self.default_timeout);
-
-            let result = match tokio::time::timeout(
-                timeout,
-                self.execute_step_inner(step, context),
-            )
-            .await
-            {
-                Ok(result) => result,
-                Err(_) => {
-                    warn!("Step {} timed out", step.id);
-                    Ok(StepResult::failure(&step.id, "Step timeout"))
+            let is_approval = matches!(step.step_type, StepType::Approval { .. });
+            let timeout = match step.timeout_secs {
+                Some(secs) => Duration::from_secs(secs),
+                None => self.default_step_timeout(step),
+            };
+            // The gate decides timed-out approvals by the step's policy
+            let timeout = if is_approval {
+                timeout + APPROVAL_POLICY_GRACE
+            } else {
+                timeout
+            };
+
+            let mut attempt = 0;
+            let result = loop {
+                let result = match tokio::time::timeout(
+                    timeout,
+                    self.execute_step_inner(step, context),
+                )
+                .await
+                {
+                    Ok(result) => result,
+                    Err(_) => {
+                        warn!("Step {} timed out", step.id);
+                        Ok(StepResult::failure(&step.id, "Step timeout"))
+                    }
+                };
+                let failed = !matches!(&result, Ok(r) if r.success);
+                if failed && !is_approval && attempt < step.retries {
+                    attempt += 1;
+                    warn!(
+                        "Step {} failed, retrying (attempt {}/{})",
+                        step.id, attempt, step.retries
+                    );
+                    continue;
                 }
+                break result;
             };
 
             let duration_ms = start.elapsed().as_millis() as u64;
@@ -154,12 +362,14 @@ impl WorkflowExecutor {
             match result {
                 Ok(mut step_result) => {
                     step_result.duration_ms = duration_ms;
+                    self.persist_step_result(context, &step_result).await;
                     context.record_result(step_result.clone());
                     Ok(step_result)
                 }
                 Err(e) => {
                     let step_result = StepResult::failure(&step.id, e.to_string())
                         .with_duration(duration_ms);
+                    self.persist_step_result(context, &step_result).await;
                     context.record_result(step_result.clone());
                     Ok(step_result)
                 }
@@ -77,3 +77,204 @@ fn test_execution_context() {
     assert_eq!(context.get("key"), Some(&serde_json::json!("value")));
     assert_eq!(context.get("nonexistent"), None);
 }
+
+/// Fails a fixed number of times before succeeding.
+struct FlakyExecutor {
+    failures: std::sync::atomic::AtomicU32,
+}
+
+#[async_trait::async_trait]
+impl AgentExecutor for FlakyExecutor {
+    async fn execute(
+        &self,
+        _agent: &str,
+        _prompt: &str,
+        _context: &ExecutionContext,
+    ) -> Result<serde_json::Value, InterfaceError> {
+        let remaining = self.failures.load(std::sync::atomic::Ordering::SeqCst);
+        if remaining > 0 {
+            self.failures.store(remaining - 1, std::sync::atomic::Ordering::SeqCst);
+            return Err(InterfaceError::Custom("transient".to_string()));
+        }
+        Ok(serde_json::json!("ok"))
+    }
+}
+
+#[tokio::test]
+async fn test_step_retries() {
+    let flaky = |failures| {
+        WorkflowExecutor::new(Arc::new(FlakyExecutor {
+            failures: std::sync::atomic::AtomicU32::new(failures),
+        }))
+    };
+    let step = WorkflowStep::agent("s1", "Flaky", "a", "p").with_retries(2);
+
+    let mut context = ExecutionContext::new();
+    let result = flaky(2).execute_step(&step, &mut context).await.unwrap();
+    assert!(result.success);
+
+    let mut context = ExecutionContext::new();
+    let result = flaky(3).execute_step(&step, &mut context).await.unwrap();
+    assert!(!result.success);
+}
+
+#[tokio::test]
+async fn test_workflow_inputs_are_templated() {
+    let step = WorkflowStep::agent("s1", "Report", "a", "Report for {{ inputs.team }}");
+    let workflow = Workflow::new("wf", "Report", step)
+        .with_input(crate::workflow::definition::WorkflowInput::new("team"));
+    let executor = WorkflowExecutor::new(Arc::new(MockAgentExecutor::new()));
+
+    let mut execution = WorkflowExecution::new("wf");
+    let err = executor
+        .execute_workflow(&workflow, &mut execution)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, InterfaceError::MissingVariables(_)));
+
+    let inputs = HashMap::from([("team".to_string(), serde_json::json!("infra"))]);
+    let mut execution = WorkflowExecution::new("wf");
+    let context = executor
+        .execute_workflow_with_inputs(&workflow, &mut execution, &inputs)
+        .await
+        .unwrap();
+    assert_eq!(execution.inputs["team"], "infra");
+    assert_eq!(
+        context.get("s1").unwrap()["prompt"],
+        "Report for infra"
+    );
+}
+
+#[tokio::test]
+async fn test_approval_step_waits_for_decision() {
+    use autohands_core::ApprovalService;
+    use autohands_protocols::approval::{ApprovalDecision, ApprovalGate};
+
+    let gate = Arc::new(ApprovalService::new());
+    let executor = WorkflowExecutor::new(Arc::new(MockAgentExecutor::new()))
+        .with_approval_gate(gate.clone());
+    let root = WorkflowStep::sequential(
+        "root",
+        "Release",
+        vec![
+            WorkflowStep::approval("confirm", "Confirm", "Ship {{ inputs.version }}?"),
+            WorkflowStep::agent("ship", "Ship", "a", "Ship it"),
+        ],
+    );
+    let workflow = Workflow::new("release", "Release", root);
+
+    let approver = {
+        let gate = gate.clone();
+        tokio::spawn(async move {
+            loop {
+                if let Some(request) = gate.pending().await.into_iter().next() {
+                    return (
+                        request.clone(),
+                        gate.decide(&request.id, ApprovalDecision::approved().by("alice"))
+                            .await,
+                    );
+                }
+                tokio::task::yield_now().await;
+            }
+        })
+    };
+
+    let inputs = HashMap::from([("version".to_string(), serde_json::json!("1.2"))]);
+    let mut execution = WorkflowExecution::new("release");
+    let context = executor
+        .execute_workflow_with_inputs(&workflow, &mut execution, &inputs)
+        .await
+        .unwrap();
+
+    let (request, decided) = approver.await.unwrap();
+    assert!(decided);
+    assert_eq!(request.title, "Ship 1.2?");
+    assert_eq!(request.source, "workflow:release/confirm");
+    assert_eq!(context.get("confirm").unwrap()["decided_by"], "alice");
+    assert!(context.get("ship").is_some());
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_approval_step_timeout_and_missing_gate() {
+    use autohands_core::ApprovalService;
+
+    let step = WorkflowStep::approval("confirm", "Confirm", "Ship?").with_timeout(30);
+
+    let mut context = ExecutionContext::new();
+    let result = WorkflowExecutor::new(Arc::new(MockAgentExecutor::new()))
+        .execute_step(&step, &mut context)
+        .await
+        .unwrap();
+    assert_eq!(result.error.as_deref(), Some("No approval gate configured"));
+
+    let executor = WorkflowExecutor::new(Arc::new(MockAgentExecutor::new()))
+        .with_approval_gate(Arc::new(ApprovalService::new()));
+    let mut context = ExecutionContext::new();
+    let result = executor.execute_step(&step, &mut context).await.unwrap();
+    assert_eq!(result.error.as_deref(), Some("Approval timed out"));
+}
+
+#[tokio::test]
+async fn test_rejected_approval_not_retried() {
+    use autohands_protocols::approval::{ApprovalDecision, ApprovalGate, ApprovalRequest};
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[derive(Default)]
+    struct RejectingGate {
+        requests: AtomicU32,
+    }
+
+    #[async_trait::async_trait]
+    impl ApprovalGate for RejectingGate {
+        async fn request_approval(&self, _request: ApprovalRequest) -> ApprovalDecision {
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            ApprovalDecision::rejected()
+        }
+
+        async fn pending(&self) -> Vec<ApprovalRequest> {
+            Vec::new()
+        }
+
+        async fn decide(&self, _request_id: &str, _decision: ApprovalDecision) -> bool {
+            false
+        }
+    }
+
+    let gate = Arc::new(RejectingGate::default());
+    let executor = WorkflowExecutor::new(Arc::new(MockAgentExecutor::new()))
+        .with_approval_gate(gate.clone());
+    let step = WorkflowStep::approval("confirm", "Confirm", "Ship?").with_retries(3);
+
+    let mut context = ExecutionContext::new();
+    let result = executor.execute_step(&step, &mut context).await.unwrap();
+    assert_eq!(result.error.as_deref(), Some("Approval rejected"));
+    assert_eq!(gate.requests.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_approval_and_workflow_time_limits() {
+    use autohands_core::ApprovalService;
+
+    let executor = WorkflowExecutor::new(Arc::new(MockAgentExecutor::new()))
+        .with_approval_gate(Arc::new(ApprovalService::new()))
+        .with_approval_timeout(Duration::from_secs(60));
+
+    // Approval steps without their own timeout get the approval timeout
+    let step = WorkflowStep::approval("confirm", "Confirm", "Ship?");
+    let started = tokio::time::Instant::now();
+    let mut context = ExecutionContext::new();
+    let result = executor.execute_step(&step, &mut context).await.unwrap();
+    assert_eq!(result.error.as_deref(), Some("Approval timed out"));
+    assert_eq!(started.elapsed().as_secs(), 60);
+
+    // A workflow waiting on people is still bounded
+    let root = WorkflowStep::sequential("root", "Release", vec![step]);
+    let workflow = Workflow::new("release", "Release", root).with_timeout(10);
+    let mut execution = WorkflowExecution::new("release");
+    let err = executor
+        .execute_workflow(&workflow, &mut execution)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, InterfaceError::Timeout));
+    assert_eq!(execution.state, ExecutionState::Failed);
+}
@@ -2,7 +2,7 @@
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::error::InterfaceError;
 
@@ -60,6 +60,9 @@ pub struct ExecutionContext {
     pub step_results: HashMap<String, StepResult>,
     /// Current execution metadata.
     pub metadata: serde_json::Value,
+    /// Steps completed before a restart whose results can be reused.
+    #[serde(skip)]
+    pub(crate) resumed: HashSet<String>,
 }
 
 impl ExecutionContext {
@@ -78,6 +81,47 @@ impl ExecutionContext {
         self.variables.get(key)
     }
 
+    /// Rebuild a context from the step results of an interrupted execution.
+    ///
+    /// Successful steps are marked as resumable so the executor skips them
+    /// and reuses their recorded output.
+    pub fn from_step_results(step_results: HashMap<String, StepResult>) -> Self {
+        let mut context = Self::new();
+        for result in step_results.into_values() {
+            if result.success {
+                context.resumed.insert(result.step_id.clone());
+            }
+            context.record_result(result);
+        }
+        context
+    }
+
+    /// Take the recorded result of a resumable step, if any.
+    pub(crate) fn take_resumed(&mut self, step_id: &str) -> Option<StepResult> {
+        if self.resumed.remove(step_id) {
+            self.step_results.get(step_id).cloned()
+        } else {
+            None
+        }
+    }
+
+    /// Resolve a dotted path such as `fetch.items.0.url`.
+    ///
+    /// The first segment names a variable (usually a step ID); remaining
+    /// segments index into its JSON value by object key or array index.
+    pub fn lookup(&self, path: &str) -> Option<&serde_json::Value> {
+        let mut segments = path.trim().split('.');
+        let mut value = self.variables.get(segments.next()?)?;
+        for segment in segments {
+            value = match value {
+                serde_json::Value::Object(map) => map.get(segment)?,
+                serde_json::Value::Array(items) => items.get(segment.parse::<usize>().ok()?)?,
+                _ => return None,
+            };
+        }
+        Some(value)
+    }
+
     /// Record a step result.
     pub fn record_result(&mut self, result: StepResult) {
         let step_id = result.step_id.clone();
@@ -99,6 +143,34 @@ pub trait AgentExecutor: Send + Sync {
         prompt: &str,
         context: &ExecutionContext,
     ) -> Result<serde_json::Value, InterfaceError>;
+
+    /// Execute an agent limited to the given tools (all tools if empty).
+    ///
+    /// Executors that cannot restrict tools fall back to [`execute`](Self::execute).
+    async fn execute_with_tools(
+        &self,
+        agent: &str,
+        prompt: &str,
+        tools: &[String],
+        context: &ExecutionContext,
+    ) -> Result<serde_json::Value, InterfaceError> {
+        let _ = tools;
+        self.execute(agent, prompt, context).await
+    }
+}
+
+/// Trait for executing tool steps.
+#[async_trait]
+pub trait ToolExecutor: Send + Sync {
+    /// Call a tool, returning its output.
+    ///
+    /// A tool reporting failure is an error.
+    async fn execute_tool(
+        &self,
+        tool: &str,
+        params: serde_json::Value,
+        context: &ExecutionContext,
+    ) -> Result<serde_json::Value, InterfaceError>;
 }
 
 /// Trait for evaluating conditions.
@@ -124,30 +196,17 @@ impl ConditionEvaluator for SimpleConditionEvaluator {
     ) -> Result<bool, InterfaceError> {
         let condition = condition.trim();
 
-        // Check for equality
-        if let Some((left, right)) = condition.split_once("==") {
-            let left = left.trim();
-            let right = right.trim().trim_matches('"');
-            if let Some(value) = context.get(left) {
-                return Ok(value.as_str().is_some_and(|v| v == right)
-                    || value.to_string().trim_matches('"') == right);
+        // Ordered so that two-character operators win over their prefixes.
+        for op in [">=", "<=", "==", "!=", ">", "<"] {
+            if let Some((left, right)) = condition.split_once(op) {
+                let value = context.lookup(left.trim());
+                let right = right.trim().trim_matches('"');
+                return Ok(compare(value, op, right));
             }
-            return Ok(false);
-        }
-
-        // Check for inequality
-        if let Some((left, right)) = condition.split_once("!=") {
-            let left = left.trim();
-            let right = right.trim().trim_matches('"');
-            if let Some(value) = context.get(left) {
-                return Ok(value.as_str().map_or(true, |v| v != right)
-                    && value.to_string().trim_matches('"') != right);
-            }
-            return Ok(true);
         }
 
         // Check for boolean/truthy variable
-        if let Some(value) = context.get(condition) {
+        if let Some(value) = context.lookup(condition) {
             return Ok(value.as_bool().unwrap_or_else(|| {
                 !value.is_null() && value.as_str().map_or(true, |s| !s.is_empty())
             }));
@@ -161,3 +220,44 @@ impl ConditionEvaluator for SimpleConditionEvaluator {
         Ok(false)
     }
 }
+
+/// Compare a resolved value against a literal using `op`.
+///
+/// Ordering operators compare numerically and are false for non-numbers;
+/// equality falls back to string comparison. A missing value only
+/// satisfies `!=`.
+fn compare(value: Option<&serde_json::Value>, op: &str, right: &str) -> bool {
+    let Some(value) = value else {
+        return op == "!=";
+    };
+    let left = value
+        .as_str()
+        .map(str::to_string)
+        .unwrap_or_else(|| value.to_string());
+
+    match op {
+        "==" => left == right,
+        "!=" => left != right,
+        _ => {
+            let (Some(l), Ok(r)) = (value_as_f64(value), right.parse::<f64>()) else {
+                return false;
+            };
+            match op {
+                ">=" => l >= r,
+                "<=" => l <= r,
+                ">" => l > r,
+                "<" => l < r,
+                _ => false,
+            }
+        }
+    }
+}
+
+fn value_as_f64(value: &serde_json::Value) -> Option<f64> {
+    match value {
+        serde_json::Value::Number(n) => n.as_f64(),
+        serde_json::Value::String(s) => s.trim().parse().ok(),
+        serde_json::Value::Array(items) => Some(items.len() as f64),
+        _ => None,
+    }
+}
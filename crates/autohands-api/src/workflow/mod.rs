@@ -1,25 +1,50 @@
 //! Workflow orchestration module.
 //!
 //! Provides multi-step task orchestration capabilities:
-//! - Workflow definitions (Sequential, Parallel, Conditional)
+//! - Workflow definitions (Sequential, Parallel, Conditional, Loop, ForEach, Map)
+//! - `{{ step.field }}` templating of step outputs into prompts
 //! - Workflow execution with timeout support
+//! - Persistent (SQLite) executions resumable after a restart
 //! - Step result tracking
+//! - Agent and tool steps run on the agent runtime
+//! - Built-in `review`, `inbox-zero` and `inbox-digest` workflows
 //! - Task-driven coordination with RunLoop
+//! - YAML workflow files loaded from a directory
 //! - HTTP API routes for workflow management
 
+mod builtin;
 mod definition;
 mod executor;
 mod executor_types;
 mod mock_executor;
 pub mod routes;
+mod runtime_executor;
+mod sqlite_store;
 pub mod store;
+mod template;
 mod workflow_composite;
+mod workflow_loops;
+mod workflow_map;
 mod workflow_steps;
+mod yaml;
 
-pub use definition::{ExecutionState, StepType, Workflow, WorkflowExecution, WorkflowStep};
-pub use executor::WorkflowExecutor;
+pub use builtin::{builtin_workflows, register_builtin_workflows};
+pub use definition::{
+    DEFAULT_MAX_ITERATIONS, ExecutionState, INPUTS_VAR, JoinMode, StepType, Workflow,
+    WorkflowExecution, WorkflowInput, WorkflowStep,
+};
+pub use executor::{WorkflowExecutor, DEFAULT_APPROVAL_STEP_TIMEOUT};
 pub use executor_types::{
     AgentExecutor, ConditionEvaluator, ExecutionContext, SimpleConditionEvaluator, StepResult,
+    ToolExecutor,
 };
 pub use mock_executor::MockAgentExecutor;
+pub use runtime_executor::RuntimeAgentExecutor;
+pub use sqlite_store::SqliteWorkflowStore;
 pub use store::{MemoryWorkflowStore, WorkflowStore};
+pub use template::{render_template, render_value};
+pub use workflow_loops::ITERATION_VAR;
+pub use yaml::{
+    is_workflow_file, load_workflow_dir, load_workflow_file, parse_workflow_yaml,
+    register_workflow_dir,
+};
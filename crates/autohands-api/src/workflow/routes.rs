@@ -7,6 +7,7 @@
 //! - POST   /workflows/{id}/run - Run workflow
 //! - DELETE /workflows/{id}  - Delete workflow
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use axum::{
@@ -15,10 +16,11 @@ use axum::{
     response::IntoResponse,
     Json,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tracing::{error, info};
 
 use super::definition::{Workflow, WorkflowExecution};
+use crate::error::InterfaceError;
 use crate::runloop_bridge::HybridAppState;
 
 /// Response for workflow operations.
@@ -45,6 +47,14 @@ pub struct WorkflowRunResponse {
     pub step_results: serde_json::Value,
 }
 
+/// Request to run a workflow.
+#[derive(Debug, Default, Deserialize)]
+pub struct RunWorkflowRequest {
+    /// Values for the workflow's declared inputs.
+    #[serde(default)]
+    pub inputs: HashMap<String, serde_json::Value>,
+}
+
 /// Create a new workflow.
 ///
 /// POST /workflows
@@ -54,6 +64,13 @@ pub async fn create_workflow(
 ) -> impl IntoResponse {
     info!("Creating workflow: {} ({})", workflow.id, workflow.name);
 
+    if let Err(e) = workflow.validate() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": e.to_string()})),
+        );
+    }
+
     let workflow_store = &state.workflow_store;
     if let Err(e) = workflow_store.save(&workflow).await {
         error!("Failed to save workflow: {}", e);
@@ -112,11 +129,15 @@ pub async fn get_workflow(
 
 /// Run a workflow.
 ///
+/// The optional JSON body supplies values for the workflow's inputs.
+///
 /// POST /workflows/{id}/run
 pub async fn run_workflow(
     State(state): State<Arc<HybridAppState>>,
     Path(id): Path<String>,
+    req: Option<Json<RunWorkflowRequest>>,
 ) -> impl IntoResponse {
+    let req = req.map(|Json(req)| req).unwrap_or_default();
     let workflow_store = &state.workflow_store;
 
     // Load the workflow
@@ -143,7 +164,7 @@ pub async fn run_workflow(
 
     match state
         .workflow_executor
-        .execute_workflow(&workflow, &mut execution)
+        .execute_workflow_with_inputs(&workflow, &mut execution, &req.inputs)
         .await
     {
         Ok(_context) => (
@@ -158,8 +179,12 @@ pub async fn run_workflow(
         ),
         Err(e) => {
             error!("Workflow execution failed: {}", e);
+            let status = match e {
+                InterfaceError::MissingVariables(_) => StatusCode::BAD_REQUEST,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
             (
-                StatusCode::INTERNAL_SERVER_ERROR,
+                status,
                 Json(serde_json::json!(WorkflowRunResponse {
                     execution_id,
                     workflow_id: workflow.id,
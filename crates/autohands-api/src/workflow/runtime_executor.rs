@@ -0,0 +1,153 @@
+//! Agent steps run on the [`AgentRuntime`].
+//!
+//! Each agent step gets a session of its own and is traced under the
+//! execution ID. Its output is an object with the agent's final `response`
+//! and, when the response ends with a fenced ```json block, the parsed
+//! block as `data`, so later steps can use `{{ findings.data }}` or
+//! conditions such as `findings.data.0`.
+//!
+//! Tool steps call a single tool through the runtime's [`ToolInvoker`], with
+//! the same approval policy and audit log as agents. Their output has the
+//! tool's `content` and, if it returned any, its structured output as `data`.
+//!
+//! [`ToolInvoker`]: autohands_runtime::ToolInvoker
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::debug;
+
+use autohands_core::ExecutionContext;
+
+use autohands_protocols::agent::RunOverrides;
+use autohands_protocols::error::AgentError;
+use autohands_protocols::types::{Message, MessageRole};
+use autohands_runtime::AgentRuntime;
+
+use crate::error::InterfaceError;
+
+use super::executor_types::{AgentExecutor, ExecutionContext, ToolExecutor};
+
+/// Runs workflow agent and tool steps on the runtime.
+pub struct RuntimeAgentExecutor {
+    runtime: Arc<AgentRuntime>,
+}
+
+impl RuntimeAgentExecutor {
+    pub fn new(runtime: Arc<AgentRuntime>) -> Self {
+        Self { runtime }
+    }
+
+    async fn run(
+        &self,
+        agent: &str,
+        prompt: &str,
+        tools: Option<&[String]>,
+        context: &ExecutionContext,
+    ) -> Result<serde_json::Value, InterfaceError> {
+        let execution_id = context.metadata["execution_id"].as_str().unwrap_or("adhoc");
+        let session_id = format!("workflow-{}-{}", execution_id, uuid::Uuid::new_v4().simple());
+        debug!("Running workflow agent '{}' in session {}", agent, session_id);
+
+        let overrides = RunOverrides {
+            tools: tools.map(<[String]>::to_vec),
+            ..Default::default()
+        };
+        let messages = self
+            .runtime
+            .execute_with_overrides(
+                agent,
+                &session_id,
+                Message::user(prompt),
+                None,
+                Some(execution_id.to_string()),
+                overrides,
+            )
+            .await
+            .map_err(|e| match e {
+                AgentError::NotFound(id) => InterfaceError::AgentNotFound(id),
+                e => InterfaceError::Custom(e.to_string()),
+            })?;
+
+        let response = messages
+            .iter()
+            .rev()
+            .find(|m| m.role == MessageRole::Assistant)
+            .map(|m| m.content.text().to_string())
+            .unwrap_or_default();
+        let mut output = serde_json::json!({
+            "response": response,
+            "session_id": session_id,
+        });
+        if let Some(data) = last_json_block(&response) {
+            output["data"] = data;
+        }
+        Ok(output)
+    }
+}
+
+#[async_trait]
+impl AgentExecutor for RuntimeAgentExecutor {
+    async fn execute(
+        &self,
+        agent: &str,
+        prompt: &str,
+        context: &ExecutionContext,
+    ) -> Result<serde_json::Value, InterfaceError> {
+        self.run(agent, prompt, None, context).await
+    }
+
+    async fn execute_with_tools(
+        &self,
+        agent: &str,
+        prompt: &str,
+        tools: &[String],
+        context: &ExecutionContext,
+    ) -> Result<serde_json::Value, InterfaceError> {
+        self.run(agent, prompt, Some(tools), context).await
+    }
+}
+
+#[async_trait]
+impl ToolExecutor for RuntimeAgentExecutor {
+    async fn execute_tool(
+        &self,
+        tool: &str,
+        params: serde_json::Value,
+        context: &ExecutionContext,
+    ) -> Result<serde_json::Value, InterfaceError> {
+        let execution_id = context.metadata["execution_id"].as_str().unwrap_or("adhoc");
+        let session_id = format!("workflow-{}-{}", execution_id, uuid::Uuid::new_v4().simple());
+        let work_dir = std::env::current_dir().unwrap_or_else(|_| ".".into());
+        let ctx = ExecutionContext::new(session_id, work_dir);
+        debug!("Running workflow tool '{}' in session {}", tool, ctx.session_id);
+
+        let result = self
+            .runtime
+            .tool_invoker()
+            .invoke(tool, params, &ctx, None)
+            .await
+            .map_err(|e| InterfaceError::Custom(e.to_string()))?;
+        if !result.success {
+            return Err(InterfaceError::Custom(result.error.unwrap_or(result.content)));
+        }
+
+        let mut output = serde_json::json!({ "content": result.content });
+        if let Some(data) = result.structured_output {
+            output["data"] = data;
+        }
+        Ok(output)
+    }
+}
+
+/// The last fenced ```json block of `text`, if it parses.
+fn last_json_block(text: &str) -> Option<serde_json::Value> {
+    let start = text.rfind("```json")? + "```json".len();
+    let rest = &text[start..];
+    let end = rest.find("```")?;
+    serde_json::from_str(rest[..end].trim()).ok()
+}
+
+#[cfg(test)]
+#[path = "runtime_executor_tests.rs"]
+mod tests;
@@ -0,0 +1,94 @@
+use super::*;
+use std::collections::HashMap;
+
+use autohands_core::registry::{ProviderRegistry, ToolRegistry};
+use autohands_protocols::agent::{Agent, AgentConfig, AgentContext, AgentResponse};
+
+/// Agent answering with a findings block, recording the tools of its runs.
+struct ReviewAgent {
+    config: AgentConfig,
+    tools: std::sync::Mutex<Vec<Option<Vec<String>>>>,
+}
+
+#[async_trait]
+impl Agent for ReviewAgent {
+    fn id(&self) -> &str {
+        &self.config.id
+    }
+
+    fn config(&self) -> &AgentConfig {
+        &self.config
+    }
+
+    async fn process(&self, message: Message, ctx: AgentContext) -> Result<AgentResponse, AgentError> {
+        self.tools.lock().unwrap().push(ctx.overrides.tools.clone());
+        let text = format!(
+            "Reviewed {}.\n```json\n[{{\"file\": \"src/lib.rs\", \"line\": 3}}]\n```",
+            message.content.text()
+        );
+        Ok(AgentResponse {
+            message: Message::assistant(&text),
+            is_complete: true,
+            tool_calls: Vec::new(),
+            metadata: HashMap::new(),
+            usage: None,
+        })
+    }
+
+    fn check_overrides(&self, _overrides: &RunOverrides) -> Result<(), AgentError> {
+        Ok(())
+    }
+}
+
+fn executor() -> (RuntimeAgentExecutor, Arc<ReviewAgent>) {
+    let runtime = AgentRuntime::new(
+        Arc::new(ProviderRegistry::new()),
+        Arc::new(ToolRegistry::new()),
+        Default::default(),
+    );
+    let agent = Arc::new(ReviewAgent {
+        config: AgentConfig::new("review", "Review", "mock-model"),
+        tools: std::sync::Mutex::new(Vec::new()),
+    });
+    runtime.register_agent(agent.clone());
+    (RuntimeAgentExecutor::new(Arc::new(runtime)), agent)
+}
+
+#[tokio::test]
+async fn test_agent_step_output() {
+    let (executor, agent) = executor();
+    let mut context = ExecutionContext::new();
+    context.metadata = serde_json::json!({"execution_id": "exec-1"});
+
+    let output = executor.execute("review", "main..HEAD", &context).await.unwrap();
+    assert!(output["response"].as_str().unwrap().starts_with("Reviewed main..HEAD."));
+    assert!(output["session_id"].as_str().unwrap().starts_with("workflow-exec-1-"));
+    assert_eq!(output["data"][0]["line"], 3);
+
+    let tools = vec!["github_review".to_string()];
+    executor.execute_with_tools("review", "post", &tools, &context).await.unwrap();
+    assert_eq!(*agent.tools.lock().unwrap(), vec![None, Some(tools)]);
+
+    let err = executor.execute("missing", "x", &context).await.unwrap_err();
+    assert!(matches!(err, InterfaceError::AgentNotFound(_)));
+}
+
+#[tokio::test]
+async fn test_tool_step_unknown_tool() {
+    let (executor, _) = executor();
+    let err = executor
+        .execute_tool("missing_tool", serde_json::json!({}), &ExecutionContext::new())
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Tool not found: missing_tool"));
+}
+
+#[test]
+fn test_last_json_block() {
+    assert_eq!(
+        last_json_block("a\n```json\n[1]\n```\nb\n```json\n{\"x\": 2}\n```"),
+        Some(serde_json::json!({"x": 2}))
+    );
+    assert_eq!(last_json_block("```json\nnot json\n```"), None);
+    assert_eq!(last_json_block("no block"), None);
+}
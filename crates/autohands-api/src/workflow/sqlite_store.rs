@@ -0,0 +1,351 @@
+//! SQLite-backed workflow store.
+//!
+//! Persists workflow definitions, executions, and per-step results so that
+//! executions interrupted by a restart can be resumed.
+
+use std::path::Path;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, OptionalExtension};
+use tokio_rusqlite::Connection;
+use uuid::Uuid;
+
+use super::definition::{ExecutionState, Workflow, WorkflowExecution};
+use super::executor_types::StepResult;
+use super::store::WorkflowStore;
+use crate::error::InterfaceError;
+
+const SCHEMA: &str = r#"
+CREATE TABLE IF NOT EXISTS workflows (
+    id TEXT PRIMARY KEY,
+    definition TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS workflow_executions (
+    id TEXT PRIMARY KEY,
+    workflow_id TEXT NOT NULL,
+    state TEXT NOT NULL,
+    started_at TEXT NOT NULL,
+    ended_at TEXT,
+    current_step TEXT,
+    error TEXT,
+    inputs TEXT
+);
+
+CREATE TABLE IF NOT EXISTS workflow_step_results (
+    execution_id TEXT NOT NULL,
+    step_id TEXT NOT NULL,
+    result TEXT NOT NULL,
+    recorded_at TEXT NOT NULL,
+    PRIMARY KEY (execution_id, step_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_workflow_executions_state ON workflow_executions(state);
+"#;
+
+fn store_error(e: impl std::fmt::Display) -> InterfaceError {
+    InterfaceError::Custom(format!("Workflow store error: {}", e))
+}
+
+fn state_to_str(state: ExecutionState) -> String {
+    serde_json::to_value(state)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn state_from_str(state: &str) -> ExecutionState {
+    serde_json::from_value(serde_json::Value::String(state.to_string()))
+        .unwrap_or(ExecutionState::Failed)
+}
+
+/// Raw execution row before step results are attached.
+struct ExecutionRow {
+    id: String,
+    workflow_id: String,
+    state: String,
+    started_at: String,
+    ended_at: Option<String>,
+    current_step: Option<String>,
+    error: Option<String>,
+    inputs: Option<String>,
+}
+
+impl ExecutionRow {
+    const COLUMNS: &'static str =
+        "id, workflow_id, state, started_at, ended_at, current_step, error, inputs";
+
+    fn from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            workflow_id: row.get(1)?,
+            state: row.get(2)?,
+            started_at: row.get(3)?,
+            ended_at: row.get(4)?,
+            current_step: row.get(5)?,
+            error: row.get(6)?,
+            inputs: row.get(7)?,
+        })
+    }
+
+    fn into_execution(self, step_results: serde_json::Value) -> Option<WorkflowExecution> {
+        let parse_time = |s: &str| {
+            DateTime::parse_from_rfc3339(s)
+                .ok()
+                .map(|t| t.with_timezone(&Utc))
+        };
+        Some(WorkflowExecution {
+            id: self.id.parse().ok()?,
+            workflow_id: self.workflow_id,
+            state: state_from_str(&self.state),
+            started_at: parse_time(&self.started_at)?,
+            ended_at: self.ended_at.as_deref().and_then(parse_time),
+            current_step: self.current_step,
+            step_results,
+            error: self.error,
+            inputs: self
+                .inputs
+                .and_then(|s| serde_json::from_str(&s).ok())
+                .unwrap_or_else(|| serde_json::json!({})),
+        })
+    }
+}
+
+fn load_step_results(
+    conn: &rusqlite::Connection,
+    execution_id: &str,
+) -> rusqlite::Result<serde_json::Value> {
+    let mut stmt = conn.prepare(
+        "SELECT step_id, result FROM workflow_step_results WHERE execution_id = ?1",
+    )?;
+    let rows = stmt.query_map(params![execution_id], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    })?;
+
+    let mut results = serde_json::Map::new();
+    for row in rows {
+        let (step_id, result) = row?;
+        if let Ok(value) = serde_json::from_str(&result) {
+            results.insert(step_id, value);
+        }
+    }
+    Ok(serde_json::Value::Object(results))
+}
+
+/// SQLite workflow store.
+pub struct SqliteWorkflowStore {
+    conn: Connection,
+}
+
+impl SqliteWorkflowStore {
+    /// Open (or create) a database file.
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self, InterfaceError> {
+        let conn = Connection::open(path.as_ref().to_path_buf())
+            .await
+            .map_err(store_error)?;
+        Self::init(conn).await
+    }
+
+    /// Create an in-memory database.
+    pub async fn in_memory() -> Result<Self, InterfaceError> {
+        let conn = Connection::open_in_memory().await.map_err(store_error)?;
+        Self::init(conn).await
+    }
+
+    async fn init(conn: Connection) -> Result<Self, InterfaceError> {
+        conn.call(|conn| Ok(conn.execute_batch(SCHEMA)?))
+            .await
+            .map_err(store_error)?;
+        Ok(Self { conn })
+    }
+}
+
+#[async_trait]
+impl WorkflowStore for SqliteWorkflowStore {
+    async fn save(&self, workflow: &Workflow) -> Result<(), InterfaceError> {
+        let id = workflow.id.clone();
+        let definition = serde_json::to_string(workflow).map_err(store_error)?;
+        let now = Utc::now().to_rfc3339();
+        self.conn
+            .call(move |conn| {
+                conn.execute(
+                    "INSERT INTO workflows (id, definition, updated_at) VALUES (?1, ?2, ?3)
+                     ON CONFLICT(id) DO UPDATE SET definition = ?2, updated_at = ?3",
+                    params![id, definition, now],
+                )?;
+                Ok(())
+            })
+            .await
+            .map_err(store_error)
+    }
+
+    async fn load(&self, id: &str) -> Result<Option<Workflow>, InterfaceError> {
+        let id = id.to_string();
+        let definition: Option<String> = self
+            .conn
+            .call(move |conn| {
+                Ok(conn
+                    .query_row(
+                        "SELECT definition FROM workflows WHERE id = ?1",
+                        params![id],
+                        |row| row.get(0),
+                    )
+                    .optional()?)
+            })
+            .await
+            .map_err(store_error)?;
+
+        definition
+            .map(|d| serde_json::from_str(&d).map_err(store_error))
+            .transpose()
+    }
+
+    async fn load_all(&self) -> Result<Vec<Workflow>, InterfaceError> {
+        let definitions: Vec<String> = self
+            .conn
+            .call(|conn| {
+                let mut stmt = conn.prepare("SELECT definition FROM workflows ORDER BY id")?;
+                let rows = stmt.query_map([], |row| row.get(0))?;
+                Ok(rows.collect::<Result<Vec<String>, _>>()?)
+            })
+            .await
+            .map_err(store_error)?;
+
+        definitions
+            .iter()
+            .map(|d| serde_json::from_str(d).map_err(store_error))
+            .collect()
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, InterfaceError> {
+        let id = id.to_string();
+        self.conn
+            .call(move |conn| {
+                Ok(conn.execute("DELETE FROM workflows WHERE id = ?1", params![id])? > 0)
+            })
+            .await
+            .map_err(store_error)
+    }
+
+    async fn save_execution(&self, execution: &WorkflowExecution) -> Result<(), InterfaceError> {
+        let execution = execution.clone();
+        let now = Utc::now().to_rfc3339();
+        self.conn
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                tx.execute(
+                    "INSERT INTO workflow_executions
+                        (id, workflow_id, state, started_at, ended_at, current_step, error, inputs)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                     ON CONFLICT(id) DO UPDATE SET
+                        state = ?3, ended_at = ?5, current_step = ?6, error = ?7",
+                    params![
+                        execution.id.to_string(),
+                        execution.workflow_id,
+                        state_to_str(execution.state),
+                        execution.started_at.to_rfc3339(),
+                        execution.ended_at.map(|t| t.to_rfc3339()),
+                        execution.current_step,
+                        execution.error,
+                        execution.inputs.to_string(),
+                    ],
+                )?;
+                if let Some(results) = execution.step_results.as_object() {
+                    for (step_id, result) in results {
+                        tx.execute(
+                            "INSERT OR REPLACE INTO workflow_step_results
+                                (execution_id, step_id, result, recorded_at)
+                             VALUES (?1, ?2, ?3, ?4)",
+                            params![execution.id.to_string(), step_id, result.to_string(), now],
+                        )?;
+                    }
+                }
+                tx.commit()?;
+                Ok(())
+            })
+            .await
+            .map_err(store_error)
+    }
+
+    async fn save_step_result(
+        &self,
+        execution_id: Uuid,
+        result: &StepResult,
+    ) -> Result<(), InterfaceError> {
+        let step_id = result.step_id.clone();
+        let result = serde_json::to_string(result).map_err(store_error)?;
+        let now = Utc::now().to_rfc3339();
+        self.conn
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                tx.execute(
+                    "INSERT OR REPLACE INTO workflow_step_results
+                        (execution_id, step_id, result, recorded_at)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![execution_id.to_string(), step_id, result, now],
+                )?;
+                tx.execute(
+                    "UPDATE workflow_executions SET current_step = ?2 WHERE id = ?1",
+                    params![execution_id.to_string(), step_id],
+                )?;
+                tx.commit()?;
+                Ok(())
+            })
+            .await
+            .map_err(store_error)
+    }
+
+    async fn load_execution(&self, id: Uuid) -> Result<Option<WorkflowExecution>, InterfaceError> {
+        let id = id.to_string();
+        self.conn
+            .call(move |conn| {
+                let row = conn
+                    .query_row(
+                        &format!(
+                            "SELECT {} FROM workflow_executions WHERE id = ?1",
+                            ExecutionRow::COLUMNS
+                        ),
+                        params![id],
+                        ExecutionRow::from_row,
+                    )
+                    .optional()?;
+                let Some(row) = row else {
+                    return Ok(None);
+                };
+                let step_results = load_step_results(conn, &row.id)?;
+                Ok(row.into_execution(step_results))
+            })
+            .await
+            .map_err(store_error)
+    }
+
+    async fn list_running_executions(&self) -> Result<Vec<WorkflowExecution>, InterfaceError> {
+        let running = state_to_str(ExecutionState::Running);
+        self.conn
+            .call(move |conn| {
+                let rows = {
+                    let mut stmt = conn.prepare(&format!(
+                        "SELECT {} FROM workflow_executions WHERE state = ?1 ORDER BY started_at",
+                        ExecutionRow::COLUMNS
+                    ))?;
+                    let rows = stmt.query_map(params![running], ExecutionRow::from_row)?;
+                    rows.collect::<Result<Vec<_>, _>>()?
+                };
+
+                let mut executions = Vec::with_capacity(rows.len());
+                for row in rows {
+                    let step_results = load_step_results(conn, &row.id)?;
+                    executions.extend(row.into_execution(step_results));
+                }
+                Ok(executions)
+            })
+            .await
+            .map_err(store_error)
+    }
+}
+
+#[cfg(test)]
+#[path = "sqlite_store_tests.rs"]
+mod tests;
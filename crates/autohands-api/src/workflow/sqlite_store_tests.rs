@@ -0,0 +1,105 @@
+//! Tests for the SQLite workflow store.
+
+use std::sync::Arc;
+
+use super::*;
+use crate::workflow::definition::WorkflowStep;
+use crate::workflow::executor::WorkflowExecutor;
+use crate::workflow::mock_executor::MockAgentExecutor;
+
+fn workflow() -> Workflow {
+    Workflow::new(
+        "wf-1",
+        "Test Workflow",
+        WorkflowStep::sequential(
+            "root",
+            "Root",
+            vec![
+                WorkflowStep::agent("s1", "Step 1", "a", "first"),
+                WorkflowStep::agent("s2", "Step 2", "b", "second"),
+            ],
+        ),
+    )
+}
+
+#[tokio::test]
+async fn test_workflow_crud() {
+    let store = SqliteWorkflowStore::in_memory().await.unwrap();
+    store.save(&workflow()).await.unwrap();
+    store.save(&workflow().with_description("updated")).await.unwrap();
+
+    let loaded = store.load("wf-1").await.unwrap().unwrap();
+    assert_eq!(loaded.description.as_deref(), Some("updated"));
+    assert_eq!(store.load_all().await.unwrap().len(), 1);
+    assert!(store.load("missing").await.unwrap().is_none());
+
+    assert!(store.delete("wf-1").await.unwrap());
+    assert!(!store.delete("wf-1").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_execution_round_trip() {
+    let store = SqliteWorkflowStore::in_memory().await.unwrap();
+    let mut execution = WorkflowExecution::new("wf-1");
+    execution.state = ExecutionState::Running;
+    execution.inputs = serde_json::json!({"team": "infra"});
+    store.save_execution(&execution).await.unwrap();
+
+    let result = StepResult::success("s1", serde_json::json!({"n": 1}));
+    store.save_step_result(execution.id, &result).await.unwrap();
+
+    let loaded = store.load_execution(execution.id).await.unwrap().unwrap();
+    assert_eq!(loaded.state, ExecutionState::Running);
+    assert_eq!(loaded.current_step.as_deref(), Some("s1"));
+    assert_eq!(loaded.step_results["s1"]["output"]["n"], 1);
+    assert_eq!(loaded.inputs["team"], "infra");
+
+    let running = store.list_running_executions().await.unwrap();
+    assert_eq!(running.len(), 1);
+
+    execution.state = ExecutionState::Completed;
+    store.save_execution(&execution).await.unwrap();
+    assert!(store.list_running_executions().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_persists_across_reopen() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("workflows.db");
+
+    {
+        let store = SqliteWorkflowStore::open(&path).await.unwrap();
+        store.save(&workflow()).await.unwrap();
+    }
+
+    let store = SqliteWorkflowStore::open(&path).await.unwrap();
+    assert!(store.load("wf-1").await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_resume_skips_completed_steps() {
+    let store: Arc<dyn WorkflowStore> = Arc::new(SqliteWorkflowStore::in_memory().await.unwrap());
+    store.save(&workflow()).await.unwrap();
+
+    // Simulate an execution interrupted after s1 completed.
+    let mut execution = WorkflowExecution::new("wf-1");
+    execution.state = ExecutionState::Running;
+    store.save_execution(&execution).await.unwrap();
+    store
+        .save_step_result(
+            execution.id,
+            &StepResult::success("s1", serde_json::json!("from before restart")),
+        )
+        .await
+        .unwrap();
+
+    let executor =
+        WorkflowExecutor::new(Arc::new(MockAgentExecutor::new())).with_store(store.clone());
+    assert_eq!(executor.resume_incomplete().await.unwrap(), 1);
+
+    let resumed = store.load_execution(execution.id).await.unwrap().unwrap();
+    assert_eq!(resumed.state, ExecutionState::Completed);
+    assert_eq!(resumed.step_results["s1"]["output"], "from before restart");
+    assert_eq!(resumed.step_results["s2"]["success"], true);
+    assert!(store.list_running_executions().await.unwrap().is_empty());
+}
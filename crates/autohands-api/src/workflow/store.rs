@@ -1,10 +1,12 @@
-//! In-memory workflow store.
+//! Workflow persistence.
 
 use std::collections::HashMap;
 
 use tokio::sync::RwLock;
+use uuid::Uuid;
 
-use super::definition::Workflow;
+use super::definition::{ExecutionState, Workflow, WorkflowExecution};
+use super::executor_types::StepResult;
 use crate::error::InterfaceError;
 
 /// Trait for workflow persistence.
@@ -21,11 +23,28 @@ pub trait WorkflowStore: Send + Sync {
 
     /// Delete a workflow by ID.
     async fn delete(&self, id: &str) -> Result<bool, InterfaceError>;
+
+    /// Save (insert or update) an execution, including its step results.
+    async fn save_execution(&self, execution: &WorkflowExecution) -> Result<(), InterfaceError>;
+
+    /// Record the result of a single step of an execution.
+    async fn save_step_result(
+        &self,
+        execution_id: Uuid,
+        result: &StepResult,
+    ) -> Result<(), InterfaceError>;
+
+    /// Load an execution with all recorded step results.
+    async fn load_execution(&self, id: Uuid) -> Result<Option<WorkflowExecution>, InterfaceError>;
+
+    /// List executions that were still running when last saved.
+    async fn list_running_executions(&self) -> Result<Vec<WorkflowExecution>, InterfaceError>;
 }
 
 /// In-memory workflow store.
 pub struct MemoryWorkflowStore {
     workflows: RwLock<HashMap<String, Workflow>>,
+    executions: RwLock<HashMap<Uuid, WorkflowExecution>>,
 }
 
 impl MemoryWorkflowStore {
@@ -33,6 +52,7 @@ impl MemoryWorkflowStore {
     pub fn new() -> Self {
         Self {
             workflows: RwLock::new(HashMap::new()),
+            executions: RwLock::new(HashMap::new()),
         }
     }
 }
@@ -65,6 +85,61 @@ impl WorkflowStore for MemoryWorkflowStore {
         let mut store = self.workflows.write().await;
         Ok(store.remove(id).is_some())
     }
+
+    async fn save_execution(&self, execution: &WorkflowExecution) -> Result<(), InterfaceError> {
+        let mut executions = self.executions.write().await;
+        let mut execution = execution.clone();
+        // Keep step results recorded individually while the execution ran.
+        if let Some(existing) = executions.get(&execution.id) {
+            merge_step_results(&mut execution.step_results, &existing.step_results);
+        }
+        executions.insert(execution.id, execution);
+        Ok(())
+    }
+
+    async fn save_step_result(
+        &self,
+        execution_id: Uuid,
+        result: &StepResult,
+    ) -> Result<(), InterfaceError> {
+        let mut executions = self.executions.write().await;
+        let execution = executions
+            .get_mut(&execution_id)
+            .ok_or_else(|| InterfaceError::Custom(format!("Unknown execution {}", execution_id)))?;
+        execution.current_step = Some(result.step_id.clone());
+        if let Some(results) = execution.step_results.as_object_mut() {
+            results.insert(
+                result.step_id.clone(),
+                serde_json::to_value(result).unwrap_or_default(),
+            );
+        }
+        Ok(())
+    }
+
+    async fn load_execution(&self, id: Uuid) -> Result<Option<WorkflowExecution>, InterfaceError> {
+        let executions = self.executions.read().await;
+        Ok(executions.get(&id).cloned())
+    }
+
+    async fn list_running_executions(&self) -> Result<Vec<WorkflowExecution>, InterfaceError> {
+        let executions = self.executions.read().await;
+        Ok(executions
+            .values()
+            .filter(|e| e.state == ExecutionState::Running)
+            .cloned()
+            .collect())
+    }
+}
+
+/// Add entries from `existing` that are missing in `target`.
+fn merge_step_results(target: &mut serde_json::Value, existing: &serde_json::Value) {
+    if let (Some(target), Some(existing)) = (target.as_object_mut(), existing.as_object()) {
+        for (step_id, result) in existing {
+            target
+                .entry(step_id.clone())
+                .or_insert_with(|| result.clone());
+        }
+    }
 }
 
 #[cfg(test)]
@@ -104,4 +179,21 @@ mod tests {
         let all = store.load_all().await.unwrap();
         assert!(all.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_memory_execution_step_results() {
+        let store = MemoryWorkflowStore::new();
+        let mut execution = WorkflowExecution::new("wf-1");
+        execution.state = ExecutionState::Running;
+        store.save_execution(&execution).await.unwrap();
+
+        let result = StepResult::success("s1", serde_json::json!({"ok": true}));
+        store.save_step_result(execution.id, &result).await.unwrap();
+
+        // Saving the execution again must not drop step results.
+        store.save_execution(&execution).await.unwrap();
+        let loaded = store.load_execution(execution.id).await.unwrap().unwrap();
+        assert_eq!(loaded.step_results["s1"]["output"]["ok"], true);
+        assert_eq!(store.list_running_executions().await.unwrap().len(), 1);
+    }
 }
@@ -0,0 +1,54 @@
+//! Step-output templating.
+//!
+//! Replaces `{{ path }}` placeholders with values from the execution
+//! context, e.g. `Summarize {{ fetch.title }}` or `{{ item.url }}`.
+//! Paths follow [`ExecutionContext::lookup`]. Strings are inserted
+//! verbatim, other JSON values are serialized; unknown paths render
+//! as an empty string.
+
+use super::executor_types::ExecutionContext;
+
+/// Render a template against the execution context.
+pub fn render_template(template: &str, context: &ExecutionContext) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        output.push_str(&rest[..start]);
+
+        let path = rest[start + 2..start + 2 + end].trim();
+        match context.lookup(path) {
+            Some(serde_json::Value::String(s)) => output.push_str(s),
+            Some(serde_json::Value::Null) | None => {}
+            Some(value) => output.push_str(&value.to_string()),
+        }
+
+        rest = &rest[start + 2 + end + 2..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// Render every string inside a JSON value, e.g. tool parameters.
+pub fn render_value(value: &serde_json::Value, context: &ExecutionContext) -> serde_json::Value {
+    match value {
+        serde_json::Value::String(s) => serde_json::Value::String(render_template(s, context)),
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.iter().map(|v| render_value(v, context)).collect())
+        }
+        serde_json::Value::Object(map) => serde_json::Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), render_value(v, context)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+#[path = "template_tests.rs"]
+mod tests;
@@ -0,0 +1,42 @@
+//! Tests for step-output templating.
+
+use super::*;
+
+fn context() -> ExecutionContext {
+    let mut context = ExecutionContext::new();
+    context.set(
+        "fetch",
+        serde_json::json!({"title": "Report", "items": [{"url": "a"}, {"url": "b"}], "count": 2}),
+    );
+    context
+}
+
+#[test]
+fn test_render_plain_text() {
+    assert_eq!(render_template("no placeholders", &context()), "no placeholders");
+}
+
+#[test]
+fn test_render_nested_paths() {
+    let rendered = render_template(
+        "Summarize {{ fetch.title }} from {{fetch.items.1.url}} ({{ fetch.count }})",
+        &context(),
+    );
+    assert_eq!(rendered, "Summarize Report from b (2)");
+}
+
+#[test]
+fn test_render_missing_path_is_empty() {
+    assert_eq!(render_template("[{{ missing.value }}]", &context()), "[]");
+}
+
+#[test]
+fn test_render_serializes_non_strings() {
+    let rendered = render_template("{{ fetch.items.0 }}", &context());
+    assert_eq!(rendered, r#"{"url":"a"}"#);
+}
+
+#[test]
+fn test_render_unterminated_placeholder() {
+    assert_eq!(render_template("keep {{ fetch.title", &context()), "keep {{ fetch.title");
+}
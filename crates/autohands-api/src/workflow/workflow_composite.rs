@@ -1,31 +1,42 @@
 //! Composite workflow step implementations (parallel, sequential, conditional).
 
-use futures::future::join_all;
+use futures::stream::{self, StreamExt};
 use tracing::{debug, info};
 
 use crate::error::InterfaceError;
 
-use super::definition::WorkflowStep;
+use super::definition::{JoinMode, WorkflowStep};
 use super::executor::WorkflowExecutor;
 use super::executor_types::{ExecutionContext, StepResult};
 
 impl WorkflowExecutor {
-    /// Execute parallel steps with actual concurrency via `join_all`.
+    /// Execute parallel steps concurrently.
     ///
-    /// Each step receives an independent clone of the initial context,
-    /// and all steps execute concurrently. Results are merged back into
-    /// the parent context after all steps complete.
+    /// Each step receives an independent clone of the initial context and
+    /// at most `max_concurrency` steps run at once. With [`JoinMode::All`]
+    /// every step runs to completion; with [`JoinMode::Any`] the group
+    /// succeeds on the first successful step and the rest are cancelled.
+    /// Contexts of finished steps are merged back into the parent context.
     pub(crate) async fn execute_parallel_steps(
         &self,
         step_id: &str,
         steps: &[WorkflowStep],
+        max_concurrency: Option<usize>,
+        join: JoinMode,
         context: &mut ExecutionContext,
     ) -> Result<StepResult, InterfaceError> {
-        info!("Executing {} parallel steps in {}", steps.len(), step_id);
+        let limit = max_concurrency.unwrap_or(steps.len()).max(1);
+        info!(
+            "Executing {} parallel steps in {} (limit {}, join {:?})",
+            steps.len(),
+            step_id,
+            limit,
+            join
+        );
 
         let initial_context = context.clone();
 
-        // Create futures for all steps, each with an independent context clone
+        // Collected up front so the stream type doesn't capture the closure.
         let futures: Vec<_> = steps
             .iter()
             .map(|step| {
@@ -36,43 +47,57 @@ impl WorkflowExecutor {
                 }
             })
             .collect();
+        let mut running = stream::iter(futures).buffer_unordered(limit);
 
-        // Execute all steps concurrently
-        let results = join_all(futures).await;
-
-        // Merge results
         let mut outputs = Vec::new();
-        let mut all_success = true;
         let mut errors = Vec::new();
+        let mut winner = None;
+
+        while let Some((result, step_context)) = running.next().await {
+            context.step_results.extend(step_context.step_results);
+            context.variables.extend(step_context.variables);
 
-        for (result, step_context) in results {
             match result {
                 Ok(step_result) => {
                     if !step_result.success {
-                        all_success = false;
                         if let Some(err) = &step_result.error {
                             errors.push(err.clone());
                         }
+                    } else if join == JoinMode::Any {
+                        winner = Some(step_result);
+                        break;
                     }
                     outputs.push(serde_json::json!({
                         "step_id": step_result.step_id,
                         "success": step_result.success,
                         "output": step_result.output,
                     }));
-                    context.step_results.extend(step_context.step_results);
-                    context.variables.extend(step_context.variables);
-                }
-                Err(e) => {
-                    all_success = false;
-                    errors.push(e.to_string());
                 }
+                Err(e) => errors.push(e.to_string()),
             }
         }
 
-        if all_success {
-            Ok(StepResult::success(step_id, serde_json::json!(outputs)))
-        } else {
-            Ok(StepResult::failure(step_id, errors.join("; ")))
+        match join {
+            JoinMode::All if errors.is_empty() => {
+                Ok(StepResult::success(step_id, serde_json::json!(outputs)))
+            }
+            JoinMode::Any => match winner {
+                Some(result) => {
+                    debug!("Parallel step {} joined on {}", step_id, result.step_id);
+                    Ok(StepResult::success(
+                        step_id,
+                        serde_json::json!({
+                            "step_id": result.step_id,
+                            "output": result.output,
+                        }),
+                    ))
+                }
+                None => Ok(StepResult::failure(
+                    step_id,
+                    format!("No parallel step succeeded: {}", errors.join("; ")),
+                )),
+            },
+            JoinMode::All => Ok(StepResult::failure(step_id, errors.join("; "))),
         }
     }
 
@@ -176,3 +201,7 @@ impl WorkflowExecutor {
         }
     }
 }
+
+#[cfg(test)]
+#[path = "workflow_composite_tests.rs"]
+mod tests;
@@ -0,0 +1,140 @@
+//! Tests for composite workflow steps.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use super::*;
+use crate::workflow::executor_types::AgentExecutor;
+
+/// Executor that tracks peak concurrency. Agents named `fail` error out,
+/// agents named `slow` take longer than the others.
+#[derive(Default)]
+struct TrackingExecutor {
+    in_flight: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+#[async_trait]
+impl AgentExecutor for TrackingExecutor {
+    async fn execute(
+        &self,
+        agent: &str,
+        _prompt: &str,
+        _context: &ExecutionContext,
+    ) -> Result<serde_json::Value, InterfaceError> {
+        let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.peak.fetch_max(now, Ordering::SeqCst);
+
+        let delay = if agent == "slow" { 200 } else { 20 };
+        tokio::time::sleep(Duration::from_millis(delay)).await;
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+        if agent == "fail" {
+            return Err(InterfaceError::Custom("boom".to_string()));
+        }
+        Ok(serde_json::json!({ "agent": agent }))
+    }
+}
+
+fn agents(names: &[&str]) -> Vec<WorkflowStep> {
+    names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| WorkflowStep::agent(format!("s{}", i), "Step", *name, "go"))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_parallel_respects_concurrency_limit() {
+    let tracker = Arc::new(TrackingExecutor::default());
+    let executor = WorkflowExecutor::new(tracker.clone());
+    let step = WorkflowStep::parallel_with(
+        "par",
+        "Parallel",
+        agents(&["a", "b", "c", "d", "e"]),
+        2,
+        JoinMode::All,
+    );
+
+    let mut context = ExecutionContext::new();
+    let result = executor.execute_step(&step, &mut context).await.unwrap();
+
+    assert!(result.success);
+    assert_eq!(result.output.as_array().unwrap().len(), 5);
+    assert_eq!(tracker.peak.load(Ordering::SeqCst), 2);
+    assert!(context.variables.contains_key("s4"));
+}
+
+#[tokio::test]
+async fn test_parallel_all_fails_on_any_failure() {
+    let executor = WorkflowExecutor::new(Arc::new(TrackingExecutor::default()));
+    let step = WorkflowStep::parallel("par", "Parallel", agents(&["a", "fail"]));
+
+    let mut context = ExecutionContext::new();
+    let result = executor.execute_step(&step, &mut context).await.unwrap();
+
+    assert!(!result.success);
+    assert!(context.variables.contains_key("s0"));
+}
+
+#[tokio::test]
+async fn test_parallel_any_returns_first_success() {
+    let executor = WorkflowExecutor::new(Arc::new(TrackingExecutor::default()));
+    let step = WorkflowStep::parallel_with(
+        "par",
+        "Parallel",
+        agents(&["fail", "slow", "a"]),
+        3,
+        JoinMode::Any,
+    );
+
+    let mut context = ExecutionContext::new();
+    let result = executor.execute_step(&step, &mut context).await.unwrap();
+
+    assert!(result.success);
+    assert_eq!(result.output["step_id"], "s2");
+    assert!(!context.step_results.contains_key("s1"));
+}
+
+#[tokio::test]
+async fn test_parallel_any_fails_when_all_fail() {
+    let executor = WorkflowExecutor::new(Arc::new(TrackingExecutor::default()));
+    let step = WorkflowStep::parallel_with(
+        "par",
+        "Parallel",
+        agents(&["fail", "fail"]),
+        2,
+        JoinMode::Any,
+    );
+
+    let mut context = ExecutionContext::new();
+    let result = executor.execute_step(&step, &mut context).await.unwrap();
+
+    assert!(!result.success);
+}
+
+#[test]
+fn test_parallel_defaults_deserialize() {
+    let step: WorkflowStep = serde_json::from_value(serde_json::json!({
+        "id": "par",
+        "name": "Parallel",
+        "step_type": {"Parallel": {"steps": []}},
+        "timeout_secs": null,
+    }))
+    .unwrap();
+
+    match step.step_type {
+        crate::workflow::StepType::Parallel {
+            max_concurrency,
+            join,
+            ..
+        } => {
+            assert_eq!(max_concurrency, None);
+            assert_eq!(join, JoinMode::All);
+        }
+        _ => panic!("expected parallel step"),
+    }
+}
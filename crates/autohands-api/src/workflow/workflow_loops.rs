@@ -0,0 +1,143 @@
+//! Loop workflow step implementations (loop, for-each).
+
+use tracing::{debug, info, warn};
+
+use crate::error::InterfaceError;
+
+use super::definition::WorkflowStep;
+use super::executor::WorkflowExecutor;
+use super::executor_types::{ExecutionContext, StepResult};
+
+/// Variable holding the zero-based iteration index inside loop bodies.
+pub const ITERATION_VAR: &str = "iteration";
+
+impl WorkflowExecutor {
+    /// Execute a loop step.
+    ///
+    /// The condition is evaluated before every iteration. Reaching
+    /// `max_iterations` while the condition still holds fails the step
+    /// instead of silently truncating the loop.
+    pub(crate) async fn execute_loop_step(
+        &self,
+        step_id: &str,
+        condition: &str,
+        body: &WorkflowStep,
+        max_iterations: u32,
+        context: &mut ExecutionContext,
+    ) -> Result<StepResult, InterfaceError> {
+        info!(
+            "Executing loop step {} (max {} iterations): {}",
+            step_id, max_iterations, condition
+        );
+
+        // Per-iteration results can't be matched up after a restart.
+        forget_resumed(body, context);
+
+        let mut outputs = Vec::new();
+        let mut iteration: u32 = 0;
+
+        while self.condition_evaluator.evaluate(condition, context).await? {
+            if iteration >= max_iterations {
+                warn!("Loop step {} exceeded {} iterations", step_id, max_iterations);
+                return Ok(StepResult::failure(
+                    step_id,
+                    format!("Loop exceeded max_iterations ({})", max_iterations),
+                ));
+            }
+
+            context.set(ITERATION_VAR, serde_json::json!(iteration));
+            let result = self.execute_step(body, context).await?;
+            if !result.success {
+                return Ok(StepResult::failure(
+                    step_id,
+                    format!(
+                        "Loop iteration {} failed: {:?}",
+                        iteration, result.error
+                    ),
+                ));
+            }
+
+            outputs.push(result.output);
+            iteration += 1;
+        }
+
+        debug!("Loop step {} finished after {} iterations", step_id, iteration);
+        Ok(StepResult::success(
+            step_id,
+            serde_json::json!({
+                "iterations": iteration,
+                "outputs": outputs,
+            }),
+        ))
+    }
+
+    /// Execute a for-each step.
+    pub(crate) async fn execute_for_each_step(
+        &self,
+        step_id: &str,
+        items: &str,
+        item_var: &str,
+        body: &WorkflowStep,
+        max_iterations: u32,
+        context: &mut ExecutionContext,
+    ) -> Result<StepResult, InterfaceError> {
+        let values = match context.lookup(items) {
+            Some(serde_json::Value::Array(values)) => values.clone(),
+            Some(_) => {
+                return Ok(StepResult::failure(
+                    step_id,
+                    format!("'{}' is not an array", items),
+                ));
+            }
+            None => {
+                return Ok(StepResult::failure(
+                    step_id,
+                    format!("'{}' not found in context", items),
+                ));
+            }
+        };
+
+        if values.len() > max_iterations as usize {
+            return Ok(StepResult::failure(
+                step_id,
+                format!(
+                    "'{}' has {} items, exceeding max_iterations ({})",
+                    items,
+                    values.len(),
+                    max_iterations
+                ),
+            ));
+        }
+
+        info!("Executing for-each step {} over {} items", step_id, values.len());
+        forget_resumed(body, context);
+
+        let mut outputs = Vec::with_capacity(values.len());
+        for (index, value) in values.into_iter().enumerate() {
+            context.set(item_var, value);
+            context.set(ITERATION_VAR, serde_json::json!(index));
+
+            let result = self.execute_step(body, context).await?;
+            if !result.success {
+                return Ok(StepResult::failure(
+                    step_id,
+                    format!("Item {} failed: {:?}", index, result.error),
+                ));
+            }
+            outputs.push(result.output);
+        }
+
+        Ok(StepResult::success(step_id, serde_json::json!(outputs)))
+    }
+}
+
+/// Drop resumable results for a loop body so every iteration re-runs.
+fn forget_resumed(body: &WorkflowStep, context: &mut ExecutionContext) {
+    for id in body.step_ids() {
+        context.resumed.remove(id);
+    }
+}
+
+#[cfg(test)]
+#[path = "workflow_loops_tests.rs"]
+mod tests;
@@ -0,0 +1,129 @@
+//! Tests for loop workflow steps.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use async_trait::async_trait;
+
+use super::*;
+use crate::workflow::executor_types::AgentExecutor;
+use crate::workflow::mock_executor::MockAgentExecutor;
+
+/// Executor that reports `done` after a fixed number of calls and echoes prompts.
+struct CountingExecutor {
+    calls: AtomicU32,
+    done_after: u32,
+}
+
+#[async_trait]
+impl AgentExecutor for CountingExecutor {
+    async fn execute(
+        &self,
+        _agent: &str,
+        prompt: &str,
+        _context: &ExecutionContext,
+    ) -> Result<serde_json::Value, InterfaceError> {
+        let calls = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+        Ok(serde_json::json!({
+            "done": calls >= self.done_after,
+            "prompt": prompt,
+        }))
+    }
+}
+
+fn counting_executor(done_after: u32) -> WorkflowExecutor {
+    WorkflowExecutor::new(Arc::new(CountingExecutor {
+        calls: AtomicU32::new(0),
+        done_after,
+    }))
+}
+
+#[tokio::test]
+async fn test_loop_runs_until_condition_false() {
+    let executor = counting_executor(3);
+    let body = WorkflowStep::agent("poll", "Poll", "worker", "poll #{{ iteration }}");
+    let step = WorkflowStep::loop_while("loop", "Loop", "poll.done != true", body, 10);
+
+    let mut context = ExecutionContext::new();
+    let result = executor.execute_step(&step, &mut context).await.unwrap();
+
+    assert!(result.success);
+    assert_eq!(result.output["iterations"], 3);
+    assert_eq!(result.output["outputs"][2]["prompt"], "poll #2");
+}
+
+#[tokio::test]
+async fn test_loop_fails_when_bound_exceeded() {
+    let executor = counting_executor(100);
+    let body = WorkflowStep::agent("poll", "Poll", "worker", "poll");
+    let step = WorkflowStep::loop_while("loop", "Loop", "poll.done != true", body, 2);
+
+    let mut context = ExecutionContext::new();
+    let result = executor.execute_step(&step, &mut context).await.unwrap();
+
+    assert!(!result.success);
+    assert!(result.error.unwrap().contains("max_iterations"));
+}
+
+#[tokio::test]
+async fn test_for_each_binds_items() {
+    let executor = counting_executor(1);
+    let body = WorkflowStep::agent("visit", "Visit", "worker", "open {{ page.url }}");
+    let step = WorkflowStep::for_each("each", "Each", "pages.list", "page", body);
+
+    let mut context = ExecutionContext::new();
+    context.set(
+        "pages",
+        serde_json::json!({"list": [{"url": "a.com"}, {"url": "b.com"}]}),
+    );
+    let result = executor.execute_step(&step, &mut context).await.unwrap();
+
+    assert!(result.success);
+    let outputs = result.output.as_array().unwrap();
+    assert_eq!(outputs.len(), 2);
+    assert_eq!(outputs[1]["prompt"], "open b.com");
+}
+
+#[tokio::test]
+async fn test_for_each_rejects_non_array() {
+    let executor = WorkflowExecutor::new(Arc::new(MockAgentExecutor::new()));
+    let body = WorkflowStep::agent("visit", "Visit", "worker", "noop");
+    let step = WorkflowStep::for_each("each", "Each", "pages", "page", body);
+
+    let mut context = ExecutionContext::new();
+    context.set("pages", serde_json::json!("not a list"));
+    let result = executor.execute_step(&step, &mut context).await.unwrap();
+
+    assert!(!result.success);
+}
+
+#[tokio::test]
+async fn test_conditional_on_step_output() {
+    let executor = Arc::new(MockAgentExecutor::new());
+    executor
+        .set_response("checker", serde_json::json!({"score": 8}))
+        .await;
+    let executor = WorkflowExecutor::new(executor);
+
+    let root = WorkflowStep::sequential(
+        "root",
+        "Root",
+        vec![
+            WorkflowStep::agent("check", "Check", "checker", "score it"),
+            WorkflowStep::conditional(
+                "branch",
+                "Branch",
+                "check.score >= 5",
+                WorkflowStep::agent("pass", "Pass", "worker", "ok"),
+                Some(WorkflowStep::agent("fail", "Fail", "worker", "retry")),
+            ),
+        ],
+    );
+
+    let mut context = ExecutionContext::new();
+    let result = executor.execute_step(&root, &mut context).await.unwrap();
+
+    assert!(result.success);
+    assert_eq!(context.step_results["branch"].output["branch"], "if_true");
+    assert!(context.step_results.contains_key("pass"));
+}
@@ -0,0 +1,170 @@
+//! Map step implementation (map-reduce over file sets).
+
+use futures::stream::{self, StreamExt};
+use tracing::{debug, info, warn};
+
+use crate::error::InterfaceError;
+
+use super::definition::WorkflowStep;
+use super::executor::WorkflowExecutor;
+use super::executor_types::{ExecutionContext, StepResult};
+use super::workflow_loops::ITERATION_VAR;
+
+/// How a map step runs its items.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct MapOptions {
+    /// Items processed at once.
+    pub(crate) max_concurrency: usize,
+    /// Succeed even if some items failed.
+    pub(crate) allow_failures: bool,
+    /// Most files the pattern may match.
+    pub(crate) max_items: usize,
+}
+
+impl WorkflowExecutor {
+    /// Execute a map step.
+    ///
+    /// The body runs once per file matching `pattern`, in a clone of the
+    /// context with `item_var` set to the file's path. Progress is recorded
+    /// as each file finishes. The optional reduce step then runs with the
+    /// map output available under the map step's id.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) async fn execute_map_step(
+        &self,
+        step_id: &str,
+        pattern: &str,
+        item_var: &str,
+        body: &WorkflowStep,
+        reduce: Option<&WorkflowStep>,
+        options: MapOptions,
+        context: &mut ExecutionContext,
+    ) -> Result<StepResult, InterfaceError> {
+        let files = match expand_files(pattern) {
+            Ok(files) => files,
+            Err(e) => return Ok(StepResult::failure(step_id, e)),
+        };
+        if files.len() > options.max_items {
+            return Ok(StepResult::failure(
+                step_id,
+                format!(
+                    "'{}' matches {} files, exceeding max_iterations ({})",
+                    pattern,
+                    files.len(),
+                    options.max_items
+                ),
+            ));
+        }
+
+        let total = files.len();
+        let limit = options.max_concurrency.max(1);
+        info!(
+            "Executing map step {} over {} files (limit {})",
+            step_id, total, limit
+        );
+
+        // Per-file results can't be matched up after a restart.
+        for id in body
+            .step_ids()
+            .into_iter()
+            .chain(reduce.into_iter().flat_map(|step| step.step_ids()))
+        {
+            context.resumed.remove(id);
+        }
+
+        let futures: Vec<_> = files
+            .iter()
+            .enumerate()
+            .map(|(index, file)| {
+                let mut item_context = context.clone();
+                item_context.set(item_var, serde_json::json!(file));
+                item_context.set(ITERATION_VAR, serde_json::json!(index));
+                async move {
+                    let result = self.execute_step(body, &mut item_context).await;
+                    (index, result)
+                }
+            })
+            .collect();
+        let mut running = stream::iter(futures).buffer_unordered(limit);
+
+        let mut results = vec![serde_json::Value::Null; total];
+        let mut failed = 0;
+        let mut done = 0;
+        while let Some((index, result)) = running.next().await {
+            let (success, output, error) = match result {
+                Ok(result) => (result.success, result.output, result.error),
+                Err(e) => (false, serde_json::Value::Null, Some(e.to_string())),
+            };
+            if !success {
+                warn!(
+                    "Map step {} failed on {}: {:?}",
+                    step_id, files[index], error
+                );
+                failed += 1;
+            }
+            done += 1;
+            results[index] = serde_json::json!({
+                "file": files[index],
+                "success": success,
+                "output": output,
+                "error": error,
+            });
+
+            info!("Map step {}: {}/{} files done", step_id, done, total);
+            let progress = StepResult {
+                success: false,
+                ..StepResult::success(
+                    step_id,
+                    serde_json::json!({
+                        "progress": { "done": done, "failed": failed, "total": total },
+                    }),
+                )
+            };
+            self.persist_step_result(context, &progress).await;
+        }
+        drop(running);
+
+        let mut output = serde_json::json!({
+            "files": total,
+            "succeeded": total - failed,
+            "failed": failed,
+            "results": results,
+        });
+        if failed > 0 && !options.allow_failures {
+            return Ok(StepResult {
+                output,
+                ..StepResult::failure(step_id, format!("{} of {} files failed", failed, total))
+            });
+        }
+
+        if let Some(reduce) = reduce {
+            context.set(step_id, output.clone());
+            let result = self.execute_step(reduce, context).await?;
+            if !result.success {
+                return Ok(StepResult::failure(
+                    step_id,
+                    format!("Reduce step failed: {:?}", result.error),
+                ));
+            }
+            output["reduced"] = result.output;
+        }
+
+        debug!("Map step {} finished: {} failed", step_id, failed);
+        Ok(StepResult::success(step_id, output))
+    }
+}
+
+/// Paths matching a glob pattern, sorted.
+fn expand_files(pattern: &str) -> Result<Vec<String>, String> {
+    let paths = glob::glob(pattern).map_err(|e| format!("Invalid pattern '{}': {}", pattern, e))?;
+    let mut files: Vec<String> = paths
+        .filter_map(Result::ok)
+        .filter(|path| path.is_file())
+        .map(|path| path.display().to_string())
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+#[path = "workflow_map_tests.rs"]
+mod tests;
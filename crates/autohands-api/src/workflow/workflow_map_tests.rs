@@ -0,0 +1,205 @@
+//! Tests for map workflow steps.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use tempfile::TempDir;
+
+use super::*;
+use crate::workflow::definition::{StepType, WorkflowExecution};
+use crate::workflow::executor_types::ToolExecutor;
+use crate::workflow::mock_executor::MockAgentExecutor;
+use crate::workflow::store::{MemoryWorkflowStore, WorkflowStore};
+
+/// Tool that reports each file's size, failing on files named `bad*`.
+#[derive(Default)]
+struct SizeTool {
+    running: AtomicUsize,
+    peak: AtomicUsize,
+    /// Store checked for recorded progress on every call.
+    store: Option<(Arc<MemoryWorkflowStore>, uuid::Uuid)>,
+    progress: Mutex<Vec<serde_json::Value>>,
+}
+
+#[async_trait]
+impl ToolExecutor for SizeTool {
+    async fn execute_tool(
+        &self,
+        tool: &str,
+        params: serde_json::Value,
+        _context: &ExecutionContext,
+    ) -> Result<serde_json::Value, InterfaceError> {
+        let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+        self.peak.fetch_max(running, Ordering::SeqCst);
+        if let Some((store, id)) = &self.store {
+            let execution = store.load_execution(*id).await?.unwrap();
+            self.progress
+                .lock()
+                .unwrap()
+                .push(execution.step_results["map"]["output"]["progress"].clone());
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        self.running.fetch_sub(1, Ordering::SeqCst);
+
+        assert_eq!(tool, "file_size");
+        let path = params["path"].as_str().unwrap();
+        if path.contains("bad") {
+            return Err(InterfaceError::Custom(format!("cannot read {}", path)));
+        }
+        let size = std::fs::metadata(path)
+            .map_err(|e| InterfaceError::Custom(e.to_string()))?
+            .len();
+        Ok(serde_json::json!(size))
+    }
+}
+
+fn files(names: &[&str]) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    for name in names {
+        std::fs::write(dir.path().join(name), name.as_bytes()).unwrap();
+    }
+    dir
+}
+
+fn map_step(dir: &TempDir) -> WorkflowStep {
+    WorkflowStep::map(
+        "map",
+        "Sizes",
+        format!("{}/*.txt", dir.path().display()),
+        "file",
+        WorkflowStep::tool(
+            "size",
+            "Size",
+            "file_size",
+            serde_json::json!({ "path": "{{ file }}" }),
+        ),
+    )
+}
+
+fn executor(tool: Arc<SizeTool>) -> WorkflowExecutor {
+    WorkflowExecutor::new(Arc::new(MockAgentExecutor::new())).with_tool_executor(tool)
+}
+
+#[tokio::test]
+async fn test_map_runs_body_per_file() {
+    let dir = files(&["a.txt", "ccc.txt", "skip.md"]);
+    let mut step = map_step(&dir);
+    if let StepType::Map {
+        max_concurrency, ..
+    } = &mut step.step_type
+    {
+        *max_concurrency = Some(2);
+    }
+    let tool = Arc::new(SizeTool::default());
+
+    let mut context = ExecutionContext::new();
+    let result = executor(tool.clone())
+        .execute_step(&step, &mut context)
+        .await
+        .unwrap();
+
+    assert!(result.success, "{:?}", result.error);
+    assert_eq!(result.output["files"], 2);
+    assert_eq!(result.output["succeeded"], 2);
+    let results = result.output["results"].as_array().unwrap();
+    assert!(results[0]["file"].as_str().unwrap().ends_with("a.txt"));
+    assert_eq!(results[0]["output"], 5);
+    assert_eq!(results[1]["output"], 7);
+    assert_eq!(tool.peak.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_map_failures_and_limits() {
+    let dir = files(&["a.txt", "bad.txt"]);
+    let step = map_step(&dir);
+    let executor = executor(Arc::new(SizeTool::default()));
+
+    let mut context = ExecutionContext::new();
+    let result = executor.execute_step(&step, &mut context).await.unwrap();
+    assert!(!result.success);
+    assert_eq!(result.error.as_deref(), Some("1 of 2 files failed"));
+    assert_eq!(result.output["results"][1]["success"], false);
+
+    let mut tolerant = step.clone();
+    if let StepType::Map { allow_failures, .. } = &mut tolerant.step_type {
+        *allow_failures = true;
+    }
+    let result = executor
+        .execute_step(&tolerant, &mut context)
+        .await
+        .unwrap();
+    assert!(result.success);
+    assert_eq!(result.output["failed"], 1);
+
+    let mut capped = step.clone();
+    if let StepType::Map { max_iterations, .. } = &mut capped.step_type {
+        *max_iterations = 1;
+    }
+    let result = executor.execute_step(&capped, &mut context).await.unwrap();
+    assert!(result
+        .error
+        .unwrap()
+        .contains("exceeding max_iterations (1)"));
+
+    // Without a tool executor, tool steps fail
+    let result = WorkflowExecutor::new(Arc::new(MockAgentExecutor::new()))
+        .execute_step(&step, &mut context)
+        .await
+        .unwrap();
+    assert_eq!(
+        result.output["results"][0]["error"],
+        "No tool executor configured"
+    );
+}
+
+#[tokio::test]
+async fn test_map_reduce() {
+    let dir = files(&["a.txt", "bb.txt"]);
+    let step = map_step(&dir).with_reduce(WorkflowStep::agent(
+        "summary",
+        "Summarize",
+        "writer",
+        "Summarize {{ map.succeeded }} files",
+    ));
+
+    let mut context = ExecutionContext::new();
+    let result = executor(Arc::new(SizeTool::default()))
+        .execute_step(&step, &mut context)
+        .await
+        .unwrap();
+
+    assert!(result.success);
+    assert_eq!(result.output["reduced"]["prompt"], "Summarize 2 files");
+    assert_eq!(
+        context.get("summary").unwrap()["prompt"],
+        "Summarize 2 files"
+    );
+}
+
+#[tokio::test]
+async fn test_map_records_progress() {
+    let dir = files(&["a.txt", "b.txt", "c.txt"]);
+    let store = Arc::new(MemoryWorkflowStore::new());
+    let execution = WorkflowExecution::new("wf");
+    store.save_execution(&execution).await.unwrap();
+    let tool = Arc::new(SizeTool {
+        store: Some((store.clone(), execution.id)),
+        ..SizeTool::default()
+    });
+
+    let mut context = ExecutionContext::new();
+    context.metadata = serde_json::json!({ "execution_id": execution.id.to_string() });
+    let result = executor(tool.clone())
+        .with_store(store.clone())
+        .execute_step(&map_step(&dir), &mut context)
+        .await
+        .unwrap();
+    assert!(result.success);
+
+    let progress = tool.progress.lock().unwrap().clone();
+    assert_eq!(progress[0], serde_json::Value::Null);
+    assert_eq!(progress[1]["done"], 1);
+    assert_eq!(progress[2]["done"], 2);
+    assert_eq!(progress[2]["total"], 3);
+}
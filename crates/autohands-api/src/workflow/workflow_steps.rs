@@ -1,4 +1,4 @@
-//! Workflow step execution - agent, event, and dispatch.
+//! Workflow step execution - agent, approval, event, and dispatch.
 
 use std::future::Future;
 use std::pin::Pin;
@@ -6,11 +6,15 @@ use std::time::Duration;
 
 use tracing::{debug, error, info, warn};
 
+use autohands_protocols::approval::{ApprovalRequest, ApprovalStatus, ApprovalTimeoutPolicy};
+
 use crate::error::InterfaceError;
 
 use super::definition::{StepType, WorkflowStep};
 use super::executor::WorkflowExecutor;
 use super::executor_types::{ExecutionContext, StepResult};
+use super::template::{render_template, render_value};
+use super::workflow_map::MapOptions;
 
 impl WorkflowExecutor {
     /// Execute an agent step.
@@ -19,11 +23,19 @@ impl WorkflowExecutor {
         step_id: &str,
         agent: &str,
         prompt: &str,
+        tools: &[String],
         context: &ExecutionContext,
     ) -> Result<StepResult, InterfaceError> {
         info!("Executing agent step: {} with agent '{}'", step_id, agent);
 
-        match self.agent_executor.execute(agent, prompt, context).await {
+        let result = if tools.is_empty() {
+            self.agent_executor.execute(agent, prompt, context).await
+        } else {
+            self.agent_executor
+                .execute_with_tools(agent, prompt, tools, context)
+                .await
+        };
+        match result {
             Ok(output) => {
                 debug!("Agent step {} completed successfully", step_id);
                 Ok(StepResult::success(step_id, output))
@@ -35,6 +47,77 @@ impl WorkflowExecutor {
         }
     }
 
+    /// Execute a tool step.
+    pub(crate) async fn execute_tool_step(
+        &self,
+        step_id: &str,
+        tool: &str,
+        params: serde_json::Value,
+        context: &ExecutionContext,
+    ) -> Result<StepResult, InterfaceError> {
+        let Some(executor) = &self.tool_executor else {
+            return Ok(StepResult::failure(step_id, "No tool executor configured"));
+        };
+
+        info!("Executing tool step: {} with tool '{}'", step_id, tool);
+        match executor.execute_tool(tool, params, context).await {
+            Ok(output) => Ok(StepResult::success(step_id, output)),
+            Err(e) => {
+                error!("Tool step {} failed: {}", step_id, e);
+                Ok(StepResult::failure(step_id, e.to_string()))
+            }
+        }
+    }
+
+    /// Execute an approval step: ask the gate and wait for its decision.
+    pub(crate) async fn execute_approval_step(
+        &self,
+        step: &WorkflowStep,
+        message: &str,
+        on_timeout: Option<ApprovalTimeoutPolicy>,
+        context: &ExecutionContext,
+    ) -> Result<StepResult, InterfaceError> {
+        let Some(gate) = &self.approval_gate else {
+            return Ok(StepResult::failure(&step.id, "No approval gate configured"));
+        };
+
+        let workflow_id = context.metadata["workflow_id"].as_str().unwrap_or("workflow");
+        let mut request =
+            ApprovalRequest::new(message, format!("workflow:{}/{}", workflow_id, step.id))
+                .with_description(&step.name)
+                .with_details(serde_json::json!({
+                    "workflow_id": workflow_id,
+                    "execution_id": context.metadata["execution_id"],
+                    "step_id": step.id,
+                }))
+                .with_timeout(self.approval_timeout_secs(step));
+        if let Some(policy) = on_timeout {
+            request = request.with_timeout_policy(policy);
+        }
+
+        info!("Waiting for approval of step {}", step.id);
+        let decision = gate.request_approval(request).await;
+        if decision.is_approved() {
+            return Ok(StepResult::success(
+                &step.id,
+                serde_json::json!({
+                    "approved": true,
+                    "decided_by": decision.decided_by,
+                    "comment": decision.comment,
+                }),
+            ));
+        }
+
+        let reason = match decision.status {
+            ApprovalStatus::TimedOut => "Approval timed out".to_string(),
+            _ => match decision.comment {
+                Some(comment) => format!("Approval rejected: {}", comment),
+                None => "Approval rejected".to_string(),
+            },
+        };
+        Ok(StepResult::failure(&step.id, reason))
+    }
+
     /// Execute a wait-for-event step.
     ///
     /// Note: This is currently a placeholder implementation.
@@ -75,12 +158,26 @@ impl WorkflowExecutor {
     {
         Box::pin(async move {
             match &step.step_type {
-                StepType::Agent { agent, prompt } => {
-                    self.execute_agent_step(&step.id, agent, prompt, context)
+                StepType::Agent {
+                    agent,
+                    prompt,
+                    tools,
+                } => {
+                    let prompt = render_template(prompt, context);
+                    self.execute_agent_step(&step.id, agent, &prompt, tools, context)
                         .await
                 }
-                StepType::Parallel { steps } => {
-                    self.execute_parallel_steps(&step.id, steps, context)
+                StepType::Tool { tool, params } => {
+                    let params = render_value(params, context);
+                    self.execute_tool_step(&step.id, tool, params, context)
+                        .await
+                }
+                StepType::Parallel {
+                    steps,
+                    max_concurrency,
+                    join,
+                } => {
+                    self.execute_parallel_steps(&step.id, steps, *max_concurrency, *join, context)
                         .await
                 }
                 StepType::Sequential { steps } => {
@@ -101,6 +198,14 @@ impl WorkflowExecutor {
                     )
                     .await
                 }
+                StepType::Approval {
+                    message,
+                    on_timeout,
+                } => {
+                    let message = render_template(message, context);
+                    self.execute_approval_step(step, &message, *on_timeout, context)
+                        .await
+                }
                 StepType::WaitForEvent {
                     event_type,
                     timeout_secs,
@@ -113,6 +218,56 @@ impl WorkflowExecutor {
                     )
                     .await
                 }
+                StepType::Loop {
+                    condition,
+                    body,
+                    max_iterations,
+                } => {
+                    self.execute_loop_step(&step.id, condition, body, *max_iterations, context)
+                        .await
+                }
+                StepType::ForEach {
+                    items,
+                    item_var,
+                    body,
+                    max_iterations,
+                } => {
+                    self.execute_for_each_step(
+                        &step.id,
+                        items,
+                        item_var,
+                        body,
+                        *max_iterations,
+                        context,
+                    )
+                    .await
+                }
+                StepType::Map {
+                    files,
+                    item_var,
+                    body,
+                    max_concurrency,
+                    allow_failures,
+                    reduce,
+                    max_iterations,
+                } => {
+                    let files = render_template(files, context);
+                    let options = MapOptions {
+                        max_concurrency: max_concurrency.unwrap_or(1),
+                        allow_failures: *allow_failures,
+                        max_items: *max_iterations as usize,
+                    };
+                    self.execute_map_step(
+                        &step.id,
+                        &files,
+                        item_var,
+                        body,
+                        reduce.as_deref(),
+                        options,
+                        context,
+                    )
+                    .await
+                }
             }
         })
     }
@@ -0,0 +1,397 @@
+//! YAML workflow definitions.
+//!
+//! A compact authoring format for workflows kept in version control. Files
+//! are compiled into regular [`Workflow`] definitions:
+//!
+//! ```yaml
+//! id: weekly-report
+//! name: Weekly report
+//! agent: general            # default agent for prompt steps
+//! inputs:
+//!   team:
+//!     description: Team to report on
+//!   period:
+//!     default: last week
+//! steps:
+//!   - id: collect
+//!     prompt: "Collect metrics for {{ inputs.team }} ({{ inputs.period }})"
+//!     tools: [web_fetch]
+//!     retries: 2
+//!   - id: confirm
+//!     approval: "Publish the report for {{ inputs.team }}?"
+//!     timeout_secs: 3600
+//!     on_timeout: reject
+//!   - id: publish
+//!     if: "collect.count > 0"
+//!     then:
+//!       id: post
+//!       prompt: "Summarize {{ collect }}"
+//!   - id: attachments
+//!     map: "{{ inputs.dir }}/*.pdf"
+//!     as: file
+//!     max_concurrency: 4
+//!     do:
+//!       id: read
+//!       tool: read_file
+//!       params: { path: "{{ file }}" }
+//! ```
+//!
+//! Each step defines exactly one of `prompt`, `tool`, `approval`, `parallel`,
+//! `steps`, `if`, `while`, `for_each`, `map` or `wait_for`. Several top-level
+//! steps run in sequence.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use autohands_protocols::approval::ApprovalTimeoutPolicy;
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use super::definition::{
+    JoinMode, StepType, Workflow, WorkflowInput, WorkflowStep, DEFAULT_MAX_ITERATIONS,
+};
+use super::store::WorkflowStore;
+use crate::error::InterfaceError;
+
+/// Top-level YAML document.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WorkflowFile {
+    id: String,
+    name: Option<String>,
+    description: Option<String>,
+    /// Default agent for prompt steps.
+    agent: Option<String>,
+    timeout_secs: Option<u64>,
+    #[serde(default)]
+    inputs: BTreeMap<String, Option<InputSpec>>,
+    steps: Vec<StepSpec>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct InputSpec {
+    description: Option<String>,
+    /// Defaults to `true` unless a default value is given.
+    required: Option<bool>,
+    default: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StepSpec {
+    id: String,
+    name: Option<String>,
+    timeout_secs: Option<u64>,
+    #[serde(default)]
+    retries: u32,
+
+    // Agent step
+    prompt: Option<String>,
+    agent: Option<String>,
+    #[serde(default)]
+    tools: Vec<String>,
+
+    // Tool step
+    tool: Option<String>,
+    params: Option<serde_json::Value>,
+
+    // Approval
+    approval: Option<String>,
+    on_timeout: Option<ApprovalTimeoutPolicy>,
+
+    // Parallel group
+    parallel: Option<Vec<StepSpec>>,
+    max_concurrency: Option<usize>,
+    join: Option<JoinMode>,
+
+    // Sequence
+    steps: Option<Vec<StepSpec>>,
+
+    // Conditional
+    #[serde(rename = "if")]
+    condition: Option<String>,
+    then: Option<Box<StepSpec>>,
+    #[serde(rename = "else")]
+    otherwise: Option<Box<StepSpec>>,
+
+    // Loops
+    #[serde(rename = "while")]
+    while_condition: Option<String>,
+    for_each: Option<String>,
+    #[serde(rename = "as")]
+    item_var: Option<String>,
+    #[serde(rename = "do")]
+    body: Option<Box<StepSpec>>,
+    max_iterations: Option<u32>,
+
+    // Map over files
+    map: Option<String>,
+    allow_failures: Option<bool>,
+    reduce: Option<Box<StepSpec>>,
+
+    // Event wait
+    wait_for: Option<String>,
+}
+
+impl StepSpec {
+    fn into_step(self, default_agent: Option<&str>) -> Result<WorkflowStep, String> {
+        let id = self.id.clone();
+        let kinds = [
+            ("prompt", self.prompt.is_some()),
+            ("tool", self.tool.is_some()),
+            ("approval", self.approval.is_some()),
+            ("parallel", self.parallel.is_some()),
+            ("steps", self.steps.is_some()),
+            ("if", self.condition.is_some()),
+            ("while", self.while_condition.is_some()),
+            ("for_each", self.for_each.is_some()),
+            ("map", self.map.is_some()),
+            ("wait_for", self.wait_for.is_some()),
+        ];
+        let defined: Vec<&str> = kinds.iter().filter(|(_, set)| *set).map(|(k, _)| *k).collect();
+        let [kind] = defined.as_slice() else {
+            return Err(format!(
+                "step '{}' must define exactly one of prompt, tool, approval, parallel, steps, if, while, for_each, map, wait_for (found {})",
+                id,
+                if defined.is_empty() { "none".to_string() } else { defined.join(", ") }
+            ));
+        };
+
+        let allowed: &[&str] = match *kind {
+            "prompt" => &["agent", "tools"],
+            "tool" => &["params"],
+            "approval" => &["on_timeout"],
+            "parallel" => &["max_concurrency", "join"],
+            "if" => &["then", "else"],
+            "while" => &["do", "max_iterations"],
+            "for_each" => &["as", "do", "max_iterations"],
+            "map" => &[
+                "as",
+                "do",
+                "max_concurrency",
+                "allow_failures",
+                "reduce",
+                "max_iterations",
+            ],
+            _ => &[],
+        };
+        let extras = [
+            ("agent", self.agent.is_some()),
+            ("tools", !self.tools.is_empty()),
+            ("params", self.params.is_some()),
+            ("on_timeout", self.on_timeout.is_some()),
+            ("max_concurrency", self.max_concurrency.is_some()),
+            ("join", self.join.is_some()),
+            ("then", self.then.is_some()),
+            ("else", self.otherwise.is_some()),
+            ("as", self.item_var.is_some()),
+            ("do", self.body.is_some()),
+            ("max_iterations", self.max_iterations.is_some()),
+            ("allow_failures", self.allow_failures.is_some()),
+            ("reduce", self.reduce.is_some()),
+        ];
+        if let Some((field, _)) = extras
+            .iter()
+            .find(|(field, set)| *set && !allowed.contains(field))
+        {
+            return Err(format!("step '{}': '{}' cannot be used with '{}'", id, field, kind));
+        }
+
+        let required = |value: Option<Box<StepSpec>>, field: &str| {
+            value.ok_or_else(|| format!("step '{}': '{}' requires '{}'", id, kind, field))
+        };
+        let children = |steps: Vec<StepSpec>| {
+            steps
+                .into_iter()
+                .map(|s| s.into_step(default_agent))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        let step_type = match *kind {
+            "prompt" => StepType::Agent {
+                agent: self
+                    .agent
+                    .or_else(|| default_agent.map(str::to_string))
+                    .ok_or_else(|| {
+                        format!("step '{}': no agent given and the workflow has no default agent", id)
+                    })?,
+                prompt: self.prompt.unwrap_or_default(),
+                tools: self.tools,
+            },
+            "tool" => StepType::Tool {
+                tool: self.tool.unwrap_or_default(),
+                params: self.params.unwrap_or_else(|| serde_json::json!({})),
+            },
+            "approval" => StepType::Approval {
+                message: self.approval.unwrap_or_default(),
+                on_timeout: self.on_timeout,
+            },
+            "parallel" => StepType::Parallel {
+                steps: children(self.parallel.unwrap_or_default())?,
+                max_concurrency: self.max_concurrency,
+                join: self.join.unwrap_or_default(),
+            },
+            "steps" => StepType::Sequential {
+                steps: children(self.steps.unwrap_or_default())?,
+            },
+            "if" => StepType::Conditional {
+                condition: self.condition.unwrap_or_default(),
+                if_true: Box::new(required(self.then, "then")?.into_step(default_agent)?),
+                if_false: match self.otherwise {
+                    Some(step) => Some(Box::new(step.into_step(default_agent)?)),
+                    None => None,
+                },
+            },
+            "while" => StepType::Loop {
+                condition: self.while_condition.unwrap_or_default(),
+                body: Box::new(required(self.body, "do")?.into_step(default_agent)?),
+                max_iterations: self.max_iterations.unwrap_or(DEFAULT_MAX_ITERATIONS),
+            },
+            "for_each" => StepType::ForEach {
+                items: self.for_each.unwrap_or_default(),
+                item_var: self
+                    .item_var
+                    .ok_or_else(|| format!("step '{}': 'for_each' requires 'as'", id))?,
+                body: Box::new(required(self.body, "do")?.into_step(default_agent)?),
+                max_iterations: self.max_iterations.unwrap_or(DEFAULT_MAX_ITERATIONS),
+            },
+            "map" => StepType::Map {
+                files: self.map.unwrap_or_default(),
+                item_var: self
+                    .item_var
+                    .ok_or_else(|| format!("step '{}': 'map' requires 'as'", id))?,
+                body: Box::new(required(self.body, "do")?.into_step(default_agent)?),
+                max_concurrency: self.max_concurrency,
+                allow_failures: self.allow_failures.unwrap_or(false),
+                reduce: match self.reduce {
+                    Some(step) => Some(Box::new(step.into_step(default_agent)?)),
+                    None => None,
+                },
+                max_iterations: self.max_iterations.unwrap_or(DEFAULT_MAX_ITERATIONS),
+            },
+            _ => StepType::WaitForEvent {
+                event_type: self.wait_for.unwrap_or_default(),
+                timeout_secs: self.timeout_secs,
+            },
+        };
+
+        Ok(WorkflowStep {
+            name: self.name.unwrap_or_else(|| id.clone()),
+            id,
+            step_type,
+            timeout_secs: self.timeout_secs,
+            retries: self.retries,
+        })
+    }
+}
+
+impl WorkflowFile {
+    fn into_workflow(self) -> Result<Workflow, String> {
+        let default_agent = self.agent.as_deref();
+        let mut steps = self
+            .steps
+            .into_iter()
+            .map(|s| s.into_step(default_agent))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let root = match steps.len() {
+            0 => return Err("workflow has no steps".to_string()),
+            1 => steps.remove(0),
+            _ => WorkflowStep::sequential(self.id.clone(), "main", steps),
+        };
+
+        let inputs = self
+            .inputs
+            .into_iter()
+            .map(|(name, spec)| {
+                let spec = spec.unwrap_or_default();
+                WorkflowInput {
+                    name,
+                    description: spec.description,
+                    required: spec.required.unwrap_or(spec.default.is_none()),
+                    default: spec.default,
+                }
+            })
+            .collect();
+
+        Ok(Workflow {
+            name: self.name.unwrap_or_else(|| self.id.clone()),
+            id: self.id,
+            description: self.description,
+            root,
+            timeout_secs: self.timeout_secs,
+            inputs,
+        })
+    }
+}
+
+/// Parse and validate a YAML workflow definition.
+pub fn parse_workflow_yaml(source: &str) -> Result<Workflow, InterfaceError> {
+    let file: WorkflowFile = serde_yaml::from_str(source)
+        .map_err(|e| InterfaceError::InvalidWorkflow(e.to_string()))?;
+    let workflow = file.into_workflow().map_err(InterfaceError::InvalidWorkflow)?;
+    workflow.validate()?;
+    Ok(workflow)
+}
+
+/// Load and validate a YAML workflow file.
+pub fn load_workflow_file(path: &Path) -> Result<Workflow, InterfaceError> {
+    let source = std::fs::read_to_string(path).map_err(|e| {
+        InterfaceError::Custom(format!("Failed to read workflow file {:?}: {}", path, e))
+    })?;
+    parse_workflow_yaml(&source)
+}
+
+/// Whether a path looks like a YAML workflow file.
+pub fn is_workflow_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext == "yaml" || ext == "yml")
+}
+
+/// Load every `*.yaml` / `*.yml` file in a directory, sorted by path.
+///
+/// A missing directory yields no entries. Each file is reported with its
+/// own result so one broken file does not hide the others.
+pub fn load_workflow_dir(dir: &Path) -> Vec<(PathBuf, Result<Workflow, InterfaceError>)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file() && is_workflow_file(path))
+        .collect();
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| {
+            let result = load_workflow_file(&path);
+            (path, result)
+        })
+        .collect()
+}
+
+/// Register the valid workflows of a directory in a store.
+///
+/// Invalid files are logged and skipped. Returns the number registered.
+pub async fn register_workflow_dir(
+    store: &dyn WorkflowStore,
+    dir: &Path,
+) -> Result<usize, InterfaceError> {
+    let mut registered = 0;
+    for (path, result) in load_workflow_dir(dir) {
+        match result {
+            Ok(workflow) => {
+                store.save(&workflow).await?;
+                info!("Registered workflow '{}' from {:?}", workflow.id, path);
+                registered += 1;
+            }
+            Err(e) => warn!("Skipping workflow file {:?}: {}", path, e),
+        }
+    }
+    Ok(registered)
+}
+
+#[cfg(test)]
+#[path = "yaml_tests.rs"]
+mod tests;
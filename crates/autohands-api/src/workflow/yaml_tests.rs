@@ -0,0 +1,211 @@
+//! Tests for YAML workflow definitions.
+
+use super::*;
+use crate::workflow::store::MemoryWorkflowStore;
+
+const REPORT: &str = r#"
+id: weekly-report
+name: Weekly report
+agent: general
+timeout_secs: 600
+inputs:
+  team:
+    description: Team to report on
+  period:
+    default: last week
+steps:
+  - id: collect
+    prompt: "Collect metrics for {{ inputs.team }}"
+    tools: [web_fetch, read_file]
+    retries: 2
+  - id: review
+    parallel:
+      - id: a
+        agent: reviewer
+        prompt: Review A
+      - id: b
+        prompt: Review B
+    join: any
+  - id: publish
+    if: "collect.count > 0"
+    then:
+      id: post
+      prompt: Post it
+"#;
+
+#[test]
+fn test_parse_full_workflow() {
+    let workflow = parse_workflow_yaml(REPORT).unwrap();
+    assert_eq!(workflow.id, "weekly-report");
+    assert_eq!(workflow.name, "Weekly report");
+    assert_eq!(workflow.timeout_secs, Some(600));
+
+    let period = workflow.inputs.iter().find(|i| i.name == "period").unwrap();
+    assert!(!period.required);
+    assert_eq!(period.default, Some(serde_json::json!("last week")));
+    assert!(workflow.inputs.iter().find(|i| i.name == "team").unwrap().required);
+
+    let StepType::Sequential { steps } = &workflow.root.step_type else {
+        panic!("expected sequential root");
+    };
+    assert_eq!(workflow.root.id, "weekly-report");
+    assert_eq!(steps.len(), 3);
+    assert_eq!(steps[0].retries, 2);
+    assert!(matches!(
+        &steps[0].step_type,
+        StepType::Agent { agent, tools, .. } if agent == "general" && tools.len() == 2
+    ));
+    let StepType::Parallel { steps: group, join, .. } = &steps[1].step_type else {
+        panic!("expected parallel step");
+    };
+    assert_eq!(*join, JoinMode::Any);
+    assert!(matches!(&group[0].step_type, StepType::Agent { agent, .. } if agent == "reviewer"));
+    assert!(matches!(&steps[2].step_type, StepType::Conditional { if_false: None, .. }));
+}
+
+#[test]
+fn test_single_step_becomes_root() {
+    let workflow = parse_workflow_yaml(
+        "id: once\nsteps:\n  - id: only\n    agent: general\n    prompt: Hi\n",
+    )
+    .unwrap();
+    assert_eq!(workflow.root.id, "only");
+    assert_eq!(workflow.name, "once");
+}
+
+#[test]
+fn test_loops() {
+    let workflow = parse_workflow_yaml(
+        r#"
+id: loops
+agent: general
+steps:
+  - id: each
+    for_each: list.items
+    as: item
+    do: { id: handle, prompt: "Handle {{ item }}" }
+  - id: poll
+    while: "poll.done != true"
+    max_iterations: 5
+    do: { id: check, prompt: Check }
+"#,
+    )
+    .unwrap();
+    let StepType::Sequential { steps } = &workflow.root.step_type else {
+        panic!("expected sequential root");
+    };
+    assert!(matches!(&steps[0].step_type, StepType::ForEach { item_var, .. } if item_var == "item"));
+    assert!(matches!(&steps[1].step_type, StepType::Loop { max_iterations: 5, .. }));
+}
+
+#[test]
+fn test_map_over_files() {
+    let workflow = parse_workflow_yaml(
+        r#"
+id: pdfs
+agent: general
+steps:
+  - id: extract
+    map: "{{ inputs.dir }}/*.pdf"
+    as: file
+    max_concurrency: 4
+    allow_failures: true
+    do:
+      id: read
+      tool: read_file
+      params: { path: "{{ file }}" }
+    reduce: { id: summary, prompt: "Summarize {{ extract.results }}" }
+"#,
+    )
+    .unwrap();
+    let StepType::Map { files, body, max_concurrency, allow_failures, reduce, .. } =
+        &workflow.root.step_type
+    else {
+        panic!("expected map step");
+    };
+    assert_eq!(files, "{{ inputs.dir }}/*.pdf");
+    assert_eq!(*max_concurrency, Some(4));
+    assert!(*allow_failures);
+    assert!(matches!(
+        &body.step_type,
+        StepType::Tool { tool, params } if tool == "read_file" && params["path"] == "{{ file }}"
+    ));
+    assert_eq!(reduce.as_ref().unwrap().id, "summary");
+
+    let err = parse_workflow_yaml(
+        "id: bad\nsteps:\n  - id: s\n    tool: read_file\n    reduce: { id: r, tool: x }\n",
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("'reduce' cannot be used with 'tool'"));
+}
+
+#[test]
+fn test_rejects_ambiguous_step() {
+    let err = parse_workflow_yaml(
+        "id: bad\nagent: a\nsteps:\n  - id: s\n    prompt: Hi\n    wait_for: deploy\n",
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("exactly one of"));
+}
+
+#[test]
+fn test_rejects_misplaced_field() {
+    let err = parse_workflow_yaml(
+        "id: bad\nsteps:\n  - id: s\n    wait_for: deploy\n    tools: [x]\n",
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("'tools' cannot be used with 'wait_for'"));
+}
+
+#[test]
+fn test_rejects_unknown_field() {
+    let err = parse_workflow_yaml("id: bad\nsteps:\n  - id: s\n    promt: Hi\n").unwrap_err();
+    assert!(err.to_string().contains("promt"));
+}
+
+#[test]
+fn test_requires_agent() {
+    let err = parse_workflow_yaml("id: bad\nsteps:\n  - id: s\n    prompt: Hi\n").unwrap_err();
+    assert!(err.to_string().contains("no agent given"));
+}
+
+#[test]
+fn test_rejects_duplicate_step_ids() {
+    let err = parse_workflow_yaml(
+        "id: dup\nagent: a\nsteps:\n  - id: s\n    prompt: one\n  - id: s\n    prompt: two\n",
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("duplicate step id 's'"));
+}
+
+#[tokio::test]
+async fn test_register_workflow_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("report.yaml"), REPORT).unwrap();
+    std::fs::write(dir.path().join("broken.yml"), "id: broken\nsteps: []\n").unwrap();
+    std::fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+    let loaded = load_workflow_dir(dir.path());
+    assert_eq!(loaded.len(), 2);
+    assert!(loaded[0].1.is_err());
+
+    let store = MemoryWorkflowStore::new();
+    assert_eq!(register_workflow_dir(&store, dir.path()).await.unwrap(), 1);
+    assert!(store.load("weekly-report").await.unwrap().is_some());
+
+    assert!(load_workflow_dir(&dir.path().join("missing")).is_empty());
+}
+
+#[test]
+fn test_approval_step() {
+    let workflow = parse_workflow_yaml(
+        "id: release\nsteps:\n  - id: confirm\n    approval: Ship it?\n    timeout_secs: 600\n    on_timeout: approve\n",
+    )
+    .unwrap();
+    assert_eq!(workflow.root.timeout_secs, Some(600));
+    assert!(matches!(
+        &workflow.root.step_type,
+        StepType::Approval { message, on_timeout: Some(ApprovalTimeoutPolicy::Approve) } if message == "Ship it?"
+    ));
+    assert!(workflow.root.requires_approval());
+}
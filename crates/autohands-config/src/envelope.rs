@@ -0,0 +1,118 @@
+//! Passphrase envelopes.
+//!
+//! Seals data with an AEAD cipher under a key derived from a passphrase
+//! (PBKDF2-HMAC-SHA256). An envelope is the salt, the nonce, then the
+//! ciphertext with its tag. The secrets file and encrypted backups both use
+//! it, each with its own cipher and its magic bytes as associated data.
+
+use std::num::NonZeroU32;
+
+use ring::aead::{self, Aad, LessSafeKey, Nonce, UnboundKey, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use thiserror::Error;
+
+/// Length of the key derivation salt.
+pub const SALT_LEN: usize = 16;
+
+/// PBKDF2 iterations of the key derivation.
+pub const PBKDF2_ITERATIONS: u32 = 600_000;
+
+/// Cipher sealing an envelope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cipher {
+    Aes256Gcm,
+    ChaCha20Poly1305,
+}
+
+impl Cipher {
+    fn algorithm(self) -> &'static aead::Algorithm {
+        match self {
+            Self::Aes256Gcm => &aead::AES_256_GCM,
+            Self::ChaCha20Poly1305 => &aead::CHACHA20_POLY1305,
+        }
+    }
+}
+
+/// Why an envelope could not be sealed or opened.
+#[derive(Debug, Error)]
+pub enum EnvelopeError {
+    #[error("failed to generate random bytes")]
+    Random,
+
+    #[error("invalid encryption key")]
+    Key,
+
+    #[error("encryption failed")]
+    Encrypt,
+
+    #[error("the data is truncated")]
+    Truncated,
+
+    #[error("wrong passphrase or corrupted data")]
+    Decrypt,
+}
+
+/// Encrypt `plain`: salt, nonce, then the ciphertext with its tag.
+pub fn seal(
+    cipher: Cipher,
+    passphrase: &str,
+    aad: &[u8],
+    mut plain: Vec<u8>,
+) -> Result<Vec<u8>, EnvelopeError> {
+    let rng = SystemRandom::new();
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill(&mut salt)
+        .and_then(|_| rng.fill(&mut nonce))
+        .map_err(|_| EnvelopeError::Random)?;
+
+    derive_key(cipher, passphrase, &salt)?
+        .seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::from(aad), &mut plain)
+        .map_err(|_| EnvelopeError::Encrypt)?;
+
+    let mut sealed = Vec::with_capacity(SALT_LEN + NONCE_LEN + plain.len());
+    sealed.extend_from_slice(&salt);
+    sealed.extend_from_slice(&nonce);
+    sealed.extend_from_slice(&plain);
+    Ok(sealed)
+}
+
+/// Decrypt what [`seal`] produced with the same cipher and `aad`.
+pub fn open(
+    cipher: Cipher,
+    passphrase: &str,
+    aad: &[u8],
+    mut sealed: Vec<u8>,
+) -> Result<Vec<u8>, EnvelopeError> {
+    if sealed.len() < SALT_LEN + NONCE_LEN {
+        return Err(EnvelopeError::Truncated);
+    }
+    let mut ciphertext = sealed.split_off(SALT_LEN + NONCE_LEN);
+    let (salt, nonce) = sealed.split_at(SALT_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| EnvelopeError::Truncated)?;
+
+    let plain_len = derive_key(cipher, passphrase, salt)?
+        .open_in_place(nonce, Aad::from(aad), &mut ciphertext)
+        .map_err(|_| EnvelopeError::Decrypt)?
+        .len();
+    ciphertext.truncate(plain_len);
+    Ok(ciphertext)
+}
+
+fn derive_key(cipher: Cipher, passphrase: &str, salt: &[u8]) -> Result<LessSafeKey, EnvelopeError> {
+    let mut key = [0u8; 32];
+    let iterations = NonZeroU32::new(PBKDF2_ITERATIONS).expect("iterations are non-zero");
+    ring::pbkdf2::derive(
+        ring::pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    let key = UnboundKey::new(cipher.algorithm(), &key).map_err(|_| EnvelopeError::Key)?;
+    Ok(LessSafeKey::new(key))
+}
+
+#[cfg(test)]
+#[path = "envelope_tests.rs"]
+mod tests;
@@ -0,0 +1,27 @@
+use super::*;
+
+#[test]
+fn test_seal_and_open() {
+    for cipher in [Cipher::Aes256Gcm, Cipher::ChaCha20Poly1305] {
+        let sealed = seal(cipher, "pass", b"AAD!", b"secret data".to_vec()).unwrap();
+        assert_eq!(sealed.len(), SALT_LEN + NONCE_LEN + 11 + 16);
+        assert!(!sealed.windows(6).any(|w| w == b"secret"));
+        assert_eq!(open(cipher, "pass", b"AAD!", sealed).unwrap(), b"secret data");
+    }
+}
+
+#[test]
+fn test_open_failures() {
+    let sealed = seal(Cipher::Aes256Gcm, "pass", b"AAD!", b"secret".to_vec()).unwrap();
+    for (cipher, passphrase, aad) in [
+        (Cipher::Aes256Gcm, "wrong", b"AAD!"),
+        (Cipher::Aes256Gcm, "pass", b"OTHR"),
+        (Cipher::ChaCha20Poly1305, "pass", b"AAD!"),
+    ] {
+        let err = open(cipher, passphrase, aad, sealed.clone()).unwrap_err();
+        assert!(matches!(err, EnvelopeError::Decrypt), "{}", err);
+    }
+    let truncated = sealed[..SALT_LEN].to_vec();
+    let err = open(Cipher::Aes256Gcm, "pass", b"AAD!", truncated).unwrap_err();
+    assert!(matches!(err, EnvelopeError::Truncated));
+}
@@ -24,6 +24,9 @@ pub enum ConfigError {
 
     #[error("TOML parse error: {0}")]
     TomlParse(#[from] toml::de::Error),
+
+    #[error("Secret error: {0}")]
+    Secret(String),
 }
 
 #[cfg(test)]
@@ -94,6 +97,7 @@ mod tests {
                 message: "m".to_string(),
             },
             ConfigError::EnvVarNotSet("VAR".to_string()),
+            ConfigError::Secret("secret".to_string()),
         ];
 
         for err in errors {
@@ -0,0 +1,299 @@
+//! Layered configuration.
+//!
+//! The effective configuration is built from these layers, each overriding
+//! the ones before it:
+//!
+//! 1. Built-in defaults
+//! 2. The TOML file (with `${VAR}` expansion), with the profile selected by
+//!    `--profile` or `AUTOHANDS_PROFILE` applied (see [`crate::profile`])
+//! 3. Environment variables: `AUTOHANDS_<SECTION>__<KEY>`, with `__`
+//!    separating the levels (e.g. `AUTOHANDS_SERVER__PORT=9000`,
+//!    `AUTOHANDS_PROVIDERS__OPENAI__API_KEY=sk-...`)
+//! 4. Command-line overrides (`--set server.port=9000`)
+//!
+//! Values of the last two layers are read as TOML (`9000`, `true`,
+//! `["a", "b"]`), falling back to a plain string. Where the default is a
+//! string, the value is always kept as one.
+//!
+//! Provider API keys also come from the conventional `<NAME>_API_KEY`
+//! variables when the file and `AUTOHANDS_*` leave them unset. Without any
+//! configured provider, `ANTHROPIC_API_KEY` and `ARK_API_KEY` add one.
+
+use std::path::{Path, PathBuf};
+
+use toml::{Table, Value};
+
+use crate::error::ConfigError;
+use crate::loader::ConfigLoader;
+use crate::profile::{apply_profile, PROFILE_ENV};
+use crate::schema::Config;
+use crate::secrets::{SecretResolver, SECRETS_PASSPHRASE_ENV};
+
+/// Prefix of configuration environment variables.
+pub const ENV_PREFIX: &str = "AUTOHANDS_";
+
+/// Separator of levels in configuration environment variables.
+const ENV_SEPARATOR: &str = "__";
+
+/// Providers added from their API key variable when none is configured.
+const ENV_PROVIDERS: &[&str] = &["anthropic", "ark"];
+
+/// Replacement of redacted values.
+const REDACTED: &str = "<redacted>";
+
+/// Sources of the effective configuration, lowest precedence first.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigLayers {
+    file: Option<PathBuf>,
+    profile: Option<String>,
+    env: Vec<(String, String)>,
+    overrides: Vec<(String, String)>,
+}
+
+impl ConfigLayers {
+    /// Create layers holding only the defaults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the configuration file.
+    pub fn with_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.file = Some(path.into());
+        self
+    }
+
+    /// Select a profile of the file, over `AUTOHANDS_PROFILE`.
+    pub fn with_profile(mut self, name: impl Into<String>) -> Self {
+        self.profile = Some(name.into());
+        self
+    }
+
+    /// Set the environment, usually `std::env::vars()`.
+    pub fn with_env(mut self, vars: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.env = vars.into_iter().collect();
+        self
+    }
+
+    /// Add a command-line override of a dotted key (e.g. `server.port`).
+    pub fn with_override(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.overrides.push((key.into(), value.into()));
+        self
+    }
+
+    /// Configuration file, if any.
+    pub fn file(&self) -> Option<&Path> {
+        self.file.as_deref()
+    }
+
+    /// Selected profile, if any.
+    pub fn profile(&self) -> Option<String> {
+        self.profile.clone().or_else(|| self.env_var(PROFILE_ENV))
+    }
+
+    /// Merge the layers into the effective configuration.
+    ///
+    /// `secret://` references are kept as they are.
+    pub fn resolve(&self) -> Result<Config, ConfigError> {
+        to_config(Value::Table(self.merge()?))
+    }
+
+    /// Merge the layers, then replace `secret://` references with the
+    /// secrets they name, through the stores of the `[secrets]` section.
+    ///
+    /// The secrets file is opened with the passphrase in
+    /// `AUTOHANDS_SECRETS_PASSPHRASE`. Vault is reached with blocking
+    /// requests.
+    pub fn resolve_with_secrets(&self) -> Result<Config, ConfigError> {
+        let mut value = Value::Table(self.merge()?);
+        let config = to_config(value.clone())?;
+        let resolver = SecretResolver::from_config(
+            &config.secrets,
+            self.env_var(SECRETS_PASSPHRASE_ENV),
+            |name| self.env_var(name),
+        );
+        resolver.resolve_value(&mut value)?;
+        to_config(value)
+    }
+
+    /// Value of an environment variable, if set and not empty.
+    fn env_var(&self, name: &str) -> Option<String> {
+        self.env
+            .iter()
+            .find(|(k, v)| k == name && !v.is_empty())
+            .map(|(_, v)| v.clone())
+    }
+
+    /// Merge the file, environment and override layers.
+    fn merge(&self) -> Result<Table, ConfigError> {
+        let defaults = Value::try_from(Config::default())
+            .map_err(|e| ConfigError::InvalidFormat(e.to_string()))?;
+
+        let mut table = match &self.file {
+            Some(path) => {
+                let content = std::fs::read_to_string(path)?;
+                ConfigLoader::expand_env_vars(&content)?.parse::<Table>()?
+            }
+            None => Table::new(),
+        };
+        apply_profile(&mut table, self.profile().as_deref())?;
+
+        for (name, value) in &self.env {
+            let Some(key) = name.strip_prefix(ENV_PREFIX) else { continue };
+            // Variables without a separator (AUTOHANDS_BACKUP_PASSPHRASE, ...)
+            // are not configuration keys
+            if !key.contains(ENV_SEPARATOR) {
+                continue;
+            }
+            let path: Vec<String> = key
+                .split(ENV_SEPARATOR)
+                .map(|part| part.to_lowercase())
+                .collect();
+            set_path(&mut table, &path, value, &defaults, name)?;
+        }
+        for (key, value) in &self.overrides {
+            let path: Vec<String> = key.split('.').map(str::to_string).collect();
+            set_path(&mut table, &path, value, &defaults, key)?;
+        }
+        self.apply_provider_keys(&mut table);
+        Ok(table)
+    }
+
+    /// Fill provider API keys from `<NAME>_API_KEY`.
+    fn apply_provider_keys(&self, table: &mut Table) {
+        let env_var = |name: &str| self.env_var(&format!("{}_API_KEY", name.to_uppercase()));
+
+        let providers = table
+            .entry("providers")
+            .or_insert_with(|| Value::Table(Table::new()));
+        let Some(providers) = providers.as_table_mut() else { return };
+        if providers.is_empty() {
+            for name in ENV_PROVIDERS {
+                if env_var(name).is_some() {
+                    providers.insert(name.to_string(), Value::Table(Table::new()));
+                }
+            }
+        }
+        for (name, provider) in providers.iter_mut() {
+            let Some(provider) = provider.as_table_mut() else { continue };
+            if provider.contains_key("api_key") {
+                continue;
+            }
+            if let Some(key) = env_var(name) {
+                provider.insert("api_key".to_string(), Value::String(key));
+            }
+        }
+    }
+}
+
+/// Build the configuration from merged layers.
+fn to_config(value: Value) -> Result<Config, ConfigError> {
+    value
+        .try_into()
+        .map_err(|e: toml::de::Error| ConfigError::InvalidFormat(e.to_string()))
+}
+
+/// Split a `key=value` command-line override.
+pub fn parse_override(arg: &str) -> Result<(String, String), ConfigError> {
+    match arg.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.to_string()))
+        }
+        _ => Err(ConfigError::InvalidFormat(format!(
+            "expected KEY=VALUE, got '{}'",
+            arg
+        ))),
+    }
+}
+
+/// Set a value at a key path, creating the tables on the way.
+fn set_path(
+    table: &mut Table,
+    path: &[String],
+    raw: &str,
+    defaults: &Value,
+    source: &str,
+) -> Result<(), ConfigError> {
+    if path.iter().any(|part| part.is_empty()) {
+        return Err(ConfigError::InvalidValue {
+            field: source.to_string(),
+            message: "empty key".to_string(),
+        });
+    }
+
+    let default = path
+        .iter()
+        .try_fold(defaults, |value, part| value.get(part.as_str()));
+    let (last, parents) = path.split_last().expect("path is not empty");
+    let value = parse_value(raw, default.is_some_and(Value::is_str) || is_secret(last));
+
+    let mut current = table;
+    for part in parents {
+        let entry = current
+            .entry(part.as_str())
+            .or_insert_with(|| Value::Table(Table::new()));
+        current = entry.as_table_mut().ok_or_else(|| ConfigError::InvalidValue {
+            field: source.to_string(),
+            message: format!("'{}' is not a table", part),
+        })?;
+    }
+    current.insert(last.clone(), value);
+    Ok(())
+}
+
+/// Read a value as TOML, or as a string.
+fn parse_value(raw: &str, string: bool) -> Value {
+    if string {
+        return Value::String(raw.to_string());
+    }
+    format!("value = {}", raw)
+        .parse::<Table>()
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| Value::String(raw.to_string()))
+}
+
+/// Whether a key holds a secret.
+fn is_secret(key: &str) -> bool {
+    let key = key.to_lowercase();
+    ["api_key", "password", "passphrase", "secret", "token", "authorization"]
+        .iter()
+        .any(|word| key == *word || key.ends_with(&format!("_{}", word)))
+}
+
+/// Replace the values of secret keys (API keys, passwords, tokens).
+fn redact_secrets(value: &mut Value) {
+    match value {
+        Value::Table(table) => {
+            for (key, value) in table.iter_mut() {
+                if is_secret(key) && value.is_str() {
+                    *value = Value::String(REDACTED.to_string());
+                } else {
+                    redact_secrets(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(redact_secrets),
+        _ => {}
+    }
+}
+
+/// Render a TOML file as it is, with secrets redacted.
+pub fn redacted_file(path: &Path) -> Result<String, ConfigError> {
+    let mut value = Value::Table(std::fs::read_to_string(path)?.parse::<Table>()?);
+    redact_secrets(&mut value);
+    toml::to_string_pretty(&value).map_err(|e| ConfigError::InvalidFormat(e.to_string()))
+}
+
+impl Config {
+    /// Render as TOML with secrets redacted.
+    pub fn to_redacted_toml(&self) -> Result<String, ConfigError> {
+        let mut value =
+            Value::try_from(self).map_err(|e| ConfigError::InvalidFormat(e.to_string()))?;
+        redact_secrets(&mut value);
+        toml::to_string_pretty(&value).map_err(|e| ConfigError::InvalidFormat(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+#[path = "layered_tests.rs"]
+mod tests;
@@ -0,0 +1,201 @@
+use super::*;
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+fn env(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+    vars.iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+fn config_file(content: &str) -> NamedTempFile {
+    let mut file = NamedTempFile::new().unwrap();
+    write!(file, "{}", content).unwrap();
+    file
+}
+
+#[test]
+fn test_defaults_only() {
+    let config = ConfigLayers::new().resolve().unwrap();
+    assert_eq!(config.server.port, 8080);
+    assert!(config.runloop.mode_policy);
+    assert!(config.providers.is_empty());
+}
+
+#[test]
+fn test_layer_precedence() {
+    let file = config_file(
+        r#"
+        [server]
+        host = "0.0.0.0"
+        port = 3000
+
+        [agent]
+        max_turns = 20
+        "#,
+    );
+    let config = ConfigLayers::new()
+        .with_file(file.path())
+        .with_env(env(&[
+            ("AUTOHANDS_SERVER__PORT", "4000"),
+            ("AUTOHANDS_AGENT__MAX_TURNS", "30"),
+            ("AUTOHANDS_DAEMON__ENABLED", "true"),
+            ("AUTOHANDS_BACKUP_PASSPHRASE", "not config"),
+            ("HOME", "/root"),
+        ]))
+        .with_override("server.port", "5000")
+        .with_override("runloop.background_after_idle_secs", "0")
+        .resolve()
+        .unwrap();
+
+    // File over defaults, env over file, CLI over env
+    assert_eq!(config.server.host, "0.0.0.0");
+    assert_eq!(config.server.port, 5000);
+    assert_eq!(config.agent.max_turns, 30);
+    assert!(config.daemon.enabled);
+    assert_eq!(config.runloop.background_after_idle_secs, 0);
+}
+
+#[test]
+fn test_values() {
+    let config = ConfigLayers::new()
+        .with_env(env(&[
+            ("AUTOHANDS_AGENT__DEFAULT", "1234"),
+            ("AUTOHANDS_CHANNELS__SLACK__MENTIONS", r#"["@autohands", "@bot"]"#),
+            ("AUTOHANDS_PROVIDERS__OPENAI__API_KEY", "42"),
+        ]))
+        .with_override("agent.overrides.allowed_models", r#"["gpt-4o"]"#)
+        .resolve()
+        .unwrap();
+
+    // String defaults and secrets stay strings
+    assert_eq!(config.agent.default, "1234");
+    assert_eq!(config.providers["openai"].api_key.as_deref(), Some("42"));
+    assert_eq!(config.channels["slack"].mentions, vec!["@autohands", "@bot"]);
+    assert_eq!(config.agent.overrides.allowed_models, vec!["gpt-4o"]);
+
+    let err = ConfigLayers::new()
+        .with_override("server.port", "eighty")
+        .resolve()
+        .unwrap_err();
+    assert!(matches!(err, ConfigError::InvalidFormat(_)));
+    let err = ConfigLayers::new()
+        .with_override("server.port", "80")
+        .with_override("server.port.number", "80")
+        .resolve()
+        .unwrap_err();
+    assert!(matches!(err, ConfigError::InvalidValue { .. }));
+}
+
+#[test]
+fn test_provider_api_keys_from_env() {
+    let file = config_file(
+        r#"
+        [providers.openai]
+        base_url = "https://proxy.example.com"
+
+        [providers.gemini]
+        api_key = "from-file"
+        "#,
+    );
+    let config = ConfigLayers::new()
+        .with_file(file.path())
+        .with_env(env(&[
+            ("OPENAI_API_KEY", "sk-openai"),
+            ("GEMINI_API_KEY", "ignored"),
+            ("ANTHROPIC_API_KEY", "sk-ant"),
+        ]))
+        .resolve()
+        .unwrap();
+    assert_eq!(config.providers["openai"].api_key.as_deref(), Some("sk-openai"));
+    assert_eq!(config.providers["gemini"].api_key.as_deref(), Some("from-file"));
+    // Only added when no provider is configured
+    assert!(!config.providers.contains_key("anthropic"));
+
+    let config = ConfigLayers::new()
+        .with_env(env(&[("ANTHROPIC_API_KEY", "sk-ant"), ("ARK_API_KEY", "")]))
+        .resolve()
+        .unwrap();
+    assert_eq!(config.providers.len(), 1);
+    assert_eq!(config.providers["anthropic"].api_key.as_deref(), Some("sk-ant"));
+}
+
+#[test]
+fn test_parse_override() {
+    assert_eq!(
+        parse_override("server.port=9000").unwrap(),
+        ("server.port".to_string(), "9000".to_string())
+    );
+    assert_eq!(parse_override("agent.default=a=b").unwrap().1, "a=b");
+    assert!(parse_override("server.port").is_err());
+    assert!(parse_override("=1").is_err());
+}
+
+#[test]
+fn test_redacted_toml() {
+    let config = ConfigLayers::new()
+        .with_env(env(&[
+            ("AUTOHANDS_PROVIDERS__OPENAI__API_KEY", "sk-secret"),
+            ("AUTOHANDS_PROVIDERS__OPENAI__EXTRA__BOT_TOKEN", "tok-secret"),
+        ]))
+        .resolve()
+        .unwrap();
+    let shown = config.to_redacted_toml().unwrap();
+    assert!(!shown.contains("sk-secret"));
+    assert!(!shown.contains("tok-secret"));
+    assert!(shown.contains("api_key = \"<redacted>\""));
+    // Not secrets
+    assert!(shown.contains("max_turns = 50"));
+    assert!(!is_secret("max_tokens"));
+
+    let file = config_file(
+        r#"
+        [providers.openai]
+        api_key = "sk-secret"
+        "#,
+    );
+    let shown = redacted_file(file.path()).unwrap();
+    assert!(shown.contains("[providers.openai]"));
+    assert!(shown.contains("api_key = \"<redacted>\""));
+}
+
+#[test]
+fn test_profile_from_flag_or_env() {
+    let file = config_file(
+        r#"
+        [server]
+        port = 3000
+
+        [profile.prod.server]
+        host = "0.0.0.0"
+        port = 80
+        "#,
+    );
+
+    let config = ConfigLayers::new().with_file(file.path()).resolve().unwrap();
+    assert_eq!(config.server.port, 3000);
+
+    let layers = ConfigLayers::new()
+        .with_file(file.path())
+        .with_env(env(&[("AUTOHANDS_PROFILE", "prod")]));
+    assert_eq!(layers.profile().as_deref(), Some("prod"));
+    let config = layers.resolve().unwrap();
+    assert_eq!(config.server.host, "0.0.0.0");
+    assert_eq!(config.server.port, 80);
+
+    // Env and CLI layers still override the profile
+    let config = ConfigLayers::new()
+        .with_file(file.path())
+        .with_profile("prod")
+        .with_env(env(&[("AUTOHANDS_SERVER__PORT", "8443")]))
+        .resolve()
+        .unwrap();
+    assert_eq!(config.server.port, 8443);
+
+    let err = ConfigLayers::new()
+        .with_file(file.path())
+        .with_profile("dev")
+        .resolve()
+        .unwrap_err();
+    assert!(err.to_string().contains("unknown profile 'dev'"));
+}
@@ -2,12 +2,24 @@
 //!
 //! Configuration management for the AutoHands framework.
 
+pub mod envelope;
 mod error;
+mod layered;
 mod loader;
+mod profile;
 mod schema;
+mod secrets;
 mod validator;
+mod validator_file;
 
 pub use error::ConfigError;
+pub use layered::{parse_override, redacted_file, ConfigLayers, ENV_PREFIX};
 pub use loader::ConfigLoader;
+pub use profile::{apply_profile, profile_names, PROFILE_ENV, PROFILE_KEY};
 pub use schema::*;
+pub use secrets::{
+    EncryptedFileStore, KeyringStore, SecretResolver, SecretStore, VaultStore, SECRETS_PASSPHRASE_ENV,
+    SECRET_SCHEME,
+};
 pub use validator::{ConfigValidator, ValidationError, ValidationResult, ValidationWarning};
+pub use validator_file::{Diagnostic, FileValidation, Severity};
@@ -26,7 +26,7 @@ impl ConfigLoader {
     }
 
     /// Expand environment variables in the format `${VAR}`.
-    fn expand_env_vars(content: &str) -> Result<String, ConfigError> {
+    pub(crate) fn expand_env_vars(content: &str) -> Result<String, ConfigError> {
         let mut result = content.to_string();
         let re = regex::Regex::new(r"\$\{([^}]+)\}").unwrap();
 
@@ -0,0 +1,130 @@
+//! Configuration profiles.
+//!
+//! One file can hold several deployments of the same agent under
+//! `[profile.<name>]` tables, e.g. a local interactive setup and a 24/7
+//! server:
+//!
+//! ```toml
+//! [agent]
+//! max_turns = 50
+//!
+//! [profile.dev.agent]
+//! default = "general"
+//!
+//! [profile.prod]
+//! inherits = "dev"
+//!
+//! [profile.prod.approval]
+//! tool_risk_threshold = "medium"
+//! ```
+//!
+//! A profile holds keys of the configuration and overrides the rest of the
+//! file: tables are merged key by key, other values (arrays included) are
+//! replaced. `inherits` names a profile applied first. The profile is
+//! selected with `--profile` or `AUTOHANDS_PROFILE`; without one, the
+//! profiles are ignored.
+
+use toml::{Table, Value};
+
+use crate::error::ConfigError;
+
+/// Table holding the profiles.
+pub const PROFILE_KEY: &str = "profile";
+
+/// Environment variable selecting the profile.
+pub const PROFILE_ENV: &str = "AUTOHANDS_PROFILE";
+
+/// Key of a profile naming the profile it builds on.
+pub(crate) const INHERITS_KEY: &str = "inherits";
+
+/// Remove the profiles from a configuration file's table, applying the
+/// selected one.
+pub fn apply_profile(table: &mut Table, name: Option<&str>) -> Result<(), ConfigError> {
+    let profiles = match table.remove(PROFILE_KEY) {
+        Some(Value::Table(profiles)) => profiles,
+        Some(_) => return Err(invalid(PROFILE_KEY, "must be a table of profiles")),
+        None => Table::new(),
+    };
+    let Some(name) = name else {
+        return Ok(());
+    };
+    for profile in chain(&profiles, name)?.into_iter().rev() {
+        merge(table, profile);
+    }
+    Ok(())
+}
+
+/// Names of the profiles in a configuration file's table.
+pub fn profile_names(table: &Table) -> Vec<String> {
+    let mut names: Vec<String> = table
+        .get(PROFILE_KEY)
+        .and_then(Value::as_table)
+        .map(|profiles| profiles.keys().cloned().collect())
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+/// A profile and the profiles it inherits, nearest first, without their
+/// `inherits` keys.
+fn chain(profiles: &Table, name: &str) -> Result<Vec<Table>, ConfigError> {
+    let mut seen: Vec<&str> = Vec::new();
+    let mut tables = Vec::new();
+    let mut next = Some(name);
+    while let Some(name) = next {
+        let field = format!("{}.{}", PROFILE_KEY, name);
+        if seen.contains(&name) {
+            seen.push(name);
+            return Err(invalid(&field, &format!("inherits itself ({})", seen.join(" -> "))));
+        }
+        let profile = match profiles.get(name) {
+            Some(Value::Table(profile)) => profile,
+            Some(_) => return Err(invalid(&field, "must be a table")),
+            None => {
+                let mut known: Vec<&str> = profiles.keys().map(String::as_str).collect();
+                known.sort();
+                let known = if known.is_empty() {
+                    "none defined".to_string()
+                } else {
+                    format!("available: {}", known.join(", "))
+                };
+                return Err(invalid(PROFILE_KEY, &format!("unknown profile '{}' ({})", name, known)));
+            }
+        };
+        seen.push(name);
+        next = match profile.get(INHERITS_KEY) {
+            Some(Value::String(parent)) => Some(parent.as_str()),
+            Some(_) => {
+                return Err(invalid(&format!("{}.{}", field, INHERITS_KEY), "must be a profile name"));
+            }
+            None => None,
+        };
+        let mut profile = profile.clone();
+        profile.remove(INHERITS_KEY);
+        tables.push(profile);
+    }
+    Ok(tables)
+}
+
+/// Merge a table into another: tables key by key, other values replaced.
+fn merge(base: &mut Table, overlay: Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base)), Value::Table(overlay)) => merge(base, overlay),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+fn invalid(field: &str, message: &str) -> ConfigError {
+    ConfigError::InvalidValue {
+        field: field.to_string(),
+        message: message.to_string(),
+    }
+}
+
+#[cfg(test)]
+#[path = "profile_tests.rs"]
+mod tests;
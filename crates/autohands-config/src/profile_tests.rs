@@ -0,0 +1,75 @@
+use super::*;
+
+fn table(content: &str) -> Table {
+    content.parse::<Table>().unwrap()
+}
+
+const PROFILES: &str = r#"
+[agent]
+max_turns = 50
+default = "general"
+
+[providers.anthropic]
+default_model = "claude-sonnet"
+
+[profile.dev.agent]
+max_turns = 10
+
+[profile.prod]
+inherits = "dev"
+
+[profile.prod.agent]
+timeout_seconds = 900
+
+[profile.prod.providers.anthropic]
+default_model = "claude-opus"
+"#;
+
+#[test]
+fn test_no_profile_drops_profiles() {
+    let mut config = table(PROFILES);
+    apply_profile(&mut config, None).unwrap();
+    assert!(config.get(PROFILE_KEY).is_none());
+    assert_eq!(config["agent"]["max_turns"].as_integer(), Some(50));
+}
+
+#[test]
+fn test_profile_inherits_and_merges() {
+    let mut config = table(PROFILES);
+    apply_profile(&mut config, Some("prod")).unwrap();
+    // From dev, through inheritance
+    assert_eq!(config["agent"]["max_turns"].as_integer(), Some(10));
+    // From prod
+    assert_eq!(config["agent"]["timeout_seconds"].as_integer(), Some(900));
+    assert_eq!(config["providers"]["anthropic"]["default_model"].as_str(), Some("claude-opus"));
+    // From the file, untouched
+    assert_eq!(config["agent"]["default"].as_str(), Some("general"));
+    assert!(config["agent"].get("inherits").is_none());
+}
+
+#[test]
+fn test_unknown_profile() {
+    let mut config = table(PROFILES);
+    let err = apply_profile(&mut config, Some("staging")).unwrap_err();
+    assert!(err.to_string().contains("unknown profile 'staging' (available: dev, prod)"));
+}
+
+#[test]
+fn test_inheritance_cycle() {
+    let mut config = table(
+        r#"
+        [profile.a]
+        inherits = "b"
+        [profile.b]
+        inherits = "a"
+        "#,
+    );
+    let err = apply_profile(&mut config, Some("a")).unwrap_err();
+    assert!(err.to_string().contains("a -> b -> a"), "{}", err);
+}
+
+#[test]
+fn test_profile_names() {
+    assert_eq!(profile_names(&table(PROFILES)), vec!["dev", "prod"]);
+    assert!(profile_names(&table("[agent]\nmax_turns = 5\n")).is_empty());
+}
@@ -1,5 +1,6 @@
 //! Configuration schema definitions.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -18,7 +19,7 @@ pub(crate) fn default_true() -> bool {
 }
 
 /// Root configuration.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct Config {
     #[serde(default)]
     pub server: ServerConfig,
@@ -42,6 +43,12 @@ pub struct Config {
     #[serde(default)]
     pub daemon: DaemonConfig,
 
+    #[serde(default)]
+    pub runloop: RunLoopConfig,
+
+    #[serde(default)]
+    pub secrets: SecretsConfig,
+
     #[serde(default)]
     pub scheduler: SchedulerConfig,
 
@@ -59,16 +66,44 @@ pub struct Config {
 
     #[serde(default)]
     pub monitor: MonitorConfig,
+
+    #[serde(default)]
+    pub approval: ApprovalConfig,
+
+    #[serde(default)]
+    pub audit: AuditConfig,
+
+    #[serde(default)]
+    pub redaction: RedactionConfig,
+
+    #[serde(default)]
+    pub features: FeaturesConfig,
+
+    #[serde(default)]
+    pub channels: HashMap<String, ChannelConfig>,
+}
+
+impl Config {
+    /// JSON Schema of the configuration file, for editors and
+    /// [`ConfigValidator::validate_source`](crate::ConfigValidator::validate_source).
+    pub fn json_schema() -> serde_json::Value {
+        serde_json::to_value(schemars::schema_for!(Config))
+            .expect("JSON Schema serializes to JSON")
+    }
 }
 
 /// Server configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ServerConfig {
     #[serde(default = "default_host")]
     pub host: String,
 
     #[serde(default = "default_port")]
     pub port: u16,
+
+    /// How long `Idempotency-Key`s are remembered, in seconds.
+    #[serde(default = "default_idempotency_ttl")]
+    pub idempotency_ttl_secs: u64,
 }
 
 impl Default for ServerConfig {
@@ -76,6 +111,7 @@ impl Default for ServerConfig {
         Self {
             host: default_host(),
             port: default_port(),
+            idempotency_ttl_secs: default_idempotency_ttl(),
         }
     }
 }
@@ -88,8 +124,12 @@ fn default_port() -> u16 {
     8080
 }
 
+fn default_idempotency_ttl() -> u64 {
+    24 * 60 * 60
+}
+
 /// Agent configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AgentConfig {
     #[serde(default = "default_agent")]
     pub default: String,
@@ -99,6 +139,36 @@ pub struct AgentConfig {
 
     #[serde(default = "default_timeout")]
     pub timeout_seconds: u64,
+
+    /// Stream model turns, continuing streams that fail halfway instead of
+    /// losing their output.
+    #[serde(default)]
+    pub stream_turns: bool,
+
+    /// Long-context model a run switches to when its context overflows,
+    /// before the history is compressed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_fallback_model: Option<String>,
+
+    /// Limits on per-run model, generation and tool overrides.
+    #[serde(default)]
+    pub overrides: OverridesConfig,
+
+    /// Rebuilding the system prompt of running agents.
+    #[serde(default)]
+    pub context_refresh: ContextRefreshConfig,
+
+    /// Project brief given to new sessions (`[agent.project_brief]`).
+    #[serde(default)]
+    pub project_brief: ProjectBriefConfig,
+
+    /// Filesystem sandbox of every agent without one in `sandboxes`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox: Option<SandboxConfig>,
+
+    /// Filesystem sandboxes by agent ID (`[agent.sandboxes.<id>]`).
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub sandboxes: HashMap<String, SandboxConfig>,
 }
 
 impl Default for AgentConfig {
@@ -107,6 +177,207 @@ impl Default for AgentConfig {
             default: default_agent(),
             max_turns: default_max_turns(),
             timeout_seconds: default_timeout(),
+            stream_turns: false,
+            context_fallback_model: None,
+            overrides: OverridesConfig::default(),
+            context_refresh: ContextRefreshConfig::default(),
+            project_brief: ProjectBriefConfig::default(),
+            sandbox: None,
+            sandboxes: HashMap::new(),
+        }
+    }
+}
+
+impl AgentConfig {
+    /// Sandbox of an agent.
+    pub fn sandbox_for(&self, agent_id: &str) -> Option<&SandboxConfig> {
+        self.sandboxes.get(agent_id).or(self.sandbox.as_ref())
+    }
+}
+
+/// Paths an agent's file and shell tools may use (`[agent.sandbox]`).
+///
+/// Tools always stay inside their working directory; the sandbox allows
+/// more roots, denies paths inside them, can forbid writes and can run
+/// shell commands in a container.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SandboxConfig {
+    /// Directories usable besides the working directory (`~` is expanded).
+    #[serde(default)]
+    pub allowed_roots: Vec<String>,
+
+    /// Glob patterns of denied paths, e.g. `~/.ssh` or `*.pem`.
+    #[serde(default)]
+    pub denied: Vec<String>,
+
+    /// Refuse file writes and shell commands.
+    #[serde(default)]
+    pub read_only: bool,
+
+    /// Run `exec` commands in an ephemeral container.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container: Option<ContainerConfig>,
+}
+
+/// Container `exec` commands run in (`[agent.sandbox.container]`).
+///
+/// The working directory and the allowed roots are mounted at their host
+/// paths; nothing else of the host is visible.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ContainerConfig {
+    /// Container engine, `docker` or `podman`.
+    #[serde(default = "default_container_engine")]
+    pub engine: String,
+
+    /// Image the commands run in.
+    #[serde(default = "default_container_image")]
+    pub image: String,
+
+    /// CPUs a command may use.
+    #[serde(default = "default_container_cpus")]
+    pub cpus: f64,
+
+    /// Memory limit, e.g. `512m` or `2g`.
+    #[serde(default = "default_container_memory")]
+    pub memory: String,
+
+    /// Give commands network access.
+    #[serde(default)]
+    pub network: bool,
+}
+
+impl Default for ContainerConfig {
+    fn default() -> Self {
+        Self {
+            engine: default_container_engine(),
+            image: default_container_image(),
+            cpus: default_container_cpus(),
+            memory: default_container_memory(),
+            network: false,
+        }
+    }
+}
+
+fn default_container_engine() -> String {
+    "docker".to_string()
+}
+
+fn default_container_image() -> String {
+    "debian:bookworm-slim".to_string()
+}
+
+fn default_container_cpus() -> f64 {
+    1.0
+}
+
+fn default_container_memory() -> String {
+    "1g".to_string()
+}
+
+/// System prompt refresh of running agents (`[agent.context_refresh]`).
+///
+/// The prompt is rebuilt from the prompt template, the skill metadata and
+/// the memories matching `memory_query`, so standing agents pick up new
+/// knowledge without being recreated.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ContextRefreshConfig {
+    /// Seconds between scheduled refreshes (0 = only on changes).
+    #[serde(default)]
+    pub interval_secs: u64,
+
+    /// Prompt template file replacing the built-in prompt; re-read on
+    /// every refresh.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_file: Option<PathBuf>,
+
+    /// Memories matching this query are added to the prompt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_query: Option<String>,
+
+    /// Most memories added to the prompt.
+    #[serde(default = "default_memory_limit")]
+    pub memory_limit: usize,
+
+    /// Refresh when the configuration or prompt template file changes.
+    #[serde(default = "default_true")]
+    pub on_file_change: bool,
+
+    /// Seconds between checks for changed files.
+    #[serde(default = "default_file_check_secs")]
+    pub file_check_secs: u64,
+}
+
+impl Default for ContextRefreshConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: 0,
+            prompt_file: None,
+            memory_query: None,
+            memory_limit: default_memory_limit(),
+            on_file_change: true,
+            file_check_secs: default_file_check_secs(),
+        }
+    }
+}
+
+fn default_memory_limit() -> usize {
+    5
+}
+
+fn default_file_check_secs() -> u64 {
+    10
+}
+
+/// Project brief of new sessions (`[agent.project_brief]`).
+///
+/// The stack, key manifests and README of the working directory are added
+/// to the context when a session starts.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ProjectBriefConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Token budget of the brief.
+    #[serde(default = "default_brief_tokens")]
+    pub max_tokens: usize,
+}
+
+impl Default for ProjectBriefConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_tokens: default_brief_tokens(),
+        }
+    }
+}
+
+fn default_brief_tokens() -> usize {
+    1500
+}
+
+/// Per-run override policy (`[agent.overrides]`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct OverridesConfig {
+    /// Whether tasks may override the agent's model, temperature, output
+    /// tokens and tools.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Models tasks may switch to (empty = any model the provider lists).
+    #[serde(default)]
+    pub allowed_models: Vec<String>,
+
+    /// Highest output token limit a task may ask for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+}
+
+impl Default for OverridesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            allowed_models: Vec::new(),
+            max_tokens: None,
         }
     }
 }
@@ -124,7 +395,7 @@ fn default_timeout() -> u64 {
 }
 
 /// Provider configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ProviderConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub api_key: Option<String>,
@@ -140,20 +411,27 @@ pub struct ProviderConfig {
 }
 
 /// Extensions configuration.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct ExtensionsConfig {
+    /// Plugin shared libraries, or directories of them, loaded at startup.
     #[serde(default)]
     pub paths: Vec<PathBuf>,
 
+    /// Extensions to load, by ID (e.g. "tools-shell"); empty loads all.
     #[serde(default)]
     pub enabled: Vec<String>,
 
+    /// Extensions never to load, even if enabled.
     #[serde(default)]
     pub disabled: Vec<String>,
+
+    /// Configuration passed to each extension, by ID.
+    #[serde(default)]
+    pub config: HashMap<String, serde_json::Value>,
 }
 
 /// Skills configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SkillsConfig {
     /// Additional skill directories.
     #[serde(default)]
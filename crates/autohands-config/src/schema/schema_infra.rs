@@ -1,12 +1,15 @@
-//! Infrastructure configuration types (scheduler, queue, checkpoint, orchestrator, monitor).
+//! Infrastructure configuration types (scheduler, queue, checkpoint, orchestrator, monitor,
+//! approval, feature flags, RunLoop, secret stores).
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use super::default_true;
 
 /// Scheduler configuration for cron jobs.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SchedulerConfig {
     /// Whether scheduler is enabled.
     #[serde(default = "default_true")]
@@ -19,24 +22,42 @@ pub struct SchedulerConfig {
     /// Scheduled jobs.
     #[serde(default)]
     pub jobs: Vec<ScheduledJob>,
+
+    /// Most job runs in progress at once (0 = unlimited).
+    #[serde(default = "default_max_concurrent_jobs")]
+    pub max_concurrent_jobs: usize,
+
+    /// Most jobs agents may create through the cron tools.
+    #[serde(default = "default_max_agent_jobs")]
+    pub max_agent_jobs: usize,
 }
 
 fn default_timezone() -> String {
     "UTC".to_string()
 }
 
+fn default_max_concurrent_jobs() -> usize {
+    4
+}
+
+fn default_max_agent_jobs() -> usize {
+    20
+}
+
 impl Default for SchedulerConfig {
     fn default() -> Self {
         Self {
             enabled: default_true(),
             timezone: default_timezone(),
             jobs: Vec::new(),
+            max_concurrent_jobs: default_max_concurrent_jobs(),
+            max_agent_jobs: default_max_agent_jobs(),
         }
     }
 }
 
 /// A scheduled job definition.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ScheduledJob {
     /// Unique job ID.
     pub id: String,
@@ -49,7 +70,7 @@ pub struct ScheduledJob {
 }
 
 /// Queue configuration for task processing.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct QueueConfig {
     /// Maximum number of concurrent workers.
     #[serde(default = "default_max_workers")]
@@ -83,7 +104,7 @@ impl Default for QueueConfig {
 }
 
 /// Checkpoint configuration for recovery.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CheckpointConfig {
     /// Whether checkpointing is enabled.
     #[serde(default = "default_true")]
@@ -122,7 +143,7 @@ impl Default for CheckpointConfig {
 }
 
 /// Orchestrator configuration for multi-agent workflows.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct OrchestratorConfig {
     /// Whether orchestrator is enabled.
     #[serde(default = "default_true")]
@@ -156,7 +177,7 @@ impl Default for OrchestratorConfig {
 }
 
 /// Monitor configuration for observability.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MonitorConfig {
     /// Whether monitoring is enabled.
     #[serde(default = "default_true")]
@@ -169,6 +190,22 @@ pub struct MonitorConfig {
     /// Metrics endpoint path.
     #[serde(default = "default_metrics_endpoint")]
     pub metrics_endpoint: String,
+
+    /// Seconds between provider health probes (0 = no probes).
+    #[serde(default = "default_provider_probe_interval")]
+    pub provider_probe_interval_secs: u64,
+
+    /// Seconds a provider health probe may take.
+    #[serde(default = "default_provider_probe_timeout")]
+    pub provider_probe_timeout_secs: u64,
+
+    /// Consecutive failed probes or requests that make a provider unhealthy.
+    #[serde(default = "default_provider_unhealthy_after")]
+    pub provider_unhealthy_after: u32,
+
+    /// Model to probe, by provider (the cheapest known model otherwise).
+    #[serde(default)]
+    pub provider_probe_models: HashMap<String, String>,
 }
 
 fn default_health_endpoint() -> String {
@@ -179,12 +216,393 @@ fn default_metrics_endpoint() -> String {
     "/metrics".to_string()
 }
 
+fn default_provider_probe_interval() -> u64 {
+    60
+}
+
+fn default_provider_probe_timeout() -> u64 {
+    15
+}
+
+fn default_provider_unhealthy_after() -> u32 {
+    2
+}
+
 impl Default for MonitorConfig {
     fn default() -> Self {
         Self {
             enabled: default_true(),
             health_endpoint: default_health_endpoint(),
             metrics_endpoint: default_metrics_endpoint(),
+            provider_probe_interval_secs: default_provider_probe_interval(),
+            provider_probe_timeout_secs: default_provider_probe_timeout(),
+            provider_unhealthy_after: default_provider_unhealthy_after(),
+            provider_probe_models: HashMap::new(),
+        }
+    }
+}
+
+/// Human approval configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ApprovalConfig {
+    /// Tools at or above this risk level ("low", "medium", "high") need
+    /// approval. Tool approval is off when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_risk_threshold: Option<String>,
+
+    /// What happens to tool calls by risk level and tool
+    /// (`[approval.tool_policy]`). Takes precedence over
+    /// `tool_risk_threshold`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_policy: Option<ToolPolicyConfig>,
+
+    /// Run every tool call without approval, for trusted environments
+    /// (`autohands run --yolo`).
+    #[serde(default)]
+    pub yolo: bool,
+
+    /// Seconds to wait for a decision when the request does not say.
+    #[serde(default = "default_approval_timeout")]
+    pub timeout_secs: u64,
+
+    /// Outcome when nobody decides in time ("reject" or "approve").
+    #[serde(default = "default_on_timeout")]
+    pub on_timeout: String,
+
+    /// Slack incoming webhook announcing new requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slack_webhook_url: Option<String>,
+
+    /// Webhook receiving new requests as JSON.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+}
+
+fn default_approval_timeout() -> u64 {
+    3600
+}
+
+fn default_on_timeout() -> String {
+    "reject".to_string()
+}
+
+impl Default for ApprovalConfig {
+    fn default() -> Self {
+        Self {
+            tool_risk_threshold: None,
+            tool_policy: None,
+            yolo: false,
+            timeout_secs: default_approval_timeout(),
+            on_timeout: default_on_timeout(),
+            slack_webhook_url: None,
+            webhook_url: None,
+        }
+    }
+}
+
+/// Audit log of tool calls, approvals and agent runs (`[audit]`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AuditConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Log file (default `~/.autohands/audit.jsonl`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+
+    /// Parameter names redacted besides the built-in ones (password,
+    /// token, api_key, ...), matched ignoring case anywhere in the name.
+    #[serde(default)]
+    pub redact_keys: Vec<String>,
+
+    /// Regular expressions of values redacted besides the built-in
+    /// credential formats.
+    #[serde(default)]
+    pub redact_patterns: Vec<String>,
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            path: None,
+            redact_keys: Vec::new(),
+            redact_patterns: Vec::new(),
+        }
+    }
+}
+
+/// Secret redaction in logs, transcripts, tool results and the context
+/// sent to LLM providers (`[redaction]`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RedactionConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Regular expressions of secrets redacted besides the built-in
+    /// credential formats (API keys, bearer tokens, AWS credentials).
+    #[serde(default)]
+    pub patterns: Vec<String>,
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            patterns: Vec::new(),
+        }
+    }
+}
+
+/// Tool approval policy: "allow" runs a tool call, "prompt" waits for a
+/// person to approve it and "deny" refuses it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ToolPolicyConfig {
+    #[serde(default = "default_low_risk_action")]
+    pub low: String,
+
+    #[serde(default = "default_medium_risk_action")]
+    pub medium: String,
+
+    #[serde(default = "default_high_risk_action")]
+    pub high: String,
+
+    /// Actions of specific tools, whatever their risk level.
+    #[serde(default)]
+    pub tools: HashMap<String, String>,
+}
+
+fn default_low_risk_action() -> String {
+    "allow".to_string()
+}
+
+fn default_medium_risk_action() -> String {
+    "prompt".to_string()
+}
+
+fn default_high_risk_action() -> String {
+    "deny".to_string()
+}
+
+impl Default for ToolPolicyConfig {
+    fn default() -> Self {
+        Self {
+            low: default_low_risk_action(),
+            medium: default_medium_risk_action(),
+            high: default_high_risk_action(),
+            tools: HashMap::new(),
         }
     }
 }
+
+/// Feature flag configuration.
+///
+/// Flags gate risky capabilities (`parallel_tools`, `auto_approval`,
+/// `provider:<id>`). Overrides set through the admin API take precedence.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct FeaturesConfig {
+    /// Global flag values.
+    #[serde(default)]
+    pub flags: HashMap<String, bool>,
+
+    /// Flag values per workspace, keyed by working directory.
+    #[serde(default)]
+    pub workspaces: HashMap<String, HashMap<String, bool>>,
+}
+
+/// Inbound rules of a channel, keyed by channel ID under `[channels]`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ChannelConfig {
+    /// Mentions a message must start with (e.g. "@autohands"). Empty
+    /// handles every message.
+    #[serde(default)]
+    pub mentions: Vec<String>,
+
+    /// Prefix of slash-style commands (e.g. "/").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command_prefix: Option<String>,
+
+    /// Commands by name.
+    #[serde(default)]
+    pub commands: HashMap<String, ChannelCommandConfig>,
+
+    /// Senders whose messages are handled. Empty allows everyone.
+    #[serde(default)]
+    pub allowed_senders: Vec<String>,
+
+    /// Minimum seconds between two tasks of a conversation (0 = none).
+    #[serde(default)]
+    pub cooldown_secs: u64,
+
+    /// Tasks a conversation may start per hour (0 = unlimited).
+    #[serde(default)]
+    pub max_tasks_per_hour: u32,
+
+    /// Reply to a throttled conversation; `{retry_after}` is the wait in
+    /// seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub throttle_message: Option<String>,
+
+    /// Places the results of the channel's tasks are also delivered to.
+    #[serde(default)]
+    pub deliver_to: Vec<ChannelDeliveryConfig>,
+}
+
+/// Extra place a channel's task results are delivered to.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ChannelDeliveryConfig {
+    /// Channel to deliver to.
+    pub channel_id: String,
+
+    /// Recipient within the channel.
+    pub target: String,
+
+    /// Rendering: "markdown", "plain", "json" or "file".
+    #[serde(default = "default_delivery_format")]
+    pub format: String,
+
+    /// Attachment name for the "file" format.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+}
+
+fn default_delivery_format() -> String {
+    "markdown".to_string()
+}
+
+/// Task a channel command runs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ChannelCommandConfig {
+    /// Task type ("agent:execute" when unset).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_type: Option<String>,
+
+    /// Agent running the command.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+
+    /// Prompt template; `{args}` is replaced by the command's arguments.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+}
+
+/// RunLoop configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RunLoopConfig {
+    /// Whether the RunLoop switches to AgentProcessing mode while agents
+    /// run, and to Background mode when idle.
+    #[serde(default = "default_true")]
+    pub mode_policy: bool,
+
+    /// Idle time before dropping to Background mode, in seconds (0 = never).
+    #[serde(default = "default_background_after_idle_secs")]
+    pub background_after_idle_secs: u64,
+
+    /// Whether to collect RunLoop metrics.
+    #[serde(default = "default_true")]
+    pub metrics_enabled: bool,
+
+    /// Attempts per task lifecycle callback before it is dropped.
+    #[serde(default = "default_callback_max_attempts")]
+    pub callback_max_attempts: u32,
+
+    /// Task lifecycle callback request timeout, in seconds.
+    #[serde(default = "default_callback_timeout_secs")]
+    pub callback_timeout_secs: u64,
+
+    /// Hosts task lifecycle callbacks may reach although they are internal
+    /// (loopback, private or link-local addresses).
+    #[serde(default)]
+    pub callback_allowed_hosts: Vec<String>,
+
+    /// Whether to keep a journal of the RunLoop's triggers, phases and
+    /// dispatch decisions (`autohands runloop events`).
+    #[serde(default)]
+    pub journal: bool,
+
+    /// Number of journal entries kept.
+    #[serde(default = "default_journal_capacity")]
+    pub journal_capacity: usize,
+}
+
+fn default_background_after_idle_secs() -> u64 {
+    300
+}
+
+fn default_callback_max_attempts() -> u32 {
+    5
+}
+
+fn default_callback_timeout_secs() -> u64 {
+    10
+}
+
+fn default_journal_capacity() -> usize {
+    10_000
+}
+
+impl Default for RunLoopConfig {
+    fn default() -> Self {
+        Self {
+            mode_policy: default_true(),
+            background_after_idle_secs: default_background_after_idle_secs(),
+            metrics_enabled: default_true(),
+            callback_max_attempts: default_callback_max_attempts(),
+            callback_timeout_secs: default_callback_timeout_secs(),
+            callback_allowed_hosts: Vec::new(),
+            journal: false,
+            journal_capacity: default_journal_capacity(),
+        }
+    }
+}
+
+/// Secret stores resolving `secret://` references.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SecretsConfig {
+    /// Keyring service holding `secret://keyring/<account>` entries.
+    #[serde(default = "default_keyring_service")]
+    pub keyring_service: String,
+
+    /// Encrypted file holding `secret://file/<name>` entries.
+    #[serde(default = "default_secrets_file")]
+    pub file: PathBuf,
+
+    /// HashiCorp Vault holding `secret://vault/<mount>/<path>#<field>`
+    /// entries.
+    #[serde(default)]
+    pub vault: VaultConfig,
+}
+
+fn default_keyring_service() -> String {
+    "autohands".to_string()
+}
+
+fn default_secrets_file() -> PathBuf {
+    PathBuf::from("~/.autohands/secrets.enc")
+}
+
+impl Default for SecretsConfig {
+    fn default() -> Self {
+        Self {
+            keyring_service: default_keyring_service(),
+            file: default_secrets_file(),
+            vault: VaultConfig::default(),
+        }
+    }
+}
+
+/// HashiCorp Vault connection (KV version 2 secrets engine).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct VaultConfig {
+    /// Server address; `VAULT_ADDR` when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub addr: Option<String>,
+
+    /// Token; `VAULT_TOKEN` when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+
+    /// Enterprise namespace.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+}
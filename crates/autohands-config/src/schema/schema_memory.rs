@@ -1,10 +1,11 @@
 //! Memory-related configuration types.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 /// Memory configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MemoryConfig {
     /// Memory backend type ("markdown", "sqlite", etc.).
     #[serde(default = "default_backend")]
@@ -17,10 +18,94 @@ pub struct MemoryConfig {
     /// Persistent storage configuration.
     #[serde(default)]
     pub persistent: PersistentMemoryConfig,
+
+    /// How long histories are summarized.
+    #[serde(default)]
+    pub summarizer: SummarizerSettings,
+
+    /// Scheduled daily digest of sessions.
+    #[serde(default)]
+    pub digest: DigestConfig,
+}
+
+/// Summarization strategy settings.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SummarizerSettings {
+    /// Strategy: "single", "map_reduce", "rolling" or "hierarchical".
+    #[serde(default = "default_summarizer_strategy")]
+    pub strategy: String,
+
+    /// Messages per chunk for the chunked strategies.
+    #[serde(default = "default_chunk_size")]
+    pub chunk_size: usize,
+}
+
+impl Default for SummarizerSettings {
+    fn default() -> Self {
+        Self {
+            strategy: default_summarizer_strategy(),
+            chunk_size: default_chunk_size(),
+        }
+    }
+}
+
+fn default_summarizer_strategy() -> String {
+    "single".to_string()
+}
+
+fn default_chunk_size() -> usize {
+    20
+}
+
+/// Daily digest configuration.
+///
+/// Once a day the sessions of that day are summarized into a single memory
+/// entry, optionally also sent through a channel.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DigestConfig {
+    /// Whether the digest job runs.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Local time of day to run at, as "HH:MM".
+    #[serde(default = "default_digest_time")]
+    pub time: String,
+
+    /// Summarization strategy for the digest.
+    #[serde(default = "default_digest_strategy")]
+    pub strategy: String,
+
+    /// Channel to deliver the digest through (e.g. "slack").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+
+    /// Recipient on the channel (chat or channel ID).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+}
+
+impl Default for DigestConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            time: default_digest_time(),
+            strategy: default_digest_strategy(),
+            channel: None,
+            target: None,
+        }
+    }
+}
+
+fn default_digest_time() -> String {
+    "23:55".to_string()
+}
+
+fn default_digest_strategy() -> String {
+    "hierarchical".to_string()
 }
 
 /// Persistent memory storage configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PersistentMemoryConfig {
     /// Whether persistent memory storage is enabled.
     #[serde(default = "default_persistent_enabled")]
@@ -75,6 +160,8 @@ impl Default for MemoryConfig {
             backend: default_backend(),
             path: None,
             persistent: PersistentMemoryConfig::default(),
+            summarizer: SummarizerSettings::default(),
+            digest: DigestConfig::default(),
         }
     }
 }
@@ -14,6 +14,7 @@ fn test_server_config_default() {
     let server = ServerConfig::default();
     assert_eq!(server.host, "127.0.0.1");
     assert_eq!(server.port, 8080);
+    assert_eq!(server.idempotency_ttl_secs, 86400);
 }
 
 #[test]
@@ -22,6 +23,10 @@ fn test_agent_config_default() {
     assert_eq!(agent.default, "general");
     assert_eq!(agent.max_turns, 50);
     assert_eq!(agent.timeout_seconds, 300);
+    assert!(!agent.stream_turns);
+    assert!(agent.context_fallback_model.is_none());
+    assert!(agent.overrides.enabled);
+    assert!(agent.overrides.allowed_models.is_empty());
 }
 
 #[test]
@@ -33,6 +38,9 @@ fn test_memory_config_default() {
     assert!(memory.persistent.enabled);
     assert_eq!(memory.persistent.max_memories, 1000);
     assert!(memory.persistent.auto_cleanup);
+    assert_eq!(memory.summarizer.strategy, "single");
+    assert!(!memory.digest.enabled);
+    assert_eq!(memory.digest.time, "23:55");
 }
 
 #[test]
@@ -115,6 +123,20 @@ fn test_extensions_config_with_paths() {
     assert_eq!(ext.disabled.len(), 1);
 }
 
+#[test]
+fn test_extensions_config_per_extension_config() {
+    let toml_str = r#"
+        [extensions]
+        disabled = ["tools-desktop"]
+
+        [extensions.config.tools-shell]
+        timeout_secs = 30
+    "#;
+    let config: Config = toml::from_str(toml_str).unwrap();
+    assert_eq!(config.extensions.disabled, vec!["tools-desktop"]);
+    assert_eq!(config.extensions.config["tools-shell"]["timeout_secs"], 30);
+}
+
 #[test]
 fn test_config_clone() {
     let config = Config::default();
@@ -155,11 +177,92 @@ fn test_toml_deserialization() {
         [agent]
         default = "test"
         max_turns = 25
+        stream_turns = true
+        context_fallback_model = "long-context-model"
     "#;
     let config: Config = toml::from_str(toml).unwrap();
     assert_eq!(config.server.host, "0.0.0.0");
     assert_eq!(config.server.port, 9000);
     assert_eq!(config.agent.default, "test");
+    assert!(config.agent.stream_turns);
+    assert_eq!(config.agent.context_fallback_model.as_deref(), Some("long-context-model"));
+}
+
+#[test]
+fn test_agent_overrides_deserialization() {
+    let toml = r#"
+        [agent.overrides]
+        allowed_models = ["fast-model"]
+        max_tokens = 4096
+    "#;
+    let config: Config = toml::from_str(toml).unwrap();
+    assert!(config.agent.overrides.enabled);
+    assert_eq!(config.agent.overrides.allowed_models, vec!["fast-model"]);
+    assert_eq!(config.agent.overrides.max_tokens, Some(4096));
+}
+
+#[test]
+fn test_agent_context_refresh_deserialization() {
+    let config = Config::default();
+    assert_eq!(config.agent.context_refresh.interval_secs, 0);
+    assert!(config.agent.context_refresh.on_file_change);
+
+    let toml = r#"
+        [agent.context_refresh]
+        interval_secs = 600
+        prompt_file = "prompts/general.md"
+        memory_query = "user preferences"
+    "#;
+    let config: Config = toml::from_str(toml).unwrap();
+    let refresh = &config.agent.context_refresh;
+    assert_eq!(refresh.interval_secs, 600);
+    assert_eq!(refresh.prompt_file.as_deref(), Some(std::path::Path::new("prompts/general.md")));
+    assert_eq!(refresh.memory_query.as_deref(), Some("user preferences"));
+    assert_eq!(refresh.memory_limit, 5);
+}
+
+#[test]
+fn test_agent_project_brief_deserialization() {
+    let config = Config::default();
+    assert!(config.agent.project_brief.enabled);
+    assert_eq!(config.agent.project_brief.max_tokens, 1500);
+
+    let config: Config = toml::from_str("[agent.project_brief]\nmax_tokens = 500\n").unwrap();
+    assert!(config.agent.project_brief.enabled);
+    assert_eq!(config.agent.project_brief.max_tokens, 500);
+}
+
+#[test]
+fn test_agent_sandbox_deserialization() {
+    assert!(Config::default().agent.sandbox_for("general").is_none());
+
+    let toml = r#"
+        [agent.sandbox]
+        allowed_roots = ["~/projects"]
+        denied = ["~/.ssh", "*.pem"]
+
+        [agent.sandboxes.reviewer]
+        read_only = true
+
+        [agent.sandboxes.builder.container]
+        image = "rust:1"
+        memory = "4g"
+    "#;
+    let config: Config = toml::from_str(toml).unwrap();
+    let general = config.agent.sandbox_for("general").unwrap();
+    assert_eq!(general.allowed_roots, vec!["~/projects"]);
+    assert_eq!(general.denied, vec!["~/.ssh", "*.pem"]);
+    assert!(!general.read_only);
+    let reviewer = config.agent.sandbox_for("reviewer").unwrap();
+    assert!(reviewer.read_only);
+    assert!(reviewer.denied.is_empty());
+    assert!(reviewer.container.is_none());
+    let container = config.agent.sandbox_for("builder").unwrap().container.clone().unwrap();
+    assert_eq!(container.engine, "docker");
+    assert_eq!(container.image, "rust:1");
+    assert_eq!(container.memory, "4g");
+    assert_eq!(container.cpus, 1.0);
+    assert!(!container.network);
 }
 
 #[test]
@@ -187,6 +290,8 @@ fn test_scheduler_config_default() {
     assert!(config.enabled);
     assert_eq!(config.timezone, "UTC");
     assert!(config.jobs.is_empty());
+    assert_eq!(config.max_concurrent_jobs, 4);
+    assert_eq!(config.max_agent_jobs, 20);
 }
 
 #[test]
@@ -219,6 +324,18 @@ fn test_monitor_config_default() {
     assert!(config.enabled);
     assert_eq!(config.health_endpoint, "/health");
     assert_eq!(config.metrics_endpoint, "/metrics");
+    assert_eq!(config.provider_probe_interval_secs, 60);
+    assert_eq!(config.provider_unhealthy_after, 2);
+}
+
+#[test]
+fn test_approval_config_default() {
+    let config = ApprovalConfig::default();
+    assert!(config.tool_risk_threshold.is_none());
+    assert!(config.tool_policy.is_none());
+    assert!(!config.yolo);
+    assert_eq!(config.timeout_secs, 3600);
+    assert_eq!(config.on_timeout, "reject");
 }
 
 #[test]
@@ -236,6 +353,7 @@ fn test_full_config_with_24_7_features() {
         [scheduler]
         enabled = true
         timezone = "Asia/Shanghai"
+        max_concurrent_jobs = 2
 
         [[scheduler.jobs]]
         id = "daily-report"
@@ -266,8 +384,59 @@ fn test_full_config_with_24_7_features() {
     assert_eq!(config.scheduler.timezone, "Asia/Shanghai");
     assert_eq!(config.scheduler.jobs.len(), 1);
     assert_eq!(config.scheduler.jobs[0].id, "daily-report");
+    assert_eq!(config.scheduler.max_concurrent_jobs, 2);
+    assert_eq!(config.scheduler.max_agent_jobs, 20);
     assert_eq!(config.queue.max_workers, 8);
     assert_eq!(config.checkpoint.interval_turns, 10);
     assert_eq!(config.orchestrator.max_concurrent_workflows, 10);
     assert_eq!(config.monitor.health_endpoint, "/api/health");
 }
+
+#[test]
+fn test_features_config() {
+    let toml = r#"
+        [features.flags]
+        parallel_tools = true
+        "provider:gemini" = false
+
+        [features.workspaces."/srv/repo"]
+        auto_approval = true
+    "#;
+
+    let config: Config = toml::from_str(toml).unwrap();
+    assert_eq!(config.features.flags.get("parallel_tools"), Some(&true));
+    assert_eq!(config.features.flags.get("provider:gemini"), Some(&false));
+    assert_eq!(
+        config.features.workspaces["/srv/repo"].get("auto_approval"),
+        Some(&true)
+    );
+    assert!(Config::default().features.flags.is_empty());
+}
+
+#[test]
+fn test_channels_config() {
+    let toml = r#"
+        [channels.slack]
+        mentions = ["@autohands"]
+        command_prefix = "/"
+        allowed_senders = ["U123"]
+        cooldown_secs = 10
+        max_tasks_per_hour = 30
+
+        [channels.slack.commands.review]
+        agent = "reviewer"
+        prompt = "Review {args}"
+    "#;
+
+    let config: Config = toml::from_str(toml).unwrap();
+    let slack = &config.channels["slack"];
+    assert_eq!(slack.mentions, vec!["@autohands"]);
+    assert_eq!(slack.command_prefix.as_deref(), Some("/"));
+    assert_eq!(slack.allowed_senders, vec!["U123"]);
+    assert_eq!(slack.commands["review"].agent.as_deref(), Some("reviewer"));
+    assert!(slack.commands["review"].task_type.is_none());
+    assert_eq!(slack.cooldown_secs, 10);
+    assert_eq!(slack.max_tasks_per_hour, 30);
+    assert!(slack.throttle_message.is_none());
+    assert!(Config::default().channels.is_empty());
+}
@@ -1,12 +1,13 @@
 //! Trigger and daemon configuration types.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 use super::default_true;
 
 /// Daemon configuration for 24/7 operation.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DaemonConfig {
     /// Whether daemon mode is enabled.
     #[serde(default = "default_daemon_enabled")]
@@ -54,7 +55,7 @@ impl Default for DaemonConfig {
 }
 
 /// Triggers configuration for event-driven execution.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct TriggersConfig {
     /// Webhook triggers.
     #[serde(default)]
@@ -63,10 +64,18 @@ pub struct TriggersConfig {
     /// File watcher triggers.
     #[serde(default)]
     pub file_watchers: Vec<FileWatcherTriggerConfig>,
+
+    /// Email (IMAP) triggers.
+    #[serde(default)]
+    pub email: Vec<EmailTriggerConfig>,
+
+    /// MQTT brokers.
+    #[serde(default)]
+    pub mqtt: Vec<MqttTriggerConfig>,
 }
 
 /// Webhook trigger configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct WebhookTriggerConfig {
     /// Trigger ID.
     pub id: String,
@@ -77,14 +86,216 @@ pub struct WebhookTriggerConfig {
 }
 
 /// File watcher trigger configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Binds changed files to a prompt template or a workflow, e.g. new
+/// `*.pdf` files in `~/Inbox` to "Summarize {path} and file it".
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct FileWatcherTriggerConfig {
     /// Trigger ID.
     pub id: String,
-    /// Paths to watch.
+    /// Paths to watch (`~` is expanded).
     pub paths: Vec<PathBuf>,
-    /// Agent to trigger.
+    /// Glob patterns a changed path must match (empty = every path).
+    #[serde(default)]
+    pub patterns: Vec<String>,
+    /// Glob patterns of changed paths to ignore.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Whether paths ignored by `.gitignore` files are left out.
+    #[serde(default)]
+    pub respect_gitignore: bool,
+    /// Kinds of change acted on: "created", "modified", "deleted",
+    /// "renamed" (empty = every kind).
+    #[serde(default)]
+    pub events: Vec<String>,
+    /// Agent to trigger (the default agent when unset).
+    #[serde(default)]
     pub agent: String,
-    /// Prompt to execute.
+    /// Prompt template; `{path}`, `{file_name}`, `{change_type}`, `{paths}`
+    /// and `{count}` describe the changes.
+    #[serde(default)]
     pub prompt: String,
+    /// Workflow to run instead of prompting the agent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workflow: Option<String>,
+    /// Whether each changed file gets its own run instead of one per batch.
+    #[serde(default)]
+    pub per_file: bool,
+    /// Quiet period, in milliseconds, before a batch of changes is handled.
+    #[serde(default = "default_debounce_ms")]
+    pub debounce_ms: u64,
+    /// Whether the trigger is enabled.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_debounce_ms() -> u64 {
+    500
+}
+
+/// Email trigger configuration.
+///
+/// Polls an IMAP mailbox and runs the agent on unseen messages matching the
+/// sender and subject filters, with the message body and attachments as
+/// context.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EmailTriggerConfig {
+    /// Trigger ID.
+    pub id: String,
+    /// IMAP server host.
+    pub host: String,
+    /// IMAP server port.
+    #[serde(default = "default_imap_port")]
+    pub port: u16,
+    /// Whether to connect over TLS.
+    #[serde(default = "default_true")]
+    pub tls: bool,
+    /// Login user name.
+    pub username: String,
+    /// Login password (usually `${VAR}` from the environment).
+    #[serde(default, skip_serializing)]
+    pub password: String,
+    /// Mailbox to poll.
+    #[serde(default = "default_mailbox")]
+    pub mailbox: String,
+    /// Seconds between polls.
+    #[serde(default = "default_poll_interval")]
+    pub poll_interval_secs: u64,
+    /// Sender address globs, e.g. `*@example.com` (empty = every sender).
+    #[serde(default)]
+    pub from: Vec<String>,
+    /// Text the subject must contain, case-insensitively (empty = any).
+    #[serde(default)]
+    pub subject: Vec<String>,
+    /// Agent to trigger (the default agent when unset).
+    #[serde(default)]
+    pub agent: String,
+    /// Instructions put before the message; `{from}` and `{subject}` are
+    /// replaced.
+    #[serde(default)]
+    pub prompt: String,
+    /// Workflow to run instead of prompting the agent, e.g. `inbox-zero`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workflow: Option<String>,
+    /// Mailbox messages matching an archive rule are moved to, e.g.
+    /// `Archive`, without running anything.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_mailbox: Option<String>,
+    /// Sender address globs whose messages are archived.
+    #[serde(default)]
+    pub archive_from: Vec<String>,
+    /// Text whose presence in the subject, case-insensitively, archives a
+    /// message.
+    #[serde(default)]
+    pub archive_subject: Vec<String>,
+    /// Whether handled messages are flagged as seen.
+    #[serde(default = "default_true")]
+    pub mark_seen: bool,
+    /// Directory attachments are saved to (`~` is expanded).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attachment_dir: Option<PathBuf>,
+    /// Whether the trigger is enabled.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+/// MQTT broker configuration.
+///
+/// Messages on the subscribed topics run the agent; the broker is also a
+/// channel, named by `id`, whose targets are topics results are published
+/// to, e.g. for Home Assistant.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MqttTriggerConfig {
+    /// Broker ID, also the ID of its channel.
+    #[serde(default = "default_mqtt_id")]
+    pub id: String,
+    /// Broker host.
+    pub host: String,
+    /// Broker port.
+    #[serde(default = "default_mqtt_port")]
+    pub port: u16,
+    /// Client ID presented to the broker.
+    #[serde(default = "default_mqtt_client_id")]
+    pub client_id: String,
+    /// Login user name (anonymous when unset).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    /// Login password (usually `${VAR}` from the environment).
+    #[serde(default, skip_serializing)]
+    pub password: String,
+    /// Keep-alive interval in seconds.
+    #[serde(default = "default_mqtt_keep_alive")]
+    pub keep_alive_secs: u64,
+    /// Topics that run the agent.
+    #[serde(default)]
+    pub subscriptions: Vec<MqttSubscriptionConfig>,
+    /// QoS (0-2) of published results.
+    #[serde(default = "default_mqtt_publish_qos")]
+    pub publish_qos: u8,
+    /// Whether published results are retained by the broker.
+    #[serde(default)]
+    pub retain: bool,
+    /// Whether the broker is enabled.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+/// Topic subscription of an MQTT broker.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MqttSubscriptionConfig {
+    /// Topic filter; `+` and `#` wildcards are allowed.
+    pub topic: String,
+    /// Subscription QoS (0-2).
+    #[serde(default)]
+    pub qos: u8,
+    /// Agent to trigger (the default agent when unset).
+    #[serde(default)]
+    pub agent: String,
+    /// Prompt template; `{topic}` and `{payload}` are replaced. The payload
+    /// is the prompt when unset.
+    #[serde(default)]
+    pub prompt: String,
+    /// Topic results are published to; `{topic}` is the message's topic.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_topic: Option<String>,
+    /// Rendering of published results: "markdown", "plain", "json" or
+    /// "file".
+    #[serde(default = "default_reply_format")]
+    pub reply_format: String,
+}
+
+fn default_mqtt_id() -> String {
+    "mqtt".to_string()
+}
+
+fn default_mqtt_port() -> u16 {
+    1883
+}
+
+fn default_mqtt_client_id() -> String {
+    "autohands".to_string()
+}
+
+fn default_mqtt_keep_alive() -> u64 {
+    30
+}
+
+fn default_mqtt_publish_qos() -> u8 {
+    1
+}
+
+fn default_reply_format() -> String {
+    "markdown".to_string()
+}
+
+fn default_imap_port() -> u16 {
+    993
+}
+
+fn default_mailbox() -> String {
+    "INBOX".to_string()
+}
+
+fn default_poll_interval() -> u64 {
+    60
 }
@@ -0,0 +1,114 @@
+//! Encrypted secrets file.
+//!
+//! Secrets are kept as a JSON map, encrypted with AES-256-GCM under a key
+//! derived from a passphrase (see [`crate::envelope`]). The file holds a
+//! magic header, then the envelope: the salt, the nonce and the ciphertext
+//! with its tag.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use super::SecretStore;
+use crate::envelope::{self, Cipher};
+use crate::error::ConfigError;
+
+/// Magic bytes of a secrets file.
+const MAGIC: &[u8; 4] = b"AHSF";
+
+/// Secrets in a passphrase-encrypted file.
+pub struct EncryptedFileStore {
+    path: PathBuf,
+    passphrase: String,
+    /// Secrets read by [`SecretStore::get`], decrypted once.
+    cache: OnceLock<BTreeMap<String, String>>,
+}
+
+impl EncryptedFileStore {
+    /// Create a store for a file, which need not exist yet.
+    pub fn new(path: impl Into<PathBuf>, passphrase: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            passphrase: passphrase.into(),
+            cache: OnceLock::new(),
+        }
+    }
+
+    /// Path of the file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read all secrets. A missing file holds none.
+    pub fn load(&self) -> Result<BTreeMap<String, String>, ConfigError> {
+        if !self.path.exists() {
+            return Ok(BTreeMap::new());
+        }
+        let data = std::fs::read(&self.path)?;
+        let sealed = data.strip_prefix(MAGIC.as_slice()).ok_or_else(|| {
+            ConfigError::Secret(format!("{} is not a secrets file", self.path.display()))
+        })?;
+        let plain = envelope::open(Cipher::Aes256Gcm, &self.passphrase, MAGIC, sealed.to_vec())
+            .map_err(|e| ConfigError::Secret(format!("Failed to decrypt secrets: {}", e)))?;
+        serde_json::from_slice(&plain).map_err(|e| ConfigError::Secret(e.to_string()))
+    }
+
+    /// Write all secrets, replacing the file (readable by the owner only).
+    pub fn save(&self, secrets: &BTreeMap<String, String>) -> Result<(), ConfigError> {
+        let plain = serde_json::to_vec(secrets).map_err(|e| ConfigError::Secret(e.to_string()))?;
+        let mut data = MAGIC.to_vec();
+        data.extend(
+            envelope::seal(Cipher::Aes256Gcm, &self.passphrase, MAGIC, plain)
+                .map_err(|e| ConfigError::Secret(format!("Failed to encrypt secrets: {}", e)))?,
+        );
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = self.path.with_extension("tmp");
+        std::fs::write(&tmp, data)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&tmp, std::fs::Permissions::from_mode(0o600))?;
+        }
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+
+    /// Add or replace a secret.
+    pub fn set(&self, name: &str, value: &str) -> Result<(), ConfigError> {
+        let mut secrets = self.load()?;
+        secrets.insert(name.to_string(), value.to_string());
+        self.save(&secrets)
+    }
+
+    /// Remove a secret, returning whether it existed.
+    pub fn remove(&self, name: &str) -> Result<bool, ConfigError> {
+        let mut secrets = self.load()?;
+        let removed = secrets.remove(name).is_some();
+        if removed {
+            self.save(&secrets)?;
+        }
+        Ok(removed)
+    }
+}
+
+impl SecretStore for EncryptedFileStore {
+    fn backend(&self) -> &str {
+        "file"
+    }
+
+    fn get(&self, path: &str) -> Result<String, ConfigError> {
+        let secrets = match self.cache.get() {
+            Some(secrets) => secrets,
+            None => {
+                let secrets = self.load()?;
+                self.cache.get_or_init(|| secrets)
+            }
+        };
+        secrets.get(path).cloned().ok_or_else(|| {
+            ConfigError::Secret(format!("No secret '{}' in {}", path, self.path.display()))
+        })
+    }
+}
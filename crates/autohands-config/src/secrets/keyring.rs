@@ -0,0 +1,60 @@
+//! OS keyring store.
+//!
+//! Reads generic passwords through the platform's command-line client:
+//! `security` for the macOS Keychain, `secret-tool` for the Secret Service
+//! (GNOME Keyring, KWallet) on Linux.
+
+use std::process::Command;
+
+use super::SecretStore;
+use crate::error::ConfigError;
+
+/// Secrets in the OS keyring, stored under one service name.
+pub struct KeyringStore {
+    service: String,
+}
+
+impl KeyringStore {
+    /// Create a store for a service name.
+    pub fn new(service: impl Into<String>) -> Self {
+        Self {
+            service: service.into(),
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    fn command(&self, account: &str) -> Command {
+        let mut command = Command::new("security");
+        command.args(["find-generic-password", "-s", &self.service, "-a", account, "-w"]);
+        command
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn command(&self, account: &str) -> Command {
+        let mut command = Command::new("secret-tool");
+        command.args(["lookup", "service", &self.service, "account", account]);
+        command
+    }
+}
+
+impl SecretStore for KeyringStore {
+    fn backend(&self) -> &str {
+        "keyring"
+    }
+
+    fn get(&self, path: &str) -> Result<String, ConfigError> {
+        let output = self.command(path).output().map_err(|e| {
+            ConfigError::Secret(format!("Cannot read the keyring: {}", e))
+        })?;
+        let secret = String::from_utf8_lossy(&output.stdout);
+        // Only the trailing newline of the client is dropped
+        let secret = secret.strip_suffix('\n').unwrap_or(&secret);
+        if !output.status.success() || secret.is_empty() {
+            return Err(ConfigError::Secret(format!(
+                "No keyring entry for service '{}', account '{}'",
+                self.service, path
+            )));
+        }
+        Ok(secret.to_string())
+    }
+}
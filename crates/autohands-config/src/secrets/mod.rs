@@ -0,0 +1,134 @@
+//! Secret stores.
+//!
+//! Configuration values may be references instead of plaintext secrets:
+//!
+//! - `secret://keyring/<account>`: the OS keyring (macOS Keychain, Secret
+//!   Service on Linux), under the `keyring_service` of `[secrets]`
+//! - `secret://file/<name>`: the encrypted secrets file
+//! - `secret://vault/<mount>/<path>#<field>`: HashiCorp Vault (KV v2)
+//!
+//! [`ConfigLayers::resolve_with_secrets`](crate::ConfigLayers::resolve_with_secrets)
+//! replaces every reference in the configuration with the secret it names.
+
+mod encrypted_file;
+mod keyring;
+mod vault;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::error::ConfigError;
+use crate::loader::ConfigLoader;
+use crate::schema::SecretsConfig;
+
+pub use encrypted_file::EncryptedFileStore;
+pub use keyring::KeyringStore;
+pub use vault::VaultStore;
+
+/// Scheme of secret references.
+pub const SECRET_SCHEME: &str = "secret://";
+
+/// Environment variable holding the passphrase of the secrets file.
+pub const SECRETS_PASSPHRASE_ENV: &str = "AUTOHANDS_SECRETS_PASSPHRASE";
+
+/// Backend holding secrets.
+pub trait SecretStore: Send + Sync {
+    /// Backend name, the host part of references (`keyring`, `file`, ...).
+    fn backend(&self) -> &str;
+
+    /// Look up the secret at a path.
+    fn get(&self, path: &str) -> Result<String, ConfigError>;
+}
+
+/// Resolves `secret://` references through the stores.
+#[derive(Default, Clone)]
+pub struct SecretResolver {
+    stores: HashMap<String, Arc<dyn SecretStore>>,
+}
+
+impl SecretResolver {
+    /// Create a resolver without stores.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a resolver with the stores of a configuration.
+    ///
+    /// The secrets file is only readable with a passphrase, and Vault only
+    /// with an address (`VAULT_ADDR` and `VAULT_TOKEN` fill in for unset
+    /// values).
+    pub fn from_config(
+        config: &SecretsConfig,
+        passphrase: Option<String>,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Self {
+        let mut resolver = Self::new().with_store(Arc::new(KeyringStore::new(&config.keyring_service)));
+        if let Some(passphrase) = passphrase {
+            let path = ConfigLoader::expand_path(&config.file.to_string_lossy());
+            resolver = resolver.with_store(Arc::new(EncryptedFileStore::new(path, passphrase)));
+        }
+        if let Some(addr) = config.vault.addr.clone().or_else(|| env("VAULT_ADDR")) {
+            let token = config.vault.token.clone().or_else(|| env("VAULT_TOKEN"));
+            resolver = resolver.with_store(Arc::new(VaultStore::new(
+                addr,
+                token,
+                config.vault.namespace.clone(),
+            )));
+        }
+        resolver
+    }
+
+    /// Add a store, replacing any store of the same backend.
+    pub fn with_store(mut self, store: Arc<dyn SecretStore>) -> Self {
+        self.stores.insert(store.backend().to_string(), store);
+        self
+    }
+
+    /// Whether a value is a secret reference.
+    pub fn is_reference(value: &str) -> bool {
+        value.starts_with(SECRET_SCHEME)
+    }
+
+    /// Look up the secret a reference names.
+    pub fn resolve(&self, reference: &str) -> Result<String, ConfigError> {
+        let rest = reference.strip_prefix(SECRET_SCHEME).ok_or_else(|| {
+            ConfigError::Secret(format!("'{}' is not a secret reference", reference))
+        })?;
+        let (backend, path) = rest
+            .split_once('/')
+            .filter(|(_, path)| !path.is_empty())
+            .ok_or_else(|| ConfigError::Secret(format!("'{}' names no secret", reference)))?;
+        let store = self.stores.get(backend).ok_or_else(|| {
+            ConfigError::Secret(format!(
+                "No '{}' secret store configured for {}",
+                backend, reference
+            ))
+        })?;
+        store.get(path)
+    }
+
+    /// Replace every secret reference in a configuration value.
+    pub fn resolve_value(&self, value: &mut toml::Value) -> Result<(), ConfigError> {
+        match value {
+            toml::Value::String(s) if Self::is_reference(s) => {
+                *s = self.resolve(s)?;
+            }
+            toml::Value::Array(values) => {
+                for value in values {
+                    self.resolve_value(value)?;
+                }
+            }
+            toml::Value::Table(table) => {
+                for (_, value) in table.iter_mut() {
+                    self.resolve_value(value)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "secrets_tests.rs"]
+mod tests;
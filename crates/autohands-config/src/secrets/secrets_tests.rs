@@ -0,0 +1,172 @@
+use super::*;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use tempfile::TempDir;
+
+/// Store serving fixed secrets.
+struct MapStore(HashMap<String, String>);
+
+impl SecretStore for MapStore {
+    fn backend(&self) -> &str {
+        "test"
+    }
+
+    fn get(&self, path: &str) -> Result<String, ConfigError> {
+        self.0
+            .get(path)
+            .cloned()
+            .ok_or_else(|| ConfigError::Secret(format!("No secret '{}'", path)))
+    }
+}
+
+fn resolver() -> SecretResolver {
+    let secrets = [("openai", "sk-openai"), ("mqtt", "hunter2")]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    SecretResolver::new().with_store(Arc::new(MapStore(secrets)))
+}
+
+#[test]
+fn test_resolve_reference() {
+    let resolver = resolver();
+    assert_eq!(resolver.resolve("secret://test/openai").unwrap(), "sk-openai");
+    assert!(resolver.resolve("secret://test/missing").is_err());
+    assert!(resolver.resolve("secret://test/").is_err());
+    assert!(resolver.resolve("secret://vault/kv/app").is_err());
+    assert!(resolver.resolve("sk-plain").is_err());
+}
+
+#[test]
+fn test_resolve_value() {
+    let mut value: toml::Value = r#"
+        [providers.openai]
+        api_key = "secret://test/openai"
+
+        [providers.gemini]
+        api_key = "plaintext"
+
+        [[triggers.mqtt]]
+        host = "broker"
+        password = "secret://test/mqtt"
+        "#
+    .parse::<toml::Table>()
+    .unwrap()
+    .into();
+
+    resolver().resolve_value(&mut value).unwrap();
+    assert_eq!(value["providers"]["openai"]["api_key"].as_str(), Some("sk-openai"));
+    assert_eq!(value["providers"]["gemini"]["api_key"].as_str(), Some("plaintext"));
+    assert_eq!(value["triggers"]["mqtt"][0]["password"].as_str(), Some("hunter2"));
+
+    value["providers"]["gemini"]["api_key"] = toml::Value::String("secret://test/nope".to_string());
+    assert!(matches!(
+        resolver().resolve_value(&mut value),
+        Err(ConfigError::Secret(_))
+    ));
+}
+
+#[test]
+fn test_encrypted_file_store() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("secrets.enc");
+    let store = EncryptedFileStore::new(&path, "correct horse");
+    assert!(store.load().unwrap().is_empty());
+
+    store.set("openai", "sk-openai").unwrap();
+    store.set("github", "ghp-token").unwrap();
+    assert!(store.remove("github").unwrap());
+    assert!(!store.remove("github").unwrap());
+
+    // Nothing readable without the passphrase
+    let data = std::fs::read(&path).unwrap();
+    assert!(!String::from_utf8_lossy(&data).contains("sk-openai"));
+
+    let reader = EncryptedFileStore::new(&path, "correct horse");
+    let resolver = SecretResolver::new().with_store(Arc::new(reader));
+    assert_eq!(resolver.resolve("secret://file/openai").unwrap(), "sk-openai");
+    assert!(resolver.resolve("secret://file/github").is_err());
+
+    let wrong = EncryptedFileStore::new(&path, "battery staple");
+    assert!(matches!(wrong.load(), Err(ConfigError::Secret(_))));
+
+    // Layered configuration resolves references through the file
+    let config_path = dir.path().join("config.toml");
+    std::fs::write(
+        &config_path,
+        format!(
+            "[secrets]\nfile = {:?}\n\n[providers.openai]\napi_key = \"secret://file/openai\"\n",
+            path.to_string_lossy()
+        ),
+    )
+    .unwrap();
+    let layers = crate::ConfigLayers::new()
+        .with_file(&config_path)
+        .with_env([(SECRETS_PASSPHRASE_ENV.to_string(), "correct horse".to_string())]);
+    let config = layers.resolve_with_secrets().unwrap();
+    assert_eq!(config.providers["openai"].api_key.as_deref(), Some("sk-openai"));
+    let config = layers.resolve().unwrap();
+    assert_eq!(
+        config.providers["openai"].api_key.as_deref(),
+        Some("secret://file/openai")
+    );
+    assert!(crate::ConfigLayers::new()
+        .with_file(&config_path)
+        .resolve_with_secrets()
+        .is_err());
+}
+
+/// Serve one HTTP response, returning the request received.
+fn serve_once(status: &str, body: &str) -> (String, std::thread::JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = format!("http://{}", listener.local_addr().unwrap());
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    let handle = std::thread::spawn(move || {
+        let (mut socket, _) = listener.accept().unwrap();
+        let mut request = vec![0u8; 4096];
+        let n = socket.read(&mut request).unwrap();
+        socket.write_all(response.as_bytes()).unwrap();
+        String::from_utf8_lossy(&request[..n]).to_string()
+    });
+    (addr, handle)
+}
+
+#[test]
+fn test_vault_store() {
+    let (addr, server) = serve_once(
+        "200 OK",
+        r#"{"data": {"data": {"openai": "sk-vault", "value": "default"}}}"#,
+    );
+    let store = VaultStore::new(format!("{}/", addr), Some("s.token".to_string()), None);
+    assert_eq!(store.get("kv/autohands#openai").unwrap(), "sk-vault");
+    let request = server.join().unwrap().to_lowercase();
+    assert!(request.starts_with("get /v1/kv/data/autohands "));
+    assert!(request.contains("x-vault-token: s.token"));
+
+    let (addr, server) = serve_once("404 Not Found", r#"{"errors": []}"#);
+    let store = VaultStore::new(addr, None, None);
+    assert!(store.get("kv/missing").is_err());
+    server.join().unwrap();
+
+    assert!(store.get("autohands").is_err());
+}
+
+#[test]
+fn test_from_config() {
+    let config = SecretsConfig::default();
+    let env = |name: &str| (name == "VAULT_ADDR").then(|| "http://127.0.0.1:1".to_string());
+    let resolver = SecretResolver::from_config(&config, None, env);
+    assert!(resolver.stores.contains_key("keyring"));
+    assert!(resolver.stores.contains_key("vault"));
+    // The file needs a passphrase
+    assert!(!resolver.stores.contains_key("file"));
+
+    let resolver = SecretResolver::from_config(&config, Some("pass".to_string()), |_| None);
+    assert!(resolver.stores.contains_key("file"));
+    assert!(!resolver.stores.contains_key("vault"));
+}
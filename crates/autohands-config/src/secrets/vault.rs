@@ -0,0 +1,89 @@
+//! HashiCorp Vault store.
+//!
+//! Reads fields of KV version 2 secrets: `<mount>/<path>#<field>` reads
+//! `GET /v1/<mount>/data/<path>` and takes `data.data.<field>`. Without a
+//! field, the secret's `value` field is used.
+
+use std::time::Duration;
+
+use super::SecretStore;
+use crate::error::ConfigError;
+
+/// Field read when a reference names none.
+const DEFAULT_FIELD: &str = "value";
+
+/// Request timeout.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Secrets in a Vault KV v2 engine.
+pub struct VaultStore {
+    addr: String,
+    token: Option<String>,
+    namespace: Option<String>,
+}
+
+impl VaultStore {
+    /// Create a store for a Vault server.
+    pub fn new(addr: impl Into<String>, token: Option<String>, namespace: Option<String>) -> Self {
+        Self {
+            addr: addr.into().trim_end_matches('/').to_string(),
+            token,
+            namespace,
+        }
+    }
+
+    /// URL of the secret at a path, and the field to read.
+    fn locate<'a>(&self, path: &'a str) -> Result<(String, &'a str), ConfigError> {
+        let (path, field) = path.split_once('#').unwrap_or((path, DEFAULT_FIELD));
+        let (mount, secret) = path
+            .split_once('/')
+            .filter(|(mount, secret)| !mount.is_empty() && !secret.is_empty())
+            .ok_or_else(|| {
+                ConfigError::Secret(format!("Vault path '{}' needs a mount and a secret", path))
+            })?;
+        Ok((format!("{}/v1/{}/data/{}", self.addr, mount, secret), field))
+    }
+}
+
+impl SecretStore for VaultStore {
+    fn backend(&self) -> &str {
+        "vault"
+    }
+
+    fn get(&self, path: &str) -> Result<String, ConfigError> {
+        let (url, field) = self.locate(path)?;
+        let client = reqwest::blocking::Client::builder()
+            .timeout(TIMEOUT)
+            .build()
+            .map_err(|e| ConfigError::Secret(e.to_string()))?;
+        let mut request = client.get(&url);
+        if let Some(token) = &self.token {
+            request = request.header("X-Vault-Token", token);
+        }
+        if let Some(namespace) = &self.namespace {
+            request = request.header("X-Vault-Namespace", namespace);
+        }
+
+        let response = request
+            .send()
+            .map_err(|e| ConfigError::Secret(format!("Vault request failed: {}", e)))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(ConfigError::Secret(format!(
+                "Vault returned {} for {}",
+                status, path
+            )));
+        }
+        let body: serde_json::Value = response
+            .json()
+            .map_err(|e| ConfigError::Secret(format!("Invalid Vault response: {}", e)))?;
+        match &body["data"]["data"][field] {
+            serde_json::Value::String(secret) => Ok(secret.clone()),
+            serde_json::Value::Null => Err(ConfigError::Secret(format!(
+                "Vault secret {} has no field '{}'",
+                path, field
+            ))),
+            other => Ok(other.to_string()),
+        }
+    }
+}
@@ -79,6 +79,23 @@ impl ConfigValidator {
         // Validate extensions
         Self::validate_extensions(config, &mut result);
 
+        // Validate approval config
+        Self::validate_approval(config, &mut result);
+        Self::validate_audit(config, &mut result);
+        Self::validate_redaction(config, &mut result);
+
+        // Validate file watcher bindings
+        Self::validate_file_watchers(config, &mut result);
+
+        // Validate email triggers
+        Self::validate_email_triggers(config, &mut result);
+
+        // Validate MQTT brokers
+        Self::validate_mqtt(config, &mut result);
+
+        // Validate channel delivery targets
+        Self::validate_channels(config, &mut result);
+
         Ok(result)
     }
 
@@ -112,6 +129,42 @@ impl ConfigValidator {
                 "Default agent cannot be empty",
             ));
         }
+
+        let sandboxes = config
+            .agent
+            .sandbox
+            .iter()
+            .map(|sandbox| ("agent.sandbox".to_string(), sandbox))
+            .chain(
+                config
+                    .agent
+                    .sandboxes
+                    .iter()
+                    .map(|(id, sandbox)| (format!("agent.sandboxes.{}", id), sandbox)),
+            );
+        for (path, sandbox) in sandboxes {
+            let Some(container) = &sandbox.container else {
+                continue;
+            };
+            if !["docker", "podman"].contains(&container.engine.as_str()) {
+                result.add_warning(ValidationWarning::new(
+                    format!("{}.container.engine", path),
+                    format!("Unknown container engine: {}", container.engine),
+                ));
+            }
+            if container.image.is_empty() {
+                result.add_error(ValidationError::new(
+                    format!("{}.container.image", path),
+                    "Container image cannot be empty",
+                ));
+            }
+            if container.cpus <= 0.0 {
+                result.add_error(ValidationError::new(
+                    format!("{}.container.cpus", path),
+                    "cpus must be positive",
+                ));
+            }
+        }
     }
 
     fn validate_providers(config: &Config, result: &mut ValidationResult) {
@@ -156,6 +209,42 @@ impl ConfigValidator {
                 "SQLite backend path not set, will use default location",
             ));
         }
+
+        let strategies = ["single", "map_reduce", "rolling", "hierarchical"];
+        let memory = &config.memory;
+        for (path, strategy) in [
+            ("memory.summarizer.strategy", &memory.summarizer.strategy),
+            ("memory.digest.strategy", &memory.digest.strategy),
+        ] {
+            if !strategies.contains(&strategy.replace('-', "_").as_str()) {
+                result.add_error(ValidationError::new(
+                    path,
+                    format!(
+                        "Unknown summary strategy '{}', valid values: {:?}",
+                        strategy, strategies
+                    ),
+                ));
+            }
+        }
+
+        let valid_time = memory.digest.time.split_once(':').is_some_and(|(h, m)| {
+            h.parse::<u32>().is_ok_and(|h| h < 24)
+                && m.len() == 2
+                && m.parse::<u32>().is_ok_and(|m| m < 60)
+        });
+        if !valid_time {
+            result.add_error(ValidationError::new(
+                "memory.digest.time",
+                format!("Invalid digest time '{}' (expected HH:MM)", memory.digest.time),
+            ));
+        }
+
+        if memory.digest.channel.is_some() != memory.digest.target.is_some() {
+            result.add_warning(ValidationWarning::new(
+                "memory.digest",
+                "Digest delivery needs both channel and target; digest will only be stored",
+            ));
+        }
     }
 
     fn validate_extensions(config: &Config, result: &mut ValidationResult) {
@@ -179,6 +268,173 @@ impl ConfigValidator {
             }
         }
     }
+
+    fn validate_approval(config: &Config, result: &mut ValidationResult) {
+        let approval = &config.approval;
+        if let Some(ref threshold) = approval.tool_risk_threshold {
+            if !["low", "medium", "high"].contains(&threshold.as_str()) {
+                result.add_error(ValidationError::new(
+                    "approval.tool_risk_threshold",
+                    format!("Unknown risk level: {} (expected low, medium or high)", threshold),
+                ));
+            }
+        }
+
+        if let Some(ref policy) = approval.tool_policy {
+            let mut actions = vec![
+                ("low".to_string(), &policy.low),
+                ("medium".to_string(), &policy.medium),
+                ("high".to_string(), &policy.high),
+            ];
+            for (tool, action) in &policy.tools {
+                actions.push((format!("tools.{}", tool), action));
+            }
+            for (key, action) in actions {
+                if !["allow", "prompt", "deny"].contains(&action.as_str()) {
+                    result.add_error(ValidationError::new(
+                        format!("approval.tool_policy.{}", key),
+                        format!("Unknown tool action: {} (expected allow, prompt or deny)", action),
+                    ));
+                }
+            }
+        }
+
+        if !["reject", "approve"].contains(&approval.on_timeout.as_str()) {
+            result.add_error(ValidationError::new(
+                "approval.on_timeout",
+                format!("Unknown timeout policy: {} (expected reject or approve)", approval.on_timeout),
+            ));
+        }
+
+        if approval.timeout_secs == 0 {
+            result.add_error(ValidationError::new(
+                "approval.timeout_secs",
+                "Approval timeout must be at least 1 second",
+            ));
+        }
+    }
+
+    fn validate_audit(config: &Config, result: &mut ValidationResult) {
+        for (i, pattern) in config.audit.redact_patterns.iter().enumerate() {
+            if let Err(e) = regex::Regex::new(pattern) {
+                result.add_error(ValidationError::new(
+                    format!("audit.redact_patterns[{}]", i),
+                    format!("Invalid regular expression: {}", e),
+                ));
+            }
+        }
+    }
+
+    fn validate_redaction(config: &Config, result: &mut ValidationResult) {
+        for (i, pattern) in config.redaction.patterns.iter().enumerate() {
+            if let Err(e) = regex::Regex::new(pattern) {
+                result.add_error(ValidationError::new(
+                    format!("redaction.patterns[{}]", i),
+                    format!("Invalid regular expression: {}", e),
+                ));
+            }
+        }
+    }
+
+    fn validate_file_watchers(config: &Config, result: &mut ValidationResult) {
+        for (i, watcher) in config.triggers.file_watchers.iter().enumerate() {
+            let path = format!("triggers.file_watchers[{}]", i);
+            if watcher.prompt.is_empty() && watcher.workflow.is_none() {
+                result.add_error(ValidationError::new(
+                    path.clone(),
+                    format!("File watcher {} needs a prompt or a workflow", watcher.id),
+                ));
+            }
+            for event in &watcher.events {
+                if !["created", "modified", "deleted", "renamed"].contains(&event.as_str()) {
+                    result.add_error(ValidationError::new(
+                        format!("{}.events", path),
+                        format!("Unknown change kind: {} (expected created, modified, deleted or renamed)", event),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn validate_email_triggers(config: &Config, result: &mut ValidationResult) {
+        for (i, trigger) in config.triggers.email.iter().enumerate() {
+            let path = format!("triggers.email[{}]", i);
+            if trigger.host.is_empty() {
+                result.add_error(ValidationError::new(
+                    format!("{}.host", path),
+                    format!("Email trigger {} needs an IMAP host", trigger.id),
+                ));
+            }
+            if trigger.password.is_empty() {
+                result.add_warning(ValidationWarning::new(
+                    format!("{}.password", path),
+                    "Password is not set, may need to be set via environment variable",
+                ));
+            }
+            if trigger.poll_interval_secs == 0 {
+                result.add_error(ValidationError::new(
+                    format!("{}.poll_interval_secs", path),
+                    "Poll interval must be at least 1 second",
+                ));
+            }
+            let has_rules = !trigger.archive_from.is_empty() || !trigger.archive_subject.is_empty();
+            if has_rules && trigger.archive_mailbox.is_none() {
+                result.add_warning(ValidationWarning::new(
+                    format!("{}.archive_mailbox", path),
+                    format!("Email trigger {} has archive rules but no archive mailbox", trigger.id),
+                ));
+            }
+        }
+    }
+
+    fn validate_mqtt(config: &Config, result: &mut ValidationResult) {
+        for (i, broker) in config.triggers.mqtt.iter().enumerate() {
+            let path = format!("triggers.mqtt[{}]", i);
+            if broker.host.is_empty() {
+                result.add_error(ValidationError::new(
+                    format!("{}.host", path),
+                    format!("MQTT broker {} needs a host", broker.id),
+                ));
+            }
+            if broker.publish_qos > 2 {
+                result.add_error(ValidationError::new(
+                    format!("{}.publish_qos", path),
+                    "QoS must be 0, 1 or 2",
+                ));
+            }
+            for (j, subscription) in broker.subscriptions.iter().enumerate() {
+                let path = format!("{}.subscriptions[{}]", path, j);
+                if subscription.qos > 2 {
+                    result.add_error(ValidationError::new(format!("{}.qos", path), "QoS must be 0, 1 or 2"));
+                }
+                if !["markdown", "plain", "json", "file"].contains(&subscription.reply_format.as_str()) {
+                    result.add_error(ValidationError::new(
+                        format!("{}.reply_format", path),
+                        format!(
+                            "Unknown format: {} (expected markdown, plain, json or file)",
+                            subscription.reply_format
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn validate_channels(config: &Config, result: &mut ValidationResult) {
+        for (id, channel) in &config.channels {
+            for (i, target) in channel.deliver_to.iter().enumerate() {
+                if !["markdown", "plain", "json", "file"].contains(&target.format.as_str()) {
+                    result.add_error(ValidationError::new(
+                        format!("channels.{}.deliver_to[{}].format", id, i),
+                        format!(
+                            "Unknown format: {} (expected markdown, plain, json or file)",
+                            target.format
+                        ),
+                    ));
+                }
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,409 @@
+//! Configuration file validation.
+//!
+//! Serde ignores keys it does not know, so a misspelled section is silently
+//! dropped. [`ConfigValidator::validate_source`] checks the file itself:
+//! TOML syntax, keys against the JSON Schema of [`Config`], value types and
+//! then [`ConfigValidator::validate`], locating every finding at a line.
+//! Each `[profile.<name>]` is checked too, and validated as applied.
+
+use std::fmt;
+use std::ops::Range;
+use std::path::Path;
+
+use serde_json::Value;
+use toml_edit::{ImDocument, Item, TableLike};
+
+use crate::error::ConfigError;
+use crate::loader::ConfigLoader;
+use crate::profile::{apply_profile, profile_names, INHERITS_KEY, PROFILE_KEY};
+use crate::schema::Config;
+use crate::validator::ConfigValidator;
+
+/// Severity of a diagnostic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A finding in a configuration file.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Key path (e.g. `triggers.mqtt[0].host`), empty for the whole file.
+    pub path: String,
+    pub message: String,
+    /// Line of the finding (1-based).
+    pub line: Option<usize>,
+    /// Column of the finding (1-based).
+    pub column: Option<usize>,
+    /// Text of the line.
+    pub source_line: Option<String>,
+}
+
+/// Findings of a configuration file, ordered by line.
+#[derive(Debug, Default)]
+pub struct FileValidation {
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl FileValidation {
+    pub fn is_valid(&self) -> bool {
+        self.error_count() == 0
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.count(Severity::Error)
+    }
+
+    pub fn warning_count(&self) -> usize {
+        self.count(Severity::Warning)
+    }
+
+    fn count(&self, severity: Severity) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == severity)
+            .count()
+    }
+}
+
+/// Collects diagnostics, turning byte spans into lines.
+struct Report<'a> {
+    content: &'a str,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Report<'a> {
+    fn new(content: &'a str) -> Self {
+        Self {
+            content,
+            diagnostics: Vec::new(),
+        }
+    }
+
+    fn push(
+        &mut self,
+        severity: Severity,
+        path: impl Into<String>,
+        message: impl Into<String>,
+        span: Option<Range<usize>>,
+    ) {
+        let location = span.map(|span| {
+            let offset = span.start.min(self.content.len());
+            let line_start = self.content[..offset].rfind('\n').map_or(0, |i| i + 1);
+            let line = self.content[..offset].matches('\n').count() + 1;
+            let text = self.content[line_start..].lines().next().unwrap_or("");
+            (line, offset - line_start + 1, text.to_string())
+        });
+        let (line, column, source_line) = match location {
+            Some((line, column, text)) => (Some(line), Some(column), Some(text)),
+            None => (None, None, None),
+        };
+        self.diagnostics.push(Diagnostic {
+            severity,
+            path: path.into(),
+            message: message.into(),
+            line,
+            column,
+            source_line,
+        });
+    }
+
+    fn finish(mut self) -> FileValidation {
+        // Findings of the whole file first, then by line
+        self.diagnostics.sort_by_key(|d| d.line.unwrap_or(0));
+        FileValidation {
+            diagnostics: self.diagnostics,
+        }
+    }
+}
+
+impl ConfigValidator {
+    /// Validate a configuration file.
+    pub fn validate_file(path: &Path) -> Result<FileValidation, ConfigError> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(Self::validate_source(&content))
+    }
+
+    /// Validate the contents of a configuration file.
+    ///
+    /// Unknown keys are errors; `${VAR}` references to unset variables are
+    /// warnings, validating with the reference left in place.
+    pub fn validate_source(content: &str) -> FileValidation {
+        let mut report = Report::new(content);
+        let document = match ImDocument::parse(content) {
+            Ok(document) => document,
+            Err(e) => {
+                report.push(Severity::Error, "", e.message().trim(), e.span());
+                return report.finish();
+            }
+        };
+
+        let schema = Config::json_schema();
+        let walker = SchemaWalker {
+            definitions: &schema["definitions"],
+        };
+        if let Some(root) = document.as_item().as_table_like() {
+            walker.check_table(root, &schema, "", &[PROFILE_KEY], &mut report);
+        }
+        if let Some(profiles) = document.get(PROFILE_KEY).and_then(Item::as_table_like) {
+            for (name, profile) in profiles.iter() {
+                if let Some(profile) = profile.as_table_like() {
+                    let path = format!("{}.{}", PROFILE_KEY, name);
+                    walker.check_table(profile, &schema, &path, &[INHERITS_KEY], &mut report);
+                }
+            }
+        }
+
+        let config: Config = match toml::from_str(content) {
+            Ok(config) => config,
+            Err(e) => {
+                report.push(Severity::Error, "", e.message().trim(), e.span());
+                return report.finish();
+            }
+        };
+        let config = match ConfigLoader::expand_env_vars(content) {
+            Ok(expanded) => toml::from_str(&expanded).unwrap_or(config),
+            Err(ConfigError::EnvVarNotSet(var)) => {
+                let reference = format!("${{{}}}", var);
+                let span = content
+                    .find(&reference)
+                    .map(|start| start..start + reference.len());
+                report.push(
+                    Severity::Warning,
+                    "",
+                    format!("Environment variable {} is not set", var),
+                    span,
+                );
+                config
+            }
+            Err(_) => config,
+        };
+
+        match Self::validate(&config) {
+            Ok(result) => {
+                for error in result.errors {
+                    let span = locate(&document, &error.path);
+                    report.push(Severity::Error, error.path, error.message, span);
+                }
+                for warning in result.warnings {
+                    let span = locate(&document, &warning.path);
+                    report.push(Severity::Warning, warning.path, warning.message, span);
+                }
+            }
+            Err(e) => report.push(Severity::Error, "", e.to_string(), None),
+        }
+        check_profiles(content, &document, &mut report);
+        report.finish()
+    }
+}
+
+/// Validate the configuration as each profile makes it, reporting what the
+/// file without profiles does not already get.
+fn check_profiles(content: &str, document: &ImDocument<&str>, report: &mut Report<'_>) {
+    let expanded = ConfigLoader::expand_env_vars(content).unwrap_or_else(|_| content.to_string());
+    let Ok(table) = expanded.parse::<toml::Table>() else {
+        return;
+    };
+    for name in profile_names(&table) {
+        let path = format!("{}.{}", PROFILE_KEY, name);
+        let mut applied = table.clone();
+        let config = apply_profile(&mut applied, Some(&name)).and_then(|()| {
+            toml::Value::Table(applied)
+                .try_into::<Config>()
+                .map_err(|e| ConfigError::InvalidFormat(e.message().trim().to_string()))
+        });
+        let config = match config {
+            Ok(config) => config,
+            Err(e) => {
+                report.push(Severity::Error, path.clone(), e.to_string(), locate(document, &path));
+                continue;
+            }
+        };
+        let Ok(result) = ConfigValidator::validate(&config) else {
+            continue;
+        };
+        for error in result.errors {
+            let reported = report
+                .diagnostics
+                .iter()
+                .any(|d| d.path == error.path && d.message == error.message);
+            if reported {
+                continue;
+            }
+            let in_profile = format!("{}.{}", path, error.path);
+            let span = locate(document, &in_profile).or_else(|| locate(document, &path));
+            report.push(
+                Severity::Error,
+                in_profile,
+                format!("{} (with profile '{}')", error.message, name),
+                span,
+            );
+        }
+    }
+}
+
+/// Span of the deepest key of a path (`a.b[0].c`) present in the document.
+fn locate(document: &ImDocument<&str>, path: &str) -> Option<Range<usize>> {
+    let mut item = document.as_item();
+    let mut span = None;
+    let path = path.replace('[', ".[");
+    for part in path.split('.').filter(|part| !part.is_empty()) {
+        let next = match part.strip_prefix('[').and_then(|p| p.strip_suffix(']')) {
+            Some(index) => index
+                .parse::<usize>()
+                .ok()
+                .and_then(|i| item.get(i))
+                .map(|child| (child.span(), child)),
+            None => item
+                .as_table_like()
+                .and_then(|table| table.get_key_value(part))
+                .map(|(key, child)| (key.span().or_else(|| child.span()), child)),
+        };
+        let Some((child_span, child)) = next else {
+            break;
+        };
+        span = child_span.or(span);
+        item = child;
+    }
+    span
+}
+
+/// Walks a document along the JSON Schema of [`Config`].
+struct SchemaWalker<'a> {
+    definitions: &'a Value,
+}
+
+impl<'a> SchemaWalker<'a> {
+    /// Follow `$ref`s to their definition.
+    fn resolve(&self, schema: &'a Value) -> &'a Value {
+        match schema
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|r| r.strip_prefix("#/definitions/"))
+        {
+            Some(name) => self.resolve(&self.definitions[name]),
+            None => schema,
+        }
+    }
+
+    /// The schema a value is checked against, unwrapping `allOf`/`anyOf`
+    /// with a single non-null branch (documented fields, `Option`s). `None`
+    /// when the value may take several shapes.
+    fn unwrap(&self, schema: &'a Value) -> Option<&'a Value> {
+        let schema = self.resolve(schema);
+        for combinator in ["allOf", "anyOf", "oneOf"] {
+            if let Some(branches) = schema.get(combinator).and_then(Value::as_array) {
+                let mut branches = branches
+                    .iter()
+                    .map(|branch| self.resolve(branch))
+                    .filter(|branch| branch.get("type").and_then(Value::as_str) != Some("null"));
+                let (Some(branch), None) = (branches.next(), branches.next()) else {
+                    return None;
+                };
+                return self.unwrap(branch);
+            }
+        }
+        Some(schema)
+    }
+
+    fn check(&self, item: &Item, schema: &'a Value, path: &str, report: &mut Report<'_>) {
+        if let Some(table) = item.as_table_like() {
+            self.check_table(table, schema, path, &[], report);
+            return;
+        }
+        let Some(schema) = self.unwrap(schema) else {
+            return;
+        };
+        if let Some(items) = schema.get("items") {
+            for (i, child) in (0..).map_while(|i| item.get(i)).enumerate() {
+                self.check(child, items, &format!("{}[{}]", path, i), report);
+            }
+        }
+    }
+
+    /// Check the keys of a table, except those in `skip`.
+    fn check_table(
+        &self,
+        table: &dyn TableLike,
+        schema: &'a Value,
+        path: &str,
+        skip: &[&str],
+        report: &mut Report<'_>,
+    ) {
+        let Some(schema) = self.unwrap(schema) else {
+            return;
+        };
+        let properties = schema.get("properties").and_then(Value::as_object);
+        // Maps (providers, channels, ...) take any key
+        let values = schema
+            .get("additionalProperties")
+            .filter(|values| values.is_object());
+        for (key, child) in table.iter() {
+            if skip.contains(&key) {
+                continue;
+            }
+            let child_path = if path.is_empty() {
+                key.to_string()
+            } else {
+                format!("{}.{}", path, key)
+            };
+            if let Some(child_schema) = properties.and_then(|p| p.get(key)).or(values) {
+                self.check(child, child_schema, &child_path, report);
+            } else if let Some(properties) = properties {
+                let mut message = format!("Unknown key '{}'", key);
+                if let Some(suggestion) = closest(key, properties.keys()) {
+                    message.push_str(&format!(", did you mean '{}'?", suggestion));
+                }
+                let span = table
+                    .get_key_value(key)
+                    .and_then(|(key, child)| key.span().or_else(|| child.span()));
+                report.push(Severity::Error, child_path, message, span);
+            }
+        }
+    }
+}
+
+/// The known key closest to a misspelled one, if close enough.
+fn closest<'k>(key: &str, known: impl Iterator<Item = &'k String>) -> Option<&'k str> {
+    let limit = (key.len() / 3).max(1);
+    known
+        .map(|candidate| (edit_distance(key, candidate), candidate))
+        .filter(|(distance, _)| *distance <= limit)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.as_str())
+}
+
+/// Levenshtein distance.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let current = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                previous
+            } else {
+                1 + previous.min(row[j]).min(current)
+            };
+            previous = current;
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+#[path = "validator_file_tests.rs"]
+mod tests;
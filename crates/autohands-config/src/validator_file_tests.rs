@@ -0,0 +1,171 @@
+use super::*;
+
+fn messages(validation: &FileValidation) -> Vec<String> {
+    validation
+        .diagnostics
+        .iter()
+        .map(|d| format!("{} {}: {}", d.severity, d.path, d.message))
+        .collect()
+}
+
+#[test]
+fn test_default_config_file_is_valid() {
+    let content = include_str!("../../../config/default.toml");
+    let validation = ConfigValidator::validate_source(content);
+    assert!(validation.is_valid(), "{:?}", messages(&validation));
+}
+
+#[test]
+fn test_syntax_error_located() {
+    let validation = ConfigValidator::validate_source("[server]\nport = \n");
+    assert_eq!(validation.error_count(), 1);
+    assert_eq!(validation.diagnostics[0].line, Some(2));
+}
+
+#[test]
+fn test_unknown_section_suggests_known_key() {
+    let validation = ConfigValidator::validate_source("[server]\nport = 9000\n\n[triger]\nx = 1\n");
+    assert!(!validation.is_valid());
+    let diagnostic = &validation.diagnostics[0];
+    assert_eq!(diagnostic.path, "triger");
+    assert!(diagnostic.message.contains("did you mean 'triggers'"));
+    assert_eq!(diagnostic.line, Some(4));
+    assert_eq!(diagnostic.source_line.as_deref(), Some("[triger]"));
+}
+
+#[test]
+fn test_unknown_nested_keys() {
+    let content = r#"
+[providers.openai]
+api_kee = "sk-test"
+
+[[triggers.mqtt]]
+id = "home"
+host = "localhost"
+
+[[triggers.mqtt.subscriptions]]
+topic = "a/b"
+qoss = 1
+"#;
+    let validation = ConfigValidator::validate_source(content);
+    let paths: Vec<_> = validation.diagnostics.iter().map(|d| d.path.as_str()).collect();
+    assert!(paths.contains(&"providers.openai.api_kee"), "{:?}", paths);
+    assert!(paths.contains(&"triggers.mqtt[0].subscriptions[0].qoss"), "{:?}", paths);
+    let qos = validation
+        .diagnostics
+        .iter()
+        .find(|d| d.path.ends_with("qoss"))
+        .unwrap();
+    assert_eq!(qos.line, Some(11));
+}
+
+#[test]
+fn test_free_form_maps_accept_any_key() {
+    let content = r#"
+[features.flags]
+"provider:openai" = false
+
+[channels.slack.commands.deploy]
+prompt = "Deploy"
+"#;
+    let validation = ConfigValidator::validate_source(content);
+    assert!(validation.is_valid(), "{:?}", messages(&validation));
+}
+
+#[test]
+fn test_type_error_located() {
+    let validation = ConfigValidator::validate_source("[server]\nport = \"eighty\"\n");
+    assert_eq!(validation.error_count(), 1);
+    assert_eq!(validation.diagnostics[0].line, Some(2));
+}
+
+#[test]
+fn test_validator_errors_located() {
+    let content = "[server]\nhost = \"127.0.0.1\"\nport = 0\n\n[approval]\non_timeout = \"ignore\"\n";
+    let validation = ConfigValidator::validate_source(content);
+    let port = validation
+        .diagnostics
+        .iter()
+        .find(|d| d.path == "server.port")
+        .unwrap();
+    assert_eq!(port.line, Some(3));
+    assert_eq!(port.source_line.as_deref(), Some("port = 0"));
+    let on_timeout = validation
+        .diagnostics
+        .iter()
+        .find(|d| d.path == "approval.on_timeout")
+        .unwrap();
+    assert_eq!(on_timeout.line, Some(6));
+}
+
+#[test]
+fn test_unset_env_var_is_warning() {
+    let content = "[providers.openai]\napi_key = \"${AUTOHANDS_TEST_UNSET_VAR_3825}\"\n";
+    let validation = ConfigValidator::validate_source(content);
+    assert!(validation.is_valid());
+    let warning = validation
+        .diagnostics
+        .iter()
+        .find(|d| d.message.contains("AUTOHANDS_TEST_UNSET_VAR_3825"))
+        .unwrap();
+    assert_eq!(warning.severity, Severity::Warning);
+    assert_eq!(warning.line, Some(2));
+}
+
+#[test]
+fn test_json_schema_describes_sections() {
+    let schema = Config::json_schema();
+    assert!(schema["properties"]["server"].is_object());
+    assert!(schema["definitions"]["ServerConfig"]["properties"]["port"].is_object());
+}
+
+#[test]
+fn test_edit_distance() {
+    assert_eq!(edit_distance("triger", "triggers"), 2);
+    assert_eq!(edit_distance("port", "port"), 0);
+    assert_eq!(edit_distance("", "abc"), 3);
+}
+
+#[test]
+fn test_profiles_checked() {
+    let content = r#"
+[agent]
+max_turns = 50
+
+[profile.dev.agent]
+max_turns = 10
+
+[profile.prod]
+inherits = "dev"
+
+[profile.prod.agents]
+max_turns = 10
+
+[profile.staging]
+inherits = "qa"
+"#;
+    let validation = ConfigValidator::validate_source(content);
+    let messages = messages(&validation);
+    assert_eq!(validation.error_count(), 2, "{:?}", messages);
+    let typo = &validation.diagnostics[0];
+    assert_eq!(typo.path, "profile.prod.agents");
+    assert!(typo.message.contains("did you mean 'agent'"));
+    assert_eq!(typo.line, Some(11));
+    let inherits = &validation.diagnostics[1];
+    assert_eq!(inherits.path, "profile.staging");
+    assert!(inherits.message.contains("unknown profile 'qa'"));
+}
+
+#[test]
+fn test_profile_values_validated() {
+    let content = r#"
+[profile.prod.server]
+port = 0
+"#;
+    let validation = ConfigValidator::validate_source(content);
+    assert!(!validation.is_valid(), "{:?}", messages(&validation));
+    let diagnostic = &validation.diagnostics[0];
+    assert_eq!(diagnostic.path, "profile.prod.server.port");
+    assert!(diagnostic.message.contains("with profile 'prod'"));
+    assert_eq!(diagnostic.line, Some(3));
+}
@@ -190,6 +190,22 @@
         assert!(result.warnings.iter().any(|w| w.path == "memory.path"));
     }
 
+    #[test]
+    fn test_validate_memory_digest() {
+        let mut config = Config::default();
+        config.memory.summarizer.strategy = "map-reduce".to_string();
+        config.memory.digest.channel = Some("slack".to_string());
+        let result = ConfigValidator::validate(&config).unwrap();
+        assert!(result.is_valid());
+        assert!(result.warnings.iter().any(|w| w.path == "memory.digest"));
+
+        config.memory.digest.strategy = "tree".to_string();
+        config.memory.digest.time = "25:00".to_string();
+        let result = ConfigValidator::validate(&config).unwrap();
+        assert!(result.errors.iter().any(|e| e.path == "memory.digest.strategy"));
+        assert!(result.errors.iter().any(|e| e.path == "memory.digest.time"));
+    }
+
     #[test]
     fn test_validate_nonexistent_extension_path() {
         let mut config = Config::default();
@@ -230,3 +246,206 @@
         let result = ConfigValidator::validate(&config).unwrap();
         assert!(result.is_valid());
     }
+
+    #[test]
+    fn test_validate_approval_config() {
+        let mut config = Config::default();
+        config.approval.tool_risk_threshold = Some("extreme".to_string());
+        config.approval.on_timeout = "ignore".to_string();
+
+        let result = ConfigValidator::validate(&config).unwrap();
+        assert!(result.errors.iter().any(|e| e.path == "approval.tool_risk_threshold"));
+        assert!(result.errors.iter().any(|e| e.path == "approval.on_timeout"));
+
+        config.approval.tool_risk_threshold = Some("high".to_string());
+        config.approval.on_timeout = "approve".to_string();
+        assert!(ConfigValidator::validate(&config).unwrap().is_valid());
+    }
+
+    #[test]
+    fn test_validate_audit_patterns() {
+        let mut config = Config::default();
+        assert!(config.audit.enabled);
+        config.audit.redact_patterns = vec![r"\d{16}".to_string(), "(".to_string()];
+
+        let result = ConfigValidator::validate(&config).unwrap();
+        let paths: Vec<_> = result.errors.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, vec!["audit.redact_patterns[1]"]);
+    }
+
+    #[test]
+    fn test_validate_redaction_patterns() {
+        let mut config = Config::default();
+        assert!(config.redaction.enabled);
+        config.redaction.patterns = vec!["[".to_string(), r"acme-\d{6}".to_string()];
+
+        let result = ConfigValidator::validate(&config).unwrap();
+        let paths: Vec<_> = result.errors.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, vec!["redaction.patterns[0]"]);
+    }
+
+    #[test]
+    fn test_validate_sandbox_container() {
+        let config: Config = toml::from_str(
+            r#"
+            [agent.sandbox.container]
+            engine = "lxc"
+
+            [agent.sandboxes.builder.container]
+            image = ""
+            cpus = 0.0
+            "#,
+        )
+        .unwrap();
+
+        let result = ConfigValidator::validate(&config).unwrap();
+        let mut paths: Vec<_> = result.errors.iter().map(|e| e.path.as_str()).collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec!["agent.sandboxes.builder.container.cpus", "agent.sandboxes.builder.container.image"]
+        );
+        assert!(result
+            .warnings
+            .iter()
+            .any(|w| w.path == "agent.sandbox.container.engine"));
+    }
+
+    #[test]
+    fn test_validate_tool_policy() {
+        let mut config: Config = toml::from_str(
+            r#"
+            [approval.tool_policy]
+            high = "prompt"
+            tools = { exec = "ask" }
+            "#,
+        )
+        .unwrap();
+        let policy = config.approval.tool_policy.as_ref().unwrap();
+        assert_eq!(policy.low, "allow");
+        assert_eq!(policy.medium, "prompt");
+
+        let result = ConfigValidator::validate(&config).unwrap();
+        let paths: Vec<_> = result.errors.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, vec!["approval.tool_policy.tools.exec"]);
+
+        let policy = config.approval.tool_policy.as_mut().unwrap();
+        policy.tools.insert("exec".to_string(), "deny".to_string());
+        assert!(ConfigValidator::validate(&config).unwrap().is_valid());
+    }
+
+    #[test]
+    fn test_validate_file_watchers() {
+        let config: Config = toml::from_str(
+            r#"
+            [[triggers.file_watchers]]
+            id = "inbox"
+            paths = ["~/Inbox"]
+            patterns = ["*.pdf"]
+            events = ["created", "moved"]
+
+            [[triggers.file_watchers]]
+            id = "ingest"
+            paths = ["~/Drop"]
+            workflow = "ingest"
+            "#,
+        )
+        .unwrap();
+
+        let result = ConfigValidator::validate(&config).unwrap();
+        let paths: Vec<_> = result.errors.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, vec!["triggers.file_watchers[0]", "triggers.file_watchers[0].events"]);
+        assert_eq!(config.triggers.file_watchers[1].debounce_ms, 500);
+        assert!(config.triggers.file_watchers[1].enabled);
+    }
+
+    #[test]
+    fn test_validate_email_triggers() {
+        let config: Config = toml::from_str(
+            r#"
+            [[triggers.email]]
+            id = "reports"
+            host = "imap.example.com"
+            username = "me@example.com"
+            password = "secret"
+            from = ["*@example.com"]
+
+            [[triggers.email]]
+            id = "broken"
+            host = ""
+            username = "me"
+            archive_subject = ["newsletter"]
+            poll_interval_secs = 0
+            "#,
+        )
+        .unwrap();
+
+        let result = ConfigValidator::validate(&config).unwrap();
+        let errors: Vec<_> = result.errors.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(errors, vec!["triggers.email[1].host", "triggers.email[1].poll_interval_secs"]);
+        assert!(result.warnings.iter().any(|w| w.path == "triggers.email[1].password"));
+        assert!(result.warnings.iter().any(|w| w.path == "triggers.email[1].archive_mailbox"));
+
+        let reports = &config.triggers.email[0];
+        assert_eq!((reports.port, reports.tls, reports.mailbox.as_str()), (993, true, "INBOX"));
+        assert!(reports.mark_seen && reports.enabled);
+    }
+
+    #[test]
+    fn test_validate_mqtt() {
+        let config: Config = toml::from_str(
+            r#"
+            [[triggers.mqtt]]
+            host = "homeassistant.local"
+            username = "autohands"
+
+            [[triggers.mqtt.subscriptions]]
+            topic = "home/+/motion"
+            prompt = "Motion in {topic}"
+            reply_topic = "autohands/result"
+            reply_format = "plain"
+
+            [[triggers.mqtt.subscriptions]]
+            topic = "autohands/ask"
+            qos = 3
+            reply_format = "yaml"
+            "#,
+        )
+        .unwrap();
+
+        let result = ConfigValidator::validate(&config).unwrap();
+        let errors: Vec<_> = result.errors.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(
+            errors,
+            vec![
+                "triggers.mqtt[0].subscriptions[1].qos",
+                "triggers.mqtt[0].subscriptions[1].reply_format",
+            ]
+        );
+
+        let broker = &config.triggers.mqtt[0];
+        assert_eq!((broker.id.as_str(), broker.port, broker.publish_qos), ("mqtt", 1883, 1));
+        assert_eq!(broker.subscriptions[0].reply_topic.as_deref(), Some("autohands/result"));
+    }
+
+    #[test]
+    fn test_validate_channel_delivery() {
+        let config: Config = toml::from_str(
+            r#"
+            [[channels.web.deliver_to]]
+            channel_id = "slack"
+            target = "#reports"
+
+            [[channels.web.deliver_to]]
+            channel_id = "mail"
+            target = "ops@example.com"
+            format = "pdf"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.channels["web"].deliver_to[0].format, "markdown");
+        let result = ConfigValidator::validate(&config).unwrap();
+        let errors: Vec<_> = result.errors.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(errors, vec!["channels.web.deliver_to[1].format"]);
+    }
@@ -0,0 +1,262 @@
+//! In-process approval gate.
+//!
+//! Keeps pending approval requests in memory, announces them through the
+//! configured notifiers and resolves the waiting caller when a decision
+//! arrives or the timeout policy applies. With a [`QuestionService`],
+//! requests of a session that talks in a conversation are also asked there,
+//! and the reply decides them.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+use tokio::sync::oneshot;
+use tracing::{info, warn};
+
+use autohands_protocols::approval::{
+    ApprovalDecision, ApprovalGate, ApprovalNotifier, ApprovalRequest, ApprovalTimeoutPolicy,
+};
+use autohands_protocols::question::{Answer, AnswerStatus, Question, QuestionGate};
+
+use crate::audit::{AuditEntry, AuditKind, AuditLog};
+use crate::question::QuestionService;
+
+/// Default time to wait for a decision.
+pub const DEFAULT_APPROVAL_TIMEOUT: Duration = Duration::from_secs(3600);
+
+struct PendingApproval {
+    request: ApprovalRequest,
+    responder: oneshot::Sender<ApprovalDecision>,
+}
+
+/// Removes a request from the pending set when its caller stops waiting.
+struct PendingGuard<'a> {
+    pending: &'a DashMap<String, PendingApproval>,
+    id: String,
+}
+
+impl Drop for PendingGuard<'_> {
+    fn drop(&mut self) {
+        self.pending.remove(&self.id);
+    }
+}
+
+/// Approval gate holding pending requests in memory.
+pub struct ApprovalService {
+    pending: DashMap<String, PendingApproval>,
+    notifiers: Vec<Arc<dyn ApprovalNotifier>>,
+    default_timeout: Duration,
+    timeout_policy: ApprovalTimeoutPolicy,
+    questions: Option<Arc<QuestionService>>,
+    audit: Option<Arc<AuditLog>>,
+}
+
+impl ApprovalService {
+    /// Create a service that rejects requests after [`DEFAULT_APPROVAL_TIMEOUT`].
+    pub fn new() -> Self {
+        Self {
+            pending: DashMap::new(),
+            notifiers: Vec::new(),
+            default_timeout: DEFAULT_APPROVAL_TIMEOUT,
+            timeout_policy: ApprovalTimeoutPolicy::default(),
+            questions: None,
+            audit: None,
+        }
+    }
+
+    /// Record every decision in an audit log.
+    pub fn with_audit_log(mut self, audit: Arc<AuditLog>) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// Ask for decisions in the conversation of the requesting session too.
+    pub fn with_questions(mut self, questions: Arc<QuestionService>) -> Self {
+        self.questions = Some(questions);
+        self
+    }
+
+    /// Add a notifier that announces new requests.
+    pub fn with_notifier(mut self, notifier: Arc<dyn ApprovalNotifier>) -> Self {
+        self.notifiers.push(notifier);
+        self
+    }
+
+    /// Set the timeout for requests that do not specify one.
+    pub fn with_default_timeout(mut self, timeout: Duration) -> Self {
+        self.default_timeout = timeout;
+        self
+    }
+
+    /// Set the timeout policy for requests that do not specify one.
+    pub fn with_timeout_policy(mut self, policy: ApprovalTimeoutPolicy) -> Self {
+        self.timeout_policy = policy;
+        self
+    }
+
+    /// Number of requests waiting for a decision.
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// The request as a question to the conversation of its session, if
+    /// the session talks in one.
+    fn question_for(&self, request: &ApprovalRequest, timeout: Duration) -> Option<Question> {
+        let session_id = request.session_id.as_deref()?;
+        let reply_to = self.questions.as_ref()?.conversation(session_id)?;
+        let mut text = format!(
+            "Approval needed: {} (risk: {:?})",
+            request.title, request.risk_level
+        );
+        if let Some(description) = &request.description {
+            text.push_str(&format!("\n{}", description));
+        }
+        Some(
+            Question::new(text)
+                .with_options(vec!["approve".to_string(), "reject".to_string()])
+                .with_session(session_id)
+                .with_reply_to(reply_to)
+                .with_timeout(timeout.as_secs()),
+        )
+    }
+}
+
+/// Audit record of a decided request.
+fn audit_entry(request: &ApprovalRequest, decision: &ApprovalDecision) -> AuditEntry {
+    let status = serde_json::to_value(decision.status).unwrap_or_default();
+    let mut entry = AuditEntry::new(AuditKind::Approval, status.as_str().unwrap_or_default())
+        .with_name(&request.source)
+        .with_params(request.details.clone())
+        .with_summary(&request.title);
+    if let Some(session_id) = &request.session_id {
+        entry = entry.with_session(session_id);
+    }
+    if let Some(decided_by) = &decision.decided_by {
+        entry = entry.with_actor(decided_by);
+    }
+    entry
+}
+
+/// Decision given by the reply to an approval question; `None` if nobody
+/// replied.
+fn decision_from_answer(answer: Answer) -> Option<ApprovalDecision> {
+    if answer.status == AnswerStatus::TimedOut {
+        return None;
+    }
+    let reply = answer.text.unwrap_or_default();
+    let approved = answer.option == Some(0)
+        || ["yes", "y", "ok"].contains(&reply.trim().to_lowercase().as_str());
+    let mut decision = if approved {
+        ApprovalDecision::approved()
+    } else {
+        ApprovalDecision::rejected()
+    };
+    if answer.option.is_none() && !approved {
+        decision = decision.with_comment(reply);
+    }
+    if let Some(by) = answer.answered_by {
+        decision = decision.by(by);
+    }
+    Some(decision)
+}
+
+impl Default for ApprovalService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ApprovalGate for ApprovalService {
+    async fn request_approval(&self, request: ApprovalRequest) -> ApprovalDecision {
+        let timeout = request
+            .timeout_secs
+            .map(Duration::from_secs)
+            .unwrap_or(self.default_timeout);
+        let policy = request.on_timeout.unwrap_or(self.timeout_policy);
+
+        let (responder, mut decision) = oneshot::channel();
+        self.pending.insert(
+            request.id.clone(),
+            PendingApproval {
+                request: request.clone(),
+                responder,
+            },
+        );
+        let _guard = PendingGuard {
+            pending: &self.pending,
+            id: request.id.clone(),
+        };
+        info!(
+            "Approval requested: {} ({}) from {}",
+            request.title, request.id, request.source
+        );
+
+        for notifier in &self.notifiers {
+            if let Err(e) = notifier.notify(&request).await {
+                warn!("Approval notifier '{}' failed: {}", notifier.name(), e);
+            }
+        }
+
+        // A reply in the conversation decides the request like the API does;
+        // a decision from elsewhere drops the question
+        let question = self.question_for(&request, timeout);
+        let decided = async {
+            let (Some(questions), Some(question)) = (&self.questions, question) else {
+                return (&mut decision).await;
+            };
+            tokio::select! {
+                decided = &mut decision => decided,
+                answer = questions.ask(question) => match decision_from_answer(answer) {
+                    Some(answered) => Ok(answered),
+                    None => (&mut decision).await,
+                },
+            }
+        };
+
+        let decision = match tokio::time::timeout(timeout, decided).await {
+            Ok(Ok(decision)) => {
+                info!("Approval {} decided: {:?}", request.id, decision.status);
+                decision
+            }
+            Ok(Err(_)) => ApprovalDecision::rejected().with_comment("Approval request was dropped"),
+            Err(_) => {
+                warn!(
+                    "Approval {} timed out after {:?}, applying {:?} policy",
+                    request.id, timeout, policy
+                );
+                match policy {
+                    ApprovalTimeoutPolicy::Reject => ApprovalDecision::timed_out(),
+                    ApprovalTimeoutPolicy::Approve => ApprovalDecision::approved()
+                        .with_comment("Approved automatically after timeout"),
+                }
+            }
+        };
+        if let Some(audit) = &self.audit {
+            audit.record(audit_entry(&request, &decision));
+        }
+        decision
+    }
+
+    async fn pending(&self) -> Vec<ApprovalRequest> {
+        let mut requests: Vec<ApprovalRequest> = self
+            .pending
+            .iter()
+            .map(|entry| entry.request.clone())
+            .collect();
+        requests.sort_by_key(|r| r.created_at);
+        requests
+    }
+
+    async fn decide(&self, request_id: &str, decision: ApprovalDecision) -> bool {
+        match self.pending.remove(request_id) {
+            Some((_, pending)) => pending.responder.send(decision).is_ok(),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "approval_tests.rs"]
+mod tests;
@@ -0,0 +1,169 @@
+use super::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use autohands_protocols::approval::ApprovalStatus;
+use autohands_protocols::error::ChannelError;
+
+struct CountingNotifier {
+    count: AtomicUsize,
+}
+
+#[async_trait]
+impl ApprovalNotifier for CountingNotifier {
+    fn name(&self) -> &str {
+        "counting"
+    }
+
+    async fn notify(&self, _request: &ApprovalRequest) -> Result<(), ChannelError> {
+        self.count.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+async fn wait_for_pending(service: &ApprovalService) -> ApprovalRequest {
+    loop {
+        if let Some(request) = service.pending().await.into_iter().next() {
+            return request;
+        }
+        tokio::task::yield_now().await;
+    }
+}
+
+#[tokio::test]
+async fn test_approve_resumes_caller() {
+    let notifier = Arc::new(CountingNotifier {
+        count: AtomicUsize::new(0),
+    });
+    let service = Arc::new(ApprovalService::new().with_notifier(notifier.clone()));
+
+    let waiter = {
+        let service = service.clone();
+        tokio::spawn(async move {
+            service
+                .request_approval(ApprovalRequest::new("Deploy", "workflow:release/confirm"))
+                .await
+        })
+    };
+
+    let request = wait_for_pending(&service).await;
+    assert_eq!(request.title, "Deploy");
+    assert!(service.decide(&request.id, ApprovalDecision::approved().by("alice")).await);
+
+    let decision = waiter.await.unwrap();
+    assert!(decision.is_approved());
+    assert_eq!(decision.decided_by.as_deref(), Some("alice"));
+    assert_eq!(notifier.count.load(Ordering::SeqCst), 1);
+    assert_eq!(service.pending_count(), 0);
+    assert!(!service.decide(&request.id, ApprovalDecision::rejected()).await);
+}
+
+#[tokio::test]
+async fn test_reject() {
+    let service = Arc::new(ApprovalService::new());
+    let waiter = {
+        let service = service.clone();
+        tokio::spawn(async move {
+            service
+                .request_approval(ApprovalRequest::new("Delete", "tool:delete_file"))
+                .await
+        })
+    };
+    let request = wait_for_pending(&service).await;
+    service
+        .decide(&request.id, ApprovalDecision::rejected().with_comment("no"))
+        .await;
+    let decision = waiter.await.unwrap();
+    assert_eq!(decision.status, ApprovalStatus::Rejected);
+    assert_eq!(decision.comment.as_deref(), Some("no"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_timeout_policies() {
+    let service = ApprovalService::new().with_default_timeout(Duration::from_secs(5));
+    let decision = service
+        .request_approval(ApprovalRequest::new("Deploy", "test"))
+        .await;
+    assert_eq!(decision.status, ApprovalStatus::TimedOut);
+    assert_eq!(service.pending_count(), 0);
+
+    let decision = service
+        .request_approval(
+            ApprovalRequest::new("Deploy", "test")
+                .with_timeout(1)
+                .with_timeout_policy(ApprovalTimeoutPolicy::Approve),
+        )
+        .await;
+    assert!(decision.is_approved());
+}
+
+#[tokio::test]
+async fn test_dropped_caller_clears_pending() {
+    let service = Arc::new(ApprovalService::new());
+    let waiter = {
+        let service = service.clone();
+        tokio::spawn(async move {
+            service
+                .request_approval(ApprovalRequest::new("Deploy", "test"))
+                .await
+        })
+    };
+    wait_for_pending(&service).await;
+    waiter.abort();
+    let _ = waiter.await;
+    assert_eq!(service.pending_count(), 0);
+}
+
+#[tokio::test]
+async fn test_reply_in_conversation_decides() {
+    use autohands_protocols::channel::ReplyAddress;
+
+    let questions = Arc::new(QuestionService::new());
+    let conversation = ReplyAddress::new("slack", "C42");
+    questions.bind_session("s-1", conversation.clone());
+    let service = Arc::new(ApprovalService::new().with_questions(questions.clone()));
+
+    let waiter = {
+        let service = service.clone();
+        tokio::spawn(async move {
+            let request = ApprovalRequest::new("Run tool 'exec'", "tool:exec").with_session("s-1");
+            service.request_approval(request).await
+        })
+    };
+    while questions.pending_count() == 0 {
+        tokio::task::yield_now().await;
+    }
+    questions.take_reply(&conversation, "2", Some("bob"));
+
+    let decision = waiter.await.unwrap();
+    assert_eq!(decision.status, ApprovalStatus::Rejected);
+    assert_eq!(decision.decided_by.as_deref(), Some("bob"));
+    assert_eq!(service.pending_count(), 0);
+}
+
+#[tokio::test]
+async fn test_decisions_are_audited() {
+    let dir = tempfile::tempdir().unwrap();
+    let audit = Arc::new(AuditLog::open(dir.path().join("audit.jsonl")).unwrap());
+    let service = Arc::new(ApprovalService::new().with_audit_log(audit.clone()));
+
+    let waiter = {
+        let service = service.clone();
+        tokio::spawn(async move {
+            let request = ApprovalRequest::new("Run tool 'exec'", "tool:exec")
+                .with_session("s-1")
+                .with_details(serde_json::json!({"arguments": {"password": "hunter2"}}));
+            service.request_approval(request).await
+        })
+    };
+    let request = wait_for_pending(&service).await;
+    service.decide(&request.id, ApprovalDecision::approved().by("alice")).await;
+    waiter.await.unwrap();
+
+    let entries = audit.query(&Default::default()).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].kind, AuditKind::Approval);
+    assert_eq!(entries[0].outcome, "approved");
+    assert_eq!(entries[0].actor.as_deref(), Some("alice"));
+    assert_eq!(entries[0].name.as_deref(), Some("tool:exec"));
+    assert_eq!(entries[0].params["arguments"]["password"], "<redacted>");
+}
@@ -0,0 +1,432 @@
+//! Tamper-evident audit log.
+//!
+//! Every tool call, approval decision and agent run is appended to a JSONL
+//! file as an [`AuditEntry`]. Each entry carries the hash of the previous
+//! one and its own hash over both, so editing, removing or reordering lines
+//! breaks the chain and shows in [`AuditLog::verify`]. Parameters and
+//! result summaries are redacted by [`RedactionRules`] before they are
+//! written.
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::warn;
+
+use crate::redact::{DEFAULT_SECRET_PATTERNS, REDACTED};
+
+/// Previous hash of the first entry.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Longest result summary kept, in characters.
+pub const MAX_SUMMARY_CHARS: usize = 200;
+
+/// Parameter names redacted by default (matched ignoring case, anywhere in
+/// the name).
+const DEFAULT_REDACTED_KEYS: &[&str] = &[
+    "password",
+    "passwd",
+    "secret",
+    "token",
+    "api_key",
+    "apikey",
+    "authorization",
+    "cookie",
+    "private_key",
+];
+
+/// What an entry records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditKind {
+    /// A tool ran, failed or was refused.
+    ToolCall,
+    /// An approval request was decided.
+    Approval,
+    /// An agent run ended.
+    AgentRun,
+}
+
+/// One line of the audit log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Position in the log, from 1.
+    pub seq: u64,
+    pub at: DateTime<Utc>,
+    pub kind: AuditKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+    /// Tool ID, or source of an approval request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Who decided, for approvals.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+    /// Redacted parameters.
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub params: serde_json::Value,
+    /// "success", "error", "refused", "approved", "completed", ...
+    pub outcome: String,
+    /// Start of the result, or of the error, redacted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    /// Hash of the previous entry.
+    pub prev_hash: String,
+    /// SHA-256 of this entry with an empty `hash`, hex encoded.
+    pub hash: String,
+}
+
+impl AuditEntry {
+    /// Entry of something that happened now; `record` chains it.
+    pub fn new(kind: AuditKind, outcome: impl Into<String>) -> Self {
+        Self {
+            seq: 0,
+            at: Utc::now(),
+            kind,
+            agent_id: None,
+            session_id: None,
+            correlation_id: None,
+            name: None,
+            actor: None,
+            params: serde_json::Value::Null,
+            outcome: outcome.into(),
+            summary: None,
+            duration_ms: None,
+            prev_hash: String::new(),
+            hash: String::new(),
+        }
+    }
+
+    pub fn with_agent(mut self, agent_id: impl Into<String>) -> Self {
+        self.agent_id = Some(agent_id.into());
+        self
+    }
+
+    pub fn with_session(mut self, session_id: impl Into<String>) -> Self {
+        self.session_id = Some(session_id.into());
+        self
+    }
+
+    pub fn with_correlation_id(mut self, correlation_id: impl Into<String>) -> Self {
+        self.correlation_id = Some(correlation_id.into());
+        self
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn with_actor(mut self, actor: impl Into<String>) -> Self {
+        self.actor = Some(actor.into());
+        self
+    }
+
+    pub fn with_params(mut self, params: serde_json::Value) -> Self {
+        self.params = params;
+        self
+    }
+
+    /// Keep the start of a result or error.
+    pub fn with_summary(mut self, text: &str) -> Self {
+        let mut summary: String = text.chars().take(MAX_SUMMARY_CHARS).collect();
+        if summary.len() < text.len() {
+            summary.push('…');
+        }
+        self.summary = Some(summary);
+        self
+    }
+
+    pub fn with_duration_ms(mut self, duration_ms: u64) -> Self {
+        self.duration_ms = Some(duration_ms);
+        self
+    }
+
+    /// Hash of the entry as it is, ignoring its `hash`.
+    fn compute_hash(&self) -> String {
+        let mut unhashed = self.clone();
+        unhashed.hash = String::new();
+        let json = serde_json::to_vec(&unhashed).unwrap_or_default();
+        hex::encode(Sha256::digest(&json))
+    }
+}
+
+/// Parameter names and value patterns kept out of the log.
+#[derive(Debug, Clone)]
+pub struct RedactionRules {
+    keys: Vec<String>,
+    patterns: Vec<Regex>,
+}
+
+impl Default for RedactionRules {
+    fn default() -> Self {
+        Self {
+            keys: DEFAULT_REDACTED_KEYS.iter().map(|k| k.to_string()).collect(),
+            patterns: DEFAULT_SECRET_PATTERNS
+                .iter()
+                .filter_map(|p| Regex::new(p).ok())
+                .collect(),
+        }
+    }
+}
+
+impl RedactionRules {
+    /// Also redact parameters whose name contains `key`, ignoring case.
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.keys.push(key.into().to_lowercase());
+        self
+    }
+
+    /// Also redact text matching `pattern`.
+    pub fn with_pattern(mut self, pattern: &str) -> Result<Self, regex::Error> {
+        self.patterns.push(Regex::new(pattern)?);
+        Ok(self)
+    }
+
+    /// Redact a JSON value in place.
+    pub fn redact(&self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(map) => {
+                for (key, value) in map.iter_mut() {
+                    let key = key.to_lowercase();
+                    if self.keys.iter().any(|k| key.contains(k.as_str())) {
+                        *value = serde_json::Value::String(REDACTED.to_string());
+                    } else {
+                        self.redact(value);
+                    }
+                }
+            }
+            serde_json::Value::Array(items) => items.iter_mut().for_each(|v| self.redact(v)),
+            serde_json::Value::String(text) => *text = self.redact_text(text),
+            _ => {}
+        }
+    }
+
+    /// Text with every pattern match replaced.
+    pub fn redact_text(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        for pattern in &self.patterns {
+            if pattern.is_match(&text) {
+                text = pattern.replace_all(&text, REDACTED).into_owned();
+            }
+        }
+        text
+    }
+}
+
+/// Filter of [`AuditLog::query`].
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct AuditQuery {
+    pub kind: Option<AuditKind>,
+    pub agent_id: Option<String>,
+    pub session_id: Option<String>,
+    pub correlation_id: Option<String>,
+    pub name: Option<String>,
+    pub outcome: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    /// Most entries returned, the latest ones (default 100).
+    pub limit: Option<usize>,
+}
+
+impl AuditQuery {
+    fn matches(&self, entry: &AuditEntry) -> bool {
+        fn same(filter: &Option<String>, value: &Option<String>) -> bool {
+            filter.is_none() || filter == value
+        }
+        self.kind.is_none_or(|kind| kind == entry.kind)
+            && same(&self.agent_id, &entry.agent_id)
+            && same(&self.session_id, &entry.session_id)
+            && same(&self.correlation_id, &entry.correlation_id)
+            && same(&self.name, &entry.name)
+            && self.outcome.as_ref().is_none_or(|o| *o == entry.outcome)
+            && self.since.is_none_or(|since| entry.at >= since)
+            && self.until.is_none_or(|until| entry.at <= until)
+    }
+}
+
+/// Result of checking the hash chain.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuditVerification {
+    pub valid: bool,
+    /// Entries checked.
+    pub entries: u64,
+    /// Line where the chain breaks.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub broken_at: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// End of the chain.
+struct ChainHead {
+    seq: u64,
+    hash: String,
+}
+
+/// Append-only, hash-chained audit log in a JSONL file.
+pub struct AuditLog {
+    path: PathBuf,
+    redaction: RedactionRules,
+    head: Mutex<ChainHead>,
+}
+
+impl AuditLog {
+    /// Open the log at `path`, continuing its chain if it exists.
+    pub fn open(path: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let path = path.into();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut head = ChainHead {
+            seq: 0,
+            hash: GENESIS_HASH.to_string(),
+        };
+        if path.exists() {
+            for line in BufReader::new(File::open(&path)?).lines() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                match serde_json::from_str::<AuditEntry>(&line) {
+                    Ok(entry) => {
+                        head = ChainHead {
+                            seq: entry.seq,
+                            hash: entry.hash,
+                        }
+                    }
+                    Err(e) => warn!("Unreadable audit log line in {:?}: {}", path, e),
+                }
+            }
+        }
+        Ok(Self {
+            path,
+            redaction: RedactionRules::default(),
+            head: Mutex::new(head),
+        })
+    }
+
+    /// Redact with these rules instead of the defaults.
+    pub fn with_redaction(mut self, redaction: RedactionRules) -> Self {
+        self.redaction = redaction;
+        self
+    }
+
+    /// File of the log.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Redact, chain and append an entry. Failures are logged, so auditing
+    /// never stops the work it records.
+    pub fn record(&self, entry: AuditEntry) {
+        if let Err(e) = self.append(entry) {
+            warn!("Failed to write audit log {:?}: {}", self.path, e);
+        }
+    }
+
+    fn append(&self, mut entry: AuditEntry) -> std::io::Result<AuditEntry> {
+        self.redaction.redact(&mut entry.params);
+        entry.summary = entry.summary.map(|s| self.redaction.redact_text(&s));
+
+        let mut head = self.head.lock();
+        entry.seq = head.seq + 1;
+        entry.prev_hash = head.hash.clone();
+        entry.hash = entry.compute_hash();
+        let mut line = serde_json::to_string(&entry).map_err(std::io::Error::other)?;
+        line.push('\n');
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        file.write_all(line.as_bytes())?;
+        *head = ChainHead {
+            seq: entry.seq,
+            hash: entry.hash.clone(),
+        };
+        Ok(entry)
+    }
+
+    /// Entries matching `query`, oldest first.
+    pub fn query(&self, query: &AuditQuery) -> std::io::Result<Vec<AuditEntry>> {
+        let mut entries: Vec<AuditEntry> = self
+            .read()?
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|entry| query.matches(entry))
+            .collect();
+        let limit = query.limit.unwrap_or(100);
+        if entries.len() > limit {
+            entries.drain(..entries.len() - limit);
+        }
+        Ok(entries)
+    }
+
+    /// Check that no entry was changed, removed or reordered.
+    pub fn verify(&self) -> std::io::Result<AuditVerification> {
+        let mut prev_hash = GENESIS_HASH.to_string();
+        let mut checked = 0;
+        for (line, entry) in self.read()?.into_iter().enumerate() {
+            let line = line as u64 + 1;
+            let broken = match entry {
+                Err(e) => Some(format!("unreadable entry: {}", e)),
+                Ok(entry) if entry.seq != line => Some(format!("sequence {} out of order", entry.seq)),
+                Ok(entry) if entry.prev_hash != prev_hash => {
+                    Some("previous hash does not match".to_string())
+                }
+                Ok(entry) if entry.compute_hash() != entry.hash => {
+                    Some("entry was modified".to_string())
+                }
+                Ok(entry) => {
+                    prev_hash = entry.hash;
+                    None
+                }
+            };
+            if let Some(reason) = broken {
+                return Ok(AuditVerification {
+                    valid: false,
+                    entries: checked,
+                    broken_at: Some(line),
+                    reason: Some(reason),
+                });
+            }
+            checked = line;
+        }
+        Ok(AuditVerification {
+            valid: true,
+            entries: checked,
+            broken_at: None,
+            reason: None,
+        })
+    }
+
+    /// Every non-empty line, parsed.
+    fn read(&self) -> std::io::Result<Vec<Result<AuditEntry, serde_json::Error>>> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let mut entries = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                entries.push(serde_json::from_str(&line));
+            }
+        }
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+#[path = "audit_tests.rs"]
+mod tests;
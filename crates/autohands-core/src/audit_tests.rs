@@ -0,0 +1,107 @@
+use super::*;
+
+fn tool_call(tool: &str, session: &str) -> AuditEntry {
+    AuditEntry::new(AuditKind::ToolCall, "success")
+        .with_name(tool)
+        .with_session(session)
+        .with_agent("general")
+}
+
+#[test]
+fn test_entries_are_chained_and_redacted() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = AuditLog::open(dir.path().join("audit.jsonl")).unwrap();
+    log.record(tool_call("exec", "s-1").with_params(serde_json::json!({
+        "command": "curl -H 'Authorization: Bearer abc.def' https://example.com",
+        "env": {"API_TOKEN": "hunter2"},
+    })));
+    log.record(tool_call("read_file", "s-2").with_summary(&"x".repeat(300)));
+
+    let entries = log.query(&AuditQuery::default()).unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].seq, 1);
+    assert_eq!(entries[0].prev_hash, GENESIS_HASH);
+    assert_eq!(entries[1].prev_hash, entries[0].hash);
+    assert_eq!(
+        entries[0].params["command"],
+        "curl -H 'Authorization: <redacted>' https://example.com"
+    );
+    assert_eq!(entries[0].params["env"]["API_TOKEN"], "<redacted>");
+    assert_eq!(entries[1].summary.as_ref().unwrap().chars().count(), MAX_SUMMARY_CHARS + 1);
+
+    let verification = log.verify().unwrap();
+    assert!(verification.valid);
+    assert_eq!(verification.entries, 2);
+
+    // Reopening continues the chain
+    let log = AuditLog::open(dir.path().join("audit.jsonl")).unwrap();
+    log.record(tool_call("exec", "s-1"));
+    let entries = log.query(&AuditQuery::default()).unwrap();
+    assert_eq!(entries[2].seq, 3);
+    assert!(log.verify().unwrap().valid);
+}
+
+#[test]
+fn test_tampering_breaks_the_chain() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("audit.jsonl");
+    let log = AuditLog::open(&path).unwrap();
+    for tool in ["exec", "write_file", "exec"] {
+        log.record(tool_call(tool, "s-1"));
+    }
+
+    let text = std::fs::read_to_string(&path).unwrap();
+    std::fs::write(&path, text.replacen("write_file", "read_file", 1)).unwrap();
+    let verification = log.verify().unwrap();
+    assert!(!verification.valid);
+    assert_eq!(verification.broken_at, Some(2));
+    assert_eq!(verification.reason.as_deref(), Some("entry was modified"));
+
+    let lines: Vec<&str> = text.lines().collect();
+    std::fs::write(&path, format!("{}\n{}\n", lines[0], lines[2])).unwrap();
+    let verification = log.verify().unwrap();
+    assert_eq!(verification.broken_at, Some(2));
+    assert_eq!(verification.entries, 1);
+}
+
+#[test]
+fn test_query_filters() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = AuditLog::open(dir.path().join("audit.jsonl")).unwrap();
+    log.record(tool_call("exec", "s-1"));
+    log.record(tool_call("exec", "s-2"));
+    log.record(AuditEntry::new(AuditKind::Approval, "approved").with_session("s-1").with_actor("alice"));
+
+    let query = AuditQuery {
+        session_id: Some("s-1".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(log.query(&query).unwrap().len(), 2);
+
+    let query = AuditQuery {
+        kind: Some(AuditKind::ToolCall),
+        limit: Some(1),
+        ..Default::default()
+    };
+    let entries = log.query(&query).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].session_id.as_deref(), Some("s-2"));
+
+    let query = AuditQuery {
+        since: Some(Utc::now() + chrono::Duration::minutes(1)),
+        ..Default::default()
+    };
+    assert!(log.query(&query).unwrap().is_empty());
+}
+
+#[test]
+fn test_custom_redaction_rules() {
+    let rules = RedactionRules::default()
+        .with_key("ssn")
+        .with_pattern(r"\d{4}-\d{4}-\d{4}-\d{4}")
+        .unwrap();
+    let mut params = serde_json::json!({"SSN": "123", "note": ["card 1234-5678-9012-3456"]});
+    rules.redact(&mut params);
+    assert_eq!(params, serde_json::json!({"SSN": "<redacted>", "note": ["card <redacted>"]}));
+    assert!(RedactionRules::default().with_pattern("(").is_err());
+}
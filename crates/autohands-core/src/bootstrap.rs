@@ -0,0 +1,197 @@
+//! Config-driven extension bootstrap.
+//!
+//! The binary describes every extension it was built with in an
+//! [`ExtensionCatalog`]; the deployment's [`ExtensionSelection`] says which
+//! of them to enable and with what configuration. [`Kernel::bootstrap`]
+//! loads the selected ones, so a deployment can leave out the shell or the
+//! desktop tools without recompiling.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use tracing::{info, warn};
+
+use autohands_protocols::extension::Extension;
+
+use crate::kernel::Kernel;
+
+/// What an extension contributes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtensionKind {
+    Tool,
+    Channel,
+    Provider,
+    Memory,
+}
+
+impl fmt::Display for ExtensionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            Self::Tool => "tool",
+            Self::Channel => "channel",
+            Self::Provider => "provider",
+            Self::Memory => "memory",
+        };
+        f.write_str(kind)
+    }
+}
+
+/// Which extensions to enable, and their configuration.
+///
+/// With an empty `enabled` list every extension is enabled; `disabled`
+/// always wins.
+#[derive(Debug, Clone, Default)]
+pub struct ExtensionSelection {
+    enabled: Vec<String>,
+    disabled: Vec<String>,
+    config: HashMap<String, serde_json::Value>,
+}
+
+impl ExtensionSelection {
+    /// Enable every extension.
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Enable only these extensions.
+    pub fn with_enabled(mut self, ids: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.enabled.extend(ids.into_iter().map(Into::into));
+        self
+    }
+
+    /// Never enable these extensions.
+    pub fn with_disabled(mut self, ids: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.disabled.extend(ids.into_iter().map(Into::into));
+        self
+    }
+
+    /// Configuration passed to an extension when it is loaded.
+    pub fn with_config(mut self, id: impl Into<String>, config: serde_json::Value) -> Self {
+        self.config.insert(id.into(), config);
+        self
+    }
+
+    /// Whether an extension is enabled.
+    pub fn is_enabled(&self, id: &str) -> bool {
+        let listed = |ids: &[String]| ids.iter().any(|i| i == id);
+        !listed(&self.disabled) && (self.enabled.is_empty() || listed(&self.enabled))
+    }
+
+    /// Configuration of an extension, `null` if none.
+    pub fn config_for(&self, id: &str) -> serde_json::Value {
+        self.config.get(id).cloned().unwrap_or_default()
+    }
+
+    /// Extensions named by the selection but not among `known`, sorted.
+    pub fn unknown<'a>(&self, known: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+        let known: Vec<&str> = known.into_iter().collect();
+        let mut unknown: Vec<String> = self
+            .enabled
+            .iter()
+            .chain(&self.disabled)
+            .chain(self.config.keys())
+            .filter(|id| !known.contains(&id.as_str()))
+            .cloned()
+            .collect();
+        unknown.sort();
+        unknown.dedup();
+        unknown
+    }
+}
+
+type ExtensionFactory = Box<dyn Fn() -> Box<dyn Extension> + Send + Sync>;
+
+struct CatalogEntry {
+    id: String,
+    kind: ExtensionKind,
+    factory: ExtensionFactory,
+}
+
+/// Extensions available to [`Kernel::bootstrap`], in loading order.
+#[derive(Default)]
+pub struct ExtensionCatalog {
+    entries: Vec<CatalogEntry>,
+}
+
+impl ExtensionCatalog {
+    /// Create an empty catalog.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an extension, built by `factory` only if it is selected.
+    pub fn add<F>(mut self, id: impl Into<String>, kind: ExtensionKind, factory: F) -> Self
+    where
+        F: Fn() -> Box<dyn Extension> + Send + Sync + 'static,
+    {
+        self.entries.push(CatalogEntry {
+            id: id.into(),
+            kind,
+            factory: Box::new(factory),
+        });
+        self
+    }
+
+    /// IDs of the extensions, in loading order.
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|entry| entry.id.as_str())
+    }
+
+    /// Kind of an extension.
+    pub fn kind(&self, id: &str) -> Option<ExtensionKind> {
+        self.entries.iter().find(|entry| entry.id == id).map(|entry| entry.kind)
+    }
+}
+
+/// Outcome of [`Kernel::bootstrap`].
+#[derive(Debug, Default)]
+pub struct BootstrapReport {
+    /// Extensions loaded.
+    pub loaded: Vec<String>,
+    /// Extensions left out by the selection.
+    pub skipped: Vec<String>,
+    /// Extensions that failed to load, with the error.
+    pub failed: Vec<(String, String)>,
+}
+
+impl BootstrapReport {
+    /// Add the outcome of another bootstrap.
+    pub fn merge(&mut self, other: BootstrapReport) {
+        self.loaded.extend(other.loaded);
+        self.skipped.extend(other.skipped);
+        self.failed.extend(other.failed);
+    }
+}
+
+impl Kernel {
+    /// Load the selected extensions of a catalog, in catalog order.
+    ///
+    /// A failing extension is reported and does not stop the others.
+    pub async fn bootstrap(
+        &self,
+        catalog: &ExtensionCatalog,
+        selection: &ExtensionSelection,
+    ) -> BootstrapReport {
+        let mut report = BootstrapReport::default();
+        for entry in &catalog.entries {
+            if !selection.is_enabled(&entry.id) {
+                info!("Extension {} ({}) disabled by configuration", entry.id, entry.kind);
+                report.skipped.push(entry.id.clone());
+                continue;
+            }
+            let extension = (entry.factory)();
+            match self.load_extension(extension, selection.config_for(&entry.id)).await {
+                Ok(()) => report.loaded.push(entry.id.clone()),
+                Err(e) => {
+                    warn!("Failed to load {} extension {}: {}", entry.kind, entry.id, e);
+                    report.failed.push((entry.id.clone(), e.to_string()));
+                }
+            }
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+#[path = "bootstrap_tests.rs"]
+mod tests;
@@ -0,0 +1,127 @@
+use super::*;
+use std::any::Any;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use autohands_protocols::error::ExtensionError;
+use autohands_protocols::extension::{ExtensionContext, ExtensionManifest};
+use autohands_protocols::types::Version;
+use parking_lot::Mutex;
+
+use crate::registry::{ProviderRegistry, ToolRegistry};
+
+struct MockExtension {
+    manifest: ExtensionManifest,
+    fail: bool,
+    seen_config: Arc<Mutex<Option<serde_json::Value>>>,
+}
+
+impl MockExtension {
+    fn new(id: &str) -> Self {
+        Self {
+            manifest: ExtensionManifest::new(id, "Mock Extension", Version::new(1, 0, 0)),
+            fail: false,
+            seen_config: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+#[async_trait]
+impl Extension for MockExtension {
+    fn manifest(&self) -> &ExtensionManifest {
+        &self.manifest
+    }
+
+    async fn initialize(&mut self, ctx: ExtensionContext) -> Result<(), ExtensionError> {
+        *self.seen_config.lock() = Some(ctx.config);
+        if self.fail {
+            return Err(ExtensionError::InitializationFailed("boom".to_string()));
+        }
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+fn catalog() -> ExtensionCatalog {
+    ExtensionCatalog::new()
+        .add("tools-a", ExtensionKind::Tool, || Box::new(MockExtension::new("tools-a")))
+        .add("memory-b", ExtensionKind::Memory, || Box::new(MockExtension::new("memory-b")))
+        .add("tools-c", ExtensionKind::Tool, || Box::new(MockExtension::new("tools-c")))
+}
+
+#[test]
+fn test_selection_enables_everything_by_default() {
+    let selection = ExtensionSelection::all();
+    assert!(selection.is_enabled("tools-a"));
+    assert_eq!(selection.config_for("tools-a"), serde_json::Value::Null);
+}
+
+#[test]
+fn test_selection_disabled_wins_over_enabled() {
+    let selection = ExtensionSelection::all()
+        .with_enabled(["tools-a", "tools-c"])
+        .with_disabled(["tools-c"]);
+    assert!(selection.is_enabled("tools-a"));
+    assert!(!selection.is_enabled("memory-b"));
+    assert!(!selection.is_enabled("tools-c"));
+}
+
+#[test]
+fn test_selection_reports_unknown_ids() {
+    let selection = ExtensionSelection::all()
+        .with_enabled(["tools-a", "tols-c"])
+        .with_config("tools-z", serde_json::json!({}));
+    assert_eq!(selection.unknown(catalog().ids()), vec!["tols-c", "tools-z"]);
+}
+
+#[tokio::test]
+async fn test_bootstrap_loads_selected_extensions_with_config() {
+    let seen = Arc::new(Mutex::new(None));
+    let seen_by_factory = seen.clone();
+    let catalog = catalog().add("tools-d", ExtensionKind::Tool, move || {
+        let mut ext = MockExtension::new("tools-d");
+        ext.seen_config = seen_by_factory.clone();
+        Box::new(ext)
+    });
+    let selection = ExtensionSelection::all()
+        .with_disabled(["memory-b"])
+        .with_config("tools-d", serde_json::json!({"level": 3}));
+
+    let kernel = Kernel::new(PathBuf::from("."));
+    let report = kernel.bootstrap(&catalog, &selection).await;
+
+    assert_eq!(report.loaded, vec!["tools-a", "tools-c", "tools-d"]);
+    assert_eq!(report.skipped, vec!["memory-b"]);
+    assert!(report.failed.is_empty());
+    assert!(kernel.extension("tools-d").is_some());
+    assert!(kernel.extension("memory-b").is_none());
+    assert_eq!(*seen.lock(), Some(serde_json::json!({"level": 3})));
+}
+
+#[tokio::test]
+async fn test_bootstrap_continues_after_a_failure() {
+    let catalog = ExtensionCatalog::new()
+        .add("tools-bad", ExtensionKind::Tool, || {
+            let mut ext = MockExtension::new("tools-bad");
+            ext.fail = true;
+            Box::new(ext)
+        })
+        .add("tools-a", ExtensionKind::Tool, || Box::new(MockExtension::new("tools-a")));
+
+    let kernel = Kernel::new(PathBuf::from("."))
+        .with_registries(Arc::new(ToolRegistry::new()), Arc::new(ProviderRegistry::new()));
+    let report = kernel.bootstrap(&catalog, &ExtensionSelection::all()).await;
+
+    assert_eq!(report.loaded, vec!["tools-a"]);
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].0, "tools-bad");
+    assert_eq!(catalog.kind("tools-bad"), Some(ExtensionKind::Tool));
+}
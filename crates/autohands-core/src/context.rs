@@ -4,7 +4,7 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use autohands_protocols::tool::AbortSignal;
+use autohands_protocols::tool::{AbortSignal, ToolContext};
 
 /// Execution context passed to tools and agents.
 #[derive(Clone)]
@@ -69,6 +69,17 @@ impl ExecutionContext {
         self.abort_signal.abort();
     }
 
+    /// Tool context sharing this context's IDs, abort signal and data.
+    ///
+    /// Aborting this context (but not a parent) aborts the tool.
+    pub fn tool_context(&self) -> ToolContext {
+        let mut ctx = ToolContext::new(self.session_id.clone(), self.work_dir.clone());
+        ctx.correlation_id = self.correlation_id.clone();
+        ctx.abort_signal = self.abort_signal.clone();
+        ctx.data = self.data.read().clone();
+        ctx
+    }
+
     /// Get a value from context data.
     pub fn get<T: serde::de::DeserializeOwned>(&self, key: &str) -> Option<T> {
         let data = self.data.read();
@@ -155,6 +166,18 @@ mod tests {
         assert_eq!(result, Some("data".to_string()));
     }
 
+    #[test]
+    fn test_execution_context_tool_context() {
+        let ctx = ExecutionContext::new("session-1", PathBuf::from("/tmp"));
+        ctx.set("key", "value");
+        let tool_ctx = ctx.tool_context();
+        assert_eq!(tool_ctx.session_id, "session-1");
+        assert_eq!(tool_ctx.correlation_id, ctx.correlation_id);
+        assert_eq!(tool_ctx.get::<String>("key"), Some("value".to_string()));
+        ctx.abort();
+        assert!(tool_ctx.is_aborted());
+    }
+
     #[test]
     fn test_execution_context_clone() {
         let ctx = ExecutionContext::new("session-1", PathBuf::from("/tmp"));
@@ -50,6 +50,18 @@ impl Kernel {
         }
     }
 
+    /// Register extension tools and providers in these registries instead
+    /// of the kernel's own.
+    pub fn with_registries(
+        mut self,
+        tool_registry: Arc<ToolRegistry>,
+        provider_registry: Arc<ProviderRegistry>,
+    ) -> Self {
+        self.tool_registry = tool_registry;
+        self.provider_registry = provider_registry;
+        self
+    }
+
     /// Start the kernel.
     pub async fn start(&self) -> Result<(), ExtensionError> {
         self.lifecycle.start().await
@@ -170,6 +182,11 @@ impl Kernel {
         &self.memory_registry
     }
 
+    /// Get a loaded extension.
+    pub fn extension(&self, id: &str) -> Option<Arc<dyn Extension>> {
+        self.extension_registry.get(id)
+    }
+
     /// List all loaded extensions.
     pub fn list_extensions(&self) -> Vec<ExtensionManifest> {
         self.extension_registry.list()
@@ -5,8 +5,15 @@
 //! ## Components
 //!
 //! - [`Kernel`] - The microkernel managing extension lifecycle
+//! - [`ExtensionCatalog`] - Extensions the kernel can bootstrap from config
+//! - [`PluginExtension`] - Third-party tools loaded from shared libraries
 //! - [`ExecutionContext`] - Context for tool/agent execution
 //! - [`LifecycleManager`] - Lifecycle management for kernel components
+//! - [`ApprovalService`] - In-process human approval gate
+//! - [`AuditLog`] - Tamper-evident log of tool calls, approvals and runs
+//! - [`QuestionService`] - In-process clarification questions to the user
+//! - [`Redactor`] - Scrubs secrets from logs, transcripts and LLM context
+//! - [`ResourceLocks`] - Exclusive resources held by tool calls
 //! - Registries for tools, providers, and extensions
 //!
 //! ## Task System
@@ -14,15 +21,37 @@
 //! Tasks are handled through the RunLoop task system. Extensions and tools
 //! use the `TaskSubmitter` trait to submit tasks that flow through RunLoop.
 
+pub mod approval;
+pub mod audit;
+pub mod bootstrap;
 pub mod context;
 pub mod kernel;
 pub mod lifecycle;
+pub mod plugin;
+pub mod question;
+pub mod redact;
 pub mod registry;
+pub mod resource_lock;
 
+pub use approval::{ApprovalService, DEFAULT_APPROVAL_TIMEOUT};
+pub use audit::{
+    AuditEntry, AuditKind, AuditLog, AuditQuery, AuditVerification, RedactionRules, GENESIS_HASH,
+};
+pub use bootstrap::{BootstrapReport, ExtensionCatalog, ExtensionKind, ExtensionSelection};
 pub use context::ExecutionContext;
 pub use kernel::Kernel;
+pub use plugin::{
+    discover_plugins, PluginApi, PluginExtension, PluginManifest, PluginRequest,
+    PLUGIN_ABI_VERSION, PLUGIN_ENTRY_SYMBOL,
+};
+pub use question::{QuestionService, ReplyRouting, DEFAULT_QUESTION_TIMEOUT};
+pub use redact::{
+    redact_json, redact_message, Redactor, RedactorChain, SecretRedactor, SharedRedactor,
+    DEFAULT_SECRET_PATTERNS, REDACTED,
+};
 pub use lifecycle::{
     KernelState, LifecycleHook, LifecycleManager, RunLoopControl, RunLoopLifecycleHook,
     ShutdownSignal,
 };
 pub use registry::{ChannelRegistry, ExtensionRegistry, ProviderRegistry, ToolRegistry};
+pub use resource_lock::{ResourceGuard, ResourceLocks, ResourceSuspension};
@@ -0,0 +1,398 @@
+//! Plugin extensions loaded from shared libraries at runtime.
+//!
+//! A plugin is a `cdylib` exporting [`PLUGIN_ENTRY_SYMBOL`], which returns
+//! a [`PluginApi`] table. Everything crossing the boundary is a
+//! NUL-terminated JSON string, so the plugin needs neither this crate nor
+//! the compiler version of the host:
+//!
+//! - `manifest()` returns a [`PluginManifest`]: the extension ID, name,
+//!   version and the [`ToolDefinition`]s of its tools.
+//! - `invoke(request)` takes a [`PluginRequest`] and returns a
+//!   [`ToolResult`].
+//! - `free_string(s)` frees a string returned by the other two.
+//!
+//! A plugin written in Rust looks like:
+//!
+//! ```ignore
+//! #[unsafe(no_mangle)]
+//! pub extern "C" fn autohands_plugin_v1() -> *const PluginApi {
+//!     static API: PluginApi = PluginApi {
+//!         abi_version: 1,
+//!         manifest,
+//!         invoke,
+//!         free_string,
+//!     };
+//!     &API
+//! }
+//! ```
+//!
+//! Functions are called from blocking threads and may be called
+//! concurrently; they must not unwind across the boundary. WASM components
+//! are not supported by this build and are reported as such.
+//!
+//! Whether a plugin is enabled is decided before opening it, from the ID
+//! given by [`plugin_id`], so a disabled plugin's code never runs.
+
+use std::ffi::{c_char, CStr, CString};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use libloading::Library;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use autohands_protocols::error::{ExtensionError, ToolError};
+use autohands_protocols::extension::{Extension, ExtensionContext, ExtensionManifest, Provides};
+use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::types::Version;
+
+use crate::bootstrap::{BootstrapReport, ExtensionSelection};
+use crate::kernel::Kernel;
+
+/// Version of the plugin ABI understood by this build.
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+
+/// Symbol a plugin library exports, of type `extern "C" fn() -> *const PluginApi`.
+pub const PLUGIN_ENTRY_SYMBOL: &str = "autohands_plugin_v1";
+
+/// Functions exported by a plugin.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct PluginApi {
+    /// Must be [`PLUGIN_ABI_VERSION`].
+    pub abi_version: u32,
+    /// The [`PluginManifest`] as JSON.
+    pub manifest: extern "C" fn() -> *mut c_char,
+    /// Run a tool: a [`PluginRequest`] in, a [`ToolResult`] out, as JSON.
+    pub invoke: extern "C" fn(request: *const c_char) -> *mut c_char,
+    /// Free a string returned by `manifest` or `invoke`.
+    pub free_string: extern "C" fn(s: *mut c_char),
+}
+
+type PluginEntry = unsafe extern "C" fn() -> *const PluginApi;
+
+/// What a plugin provides.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginManifest {
+    /// Extension ID, used by `[extensions]` like a built-in one.
+    pub id: String,
+    pub name: String,
+    pub version: Version,
+    #[serde(default)]
+    pub description: String,
+    /// Tools of the plugin.
+    #[serde(default)]
+    pub tools: Vec<ToolDefinition>,
+}
+
+/// A tool call passed to a plugin.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginRequest {
+    /// Tool ID.
+    pub tool: String,
+    /// Tool parameters.
+    pub params: serde_json::Value,
+    pub session_id: String,
+    pub work_dir: PathBuf,
+    /// Configuration of the extension from `[extensions.config]`.
+    pub config: serde_json::Value,
+}
+
+/// A plugin's functions and the library holding them.
+struct PluginHandle {
+    api: PluginApi,
+    /// Keeps the functions of `api` loaded.
+    _library: Option<Library>,
+}
+
+impl PluginHandle {
+    /// Take ownership of a string returned by the plugin.
+    fn take_string(&self, ptr: *mut c_char) -> Option<String> {
+        if ptr.is_null() {
+            return None;
+        }
+        // SAFETY: the ABI requires a NUL-terminated string allocated by the
+        // plugin, valid until passed to `free_string`
+        let text = unsafe { CStr::from_ptr(ptr) }.to_string_lossy().into_owned();
+        (self.api.free_string)(ptr);
+        Some(text)
+    }
+
+    fn manifest(&self) -> Result<PluginManifest, String> {
+        let json = self
+            .take_string((self.api.manifest)())
+            .ok_or("plugin returned no manifest")?;
+        serde_json::from_str(&json).map_err(|e| format!("invalid plugin manifest: {}", e))
+    }
+
+    fn invoke(&self, request: &PluginRequest) -> Result<ToolResult, String> {
+        let request = serde_json::to_string(request).map_err(|e| e.to_string())?;
+        let request = CString::new(request).map_err(|e| e.to_string())?;
+        let json = self
+            .take_string((self.api.invoke)(request.as_ptr()))
+            .ok_or("plugin returned no result")?;
+        serde_json::from_str(&json).map_err(|e| format!("invalid plugin result: {}", e))
+    }
+}
+
+/// An extension provided by a plugin.
+pub struct PluginExtension {
+    handle: Arc<PluginHandle>,
+    manifest: ExtensionManifest,
+    tools: Vec<ToolDefinition>,
+}
+
+impl PluginExtension {
+    /// Load a plugin library.
+    pub fn load(path: &Path) -> Result<Self, ExtensionError> {
+        let failed = |message: String| {
+            ExtensionError::InitializationFailed(format!("{}: {}", path.display(), message))
+        };
+        if path.extension().is_some_and(|ext| ext == "wasm") {
+            return Err(failed(
+                "WASM plugins are not supported by this build; build the plugin as a shared library"
+                    .to_string(),
+            ));
+        }
+        // SAFETY: loading runs the library's initializers; plugins are
+        // trusted code named by the configuration
+        let library = unsafe { Library::new(path) }.map_err(|e| failed(e.to_string()))?;
+        // SAFETY: the symbol has the signature documented by the ABI
+        let api = unsafe {
+            let entry = library
+                .get::<PluginEntry>(PLUGIN_ENTRY_SYMBOL.as_bytes())
+                .map_err(|e| failed(e.to_string()))?;
+            let api = entry();
+            if api.is_null() {
+                return Err(failed("plugin returned no API".to_string()));
+            }
+            *api
+        };
+        Self::new(PluginHandle {
+            api,
+            _library: Some(library),
+        })
+        .map_err(failed)
+    }
+
+    /// Plugin from functions linked into the binary.
+    pub fn from_api(api: PluginApi) -> Result<Self, ExtensionError> {
+        Self::new(PluginHandle {
+            api,
+            _library: None,
+        })
+        .map_err(ExtensionError::InitializationFailed)
+    }
+
+    fn new(handle: PluginHandle) -> Result<Self, String> {
+        if handle.api.abi_version != PLUGIN_ABI_VERSION {
+            return Err(format!(
+                "plugin ABI version {} is not supported (expected {})",
+                handle.api.abi_version, PLUGIN_ABI_VERSION
+            ));
+        }
+        let plugin = handle.manifest()?;
+        let mut manifest = ExtensionManifest::new(&plugin.id, &plugin.name, plugin.version)
+            .with_description(plugin.description);
+        manifest.provides = Provides {
+            tools: plugin.tools.iter().map(|tool| tool.id.clone()).collect(),
+            ..Default::default()
+        };
+        let tools = plugin
+            .tools
+            .into_iter()
+            .map(|mut tool| {
+                tool.extension_id = Some(plugin.id.clone());
+                tool
+            })
+            .collect();
+        Ok(Self {
+            handle: Arc::new(handle),
+            manifest,
+            tools,
+        })
+    }
+}
+
+#[async_trait]
+impl Extension for PluginExtension {
+    fn manifest(&self) -> &ExtensionManifest {
+        &self.manifest
+    }
+
+    async fn initialize(&mut self, ctx: ExtensionContext) -> Result<(), ExtensionError> {
+        for definition in &self.tools {
+            ctx.tool_registry.register_tool(Arc::new(PluginTool {
+                definition: definition.clone(),
+                handle: self.handle.clone(),
+                config: ctx.config.clone(),
+            }))?;
+        }
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+/// A tool run by a plugin.
+struct PluginTool {
+    definition: ToolDefinition,
+    handle: Arc<PluginHandle>,
+    config: serde_json::Value,
+}
+
+#[async_trait]
+impl Tool for PluginTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let request = PluginRequest {
+            tool: self.definition.id.clone(),
+            params,
+            session_id: ctx.session_id,
+            work_dir: ctx.work_dir,
+            config: self.config.clone(),
+        };
+        let handle = self.handle.clone();
+        tokio::task::spawn_blocking(move || handle.invoke(&request))
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?
+            .map_err(ToolError::ExecutionFailed)
+    }
+}
+
+/// Manifest next to a plugin file, `<name>.json` for `<name>.so`.
+#[derive(Debug, Deserialize)]
+struct PluginSidecar {
+    id: String,
+}
+
+/// ID of the plugin at `path`, known without opening it: the `id` of its
+/// sidecar manifest `<name>.json` if there is one, otherwise the file name
+/// without its extension and `lib` prefix, with `_` read as `-` (cargo's
+/// `libmy_tools.so` for a `my-tools` crate is `my-tools`).
+pub fn plugin_id(path: &Path) -> Result<String, String> {
+    let sidecar = path.with_extension("json");
+    match std::fs::read_to_string(&sidecar) {
+        Ok(raw) => {
+            return serde_json::from_str::<PluginSidecar>(&raw)
+                .map(|sidecar| sidecar.id)
+                .map_err(|e| format!("invalid plugin manifest {}: {}", sidecar.display(), e));
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(format!("cannot read {}: {}", sidecar.display(), e)),
+    }
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .ok_or_else(|| format!("{} has no file name", path.display()))?;
+    let stem = stem.strip_prefix("lib").unwrap_or(&stem);
+    Ok(stem.replace('_', "-"))
+}
+
+/// Plugin files at `paths`: files as given, and the shared libraries and
+/// WASM modules in directories, sorted. Missing paths are skipped.
+pub fn discover_plugins(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut plugins = Vec::new();
+    for path in paths {
+        if path.is_file() {
+            plugins.push(path.clone());
+            continue;
+        }
+        let Ok(entries) = std::fs::read_dir(path) else {
+            continue;
+        };
+        let mut found: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.is_file()
+                    && path.extension().is_some_and(|ext| {
+                        ext == std::env::consts::DLL_EXTENSION || ext == "wasm"
+                    })
+            })
+            .collect();
+        found.sort();
+        plugins.extend(found);
+    }
+    plugins
+}
+
+impl Kernel {
+    /// Load the plugins found at `paths` that the selection enables, like
+    /// the extensions of a catalog.
+    ///
+    /// Plugins are selected by their [`plugin_id`]; disabled ones are not
+    /// opened. A plugin that cannot be opened is reported as failed under
+    /// its path, and one declaring another ID than its file name or sidecar
+    /// manifest is refused.
+    pub async fn load_plugins(
+        &self,
+        paths: &[PathBuf],
+        selection: &ExtensionSelection,
+    ) -> BootstrapReport {
+        let mut report = BootstrapReport::default();
+        for path in discover_plugins(paths) {
+            let id = match plugin_id(&path) {
+                Ok(id) => id,
+                Err(e) => {
+                    warn!("Failed to identify plugin {}: {}", path.display(), e);
+                    report.failed.push((path.display().to_string(), e));
+                    continue;
+                }
+            };
+            if !selection.is_enabled(&id) {
+                info!("Plugin {} ({}) disabled by configuration", id, path.display());
+                report.skipped.push(id);
+                continue;
+            }
+            let plugin = match PluginExtension::load(&path) {
+                Ok(plugin) => plugin,
+                Err(e) => {
+                    warn!("Failed to open plugin {}: {}", path.display(), e);
+                    report.failed.push((path.display().to_string(), e.to_string()));
+                    continue;
+                }
+            };
+            if plugin.manifest.id != id {
+                let e = format!(
+                    "plugin declares ID {} but is named {}; add a {} manifest with its ID",
+                    plugin.manifest.id,
+                    id,
+                    path.with_extension("json").display()
+                );
+                warn!("Refused plugin {}: {}", path.display(), e);
+                report.failed.push((id, e));
+                continue;
+            }
+            match self.load_extension(Box::new(plugin), selection.config_for(&id)).await {
+                Ok(()) => {
+                    info!("Loaded plugin {} from {}", id, path.display());
+                    report.loaded.push(id);
+                }
+                Err(e) => {
+                    warn!("Failed to load plugin {}: {}", id, e);
+                    report.failed.push((id, e.to_string()));
+                }
+            }
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+#[path = "plugin_tests.rs"]
+mod tests;
@@ -0,0 +1,160 @@
+use super::*;
+
+/// A plugin with an `echo` tool, as a plugin library would implement it.
+mod echo_plugin {
+    use std::ffi::{c_char, CStr, CString};
+
+    fn to_c(json: serde_json::Value) -> *mut c_char {
+        CString::new(json.to_string()).unwrap().into_raw()
+    }
+
+    pub extern "C" fn manifest() -> *mut c_char {
+        to_c(serde_json::json!({
+            "id": "plugin-echo",
+            "name": "Echo",
+            "version": {"major": 1, "minor": 2, "patch": 0},
+            "tools": [{"id": "echo", "name": "Echo", "description": "Echo the text"}],
+        }))
+    }
+
+    pub extern "C" fn invoke(request: *const c_char) -> *mut c_char {
+        let request = unsafe { CStr::from_ptr(request) }.to_string_lossy();
+        let request: serde_json::Value = serde_json::from_str(&request).unwrap();
+        to_c(serde_json::json!({
+            "success": true,
+            "content": format!(
+                "{}{}",
+                request["config"]["prefix"].as_str().unwrap_or_default(),
+                request["params"]["text"].as_str().unwrap_or_default()
+            ),
+        }))
+    }
+
+    pub extern "C" fn no_result(_request: *const c_char) -> *mut c_char {
+        std::ptr::null_mut()
+    }
+
+    pub extern "C" fn free_string(s: *mut c_char) {
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
+
+fn echo_api() -> PluginApi {
+    PluginApi {
+        abi_version: PLUGIN_ABI_VERSION,
+        manifest: echo_plugin::manifest,
+        invoke: echo_plugin::invoke,
+        free_string: echo_plugin::free_string,
+    }
+}
+
+#[test]
+fn test_plugin_manifest() {
+    let plugin = PluginExtension::from_api(echo_api()).unwrap();
+    let manifest = plugin.manifest();
+    assert_eq!(manifest.id, "plugin-echo");
+    assert_eq!(manifest.version.minor, 2);
+    assert_eq!(manifest.provides.tools, vec!["echo".to_string()]);
+    assert_eq!(plugin.tools[0].extension_id.as_deref(), Some("plugin-echo"));
+}
+
+#[test]
+fn test_plugin_abi_version_checked() {
+    let api = PluginApi {
+        abi_version: PLUGIN_ABI_VERSION + 1,
+        ..echo_api()
+    };
+    let err = PluginExtension::from_api(api).err().unwrap();
+    assert!(err.to_string().contains("ABI version"));
+}
+
+#[tokio::test]
+async fn test_plugin_tools_run_through_the_plugin() {
+    let kernel = Kernel::new(PathBuf::from("."));
+    let plugin = PluginExtension::from_api(echo_api()).unwrap();
+    kernel
+        .load_extension(Box::new(plugin), serde_json::json!({"prefix": "> "}))
+        .await
+        .unwrap();
+
+    let tool = kernel.tool_registry().get("echo").unwrap();
+    let result = tool
+        .execute(
+            serde_json::json!({"text": "hi"}),
+            ToolContext::new("session-1", PathBuf::from(".")),
+        )
+        .await
+        .unwrap();
+    assert!(result.success);
+    assert_eq!(result.content, "> hi");
+}
+
+#[tokio::test]
+async fn test_plugin_without_result_fails_the_call() {
+    let kernel = Kernel::new(PathBuf::from("."));
+    let api = PluginApi {
+        invoke: echo_plugin::no_result,
+        ..echo_api()
+    };
+    let plugin = PluginExtension::from_api(api).unwrap();
+    kernel.load_extension(Box::new(plugin), serde_json::Value::Null).await.unwrap();
+
+    let tool = kernel.tool_registry().get("echo").unwrap();
+    let err = tool
+        .execute(serde_json::json!({}), ToolContext::new("session-1", PathBuf::from(".")))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("no result"));
+}
+
+#[tokio::test]
+async fn test_load_plugins_reports_unloadable_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let library = dir.path().join(format!("broken.{}", std::env::consts::DLL_EXTENSION));
+    std::fs::write(&library, b"not a library").unwrap();
+    std::fs::write(dir.path().join("tool.wasm"), b"\0asm").unwrap();
+    std::fs::write(dir.path().join("README.md"), b"docs").unwrap();
+
+    let paths = vec![dir.path().to_path_buf(), dir.path().join("missing")];
+    assert_eq!(
+        discover_plugins(&paths),
+        vec![library.clone(), dir.path().join("tool.wasm")]
+    );
+
+    let kernel = Kernel::new(PathBuf::from("."));
+    let report = kernel.load_plugins(&paths, &ExtensionSelection::all()).await;
+    assert!(report.loaded.is_empty());
+    assert_eq!(report.failed.len(), 2);
+    assert_eq!(report.failed[0].0, library.display().to_string());
+    assert!(report.failed[1].1.contains("WASM plugins are not supported"));
+}
+
+#[test]
+fn test_plugin_id() {
+    let dir = tempfile::tempdir().unwrap();
+    assert_eq!(plugin_id(&dir.path().join("libmy_tools.so")).unwrap(), "my-tools");
+    assert_eq!(plugin_id(&dir.path().join("tool.wasm")).unwrap(), "tool");
+
+    let library = dir.path().join("libnamed.so");
+    std::fs::write(dir.path().join("libnamed.json"), r#"{"id": "plugin-echo"}"#).unwrap();
+    assert_eq!(plugin_id(&library).unwrap(), "plugin-echo");
+
+    std::fs::write(dir.path().join("libnamed.json"), "not json").unwrap();
+    assert!(plugin_id(&library).unwrap_err().contains("invalid plugin manifest"));
+}
+
+#[tokio::test]
+async fn test_disabled_plugins_are_not_opened() {
+    let dir = tempfile::tempdir().unwrap();
+    let library = dir.path().join(format!("libbroken.{}", std::env::consts::DLL_EXTENSION));
+    std::fs::write(&library, b"not a library").unwrap();
+    std::fs::write(dir.path().join("libbroken.json"), r#"{"id": "plugin-broken"}"#).unwrap();
+    std::fs::write(dir.path().join("tool.wasm"), b"\0asm").unwrap();
+
+    // Opening either file would fail, so a clean report shows neither was
+    let kernel = Kernel::new(PathBuf::from("."));
+    let selection = ExtensionSelection::all().with_disabled(["plugin-broken", "tool"]);
+    let report = kernel.load_plugins(&[dir.path().to_path_buf()], &selection).await;
+    assert!(report.failed.is_empty());
+    assert_eq!(report.skipped, vec!["plugin-broken".to_string(), "tool".to_string()]);
+}
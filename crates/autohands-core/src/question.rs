@@ -0,0 +1,301 @@
+//! In-process question gate.
+//!
+//! Sends each question to the conversation of the session asking it, keeps
+//! it pending until a reply is routed back ([`QuestionService::take_reply`])
+//! or the timeout hands out the default answer.
+//!
+//! Pending questions are also written to a file. After a restart nobody
+//! waits for them any more, but a reply still matches its question, so the
+//! channel bridge can resume the session with the question and its answer.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use dashmap::DashMap;
+use tokio::sync::oneshot;
+use tracing::{info, warn};
+
+use autohands_protocols::channel::{OutboundMessage, ReplyAddress};
+use autohands_protocols::question::{Answer, Question, QuestionGate};
+
+use crate::registry::ChannelRegistry;
+
+/// Default time to wait for a reply.
+pub const DEFAULT_QUESTION_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+struct PendingQuestion {
+    question: Question,
+    /// `None` for questions loaded from the file, asked before a restart.
+    responder: Option<oneshot::Sender<Answer>>,
+}
+
+/// Where a reply from a conversation went.
+#[derive(Debug)]
+pub enum ReplyRouting {
+    /// No question is pending in the conversation.
+    NotAnAnswer,
+    /// The reply answered a question its session is waiting for.
+    Delivered,
+    /// The reply answered a question nobody waits for any more.
+    Orphaned(Box<Question>),
+}
+
+/// Removes a question from the pending set when its asker stops waiting.
+///
+/// The store is left alone: an asker dropped by a shutdown leaves its
+/// question on file, to be answered after the restart.
+struct PendingGuard<'a> {
+    pending: &'a DashMap<String, PendingQuestion>,
+    id: String,
+}
+
+impl Drop for PendingGuard<'_> {
+    fn drop(&mut self) {
+        self.pending.remove(&self.id);
+    }
+}
+
+/// Question gate holding pending questions in memory and, optionally, in a
+/// file.
+pub struct QuestionService {
+    pending: DashMap<String, PendingQuestion>,
+    /// Conversation of each session, learned from inbound messages.
+    sessions: DashMap<String, ReplyAddress>,
+    channels: Option<Arc<ChannelRegistry>>,
+    store: Option<PathBuf>,
+    default_timeout: Duration,
+}
+
+impl QuestionService {
+    /// Create a service waiting [`DEFAULT_QUESTION_TIMEOUT`] for replies.
+    pub fn new() -> Self {
+        Self {
+            pending: DashMap::new(),
+            sessions: DashMap::new(),
+            channels: None,
+            store: None,
+            default_timeout: DEFAULT_QUESTION_TIMEOUT,
+        }
+    }
+
+    /// Send questions through the registered channels.
+    pub fn with_channels(mut self, channels: Arc<ChannelRegistry>) -> Self {
+        self.channels = Some(channels);
+        self
+    }
+
+    /// Keep pending questions in a JSON file, loading those still within
+    /// their timeout.
+    pub fn with_store(mut self, path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let questions: Vec<Question> = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+                warn!("Ignoring unreadable question store {:?}: {}", path, e);
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+        let now = Utc::now();
+        for question in questions {
+            let timeout = question
+                .timeout_secs
+                .map(Duration::from_secs)
+                .unwrap_or(self.default_timeout);
+            let expired = chrono::Duration::from_std(timeout)
+                .is_ok_and(|timeout| question.created_at + timeout < now);
+            if !expired {
+                self.pending.insert(
+                    question.id.clone(),
+                    PendingQuestion {
+                        question,
+                        responder: None,
+                    },
+                );
+            }
+        }
+        if !self.pending.is_empty() {
+            info!("Loaded {} pending question(s) from {:?}", self.pending.len(), path);
+        }
+        self.store = Some(path);
+        self
+    }
+
+    /// Set the timeout for questions that do not specify one.
+    pub fn with_default_timeout(mut self, timeout: Duration) -> Self {
+        self.default_timeout = timeout;
+        self
+    }
+
+    /// Remember the conversation a session talks in.
+    pub fn bind_session(&self, session_id: impl Into<String>, reply_to: ReplyAddress) {
+        self.sessions.insert(session_id.into(), reply_to);
+    }
+
+    /// Conversation a session talks in.
+    pub fn conversation(&self, session_id: &str) -> Option<ReplyAddress> {
+        self.sessions.get(session_id).map(|entry| entry.clone())
+    }
+
+    /// Number of questions waiting for an answer.
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Route a reply from a conversation to its oldest pending question.
+    pub fn take_reply(&self, reply_to: &ReplyAddress, reply: &str, from: Option<&str>) -> ReplyRouting {
+        let oldest = self
+            .pending
+            .iter()
+            .filter(|entry| {
+                entry.question.reply_to.as_ref().is_some_and(|addr| {
+                    addr.channel_id == reply_to.channel_id && addr.target == reply_to.target
+                })
+            })
+            .min_by_key(|entry| entry.question.created_at)
+            .map(|entry| entry.key().clone());
+        let Some((_, pending)) = oldest.and_then(|id| self.pending.remove(&id)) else {
+            return ReplyRouting::NotAnAnswer;
+        };
+        self.persist();
+
+        let mut answer = pending.question.parse_reply(reply);
+        if let Some(from) = from {
+            answer = answer.by(from);
+        }
+        let delivered = pending
+            .responder
+            .is_some_and(|responder| responder.send(answer).is_ok());
+        if delivered {
+            info!("Question {} answered", pending.question.id);
+            ReplyRouting::Delivered
+        } else {
+            ReplyRouting::Orphaned(Box::new(pending.question))
+        }
+    }
+
+    /// Write the pending questions to the store.
+    fn persist(&self) {
+        let Some(path) = &self.store else {
+            return;
+        };
+        let mut questions: Vec<Question> = self
+            .pending
+            .iter()
+            .map(|entry| entry.question.clone())
+            .collect();
+        questions.sort_by_key(|q| q.created_at);
+        let result = serde_json::to_vec_pretty(&questions)
+            .map_err(std::io::Error::other)
+            .and_then(|json| {
+                if let Some(dir) = path.parent() {
+                    std::fs::create_dir_all(dir)?;
+                }
+                std::fs::write(path, json)
+            });
+        if let Err(e) = result {
+            warn!("Failed to write question store {:?}: {}", path, e);
+        }
+    }
+
+    /// Send a question to its conversation.
+    async fn send(&self, question: &Question) {
+        let Some(reply_to) = &question.reply_to else {
+            info!(
+                "Question {} has no conversation to go to; answer it through the API",
+                question.id
+            );
+            return;
+        };
+        let Some(channel) = self
+            .channels
+            .as_ref()
+            .and_then(|channels| channels.get(&reply_to.channel_id))
+        else {
+            warn!("Channel {} of question {} not found", reply_to.channel_id, question.id);
+            return;
+        };
+        let message = OutboundMessage::text(question.render())
+            .with_metadata("question_id", question.id.clone().into());
+        if let Err(e) = channel.send(reply_to, message).await {
+            warn!("Failed to send question {}: {}", question.id, e);
+        }
+    }
+}
+
+impl Default for QuestionService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl QuestionGate for QuestionService {
+    async fn ask(&self, mut question: Question) -> Answer {
+        let timeout = question
+            .timeout_secs
+            .map(Duration::from_secs)
+            .unwrap_or(self.default_timeout);
+        question.timeout_secs = Some(timeout.as_secs());
+        if question.reply_to.is_none() {
+            question.reply_to = question
+                .session_id
+                .as_deref()
+                .and_then(|session_id| self.conversation(session_id));
+        }
+
+        let (responder, answer) = oneshot::channel();
+        self.pending.insert(
+            question.id.clone(),
+            PendingQuestion {
+                question: question.clone(),
+                responder: Some(responder),
+            },
+        );
+        self.persist();
+        let _guard = PendingGuard {
+            pending: &self.pending,
+            id: question.id.clone(),
+        };
+        info!("Question asked: {} ({})", question.text, question.id);
+
+        self.send(&question).await;
+
+        match tokio::time::timeout(timeout, answer).await {
+            Ok(Ok(answer)) => answer,
+            Ok(Err(_)) => Answer::timed_out(question.default.clone()),
+            Err(_) => {
+                warn!("Question {} timed out after {:?}", question.id, timeout);
+                self.pending.remove(&question.id);
+                self.persist();
+                Answer::timed_out(question.default.clone())
+            }
+        }
+    }
+
+    async fn pending(&self) -> Vec<Question> {
+        let mut questions: Vec<Question> = self
+            .pending
+            .iter()
+            .map(|entry| entry.question.clone())
+            .collect();
+        questions.sort_by_key(|q| q.created_at);
+        questions
+    }
+
+    async fn answer(&self, question_id: &str, answer: Answer) -> bool {
+        let Some((_, pending)) = self.pending.remove(question_id) else {
+            return false;
+        };
+        self.persist();
+        pending
+            .responder
+            .is_some_and(|responder| responder.send(answer).is_ok())
+    }
+}
+
+#[cfg(test)]
+#[path = "question_tests.rs"]
+mod tests;
@@ -0,0 +1,176 @@
+use super::*;
+
+use autohands_protocols::channel::{
+    Channel, ChannelCapabilities, ChannelId, InboundMessage, SentMessage,
+};
+use autohands_protocols::error::ChannelError;
+use autohands_protocols::question::AnswerStatus;
+use parking_lot::Mutex;
+use tokio::sync::broadcast;
+
+/// Channel recording what it sends.
+struct RecordingChannel {
+    id: ChannelId,
+    capabilities: ChannelCapabilities,
+    sent: Mutex<Vec<(ReplyAddress, OutboundMessage)>>,
+    inbound: broadcast::Sender<InboundMessage>,
+}
+
+impl RecordingChannel {
+    fn new(id: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            capabilities: ChannelCapabilities::default(),
+            sent: Mutex::new(Vec::new()),
+            inbound: broadcast::channel(4).0,
+        }
+    }
+}
+
+#[async_trait]
+impl Channel for RecordingChannel {
+    fn id(&self) -> &ChannelId {
+        &self.id
+    }
+
+    fn capabilities(&self) -> &ChannelCapabilities {
+        &self.capabilities
+    }
+
+    async fn start(&self) -> Result<(), ChannelError> {
+        Ok(())
+    }
+
+    async fn stop(&self) -> Result<(), ChannelError> {
+        Ok(())
+    }
+
+    async fn send(
+        &self,
+        target: &ReplyAddress,
+        message: OutboundMessage,
+    ) -> Result<SentMessage, ChannelError> {
+        self.sent.lock().push((target.clone(), message));
+        Ok(SentMessage {
+            id: "sent".to_string(),
+            timestamp: Utc::now(),
+        })
+    }
+
+    fn inbound(&self) -> broadcast::Receiver<InboundMessage> {
+        self.inbound.subscribe()
+    }
+}
+
+async fn wait_for_pending(service: &QuestionService) -> Question {
+    loop {
+        if let Some(question) = service.pending().await.into_iter().next() {
+            return question;
+        }
+        tokio::task::yield_now().await;
+    }
+}
+
+#[tokio::test]
+async fn test_question_round_trip_through_session_conversation() {
+    let channel = Arc::new(RecordingChannel::new("slack"));
+    let registry = Arc::new(ChannelRegistry::new());
+    registry.register(channel.clone()).unwrap();
+    let service = Arc::new(QuestionService::new().with_channels(registry));
+    let conversation = ReplyAddress::new("slack", "C42");
+    service.bind_session("s-1", conversation.clone());
+
+    let asker = {
+        let service = service.clone();
+        tokio::spawn(async move {
+            let question = Question::new("Which environment?")
+                .with_options(vec!["staging".to_string(), "production".to_string()])
+                .with_session("s-1");
+            service.ask(question).await
+        })
+    };
+    let question = wait_for_pending(&service).await;
+    assert_eq!(question.reply_to.as_ref(), Some(&conversation));
+    {
+        let sent = channel.sent.lock();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].0, conversation);
+        assert!(sent[0].1.content.contains("2. production"));
+    }
+
+    // Replies from other conversations are not answers
+    let other = ReplyAddress::new("slack", "C7");
+    assert!(matches!(service.take_reply(&other, "2", None), ReplyRouting::NotAnAnswer));
+
+    let routing = service.take_reply(&conversation, "2", Some("alice"));
+    assert!(matches!(routing, ReplyRouting::Delivered));
+    let answer = asker.await.unwrap();
+    assert_eq!(answer.status, AnswerStatus::Answered);
+    assert_eq!(answer.text.as_deref(), Some("production"));
+    assert_eq!(answer.option, Some(1));
+    assert_eq!(answer.answered_by.as_deref(), Some("alice"));
+    assert_eq!(service.pending_count(), 0);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_timeout_gives_default() {
+    let service = QuestionService::new();
+    let question = Question::new("Proceed?").with_default("yes").with_timeout(5);
+    let answer = service.ask(question).await;
+    assert_eq!(answer.status, AnswerStatus::TimedOut);
+    assert_eq!(answer.text.as_deref(), Some("yes"));
+    assert_eq!(service.pending_count(), 0);
+}
+
+#[tokio::test]
+async fn test_answer_by_id() {
+    let service = Arc::new(QuestionService::new());
+    let asker = {
+        let service = service.clone();
+        tokio::spawn(async move { service.ask(Question::new("Name?")).await })
+    };
+    let question = wait_for_pending(&service).await;
+    assert!(service.answer(&question.id, Answer::answered("Ada")).await);
+    assert_eq!(asker.await.unwrap().text.as_deref(), Some("Ada"));
+    assert!(!service.answer(&question.id, Answer::answered("Ada")).await);
+}
+
+#[tokio::test]
+async fn test_store_keeps_questions_across_restart() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("questions.json");
+    let conversation = ReplyAddress::new("slack", "C42");
+
+    let service = Arc::new(QuestionService::new().with_store(&path));
+    let asker = {
+        let service = service.clone();
+        let question = Question::new("Proceed?").with_reply_to(conversation.clone());
+        tokio::spawn(async move { service.ask(question).await })
+    };
+    let question = wait_for_pending(&service).await;
+    // The server shuts down while the question is pending
+    asker.abort();
+    let _ = asker.await;
+    assert_eq!(service.pending_count(), 0);
+
+    let restarted = QuestionService::new().with_store(&path);
+    assert_eq!(restarted.pending_count(), 1);
+    match restarted.take_reply(&conversation, "yes", None) {
+        ReplyRouting::Orphaned(orphan) => assert_eq!(orphan.id, question.id),
+        other => panic!("expected an orphaned question, got {:?}", other),
+    }
+    assert_eq!(restarted.pending_count(), 0);
+    assert_eq!(std::fs::read_to_string(&path).unwrap().trim(), "[]");
+}
+
+#[tokio::test]
+async fn test_store_drops_expired_questions() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("questions.json");
+    let mut question = Question::new("Proceed?").with_timeout(60);
+    question.created_at = Utc::now() - chrono::Duration::hours(1);
+    std::fs::write(&path, serde_json::to_vec(&vec![question]).unwrap()).unwrap();
+
+    let service = QuestionService::new().with_store(&path);
+    assert_eq!(service.pending_count(), 0);
+}
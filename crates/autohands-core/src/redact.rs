@@ -0,0 +1,181 @@
+//! Secret redaction.
+//!
+//! A [`Redactor`] scrubs secrets from text before it is logged, written to
+//! a transcript, returned by a tool or sent to an LLM provider.
+//! [`SecretRedactor`] matches well-known credential formats (API keys,
+//! bearer tokens, AWS credentials) and configured regular expressions;
+//! [`RedactorChain`] runs several redactors in turn. Log output is set up
+//! before the configuration is read, so it goes through a
+//! [`SharedRedactor`] whose redactor is replaced once it is.
+
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use regex::Regex;
+
+use autohands_protocols::types::{ContentPart, Message, MessageContent};
+
+/// Replacement of redacted values.
+pub const REDACTED: &str = "<redacted>";
+
+/// Credentials redacted by default wherever they appear.
+pub const DEFAULT_SECRET_PATTERNS: &[&str] = &[
+    // Authorization headers
+    r"(?i)bearer\s+[a-z0-9._~+/-]+=*",
+    // OpenAI and Anthropic API keys
+    r"sk-[A-Za-z0-9_-]{16,}",
+    // GitHub tokens
+    r"gh[pousr]_[A-Za-z0-9]{36,}",
+    r"github_pat_[A-Za-z0-9_]{22,}",
+    // Google API keys
+    r"AIza[0-9A-Za-z_-]{35}",
+    // Slack tokens
+    r"xox[abprs]-[A-Za-z0-9-]{10,}",
+    // AWS access key IDs and secret access keys
+    r"\b(?:AKIA|ASIA)[0-9A-Z]{16}\b",
+    r#"(?i)aws_secret_access_key["']?\s*[:=]\s*["']?[A-Za-z0-9/+=]{40}"#,
+];
+
+/// Scrubs secrets from text.
+pub trait Redactor: Send + Sync {
+    /// `text` with its secrets replaced, borrowed when it has none.
+    fn redact<'a>(&self, text: &'a str) -> Cow<'a, str>;
+}
+
+/// Redacts text matching the built-in credential formats and any added
+/// patterns.
+#[derive(Debug, Clone)]
+pub struct SecretRedactor {
+    patterns: Vec<Regex>,
+}
+
+impl Default for SecretRedactor {
+    fn default() -> Self {
+        Self {
+            patterns: DEFAULT_SECRET_PATTERNS
+                .iter()
+                .filter_map(|p| Regex::new(p).ok())
+                .collect(),
+        }
+    }
+}
+
+impl SecretRedactor {
+    /// Also redact text matching `pattern`.
+    pub fn with_pattern(mut self, pattern: &str) -> Result<Self, regex::Error> {
+        self.patterns.push(Regex::new(pattern)?);
+        Ok(self)
+    }
+}
+
+impl Redactor for SecretRedactor {
+    fn redact<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mut text = Cow::Borrowed(text);
+        for pattern in &self.patterns {
+            if pattern.is_match(&text) {
+                text = Cow::Owned(pattern.replace_all(&text, REDACTED).into_owned());
+            }
+        }
+        text
+    }
+}
+
+/// Runs redactors one after the other. An empty chain redacts nothing.
+#[derive(Clone, Default)]
+pub struct RedactorChain {
+    redactors: Vec<Arc<dyn Redactor>>,
+}
+
+impl RedactorChain {
+    /// Run `redactor` after the ones already in the chain.
+    pub fn with(mut self, redactor: Arc<dyn Redactor>) -> Self {
+        self.redactors.push(redactor);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.redactors.is_empty()
+    }
+}
+
+impl Redactor for RedactorChain {
+    fn redact<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mut text = Cow::Borrowed(text);
+        for redactor in &self.redactors {
+            if let Cow::Owned(redacted) = redactor.redact(&text) {
+                text = Cow::Owned(redacted);
+            }
+        }
+        text
+    }
+}
+
+/// Redactor that can be replaced while in use.
+pub struct SharedRedactor {
+    inner: RwLock<Arc<dyn Redactor>>,
+}
+
+impl SharedRedactor {
+    pub fn new(redactor: Arc<dyn Redactor>) -> Self {
+        Self {
+            inner: RwLock::new(redactor),
+        }
+    }
+
+    /// Redact with `redactor` from now on.
+    pub fn set(&self, redactor: Arc<dyn Redactor>) {
+        *self.inner.write() = redactor;
+    }
+}
+
+impl Redactor for SharedRedactor {
+    fn redact<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let redactor = self.inner.read().clone();
+        match redactor.redact(text) {
+            Cow::Borrowed(_) => Cow::Borrowed(text),
+            Cow::Owned(redacted) => Cow::Owned(redacted),
+        }
+    }
+}
+
+/// Redact every string of a JSON value in place.
+pub fn redact_json(redactor: &dyn Redactor, value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::String(text) => redact_string(redactor, text),
+        serde_json::Value::Array(items) => items.iter_mut().for_each(|v| redact_json(redactor, v)),
+        serde_json::Value::Object(map) => map.values_mut().for_each(|v| redact_json(redactor, v)),
+        _ => {}
+    }
+}
+
+/// Redact the text, tool calls and tool results of a message in place.
+/// Images are left alone.
+pub fn redact_message(redactor: &dyn Redactor, message: &mut Message) {
+    match &mut message.content {
+        MessageContent::Text(text) => redact_string(redactor, text),
+        MessageContent::Parts(parts) => {
+            for part in parts {
+                match part {
+                    ContentPart::Text { text } => redact_string(redactor, text),
+                    ContentPart::ToolUse { input, .. } => redact_json(redactor, input),
+                    ContentPart::ToolResult { content, .. } => redact_string(redactor, content),
+                    ContentPart::Image { .. } => {}
+                }
+            }
+        }
+    }
+    for call in &mut message.tool_calls {
+        redact_json(redactor, &mut call.arguments);
+    }
+}
+
+fn redact_string(redactor: &dyn Redactor, text: &mut String) {
+    if let Cow::Owned(redacted) = redactor.redact(text) {
+        *text = redacted;
+    }
+}
+
+#[cfg(test)]
+#[path = "redact_tests.rs"]
+mod tests;
@@ -17,4 +17,4 @@ pub use channel::ChannelRegistry;
 pub use extension::ExtensionRegistry;
 pub use memory::MemoryRegistry;
 pub use provider::ProviderRegistry;
-pub use tool::ToolRegistry;
+pub use tool::{ToolRegistry, ToolUsage};
@@ -2,11 +2,17 @@
 
 use std::sync::Arc;
 
-use autohands_protocols::error::ExtensionError;
+use chrono::{DateTime, Utc};
+use dashmap::{DashMap, DashSet};
+use serde::Serialize;
+use tracing::info;
+
+use autohands_protocols::error::{ExtensionError, ToolError};
 use autohands_protocols::extension::ToolRegistryAccess;
 use autohands_protocols::tool::{Tool, ToolDefinition};
 
 use super::base::{BaseRegistry, Registerable};
+use crate::resource_lock::{ResourceGuard, ResourceLocks};
 
 // Implement Registerable for Tool trait objects
 impl Registerable for dyn Tool {
@@ -15,11 +21,27 @@ impl Registerable for dyn Tool {
     }
 }
 
+/// Usage counters for a single tool.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ToolUsage {
+    /// Total number of executions.
+    pub calls: u64,
+    /// Number of executions that returned an error.
+    pub errors: u64,
+    /// Time of the most recent execution.
+    pub last_used: Option<DateTime<Utc>>,
+}
+
 /// Registry for managing tools.
 ///
-/// Built on `BaseRegistry` for consistent behavior.
+/// Built on `BaseRegistry` for consistent behavior. Tools can be disabled
+/// at runtime without unregistering them; disabled tools are hidden from
+/// `get` and `list` so agents can neither see nor call them.
 pub struct ToolRegistry {
     inner: BaseRegistry<dyn Tool>,
+    disabled: DashSet<String>,
+    usage: DashMap<String, ToolUsage>,
+    resource_locks: ResourceLocks,
 }
 
 impl ToolRegistry {
@@ -27,6 +49,9 @@ impl ToolRegistry {
     pub fn new() -> Self {
         Self {
             inner: BaseRegistry::new(),
+            disabled: DashSet::new(),
+            usage: DashMap::new(),
+            resource_locks: ResourceLocks::new(),
         }
     }
 
@@ -40,15 +65,99 @@ impl ToolRegistry {
         self.inner.unregister(id)
     }
 
-    /// Get a tool by ID.
+    /// Get an enabled tool by ID.
     pub fn get(&self, id: &str) -> Option<Arc<dyn Tool>> {
+        if self.disabled.contains(id) {
+            return None;
+        }
         self.inner.get(id)
     }
 
-    /// List all tool definitions.
+    /// List enabled tool definitions.
     pub fn list(&self) -> Vec<ToolDefinition> {
+        self.inner
+            .iter()
+            .filter(|t| !self.disabled.contains(&t.definition().id))
+            .map(|t| t.definition().clone())
+            .collect()
+    }
+
+    /// List every registered tool definition, including disabled ones.
+    pub fn list_all(&self) -> Vec<ToolDefinition> {
         self.inner.iter().map(|t| t.definition().clone()).collect()
     }
+
+    /// Enable or disable a registered tool.
+    pub fn set_enabled(&self, id: &str, enabled: bool) -> Result<(), ExtensionError> {
+        if !self.inner.contains(id) {
+            return Err(ExtensionError::NotFound(id.to_string()));
+        }
+        if enabled {
+            self.disabled.remove(id);
+        } else {
+            self.disabled.insert(id.to_string());
+        }
+        Ok(())
+    }
+
+    /// Check whether a tool is enabled.
+    pub fn is_enabled(&self, id: &str) -> bool {
+        !self.disabled.contains(id)
+    }
+
+    /// Record one execution of a tool.
+    pub fn record_usage(&self, id: &str, success: bool) {
+        let mut usage = self.usage.entry(id.to_string()).or_default();
+        usage.calls += 1;
+        if !success {
+            usage.errors += 1;
+        }
+        usage.last_used = Some(Utc::now());
+    }
+
+    /// Get usage counters for a tool.
+    pub fn usage(&self, id: &str) -> ToolUsage {
+        self.usage.get(id).map(|u| u.clone()).unwrap_or_default()
+    }
+
+    /// Wait for the exclusive resources a call of a tool needs and hold
+    /// them until the guard is dropped.
+    ///
+    /// Fails if one of them is suspended, including while the call waited;
+    /// with [`ToolError::Cancelled`] if it was aborted, so the caller
+    /// cancels its run.
+    pub async fn lock_resources(
+        &self,
+        definition: &ToolDefinition,
+        params: &serde_json::Value,
+    ) -> Result<ResourceGuard, ToolError> {
+        let guard = self
+            .resource_locks
+            .acquire(definition.exclusive_resources_for(params))
+            .await;
+        let suspension = guard
+            .resources()
+            .find_map(|resource| self.resource_locks.suspension(resource));
+        match suspension {
+            Some(suspension) if suspension.aborts_runs => {
+                info!(
+                    "Call cancelled, {} is aborted: {}",
+                    suspension.resource, suspension.reason
+                );
+                Err(ToolError::Cancelled)
+            }
+            Some(suspension) => Err(ToolError::PermissionDenied(format!(
+                "{} is suspended: {}",
+                suspension.resource, suspension.reason
+            ))),
+            None => Ok(guard),
+        }
+    }
+
+    /// Locks of the exclusive resources in use.
+    pub fn resource_locks(&self) -> &ResourceLocks {
+        &self.resource_locks
+    }
 }
 
 impl Default for ToolRegistry {
@@ -65,6 +174,22 @@ impl ToolRegistryAccess for ToolRegistry {
     fn unregister_tool(&self, tool_id: &str) -> Result<(), ExtensionError> {
         self.unregister(tool_id)
     }
+
+    fn suspend_resource(&self, resource: &str, reason: &str) {
+        self.resource_locks.suspend(resource, reason);
+    }
+
+    fn resume_resource(&self, resource: &str) -> bool {
+        self.resource_locks.resume(resource)
+    }
+
+    fn abort_resource(&self, resource: &str, reason: &str) {
+        self.resource_locks.abort(resource, reason);
+    }
+
+    fn is_resource_suspended(&self, resource: &str) -> bool {
+        self.resource_locks.suspension(resource).is_some()
+    }
 }
 
 #[cfg(test)]
@@ -179,6 +304,110 @@ mod tests {
         assert_eq!(list.len(), 2);
     }
 
+    #[test]
+    fn test_disable_tool_hides_it() {
+        let registry = ToolRegistry::new();
+        registry.register(Arc::new(MockTool::new("tool1"))).unwrap();
+        registry.register(Arc::new(MockTool::new("tool2"))).unwrap();
+
+        registry.set_enabled("tool1", false).unwrap();
+        assert!(!registry.is_enabled("tool1"));
+        assert!(registry.get("tool1").is_none());
+        assert_eq!(registry.list().len(), 1);
+        assert_eq!(registry.list_all().len(), 2);
+
+        registry.set_enabled("tool1", true).unwrap();
+        assert!(registry.get("tool1").is_some());
+    }
+
+    #[test]
+    fn test_set_enabled_unknown_tool() {
+        let registry = ToolRegistry::new();
+        assert!(registry.set_enabled("missing", false).is_err());
+    }
+
+    #[test]
+    fn test_record_usage() {
+        let registry = ToolRegistry::new();
+        assert_eq!(registry.usage("tool1").calls, 0);
+
+        registry.record_usage("tool1", true);
+        registry.record_usage("tool1", false);
+
+        let usage = registry.usage("tool1");
+        assert_eq!(usage.calls, 2);
+        assert_eq!(usage.errors, 1);
+        assert!(usage.last_used.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_lock_resources() {
+        let registry = ToolRegistry::new();
+        let definition = ToolDefinition::new("browser_click", "Click", "Click")
+            .with_exclusive_resource("browser-page:{page_id}");
+
+        let guard = registry
+            .lock_resources(&definition, &serde_json::json!({"page_id": "p1"}))
+            .await
+            .unwrap();
+        assert!(registry.resource_locks().is_held("browser-page:p1"));
+        assert!(!registry.resource_locks().is_held("browser-page:p2"));
+        drop(guard);
+        assert!(registry.resource_locks().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_suspended_resource_refuses_calls() {
+        let registry = ToolRegistry::new();
+        let definition = ToolDefinition::new("desktop_mouse_click", "Click", "Click")
+            .with_exclusive_resource("desktop-input");
+        let params = serde_json::json!({});
+
+        registry.suspend_resource("desktop-input", "user input detected");
+        assert!(registry.is_resource_suspended("desktop-input"));
+        let err = registry.lock_resources(&definition, &params).await.err().unwrap();
+        assert!(matches!(err, ToolError::PermissionDenied(_)));
+        assert!(err.to_string().contains("desktop-input is suspended: user input detected"));
+        // The refused call holds nothing
+        assert!(registry.resource_locks().is_empty());
+
+        // Tools without the resource are not affected
+        let other = ToolDefinition::new("read_file", "Read", "Read");
+        assert!(registry.lock_resources(&other, &params).await.is_ok());
+
+        assert!(registry.resume_resource("desktop-input"));
+        assert!(!registry.resume_resource("desktop-input"));
+        assert!(registry.lock_resources(&definition, &params).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_aborted_resource_cancels_calls() {
+        let registry = ToolRegistry::new();
+        let definition = ToolDefinition::new("desktop_mouse_click", "Click", "Click")
+            .with_exclusive_resource("desktop-input");
+        let params = serde_json::json!({});
+
+        let guard = registry.lock_resources(&definition, &params).await.unwrap();
+        registry.abort_resource("desktop-input", "abort corner reached");
+        // The call holding it is revoked
+        tokio::time::timeout(std::time::Duration::from_secs(1), guard.revoked())
+            .await
+            .unwrap();
+        drop(guard);
+
+        assert!(registry.is_resource_suspended("desktop-input"));
+        let err = registry.lock_resources(&definition, &params).await.err().unwrap();
+        assert!(matches!(err, ToolError::Cancelled));
+
+        assert!(registry.resume_resource("desktop-input"));
+        let guard = registry.lock_resources(&definition, &params).await.unwrap();
+        assert!(
+            tokio::time::timeout(std::time::Duration::from_millis(50), guard.revoked())
+                .await
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_tool_registry_access_trait() {
         let registry = ToolRegistry::new();
@@ -0,0 +1,212 @@
+//! Exclusive resources held by tool calls.
+//!
+//! Tools declare the resources they drive (see
+//! `ToolDefinition::exclusive_resources`), such as the desktop's mouse and
+//! keyboard or a browser page. [`ResourceLocks`] makes concurrent calls
+//! needing the same resource take turns, first come first served, so two
+//! sessions cannot move the mouse or drive a page at the same time.
+//!
+//! A resource can also be suspended, e.g. by the desktop interlock when the
+//! user takes the mouse back: calls needing it fail until it is resumed.
+//! Aborting it goes further, cancelling the run of the call holding it and
+//! of every call needing it until then.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde::Serialize;
+use tokio::sync::{watch, Mutex, OwnedMutexGuard};
+use tracing::{debug, info};
+
+/// A suspended resource.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ResourceSuspension {
+    pub resource: String,
+    /// Why it was suspended.
+    pub reason: String,
+    pub since: DateTime<Utc>,
+    /// Whether the runs of calls needing it are cancelled, rather than
+    /// only the calls refused.
+    pub aborts_runs: bool,
+}
+
+/// Locks of the resources in use, by name.
+#[derive(Default)]
+pub struct ResourceLocks {
+    locks: Arc<DashMap<String, Arc<Mutex<()>>>>,
+    suspended: DashMap<String, ResourceSuspension>,
+    /// Revocation of the call holding each resource.
+    holders: Arc<DashMap<String, Arc<watch::Sender<bool>>>>,
+}
+
+impl ResourceLocks {
+    /// Create an empty lock table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wait until every resource is free and hold them until the guard is
+    /// dropped.
+    ///
+    /// Resources are taken in name order, so calls sharing several of them
+    /// cannot deadlock.
+    pub async fn acquire(&self, mut resources: Vec<String>) -> ResourceGuard {
+        resources.sort();
+        resources.dedup();
+        let mut held = Vec::with_capacity(resources.len());
+        for resource in resources {
+            let lock = self.locks.entry(resource.clone()).or_default().clone();
+            let guard = match lock.clone().try_lock_owned() {
+                Ok(guard) => guard,
+                Err(_) => {
+                    debug!("Waiting for exclusive resource {}", resource);
+                    lock.lock_owned().await
+                }
+            };
+            held.push((resource, guard));
+        }
+        let (revoke, revoked) = watch::channel(false);
+        let revoke = Arc::new(revoke);
+        for (resource, _) in &held {
+            self.holders.insert(resource.clone(), revoke.clone());
+        }
+        ResourceGuard {
+            locks: self.locks.clone(),
+            holders: self.holders.clone(),
+            held,
+            revoked,
+        }
+    }
+
+    /// Whether a resource is held.
+    pub fn is_held(&self, resource: &str) -> bool {
+        self.locks
+            .get(resource)
+            .is_some_and(|lock| lock.try_lock().is_err())
+    }
+
+    /// Suspend a resource until [`resume`](Self::resume) is called, and
+    /// return its suspension.
+    ///
+    /// Suspending a suspended resource keeps the first reason.
+    pub fn suspend(&self, resource: &str, reason: &str) -> ResourceSuspension {
+        self.suspended
+            .entry(resource.to_string())
+            .or_insert_with(|| {
+                info!("Suspended exclusive resource {}: {}", resource, reason);
+                ResourceSuspension {
+                    resource: resource.to_string(),
+                    reason: reason.to_string(),
+                    since: Utc::now(),
+                    aborts_runs: false,
+                }
+            })
+            .clone()
+    }
+
+    /// Suspend a resource and revoke it from the call holding it, whose
+    /// run is then cancelled, as are the runs of later calls needing it
+    /// until it is resumed. Returns its suspension.
+    ///
+    /// Aborting a suspended resource keeps the first reason.
+    pub fn abort(&self, resource: &str, reason: &str) -> ResourceSuspension {
+        let suspension = self
+            .suspended
+            .entry(resource.to_string())
+            .and_modify(|suspension| suspension.aborts_runs = true)
+            .or_insert_with(|| ResourceSuspension {
+                resource: resource.to_string(),
+                reason: reason.to_string(),
+                since: Utc::now(),
+                aborts_runs: true,
+            })
+            .clone();
+        info!("Aborted exclusive resource {}: {}", resource, reason);
+        if let Some(holder) = self.holders.get(resource) {
+            holder.send_replace(true);
+        }
+        suspension
+    }
+
+    /// Resume a suspended resource; returns whether it was suspended.
+    pub fn resume(&self, resource: &str) -> bool {
+        let resumed = self.suspended.remove(resource).is_some();
+        if resumed {
+            info!("Resumed exclusive resource {}", resource);
+        }
+        resumed
+    }
+
+    /// The suspension of a resource, if suspended.
+    pub fn suspension(&self, resource: &str) -> Option<ResourceSuspension> {
+        self.suspended.get(resource).map(|s| s.clone())
+    }
+
+    /// Suspended resources, sorted by name.
+    pub fn suspensions(&self) -> Vec<ResourceSuspension> {
+        let mut suspensions: Vec<_> = self.suspended.iter().map(|s| s.clone()).collect();
+        suspensions.sort_by(|a, b| a.resource.cmp(&b.resource));
+        suspensions
+    }
+
+    /// Names of the resources held, sorted.
+    pub fn held(&self) -> Vec<String> {
+        let mut held: Vec<_> = self
+            .locks
+            .iter()
+            .filter(|lock| lock.value().try_lock().is_err())
+            .map(|lock| lock.key().clone())
+            .collect();
+        held.sort();
+        held
+    }
+
+    /// Number of resources held or waited for.
+    pub fn len(&self) -> usize {
+        self.locks.len()
+    }
+
+    /// Whether no resource is held or waited for.
+    pub fn is_empty(&self) -> bool {
+        self.locks.is_empty()
+    }
+}
+
+/// Resources held by a call; released on drop.
+pub struct ResourceGuard {
+    locks: Arc<DashMap<String, Arc<Mutex<()>>>>,
+    holders: Arc<DashMap<String, Arc<watch::Sender<bool>>>>,
+    held: Vec<(String, OwnedMutexGuard<()>)>,
+    revoked: watch::Receiver<bool>,
+}
+
+impl ResourceGuard {
+    /// Names of the held resources.
+    pub fn resources(&self) -> impl Iterator<Item = &str> {
+        self.held.iter().map(|(resource, _)| resource.as_str())
+    }
+
+    /// Wait until one of the held resources is aborted.
+    pub async fn revoked(&self) {
+        let mut revoked = self.revoked.clone();
+        if revoked.wait_for(|revoked| *revoked).await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
+impl Drop for ResourceGuard {
+    fn drop(&mut self) {
+        for (resource, guard) in self.held.drain(..) {
+            self.holders.remove(&resource);
+            drop(guard);
+            // Forget the lock once nobody holds or waits for it
+            self.locks.remove_if(&resource, |_, lock| Arc::strong_count(lock) == 1);
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "resource_lock_tests.rs"]
+mod tests;
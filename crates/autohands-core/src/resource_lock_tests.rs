@@ -0,0 +1,130 @@
+use super::*;
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_calls_on_a_resource_take_turns() {
+    let locks = Arc::new(ResourceLocks::new());
+    let guard = locks.acquire(vec!["desktop-input".to_string()]).await;
+    assert!(locks.is_held("desktop-input"));
+
+    let waiting = {
+        let locks = locks.clone();
+        tokio::spawn(async move { locks.acquire(vec!["desktop-input".to_string()]).await })
+    };
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert!(!waiting.is_finished());
+
+    // Other resources are not blocked
+    let other = locks.acquire(vec!["browser-page:p1".to_string()]).await;
+    drop(other);
+
+    drop(guard);
+    let second = tokio::time::timeout(Duration::from_secs(1), waiting)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(second.resources().collect::<Vec<_>>(), vec!["desktop-input"]);
+    drop(second);
+    assert!(locks.is_empty());
+}
+
+#[tokio::test]
+async fn test_resources_taken_in_order() {
+    let locks = Arc::new(ResourceLocks::new());
+    let guard = locks
+        .acquire(vec!["b".to_string(), "a".to_string(), "b".to_string()])
+        .await;
+    assert_eq!(guard.resources().collect::<Vec<_>>(), vec!["a", "b"]);
+    assert_eq!(locks.len(), 2);
+
+    // A call taking the same resources in the opposite order waits, then runs
+    let waiting = {
+        let locks = locks.clone();
+        tokio::spawn(async move { locks.acquire(vec!["b".to_string(), "a".to_string()]).await })
+    };
+    drop(guard);
+    let guard = tokio::time::timeout(Duration::from_secs(1), waiting)
+        .await
+        .unwrap()
+        .unwrap();
+    drop(guard);
+    assert!(locks.is_empty());
+}
+
+#[tokio::test]
+async fn test_no_resources() {
+    let locks = ResourceLocks::new();
+    let guard = locks.acquire(Vec::new()).await;
+    assert_eq!(guard.resources().count(), 0);
+    assert!(locks.is_empty());
+}
+
+#[tokio::test]
+async fn test_suspend_and_resume() {
+    let locks = ResourceLocks::new();
+    let first = locks.suspend("desktop-input", "user input detected");
+    let again = locks.suspend("desktop-input", "paused from the API");
+    locks.suspend("browser-page:p1", "paused from the API");
+    assert_eq!(again, first);
+
+    let suspension = locks.suspension("desktop-input").unwrap();
+    assert_eq!(suspension, first);
+    assert_eq!(suspension.reason, "user input detected");
+    let suspended: Vec<_> = locks.suspensions().into_iter().map(|s| s.resource).collect();
+    assert_eq!(suspended, vec!["browser-page:p1", "desktop-input"]);
+
+    // Suspension does not take the lock
+    let guard = locks.acquire(vec!["desktop-input".to_string()]).await;
+    assert_eq!(locks.held(), vec!["desktop-input"]);
+    drop(guard);
+    assert!(locks.held().is_empty());
+
+    assert!(locks.resume("desktop-input"));
+    assert!(!locks.resume("desktop-input"));
+    assert!(locks.suspension("desktop-input").is_none());
+}
+
+#[tokio::test]
+async fn test_abort_revokes_the_holder() {
+    let locks = ResourceLocks::new();
+    let guard = locks.acquire(vec!["desktop-input".to_string()]).await;
+    let other = locks.acquire(vec!["browser-page:p1".to_string()]).await;
+
+    locks.abort("desktop-input", "kill switch");
+    tokio::time::timeout(Duration::from_secs(1), guard.revoked())
+        .await
+        .unwrap();
+    // Holders of other resources keep them
+    assert!(
+        tokio::time::timeout(Duration::from_millis(20), other.revoked())
+            .await
+            .is_err()
+    );
+
+    let suspension = locks.suspension("desktop-input").unwrap();
+    assert_eq!(suspension.reason, "kill switch");
+    assert!(suspension.aborts_runs);
+    drop(guard);
+
+    // The next holder is not revoked by the past abort
+    assert!(locks.resume("desktop-input"));
+    let guard = locks.acquire(vec!["desktop-input".to_string()]).await;
+    assert!(
+        tokio::time::timeout(Duration::from_millis(20), guard.revoked())
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_abort_upgrades_a_suspension() {
+    let locks = ResourceLocks::new();
+    locks.suspend("desktop-input", "user input detected");
+    assert!(!locks.suspension("desktop-input").unwrap().aborts_runs);
+
+    let aborted = locks.abort("desktop-input", "kill switch");
+    let suspension = locks.suspension("desktop-input").unwrap();
+    assert_eq!(aborted, suspension);
+    assert_eq!(suspension.reason, "user input detected");
+    assert!(suspension.aborts_runs);
+}
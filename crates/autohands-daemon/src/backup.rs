@@ -0,0 +1,372 @@
+//! Backup and restore of the state directory (`~/.autohands`).
+//!
+//! A backup is a single file holding a tar.gz archive of the state directory
+//! (skills, memories, sessions, jobs, checkpoints, stores) and, optionally,
+//! the configuration file:
+//!
+//! ```text
+//! [AHBK][v1][encrypted flag][salt, nonce if encrypted][tar.gz content]
+//! ```
+//!
+//! Encrypted backups seal the archive with ChaCha20-Poly1305 under a key
+//! derived from a passphrase (see [`autohands_config::envelope`]). Inside the archive, state
+//! files live under `state/` and the configuration file under `config/`.
+
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use autohands_config::envelope::{self, Cipher};
+use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use tar::{Archive, Builder, EntryType};
+use tracing::{debug, info};
+
+use crate::error::DaemonError;
+
+/// Magic bytes of backup files.
+const MAGIC: &[u8; 4] = b"AHBK";
+
+/// Current backup format version.
+const VERSION: u8 = 1;
+
+/// Archive directory of the state files.
+const STATE_PREFIX: &str = "state";
+
+/// Archive directory of the configuration file.
+const CONFIG_PREFIX: &str = "config";
+
+/// Extension of backup files.
+pub const BACKUP_EXTENSION: &str = "backup";
+
+/// State paths never backed up: logs, the PID file and the backups
+/// themselves.
+pub const DEFAULT_EXCLUDES: &[&str] = &["logs", "debug", "backups", "*.pid"];
+
+/// What to back up.
+#[derive(Debug, Clone, Default)]
+pub struct BackupOptions {
+    /// Glob patterns of state paths (relative, `/`-separated) to leave out,
+    /// in addition to [`DEFAULT_EXCLUDES`]. A matching directory is left out
+    /// with everything in it.
+    pub exclude: Vec<String>,
+    /// Encrypt the backup with this passphrase.
+    pub passphrase: Option<String>,
+    /// Configuration file to include.
+    pub config_file: Option<PathBuf>,
+}
+
+/// How to restore a backup.
+#[derive(Debug, Clone, Default)]
+pub struct RestoreOptions {
+    /// Passphrase of an encrypted backup.
+    pub passphrase: Option<String>,
+    /// Where to restore the configuration file, if the backup has one.
+    pub config_file: Option<PathBuf>,
+    /// Replace existing files instead of refusing to restore.
+    pub overwrite: bool,
+}
+
+/// Result of a backup or restore.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupSummary {
+    /// Backup file.
+    pub path: PathBuf,
+    /// Files archived or restored.
+    pub files: usize,
+    /// Size of the files, uncompressed.
+    pub bytes: u64,
+    /// Whether the backup is encrypted.
+    pub encrypted: bool,
+    /// Whether the configuration file was archived or restored.
+    pub config: bool,
+}
+
+/// File name of a backup taken at `at`, e.g. `autohands-20260101-120000.backup`.
+pub fn backup_file_name(at: DateTime<Utc>) -> String {
+    format!("autohands-{}.{}", at.format("%Y%m%d-%H%M%S"), BACKUP_EXTENSION)
+}
+
+/// Back up `state_dir` to the file `output`.
+pub fn create_backup(
+    state_dir: &Path,
+    output: &Path,
+    options: &BackupOptions,
+) -> Result<BackupSummary, DaemonError> {
+    let patterns = exclude_patterns(&options.exclude)?;
+    let mut summary = BackupSummary {
+        path: output.to_path_buf(),
+        files: 0,
+        bytes: 0,
+        encrypted: options.passphrase.is_some(),
+        config: false,
+    };
+
+    let mut archive = Vec::new();
+    {
+        let encoder = GzEncoder::new(&mut archive, Compression::default());
+        let mut tar = Builder::new(encoder);
+        tar.follow_symlinks(false);
+
+        if state_dir.is_dir() {
+            let walker = walkdir::WalkDir::new(state_dir)
+                .min_depth(1)
+                .into_iter()
+                .filter_entry(|entry| {
+                    let relative = entry.path().strip_prefix(state_dir).unwrap_or(entry.path());
+                    !is_excluded(relative, &patterns)
+                });
+            for entry in walker {
+                let entry = entry.map_err(|e| backup_error("read state directory", e))?;
+                if !entry.file_type().is_file() {
+                    continue;
+                }
+                let relative = entry.path().strip_prefix(state_dir).unwrap_or(entry.path());
+                let name = Path::new(STATE_PREFIX).join(relative);
+                tar.append_path_with_name(entry.path(), &name)
+                    .map_err(|e| backup_error(&format!("archive {}", relative.display()), e))?;
+                summary.files += 1;
+                summary.bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+            }
+        }
+
+        if let Some(config_file) = options.config_file.as_deref().filter(|p| p.is_file()) {
+            let file_name = config_file.file_name().unwrap_or_default();
+            tar.append_path_with_name(config_file, Path::new(CONFIG_PREFIX).join(file_name))
+                .map_err(|e| backup_error("archive configuration file", e))?;
+            summary.config = true;
+            summary.bytes += fs::metadata(config_file).map(|m| m.len()).unwrap_or(0);
+        }
+
+        tar.into_inner()
+            .and_then(|encoder| encoder.finish())
+            .map_err(|e| backup_error("finalize archive", e))?;
+    }
+
+    if let Some(parent) = output.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = File::create(output)?;
+    file.write_all(MAGIC)?;
+    file.write_all(&[VERSION])?;
+    match &options.passphrase {
+        Some(passphrase) => {
+            file.write_all(&[1])?;
+            let sealed = envelope::seal(Cipher::ChaCha20Poly1305, passphrase, MAGIC, archive)
+                .map_err(|e| DaemonError::Backup(format!("Failed to encrypt backup: {}", e)))?;
+            file.write_all(&sealed)?;
+        }
+        None => {
+            file.write_all(&[0])?;
+            file.write_all(&archive)?;
+        }
+    }
+    file.sync_all()?;
+
+    info!(
+        "Backed up {} file(s) from {} to {}",
+        summary.files,
+        state_dir.display(),
+        output.display()
+    );
+    Ok(summary)
+}
+
+/// Restore the backup file `path` into `state_dir`.
+///
+/// Unless [`RestoreOptions::overwrite`] is set, nothing is written when any
+/// file of the backup already exists.
+pub fn restore_backup(
+    path: &Path,
+    state_dir: &Path,
+    options: &RestoreOptions,
+) -> Result<BackupSummary, DaemonError> {
+    let (archive, encrypted) = read_backup(path, options.passphrase.as_deref())?;
+    let mut summary = BackupSummary {
+        path: path.to_path_buf(),
+        files: 0,
+        bytes: 0,
+        encrypted,
+        config: false,
+    };
+
+    if !options.overwrite {
+        let mut tar = Archive::new(GzDecoder::new(archive.as_slice()));
+        for entry in tar.entries().map_err(|e| backup_error("read archive", e))? {
+            let entry = entry.map_err(|e| backup_error("read archive", e))?;
+            let name = entry.path().map_err(|e| backup_error("read archive", e))?;
+            if let Some(target) = target_path(&name, state_dir, options.config_file.as_deref()) {
+                if target.exists() {
+                    return Err(DaemonError::Backup(format!(
+                        "{} already exists; restore with overwrite to replace it",
+                        target.display()
+                    )));
+                }
+            }
+        }
+    }
+
+    fs::create_dir_all(state_dir)?;
+    let mut tar = Archive::new(GzDecoder::new(archive.as_slice()));
+    for entry in tar.entries().map_err(|e| backup_error("read archive", e))? {
+        let mut entry = entry.map_err(|e| backup_error("read archive", e))?;
+        if entry.header().entry_type() != EntryType::Regular {
+            continue;
+        }
+        let name = entry.path().map_err(|e| backup_error("read archive", e))?.into_owned();
+        let config = name.starts_with(CONFIG_PREFIX);
+        let (dir, relative) = if config {
+            let Some(config_file) = options.config_file.as_deref() else {
+                debug!("Skipping configuration file {}", name.display());
+                continue;
+            };
+            let Some(dir) = config_file.parent() else {
+                continue;
+            };
+            (dir.to_path_buf(), PathBuf::from(config_file.file_name().unwrap_or_default()))
+        } else {
+            match name.strip_prefix(STATE_PREFIX) {
+                Ok(relative) => (state_dir.to_path_buf(), relative.to_path_buf()),
+                Err(_) => continue,
+            }
+        };
+
+        let target = safe_join(&dir, &relative)?;
+        let mut contents = Vec::new();
+        entry
+            .read_to_end(&mut contents)
+            .map_err(|e| backup_error(&format!("read {}", name.display()), e))?;
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&target, &contents)?;
+
+        summary.bytes += contents.len() as u64;
+        if config {
+            summary.config = true;
+        } else {
+            summary.files += 1;
+        }
+    }
+
+    info!(
+        "Restored {} file(s) from {} to {}",
+        summary.files,
+        path.display(),
+        state_dir.display()
+    );
+    Ok(summary)
+}
+
+/// Delete the oldest backups in `dir` beyond the newest `keep`, returning
+/// how many were deleted.
+pub fn prune_backups(dir: &Path, keep: usize) -> Result<usize, DaemonError> {
+    let mut backups: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.is_file()
+                && path.extension().is_some_and(|ext| ext == BACKUP_EXTENSION)
+                && path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.starts_with("autohands-"))
+        })
+        .collect();
+    // Names embed the timestamp, so they sort oldest first
+    backups.sort();
+    let excess = backups.len().saturating_sub(keep);
+    for path in &backups[..excess] {
+        fs::remove_file(path)?;
+        debug!("Pruned backup {}", path.display());
+    }
+    Ok(excess)
+}
+
+/// Read a backup file, returning its tar.gz archive and whether it was
+/// encrypted.
+fn read_backup(path: &Path, passphrase: Option<&str>) -> Result<(Vec<u8>, bool), DaemonError> {
+    let mut data = Vec::new();
+    File::open(path)?.read_to_end(&mut data)?;
+
+    if data.len() < MAGIC.len() + 2 || &data[..MAGIC.len()] != MAGIC {
+        return Err(DaemonError::Backup(format!("{} is not a backup file", path.display())));
+    }
+    let version = data[MAGIC.len()];
+    if version > VERSION {
+        return Err(DaemonError::Backup(format!(
+            "Unsupported backup version: {} (max supported: {})",
+            version, VERSION
+        )));
+    }
+    let encrypted = data[MAGIC.len() + 1] == 1;
+    let body = data.split_off(MAGIC.len() + 2);
+    if !encrypted {
+        return Ok((body, false));
+    }
+
+    let passphrase = passphrase.ok_or_else(|| {
+        DaemonError::Backup(format!("{} is encrypted; a passphrase is required", path.display()))
+    })?;
+    let archive = envelope::open(Cipher::ChaCha20Poly1305, passphrase, MAGIC, body)
+        .map_err(|e| DaemonError::Backup(format!("Failed to decrypt backup: {}", e)))?;
+    Ok((archive, true))
+}
+
+fn exclude_patterns(exclude: &[String]) -> Result<Vec<glob::Pattern>, DaemonError> {
+    DEFAULT_EXCLUDES
+        .iter()
+        .copied()
+        .chain(exclude.iter().map(String::as_str))
+        .map(|pattern| {
+            glob::Pattern::new(pattern.trim_matches('/')).map_err(|e| {
+                DaemonError::Backup(format!("Invalid exclude pattern '{}': {}", pattern, e))
+            })
+        })
+        .collect()
+}
+
+fn is_excluded(relative: &Path, patterns: &[glob::Pattern]) -> bool {
+    let relative = relative.to_string_lossy().replace('\\', "/");
+    let options = glob::MatchOptions {
+        require_literal_separator: true,
+        ..Default::default()
+    };
+    patterns.iter().any(|p| p.matches_with(&relative, options))
+}
+
+/// Where an archive entry is restored, for conflict checks.
+fn target_path(name: &Path, state_dir: &Path, config_file: Option<&Path>) -> Option<PathBuf> {
+    if name.starts_with(CONFIG_PREFIX) {
+        config_file.map(Path::to_path_buf)
+    } else {
+        let relative = name.strip_prefix(STATE_PREFIX).ok()?;
+        safe_join(state_dir, relative).ok()
+    }
+}
+
+/// Join an archive path to a directory, rejecting paths that escape it.
+fn safe_join(dir: &Path, relative: &Path) -> Result<PathBuf, DaemonError> {
+    use std::path::Component;
+    if relative
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_)))
+    {
+        return Err(DaemonError::Backup(format!(
+            "Refusing to restore {} outside {}",
+            relative.display(),
+            dir.display()
+        )));
+    }
+    Ok(dir.join(relative))
+}
+
+fn backup_error(action: &str, e: impl std::fmt::Display) -> DaemonError {
+    DaemonError::Backup(format!("Failed to {}: {}", action, e))
+}
+
+#[cfg(test)]
+#[path = "backup_tests.rs"]
+mod tests;
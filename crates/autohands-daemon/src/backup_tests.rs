@@ -0,0 +1,152 @@
+use super::*;
+use tempfile::TempDir;
+
+fn state_dir() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path();
+    fs::create_dir_all(root.join("skills/review")).unwrap();
+    fs::create_dir_all(root.join("sessions")).unwrap();
+    fs::create_dir_all(root.join("logs")).unwrap();
+    fs::write(root.join("skills/review/SKILL.md"), "# Review").unwrap();
+    fs::write(root.join("sessions/s1.jsonl"), "{}\n").unwrap();
+    fs::write(root.join("jobs.json"), "[]").unwrap();
+    fs::write(root.join("logs/stdout.log"), "noise").unwrap();
+    fs::write(root.join("autohands.pid"), "42").unwrap();
+    dir
+}
+
+#[test]
+fn test_backup_roundtrip() {
+    let state = state_dir();
+    let out = TempDir::new().unwrap();
+    let config = out.path().join("default.toml");
+    fs::write(&config, "[server]\nport = 8080\n").unwrap();
+    let path = out.path().join(backup_file_name(Utc::now()));
+
+    let options = BackupOptions {
+        config_file: Some(config.clone()),
+        ..Default::default()
+    };
+    let summary = create_backup(state.path(), &path, &options).unwrap();
+    assert_eq!(summary.files, 3);
+    assert!(summary.config);
+    assert!(!summary.encrypted);
+
+    let target = TempDir::new().unwrap();
+    let restored_config = target.path().join("config/default.toml");
+    let restore = RestoreOptions {
+        config_file: Some(restored_config.clone()),
+        ..Default::default()
+    };
+    let summary = restore_backup(&path, &target.path().join("state"), &restore).unwrap();
+    assert_eq!(summary.files, 3);
+    assert!(summary.config);
+
+    let restored = target.path().join("state");
+    assert_eq!(fs::read_to_string(restored.join("skills/review/SKILL.md")).unwrap(), "# Review");
+    assert!(restored.join("jobs.json").exists());
+    assert!(!restored.join("logs").exists());
+    assert!(!restored.join("autohands.pid").exists());
+    assert!(fs::read_to_string(restored_config).unwrap().contains("8080"));
+}
+
+#[test]
+fn test_backup_excludes() {
+    let state = state_dir();
+    let out = TempDir::new().unwrap();
+    let path = out.path().join("b.backup");
+
+    let options = BackupOptions {
+        exclude: vec!["sessions".to_string(), "*.json".to_string()],
+        ..Default::default()
+    };
+    let summary = create_backup(state.path(), &path, &options).unwrap();
+    assert_eq!(summary.files, 1);
+
+    let bad = BackupOptions {
+        exclude: vec!["[".to_string()],
+        ..Default::default()
+    };
+    assert!(matches!(create_backup(state.path(), &path, &bad), Err(DaemonError::Backup(_))));
+}
+
+#[test]
+fn test_encrypted_backup() {
+    let state = state_dir();
+    let out = TempDir::new().unwrap();
+    let path = out.path().join("b.backup");
+
+    let options = BackupOptions {
+        passphrase: Some("correct horse".to_string()),
+        ..Default::default()
+    };
+    assert!(create_backup(state.path(), &path, &options).unwrap().encrypted);
+    // The archive is not readable without the passphrase
+    let raw = fs::read(&path).unwrap();
+    assert!(!raw.windows(8).any(|w| w == b"# Review"));
+
+    let target = TempDir::new().unwrap();
+    let err = restore_backup(&path, target.path(), &RestoreOptions::default()).unwrap_err();
+    assert!(err.to_string().contains("passphrase"));
+
+    let wrong = RestoreOptions {
+        passphrase: Some("wrong".to_string()),
+        ..Default::default()
+    };
+    assert!(restore_backup(&path, target.path(), &wrong).is_err());
+
+    let right = RestoreOptions {
+        passphrase: Some("correct horse".to_string()),
+        ..Default::default()
+    };
+    let summary = restore_backup(&path, target.path(), &right).unwrap();
+    assert!(summary.encrypted);
+    assert_eq!(summary.files, 3);
+}
+
+#[test]
+fn test_restore_refuses_to_overwrite() {
+    let state = state_dir();
+    let out = TempDir::new().unwrap();
+    let path = out.path().join("b.backup");
+    create_backup(state.path(), &path, &BackupOptions::default()).unwrap();
+
+    fs::write(state.path().join("jobs.json"), "[1]").unwrap();
+    let err = restore_backup(&path, state.path(), &RestoreOptions::default()).unwrap_err();
+    assert!(err.to_string().contains("already exists"));
+    assert_eq!(fs::read_to_string(state.path().join("jobs.json")).unwrap(), "[1]");
+
+    let overwrite = RestoreOptions {
+        overwrite: true,
+        ..Default::default()
+    };
+    restore_backup(&path, state.path(), &overwrite).unwrap();
+    assert_eq!(fs::read_to_string(state.path().join("jobs.json")).unwrap(), "[]");
+}
+
+#[test]
+fn test_restore_rejects_other_files() {
+    let out = TempDir::new().unwrap();
+    let path = out.path().join("not-a-backup");
+    fs::write(&path, "hello").unwrap();
+    let err = restore_backup(&path, out.path(), &RestoreOptions::default()).unwrap_err();
+    assert!(err.to_string().contains("not a backup"));
+}
+
+#[test]
+fn test_prune_backups() {
+    let dir = TempDir::new().unwrap();
+    for name in [
+        "autohands-20260101-000000.backup",
+        "autohands-20260102-000000.backup",
+        "autohands-20260103-000000.backup",
+        "notes.txt",
+    ] {
+        fs::write(dir.path().join(name), "x").unwrap();
+    }
+
+    assert_eq!(prune_backups(dir.path(), 2).unwrap(), 1);
+    assert!(!dir.path().join("autohands-20260101-000000.backup").exists());
+    assert!(dir.path().join("autohands-20260103-000000.backup").exists());
+    assert!(dir.path().join("notes.txt").exists());
+}
@@ -54,6 +54,10 @@ pub enum DaemonError {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
+    /// Backup or restore failed.
+    #[error("Backup error: {0}")]
+    Backup(String),
+
     /// Configuration error.
     #[error("Configuration error: {0}")]
     Config(String),
@@ -0,0 +1,285 @@
+//! Garbage collection of orphaned artifacts and temporary files.
+//!
+//! A GC pass looks for leftovers that nothing cleans up on its own:
+//!
+//! - Browser profiles (`browser-profile-*` in the state directory) no Chrome
+//!   holds open and unused for `profile_max_age`. The default
+//!   `browser-profile` is kept.
+//! - Temporary files and shell session directories (`autohands*` in the
+//!   system temp directory) older than `temp_max_age`.
+//! - Session artifacts (`sessions/artifacts/<session id>/`) whose transcript
+//!   is gone, or older than `artifact_retention`.
+//! - Rotated debug logs (`debug/autohands.*.log`) older than
+//!   `log_retention`.
+//!
+//! With `dry_run`, the pass only reports what it would remove.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde::Serialize;
+use tracing::{debug, info, warn};
+
+use crate::pid::PidFile;
+
+/// Prefix of the browser profiles the GC may remove.
+const PROFILE_PREFIX: &str = "browser-profile-";
+
+/// Prefix of the temporary files and directories AutoHands creates.
+const TEMP_PREFIX: &str = "autohands";
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// What a GC pass looks at and how long things are kept.
+#[derive(Debug, Clone)]
+pub struct GcOptions {
+    /// State directory (`~/.autohands`).
+    pub state_dir: PathBuf,
+    /// System temp directory.
+    pub temp_dir: PathBuf,
+    /// Age after which an unused browser profile is removed.
+    pub profile_max_age: Duration,
+    /// Age after which a temporary file or directory is removed.
+    pub temp_max_age: Duration,
+    /// Age after which a session's artifacts are removed.
+    pub artifact_retention: Duration,
+    /// Age after which a rotated debug log is removed.
+    pub log_retention: Duration,
+    /// Report what would be removed without removing it.
+    pub dry_run: bool,
+}
+
+impl GcOptions {
+    /// Default retention for a state directory.
+    pub fn new(state_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            state_dir: state_dir.into(),
+            temp_dir: std::env::temp_dir(),
+            profile_max_age: 7 * DAY,
+            temp_max_age: DAY,
+            artifact_retention: 30 * DAY,
+            log_retention: 14 * DAY,
+            dry_run: false,
+        }
+    }
+}
+
+/// Kind of a collected item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GcKind {
+    /// Unused browser profile.
+    BrowserProfile,
+    /// Stale temporary file or shell session directory.
+    TempFile,
+    /// Orphaned or expired session artifacts.
+    Artifact,
+    /// Rotated debug log.
+    DebugLog,
+}
+
+/// An item removed (or, in a dry run, to be removed).
+#[derive(Debug, Clone, Serialize)]
+pub struct GcItem {
+    /// What the item is.
+    pub kind: GcKind,
+    /// Where it is.
+    pub path: PathBuf,
+    /// Its size on disk.
+    pub bytes: u64,
+    /// Why it is collected.
+    pub reason: String,
+}
+
+/// Outcome of a GC pass.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GcReport {
+    /// Whether nothing was removed.
+    pub dry_run: bool,
+    /// Items removed, or to be removed in a dry run.
+    pub items: Vec<GcItem>,
+    /// Bytes freed, or to be freed in a dry run.
+    pub bytes: u64,
+    /// Items that could not be removed.
+    pub errors: Vec<String>,
+}
+
+/// Run a GC pass.
+pub fn collect_garbage(options: &GcOptions) -> GcReport {
+    let now = SystemTime::now();
+    let mut candidates = Vec::new();
+    candidates.extend(browser_profiles(options, now));
+    candidates.extend(temp_files(options, now));
+    candidates.extend(artifacts(options, now));
+    candidates.extend(debug_logs(options, now));
+
+    let mut report = GcReport {
+        dry_run: options.dry_run,
+        ..Default::default()
+    };
+    for item in candidates {
+        if !options.dry_run {
+            let result = if item.path.is_dir() {
+                fs::remove_dir_all(&item.path)
+            } else {
+                fs::remove_file(&item.path)
+            };
+            if let Err(e) = result {
+                warn!("GC failed to remove {}: {}", item.path.display(), e);
+                report.errors.push(format!("{}: {}", item.path.display(), e));
+                continue;
+            }
+            debug!("GC removed {} ({})", item.path.display(), item.reason);
+        }
+        report.bytes += item.bytes;
+        report.items.push(item);
+    }
+    if !options.dry_run && !report.items.is_empty() {
+        info!("GC removed {} item(s), {} bytes", report.items.len(), report.bytes);
+    }
+    report
+}
+
+/// Browser profiles no running Chrome holds and unused for long enough.
+fn browser_profiles(options: &GcOptions, now: SystemTime) -> Vec<GcItem> {
+    entries(&options.state_dir)
+        .filter(|path| path.is_dir() && file_name(path).starts_with(PROFILE_PREFIX))
+        .filter(|path| !profile_in_use(path))
+        .filter_map(|path| {
+            let age = age(&path, now)?;
+            (age >= options.profile_max_age).then(|| GcItem {
+                kind: GcKind::BrowserProfile,
+                bytes: size(&path),
+                reason: format!("unused for {}", days(age)),
+                path,
+            })
+        })
+        .collect()
+}
+
+/// Whether a running Chrome holds a profile.
+///
+/// Chrome links `SingletonLock` to `<hostname>-<pid>` while it runs.
+fn profile_in_use(profile: &Path) -> bool {
+    let Ok(target) = fs::read_link(profile.join("SingletonLock")) else {
+        return false;
+    };
+    target
+        .to_string_lossy()
+        .rsplit('-')
+        .next()
+        .and_then(|pid| pid.parse().ok())
+        .is_some_and(PidFile::is_process_running)
+}
+
+/// Stale temporary files and shell session directories.
+fn temp_files(options: &GcOptions, now: SystemTime) -> Vec<GcItem> {
+    entries(&options.temp_dir)
+        .filter(|path| file_name(path).starts_with(TEMP_PREFIX))
+        .filter_map(|path| {
+            let age = age(&path, now)?;
+            (age >= options.temp_max_age).then(|| GcItem {
+                kind: GcKind::TempFile,
+                bytes: size(&path),
+                reason: format!("untouched for {}", days(age)),
+                path,
+            })
+        })
+        .collect()
+}
+
+/// Artifacts of deleted sessions, and artifacts past retention.
+fn artifacts(options: &GcOptions, now: SystemTime) -> Vec<GcItem> {
+    let sessions = options.state_dir.join("sessions");
+    entries(&sessions.join("artifacts"))
+        .filter(|path| path.is_dir())
+        .filter_map(|path| {
+            let transcript = sessions.join(format!("{}.jsonl", file_name(&path)));
+            let reason = if !transcript.exists() {
+                "session no longer exists".to_string()
+            } else {
+                let age = age(&path, now)?;
+                if age < options.artifact_retention {
+                    return None;
+                }
+                format!("older than {}", days(options.artifact_retention))
+            };
+            Some(GcItem {
+                kind: GcKind::Artifact,
+                bytes: size(&path),
+                reason,
+                path,
+            })
+        })
+        .collect()
+}
+
+/// Rotated debug logs past retention.
+fn debug_logs(options: &GcOptions, now: SystemTime) -> Vec<GcItem> {
+    entries(&options.state_dir.join("debug"))
+        .filter(|path| {
+            let name = file_name(path);
+            path.is_file() && name.starts_with("autohands.") && name.ends_with(".log")
+        })
+        .filter_map(|path| {
+            let age = age(&path, now)?;
+            (age >= options.log_retention).then(|| GcItem {
+                kind: GcKind::DebugLog,
+                bytes: size(&path),
+                reason: format!("older than {}", days(options.log_retention)),
+                path,
+            })
+        })
+        .collect()
+}
+
+/// Entries of a directory; none when it cannot be read.
+fn entries(dir: &Path) -> impl Iterator<Item = PathBuf> {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Time since a path, or anything directly in it, was last modified.
+fn age(path: &Path, now: SystemTime) -> Option<Duration> {
+    let modified = |path: &Path| fs::symlink_metadata(path).and_then(|m| m.modified()).ok();
+    let mut latest = modified(path)?;
+    if path.is_dir() {
+        for entry in entries(path) {
+            latest = latest.max(modified(&entry).unwrap_or(latest));
+        }
+    }
+    Some(now.duration_since(latest).unwrap_or_default())
+}
+
+/// Size of a file, or of everything under a directory.
+fn size(path: &Path) -> u64 {
+    walkdir::WalkDir::new(path)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Human-readable whole days.
+fn days(duration: Duration) -> String {
+    match duration.as_secs() / DAY.as_secs() {
+        1 => "1 day".to_string(),
+        n => format!("{} days", n),
+    }
+}
+
+#[cfg(test)]
+#[path = "gc_tests.rs"]
+mod tests;
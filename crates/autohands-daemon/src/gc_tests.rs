@@ -0,0 +1,138 @@
+use super::*;
+use tempfile::TempDir;
+
+/// Set a path's modification time `days` in the past.
+fn backdate(path: &Path, days: u64) {
+    let time = SystemTime::now() - DAY * days as u32;
+    fs::File::open(path).unwrap().set_modified(time).unwrap();
+}
+
+fn options(state: &TempDir, temp: &TempDir) -> GcOptions {
+    GcOptions {
+        temp_dir: temp.path().to_path_buf(),
+        ..GcOptions::new(state.path())
+    }
+}
+
+fn state_dir() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path();
+    for sub in ["debug", "sessions/artifacts/live", "sessions/artifacts/gone", "sessions/artifacts/old"] {
+        fs::create_dir_all(root.join(sub)).unwrap();
+    }
+    fs::write(root.join("sessions/live.jsonl"), "{}\n").unwrap();
+    fs::write(root.join("sessions/old.jsonl"), "{}\n").unwrap();
+    fs::write(root.join("sessions/artifacts/gone/out.txt"), "12345").unwrap();
+    fs::write(root.join("sessions/artifacts/old/out.txt"), "x").unwrap();
+    backdate(&root.join("sessions/artifacts/old/out.txt"), 40);
+    backdate(&root.join("sessions/artifacts/old"), 40);
+
+    fs::write(root.join("debug/autohands.2026-01-01.log"), "old").unwrap();
+    backdate(&root.join("debug/autohands.2026-01-01.log"), 20);
+    fs::write(root.join("debug/autohands.2026-10-15.log"), "recent").unwrap();
+    dir
+}
+
+fn paths(report: &GcReport, kind: GcKind) -> Vec<String> {
+    let mut names: Vec<String> = report
+        .items
+        .iter()
+        .filter(|item| item.kind == kind)
+        .map(|item| file_name(&item.path))
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_dry_run_reports_without_removing() {
+    let state = state_dir();
+    let temp = TempDir::new().unwrap();
+    let options = GcOptions {
+        dry_run: true,
+        ..options(&state, &temp)
+    };
+
+    let report = collect_garbage(&options);
+    assert!(report.dry_run);
+    assert_eq!(paths(&report, GcKind::Artifact), vec!["gone", "old"]);
+    assert_eq!(paths(&report, GcKind::DebugLog), vec!["autohands.2026-01-01.log"]);
+    assert_eq!(report.bytes, 5 + 1 + 3);
+    assert!(state.path().join("sessions/artifacts/gone").exists());
+    assert!(state.path().join("debug/autohands.2026-01-01.log").exists());
+}
+
+#[test]
+fn test_gc_removes_orphans_and_expired() {
+    let state = state_dir();
+    let temp = TempDir::new().unwrap();
+
+    let report = collect_garbage(&options(&state, &temp));
+    assert!(!report.dry_run);
+    assert_eq!(report.items.len(), 3);
+    assert!(report.errors.is_empty());
+
+    let root = state.path();
+    assert!(!root.join("sessions/artifacts/gone").exists());
+    assert!(!root.join("sessions/artifacts/old").exists());
+    assert!(root.join("sessions/artifacts/live").exists());
+    assert!(!root.join("debug/autohands.2026-01-01.log").exists());
+    assert!(root.join("debug/autohands.2026-10-15.log").exists());
+
+    // Nothing left to collect
+    assert!(collect_garbage(&options(&state, &temp)).items.is_empty());
+}
+
+#[test]
+fn test_gc_temp_files() {
+    let state = TempDir::new().unwrap();
+    let temp = TempDir::new().unwrap();
+    let shell_dir = temp.path().join("autohands-shell-1234");
+    fs::create_dir_all(&shell_dir).unwrap();
+    fs::write(shell_dir.join("history"), "ls").unwrap();
+    backdate(&shell_dir.join("history"), 3);
+    backdate(&shell_dir, 3);
+    fs::write(temp.path().join("autohands_playwright_bridge.js"), "//").unwrap();
+    fs::write(temp.path().join("other-tool.tmp"), "").unwrap();
+    backdate(&temp.path().join("other-tool.tmp"), 3);
+
+    let report = collect_garbage(&options(&state, &temp));
+    assert_eq!(paths(&report, GcKind::TempFile), vec!["autohands-shell-1234"]);
+    assert!(!shell_dir.exists());
+    assert!(temp.path().join("autohands_playwright_bridge.js").exists());
+    assert!(temp.path().join("other-tool.tmp").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_gc_browser_profiles() {
+    let state = TempDir::new().unwrap();
+    let temp = TempDir::new().unwrap();
+    let root = state.path();
+    for name in ["browser-profile", "browser-profile-old", "browser-profile-live", "browser-profile-new"] {
+        fs::create_dir_all(root.join(name)).unwrap();
+    }
+    // Held by a running Chrome (this process stands in for it)
+    std::os::unix::fs::symlink(
+        format!("host-{}", std::process::id()),
+        root.join("browser-profile-live/SingletonLock"),
+    )
+    .unwrap();
+    for name in ["browser-profile", "browser-profile-old"] {
+        backdate(&root.join(name), 10);
+    }
+
+    let report = collect_garbage(&options(&state, &temp));
+    assert_eq!(paths(&report, GcKind::BrowserProfile), vec!["browser-profile-old"]);
+    assert!(root.join("browser-profile").exists());
+    assert!(root.join("browser-profile-new").exists());
+
+    // Profiles in use are kept whatever their age
+    let options = GcOptions {
+        profile_max_age: Duration::ZERO,
+        ..options(&state, &temp)
+    };
+    let report = collect_garbage(&options);
+    assert_eq!(paths(&report, GcKind::BrowserProfile), vec!["browser-profile-new"]);
+    assert!(root.join("browser-profile-live").exists());
+}
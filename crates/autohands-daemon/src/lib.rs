@@ -11,6 +11,8 @@
 //! - Auto-restart on crash
 //! - macOS LaunchAgent integration
 //! - Linux Systemd integration
+//! - Backup and restore of the state directory
+//! - Garbage collection of orphaned artifacts and temp files
 //!
 //! ## Usage
 //!
@@ -44,11 +46,13 @@
 //! service.install()?;
 //! ```
 
+pub mod backup;
 pub mod config;
 pub mod daemon;
 pub mod daemon_impl;
 pub mod daemon_status;
 pub mod error;
+pub mod gc;
 pub mod health;
 pub mod pid;
 pub mod runloop;
@@ -62,10 +66,12 @@ pub mod launchd;
 pub mod systemd;
 
 // Re-exports
+pub use backup::{BackupOptions, BackupSummary, RestoreOptions};
 pub use config::DaemonConfig;
 pub use daemon::{Daemon, DaemonState};
 pub use daemon_status::DaemonStatus;
 pub use error::DaemonError;
+pub use gc::{GcItem, GcKind, GcOptions, GcReport};
 pub use health::HealthChecker;
 pub use pid::PidFile;
 pub use runloop::{RunLoopDaemonBuilder, RunLoopRunner};
@@ -0,0 +1,186 @@
+//! Live statistics for the web dashboard.
+//!
+//! Unlike [`MetricsRegistry`](crate::metrics::MetricsRegistry), which exports
+//! flat Prometheus series, this keeps the handful of aggregates the dashboard
+//! page shows: token spend for the current day, the most recent failures and
+//! per-provider latencies.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+/// Default number of failures kept for display.
+pub const DEFAULT_FAILURE_CAPACITY: usize = 20;
+
+/// A recorded failure.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailureRecord {
+    /// Where the failure happened (event type, provider, ...).
+    pub source: String,
+    /// Error message.
+    pub message: String,
+    /// When it happened.
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Latency statistics for one provider.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProviderLatency {
+    /// Provider ID.
+    pub provider: String,
+    /// Number of completed calls.
+    pub calls: u64,
+    /// Number of failed calls.
+    pub errors: u64,
+    /// Average latency in milliseconds.
+    pub avg_ms: f64,
+    /// Latency of the most recent call in milliseconds.
+    pub last_ms: u64,
+    /// Slowest call in milliseconds.
+    pub max_ms: u64,
+}
+
+/// Token usage for a single day.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenSpend {
+    /// Day the counters belong to (UTC).
+    pub date: NaiveDate,
+    /// Prompt tokens.
+    pub prompt_tokens: u64,
+    /// Completion tokens.
+    pub completion_tokens: u64,
+    /// Total tokens.
+    pub total_tokens: u64,
+}
+
+impl TokenSpend {
+    fn new(date: NaiveDate) -> Self {
+        Self {
+            date,
+            prompt_tokens: 0,
+            completion_tokens: 0,
+            total_tokens: 0,
+        }
+    }
+}
+
+/// Point-in-time copy of the dashboard statistics.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DashboardSnapshot {
+    /// Tokens spent today.
+    pub tokens_today: TokenSpend,
+    /// Most recent failures, newest first.
+    pub recent_failures: Vec<FailureRecord>,
+    /// Per-provider latencies, sorted by provider ID.
+    pub providers: Vec<ProviderLatency>,
+}
+
+struct Inner {
+    tokens: TokenSpend,
+    failures: VecDeque<FailureRecord>,
+    providers: HashMap<String, ProviderLatency>,
+}
+
+/// Collector for dashboard statistics.
+pub struct DashboardStats {
+    inner: Mutex<Inner>,
+    failure_capacity: usize,
+}
+
+impl DashboardStats {
+    /// Create a collector keeping [`DEFAULT_FAILURE_CAPACITY`] failures.
+    pub fn new() -> Self {
+        Self::with_failure_capacity(DEFAULT_FAILURE_CAPACITY)
+    }
+
+    /// Create a collector keeping at most `capacity` failures.
+    pub fn with_failure_capacity(capacity: usize) -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                tokens: TokenSpend::new(Utc::now().date_naive()),
+                failures: VecDeque::with_capacity(capacity),
+                providers: HashMap::new(),
+            }),
+            failure_capacity: capacity,
+        }
+    }
+
+    /// Add token usage. Counters reset when the UTC day changes.
+    pub fn record_tokens(&self, prompt_tokens: u32, completion_tokens: u32) {
+        let mut inner = self.inner.lock();
+        let today = Utc::now().date_naive();
+        if inner.tokens.date != today {
+            inner.tokens = TokenSpend::new(today);
+        }
+        inner.tokens.prompt_tokens += u64::from(prompt_tokens);
+        inner.tokens.completion_tokens += u64::from(completion_tokens);
+        inner.tokens.total_tokens += u64::from(prompt_tokens) + u64::from(completion_tokens);
+    }
+
+    /// Record a failure, evicting the oldest one when full.
+    pub fn record_failure(&self, source: impl Into<String>, message: impl Into<String>) {
+        if self.failure_capacity == 0 {
+            return;
+        }
+        let mut inner = self.inner.lock();
+        if inner.failures.len() == self.failure_capacity {
+            inner.failures.pop_back();
+        }
+        inner.failures.push_front(FailureRecord {
+            source: source.into(),
+            message: message.into(),
+            timestamp: Utc::now(),
+        });
+    }
+
+    /// Record the duration of a provider call.
+    pub fn record_provider_latency(&self, provider: &str, elapsed: Duration, success: bool) {
+        let ms = elapsed.as_millis() as u64;
+        let mut inner = self.inner.lock();
+        let entry = inner
+            .providers
+            .entry(provider.to_string())
+            .or_insert_with(|| ProviderLatency {
+                provider: provider.to_string(),
+                ..Default::default()
+            });
+        entry.avg_ms = (entry.avg_ms * entry.calls as f64 + ms as f64) / (entry.calls + 1) as f64;
+        entry.calls += 1;
+        if !success {
+            entry.errors += 1;
+        }
+        entry.last_ms = ms;
+        entry.max_ms = entry.max_ms.max(ms);
+    }
+
+    /// Take a snapshot of the current statistics.
+    pub fn snapshot(&self) -> DashboardSnapshot {
+        let inner = self.inner.lock();
+        let today = Utc::now().date_naive();
+        let tokens_today = if inner.tokens.date == today {
+            inner.tokens.clone()
+        } else {
+            TokenSpend::new(today)
+        };
+        let mut providers: Vec<_> = inner.providers.values().cloned().collect();
+        providers.sort_by(|a, b| a.provider.cmp(&b.provider));
+        DashboardSnapshot {
+            tokens_today,
+            recent_failures: inner.failures.iter().cloned().collect(),
+            providers,
+        }
+    }
+}
+
+impl Default for DashboardStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+#[path = "dashboard_tests.rs"]
+mod tests;
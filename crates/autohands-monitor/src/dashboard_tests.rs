@@ -0,0 +1,63 @@
+use super::*;
+
+#[test]
+fn test_record_tokens() {
+    let stats = DashboardStats::new();
+    stats.record_tokens(100, 20);
+    stats.record_tokens(50, 5);
+
+    let snapshot = stats.snapshot();
+    assert_eq!(snapshot.tokens_today.date, Utc::now().date_naive());
+    assert_eq!(snapshot.tokens_today.prompt_tokens, 150);
+    assert_eq!(snapshot.tokens_today.completion_tokens, 25);
+    assert_eq!(snapshot.tokens_today.total_tokens, 175);
+}
+
+#[test]
+fn test_failures_newest_first_and_capped() {
+    let stats = DashboardStats::with_failure_capacity(2);
+    stats.record_failure("agent", "first");
+    stats.record_failure("agent", "second");
+    stats.record_failure("provider", "third");
+
+    let failures = stats.snapshot().recent_failures;
+    assert_eq!(failures.len(), 2);
+    assert_eq!(failures[0].message, "third");
+    assert_eq!(failures[0].source, "provider");
+    assert_eq!(failures[1].message, "second");
+}
+
+#[test]
+fn test_zero_failure_capacity() {
+    let stats = DashboardStats::with_failure_capacity(0);
+    stats.record_failure("agent", "ignored");
+    assert!(stats.snapshot().recent_failures.is_empty());
+}
+
+#[test]
+fn test_provider_latency() {
+    let stats = DashboardStats::new();
+    stats.record_provider_latency("openai", Duration::from_millis(100), true);
+    stats.record_provider_latency("openai", Duration::from_millis(300), false);
+    stats.record_provider_latency("anthropic", Duration::from_millis(50), true);
+
+    let providers = stats.snapshot().providers;
+    assert_eq!(providers.len(), 2);
+    assert_eq!(providers[0].provider, "anthropic");
+
+    let openai = &providers[1];
+    assert_eq!(openai.calls, 2);
+    assert_eq!(openai.errors, 1);
+    assert_eq!(openai.last_ms, 300);
+    assert_eq!(openai.max_ms, 300);
+    assert!((openai.avg_ms - 200.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn test_snapshot_serializes() {
+    let stats = DashboardStats::new();
+    stats.record_tokens(1, 1);
+    let json = serde_json::to_value(stats.snapshot()).unwrap();
+    assert_eq!(json["tokens_today"]["total_tokens"], 2);
+    assert!(json["recent_failures"].is_array());
+}
@@ -7,6 +7,7 @@
 //! - Health check endpoint (/health)
 //! - Prometheus format metrics (/metrics)
 //! - Alert notifications (email/Slack/Telegram)
+//! - Dashboard statistics (token spend, failures, provider latency)
 
 pub mod config;
 pub mod error;
@@ -15,6 +16,7 @@ pub mod metrics;
 pub mod alerts;
 pub mod alert_channels;
 pub mod alert_manager;
+pub mod dashboard;
 
 pub use config::MonitorConfig;
 pub use error::MonitorError;
@@ -25,3 +27,4 @@ pub use alerts::{
 };
 pub use alert_channels::{EmailChannel, SlackChannel, TelegramChannel};
 pub use alert_manager::AlertManager;
+pub use dashboard::{DashboardSnapshot, DashboardStats, FailureRecord, ProviderLatency, TokenSpend};
@@ -3,11 +3,13 @@
 //! Agents are the core execution units that process user requests.
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
 use crate::error::AgentError;
+use crate::provider::{ModelDefinition, ProviderCapabilities};
 use crate::types::{Message, Metadata};
 
 /// Core trait for agents.
@@ -31,6 +33,19 @@ pub trait Agent: Send + Sync {
         let _ = message;
         true
     }
+
+    /// Check that a run's overrides can be honored. Agents that do not
+    /// apply overrides reject all but empty ones.
+    fn check_overrides(&self, overrides: &RunOverrides) -> Result<(), AgentError> {
+        if overrides.is_empty() {
+            Ok(())
+        } else {
+            Err(AgentError::InvalidOverride(format!(
+                "agent '{}' does not support per-run overrides",
+                self.id()
+            )))
+        }
+    }
 }
 
 /// Agent configuration.
@@ -75,6 +90,10 @@ pub struct AgentConfig {
     /// 工具输出最大字符数，超出则截断。0 表示不限制。
     #[serde(default = "default_max_tool_output_chars")]
     pub max_tool_output_chars: usize,
+
+    /// Paths the agent's tools may use besides the working directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox: Option<crate::tool::SandboxPolicy>,
 }
 
 fn default_max_turns() -> u32 {
@@ -107,6 +126,7 @@ impl AgentConfig {
             skills: Vec::new(),
             metadata: HashMap::new(),
             max_tool_output_chars: default_max_tool_output_chars(),
+            sandbox: None,
         }
     }
 
@@ -119,6 +139,162 @@ impl AgentConfig {
         self.tools = tools;
         self
     }
+
+    pub fn with_sandbox(mut self, sandbox: crate::tool::SandboxPolicy) -> Self {
+        self.sandbox = Some(sandbox);
+        self
+    }
+}
+
+/// Per-run overrides of an agent's model and generation parameters.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RunOverrides {
+    /// Model to use instead of the agent's default model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+
+    /// Sampling temperature (0.0 - 2.0).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+
+    /// Maximum output tokens per model call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+
+    /// Tool IDs the run may use, a subset of the agent's tools.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<String>>,
+}
+
+impl RunOverrides {
+    /// Whether nothing is overridden.
+    pub fn is_empty(&self) -> bool {
+        self.model.is_none()
+            && self.temperature.is_none()
+            && self.max_tokens.is_none()
+            && self.tools.is_none()
+    }
+
+    /// Model the run uses.
+    pub fn model_or<'a>(&'a self, default_model: &'a str) -> &'a str {
+        self.model.as_deref().unwrap_or(default_model)
+    }
+
+    /// Whether the run may use a tool.
+    pub fn allows_tool(&self, tool_id: &str) -> bool {
+        self.tools.as_ref().is_none_or(|tools| tools.iter().any(|t| t == tool_id))
+    }
+
+    /// Validate the overrides against a provider's models and capabilities.
+    ///
+    /// An unlisted model is rejected unless the provider lists none; the
+    /// output token limit is checked against the model the run uses.
+    pub fn validate(
+        &self,
+        default_model: &str,
+        models: &[ModelDefinition],
+        capabilities: &ProviderCapabilities,
+    ) -> Result<(), AgentError> {
+        let invalid = |msg: String| Err(AgentError::InvalidOverride(msg));
+        let model_id = self.model_or(default_model);
+        let model = models.iter().find(|m| m.id == model_id);
+        if self.model.is_some() && model.is_none() && !models.is_empty() {
+            return invalid(format!("unknown model '{}'", model_id));
+        }
+        if let Some(temperature) = self.temperature {
+            if !(0.0..=2.0).contains(&temperature) {
+                return invalid(format!("temperature {} is outside 0.0 - 2.0", temperature));
+            }
+        }
+        if let Some(max_tokens) = self.max_tokens {
+            if max_tokens == 0 {
+                return invalid("max_tokens must be positive".to_string());
+            }
+            if let Some(model) = model.filter(|m| max_tokens > m.max_output_tokens) {
+                return invalid(format!(
+                    "max_tokens {} exceeds the {} output tokens of model '{}'",
+                    max_tokens, model.max_output_tokens, model.id
+                ));
+            }
+        }
+        if self.tools.as_ref().is_some_and(|tools| !tools.is_empty())
+            && (!capabilities.tool_calling || model.is_some_and(|m| !m.supports_tools))
+        {
+            return invalid(format!("model '{}' does not support tools", model_id));
+        }
+        Ok(())
+    }
+}
+
+/// Priority names, lowest first, as used in task payloads.
+pub const PRIORITIES: [&str; 5] = ["low", "normal", "high", "critical", "system"];
+
+/// Priority and deadline a run inherits from the task that started it.
+///
+/// Tools hand it on to the sub-agents and tasks they spawn, so work done on
+/// behalf of an urgent task is not queued behind background work.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RunScheduling {
+    /// Priority name, one of [`PRIORITIES`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+
+    /// Time by which the work must be done.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline: Option<DateTime<Utc>>,
+}
+
+impl RunScheduling {
+    /// Whether neither priority nor deadline is set.
+    pub fn is_empty(&self) -> bool {
+        self.priority.is_none() && self.deadline.is_none()
+    }
+
+    /// Scheduling given by a task payload's `priority` and `deadline`.
+    pub fn from_payload(payload: &serde_json::Value) -> Self {
+        Self {
+            priority: payload
+                .get("priority")
+                .and_then(|v| v.as_str())
+                .filter(|p| PRIORITIES.contains(p))
+                .map(str::to_string),
+            deadline: payload
+                .get("deadline")
+                .and_then(|v| serde_json::from_value(v.clone()).ok()),
+        }
+    }
+
+    /// Write the priority and deadline into a task payload.
+    ///
+    /// A higher priority or earlier deadline already in the payload is kept.
+    pub fn apply_to(&self, payload: &mut serde_json::Value) {
+        let current = Self::from_payload(payload);
+        let Some(object) = payload.as_object_mut() else {
+            return;
+        };
+        if let Some(priority) = &self.priority {
+            if rank(current.priority.as_deref()) < rank(Some(priority)) {
+                object.insert("priority".to_string(), serde_json::json!(priority));
+            }
+        }
+        if let Some(deadline) = self.deadline {
+            if current.deadline.is_none_or(|current| deadline < current) {
+                object.insert("deadline".to_string(), serde_json::json!(deadline));
+            }
+        }
+    }
+
+    /// Time left until the deadline, zero once it has passed.
+    pub fn time_left(&self) -> Option<std::time::Duration> {
+        self.deadline
+            .map(|deadline| (deadline - Utc::now()).to_std().unwrap_or_default())
+    }
+}
+
+/// Position of a priority name in [`PRIORITIES`]; unset counts as normal.
+fn rank(priority: Option<&str>) -> usize {
+    let priority = priority.unwrap_or("normal");
+    PRIORITIES.iter().position(|p| *p == priority).unwrap_or(1)
 }
 
 /// Context for agent execution.
@@ -138,6 +314,23 @@ pub struct AgentContext {
 
     /// Working directory for tool execution. Falls back to `current_dir()` if `None`.
     pub work_dir: Option<PathBuf>,
+
+    /// Correlation ID of the work this run belongs to, carried into tool
+    /// contexts, provider requests and tracing spans.
+    pub correlation_id: Option<String>,
+
+    /// Overrides of the agent's model and generation parameters.
+    pub overrides: RunOverrides,
+
+    /// Sandbox the run's tool calls are confined to.
+    pub sandbox: Option<std::sync::Arc<crate::tool::SandboxPolicy>>,
+
+    /// Priority and deadline inherited from the task that started the run.
+    pub scheduling: RunScheduling,
+
+    /// Whether tools with side effects are only simulated, so a run can be
+    /// previewed before it is allowed to change anything.
+    pub dry_run: bool,
 }
 
 impl AgentContext {
@@ -148,6 +341,11 @@ impl AgentContext {
             abort_signal: std::sync::Arc::new(crate::tool::AbortSignal::new()),
             data: HashMap::new(),
             work_dir: None,
+            correlation_id: None,
+            overrides: RunOverrides::default(),
+            sandbox: None,
+            scheduling: RunScheduling::default(),
+            dry_run: false,
         }
     }
 
@@ -160,6 +358,31 @@ impl AgentContext {
         self.history = history;
         self
     }
+
+    pub fn with_correlation_id(mut self, correlation_id: impl Into<String>) -> Self {
+        self.correlation_id = Some(correlation_id.into());
+        self
+    }
+
+    pub fn with_overrides(mut self, overrides: RunOverrides) -> Self {
+        self.overrides = overrides;
+        self
+    }
+
+    pub fn with_sandbox(mut self, sandbox: std::sync::Arc<crate::tool::SandboxPolicy>) -> Self {
+        self.sandbox = Some(sandbox);
+        self
+    }
+
+    pub fn with_scheduling(mut self, scheduling: RunScheduling) -> Self {
+        self.scheduling = scheduling;
+        self
+    }
+
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
 }
 
 /// Response from an agent.
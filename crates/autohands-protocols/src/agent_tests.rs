@@ -77,6 +77,14 @@ fn test_agent_context_with_history() {
     assert_eq!(ctx.history.len(), 2);
 }
 
+#[test]
+fn test_agent_context_with_correlation_id() {
+    let ctx = AgentContext::new("session-123");
+    assert!(ctx.correlation_id.is_none());
+    let ctx = ctx.with_correlation_id("trace-1");
+    assert_eq!(ctx.correlation_id.as_deref(), Some("trace-1"));
+}
+
 #[test]
 fn test_agent_context_clone() {
     let ctx = AgentContext::new("session-123")
@@ -150,3 +158,105 @@ fn test_default_max_turns() {
 fn test_default_timeout() {
     assert_eq!(default_timeout(), 300);
 }
+
+#[test]
+fn test_run_overrides_validate() {
+    let models = vec![
+        ModelDefinition::new("small", "Small"),
+        ModelDefinition {
+            max_output_tokens: 16_000,
+            ..ModelDefinition::new("large", "Large")
+        },
+    ];
+    let capabilities = ProviderCapabilities {
+        tool_calling: true,
+        ..Default::default()
+    };
+    let validate = |overrides: RunOverrides| overrides.validate("small", &models, &capabilities);
+
+    assert!(RunOverrides::default().is_empty());
+    assert!(validate(RunOverrides::default()).is_ok());
+    assert!(validate(RunOverrides {
+        model: Some("large".to_string()),
+        temperature: Some(0.2),
+        max_tokens: Some(8000),
+        tools: Some(vec!["read_file".to_string()]),
+    })
+    .is_ok());
+
+    // The token limit is that of the model the run uses
+    let err = validate(RunOverrides {
+        max_tokens: Some(8000),
+        ..Default::default()
+    })
+    .unwrap_err();
+    assert!(err.to_string().contains("exceeds the 4096 output tokens of model 'small'"));
+
+    let invalid = [
+        RunOverrides {
+            model: Some("huge".to_string()),
+            ..Default::default()
+        },
+        RunOverrides {
+            temperature: Some(2.5),
+            ..Default::default()
+        },
+        RunOverrides {
+            max_tokens: Some(0),
+            ..Default::default()
+        },
+    ];
+    for overrides in invalid {
+        assert!(matches!(validate(overrides), Err(AgentError::InvalidOverride(_))));
+    }
+
+    // Tools need tool calling
+    let tools = RunOverrides {
+        tools: Some(vec!["read_file".to_string()]),
+        ..Default::default()
+    };
+    assert!(tools.validate("small", &models, &ProviderCapabilities::default()).is_err());
+    // Providers listing no models accept any model
+    let model = RunOverrides {
+        model: Some("custom".to_string()),
+        ..Default::default()
+    };
+    assert!(model.validate("small", &[], &capabilities).is_ok());
+}
+
+#[test]
+fn test_run_overrides_allows_tool() {
+    assert!(RunOverrides::default().allows_tool("shell"));
+    let overrides = RunOverrides {
+        tools: Some(vec!["read_file".to_string()]),
+        ..Default::default()
+    };
+    assert!(overrides.allows_tool("read_file"));
+    assert!(!overrides.allows_tool("shell"));
+    assert_eq!(overrides.model_or("default"), "default");
+}
+
+#[test]
+fn test_run_scheduling_payload() {
+    let deadline: DateTime<Utc> = "2020-01-01T12:00:00Z".parse().unwrap();
+    let scheduling = RunScheduling {
+        priority: Some("high".to_string()),
+        deadline: Some(deadline),
+    };
+
+    let mut payload = serde_json::json!({"prompt": "sub task"});
+    scheduling.apply_to(&mut payload);
+    assert_eq!(payload["priority"], "high");
+    assert_eq!(RunScheduling::from_payload(&payload), scheduling);
+
+    // A higher priority and an earlier deadline are kept
+    let earlier = "2020-01-01T11:00:00Z";
+    let mut payload = serde_json::json!({"priority": "critical", "deadline": earlier});
+    scheduling.apply_to(&mut payload);
+    assert_eq!(payload["priority"], "critical");
+    assert_eq!(payload["deadline"], earlier);
+
+    // Unknown priorities are ignored
+    assert!(RunScheduling::from_payload(&serde_json::json!({"priority": "urgent"})).is_empty());
+    assert_eq!(scheduling.time_left(), Some(std::time::Duration::ZERO));
+}
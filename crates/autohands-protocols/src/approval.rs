@@ -0,0 +1,278 @@
+//! Human approval protocol definitions.
+//!
+//! An approval gate pauses an operation until a person approves or rejects
+//! it, or until a timeout policy decides. Workflows and high-risk tools use
+//! it to run unattended without acting on their own for risky steps.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::ChannelError;
+use crate::types::RiskLevel;
+
+/// Core trait for approval gates.
+#[async_trait]
+pub trait ApprovalGate: Send + Sync {
+    /// Submit a request and wait until it is decided or times out.
+    async fn request_approval(&self, request: ApprovalRequest) -> ApprovalDecision;
+
+    /// Requests currently waiting for a decision, oldest first.
+    async fn pending(&self) -> Vec<ApprovalRequest>;
+
+    /// Decide a pending request. Returns `false` if it is not pending.
+    async fn decide(&self, request_id: &str, decision: ApprovalDecision) -> bool;
+}
+
+/// Delivers approval requests to people (chat, webhook, ...).
+#[async_trait]
+pub trait ApprovalNotifier: Send + Sync {
+    /// Notifier name, used in logs.
+    fn name(&self) -> &str;
+
+    /// Announce a new pending request.
+    async fn notify(&self, request: &ApprovalRequest) -> Result<(), ChannelError>;
+}
+
+/// What happens when nobody decides before the timeout.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ApprovalTimeoutPolicy {
+    /// Treat the request as rejected.
+    #[default]
+    Reject,
+    /// Treat the request as approved.
+    Approve,
+}
+
+/// A request for human approval.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalRequest {
+    /// Unique request ID.
+    pub id: String,
+
+    /// Short summary of what needs approval.
+    pub title: String,
+
+    /// Longer explanation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Origin, e.g. `workflow:deploy/confirm` or `tool:exec`.
+    pub source: String,
+
+    /// Session the request belongs to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+
+    /// Risk of the operation.
+    #[serde(default)]
+    pub risk_level: RiskLevel,
+
+    /// Structured details (tool arguments, step context, ...).
+    #[serde(default)]
+    pub details: serde_json::Value,
+
+    /// Seconds to wait for a decision (gate default if unset).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+
+    /// Timeout outcome (gate default if unset).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_timeout: Option<ApprovalTimeoutPolicy>,
+
+    /// Creation time.
+    pub created_at: DateTime<Utc>,
+}
+
+impl ApprovalRequest {
+    /// Create a request with a generated ID.
+    pub fn new(title: impl Into<String>, source: impl Into<String>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            title: title.into(),
+            description: None,
+            source: source.into(),
+            session_id: None,
+            risk_level: RiskLevel::default(),
+            details: serde_json::Value::Null,
+            timeout_secs: None,
+            on_timeout: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn with_session(mut self, session_id: impl Into<String>) -> Self {
+        self.session_id = Some(session_id.into());
+        self
+    }
+
+    pub fn with_risk_level(mut self, risk_level: RiskLevel) -> Self {
+        self.risk_level = risk_level;
+        self
+    }
+
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = details;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout_secs: u64) -> Self {
+        self.timeout_secs = Some(timeout_secs);
+        self
+    }
+
+    pub fn with_timeout_policy(mut self, policy: ApprovalTimeoutPolicy) -> Self {
+        self.on_timeout = Some(policy);
+        self
+    }
+}
+
+/// Outcome of an approval request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalStatus {
+    Approved,
+    Rejected,
+    /// Nobody decided in time and the timeout policy rejected it.
+    TimedOut,
+}
+
+/// A decision on an approval request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalDecision {
+    /// Outcome.
+    pub status: ApprovalStatus,
+
+    /// Who decided.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decided_by: Option<String>,
+
+    /// Reason or note from the decider.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+
+    /// Decision time.
+    pub decided_at: DateTime<Utc>,
+}
+
+impl ApprovalDecision {
+    fn with_status(status: ApprovalStatus) -> Self {
+        Self {
+            status,
+            decided_by: None,
+            comment: None,
+            decided_at: Utc::now(),
+        }
+    }
+
+    pub fn approved() -> Self {
+        Self::with_status(ApprovalStatus::Approved)
+    }
+
+    pub fn rejected() -> Self {
+        Self::with_status(ApprovalStatus::Rejected)
+    }
+
+    pub fn timed_out() -> Self {
+        Self::with_status(ApprovalStatus::TimedOut)
+    }
+
+    pub fn by(mut self, decided_by: impl Into<String>) -> Self {
+        self.decided_by = Some(decided_by.into());
+        self
+    }
+
+    pub fn with_comment(mut self, comment: impl Into<String>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    /// Whether the operation may proceed.
+    pub fn is_approved(&self) -> bool {
+        self.status == ApprovalStatus::Approved
+    }
+}
+
+/// What the agent loop does before running a tool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolApprovalAction {
+    /// Run the tool.
+    Allow,
+    /// Run the tool once a person approves it.
+    Prompt,
+    /// Refuse to run the tool.
+    Deny,
+}
+
+/// Approval actions of tool calls by risk level, with per-tool overrides.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolApprovalPolicy {
+    pub low: ToolApprovalAction,
+    pub medium: ToolApprovalAction,
+    pub high: ToolApprovalAction,
+
+    /// Actions of specific tools, whatever their risk level.
+    pub tools: HashMap<String, ToolApprovalAction>,
+}
+
+impl Default for ToolApprovalPolicy {
+    fn default() -> Self {
+        Self {
+            low: ToolApprovalAction::Allow,
+            medium: ToolApprovalAction::Prompt,
+            high: ToolApprovalAction::Deny,
+            tools: HashMap::new(),
+        }
+    }
+}
+
+impl ToolApprovalPolicy {
+    /// Prompt for tools at or above `min_risk` and allow the others.
+    pub fn threshold(min_risk: RiskLevel) -> Self {
+        let action = |risk: RiskLevel| {
+            if risk >= min_risk {
+                ToolApprovalAction::Prompt
+            } else {
+                ToolApprovalAction::Allow
+            }
+        };
+        Self {
+            low: action(RiskLevel::Low),
+            medium: action(RiskLevel::Medium),
+            high: action(RiskLevel::High),
+            tools: HashMap::new(),
+        }
+    }
+
+    /// Override the action of one tool.
+    pub fn with_tool(mut self, tool_id: impl Into<String>, action: ToolApprovalAction) -> Self {
+        self.tools.insert(tool_id.into(), action);
+        self
+    }
+
+    /// Action for a call of `tool_id`, whose risk level is `risk_level`.
+    pub fn action(&self, tool_id: &str, risk_level: RiskLevel) -> ToolApprovalAction {
+        if let Some(action) = self.tools.get(tool_id) {
+            return *action;
+        }
+        match risk_level {
+            RiskLevel::Low => self.low,
+            RiskLevel::Medium => self.medium,
+            RiskLevel::High => self.high,
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "approval_tests.rs"]
+mod tests;
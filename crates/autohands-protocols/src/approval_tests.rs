@@ -0,0 +1,59 @@
+use super::*;
+
+#[test]
+fn test_request_builder() {
+    let request = ApprovalRequest::new("Run rm -rf build", "tool:exec")
+        .with_session("s-1")
+        .with_risk_level(RiskLevel::High)
+        .with_timeout(60)
+        .with_timeout_policy(ApprovalTimeoutPolicy::Approve);
+    assert!(!request.id.is_empty());
+    assert_eq!(request.session_id.as_deref(), Some("s-1"));
+    assert_eq!(request.risk_level, RiskLevel::High);
+    assert_eq!(request.timeout_secs, Some(60));
+    assert_eq!(request.on_timeout, Some(ApprovalTimeoutPolicy::Approve));
+}
+
+#[test]
+fn test_request_serialization() {
+    let request = ApprovalRequest::new("Deploy", "workflow:release/confirm");
+    let json = serde_json::to_value(&request).unwrap();
+    assert_eq!(json["source"], "workflow:release/confirm");
+    assert_eq!(json["risk_level"], "low");
+    assert!(json.get("timeout_secs").is_none());
+
+    let parsed: ApprovalRequest = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed.id, request.id);
+}
+
+#[test]
+fn test_decision() {
+    let decision = ApprovalDecision::approved().by("alice").with_comment("ok");
+    assert!(decision.is_approved());
+    assert_eq!(decision.decided_by.as_deref(), Some("alice"));
+    assert!(!ApprovalDecision::rejected().is_approved());
+    assert!(!ApprovalDecision::timed_out().is_approved());
+    assert_eq!(
+        serde_json::to_value(ApprovalStatus::TimedOut).unwrap(),
+        "timed_out"
+    );
+}
+
+#[test]
+fn test_tool_approval_policy() {
+    let policy = ToolApprovalPolicy::default().with_tool("exec", ToolApprovalAction::Prompt);
+    assert_eq!(policy.action("read_file", RiskLevel::Low), ToolApprovalAction::Allow);
+    assert_eq!(policy.action("write_file", RiskLevel::Medium), ToolApprovalAction::Prompt);
+    assert_eq!(policy.action("desktop_click", RiskLevel::High), ToolApprovalAction::Deny);
+    assert_eq!(policy.action("exec", RiskLevel::High), ToolApprovalAction::Prompt);
+
+    let threshold = ToolApprovalPolicy::threshold(RiskLevel::Medium);
+    assert_eq!(threshold.action("a", RiskLevel::Low), ToolApprovalAction::Allow);
+    assert_eq!(threshold.action("a", RiskLevel::High), ToolApprovalAction::Prompt);
+
+    let parsed: ToolApprovalPolicy =
+        serde_json::from_value(serde_json::json!({"high": "prompt", "tools": {"exec": "deny"}}))
+            .unwrap();
+    assert_eq!(parsed.medium, ToolApprovalAction::Prompt);
+    assert_eq!(parsed.action("exec", RiskLevel::Low), ToolApprovalAction::Deny);
+}
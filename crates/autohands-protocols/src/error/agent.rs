@@ -23,6 +23,18 @@ pub enum AgentError {
 
     #[error("Provider error: {0}")]
     ProviderError(#[from] ProviderError),
+
+    #[error("Invalid run override: {0}")]
+    InvalidOverride(String),
+
+    /// The provider failed after part of the turn's output had arrived.
+    #[error("Provider failed mid-turn: {source}")]
+    Interrupted {
+        /// Text received before the failure.
+        partial: String,
+        #[source]
+        source: ProviderError,
+    },
 }
 
 #[cfg(test)]
@@ -61,6 +73,17 @@ mod tests {
         assert!(err.to_string().contains("aborted"));
     }
 
+    #[test]
+    fn test_agent_error_interrupted() {
+        let err = AgentError::Interrupted {
+            partial: "Half an ans".to_string(),
+            source: ProviderError::Network("connection reset".to_string()),
+        };
+        assert!(err.to_string().contains("mid-turn"));
+        assert!(err.to_string().contains("connection reset"));
+        assert!(std::error::Error::source(&err).is_some());
+    }
+
     #[test]
     fn test_agent_error_debug() {
         let err = AgentError::NotFound("test".to_string());
@@ -40,6 +40,21 @@ pub trait ToolRegistryAccess: Send + Sync {
 
     /// Unregister a tool.
     fn unregister_tool(&self, tool_id: &str) -> Result<(), ExtensionError>;
+
+    /// Make calls of tools holding an exclusive resource fail until it is
+    /// resumed.
+    fn suspend_resource(&self, resource: &str, reason: &str);
+
+    /// Suspend an exclusive resource and cancel the runs using it: the one
+    /// whose call holds it, and those calling a tool needing it until it is
+    /// resumed.
+    fn abort_resource(&self, resource: &str, reason: &str);
+
+    /// Resume a suspended resource; returns whether it was suspended.
+    fn resume_resource(&self, resource: &str) -> bool;
+
+    /// Whether an exclusive resource is suspended.
+    fn is_resource_suspended(&self, resource: &str) -> bool;
 }
 
 /// Trait for accessing the provider registry from extensions.
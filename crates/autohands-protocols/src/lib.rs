@@ -12,7 +12,10 @@
 //! - [`MemoryBackend`] - Trait for memory storage implementations
 //! - [`Agent`] - Trait for agent implementations
 //! - [`SkillLoader`] - Trait for skill loading implementations
+//! - [`ApprovalGate`] - Trait for human approval gates
+//! - [`QuestionGate`] - Trait for clarification questions to the user
 
+pub mod approval;
 pub mod error;
 pub mod extension;
 pub mod tool;
@@ -21,19 +24,25 @@ pub mod channel;
 pub mod memory;
 pub mod agent;
 pub mod skill;
+pub mod question;
 pub mod types;
 
 // Re-export core traits
 pub use extension::{Extension, ExtensionContext, ExtensionManifest};
-pub use tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+pub use tool::{Tool, ToolContext, ToolDefinition, ToolExample, ToolResult};
 pub use provider::{CompletionRequest, CompletionResponse, CompletionStream, LLMProvider};
 pub use channel::{
     Channel, ChannelCapabilities, ChannelId, InboundMessage, IncomingMessage, OutboundMessage,
     OutgoingMessage, ReplyAddress,
 };
 pub use memory::{MemoryBackend, MemoryEntry, MemoryQuery};
-pub use agent::{Agent, AgentConfig, AgentContext};
+pub use agent::{Agent, AgentConfig, AgentContext, RunOverrides, RunScheduling};
 pub use skill::{Skill, SkillDefinition, SkillLoader};
+pub use approval::{
+    ApprovalDecision, ApprovalGate, ApprovalNotifier, ApprovalRequest, ApprovalStatus,
+    ApprovalTimeoutPolicy,
+};
+pub use question::{Answer, AnswerStatus, Question, QuestionGate};
 pub use error::{
     AgentError, ChannelError, ExtensionError, MemoryError, ProtocolError, ProviderError,
     SkillError, ToolError,
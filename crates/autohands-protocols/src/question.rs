@@ -0,0 +1,198 @@
+//! Clarification question protocol definitions.
+//!
+//! Instead of guessing, an agent can ask the person it works for. The
+//! question goes out through the conversation its task came from, and the
+//! agent waits for the reply, or for the timeout and the question's default
+//! answer.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::channel::ReplyAddress;
+
+/// Core trait for question gates.
+#[async_trait]
+pub trait QuestionGate: Send + Sync {
+    /// Ask a question and wait until it is answered or times out.
+    async fn ask(&self, question: Question) -> Answer;
+
+    /// Questions currently waiting for an answer, oldest first.
+    async fn pending(&self) -> Vec<Question>;
+
+    /// Answer a pending question. Returns `false` if nobody waits for it.
+    async fn answer(&self, question_id: &str, answer: Answer) -> bool;
+}
+
+/// A question for the user.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Question {
+    /// Unique question ID.
+    pub id: String,
+
+    /// The question.
+    pub text: String,
+
+    /// Choices offered; the user may still reply freely.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+
+    /// Answer used when nobody replies in time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+
+    /// Session asking.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+
+    /// Conversation the question is sent to (the session's when unset).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_to: Option<ReplyAddress>,
+
+    /// Seconds to wait for a reply (gate default if unset).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+
+    /// Creation time.
+    pub created_at: DateTime<Utc>,
+}
+
+impl Question {
+    /// Create a question with a generated ID.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            text: text.into(),
+            options: Vec::new(),
+            default: None,
+            session_id: None,
+            reply_to: None,
+            timeout_secs: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn with_options(mut self, options: Vec<String>) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn with_default(mut self, default: impl Into<String>) -> Self {
+        self.default = Some(default.into());
+        self
+    }
+
+    pub fn with_session(mut self, session_id: impl Into<String>) -> Self {
+        self.session_id = Some(session_id.into());
+        self
+    }
+
+    pub fn with_reply_to(mut self, reply_to: ReplyAddress) -> Self {
+        self.reply_to = Some(reply_to);
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout_secs: u64) -> Self {
+        self.timeout_secs = Some(timeout_secs);
+        self
+    }
+
+    /// The question as a chat message, with numbered options.
+    pub fn render(&self) -> String {
+        let mut text = format!("❓ {}", self.text);
+        for (i, option) in self.options.iter().enumerate() {
+            text.push_str(&format!("\n{}. {}", i + 1, option));
+        }
+        if let Some(default) = &self.default {
+            text.push_str(&format!("\n(Default if no reply: {})", default));
+        }
+        text
+    }
+
+    /// Interpret a reply: an option's number or text picks that option.
+    pub fn parse_reply(&self, reply: &str) -> Answer {
+        let reply = reply.trim();
+        let option = reply
+            .parse::<usize>()
+            .ok()
+            .filter(|n| (1..=self.options.len()).contains(n))
+            .map(|n| n - 1)
+            .or_else(|| {
+                self.options
+                    .iter()
+                    .position(|option| option.eq_ignore_ascii_case(reply))
+            });
+        match option {
+            Some(i) => Answer::answered(self.options[i].clone()).with_option(i),
+            None => Answer::answered(reply),
+        }
+    }
+}
+
+/// How a question was resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnswerStatus {
+    Answered,
+    /// Nobody replied in time; the default applies.
+    TimedOut,
+}
+
+/// The answer to a question.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Answer {
+    /// Outcome.
+    pub status: AnswerStatus,
+
+    /// Reply, or the default after a timeout.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+
+    /// Index of the chosen option.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub option: Option<usize>,
+
+    /// Who answered.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answered_by: Option<String>,
+
+    /// Answer time.
+    pub answered_at: DateTime<Utc>,
+}
+
+impl Answer {
+    pub fn answered(text: impl Into<String>) -> Self {
+        Self {
+            status: AnswerStatus::Answered,
+            text: Some(text.into()),
+            option: None,
+            answered_by: None,
+            answered_at: Utc::now(),
+        }
+    }
+
+    /// Timeout outcome, carrying the question's default if any.
+    pub fn timed_out(default: Option<String>) -> Self {
+        Self {
+            status: AnswerStatus::TimedOut,
+            text: default,
+            option: None,
+            answered_by: None,
+            answered_at: Utc::now(),
+        }
+    }
+
+    pub fn with_option(mut self, option: usize) -> Self {
+        self.option = Some(option);
+        self
+    }
+
+    pub fn by(mut self, answered_by: impl Into<String>) -> Self {
+        self.answered_by = Some(answered_by.into());
+        self
+    }
+}
+
+#[cfg(test)]
+#[path = "question_tests.rs"]
+mod tests;
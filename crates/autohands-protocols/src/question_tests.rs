@@ -0,0 +1,65 @@
+use super::*;
+
+fn deploy_question() -> Question {
+    Question::new("Which environment?")
+        .with_options(vec!["staging".to_string(), "production".to_string()])
+        .with_default("staging")
+}
+
+#[test]
+fn test_question_builder() {
+    let question = deploy_question()
+        .with_session("s-1")
+        .with_reply_to(ReplyAddress::new("slack", "C42"))
+        .with_timeout(60);
+    assert!(!question.id.is_empty());
+    assert_eq!(question.options.len(), 2);
+    assert_eq!(question.default.as_deref(), Some("staging"));
+    assert_eq!(question.session_id.as_deref(), Some("s-1"));
+    assert_eq!(question.reply_to.unwrap().target, "C42");
+    assert_eq!(question.timeout_secs, Some(60));
+}
+
+#[test]
+fn test_render_numbers_options() {
+    let text = deploy_question().render();
+    assert!(text.contains("Which environment?"));
+    assert!(text.contains("1. staging"));
+    assert!(text.contains("2. production"));
+    assert!(text.contains("Default if no reply: staging"));
+}
+
+#[test]
+fn test_parse_reply_picks_options() {
+    let question = deploy_question();
+
+    let by_number = question.parse_reply(" 2 ");
+    assert_eq!(by_number.option, Some(1));
+    assert_eq!(by_number.text.as_deref(), Some("production"));
+
+    let by_text = question.parse_reply("Staging");
+    assert_eq!(by_text.option, Some(0));
+    assert_eq!(by_text.text.as_deref(), Some("staging"));
+
+    let free = question.parse_reply("neither, wait until Monday");
+    assert_eq!(free.option, None);
+    assert_eq!(free.text.as_deref(), Some("neither, wait until Monday"));
+
+    let out_of_range = question.parse_reply("3");
+    assert_eq!(out_of_range.option, None);
+}
+
+#[test]
+fn test_serialization() {
+    let question = Question::new("Proceed?");
+    let json = serde_json::to_value(&question).unwrap();
+    assert!(json.get("options").is_none());
+    assert!(json.get("reply_to").is_none());
+    let parsed: Question = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed.id, question.id);
+
+    let answer = Answer::timed_out(Some("yes".to_string()));
+    let json = serde_json::to_value(&answer).unwrap();
+    assert_eq!(json["status"], "timed_out");
+    assert_eq!(json["text"], "yes");
+}
@@ -3,9 +3,16 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
 
+use crate::agent::RunScheduling;
 use crate::extension::TaskSubmitter;
 
+use super::{OutputStream, SandboxPolicy, ToolDefinition, ToolOutputChunk};
+
+/// Receives a tool's output as it is produced.
+pub type ToolOutputSink = Arc<dyn Fn(ToolOutputChunk) + Send + Sync>;
+
 /// Context for tool execution.
 #[derive(Clone)]
 pub struct ToolContext {
@@ -26,6 +33,20 @@ pub struct ToolContext {
 
     /// Additional context data.
     pub data: HashMap<String, serde_json::Value>,
+
+    /// Sandbox of the agent, limiting the paths tools may use beyond the
+    /// working directory.
+    pub sandbox: Option<Arc<SandboxPolicy>>,
+
+    /// Priority and deadline of the run, for work the tool spawns.
+    pub scheduling: RunScheduling,
+
+    /// Receiver of incremental output, when the caller streams it.
+    pub output: Option<ToolOutputSink>,
+
+    /// Whether the call is part of a dry run, in which tools with side
+    /// effects only describe what they would do.
+    pub dry_run: bool,
 }
 
 impl ToolContext {
@@ -38,9 +59,45 @@ impl ToolContext {
             abort_signal: Arc::new(AbortSignal::new()),
             task_submitter: None,
             data: HashMap::new(),
+            sandbox: None,
+            scheduling: RunScheduling::default(),
+            output: None,
+            dry_run: false,
         }
     }
 
+    /// Stream the tool's incremental output to a sink.
+    pub fn with_output(mut self, output: ToolOutputSink) -> Self {
+        self.output = Some(output);
+        self
+    }
+
+    /// Whether the caller streams incremental output.
+    pub fn streams_output(&self) -> bool {
+        self.output.is_some()
+    }
+
+    /// Report output produced so far; ignored unless the caller streams it.
+    pub fn emit_output(&self, stream: OutputStream, content: impl Into<String>) {
+        if let Some(output) = &self.output {
+            output(ToolOutputChunk {
+                stream,
+                content: content.into(),
+            });
+        }
+    }
+
+    /// Run under a sandbox.
+    pub fn with_sandbox(mut self, sandbox: Arc<SandboxPolicy>) -> Self {
+        self.sandbox = Some(sandbox);
+        self
+    }
+
+    /// Whether a call to the tool is simulated rather than executed.
+    pub fn simulates(&self, definition: &ToolDefinition) -> bool {
+        self.dry_run && !definition.read_only
+    }
+
     /// Check if the operation should be aborted.
     pub fn is_aborted(&self) -> bool {
         self.abort_signal.is_aborted()
@@ -62,27 +119,47 @@ impl ToolContext {
 }
 
 /// Signal for aborting operations.
+///
+/// Signals form a tree: aborting one aborts every signal derived from it
+/// with [`child`](Self::child), so cancelling a session reaches the
+/// sub-agents, processes and browser operations it started.
 pub struct AbortSignal {
-    aborted: std::sync::atomic::AtomicBool,
+    token: CancellationToken,
 }
 
 impl AbortSignal {
     /// Create a new abort signal.
     pub fn new() -> Self {
         Self {
-            aborted: std::sync::atomic::AtomicBool::new(false),
+            token: CancellationToken::new(),
         }
     }
 
+    /// A signal aborted along with this one, but abortable on its own.
+    pub fn child(&self) -> Arc<Self> {
+        Arc::new(Self {
+            token: self.token.child_token(),
+        })
+    }
+
     /// Check if aborted.
     pub fn is_aborted(&self) -> bool {
-        self.aborted.load(std::sync::atomic::Ordering::Relaxed)
+        self.token.is_cancelled()
     }
 
     /// Trigger the abort.
     pub fn abort(&self) {
-        self.aborted
-            .store(true, std::sync::atomic::Ordering::Relaxed);
+        self.token.cancel();
+    }
+
+    /// Wait until the signal is aborted.
+    pub async fn aborted(&self) {
+        self.token.cancelled().await
+    }
+
+    /// The underlying cancellation token.
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
     }
 }
 
@@ -33,6 +33,28 @@ fn test_tool_context_get_set() {
     assert_eq!(result, Some("value".to_string()));
 }
 
+#[test]
+fn test_tool_context_emit_output() {
+    let ctx = ToolContext::new("session-1", PathBuf::from("/tmp"));
+    assert!(!ctx.streams_output());
+    ctx.emit_output(OutputStream::Stdout, "ignored");
+
+    let chunks = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let sink = chunks.clone();
+    let ctx = ctx.with_output(Arc::new(move |chunk: ToolOutputChunk| {
+        sink.lock().unwrap().push(chunk)
+    }));
+    assert!(ctx.streams_output());
+    ctx.emit_output(OutputStream::Stderr, "warning: unused");
+    assert_eq!(
+        *chunks.lock().unwrap(),
+        vec![ToolOutputChunk {
+            stream: OutputStream::Stderr,
+            content: "warning: unused".to_string(),
+        }]
+    );
+}
+
 #[test]
 fn test_tool_context_get_missing() {
     let ctx = ToolContext::new("session-1", PathBuf::from("/tmp"));
@@ -94,6 +116,25 @@ fn test_abort_signal_multiple_aborts() {
     assert!(signal.is_aborted());
 }
 
+#[tokio::test]
+async fn test_abort_signal_child() {
+    let parent = AbortSignal::new();
+    let child = parent.child();
+    let grandchild = child.child();
+
+    // Aborting a child leaves its parent running
+    let sibling = parent.child();
+    sibling.abort();
+    assert!(!parent.is_aborted());
+
+    parent.abort();
+    assert!(child.is_aborted());
+    assert!(grandchild.is_aborted());
+    tokio::time::timeout(std::time::Duration::from_secs(1), grandchild.aborted())
+        .await
+        .unwrap();
+}
+
 #[test]
 fn test_tool_context_shared_abort_signal() {
     let ctx = ToolContext::new("session-1", PathBuf::from("/tmp"));
@@ -5,6 +5,16 @@ use std::collections::HashMap;
 
 use crate::types::{Metadata, RiskLevel};
 
+/// An example invocation of a tool.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolExample {
+    /// What the example does.
+    pub description: String,
+
+    /// Parameters passed to the tool.
+    pub input: serde_json::Value,
+}
+
 /// Definition of a tool.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolDefinition {
@@ -29,6 +39,11 @@ pub struct ToolDefinition {
     #[serde(default)]
     pub supports_streaming: bool,
 
+    /// Whether calls have no side effects, so they run for real in a dry
+    /// run instead of being simulated.
+    #[serde(default)]
+    pub read_only: bool,
+
     /// Extension ID that provides this tool.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extension_id: Option<String>,
@@ -36,6 +51,16 @@ pub struct ToolDefinition {
     /// Additional metadata.
     #[serde(default)]
     pub metadata: Metadata,
+
+    /// Example invocations, for clients presenting the tool.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<ToolExample>,
+
+    /// Resources a call holds exclusively while it runs, e.g.
+    /// `"desktop-input"` or `"browser-page:{page_id}"`. `{name}` stands for
+    /// the call's `name` parameter; calls needing a held resource wait.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclusive_resources: Vec<String>,
 }
 
 impl ToolDefinition {
@@ -52,8 +77,11 @@ impl ToolDefinition {
             parameters_schema: None,
             risk_level: RiskLevel::Low,
             supports_streaming: false,
+            read_only: false,
             extension_id: None,
             metadata: HashMap::new(),
+            examples: Vec::new(),
+            exclusive_resources: Vec::new(),
         }
     }
 
@@ -69,6 +97,46 @@ impl ToolDefinition {
         self
     }
 
+    /// Mark the tool as free of side effects.
+    pub fn with_read_only(mut self) -> Self {
+        self.read_only = true;
+        self
+    }
+
+    /// Add an example invocation.
+    pub fn with_example(
+        mut self,
+        description: impl Into<String>,
+        input: serde_json::Value,
+    ) -> Self {
+        self.examples.push(ToolExample {
+            description: description.into(),
+            input,
+        });
+        self
+    }
+
+    /// Hold a resource exclusively while a call runs.
+    pub fn with_exclusive_resource(mut self, resource: impl Into<String>) -> Self {
+        self.exclusive_resources.push(resource.into());
+        self
+    }
+
+    /// Resources a call with these parameters holds, placeholders filled in.
+    ///
+    /// A missing parameter leaves its placeholder empty.
+    pub fn exclusive_resources_for(&self, params: &serde_json::Value) -> Vec<String> {
+        self.exclusive_resources
+            .iter()
+            .map(|template| fill_placeholders(template, params))
+            .collect()
+    }
+
+    /// JSON Schema for the parameters; an empty object schema if none is set.
+    pub fn input_schema(&self) -> serde_json::Value {
+        self.parameters_schema.clone().unwrap_or_else(empty_object_schema)
+    }
+
     /// Convert to OpenAI function calling format.
     pub fn to_openai_function(&self) -> serde_json::Value {
         serde_json::json!({
@@ -76,7 +144,7 @@ impl ToolDefinition {
             "function": {
                 "name": self.id,
                 "description": self.description,
-                "parameters": self.parameters_schema.clone().unwrap_or_else(empty_object_schema)
+                "parameters": self.input_schema()
             }
         })
     }
@@ -86,11 +154,30 @@ impl ToolDefinition {
         serde_json::json!({
             "name": self.id,
             "description": self.description,
-            "input_schema": self.parameters_schema.clone().unwrap_or_else(empty_object_schema)
+            "input_schema": self.input_schema()
         })
     }
 }
 
+fn fill_placeholders(template: &str, params: &serde_json::Value) -> String {
+    let mut resource = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        resource.push_str(&rest[..start]);
+        match params.get(&rest[start + 1..start + len]) {
+            Some(serde_json::Value::String(value)) => resource.push_str(value),
+            Some(serde_json::Value::Null) | None => {}
+            Some(value) => resource.push_str(&value.to_string()),
+        }
+        rest = &rest[start + len + 1..];
+    }
+    resource.push_str(rest);
+    resource
+}
+
 fn empty_object_schema() -> serde_json::Value {
     serde_json::json!({
         "type": "object",
@@ -125,8 +125,11 @@ fn test_tool_definition_full() {
         parameters_schema: Some(serde_json::json!({"type": "object"})),
         risk_level: RiskLevel::Medium,
         supports_streaming: true,
+        read_only: false,
         extension_id: Some("my-extension".to_string()),
         metadata,
+        examples: Vec::new(),
+        exclusive_resources: Vec::new(),
     };
 
     assert_eq!(tool.id, "full_tool");
@@ -187,3 +190,44 @@ fn test_openai_function_preserves_required() {
     let func = tool.to_openai_function();
     assert!(func["function"]["parameters"]["required"].is_array());
 }
+
+#[test]
+fn test_with_example() {
+    let tool = ToolDefinition::new("read_file", "Read File", "Read a file")
+        .with_example("Read the README", serde_json::json!({"path": "README.md"}));
+    assert_eq!(tool.examples.len(), 1);
+    assert_eq!(tool.examples[0].input["path"], "README.md");
+
+    let json = serde_json::to_value(&tool).unwrap();
+    assert_eq!(json["examples"][0]["description"], "Read the README");
+    // Tools without examples serialize as before
+    let json = serde_json::to_value(ToolDefinition::new("t", "T", "T")).unwrap();
+    assert!(json.get("examples").is_none());
+}
+
+#[test]
+fn test_input_schema_defaults_to_empty_object() {
+    let tool = ToolDefinition::new("t", "T", "T");
+    assert_eq!(tool.input_schema()["type"], "object");
+}
+
+#[test]
+fn test_exclusive_resources_for() {
+    let tool = ToolDefinition::new("browser_click", "Click", "Click")
+        .with_exclusive_resource("browser-page:{page_id}")
+        .with_exclusive_resource("desktop-input");
+    assert_eq!(
+        tool.exclusive_resources_for(&serde_json::json!({"page_id": "p1"})),
+        vec!["browser-page:p1".to_string(), "desktop-input".to_string()]
+    );
+    assert_eq!(
+        tool.exclusive_resources_for(&serde_json::json!({"page_id": 7})),
+        vec!["browser-page:7".to_string(), "desktop-input".to_string()]
+    );
+    assert_eq!(tool.exclusive_resources_for(&serde_json::json!({}))[0], "browser-page:");
+
+    let json = serde_json::to_value(&tool).unwrap();
+    assert_eq!(json["exclusive_resources"][1], "desktop-input");
+    let json = serde_json::to_value(ToolDefinition::new("t", "T", "T")).unwrap();
+    assert!(json.get("exclusive_resources").is_none());
+}
@@ -6,8 +6,10 @@ mod traits;
 mod definition;
 mod context;
 mod result;
+mod sandbox;
 
 pub use traits::*;
 pub use definition::*;
 pub use context::*;
 pub use result::*;
+pub use sandbox::*;
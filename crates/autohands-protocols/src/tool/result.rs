@@ -5,6 +5,24 @@ use std::collections::HashMap;
 
 use crate::types::Metadata;
 
+/// Stream a chunk of tool output was written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+/// Output produced by a tool while it is still running.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolOutputChunk {
+    pub stream: OutputStream,
+    pub content: String,
+}
+
+/// Start of the content of a simulated result.
+pub const DRY_RUN_PREFIX: &str = "[dry run]";
+
 /// Result of a tool execution.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolResult {
@@ -50,6 +68,18 @@ impl ToolResult {
         }
     }
 
+    /// Create the result of a simulated call, describing what the call
+    /// would have done.
+    pub fn simulated(description: impl Into<String>) -> Self {
+        Self::success(format!("{} {}", DRY_RUN_PREFIX, description.into()))
+            .with_metadata("dry_run", serde_json::json!(true))
+    }
+
+    /// Whether the result comes from a simulated call.
+    pub fn is_simulated(&self) -> bool {
+        self.metadata.get("dry_run") == Some(&serde_json::json!(true))
+    }
+
     /// Create an error result.
     pub fn error(error: impl Into<String>) -> Self {
         let error_msg = error.into();
@@ -67,6 +97,35 @@ impl ToolResult {
         self.metadata.insert(key.into(), value);
         self
     }
+
+    /// JSON Schema of a serialized result.
+    pub fn json_schema() -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the execution was successful"
+                },
+                "content": {
+                    "type": "string",
+                    "description": "Output content"
+                },
+                "structured_output": {
+                    "description": "Structured output, if the tool produces one"
+                },
+                "error": {
+                    "type": "string",
+                    "description": "Error message if execution failed"
+                },
+                "metadata": {
+                    "type": "object",
+                    "description": "Additional metadata about the execution"
+                }
+            },
+            "required": ["success", "content"]
+        })
+    }
 }
 
 /// A streaming tool result chunk.
@@ -184,3 +184,12 @@ fn test_tool_result_serialization_skips_none() {
     assert!(!json.contains("structured_output"));
     assert!(!json.contains("error"));
 }
+
+#[test]
+fn test_tool_result_json_schema_covers_fields() {
+    let schema = ToolResult::json_schema();
+    let json = serde_json::to_value(ToolResult::success_json("OK", serde_json::json!({}))).unwrap();
+    for key in json.as_object().unwrap().keys() {
+        assert!(schema["properties"].get(key).is_some(), "missing {}", key);
+    }
+}
@@ -0,0 +1,279 @@
+//! Filesystem sandbox of a tool call.
+//!
+//! Without a [`SandboxPolicy`], tools may use paths inside the working
+//! directory. A policy adds further allowed roots, denies paths by glob
+//! (`~/.ssh`, `*.pem`) and can make the sandbox read-only. Paths are
+//! checked after resolving symlinks, so a link inside an allowed root
+//! cannot reach outside it or into a denied path. Shell commands cannot
+//! be confined on the host: under a sandbox they only run in its
+//! [`ContainerSandbox`], an ephemeral container.
+
+use std::path::{Component, Path, PathBuf};
+
+use glob::Pattern;
+use serde::{Deserialize, Serialize};
+
+use super::ToolContext;
+use crate::error::ToolError;
+
+/// Paths an agent's tools may use.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SandboxPolicy {
+    /// Directories usable besides the working directory. `~` stands for
+    /// the home directory.
+    pub allowed_roots: Vec<String>,
+
+    /// Glob patterns of paths that may not be used, even inside an allowed
+    /// root. A pattern containing `/` matches a path or any of its parent
+    /// directories (`~/.ssh` covers the keys in it); one without matches
+    /// any path component (`.env`, `*.pem`).
+    pub denied: Vec<String>,
+
+    /// Refuse writes, and shell commands.
+    pub read_only: bool,
+
+    /// Container shell commands run in; without one, the sandbox refuses
+    /// them.
+    pub container: Option<ContainerSandbox>,
+}
+
+/// Ephemeral container shell commands run in. The working directory and
+/// the allowed roots are mounted at their host paths.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContainerSandbox {
+    /// Container engine, `docker` or `podman`.
+    pub engine: String,
+
+    /// Image the commands run in.
+    pub image: String,
+
+    /// CPUs a command may use.
+    pub cpus: f64,
+
+    /// Memory limit in the engine's format, e.g. `1g`.
+    pub memory: String,
+
+    /// Give the container network access.
+    pub network: bool,
+}
+
+impl Default for ContainerSandbox {
+    fn default() -> Self {
+        Self {
+            engine: "docker".to_string(),
+            image: "debian:bookworm-slim".to_string(),
+            cpus: 1.0,
+            memory: "1g".to_string(),
+            network: false,
+        }
+    }
+}
+
+/// How a tool uses a path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathAccess {
+    Read,
+    Write,
+}
+
+impl SandboxPolicy {
+    /// The allowed roots that exist, canonicalized.
+    pub fn allowed_root_paths(&self) -> Vec<PathBuf> {
+        self.allowed_roots
+            .iter()
+            .filter_map(|root| expand_home(root).canonicalize().ok())
+            .collect()
+    }
+
+    /// Error if `path` matches a denied pattern.
+    pub fn check_denied(&self, path: &Path) -> Result<(), ToolError> {
+        for denied in &self.denied {
+            let expanded = expand_home(denied);
+            let expanded = expanded.to_string_lossy();
+            let Ok(pattern) = Pattern::new(&expanded) else {
+                continue;
+            };
+            let matched = if expanded.contains('/') {
+                path.ancestors().any(|ancestor| pattern.matches_path(ancestor))
+            } else {
+                path.components().any(|component| match component {
+                    Component::Normal(name) => pattern.matches(&name.to_string_lossy()),
+                    _ => false,
+                })
+            };
+            if matched {
+                return Err(ToolError::PermissionDenied(format!(
+                    "{} is denied by the sandbox ({})",
+                    path.display(),
+                    denied
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Resolve a path given to a tool against `work_dir` and check it against
+/// the sandbox.
+///
+/// Returns the canonicalized path, or `ToolError::ExecutionFailed` with
+/// "Path traversal denied" when it falls outside `work_dir` and the allowed
+/// roots. For paths that do not exist yet (write / create_dir), the longest
+/// existing ancestor is canonicalized and the remaining suffix appended.
+pub fn resolve_path(
+    path: &str,
+    work_dir: &Path,
+    sandbox: Option<&SandboxPolicy>,
+    access: PathAccess,
+) -> Result<PathBuf, ToolError> {
+    // Canonicalize work_dir first (must exist).
+    let canon_work = work_dir
+        .canonicalize()
+        .map_err(|e| ToolError::ExecutionFailed(format!("Cannot resolve work_dir: {}", e)))?;
+
+    let raw = expand_home(path);
+    // Join relative paths against canonicalized work_dir to avoid symlink mismatch.
+    let joined = if raw.is_absolute() { raw } else { canon_work.join(raw) };
+    let normalized = normalize_path(&joined);
+    let resolved = canonicalize_partial(&normalized)?;
+
+    let mut roots = vec![canon_work];
+    if let Some(sandbox) = sandbox {
+        roots.extend(sandbox.allowed_root_paths());
+    }
+    if !roots.iter().any(|root| resolved.starts_with(root)) {
+        return Err(ToolError::ExecutionFailed("Path traversal denied".to_string()));
+    }
+
+    if let Some(sandbox) = sandbox {
+        // Check the path as written too, for denied paths reached through a
+        // symlinked parent such as a symlinked home directory
+        sandbox.check_denied(&normalized)?;
+        sandbox.check_denied(&resolved)?;
+        if sandbox.read_only && access == PathAccess::Write {
+            return Err(ToolError::PermissionDenied(format!(
+                "the sandbox is read-only, cannot write {}",
+                resolved.display()
+            )));
+        }
+    }
+    Ok(resolved)
+}
+
+/// Canonicalize `path`, or for a path that does not exist yet, its nearest
+/// existing ancestor with the remaining components re-attached.
+fn canonicalize_partial(path: &Path) -> Result<PathBuf, ToolError> {
+    if let Ok(canon) = path.canonicalize() {
+        return Ok(canon);
+    }
+
+    // Walk up to find the longest existing prefix, canonicalize that prefix
+    // (resolves symlinks), and rebuild the tail.
+    let mut existing = path;
+    let mut tail: Vec<&std::ffi::OsStr> = Vec::new();
+    loop {
+        if existing.exists() {
+            break;
+        }
+        match (existing.file_name(), existing.parent()) {
+            (Some(name), Some(parent)) => {
+                tail.push(name);
+                existing = parent;
+            }
+            _ => break,
+        }
+    }
+    let mut resolved = existing
+        .canonicalize()
+        .map_err(|e| ToolError::ExecutionFailed(format!("Cannot resolve path: {}", e)))?;
+    for part in tail.into_iter().rev() {
+        resolved.push(part);
+    }
+    Ok(resolved)
+}
+
+/// Normalize a path by resolving `.` and `..` components without touching the filesystem.
+/// This is used for paths that do not yet exist.
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut result = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => { /* skip `.` */ }
+            Component::ParentDir => {
+                // Pop the last component if possible; otherwise keep the `..`
+                if !result.pop() {
+                    result.push(component);
+                }
+            }
+            other => result.push(other),
+        }
+    }
+    result
+}
+
+/// Replace a leading `~` with the home directory.
+fn expand_home(path: &str) -> PathBuf {
+    let home = || std::env::var_os("HOME").map(PathBuf::from);
+    match path.strip_prefix('~') {
+        Some("") => home().unwrap_or_else(|| PathBuf::from(path)),
+        Some(rest) if rest.starts_with('/') => match home() {
+            Some(home) => home.join(&rest[1..]),
+            None => PathBuf::from(path),
+        },
+        _ => PathBuf::from(path),
+    }
+}
+
+impl ToolContext {
+    /// Resolve a path given to the tool, confined to the working directory
+    /// and the sandbox.
+    pub fn resolve_path(&self, path: &str, access: PathAccess) -> Result<PathBuf, ToolError> {
+        resolve_path(path, &self.work_dir, self.sandbox.as_deref(), access)
+    }
+
+    /// Check where a shell command may run and return the directory: `cwd`,
+    /// or the working directory.
+    ///
+    /// A shell reaches any path the daemon can, whatever the command looks
+    /// like (`$HOME`, `cd`, command substitution), so it cannot be confined
+    /// by inspecting the command. Under a sandbox, commands therefore only
+    /// run in its container, from a directory inside the sandbox; they are
+    /// refused without a container and when the sandbox is read-only.
+    pub fn check_command(&self, cwd: Option<&str>) -> Result<PathBuf, ToolError> {
+        let Some(sandbox) = self.sandbox.as_deref() else {
+            return Ok(cwd.map(PathBuf::from).unwrap_or_else(|| self.work_dir.clone()));
+        };
+        if sandbox.read_only {
+            return Err(ToolError::PermissionDenied(
+                "shell commands are not allowed in a read-only sandbox".to_string(),
+            ));
+        }
+        if sandbox.container.is_none() {
+            return Err(ToolError::PermissionDenied(
+                "shell commands cannot be confined to the sandbox on the host; \
+                 configure a container for them"
+                    .to_string(),
+            ));
+        }
+        self.resolve_path(cwd.unwrap_or("."), PathAccess::Read)
+    }
+
+    /// Check a shell command that always runs on the host, such as a
+    /// background process or a persistent shell session, and return its
+    /// directory. Refused under any sandbox, see
+    /// [`check_command`](Self::check_command).
+    pub fn check_host_command(&self, cwd: Option<&str>) -> Result<PathBuf, ToolError> {
+        if self.sandbox.is_some() {
+            return Err(ToolError::PermissionDenied(
+                "host shell commands cannot be confined to the sandbox".to_string(),
+            ));
+        }
+        Ok(cwd.map(PathBuf::from).unwrap_or_else(|| self.work_dir.clone()))
+    }
+}
+
+#[cfg(test)]
+#[path = "sandbox_tests.rs"]
+mod tests;
@@ -0,0 +1,205 @@
+use super::*;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn resolve_path_safe(path: &str, work_dir: &Path) -> Result<PathBuf, ToolError> {
+    resolve_path(path, work_dir, None, PathAccess::Read)
+}
+
+fn sandboxed(work_dir: &Path, sandbox: SandboxPolicy) -> ToolContext {
+    ToolContext::new("session-1", work_dir.to_path_buf()).with_sandbox(Arc::new(sandbox))
+}
+
+#[test]
+fn test_resolve_safe_relative_path() {
+    let tmp = TempDir::new().unwrap();
+    std::fs::write(tmp.path().join("hello.txt"), "hi").unwrap();
+    let resolved = resolve_path_safe("hello.txt", tmp.path()).unwrap();
+    assert!(resolved.starts_with(tmp.path().canonicalize().unwrap()));
+}
+
+#[test]
+fn test_resolve_safe_absolute_path_within() {
+    let tmp = TempDir::new().unwrap();
+    let file = tmp.path().join("a.txt");
+    std::fs::write(&file, "data").unwrap();
+    let resolved = resolve_path_safe(file.to_str().unwrap(), tmp.path()).unwrap();
+    assert!(resolved.starts_with(tmp.path().canonicalize().unwrap()));
+}
+
+#[test]
+fn test_reject_dot_dot_traversal() {
+    let tmp = TempDir::new().unwrap();
+    let result = resolve_path_safe("../../../etc/passwd", tmp.path());
+    assert!(result.is_err());
+    let err_msg = format!("{}", result.unwrap_err());
+    assert!(err_msg.contains("Path traversal denied"));
+}
+
+#[test]
+fn test_reject_absolute_outside_workdir() {
+    let tmp = TempDir::new().unwrap();
+    let result = resolve_path_safe("/etc/passwd", tmp.path());
+    assert!(result.is_err());
+    let err_msg = format!("{}", result.unwrap_err());
+    assert!(err_msg.contains("Path traversal denied"));
+}
+
+#[test]
+fn test_reject_complex_traversal() {
+    let tmp = TempDir::new().unwrap();
+    std::fs::create_dir_all(tmp.path().join("a/b")).unwrap();
+    let result = resolve_path_safe("a/b/../../../etc/passwd", tmp.path());
+    assert!(result.is_err());
+    let err_msg = format!("{}", result.unwrap_err());
+    assert!(err_msg.contains("Path traversal denied"));
+}
+
+#[test]
+fn test_allow_nonexistent_path_within_workdir() {
+    let tmp = TempDir::new().unwrap();
+    // Path does not exist yet but is within work_dir
+    let resolved = resolve_path_safe("new_dir/new_file.txt", tmp.path()).unwrap();
+    assert!(resolved.starts_with(tmp.path().canonicalize().unwrap()));
+}
+
+#[test]
+fn test_reject_traversal_via_nonexistent_intermediate() {
+    let tmp = TempDir::new().unwrap();
+    let result = resolve_path_safe("nonexistent/../../etc/passwd", tmp.path());
+    assert!(result.is_err());
+    let err_msg = format!("{}", result.unwrap_err());
+    assert!(err_msg.contains("Path traversal denied"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_reject_symlink_escape() {
+    let tmp = TempDir::new().unwrap();
+    let link_path = tmp.path().join("escape_link");
+    std::os::unix::fs::symlink("/etc", &link_path).unwrap();
+    let result = resolve_path_safe("escape_link/passwd", tmp.path());
+    assert!(result.is_err());
+    let err_msg = format!("{}", result.unwrap_err());
+    assert!(err_msg.contains("Path traversal denied"));
+}
+
+#[test]
+fn test_allowed_roots_and_denied_globs() {
+    let work = TempDir::new().unwrap();
+    let shared = TempDir::new().unwrap();
+    std::fs::create_dir_all(shared.path().join("keys")).unwrap();
+    std::fs::write(shared.path().join("notes.txt"), "notes").unwrap();
+    let shared_dir = shared.path().to_str().unwrap();
+
+    let outside = shared.path().join("notes.txt");
+    let outside = outside.to_str().unwrap();
+    let plain = ToolContext::new("session-1", work.path().to_path_buf());
+    assert!(plain.resolve_path(outside, PathAccess::Read).is_err());
+
+    let ctx = sandboxed(
+        work.path(),
+        SandboxPolicy {
+            allowed_roots: vec![shared_dir.to_string()],
+            denied: vec![format!("{}/keys", shared_dir), "*.pem".to_string()],
+            read_only: false,
+            container: None,
+        },
+    );
+    assert!(ctx.resolve_path(outside, PathAccess::Write).is_ok());
+
+    let key = format!("{}/keys/id_rsa", shared_dir);
+    let err = ctx.resolve_path(&key, PathAccess::Read).unwrap_err();
+    assert!(matches!(err, ToolError::PermissionDenied(_)));
+    assert!(err.to_string().contains("denied by the sandbox"));
+    assert!(ctx.resolve_path("certs/server.pem", PathAccess::Write).is_err());
+    assert!(ctx.resolve_path("certs/server.crt", PathAccess::Write).is_ok());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_symlink_into_denied_path_rejected() {
+    let work = TempDir::new().unwrap();
+    let secrets = work.path().join("secrets");
+    std::fs::create_dir_all(&secrets).unwrap();
+    std::fs::write(secrets.join("token"), "t").unwrap();
+    std::os::unix::fs::symlink(&secrets, work.path().join("innocent")).unwrap();
+
+    let denied = secrets.canonicalize().unwrap().display().to_string();
+    let ctx = sandboxed(
+        work.path(),
+        SandboxPolicy {
+            denied: vec![denied],
+            ..Default::default()
+        },
+    );
+    assert!(ctx.resolve_path("innocent/token", PathAccess::Read).is_err());
+}
+
+#[test]
+fn test_read_only_sandbox() {
+    let work = TempDir::new().unwrap();
+    std::fs::write(work.path().join("a.txt"), "a").unwrap();
+    let ctx = sandboxed(
+        work.path(),
+        SandboxPolicy {
+            read_only: true,
+            ..Default::default()
+        },
+    );
+    assert!(ctx.resolve_path("a.txt", PathAccess::Read).is_ok());
+    let err = ctx.resolve_path("a.txt", PathAccess::Write).unwrap_err();
+    assert!(err.to_string().contains("read-only"));
+    assert!(ctx.check_command(None).is_err());
+}
+
+#[test]
+fn test_check_command() {
+    let work = TempDir::new().unwrap();
+    let plain = ToolContext::new("session-1", work.path().to_path_buf());
+    assert_eq!(plain.check_command(Some("/")).unwrap(), PathBuf::from("/"));
+
+    // On the host, a shell cannot be confined however the command reads
+    let host = sandboxed(work.path(), SandboxPolicy::default());
+    let err = host.check_command(None).unwrap_err();
+    assert!(matches!(err, ToolError::PermissionDenied(_)));
+    assert!(err.to_string().contains("container"));
+
+    let ctx = sandboxed(
+        work.path(),
+        SandboxPolicy {
+            container: Some(ContainerSandbox::default()),
+            ..Default::default()
+        },
+    );
+    let dir = ctx.check_command(None).unwrap();
+    assert_eq!(dir, work.path().canonicalize().unwrap());
+    assert!(ctx.check_command(Some("/")).is_err());
+}
+
+#[test]
+fn test_check_host_command() {
+    let work = TempDir::new().unwrap();
+    let plain = ToolContext::new("session-1", work.path().to_path_buf());
+    assert_eq!(plain.check_host_command(None).unwrap(), work.path());
+
+    // Not even a container sandbox confines commands run on the host
+    let ctx = sandboxed(
+        work.path(),
+        SandboxPolicy {
+            container: Some(ContainerSandbox::default()),
+            ..Default::default()
+        },
+    );
+    let err = ctx.check_host_command(None).unwrap_err();
+    assert!(matches!(err, ToolError::PermissionDenied(_)));
+}
+
+#[test]
+fn test_expand_home() {
+    if let Some(home) = std::env::var_os("HOME") {
+        assert_eq!(expand_home("~/.ssh"), PathBuf::from(home).join(".ssh"));
+    }
+    assert_eq!(expand_home("~user/x"), PathBuf::from("~user/x"));
+    assert_eq!(expand_home("/abs"), PathBuf::from("/abs"));
+}
@@ -38,6 +38,38 @@ pub trait Tool: Send + Sync {
     fn risk_level(&self) -> RiskLevel {
         self.definition().risk_level
     }
+
+    /// Describe what a call would do, without doing it.
+    ///
+    /// Used instead of [`execute`](Self::execute) in dry runs, unless the
+    /// tool is read-only. The default names the tool and its parameters;
+    /// tools override it to report what they would change.
+    async fn simulate(
+        &self,
+        params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let _ = ctx;
+        Ok(ToolResult::simulated(format!(
+            "Would call {} with {}; nothing was changed.",
+            self.definition().id,
+            params
+        )))
+    }
+}
+
+/// Execute a tool call, or simulate it if the context is a dry run and the
+/// tool has side effects.
+pub async fn execute_or_simulate(
+    tool: &dyn Tool,
+    params: serde_json::Value,
+    ctx: ToolContext,
+) -> Result<ToolResult, ToolError> {
+    if ctx.simulates(tool.definition()) {
+        tool.simulate(params, ctx).await
+    } else {
+        tool.execute(params, ctx).await
+    }
 }
 
 #[cfg(test)]
@@ -157,3 +157,32 @@ async fn test_tool_execute() {
     let tool_result = result.unwrap();
     assert_eq!(tool_result.content, "executed");
 }
+
+#[tokio::test]
+async fn test_execute_or_simulate() {
+    let tool = MockTool::new();
+    let mut ctx = ToolContext::new("session", PathBuf::from("/tmp"));
+    let params = serde_json::json!({"path": "a.txt"});
+
+    let result = execute_or_simulate(&tool, params.clone(), ctx.clone())
+        .await
+        .unwrap();
+    assert_eq!(result.content, "executed");
+    assert!(!result.is_simulated());
+
+    ctx.dry_run = true;
+    let result = execute_or_simulate(&tool, params.clone(), ctx.clone())
+        .await
+        .unwrap();
+    assert!(result.is_simulated());
+    assert_eq!(
+        result.content,
+        "[dry run] Would call mock_tool with {\"path\":\"a.txt\"}; nothing was changed."
+    );
+
+    let read_only = MockTool {
+        definition: ToolDefinition::new("mock_tool", "Mock Tool", "A mock tool").with_read_only(),
+    };
+    let result = execute_or_simulate(&read_only, params, ctx).await.unwrap();
+    assert_eq!(result.content, "executed");
+}
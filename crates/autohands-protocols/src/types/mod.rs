@@ -3,7 +3,9 @@
 mod message;
 mod content;
 mod common;
+mod privacy;
 
 pub use message::*;
 pub use content::*;
 pub use common::*;
+pub use privacy::*;
@@ -0,0 +1,67 @@
+//! Privacy masks of screenshots.
+
+use serde::{Deserialize, Serialize};
+
+/// Parts of the screen blacked out of screenshots when they are taken,
+/// before they reach a provider, a transcript or an artifact.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrivacyMasks {
+    /// Rectangles in screen (desktop) or viewport (browser) coordinates.
+    pub regions: Vec<MaskRegion>,
+
+    /// Windows (desktop) or pages (browser) whose title contains one of
+    /// these, ignoring case.
+    pub window_titles: Vec<String>,
+}
+
+impl PrivacyMasks {
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty() && self.window_titles.is_empty()
+    }
+
+    /// Whether a window or page with this title is masked.
+    pub fn masks_title(&self, title: &str) -> bool {
+        let title = title.to_lowercase();
+        self.window_titles
+            .iter()
+            .any(|masked| !masked.is_empty() && title.contains(&masked.to_lowercase()))
+    }
+}
+
+/// A rectangle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaskRegion {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl MaskRegion {
+    pub fn new(x: i32, y: i32, width: u32, height: u32) -> Self {
+        Self { x, y, width, height }
+    }
+
+    /// The part of this rectangle inside `area`, relative to the top-left
+    /// corner of `area`.
+    pub fn within(&self, area: &MaskRegion) -> Option<MaskRegion> {
+        let left = self.x.max(area.x) as i64;
+        let top = self.y.max(area.y) as i64;
+        let right = (self.x as i64 + self.width as i64).min(area.x as i64 + area.width as i64);
+        let bottom = (self.y as i64 + self.height as i64).min(area.y as i64 + area.height as i64);
+        if right <= left || bottom <= top {
+            return None;
+        }
+        Some(MaskRegion {
+            x: (left - area.x as i64) as i32,
+            y: (top - area.y as i64) as i32,
+            width: (right - left) as u32,
+            height: (bottom - top) as u32,
+        })
+    }
+}
+
+#[cfg(test)]
+#[path = "privacy_tests.rs"]
+mod tests;
@@ -0,0 +1,38 @@
+use super::*;
+
+#[test]
+fn test_masks_title() {
+    let masks = PrivacyMasks {
+        window_titles: vec!["1Password".to_string(), "".to_string()],
+        ..Default::default()
+    };
+    assert!(masks.masks_title("Vault - 1password"));
+    assert!(!masks.masks_title("Terminal"));
+    assert!(!masks.is_empty());
+    assert!(PrivacyMasks::default().is_empty());
+}
+
+#[test]
+fn test_region_within() {
+    let area = MaskRegion::new(100, 100, 200, 100);
+    assert_eq!(
+        MaskRegion::new(50, 150, 100, 100).within(&area),
+        Some(MaskRegion::new(0, 50, 50, 50))
+    );
+    assert_eq!(
+        MaskRegion::new(120, 110, 10, 10).within(&area),
+        Some(MaskRegion::new(20, 10, 10, 10))
+    );
+    assert_eq!(MaskRegion::new(0, 0, 100, 100).within(&area), None);
+    assert_eq!(MaskRegion::new(400, 100, 10, 10).within(&area), None);
+}
+
+#[test]
+fn test_privacy_masks_deserialize() {
+    let masks: PrivacyMasks = serde_json::from_value(serde_json::json!({
+        "regions": [{"x": 0, "y": 0, "width": 300, "height": 40}],
+    }))
+    .unwrap();
+    assert_eq!(masks.regions, vec![MaskRegion::new(0, 0, 300, 40)]);
+    assert!(masks.window_titles.is_empty());
+}
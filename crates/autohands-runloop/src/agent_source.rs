@@ -196,23 +196,17 @@ impl AgentTaskInjector {
         }
     }
 
-    /// Create a child task with correlation.
+    /// Create a child task inheriting the parent's correlation ID,
+    /// priority and deadline.
     pub fn create_child_task(
         &self,
         parent: &Task,
         task_type: impl Into<String>,
         payload: serde_json::Value,
     ) -> Task {
-        let mut task = Task::new(task_type, payload)
+        Task::new(task_type, payload)
             .with_source(crate::task::TaskSource::Agent)
-            .with_parent(parent.id);
-
-        // Inherit correlation ID
-        if let Some(ref correlation_id) = parent.correlation_id {
-            task = task.with_correlation_id(correlation_id.clone());
-        }
-
-        task
+            .inherit_from(parent)
     }
 }
 
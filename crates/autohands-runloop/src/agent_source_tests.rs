@@ -1,5 +1,6 @@
     use super::*;
     use crate::config::RunLoopConfig;
+    use crate::task::TaskPriority;
 
     #[test]
     fn test_agent_source_new() {
@@ -82,6 +83,16 @@
 
         assert_eq!(child.parent_id, Some(parent.id));
         assert_eq!(child.correlation_id, Some("chain-1".to_string()));
+        assert_eq!(child.priority, TaskPriority::Normal);
+
+        // Children of urgent tasks are urgent too
+        let deadline = chrono::Utc::now() + chrono::Duration::minutes(5);
+        let urgent = parent
+            .with_priority(TaskPriority::High)
+            .with_deadline(deadline);
+        let child = injector.create_child_task(&urgent, "child", serde_json::json!({}));
+        assert_eq!(child.priority, TaskPriority::High);
+        assert_eq!(child.deadline, Some(deadline));
     }
 
     #[test]
@@ -0,0 +1,467 @@
+//! Task lifecycle callbacks.
+//!
+//! A task may carry a [`TaskCallback`]: an HTTP endpoint the RunLoop posts
+//! [`CallbackEvent`]s to as the task runs (started, each agent turn,
+//! approval needed, finished with its result), so integrators need neither
+//! poll nor hold a WebSocket open.
+//!
+//! Each task's events go out in order through their own delivery queue.
+//! A delivery is retried with exponential backoff on network errors, 429 and
+//! 5xx answers, and dropped after [`CallbackConfig::max_attempts`].
+//!
+//! With a secret, every request is signed: `X-AutoHands-Signature` is
+//! `sha256=` followed by the hex HMAC-SHA256 of `{timestamp}.{body}`, with
+//! the timestamp sent in `X-AutoHands-Timestamp` so receivers can reject
+//! replays.
+//!
+//! Callbacks to loopback, private, link-local and other internal addresses
+//! are refused unless their host is in [`CallbackConfig::allowed_hosts`], so
+//! a task submitter cannot make the server post to services only it
+//! reaches. Host names are checked again at delivery, against the addresses
+//! they resolve to, and redirects are not followed.
+
+use std::net::IpAddr;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::config::CallbackConfig;
+use crate::outcome::TaskOutcome;
+use crate::task::Task;
+
+/// Header carrying the request signature.
+pub const SIGNATURE_HEADER: &str = "X-AutoHands-Signature";
+/// Header carrying the signing timestamp (Unix seconds).
+pub const TIMESTAMP_HEADER: &str = "X-AutoHands-Timestamp";
+/// Header carrying the event kind.
+pub const EVENT_HEADER: &str = "X-AutoHands-Event";
+/// Header carrying the event ID, stable across retries.
+pub const DELIVERY_HEADER: &str = "X-AutoHands-Delivery";
+
+/// Where a task's lifecycle events are posted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskCallback {
+    /// HTTP(S) endpoint receiving the events.
+    pub url: String,
+    /// Key signing each request; unsigned without it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+impl TaskCallback {
+    /// Create an unsigned callback.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            secret: None,
+        }
+    }
+
+    /// Sign requests with a secret.
+    pub fn with_secret(mut self, secret: impl Into<String>) -> Self {
+        self.secret = Some(secret.into());
+        self
+    }
+
+    /// Check the URL is an HTTP(S) address outside the internal network,
+    /// unless its host is in `allowed_hosts`.
+    pub fn validate(&self, allowed_hosts: &[String]) -> Result<(), String> {
+        let url = reqwest::Url::parse(&self.url)
+            .map_err(|e| format!("Invalid callback URL '{}': {}", self.url, e))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(format!("Unsupported callback URL scheme '{}'", url.scheme()));
+        }
+        let Some(host) = callback_host(&url) else {
+            return Err(format!("Callback URL '{}' has no host", self.url));
+        };
+        if is_allowed(&host, allowed_hosts) {
+            return Ok(());
+        }
+        let internal = match host.parse::<IpAddr>() {
+            Ok(ip) => is_internal(ip),
+            Err(_) => host == "localhost" || host.ends_with(".localhost"),
+        };
+        if internal {
+            return Err(format!("Callback URL '{}' points to an internal address", self.url));
+        }
+        Ok(())
+    }
+}
+
+/// Host of a callback URL, lowercase, without IPv6 brackets or a trailing dot.
+fn callback_host(url: &reqwest::Url) -> Option<String> {
+    let host = url.host_str()?;
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    Some(host.trim_end_matches('.').to_ascii_lowercase())
+}
+
+/// Whether a host is in the callback allowlist.
+fn is_allowed(host: &str, allowed_hosts: &[String]) -> bool {
+    allowed_hosts.iter().any(|allowed| {
+        allowed
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .eq_ignore_ascii_case(host)
+    })
+}
+
+/// Whether an address is loopback, private, link-local or otherwise not
+/// reachable from the internet.
+fn is_internal(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || a == 0
+                // Shared address space (carrier-grade NAT), 100.64.0.0/10
+                || (a == 100 && (b & 0xc0) == 64)
+        }
+        IpAddr::V6(ip) => {
+            if let Some(v4) = ip.to_ipv4_mapped() {
+                return is_internal(IpAddr::V4(v4));
+            }
+            let first = ip.segments()[0];
+            ip.is_loopback()
+                || ip.is_unspecified()
+                // Unique local, fc00::/7
+                || (first & 0xfe00) == 0xfc00
+                // Link-local, fe80::/10
+                || (first & 0xffc0) == 0xfe80
+        }
+    }
+}
+
+/// Refuse a callback whose host resolves to an internal address, unless it
+/// is allowlisted. Resolution failures are left to the request itself.
+async fn check_resolved(callback: &TaskCallback, allowed_hosts: &[String]) -> Result<(), String> {
+    callback.validate(allowed_hosts)?;
+    let Ok(url) = reqwest::Url::parse(&callback.url) else {
+        return Ok(());
+    };
+    let Some(host) = callback_host(&url) else {
+        return Ok(());
+    };
+    if is_allowed(&host, allowed_hosts) {
+        return Ok(());
+    }
+    let port = url.port_or_known_default().unwrap_or(80);
+    let Ok(addrs) = tokio::net::lookup_host((host.as_str(), port)).await else {
+        return Ok(());
+    };
+    for addr in addrs {
+        if is_internal(addr.ip()) {
+            return Err(format!(
+                "Callback host '{}' resolves to internal address {}",
+                host,
+                addr.ip()
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Lifecycle event kinds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CallbackEventKind {
+    /// The agent started working on the task.
+    Started,
+    /// The agent finished a turn.
+    TurnCompleted,
+    /// The agent waits for a human approval.
+    NeedsApproval,
+    /// The task finished; `data` holds its outcome.
+    Finished,
+}
+
+impl CallbackEventKind {
+    /// Name sent in the event header.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Started => "started",
+            Self::TurnCompleted => "turn_completed",
+            Self::NeedsApproval => "needs_approval",
+            Self::Finished => "finished",
+        }
+    }
+}
+
+/// A lifecycle event posted to a callback.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CallbackEvent {
+    /// Event ID, the same on every retry.
+    pub id: Uuid,
+    /// Event kind.
+    pub event: CallbackEventKind,
+    /// Task the event belongs to.
+    pub task_id: Uuid,
+    /// Agent session of the task.
+    pub session_id: String,
+    /// When the event happened.
+    pub at: DateTime<Utc>,
+    /// Event details.
+    #[serde(default)]
+    pub data: serde_json::Value,
+}
+
+/// A task whose events are being delivered.
+struct CallbackStream {
+    session_id: String,
+    tx: mpsc::UnboundedSender<CallbackEvent>,
+}
+
+/// Posts task lifecycle events to their callbacks.
+pub struct CallbackDispatcher {
+    client: reqwest::Client,
+    config: CallbackConfig,
+    streams: DashMap<Uuid, CallbackStream>,
+    sessions: DashMap<String, Uuid>,
+}
+
+impl CallbackDispatcher {
+    /// Create a dispatcher.
+    pub fn new(config: CallbackConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            // A redirect could lead to an internal address
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap_or_default();
+        Self {
+            client,
+            config,
+            streams: DashMap::new(),
+            sessions: DashMap::new(),
+        }
+    }
+
+    /// Check a task's callback against the dispatcher's allowlist.
+    pub fn validate(&self, callback: &TaskCallback) -> Result<(), String> {
+        callback.validate(&self.config.allowed_hosts)
+    }
+
+    /// Open a task's delivery queue and post its `started` event.
+    ///
+    /// Tasks without a callback are ignored.
+    pub fn started(&self, task: &Task) {
+        if self.open(task) {
+            self.emit(
+                task.id,
+                CallbackEventKind::Started,
+                serde_json::json!({"task_type": task.task_type}),
+            );
+        }
+    }
+
+    /// Post `finished` for a task that ended without starting, e.g. dropped
+    /// from a full queue.
+    pub fn abandoned(&self, task: &Task, outcome: &TaskOutcome) {
+        if self.open(task) {
+            self.finished(task.id, outcome);
+        }
+    }
+
+    /// Open a task's delivery queue; false if it has no callback.
+    fn open(&self, task: &Task) -> bool {
+        let Some(callback) = task.callback.clone() else {
+            return false;
+        };
+        // Same session the agent handler runs the task in
+        let session_id = task
+            .payload
+            .get("session_id")
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .or_else(|| task.correlation_id.clone())
+            .unwrap_or_else(|| task.id.to_string());
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(deliver_all(
+            self.client.clone(),
+            self.config.clone(),
+            callback,
+            rx,
+        ));
+        self.sessions.insert(session_id.clone(), task.id);
+        self.streams.insert(task.id, CallbackStream { session_id, tx });
+        true
+    }
+
+    /// Queue an event for a task; ignored if the task has no open callback.
+    pub fn emit(&self, task_id: Uuid, kind: CallbackEventKind, data: serde_json::Value) {
+        let Some(stream) = self.streams.get(&task_id) else {
+            return;
+        };
+        let event = CallbackEvent {
+            id: Uuid::new_v4(),
+            event: kind,
+            task_id,
+            session_id: stream.session_id.clone(),
+            at: Utc::now(),
+            data,
+        };
+        let _ = stream.tx.send(event);
+    }
+
+    /// Queue an event for the task running an agent session.
+    ///
+    /// Used by agent-side hooks (turns, approvals) that only know the session.
+    pub fn emit_for_session(
+        &self,
+        session_id: &str,
+        kind: CallbackEventKind,
+        data: serde_json::Value,
+    ) {
+        let Some(task_id) = self.sessions.get(session_id).map(|id| *id) else {
+            return;
+        };
+        self.emit(task_id, kind, data);
+    }
+
+    /// Post a task's `finished` event and close its delivery queue.
+    ///
+    /// Queued events are still delivered after the queue closes.
+    pub fn finished(&self, task_id: Uuid, outcome: &TaskOutcome) {
+        self.emit(
+            task_id,
+            CallbackEventKind::Finished,
+            serde_json::to_value(outcome).unwrap_or_default(),
+        );
+        if let Some((_, stream)) = self.streams.remove(&task_id) {
+            self.sessions
+                .remove_if(&stream.session_id, |_, id| *id == task_id);
+        }
+    }
+
+    /// Number of tasks with an open callback.
+    pub fn open_callbacks(&self) -> usize {
+        self.streams.len()
+    }
+}
+
+impl Default for CallbackDispatcher {
+    fn default() -> Self {
+        Self::new(CallbackConfig::default())
+    }
+}
+
+/// Deliver a task's events in order until its queue closes.
+async fn deliver_all(
+    client: reqwest::Client,
+    config: CallbackConfig,
+    callback: TaskCallback,
+    mut rx: mpsc::UnboundedReceiver<CallbackEvent>,
+) {
+    while let Some(event) = rx.recv().await {
+        deliver(&client, &config, &callback, &event).await;
+    }
+}
+
+/// Post one event, retrying transient failures.
+async fn deliver(
+    client: &reqwest::Client,
+    config: &CallbackConfig,
+    callback: &TaskCallback,
+    event: &CallbackEvent,
+) -> bool {
+    let body = match serde_json::to_string(event) {
+        Ok(body) => body,
+        Err(e) => {
+            warn!("Failed to serialize callback event {}: {}", event.id, e);
+            return false;
+        }
+    };
+    if let Err(e) = check_resolved(callback, &config.allowed_hosts).await {
+        warn!(
+            "Callback {} for task {} refused: {}",
+            event.event.as_str(),
+            event.task_id,
+            e
+        );
+        return false;
+    }
+
+    let mut backoff = Duration::from_millis(config.initial_backoff_ms);
+    for attempt in 1..=config.max_attempts.max(1) {
+        let timestamp = Utc::now().timestamp().to_string();
+        let mut request = client
+            .post(&callback.url)
+            .header("Content-Type", "application/json")
+            .header(EVENT_HEADER, event.event.as_str())
+            .header(DELIVERY_HEADER, event.id.to_string())
+            .header(TIMESTAMP_HEADER, &timestamp);
+        if let Some(secret) = &callback.secret {
+            request = request.header(SIGNATURE_HEADER, sign(secret, &timestamp, &body));
+        }
+
+        let retry = match request.body(body.clone()).send().await {
+            Ok(resp) if resp.status().is_success() => {
+                debug!(
+                    "Callback {} for task {} delivered (attempt {})",
+                    event.event.as_str(),
+                    event.task_id,
+                    attempt
+                );
+                return true;
+            }
+            Ok(resp) => {
+                let status = resp.status();
+                warn!(
+                    "Callback {} for task {} answered HTTP {} (attempt {})",
+                    event.event.as_str(),
+                    event.task_id,
+                    status,
+                    attempt
+                );
+                status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+            }
+            Err(e) => {
+                warn!(
+                    "Callback {} for task {} failed (attempt {}): {}",
+                    event.event.as_str(),
+                    event.task_id,
+                    attempt,
+                    e
+                );
+                true
+            }
+        };
+        if !retry || attempt >= config.max_attempts {
+            break;
+        }
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(Duration::from_millis(config.max_backoff_ms));
+    }
+
+    warn!(
+        "Giving up on callback {} for task {}",
+        event.event.as_str(),
+        event.task_id
+    );
+    false
+}
+
+/// Signature header value for a request body.
+pub fn sign(secret: &str, timestamp: &str, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+#[cfg(test)]
+#[path = "callback_tests.rs"]
+mod tests;
@@ -0,0 +1,189 @@
+use super::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::post;
+use axum::Router;
+
+/// Events received by the test endpoint, with their headers.
+#[derive(Clone, Default)]
+struct Receiver {
+    events: Arc<tokio::sync::Mutex<Vec<(HeaderMap, String)>>>,
+    /// Requests answered with 503 before accepting.
+    fail_first: Arc<AtomicUsize>,
+}
+
+async fn receive(State(receiver): State<Receiver>, headers: HeaderMap, body: String) -> StatusCode {
+    if receiver
+        .fail_first
+        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+        .is_ok()
+    {
+        return StatusCode::SERVICE_UNAVAILABLE;
+    }
+    receiver.events.lock().await.push((headers, body));
+    StatusCode::OK
+}
+
+async fn serve(receiver: Receiver) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let app = Router::new()
+        .route("/hook", post(receive))
+        .with_state(receiver);
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}/hook", addr)
+}
+
+fn fast_config() -> CallbackConfig {
+    CallbackConfig {
+        max_attempts: 3,
+        initial_backoff_ms: 10,
+        max_backoff_ms: 20,
+        timeout_secs: 5,
+        allowed_hosts: vec!["127.0.0.1".to_string()],
+    }
+}
+
+async fn wait_for_events(receiver: &Receiver, count: usize) -> Vec<(HeaderMap, CallbackEvent)> {
+    for _ in 0..200 {
+        let events = receiver.events.lock().await;
+        if events.len() >= count {
+            return events
+                .iter()
+                .map(|(headers, body)| (headers.clone(), serde_json::from_str(body).unwrap()))
+                .collect();
+        }
+        drop(events);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("callback events not received");
+}
+
+#[test]
+fn test_sign() {
+    let signature = sign("secret", "1700000000", r#"{"a":1}"#);
+    assert!(signature.starts_with("sha256="));
+    assert_eq!(signature.len(), "sha256=".len() + 64);
+    assert_eq!(signature, sign("secret", "1700000000", r#"{"a":1}"#));
+    assert_ne!(signature, sign("other", "1700000000", r#"{"a":1}"#));
+    assert_ne!(signature, sign("secret", "1700000001", r#"{"a":1}"#));
+}
+
+#[test]
+fn test_validate_url() {
+    assert!(TaskCallback::new("https://example.com/hook").validate(&[]).is_ok());
+    assert!(TaskCallback::new("ftp://example.com/hook").validate(&[]).is_err());
+    assert!(TaskCallback::new("not a url").validate(&[]).is_err());
+}
+
+#[test]
+fn test_validate_refuses_internal_addresses() {
+    for url in [
+        "http://127.0.0.1:8080/hook",
+        "http://localhost/hook",
+        "http://api.localhost/hook",
+        "http://10.0.0.5/hook",
+        "http://192.168.1.1/hook",
+        "http://172.16.0.1/hook",
+        "http://169.254.169.254/latest/meta-data",
+        "http://0.0.0.0/hook",
+        "http://[::1]/hook",
+        "http://[fe80::1]/hook",
+        "http://[fd00::1]/hook",
+        "http://[::ffff:127.0.0.1]/hook",
+    ] {
+        assert!(TaskCallback::new(url).validate(&[]).is_err(), "{}", url);
+    }
+    assert!(TaskCallback::new("http://8.8.8.8/hook").validate(&[]).is_ok());
+
+    let allowed = vec!["127.0.0.1".to_string(), "LOCALHOST".to_string(), "::1".to_string()];
+    assert!(TaskCallback::new("http://127.0.0.1:8080/hook").validate(&allowed).is_ok());
+    assert!(TaskCallback::new("http://localhost/hook").validate(&allowed).is_ok());
+    assert!(TaskCallback::new("http://[::1]/hook").validate(&allowed).is_ok());
+    assert!(TaskCallback::new("http://10.0.0.5/hook").validate(&allowed).is_err());
+}
+
+#[tokio::test]
+async fn test_internal_callback_not_delivered() {
+    let receiver = Receiver::default();
+    let url = serve(receiver.clone()).await;
+    let config = CallbackConfig {
+        allowed_hosts: Vec::new(),
+        ..fast_config()
+    };
+    let dispatcher = CallbackDispatcher::new(config);
+
+    let task = Task::new("agent:execute", serde_json::json!({}))
+        .with_callback(TaskCallback::new(url));
+    dispatcher.started(&task);
+    dispatcher.finished(task.id, &TaskOutcome::succeeded(None));
+
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert!(receiver.events.lock().await.is_empty());
+}
+
+#[tokio::test]
+async fn test_lifecycle_events_in_order_and_signed() {
+    let receiver = Receiver::default();
+    let url = serve(receiver.clone()).await;
+    let dispatcher = CallbackDispatcher::new(fast_config());
+
+    let task = Task::new("agent:execute", serde_json::json!({"session_id": "s1"}))
+        .with_callback(TaskCallback::new(url).with_secret("secret"));
+    dispatcher.started(&task);
+    dispatcher.emit_for_session("s1", CallbackEventKind::TurnCompleted, serde_json::json!({"turn": 1}));
+    dispatcher.emit_for_session("other", CallbackEventKind::TurnCompleted, serde_json::json!({}));
+    dispatcher.finished(task.id, &TaskOutcome::succeeded(Some("done".to_string())));
+    assert_eq!(dispatcher.open_callbacks(), 0);
+
+    let events = wait_for_events(&receiver, 3).await;
+    let kinds: Vec<_> = events.iter().map(|(_, e)| e.event).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            CallbackEventKind::Started,
+            CallbackEventKind::TurnCompleted,
+            CallbackEventKind::Finished
+        ]
+    );
+    assert!(events.iter().all(|(_, e)| e.task_id == task.id && e.session_id == "s1"));
+    assert_eq!(events[2].1.data["response"], "done");
+
+    let bodies = receiver.events.lock().await;
+    for (headers, body) in bodies.iter() {
+        let timestamp = headers[TIMESTAMP_HEADER].to_str().unwrap();
+        assert_eq!(headers[SIGNATURE_HEADER].to_str().unwrap(), sign("secret", timestamp, body));
+    }
+    assert_eq!(bodies[0].0[EVENT_HEADER], "started");
+}
+
+#[tokio::test]
+async fn test_retries_transient_failures() {
+    let receiver = Receiver::default();
+    receiver.fail_first.store(2, Ordering::SeqCst);
+    let url = serve(receiver.clone()).await;
+    let dispatcher = CallbackDispatcher::new(fast_config());
+
+    let task = Task::new("agent:execute", serde_json::json!({}))
+        .with_callback(TaskCallback::new(url));
+    dispatcher.started(&task);
+
+    let events = wait_for_events(&receiver, 1).await;
+    assert_eq!(events[0].1.event, CallbackEventKind::Started);
+    // Without a secret nothing is signed; the session falls back to the task ID
+    assert!(!events[0].0.contains_key(SIGNATURE_HEADER));
+    assert_eq!(events[0].1.session_id, task.id.to_string());
+}
+
+#[tokio::test]
+async fn test_task_without_callback_ignored() {
+    let dispatcher = CallbackDispatcher::default();
+    let task = Task::new("agent:execute", serde_json::json!({"session_id": "s1"}));
+    dispatcher.started(&task);
+    dispatcher.emit_for_session("s1", CallbackEventKind::NeedsApproval, serde_json::json!({}));
+    dispatcher.finished(task.id, &TaskOutcome::failed("boom"));
+    assert_eq!(dispatcher.open_callbacks(), 0);
+}
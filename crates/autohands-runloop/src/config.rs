@@ -1,5 +1,6 @@
 //! Configuration for the RunLoop.
 
+use std::collections::HashMap;
 use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
@@ -30,6 +31,18 @@ pub struct RunLoopConfig {
     /// Checkpoint interval in seconds (0 = disabled).
     #[serde(default)]
     pub checkpoint_interval_secs: u64,
+
+    /// Automatic mode switching.
+    #[serde(default)]
+    pub mode_policy: ModePolicyConfig,
+
+    /// Task lifecycle callback delivery.
+    #[serde(default)]
+    pub callbacks: CallbackConfig,
+
+    /// Event journal.
+    #[serde(default)]
+    pub journal: JournalConfig,
 }
 
 fn default_metrics_enabled() -> bool {
@@ -45,6 +58,113 @@ impl Default for RunLoopConfig {
             retry: RetryConfig::default(),
             metrics_enabled: true,
             checkpoint_interval_secs: 60,
+            mode_policy: ModePolicyConfig::default(),
+            callbacks: CallbackConfig::default(),
+            journal: JournalConfig::default(),
+        }
+    }
+}
+
+/// Automatic mode switching configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModePolicyConfig {
+    /// Whether the RunLoop switches between Default, AgentProcessing and
+    /// Background mode on its own.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Idle time before dropping to Background mode, in seconds (0 = never).
+    #[serde(default = "default_background_after_idle_secs")]
+    pub background_after_idle_secs: u64,
+}
+
+fn default_background_after_idle_secs() -> u64 {
+    300
+}
+
+impl Default for ModePolicyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            background_after_idle_secs: default_background_after_idle_secs(),
+        }
+    }
+}
+
+/// Task lifecycle callback delivery configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallbackConfig {
+    /// Attempts per event before it is dropped.
+    #[serde(default = "default_callback_max_attempts")]
+    pub max_attempts: u32,
+
+    /// Wait before the first retry, in milliseconds; doubles per retry.
+    #[serde(default = "default_callback_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+
+    /// Longest wait between retries, in milliseconds.
+    #[serde(default = "default_callback_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+
+    /// Request timeout, in seconds.
+    #[serde(default = "default_callback_timeout_secs")]
+    pub timeout_secs: u64,
+
+    /// Hosts callbacks may reach although they are internal (loopback,
+    /// private or link-local), e.g. `127.0.0.1` or `hooks.internal`.
+    #[serde(default)]
+    pub allowed_hosts: Vec<String>,
+}
+
+fn default_callback_max_attempts() -> u32 {
+    5
+}
+
+fn default_callback_initial_backoff_ms() -> u64 {
+    500
+}
+
+fn default_callback_max_backoff_ms() -> u64 {
+    30_000
+}
+
+fn default_callback_timeout_secs() -> u64 {
+    10
+}
+
+impl Default for CallbackConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_callback_max_attempts(),
+            initial_backoff_ms: default_callback_initial_backoff_ms(),
+            max_backoff_ms: default_callback_max_backoff_ms(),
+            timeout_secs: default_callback_timeout_secs(),
+            allowed_hosts: Vec::new(),
+        }
+    }
+}
+
+/// Event journal configuration (see [`crate::journal`]).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalConfig {
+    /// Whether the RunLoop records its events.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Number of entries kept; the oldest are dropped first.
+    #[serde(default = "default_journal_capacity")]
+    pub capacity: usize,
+}
+
+fn default_journal_capacity() -> usize {
+    10_000
+}
+
+impl Default for JournalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            capacity: default_journal_capacity(),
         }
     }
 }
@@ -80,6 +200,22 @@ pub struct TaskQueueConfig {
     /// Path for task persistence (empty = in-memory only).
     #[serde(default)]
     pub persist_path: Option<String>,
+
+    /// Bound applied to each task source without its own entry in `sources`.
+    #[serde(default)]
+    pub per_source: SourceQueueConfig,
+
+    /// Bounds for specific sources, keyed by source name (e.g. "webhook").
+    #[serde(default)]
+    pub sources: HashMap<String, SourceQueueConfig>,
+
+    /// Directory for tasks spilled by the `spill_to_disk` policy.
+    #[serde(default)]
+    pub spill_dir: Option<String>,
+
+    /// Weighted-fair dispatch across task sources.
+    #[serde(default)]
+    pub fairness: FairnessConfig,
 }
 
 fn default_max_pending_tasks() -> usize {
@@ -91,10 +227,111 @@ impl Default for TaskQueueConfig {
         Self {
             max_pending_tasks: default_max_pending_tasks(),
             persist_path: None,
+            per_source: SourceQueueConfig::default(),
+            sources: HashMap::new(),
+            spill_dir: None,
+            fairness: FairnessConfig::default(),
+        }
+    }
+}
+
+impl TaskQueueConfig {
+    /// Bound for a task source.
+    pub fn source(&self, name: &str) -> &SourceQueueConfig {
+        self.sources.get(name).unwrap_or(&self.per_source)
+    }
+}
+
+/// What happens to a task arriving at a full queue.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverflowPolicy {
+    /// Drop the source's oldest pending task to make room.
+    DropOldest,
+    /// Reject the new task with an error.
+    #[default]
+    Reject,
+    /// Write the new task to disk; it is queued once the source has room.
+    SpillToDisk,
+}
+
+/// Bound on the pending tasks of one source.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SourceQueueConfig {
+    /// Maximum pending tasks from the source (0 = only `max_pending_tasks`).
+    #[serde(default)]
+    pub max_pending: usize,
+
+    /// Policy when the source, or the whole queue, is full.
+    #[serde(default)]
+    pub overflow: OverflowPolicy,
+}
+
+/// Weighted-fair dispatch across task sources.
+///
+/// Sources share dispatch in proportion to their weight, so a burst from one
+/// source (e.g. a file watcher storm) cannot starve the others. Within a
+/// source tasks keep their priority order; across sources a task's priority
+/// doubles its source's share per level (Low, Normal, High). Critical and
+/// System tasks always go first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FairnessConfig {
+    /// Whether dispatch is shared across sources; when off, tasks are
+    /// dispatched strictly by priority, then age.
+    #[serde(default = "default_fairness_enabled")]
+    pub enabled: bool,
+
+    /// Weight of sources without an entry in `weights`.
+    #[serde(default = "default_source_weight")]
+    pub default_weight: u32,
+
+    /// Weights by source name. Custom sources named `<kind>:<id>` (e.g.
+    /// `channel:slack`) fall back to the entry for `<kind>`.
+    #[serde(default = "default_source_weights")]
+    pub weights: HashMap<String, u32>,
+}
+
+fn default_fairness_enabled() -> bool {
+    true
+}
+
+fn default_source_weight() -> u32 {
+    1
+}
+
+fn default_source_weights() -> HashMap<String, u32> {
+    // Interactive sources get a larger share than background ones
+    [("user", 4), ("websocket", 4), ("channel", 4), ("webhook", 2), ("agent", 2)]
+        .into_iter()
+        .map(|(name, weight)| (name.to_string(), weight))
+        .collect()
+}
+
+impl Default for FairnessConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_fairness_enabled(),
+            default_weight: default_source_weight(),
+            weights: default_source_weights(),
         }
     }
 }
 
+impl FairnessConfig {
+    /// Weight of a task source (at least 1).
+    pub fn weight(&self, source: &str) -> u32 {
+        self.weights
+            .get(source)
+            .or_else(|| {
+                let (kind, _) = source.split_once(':')?;
+                self.weights.get(kind)
+            })
+            .copied()
+            .unwrap_or(self.default_weight)
+            .max(1)
+    }
+}
+
 /// Task chain configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskChainConfig {
@@ -174,6 +411,37 @@ mod tests {
         assert_eq!(config.retry_delay(), Duration::from_millis(500));
     }
 
+    #[test]
+    fn test_source_queue_config() {
+        let config: TaskQueueConfig = serde_json::from_str(
+            r#"{"per_source": {"max_pending": 100},
+                "sources": {"webhook": {"max_pending": 10, "overflow": "drop_oldest"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(config.max_pending_tasks, 10000);
+        assert_eq!(config.source("webhook").max_pending, 10);
+        assert_eq!(config.source("webhook").overflow, OverflowPolicy::DropOldest);
+        assert_eq!(config.source("user").max_pending, 100);
+        assert_eq!(config.source("user").overflow, OverflowPolicy::Reject);
+    }
+
+    #[test]
+    fn test_fairness_config() {
+        let config: TaskQueueConfig = serde_json::from_str(
+            r#"{"fairness": {"default_weight": 3, "weights": {"channel": 5, "file_watcher": 0}}}"#,
+        )
+        .unwrap();
+        assert!(config.fairness.enabled);
+        assert_eq!(config.fairness.weight("channel:slack"), 5);
+        assert_eq!(config.fairness.weight("channel"), 5);
+        assert_eq!(config.fairness.weight("file_watcher"), 1);
+        assert_eq!(config.fairness.weight("user"), 3);
+
+        let defaults = FairnessConfig::default();
+        assert_eq!(defaults.weight("user"), 4);
+        assert_eq!(defaults.weight("scheduler"), 1);
+    }
+
     #[test]
     fn test_config_serialization() {
         let config = RunLoopConfig::default();
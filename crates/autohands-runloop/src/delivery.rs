@@ -0,0 +1,273 @@
+//! Task result delivery targets.
+//!
+//! A task may declare several places its final result should go besides the
+//! channel it came from, each with its own formatting. The RunLoop fans the
+//! result out once the agent finishes and records a [`DeliveryStatus`] per
+//! target in the [`DeliveryLog`].
+
+use std::collections::VecDeque;
+
+use autohands_protocols::channel::{Attachment, ChannelCapabilities, OutboundMessage, ReplyAddress};
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Number of tasks whose delivery statuses are kept.
+pub const DELIVERY_LOG_CAPACITY: usize = 1000;
+
+/// How a result is rendered for a target.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryFormat {
+    /// Agent output as-is (it is usually markdown).
+    #[default]
+    Markdown,
+    /// Markdown markup stripped, for channels that show raw text.
+    Plain,
+    /// A JSON document with the task ID and the result.
+    Json,
+    /// The result attached as a file, with a short note as the message.
+    File,
+}
+
+/// A place a task's final result is delivered to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeliveryTarget {
+    /// Channel and recipient.
+    #[serde(flatten)]
+    pub address: ReplyAddress,
+    /// Rendering for this target.
+    #[serde(default)]
+    pub format: DeliveryFormat,
+    /// Attachment name for [`DeliveryFormat::File`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+}
+
+impl DeliveryTarget {
+    /// Create a markdown target.
+    pub fn new(channel_id: impl Into<String>, target: impl Into<String>) -> Self {
+        Self::from(ReplyAddress::new(channel_id, target))
+    }
+
+    /// Set the format.
+    pub fn with_format(mut self, format: DeliveryFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Set the attachment name (implies [`DeliveryFormat::File`]).
+    pub fn with_file_name(mut self, name: impl Into<String>) -> Self {
+        self.format = DeliveryFormat::File;
+        self.file_name = Some(name.into());
+        self
+    }
+
+    /// Render a result for this target.
+    ///
+    /// Files fall back to markdown text when the channel cannot take them,
+    /// and text is cut to the channel's maximum message length. JSON is
+    /// kept valid by cutting the result inside it.
+    pub fn render(
+        &self,
+        task_id: Uuid,
+        result: &str,
+        capabilities: &ChannelCapabilities,
+    ) -> OutboundMessage {
+        let format = match self.format {
+            DeliveryFormat::File if !capabilities.supports_files => DeliveryFormat::Markdown,
+            format => format,
+        };
+        let message = match format {
+            DeliveryFormat::Markdown => OutboundMessage::text(result),
+            DeliveryFormat::Plain => OutboundMessage::text(strip_markdown(result)),
+            DeliveryFormat::Json => OutboundMessage::text(render_json(
+                task_id,
+                result,
+                capabilities.max_message_length,
+            )),
+            DeliveryFormat::File => {
+                let name = self
+                    .file_name
+                    .clone()
+                    .unwrap_or_else(|| format!("result-{}.md", task_id));
+                OutboundMessage::text(format!("Result attached: {}", name)).with_attachment(
+                    Attachment {
+                        content_type: content_type_for(&name).to_string(),
+                        name,
+                        url: None,
+                        data: Some(result.as_bytes().to_vec()),
+                    },
+                )
+            }
+        };
+        let mut message = message.with_metadata("format", serde_json::json!(format));
+        if let Some(max) = capabilities.max_message_length {
+            if format != DeliveryFormat::Json {
+                message.content = truncate(&message.content, max);
+            }
+        }
+        message
+    }
+}
+
+impl From<ReplyAddress> for DeliveryTarget {
+    fn from(address: ReplyAddress) -> Self {
+        Self {
+            address,
+            format: DeliveryFormat::default(),
+            file_name: None,
+        }
+    }
+}
+
+/// Outcome of delivering a result to one target.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeliveryStatus {
+    /// Channel the result was sent to.
+    pub channel_id: String,
+    /// Recipient within the channel.
+    pub target: String,
+    /// Format used.
+    pub format: DeliveryFormat,
+    /// Whether the channel accepted the message.
+    pub delivered: bool,
+    /// Channel message ID on success.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<String>,
+    /// Error on failure.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// When delivery was attempted.
+    pub timestamp: DateTime<Utc>,
+}
+
+impl DeliveryStatus {
+    /// Successful delivery.
+    pub fn delivered(target: &DeliveryTarget, message_id: impl Into<String>) -> Self {
+        Self::new(target, Some(message_id.into()), None)
+    }
+
+    /// Failed delivery.
+    pub fn failed(target: &DeliveryTarget, error: impl Into<String>) -> Self {
+        Self::new(target, None, Some(error.into()))
+    }
+
+    fn new(target: &DeliveryTarget, message_id: Option<String>, error: Option<String>) -> Self {
+        Self {
+            channel_id: target.address.channel_id.clone(),
+            target: target.address.target.clone(),
+            format: target.format,
+            delivered: error.is_none(),
+            message_id,
+            error,
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+/// Recent per-target delivery statuses, keyed by task.
+///
+/// Only the last [`DELIVERY_LOG_CAPACITY`] tasks are kept.
+#[derive(Default)]
+pub struct DeliveryLog {
+    entries: Mutex<VecDeque<(Uuid, Vec<DeliveryStatus>)>>,
+}
+
+impl DeliveryLog {
+    /// Create an empty log.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the statuses for a task, replacing earlier ones.
+    pub fn record(&self, task_id: Uuid, statuses: Vec<DeliveryStatus>) {
+        let mut entries = self.entries.lock();
+        entries.retain(|(id, _)| *id != task_id);
+        if entries.len() >= DELIVERY_LOG_CAPACITY {
+            entries.pop_front();
+        }
+        entries.push_back((task_id, statuses));
+    }
+
+    /// Statuses recorded for a task.
+    pub fn get(&self, task_id: Uuid) -> Option<Vec<DeliveryStatus>> {
+        self.entries
+            .lock()
+            .iter()
+            .find(|(id, _)| *id == task_id)
+            .map(|(_, statuses)| statuses.clone())
+    }
+
+    /// Number of tasks in the log.
+    pub fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    /// Whether the log is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.lock().is_empty()
+    }
+}
+
+fn content_type_for(name: &str) -> &'static str {
+    match name.rsplit_once('.').map(|(_, ext)| ext.to_ascii_lowercase()) {
+        Some(ext) if ext == "md" => "text/markdown",
+        Some(ext) if ext == "json" => "application/json",
+        Some(ext) if ext == "html" || ext == "htm" => "text/html",
+        // The result is always text, whatever the name says.
+        _ => "text/plain",
+    }
+}
+
+/// Remove common markdown markup: headings, emphasis, inline code and fences.
+fn strip_markdown(text: &str) -> String {
+    text.lines()
+        .filter(|line| !line.trim_start().starts_with("```"))
+        .map(|line| {
+            line.trim_start_matches('#')
+                .trim_start()
+                .replace("**", "")
+                .replace("__", "")
+                .replace('`', "")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The JSON document of a result, the result cut so that the document fits
+/// in `max_chars` when the task ID and field names leave room for it.
+fn render_json(task_id: Uuid, result: &str, max_chars: Option<usize>) -> String {
+    let render = |result: &str| {
+        serde_json::json!({"task_id": task_id, "result": result}).to_string()
+    };
+    let mut json = render(result);
+    let Some(max) = max_chars else {
+        return json;
+    };
+    // Escaping makes the document grow faster than the result, so cut by
+    // the excess until it fits
+    let mut keep = result.chars().count();
+    loop {
+        let len = json.chars().count();
+        if len <= max || keep == 0 {
+            return json;
+        }
+        keep = keep.saturating_sub(len - max);
+        json = render(&truncate(result, keep));
+    }
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    cut.push('…');
+    cut
+}
+
+#[cfg(test)]
+#[path = "delivery_tests.rs"]
+mod tests;
@@ -0,0 +1,104 @@
+use super::*;
+
+fn caps(supports_files: bool, max_message_length: Option<usize>) -> ChannelCapabilities {
+    ChannelCapabilities {
+        supports_files,
+        max_message_length,
+        ..Default::default()
+    }
+}
+
+const RESULT: &str = "# Report\n\nAll **green**, see `ci`.\n```\nlog\n```";
+
+#[test]
+fn test_markdown_is_verbatim() {
+    let msg = DeliveryTarget::new("web", "c1").render(Uuid::nil(), RESULT, &caps(false, None));
+    assert_eq!(msg.content, RESULT);
+    assert_eq!(msg.metadata["format"], "markdown");
+}
+
+#[test]
+fn test_plain_strips_markup() {
+    let msg = DeliveryTarget::new("web", "c1")
+        .with_format(DeliveryFormat::Plain)
+        .render(Uuid::nil(), RESULT, &caps(false, None));
+    assert_eq!(msg.content, "Report\n\nAll green, see ci.\nlog");
+}
+
+#[test]
+fn test_json_wraps_result() {
+    let id = Uuid::new_v4();
+    let msg = DeliveryTarget::new("hook", "x")
+        .with_format(DeliveryFormat::Json)
+        .render(id, "done", &caps(false, None));
+    let body: serde_json::Value = serde_json::from_str(&msg.content).unwrap();
+    assert_eq!(body["task_id"], id.to_string());
+    assert_eq!(body["result"], "done");
+}
+
+#[test]
+fn test_json_truncated_inside_document() {
+    let id = Uuid::new_v4();
+    let result = "line \"quoted\"\n".repeat(20);
+    let msg = DeliveryTarget::new("sms", "1")
+        .with_format(DeliveryFormat::Json)
+        .render(id, &result, &caps(false, Some(100)));
+    assert!(msg.content.chars().count() <= 100);
+    let body: serde_json::Value = serde_json::from_str(&msg.content).unwrap();
+    assert_eq!(body["task_id"], id.to_string());
+    let cut = body["result"].as_str().unwrap();
+    assert!(cut.ends_with('…'));
+    assert!(result.starts_with(cut.trim_end_matches('…')));
+}
+
+#[test]
+fn test_file_attachment_and_fallback() {
+    let target = DeliveryTarget::new("mail", "ops@example.com").with_file_name("report.md");
+    let msg = target.render(Uuid::nil(), RESULT, &caps(true, None));
+    assert_eq!(msg.content, "Result attached: report.md");
+    assert_eq!(msg.attachments[0].content_type, "text/markdown");
+    assert_eq!(msg.attachments[0].data.as_deref(), Some(RESULT.as_bytes()));
+
+    let msg = target.render(Uuid::nil(), RESULT, &caps(false, None));
+    assert!(msg.attachments.is_empty());
+    assert_eq!(msg.content, RESULT);
+    assert_eq!(msg.metadata["format"], "markdown");
+}
+
+#[test]
+fn test_truncates_to_channel_limit() {
+    let msg = DeliveryTarget::new("sms", "1").render(Uuid::nil(), "abcdefgh", &caps(false, Some(5)));
+    assert_eq!(msg.content, "abcd…");
+}
+
+#[test]
+fn test_target_deserialize() {
+    let target: DeliveryTarget = serde_json::from_value(serde_json::json!({
+        "channel_id": "slack",
+        "target": "#reports",
+        "format": "plain"
+    }))
+    .unwrap();
+    assert_eq!(target.address, ReplyAddress::new("slack", "#reports"));
+    assert_eq!(target.format, DeliveryFormat::Plain);
+    assert!(target.file_name.is_none());
+}
+
+#[test]
+fn test_log_replaces_and_evicts() {
+    let log = DeliveryLog::new();
+    let target = DeliveryTarget::new("web", "c1");
+    let first = Uuid::new_v4();
+    log.record(first, vec![DeliveryStatus::failed(&target, "offline")]);
+    log.record(first, vec![DeliveryStatus::delivered(&target, "m1")]);
+    assert_eq!(log.len(), 1);
+    let statuses = log.get(first).unwrap();
+    assert!(statuses[0].delivered);
+    assert_eq!(statuses[0].message_id.as_deref(), Some("m1"));
+
+    for _ in 0..DELIVERY_LOG_CAPACITY {
+        log.record(Uuid::new_v4(), Vec::new());
+    }
+    assert_eq!(log.len(), DELIVERY_LOG_CAPACITY);
+    assert!(log.get(first).is_none());
+}
@@ -38,6 +38,14 @@ pub enum RunLoopError {
     #[error("Task processing error: {0}")]
     TaskProcessingError(String),
 
+    /// The RunLoop is draining and refuses new tasks.
+    #[error("RunLoop is draining; new tasks are refused")]
+    Draining,
+
+    /// A source's tasks, or the whole task queue, are at their limit.
+    #[error("Task queue is full for source '{0}'")]
+    QueueFull(String),
+
     /// Channel closed.
     #[error("Channel closed")]
     ChannelClosed,
@@ -0,0 +1,149 @@
+//! Weighted-fair ready queue across task sources.
+//!
+//! Each source has its own priority heap. Dispatch uses stride scheduling:
+//! every source carries a virtual pass, the source with the lowest pass is
+//! served next, and serving a task advances the source's pass by
+//! `STRIDE / weight`. The weight is the source's configured weight doubled
+//! per priority level of the task served, so sources share dispatch in
+//! proportion to their weights while urgent work still gets more of it.
+//! Critical and System tasks bypass the shares and go first.
+//!
+//! A source that becomes active again starts at the current virtual time, so
+//! it cannot bank credit while idle and then monopolize dispatch.
+
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::config::FairnessConfig;
+use crate::task::{PriorityTask, Task, TaskPriority};
+
+/// Pass advance for a task of weight 1.
+const STRIDE: u64 = 1 << 20;
+
+/// Pending tasks of one source.
+#[derive(Default)]
+struct Lane {
+    /// Tasks, highest priority first.
+    heap: BinaryHeap<PriorityTask>,
+    /// Virtual time at which the source is next served.
+    pass: u64,
+}
+
+impl Lane {
+    fn head(&self) -> Option<&Task> {
+        self.heap.peek().map(|pt| &pt.0)
+    }
+}
+
+/// Ready tasks grouped by source, dispatched weighted-fair.
+pub(crate) struct FairQueue {
+    config: FairnessConfig,
+    lanes: HashMap<String, Lane>,
+    /// Pass of the last source served.
+    vtime: u64,
+    len: usize,
+}
+
+impl FairQueue {
+    /// Create an empty queue.
+    pub(crate) fn new(config: FairnessConfig) -> Self {
+        Self {
+            config,
+            lanes: HashMap::new(),
+            vtime: 0,
+            len: 0,
+        }
+    }
+
+    /// Add a ready task.
+    pub(crate) fn push(&mut self, task: Task) {
+        let lane = self.lanes.entry(task.source.name().to_string()).or_default();
+        if lane.heap.is_empty() {
+            lane.pass = lane.pass.max(self.vtime);
+        }
+        lane.heap.push(PriorityTask(task));
+        self.len += 1;
+    }
+
+    /// Remove the next task to dispatch.
+    pub(crate) fn pop(&mut self) -> Option<Task> {
+        let source = self.next_source()?;
+        let lane = self.lanes.get_mut(&source)?;
+        let task = lane.heap.pop()?.0;
+        self.len -= 1;
+
+        if self.config.enabled && task.priority < TaskPriority::Critical {
+            self.vtime = self.vtime.max(lane.pass);
+            let weight = u64::from(self.config.weight(&source)) << (task.priority as u8);
+            lane.pass = lane.pass.max(self.vtime) + STRIDE / weight;
+        }
+        // Idle sources are forgotten once they have no dispatch to pay back
+        let vtime = self.vtime;
+        self.lanes
+            .retain(|_, lane| !lane.heap.is_empty() || lane.pass > vtime);
+        Some(task)
+    }
+
+    /// Source whose head task is dispatched next.
+    fn next_source(&self) -> Option<String> {
+        let heads = self
+            .lanes
+            .iter()
+            .filter_map(|(source, lane)| Some((source, lane, lane.head()?)));
+
+        // Highest priority first, then the oldest
+        let strict = heads.clone().max_by(|(_, _, a), (_, _, b)| {
+            a.priority
+                .cmp(&b.priority)
+                .then_with(|| b.created_at.cmp(&a.created_at))
+        })?;
+        if !self.config.enabled || strict.2.priority >= TaskPriority::Critical {
+            return Some(strict.0.clone());
+        }
+
+        // Lowest pass first; the older head breaks ties
+        heads
+            .min_by(|(_, a, a_head), (_, b, b_head)| {
+                a.pass
+                    .cmp(&b.pass)
+                    .then_with(|| a_head.created_at.cmp(&b_head.created_at))
+            })
+            .map(|(source, _, _)| source.clone())
+    }
+
+    /// Number of ready tasks.
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether no task is ready.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Ready tasks of every source, in no particular order.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &Task> {
+        self.lanes.values().flat_map(|lane| lane.heap.iter().map(|pt| &pt.0))
+    }
+
+    /// Remove a task by ID, returning whether it was queued.
+    pub(crate) fn remove(&mut self, task: &Task) -> bool {
+        let Some(lane) = self.lanes.get_mut(task.source.name()) else {
+            return false;
+        };
+        let before = lane.heap.len();
+        lane.heap.retain(|pt| pt.0.id != task.id);
+        let removed = before - lane.heap.len();
+        self.len -= removed;
+        removed > 0
+    }
+
+    /// Remove every task.
+    pub(crate) fn clear(&mut self) {
+        self.lanes.clear();
+        self.len = 0;
+    }
+}
+
+#[cfg(test)]
+#[path = "fair_queue_tests.rs"]
+mod tests;
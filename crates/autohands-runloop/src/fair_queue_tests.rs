@@ -0,0 +1,117 @@
+use super::*;
+use crate::task::TaskSource;
+
+fn task(name: &str, source: TaskSource, priority: TaskPriority) -> Task {
+    Task::new(name, serde_json::Value::Null)
+        .with_source(source)
+        .with_priority(priority)
+}
+
+fn drain(queue: &mut FairQueue) -> Vec<String> {
+    std::iter::from_fn(|| queue.pop()).map(|t| t.task_type).collect()
+}
+
+#[test]
+fn test_noisy_source_does_not_starve_others() {
+    let mut queue = FairQueue::new(FairnessConfig::default());
+    for i in 0..50 {
+        queue.push(task(&format!("fs{}", i), TaskSource::FileWatcher, TaskPriority::Normal));
+    }
+    for i in 0..5 {
+        queue.push(task(&format!("chat{}", i), TaskSource::Custom("channel:slack".into()), TaskPriority::Normal));
+    }
+    assert_eq!(queue.len(), 55);
+
+    // The channel (weight 4) gets four dispatches for each file watcher one
+    let order = drain(&mut queue);
+    let last_chat = order.iter().rposition(|name| name.starts_with("chat")).unwrap();
+    assert!(last_chat < 8, "chat messages dispatched late: {:?}", order);
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn test_equal_weights_alternate() {
+    let mut config = FairnessConfig::default();
+    config.weights.clear();
+    let mut queue = FairQueue::new(config);
+    for i in 0..3 {
+        queue.push(task(&format!("s{}", i), TaskSource::Scheduler, TaskPriority::Normal));
+    }
+    for i in 0..3 {
+        queue.push(task(&format!("w{}", i), TaskSource::Webhook, TaskPriority::Normal));
+    }
+
+    let order = drain(&mut queue);
+    assert_eq!(order, vec!["s0", "w0", "s1", "w1", "s2", "w2"]);
+}
+
+#[test]
+fn test_priority_order_within_source() {
+    let mut queue = FairQueue::new(FairnessConfig::default());
+    queue.push(task("low", TaskSource::Webhook, TaskPriority::Low));
+    queue.push(task("normal", TaskSource::Webhook, TaskPriority::Normal));
+    queue.push(task("high", TaskSource::Webhook, TaskPriority::High));
+
+    assert_eq!(drain(&mut queue), vec!["high", "normal", "low"]);
+}
+
+#[test]
+fn test_critical_tasks_go_first() {
+    let mut queue = FairQueue::new(FairnessConfig::default());
+    queue.push(task("chat", TaskSource::User, TaskPriority::High));
+    queue.push(task("shutdown", TaskSource::System, TaskPriority::System));
+    queue.push(task("alert", TaskSource::Webhook, TaskPriority::Critical));
+
+    assert_eq!(drain(&mut queue), vec!["shutdown", "alert", "chat"]);
+}
+
+#[test]
+fn test_idle_source_does_not_bank_credit() {
+    let mut config = FairnessConfig::default();
+    config.weights.clear();
+    let mut queue = FairQueue::new(config);
+    for i in 0..10 {
+        queue.push(task(&format!("s{}", i), TaskSource::Scheduler, TaskPriority::Normal));
+    }
+    for _ in 0..5 {
+        queue.pop();
+    }
+
+    // A source arriving late shares from now on instead of catching up
+    for i in 0..3 {
+        queue.push(task(&format!("w{}", i), TaskSource::Webhook, TaskPriority::Normal));
+    }
+    let order = drain(&mut queue);
+    assert_eq!(&order[..6], &["w0", "s5", "w1", "s6", "w2", "s7"]);
+}
+
+#[test]
+fn test_disabled_is_strict_priority() {
+    let config = FairnessConfig {
+        enabled: false,
+        ..Default::default()
+    };
+    let mut queue = FairQueue::new(config);
+    queue.push(task("fs", TaskSource::FileWatcher, TaskPriority::High));
+    queue.push(task("chat", TaskSource::User, TaskPriority::Normal));
+    queue.push(task("fs2", TaskSource::FileWatcher, TaskPriority::High));
+
+    assert_eq!(drain(&mut queue), vec!["fs", "fs2", "chat"]);
+}
+
+#[test]
+fn test_remove_and_clear() {
+    let mut queue = FairQueue::new(FairnessConfig::default());
+    let first = task("a", TaskSource::Webhook, TaskPriority::Normal);
+    queue.push(first.clone());
+    queue.push(task("b", TaskSource::Webhook, TaskPriority::Normal));
+
+    assert!(queue.remove(&first));
+    assert!(!queue.remove(&first));
+    assert_eq!(queue.len(), 1);
+    assert_eq!(queue.iter().count(), 1);
+
+    queue.clear();
+    assert!(queue.is_empty());
+    assert!(queue.pop().is_none());
+}
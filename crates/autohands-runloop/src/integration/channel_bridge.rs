@@ -4,6 +4,21 @@
 //! - Listens for inbound messages from all registered channels
 //! - Converts messages to Tasks and injects them into the RunLoop
 //! - Triggers the RunLoop wakeup mechanism
+//! - Fans a task's final result out to its delivery targets ([`fan_out`])
+//!
+//! A message's `correlation_id` metadata becomes the task's correlation ID
+//! (the message ID is used without one), and replies carry it back in their
+//! `correlation_id` metadata, so one ID traces the whole exchange.
+//!
+//! Each channel may have an [`InboundFilter`] that ignores messages not meant
+//! for the agent, turns slash-style commands into their own tasks and names
+//! further delivery targets for their results, and a [`RateLimit`] that
+//! throttles busy conversations.
+//!
+//! With a [`QuestionService`], a message in a conversation where the agent
+//! asked a question answers it instead of starting a task. If nobody waits
+//! for the answer any more (the server restarted), the reply starts a task
+//! in the asking session, carrying the question and its answer.
 //!
 //! ## Architecture
 //!
@@ -13,12 +28,19 @@
 //! User Output ← Channel ← OutboundMessage ← ChannelRegistry ← Agent ← RunLoop
 //! ```
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use autohands_core::registry::ChannelRegistry;
-use autohands_protocols::channel::{InboundMessage, ReplyAddress};
+use autohands_core::{QuestionService, ReplyRouting};
+use autohands_protocols::question::Question;
+use autohands_protocols::channel::{Channel, InboundMessage, OutboundMessage, ReplyAddress};
+use autohands_runtime::{TraceEvent, TraceEventKind};
 use tracing::{debug, error, info, warn};
 
+use super::channel_filter::{sender, InboundAction, InboundFilter};
+use super::channel_rate_limit::{Admission, ConversationLimiter, RateLimit};
+use crate::delivery::DeliveryStatus;
 use crate::run_loop::RunLoop;
 use crate::task::{Task, TaskPriority, TaskSource};
 
@@ -31,6 +53,12 @@ pub struct ChannelBridge {
     channel_registry: Arc<ChannelRegistry>,
     /// RunLoop for injecting tasks.
     run_loop: Arc<RunLoop>,
+    /// How messages become tasks.
+    config: Arc<ChannelBridgeConfig>,
+    /// Task starts per conversation.
+    limiter: Arc<ConversationLimiter>,
+    /// Questions waiting for replies.
+    questions: Option<Arc<QuestionService>>,
 }
 
 impl ChannelBridge {
@@ -42,15 +70,32 @@ impl ChannelBridge {
         Self {
             channel_registry,
             run_loop,
+            config: Arc::new(ChannelBridgeConfig::default()),
+            limiter: Arc::new(ConversationLimiter::new()),
+            questions: None,
         }
     }
 
+    /// Set how messages become tasks.
+    pub fn with_config(mut self, config: ChannelBridgeConfig) -> Self {
+        self.config = Arc::new(config);
+        self
+    }
+
+    /// Route replies to the questions of this service, and tell it which
+    /// conversation each session talks in.
+    pub fn with_questions(mut self, questions: Arc<QuestionService>) -> Self {
+        self.questions = Some(questions);
+        self
+    }
+
     /// Start listening on all channels.
     ///
     /// This spawns a listener task for each registered channel that:
     /// 1. Subscribes to inbound messages
-    /// 2. Converts messages to Tasks
-    /// 3. Injects tasks into the RunLoop
+    /// 2. Filters messages and converts them to Tasks
+    /// 3. Throttles conversations over their rate limit
+    /// 4. Injects tasks into the RunLoop
     pub async fn start(&self) {
         let channel_ids = self.channel_registry.list_ids();
 
@@ -69,6 +114,9 @@ impl ChannelBridge {
             if let Some(channel) = self.channel_registry.get(&channel_id) {
                 let mut inbound = channel.inbound();
                 let run_loop = self.run_loop.clone();
+                let config = self.config.clone();
+                let limiter = self.limiter.clone();
+                let questions = self.questions.clone();
                 let cid = channel_id.clone();
 
                 tokio::spawn(async move {
@@ -77,7 +125,16 @@ impl ChannelBridge {
                     loop {
                         match inbound.recv().await {
                             Ok(msg) => {
-                                if let Err(e) = handle_inbound_message(&cid, msg, &run_loop).await {
+                                if let Err(e) = handle_inbound_message(
+                                    &channel,
+                                    msg,
+                                    &run_loop,
+                                    &config,
+                                    &limiter,
+                                    questions.as_deref(),
+                                )
+                                .await
+                                {
                                     error!("Failed to handle inbound message: {}", e);
                                 }
                             }
@@ -98,18 +155,49 @@ impl ChannelBridge {
 
 /// Handle an inbound message by converting it to a task.
 async fn handle_inbound_message(
-    channel_id: &str,
+    channel: &Arc<dyn Channel>,
     msg: InboundMessage,
     run_loop: &RunLoop,
+    config: &ChannelBridgeConfig,
+    limiter: &ConversationLimiter,
+    questions: Option<&QuestionService>,
 ) -> Result<(), String> {
+    let channel_id = channel.id().as_str();
     let msg_id = msg.id.clone();
+    let reply_to = msg.reply_to.clone();
     info!(
         "ChannelBridge received message from channel {}: {} (conn: {})",
         channel_id, msg_id, msg.reply_to.target
     );
 
-    // Create a task from the inbound message
-    let task = create_task_from_message(msg);
+    let routing = match questions {
+        Some(questions) => answer_question(channel_id, &msg, config, questions),
+        None => ReplyRouting::NotAnAnswer,
+    };
+    // Create a task from the inbound message, unless it is filtered out
+    let task = match routing {
+        ReplyRouting::Delivered => return Ok(()),
+        ReplyRouting::Orphaned(question) => resume_task(msg, &question, config),
+        ReplyRouting::NotAnAnswer => match route_message(channel_id, msg, config) {
+            Some(task) => task,
+            None => return Ok(()),
+        },
+    };
+    if let (Some(questions), Some(session_id)) = (questions, task.payload["session_id"].as_str()) {
+        questions.bind_session(session_id, reply_to.clone());
+    }
+    if let Some(filter) = config.channels.get(channel_id) {
+        if !admit(channel.as_ref(), &reply_to, &msg_id, &filter.rate_limit, limiter).await {
+            return Ok(());
+        }
+    }
+    run_loop.trace_log().record(
+        &task.trace_id(),
+        TraceEvent::new(TraceEventKind::MessageReceived)
+            .with_task(task.id)
+            .with_name(channel_id)
+            .with_detail(serde_json::json!({"message_id": msg_id})),
+    );
 
     // Inject task into RunLoop (this also wakes up the RunLoop)
     run_loop
@@ -125,15 +213,137 @@ async fn handle_inbound_message(
     Ok(())
 }
 
+/// Route a message to the question pending in its conversation, if any.
+///
+/// Only senders the channel allows may answer.
+fn answer_question(
+    channel_id: &str,
+    msg: &InboundMessage,
+    config: &ChannelBridgeConfig,
+    questions: &QuestionService,
+) -> ReplyRouting {
+    if let Some(filter) = config.channels.get(channel_id) {
+        if let Err(reason) = filter.check_sender(msg) {
+            debug!("Message {} cannot answer questions: {}", msg.id, reason);
+            return ReplyRouting::NotAnAnswer;
+        }
+    }
+    questions.take_reply(&msg.reply_to, msg.content.trim(), sender(msg))
+}
+
+/// Create the task resuming a session whose question was answered after a
+/// restart.
+fn resume_task(mut msg: InboundMessage, question: &Question, config: &ChannelBridgeConfig) -> Task {
+    info!("Message {} answers question {} asked before a restart", msg.id, question.id);
+    let answer = question.parse_reply(&msg.content);
+    let deliver_to = config
+        .channels
+        .get(&msg.reply_to.channel_id)
+        .map(|filter| filter.deliver_to.clone())
+        .unwrap_or_default();
+    msg.content = format!(
+        "You asked the user: {}\nThe user replied: {}\nContinue the task with this answer.",
+        question.text,
+        answer.text.unwrap_or_default()
+    );
+    let mut task = config.apply_defaults(create_task_from_message(msg));
+    task.deliver_to = deliver_to;
+    if let Some(session_id) = &question.session_id {
+        task.payload["session_id"] = session_id.clone().into();
+    }
+    task.payload["question_id"] = question.id.clone().into();
+    task
+}
+
+/// Check a conversation's rate limit, telling it once when it is throttled.
+///
+/// Returns whether the message may start a task.
+async fn admit(
+    channel: &dyn Channel,
+    reply_to: &ReplyAddress,
+    msg_id: &str,
+    limit: &RateLimit,
+    limiter: &ConversationLimiter,
+) -> bool {
+    let conversation = format!("{}:{}", reply_to.channel_id, reply_to.target);
+    let Admission::Throttled { retry_after, notify } =
+        limiter.check(&conversation, limit, std::time::Instant::now())
+    else {
+        return true;
+    };
+    info!(
+        "Throttling conversation {} for {}s, dropping message {}",
+        conversation,
+        retry_after.as_secs(),
+        msg_id
+    );
+    if notify {
+        let reply = OutboundMessage::reply(limit.render_message(retry_after), msg_id);
+        if let Err(e) = channel.send(reply_to, reply).await {
+            warn!("Failed to notify throttled conversation {}: {}", conversation, e);
+        }
+    }
+    false
+}
+
+/// Apply a channel's inbound rules to a message and create its task.
+///
+/// Returns `None` when the message is ignored.
+fn route_message(channel_id: &str, mut msg: InboundMessage, config: &ChannelBridgeConfig) -> Option<Task> {
+    let Some(filter) = config.channels.get(channel_id) else {
+        return Some(config.apply_defaults(create_task_from_message(msg)));
+    };
+
+    let mut task = match filter.apply(&msg) {
+        InboundAction::Ignore(reason) => {
+            debug!("Ignoring message {} on channel {}: {}", msg.id, channel_id, reason);
+            return None;
+        }
+        InboundAction::Prompt(prompt) => {
+            msg.content = prompt;
+            config.apply_defaults(create_task_from_message(msg))
+        }
+        InboundAction::Command { name, args } => {
+            let route = filter.commands.get(&name)?;
+            info!("Message {} on channel {} runs command '{}'", msg.id, channel_id, name);
+            msg.content = route.render_prompt(&args);
+            let mut task = config.apply_defaults(create_task_from_message(msg));
+            task.task_type = route.task_type.clone();
+            task.payload["command"] = name.into();
+            task.payload["args"] = args.into();
+            if let Some(agent) = &route.agent {
+                task.payload["agent"] = agent.clone().into();
+            }
+            task
+        }
+    };
+    task.deliver_to = filter.deliver_to.clone();
+    Some(task)
+}
+
 /// Create a Task from an InboundMessage.
+///
+/// Per-run model and generation overrides may be given in the message's
+/// `overrides` metadata. Delivery targets are not taken from the message;
+/// they come from the channel's [`InboundFilter`].
 fn create_task_from_message(msg: InboundMessage) -> Task {
+    let correlation_id = msg
+        .metadata
+        .get("correlation_id")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .unwrap_or_else(|| msg.id.clone());
+
     // Build the payload with message content and session info
-    let payload = serde_json::json!({
+    let mut payload = serde_json::json!({
         "prompt": msg.content,
         "session_id": msg.reply_to.target.clone(),
         "message_id": msg.id,
         "metadata": msg.metadata,
     });
+    if let Some(overrides) = msg.metadata.get("overrides") {
+        payload["overrides"] = overrides.clone();
+    }
 
     Task::new("agent:execute", payload)
         .with_source(TaskSource::Custom(format!(
@@ -141,9 +351,48 @@ fn create_task_from_message(msg: InboundMessage) -> Task {
             msg.reply_to.channel_id
         )))
         .with_priority(TaskPriority::Normal)
+        .with_correlation_id(correlation_id)
         .with_reply_to(msg.reply_to)
 }
 
+/// Deliver a task's final result to every target.
+///
+/// Each target gets the result rendered in its own format, and the sends run
+/// concurrently. One status is returned per target, in target order; a
+/// failing target does not stop the others.
+pub async fn fan_out(
+    registry: &ChannelRegistry,
+    task: &Task,
+    result: &str,
+) -> Vec<DeliveryStatus> {
+    let sends = task.delivery_targets().into_iter().map(|target| async move {
+        let Some(channel) = registry.get(&target.address.channel_id) else {
+            return DeliveryStatus::failed(&target, "Channel not found");
+        };
+        let mut message = target.render(task.id, result, channel.capabilities());
+        message
+            .metadata
+            .insert("correlation_id".to_string(), task.trace_id().into());
+        match channel.send(&target.address, message).await {
+            Ok(sent) => {
+                info!(
+                    "Result of task {} delivered to {}:{}",
+                    task.id, target.address.channel_id, target.address.target
+                );
+                DeliveryStatus::delivered(&target, sent.id)
+            }
+            Err(e) => {
+                error!(
+                    "Failed to deliver result of task {} to {}:{}: {}",
+                    task.id, target.address.channel_id, target.address.target, e
+                );
+                DeliveryStatus::failed(&target, e.to_string())
+            }
+        }
+    });
+    futures::future::join_all(sends).await
+}
+
 /// Configuration for channel bridge behavior.
 #[derive(Debug, Clone)]
 pub struct ChannelBridgeConfig {
@@ -151,6 +400,18 @@ pub struct ChannelBridgeConfig {
     pub default_priority: TaskPriority,
     /// Task type for channel messages.
     pub task_type: String,
+    /// Inbound rules by channel ID. Channels without rules handle every
+    /// message.
+    pub channels: HashMap<String, InboundFilter>,
+}
+
+impl ChannelBridgeConfig {
+    /// Apply the default task type and priority to a message's task.
+    fn apply_defaults(&self, mut task: Task) -> Task {
+        task.task_type = self.task_type.clone();
+        task.priority = self.default_priority;
+        task
+    }
 }
 
 impl Default for ChannelBridgeConfig {
@@ -158,6 +419,7 @@ impl Default for ChannelBridgeConfig {
         Self {
             default_priority: TaskPriority::Normal,
             task_type: "agent:execute".to_string(),
+            channels: HashMap::new(),
         }
     }
 }
@@ -52,6 +52,19 @@
         assert_eq!(user_name, Some("John"));
     }
 
+    #[test]
+    fn test_create_task_correlation_id() {
+        let reply_to = ReplyAddress::new("web", "conn-1");
+        let task = create_task_from_message(InboundMessage::new("msg-9", "Hi", reply_to.clone()));
+        assert_eq!(task.correlation_id.as_deref(), Some("msg-9"));
+
+        let mut msg = InboundMessage::new("msg-10", "Hi", reply_to);
+        msg.metadata
+            .insert("correlation_id".to_string(), serde_json::json!("trace-abc"));
+        let task = create_task_from_message(msg);
+        assert_eq!(task.trace_id(), "trace-abc");
+    }
+
     #[test]
     fn test_task_source() {
         let reply_to = ReplyAddress::new("wechat", "user-789");
@@ -76,3 +89,246 @@
         assert_eq!(config.default_priority, TaskPriority::Normal);
         assert_eq!(config.task_type, "agent:execute");
     }
+
+    #[test]
+    fn test_deliver_to_from_channel_config_only() {
+        use crate::delivery::DeliveryTarget;
+
+        let filter = InboundFilter {
+            deliver_to: vec![DeliveryTarget::new("slack", "#reports")],
+            ..Default::default()
+        };
+        let config = ChannelBridgeConfig {
+            channels: HashMap::from([("web".to_string(), filter)]),
+            ..Default::default()
+        };
+        let mut msg = InboundMessage::new("msg-4", "Weekly report", ReplyAddress::new("web", "c1"));
+        msg.metadata.insert(
+            "deliver_to".to_string(),
+            serde_json::json!([{"channel_id": "mail", "target": "attacker@example.com"}]),
+        );
+
+        let task = route_message("web", msg.clone(), &config).unwrap();
+        assert_eq!(task.deliver_to, vec![DeliveryTarget::new("slack", "#reports")]);
+        assert_eq!(task.delivery_targets().len(), 2);
+
+        // Channels without rules deliver to the conversation only
+        let task = route_message("web", msg, &ChannelBridgeConfig::default()).unwrap();
+        assert!(task.deliver_to.is_empty());
+    }
+
+    #[test]
+    fn test_create_task_with_overrides() {
+        let mut msg = InboundMessage::new("msg-5", "Summarize", ReplyAddress::new("web", "c1"));
+        msg.metadata.insert(
+            "overrides".to_string(),
+            serde_json::json!({"model": "fast", "temperature": 0.2}),
+        );
+
+        let task = create_task_from_message(msg);
+        assert_eq!(task.payload["overrides"]["model"], "fast");
+
+        let msg = InboundMessage::new("msg-6", "Summarize", ReplyAddress::new("web", "c1"));
+        assert!(create_task_from_message(msg).payload.get("overrides").is_none());
+    }
+
+    struct RecordingChannel {
+        id: String,
+        capabilities: autohands_protocols::channel::ChannelCapabilities,
+        sent: std::sync::Mutex<Vec<(ReplyAddress, autohands_protocols::channel::OutboundMessage)>>,
+        inbound_tx: tokio::sync::broadcast::Sender<InboundMessage>,
+    }
+
+    impl RecordingChannel {
+        fn new(id: &str) -> Arc<Self> {
+            Arc::new(Self {
+                id: id.to_string(),
+                capabilities: Default::default(),
+                sent: std::sync::Mutex::new(Vec::new()),
+                inbound_tx: tokio::sync::broadcast::channel(1).0,
+            })
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl autohands_protocols::channel::Channel for RecordingChannel {
+        fn id(&self) -> &String {
+            &self.id
+        }
+
+        fn capabilities(&self) -> &autohands_protocols::channel::ChannelCapabilities {
+            &self.capabilities
+        }
+
+        async fn start(&self) -> Result<(), autohands_protocols::error::ChannelError> {
+            Ok(())
+        }
+
+        async fn stop(&self) -> Result<(), autohands_protocols::error::ChannelError> {
+            Ok(())
+        }
+
+        async fn send(
+            &self,
+            target: &ReplyAddress,
+            message: autohands_protocols::channel::OutboundMessage,
+        ) -> Result<autohands_protocols::channel::SentMessage, autohands_protocols::error::ChannelError>
+        {
+            self.sent.lock().unwrap().push((target.clone(), message));
+            Ok(autohands_protocols::channel::SentMessage {
+                id: format!("{}-msg", self.id),
+                timestamp: chrono::Utc::now(),
+            })
+        }
+
+        fn inbound(&self) -> tokio::sync::broadcast::Receiver<InboundMessage> {
+            self.inbound_tx.subscribe()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_per_target() {
+        use crate::delivery::{DeliveryFormat, DeliveryTarget};
+
+        let web = RecordingChannel::new("web");
+        let slack = RecordingChannel::new("slack");
+        let registry = ChannelRegistry::new();
+        registry.register(web.clone()).unwrap();
+        registry.register(slack.clone()).unwrap();
+
+        let task = Task::new("agent:execute", serde_json::Value::Null)
+            .with_reply_to(ReplyAddress::new("web", "c1"))
+            .with_delivery_target(
+                DeliveryTarget::new("slack", "#reports").with_format(DeliveryFormat::Plain),
+            )
+            .with_delivery_target(DeliveryTarget::new("email", "ops@example.com"));
+
+        let statuses = fan_out(&registry, &task, "**Done**").await;
+
+        assert_eq!(statuses.len(), 3);
+        assert!(statuses[0].delivered);
+        assert_eq!(statuses[0].message_id.as_deref(), Some("web-msg"));
+        assert!(statuses[1].delivered);
+        assert!(!statuses[2].delivered);
+        assert_eq!(statuses[2].error.as_deref(), Some("Channel not found"));
+
+        assert_eq!(web.sent.lock().unwrap()[0].1.content, "**Done**");
+        assert_eq!(
+            web.sent.lock().unwrap()[0].1.metadata["correlation_id"],
+            task.id.to_string()
+        );
+        let slack_sent = slack.sent.lock().unwrap();
+        assert_eq!(slack_sent[0].0.target, "#reports");
+        assert_eq!(slack_sent[0].1.content, "Done");
+    }
+
+    #[test]
+    fn test_route_message_filters_and_commands() {
+        use crate::integration::channel_filter::CommandRoute;
+
+        let route = CommandRoute {
+            task_type: "agent:review".to_string(),
+            agent: Some("reviewer".to_string()),
+            prompt: Some("Review {args}".to_string()),
+        };
+        let filter = InboundFilter {
+            mentions: vec!["@autohands".to_string()],
+            command_prefix: Some("/".to_string()),
+            commands: HashMap::from([("review".to_string(), route)]),
+            ..Default::default()
+        };
+        let config = ChannelBridgeConfig {
+            default_priority: TaskPriority::High,
+            channels: HashMap::from([("slack".to_string(), filter)]),
+            ..Default::default()
+        };
+        let reply_to = ReplyAddress::new("slack", "#dev");
+
+        let ignored = InboundMessage::new("m1", "good morning", reply_to.clone());
+        assert!(route_message("slack", ignored, &config).is_none());
+
+        let prompt = InboundMessage::new("m2", "@autohands what broke?", reply_to.clone());
+        let task = route_message("slack", prompt, &config).unwrap();
+        assert_eq!(task.task_type, "agent:execute");
+        assert_eq!(task.priority, TaskPriority::High);
+        assert_eq!(task.payload["prompt"], "what broke?");
+
+        let command = InboundMessage::new("m3", "@autohands /review PR 42", reply_to.clone());
+        let task = route_message("slack", command, &config).unwrap();
+        assert_eq!(task.task_type, "agent:review");
+        assert_eq!(task.payload["prompt"], "Review PR 42");
+        assert_eq!(task.payload["command"], "review");
+        assert_eq!(task.payload["args"], "PR 42");
+        assert_eq!(task.payload["agent"], "reviewer");
+
+        // Channels without rules handle every message
+        let web = InboundMessage::new("m4", "good morning", ReplyAddress::new("web", "c1"));
+        assert_eq!(route_message("web", web, &config).unwrap().payload["prompt"], "good morning");
+    }
+
+    #[tokio::test]
+    async fn test_admit_throttles_conversation() {
+        let channel = RecordingChannel::new("web");
+        let limiter = ConversationLimiter::new();
+        let limit = RateLimit {
+            cooldown_secs: 60,
+            ..Default::default()
+        };
+        let reply_to = ReplyAddress::new("web", "c1");
+
+        assert!(admit(channel.as_ref(), &reply_to, "m1", &limit, &limiter).await);
+        assert!(!admit(channel.as_ref(), &reply_to, "m2", &limit, &limiter).await);
+        assert!(!admit(channel.as_ref(), &reply_to, "m3", &limit, &limiter).await);
+
+        // One polite reply, to the first throttled message
+        let sent = channel.sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].0.target, "c1");
+        assert_eq!(sent[0].1.reply_to_message_id.as_deref(), Some("m2"));
+        assert!(sent[0].1.content.contains("try again in 60 seconds"));
+    }
+
+    #[tokio::test]
+    async fn test_reply_to_question_after_restart_resumes_session() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("questions.json");
+        let reply_to = ReplyAddress::new("slack", "#dev");
+        let question = Question::new("Which environment?")
+            .with_options(vec!["staging".to_string(), "production".to_string()])
+            .with_session("s-1")
+            .with_reply_to(reply_to.clone());
+        std::fs::write(&path, serde_json::to_vec(&vec![question.clone()]).unwrap()).unwrap();
+        let questions = QuestionService::new().with_store(&path);
+
+        let filter = InboundFilter {
+            mentions: vec!["@autohands".to_string()],
+            allowed_senders: vec!["alice".to_string()],
+            ..Default::default()
+        };
+        let config = ChannelBridgeConfig {
+            channels: HashMap::from([("slack".to_string(), filter)]),
+            ..Default::default()
+        };
+
+        // Senders the channel ignores cannot answer
+        let mut stranger = InboundMessage::new("m1", "2", reply_to.clone());
+        stranger.metadata.insert("sender".to_string(), "mallory".into());
+        assert!(matches!(
+            answer_question("slack", &stranger, &config, &questions),
+            ReplyRouting::NotAnAnswer
+        ));
+
+        // The reply needs no mention, and resumes the asking session
+        let mut reply = InboundMessage::new("m2", "2", reply_to.clone());
+        reply.metadata.insert("sender".to_string(), "alice".into());
+        let ReplyRouting::Orphaned(orphan) = answer_question("slack", &reply, &config, &questions) else {
+            panic!("expected an orphaned question");
+        };
+        let task = resume_task(reply, &orphan, &config);
+        assert_eq!(task.payload["session_id"], "s-1");
+        assert_eq!(task.payload["question_id"], question.id.as_str());
+        let prompt = task.payload["prompt"].as_str().unwrap();
+        assert!(prompt.contains("Which environment?"));
+        assert!(prompt.contains("The user replied: production"));
+        assert_eq!(questions.pending_count(), 0);
+    }
@@ -0,0 +1,191 @@
+//! Inbound message filtering and command parsing for channels.
+//!
+//! In a busy room the agent should only answer what is meant for it. An
+//! [`InboundFilter`] decides, per channel, what happens to a message:
+//!
+//! - Senders outside `allowed_senders` are ignored.
+//! - With `mentions` set, only messages starting with one of them (e.g.
+//!   `@autohands`) are handled, and the mention is stripped.
+//! - With `command_prefix` set, a message such as `/review src/lib.rs`
+//!   runs the `review` command: its [`CommandRoute`] picks the task type,
+//!   agent and prompt. Unknown commands are handled as plain prompts.
+//!
+//! The sender is read from the message's `sender` (or `user_id`) metadata.
+//!
+//! Where the results of a channel's tasks go besides the conversation is
+//! set here too, by the operator: `deliver_to` metadata on a message is
+//! ignored, since anyone able to post could send results anywhere.
+
+use std::collections::HashMap;
+
+use autohands_protocols::channel::InboundMessage;
+use serde::{Deserialize, Serialize};
+
+use super::channel_rate_limit::RateLimit;
+use crate::delivery::DeliveryTarget;
+
+/// Metadata keys holding the sender of a message, in lookup order.
+const SENDER_KEYS: [&str; 2] = ["sender", "user_id"];
+
+/// Inbound rules of one channel.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InboundFilter {
+    /// Mentions a message must start with to be handled. Empty handles
+    /// every message.
+    #[serde(default)]
+    pub mentions: Vec<String>,
+
+    /// Prefix of slash-style commands (usually `/`). Commands are not
+    /// parsed when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command_prefix: Option<String>,
+
+    /// Commands by name.
+    #[serde(default)]
+    pub commands: HashMap<String, CommandRoute>,
+
+    /// Senders whose messages are handled. Empty allows everyone.
+    #[serde(default)]
+    pub allowed_senders: Vec<String>,
+
+    /// Per-conversation cooldown and hourly task limit.
+    #[serde(default)]
+    pub rate_limit: RateLimit,
+
+    /// Places the results of the channel's tasks are also delivered to.
+    #[serde(default)]
+    pub deliver_to: Vec<DeliveryTarget>,
+}
+
+/// Task a command turns into.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandRoute {
+    /// Task type.
+    #[serde(default = "default_task_type")]
+    pub task_type: String,
+
+    /// Agent running the task. The default agent when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+
+    /// Prompt template; `{args}` is replaced by the command's arguments.
+    /// The arguments are the prompt when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+}
+
+fn default_task_type() -> String {
+    "agent:execute".to_string()
+}
+
+impl Default for CommandRoute {
+    fn default() -> Self {
+        Self {
+            task_type: default_task_type(),
+            agent: None,
+            prompt: None,
+        }
+    }
+}
+
+impl CommandRoute {
+    /// Prompt for a command invocation.
+    pub fn render_prompt(&self, args: &str) -> String {
+        match &self.prompt {
+            Some(template) => template.replace("{args}", args),
+            None => args.to_string(),
+        }
+    }
+}
+
+/// What to do with an inbound message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InboundAction {
+    /// Drop the message.
+    Ignore(String),
+    /// Run the agent on a prompt.
+    Prompt(String),
+    /// Run a configured command.
+    Command {
+        /// Command name.
+        name: String,
+        /// Text after the command name.
+        args: String,
+    },
+}
+
+impl InboundFilter {
+    /// Decide what to do with a message.
+    pub fn apply(&self, msg: &InboundMessage) -> InboundAction {
+        if let Err(reason) = self.check_sender(msg) {
+            return InboundAction::Ignore(reason);
+        }
+
+        let content = msg.content.trim();
+        let content = if self.mentions.is_empty() {
+            content
+        } else {
+            match self.mentions.iter().find_map(|m| strip_mention(content, m)) {
+                Some(rest) => rest,
+                None => return InboundAction::Ignore("not addressed to the agent".to_string()),
+            }
+        };
+
+        if let Some((name, args)) = self.parse_command(content) {
+            if self.commands.contains_key(name) {
+                return InboundAction::Command {
+                    name: name.to_string(),
+                    args: args.to_string(),
+                };
+            }
+        }
+        if content.is_empty() {
+            return InboundAction::Ignore("empty message".to_string());
+        }
+        InboundAction::Prompt(content.to_string())
+    }
+
+    /// Check the sender of a message against `allowed_senders`, returning
+    /// why it is not allowed.
+    pub fn check_sender(&self, msg: &InboundMessage) -> Result<(), String> {
+        if self.allowed_senders.is_empty() {
+            return Ok(());
+        }
+        match sender(msg) {
+            Some(sender) if self.allowed_senders.iter().any(|s| s == sender) => Ok(()),
+            Some(sender) => Err(format!("sender '{}' not allowed", sender)),
+            None => Err("unknown sender".to_string()),
+        }
+    }
+
+    /// Split a command into its name and arguments.
+    fn parse_command<'a>(&self, content: &'a str) -> Option<(&'a str, &'a str)> {
+        let rest = content.strip_prefix(self.command_prefix.as_deref()?)?;
+        let (name, args) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        (!name.is_empty()).then(|| (name, args.trim()))
+    }
+}
+
+/// Sender of a message, from its metadata.
+pub(crate) fn sender(msg: &InboundMessage) -> Option<&str> {
+    SENDER_KEYS
+        .iter()
+        .find_map(|key| msg.metadata.get(*key)?.as_str())
+}
+
+/// Text after a leading mention, which must end at a word boundary.
+fn strip_mention<'a>(content: &'a str, mention: &str) -> Option<&'a str> {
+    let head = content.get(..mention.len())?;
+    if !head.eq_ignore_ascii_case(mention) {
+        return None;
+    }
+    let rest = &content[mention.len()..];
+    if rest.starts_with(|c: char| c.is_alphanumeric() || c == '_' || c == '-') {
+        return None;
+    }
+    Some(rest.trim_start_matches([':', ',']).trim_start())
+}
+
+#[cfg(test)]
+#[path = "channel_filter_tests.rs"]
+mod tests;
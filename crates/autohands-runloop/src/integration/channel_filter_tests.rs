@@ -0,0 +1,81 @@
+use super::*;
+use autohands_protocols::channel::ReplyAddress;
+
+fn message(content: &str) -> InboundMessage {
+    InboundMessage::new("msg-1", content, ReplyAddress::new("slack", "#general"))
+}
+
+fn slack_filter() -> InboundFilter {
+    InboundFilter {
+        mentions: vec!["@autohands".to_string()],
+        command_prefix: Some("/".to_string()),
+        commands: HashMap::from([("review".to_string(), CommandRoute::default())]),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_default_filter_handles_everything() {
+    let filter = InboundFilter::default();
+    assert_eq!(filter.apply(&message("  hello ")), InboundAction::Prompt("hello".to_string()));
+    assert_eq!(filter.apply(&message("/review x")), InboundAction::Prompt("/review x".to_string()));
+}
+
+#[test]
+fn test_mention_required() {
+    let filter = slack_filter();
+    assert!(matches!(filter.apply(&message("lunch anyone?")), InboundAction::Ignore(_)));
+    assert!(matches!(filter.apply(&message("@autohandsbot hi")), InboundAction::Ignore(_)));
+    assert!(matches!(filter.apply(&message("@autohands")), InboundAction::Ignore(_)));
+    assert_eq!(
+        filter.apply(&message("@AutoHands: summarize the thread")),
+        InboundAction::Prompt("summarize the thread".to_string())
+    );
+}
+
+#[test]
+fn test_command_parsing() {
+    let filter = slack_filter();
+    assert_eq!(
+        filter.apply(&message("@autohands /review  src/lib.rs  ")),
+        InboundAction::Command {
+            name: "review".to_string(),
+            args: "src/lib.rs".to_string(),
+        }
+    );
+    assert_eq!(
+        filter.apply(&message("@autohands /review")),
+        InboundAction::Command {
+            name: "review".to_string(),
+            args: String::new(),
+        }
+    );
+    // Unknown commands are plain prompts
+    assert_eq!(
+        filter.apply(&message("@autohands /etc/hosts is broken")),
+        InboundAction::Prompt("/etc/hosts is broken".to_string())
+    );
+}
+
+#[test]
+fn test_sender_allowlist() {
+    let filter = InboundFilter {
+        allowed_senders: vec!["U123".to_string()],
+        ..Default::default()
+    };
+    let allowed = message("deploy").with_metadata("sender", serde_json::json!("U123"));
+    let other = message("deploy").with_metadata("user_id", serde_json::json!("U999"));
+
+    assert_eq!(filter.apply(&allowed), InboundAction::Prompt("deploy".to_string()));
+    assert!(matches!(filter.apply(&other), InboundAction::Ignore(_)));
+    assert!(matches!(filter.apply(&message("deploy")), InboundAction::Ignore(_)));
+}
+
+#[test]
+fn test_command_route() {
+    let route: CommandRoute =
+        serde_json::from_value(serde_json::json!({"agent": "reviewer", "prompt": "Review {args}"})).unwrap();
+    assert_eq!(route.task_type, "agent:execute");
+    assert_eq!(route.render_prompt("src/lib.rs"), "Review src/lib.rs");
+    assert_eq!(CommandRoute::default().render_prompt("as is"), "as is");
+}
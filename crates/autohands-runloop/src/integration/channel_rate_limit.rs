@@ -0,0 +1,150 @@
+//! Per-conversation rate limiting for channels.
+//!
+//! A conversation (a channel's reply target: a chat, a room, a connection)
+//! may be held to a cooldown between tasks and to a number of tasks per
+//! rolling hour, so a user or a looping bot cannot start hundreds of agent
+//! runs. A throttled conversation is told once, politely, when it may try
+//! again; further messages are dropped silently until then.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+const HOUR: Duration = Duration::from_secs(60 * 60);
+
+/// Per-conversation limits of a channel.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateLimit {
+    /// Minimum seconds between two tasks of a conversation (0 = none).
+    #[serde(default)]
+    pub cooldown_secs: u64,
+
+    /// Tasks a conversation may start per rolling hour (0 = unlimited).
+    #[serde(default)]
+    pub max_tasks_per_hour: u32,
+
+    /// Reply to a throttled conversation; `{retry_after}` is the number of
+    /// seconds until it may start a task again.
+    #[serde(default = "default_throttle_message")]
+    pub message: String,
+}
+
+fn default_throttle_message() -> String {
+    "I'm getting a lot of requests from this conversation. Please try again in {retry_after} seconds."
+        .to_string()
+}
+
+impl Default for RateLimit {
+    fn default() -> Self {
+        Self {
+            cooldown_secs: 0,
+            max_tasks_per_hour: 0,
+            message: default_throttle_message(),
+        }
+    }
+}
+
+impl RateLimit {
+    /// Whether any limit is set.
+    pub fn is_enabled(&self) -> bool {
+        self.cooldown_secs > 0 || self.max_tasks_per_hour > 0
+    }
+
+    /// Throttling reply for a wait.
+    pub fn render_message(&self, retry_after: Duration) -> String {
+        let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+        self.message.replace("{retry_after}", &secs.to_string())
+    }
+
+    fn cooldown(&self) -> Duration {
+        Duration::from_secs(self.cooldown_secs)
+    }
+
+    /// How long task starts are remembered.
+    fn window(&self) -> Duration {
+        if self.max_tasks_per_hour > 0 {
+            HOUR.max(self.cooldown())
+        } else {
+            self.cooldown()
+        }
+    }
+}
+
+/// Whether a conversation may start a task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Admission {
+    /// Start the task.
+    Allowed,
+    /// Drop the message.
+    Throttled {
+        /// Time until the conversation may start a task again.
+        retry_after: Duration,
+        /// Whether this is the first throttled message, which gets a reply.
+        notify: bool,
+    },
+}
+
+/// Task starts of one conversation.
+#[derive(Default)]
+struct Conversation {
+    /// Start times within the limit window, oldest first.
+    started: VecDeque<Instant>,
+    /// Whether the conversation was told it is throttled.
+    notified: bool,
+}
+
+/// Tracks task starts per conversation.
+#[derive(Default)]
+pub(crate) struct ConversationLimiter {
+    conversations: Mutex<HashMap<String, Conversation>>,
+}
+
+impl ConversationLimiter {
+    /// Create a limiter.
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check a conversation against its limits, recording a task start when
+    /// allowed.
+    pub(crate) fn check(&self, conversation: &str, limit: &RateLimit, now: Instant) -> Admission {
+        if !limit.is_enabled() {
+            return Admission::Allowed;
+        }
+        let window = limit.window();
+        let mut conversations = self.conversations.lock();
+        // Forget conversations with nothing left in their window
+        conversations.retain(|_, c| c.started.back().is_some_and(|last| now.duration_since(*last) < window));
+
+        let entry = conversations.entry(conversation.to_string()).or_default();
+        while entry.started.front().is_some_and(|t| now.duration_since(*t) >= window) {
+            entry.started.pop_front();
+        }
+
+        let mut retry_after = Duration::ZERO;
+        if let Some(last) = entry.started.back() {
+            retry_after = retry_after.max(limit.cooldown().saturating_sub(now.duration_since(*last)));
+        }
+        let max = limit.max_tasks_per_hour as usize;
+        if max > 0 && entry.started.len() >= max {
+            let oldest = entry.started[entry.started.len() - max];
+            retry_after = retry_after.max(HOUR.saturating_sub(now.duration_since(oldest)));
+        }
+
+        if retry_after.is_zero() {
+            entry.started.push_back(now);
+            entry.notified = false;
+            Admission::Allowed
+        } else {
+            let notify = !entry.notified;
+            entry.notified = true;
+            Admission::Throttled { retry_after, notify }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "channel_rate_limit_tests.rs"]
+mod tests;
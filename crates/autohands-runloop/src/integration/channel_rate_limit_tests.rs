@@ -0,0 +1,90 @@
+use super::*;
+
+fn limit(cooldown_secs: u64, max_tasks_per_hour: u32) -> RateLimit {
+    RateLimit {
+        cooldown_secs,
+        max_tasks_per_hour,
+        ..Default::default()
+    }
+}
+
+fn secs(n: u64) -> Duration {
+    Duration::from_secs(n)
+}
+
+#[test]
+fn test_disabled_allows_everything() {
+    let limiter = ConversationLimiter::new();
+    let now = Instant::now();
+    for _ in 0..100 {
+        assert_eq!(limiter.check("web:c1", &RateLimit::default(), now), Admission::Allowed);
+    }
+}
+
+#[test]
+fn test_cooldown() {
+    let limiter = ConversationLimiter::new();
+    let limit = limit(30, 0);
+    let start = Instant::now();
+
+    assert_eq!(limiter.check("web:c1", &limit, start), Admission::Allowed);
+    assert_eq!(
+        limiter.check("web:c1", &limit, start + secs(10)),
+        Admission::Throttled {
+            retry_after: secs(20),
+            notify: true
+        }
+    );
+    // Told once, then dropped silently
+    assert_eq!(
+        limiter.check("web:c1", &limit, start + secs(20)),
+        Admission::Throttled {
+            retry_after: secs(10),
+            notify: false
+        }
+    );
+    // Other conversations are not affected
+    assert_eq!(limiter.check("web:c2", &limit, start + secs(20)), Admission::Allowed);
+
+    assert_eq!(limiter.check("web:c1", &limit, start + secs(30)), Admission::Allowed);
+    assert!(matches!(
+        limiter.check("web:c1", &limit, start + secs(31)),
+        Admission::Throttled { notify: true, .. }
+    ));
+}
+
+#[test]
+fn test_max_tasks_per_hour() {
+    let limiter = ConversationLimiter::new();
+    let limit = limit(0, 3);
+    let start = Instant::now();
+
+    for minute in 0..3 {
+        assert_eq!(limiter.check("slack:#dev", &limit, start + secs(minute * 60)), Admission::Allowed);
+    }
+    assert_eq!(
+        limiter.check("slack:#dev", &limit, start + secs(600)),
+        Admission::Throttled {
+            retry_after: secs(3000),
+            notify: true
+        }
+    );
+    // The oldest start leaves the window after an hour
+    assert_eq!(limiter.check("slack:#dev", &limit, start + secs(3600)), Admission::Allowed);
+    assert!(matches!(
+        limiter.check("slack:#dev", &limit, start + secs(3601)),
+        Admission::Throttled { .. }
+    ));
+}
+
+#[test]
+fn test_render_message() {
+    let limit = RateLimit::default();
+    assert!(limit.render_message(Duration::from_millis(20_500)).contains("try again in 21 seconds"));
+    let custom = RateLimit {
+        message: "Slow down ({retry_after}s)".to_string(),
+        ..limit
+    };
+    assert_eq!(custom.render_message(secs(5)), "Slow down (5s)");
+    assert!(!custom.is_enabled());
+}
@@ -0,0 +1,474 @@
+//! Email trigger integration via TaskSubmitter.
+//!
+//! Provides `EmailInjector` which polls a mailbox for new messages and
+//! injects those matching its `from`/`subject` filters as
+//! `trigger:email:received` tasks. The prompt carries the message's
+//! headers, text body and attachments (their text when they are text,
+//! otherwise their name, type, size and, with `attachment_dir`, saved path).
+//! With a `workflow`, the message starts a run of that workflow instead (see
+//! [`email_inputs`]). Messages matching the trigger's archive rules are moved
+//! to `archive_mailbox` without either.
+//! Decoupled from RunLoop internals.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use mail_parser::{MessageParser, MimeHeaders};
+use serde::Serialize;
+use serde_json::{json, Value};
+use tracing::{debug, info, warn};
+
+use autohands_protocols::extension::TaskSubmitter;
+
+use super::file_pipeline::WorkflowLauncher;
+use super::trigger_types::{EmailConfig, TriggerError};
+
+/// Longest body, in characters, put into a prompt.
+const MAX_BODY_CHARS: usize = 20_000;
+
+/// Longest text attachment, in characters, put into a prompt.
+const MAX_ATTACHMENT_CHARS: usize = 10_000;
+
+/// A message as fetched from the mailbox.
+#[derive(Debug, Clone)]
+pub struct RawEmail {
+    /// Mailbox UID.
+    pub uid: u32,
+    /// RFC 5322 message.
+    pub raw: Vec<u8>,
+}
+
+/// Source of new messages.
+#[async_trait]
+pub trait MailFetcher: Send + Sync {
+    /// Unseen messages with a UID above `after`, oldest first.
+    async fn fetch_unseen(&self, after: u32) -> Result<Vec<RawEmail>, TriggerError>;
+
+    /// Flag messages as seen.
+    async fn mark_seen(&self, uids: &[u32]) -> Result<(), TriggerError>;
+
+    /// Move messages to another mailbox.
+    async fn move_to(&self, uids: &[u32], mailbox: &str) -> Result<(), TriggerError>;
+}
+
+/// Attachment of a parsed message.
+#[derive(Debug, Clone, Serialize)]
+pub struct EmailAttachment {
+    /// File name.
+    pub name: String,
+    /// MIME type.
+    pub content_type: String,
+    /// Size in bytes.
+    pub size: usize,
+    /// Contents of text attachments.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Where the attachment was saved.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    #[serde(skip)]
+    contents: Vec<u8>,
+}
+
+/// A parsed message.
+#[derive(Debug, Clone, Serialize)]
+pub struct EmailMessage {
+    /// Mailbox UID.
+    pub uid: u32,
+    /// `Message-ID` header.
+    pub message_id: Option<String>,
+    /// Sender address.
+    pub from: String,
+    /// Sender display name.
+    pub from_name: Option<String>,
+    /// Subject.
+    pub subject: String,
+    /// `Date` header, RFC 3339.
+    pub date: Option<String>,
+    /// Text body.
+    pub body: String,
+    /// Attachments.
+    pub attachments: Vec<EmailAttachment>,
+}
+
+impl EmailMessage {
+    /// Parse a fetched message.
+    pub fn parse(email: &RawEmail) -> Option<Self> {
+        let message = MessageParser::default().parse(&email.raw)?;
+        let sender = message.from().and_then(|from| from.first());
+        let body = message
+            .body_text(0)
+            .map(|body| body.trim().to_string())
+            .unwrap_or_default();
+        let attachments = message
+            .attachments()
+            .enumerate()
+            .map(|(i, part)| {
+                let content_type = part
+                    .content_type()
+                    .map(|ct| match ct.subtype() {
+                        Some(subtype) => format!("{}/{}", ct.ctype(), subtype),
+                        None => ct.ctype().to_string(),
+                    })
+                    .unwrap_or_else(|| "application/octet-stream".to_string());
+                let text = content_type
+                    .starts_with("text/")
+                    .then(|| part.text_contents().map(|text| truncate(text, MAX_ATTACHMENT_CHARS)))
+                    .flatten();
+                EmailAttachment {
+                    name: part
+                        .attachment_name()
+                        .map(str::to_string)
+                        .unwrap_or_else(|| format!("attachment-{}", i + 1)),
+                    content_type,
+                    size: part.contents().len(),
+                    text,
+                    path: None,
+                    contents: part.contents().to_vec(),
+                }
+            })
+            .collect();
+
+        Some(Self {
+            uid: email.uid,
+            message_id: message.message_id().map(str::to_string),
+            from: sender
+                .and_then(|addr| addr.address())
+                .unwrap_or_default()
+                .to_string(),
+            from_name: sender.and_then(|addr| addr.name()).map(str::to_string),
+            subject: message.subject().unwrap_or_default().to_string(),
+            date: message.date().map(|date| date.to_rfc3339()),
+            body: truncate(&body, MAX_BODY_CHARS),
+            attachments,
+        })
+    }
+
+    /// Save the attachments under `dir/<uid>/`, recording their paths.
+    pub fn save_attachments(&mut self, dir: &Path) -> std::io::Result<()> {
+        if self.attachments.is_empty() {
+            return Ok(());
+        }
+        let dir = dir.join(self.uid.to_string());
+        std::fs::create_dir_all(&dir)?;
+        for attachment in &mut self.attachments {
+            // Only the file name; attachment names come from the sender
+            let name = Path::new(&attachment.name)
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "attachment".to_string());
+            let path = dir.join(name);
+            std::fs::write(&path, &attachment.contents)?;
+            attachment.path = Some(path);
+        }
+        Ok(())
+    }
+
+    /// Prompt for the agent: the trigger's instructions, then the message.
+    pub fn render_prompt(&self, instructions: &str) -> String {
+        let mut prompt = String::new();
+        if !instructions.is_empty() {
+            prompt.push_str(
+                &instructions
+                    .replace("{from}", &self.from)
+                    .replace("{subject}", &self.subject),
+            );
+            prompt.push_str("\n\n");
+        }
+        let from = match &self.from_name {
+            Some(name) => format!("{} <{}>", name, self.from),
+            None => self.from.clone(),
+        };
+        prompt.push_str(&format!("From: {}\nSubject: {}\n", from, self.subject));
+        if let Some(date) = &self.date {
+            prompt.push_str(&format!("Date: {}\n", date));
+        }
+        prompt.push('\n');
+        prompt.push_str(&self.body);
+
+        for attachment in &self.attachments {
+            prompt.push_str(&format!(
+                "\n\nAttachment: {} ({}, {} bytes)",
+                attachment.name, attachment.content_type, attachment.size
+            ));
+            if let Some(path) = &attachment.path {
+                prompt.push_str(&format!(", saved to {}", path.display()));
+            }
+            if let Some(text) = &attachment.text {
+                prompt.push_str(&format!("\n{}", text));
+            }
+        }
+        prompt
+    }
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => format!("{}\n[truncated]", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+/// Workflow inputs for a message: `trigger_id`, `uid`, `from`, `from_name`,
+/// `subject`, `date`, `body`, `attachments` and `message`, the message
+/// rendered as in a prompt.
+pub fn email_inputs(trigger_id: &str, message: &EmailMessage) -> HashMap<String, Value> {
+    HashMap::from([
+        ("trigger_id".to_string(), json!(trigger_id)),
+        ("uid".to_string(), json!(message.uid)),
+        ("from".to_string(), json!(message.from)),
+        ("from_name".to_string(), json!(message.from_name)),
+        ("subject".to_string(), json!(message.subject)),
+        ("date".to_string(), json!(message.date)),
+        ("body".to_string(), json!(message.body)),
+        ("attachments".to_string(), json!(message.attachments)),
+        ("message".to_string(), json!(message.render_prompt(""))),
+    ])
+}
+
+/// Sender and subject filters of an email trigger.
+pub struct EmailFilter {
+    from: Vec<glob::Pattern>,
+    subject: Vec<String>,
+}
+
+impl EmailFilter {
+    /// Build the filter of a trigger. Invalid sender globs are skipped.
+    pub fn new(config: &EmailConfig) -> Self {
+        Self {
+            from: sender_patterns(&config.from),
+            subject: lowercase(&config.subject),
+        }
+    }
+
+    /// Whether a message passes the filters.
+    pub fn matches(&self, message: &EmailMessage) -> bool {
+        let from = message.from.to_lowercase();
+        let subject = message.subject.to_lowercase();
+        (self.from.is_empty() || self.from.iter().any(|p| p.matches(&from)))
+            && (self.subject.is_empty() || self.subject.iter().any(|s| subject.contains(s.as_str())))
+    }
+}
+
+/// Archive rules of an email trigger.
+///
+/// Unlike the filters, a message matches when any rule does, and no rules
+/// match nothing.
+pub struct ArchiveRules {
+    from: Vec<glob::Pattern>,
+    subject: Vec<String>,
+}
+
+impl ArchiveRules {
+    /// Build the rules of a trigger. Invalid sender globs are skipped.
+    pub fn new(config: &EmailConfig) -> Self {
+        Self {
+            from: sender_patterns(&config.archive_from),
+            subject: lowercase(&config.archive_subject),
+        }
+    }
+
+    /// Whether a message is to be archived.
+    pub fn matches(&self, message: &EmailMessage) -> bool {
+        let from = message.from.to_lowercase();
+        let subject = message.subject.to_lowercase();
+        self.from.iter().any(|p| p.matches(&from))
+            || self.subject.iter().any(|s| subject.contains(s.as_str()))
+    }
+}
+
+fn sender_patterns(patterns: &[String]) -> Vec<glob::Pattern> {
+    patterns
+        .iter()
+        .filter_map(|pattern| {
+            glob::Pattern::new(&pattern.to_lowercase())
+                .inspect_err(|e| warn!("Invalid email sender pattern '{}': {}", pattern, e))
+                .ok()
+        })
+        .collect()
+}
+
+fn lowercase(words: &[String]) -> Vec<String> {
+    words.iter().map(|s| s.to_lowercase()).collect()
+}
+
+/// Email injector.
+///
+/// Polls a mailbox and injects matching messages as tasks via
+/// `TaskSubmitter`, or as workflow runs via a [`WorkflowLauncher`].
+/// Messages already in the mailbox when polling starts are handled too, as
+/// long as they are unseen.
+pub struct EmailInjector {
+    config: EmailConfig,
+    filter: EmailFilter,
+    archive: ArchiveRules,
+    fetcher: Arc<dyn MailFetcher>,
+    task_submitter: Arc<dyn TaskSubmitter>,
+    workflows: Option<Arc<dyn WorkflowLauncher>>,
+    /// Highest UID handled.
+    last_uid: AtomicU32,
+    running: AtomicBool,
+}
+
+impl EmailInjector {
+    /// Create a new email injector.
+    pub fn new(
+        config: EmailConfig,
+        fetcher: Arc<dyn MailFetcher>,
+        task_submitter: Arc<dyn TaskSubmitter>,
+    ) -> Self {
+        Self {
+            filter: EmailFilter::new(&config),
+            archive: ArchiveRules::new(&config),
+            config,
+            fetcher,
+            task_submitter,
+            workflows: None,
+            last_uid: AtomicU32::new(0),
+            running: AtomicBool::new(false),
+        }
+    }
+
+    /// Set the launcher of the trigger's workflow.
+    pub fn with_workflow_launcher(mut self, launcher: Arc<dyn WorkflowLauncher>) -> Self {
+        self.workflows = Some(launcher);
+        self
+    }
+
+    /// Poll the mailbox once, returning the number of tasks injected.
+    ///
+    /// Archived messages do not count.
+    pub async fn poll(&self) -> Result<usize, TriggerError> {
+        let emails = self
+            .fetcher
+            .fetch_unseen(self.last_uid.load(Ordering::SeqCst))
+            .await?;
+
+        let mut handled = Vec::new();
+        let mut archived = Vec::new();
+        for email in &emails {
+            let Some(mut message) = EmailMessage::parse(email) else {
+                warn!("Email trigger {}: cannot parse message {}", self.config.id, email.uid);
+                self.last_uid.fetch_max(email.uid, Ordering::SeqCst);
+                continue;
+            };
+            if !self.filter.matches(&message) {
+                debug!(
+                    "Email trigger {} ignoring '{}' from {}",
+                    self.config.id, message.subject, message.from
+                );
+                self.last_uid.fetch_max(email.uid, Ordering::SeqCst);
+                continue;
+            }
+            if self.config.archive_mailbox.is_some() && self.archive.matches(&message) {
+                debug!(
+                    "Email trigger {} archiving '{}' from {}",
+                    self.config.id, message.subject, message.from
+                );
+                self.last_uid.fetch_max(email.uid, Ordering::SeqCst);
+                archived.push(message.uid);
+                continue;
+            }
+            if let Some(dir) = &self.config.attachment_dir {
+                if let Err(e) = message.save_attachments(dir) {
+                    warn!("Email trigger {}: cannot save attachments: {}", self.config.id, e);
+                }
+            }
+            // A message that fails to inject is retried on the next poll
+            self.inject(&message).await?;
+            self.last_uid.fetch_max(email.uid, Ordering::SeqCst);
+            handled.push(message.uid);
+        }
+
+        if self.config.mark_seen && !handled.is_empty() {
+            self.fetcher.mark_seen(&handled).await?;
+        }
+        if let Some(mailbox) = &self.config.archive_mailbox {
+            if !archived.is_empty() {
+                self.fetcher.move_to(&archived, mailbox).await?;
+                info!(
+                    "Email trigger {}: archived {} message(s) to {}",
+                    self.config.id,
+                    archived.len(),
+                    mailbox
+                );
+            }
+        }
+        Ok(handled.len())
+    }
+
+    async fn inject(&self, message: &EmailMessage) -> Result<(), TriggerError> {
+        if let Some(workflow) = &self.config.workflow {
+            let launcher = self.workflows.as_ref().ok_or_else(|| {
+                TriggerError::Email(format!("Workflow '{}' cannot run: workflows are unavailable", workflow))
+            })?;
+            launcher
+                .launch(workflow, email_inputs(&self.config.id, message))
+                .await
+                .map_err(|e| TriggerError::Email(format!("Failed to start workflow '{}': {}", workflow, e)))?;
+            info!(
+                "Email trigger {}: '{}' from {} started workflow {}",
+                self.config.id, message.subject, message.from, workflow
+            );
+            return Ok(());
+        }
+
+        let mut payload = json!({
+            "prompt": message.render_prompt(&self.config.prompt),
+            "trigger_id": self.config.id,
+            "email": message,
+        });
+        if !self.config.agent.is_empty() {
+            payload["agent"] = json!(self.config.agent);
+        }
+        self.task_submitter
+            .submit_task("trigger:email:received", payload, message.message_id.clone())
+            .await
+            .map_err(|e| TriggerError::Email(format!("Failed to inject email task: {}", e)))?;
+        info!(
+            "Email trigger {}: '{}' from {} queued",
+            self.config.id, message.subject, message.from
+        );
+        Ok(())
+    }
+
+    /// Start the polling loop in a background task.
+    pub fn start(self: &Arc<Self>) {
+        self.running.store(true, Ordering::SeqCst);
+        let this = self.clone();
+        let interval = Duration::from_secs(self.config.poll_interval_secs.max(1));
+
+        tokio::spawn(async move {
+            info!(
+                "EmailInjector {} started (interval={}s)",
+                this.config.id,
+                interval.as_secs()
+            );
+            while this.running.load(Ordering::SeqCst) {
+                if let Err(e) = this.poll().await {
+                    warn!("Email trigger {} poll failed: {}", this.config.id, e);
+                }
+                tokio::time::sleep(interval).await;
+            }
+            info!("EmailInjector {} stopped", this.config.id);
+        });
+    }
+
+    /// Stop the polling loop.
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+
+    /// Check if the injector is running.
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+#[path = "email_tests.rs"]
+mod tests;
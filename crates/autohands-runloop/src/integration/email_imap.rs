@@ -0,0 +1,276 @@
+//! Minimal IMAP client for email triggers.
+//!
+//! Supports what polling a mailbox needs: `LOGIN`, `SELECT`, `UID SEARCH`,
+//! `UID FETCH` of whole messages, `UID STORE` of the `\Seen` flag and
+//! `UID MOVE` (RFC 6851) for archiving, over TLS (or plain TCP for local
+//! servers). A session is opened per poll.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tracing::debug;
+
+use super::email::{MailFetcher, RawEmail};
+use super::trigger_types::{EmailConfig, TriggerError};
+
+/// Longest an IMAP session may take.
+const SESSION_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Most messages fetched per poll.
+const MAX_FETCH: usize = 50;
+
+trait ImapStream: AsyncRead + AsyncWrite + Unpin + Send {}
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> ImapStream for T {}
+
+/// Untagged response line, with the literals it carried.
+#[derive(Debug, Default)]
+pub(crate) struct Untagged {
+    /// Text of the line, literals left out.
+    pub(crate) text: String,
+    /// Literal payloads, in order.
+    pub(crate) literals: Vec<Vec<u8>>,
+}
+
+/// An authenticated IMAP session.
+struct Session {
+    stream: BufReader<Box<dyn ImapStream>>,
+    next_tag: u32,
+}
+
+impl Session {
+    /// Connect and read the server greeting.
+    async fn connect(config: &EmailConfig) -> Result<Self, TriggerError> {
+        let tcp = TcpStream::connect((config.host.as_str(), config.port))
+            .await
+            .map_err(|e| imap_error(format!("connect to {}:{}: {}", config.host, config.port, e)))?;
+        let stream: Box<dyn ImapStream> = if config.tls {
+            let connector = native_tls_connector()?;
+            let tls = connector
+                .connect(&config.host, tcp)
+                .await
+                .map_err(|e| imap_error(format!("TLS handshake with {}: {}", config.host, e)))?;
+            Box::new(tls)
+        } else {
+            Box::new(tcp)
+        };
+        let mut session = Self {
+            stream: BufReader::new(stream),
+            next_tag: 1,
+        };
+        let greeting = session.read_line().await?;
+        if !greeting.text.starts_with("* OK") && !greeting.text.starts_with("* PREAUTH") {
+            return Err(imap_error(format!("unexpected greeting: {}", greeting.text)));
+        }
+        Ok(session)
+    }
+
+    /// Send a command and collect its untagged responses.
+    async fn command(&mut self, command: &str) -> Result<Vec<Untagged>, TriggerError> {
+        let tag = format!("a{}", self.next_tag);
+        self.next_tag += 1;
+        let stream = self.stream.get_mut();
+        stream
+            .write_all(format!("{} {}\r\n", tag, command).as_bytes())
+            .await
+            .map_err(|e| imap_error(format!("write: {}", e)))?;
+        stream.flush().await.map_err(|e| imap_error(format!("write: {}", e)))?;
+
+        let mut responses = Vec::new();
+        loop {
+            let line = self.read_line().await?;
+            let Some(status) = line.text.strip_prefix(&format!("{} ", tag)) else {
+                responses.push(line);
+                continue;
+            };
+            if status.starts_with("OK") {
+                return Ok(responses);
+            }
+            // Keep credentials out of errors
+            let verb = command.split_whitespace().next().unwrap_or_default();
+            return Err(imap_error(format!("{} failed: {}", verb, status)));
+        }
+    }
+
+    /// Read one response line, including any literals.
+    async fn read_line(&mut self) -> Result<Untagged, TriggerError> {
+        let mut line = Untagged::default();
+        loop {
+            let mut chunk = Vec::new();
+            let n = self
+                .stream
+                .read_until(b'\n', &mut chunk)
+                .await
+                .map_err(|e| imap_error(format!("read: {}", e)))?;
+            if n == 0 {
+                return Err(imap_error("connection closed".to_string()));
+            }
+            let chunk = String::from_utf8_lossy(&chunk);
+            let chunk = chunk.trim_end_matches(['\r', '\n']);
+            match literal_size(chunk) {
+                Some((text, size)) => {
+                    line.text.push_str(text);
+                    let mut literal = vec![0; size];
+                    self.stream
+                        .read_exact(&mut literal)
+                        .await
+                        .map_err(|e| imap_error(format!("read: {}", e)))?;
+                    line.literals.push(literal);
+                }
+                None => {
+                    line.text.push_str(chunk);
+                    return Ok(line);
+                }
+            }
+        }
+    }
+}
+
+/// Split a `... {123}` line into its text and literal size.
+fn literal_size(line: &str) -> Option<(&str, usize)> {
+    let body = line.strip_suffix('}')?;
+    let open = body.rfind('{')?;
+    let size = body[open + 1..].trim_end_matches('+').parse().ok()?;
+    Some((&line[..open], size))
+}
+
+fn native_tls_connector() -> Result<tokio_native_tls::TlsConnector, TriggerError> {
+    let connector = tokio_native_tls::native_tls::TlsConnector::new()
+        .map_err(|e| imap_error(format!("TLS setup: {}", e)))?;
+    Ok(tokio_native_tls::TlsConnector::from(connector))
+}
+
+fn imap_error(message: String) -> TriggerError {
+    TriggerError::Email(message)
+}
+
+/// IMAP quoted string.
+pub(crate) fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// UIDs in `* SEARCH` responses.
+pub(crate) fn parse_search(responses: &[Untagged]) -> Vec<u32> {
+    responses
+        .iter()
+        .filter_map(|r| r.text.strip_prefix("* SEARCH"))
+        .flat_map(|ids| ids.split_whitespace().filter_map(|id| id.parse().ok()))
+        .collect()
+}
+
+/// UID and message of a `* n FETCH (UID u BODY[] {size}` response.
+pub(crate) fn parse_fetch(response: Untagged) -> Option<RawEmail> {
+    let text = response.text.strip_prefix("* ")?;
+    let (_, rest) = text.split_once(" FETCH ")?;
+    let uid = rest
+        .split_once("UID ")?
+        .1
+        .split(|c: char| !c.is_ascii_digit())
+        .next()?
+        .parse()
+        .ok()?;
+    let raw = response.literals.into_iter().next()?;
+    Some(RawEmail { uid, raw })
+}
+
+/// Polls a mailbox over IMAP.
+pub struct ImapFetcher {
+    config: EmailConfig,
+}
+
+impl ImapFetcher {
+    /// Create a fetcher for a trigger's mailbox.
+    pub fn new(config: EmailConfig) -> Self {
+        Self { config }
+    }
+
+    async fn open(&self) -> Result<Session, TriggerError> {
+        let mut session = Session::connect(&self.config).await?;
+        session
+            .command(&format!(
+                "LOGIN {} {}",
+                quote(&self.config.username),
+                quote(&self.config.password)
+            ))
+            .await?;
+        session.command(&format!("SELECT {}", quote(&self.config.mailbox))).await?;
+        Ok(session)
+    }
+
+    async fn fetch(&self, after: u32) -> Result<Vec<RawEmail>, TriggerError> {
+        let mut session = self.open().await?;
+        let search = session
+            .command(&format!("UID SEARCH UNSEEN UID {}:*", after.saturating_add(1)))
+            .await?;
+        // `n:*` always includes the last message, even below n
+        let mut uids: Vec<u32> = parse_search(&search).into_iter().filter(|uid| *uid > after).collect();
+        uids.sort_unstable();
+        uids.truncate(MAX_FETCH);
+        debug!("Email trigger {}: {} new message(s)", self.config.id, uids.len());
+
+        let mut emails = Vec::new();
+        if !uids.is_empty() {
+            let fetched = session
+                .command(&format!("UID FETCH {} (UID BODY.PEEK[])", uid_set(&uids)))
+                .await?;
+            emails.extend(fetched.into_iter().filter_map(parse_fetch));
+            emails.sort_by_key(|email| email.uid);
+        }
+        let _ = session.command("LOGOUT").await;
+        Ok(emails)
+    }
+
+    async fn store_seen(&self, uids: &[u32]) -> Result<(), TriggerError> {
+        let mut session = self.open().await?;
+        session
+            .command(&format!("UID STORE {} +FLAGS.SILENT (\\Seen)", uid_set(uids)))
+            .await?;
+        let _ = session.command("LOGOUT").await;
+        Ok(())
+    }
+
+    async fn move_uids(&self, uids: &[u32], mailbox: &str) -> Result<(), TriggerError> {
+        let mut session = self.open().await?;
+        session
+            .command(&format!("UID MOVE {} {}", uid_set(uids), quote(mailbox)))
+            .await?;
+        let _ = session.command("LOGOUT").await;
+        Ok(())
+    }
+}
+
+fn uid_set(uids: &[u32]) -> String {
+    uids.iter().map(u32::to_string).collect::<Vec<_>>().join(",")
+}
+
+#[async_trait]
+impl MailFetcher for ImapFetcher {
+    async fn fetch_unseen(&self, after: u32) -> Result<Vec<RawEmail>, TriggerError> {
+        tokio::time::timeout(SESSION_TIMEOUT, self.fetch(after))
+            .await
+            .map_err(|_| imap_error("session timed out".to_string()))?
+    }
+
+    async fn mark_seen(&self, uids: &[u32]) -> Result<(), TriggerError> {
+        if uids.is_empty() {
+            return Ok(());
+        }
+        tokio::time::timeout(SESSION_TIMEOUT, self.store_seen(uids))
+            .await
+            .map_err(|_| imap_error("session timed out".to_string()))?
+    }
+
+    async fn move_to(&self, uids: &[u32], mailbox: &str) -> Result<(), TriggerError> {
+        if uids.is_empty() {
+            return Ok(());
+        }
+        tokio::time::timeout(SESSION_TIMEOUT, self.move_uids(uids, mailbox))
+            .await
+            .map_err(|_| imap_error("session timed out".to_string()))?
+    }
+}
+
+#[cfg(test)]
+#[path = "email_imap_tests.rs"]
+mod tests;
@@ -0,0 +1,117 @@
+use super::*;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+
+const MESSAGE: &str = "From: alice@example.com\r\nSubject: Hi\r\n\r\nHello\r\n";
+
+/// Serve one scripted IMAP session, returning the commands received.
+async fn fake_server(listener: TcpListener) -> Vec<String> {
+    let (socket, _) = listener.accept().await.unwrap();
+    let (read, mut write) = socket.into_split();
+    let mut lines = BufReader::new(read).lines();
+    write.write_all(b"* OK IMAP ready\r\n").await.unwrap();
+
+    let mut commands = Vec::new();
+    while let Some(line) = lines.next_line().await.unwrap() {
+        let (tag, command) = line.split_once(' ').unwrap();
+        commands.push(command.to_string());
+        let reply = if command.starts_with("UID SEARCH") {
+            "* SEARCH 4 12 13\r\n".to_string()
+        } else if command.starts_with("UID FETCH") {
+            format!(
+                "* 2 FETCH (UID 12 BODY[] {{{len}}}\r\n{msg})\r\n* 3 FETCH (UID 13 BODY[] {{{len}}}\r\n{msg})\r\n",
+                len = MESSAGE.len(),
+                msg = MESSAGE
+            )
+        } else if command.starts_with("LOGOUT") {
+            "* BYE\r\n".to_string()
+        } else {
+            String::new()
+        };
+        let status = if command.starts_with("SELECT \"Missing\"") { "NO no such mailbox" } else { "OK done" };
+        write
+            .write_all(format!("{}{} {}\r\n", reply, tag, status).as_bytes())
+            .await
+            .unwrap();
+        if command.starts_with("LOGOUT") || status.starts_with("NO") {
+            break;
+        }
+    }
+    commands
+}
+
+async fn fetcher(mailbox: &str) -> (ImapFetcher, TcpListener) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let config: EmailConfig = serde_json::from_value(serde_json::json!({
+        "id": "test",
+        "host": "127.0.0.1",
+        "port": listener.local_addr().unwrap().port(),
+        "tls": false,
+        "username": "me@example.com",
+        "password": "p\"ss",
+        "mailbox": mailbox,
+    }))
+    .unwrap();
+    (ImapFetcher::new(config), listener)
+}
+
+#[tokio::test]
+async fn test_fetch_unseen() {
+    let (fetcher, listener) = fetcher("INBOX").await;
+    let server = tokio::spawn(fake_server(listener));
+
+    let emails = fetcher.fetch_unseen(11).await.unwrap();
+    assert_eq!(emails.iter().map(|e| e.uid).collect::<Vec<_>>(), vec![12, 13]);
+    assert_eq!(emails[0].raw, MESSAGE.as_bytes());
+
+    let commands = server.await.unwrap();
+    assert_eq!(
+        commands,
+        vec![
+            "LOGIN \"me@example.com\" \"p\\\"ss\"",
+            "SELECT \"INBOX\"",
+            "UID SEARCH UNSEEN UID 12:*",
+            "UID FETCH 12,13 (UID BODY.PEEK[])",
+            "LOGOUT",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_mark_seen() {
+    let (fetcher, listener) = fetcher("INBOX").await;
+    let server = tokio::spawn(fake_server(listener));
+
+    fetcher.mark_seen(&[12, 13]).await.unwrap();
+    let commands = server.await.unwrap();
+    assert_eq!(commands[2], "UID STORE 12,13 +FLAGS.SILENT (\\Seen)");
+}
+
+#[tokio::test]
+async fn test_command_failure() {
+    let (fetcher, listener) = fetcher("Missing").await;
+    tokio::spawn(fake_server(listener));
+
+    let err = fetcher.fetch_unseen(0).await.unwrap_err().to_string();
+    assert!(err.contains("SELECT failed: NO no such mailbox"), "{}", err);
+}
+
+#[test]
+fn test_parsing_helpers() {
+    assert_eq!(literal_size("* 1 FETCH (UID 3 BODY[] {42}"), Some(("* 1 FETCH (UID 3 BODY[] ", 42)));
+    assert_eq!(literal_size("a1 OK done"), None);
+
+    let search = [Untagged {
+        text: "* SEARCH 3 9".to_string(),
+        literals: vec![],
+    }];
+    assert_eq!(parse_search(&search), vec![3, 9]);
+
+    let fetch = Untagged {
+        text: "* 4 FETCH (FLAGS () UID 17 BODY[] )".to_string(),
+        literals: vec![b"raw".to_vec()],
+    };
+    let email = parse_fetch(fetch).unwrap();
+    assert_eq!((email.uid, email.raw.as_slice()), (17, b"raw".as_slice()));
+    assert_eq!(quote("a\\b"), "\"a\\\\b\"");
+}
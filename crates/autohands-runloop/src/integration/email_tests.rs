@@ -0,0 +1,284 @@
+use super::*;
+use autohands_protocols::error::ExtensionError;
+use parking_lot::Mutex;
+
+const REPORT: &str = "From: Alice Smith <alice@example.com>\r\n\
+Subject: Weekly report\r\n\
+Message-ID: <report-1@example.com>\r\n\
+Date: Thu, 15 Oct 2026 09:00:00 +0000\r\n\
+MIME-Version: 1.0\r\n\
+Content-Type: multipart/mixed; boundary=\"b1\"\r\n\
+\r\n\
+--b1\r\n\
+Content-Type: text/plain\r\n\
+\r\n\
+Numbers are attached.\r\n\
+--b1\r\n\
+Content-Type: text/csv\r\n\
+Content-Disposition: attachment; filename=\"numbers.csv\"\r\n\
+\r\n\
+week,sales\r\n\
+41,120\r\n\
+--b1\r\n\
+Content-Type: application/pdf\r\n\
+Content-Disposition: attachment; filename=\"../../report.pdf\"\r\n\
+Content-Transfer-Encoding: base64\r\n\
+\r\n\
+JVBERi0xLjQ=\r\n\
+--b1--\r\n";
+
+const NEWSLETTER: &str = "From: news@shop.example\r\n\
+Subject: Big sale\r\n\
+\r\n\
+Buy now.\r\n";
+
+fn raw(uid: u32, message: &str) -> RawEmail {
+    RawEmail {
+        uid,
+        raw: message.as_bytes().to_vec(),
+    }
+}
+
+fn config() -> EmailConfig {
+    serde_json::from_value(json!({
+        "id": "reports",
+        "host": "imap.example.com",
+        "username": "me",
+        "from": ["*@example.com"],
+        "subject": ["report"],
+        "prompt": "Summarize this report from {from}.",
+    }))
+    .unwrap()
+}
+
+#[derive(Default)]
+struct MockFetcher {
+    inbox: Mutex<Vec<RawEmail>>,
+    seen: Mutex<Vec<u32>>,
+    queries: Mutex<Vec<u32>>,
+    moved: Mutex<Vec<(u32, String)>>,
+}
+
+#[async_trait]
+impl MailFetcher for MockFetcher {
+    async fn fetch_unseen(&self, after: u32) -> Result<Vec<RawEmail>, TriggerError> {
+        self.queries.lock().push(after);
+        let seen = self.seen.lock().clone();
+        Ok(self
+            .inbox
+            .lock()
+            .iter()
+            .filter(|email| email.uid > after && !seen.contains(&email.uid))
+            .cloned()
+            .collect())
+    }
+
+    async fn mark_seen(&self, uids: &[u32]) -> Result<(), TriggerError> {
+        self.seen.lock().extend_from_slice(uids);
+        Ok(())
+    }
+
+    async fn move_to(&self, uids: &[u32], mailbox: &str) -> Result<(), TriggerError> {
+        self.inbox.lock().retain(|email| !uids.contains(&email.uid));
+        self.moved
+            .lock()
+            .extend(uids.iter().map(|uid| (*uid, mailbox.to_string())));
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct MockLauncher {
+    runs: Mutex<Vec<(String, HashMap<String, Value>)>>,
+}
+
+#[async_trait]
+impl WorkflowLauncher for MockLauncher {
+    async fn launch(&self, workflow_id: &str, inputs: HashMap<String, Value>) -> Result<(), String> {
+        self.runs.lock().push((workflow_id.to_string(), inputs));
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct MockSubmitter {
+    tasks: Mutex<Vec<(String, serde_json::Value, Option<String>)>>,
+}
+
+#[async_trait]
+impl TaskSubmitter for MockSubmitter {
+    async fn submit_task(
+        &self,
+        task_type: &str,
+        payload: serde_json::Value,
+        correlation_id: Option<String>,
+    ) -> Result<(), ExtensionError> {
+        self.tasks.lock().push((task_type.to_string(), payload, correlation_id));
+        Ok(())
+    }
+}
+
+#[test]
+fn test_config_defaults() {
+    let config = config();
+    assert_eq!(config.port, 993);
+    assert!(config.tls);
+    assert_eq!(config.mailbox, "INBOX");
+    assert_eq!(config.poll_interval_secs, 60);
+    assert!(config.mark_seen);
+    assert!(config.password.is_empty());
+    // The password is never written back out
+    let mut with_password = config.clone();
+    with_password.password = "secret".to_string();
+    assert!(serde_json::to_value(&with_password).unwrap().get("password").is_none());
+}
+
+#[test]
+fn test_parse_message() {
+    let message = EmailMessage::parse(&raw(7, REPORT)).unwrap();
+    assert_eq!(message.uid, 7);
+    assert_eq!(message.from, "alice@example.com");
+    assert_eq!(message.from_name.as_deref(), Some("Alice Smith"));
+    assert_eq!(message.subject, "Weekly report");
+    assert_eq!(message.message_id.as_deref(), Some("report-1@example.com"));
+    assert_eq!(message.body, "Numbers are attached.");
+    assert_eq!(message.attachments.len(), 2);
+
+    let csv = &message.attachments[0];
+    assert_eq!(csv.name, "numbers.csv");
+    assert_eq!(csv.content_type, "text/csv");
+    assert!(csv.text.as_deref().unwrap().contains("41,120"));
+    let pdf = &message.attachments[1];
+    assert_eq!(pdf.content_type, "application/pdf");
+    assert!(pdf.text.is_none());
+    assert_eq!(pdf.size, 8);
+
+    let prompt = message.render_prompt("Summarize this report from {from}.");
+    assert!(prompt.starts_with("Summarize this report from alice@example.com.\n\nFrom: Alice Smith <alice@example.com>\nSubject: Weekly report\n"));
+    assert!(prompt.contains("Numbers are attached."));
+    assert!(prompt.contains("Attachment: numbers.csv (text/csv, "));
+    assert!(prompt.contains("week,sales"));
+}
+
+#[test]
+fn test_save_attachments() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let mut message = EmailMessage::parse(&raw(7, REPORT)).unwrap();
+    message.save_attachments(dir.path()).unwrap();
+
+    // Names cannot escape the message's directory
+    let pdf = message.attachments[1].path.clone().unwrap();
+    assert_eq!(pdf, dir.path().join("7").join("report.pdf"));
+    assert_eq!(std::fs::read(&pdf).unwrap(), b"%PDF-1.4");
+    assert!(message.render_prompt("").contains(&format!("saved to {}", pdf.display())));
+}
+
+#[test]
+fn test_filter() {
+    let filter = EmailFilter::new(&config());
+    let report = EmailMessage::parse(&raw(1, REPORT)).unwrap();
+    let newsletter = EmailMessage::parse(&raw(2, NEWSLETTER)).unwrap();
+    assert!(filter.matches(&report));
+    assert!(!filter.matches(&newsletter));
+
+    let any = EmailFilter::new(&EmailConfig {
+        from: vec![],
+        subject: vec!["SALE".to_string()],
+        ..config()
+    });
+    assert!(any.matches(&newsletter));
+    assert!(!any.matches(&report));
+}
+
+#[tokio::test]
+async fn test_poll_injects_matching_messages() {
+    let fetcher = Arc::new(MockFetcher::default());
+    fetcher.inbox.lock().extend([raw(3, NEWSLETTER), raw(5, REPORT)]);
+    let submitter = Arc::new(MockSubmitter::default());
+    let injector = EmailInjector::new(config(), fetcher.clone(), submitter.clone());
+
+    assert_eq!(injector.poll().await.unwrap(), 1);
+    {
+        let tasks = submitter.tasks.lock();
+        assert_eq!(tasks.len(), 1);
+        let (task_type, payload, correlation_id) = &tasks[0];
+        assert_eq!(task_type, "trigger:email:received");
+        assert_eq!(correlation_id.as_deref(), Some("report-1@example.com"));
+        assert_eq!(payload["trigger_id"], "reports");
+        assert_eq!(payload["email"]["subject"], "Weekly report");
+        assert_eq!(payload["email"]["attachments"][0]["name"], "numbers.csv");
+        assert!(payload.get("agent").is_none());
+        assert!(payload["prompt"].as_str().unwrap().contains("Numbers are attached."));
+    }
+    assert_eq!(*fetcher.seen.lock(), vec![5]);
+
+    // Later polls only ask for newer messages
+    fetcher.inbox.lock().push(raw(9, REPORT));
+    assert_eq!(injector.poll().await.unwrap(), 1);
+    assert_eq!(*fetcher.queries.lock(), vec![0, 5]);
+    assert_eq!(injector.poll().await.unwrap(), 0);
+    assert_eq!(fetcher.queries.lock().last(), Some(&9));
+}
+
+#[tokio::test]
+async fn test_poll_archives_by_rules() {
+    let fetcher = Arc::new(MockFetcher::default());
+    fetcher.inbox.lock().extend([raw(3, NEWSLETTER), raw(5, REPORT)]);
+    let submitter = Arc::new(MockSubmitter::default());
+    let config = EmailConfig {
+        from: vec![],
+        subject: vec![],
+        archive_mailbox: Some("Archive".to_string()),
+        archive_from: vec!["*@shop.example".to_string()],
+        ..config()
+    };
+    let rules = ArchiveRules::new(&config);
+    let injector = EmailInjector::new(config, fetcher.clone(), submitter.clone());
+
+    assert_eq!(injector.poll().await.unwrap(), 1);
+    assert_eq!(*fetcher.moved.lock(), vec![(3, "Archive".to_string())]);
+    assert_eq!(*fetcher.seen.lock(), vec![5]);
+    assert_eq!(submitter.tasks.lock()[0].1["trigger_id"], "reports");
+
+    // No rules archive nothing; a subject rule matches on its own
+    let report = EmailMessage::parse(&raw(5, REPORT)).unwrap();
+    assert!(!rules.matches(&report));
+    assert!(!ArchiveRules::new(&config()).matches(&report));
+    let weekly = ArchiveRules::new(&EmailConfig {
+        archive_subject: vec!["WEEKLY".to_string()],
+        ..config()
+    });
+    assert!(weekly.matches(&report));
+}
+
+#[tokio::test]
+async fn test_poll_starts_workflow() {
+    let fetcher = Arc::new(MockFetcher::default());
+    fetcher.inbox.lock().push(raw(5, REPORT));
+    let submitter = Arc::new(MockSubmitter::default());
+    let config = EmailConfig {
+        workflow: Some("inbox-zero".to_string()),
+        ..config()
+    };
+
+    // Without a launcher the message is left for the next poll
+    let injector = EmailInjector::new(config.clone(), fetcher.clone(), submitter.clone());
+    assert!(injector.poll().await.is_err());
+    assert!(fetcher.seen.lock().is_empty());
+
+    let launcher = Arc::new(MockLauncher::default());
+    let injector = EmailInjector::new(config, fetcher.clone(), submitter.clone())
+        .with_workflow_launcher(launcher.clone());
+    assert_eq!(injector.poll().await.unwrap(), 1);
+    assert!(submitter.tasks.lock().is_empty());
+
+    let runs = launcher.runs.lock();
+    let (workflow, inputs) = &runs[0];
+    assert_eq!(workflow, "inbox-zero");
+    assert_eq!(inputs["trigger_id"], "reports");
+    assert_eq!(inputs["uid"], 5);
+    assert_eq!(inputs["from_name"], "Alice Smith");
+    assert_eq!(inputs["subject"], "Weekly report");
+    assert_eq!(inputs["attachments"][0]["name"], "numbers.csv");
+    assert!(inputs["message"].as_str().unwrap().starts_with("From: Alice Smith <alice@example.com>\n"));
+}
@@ -0,0 +1,243 @@
+//! Watch-and-act pipeline for file watchers.
+//!
+//! A [`FilePipeline`] turns the batches of a [`FileWatcherManager`]'s
+//! triggers into work, as declared in each watcher's config:
+//!
+//! - With a `prompt`, the agent runs the rendered prompt as a
+//!   `trigger:file:changed` task (see [`render_file_prompt`]).
+//! - With a `workflow`, the workflow runs through a [`WorkflowLauncher`] with
+//!   the changes as its inputs (`path`, `paths`, `file_name`, `change_type`,
+//!   `changes` and `trigger_id`).
+//!
+//! With `per_file`, each changed file gets its own task or run; otherwise
+//! a batch is handled at once.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, error, info, warn};
+
+use super::file_watcher_manager::FileWatcherManager;
+use super::trigger_types::{FileWatcherConfig, Trigger, TriggerEvent};
+use crate::run_loop::RunLoop;
+use crate::task::{Task, TaskSource};
+
+/// Starts workflow runs on behalf of file watchers.
+#[async_trait]
+pub trait WorkflowLauncher: Send + Sync {
+    /// Start a run of a workflow with the given inputs.
+    async fn launch(&self, workflow_id: &str, inputs: HashMap<String, Value>) -> Result<(), String>;
+}
+
+/// A changed file, as reported in a file watcher batch.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct FileChange {
+    /// Path of the file.
+    pub path: String,
+    /// Kind of change (`created`, `modified`, `deleted`, `renamed`).
+    pub change_type: String,
+}
+
+impl FileChange {
+    /// Changes carried by a file watcher event.
+    pub fn from_event(event: &TriggerEvent) -> Vec<FileChange> {
+        event
+            .data
+            .get("changes")
+            .cloned()
+            .and_then(|changes| serde_json::from_value(changes).ok())
+            .unwrap_or_default()
+    }
+
+    fn file_name(&self) -> String {
+        Path::new(&self.path)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+}
+
+/// Render a prompt template for a batch of changes.
+///
+/// `{path}`, `{file_name}` and `{change_type}` are those of the first change,
+/// `{paths}` lists every changed path, one per line, and `{count}` is the
+/// number of changes.
+pub fn render_file_prompt(template: &str, changes: &[FileChange]) -> String {
+    let first = changes.first();
+    let paths: Vec<&str> = changes.iter().map(|c| c.path.as_str()).collect();
+    template
+        .replace("{path}", first.map_or("", |c| c.path.as_str()))
+        .replace("{file_name}", &first.map(FileChange::file_name).unwrap_or_default())
+        .replace("{change_type}", first.map_or("", |c| c.change_type.as_str()))
+        .replace("{paths}", &paths.join("\n"))
+        .replace("{count}", &changes.len().to_string())
+}
+
+/// Work to do for a batch of changes.
+#[derive(Debug)]
+pub(crate) enum FileAction {
+    /// Run a task on the RunLoop.
+    Task(Box<Task>),
+    /// Start a workflow run.
+    Workflow {
+        /// Workflow ID.
+        id: String,
+        /// Workflow inputs.
+        inputs: HashMap<String, Value>,
+    },
+}
+
+/// Actions a watcher's config calls for on a batch of changes.
+pub(crate) fn file_actions(config: &FileWatcherConfig, changes: Vec<FileChange>) -> Vec<FileAction> {
+    if changes.is_empty() {
+        return Vec::new();
+    }
+    let groups = if config.per_file {
+        changes.into_iter().map(|change| vec![change]).collect()
+    } else {
+        vec![changes]
+    };
+    groups
+        .into_iter()
+        .map(|changes| file_action(config, &changes))
+        .collect()
+}
+
+fn file_action(config: &FileWatcherConfig, changes: &[FileChange]) -> FileAction {
+    let first = &changes[0];
+    let change_list: Vec<Value> = changes
+        .iter()
+        .map(|c| json!({"path": c.path, "change_type": c.change_type}))
+        .collect();
+
+    if let Some(workflow) = &config.workflow {
+        let inputs = HashMap::from([
+            ("trigger_id".to_string(), json!(config.id)),
+            ("path".to_string(), json!(first.path)),
+            ("paths".to_string(), json!(changes.iter().map(|c| &c.path).collect::<Vec<_>>())),
+            ("file_name".to_string(), json!(first.file_name())),
+            ("change_type".to_string(), json!(first.change_type)),
+            ("changes".to_string(), Value::Array(change_list)),
+        ]);
+        return FileAction::Workflow {
+            id: workflow.clone(),
+            inputs,
+        };
+    }
+
+    let mut payload = json!({
+        "prompt": render_file_prompt(&config.prompt, changes),
+        "trigger_id": config.id,
+        "path": first.path,
+        "changes": change_list,
+    });
+    if !config.agent.is_empty() {
+        payload["agent"] = json!(config.agent);
+    }
+    FileAction::Task(Box::new(
+        Task::new("trigger:file:changed", payload).with_source(TaskSource::FileWatcher),
+    ))
+}
+
+/// Runs the work file watchers are bound to.
+pub struct FilePipeline {
+    manager: Arc<FileWatcherManager>,
+    run_loop: Arc<RunLoop>,
+    workflows: Option<Arc<dyn WorkflowLauncher>>,
+}
+
+impl FilePipeline {
+    /// Create a pipeline for a manager's triggers.
+    pub fn new(manager: Arc<FileWatcherManager>, run_loop: Arc<RunLoop>) -> Self {
+        Self {
+            manager,
+            run_loop,
+            workflows: None,
+        }
+    }
+
+    /// Set the launcher of workflow bindings.
+    pub fn with_workflow_launcher(mut self, launcher: Arc<dyn WorkflowLauncher>) -> Self {
+        self.workflows = Some(launcher);
+        self
+    }
+
+    /// Start every trigger and act on its batches.
+    ///
+    /// Watchers with neither a prompt nor a workflow are left alone.
+    pub async fn start(&self) {
+        for trigger in self.manager.list().await {
+            let config = trigger.config.clone();
+            if config.prompt.is_empty() && config.workflow.is_none() {
+                warn!("File watcher {} has neither a prompt nor a workflow", config.id);
+                continue;
+            }
+            if config.workflow.is_some() && self.workflows.is_none() {
+                warn!("File watcher {} runs a workflow, but workflows are unavailable", config.id);
+                continue;
+            }
+
+            let mut events = trigger.subscribe();
+            if let Err(e) = trigger.start().await {
+                error!("Failed to start file watcher {}: {}", config.id, e);
+                continue;
+            }
+            let run_loop = self.run_loop.clone();
+            let workflows = self.workflows.clone();
+            tokio::spawn(async move {
+                loop {
+                    match events.recv().await {
+                        Ok(event) => {
+                            let changes = FileChange::from_event(&event);
+                            for action in file_actions(&config, changes) {
+                                run_action(&config.id, action, &run_loop, workflows.as_deref()).await;
+                            }
+                        }
+                        Err(RecvError::Lagged(n)) => {
+                            warn!("File pipeline {} lagged, missed {} batches", config.id, n);
+                        }
+                        Err(RecvError::Closed) => {
+                            debug!("File watcher {} closed, stopping pipeline", config.id);
+                            break;
+                        }
+                    }
+                }
+            });
+        }
+    }
+}
+
+async fn run_action(
+    trigger_id: &str,
+    action: FileAction,
+    run_loop: &RunLoop,
+    workflows: Option<&dyn WorkflowLauncher>,
+) {
+    match action {
+        FileAction::Task(task) => {
+            let task_id = task.id;
+            match run_loop.inject_task(*task).await {
+                Ok(()) => info!("File watcher {} queued task {}", trigger_id, task_id),
+                Err(e) => error!("File watcher {} failed to queue task: {}", trigger_id, e),
+            }
+        }
+        FileAction::Workflow { id, inputs } => {
+            let Some(workflows) = workflows else {
+                return;
+            };
+            match workflows.launch(&id, inputs).await {
+                Ok(()) => info!("File watcher {} started workflow {}", trigger_id, id),
+                Err(e) => error!("File watcher {} failed to start workflow {}: {}", trigger_id, id, e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "file_pipeline_tests.rs"]
+mod tests;
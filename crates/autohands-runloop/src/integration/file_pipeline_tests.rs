@@ -0,0 +1,112 @@
+use super::*;
+use std::path::PathBuf;
+
+use crate::integration::file_watcher_source::FileChangeType;
+
+fn config() -> FileWatcherConfig {
+    serde_json::from_value(json!({
+        "id": "inbox",
+        "paths": ["/home/me/Inbox"],
+        "patterns": ["*.pdf"],
+        "prompt": "Summarize {file_name} ({change_type}) and file it",
+    }))
+    .unwrap()
+}
+
+fn change(path: &str, change_type: &str) -> FileChange {
+    FileChange {
+        path: path.to_string(),
+        change_type: change_type.to_string(),
+    }
+}
+
+#[test]
+fn test_config_defaults() {
+    let config = config();
+    assert!(config.agent.is_empty());
+    assert!(config.workflow.is_none());
+    assert!(config.events.is_empty());
+    assert!(!config.per_file);
+    assert_eq!(config.paths, vec![PathBuf::from("/home/me/Inbox")]);
+
+    let config: FileWatcherConfig = serde_json::from_value(json!({
+        "id": "w", "paths": [], "workflow": "ingest", "events": ["created", "renamed"],
+    }))
+    .unwrap();
+    assert_eq!(config.events, vec![FileChangeType::Created, FileChangeType::Renamed]);
+}
+
+#[test]
+fn test_render_file_prompt() {
+    let changes = vec![change("/in/a.pdf", "created"), change("/in/b.pdf", "modified")];
+    assert_eq!(
+        render_file_prompt("{count} new: {paths}; first {file_name} at {path}", &changes),
+        "2 new: /in/a.pdf\n/in/b.pdf; first a.pdf at /in/a.pdf"
+    );
+    assert_eq!(render_file_prompt("Check {path}", &[]), "Check ");
+}
+
+#[test]
+fn test_file_actions_prompt() {
+    let mut config = config();
+    let changes = vec![change("/in/a.pdf", "created"), change("/in/b.pdf", "created")];
+
+    let actions = file_actions(&config, changes.clone());
+    assert_eq!(actions.len(), 1);
+    let FileAction::Task(task) = &actions[0] else {
+        panic!("expected a task");
+    };
+    assert_eq!(task.task_type, "trigger:file:changed");
+    assert_eq!(task.source, TaskSource::FileWatcher);
+    assert_eq!(task.payload["prompt"], "Summarize a.pdf (created) and file it");
+    assert_eq!(task.payload["changes"].as_array().unwrap().len(), 2);
+    assert!(task.payload.get("agent").is_none());
+
+    config.per_file = true;
+    config.agent = "archivist".to_string();
+    let actions = file_actions(&config, changes);
+    let prompts: Vec<_> = actions
+        .iter()
+        .map(|action| match action {
+            FileAction::Task(task) => {
+                assert_eq!(task.payload["agent"], "archivist");
+                task.payload["prompt"].as_str().unwrap().to_string()
+            }
+            FileAction::Workflow { .. } => panic!("expected a task"),
+        })
+        .collect();
+    assert_eq!(
+        prompts,
+        vec!["Summarize a.pdf (created) and file it", "Summarize b.pdf (created) and file it"]
+    );
+
+    assert!(file_actions(&config, Vec::new()).is_empty());
+}
+
+#[test]
+fn test_file_actions_workflow() {
+    let config = FileWatcherConfig {
+        workflow: Some("ingest".to_string()),
+        ..config()
+    };
+    let actions = file_actions(&config, vec![change("/in/a.pdf", "created")]);
+    let FileAction::Workflow { id, inputs } = &actions[0] else {
+        panic!("expected a workflow");
+    };
+    assert_eq!(id, "ingest");
+    assert_eq!(inputs["trigger_id"], "inbox");
+    assert_eq!(inputs["path"], "/in/a.pdf");
+    assert_eq!(inputs["file_name"], "a.pdf");
+    assert_eq!(inputs["paths"], json!(["/in/a.pdf"]));
+    assert_eq!(inputs["changes"][0]["change_type"], "created");
+}
+
+#[test]
+fn test_changes_from_event() {
+    let event = TriggerEvent::new("inbox", "file_watcher", "", "").with_data(json!({
+        "paths": ["/in/a.pdf"],
+        "changes": [{"path": "/in/a.pdf", "change_type": "created"}],
+    }));
+    assert_eq!(FileChange::from_event(&event), vec![change("/in/a.pdf", "created")]);
+    assert!(FileChange::from_event(&TriggerEvent::new("x", "file_watcher", "", "")).is_empty());
+}
@@ -6,6 +6,7 @@ mod tests;
 
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 
 use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
@@ -13,6 +14,7 @@ use serde_json::json;
 use tokio::sync::{broadcast, mpsc, RwLock};
 use tracing::{info, warn};
 
+use super::file_watcher_filter::PathFilter;
 use super::trigger_types::{FileWatcherConfig, TriggerError, TriggerEvent};
 
 /// File watcher trigger that monitors file system changes.
@@ -20,6 +22,8 @@ pub struct FileWatcherTrigger {
     pub(crate) config: FileWatcherConfig,
     pub(crate) enabled: AtomicBool,
     pub(crate) event_sender: broadcast::Sender<TriggerEvent>,
+    /// Path filter, rebuilt on start so `.gitignore` edits are picked up.
+    pub(crate) filter: parking_lot::RwLock<Arc<PathFilter>>,
     /// Watcher handle (Some when running).
     pub(crate) watcher: RwLock<Option<WatcherHandle>>,
 }
@@ -36,6 +40,7 @@ impl FileWatcherTrigger {
         let (sender, _) = broadcast::channel(100);
         Self {
             enabled: AtomicBool::new(config.enabled),
+            filter: parking_lot::RwLock::new(Arc::new(PathFilter::new(&config))),
             config,
             event_sender: sender,
             watcher: RwLock::new(None),
@@ -47,17 +52,10 @@ impl FileWatcherTrigger {
         self.event_sender.subscribe()
     }
 
-    /// Check if a path matches the configured patterns.
+    /// Check if a path passes the configured include and exclude patterns
+    /// and, when enabled, `.gitignore` rules.
     pub(crate) fn matches_pattern(&self, path: &PathBuf) -> bool {
-        if self.config.patterns.is_empty() {
-            return true;
-        }
-        let path_str = path.to_string_lossy();
-        self.config.patterns.iter().any(|pattern| {
-            glob::Pattern::new(pattern)
-                .map(|p| p.matches(&path_str))
-                .unwrap_or(false)
-        })
+        self.filter.read().matches(path)
     }
 
     /// Handle a file event.
@@ -105,6 +103,11 @@ impl FileWatcherTrigger {
         Duration::from_millis(self.config.debounce_ms)
     }
 
+    /// Get the longest time a batch of changes is held.
+    pub fn max_wait_duration(&self) -> Duration {
+        Duration::from_millis(self.config.max_wait_ms)
+    }
+
     /// Create the notify watcher and configure watched paths.
     pub(crate) fn create_watcher(
         &self,
@@ -0,0 +1,178 @@
+//! Path filtering and change batching for file watchers.
+//!
+//! [`PathFilter`] decides which changed paths a watcher reports: include
+//! globs, exclude globs and, optionally, the `.gitignore` files of the
+//! watched trees. [`ChangeBatcher`] coalesces bursts of changes (a build, a
+//! `git checkout`) into a single batch.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+use tokio::time::Instant;
+use tracing::{debug, warn};
+
+use super::file_watcher_source::FileChangeType;
+use super::trigger_types::FileWatcherConfig;
+
+/// Decides which changed paths a file watcher reports.
+pub(crate) struct PathFilter {
+    include: Vec<glob::Pattern>,
+    exclude: Vec<glob::Pattern>,
+    /// `.gitignore` matchers, deepest directory first.
+    gitignores: Vec<Gitignore>,
+    /// Whether `.gitignore` rules (and `.git/`) are respected.
+    respect_gitignore: bool,
+}
+
+impl PathFilter {
+    /// Build the filter of a watcher, reading the `.gitignore` files under
+    /// its paths when `respect_gitignore` is set.
+    pub(crate) fn new(config: &FileWatcherConfig) -> Self {
+        let mut gitignores = Vec::new();
+        if config.respect_gitignore {
+            for root in &config.paths {
+                gitignores.extend(load_gitignores(root));
+            }
+            // Nested files override their parents
+            gitignores.sort_by_key(|g| std::cmp::Reverse(g.path().components().count()));
+        }
+        Self {
+            include: compile(&config.patterns),
+            exclude: compile(&config.exclude),
+            gitignores,
+            respect_gitignore: config.respect_gitignore,
+        }
+    }
+
+    /// Whether a changed path is reported.
+    pub(crate) fn matches(&self, path: &Path) -> bool {
+        let path_str = path.to_string_lossy();
+        if !self.include.is_empty() && !self.include.iter().any(|p| p.matches(&path_str)) {
+            return false;
+        }
+        if self.exclude.iter().any(|p| p.matches(&path_str)) {
+            return false;
+        }
+        !(self.respect_gitignore && self.is_ignored(path))
+    }
+
+    /// Whether git ignores a path.
+    fn is_ignored(&self, path: &Path) -> bool {
+        if path.components().any(|c| c.as_os_str() == ".git") {
+            return true;
+        }
+        let is_dir = path.is_dir();
+        for gitignore in &self.gitignores {
+            if !path.starts_with(gitignore.path()) {
+                continue;
+            }
+            match gitignore.matched_path_or_any_parents(path, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        false
+    }
+}
+
+/// Compile glob patterns, skipping invalid ones.
+fn compile(patterns: &[String]) -> Vec<glob::Pattern> {
+    patterns
+        .iter()
+        .filter_map(|pattern| {
+            glob::Pattern::new(pattern)
+                .inspect_err(|e| warn!("Invalid file watcher pattern '{}': {}", pattern, e))
+                .ok()
+        })
+        .collect()
+}
+
+/// Matchers for the `.gitignore` files under a watched path.
+///
+/// Directories git ignores are not descended into.
+fn load_gitignores(root: &Path) -> Vec<Gitignore> {
+    let walk = ignore::WalkBuilder::new(root)
+        .hidden(false)
+        .git_global(false)
+        .require_git(false)
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .build();
+
+    walk.filter_map(Result::ok)
+        .filter(|entry| entry.file_name() == ".gitignore")
+        .filter_map(|entry| {
+            let dir = entry.path().parent()?;
+            let mut builder = GitignoreBuilder::new(dir);
+            if let Some(e) = builder.add(entry.path()) {
+                warn!("Failed to read {}: {}", entry.path().display(), e);
+            }
+            match builder.build() {
+                Ok(gitignore) => {
+                    debug!("Loaded {}", entry.path().display());
+                    Some(gitignore)
+                }
+                Err(e) => {
+                    warn!("Invalid {}: {}", entry.path().display(), e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Coalesces bursts of file changes into batches.
+///
+/// Repeated changes to a path collapse into one. A batch is due once no
+/// change arrived for the debounce window, or `max_wait` after its first
+/// change so a steady stream of changes is still reported.
+pub(crate) struct ChangeBatcher {
+    debounce: Duration,
+    max_wait: Duration,
+    changes: BTreeMap<PathBuf, FileChangeType>,
+    first: Option<Instant>,
+    last: Option<Instant>,
+}
+
+impl ChangeBatcher {
+    /// Create a batcher.
+    pub(crate) fn new(debounce: Duration, max_wait: Duration) -> Self {
+        Self {
+            debounce,
+            max_wait: max_wait.max(debounce),
+            changes: BTreeMap::new(),
+            first: None,
+            last: None,
+        }
+    }
+
+    /// Record a change.
+    pub(crate) fn record(&mut self, path: PathBuf, change: FileChangeType, now: Instant) {
+        self.changes
+            .entry(path)
+            .and_modify(|existing| *existing = existing.merge(change))
+            .or_insert(change);
+        self.first.get_or_insert(now);
+        self.last = Some(now);
+    }
+
+    /// When the pending batch is due.
+    pub(crate) fn deadline(&self) -> Option<Instant> {
+        let first = self.first?;
+        let last = self.last?;
+        Some((last + self.debounce).min(first + self.max_wait))
+    }
+
+    /// Take the pending batch if it is due.
+    pub(crate) fn take_due(&mut self, now: Instant) -> Option<Vec<(PathBuf, FileChangeType)>> {
+        if self.deadline()? > now {
+            return None;
+        }
+        self.first = None;
+        self.last = None;
+        Some(std::mem::take(&mut self.changes).into_iter().collect())
+    }
+}
@@ -5,6 +5,8 @@
 
 use std::sync::Arc;
 
+use notify::event::{EventKind, ModifyKind};
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 use autohands_protocols::extension::TaskSubmitter;
@@ -23,7 +25,8 @@ pub struct FileChangeEvent {
 }
 
 /// Type of file change.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum FileChangeType {
     Created,
     Modified,
@@ -31,6 +34,30 @@ pub enum FileChangeType {
     Renamed,
 }
 
+impl FileChangeType {
+    /// Change type of a filesystem event; `None` for accesses.
+    pub fn from_event_kind(kind: &EventKind) -> Option<Self> {
+        match kind {
+            EventKind::Create(_) => Some(FileChangeType::Created),
+            EventKind::Modify(ModifyKind::Name(_)) => Some(FileChangeType::Renamed),
+            EventKind::Modify(_) | EventKind::Any | EventKind::Other => {
+                Some(FileChangeType::Modified)
+            }
+            EventKind::Remove(_) => Some(FileChangeType::Deleted),
+            EventKind::Access(_) => None,
+        }
+    }
+
+    /// Combined change when a path changes again within a batch.
+    pub fn merge(self, later: FileChangeType) -> FileChangeType {
+        match (self, later) {
+            // Still new to whoever handles the batch
+            (FileChangeType::Created, FileChangeType::Modified) => FileChangeType::Created,
+            (_, later) => later,
+        }
+    }
+}
+
 impl std::fmt::Display for FileChangeType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -71,4 +98,29 @@ impl FileWatcherInjector {
             )
             .await
     }
+
+    /// Inject a batch of file changes as a single task.
+    ///
+    /// The agent and prompt are taken from the first change.
+    pub async fn inject_batch(&self, events: Vec<FileChangeEvent>) -> Result<(), autohands_protocols::error::ExtensionError> {
+        let Some(first) = events.first() else {
+            return Ok(());
+        };
+        let (agent, prompt) = (first.agent.clone(), first.prompt.clone());
+        let changes: Vec<_> = events
+            .iter()
+            .map(|event| json!({"path": event.path, "change_type": event.change_type.to_string()}))
+            .collect();
+        self.task_submitter
+            .submit_task(
+                "trigger:file:changed",
+                json!({
+                    "changes": changes,
+                    "agent": agent,
+                    "prompt": prompt,
+                }),
+                None,
+            )
+            .await
+    }
 }
@@ -4,6 +4,7 @@ use super::*;
 use super::super::trigger_types::{FileWatcherConfig, Trigger};
 use super::super::file_watcher_source::{FileChangeEvent, FileChangeType, FileWatcherInjector};
 use super::super::file_watcher_manager::FileWatcherManager;
+use super::super::file_watcher_filter::{ChangeBatcher, PathFilter};
 
 use std::path::PathBuf;
 use std::time::Duration;
@@ -15,10 +16,16 @@ fn test_config() -> FileWatcherConfig {
         id: "test-watcher".to_string(),
         paths: vec![PathBuf::from("/tmp")],
         patterns: vec!["*.txt".to_string()],
+        exclude: vec![],
+        respect_gitignore: false,
         agent: "general".to_string(),
         prompt: "Process file change".to_string(),
+        workflow: None,
+        events: vec![],
+        per_file: false,
         enabled: true,
         debounce_ms: 500,
+        max_wait_ms: 5000,
     }
 }
 
@@ -48,6 +55,111 @@ fn test_matches_pattern_empty() {
     assert!(trigger.matches_pattern(&PathBuf::from("/tmp/anything")));
 }
 
+#[test]
+fn test_exclude_patterns() {
+    let mut config = test_config();
+    config.patterns = vec![];
+    config.exclude = vec!["**/target/**".to_string(), "*.tmp".to_string()];
+    let filter = PathFilter::new(&config);
+
+    assert!(filter.matches(&PathBuf::from("/repo/src/main.rs")));
+    assert!(!filter.matches(&PathBuf::from("/repo/target/debug/app")));
+    assert!(!filter.matches(&PathBuf::from("/repo/notes.tmp")));
+}
+
+#[test]
+fn test_respect_gitignore() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    std::fs::create_dir_all(root.join("logs")).unwrap();
+    std::fs::create_dir_all(root.join("docs")).unwrap();
+    std::fs::write(root.join(".gitignore"), "*.log\nbuild/\n").unwrap();
+    std::fs::write(root.join("docs/.gitignore"), "!keep.log\n").unwrap();
+
+    let mut config = test_config();
+    config.paths = vec![root.to_path_buf()];
+    config.patterns = vec![];
+    config.respect_gitignore = true;
+    let filter = PathFilter::new(&config);
+
+    assert!(filter.matches(&root.join("src/main.rs")));
+    assert!(!filter.matches(&root.join("logs/app.log")));
+    assert!(!filter.matches(&root.join("build/out.txt")));
+    assert!(!filter.matches(&root.join(".git/index")));
+    // A nested .gitignore overrides its parent
+    assert!(filter.matches(&root.join("docs/keep.log")));
+
+    config.respect_gitignore = false;
+    assert!(PathFilter::new(&config).matches(&root.join("logs/app.log")));
+}
+
+#[test]
+fn test_change_batcher() {
+    let mut batcher = ChangeBatcher::new(Duration::from_millis(100), Duration::from_millis(250));
+    let start = tokio::time::Instant::now();
+    assert!(batcher.deadline().is_none());
+
+    batcher.record(PathBuf::from("/a"), FileChangeType::Created, start);
+    batcher.record(PathBuf::from("/b"), FileChangeType::Modified, start + Duration::from_millis(50));
+    batcher.record(PathBuf::from("/a"), FileChangeType::Modified, start + Duration::from_millis(80));
+
+    // Not quiet yet
+    assert!(batcher.take_due(start + Duration::from_millis(150)).is_none());
+    assert_eq!(batcher.deadline(), Some(start + Duration::from_millis(180)));
+
+    let batch = batcher.take_due(start + Duration::from_millis(180)).unwrap();
+    assert_eq!(
+        batch,
+        vec![
+            (PathBuf::from("/a"), FileChangeType::Created),
+            (PathBuf::from("/b"), FileChangeType::Modified),
+        ]
+    );
+    assert!(batcher.deadline().is_none());
+}
+
+#[test]
+fn test_change_batcher_max_wait() {
+    let mut batcher = ChangeBatcher::new(Duration::from_millis(100), Duration::from_millis(250));
+    let start = tokio::time::Instant::now();
+    for i in 0..10 {
+        let at = start + Duration::from_millis(i * 50);
+        batcher.record(PathBuf::from(format!("/f{}", i)), FileChangeType::Modified, at);
+    }
+
+    // A steady stream is flushed after max_wait
+    assert_eq!(batcher.deadline(), Some(start + Duration::from_millis(250)));
+    assert_eq!(batcher.take_due(start + Duration::from_millis(450)).unwrap().len(), 10);
+}
+
+#[test]
+fn test_change_type_from_event_kind() {
+    use notify::event::{AccessKind, CreateKind, ModifyKind, RemoveKind, RenameMode};
+    use notify::EventKind;
+
+    assert_eq!(
+        FileChangeType::from_event_kind(&EventKind::Create(CreateKind::File)),
+        Some(FileChangeType::Created)
+    );
+    assert_eq!(
+        FileChangeType::from_event_kind(&EventKind::Modify(ModifyKind::Name(RenameMode::Both))),
+        Some(FileChangeType::Renamed)
+    );
+    assert_eq!(
+        FileChangeType::from_event_kind(&EventKind::Remove(RemoveKind::File)),
+        Some(FileChangeType::Deleted)
+    );
+    assert_eq!(FileChangeType::from_event_kind(&EventKind::Access(AccessKind::Any)), None);
+    assert_eq!(
+        FileChangeType::Created.merge(FileChangeType::Modified),
+        FileChangeType::Created
+    );
+    assert_eq!(
+        FileChangeType::Created.merge(FileChangeType::Deleted),
+        FileChangeType::Deleted
+    );
+}
+
 #[test]
 fn test_handle_event() {
     let trigger = FileWatcherTrigger::new(test_config());
@@ -149,6 +261,7 @@ async fn test_file_watcher_event_detection() {
     // This test mainly verifies no panics/crashes
     if let Ok(Ok(event)) = result {
         assert_eq!(event.trigger_type, "file_watcher");
+        assert_eq!(event.data["changes"][0]["change_type"], "created");
     }
 }
 
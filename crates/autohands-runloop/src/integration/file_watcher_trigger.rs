@@ -1,16 +1,17 @@
 //! Trigger trait implementation for FileWatcherTrigger and event processing.
 
-use std::collections::HashMap;
-use std::path::PathBuf;
 use std::sync::atomic::Ordering;
-use std::time::{Duration, Instant};
+use std::sync::Arc;
 
 use async_trait::async_trait;
 use serde_json::json;
 use tokio::sync::{broadcast, mpsc};
+use tokio::time::Instant;
 use tracing::{debug, error, info, warn};
 
 use super::file_watcher::{FileWatcherTrigger, WatcherHandle};
+use super::file_watcher_filter::{ChangeBatcher, PathFilter};
+use super::file_watcher_source::FileChangeType;
 use super::trigger_types::{Trigger, TriggerError, TriggerEvent};
 
 #[async_trait]
@@ -49,13 +50,19 @@ impl Trigger for FileWatcherTrigger {
             });
         }
 
+        let filter = Arc::new(PathFilter::new(&self.config));
+        *self.filter.write() = filter.clone();
+
         spawn_event_processor(
-            self.config.id.clone(),
-            self.config.patterns.clone(),
-            self.config.agent.clone(),
-            self.config.prompt.clone(),
-            self.config.debounce_ms,
-            self.event_sender.clone(),
+            EventProcessor {
+                trigger_id: self.config.id.clone(),
+                agent: self.config.agent.clone(),
+                prompt: self.config.prompt.clone(),
+                events: self.config.events.clone(),
+                filter,
+                batcher: ChangeBatcher::new(self.debounce_duration(), self.max_wait_duration()),
+                event_sender: self.event_sender.clone(),
+            },
             event_rx,
             shutdown_rx,
         );
@@ -80,92 +87,96 @@ impl Trigger for FileWatcherTrigger {
     }
 }
 
-/// Spawn event processing task for file watcher.
-fn spawn_event_processor(
+/// State of a running watcher's event processing task.
+struct EventProcessor {
     trigger_id: String,
-    patterns: Vec<String>,
     agent: String,
     prompt: String,
-    debounce_ms: u64,
+    /// Kinds of change reported (empty = every kind).
+    events: Vec<FileChangeType>,
+    filter: Arc<PathFilter>,
+    batcher: ChangeBatcher,
     event_sender: broadcast::Sender<TriggerEvent>,
+}
+
+impl EventProcessor {
+    /// Add the matching paths of a filesystem event to the pending batch.
+    fn record(&mut self, event: notify::Event) {
+        let Some(change) = FileChangeType::from_event_kind(&event.kind) else {
+            return;
+        };
+        let now = Instant::now();
+        for path in event.paths {
+            if self.filter.matches(&path) {
+                self.batcher.record(path, change, now);
+            } else {
+                debug!("File watcher {} ignoring {:?}", self.trigger_id, path);
+            }
+        }
+    }
+
+    /// Fire the trigger with the pending batch if it is due.
+    fn flush(&mut self) {
+        let Some(mut changes) = self.batcher.take_due(Instant::now()) else {
+            return;
+        };
+        if !self.events.is_empty() {
+            changes.retain(|(_, change)| self.events.contains(change));
+        }
+        if changes.is_empty() {
+            return;
+        }
+        let trigger_event = TriggerEvent::new(
+            &self.trigger_id, "file_watcher", &self.agent, &self.prompt,
+        ).with_data(json!({
+            "paths": changes.iter().map(|(p, _)| p.to_string_lossy()).collect::<Vec<_>>(),
+            "changes": changes.iter().map(|(p, change)| json!({
+                "path": p.to_string_lossy(),
+                "change_type": change.to_string(),
+            })).collect::<Vec<_>>(),
+        }));
+        if let Err(e) = self.event_sender.send(trigger_event) {
+            warn!("Failed to send trigger event: {}", e);
+        } else {
+            info!("File watcher {} triggered: {} files changed",
+                self.trigger_id, changes.len());
+        }
+    }
+}
+
+/// Spawn event processing task for file watcher.
+///
+/// Matching changes are batched and the trigger fires once per batch.
+fn spawn_event_processor(
+    mut processor: EventProcessor,
     mut event_rx: mpsc::Receiver<notify::Result<notify::Event>>,
     mut shutdown_rx: mpsc::Receiver<()>,
 ) {
     tokio::spawn(async move {
-        let mut debounce_map: HashMap<PathBuf, Instant> = HashMap::new();
-        let debounce_duration = Duration::from_millis(debounce_ms);
-
         loop {
+            let deadline = processor.batcher.deadline();
             tokio::select! {
                 _ = shutdown_rx.recv() => {
-                    info!("File watcher {} shutting down", trigger_id);
+                    info!("File watcher {} shutting down", processor.trigger_id);
                     break;
                 }
-                Some(result) = event_rx.recv() => {
-                    if let Ok(event) = result {
-                        let paths = filter_and_debounce(
-                            event.paths,
-                            &patterns,
-                            &mut debounce_map,
-                            debounce_duration,
-                        );
-                        if !paths.is_empty() {
-                            let trigger_event = TriggerEvent::new(
-                                &trigger_id, "file_watcher", &agent, &prompt,
-                            ).with_data(json!({
-                                "paths": paths.iter().map(|p| p.to_string_lossy()).collect::<Vec<_>>(),
-                                "event_kind": format!("{:?}", event.kind),
-                            }));
-                            if let Err(e) = event_sender.send(trigger_event) {
-                                warn!("Failed to send trigger event: {}", e);
-                            } else {
-                                info!("File watcher {} triggered: {} files changed",
-                                    trigger_id, paths.len());
-                            }
-                        }
-                    } else if let Err(e) = result {
-                        error!("File watcher {} error: {}", trigger_id, e);
+                result = event_rx.recv() => {
+                    match result {
+                        Some(Ok(event)) => processor.record(event),
+                        Some(Err(e)) => error!("File watcher {} error: {}", processor.trigger_id, e),
+                        None => break,
                     }
                 }
+                _ = sleep_until_deadline(deadline) => processor.flush(),
             }
         }
     });
 }
 
-/// Filter paths by pattern and debounce.
-fn filter_and_debounce(
-    paths: Vec<PathBuf>,
-    patterns: &[String],
-    debounce_map: &mut HashMap<PathBuf, Instant>,
-    debounce_duration: Duration,
-) -> Vec<PathBuf> {
-    let now = Instant::now();
-    let mut result = Vec::new();
-
-    for path in paths {
-        if let Some(last_time) = debounce_map.get(&path) {
-            if now.duration_since(*last_time) < debounce_duration {
-                debug!("Debouncing event for {:?}", path);
-                continue;
-            }
-        }
-
-        let path_str = path.to_string_lossy();
-        let matches = if patterns.is_empty() {
-            true
-        } else {
-            patterns.iter().any(|p| {
-                glob::Pattern::new(p)
-                    .map(|pat| pat.matches(&path_str))
-                    .unwrap_or(false)
-            })
-        };
-
-        if matches {
-            debounce_map.insert(path.clone(), now);
-            result.push(path);
-        }
+/// Sleep until a batch deadline, or forever without one.
+async fn sleep_until_deadline(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
     }
-
-    result
 }
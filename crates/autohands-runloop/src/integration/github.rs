@@ -0,0 +1,320 @@
+//! GitHub webhook event normalization.
+//!
+//! Turns raw GitHub webhook deliveries (the `X-GitHub-Event` header plus the
+//! JSON body) into typed [`GitHubEvent`]s and from there into
+//! [`TriggerEvent`]s with a `github:<event>.<action>` trigger type, e.g.
+//! `github:issues.opened` or `github:check_run.completed`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::trigger_types::{TriggerError, TriggerEvent};
+
+/// Longest issue or PR body copied into a trigger prompt.
+const MAX_PROMPT_BODY_CHARS: usize = 4000;
+
+/// An issue event (`issues`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IssueEvent {
+    /// Action, e.g. `opened`, `closed`, `labeled`.
+    pub action: String,
+    /// Repository as `owner/name`.
+    pub repo: String,
+    /// Issue number.
+    pub number: u64,
+    /// Issue title.
+    pub title: String,
+    /// Issue body.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    /// Login of the issue author.
+    pub author: String,
+    /// Web URL of the issue.
+    pub url: String,
+    /// Label names.
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+/// A pull request event (`pull_request`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PullRequestEvent {
+    /// Action, e.g. `opened`, `synchronize`, `closed`.
+    pub action: String,
+    /// Repository as `owner/name`.
+    pub repo: String,
+    /// Pull request number.
+    pub number: u64,
+    /// Pull request title.
+    pub title: String,
+    /// Pull request body.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    /// Login of the pull request author.
+    pub author: String,
+    /// Web URL of the pull request.
+    pub url: String,
+    /// Source branch.
+    pub head: String,
+    /// Target branch.
+    pub base: String,
+    /// Whether the pull request is a draft.
+    pub draft: bool,
+    /// Whether the pull request was merged.
+    pub merged: bool,
+}
+
+/// A check run or check suite event (`check_run`, `check_suite`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckEvent {
+    /// Action, e.g. `completed`, `requested`.
+    pub action: String,
+    /// Repository as `owner/name`.
+    pub repo: String,
+    /// Check name (the app name for suites).
+    pub name: String,
+    /// Status, e.g. `queued`, `in_progress`, `completed`.
+    pub status: String,
+    /// Conclusion once completed, e.g. `success`, `failure`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conclusion: Option<String>,
+    /// Commit the check ran on.
+    pub head_sha: String,
+    /// Web URL of the check, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Numbers of the pull requests the check belongs to.
+    #[serde(default)]
+    pub pull_requests: Vec<u64>,
+}
+
+/// A GitHub webhook event AutoHands understands.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GitHubEvent {
+    /// `issues` event.
+    Issue(IssueEvent),
+    /// `pull_request` event.
+    PullRequest(PullRequestEvent),
+    /// `check_run` event.
+    CheckRun(CheckEvent),
+    /// `check_suite` event.
+    CheckSuite(CheckEvent),
+}
+
+impl GitHubEvent {
+    /// Parse a webhook delivery.
+    ///
+    /// Returns `Ok(None)` for event types that are not normalized (e.g.
+    /// `push` or `ping`), and an error if a supported event lacks fields.
+    pub fn parse(event_type: &str, body: &Value) -> Result<Option<Self>, TriggerError> {
+        let event = match event_type {
+            "issues" => Self::Issue(parse_issue(body)?),
+            "pull_request" => Self::PullRequest(parse_pull_request(body)?),
+            "check_run" => Self::CheckRun(parse_check(body, "check_run")?),
+            "check_suite" => Self::CheckSuite(parse_check(body, "check_suite")?),
+            _ => return Ok(None),
+        };
+        Ok(Some(event))
+    }
+
+    /// GitHub event type, as in the `X-GitHub-Event` header.
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            Self::Issue(_) => "issues",
+            Self::PullRequest(_) => "pull_request",
+            Self::CheckRun(_) => "check_run",
+            Self::CheckSuite(_) => "check_suite",
+        }
+    }
+
+    /// Event action.
+    pub fn action(&self) -> &str {
+        match self {
+            Self::Issue(e) => &e.action,
+            Self::PullRequest(e) => &e.action,
+            Self::CheckRun(e) | Self::CheckSuite(e) => &e.action,
+        }
+    }
+
+    /// Repository as `owner/name`.
+    pub fn repo(&self) -> &str {
+        match self {
+            Self::Issue(e) => &e.repo,
+            Self::PullRequest(e) => &e.repo,
+            Self::CheckRun(e) | Self::CheckSuite(e) => &e.repo,
+        }
+    }
+
+    /// Event name, e.g. `issues.opened`.
+    pub fn name(&self) -> String {
+        format!("{}.{}", self.event_type(), self.action())
+    }
+
+    /// Default prompt describing the event to an agent.
+    pub fn prompt(&self) -> String {
+        match self {
+            Self::Issue(e) => format!(
+                "GitHub issue #{} in {} was {} by {}: {}\n{}\n\n{}",
+                e.number,
+                e.repo,
+                e.action,
+                e.author,
+                e.title,
+                e.url,
+                excerpt(e.body.as_deref())
+            ),
+            Self::PullRequest(e) => format!(
+                "GitHub pull request #{} in {} ({} -> {}) was {} by {}: {}\n{}\n\n{}",
+                e.number,
+                e.repo,
+                e.head,
+                e.base,
+                e.action,
+                e.author,
+                e.title,
+                e.url,
+                excerpt(e.body.as_deref())
+            ),
+            Self::CheckRun(e) | Self::CheckSuite(e) => format!(
+                "GitHub {} '{}' in {} is {} ({}) for commit {}{}",
+                if matches!(self, Self::CheckRun(_)) { "check run" } else { "check suite" },
+                e.name,
+                e.repo,
+                e.status,
+                e.conclusion.as_deref().unwrap_or("no conclusion"),
+                e.head_sha,
+                e.url.as_deref().map(|u| format!("\n{}", u)).unwrap_or_default()
+            ),
+        }
+    }
+
+    /// Convert to a trigger event for `agent`, carrying the typed event as data.
+    pub fn to_trigger_event(
+        &self,
+        trigger_id: impl Into<String>,
+        agent: impl Into<String>,
+    ) -> TriggerEvent {
+        TriggerEvent::new(
+            trigger_id,
+            format!("github:{}", self.name()),
+            agent,
+            self.prompt(),
+        )
+        .with_data(serde_json::to_value(self).unwrap_or_default())
+    }
+}
+
+fn parse_issue(body: &Value) -> Result<IssueEvent, TriggerError> {
+    let issue = field(body, "issue")?;
+    Ok(IssueEvent {
+        action: str_field(body, "action")?,
+        repo: repo_name(body)?,
+        number: u64_field(issue, "number")?,
+        title: str_field(issue, "title")?,
+        body: opt_str(issue, "body"),
+        author: login(issue)?,
+        url: str_field(issue, "html_url")?,
+        labels: issue
+            .get("labels")
+            .and_then(Value::as_array)
+            .map(|labels| {
+                labels
+                    .iter()
+                    .filter_map(|l| l.get("name").and_then(Value::as_str))
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default(),
+    })
+}
+
+fn parse_pull_request(body: &Value) -> Result<PullRequestEvent, TriggerError> {
+    let pr = field(body, "pull_request")?;
+    Ok(PullRequestEvent {
+        action: str_field(body, "action")?,
+        repo: repo_name(body)?,
+        number: u64_field(pr, "number")?,
+        title: str_field(pr, "title")?,
+        body: opt_str(pr, "body"),
+        author: login(pr)?,
+        url: str_field(pr, "html_url")?,
+        head: str_field(field(pr, "head")?, "ref")?,
+        base: str_field(field(pr, "base")?, "ref")?,
+        draft: pr.get("draft").and_then(Value::as_bool).unwrap_or(false),
+        merged: pr.get("merged").and_then(Value::as_bool).unwrap_or(false),
+    })
+}
+
+fn parse_check(body: &Value, key: &str) -> Result<CheckEvent, TriggerError> {
+    let check = field(body, key)?;
+    let name = match check.get("name").and_then(Value::as_str) {
+        Some(name) => name.to_string(),
+        // Suites have no name of their own; use the app that runs them
+        None => str_field(field(check, "app")?, "name")?,
+    };
+    Ok(CheckEvent {
+        action: str_field(body, "action")?,
+        repo: repo_name(body)?,
+        name,
+        status: str_field(check, "status")?,
+        conclusion: opt_str(check, "conclusion"),
+        head_sha: str_field(check, "head_sha")?,
+        url: opt_str(check, "html_url"),
+        pull_requests: check
+            .get("pull_requests")
+            .and_then(Value::as_array)
+            .map(|prs| {
+                prs.iter()
+                    .filter_map(|pr| pr.get("number").and_then(Value::as_u64))
+                    .collect()
+            })
+            .unwrap_or_default(),
+    })
+}
+
+fn field<'a>(value: &'a Value, key: &str) -> Result<&'a Value, TriggerError> {
+    value
+        .get(key)
+        .filter(|v| !v.is_null())
+        .ok_or_else(|| TriggerError::Webhook(format!("GitHub event missing '{}'", key)))
+}
+
+fn str_field(value: &Value, key: &str) -> Result<String, TriggerError> {
+    field(value, key)?
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| TriggerError::Webhook(format!("GitHub event field '{}' is not a string", key)))
+}
+
+fn u64_field(value: &Value, key: &str) -> Result<u64, TriggerError> {
+    field(value, key)?
+        .as_u64()
+        .ok_or_else(|| TriggerError::Webhook(format!("GitHub event field '{}' is not a number", key)))
+}
+
+fn opt_str(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+fn repo_name(body: &Value) -> Result<String, TriggerError> {
+    str_field(field(body, "repository")?, "full_name")
+}
+
+fn login(item: &Value) -> Result<String, TriggerError> {
+    str_field(field(item, "user")?, "login")
+}
+
+fn excerpt(body: Option<&str>) -> String {
+    let body = body.unwrap_or("").trim();
+    if body.chars().count() <= MAX_PROMPT_BODY_CHARS {
+        return body.to_string();
+    }
+    let mut cut: String = body.chars().take(MAX_PROMPT_BODY_CHARS).collect();
+    cut.push_str("\n[truncated]");
+    cut
+}
+
+#[cfg(test)]
+#[path = "github_tests.rs"]
+mod tests;
@@ -0,0 +1,127 @@
+    use super::*;
+    use serde_json::json;
+
+    fn repository() -> Value {
+        json!({"full_name": "acme/app"})
+    }
+
+    #[test]
+    fn test_parse_issue() {
+        let body = json!({
+            "action": "opened",
+            "repository": repository(),
+            "issue": {
+                "number": 42,
+                "title": "Crash on start",
+                "body": "Steps to reproduce...",
+                "user": {"login": "octocat"},
+                "html_url": "https://github.com/acme/app/issues/42",
+                "labels": [{"name": "bug"}, {"name": "p1"}],
+            },
+        });
+        let event = GitHubEvent::parse("issues", &body).unwrap().unwrap();
+        assert_eq!(event.name(), "issues.opened");
+        assert_eq!(event.repo(), "acme/app");
+        let GitHubEvent::Issue(issue) = &event else {
+            panic!("expected issue event");
+        };
+        assert_eq!(issue.number, 42);
+        assert_eq!(issue.labels, vec!["bug", "p1"]);
+        assert!(event.prompt().contains("#42 in acme/app was opened by octocat"));
+    }
+
+    #[test]
+    fn test_parse_pull_request() {
+        let body = json!({
+            "action": "synchronize",
+            "repository": repository(),
+            "pull_request": {
+                "number": 7,
+                "title": "Add feature",
+                "body": null,
+                "user": {"login": "dev"},
+                "html_url": "https://github.com/acme/app/pull/7",
+                "head": {"ref": "feature"},
+                "base": {"ref": "main"},
+                "draft": true,
+            },
+        });
+        let event = GitHubEvent::parse("pull_request", &body).unwrap().unwrap();
+        let GitHubEvent::PullRequest(pr) = &event else {
+            panic!("expected pull request event");
+        };
+        assert_eq!((pr.head.as_str(), pr.base.as_str()), ("feature", "main"));
+        assert!(pr.draft);
+        assert!(!pr.merged);
+        assert!(pr.body.is_none());
+    }
+
+    #[test]
+    fn test_parse_checks() {
+        let run = json!({
+            "action": "completed",
+            "repository": repository(),
+            "check_run": {
+                "name": "ci / test",
+                "status": "completed",
+                "conclusion": "failure",
+                "head_sha": "abc123",
+                "html_url": "https://github.com/acme/app/runs/1",
+                "pull_requests": [{"number": 7}],
+            },
+        });
+        let event = GitHubEvent::parse("check_run", &run).unwrap().unwrap();
+        assert_eq!(event.name(), "check_run.completed");
+        let GitHubEvent::CheckRun(check) = &event else {
+            panic!("expected check run event");
+        };
+        assert_eq!(check.conclusion.as_deref(), Some("failure"));
+        assert_eq!(check.pull_requests, vec![7]);
+
+        let suite = json!({
+            "action": "completed",
+            "repository": repository(),
+            "check_suite": {
+                "app": {"name": "GitHub Actions"},
+                "status": "completed",
+                "conclusion": "success",
+                "head_sha": "abc123",
+            },
+        });
+        let event = GitHubEvent::parse("check_suite", &suite).unwrap().unwrap();
+        let GitHubEvent::CheckSuite(check) = &event else {
+            panic!("expected check suite event");
+        };
+        assert_eq!(check.name, "GitHub Actions");
+        assert!(check.url.is_none());
+    }
+
+    #[test]
+    fn test_parse_unsupported_and_malformed() {
+        assert!(GitHubEvent::parse("push", &json!({})).unwrap().is_none());
+        let err = GitHubEvent::parse("issues", &json!({"action": "opened"})).unwrap_err();
+        assert!(err.to_string().contains("missing 'issue'"));
+    }
+
+    #[test]
+    fn test_to_trigger_event() {
+        let event = GitHubEvent::CheckRun(CheckEvent {
+            action: "completed".to_string(),
+            repo: "acme/app".to_string(),
+            name: "lint".to_string(),
+            status: "completed".to_string(),
+            conclusion: Some("failure".to_string()),
+            head_sha: "abc".to_string(),
+            url: None,
+            pull_requests: vec![],
+        });
+        let trigger = event.to_trigger_event("github", "general");
+        assert_eq!(trigger.trigger_type, "github:check_run.completed");
+        assert_eq!(trigger.agent, "general");
+        assert_eq!(trigger.data["kind"], "check_run");
+        assert_eq!(trigger.data["conclusion"], "failure");
+        assert!(trigger.prompt.contains("check run 'lint'"));
+
+        let round_trip: GitHubEvent = serde_json::from_value(trigger.data).unwrap();
+        assert_eq!(round_trip, event);
+    }
@@ -7,12 +7,20 @@
 //! no separate adapter needed.
 
 pub mod channel_bridge;
+pub mod channel_filter;
+pub mod channel_rate_limit;
 pub mod checkpoint;
+pub mod email;
+pub mod email_imap;
+pub mod file_pipeline;
 pub mod file_watcher;
+mod file_watcher_filter;
 pub mod file_watcher_manager;
 pub mod file_watcher_source;
 mod file_watcher_trigger;
+pub mod github;
 pub mod health;
+pub mod mqtt;
 pub mod runtime;
 pub mod scheduler;
 pub mod signal;
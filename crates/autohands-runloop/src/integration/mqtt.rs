@@ -0,0 +1,312 @@
+//! MQTT integration with RunLoop.
+//!
+//! Connects to a broker for two purposes:
+//!
+//! - [`MqttSource1`] turns messages on the subscribed topics into
+//!   `trigger:mqtt:message` tasks, rendering the subscription's prompt.
+//! - [`MqttChannel`] is a channel whose targets are topics, so agent results
+//!   can be published, e.g. to Home Assistant, through a subscription's
+//!   `reply_topic` or any task's delivery targets.
+//!
+//! Both share one client; its event loop reconnects and resubscribes on its
+//! own after the broker goes away.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use autohands_protocols::channel::{
+    Channel, ChannelCapabilities, ChannelId, InboundMessage, OutboundMessage, ReplyAddress,
+    SentMessage,
+};
+use autohands_protocols::error::ChannelError;
+use rumqttc::{AsyncClient, Event, EventLoop, MqttOptions, Packet, QoS};
+use serde_json::{json, Value};
+use tokio::sync::{broadcast, mpsc};
+use tracing::{debug, info, warn};
+
+use super::trigger_types::{MqttConfig, MqttSubscription};
+use crate::delivery::DeliveryTarget;
+use crate::error::RunLoopResult;
+use crate::mode::RunLoopMode;
+use crate::source::{PortMessage, Source1, Source1Receiver};
+use crate::task::{Task, TaskSource};
+
+/// Pending client requests before publishing waits.
+const REQUEST_CAPACITY: usize = 64;
+
+/// Pause before reconnecting after a connection error.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Whether a topic matches a subscription filter (`+` matches one level,
+/// a trailing `#` any number of levels).
+pub fn topic_matches(filter: &str, topic: &str) -> bool {
+    let mut levels = topic.split('/');
+    for part in filter.split('/') {
+        match part {
+            "#" => return true,
+            "+" => {
+                if levels.next().is_none() {
+                    return false;
+                }
+            }
+            part => {
+                if levels.next() != Some(part) {
+                    return false;
+                }
+            }
+        }
+    }
+    levels.next().is_none()
+}
+
+fn qos(level: u8) -> QoS {
+    match level {
+        0 => QoS::AtMostOnce,
+        1 => QoS::AtLeastOnce,
+        _ => QoS::ExactlyOnce,
+    }
+}
+
+/// MQTT Source1.
+///
+/// Receives messages from the broker connection and produces tasks.
+pub struct MqttSource1 {
+    id: String,
+    config: MqttConfig,
+    cancelled: Arc<AtomicBool>,
+    modes: Vec<RunLoopMode>,
+}
+
+impl MqttSource1 {
+    /// Create a new MQTT source for a broker.
+    pub fn new(config: MqttConfig) -> Self {
+        Self {
+            id: format!("mqtt:{}", config.id),
+            config,
+            cancelled: Arc::new(AtomicBool::new(false)),
+            modes: vec![RunLoopMode::Default],
+        }
+    }
+
+    /// Connect to the broker, returning the Source1Receiver for the RunLoop
+    /// and the channel publishing to it.
+    ///
+    /// The connection runs in the background until the source is cancelled
+    /// or its receiver dropped.
+    pub fn connect(self) -> (Source1Receiver, Arc<MqttChannel>) {
+        let mut options = MqttOptions::new(&self.config.client_id, &self.config.host, self.config.port);
+        options.set_keep_alive(Duration::from_secs(self.config.keep_alive_secs.max(1)));
+        if let Some(username) = &self.config.username {
+            options.set_credentials(username, &self.config.password);
+        }
+        let (client, event_loop) = AsyncClient::new(options, REQUEST_CAPACITY);
+
+        let (tx, rx) = mpsc::channel(REQUEST_CAPACITY);
+        let channel = Arc::new(MqttChannel::new(&self.config, client.clone()));
+        tokio::spawn(Self::run(
+            self.id.clone(),
+            self.config.subscriptions.clone(),
+            client,
+            event_loop,
+            tx,
+            self.cancelled.clone(),
+        ));
+        (Source1Receiver::new(Arc::new(self), rx), channel)
+    }
+
+    /// Drive the connection, forwarding published messages as port messages.
+    async fn run(
+        source_id: String,
+        subscriptions: Vec<MqttSubscription>,
+        client: AsyncClient,
+        mut event_loop: EventLoop,
+        tx: mpsc::Sender<PortMessage>,
+        cancelled: Arc<AtomicBool>,
+    ) {
+        while !cancelled.load(Ordering::SeqCst) {
+            match event_loop.poll().await {
+                Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                    info!("MQTT {} connected", source_id);
+                    // Subscriptions do not survive a clean session
+                    for subscription in &subscriptions {
+                        if let Err(e) = client.try_subscribe(&subscription.topic, qos(subscription.qos)) {
+                            warn!("MQTT {} cannot subscribe to {}: {}", source_id, subscription.topic, e);
+                        }
+                    }
+                }
+                Ok(Event::Incoming(Packet::Publish(publish))) => {
+                    let msg = Self::create_message(&source_id, &publish.topic, &publish.payload);
+                    if tx.send(msg).await.is_err() {
+                        break;
+                    }
+                }
+                Ok(event) => debug!("MQTT {} event: {:?}", source_id, event),
+                Err(e) => {
+                    warn!("MQTT {} connection error: {}", source_id, e);
+                    tokio::time::sleep(RECONNECT_DELAY).await;
+                }
+            }
+        }
+        let _ = client.try_disconnect();
+        info!("MQTT {} disconnected", source_id);
+    }
+
+    /// Create a PortMessage from a published message.
+    pub fn create_message(source_id: &str, topic: &str, payload: &[u8]) -> PortMessage {
+        PortMessage::new(
+            source_id,
+            json!({
+                "topic": topic,
+                "payload": String::from_utf8_lossy(payload),
+            }),
+        )
+    }
+
+    /// Task for a message on a topic, if a subscription covers it.
+    fn task_for(&self, topic: &str, payload: &str) -> Option<Task> {
+        let subscription = self
+            .config
+            .subscriptions
+            .iter()
+            .find(|s| topic_matches(&s.topic, topic))?;
+
+        let prompt = if subscription.prompt.is_empty() {
+            payload.to_string()
+        } else {
+            subscription
+                .prompt
+                .replace("{topic}", topic)
+                .replace("{payload}", payload)
+        };
+        // JSON payloads are passed on structured
+        let message = serde_json::from_str::<Value>(payload).unwrap_or_else(|_| json!(payload));
+        let mut task_payload = json!({
+            "prompt": prompt,
+            "trigger_id": self.config.id,
+            "topic": topic,
+            "message": message,
+        });
+        if !subscription.agent.is_empty() {
+            task_payload["agent"] = json!(subscription.agent);
+        }
+
+        let mut task = Task::new("trigger:mqtt:message", task_payload)
+            .with_source(TaskSource::Custom("mqtt".to_string()));
+        if let Some(reply_topic) = &subscription.reply_topic {
+            task = task.with_delivery_target(
+                DeliveryTarget::new(&self.config.id, reply_topic.replace("{topic}", topic))
+                    .with_format(subscription.reply_format),
+            );
+        }
+        Some(task)
+    }
+}
+
+#[async_trait]
+impl Source1 for MqttSource1 {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    async fn handle(&self, msg: PortMessage) -> RunLoopResult<Vec<Task>> {
+        let topic = msg.payload["topic"].as_str().unwrap_or_default();
+        let payload = msg.payload["payload"].as_str().unwrap_or_default();
+        match self.task_for(topic, payload) {
+            Some(task) => Ok(vec![task]),
+            None => {
+                debug!("MQTT {}: no subscription for topic {}", self.id, topic);
+                Ok(Vec::new())
+            }
+        }
+    }
+
+    fn modes(&self) -> &[RunLoopMode] {
+        &self.modes
+    }
+
+    fn is_valid(&self) -> bool {
+        !self.cancelled.load(Ordering::SeqCst)
+    }
+
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+}
+
+/// MQTT notification channel.
+///
+/// Publishes outbound messages to the topic named by the reply address's
+/// target. It has no inbound messages; subscriptions produce tasks through
+/// [`MqttSource1`] instead.
+pub struct MqttChannel {
+    id: ChannelId,
+    client: AsyncClient,
+    qos: QoS,
+    retain: bool,
+    capabilities: ChannelCapabilities,
+    inbound_tx: broadcast::Sender<InboundMessage>,
+}
+
+impl MqttChannel {
+    fn new(config: &MqttConfig, client: AsyncClient) -> Self {
+        let (inbound_tx, _) = broadcast::channel(1);
+        Self {
+            id: config.id.clone(),
+            client,
+            qos: qos(config.publish_qos),
+            retain: config.retain,
+            capabilities: ChannelCapabilities::default(),
+            inbound_tx,
+        }
+    }
+}
+
+#[async_trait]
+impl Channel for MqttChannel {
+    fn id(&self) -> &ChannelId {
+        &self.id
+    }
+
+    fn capabilities(&self) -> &ChannelCapabilities {
+        &self.capabilities
+    }
+
+    async fn start(&self) -> Result<(), ChannelError> {
+        Ok(())
+    }
+
+    async fn stop(&self) -> Result<(), ChannelError> {
+        self.client
+            .disconnect()
+            .await
+            .map_err(|e| ChannelError::ConnectionFailed(e.to_string()))
+    }
+
+    async fn send(
+        &self,
+        target: &ReplyAddress,
+        message: OutboundMessage,
+    ) -> Result<SentMessage, ChannelError> {
+        if target.target.is_empty() || target.target.contains(['+', '#']) {
+            return Err(ChannelError::SendFailed(format!("Invalid topic: {}", target.target)));
+        }
+        self.client
+            .publish(&target.target, self.qos, self.retain, message.content)
+            .await
+            .map_err(|e| ChannelError::SendFailed(e.to_string()))?;
+        Ok(SentMessage {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: chrono::Utc::now(),
+        })
+    }
+
+    fn inbound(&self) -> broadcast::Receiver<InboundMessage> {
+        self.inbound_tx.subscribe()
+    }
+}
+
+#[cfg(test)]
+#[path = "mqtt_tests.rs"]
+mod tests;
@@ -0,0 +1,138 @@
+use super::*;
+use crate::delivery::DeliveryFormat;
+use bytes::BytesMut;
+use rumqttc::{ConnAck, ConnectReturnCode, Publish, SubAck, SubscribeReasonCode};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+fn config(port: u16) -> MqttConfig {
+    serde_json::from_value(json!({
+        "host": "127.0.0.1",
+        "port": port,
+        "publish_qos": 0,
+        "subscriptions": [
+            {
+                "topic": "home/+/motion",
+                "prompt": "Motion in {topic}: {payload}",
+                "agent": "house",
+                "reply_topic": "{topic}/reply",
+                "reply_format": "plain",
+            },
+            {"topic": "autohands/ask/#"},
+        ],
+    }))
+    .unwrap()
+}
+
+/// Read one packet from a client.
+async fn read_packet(socket: &mut TcpStream, buf: &mut BytesMut) -> Packet {
+    loop {
+        if let Ok(packet) = Packet::read(buf, 1024 * 1024) {
+            return packet;
+        }
+        assert!(socket.read_buf(buf).await.unwrap() > 0, "client disconnected");
+    }
+}
+
+async fn write_packet(socket: &mut TcpStream, packet: Packet) {
+    let mut out = BytesMut::new();
+    packet.write(&mut out, 1024 * 1024).unwrap();
+    socket.write_all(&out).await.unwrap();
+}
+
+#[test]
+fn test_topic_matches() {
+    assert!(topic_matches("home/kitchen/motion", "home/kitchen/motion"));
+    assert!(topic_matches("home/+/motion", "home/kitchen/motion"));
+    assert!(!topic_matches("home/+/motion", "home/kitchen/door"));
+    assert!(!topic_matches("home/+/motion", "home/motion"));
+    assert!(topic_matches("home/#", "home/kitchen/motion"));
+    assert!(topic_matches("home/#", "home"));
+    assert!(!topic_matches("home/kitchen", "home/kitchen/motion"));
+}
+
+#[test]
+fn test_config_defaults() {
+    let config = config(1883);
+    assert_eq!(config.id, "mqtt");
+    assert_eq!(config.client_id, "autohands");
+    assert_eq!(config.keep_alive_secs, 30);
+    assert!(config.enabled && !config.retain);
+    assert_eq!(config.subscriptions[1].qos, 0);
+    assert_eq!(config.subscriptions[1].reply_format, DeliveryFormat::Markdown);
+}
+
+#[tokio::test]
+async fn test_handle_creates_tasks() {
+    let source = MqttSource1::new(config(1883));
+    assert_eq!(Source1::id(&source), "mqtt:mqtt");
+
+    let msg = MqttSource1::create_message("mqtt:mqtt", "home/kitchen/motion", br#"{"occupied": true}"#);
+    let tasks = source.handle(msg).await.unwrap();
+    assert_eq!(tasks.len(), 1);
+    let task = &tasks[0];
+    assert_eq!(task.task_type, "trigger:mqtt:message");
+    assert_eq!(task.source, TaskSource::Custom("mqtt".to_string()));
+    assert_eq!(task.payload["prompt"], r#"Motion in home/kitchen/motion: {"occupied": true}"#);
+    assert_eq!(task.payload["message"]["occupied"], true);
+    assert_eq!(task.payload["agent"], "house");
+    assert_eq!(
+        task.deliver_to,
+        vec![DeliveryTarget::new("mqtt", "home/kitchen/motion/reply").with_format(DeliveryFormat::Plain)]
+    );
+
+    // Without a prompt the payload is the prompt
+    let msg = MqttSource1::create_message("mqtt:mqtt", "autohands/ask/lights", b"Turn off the lights");
+    let tasks = source.handle(msg).await.unwrap();
+    assert_eq!(tasks[0].payload["prompt"], "Turn off the lights");
+    assert!(tasks[0].payload.get("agent").is_none());
+    assert!(tasks[0].deliver_to.is_empty());
+
+    let msg = MqttSource1::create_message("mqtt:mqtt", "garden/rain", b"yes");
+    assert!(source.handle(msg).await.unwrap().is_empty());
+
+    source.cancel();
+    assert!(!source.is_valid());
+}
+
+#[tokio::test]
+async fn test_connect_subscribes_and_publishes() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (receiver, channel) = MqttSource1::new(config(port)).connect();
+
+    let (mut socket, _) = listener.accept().await.unwrap();
+    let mut buf = BytesMut::new();
+    assert!(matches!(read_packet(&mut socket, &mut buf).await, Packet::Connect(_)));
+    write_packet(&mut socket, Packet::ConnAck(ConnAck::new(ConnectReturnCode::Success, false))).await;
+
+    let mut topics = Vec::new();
+    while topics.len() < 2 {
+        let Packet::Subscribe(subscribe) = read_packet(&mut socket, &mut buf).await else {
+            panic!("expected a subscription");
+        };
+        topics.extend(subscribe.filters.iter().map(|f| f.path.clone()));
+        let codes = vec![SubscribeReasonCode::Success(QoS::AtMostOnce); subscribe.filters.len()];
+        write_packet(&mut socket, Packet::SubAck(SubAck::new(subscribe.pkid, codes))).await;
+    }
+    assert_eq!(topics, vec!["home/+/motion", "autohands/ask/#"]);
+
+    // Published messages reach the RunLoop as port messages
+    let publish = Publish::new("home/hall/motion", QoS::AtMostOnce, "on");
+    write_packet(&mut socket, Packet::Publish(publish)).await;
+    let msg = receiver.receiver_arc().lock().await.recv().await.unwrap();
+    assert_eq!(msg.source_id, "mqtt:mqtt");
+    assert_eq!(msg.payload, json!({"topic": "home/hall/motion", "payload": "on"}));
+
+    // Results are published to the target topic
+    let target = ReplyAddress::new("mqtt", "home/hall/motion/reply");
+    channel.send(&target, OutboundMessage::text("Lights on")).await.unwrap();
+    let Packet::Publish(publish) = read_packet(&mut socket, &mut buf).await else {
+        panic!("expected a publish");
+    };
+    assert_eq!(publish.topic, "home/hall/motion/reply");
+    assert_eq!(&publish.payload[..], b"Lights on");
+
+    let wildcard = ReplyAddress::new("mqtt", "home/#");
+    assert!(channel.send(&wildcard, OutboundMessage::text("x")).await.is_err());
+}
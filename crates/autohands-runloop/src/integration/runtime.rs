@@ -8,6 +8,8 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use tracing::{debug, error, info};
 
+use autohands_protocols::agent::RunOverrides;
+use autohands_protocols::error::AgentError;
 use autohands_protocols::types::Message;
 use autohands_runtime::AgentRuntime;
 
@@ -61,13 +63,16 @@ impl RuntimeAgentEventHandler {
 
     /// Extract session ID from task.
     fn get_session_id(&self, task: &Task) -> String {
-        task
-            .payload
-            .get("session_id")
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .or_else(|| task.correlation_id.clone())
-            .unwrap_or_else(|| task.id.to_string())
+        task.session_id()
+    }
+
+    /// Extract per-run model and generation overrides from task payload.
+    fn get_overrides(&self, task: &Task) -> Result<RunOverrides, AgentError> {
+        match task.payload.get("overrides") {
+            Some(overrides) => serde_json::from_value(overrides.clone())
+                .map_err(|e| AgentError::InvalidOverride(e.to_string())),
+            None => Ok(RunOverrides::default()),
+        }
     }
 
     /// Execute agent and convert result to AgentResult.
@@ -90,8 +95,26 @@ impl RuntimeAgentEventHandler {
         // Create user message from prompt
         let message = Message::user(&prompt);
 
-        // Execute through AgentRuntime
-        match self.runtime.execute(&agent_id, &session_id, message).await {
+        // Execute through AgentRuntime, traced under the task's correlation ID;
+        // tools hand the task's priority and deadline on to what they spawn,
+        // and a dry run only simulates tools with side effects
+        let result = match self.get_overrides(task) {
+            Ok(overrides) => {
+                self.runtime
+                    .execute_scheduled(
+                        &agent_id,
+                        &session_id,
+                        message,
+                        Some(task.trace_id()),
+                        overrides,
+                        task.scheduling(),
+                        task.is_dry_run(),
+                    )
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        match result {
             Ok(messages) => {
                 // Extract the final assistant response
                 let response = messages
@@ -168,13 +191,7 @@ impl RuntimeAgentEventHandler {
         follow_up: FollowUpTask,
     ) -> Task {
         let mut task = Task::new(follow_up.task_type, follow_up.payload)
-            .with_source(TaskSource::Agent)
-            .with_parent(parent_task.id);
-
-        // Inherit correlation ID for task chain tracking
-        if let Some(ref correlation_id) = parent_task.correlation_id {
-            task = task.with_correlation_id(correlation_id.clone());
-        }
+            .with_source(TaskSource::Agent);
 
         // Set scheduled time for delayed tasks
         if let Some(delay_ms) = follow_up.delay_ms {
@@ -188,7 +205,9 @@ impl RuntimeAgentEventHandler {
             task.priority = priority;
         }
 
-        task
+        // Inherit correlation ID for task chain tracking, and the parent's
+        // priority and deadline
+        task.inherit_from(parent_task)
     }
 
     /// Create an error task for notification.
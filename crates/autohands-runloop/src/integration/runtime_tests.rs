@@ -107,6 +107,33 @@
         assert_eq!(handler.get_session_id(&task), task.id.to_string());
     }
 
+    #[test]
+    fn test_get_overrides() {
+        let runtime = create_test_runtime();
+        let handler = RuntimeAgentEventHandler::new(runtime, "default");
+
+        let task = Task::new("agent:execute", serde_json::json!({ "prompt": "test" }));
+        assert!(handler.get_overrides(&task).unwrap().is_empty());
+
+        let task = Task::new(
+            "agent:execute",
+            serde_json::json!({
+                "prompt": "test",
+                "overrides": { "model": "fast", "max_tokens": 256, "tools": ["read_file"] },
+            }),
+        );
+        let overrides = handler.get_overrides(&task).unwrap();
+        assert_eq!(overrides.model.as_deref(), Some("fast"));
+        assert_eq!(overrides.max_tokens, Some(256));
+        assert!(overrides.allows_tool("read_file") && !overrides.allows_tool("exec"));
+
+        let task = Task::new(
+            "agent:execute",
+            serde_json::json!({ "prompt": "test", "overrides": { "temperature": "hot" } }),
+        );
+        assert!(handler.get_overrides(&task).is_err());
+    }
+
     #[test]
     fn test_create_error_task() {
         let runtime = create_test_runtime();
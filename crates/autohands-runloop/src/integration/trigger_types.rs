@@ -8,6 +8,9 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use uuid::Uuid;
 
+use super::file_watcher_source::FileChangeType;
+use crate::delivery::DeliveryFormat;
+
 /// Trigger error types.
 #[derive(Debug, Error)]
 pub enum TriggerError {
@@ -31,6 +34,10 @@ pub enum TriggerError {
     #[error("Webhook error: {0}")]
     Webhook(String),
 
+    /// Email (IMAP) error.
+    #[error("Email error: {0}")]
+    Email(String),
+
     /// Trigger disabled.
     #[error("Trigger is disabled: {0}")]
     Disabled(String),
@@ -118,6 +125,14 @@ pub struct TriggersConfig {
     /// File watcher triggers.
     #[serde(default)]
     pub file_watchers: Vec<FileWatcherConfig>,
+
+    /// Email triggers.
+    #[serde(default)]
+    pub email: Vec<EmailConfig>,
+
+    /// MQTT brokers.
+    #[serde(default)]
+    pub mqtt: Vec<MqttConfig>,
 }
 
 /// Webhook trigger configuration.
@@ -145,19 +160,206 @@ pub struct FileWatcherConfig {
     pub id: String,
     /// Paths to watch.
     pub paths: Vec<PathBuf>,
-    /// File patterns to match.
+    /// Glob patterns a changed path must match (empty = every path).
     #[serde(default)]
     pub patterns: Vec<String>,
-    /// Agent to trigger.
+    /// Glob patterns of changed paths to ignore.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Whether paths ignored by the `.gitignore` files under the watched
+    /// paths (and anything under `.git/`) are left out.
+    #[serde(default)]
+    pub respect_gitignore: bool,
+    /// Agent to trigger (the default agent when empty).
+    #[serde(default)]
     pub agent: String,
-    /// Prompt to execute.
+    /// Prompt template to execute. See
+    /// [`render_file_prompt`](super::file_pipeline::render_file_prompt).
+    #[serde(default)]
     pub prompt: String,
+    /// Workflow to run instead of prompting the agent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workflow: Option<String>,
+    /// Kinds of change reported (empty = every kind).
+    #[serde(default)]
+    pub events: Vec<FileChangeType>,
+    /// Whether each changed file gets its own task instead of one task per
+    /// batch.
+    #[serde(default)]
+    pub per_file: bool,
     /// Whether trigger is enabled.
     #[serde(default = "default_enabled")]
     pub enabled: bool,
-    /// Debounce delay in milliseconds.
+    /// Quiet period, in milliseconds, after which a burst of changes is
+    /// reported as one batch.
     #[serde(default = "default_debounce")]
     pub debounce_ms: u64,
+    /// Longest a batch is held while changes keep arriving, in milliseconds.
+    #[serde(default = "default_max_wait")]
+    pub max_wait_ms: u64,
+}
+
+/// Email trigger configuration.
+///
+/// Polls an IMAP mailbox and turns new messages that pass the `from` and
+/// `subject` filters into tasks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmailConfig {
+    /// Trigger ID.
+    pub id: String,
+    /// IMAP server host.
+    pub host: String,
+    /// IMAP server port.
+    #[serde(default = "default_imap_port")]
+    pub port: u16,
+    /// Whether to connect over TLS.
+    #[serde(default = "default_enabled")]
+    pub tls: bool,
+    /// Login user name.
+    pub username: String,
+    /// Login password.
+    #[serde(default, skip_serializing)]
+    pub password: String,
+    /// Mailbox to poll.
+    #[serde(default = "default_mailbox")]
+    pub mailbox: String,
+    /// Seconds between polls.
+    #[serde(default = "default_poll_interval")]
+    pub poll_interval_secs: u64,
+    /// Sender addresses or globs (e.g. `*@example.com`) a message must be
+    /// from (empty = any sender).
+    #[serde(default)]
+    pub from: Vec<String>,
+    /// Words one of which the subject must contain, ignoring case (empty =
+    /// any subject).
+    #[serde(default)]
+    pub subject: Vec<String>,
+    /// Agent to trigger (the default agent when empty).
+    #[serde(default)]
+    pub agent: String,
+    /// Instructions preceding the email in the prompt; `{from}` and
+    /// `{subject}` are replaced.
+    #[serde(default)]
+    pub prompt: String,
+    /// Workflow to run instead of prompting the agent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workflow: Option<String>,
+    /// Mailbox messages matching an archive rule are moved to, unhandled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_mailbox: Option<String>,
+    /// Sender addresses or globs whose messages are archived.
+    #[serde(default)]
+    pub archive_from: Vec<String>,
+    /// Words whose presence in the subject, ignoring case, archives a
+    /// message.
+    #[serde(default)]
+    pub archive_subject: Vec<String>,
+    /// Whether handled messages are flagged as seen.
+    #[serde(default = "default_enabled")]
+    pub mark_seen: bool,
+    /// Directory attachments are saved to, one subdirectory per message.
+    /// Attachments are only listed when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attachment_dir: Option<PathBuf>,
+    /// Whether trigger is enabled.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+/// MQTT broker configuration.
+///
+/// Messages on the subscribed topics become tasks; the broker is also a
+/// channel, `id`, whose targets are topics agent results are published to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MqttConfig {
+    /// Broker ID, also the ID of its channel.
+    #[serde(default = "default_mqtt_id")]
+    pub id: String,
+    /// Broker host.
+    pub host: String,
+    /// Broker port.
+    #[serde(default = "default_mqtt_port")]
+    pub port: u16,
+    /// Client ID presented to the broker.
+    #[serde(default = "default_mqtt_client_id")]
+    pub client_id: String,
+    /// Login user name (anonymous when unset).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    /// Login password.
+    #[serde(default, skip_serializing)]
+    pub password: String,
+    /// Keep-alive interval in seconds.
+    #[serde(default = "default_keep_alive")]
+    pub keep_alive_secs: u64,
+    /// Topics turned into tasks.
+    #[serde(default)]
+    pub subscriptions: Vec<MqttSubscription>,
+    /// QoS (0-2) of published results.
+    #[serde(default = "default_publish_qos")]
+    pub publish_qos: u8,
+    /// Whether published results are retained by the broker.
+    #[serde(default)]
+    pub retain: bool,
+    /// Whether the broker is enabled.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+/// Topic subscription of an MQTT broker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MqttSubscription {
+    /// Topic filter; `+` and `#` wildcards are allowed.
+    pub topic: String,
+    /// Subscription QoS (0-2).
+    #[serde(default)]
+    pub qos: u8,
+    /// Agent to trigger (the default agent when empty).
+    #[serde(default)]
+    pub agent: String,
+    /// Prompt template; `{topic}` and `{payload}` are replaced. The payload
+    /// is the prompt when empty.
+    #[serde(default)]
+    pub prompt: String,
+    /// Topic the agent's result is published to; `{topic}` is replaced by
+    /// the message's topic.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_topic: Option<String>,
+    /// Rendering of published results.
+    #[serde(default)]
+    pub reply_format: DeliveryFormat,
+}
+
+fn default_mqtt_id() -> String {
+    "mqtt".to_string()
+}
+
+fn default_mqtt_port() -> u16 {
+    1883
+}
+
+fn default_mqtt_client_id() -> String {
+    "autohands".to_string()
+}
+
+fn default_keep_alive() -> u64 {
+    30
+}
+
+fn default_publish_qos() -> u8 {
+    1
+}
+
+fn default_imap_port() -> u16 {
+    993
+}
+
+fn default_mailbox() -> String {
+    "INBOX".to_string()
+}
+
+fn default_poll_interval() -> u64 {
+    60
 }
 
 fn default_enabled() -> bool {
@@ -167,3 +369,7 @@ fn default_enabled() -> bool {
 fn default_debounce() -> u64 {
     500
 }
+
+fn default_max_wait() -> u64 {
+    5000
+}
@@ -0,0 +1,268 @@
+//! RunLoop event journal.
+//!
+//! With [`JournalConfig::enabled`](crate::config::JournalConfig::enabled),
+//! the RunLoop records what it does in a bounded in-memory journal: phase
+//! transitions, wakeups, tasks produced by sources, queueing and dispatch
+//! decisions, mode switches and pauses, each with a timestamp. Reading it
+//! back answers "why didn't my trigger fire at 02:00": whether the timer
+//! was promoted, its task queued, rejected or dispatched, or the loop
+//! paused at the time.
+//!
+//! Iterations that only cycle through the phases and wake up on the poll
+//! timeout are folded into one [`JournalEventKind::Idle`] entry, so an idle
+//! loop does not push the events that matter out of the journal.
+
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::mode::{DispatchState, RunLoopPhase};
+use crate::mode_policy::ModeSwitchReason;
+use crate::task::Task;
+
+/// Wakeup reason of an iteration that waited out its poll timeout.
+pub(crate) const IDLE_WAKEUP: &str = "timeout";
+
+/// A task as the journal refers to it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalTask {
+    pub id: Uuid,
+    pub task_type: String,
+    /// Source name, as in queue configuration and metrics.
+    pub source: String,
+}
+
+impl JournalTask {
+    pub fn of(task: &Task) -> Self {
+        Self {
+            id: task.id,
+            task_type: task.task_type.clone(),
+            source: task.source.name().to_string(),
+        }
+    }
+}
+
+/// How a task taken from the queue was handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DispatchDecision {
+    /// Handled in the loop (timer and system tasks).
+    Inline,
+    /// Cron task; its next occurrence was scheduled.
+    Rescheduled,
+    /// Started as a background agent task.
+    Spawned,
+    /// Ignored: no agent event handler is configured.
+    NoHandler,
+}
+
+/// What happened.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum JournalEventKind {
+    /// The loop reached a phase.
+    Phase { mode: String, phase: RunLoopPhase },
+    /// Iterations that did nothing but wait, folded together; `at` is when
+    /// the first one started.
+    Idle { iterations: u64 },
+    /// The loop woke up from waiting.
+    Wakeup { reason: String },
+    /// A source produced tasks.
+    SourceTriggered { source_id: String, tasks: usize },
+    /// A source failed to produce its tasks.
+    SourceFailed { source_id: String, error: String },
+    /// A task entered the queue, ready now or at `scheduled_at`.
+    Queued {
+        task: JournalTask,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        scheduled_at: Option<DateTime<Utc>>,
+    },
+    /// A task was refused, or dropped from the queue to make room.
+    Rejected { task: JournalTask, reason: String },
+    /// A delayed task reached its time and became ready.
+    Promoted {
+        task: JournalTask,
+        scheduled_at: DateTime<Utc>,
+    },
+    /// A ready task was taken from the queue.
+    Dispatched {
+        task: JournalTask,
+        decision: DispatchDecision,
+    },
+    /// An agent task was not started because its deadline had passed.
+    DeadlineMissed { task: JournalTask },
+    /// The mode policy switched modes.
+    ModeSwitched {
+        from: String,
+        to: String,
+        reason: ModeSwitchReason,
+    },
+    /// Dispatch was paused, resumed or drained.
+    DispatchChanged {
+        from: DispatchState,
+        to: DispatchState,
+    },
+}
+
+impl JournalEventKind {
+    /// Name of the kind, as serialized.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Phase { .. } => "phase",
+            Self::Idle { .. } => "idle",
+            Self::Wakeup { .. } => "wakeup",
+            Self::SourceTriggered { .. } => "source_triggered",
+            Self::SourceFailed { .. } => "source_failed",
+            Self::Queued { .. } => "queued",
+            Self::Rejected { .. } => "rejected",
+            Self::Promoted { .. } => "promoted",
+            Self::Dispatched { .. } => "dispatched",
+            Self::DeadlineMissed { .. } => "deadline_missed",
+            Self::ModeSwitched { .. } => "mode_switched",
+            Self::DispatchChanged { .. } => "dispatch_changed",
+        }
+    }
+
+    /// Whether the event is part of an iteration that may turn out idle.
+    fn is_routine(&self) -> bool {
+        match self {
+            Self::Phase { phase, .. } => {
+                !matches!(phase, RunLoopPhase::Entry | RunLoopPhase::Exit)
+            }
+            Self::Wakeup { reason } => reason == IDLE_WAKEUP,
+            _ => false,
+        }
+    }
+}
+
+/// A journal entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEvent {
+    /// Position in the journal, increasing by one per entry.
+    pub seq: u64,
+    pub at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub kind: JournalEventKind,
+}
+
+#[derive(Default)]
+struct JournalInner {
+    events: VecDeque<JournalEvent>,
+    /// Routine events of the current iteration, kept back until something
+    /// else happens in it.
+    iteration: Vec<(DateTime<Utc>, JournalEventKind)>,
+    /// Idle iterations not yet written, and when the first one started.
+    idle: Option<(DateTime<Utc>, u64)>,
+    next_seq: u64,
+}
+
+impl JournalInner {
+    fn push(&mut self, capacity: usize, at: DateTime<Utc>, kind: JournalEventKind) {
+        if self.events.len() >= capacity {
+            self.events.pop_front();
+        }
+        self.events.push_back(JournalEvent {
+            seq: self.next_seq,
+            at,
+            kind,
+        });
+        self.next_seq += 1;
+    }
+
+    /// Write the idle iterations and the current iteration's events.
+    fn flush(&mut self, capacity: usize) {
+        if let Some((since, iterations)) = self.idle.take() {
+            self.push(capacity, since, JournalEventKind::Idle { iterations });
+        }
+        for (at, kind) in std::mem::take(&mut self.iteration) {
+            self.push(capacity, at, kind);
+        }
+    }
+}
+
+/// Recent RunLoop events, oldest first.
+///
+/// Only the last `capacity` entries are kept.
+pub struct RunLoopJournal {
+    capacity: usize,
+    inner: Mutex<JournalInner>,
+}
+
+impl RunLoopJournal {
+    /// Create an empty journal keeping `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            inner: Mutex::new(JournalInner::default()),
+        }
+    }
+
+    /// Record an event.
+    pub fn record(&self, kind: JournalEventKind) {
+        let at = Utc::now();
+        let mut inner = self.inner.lock();
+        if !kind.is_routine() {
+            inner.flush(self.capacity);
+            inner.push(self.capacity, at, kind);
+            return;
+        }
+        // A new iteration: the previous one had nothing but routine events
+        let starts_iteration = matches!(
+            kind,
+            JournalEventKind::Phase {
+                phase: RunLoopPhase::BeforeTimers,
+                ..
+            }
+        );
+        if starts_iteration && !inner.iteration.is_empty() {
+            let started = inner.iteration[0].0;
+            inner.idle.get_or_insert((started, 0)).1 += 1;
+            inner.iteration.clear();
+        }
+        inner.iteration.push((at, kind));
+    }
+
+    /// Entries recorded at or after `since`, or all of them.
+    ///
+    /// Idle iterations not yet written are reported as a last
+    /// [`JournalEventKind::Idle`] entry.
+    pub fn events(&self, since: Option<DateTime<Utc>>) -> Vec<JournalEvent> {
+        let inner = self.inner.lock();
+        let mut events: Vec<JournalEvent> = inner
+            .events
+            .iter()
+            .filter(|event| since.map_or(true, |since| event.at >= since))
+            .cloned()
+            .collect();
+        if let Some((at, iterations)) = inner.idle {
+            events.push(JournalEvent {
+                seq: inner.next_seq,
+                at,
+                kind: JournalEventKind::Idle { iterations },
+            });
+        }
+        events
+    }
+
+    /// Number of entries written.
+    pub fn len(&self) -> usize {
+        self.inner.lock().events.len()
+    }
+
+    /// Whether no entry was written.
+    pub fn is_empty(&self) -> bool {
+        self.inner.lock().events.is_empty()
+    }
+
+    /// Number of entries kept.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+#[cfg(test)]
+#[path = "journal_tests.rs"]
+mod tests;
@@ -0,0 +1,101 @@
+use super::*;
+
+fn phase(phase: RunLoopPhase) -> JournalEventKind {
+    JournalEventKind::Phase {
+        mode: "Default".to_string(),
+        phase,
+    }
+}
+
+fn idle_iteration(journal: &RunLoopJournal) {
+    journal.record(phase(RunLoopPhase::BeforeTimers));
+    journal.record(phase(RunLoopPhase::BeforeSources));
+    journal.record(phase(RunLoopPhase::BeforeWaiting));
+    journal.record(JournalEventKind::Wakeup {
+        reason: IDLE_WAKEUP.to_string(),
+    });
+    journal.record(phase(RunLoopPhase::AfterWaiting));
+}
+
+fn task() -> JournalTask {
+    JournalTask::of(&Task::new("cron:nightly", serde_json::Value::Null))
+}
+
+#[test]
+fn test_idle_iterations_are_folded() {
+    let journal = RunLoopJournal::new(100);
+    journal.record(phase(RunLoopPhase::Entry));
+    for _ in 0..3 {
+        idle_iteration(&journal);
+    }
+    let promoted = JournalEventKind::Promoted {
+        task: task(),
+        scheduled_at: Utc::now(),
+    };
+    journal.record(phase(RunLoopPhase::BeforeTimers));
+    journal.record(promoted.clone());
+
+    let kinds: Vec<_> = journal.events(None).into_iter().map(|e| e.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            phase(RunLoopPhase::Entry),
+            JournalEventKind::Idle { iterations: 3 },
+            phase(RunLoopPhase::BeforeTimers),
+            promoted,
+        ]
+    );
+    let seqs: Vec<_> = journal.events(None).iter().map(|e| e.seq).collect();
+    assert_eq!(seqs, vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn test_pending_idle_is_reported() {
+    let journal = RunLoopJournal::new(100);
+    idle_iteration(&journal);
+    idle_iteration(&journal);
+    journal.record(phase(RunLoopPhase::BeforeTimers));
+
+    let events = journal.events(None);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].kind, JournalEventKind::Idle { iterations: 2 });
+    assert!(journal.is_empty());
+}
+
+#[test]
+fn test_capacity_and_since() {
+    let journal = RunLoopJournal::new(3);
+    for i in 0..5 {
+        journal.record(JournalEventKind::Wakeup {
+            reason: format!("task_submitted:{}", i),
+        });
+    }
+    let events = journal.events(None);
+    assert_eq!(events.len(), 3);
+    assert_eq!(events[0].seq, 2);
+
+    let since = events[2].at;
+    assert!(journal
+        .events(Some(since))
+        .iter()
+        .all(|event| event.at >= since));
+    assert!(journal
+        .events(Some(since + chrono::Duration::seconds(1)))
+        .is_empty());
+}
+
+#[test]
+fn test_event_serialization() {
+    let journal = RunLoopJournal::new(10);
+    journal.record(JournalEventKind::Dispatched {
+        task: task(),
+        decision: DispatchDecision::Rescheduled,
+    });
+    let json = serde_json::to_value(&journal.events(None)[0]).unwrap();
+    assert_eq!(json["kind"], "dispatched");
+    assert_eq!(json["decision"], "rescheduled");
+    assert_eq!(json["task"]["task_type"], "cron:nightly");
+
+    let event: JournalEvent = serde_json::from_value(json).unwrap();
+    assert_eq!(event.kind.name(), "dispatched");
+}
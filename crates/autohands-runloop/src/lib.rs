@@ -45,6 +45,7 @@
 //! - [`Task`]: Tasks flowing through the loop
 //! - [`Timer`]: High-level timer abstraction
 //! - [`AgentEventHandler`]: Agent task handler trait
+//! - [`RunLoopJournal`]: Optional journal of the loop's events
 //!
 //! ## Example
 //!
@@ -67,21 +68,31 @@
 
 pub mod agent_driver;
 pub mod agent_source;
+pub mod callback;
 pub mod config;
 pub mod correlation;
 pub mod cron_timer;
+pub mod delivery;
 pub mod error;
+mod fair_queue;
 pub mod task;
 pub mod task_chain;
+pub mod task_graph;
 pub mod task_queue;
+pub mod task_spill;
 pub mod integration;
+pub mod journal;
 pub mod metrics;
 pub mod mode;
+pub mod mode_policy;
 pub mod observer;
+pub mod outcome;
 pub mod run_loop;
 mod run_loop_accessors;
+mod run_loop_control;
 mod run_loop_execution;
 mod run_loop_handlers;
+mod run_loop_modes;
 mod run_loop_processing;
 mod run_loop_task_dispatch;
 mod run_loop_traits;
@@ -94,24 +105,35 @@ pub mod timer;
 // Re-exports
 pub use agent_driver::{AgentEventHandler, AgentExecutionContext, AgentResult, ExecutionStatus};
 pub use agent_source::{AgentTaskInjector, AgentSource0};
-pub use config::{TaskChainConfig, TaskQueueConfig, RetryConfig, RunLoopConfig, WorkerPoolConfig};
+pub use callback::{CallbackDispatcher, CallbackEvent, CallbackEventKind, TaskCallback};
+pub use delivery::{DeliveryFormat, DeliveryLog, DeliveryStatus, DeliveryTarget};
+pub use outcome::{TaskOutcome, TaskOutcomeLog};
+pub use journal::{DispatchDecision, JournalEvent, JournalEventKind, JournalTask, RunLoopJournal};
+pub use config::{
+    CallbackConfig, FairnessConfig, JournalConfig, ModePolicyConfig, OverflowPolicy, RetryConfig, RunLoopConfig, SourceQueueConfig,
+    TaskChainConfig, TaskQueueConfig, WorkerPoolConfig,
+};
 pub use error::{TaskChainError, RunLoopError, RunLoopResult};
 pub use task::{Task, TaskPriority, TaskSource};
 pub use task_chain::TaskChainTracker;
+pub use task_graph::{TaskGraph, TaskNode, TaskNodeStatus};
 pub use task_queue::TaskQueue;
-pub use metrics::{MetricsSnapshot, RunLoopMetrics};
-pub use mode::{RunLoopMode, RunLoopPhase, RunLoopRunResult, RunLoopState};
+pub use task_spill::TaskSpill;
+pub use metrics::{MetricsSnapshot, QueueDepth, RunLoopMetrics, SourceStats};
+pub use mode::{DispatchState, RunLoopMode, RunLoopPhase, RunLoopRunResult, RunLoopState};
+pub use mode_policy::{ModePolicy, ModeSwitchReason, ModeTransition};
 pub use observer::{
     EventBatchCommitObserver, LoggingObserver, MetricsObserver, ObserverHandle,
     ResourceCleanupObserver, RunLoopObserver, SpawnerObserver,
 };
 pub use run_loop::{RunLoop, WakeupSignal};
+pub use run_loop_control::ControlStatus;
 pub use source::{PortMessage, Source0, Source0Base, Source1, Source1Receiver};
 pub use timer::{Timer, TimerBuilder};
 pub use cron_timer::{CronTimer, CronTimerBuilder, schedules as cron_schedules};
 pub use spawner::{
     CorrelationGuard, RunLoopSpawner, SpawnedTaskHandle, SpawnerInner, SpawnerMetrics,
-    SpawnerStateProvider, TaskInfo, TaskState,
+    SpawnerShutdownReport, SpawnerStateProvider, TaskInfo, TaskState,
 };
 // Re-export CancellationToken for convenience
 pub use tokio_util::sync::CancellationToken;
@@ -122,6 +144,7 @@ pub use integration::checkpoint::{
     RunLoopCheckpoint,
 };
 // Note: TaskSubmitter is implemented directly on RunLoop
+pub use integration::github::{CheckEvent, GitHubEvent, IssueEvent, PullRequestEvent};
 pub use integration::health::{
     HealthCheckError, HealthCheckObserver, HealthCheckable, HealthStatus,
     LivenessCheck, MemoryCheck, TaskQueueCheck,
@@ -133,17 +156,28 @@ pub use integration::websocket::{HttpTaskInjector, WsMessageType};
 
 // Trigger types (shared by file_watcher and webhook)
 pub use integration::trigger_types::{
-    FileWatcherConfig, Trigger, TriggerError, TriggerEvent, TriggersConfig, WebhookConfig,
+    EmailConfig, FileWatcherConfig, MqttConfig, MqttSubscription, Trigger, TriggerError,
+    TriggerEvent, TriggersConfig, WebhookConfig,
 };
 // File watcher exports
 pub use integration::file_watcher::FileWatcherTrigger;
 pub use integration::file_watcher_manager::FileWatcherManager;
+pub use integration::file_pipeline::{render_file_prompt, FileChange, FilePipeline, WorkflowLauncher};
 pub use integration::file_watcher_source::{FileChangeEvent, FileChangeType, FileWatcherInjector};
+// Email trigger exports
+pub use integration::email::{
+    email_inputs, ArchiveRules, EmailAttachment, EmailFilter, EmailInjector, EmailMessage, MailFetcher, RawEmail,
+};
+pub use integration::email_imap::ImapFetcher;
+// MQTT exports
+pub use integration::mqtt::{topic_matches, MqttChannel, MqttSource1};
 // Webhook exports
 pub use integration::webhook::{WebhookEvent, WebhookInjector, WebhookTrigger};
 
 // Channel bridge exports
-pub use integration::channel_bridge::{ChannelBridge, ChannelBridgeConfig};
+pub use integration::channel_bridge::{fan_out, ChannelBridge, ChannelBridgeConfig};
+pub use integration::channel_filter::{CommandRoute, InboundAction, InboundFilter};
+pub use integration::channel_rate_limit::RateLimit;
 
 #[cfg(test)]
 #[path = "lib_tests.rs"]
@@ -1,9 +1,43 @@
 //! RunLoop metrics collection.
 
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Instant;
 
 use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Pending tasks of one source.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct QueueDepth {
+    /// Tasks in the in-memory queue.
+    pub pending: u64,
+    /// Tasks spilled to disk, waiting for room.
+    pub spilled: u64,
+}
+
+/// Dispatch throughput and queue wait of one source.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct SourceStats {
+    /// Tasks dispatched.
+    pub dispatched: u64,
+    /// Total time dispatched tasks waited in the queue, in milliseconds.
+    pub total_wait_ms: u64,
+    /// Longest time a task waited in the queue, in milliseconds.
+    pub max_wait_ms: u64,
+    /// Time the last dispatched task waited in the queue, in milliseconds.
+    pub last_wait_ms: u64,
+}
+
+impl SourceStats {
+    /// Average time dispatched tasks waited in the queue, in milliseconds.
+    pub fn avg_wait_ms(&self) -> f64 {
+        if self.dispatched == 0 {
+            return 0.0;
+        }
+        self.total_wait_ms as f64 / self.dispatched as f64
+    }
+}
 
 /// RunLoop metrics.
 #[derive(Debug, Default)]
@@ -41,6 +75,21 @@ pub struct RunLoopMetrics {
     /// Current active spawned tasks count.
     pub active_tasks: AtomicU64,
 
+    /// Tasks dropped to make room in a full queue.
+    pub tasks_dropped: AtomicU64,
+
+    /// Tasks rejected by a full queue.
+    pub tasks_rejected: AtomicU64,
+
+    /// Tasks spilled to disk by a full queue.
+    pub tasks_spilled: AtomicU64,
+
+    /// Current queue depth per task source.
+    queue_depths: parking_lot::RwLock<HashMap<String, QueueDepth>>,
+
+    /// Dispatch throughput and queue wait per task source.
+    source_stats: parking_lot::RwLock<HashMap<String, SourceStats>>,
+
     /// Start time.
     start_time: parking_lot::RwLock<Option<Instant>>,
 }
@@ -119,6 +168,58 @@ impl RunLoopMetrics {
         self.active_tasks.store(count, Ordering::Relaxed);
     }
 
+    /// Record a task dropped by a full queue.
+    pub fn record_task_dropped(&self) {
+        self.tasks_dropped.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a task rejected by a full queue.
+    pub fn record_task_rejected(&self) {
+        self.tasks_rejected.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a task spilled to disk.
+    pub fn record_task_spilled(&self) {
+        self.tasks_spilled.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Set a source's queue depth.
+    pub fn set_queue_depth(&self, source: &str, depth: QueueDepth) {
+        let mut depths = self.queue_depths.write();
+        if depth == QueueDepth::default() {
+            depths.remove(source);
+        } else {
+            depths.insert(source.to_string(), depth);
+        }
+    }
+
+    /// Clear every source's queue depth.
+    pub fn clear_queue_depths(&self) {
+        self.queue_depths.write().clear();
+    }
+
+    /// Current queue depth per task source; sources with no pending tasks
+    /// are left out.
+    pub fn queue_depths(&self) -> HashMap<String, QueueDepth> {
+        self.queue_depths.read().clone()
+    }
+
+    /// Record a task of a source dispatched after waiting `wait_ms` in the
+    /// queue.
+    pub fn record_dispatch(&self, source: &str, wait_ms: u64) {
+        let mut stats = self.source_stats.write();
+        let stats = stats.entry(source.to_string()).or_default();
+        stats.dispatched += 1;
+        stats.total_wait_ms = stats.total_wait_ms.saturating_add(wait_ms);
+        stats.max_wait_ms = stats.max_wait_ms.max(wait_ms);
+        stats.last_wait_ms = wait_ms;
+    }
+
+    /// Dispatch throughput and queue wait per task source.
+    pub fn source_stats(&self) -> HashMap<String, SourceStats> {
+        self.source_stats.read().clone()
+    }
+
     /// Get a snapshot of the metrics.
     pub fn snapshot(&self) -> MetricsSnapshot {
         MetricsSnapshot {
@@ -135,6 +236,11 @@ impl RunLoopMetrics {
             wakeups: self.wakeups.load(Ordering::Relaxed),
             pending_events: self.pending_events.load(Ordering::Relaxed),
             active_tasks: self.active_tasks.load(Ordering::Relaxed),
+            tasks_dropped: self.tasks_dropped.load(Ordering::Relaxed),
+            tasks_rejected: self.tasks_rejected.load(Ordering::Relaxed),
+            tasks_spilled: self.tasks_spilled.load(Ordering::Relaxed),
+            queue_depths: self.queue_depths(),
+            source_stats: self.source_stats(),
         }
     }
 }
@@ -155,6 +261,11 @@ pub struct MetricsSnapshot {
     pub wakeups: u64,
     pub pending_events: u64,
     pub active_tasks: u64,
+    pub tasks_dropped: u64,
+    pub tasks_rejected: u64,
+    pub tasks_spilled: u64,
+    pub queue_depths: HashMap<String, QueueDepth>,
+    pub source_stats: HashMap<String, SourceStats>,
 }
 
 impl MetricsSnapshot {
@@ -174,6 +285,21 @@ impl MetricsSnapshot {
         (self.wait_time_us as f64 / self.wakeups as f64) / 1000.0
     }
 
+    /// Tasks dispatched per second for each source.
+    pub fn source_throughput(&self) -> HashMap<String, f64> {
+        self.source_stats
+            .iter()
+            .map(|(source, stats)| {
+                let rate = if self.uptime_secs == 0 {
+                    0.0
+                } else {
+                    stats.dispatched as f64 / self.uptime_secs as f64
+                };
+                (source.clone(), rate)
+            })
+            .collect()
+    }
+
     /// Calculate average process time in milliseconds.
     pub fn avg_process_time_ms(&self) -> f64 {
         if self.iterations == 0 {
@@ -25,6 +25,39 @@
         assert_eq!(snapshot.events_processed, 5);
     }
 
+    #[test]
+    fn test_queue_depths() {
+        let metrics = RunLoopMetrics::new();
+        let depth = QueueDepth {
+            pending: 3,
+            spilled: 1,
+        };
+        metrics.set_queue_depth("webhook", depth);
+        metrics.record_task_rejected();
+        assert_eq!(metrics.snapshot().queue_depths["webhook"], depth);
+        assert_eq!(metrics.snapshot().tasks_rejected, 1);
+
+        metrics.set_queue_depth("webhook", QueueDepth::default());
+        assert!(metrics.queue_depths().is_empty());
+    }
+
+    #[test]
+    fn test_source_stats() {
+        let metrics = RunLoopMetrics::new();
+        metrics.record_dispatch("webhook", 10);
+        metrics.record_dispatch("webhook", 30);
+        metrics.record_dispatch("user", 0);
+
+        let snapshot = metrics.snapshot();
+        let webhook = snapshot.source_stats["webhook"];
+        assert_eq!(webhook.dispatched, 2);
+        assert_eq!(webhook.max_wait_ms, 30);
+        assert_eq!(webhook.last_wait_ms, 30);
+        assert_eq!(webhook.avg_wait_ms(), 20.0);
+        assert_eq!(snapshot.source_stats["user"].dispatched, 1);
+        assert_eq!(SourceStats::default().avg_wait_ms(), 0.0);
+    }
+
     #[test]
     fn test_events_per_second() {
         let snapshot = MetricsSnapshot {
@@ -41,6 +74,11 @@
             wakeups: 100,
             pending_events: 10,
             active_tasks: 5,
+            tasks_dropped: 0,
+            tasks_rejected: 0,
+            tasks_spilled: 0,
+            queue_depths: Default::default(),
+            source_stats: Default::default(),
         };
 
         assert_eq!(snapshot.events_per_second(), 50.0);
@@ -64,6 +102,11 @@
             wakeups: 0,
             pending_events: 0,
             active_tasks: 0,
+            tasks_dropped: 0,
+            tasks_rejected: 0,
+            tasks_spilled: 0,
+            queue_depths: Default::default(),
+            source_stats: Default::default(),
         };
 
         assert_eq!(snapshot.events_per_second(), 0.0);
@@ -30,7 +30,8 @@ pub enum RunLoopMode {
     /// Similar to kCFRunLoopCommonModes.
     Common,
 
-    /// Custom mode for specialized use cases.
+    /// Embedder-defined mode (e.g. maintenance, incident) with its own
+    /// sources and observers; see [`RunLoop::add_mode`](crate::RunLoop::add_mode).
     Custom(String),
 }
 
@@ -53,6 +54,11 @@ impl std::fmt::Display for RunLoopMode {
 }
 
 impl RunLoopMode {
+    /// Create a custom mode.
+    pub fn custom(name: impl Into<String>) -> Self {
+        RunLoopMode::Custom(name.into())
+    }
+
     /// Check if this mode is included in the Common modes set.
     pub fn is_common_mode(&self) -> bool {
         matches!(self, RunLoopMode::Default | RunLoopMode::AgentProcessing)
@@ -138,6 +144,32 @@ impl From<u8> for RunLoopState {
     }
 }
 
+/// Whether the RunLoop dispatches and accepts tasks.
+///
+/// Orthogonal to [`RunLoopState`]: a paused or draining RunLoop keeps running
+/// its sources, observers and in-flight agent tasks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[repr(u8)]
+pub enum DispatchState {
+    /// Accepting and dispatching tasks.
+    Active = 0,
+    /// Accepting tasks but leaving them queued.
+    Paused = 1,
+    /// Refusing new tasks while queued and in-flight ones finish.
+    Draining = 2,
+}
+
+impl From<u8> for DispatchState {
+    fn from(v: u8) -> Self {
+        match v {
+            1 => DispatchState::Paused,
+            2 => DispatchState::Draining,
+            _ => DispatchState::Active,
+        }
+    }
+}
+
 impl std::fmt::Display for RunLoopState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -0,0 +1,112 @@
+//! Automatic mode switching.
+//!
+//! With [`ModePolicyConfig::enabled`], the RunLoop picks its mode on its own:
+//! - [`RunLoopMode::AgentProcessing`] while an agent task is running, so
+//!   observers and sources registered only for Default or Background mode
+//!   are deferred until the agent finishes
+//! - [`RunLoopMode::Background`] after no activity for
+//!   `background_after_idle_secs`
+//! - [`RunLoopMode::Default`] otherwise
+//!
+//! Observers learn about transitions through
+//! [`RunLoopObserver::on_mode_change`](crate::observer::RunLoopObserver::on_mode_change).
+
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::config::ModePolicyConfig;
+use crate::mode::RunLoopMode;
+
+/// Why the RunLoop switched modes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModeSwitchReason {
+    /// An agent task started.
+    AgentActive,
+    /// The last running agent task finished.
+    AgentsFinished,
+    /// Nothing happened for the idle timeout.
+    Idle,
+    /// New work arrived while in Background mode.
+    Activity,
+}
+
+/// A mode switch made by the policy.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModeTransition {
+    /// Mode left.
+    pub from: RunLoopMode,
+    /// Mode entered.
+    pub to: RunLoopMode,
+    /// Why the switch happened.
+    pub reason: ModeSwitchReason,
+    /// When the switch happened.
+    pub at: DateTime<Utc>,
+}
+
+/// Decides the RunLoop's mode from agent activity and idle time.
+#[derive(Debug)]
+pub struct ModePolicy {
+    idle_timeout: Option<Duration>,
+    last_activity: Instant,
+}
+
+impl ModePolicy {
+    /// Create a policy, counting the RunLoop as active now.
+    pub fn new(config: &ModePolicyConfig) -> Self {
+        Self {
+            idle_timeout: (config.background_after_idle_secs > 0)
+                .then(|| Duration::from_secs(config.background_after_idle_secs)),
+            last_activity: Instant::now(),
+        }
+    }
+
+    /// Whether the policy manages a mode; loops run in other modes are left
+    /// alone.
+    pub fn manages(mode: &RunLoopMode) -> bool {
+        matches!(
+            mode,
+            RunLoopMode::Default | RunLoopMode::AgentProcessing | RunLoopMode::Background
+        )
+    }
+
+    /// Note that work happened (task processed, source fired, wakeup).
+    pub fn record_activity(&mut self, now: Instant) {
+        self.last_activity = now;
+    }
+
+    /// Mode to switch to, if any.
+    pub fn next_mode(
+        &mut self,
+        current: &RunLoopMode,
+        active_agents: usize,
+        now: Instant,
+    ) -> Option<ModeTransition> {
+        let (to, reason) = if active_agents > 0 {
+            self.record_activity(now);
+            (RunLoopMode::AgentProcessing, ModeSwitchReason::AgentActive)
+        } else if self
+            .idle_timeout
+            .is_some_and(|timeout| now.saturating_duration_since(self.last_activity) >= timeout)
+        {
+            (RunLoopMode::Background, ModeSwitchReason::Idle)
+        } else if *current == RunLoopMode::AgentProcessing {
+            (RunLoopMode::Default, ModeSwitchReason::AgentsFinished)
+        } else {
+            (RunLoopMode::Default, ModeSwitchReason::Activity)
+        };
+
+        (*current != to).then(|| ModeTransition {
+            from: current.clone(),
+            to,
+            reason,
+            at: Utc::now(),
+        })
+    }
+}
+
+#[cfg(test)]
+#[path = "mode_policy_tests.rs"]
+mod tests;
@@ -0,0 +1,75 @@
+use super::*;
+
+fn policy(idle_secs: u64) -> ModePolicy {
+    ModePolicy::new(&ModePolicyConfig {
+        enabled: true,
+        background_after_idle_secs: idle_secs,
+    })
+}
+
+#[test]
+fn test_agent_activity_enters_agent_processing() {
+    let mut policy = policy(60);
+    let now = Instant::now();
+
+    let transition = policy.next_mode(&RunLoopMode::Default, 1, now).unwrap();
+    assert_eq!(transition.to, RunLoopMode::AgentProcessing);
+    assert_eq!(transition.reason, ModeSwitchReason::AgentActive);
+    assert!(policy.next_mode(&RunLoopMode::AgentProcessing, 2, now).is_none());
+
+    let transition = policy
+        .next_mode(&RunLoopMode::AgentProcessing, 0, now)
+        .unwrap();
+    assert_eq!(transition.from, RunLoopMode::AgentProcessing);
+    assert_eq!(transition.to, RunLoopMode::Default);
+    assert_eq!(transition.reason, ModeSwitchReason::AgentsFinished);
+}
+
+#[test]
+fn test_idle_drops_to_background() {
+    let mut policy = policy(60);
+    let start = Instant::now();
+
+    assert!(policy
+        .next_mode(&RunLoopMode::Default, 0, start + Duration::from_secs(59))
+        .is_none());
+    let later = start + Duration::from_secs(61);
+    let transition = policy.next_mode(&RunLoopMode::Default, 0, later).unwrap();
+    assert_eq!(transition.to, RunLoopMode::Background);
+    assert_eq!(transition.reason, ModeSwitchReason::Idle);
+
+    // New work brings the loop back to Default mode
+    policy.record_activity(later);
+    let transition = policy
+        .next_mode(&RunLoopMode::Background, 0, later)
+        .unwrap();
+    assert_eq!(transition.to, RunLoopMode::Default);
+    assert_eq!(transition.reason, ModeSwitchReason::Activity);
+}
+
+#[test]
+fn test_running_agent_is_not_idle() {
+    let mut policy = policy(60);
+    let start = Instant::now();
+
+    // A long agent run counts as activity, so the idle clock restarts after it
+    policy.next_mode(&RunLoopMode::Default, 1, start + Duration::from_secs(120));
+    let transition = policy
+        .next_mode(&RunLoopMode::AgentProcessing, 0, start + Duration::from_secs(150))
+        .unwrap();
+    assert_eq!(transition.to, RunLoopMode::Default);
+}
+
+#[test]
+fn test_zero_idle_never_backgrounds() {
+    let mut policy = policy(0);
+    let later = Instant::now() + Duration::from_secs(86400);
+    assert!(policy.next_mode(&RunLoopMode::Default, 0, later).is_none());
+}
+
+#[test]
+fn test_manages() {
+    assert!(ModePolicy::manages(&RunLoopMode::Default));
+    assert!(ModePolicy::manages(&RunLoopMode::Background));
+    assert!(!ModePolicy::manages(&RunLoopMode::Custom("batch".to_string())));
+}
@@ -9,6 +9,7 @@ use std::sync::Arc;
 use async_trait::async_trait;
 
 use crate::mode::RunLoopPhase;
+use crate::mode_policy::ModeTransition;
 use crate::RunLoop;
 
 #[path = "observer_builtin.rs"]
@@ -45,6 +46,12 @@ pub trait RunLoopObserver: Send + Sync {
 
     /// Called when the observed phase is triggered.
     async fn on_phase(&self, phase: RunLoopPhase, run_loop: &RunLoop);
+
+    /// Called when the mode policy switches modes.
+    ///
+    /// Global observers and the observers of both the old and the new mode
+    /// are told, whatever their activity mask.
+    async fn on_mode_change(&self, _transition: &ModeTransition, _run_loop: &RunLoop) {}
 }
 
 /// Observer registration handle.
@@ -1,6 +1,7 @@
 //! Built-in observer implementations for RunLoop.
 
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use tracing::debug;
@@ -66,8 +67,13 @@ impl RunLoopObserver for MetricsObserver {
 ///
 /// Cleans up resources at BeforeWaiting and Exit phases.
 /// Similar to AutoreleasePool behavior in iOS.
+///
+/// Expensive passes (e.g. garbage collection on disk) can be throttled with
+/// [`with_interval`](Self::with_interval).
 pub struct ResourceCleanupObserver {
     cleanup_fn: Arc<dyn Fn() + Send + Sync>,
+    interval: Option<Duration>,
+    last_run: parking_lot::Mutex<Option<Instant>>,
 }
 
 impl ResourceCleanupObserver {
@@ -78,8 +84,29 @@ impl ResourceCleanupObserver {
     {
         Self {
             cleanup_fn: Arc::new(cleanup_fn),
+            interval: None,
+            last_run: parking_lot::Mutex::new(None),
         }
     }
+
+    /// Run the cleanup at most once per `interval`.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// Whether the cleanup is due, marking it run if so.
+    fn take_due(&self) -> bool {
+        let mut last_run = self.last_run.lock();
+        let due = match (self.interval, *last_run) {
+            (Some(interval), Some(last)) => last.elapsed() >= interval,
+            _ => true,
+        };
+        if due {
+            *last_run = Some(Instant::now());
+        }
+        due
+    }
 }
 
 #[async_trait]
@@ -93,6 +120,9 @@ impl RunLoopObserver for ResourceCleanupObserver {
     }
 
     async fn on_phase(&self, phase: RunLoopPhase, _run_loop: &RunLoop) {
+        if !self.take_due() {
+            return;
+        }
         debug!("Resource cleanup at phase: {:?}", phase);
         (self.cleanup_fn)();
     }
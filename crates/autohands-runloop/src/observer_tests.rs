@@ -72,6 +72,21 @@ fn test_resource_cleanup_observer() {
     assert!(RunLoopPhase::Exit.matches(observer.activities()));
 }
 
+#[tokio::test]
+async fn test_resource_cleanup_observer_interval() {
+    let counter = Arc::new(AtomicU32::new(0));
+    let counter_clone = counter.clone();
+    let observer = ResourceCleanupObserver::new(move || {
+        counter_clone.fetch_add(1, Ordering::SeqCst);
+    })
+    .with_interval(Duration::from_secs(3600));
+    let run_loop = RunLoop::new(crate::RunLoopConfig::default());
+
+    observer.on_phase(RunLoopPhase::BeforeWaiting, &run_loop).await;
+    observer.on_phase(RunLoopPhase::BeforeWaiting, &run_loop).await;
+    assert_eq!(counter.load(Ordering::SeqCst), 1);
+}
+
 #[test]
 fn test_logging_observer() {
     let observer = LoggingObserver::new("test");
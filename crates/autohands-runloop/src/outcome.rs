@@ -0,0 +1,119 @@
+//! Task outcomes.
+//!
+//! When an agent-class task finishes, the RunLoop records whether it
+//! succeeded and the agent's final response in the [`TaskOutcomeLog`], so
+//! submitters can poll or wait for the result of a task they injected.
+
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+use uuid::Uuid;
+
+/// Number of tasks whose outcomes are kept.
+pub const OUTCOME_LOG_CAPACITY: usize = 1000;
+
+/// How a task ended.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskOutcome {
+    /// Whether the agent finished without error.
+    pub success: bool,
+    /// Agent's final response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response: Option<String>,
+    /// Error on failure.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// When the task finished.
+    pub finished_at: DateTime<Utc>,
+}
+
+impl TaskOutcome {
+    /// Successful outcome.
+    pub fn succeeded(response: Option<String>) -> Self {
+        Self {
+            success: true,
+            response,
+            error: None,
+            finished_at: Utc::now(),
+        }
+    }
+
+    /// Failed outcome.
+    pub fn failed(error: impl Into<String>) -> Self {
+        Self {
+            success: false,
+            response: None,
+            error: Some(error.into()),
+            finished_at: Utc::now(),
+        }
+    }
+}
+
+/// Recent task outcomes, keyed by task.
+///
+/// Only the last [`OUTCOME_LOG_CAPACITY`] tasks are kept.
+#[derive(Default)]
+pub struct TaskOutcomeLog {
+    entries: Mutex<VecDeque<(Uuid, TaskOutcome)>>,
+    recorded: Notify,
+}
+
+impl TaskOutcomeLog {
+    /// Create an empty log.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the outcome of a task, replacing an earlier one.
+    pub fn record(&self, task_id: Uuid, outcome: TaskOutcome) {
+        {
+            let mut entries = self.entries.lock();
+            entries.retain(|(id, _)| *id != task_id);
+            if entries.len() >= OUTCOME_LOG_CAPACITY {
+                entries.pop_front();
+            }
+            entries.push_back((task_id, outcome));
+        }
+        self.recorded.notify_waiters();
+    }
+
+    /// Outcome recorded for a task.
+    pub fn get(&self, task_id: Uuid) -> Option<TaskOutcome> {
+        self.entries
+            .lock()
+            .iter()
+            .find(|(id, _)| *id == task_id)
+            .map(|(_, outcome)| outcome.clone())
+    }
+
+    /// Wait until a task's outcome is recorded.
+    pub async fn wait(&self, task_id: Uuid) -> TaskOutcome {
+        loop {
+            let recorded = self.recorded.notified();
+            tokio::pin!(recorded);
+            // Register before checking so a record in between is not missed
+            recorded.as_mut().enable();
+            if let Some(outcome) = self.get(task_id) {
+                return outcome;
+            }
+            recorded.await;
+        }
+    }
+
+    /// Number of tasks in the log.
+    pub fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    /// Whether the log is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.lock().is_empty()
+    }
+}
+
+#[cfg(test)]
+#[path = "outcome_tests.rs"]
+mod tests;
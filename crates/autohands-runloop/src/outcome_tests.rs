@@ -0,0 +1,53 @@
+use super::*;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[test]
+fn test_record_and_get() {
+    let log = TaskOutcomeLog::new();
+    let id = Uuid::new_v4();
+    assert!(log.get(id).is_none());
+
+    log.record(id, TaskOutcome::failed("boom"));
+    log.record(id, TaskOutcome::succeeded(Some("done".to_string())));
+
+    let outcome = log.get(id).unwrap();
+    assert!(outcome.success);
+    assert_eq!(outcome.response.as_deref(), Some("done"));
+    assert_eq!(log.len(), 1);
+}
+
+#[test]
+fn test_capacity() {
+    let log = TaskOutcomeLog::new();
+    let first = Uuid::new_v4();
+    log.record(first, TaskOutcome::succeeded(None));
+    for _ in 0..OUTCOME_LOG_CAPACITY {
+        log.record(Uuid::new_v4(), TaskOutcome::succeeded(None));
+    }
+    assert_eq!(log.len(), OUTCOME_LOG_CAPACITY);
+    assert!(log.get(first).is_none());
+}
+
+#[tokio::test]
+async fn test_wait_for_outcome() {
+    let log = Arc::new(TaskOutcomeLog::new());
+    let id = Uuid::new_v4();
+
+    let waiter = tokio::spawn({
+        let log = log.clone();
+        async move { log.wait(id).await }
+    });
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    log.record(Uuid::new_v4(), TaskOutcome::succeeded(None));
+    log.record(id, TaskOutcome::failed("boom"));
+
+    let outcome = tokio::time::timeout(Duration::from_secs(1), waiter)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(outcome.error.as_deref(), Some("boom"));
+
+    // Already recorded outcomes return immediately
+    assert!(!log.wait(id).await.success);
+}
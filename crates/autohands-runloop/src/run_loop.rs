@@ -4,18 +4,23 @@
 //! inspired by iOS CFRunLoop design.
 
 use std::collections::HashSet;
-use std::sync::atomic::AtomicU8;
+use std::sync::atomic::{AtomicU8, AtomicUsize};
 use std::sync::Arc;
 
 use dashmap::DashMap;
 use tokio::sync::{mpsc, RwLock};
 
 use autohands_core::registry::ChannelRegistry;
+use autohands_runtime::TraceLog;
 
 use crate::agent_driver::AgentEventHandler;
+use crate::callback::CallbackDispatcher;
 use crate::config::RunLoopConfig;
+use crate::delivery::DeliveryLog;
+use crate::journal::{JournalEventKind, RunLoopJournal};
+use crate::outcome::TaskOutcomeLog;
 use crate::metrics::RunLoopMetrics;
-use crate::mode::{RunLoopMode, RunLoopState};
+use crate::mode::{DispatchState, RunLoopMode, RunLoopState};
 use crate::observer::ObserverHandle;
 use crate::source::{PortMessage, Source0, Source1Receiver};
 use crate::spawner::SpawnerInner;
@@ -26,6 +31,7 @@ use crate::task_queue::TaskQueue;
 // - run_loop_execution: run() & run_in_mode() event loop
 // - run_loop_processing: task processing & source management
 // - run_loop_handlers: observer/wakeup handling
+// - run_loop_modes: mode registration (custom and common modes)
 // - run_loop_traits: Default & TaskSubmitter impls
 
 /// Wakeup signal for the RunLoop.
@@ -75,6 +81,8 @@ pub struct RunLoop {
     pub(crate) modes: DashMap<RunLoopMode, ModeData>,
     /// Common modes set.
     pub(crate) common_modes: RwLock<HashSet<RunLoopMode>>,
+    /// Source0s added for `Common`, synced into modes added to the set later.
+    pub(crate) common_sources0: RwLock<Vec<Arc<dyn Source0>>>,
     /// Current state.
     pub(crate) state: AtomicU8,
     /// Wakeup channel sender.
@@ -88,7 +96,7 @@ pub struct RunLoop {
     /// Task queue.
     pub(crate) task_queue: Arc<TaskQueue>,
     /// Configuration.
-    pub(crate) _config: RunLoopConfig,
+    pub(crate) config: RunLoopConfig,
     /// Metrics.
     pub(crate) metrics: Arc<RunLoopMetrics>,
     /// Spawner inner state for task tracking.
@@ -100,6 +108,20 @@ pub struct RunLoop {
     /// Wrapped in Arc so it can be cheaply cloned into `tokio::spawn` closures
     /// for reliable `.read().await` instead of fallible `try_read()`.
     pub(crate) channel_registry: Arc<RwLock<Option<Arc<ChannelRegistry>>>>,
+    /// Per-target delivery statuses of recent task results.
+    pub(crate) delivery_log: Arc<DeliveryLog>,
+    /// Outcomes of recently finished agent tasks.
+    pub(crate) outcome_log: Arc<TaskOutcomeLog>,
+    /// Number of agent tasks currently running.
+    pub(crate) active_agents: Arc<AtomicUsize>,
+    /// Posts lifecycle events of tasks with a callback.
+    pub(crate) callbacks: Arc<CallbackDispatcher>,
+    /// Timelines of traced tasks, keyed by correlation ID.
+    pub(crate) trace_log: Arc<TraceLog>,
+    /// Whether tasks are dispatched and accepted ([`DispatchState`]).
+    pub(crate) dispatch: AtomicU8,
+    /// Journal of the loop's events, when enabled.
+    pub(crate) journal: Option<Arc<RunLoopJournal>>,
 }
 
 impl RunLoop {
@@ -107,26 +129,46 @@ impl RunLoop {
     pub fn new(config: RunLoopConfig) -> Self {
         let (wakeup_tx, wakeup_rx) = mpsc::channel(1024);
 
-        let task_queue = Arc::new(TaskQueue::new(
-            config.queue.clone(),
-            config.chain.max_tasks_per_chain,
-        ));
+        let metrics = Arc::new(RunLoopMetrics::new());
+        let journal = config
+            .journal
+            .enabled
+            .then(|| Arc::new(RunLoopJournal::new(config.journal.capacity)));
+        let outcome_log = Arc::new(TaskOutcomeLog::new());
+        let mut task_queue = TaskQueue::new(config.queue.clone(), config.chain.max_tasks_per_chain)
+            .with_metrics(metrics.clone())
+            .with_outcome_log(outcome_log.clone());
+        if let Some(journal) = &journal {
+            task_queue = task_queue.with_journal(journal.clone());
+        }
+        let task_queue = Arc::new(task_queue);
+
+        let callbacks = Arc::new(CallbackDispatcher::new(config.callbacks.clone()));
+        task_queue.set_callbacks(callbacks.clone());
 
         let run_loop = Self {
             current_mode: RwLock::new(RunLoopMode::Default),
             modes: DashMap::new(),
             common_modes: RwLock::new(RunLoopMode::default_common_modes()),
+            common_sources0: RwLock::new(Vec::new()),
             state: AtomicU8::new(RunLoopState::Created as u8),
             wakeup_tx,
             wakeup_rx: RwLock::new(wakeup_rx),
             source1_receivers: RwLock::new(Vec::new()),
             global_observers: RwLock::new(Vec::new()),
             task_queue,
-            _config: config,
-            metrics: Arc::new(RunLoopMetrics::new()),
+            config,
+            metrics,
             spawner_inner: Arc::new(SpawnerInner::new()),
             handler: RwLock::new(None),
             channel_registry: Arc::new(RwLock::new(None)),
+            delivery_log: Arc::new(DeliveryLog::new()),
+            outcome_log,
+            active_agents: Arc::new(AtomicUsize::new(0)),
+            callbacks,
+            trace_log: Arc::new(TraceLog::new()),
+            dispatch: AtomicU8::new(DispatchState::Active as u8),
+            journal,
         };
 
         // Initialize default modes
@@ -140,6 +182,28 @@ impl RunLoop {
 
         run_loop
     }
+
+    /// Use a shared callback dispatcher, so agent-side hooks created before
+    /// the RunLoop (turn observers, approval notifiers) reach the same tasks.
+    pub fn with_callback_dispatcher(mut self, callbacks: Arc<CallbackDispatcher>) -> Self {
+        self.task_queue.set_callbacks(callbacks.clone());
+        self.callbacks = callbacks;
+        self
+    }
+
+    /// Use a shared trace log, so agent steps recorded by the runtime land
+    /// in the same timelines as the RunLoop's task steps.
+    pub fn with_trace_log(mut self, trace_log: Arc<TraceLog>) -> Self {
+        self.trace_log = trace_log;
+        self
+    }
+
+    /// Record an event in the journal, if enabled.
+    pub(crate) fn record_event(&self, kind: impl FnOnce() -> JournalEventKind) {
+        if let Some(journal) = &self.journal {
+            journal.record(kind());
+        }
+    }
 }
 
 #[cfg(test)]
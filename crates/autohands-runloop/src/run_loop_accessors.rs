@@ -7,14 +7,20 @@ use tokio::sync::mpsc;
 use tracing::{info, warn};
 
 use autohands_core::registry::ChannelRegistry;
+use autohands_runtime::TraceLog;
 
 use crate::agent_driver::AgentEventHandler;
-use crate::error::RunLoopResult;
+use crate::callback::CallbackDispatcher;
+use crate::delivery::DeliveryLog;
+use crate::journal::{JournalEventKind, JournalTask, RunLoopJournal};
+use crate::outcome::TaskOutcomeLog;
+use crate::error::{RunLoopError, RunLoopResult};
 use crate::metrics::RunLoopMetrics;
 use crate::mode::{RunLoopMode, RunLoopState};
 use crate::run_loop::{RunLoop, WakeupSignal};
 use crate::spawner::SpawnerInner;
 use crate::task::Task;
+use crate::task_graph::TaskGraph;
 use crate::task_queue::TaskQueue;
 
 impl RunLoop {
@@ -30,6 +36,37 @@ impl RunLoop {
         info!("RunLoop: Channel registry configured");
     }
 
+    /// Per-target delivery statuses of recent task results.
+    pub fn delivery_log(&self) -> Arc<DeliveryLog> {
+        self.delivery_log.clone()
+    }
+
+    /// Outcomes of recently finished agent tasks.
+    pub fn outcome_log(&self) -> Arc<TaskOutcomeLog> {
+        self.outcome_log.clone()
+    }
+
+    /// Journal of the loop's events; `None` unless enabled in
+    /// [`JournalConfig`](crate::config::JournalConfig).
+    pub fn journal(&self) -> Option<Arc<RunLoopJournal>> {
+        self.journal.clone()
+    }
+
+    /// Timelines of traced tasks.
+    pub fn trace_log(&self) -> Arc<TraceLog> {
+        self.trace_log.clone()
+    }
+
+    /// Parent/child graph of recent agent tasks.
+    pub fn task_graph(&self) -> Arc<TaskGraph> {
+        self.task_queue.task_graph().clone()
+    }
+
+    /// Dispatcher posting task lifecycle events to callbacks.
+    pub fn callback_dispatcher(&self) -> Arc<CallbackDispatcher> {
+        self.callbacks.clone()
+    }
+
     /// Get current state.
     pub fn state(&self) -> RunLoopState {
         RunLoopState::from(self.state.load(Ordering::SeqCst))
@@ -45,6 +82,11 @@ impl RunLoop {
         self.current_mode.read().await.clone()
     }
 
+    /// Number of agent tasks currently running.
+    pub fn active_agent_tasks(&self) -> usize {
+        self.active_agents.load(Ordering::SeqCst)
+    }
+
     /// Get metrics.
     pub fn metrics(&self) -> &Arc<RunLoopMetrics> {
         &self.metrics
@@ -82,7 +124,16 @@ impl RunLoop {
     }
 
     /// Inject a task into the queue.
+    ///
+    /// Refused with [`RunLoopError::Draining`] while the RunLoop drains.
     pub async fn inject_task(&self, task: Task) -> RunLoopResult<()> {
+        if !self.is_accepting() {
+            self.record_event(|| JournalEventKind::Rejected {
+                task: JournalTask::of(&task),
+                reason: RunLoopError::Draining.to_string(),
+            });
+            return Err(RunLoopError::Draining);
+        }
         self.task_queue.enqueue(task).await?;
         self.metrics.record_event_enqueued();
         Ok(())
@@ -0,0 +1,117 @@
+//! RunLoop dispatch control: pause, resume and drain.
+//!
+//! Pausing leaves queued tasks in the queue while sources keep feeding it and
+//! in-flight agent tasks run to completion. Draining refuses new tasks (from
+//! [`RunLoop::inject_task`] and sources) while queued and in-flight ones
+//! finish, so operators can do maintenance without killing agent work.
+//! Follow-ups of in-flight work (subtasks, timer reschedules) are still
+//! accepted. Resuming returns to normal dispatch.
+
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::time::Instant;
+use tracing::info;
+
+use crate::journal::JournalEventKind;
+use crate::mode::DispatchState;
+use crate::run_loop::RunLoop;
+
+/// Interval at which [`RunLoop::wait_drained`] checks for completion.
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Dispatch state with the work still outstanding.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ControlStatus {
+    /// Whether tasks are dispatched and accepted.
+    pub dispatch: DispatchState,
+    /// Tasks waiting to be dispatched.
+    pub queued_tasks: usize,
+    /// Tasks scheduled for later.
+    pub delayed_tasks: usize,
+    /// Agent tasks running.
+    pub active_agent_tasks: usize,
+    /// Whether a drain has finished: no task queued or running.
+    pub drained: bool,
+}
+
+impl RunLoop {
+    /// Whether tasks are dispatched and accepted.
+    pub fn dispatch_state(&self) -> DispatchState {
+        DispatchState::from(self.dispatch.load(Ordering::SeqCst))
+    }
+
+    fn set_dispatch_state(&self, state: DispatchState) {
+        let previous = DispatchState::from(self.dispatch.swap(state as u8, Ordering::SeqCst));
+        if previous != state {
+            info!("RunLoop dispatch: {:?} -> {:?}", previous, state);
+            self.record_event(|| JournalEventKind::DispatchChanged {
+                from: previous,
+                to: state,
+            });
+        }
+    }
+
+    /// Stop dispatching queued tasks; new tasks are still queued.
+    pub fn pause(&self) {
+        self.set_dispatch_state(DispatchState::Paused);
+    }
+
+    /// Accept and dispatch tasks again after a pause or drain.
+    pub fn resume(&self) {
+        self.set_dispatch_state(DispatchState::Active);
+        self.wakeup("resumed");
+    }
+
+    /// Refuse new tasks while queued and in-flight ones finish.
+    ///
+    /// Use [`RunLoop::wait_drained`] to wait for the work to finish.
+    pub fn drain(&self) {
+        self.set_dispatch_state(DispatchState::Draining);
+        self.wakeup("draining");
+    }
+
+    /// Whether new tasks are accepted.
+    pub fn is_accepting(&self) -> bool {
+        self.dispatch_state() != DispatchState::Draining
+    }
+
+    /// Whether queued tasks are dispatched.
+    pub fn is_dispatching(&self) -> bool {
+        self.dispatch_state() != DispatchState::Paused
+    }
+
+    /// Dispatch state with the work still outstanding.
+    pub async fn control_status(&self) -> ControlStatus {
+        let dispatch = self.dispatch_state();
+        let queued_tasks = self.task_queue.immediate_len().await;
+        let active_agent_tasks = self.active_agent_tasks();
+        ControlStatus {
+            dispatch,
+            queued_tasks,
+            delayed_tasks: self.task_queue.delayed_len().await,
+            active_agent_tasks,
+            drained: dispatch == DispatchState::Draining
+                && queued_tasks == 0
+                && active_agent_tasks == 0,
+        }
+    }
+
+    /// Wait until a drain finishes, returning whether it did in time.
+    ///
+    /// Returns false right away when the RunLoop is not draining.
+    pub async fn wait_drained(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let status = self.control_status().await;
+            if status.drained {
+                return true;
+            }
+            if status.dispatch != DispatchState::Draining || Instant::now() >= deadline {
+                return false;
+            }
+            tokio::time::sleep_until((Instant::now() + DRAIN_POLL_INTERVAL).min(deadline)).await;
+        }
+    }
+}
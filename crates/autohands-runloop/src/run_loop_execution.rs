@@ -2,11 +2,14 @@
 
 use std::time::{Duration, Instant};
 
-use tracing::{debug, error, info, warn};
+use tracing::{debug, debug_span, error, info, info_span, warn, Instrument};
 
 use crate::error::{RunLoopError, RunLoopResult};
+use crate::journal::{JournalEventKind, JournalTask, IDLE_WAKEUP};
 use crate::mode::{RunLoopMode, RunLoopPhase, RunLoopRunResult, RunLoopState};
+use crate::mode_policy::ModePolicy;
 use crate::run_loop::{RunLoop, WakeupSignal};
+use crate::task::Task;
 
 /// Maximum number of Source1 messages to process per iteration,
 /// preventing livelock when Source1 produces messages faster than they are consumed.
@@ -29,14 +32,16 @@ impl RunLoop {
     ) -> RunLoopResult<RunLoopRunResult> {
         let deadline = Instant::now() + timeout;
 
+        if !self.modes.contains_key(&mode) {
+            return Err(RunLoopError::ModeNotFound(mode));
+        }
+
         *self.current_mode.write().await = mode.clone();
         self.set_state(RunLoopState::Running);
         self.metrics.mark_start();
-
-        let mode_data = self
-            .modes
-            .get(&mode)
-            .ok_or(RunLoopError::ModeNotFound(mode.clone()))?;
+        let mut mode = mode;
+        let mut policy = (self.config.mode_policy.enabled && ModePolicy::manages(&mode))
+            .then(|| ModePolicy::new(&self.config.mode_policy));
 
         debug!("RunLoop: Entry");
         self.notify_observers(RunLoopPhase::Entry, &mode).await;
@@ -52,43 +57,84 @@ impl RunLoop {
                 return Ok(RunLoopRunResult::TimedOut);
             }
 
+            if let Some(policy) = policy.as_mut() {
+                if let Some(transition) =
+                    policy.next_mode(&mode, self.active_agent_tasks(), Instant::now())
+                {
+                    mode = self.switch_mode(transition).await;
+                }
+            }
+
             let process_start = Instant::now();
 
+            // Phases are debug spans so idle iterations stay out of exported
+            // traces unless asked for
             debug!("RunLoop: BeforeTimers");
-            self.notify_observers(RunLoopPhase::BeforeTimers, &mode).await;
-            self.task_queue.promote_delayed().await;
+            async {
+                self.notify_observers(RunLoopPhase::BeforeTimers, &mode).await;
+                self.task_queue.promote_delayed().await;
+            }
+            .instrument(debug_span!("runloop_timers", mode = %mode))
+            .await;
 
             debug!("RunLoop: BeforeSources");
-            self.notify_observers(RunLoopPhase::BeforeSources, &mode).await;
-            let source0_tasks = self.process_sources0(&mode_data).await?;
-            for task in source0_tasks {
-                self.task_queue.enqueue(task).await?;
-            }
+            async {
+                self.notify_observers(RunLoopPhase::BeforeSources, &mode).await;
+                let source0_tasks = match self.modes.get(&mode) {
+                    Some(mode_data) => self.process_sources0(&mode_data).await?,
+                    None => Vec::new(),
+                };
+                if let (Some(policy), false) = (policy.as_mut(), source0_tasks.is_empty()) {
+                    policy.record_activity(Instant::now());
+                }
+                for task in source0_tasks {
+                    self.enqueue_produced(task).await;
+                }
 
-            // Process Source1 messages with bounded batch size to prevent livelock.
-            // If Source1 keeps producing messages, we still fall through to task dequeue.
-            let mut source1_count = 0;
-            while source1_count < MAX_SOURCE1_BATCH {
-                if let Some(tasks) = self.try_process_source1().await? {
-                    for task in tasks {
-                        self.task_queue.enqueue(task).await?;
+                // Process Source1 messages with bounded batch size to prevent livelock.
+                // If Source1 keeps producing messages, we still fall through to task dequeue.
+                let mut source1_count = 0;
+                while source1_count < MAX_SOURCE1_BATCH {
+                    if let Some(tasks) = self.try_process_source1().await? {
+                        for task in tasks {
+                            self.enqueue_produced(task).await;
+                        }
+                        source1_count += 1;
+                    } else {
+                        break;
                     }
-                    source1_count += 1;
-                } else {
-                    break;
                 }
+                if source1_count >= MAX_SOURCE1_BATCH {
+                    warn!(
+                        "Source1 batch limit reached ({} messages), yielding to task dequeue",
+                        MAX_SOURCE1_BATCH
+                    );
+                }
+                Ok::<_, RunLoopError>(())
             }
-            if source1_count >= MAX_SOURCE1_BATCH {
-                warn!(
-                    "Source1 batch limit reached ({} messages), yielding to task dequeue",
-                    MAX_SOURCE1_BATCH
-                );
-            }
-
-            if let Some(task) = self.task_queue.dequeue().await {
+            .instrument(debug_span!("runloop_sources", mode = %mode))
+            .await?;
+
+            // Paused: queued tasks wait for resume
+            let next = if self.is_dispatching() {
+                self.task_queue.dequeue().await
+            } else {
+                None
+            };
+            if let Some(task) = next {
                 info!("Processing task: {} (type: {})", task.id, task.task_type);
+                if let Some(policy) = policy.as_mut() {
+                    policy.record_activity(Instant::now());
+                }
                 self.metrics.record_events_processed(1);
-                if let Err(e) = self.process_task(task).await {
+                let span = info_span!(
+                    "task_dispatch",
+                    task_id = %task.id,
+                    task_type = %task.task_type,
+                    priority = ?task.priority,
+                    mode = %mode,
+                );
+                if let Err(e) = self.process_task(task).instrument(span).await {
                     error!("Task processing error: {}", e);
                 }
                 continue;
@@ -103,12 +149,31 @@ impl RunLoop {
             self.cleanup_observers(&mode).await;
 
             let wait_start = Instant::now();
-            let wakeup = self.wait_for_wakeup(deadline).await;
+            let wakeup = self
+                .wait_for_wakeup(deadline)
+                .instrument(debug_span!("runloop_wait", mode = %mode))
+                .await;
             self.metrics
                 .record_wait_time(wait_start.elapsed().as_micros() as u64);
 
             self.set_state(RunLoopState::Running);
             debug!("RunLoop: AfterWaiting (wakeup: {:?})", wakeup);
+            self.record_event(|| JournalEventKind::Wakeup {
+                reason: match &wakeup {
+                    WakeupSignal::Stop => "stop".to_string(),
+                    WakeupSignal::SourceReady { source_id, .. } => {
+                        format!("source_ready:{}", source_id)
+                    }
+                    WakeupSignal::Explicit { reason } => reason.clone(),
+                },
+            });
+            let idle_wakeup = matches!(
+                &wakeup,
+                WakeupSignal::Explicit { reason } if reason == IDLE_WAKEUP
+            );
+            if let (Some(policy), false) = (policy.as_mut(), idle_wakeup) {
+                policy.record_activity(Instant::now());
+            }
             self.notify_observers(RunLoopPhase::AfterWaiting, &mode).await;
 
             match wakeup {
@@ -117,7 +182,7 @@ impl RunLoop {
                     debug!("Source1 ready: {}", source_id);
                     let tasks = self.handle_source1_message(&source_id, message).await?;
                     for task in tasks {
-                        self.task_queue.enqueue(task).await?;
+                        self.enqueue_produced(task).await;
                     }
                 }
                 WakeupSignal::Explicit { reason } => {
@@ -134,4 +199,23 @@ impl RunLoop {
         info!("RunLoop stopped");
         Ok(RunLoopRunResult::Stopped)
     }
+
+    /// Enqueue a task produced by a source.
+    ///
+    /// A full queue, exhausted task chain or drain drops the task instead
+    /// of stopping the loop.
+    async fn enqueue_produced(&self, task: Task) {
+        let task_id = task.id;
+        if !self.is_accepting() {
+            warn!("Dropping task {}: {}", task_id, RunLoopError::Draining);
+            self.record_event(|| JournalEventKind::Rejected {
+                task: JournalTask::of(&task),
+                reason: RunLoopError::Draining.to_string(),
+            });
+            return;
+        }
+        if let Err(e) = self.task_queue.enqueue(task).await {
+            warn!("Dropping task {}: {}", task_id, e);
+        }
+    }
 }
@@ -4,9 +4,11 @@ use std::panic::AssertUnwindSafe;
 use std::sync::Arc;
 
 use futures::FutureExt;
-use tracing::error;
+use tracing::{error, info};
 
+use crate::journal::JournalEventKind;
 use crate::mode::{RunLoopMode, RunLoopPhase};
+use crate::mode_policy::ModeTransition;
 use crate::run_loop::RunLoop;
 
 impl RunLoop {
@@ -15,6 +17,11 @@ impl RunLoop {
     /// Each observer call is isolated with `catch_unwind` so that a panicking
     /// observer cannot kill the RunLoop main loop.
     pub(crate) async fn notify_observers(&self, phase: RunLoopPhase, mode: &RunLoopMode) {
+        self.record_event(|| JournalEventKind::Phase {
+            mode: mode.to_string(),
+            phase,
+        });
+
         // Global observers
         {
             let observers = self.global_observers.read().await;
@@ -70,6 +77,50 @@ impl RunLoop {
         }
     }
 
+    /// Enter the mode chosen by the mode policy and tell observers.
+    pub(crate) async fn switch_mode(&self, transition: ModeTransition) -> RunLoopMode {
+        info!(
+            "RunLoop mode {} -> {} ({:?})",
+            transition.from, transition.to, transition.reason
+        );
+        *self.current_mode.write().await = transition.to.clone();
+        self.record_event(|| JournalEventKind::ModeSwitched {
+            from: transition.from.to_string(),
+            to: transition.to.to_string(),
+            reason: transition.reason,
+        });
+
+        let mut observers: Vec<_> = self
+            .global_observers
+            .read()
+            .await
+            .iter()
+            .map(|h| (h.id().to_string(), h.observer().clone()))
+            .collect();
+        for mode in [&transition.from, &transition.to] {
+            if let Some(mode_data) = self.modes.get(mode) {
+                observers.extend(
+                    mode_data
+                        .observers
+                        .read()
+                        .await
+                        .iter()
+                        .map(|h| (h.id().to_string(), h.observer().clone())),
+                );
+            }
+        }
+
+        for (id, observer) in observers {
+            let result = AssertUnwindSafe(observer.on_mode_change(&transition, self))
+                .catch_unwind()
+                .await;
+            if result.is_err() {
+                error!("Observer '{}' panicked on mode change", id);
+            }
+        }
+        transition.to
+    }
+
     /// Clean up non-repeating observers.
     pub(crate) async fn cleanup_observers(&self, mode: &RunLoopMode) {
         self.global_observers
@@ -97,13 +148,17 @@ impl RunLoop {
             .sort_by_key(|h| h.observer().priority());
     }
 
-    /// Add an observer to a specific mode.
+    /// Add an observer to a specific mode, registering a custom mode if
+    /// needed.
     pub async fn add_mode_observer(
         &self,
         mode: &RunLoopMode,
         id: impl Into<String>,
         observer: Arc<dyn crate::observer::RunLoopObserver>,
     ) {
+        if *mode != RunLoopMode::Common {
+            self.ensure_mode(mode);
+        }
         if let Some(mode_data) = self.modes.get(mode) {
             let handle = crate::observer::ObserverHandle::new(id, observer);
             mode_data.observers.write().await.push(handle);
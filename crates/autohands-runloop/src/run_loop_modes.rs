@@ -0,0 +1,108 @@
+//! RunLoop mode registration.
+//!
+//! Default, AgentProcessing and Background are always registered. Embedders
+//! add their own [`RunLoopMode::Custom`] modes (e.g. maintenance, incident)
+//! to isolate event classes: a custom mode has its own Source0s and
+//! observers, and [`RunLoop::run_in_mode`] can run it like a built-in one.
+//! Adding a Source0 or observer for an unregistered custom mode registers it.
+
+use std::sync::Arc;
+
+use dashmap::mapref::entry::Entry;
+use tracing::info;
+
+use crate::error::{RunLoopError, RunLoopResult};
+use crate::mode::{RunLoopMode, RunLoopState};
+use crate::run_loop::{ModeData, RunLoop};
+
+impl RunLoop {
+    /// Register a mode, returning whether it was new.
+    ///
+    /// `Common` names a set of modes, not a mode, and is rejected; use
+    /// [`RunLoop::add_common_mode`] to add a mode to that set.
+    pub fn add_mode(&self, mode: RunLoopMode) -> RunLoopResult<bool> {
+        if mode == RunLoopMode::Common {
+            return Err(RunLoopError::ConfigError(
+                "Common is a set of modes; use add_common_mode".to_string(),
+            ));
+        }
+        Ok(self.ensure_mode(&mode))
+    }
+
+    /// Register a mode if needed, returning whether it was new.
+    pub(crate) fn ensure_mode(&self, mode: &RunLoopMode) -> bool {
+        match self.modes.entry(mode.clone()) {
+            Entry::Occupied(_) => false,
+            Entry::Vacant(entry) => {
+                entry.insert(ModeData::new());
+                info!("RunLoop mode registered: {}", mode);
+                true
+            }
+        }
+    }
+
+    /// Add a mode to the common set, registering it if needed.
+    ///
+    /// Source0s added for `Common` so far are added to the mode as well, as
+    /// are those added later.
+    pub async fn add_common_mode(&self, mode: RunLoopMode) -> RunLoopResult<()> {
+        self.add_mode(mode.clone())?;
+        if !self.common_modes.write().await.insert(mode.clone()) {
+            return Ok(());
+        }
+
+        let common_sources = self.common_sources0.read().await.clone();
+        if let Some(mode_data) = self.modes.get(&mode) {
+            let mut sources = mode_data.sources0.write().await;
+            for source in common_sources {
+                if !sources.iter().any(|s| Arc::ptr_eq(s, &source)) {
+                    sources.push(source);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Unregister a custom mode with its Source0s and observers, returning
+    /// whether it was registered.
+    ///
+    /// Built-in modes cannot be removed, nor can the mode the RunLoop is
+    /// running in.
+    pub async fn remove_mode(&self, mode: &RunLoopMode) -> RunLoopResult<bool> {
+        if !matches!(mode, RunLoopMode::Custom(_)) {
+            return Err(RunLoopError::ConfigError(format!(
+                "Built-in mode {} cannot be removed",
+                mode
+            )));
+        }
+        let running = matches!(self.state(), RunLoopState::Running | RunLoopState::Waiting);
+        if running && *self.current_mode.read().await == *mode {
+            return Err(RunLoopError::ConfigError(format!(
+                "Mode {} is running",
+                mode
+            )));
+        }
+
+        self.common_modes.write().await.remove(mode);
+        let removed = self.modes.remove(mode).is_some();
+        if removed {
+            info!("RunLoop mode removed: {}", mode);
+        }
+        Ok(removed)
+    }
+
+    /// Whether a mode is registered.
+    pub fn has_mode(&self, mode: &RunLoopMode) -> bool {
+        self.modes.contains_key(mode)
+    }
+
+    /// Registered modes.
+    pub fn modes(&self) -> Vec<RunLoopMode> {
+        self.modes.iter().map(|entry| entry.key().clone()).collect()
+    }
+
+    /// Modes in the common set.
+    pub async fn common_modes(&self) -> Vec<RunLoopMode> {
+        self.common_modes.read().await.iter().cloned().collect()
+    }
+}
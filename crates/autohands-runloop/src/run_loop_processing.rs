@@ -5,25 +5,31 @@ use std::sync::Arc;
 use tracing::{debug, warn};
 
 use crate::error::RunLoopResult;
+use crate::journal::JournalEventKind;
 use crate::mode::RunLoopMode;
 use crate::run_loop::{ModeData, RunLoop};
 use crate::source::PortMessage;
 use crate::task::Task;
 
 impl RunLoop {
-    /// Add a Source0 to the specified modes.
+    /// Add a Source0 to the specified modes, registering custom modes as
+    /// needed.
     pub async fn add_source0(&self, source: Arc<dyn crate::source::Source0>) {
         for mode in source.modes() {
             if *mode == RunLoopMode::Common {
-                // Add to all common modes
+                // Add to all common modes, and to those added to the set later
+                self.common_sources0.write().await.push(source.clone());
                 let common = self.common_modes.read().await;
                 for m in common.iter() {
                     if let Some(mode_data) = self.modes.get(m) {
                         mode_data.sources0.write().await.push(source.clone());
                     }
                 }
-            } else if let Some(mode_data) = self.modes.get(mode) {
-                mode_data.sources0.write().await.push(source.clone());
+            } else {
+                self.ensure_mode(mode);
+                if let Some(mode_data) = self.modes.get(mode) {
+                    mode_data.sources0.write().await.push(source.clone());
+                }
             }
         }
     }
@@ -35,6 +41,10 @@ impl RunLoop {
 
     /// Remove a Source0 by ID.
     pub async fn remove_source0(&self, source_id: &str) {
+        self.common_sources0
+            .write()
+            .await
+            .retain(|s| s.id() != source_id);
         for mode_data in self.modes.iter() {
             mode_data
                 .sources0
@@ -57,10 +67,18 @@ impl RunLoop {
                 self.metrics.record_source0_perform();
                 match source.perform().await {
                     Ok(source_tasks) => {
+                        self.record_event(|| JournalEventKind::SourceTriggered {
+                            source_id: source.id().to_string(),
+                            tasks: source_tasks.len(),
+                        });
                         tasks.extend(source_tasks);
                     }
                     Err(e) => {
                         warn!("Source0 {} perform error: {}", source.id(), e);
+                        self.record_event(|| JournalEventKind::SourceFailed {
+                            source_id: source.id().to_string(),
+                            error: e.to_string(),
+                        });
                     }
                 }
             }
@@ -81,6 +99,7 @@ impl RunLoop {
             if let Some(msg) = receiver.try_recv() {
                 self.metrics.record_source1_message();
                 let tasks = receiver.source.handle(msg).await?;
+                self.record_source1(receiver.source.id(), &tasks);
                 return Ok(Some(tasks));
             }
         }
@@ -104,11 +123,20 @@ impl RunLoop {
 
         for receiver in receivers.iter() {
             if receiver.source.id() == source_id && receiver.source.is_valid() {
-                return receiver.source.handle(message).await;
+                let tasks = receiver.source.handle(message).await?;
+                self.record_source1(source_id, &tasks);
+                return Ok(tasks);
             }
         }
 
         debug!("No receiver found for source: {}", source_id);
         Ok(Vec::new())
     }
+
+    fn record_source1(&self, source_id: &str, tasks: &[Task]) {
+        self.record_event(|| JournalEventKind::SourceTriggered {
+            source_id: source_id.to_string(),
+            tasks: tasks.len(),
+        });
+    }
 }
@@ -1,18 +1,23 @@
 //! RunLoop task dispatch and processing logic.
 
 use std::panic::AssertUnwindSafe;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 use chrono::{Duration as ChronoDuration, Utc};
 use cron::Schedule;
 use futures::FutureExt;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, info_span, warn, Instrument};
 
-use autohands_protocols::channel::OutboundMessage;
+use autohands_runtime::{TraceEvent, TraceEventKind, TraceLog};
 
 use crate::agent_source::AgentTaskInjector;
-use crate::error::RunLoopResult;
-use crate::run_loop::RunLoop;
+use crate::delivery::DeliveryLog;
+use crate::outcome::TaskOutcome;
+use crate::error::{RunLoopError, RunLoopResult};
+use crate::journal::{DispatchDecision, JournalEventKind, JournalTask};
+use crate::integration::channel_bridge::fan_out;
+use crate::run_loop::{RunLoop, WakeupSignal};
 use crate::task::{Task, TaskSource};
 
 impl RunLoop {
@@ -27,6 +32,7 @@ impl RunLoop {
             Some(h) => h.clone(),
             None => {
                 warn!("No handler configured, task {} ignored", task.id);
+                self.record_dispatch(&task, DispatchDecision::NoHandler);
                 return Ok(());
             }
         };
@@ -35,6 +41,7 @@ impl RunLoop {
         // Route task to appropriate handler method based on task type
         match task.task_type.as_str() {
             t if t.starts_with("timer:") || t.starts_with("system:") => {
+                self.record_dispatch(&task, DispatchDecision::Inline);
                 debug!(
                     "Processing timer/system task: task_id={}, type={}",
                     task.id, task.task_type
@@ -48,6 +55,7 @@ impl RunLoop {
                 Ok(())
             }
             t if t.starts_with("cron:") => {
+                self.record_dispatch(&task, DispatchDecision::Rescheduled);
                 debug!(
                     "Processing cron task: task_id={}, type={}",
                     task.id, task.task_type
@@ -58,12 +66,20 @@ impl RunLoop {
             }
             // Agent-class tasks: spawn into background to avoid blocking the RunLoop
             _ => {
+                self.record_dispatch(&task, DispatchDecision::Spawned);
                 self.spawn_agent_task(handler, task);
                 Ok(())
             }
         }
     }
 
+    fn record_dispatch(&self, task: &Task, decision: DispatchDecision) {
+        self.record_event(|| JournalEventKind::Dispatched {
+            task: JournalTask::of(task),
+            decision,
+        });
+    }
+
     /// Spawn an agent-class task in the background via `tokio::spawn`.
     ///
     /// The RunLoop event loop continues processing timers, sources, and new tasks
@@ -76,15 +92,40 @@ impl RunLoop {
         let task_queue = self.task_queue.clone();
         // Clone the Arc<RwLock<...>> so we can read().await inside the spawn closure
         let channel_registry_lock = self.channel_registry.clone();
+        let delivery_log = self.delivery_log.clone();
+        let outcome_log = self.outcome_log.clone();
+        let callbacks = self.callbacks.clone();
+        let trace_log = self.trace_log.clone();
+        let journal = self.journal.clone();
+        let task_graph = self.task_queue.task_graph().clone();
+        let active_agents = self.active_agents.clone();
+        let wakeup_tx = self.wakeup_tx.clone();
 
         let task_id = task.id;
         let task_type = task.task_type.clone();
+        let trace_id = task.trace_id();
 
         info!(
             "Spawning agent task: task_id={}, type={}, correlation_id={:?}",
             task_id, task_type, task.correlation_id
         );
 
+        active_agents.fetch_add(1, Ordering::SeqCst);
+        callbacks.started(&task);
+        task_graph.running(&task);
+        let started_at = Utc::now();
+        trace_log.record(
+            &trace_id,
+            TraceEvent::new(TraceEventKind::TaskStarted)
+                .with_task(task_id)
+                .with_name(task_type.clone()),
+        );
+        let span = info_span!(
+            "agent_task",
+            task_id = %task_id,
+            task_type = %task_type,
+            correlation_id = %trace_id,
+        );
         tokio::spawn(async move {
             let result = AssertUnwindSafe(async {
                 // Acquire channel registry inside the spawn to guarantee read access
@@ -94,6 +135,18 @@ impl RunLoop {
                 let injector = AgentTaskInjector::with_queue(task_queue.clone());
 
                 let result = match task.task_type.as_str() {
+                    // Work nobody is waiting for anymore is not started
+                    _ if task.deadline.is_some_and(|deadline| deadline <= Utc::now()) => {
+                        warn!("Task {} missed its deadline before starting", task_id);
+                        if let Some(journal) = &journal {
+                            journal.record(JournalEventKind::DeadlineMissed {
+                                task: JournalTask::of(&task),
+                            });
+                        }
+                        Err(RunLoopError::TaskProcessingError(
+                            "Deadline passed before the task started".to_string(),
+                        ))
+                    }
                     "agent:execute" => handler.handle_execute(&task, &injector).await,
                     "agent:subtask" => handler.handle_subtask(&task, &injector).await,
                     "agent:delayed" => handler.handle_delayed(&task, &injector).await,
@@ -109,39 +162,79 @@ impl RunLoop {
 
                 match result {
                     Ok(agent_result) => {
+                        let outcome = Self::outcome_of(&agent_result);
+                        outcome_log.record(task_id, outcome.clone());
                         if let Err(e) =
-                            Self::handle_agent_result_static(&task, agent_result, &task_queue, channel_registry.as_ref()).await
+                            Self::handle_agent_result_static(&task, agent_result, &task_queue, channel_registry.as_ref(), &delivery_log, &trace_log).await
                         {
                             error!("Failed to handle agent result: task_id={}, error={}", task_id, e);
                         }
+                        outcome
                     }
                     Err(e) => {
                         error!("Task execution failed: task_id={}, error={}", task_id, e);
+                        let outcome = TaskOutcome::failed(e.to_string());
+                        outcome_log.record(task_id, outcome.clone());
+                        outcome
                     }
                 }
             })
             .catch_unwind()
             .await;
 
-            if let Err(panic_info) = result {
-                let msg = panic_info
-                    .downcast_ref::<&str>()
-                    .map(|s| s.to_string())
-                    .or_else(|| panic_info.downcast_ref::<String>().cloned())
-                    .unwrap_or_else(|| "unknown panic".to_string());
-                error!("Agent task panicked: task_id={}, panic={}", task_id, msg);
+            let outcome = match result {
+                Ok(outcome) => outcome,
+                Err(panic_info) => {
+                    let msg = panic_info
+                        .downcast_ref::<&str>()
+                        .map(|s| s.to_string())
+                        .or_else(|| panic_info.downcast_ref::<String>().cloned())
+                        .unwrap_or_else(|| "unknown panic".to_string());
+                    error!("Agent task panicked: task_id={}, panic={}", task_id, msg);
+                    let outcome = TaskOutcome::failed(format!("Agent panicked: {}", msg));
+                    outcome_log.record(task_id, outcome.clone());
+                    outcome
+                }
+            };
+            callbacks.finished(task_id, &outcome);
+            task_graph.finished(task_id, &outcome);
+            let mut finished = TraceEvent::new(TraceEventKind::TaskFinished)
+                .started_at(started_at)
+                .with_task(task_id);
+            if let Some(ref error) = outcome.error {
+                finished = finished
+                    .failed()
+                    .with_detail(serde_json::json!({"error": error}));
             }
-        });
+            trace_log.record(&trace_id, finished);
+
+            // Let the loop re-evaluate its mode now that the agent is done
+            active_agents.fetch_sub(1, Ordering::SeqCst);
+            let _ = wakeup_tx.try_send(WakeupSignal::Explicit {
+                reason: "agent_task_finished".to_string(),
+            });
+        }.instrument(span));
+    }
+
+    /// Outcome recorded for an agent result.
+    fn outcome_of(agent_result: &crate::agent_driver::AgentResult) -> TaskOutcome {
+        match &agent_result.error {
+            Some(error) => TaskOutcome::failed(error.clone()),
+            None => TaskOutcome::succeeded(agent_result.response.clone()),
+        }
     }
 
     /// Handle a successful agent result (static version for use inside `tokio::spawn`).
     ///
-    /// Injects follow-up tasks, sends response via channel, and resets the task chain.
+    /// Injects follow-up tasks, delivers the response to every target, and resets
+    /// the task chain.
     async fn handle_agent_result_static(
         task: &Task,
         agent_result: crate::agent_driver::AgentResult,
         task_queue: &Arc<crate::task_queue::TaskQueue>,
         channel_registry: Option<&Arc<autohands_core::registry::ChannelRegistry>>,
+        delivery_log: &DeliveryLog,
+        trace_log: &TraceLog,
     ) -> RunLoopResult<()> {
         // Inject follow-up tasks
         if !agent_result.tasks.is_empty() {
@@ -151,25 +244,33 @@ impl RunLoop {
             }
         }
 
-        // Send response back via channel if reply_to is set
-        if let Some(ref reply_to) = task.reply_to {
-            if let Some(ref response) = agent_result.response {
+        // Fan the response out to the reply address and any declared targets
+        if let Some(ref response) = agent_result.response {
+            if task.reply_to.is_some() || !task.deliver_to.is_empty() {
                 if let Some(registry) = channel_registry {
-                    let outbound = OutboundMessage::text(response);
-                    match registry.send(reply_to, outbound).await {
-                        Ok(_) => {
-                            info!(
-                                "Response sent to channel: {} target: {}",
-                                reply_to.channel_id, reply_to.target
-                            );
-                        }
-                        Err(e) => {
-                            error!(
-                                "Failed to send response to channel {}: {}",
-                                reply_to.channel_id, e
-                            );
+                    let statuses = fan_out(registry, task, response).await;
+                    for status in &statuses {
+                        let mut event = TraceEvent::new(TraceEventKind::MessageSent)
+                            .with_task(task.id)
+                            .with_name(status.channel_id.clone())
+                            .with_detail(serde_json::json!({
+                                "target": status.target,
+                                "message_id": status.message_id,
+                                "error": status.error,
+                            }));
+                        if !status.delivered {
+                            event = event.failed();
                         }
+                        trace_log.record(&task.trace_id(), event);
+                    }
+                    let failed = statuses.iter().filter(|s| !s.delivered).count();
+                    if failed > 0 {
+                        warn!(
+                            "Task {} result failed to reach {} of {} target(s)",
+                            task.id, failed, statuses.len()
+                        );
                     }
+                    delivery_log.record(task.id, statuses);
                 } else {
                     warn!("No channel registry configured, cannot send response");
                 }
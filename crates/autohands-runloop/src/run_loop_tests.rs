@@ -3,6 +3,7 @@ use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::error::RunLoopError;
 use crate::mode::{RunLoopPhase, RunLoopRunResult};
 use crate::task::Task;
 
@@ -122,3 +123,219 @@ async fn test_runloop_wakeup() {
         .await;
     assert!(matches!(result, Ok(RunLoopRunResult::Stopped)));
 }
+
+#[tokio::test]
+async fn test_runloop_add_custom_mode() {
+    let run_loop = RunLoop::default();
+    let maintenance = RunLoopMode::custom("maintenance");
+
+    assert!(!run_loop.has_mode(&maintenance));
+    assert!(run_loop.add_mode(maintenance.clone()).unwrap());
+    assert!(!run_loop.add_mode(maintenance.clone()).unwrap());
+    assert!(run_loop.has_mode(&maintenance));
+    assert!(run_loop.modes().contains(&maintenance));
+
+    assert!(run_loop.add_mode(RunLoopMode::Common).is_err());
+}
+
+#[tokio::test]
+async fn test_runloop_source_registers_custom_mode() {
+    use crate::agent_source::AgentSource0;
+
+    let run_loop = RunLoop::default();
+    let incident = RunLoopMode::custom("incident");
+    let source = AgentSource0::new("incident-source").with_modes(vec![incident.clone()]);
+
+    run_loop.add_source0(Arc::new(source)).await;
+
+    assert!(run_loop.has_mode(&incident));
+    let mode_data = run_loop.modes.get(&incident).unwrap();
+    assert_eq!(mode_data.sources0.read().await.len(), 1);
+}
+
+#[tokio::test]
+async fn test_runloop_run_in_custom_mode() {
+    use crate::observer::RunLoopObserver;
+    use async_trait::async_trait;
+
+    struct TestObserver {
+        counter: Arc<AtomicU32>,
+    }
+
+    #[async_trait]
+    impl RunLoopObserver for TestObserver {
+        fn activities(&self) -> u32 {
+            RunLoopPhase::Entry as u32 | RunLoopPhase::Exit as u32
+        }
+
+        async fn on_phase(&self, _phase: RunLoopPhase, _run_loop: &RunLoop) {
+            self.counter.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let counter = Arc::new(AtomicU32::new(0));
+    let maintenance = RunLoopMode::custom("maintenance");
+    let run_loop = Arc::new(RunLoop::default());
+    run_loop
+        .add_mode_observer(
+            &maintenance,
+            "test",
+            Arc::new(TestObserver {
+                counter: counter.clone(),
+            }),
+        )
+        .await;
+    assert!(run_loop.has_mode(&maintenance));
+
+    let run_loop_clone = run_loop.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        run_loop_clone.stop();
+    });
+
+    let result = run_loop
+        .run_in_mode(maintenance.clone(), Duration::from_secs(1))
+        .await;
+    assert!(matches!(result, Ok(RunLoopRunResult::Stopped)));
+    assert_eq!(counter.load(Ordering::SeqCst), 2);
+
+    // Unregistered custom modes cannot run
+    let result = run_loop
+        .run_in_mode(RunLoopMode::custom("unknown"), Duration::from_millis(10))
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_runloop_add_common_mode_syncs_sources() {
+    use crate::agent_source::AgentSource0;
+
+    let run_loop = RunLoop::default();
+    let source = AgentSource0::new("common-source").with_modes(vec![RunLoopMode::Common]);
+    run_loop.add_source0(Arc::new(source)).await;
+
+    let maintenance = RunLoopMode::custom("maintenance");
+    run_loop.add_common_mode(maintenance.clone()).await.unwrap();
+    assert!(run_loop.common_modes().await.contains(&maintenance));
+
+    let mode_data = run_loop.modes.get(&maintenance).unwrap();
+    assert_eq!(mode_data.sources0.read().await.len(), 1);
+    drop(mode_data);
+
+    // Adding it again does not duplicate the source
+    run_loop.add_common_mode(maintenance.clone()).await.unwrap();
+    let mode_data = run_loop.modes.get(&maintenance).unwrap();
+    assert_eq!(mode_data.sources0.read().await.len(), 1);
+}
+
+#[tokio::test]
+async fn test_runloop_remove_mode() {
+    let run_loop = RunLoop::default();
+    let maintenance = RunLoopMode::custom("maintenance");
+    run_loop.add_mode(maintenance.clone()).unwrap();
+
+    assert!(run_loop.remove_mode(&RunLoopMode::Default).await.is_err());
+    assert!(run_loop.remove_mode(&maintenance).await.unwrap());
+    assert!(!run_loop.remove_mode(&maintenance).await.unwrap());
+    assert!(!run_loop.has_mode(&maintenance));
+}
+
+#[tokio::test]
+async fn test_runloop_pause_resume() {
+    let run_loop = RunLoop::default();
+    run_loop.pause();
+    assert_eq!(run_loop.dispatch_state(), DispatchState::Paused);
+
+    // Paused: tasks are accepted but stay queued
+    run_loop
+        .inject_task(Task::new("test:task", serde_json::json!({})))
+        .await
+        .unwrap();
+    run_loop
+        .run_in_mode(RunLoopMode::Default, Duration::from_millis(50))
+        .await
+        .unwrap();
+    assert_eq!(run_loop.pending_task_count().await, 1);
+
+    run_loop.resume();
+    run_loop
+        .run_in_mode(RunLoopMode::Default, Duration::from_millis(50))
+        .await
+        .unwrap();
+    assert_eq!(run_loop.pending_task_count().await, 0);
+    assert_eq!(run_loop.dispatch_state(), DispatchState::Active);
+}
+
+#[tokio::test]
+async fn test_runloop_drain() {
+    let run_loop = RunLoop::default();
+    run_loop
+        .inject_task(Task::new("test:task", serde_json::json!({})))
+        .await
+        .unwrap();
+
+    run_loop.drain();
+    let result = run_loop
+        .inject_task(Task::new("test:task", serde_json::json!({})))
+        .await;
+    assert!(matches!(result, Err(RunLoopError::Draining)));
+
+    let status = run_loop.control_status().await;
+    assert_eq!(status.queued_tasks, 1);
+    assert!(!status.drained);
+    assert!(!run_loop.wait_drained(Duration::from_millis(10)).await);
+
+    // Queued tasks still dispatch while draining
+    run_loop
+        .run_in_mode(RunLoopMode::Default, Duration::from_millis(50))
+        .await
+        .unwrap();
+    assert!(run_loop.control_status().await.drained);
+    assert!(run_loop.wait_drained(Duration::from_millis(10)).await);
+
+    run_loop.resume();
+    assert!(!run_loop.wait_drained(Duration::from_secs(1)).await);
+    run_loop
+        .inject_task(Task::new("test:task", serde_json::json!({})))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_runloop_journal() {
+    use crate::journal::{DispatchDecision, JournalEventKind};
+
+    assert!(RunLoop::default().journal().is_none());
+
+    let mut config = RunLoopConfig::default();
+    config.journal.enabled = true;
+    let run_loop = RunLoop::new(config);
+    let task = Task::new("agent:execute", serde_json::json!({}));
+    let task_id = task.id;
+    run_loop.inject_task(task).await.unwrap();
+    run_loop.pause();
+
+    let result = run_loop
+        .run_in_mode(RunLoopMode::Default, Duration::from_millis(50))
+        .await;
+    assert!(matches!(result, Ok(RunLoopRunResult::TimedOut)));
+    run_loop.resume();
+    run_loop
+        .run_in_mode(RunLoopMode::Default, Duration::from_millis(50))
+        .await
+        .unwrap();
+
+    let kinds: Vec<_> = run_loop
+        .journal()
+        .unwrap()
+        .events(None)
+        .into_iter()
+        .map(|event| event.kind)
+        .collect();
+    assert!(matches!(&kinds[0], JournalEventKind::Queued { task, .. } if task.id == task_id));
+    assert!(kinds.iter().any(|kind| matches!(kind, JournalEventKind::DispatchChanged { .. })));
+    assert!(kinds.iter().any(|kind| matches!(
+        kind,
+        JournalEventKind::Dispatched { task, decision: DispatchDecision::NoHandler } if task.id == task_id
+    )));
+}
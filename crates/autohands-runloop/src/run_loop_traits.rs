@@ -19,23 +19,12 @@ impl autohands_protocols::extension::TaskSubmitter for RunLoop {
         payload: serde_json::Value,
         correlation_id: Option<String>,
     ) -> Result<(), autohands_protocols::error::ExtensionError> {
-        use crate::task::{Task, TaskPriority, TaskSource};
-
-        // Create Task from parameters
-        let mut task = Task::new(task_type.to_string(), payload.clone())
-            .with_source(TaskSource::Custom("task_submitter".to_string()));
-
-        // Map priority if present in payload
-        if let Some(priority) = payload.get("priority") {
-            if let Some(p) = priority.as_str() {
-                task = task.with_priority(match p {
-                    "low" => TaskPriority::Low,
-                    "high" => TaskPriority::High,
-                    "critical" => TaskPriority::Critical,
-                    _ => TaskPriority::Normal,
-                });
-            }
-        }
+        use crate::task::{Task, TaskSource};
+
+        // Create Task from parameters, with the payload's priority and deadline
+        let mut task = Task::new(task_type.to_string(), payload)
+            .with_source(TaskSource::Custom("task_submitter".to_string()))
+            .with_payload_scheduling();
 
         // Copy correlation ID
         if let Some(ref cid) = correlation_id {
@@ -37,7 +37,7 @@ impl RunLoop {
             // Timeout
             _ = tokio::time::sleep(wait_timeout) => {
                 WakeupSignal::Explicit {
-                    reason: "timeout".to_string(),
+                    reason: crate::journal::IDLE_WAKEUP.to_string(),
                 }
             }
         }
@@ -12,7 +12,8 @@
 //! Using `RunLoopSpawner` ensures:
 //! 1. All async work is traceable through correlation IDs
 //! 2. Tasks respect the current RunLoop mode
-//! 3. Tasks can be cancelled when RunLoop stops
+//! 3. Tasks can be cancelled when RunLoop stops, or with the session that
+//!    spawned them ([`RunLoopSpawner::spawn_child`])
 //! 4. Task metrics are collected
 //!
 //! ## Example
@@ -34,9 +35,11 @@
 //! }
 //! ```
 
+use std::collections::HashMap;
 use std::future::Future;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Duration;
 
 use tokio::sync::RwLock;
 use tokio_util::sync::CancellationToken;
@@ -49,9 +52,13 @@ use crate::mode::RunLoopState;
 // `crate::spawner::TaskState` etc. paths continue to work.
 pub use crate::correlation::CorrelationGuard;
 pub use crate::spawner_types::{
-    SpawnedTaskHandle, SpawnerInner, SpawnerMetrics, SpawnerStateProvider, TaskInfo, TaskState,
+    SpawnedTaskHandle, SpawnerInner, SpawnerMetrics, SpawnerShutdownReport, SpawnerStateProvider,
+    TaskInfo, TaskState,
 };
 
+/// How often shutdown checks whether cancelled tasks have stopped.
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
 /// RunLoop-aware task spawner.
 ///
 /// Provides unified async task management with:
@@ -171,6 +178,7 @@ impl RunLoopSpawner {
 
         // Spawn with instrumentation
         let handle = tokio::spawn(future.instrument(span));
+        self.inner.track(task_id, handle.abort_handle());
 
         SpawnedTaskHandle {
             id: task_id,
@@ -208,6 +216,7 @@ impl RunLoopSpawner {
         );
 
         let handle = tokio::task::spawn_blocking(f);
+        self.inner.track(task_id, handle.abort_handle());
 
         SpawnedTaskHandle {
             id: task_id,
@@ -254,6 +263,40 @@ impl RunLoopSpawner {
         name: impl Into<String>,
         f: F,
     ) -> SpawnedTaskHandle<T>
+    where
+        F: FnOnce(CancellationToken) -> Fut + Send + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        self.spawn_with_token(name, CancellationToken::new(), f)
+            .await
+    }
+
+    /// Spawn a cancellable task on behalf of a parent, such as a session.
+    ///
+    /// The task's token is a child of `parent`: cancelling the parent
+    /// cancels the task, and through it anything the task spawns with its
+    /// own token, while the task can still be cancelled on its own.
+    pub async fn spawn_child<F, Fut, T>(
+        &self,
+        parent: &CancellationToken,
+        name: impl Into<String>,
+        f: F,
+    ) -> SpawnedTaskHandle<T>
+    where
+        F: FnOnce(CancellationToken) -> Fut + Send + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        self.spawn_with_token(name, parent.child_token(), f).await
+    }
+
+    async fn spawn_with_token<F, Fut, T>(
+        &self,
+        name: impl Into<String>,
+        cancellation_token: CancellationToken,
+        f: F,
+    ) -> SpawnedTaskHandle<T>
     where
         F: FnOnce(CancellationToken) -> Fut + Send + 'static,
         Fut: Future<Output = T> + Send + 'static,
@@ -262,7 +305,6 @@ impl RunLoopSpawner {
         let task_id = Uuid::new_v4();
         let task_name = name.into();
         let correlation_id = self.correlation_context.read().await.clone();
-        let cancellation_token = CancellationToken::new();
 
         // Check RunLoop state if provider is available
         if let Some(ref provider) = self.state_provider {
@@ -309,6 +351,7 @@ impl RunLoopSpawner {
 
         // Spawn with instrumentation
         let handle = tokio::spawn(future.instrument(span));
+        self.inner.track(task_id, handle.abort_handle());
 
         SpawnedTaskHandle {
             id: task_id,
@@ -358,6 +401,50 @@ impl RunLoopSpawner {
         cancelled_count
     }
 
+    /// Cancel all tasks and wait up to `grace` for them to stop.
+    ///
+    /// Cancellable tasks are asked to stop through their tokens; others
+    /// get the same time to finish. Tasks still running afterwards are
+    /// aborted and reported.
+    pub async fn shutdown(&self, grace: Duration) -> SpawnerShutdownReport {
+        let names: HashMap<Uuid, TaskInfo> = self
+            .inner
+            .tasks
+            .iter()
+            .map(|e| (*e.key(), e.value().clone()))
+            .collect();
+        let cancelled = self.inner.cancel_all();
+
+        let deadline = tokio::time::Instant::now() + grace;
+        while !self.inner.unfinished().is_empty() && tokio::time::Instant::now() < deadline {
+            tokio::time::sleep(SHUTDOWN_POLL_INTERVAL).await;
+        }
+
+        let mut forced = Vec::new();
+        for (id, handle) in self.inner.unfinished() {
+            handle.abort();
+            if let Some(info) = names.get(&id) {
+                warn!(
+                    task_id = %id,
+                    task_name = %info.name,
+                    "Task did not stop within {:?}, aborted", grace
+                );
+                forced.push(TaskInfo {
+                    state: TaskState::Cancelled,
+                    ..info.clone()
+                });
+            }
+            self.inner.mark_cancelled(id);
+        }
+        // Finished tasks whose handles were never awaited
+        let remaining: Vec<Uuid> = self.inner.tasks.iter().map(|e| *e.key()).collect();
+        for id in remaining {
+            self.inner.mark_completed(id);
+        }
+
+        SpawnerShutdownReport { cancelled, forced }
+    }
+
     /// Cancel a specific task by ID.
     ///
     /// If the task is cancellable (spawned via `spawn_cancellable`), its
@@ -382,3 +382,45 @@ async fn test_spawner_inner_cancel_all() {
     assert_eq!(inner.tasks.len(), 0);
     assert_eq!(inner.cancellable_count(), 0);
 }
+
+#[tokio::test]
+async fn test_spawn_child_follows_parent() {
+    let spawner = RunLoopSpawner::new();
+    let session = CancellationToken::new();
+
+    let wait = |token: CancellationToken| async move {
+        token.cancelled().await;
+        "cancelled"
+    };
+    let first = spawner.spawn_child(&session, "sub-agent", wait).await;
+    let second = spawner.spawn_child(&session, "browser", wait).await;
+
+    // A child can be cancelled alone
+    assert!(spawner.cancel_task(first.id));
+    assert_eq!(first.await.unwrap(), "cancelled");
+    assert!(!session.is_cancelled());
+
+    session.cancel();
+    let result = tokio::time::timeout(Duration::from_secs(1), second).await;
+    assert_eq!(result.unwrap().unwrap(), "cancelled");
+}
+
+#[tokio::test]
+async fn test_shutdown_forces_stuck_tasks() {
+    let spawner = RunLoopSpawner::new();
+    let cooperative = spawner
+        .spawn_cancellable("cooperative", |token| async move {
+            token.cancelled().await;
+        })
+        .await;
+    let stuck = spawner.spawn("stuck", std::future::pending::<()>()).await;
+
+    let report = spawner.shutdown(Duration::from_millis(100)).await;
+    assert_eq!(report.cancelled, 1);
+    assert!(!report.is_clean());
+    assert_eq!(report.forced.len(), 1);
+    assert_eq!(report.forced[0].name, "stuck");
+    assert!(cooperative.await.is_ok());
+    assert!(stuck.await.unwrap_err().is_cancelled());
+    assert!(spawner.active_tasks().is_empty());
+}
@@ -5,6 +5,7 @@
 //! - [`SpawnedTaskHandle`]: Handle to a spawned task (implements `Future`)
 //! - [`SpawnerInner`]: Shared inner state for task tracking and cancellation
 //! - [`SpawnerMetrics`]: Metrics snapshot
+//! - [`SpawnerShutdownReport`]: Outcome of a bounded shutdown
 //! - [`SpawnerStateProvider`]: Trait for RunLoop state awareness
 
 use std::future::Future;
@@ -12,7 +13,7 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 use dashmap::DashMap;
-use tokio::task::JoinHandle;
+use tokio::task::{AbortHandle, JoinHandle};
 use tokio_util::sync::CancellationToken;
 use tracing::info;
 
@@ -102,6 +103,8 @@ pub struct SpawnerInner {
     pub tasks: DashMap<uuid::Uuid, TaskInfo>,
     /// Cancellation tokens for cancellable tasks.
     cancellation_tokens: DashMap<uuid::Uuid, CancellationToken>,
+    /// Abort handles of running tasks, for forced cleanup on shutdown.
+    abort_handles: DashMap<uuid::Uuid, AbortHandle>,
     /// Total tasks spawned.
     pub total_spawned: AtomicU64,
     /// Total tasks completed.
@@ -124,6 +127,7 @@ impl SpawnerInner {
         Self {
             tasks: DashMap::new(),
             cancellation_tokens: DashMap::new(),
+            abort_handles: DashMap::new(),
             total_spawned: AtomicU64::new(0),
             total_completed: AtomicU64::new(0),
             total_cancelled: AtomicU64::new(0),
@@ -140,6 +144,22 @@ impl SpawnerInner {
         self.total_spawned.fetch_add(1, Ordering::SeqCst);
     }
 
+    /// Track a spawned task's abort handle.
+    pub(crate) fn track(&self, id: uuid::Uuid, handle: AbortHandle) {
+        if !handle.is_finished() {
+            self.abort_handles.insert(id, handle);
+        }
+    }
+
+    /// Tasks tracked for forced cleanup that are still running.
+    pub(crate) fn unfinished(&self) -> Vec<(uuid::Uuid, AbortHandle)> {
+        self.abort_handles.retain(|_, handle| !handle.is_finished());
+        self.abort_handles
+            .iter()
+            .map(|e| (*e.key(), e.value().clone()))
+            .collect()
+    }
+
     /// Cancel a specific task by ID.
     pub fn cancel_task(&self, id: uuid::Uuid) -> bool {
         if let Some((_, token)) = self.cancellation_tokens.remove(&id) {
@@ -186,6 +206,7 @@ impl SpawnerInner {
         }
         self.total_completed.fetch_add(1, Ordering::SeqCst);
         self.cancellation_tokens.remove(&id);
+        self.abort_handles.remove(&id);
         self.tasks.remove(&id);
     }
 
@@ -204,6 +225,7 @@ impl SpawnerInner {
         }
         self.total_failed.fetch_add(1, Ordering::SeqCst);
         self.cancellation_tokens.remove(&id);
+        self.abort_handles.remove(&id);
         self.tasks.remove(&id);
     }
 
@@ -228,6 +250,22 @@ pub struct SpawnerMetrics {
     pub active_tasks: usize,
 }
 
+/// Outcome of [`RunLoopSpawner::shutdown`](crate::spawner::RunLoopSpawner::shutdown).
+#[derive(Debug, Clone, Default)]
+pub struct SpawnerShutdownReport {
+    /// Cancellable tasks whose tokens were triggered.
+    pub cancelled: usize,
+    /// Tasks still running after the grace period, aborted.
+    pub forced: Vec<TaskInfo>,
+}
+
+impl SpawnerShutdownReport {
+    /// Whether every task stopped within the grace period.
+    pub fn is_clean(&self) -> bool {
+        self.forced.is_empty()
+    }
+}
+
 /// State provider for spawner to check RunLoop state without circular reference.
 pub trait SpawnerStateProvider: Send + Sync {
     /// Get the current RunLoop state.
@@ -7,11 +7,15 @@ mod tests;
 use std::cmp::Ordering;
 use std::collections::HashMap;
 
+use autohands_protocols::agent::RunScheduling;
 use autohands_protocols::channel::ReplyAddress;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::callback::TaskCallback;
+use crate::delivery::DeliveryTarget;
+
 /// Task priority levels.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[repr(u8)]
@@ -34,6 +38,35 @@ impl Default for TaskPriority {
     }
 }
 
+impl TaskPriority {
+    /// Highest priority children inherit; critical and system priorities
+    /// are kept for the tasks that have them.
+    pub const MAX_INHERITED: TaskPriority = TaskPriority::High;
+
+    /// Name of the priority, as in task payloads.
+    pub fn name(self) -> &'static str {
+        match self {
+            TaskPriority::Low => "low",
+            TaskPriority::Normal => "normal",
+            TaskPriority::High => "high",
+            TaskPriority::Critical => "critical",
+            TaskPriority::System => "system",
+        }
+    }
+
+    /// Priority with the given name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "low" => Some(TaskPriority::Low),
+            "normal" => Some(TaskPriority::Normal),
+            "high" => Some(TaskPriority::High),
+            "critical" => Some(TaskPriority::Critical),
+            "system" => Some(TaskPriority::System),
+            _ => None,
+        }
+    }
+}
+
 /// Task source identification.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TaskSource {
@@ -63,6 +96,23 @@ impl Default for TaskSource {
     }
 }
 
+impl TaskSource {
+    /// Name of the source, as used in queue configuration and metrics.
+    pub fn name(&self) -> &str {
+        match self {
+            TaskSource::User => "user",
+            TaskSource::Scheduler => "scheduler",
+            TaskSource::FileWatcher => "file_watcher",
+            TaskSource::Webhook => "webhook",
+            TaskSource::WebSocket => "websocket",
+            TaskSource::Agent => "agent",
+            TaskSource::System => "system",
+            TaskSource::Timer => "timer",
+            TaskSource::Custom(name) => name,
+        }
+    }
+}
+
 /// A RunLoop task.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Task {
@@ -94,6 +144,15 @@ pub struct Task {
     /// Reply address for routing responses back to the source channel.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reply_to: Option<ReplyAddress>,
+    /// Extra places the final result is delivered to.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deliver_to: Vec<DeliveryTarget>,
+    /// Endpoint receiving the task's lifecycle events.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub callback: Option<TaskCallback>,
+    /// Time by which the task must be done; it fails once this passes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline: Option<DateTime<Utc>>,
 }
 
 impl Task {
@@ -113,6 +172,9 @@ impl Task {
             retry_count: 0,
             max_retries: 3,
             reply_to: None,
+            deliver_to: Vec::new(),
+            callback: None,
+            deadline: None,
         }
     }
 
@@ -164,6 +226,101 @@ impl Task {
         self
     }
 
+    /// Add a delivery target for the final result.
+    pub fn with_delivery_target(mut self, target: DeliveryTarget) -> Self {
+        self.deliver_to.push(target);
+        self
+    }
+
+    /// Set the time by which the task must be done.
+    pub fn with_deadline(mut self, deadline: DateTime<Utc>) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Make this task a child of `parent`.
+    ///
+    /// Besides the parent ID and correlation ID, the child inherits the
+    /// parent's priority (up to [`TaskPriority::MAX_INHERITED`]) unless its
+    /// own is higher, and the parent's deadline unless its own is earlier,
+    /// so work done for an urgent task is not starved by background work.
+    /// Children of a dry run are dry runs too.
+    pub fn inherit_from(mut self, parent: &Task) -> Self {
+        if parent.is_dry_run() {
+            self = self.with_dry_run();
+        }
+        self.parent_id = Some(parent.id);
+        if self.correlation_id.is_none() {
+            self.correlation_id = parent.correlation_id.clone();
+        }
+        self.priority = self
+            .priority
+            .max(parent.priority.min(TaskPriority::MAX_INHERITED));
+        self.deadline = match (self.deadline, parent.deadline) {
+            (Some(own), Some(inherited)) => Some(own.min(inherited)),
+            (own, inherited) => own.or(inherited),
+        };
+        self
+    }
+
+    /// Apply a payload's `priority` and `deadline`, e.g. one written by
+    /// [`RunScheduling::apply_to`]. Payloads cannot ask for system priority.
+    pub fn with_payload_scheduling(mut self) -> Self {
+        let scheduling = RunScheduling::from_payload(&self.payload);
+        let priority = scheduling.priority.as_deref();
+        if let Some(priority) = priority.and_then(TaskPriority::from_name) {
+            self.priority = priority.min(TaskPriority::Critical);
+        }
+        if scheduling.deadline.is_some() {
+            self.deadline = scheduling.deadline;
+        }
+        self
+    }
+
+    /// Mark the task as a dry run (`"dry_run": true` in the payload).
+    pub fn with_dry_run(mut self) -> Self {
+        if let Some(object) = self.payload.as_object_mut() {
+            object.insert("dry_run".to_string(), serde_json::json!(true));
+        }
+        self
+    }
+
+    /// Whether the task's agent run only simulates tools with side effects,
+    /// previewing what it would do.
+    pub fn is_dry_run(&self) -> bool {
+        self.payload.get("dry_run").and_then(|v| v.as_bool()) == Some(true)
+    }
+
+    /// Priority and deadline handed to the task's agent run.
+    pub fn scheduling(&self) -> RunScheduling {
+        let priority = self.priority.min(TaskPriority::MAX_INHERITED);
+        RunScheduling {
+            priority: Some(priority.name().to_string()),
+            deadline: self.deadline,
+        }
+    }
+
+    /// Post the task's lifecycle events to a callback.
+    pub fn with_callback(mut self, callback: TaskCallback) -> Self {
+        self.callback = Some(callback);
+        self
+    }
+
+    /// All targets for the final result.
+    ///
+    /// The reply address comes first, as markdown, unless `deliver_to`
+    /// already lists it with its own formatting.
+    pub fn delivery_targets(&self) -> Vec<DeliveryTarget> {
+        let mut targets = Vec::with_capacity(self.deliver_to.len() + 1);
+        if let Some(reply_to) = &self.reply_to {
+            if !self.deliver_to.iter().any(|t| &t.address == reply_to) {
+                targets.push(DeliveryTarget::from(reply_to.clone()));
+            }
+        }
+        targets.extend(self.deliver_to.iter().cloned());
+        targets
+    }
+
     /// Check if the task is ready to execute.
     pub fn is_ready(&self) -> bool {
         match self.scheduled_at {
@@ -182,6 +339,25 @@ impl Task {
         self.retry_count += 1;
     }
 
+    /// Agent session the task runs in: the payload's `session_id`, else its
+    /// correlation ID, else its own ID.
+    pub fn session_id(&self) -> String {
+        self.payload
+            .get("session_id")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .or_else(|| self.correlation_id.clone())
+            .unwrap_or_else(|| self.id.to_string())
+    }
+
+    /// ID the task's work is traced under: its correlation ID, or its own
+    /// ID when it has none.
+    pub fn trace_id(&self) -> String {
+        self.correlation_id
+            .clone()
+            .unwrap_or_else(|| self.id.to_string())
+    }
+
     /// Get or create correlation ID.
     pub fn ensure_correlation_id(&mut self) -> String {
         self.correlation_id
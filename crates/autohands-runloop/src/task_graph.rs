@@ -0,0 +1,219 @@
+//! Parent/child graph of agent tasks.
+//!
+//! Tasks chained by an agent (subtasks, delayed follow-ups, error reports)
+//! carry their parent's ID. The [`TaskGraph`] records every agent-class task
+//! as it is queued, started and finished, so a whole multi-agent run can be
+//! walked from any of its tasks.
+
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::outcome::TaskOutcome;
+use crate::task::Task;
+
+/// Number of tasks kept in the graph.
+pub const TASK_GRAPH_CAPACITY: usize = 1000;
+
+/// Where a task is in its life.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskNodeStatus {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl TaskNodeStatus {
+    /// Name of the status, as serialized.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Queued => "queued",
+            Self::Running => "running",
+            Self::Succeeded => "succeeded",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// A task in the graph.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskNode {
+    /// Task ID.
+    pub id: Uuid,
+    /// Task that produced this one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<Uuid>,
+    /// Task type.
+    pub task_type: String,
+    /// Correlation ID of the chain.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+    /// Agent session the task runs in.
+    pub session_id: String,
+    /// Status.
+    pub status: TaskNodeStatus,
+    /// When the task was created.
+    pub created_at: DateTime<Utc>,
+    /// When the task started.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<DateTime<Utc>>,
+    /// When the task finished.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Error on failure.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Recent agent tasks and their parents.
+///
+/// Only the last [`TASK_GRAPH_CAPACITY`] tasks are kept.
+#[derive(Default)]
+pub struct TaskGraph {
+    nodes: Mutex<VecDeque<TaskNode>>,
+}
+
+impl TaskGraph {
+    /// Create an empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether a task is recorded: timer, cron and system tasks are not.
+    pub fn tracks(task: &Task) -> bool {
+        !["timer:", "cron:", "system:"]
+            .iter()
+            .any(|prefix| task.task_type.starts_with(prefix))
+    }
+
+    /// Record a queued task.
+    pub fn queued(&self, task: &Task) {
+        if !Self::tracks(task) {
+            return;
+        }
+        let mut nodes = self.nodes.lock();
+        if nodes.iter().any(|node| node.id == task.id) {
+            return;
+        }
+        if nodes.len() >= TASK_GRAPH_CAPACITY {
+            nodes.pop_front();
+        }
+        nodes.push_back(TaskNode {
+            id: task.id,
+            parent_id: task.parent_id,
+            task_type: task.task_type.clone(),
+            correlation_id: task.correlation_id.clone(),
+            session_id: task.session_id(),
+            status: TaskNodeStatus::Queued,
+            created_at: task.created_at,
+            started_at: None,
+            finished_at: None,
+            error: None,
+        });
+    }
+
+    /// Record that a task started.
+    pub fn running(&self, task: &Task) {
+        self.queued(task);
+        self.update(task.id, |node| {
+            node.status = TaskNodeStatus::Running;
+            node.started_at = Some(Utc::now());
+        });
+    }
+
+    /// Record how a task ended.
+    pub fn finished(&self, task_id: Uuid, outcome: &TaskOutcome) {
+        self.update(task_id, |node| {
+            node.status = if outcome.success {
+                TaskNodeStatus::Succeeded
+            } else {
+                TaskNodeStatus::Failed
+            };
+            node.finished_at = Some(outcome.finished_at);
+            node.error = outcome.error.clone();
+        });
+    }
+
+    fn update(&self, task_id: Uuid, apply: impl FnOnce(&mut TaskNode)) {
+        if let Some(node) = self.nodes.lock().iter_mut().find(|node| node.id == task_id) {
+            apply(node);
+        }
+    }
+
+    /// A task.
+    pub fn get(&self, task_id: Uuid) -> Option<TaskNode> {
+        self.nodes.lock().iter().find(|node| node.id == task_id).cloned()
+    }
+
+    /// Tasks produced by a task, oldest first.
+    pub fn children(&self, task_id: Uuid) -> Vec<TaskNode> {
+        self.nodes
+            .lock()
+            .iter()
+            .filter(|node| node.parent_id == Some(task_id))
+            .cloned()
+            .collect()
+    }
+
+    /// The oldest ancestor of a task still in the graph.
+    pub fn root_of(&self, task_id: Uuid) -> Option<Uuid> {
+        let nodes = self.nodes.lock();
+        let mut current = nodes.iter().find(|node| node.id == task_id)?;
+        // Bounded by the graph size in case of a malformed cycle
+        for _ in 0..nodes.len() {
+            let Some(parent) = current
+                .parent_id
+                .and_then(|parent_id| nodes.iter().find(|node| node.id == parent_id))
+            else {
+                break;
+            };
+            current = parent;
+        }
+        Some(current.id)
+    }
+
+    /// Tasks without a parent in the graph, newest first.
+    pub fn roots(&self, limit: usize) -> Vec<TaskNode> {
+        let nodes = self.nodes.lock();
+        nodes
+            .iter()
+            .rev()
+            .filter(|node| {
+                node.parent_id
+                    .is_none_or(|parent_id| !nodes.iter().any(|other| other.id == parent_id))
+            })
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// Root of the oldest task of a session still in the graph.
+    pub fn root_of_session(&self, session_id: &str) -> Option<Uuid> {
+        let task_id = self
+            .nodes
+            .lock()
+            .iter()
+            .find(|node| node.session_id == session_id)
+            .map(|node| node.id)?;
+        self.root_of(task_id)
+    }
+
+    /// Number of tasks in the graph.
+    pub fn len(&self) -> usize {
+        self.nodes.lock().len()
+    }
+
+    /// Whether the graph is empty.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.lock().is_empty()
+    }
+}
+
+#[cfg(test)]
+#[path = "task_graph_tests.rs"]
+mod tests;
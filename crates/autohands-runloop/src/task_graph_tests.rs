@@ -0,0 +1,71 @@
+use super::*;
+
+fn agent_task() -> Task {
+    Task::new("agent:execute", serde_json::json!({"prompt": "hi"}))
+}
+
+#[test]
+fn test_lifecycle() {
+    let graph = TaskGraph::new();
+    let task = agent_task().with_correlation_id("chain-1");
+
+    graph.queued(&task);
+    let node = graph.get(task.id).unwrap();
+    assert_eq!(node.status, TaskNodeStatus::Queued);
+    assert_eq!(node.session_id, "chain-1");
+
+    graph.running(&task);
+    assert_eq!(graph.get(task.id).unwrap().status, TaskNodeStatus::Running);
+
+    graph.finished(task.id, &TaskOutcome::failed("boom"));
+    let node = graph.get(task.id).unwrap();
+    assert_eq!(node.status, TaskNodeStatus::Failed);
+    assert_eq!(node.error.as_deref(), Some("boom"));
+    assert!(node.started_at.is_some() && node.finished_at.is_some());
+    assert_eq!(graph.len(), 1);
+}
+
+#[test]
+fn test_children_and_root() {
+    let graph = TaskGraph::new();
+    let root = agent_task();
+    let child = Task::new("agent:subtask", serde_json::json!({})).with_parent(root.id);
+    let grandchild = Task::new("agent:delayed", serde_json::json!({})).with_parent(child.id);
+    for task in [&root, &child, &grandchild] {
+        graph.queued(task);
+    }
+
+    assert_eq!(graph.children(root.id).len(), 1);
+    assert_eq!(graph.children(child.id)[0].id, grandchild.id);
+    assert_eq!(graph.root_of(grandchild.id), Some(root.id));
+    assert_eq!(graph.root_of(root.id), Some(root.id));
+    assert_eq!(graph.root_of(Uuid::new_v4()), None);
+    assert_eq!(graph.root_of_session(&grandchild.session_id()), Some(root.id));
+    assert_eq!(graph.root_of_session("unknown"), None);
+
+    let other = agent_task();
+    graph.queued(&other);
+    let roots: Vec<Uuid> = graph.roots(10).iter().map(|node| node.id).collect();
+    assert_eq!(roots, vec![other.id, root.id]);
+    assert_eq!(graph.roots(1).len(), 1);
+}
+
+#[test]
+fn test_ignores_timer_tasks() {
+    let graph = TaskGraph::new();
+    graph.queued(&Task::new("timer:tick", serde_json::json!({})));
+    graph.queued(&Task::new("cron:nightly", serde_json::json!({})));
+    assert!(graph.is_empty());
+}
+
+#[test]
+fn test_capacity() {
+    let graph = TaskGraph::new();
+    let first = agent_task();
+    graph.queued(&first);
+    for _ in 0..TASK_GRAPH_CAPACITY {
+        graph.queued(&agent_task());
+    }
+    assert_eq!(graph.len(), TASK_GRAPH_CAPACITY);
+    assert!(graph.get(first.id).is_none());
+}
@@ -1,60 +1,218 @@
 //! Task queue with priority and delayed task support.
+//!
+//! Pending tasks are counted per [`TaskSource`](crate::task::TaskSource).
+//! When a source reaches its configured bound, or the whole queue reaches
+//! `max_pending_tasks`, the source's [`OverflowPolicy`] decides whether the
+//! new task is rejected, replaces the source's oldest task, or is spilled to
+//! disk until the source has room again.
+//!
+//! Ready tasks are dispatched weighted-fair across sources (see
+//! [`FairnessConfig`](crate::config::FairnessConfig)), and each dispatch
+//! records the task's queue wait in the source's metrics.
+//!
+//! A task the queue gives up on (dropped for a newer one, or failed to
+//! spill) ends as failed: its outcome is recorded, its callback gets
+//! `finished` and its node in the task graph is closed.
 
-use std::collections::BinaryHeap;
+use std::collections::{BinaryHeap, HashMap};
 use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
 use tokio::sync::RwLock;
-use tracing::debug;
+use tracing::{debug, warn};
 
-use crate::config::TaskQueueConfig;
+use crate::callback::CallbackDispatcher;
+use crate::config::{OverflowPolicy, TaskQueueConfig};
 use crate::error::{RunLoopError, RunLoopResult};
-use crate::task::{DelayedTask, PriorityTask, Task};
+use crate::fair_queue::FairQueue;
+use crate::journal::{JournalEventKind, JournalTask, RunLoopJournal};
+use crate::metrics::{QueueDepth, RunLoopMetrics};
+use crate::outcome::{TaskOutcome, TaskOutcomeLog};
+use crate::task::{DelayedTask, Task};
 use crate::task_chain::TaskChainTracker;
+use crate::task_graph::TaskGraph;
+use crate::task_spill::TaskSpill;
 
 /// Task queue with priority and delayed task support.
 pub struct TaskQueue {
     /// Configuration.
     config: TaskQueueConfig,
 
-    /// Immediate execution queue (priority sorted).
-    immediate: RwLock<BinaryHeap<PriorityTask>>,
+    /// Immediate execution queue (weighted-fair across sources).
+    immediate: RwLock<FairQueue>,
 
     /// Delayed tasks queue (by scheduled time).
     delayed: RwLock<BinaryHeap<DelayedTask>>,
 
+    /// Pending tasks per source.
+    depths: parking_lot::Mutex<HashMap<String, usize>>,
+
+    /// Tasks spilled to disk.
+    spill: Option<TaskSpill>,
+
+    /// Metrics receiving queue-depth gauges.
+    metrics: Option<Arc<RunLoopMetrics>>,
+
+    /// Journal recording queueing decisions.
+    journal: Option<Arc<RunLoopJournal>>,
+
+    /// Log receiving the outcome of tasks the queue gave up on.
+    outcome_log: Option<Arc<TaskOutcomeLog>>,
+
+    /// Dispatcher posting `finished` for tasks the queue gave up on.
+    callbacks: parking_lot::RwLock<Option<Arc<CallbackDispatcher>>>,
+
     /// Task chain tracker.
     chain_tracker: Arc<TaskChainTracker>,
+
+    /// Graph of the agent tasks that went through the queue.
+    task_graph: Arc<TaskGraph>,
 }
 
 impl TaskQueue {
     /// Create a new task queue.
     pub fn new(config: TaskQueueConfig, max_tasks_per_chain: u32) -> Self {
+        let spill = config.spill_dir.as_ref().and_then(|dir| match TaskSpill::open(dir) {
+            Ok(spill) => Some(spill),
+            Err(e) => {
+                warn!("Task spill disabled: {}", e);
+                None
+            }
+        });
         Self {
+            immediate: RwLock::new(FairQueue::new(config.fairness.clone())),
             config,
-            immediate: RwLock::new(BinaryHeap::new()),
             delayed: RwLock::new(BinaryHeap::new()),
+            depths: parking_lot::Mutex::new(HashMap::new()),
+            spill,
+            metrics: None,
+            journal: None,
+            outcome_log: None,
+            callbacks: parking_lot::RwLock::new(None),
             chain_tracker: Arc::new(TaskChainTracker::new(max_tasks_per_chain)),
+            task_graph: Arc::new(TaskGraph::new()),
+        }
+    }
+
+    /// Publish queue-depth gauges to metrics.
+    pub fn with_metrics(mut self, metrics: Arc<RunLoopMetrics>) -> Self {
+        if let Some(spill) = &self.spill {
+            for source in spill.sources() {
+                metrics.set_queue_depth(
+                    &source,
+                    QueueDepth {
+                        pending: 0,
+                        spilled: spill.len(&source) as u64,
+                    },
+                );
+            }
         }
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Record queueing decisions and promotions in a journal.
+    pub fn with_journal(mut self, journal: Arc<RunLoopJournal>) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
+    /// Record the outcome of tasks the queue gives up on.
+    pub fn with_outcome_log(mut self, outcome_log: Arc<TaskOutcomeLog>) -> Self {
+        self.outcome_log = Some(outcome_log);
+        self
+    }
+
+    /// Post `finished` to the callbacks of tasks the queue gives up on.
+    pub fn set_callbacks(&self, callbacks: Arc<CallbackDispatcher>) {
+        *self.callbacks.write() = Some(callbacks);
     }
 
     /// Enqueue a task.
+    ///
+    /// Fails with [`RunLoopError::QueueFull`] when the task's source is full
+    /// and its overflow policy is `reject`, or no task could be dropped or
+    /// spilled to make room.
     pub async fn enqueue(&self, task: Task) -> RunLoopResult<()> {
+        let Some(journal) = &self.journal else {
+            return self.enqueue_task(task).await;
+        };
+        let entry = JournalTask::of(&task);
+        let scheduled_at = task.scheduled_at.filter(|at| *at > Utc::now());
+        let result = self.enqueue_task(task).await;
+        journal.record(match &result {
+            Ok(()) => JournalEventKind::Queued {
+                task: entry,
+                scheduled_at,
+            },
+            Err(e) => JournalEventKind::Rejected {
+                task: entry,
+                reason: e.to_string(),
+            },
+        });
+        result
+    }
+
+    async fn enqueue_task(&self, task: Task) -> RunLoopResult<()> {
         // Check chain limit if correlation ID exists
         if let Some(ref correlation_id) = task.correlation_id {
             self.chain_tracker.try_produce(correlation_id)?;
         }
 
-        // Check queue size limit
-        let immediate_len = self.immediate.read().await.len();
-        let delayed_len = self.delayed.read().await.len();
-        if immediate_len + delayed_len >= self.config.max_pending_tasks {
-            return Err(RunLoopError::TaskProcessingError(
-                "Task queue is full".to_string(),
-            ));
+        let source = task.source.name().to_string();
+        let limit = self.config.source(&source);
+
+        // Keep the source's order while earlier tasks are still on disk
+        if limit.overflow == OverflowPolicy::SpillToDisk && self.spilled(&source) > 0 {
+            return self.spill(task, &source).await;
         }
 
-        // Route to appropriate queue
+        let mut delayed = self.delayed.write().await;
+        let mut immediate = self.immediate.write().await;
+
+        let source_full = limit.max_pending > 0 && self.depth(&source) >= limit.max_pending;
+        let queue_full = immediate.len() + delayed.len() >= self.config.max_pending_tasks;
+        if source_full || queue_full {
+            match limit.overflow {
+                OverflowPolicy::Reject => return Err(self.reject(&source)),
+                OverflowPolicy::DropOldest => {
+                    let Some(dropped) = drop_oldest(&mut immediate, &mut delayed, &source) else {
+                        return Err(self.reject(&source));
+                    };
+                    warn!("Queue full for source '{}', dropped task {}", source, dropped.id);
+                    if let Some(journal) = &self.journal {
+                        journal.record(JournalEventKind::Rejected {
+                            task: JournalTask::of(&dropped),
+                            reason: format!("Dropped for a newer task: queue full for source '{}'", source),
+                        });
+                    }
+                    self.adjust_depth(&source, -1);
+                    if let Some(metrics) = &self.metrics {
+                        metrics.record_task_dropped();
+                    }
+                    self.abandon(&dropped);
+                }
+                OverflowPolicy::SpillToDisk => {
+                    drop(immediate);
+                    drop(delayed);
+                    return self.spill(task, &source).await;
+                }
+            }
+        }
+
+        self.task_graph.queued(&task);
+        self.push(&mut immediate, &mut delayed, task);
+        self.adjust_depth(&source, 1);
+        Ok(())
+    }
+
+    /// Route a task to the immediate or delayed queue.
+    fn push(
+        &self,
+        immediate: &mut FairQueue,
+        delayed: &mut BinaryHeap<DelayedTask>,
+        task: Task,
+    ) {
         if let Some(scheduled_at) = task.scheduled_at {
             if scheduled_at > Utc::now() {
                 debug!(
@@ -62,12 +220,11 @@ impl TaskQueue {
                     task.id,
                     scheduled_at.to_rfc3339()
                 );
-                let mut delayed = self.delayed.write().await;
                 delayed.push(DelayedTask {
                     task,
                     scheduled_at,
                 });
-                return Ok(());
+                return;
             }
         }
 
@@ -75,23 +232,140 @@ impl TaskQueue {
             "Task {} enqueued (priority: {:?})",
             task.id, task.priority
         );
-        let mut immediate = self.immediate.write().await;
-        immediate.push(PriorityTask(task));
+        immediate.push(task);
+    }
 
+    /// Write a task to the spill directory.
+    async fn spill(&self, task: Task, source: &str) -> RunLoopResult<()> {
+        let Some(spill) = &self.spill else {
+            warn!("Queue full for source '{}' and no spill directory configured", source);
+            self.abandon(&task);
+            return Err(self.reject(source));
+        };
+        if let Err(e) = spill.push(&task).await {
+            warn!("Failed to spill task {}: {}", task.id, e);
+            self.abandon(&task);
+            return Err(self.reject(source));
+        }
+        debug!("Task {} spilled to {}", task.id, spill.dir().display());
+        self.task_graph.queued(&task);
+        if let Some(metrics) = &self.metrics {
+            metrics.record_task_spilled();
+        }
+        self.publish(source);
         Ok(())
     }
 
-    /// Dequeue the highest priority ready task.
+    /// Move spilled tasks back into the queue while their sources have room.
+    async fn restore_spilled(&self) {
+        let Some(spill) = &self.spill else {
+            return;
+        };
+        if spill.is_empty() {
+            return;
+        }
+        for source in spill.sources() {
+            let limit = self.config.source(&source);
+            loop {
+                let room = (limit.max_pending == 0 || self.depth(&source) < limit.max_pending)
+                    && self.len().await < self.config.max_pending_tasks;
+                if !room {
+                    break;
+                }
+                let task = match spill.pop(&source).await {
+                    Ok(Some(task)) => task,
+                    Ok(None) => break,
+                    Err(e) => {
+                        warn!("Failed to restore spilled task: {}", e);
+                        break;
+                    }
+                };
+                debug!("Task {} restored from spill", task.id);
+                let mut delayed = self.delayed.write().await;
+                let mut immediate = self.immediate.write().await;
+                self.push(&mut immediate, &mut delayed, task);
+                self.adjust_depth(&source, 1);
+            }
+        }
+    }
+
+    /// End a task the queue gave up on as failed, so whoever waits for its
+    /// outcome or callback is not left waiting.
+    fn abandon(&self, task: &Task) {
+        let outcome = TaskOutcome::failed("dropped: queue full");
+        if let Some(outcome_log) = &self.outcome_log {
+            outcome_log.record(task.id, outcome.clone());
+        }
+        if let Some(callbacks) = self.callbacks.read().clone() {
+            callbacks.abandoned(task, &outcome);
+        }
+        self.task_graph.finished(task.id, &outcome);
+    }
+
+    /// Count a rejected task and build its error.
+    fn reject(&self, source: &str) -> RunLoopError {
+        if let Some(metrics) = &self.metrics {
+            metrics.record_task_rejected();
+        }
+        RunLoopError::QueueFull(source.to_string())
+    }
+
+    /// Pending in-memory tasks of a source.
+    pub fn depth(&self, source: &str) -> usize {
+        self.depths.lock().get(source).copied().unwrap_or(0)
+    }
+
+    /// Spilled tasks of a source.
+    pub fn spilled(&self, source: &str) -> usize {
+        self.spill.as_ref().map_or(0, |spill| spill.len(source))
+    }
+
+    /// Change a source's depth and publish it.
+    fn adjust_depth(&self, source: &str, delta: isize) {
+        {
+            let mut depths = self.depths.lock();
+            let depth = depths.entry(source.to_string()).or_default();
+            *depth = depth.saturating_add_signed(delta);
+            if *depth == 0 {
+                depths.remove(source);
+            }
+        }
+        self.publish(source);
+    }
+
+    /// Publish a source's depth gauge.
+    fn publish(&self, source: &str) {
+        if let Some(metrics) = &self.metrics {
+            metrics.set_queue_depth(
+                source,
+                QueueDepth {
+                    pending: self.depth(source) as u64,
+                    spilled: self.spilled(source) as u64,
+                },
+            );
+        }
+    }
+
+    /// Dequeue the next ready task: Critical and System tasks first, then
+    /// weighted-fair across sources.
     pub async fn dequeue(&self) -> Option<Task> {
-        let mut immediate = self.immediate.write().await;
-        immediate.pop().map(|pt| {
-            debug!("Task {} dequeued", pt.0.id);
-            pt.0
-        })
+        let task = self.immediate.write().await.pop()?;
+        debug!("Task {} dequeued", task.id);
+        self.adjust_depth(task.source.name(), -1);
+        if let Some(metrics) = &self.metrics {
+            // Delayed tasks wait from their scheduled time
+            let ready_at = task.scheduled_at.unwrap_or(task.created_at).max(task.created_at);
+            let wait_ms = (Utc::now() - ready_at).num_milliseconds().max(0) as u64;
+            metrics.record_dispatch(task.source.name(), wait_ms);
+        }
+        Some(task)
     }
 
-    /// Promote delayed tasks that are now due.
+    /// Promote delayed tasks that are now due, and restore spilled tasks
+    /// whose sources have room again.
     pub async fn promote_delayed(&self) {
+        self.restore_spilled().await;
+
         let now = Utc::now();
         let mut delayed = self.delayed.write().await;
         let mut immediate = self.immediate.write().await;
@@ -104,7 +378,13 @@ impl TaskQueue {
                     entry.task.id,
                     entry.scheduled_at.to_rfc3339()
                 );
-                immediate.push(PriorityTask(entry.task));
+                if let Some(journal) = &self.journal {
+                    journal.record(JournalEventKind::Promoted {
+                        task: JournalTask::of(&entry.task),
+                        scheduled_at: entry.scheduled_at,
+                    });
+                }
+                immediate.push(entry.task);
             } else {
                 break;
             }
@@ -141,9 +421,39 @@ impl TaskQueue {
         &self.chain_tracker
     }
 
+    /// Get the graph of queued agent tasks.
+    pub fn task_graph(&self) -> &Arc<TaskGraph> {
+        &self.task_graph
+    }
+
     /// Clear all tasks.
     pub async fn clear(&self) {
         self.immediate.write().await.clear();
         self.delayed.write().await.clear();
+        self.depths.lock().clear();
+        if let Some(metrics) = &self.metrics {
+            metrics.clear_queue_depths();
+        }
     }
 }
+
+/// Remove the oldest pending task of a source.
+fn drop_oldest(
+    immediate: &mut FairQueue,
+    delayed: &mut BinaryHeap<DelayedTask>,
+    source: &str,
+) -> Option<Task> {
+    let oldest = immediate
+        .iter()
+        .chain(delayed.iter().map(|dt| &dt.task))
+        .filter(|task| task.source.name() == source)
+        .min_by_key(|task| task.created_at)?
+        .clone();
+    immediate.remove(&oldest);
+    delayed.retain(|dt| dt.task.id != oldest.id);
+    Some(oldest)
+}
+
+#[cfg(test)]
+#[path = "task_queue_tests.rs"]
+mod tests;
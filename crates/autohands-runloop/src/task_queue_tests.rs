@@ -0,0 +1,176 @@
+use super::*;
+use crate::config::SourceQueueConfig;
+use crate::task::TaskSource;
+use crate::task_graph::TaskNodeStatus;
+
+fn webhook_task(name: &str) -> Task {
+    Task::new(name, serde_json::Value::Null).with_source(TaskSource::Webhook)
+}
+
+fn bounded(max_pending: usize, overflow: OverflowPolicy) -> TaskQueueConfig {
+    let mut config = TaskQueueConfig::default();
+    config.sources.insert(
+        "webhook".to_string(),
+        SourceQueueConfig {
+            max_pending,
+            overflow,
+        },
+    );
+    config
+}
+
+#[tokio::test]
+async fn test_reject_when_source_full() {
+    let metrics = Arc::new(RunLoopMetrics::new());
+    let queue = TaskQueue::new(bounded(2, OverflowPolicy::Reject), 100)
+        .with_metrics(metrics.clone());
+
+    queue.enqueue(webhook_task("a")).await.unwrap();
+    queue.enqueue(webhook_task("b")).await.unwrap();
+    let err = queue.enqueue(webhook_task("c")).await.unwrap_err();
+    assert!(matches!(err, RunLoopError::QueueFull(ref source) if source == "webhook"));
+
+    // Other sources are not affected by the webhook bound
+    queue
+        .enqueue(Task::new("user", serde_json::Value::Null))
+        .await
+        .unwrap();
+
+    assert_eq!(queue.depth("webhook"), 2);
+    assert_eq!(metrics.queue_depths()["webhook"].pending, 2);
+    assert_eq!(metrics.queue_depths()["user"].pending, 1);
+    assert_eq!(metrics.snapshot().tasks_rejected, 1);
+}
+
+#[tokio::test]
+async fn test_drop_oldest() {
+    let metrics = Arc::new(RunLoopMetrics::new());
+    let outcomes = Arc::new(TaskOutcomeLog::new());
+    let queue = TaskQueue::new(bounded(2, OverflowPolicy::DropOldest), 100)
+        .with_metrics(metrics.clone())
+        .with_outcome_log(outcomes.clone());
+
+    let oldest = webhook_task("a");
+    queue.enqueue(oldest.clone()).await.unwrap();
+    for name in ["b", "c"] {
+        queue.enqueue(webhook_task(name)).await.unwrap();
+    }
+
+    // The dropped task ends as failed
+    let outcome = outcomes.get(oldest.id).unwrap();
+    assert_eq!(outcome.error.as_deref(), Some("dropped: queue full"));
+    let node = queue.task_graph().get(oldest.id).unwrap();
+    assert_eq!(node.status, TaskNodeStatus::Failed);
+
+    assert_eq!(queue.len().await, 2);
+    let mut names = vec![
+        queue.dequeue().await.unwrap().task_type,
+        queue.dequeue().await.unwrap().task_type,
+    ];
+    names.sort();
+    assert_eq!(names, vec!["b", "c"]);
+    assert_eq!(metrics.snapshot().tasks_dropped, 1);
+    assert!(metrics.queue_depths().is_empty());
+}
+
+#[tokio::test]
+async fn test_global_limit_applies_source_policy() {
+    let mut config = bounded(0, OverflowPolicy::Reject);
+    config.max_pending_tasks = 1;
+    let queue = TaskQueue::new(config, 100);
+
+    queue.enqueue(webhook_task("a")).await.unwrap();
+    assert!(queue.enqueue(webhook_task("b")).await.is_err());
+}
+
+#[tokio::test]
+async fn test_spill_to_disk_and_restore() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut config = bounded(1, OverflowPolicy::SpillToDisk);
+    config.spill_dir = Some(dir.path().display().to_string());
+    let metrics = Arc::new(RunLoopMetrics::new());
+    let queue = TaskQueue::new(config.clone(), 100).with_metrics(metrics.clone());
+
+    for name in ["a", "b", "c"] {
+        queue.enqueue(webhook_task(name)).await.unwrap();
+    }
+    assert_eq!(queue.len().await, 1);
+    assert_eq!(queue.spilled("webhook"), 2);
+    assert_eq!(
+        metrics.queue_depths()["webhook"],
+        QueueDepth {
+            pending: 1,
+            spilled: 2
+        }
+    );
+
+    // No room yet: nothing is restored
+    queue.promote_delayed().await;
+    assert_eq!(queue.len().await, 1);
+
+    assert_eq!(queue.dequeue().await.unwrap().task_type, "a");
+    queue.promote_delayed().await;
+    assert_eq!(queue.dequeue().await.unwrap().task_type, "b");
+    assert_eq!(queue.spilled("webhook"), 1);
+
+    // Spilled tasks survive a restart
+    drop(queue);
+    let queue = TaskQueue::new(config, 100);
+    assert_eq!(queue.spilled("webhook"), 1);
+    queue.promote_delayed().await;
+    assert_eq!(queue.dequeue().await.unwrap().task_type, "c");
+    assert_eq!(queue.spilled("webhook"), 0);
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+}
+
+#[tokio::test]
+async fn test_spill_without_directory_rejects() {
+    let outcomes = Arc::new(TaskOutcomeLog::new());
+    let queue = TaskQueue::new(bounded(1, OverflowPolicy::SpillToDisk), 100)
+        .with_outcome_log(outcomes.clone());
+    queue.enqueue(webhook_task("a")).await.unwrap();
+    let task = webhook_task("b");
+    assert!(matches!(
+        queue.enqueue(task.clone()).await,
+        Err(RunLoopError::QueueFull(_))
+    ));
+    assert!(!outcomes.get(task.id).unwrap().success);
+}
+
+#[tokio::test]
+async fn test_fair_dequeue_records_source_stats() {
+    let metrics = Arc::new(RunLoopMetrics::new());
+    let queue = TaskQueue::new(TaskQueueConfig::default(), 100).with_metrics(metrics.clone());
+
+    for name in ["a", "b", "c"] {
+        queue
+            .enqueue(Task::new(name, serde_json::Value::Null).with_source(TaskSource::FileWatcher))
+            .await
+            .unwrap();
+    }
+    queue.enqueue(webhook_task("hook")).await.unwrap();
+
+    // The webhook is served before the file watcher's backlog drains
+    assert_eq!(queue.dequeue().await.unwrap().task_type, "a");
+    assert_eq!(queue.dequeue().await.unwrap().task_type, "hook");
+
+    let stats = metrics.source_stats();
+    assert_eq!(stats["file_watcher"].dispatched, 1);
+    assert_eq!(stats["webhook"].dispatched, 1);
+}
+
+#[tokio::test]
+async fn test_enqueued_tasks_join_the_graph() {
+    let queue = TaskQueue::new(bounded(1, OverflowPolicy::Reject), 100);
+    let parent = webhook_task("agent:execute");
+    let child = Task::new("agent:subtask", serde_json::Value::Null).with_parent(parent.id);
+
+    queue.enqueue(parent.clone()).await.unwrap();
+    queue.enqueue(child.clone()).await.unwrap();
+    // Rejected tasks are not recorded
+    assert!(queue.enqueue(webhook_task("agent:execute")).await.is_err());
+
+    let graph = queue.task_graph();
+    assert_eq!(graph.len(), 2);
+    assert_eq!(graph.children(parent.id)[0].id, child.id);
+}
@@ -0,0 +1,130 @@
+//! Disk spill for tasks that overflow a full queue.
+//!
+//! Each spilled task is one JSON file in the spill directory, named so that
+//! files sort in spill order. Tasks left over from a previous run are picked
+//! up again when the spill is opened.
+
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use parking_lot::Mutex;
+use tracing::warn;
+
+use crate::error::{RunLoopError, RunLoopResult};
+use crate::task::Task;
+
+/// Tasks spilled to disk, in spill order per source.
+pub struct TaskSpill {
+    dir: PathBuf,
+    files: Mutex<HashMap<String, VecDeque<PathBuf>>>,
+}
+
+impl TaskSpill {
+    /// Open a spill directory, creating it if needed.
+    pub fn open(dir: impl Into<PathBuf>) -> RunLoopResult<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir).map_err(|e| spill_error(&dir, e))?;
+
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(&dir)
+            .map_err(|e| spill_error(&dir, e))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        paths.sort();
+
+        let mut files: HashMap<String, VecDeque<PathBuf>> = HashMap::new();
+        for path in paths {
+            match read_task(&path) {
+                Ok(task) => files
+                    .entry(task.source.name().to_string())
+                    .or_default()
+                    .push_back(path),
+                Err(e) => warn!("Skipping unreadable spilled task {}: {}", path.display(), e),
+            }
+        }
+
+        Ok(Self {
+            dir,
+            files: Mutex::new(files),
+        })
+    }
+
+    /// Spill directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Write a task to disk.
+    pub async fn push(&self, task: &Task) -> RunLoopResult<()> {
+        let name = format!(
+            "{:020}-{}.json",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default(),
+            task.id
+        );
+        let path = self.dir.join(name);
+        let data = serde_json::to_vec(task)?;
+        tokio::fs::write(&path, data)
+            .await
+            .map_err(|e| spill_error(&path, e))?;
+        self.files
+            .lock()
+            .entry(task.source.name().to_string())
+            .or_default()
+            .push_back(path);
+        Ok(())
+    }
+
+    /// Take the oldest spilled task of a source off disk.
+    pub async fn pop(&self, source: &str) -> RunLoopResult<Option<Task>> {
+        loop {
+            let Some(path) = self.files.lock().get_mut(source).and_then(|f| f.pop_front()) else {
+                return Ok(None);
+            };
+            let task = tokio::fs::read(&path)
+                .await
+                .map_err(|e| spill_error(&path, e))
+                .and_then(|data| Ok(serde_json::from_slice::<Task>(&data)?));
+            if let Err(e) = tokio::fs::remove_file(&path).await {
+                warn!("Failed to remove spilled task {}: {}", path.display(), e);
+            }
+            match task {
+                Ok(task) => return Ok(Some(task)),
+                Err(e) => warn!("Dropping unreadable spilled task {}: {}", path.display(), e),
+            }
+        }
+    }
+
+    /// Number of spilled tasks of a source.
+    pub fn len(&self, source: &str) -> usize {
+        self.files.lock().get(source).map_or(0, VecDeque::len)
+    }
+
+    /// Sources with spilled tasks.
+    pub fn sources(&self) -> Vec<String> {
+        self.files
+            .lock()
+            .iter()
+            .filter(|(_, files)| !files.is_empty())
+            .map(|(source, _)| source.clone())
+            .collect()
+    }
+
+    /// Whether no task is spilled.
+    pub fn is_empty(&self) -> bool {
+        self.files.lock().values().all(VecDeque::is_empty)
+    }
+}
+
+fn read_task(path: &Path) -> RunLoopResult<Task> {
+    let data = std::fs::read(path).map_err(|e| spill_error(path, e))?;
+    Ok(serde_json::from_slice(&data)?)
+}
+
+fn spill_error(path: &Path, e: std::io::Error) -> RunLoopError {
+    RunLoopError::Internal(format!("Task spill {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+#[path = "task_spill_tests.rs"]
+mod tests;
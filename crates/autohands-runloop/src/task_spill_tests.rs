@@ -0,0 +1,45 @@
+use super::*;
+use crate::task::TaskSource;
+
+#[tokio::test]
+async fn test_push_and_pop_in_order() {
+    let dir = tempfile::tempdir().unwrap();
+    let spill = TaskSpill::open(dir.path()).unwrap();
+    assert!(spill.is_empty());
+
+    for name in ["a", "b"] {
+        let task = Task::new(name, serde_json::Value::Null).with_source(TaskSource::Webhook);
+        spill.push(&task).await.unwrap();
+    }
+    spill
+        .push(&Task::new("user", serde_json::Value::Null))
+        .await
+        .unwrap();
+
+    assert_eq!(spill.len("webhook"), 2);
+    assert_eq!(spill.len("user"), 1);
+    let mut sources = spill.sources();
+    sources.sort();
+    assert_eq!(sources, vec!["user", "webhook"]);
+
+    assert_eq!(spill.pop("webhook").await.unwrap().unwrap().task_type, "a");
+    assert_eq!(spill.pop("webhook").await.unwrap().unwrap().task_type, "b");
+    assert!(spill.pop("webhook").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_reopen_skips_unreadable_files() {
+    let dir = tempfile::tempdir().unwrap();
+    {
+        let spill = TaskSpill::open(dir.path()).unwrap();
+        spill
+            .push(&Task::new("kept", serde_json::Value::Null))
+            .await
+            .unwrap();
+    }
+    std::fs::write(dir.path().join("0-broken.json"), "not json").unwrap();
+
+    let spill = TaskSpill::open(dir.path()).unwrap();
+    assert_eq!(spill.len("user"), 1);
+    assert_eq!(spill.pop("user").await.unwrap().unwrap().task_type, "kept");
+}
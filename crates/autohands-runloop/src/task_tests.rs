@@ -146,6 +146,77 @@ fn test_ensure_correlation_id() {
     assert_eq!(id1, id2);
 }
 
+#[test]
+fn test_task_trace_id() {
+    let task = Task::new("test", serde_json::Value::Null);
+    assert_eq!(task.trace_id(), task.id.to_string());
+
+    let task = task.with_correlation_id("chain-1");
+    assert_eq!(task.trace_id(), "chain-1");
+}
+
+#[test]
+fn test_inherit_from() {
+    let soon = Utc::now() + chrono::Duration::minutes(1);
+    let later = soon + chrono::Duration::minutes(10);
+    let parent = Task::new("parent", serde_json::Value::Null)
+        .with_priority(TaskPriority::Critical)
+        .with_correlation_id("chain-1")
+        .with_deadline(soon);
+
+    // Priority up to high and the earlier deadline are inherited
+    let child = Task::new("child", serde_json::Value::Null)
+        .with_deadline(later)
+        .inherit_from(&parent);
+    assert_eq!(child.parent_id, Some(parent.id));
+    assert_eq!(child.correlation_id.as_deref(), Some("chain-1"));
+    assert_eq!(child.priority, TaskPriority::High);
+    assert_eq!(child.deadline, Some(soon));
+
+    // A child's own higher priority is kept
+    let low_parent = Task::new("parent", serde_json::Value::Null).with_priority(TaskPriority::Low);
+    let child = Task::new("child", serde_json::Value::Null)
+        .with_priority(TaskPriority::High)
+        .inherit_from(&low_parent);
+    assert_eq!(child.priority, TaskPriority::High);
+    assert_eq!(child.deadline, None);
+}
+
+#[test]
+fn test_dry_run() {
+    let parent = Task::new("agent:execute", serde_json::json!({"prompt": "clean up"}));
+    assert!(!parent.is_dry_run());
+    let parent = parent.with_dry_run();
+    assert!(parent.is_dry_run());
+    assert_eq!(parent.payload["prompt"], "clean up");
+
+    // Follow-up work of a dry run is simulated too
+    let child =
+        Task::new("agent:subtask", serde_json::json!({"prompt": "next"})).inherit_from(&parent);
+    assert!(child.is_dry_run());
+}
+
+#[test]
+fn test_payload_scheduling() {
+    let deadline = Utc::now() + chrono::Duration::minutes(1);
+    let parent = Task::new("parent", serde_json::Value::Null)
+        .with_priority(TaskPriority::High)
+        .with_deadline(deadline);
+    let scheduling = parent.scheduling();
+    assert_eq!(scheduling.priority.as_deref(), Some("high"));
+
+    let mut payload = serde_json::json!({"prompt": "follow up"});
+    scheduling.apply_to(&mut payload);
+    let task = Task::new("agent:execute", payload).with_payload_scheduling();
+    assert_eq!(task.priority, TaskPriority::High);
+    assert_eq!(task.deadline, Some(deadline));
+
+    // Payloads cannot ask for system priority
+    let task = Task::new("agent:execute", serde_json::json!({"priority": "system"}))
+        .with_payload_scheduling();
+    assert_eq!(task.priority, TaskPriority::Critical);
+}
+
 #[test]
 fn test_task_with_reply_to() {
     let reply_to = ReplyAddress::new("web", "conn-123");
@@ -202,3 +273,25 @@ fn test_task_reply_to_deserialization() {
     assert_eq!(reply_to.channel_id, "web");
     assert_eq!(reply_to.target, "conn-123");
 }
+
+#[test]
+fn test_delivery_targets() {
+    use crate::delivery::{DeliveryFormat, DeliveryTarget};
+
+    let reply_to = ReplyAddress::new("web", "conn-1");
+    let task = Task::new("agent:execute", serde_json::Value::Null)
+        .with_reply_to(reply_to.clone())
+        .with_delivery_target(DeliveryTarget::new("slack", "#reports"));
+    let targets = task.delivery_targets();
+    assert_eq!(targets.len(), 2);
+    assert_eq!(targets[0].address, reply_to);
+    assert_eq!(targets[1].address.channel_id, "slack");
+
+    // Listing the reply address explicitly overrides its default format
+    let task = task.with_delivery_target(
+        DeliveryTarget::from(reply_to.clone()).with_format(DeliveryFormat::Plain),
+    );
+    let targets = task.delivery_targets();
+    assert_eq!(targets.len(), 2);
+    assert_eq!(targets[1].format, DeliveryFormat::Plain);
+}
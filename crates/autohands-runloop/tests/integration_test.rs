@@ -14,7 +14,8 @@ use autohands_runloop::{
     AgentEventHandler, AgentTaskInjector, AgentSource0, AgentResult,
     TaskPriority, TaskQueue, TaskQueueConfig, TaskSource, HttpTaskInjector,
     RunLoop, RunLoopConfig, Task, RunLoopMode, RunLoopObserver, RunLoopPhase,
-    RunLoopResult, RunLoopRunResult, Source0, TimerBuilder, WakeupSignal,
+    RunLoopResult, RunLoopRunResult, Source0, TimerBuilder, WakeupSignal, ModeSwitchReason,
+    ModeTransition,
 };
 
 // ============================================================================
@@ -186,6 +187,7 @@ async fn test_runloop_handler_event_processing() {
         .with_source(TaskSource::Timer)
         .with_priority(TaskPriority::Low);
 
+    let execute_id = execute_event.id;
     run_loop.inject_task(execute_event).await.unwrap();
     run_loop.inject_task(subtask_event).await.unwrap();
     run_loop.inject_task(delayed_event).await.unwrap();
@@ -207,6 +209,46 @@ async fn test_runloop_handler_event_processing() {
     assert_eq!(execute_count.load(Ordering::SeqCst), 1);
     assert_eq!(subtask_count.load(Ordering::SeqCst), 1);
     assert_eq!(delayed_count.load(Ordering::SeqCst), 1);
+
+    // Finished agent tasks record their outcome
+    let outcome = run_loop.outcome_log().get(execute_id).unwrap();
+    assert!(outcome.success);
+    assert_eq!(outcome.response.as_deref(), Some("Executed: test task"));
+}
+
+/// Test: Agent tasks are traced under their correlation ID.
+#[tokio::test]
+async fn test_agent_task_traced() {
+    use autohands_runtime::TraceEventKind;
+
+    let run_loop = Arc::new(RunLoop::default());
+    let (handler, _, _, _) = TestEventHandler::new();
+    run_loop.set_handler(Arc::new(handler)).await;
+
+    let task = Task::new("agent:execute", json!({"prompt": "traced"}))
+        .with_correlation_id("trace-42");
+    let task_id = task.id;
+    run_loop.inject_task(task).await.unwrap();
+
+    let run_loop_clone = run_loop.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        run_loop_clone.stop();
+    });
+    run_loop
+        .run_in_mode(RunLoopMode::Default, Duration::from_secs(5))
+        .await
+        .unwrap();
+
+    let trace = run_loop.trace_log().get("trace-42").unwrap();
+    assert_eq!(trace.task_ids, vec![task_id]);
+    let kinds: Vec<_> = trace.events.iter().map(|e| e.kind).collect();
+    assert_eq!(kinds, vec![TraceEventKind::TaskStarted, TraceEventKind::TaskFinished]);
+    assert!(!trace.events[1].failed);
+
+    // The trace can be looked up by task ID too
+    let by_task = run_loop.trace_log().get(&task_id.to_string()).unwrap();
+    assert_eq!(by_task.correlation_id, "trace-42");
 }
 
 /// Test: Event injection via AgentSource0 (self-driving).
@@ -446,6 +488,125 @@ async fn test_mode_isolation() {
     assert_eq!(entry_count.load(Ordering::SeqCst), 1);
 }
 
+/// Handler whose agent runs until released.
+struct BlockingHandler {
+    release: Arc<tokio::sync::Notify>,
+}
+
+#[async_trait]
+impl AgentEventHandler for BlockingHandler {
+    async fn handle_execute(
+        &self,
+        _event: &Task,
+        _injector: &AgentTaskInjector,
+    ) -> RunLoopResult<AgentResult> {
+        self.release.notified().await;
+        Ok(AgentResult::completed("done"))
+    }
+
+    async fn handle_subtask(
+        &self,
+        _event: &Task,
+        _injector: &AgentTaskInjector,
+    ) -> RunLoopResult<AgentResult> {
+        Ok(AgentResult::completed("done"))
+    }
+
+    async fn handle_delayed(
+        &self,
+        _event: &Task,
+        _injector: &AgentTaskInjector,
+    ) -> RunLoopResult<AgentResult> {
+        Ok(AgentResult::completed("done"))
+    }
+}
+
+/// Observer recording mode transitions.
+struct TransitionObserver {
+    transitions: Arc<std::sync::Mutex<Vec<ModeTransition>>>,
+}
+
+#[async_trait]
+impl RunLoopObserver for TransitionObserver {
+    fn activities(&self) -> u32 {
+        0
+    }
+
+    async fn on_phase(&self, _phase: RunLoopPhase, _run_loop: &RunLoop) {}
+
+    async fn on_mode_change(&self, transition: &ModeTransition, _run_loop: &RunLoop) {
+        self.transitions.lock().unwrap().push(transition.clone());
+    }
+}
+
+/// Test: The mode policy enters AgentProcessing mode while an agent runs.
+#[tokio::test]
+async fn test_mode_auto_switching() {
+    let mut config = RunLoopConfig::default();
+    config.mode_policy.enabled = true;
+    let run_loop = Arc::new(RunLoop::new(config));
+    let release = Arc::new(tokio::sync::Notify::new());
+    run_loop
+        .set_handler(Arc::new(BlockingHandler {
+            release: release.clone(),
+        }))
+        .await;
+
+    let transitions = Arc::new(std::sync::Mutex::new(Vec::new()));
+    run_loop
+        .add_observer(
+            "transitions",
+            Arc::new(TransitionObserver {
+                transitions: transitions.clone(),
+            }),
+        )
+        .await;
+
+    // Default-mode observers are deferred while the agent runs
+    let (observer, _, _, before_waiting) = TestObserver::new();
+    run_loop
+        .add_mode_observer(&RunLoopMode::Default, "default-only", Arc::new(observer))
+        .await;
+
+    run_loop
+        .inject_task(Task::new("agent:execute", json!({"prompt": "wait"})))
+        .await
+        .unwrap();
+
+    let run_loop_clone = run_loop.clone();
+    let check = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let mode = run_loop_clone.current_mode().await;
+        let agents = run_loop_clone.active_agent_tasks();
+        let deferred = before_waiting.load(Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let still_deferred = before_waiting.load(Ordering::SeqCst) == deferred;
+        release.notify_one();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let after = run_loop_clone.current_mode().await;
+        run_loop_clone.stop();
+        (mode, agents, still_deferred, after)
+    });
+
+    run_loop
+        .run_in_mode(RunLoopMode::Default, Duration::from_secs(2))
+        .await
+        .unwrap();
+
+    let (mode, agents, still_deferred, after) = check.await.unwrap();
+    assert_eq!(mode, RunLoopMode::AgentProcessing);
+    assert_eq!(agents, 1);
+    assert!(still_deferred);
+    assert_eq!(after, RunLoopMode::Default);
+
+    let transitions = transitions.lock().unwrap();
+    assert_eq!(transitions.len(), 2);
+    assert_eq!(transitions[0].to, RunLoopMode::AgentProcessing);
+    assert_eq!(transitions[0].reason, ModeSwitchReason::AgentActive);
+    assert_eq!(transitions[1].to, RunLoopMode::Default);
+    assert_eq!(transitions[1].reason, ModeSwitchReason::AgentsFinished);
+}
+
 /// Test: Metrics are collected correctly.
 #[tokio::test]
 async fn test_metrics_collection() {
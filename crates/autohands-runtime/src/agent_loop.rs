@@ -3,19 +3,32 @@
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use tracing::{debug, info, warn};
+use chrono::Utc;
+use tracing::{debug, field, info, info_span, warn, Instrument};
 
+use autohands_core::audit::{AuditEntry, AuditKind, AuditLog};
+use autohands_core::redact::Redactor;
 use autohands_core::registry::{ProviderRegistry, ToolRegistry};
-use autohands_protocols::agent::{Agent, AgentContext};
+use autohands_protocols::agent::{Agent, AgentContext, AgentResponse, RunOverrides};
+use autohands_protocols::approval::{
+    ApprovalGate, ApprovalRequest, ApprovalStatus, ToolApprovalAction, ToolApprovalPolicy,
+};
 use autohands_protocols::error::AgentError;
 use autohands_protocols::memory::{MemoryBackend, MemoryQuery};
-use autohands_protocols::tool::ToolContext;
-use autohands_protocols::types::Message;
+use autohands_protocols::tool::{ToolOutputChunk, ToolOutputSink};
+use autohands_protocols::types::{Message, RiskLevel};
 
 use crate::checkpoint::CheckpointSupport;
+use crate::feature_flags::{FeatureFlags, PARALLEL_TOOLS};
 use crate::memory_persistence;
+use crate::progress::ProgressTracker;
+use crate::project::ProjectDetector;
+use crate::streaming::StreamEvent;
 use crate::summarizer::HistoryCompressor;
+use crate::tool_pipeline::{ToolOutcome, ToolPipeline};
+use crate::trace::{TraceEvent, TraceEventKind, TraceLog};
 use crate::transcript::TranscriptWriter;
+use crate::turn_observer::TurnObserver;
 
 /// Configuration for the agent loop.
 #[derive(Debug, Clone)]
@@ -24,6 +37,9 @@ pub struct AgentLoopConfig {
     pub checkpoint_enabled: bool,
     /// 工具输出最大字符数，超出则截断并附加提示。0 表示不限制。
     pub max_tool_output_chars: usize,
+    /// Long-context model a run switches to when its context overflows,
+    /// before the history is compressed.
+    pub context_fallback_model: Option<String>,
 }
 
 impl Default for AgentLoopConfig {
@@ -31,6 +47,7 @@ impl Default for AgentLoopConfig {
         Self {
             checkpoint_enabled: false,
             max_tool_output_chars: 100_000, // ~25K tokens
+            context_fallback_model: None,
         }
     }
 }
@@ -43,14 +60,77 @@ impl AgentLoopConfig {
     }
 }
 
+/// Human approval for risky tool calls.
+#[derive(Clone)]
+pub struct ToolApproval {
+    /// Gate that decides tool calls.
+    pub gate: Arc<dyn ApprovalGate>,
+    /// Which tool calls run, need approval or are refused.
+    pub policy: ToolApprovalPolicy,
+}
+
+impl ToolApproval {
+    /// Apply the policy to a tool call, asking for approval if it says so.
+    ///
+    /// Returns why the call was refused, or `None` if it may run.
+    pub async fn check(
+        &self,
+        tool_id: &str,
+        risk_level: RiskLevel,
+        arguments: &serde_json::Value,
+        session_id: &str,
+    ) -> Option<String> {
+        match self.policy.action(tool_id, risk_level) {
+            ToolApprovalAction::Allow => return None,
+            ToolApprovalAction::Deny => {
+                info!("Tool call {} refused by policy", tool_id);
+                return Some(format!(
+                    "Tool '{}' was not run: denied by the approval policy",
+                    tool_id
+                ));
+            }
+            ToolApprovalAction::Prompt => {}
+        }
+
+        let request = ApprovalRequest::new(
+            format!("Run tool '{}'", tool_id),
+            format!("tool:{}", tool_id),
+        )
+        .with_session(session_id)
+        .with_risk_level(risk_level)
+        .with_details(serde_json::json!({
+            "tool": tool_id,
+            "arguments": arguments,
+        }));
+        let decision = self.gate.request_approval(request).await;
+        if decision.is_approved() {
+            return None;
+        }
+
+        let outcome = match decision.status {
+            ApprovalStatus::TimedOut => "approval timed out",
+            _ => "rejected by reviewer",
+        };
+        info!("Tool call {} refused: {}", tool_id, outcome);
+        Some(match decision.comment {
+            Some(comment) => format!("Tool '{}' was not run: {} ({})", tool_id, outcome, comment),
+            None => format!("Tool '{}' was not run: {}", tool_id, outcome),
+        })
+    }
+}
+
 /// The agentic loop executor.
 pub struct AgentLoop {
-    tool_registry: Arc<ToolRegistry>,
+    tools: ToolPipeline,
     config: AgentLoopConfig,
     checkpoint: Option<Arc<dyn CheckpointSupport>>,
     transcript: Option<Arc<TranscriptWriter>>,
     compressor: Option<Arc<HistoryCompressor>>,
     memory_backend: Option<Arc<dyn MemoryBackend>>,
+    turn_observer: Option<Arc<dyn TurnObserver>>,
+    progress: Option<Arc<ProgressTracker>>,
+    trace_log: Option<Arc<TraceLog>>,
+    project_detector: Option<Arc<ProjectDetector>>,
 }
 
 impl AgentLoop {
@@ -60,12 +140,16 @@ impl AgentLoop {
         config: AgentLoopConfig,
     ) -> Self {
         Self {
-            tool_registry,
+            tools: ToolPipeline::new(tool_registry, config.max_tool_output_chars),
             config,
             checkpoint: None,
             transcript: None,
             compressor: None,
             memory_backend: None,
+            turn_observer: None,
+            progress: None,
+            trace_log: None,
+            project_detector: None,
         }
     }
 
@@ -93,11 +177,62 @@ impl AgentLoop {
         self
     }
 
+    /// Require human approval for risky tool calls.
+    pub fn with_approval(mut self, approval: ToolApproval) -> Self {
+        self.tools.approval = Some(approval);
+        self
+    }
+
+    /// Notify an observer after every turn.
+    pub fn with_turn_observer(mut self, observer: Arc<dyn TurnObserver>) -> Self {
+        self.turn_observer = Some(observer);
+        self
+    }
+
+    /// Track session progress.
+    pub fn with_progress(mut self, progress: Arc<ProgressTracker>) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Record turns, provider calls and tools in a trace log, under the
+    /// context's correlation ID.
+    pub fn with_trace_log(mut self, trace_log: Arc<TraceLog>) -> Self {
+        self.trace_log = Some(trace_log);
+        self
+    }
+
+    /// Gate parallel tool calls and auto-approval with feature flags,
+    /// evaluated for the context's working directory.
+    pub fn with_feature_flags(mut self, flags: Arc<FeatureFlags>) -> Self {
+        self.tools.feature_flags = Some(flags);
+        self
+    }
+
     /// Get the transcript writer (for passing to agent executor).
     pub fn transcript(&self) -> Option<Arc<TranscriptWriter>> {
         self.transcript.clone()
     }
 
+    /// Brief new sessions on the project in their working directory.
+    pub fn with_project_detector(mut self, detector: Arc<ProjectDetector>) -> Self {
+        self.project_detector = Some(detector);
+        self
+    }
+
+    /// Record tool calls and the end of runs in an audit log.
+    pub fn with_audit_log(mut self, audit_log: Arc<AuditLog>) -> Self {
+        self.tools.audit_log = Some(audit_log);
+        self
+    }
+
+    /// Scrub secrets from tool results before the model, transcript and
+    /// audit log see them.
+    pub fn with_redactor(mut self, redactor: Arc<dyn Redactor>) -> Self {
+        self.tools.redactor = Some(redactor);
+        self
+    }
+
     /// Run the agent loop.
     pub async fn run(
         &self,
@@ -108,6 +243,11 @@ impl AgentLoop {
         let start_time = std::time::Instant::now();
         let mut messages = ctx.history.clone();
 
+        // Project brief injection: only when the session starts
+        if messages.is_empty() {
+            self.inject_project_brief(&ctx, &mut messages).await;
+        }
+
         // Memory context injection: search for memories related to the initial message
         self.inject_memory_context(&initial_message.content.text(), &mut messages)
             .await;
@@ -151,6 +291,13 @@ impl AgentLoop {
                         cp_data.turn,
                         cp_data.messages.len()
                     );
+                    if let Some(partial) = cp_data.context.get("partial_output").and_then(|v| v.as_str()) {
+                        info!(
+                            "Turn {} had failed after {} characters of output",
+                            cp_data.turn + 1,
+                            partial.len()
+                        );
+                    }
 
                     // Restore context data from checkpoint
                     if let Some(data) = cp_data.context.get("data") {
@@ -235,6 +382,21 @@ impl AgentLoop {
         }
     }
 
+    /// Inject a brief of the project in the run's working directory.
+    async fn inject_project_brief(&self, ctx: &AgentContext, messages: &mut Vec<Message>) {
+        let Some(ref detector) = self.project_detector else {
+            return;
+        };
+        let work_dir = ctx
+            .work_dir
+            .clone()
+            .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+        if let Some(brief) = detector.detect(&work_dir).await {
+            messages.push(Message::system(brief.render()));
+            debug!("Injected project brief ({:?}) into context", brief.stacks);
+        }
+    }
+
     /// Inject memory context at a specific position (used by `run_from_turn()`).
     async fn inject_memory_context_at(
         &self,
@@ -268,12 +430,68 @@ impl AgentLoop {
         }
     }
 
-    /// Shared loop body for `run()` and `run_from_turn()`.
+    /// Shared loop body for `run()` and `run_from_turn()`, with progress
+    /// tracking.
+    async fn run_loop_inner(
+        &self,
+        agent: &dyn Agent,
+        ctx: &mut AgentContext,
+        messages: Vec<Message>,
+        start_turn: u32,
+        start_time: &std::time::Instant,
+    ) -> Result<Vec<Message>, AgentError> {
+        if let Some(ref progress) = self.progress {
+            progress.start(&ctx.session_id);
+        }
+        let span = info_span!(
+            "agent_run",
+            session_id = %ctx.session_id,
+            correlation_id = ctx.correlation_id.as_deref().unwrap_or_default(),
+        );
+        let result = self
+            .run_turns(agent, ctx, messages, start_turn, start_time)
+            .instrument(span)
+            .await;
+        if let Err(ref e) = result {
+            self.track(&ctx.session_id, StreamEvent::Error { error: e.to_string() });
+        }
+        let mut entry = match &result {
+            Ok(_) => AuditEntry::new(AuditKind::AgentRun, "completed"),
+            Err(AgentError::Aborted) => AuditEntry::new(AuditKind::AgentRun, "aborted"),
+            Err(e) => AuditEntry::new(AuditKind::AgentRun, "failed").with_summary(&e.to_string()),
+        };
+        entry = entry
+            .with_agent(agent.id())
+            .with_duration_ms(start_time.elapsed().as_millis() as u64);
+        self.tools.audit(ctx, entry);
+        result
+    }
+
+    /// Record a progress event for a session.
+    fn track(&self, session_id: &str, event: StreamEvent) {
+        if let Some(ref progress) = self.progress {
+            progress.record(session_id, &event);
+        }
+    }
+
+    /// Whether a feature flag is on for the run's workspace.
+    fn flag(&self, flag: &str, ctx: &AgentContext) -> bool {
+        self.tools.flag(flag, ctx)
+    }
+
+    /// Record a trace step of the run.
+    fn trace(&self, ctx: &AgentContext, event: TraceEvent) {
+        if let (Some(log), Some(correlation_id)) = (&self.trace_log, &ctx.correlation_id) {
+            log.record(correlation_id, event.with_session(ctx.session_id.clone()));
+        }
+    }
+
+    /// Turn iteration for `run_loop_inner()`.
     ///
     /// Handles: turn iteration, abort/max-turns checks, agent processing with
     /// context-length recovery, transcript recording, token accumulation,
     /// checkpointing, completion/memory flush, and tool execution.
-    async fn run_loop_inner(
+    async fn run_turns(
         &self,
         agent: &dyn Agent,
         ctx: &mut AgentContext,
@@ -293,37 +511,51 @@ impl AgentLoop {
 
             turn += 1;
             debug!("Agent loop turn {}", turn);
-
+            let turn_span = info_span!("agent_turn", turn);
+            self.track(&ctx.session_id, StreamEvent::TurnStart { turn });
+            self.trace(ctx, TraceEvent::new(TraceEventKind::TurnStarted).with_turn(turn));
+
+            let call_started = Utc::now();
+            let call_span = info_span!(
+                parent: &turn_span,
+                "provider_call",
+                turn,
+                prompt_tokens = field::Empty,
+                completion_tokens = field::Empty,
+                total_tokens = field::Empty,
+                "otel.status_code" = field::Empty,
+            );
             // Process through agent (with context length recovery)
-            ctx.history = messages.clone();
-            let last_msg = messages
-                .last()
-                .ok_or_else(|| AgentError::ExecutionFailed("Message history is empty".to_string()))?
-                .clone();
-            let response = match agent
-                .process(last_msg, ctx.clone())
+            let response = match self
+                .process_turn(agent, ctx, &mut messages, turn)
+                .instrument(call_span.clone())
                 .await
             {
                 Ok(resp) => resp,
-                Err(AgentError::ProviderError(ref provider_err))
-                    if provider_err.is_context_length_error() =>
-                {
-                    warn!(
-                        "Context length exceeded at turn {}, attempting compression",
-                        turn
+                Err(e) => {
+                    call_span.record("otel.status_code", "ERROR");
+                    self.trace(
+                        ctx,
+                        TraceEvent::new(TraceEventKind::ProviderCall)
+                            .started_at(call_started)
+                            .with_turn(turn)
+                            .failed()
+                            .with_detail(serde_json::json!({"error": e.to_string()})),
                     );
-                    messages = self.compress_messages(messages).await?;
-                    ctx.history = messages.clone();
-                    let last_msg = messages
-                        .last()
-                        .ok_or_else(|| AgentError::ExecutionFailed("Message history is empty after compression".to_string()))?
-                        .clone();
-                    agent
-                        .process(last_msg, ctx.clone())
-                        .await?
+                    self.checkpoint_failed_turn(ctx, turn, &messages, &e).await;
+                    return Err(e);
                 }
-                Err(e) => return Err(e),
             };
+            self.trace(
+                ctx,
+                TraceEvent::new(TraceEventKind::ProviderCall)
+                    .started_at(call_started)
+                    .with_turn(turn)
+                    .with_detail(serde_json::json!({
+                        "total_tokens": response.usage.as_ref().map(|u| u.total_tokens),
+                        "tool_calls": response.tool_calls.len(),
+                    })),
+            );
 
             // Record assistant message to transcript
             if let Some(ref transcript) = self.transcript {
@@ -339,6 +571,9 @@ impl AgentLoop {
                 total_usage.prompt_tokens += usage.prompt_tokens;
                 total_usage.completion_tokens += usage.completion_tokens;
                 total_usage.total_tokens += usage.total_tokens;
+                call_span.record("prompt_tokens", usage.prompt_tokens);
+                call_span.record("completion_tokens", usage.completion_tokens);
+                call_span.record("total_tokens", usage.total_tokens);
                 debug!(
                     "Turn {} usage: prompt={}, completion={}, total={}; cumulative total={}",
                     turn,
@@ -347,6 +582,7 @@ impl AgentLoop {
                     usage.total_tokens,
                     total_usage.total_tokens
                 );
+                self.track(&ctx.session_id, StreamEvent::Usage { usage: usage.clone() });
             }
 
             messages.push(response.message.clone());
@@ -370,6 +606,12 @@ impl AgentLoop {
                 }
             }
 
+            if let Some(ref observer) = self.turn_observer {
+                observer
+                    .on_turn_completed(&ctx.session_id, turn, &response.message)
+                    .await;
+            }
+
             if response.is_complete {
                 info!("Agent completed after {} turns", turn);
                 // Flush memory and store session summary on normal completion
@@ -378,6 +620,7 @@ impl AgentLoop {
                         &messages,
                         memory,
                         "session-end-flush",
+                        &ctx.session_id,
                     )
                     .await;
                     memory_persistence::store_session_summary(
@@ -389,87 +632,264 @@ impl AgentLoop {
                 }
                 self.record_session_end("completed", None, turn, start_time)
                     .await;
+                self.track(&ctx.session_id, StreamEvent::TurnComplete { turn });
+                self.trace(ctx, TraceEvent::new(TraceEventKind::TurnCompleted).with_turn(turn));
+                self.track(
+                    &ctx.session_id,
+                    StreamEvent::Complete {
+                        message: response.message.clone(),
+                    },
+                );
                 break;
             }
 
             // Handle tool calls
-            for tool_call in &response.tool_calls {
-                // Record tool use to transcript
-                if let Some(ref transcript) = self.transcript {
-                    if let Err(e) = transcript
-                        .record_tool_use(
-                            &tool_call.id,
-                            &tool_call.name,
-                            tool_call.arguments.clone(),
-                        )
-                        .await
-                    {
-                        warn!("Failed to record tool use to transcript: {}", e);
-                    }
+            let results = if response.tool_calls.len() > 1 && self.flag(PARALLEL_TOOLS, ctx) {
+                debug!("Running {} tool calls in parallel", response.tool_calls.len());
+                let ctx = &*ctx;
+                futures::future::join_all(
+                    response
+                        .tool_calls
+                        .iter()
+                        .map(|tool_call| self.run_tool_call(agent.id(), tool_call, ctx, turn, &turn_span)),
+                )
+                .await
+            } else {
+                let mut results = Vec::with_capacity(response.tool_calls.len());
+                for tool_call in &response.tool_calls {
+                    results.push(self.run_tool_call(agent.id(), tool_call, ctx, turn, &turn_span).await);
                 }
+                results
+            };
+            for (tool_call, result) in response.tool_calls.iter().zip(results) {
+                messages.push(Message::tool(&tool_call.id, result));
+            }
+            self.track(&ctx.session_id, StreamEvent::TurnComplete { turn });
+            self.trace(ctx, TraceEvent::new(TraceEventKind::TurnCompleted).with_turn(turn));
+        }
 
-                let tool_start = std::time::Instant::now();
-                let result = self.execute_tool(tool_call, ctx).await;
-                let duration_ms = tool_start.elapsed().as_millis() as u64;
-
-                // Record tool result to transcript
-                if let Some(ref transcript) = self.transcript {
-                    let is_error = result.starts_with("Error:");
-                    if let Err(e) = transcript
-                        .record_tool_result(
-                            &tool_call.id,
-                            &tool_call.name,
-                            !is_error,
-                            Some(&result),
-                            if is_error { Some(&result) } else { None },
-                            Some(duration_ms),
-                        )
-                        .await
-                    {
-                        warn!("Failed to record tool result to transcript: {}", e);
+        Ok(messages)
+    }
+
+    /// Process one turn, recovering from context overflow: first by switching
+    /// to the long-context fallback model, then by compressing the history.
+    async fn process_turn(
+        &self,
+        agent: &dyn Agent,
+        ctx: &mut AgentContext,
+        messages: &mut Vec<Message>,
+        turn: u32,
+    ) -> Result<AgentResponse, AgentError> {
+        let mut fallback_tried = false;
+        let mut compressed = false;
+        loop {
+            ctx.history = messages.clone();
+            let last_msg = messages
+                .last()
+                .ok_or_else(|| AgentError::ExecutionFailed("Message history is empty".to_string()))?
+                .clone();
+            match agent.process(last_msg, ctx.clone()).await {
+                Err(AgentError::ProviderError(ref provider_err))
+                    if provider_err.is_context_length_error() =>
+                {
+                    if !fallback_tried {
+                        fallback_tried = true;
+                        if self.switch_to_fallback_model(agent, ctx, turn) {
+                            continue;
+                        }
                     }
+                    if !compressed {
+                        compressed = true;
+                        warn!(
+                            "Context length exceeded at turn {}, attempting compression",
+                            turn
+                        );
+                        *messages = self
+                            .compress_messages(std::mem::take(messages), &ctx.session_id)
+                            .await?;
+                        continue;
+                    }
+                    return Err(AgentError::ExecutionFailed(
+                        "The conversation no longer fits the model's context window, even after compression"
+                            .to_string(),
+                    ));
                 }
-
-                let tool_message = Message::tool(&tool_call.id, result);
-                messages.push(tool_message);
+                result => return result,
             }
         }
+    }
 
-        Ok(messages)
+    /// Switch the rest of the run to the context fallback model, if one is
+    /// configured, not in use yet and accepted by the agent.
+    fn switch_to_fallback_model(&self, agent: &dyn Agent, ctx: &mut AgentContext, turn: u32) -> bool {
+        let Some(ref fallback) = self.config.context_fallback_model else {
+            return false;
+        };
+        if ctx.overrides.model_or(&agent.config().default_model) == fallback {
+            return false;
+        }
+        let overrides = RunOverrides {
+            model: Some(fallback.clone()),
+            ..ctx.overrides.clone()
+        };
+        if let Err(e) = agent.check_overrides(&overrides) {
+            warn!("Cannot switch to context fallback model {}: {}", fallback, e);
+            return false;
+        }
+        warn!(
+            "Context length exceeded at turn {}, switching to {}",
+            turn, fallback
+        );
+        ctx.overrides = overrides;
+        true
+    }
+
+    /// Checkpoint the turns completed before a provider failure, so a retry
+    /// resumes after their tool work instead of repeating it. Output the
+    /// provider sent before failing is kept as `partial_output`.
+    async fn checkpoint_failed_turn(
+        &self,
+        ctx: &AgentContext,
+        turn: u32,
+        messages: &[Message],
+        error: &AgentError,
+    ) {
+        let Some(ref checkpoint) = self.checkpoint else {
+            return;
+        };
+        let partial = match error {
+            AgentError::Interrupted { partial, .. } => Some(partial),
+            AgentError::ProviderError(_) => None,
+            _ => return,
+        };
+        // Nothing worth keeping before a turn completed
+        if turn <= 1 {
+            return;
+        }
+        let mut context_data = serde_json::json!({
+            "session_id": ctx.session_id,
+            "data": ctx.data,
+        });
+        if let Some(partial) = partial.filter(|p| !p.is_empty()) {
+            context_data["partial_output"] = partial.clone().into();
+        }
+        match checkpoint
+            .create_checkpoint(&ctx.session_id, turn - 1, messages, &context_data)
+            .await
+        {
+            Ok(()) => info!("Checkpoint created after failed turn {}", turn),
+            Err(e) => warn!("Failed to checkpoint failed turn {}: {}", turn, e),
+        }
     }
 
-    async fn execute_tool(
+    /// Run one tool call of a turn, recording it in the transcript,
+    /// progress and trace.
+    async fn run_tool_call(
         &self,
+        agent_id: &str,
         tool_call: &autohands_protocols::types::ToolCall,
         ctx: &AgentContext,
+        turn: u32,
+        turn_span: &tracing::Span,
     ) -> String {
-        let tool = match self.tool_registry.get(&tool_call.name) {
-            Some(t) => t,
-            None => return format!("Tool not found: {}", tool_call.name),
-        };
+        // Record tool use to transcript
+        if let Some(ref transcript) = self.transcript {
+            if let Err(e) = transcript
+                .record_tool_use(
+                    &tool_call.id,
+                    &tool_call.name,
+                    tool_call.arguments.clone(),
+                )
+                .await
+            {
+                warn!("Failed to record tool use to transcript: {}", e);
+            }
+        }
 
-        let work_dir = ctx
-            .work_dir
-            .clone()
-            .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
-        let tool_ctx = ToolContext::new(&ctx.session_id, work_dir);
+        self.track(
+            &ctx.session_id,
+            StreamEvent::ToolCallStart {
+                id: tool_call.id.clone(),
+                name: tool_call.name.clone(),
+            },
+        );
+        let tool_started_at = Utc::now();
+        let tool_span = info_span!(
+            parent: turn_span,
+            "tool",
+            name = %tool_call.name,
+            turn,
+            "otel.status_code" = field::Empty,
+        );
+        let result = self
+            .tools
+            .run(agent_id, tool_call, ctx, self.tool_output(ctx, tool_call))
+            .instrument(tool_span.clone())
+            .await;
+        let duration_ms = result.duration_ms;
+        let mut event = TraceEvent::new(TraceEventKind::ToolCall)
+            .started_at(tool_started_at)
+            .with_turn(turn)
+            .with_name(tool_call.name.clone());
+        if result.outcome == ToolOutcome::Error {
+            tool_span.record("otel.status_code", "ERROR");
+            event = event.failed();
+        }
+        self.trace(ctx, event);
+        let result = result.content;
 
-        let result = match tool.execute(tool_call.arguments.clone(), tool_ctx).await {
-            Ok(result) => result.content,
-            Err(e) => format!("Tool error: {}", e),
-        };
+        // Record tool result to transcript
+        if let Some(ref transcript) = self.transcript {
+            let is_error = result.starts_with("Error:");
+            if let Err(e) = transcript
+                .record_tool_result(
+                    &tool_call.id,
+                    &tool_call.name,
+                    !is_error,
+                    Some(&result),
+                    if is_error { Some(&result) } else { None },
+                    Some(duration_ms),
+                )
+                .await
+            {
+                warn!("Failed to record tool result to transcript: {}", e);
+            }
+        }
 
-        self.truncate_output(result)
+        result
+    }
+
+    /// Sink recording a running tool's output in the session progress.
+    fn tool_output(
+        &self,
+        ctx: &AgentContext,
+        tool_call: &autohands_protocols::types::ToolCall,
+    ) -> Option<ToolOutputSink> {
+        let progress = self.progress.clone()?;
+        let session_id = ctx.session_id.clone();
+        let id = tool_call.id.clone();
+        Some(Arc::new(move |chunk: ToolOutputChunk| {
+            progress.record(
+                &session_id,
+                &StreamEvent::ToolOutput {
+                    id: id.clone(),
+                    stream: chunk.stream,
+                    content: chunk.content,
+                },
+            );
+        }))
     }
 
     /// 压缩消息历史，用于上下文长度恢复。
     async fn compress_messages(
         &self,
         messages: Vec<Message>,
+        session_id: &str,
     ) -> Result<Vec<Message>, AgentError> {
         // Memory flush: extract key information before compression
         if let Some(ref memory) = self.memory_backend {
-            memory_persistence::flush_memories_to_backend(&messages, memory, "auto-flush").await;
+            memory_persistence::flush_memories_to_backend(&messages, memory, "auto-flush", session_id)
+                .await;
         }
 
         if let Some(ref compressor) = self.compressor {
@@ -501,28 +921,6 @@ impl AgentLoop {
             Ok(messages.into_iter().skip(len - keep).collect())
         }
     }
-
-    /// 截断过大的工具输出，防止撑爆上下文。
-    fn truncate_output(&self, content: String) -> String {
-        let max = self.config.max_tool_output_chars;
-        if max == 0 || content.len() <= max {
-            return content;
-        }
-        warn!(
-            "Tool output truncated: {} chars -> {} chars",
-            content.len(),
-            max
-        );
-        // 确保在 char 边界上截断
-        let boundary = memory_persistence::floor_char_boundary(&content, max);
-        let truncated = &content[..boundary];
-        format!(
-            "{}\n\n[OUTPUT TRUNCATED: original {} chars, showing first {}. Use more specific queries to reduce output size.]",
-            truncated,
-            content.len(),
-            max
-        )
-    }
 }
 
 #[cfg(test)]
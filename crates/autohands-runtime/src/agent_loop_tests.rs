@@ -1,14 +1,24 @@
 use super::*;
 use crate::checkpoint::{CheckpointData, CheckpointSupport};
+use crate::feature_flags::AUTO_APPROVAL;
 use crate::memory_persistence;
 use async_trait::async_trait;
-use autohands_protocols::agent::{AgentConfig, AgentResponse};
-use autohands_protocols::tool::AbortSignal;
+use autohands_protocols::agent::{AgentConfig, AgentResponse, RunOverrides};
+use autohands_protocols::tool::{AbortSignal, ToolContext};
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+/// Run a tool call through the loop's tool pipeline.
+async fn execute_tool(
+    agent_loop: &AgentLoop,
+    tool_call: &autohands_protocols::types::ToolCall,
+    ctx: &AgentContext,
+) -> String {
+    agent_loop.tools.execute(tool_call, ctx, None).await.0
+}
+
 struct MockAgent {
     config: AgentConfig,
     complete_immediately: bool,
@@ -53,6 +63,7 @@ struct MockCheckpointSupport {
     interval: u32,
     checkpoint_count: AtomicU32,
     checkpoints: Mutex<Vec<(u32, Vec<Message>)>>,
+    contexts: Mutex<Vec<serde_json::Value>>,
 }
 
 impl MockCheckpointSupport {
@@ -61,6 +72,7 @@ impl MockCheckpointSupport {
             interval,
             checkpoint_count: AtomicU32::new(0),
             checkpoints: Mutex::new(Vec::new()),
+            contexts: Mutex::new(Vec::new()),
         }
     }
 
@@ -80,9 +92,10 @@ impl CheckpointSupport for MockCheckpointSupport {
         _session_id: &str,
         turn: u32,
         messages: &[Message],
-        _context: &serde_json::Value,
+        context: &serde_json::Value,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         self.checkpoint_count.fetch_add(1, Ordering::SeqCst);
+        self.contexts.lock().await.push(context.clone());
         let mut checkpoints = self.checkpoints.lock().await;
         checkpoints.push((turn, messages.to_vec()));
         Ok(())
@@ -158,6 +171,7 @@ fn test_agent_loop_with_custom_config() {
     let config = AgentLoopConfig {
         checkpoint_enabled: false,
         max_tool_output_chars: 50_000,
+        context_fallback_model: None,
     };
 
     let _loop = AgentLoop::new(provider_registry, tool_registry, config);
@@ -208,6 +222,11 @@ async fn test_agent_loop_run_aborted() {
         abort_signal,
         data: HashMap::new(),
         work_dir: None,
+        correlation_id: None,
+        overrides: Default::default(),
+        sandbox: None,
+        scheduling: Default::default(),
+        dry_run: false,
     };
     let message = Message::user("Hello");
 
@@ -229,7 +248,7 @@ async fn test_agent_loop_execute_tool_not_found() {
     };
     let ctx = AgentContext::new("test-session");
 
-    let result = agent_loop.execute_tool(&tool_call, &ctx).await;
+    let result = execute_tool(&agent_loop, &tool_call, &ctx).await;
     assert!(result.contains("Tool not found"));
 }
 
@@ -495,13 +514,19 @@ async fn test_memory_flush_scans_assistant_messages() {
         Message::assistant("The plan is to use Redis for caching and PostgreSQL for persistence"),
     ];
 
-    memory_persistence::flush_memories_to_backend(&messages, &memory_dyn, "session-end-flush")
-        .await;
+    memory_persistence::flush_memories_to_backend(
+        &messages,
+        &memory_dyn,
+        "session-end-flush",
+        "s1",
+    )
+    .await;
 
     let entries = memory_concrete.stored_entries().await;
     // The assistant message contains "the plan is" -> decision type
     let decision = entries.iter().find(|e| e.memory_type == "decision");
     assert!(decision.is_some(), "Expected assistant decision to be captured");
+    assert!(decision.unwrap().tags.contains(&"session:s1".to_string()));
 }
 
 #[tokio::test]
@@ -524,6 +549,11 @@ async fn test_memory_no_flush_on_abort() {
         abort_signal,
         data: HashMap::new(),
         work_dir: None,
+        correlation_id: None,
+        overrides: Default::default(),
+        sandbox: None,
+        scheduling: Default::default(),
+        dry_run: false,
     };
     let message = Message::user("I prefer Python");
 
@@ -565,6 +595,54 @@ async fn test_memory_context_injection_on_run() {
     assert!(has_memory_msg, "Expected memory context injection as system message");
 }
 
+#[tokio::test]
+async fn test_project_brief_injected_at_session_start() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+    let agent_loop = AgentLoop::new(
+        Arc::new(ProviderRegistry::new()),
+        Arc::new(ToolRegistry::new()),
+        AgentLoopConfig::default(),
+    )
+    .with_project_detector(Arc::new(crate::project::ProjectDetector::default()));
+    let agent = MockAgent::new(true);
+    let is_brief = |m: &Message| {
+        matches!(m.role, autohands_protocols::types::MessageRole::System)
+            && m.content.text().contains("Stack: Rust")
+    };
+
+    let ctx = AgentContext::new("test-session").with_work_dir(dir.path().to_path_buf());
+    let message = Message::user("Fix the build");
+    let messages = agent_loop.run(&agent, ctx.clone(), message).await.unwrap();
+    assert!(is_brief(&messages[0]));
+
+    // A session with history already has its brief
+    let ctx = ctx.with_history(vec![Message::user("Fix the build")]);
+    let messages = agent_loop.run(&agent, ctx, Message::user("Now the tests")).await.unwrap();
+    assert!(!messages.iter().any(is_brief));
+}
+
+#[tokio::test]
+async fn test_agent_run_audited() {
+    let dir = tempfile::tempdir().unwrap();
+    let audit = Arc::new(AuditLog::open(dir.path().join("audit.jsonl")).unwrap());
+    let agent_loop = AgentLoop::new(
+        Arc::new(ProviderRegistry::new()),
+        Arc::new(ToolRegistry::new()),
+        AgentLoopConfig::default(),
+    )
+    .with_audit_log(audit.clone());
+
+    let ctx = AgentContext::new("test-session");
+    agent_loop.run(&MockAgent::new(true), ctx, Message::user("Hi")).await.unwrap();
+
+    let entries = audit.query(&Default::default()).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].kind, AuditKind::AgentRun);
+    assert_eq!(entries[0].outcome, "completed");
+    assert_eq!(entries[0].agent_id.as_deref(), Some("mock-agent"));
+}
+
 #[tokio::test]
 async fn test_session_summary_content() {
     let provider_registry = Arc::new(ProviderRegistry::new());
@@ -654,3 +732,668 @@ async fn test_run_from_turn_with_memory_integration() {
     let has_summary = entries.iter().any(|e| e.tags.contains(&"session-summary".to_string()));
     assert!(has_summary, "Expected session summary from run_from_turn");
 }
+
+// ---- Tool approval ----
+
+use autohands_core::audit::{AuditKind, AuditLog};
+use autohands_protocols::approval::ApprovalDecision;
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{Tool, ToolDefinition, ToolResult};
+
+struct RiskyTool {
+    definition: ToolDefinition,
+}
+
+#[async_trait]
+impl Tool for RiskyTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        _params: serde_json::Value,
+        _ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        Ok(ToolResult::success("done"))
+    }
+}
+
+/// Gate that answers every request with a fixed decision.
+struct FixedGate {
+    approve: bool,
+    requests: Mutex<Vec<ApprovalRequest>>,
+}
+
+#[async_trait]
+impl ApprovalGate for FixedGate {
+    async fn request_approval(&self, request: ApprovalRequest) -> ApprovalDecision {
+        self.requests.lock().await.push(request);
+        if self.approve {
+            ApprovalDecision::approved()
+        } else {
+            ApprovalDecision::rejected().with_comment("too risky")
+        }
+    }
+
+    async fn pending(&self) -> Vec<ApprovalRequest> {
+        Vec::new()
+    }
+
+    async fn decide(&self, _request_id: &str, _decision: ApprovalDecision) -> bool {
+        false
+    }
+}
+
+fn approval_loop(approve: bool) -> (AgentLoop, Arc<FixedGate>) {
+    approval_loop_with(approve, ToolApprovalPolicy::threshold(RiskLevel::High))
+}
+
+fn approval_loop_with(approve: bool, policy: ToolApprovalPolicy) -> (AgentLoop, Arc<FixedGate>) {
+    let tool_registry = Arc::new(ToolRegistry::new());
+    for (id, risk) in [("exec", RiskLevel::High), ("read", RiskLevel::Low)] {
+        tool_registry
+            .register(Arc::new(RiskyTool {
+                definition: ToolDefinition::new(id, id, "test").with_risk_level(risk),
+            }))
+            .unwrap();
+    }
+    let gate = Arc::new(FixedGate {
+        approve,
+        requests: Mutex::new(Vec::new()),
+    });
+    let agent_loop = AgentLoop::new(
+        Arc::new(ProviderRegistry::new()),
+        tool_registry,
+        AgentLoopConfig::default(),
+    )
+    .with_approval(ToolApproval {
+        gate: gate.clone(),
+        policy,
+    });
+    (agent_loop, gate)
+}
+
+fn tool_call(name: &str) -> autohands_protocols::types::ToolCall {
+    autohands_protocols::types::ToolCall {
+        id: "call_1".to_string(),
+        name: name.to_string(),
+        arguments: serde_json::json!({"command": "rm -rf build"}),
+    }
+}
+
+#[tokio::test]
+async fn test_risky_tool_rejected() {
+    let (agent_loop, gate) = approval_loop(false);
+    let ctx = AgentContext::new("test-session");
+
+    let result = execute_tool(&agent_loop, &tool_call("exec"), &ctx).await;
+    assert_eq!(result, "Tool 'exec' was not run: rejected by reviewer (too risky)");
+
+    let requests = gate.requests.lock().await;
+    assert_eq!(requests[0].source, "tool:exec");
+    assert_eq!(requests[0].session_id.as_deref(), Some("test-session"));
+    assert_eq!(requests[0].details["arguments"]["command"], "rm -rf build");
+}
+
+#[tokio::test]
+async fn test_risky_tool_approved_and_low_risk_skips_gate() {
+    let (agent_loop, gate) = approval_loop(true);
+    let ctx = AgentContext::new("test-session");
+
+    assert_eq!(execute_tool(&agent_loop, &tool_call("exec"), &ctx).await, "done");
+    assert_eq!(execute_tool(&agent_loop, &tool_call("read"), &ctx).await, "done");
+    assert_eq!(gate.requests.lock().await.len(), 1);
+}
+
+#[tokio::test]
+async fn test_approval_policy_deny_and_tool_override() {
+    let policy = ToolApprovalPolicy::default().with_tool("read", ToolApprovalAction::Prompt);
+    let (agent_loop, gate) = approval_loop_with(true, policy);
+    let ctx = AgentContext::new("test-session");
+
+    assert_eq!(
+        execute_tool(&agent_loop, &tool_call("exec"), &ctx).await,
+        "Tool 'exec' was not run: denied by the approval policy"
+    );
+    assert_eq!(execute_tool(&agent_loop, &tool_call("read"), &ctx).await, "done");
+    let requests = gate.requests.lock().await;
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].source, "tool:read");
+}
+
+#[tokio::test]
+async fn test_tool_calls_audited() {
+    let dir = tempfile::tempdir().unwrap();
+    let audit = Arc::new(AuditLog::open(dir.path().join("audit.jsonl")).unwrap());
+    let (agent_loop, _gate) = approval_loop(false);
+    let agent_loop = agent_loop.with_audit_log(audit.clone());
+    let ctx = AgentContext::new("test-session").with_correlation_id("corr-1");
+    let span = tracing::Span::none();
+
+    agent_loop.run_tool_call("general", &tool_call("read"), &ctx, 1, &span).await;
+    agent_loop.run_tool_call("general", &tool_call("exec"), &ctx, 1, &span).await;
+    agent_loop.run_tool_call("general", &tool_call("missing"), &ctx, 1, &span).await;
+
+    let entries = audit.query(&Default::default()).unwrap();
+    let outcomes: Vec<_> = entries.iter().map(|e| e.outcome.as_str()).collect();
+    assert_eq!(outcomes, vec!["success", "refused", "error"]);
+    assert_eq!(entries[0].kind, AuditKind::ToolCall);
+    assert_eq!(entries[0].agent_id.as_deref(), Some("general"));
+    assert_eq!(entries[0].session_id.as_deref(), Some("test-session"));
+    assert_eq!(entries[0].correlation_id.as_deref(), Some("corr-1"));
+    assert_eq!(entries[0].params["command"], "rm -rf build");
+    assert_eq!(entries[0].summary.as_deref(), Some("done"));
+    assert!(audit.verify().unwrap().valid);
+}
+
+#[tokio::test]
+async fn test_tool_allowlist_override() {
+    let (agent_loop, gate) = approval_loop(true);
+    let ctx = AgentContext::new("test-session").with_overrides(RunOverrides {
+        tools: Some(vec!["read".to_string()]),
+        ..Default::default()
+    });
+
+    assert_eq!(execute_tool(&agent_loop, &tool_call("read"), &ctx).await, "done");
+    assert_eq!(
+        execute_tool(&agent_loop, &tool_call("exec"), &ctx).await,
+        "Tool not allowed in this run: exec"
+    );
+    assert!(gate.requests.lock().await.is_empty());
+}
+
+#[tokio::test]
+async fn test_dry_run_simulates_tools_with_side_effects() {
+    let dir = tempfile::tempdir().unwrap();
+    let audit = Arc::new(AuditLog::open(dir.path().join("audit.jsonl")).unwrap());
+    let (agent_loop, gate) = approval_loop(false);
+    let agent_loop = agent_loop.with_audit_log(audit.clone());
+    agent_loop
+        .tools
+        .tool_registry
+        .register(Arc::new(RiskyTool {
+            definition: ToolDefinition::new("cat", "cat", "test").with_read_only(),
+        }))
+        .unwrap();
+    let ctx = AgentContext::new("test-session").with_dry_run(true);
+    let span = tracing::Span::none();
+
+    // Simulated without asking the reviewer, who would reject the call
+    let result = agent_loop.run_tool_call("general", &tool_call("exec"), &ctx, 1, &span).await;
+    assert_eq!(
+        result,
+        "[dry run] Would call exec with {\"command\":\"rm -rf build\"}; nothing was changed."
+    );
+    assert_eq!(execute_tool(&agent_loop, &tool_call("cat"), &ctx).await, "done");
+    assert!(gate.requests.lock().await.is_empty());
+
+    let entries = audit.query(&Default::default()).unwrap();
+    assert_eq!(entries[0].outcome, "simulated");
+}
+
+struct RecordingTurnObserver {
+    turns: Mutex<Vec<(String, u32, String)>>,
+}
+
+#[async_trait]
+impl TurnObserver for RecordingTurnObserver {
+    async fn on_turn_completed(&self, session_id: &str, turn: u32, message: &Message) {
+        self.turns
+            .lock()
+            .await
+            .push((session_id.to_string(), turn, message.content.text().to_string()));
+    }
+}
+
+#[tokio::test]
+async fn test_turn_observer_notified() {
+    let observer = Arc::new(RecordingTurnObserver {
+        turns: Mutex::new(Vec::new()),
+    });
+    let agent_loop = AgentLoop::new(
+        Arc::new(ProviderRegistry::new()),
+        Arc::new(ToolRegistry::new()),
+        AgentLoopConfig::default(),
+    )
+    .with_turn_observer(observer.clone());
+
+    let agent = MockAgent::new(true);
+    let ctx = AgentContext::new("test-session");
+    agent_loop.run(&agent, ctx, Message::user("Hello")).await.unwrap();
+
+    let turns = observer.turns.lock().await;
+    assert_eq!(turns.len(), 1);
+    assert_eq!(turns[0], ("test-session".to_string(), 1, "Echo: Hello".to_string()));
+}
+
+/// Agent calling a tool on its first turn and answering on the second.
+struct ToolThenAnswerAgent {
+    config: AgentConfig,
+    calls: AtomicU32,
+}
+
+#[async_trait]
+impl Agent for ToolThenAnswerAgent {
+    fn id(&self) -> &str {
+        &self.config.id
+    }
+
+    fn config(&self) -> &AgentConfig {
+        &self.config
+    }
+
+    async fn process(
+        &self,
+        _message: Message,
+        _ctx: AgentContext,
+    ) -> Result<AgentResponse, AgentError> {
+        let first = self.calls.fetch_add(1, Ordering::SeqCst) == 0;
+        let tool_calls = if first {
+            vec![autohands_protocols::types::ToolCall {
+                id: "call-1".to_string(),
+                name: "lookup".to_string(),
+                arguments: serde_json::json!({}),
+            }]
+        } else {
+            Vec::new()
+        };
+        Ok(AgentResponse {
+            message: Message::assistant("working"),
+            is_complete: !first,
+            tool_calls,
+            metadata: HashMap::new(),
+            usage: Some(autohands_protocols::types::Usage {
+                prompt_tokens: 10,
+                completion_tokens: 5,
+                total_tokens: 15,
+                ..Default::default()
+            }),
+        })
+    }
+}
+
+/// Agent whose default model's context window is too small, recording the
+/// model of every call.
+struct SmallContextAgent {
+    config: AgentConfig,
+    models: Mutex<Vec<String>>,
+}
+
+impl SmallContextAgent {
+    fn new() -> Self {
+        Self {
+            config: AgentConfig::new("small-agent", "Small Agent", "small"),
+            models: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl Agent for SmallContextAgent {
+    fn id(&self) -> &str {
+        &self.config.id
+    }
+
+    fn config(&self) -> &AgentConfig {
+        &self.config
+    }
+
+    async fn process(
+        &self,
+        _message: Message,
+        ctx: AgentContext,
+    ) -> Result<AgentResponse, AgentError> {
+        let model = ctx.overrides.model_or(&self.config.default_model).to_string();
+        self.models.lock().await.push(model.clone());
+        if model != "long" {
+            return Err(AgentError::ProviderError(
+                autohands_protocols::error::ProviderError::ContextLengthExceeded {
+                    used: 9000,
+                    max: 8000,
+                },
+            ));
+        }
+        Ok(AgentResponse {
+            message: Message::assistant("fits now"),
+            is_complete: true,
+            tool_calls: Vec::new(),
+            metadata: HashMap::new(),
+            usage: None,
+        })
+    }
+
+    fn check_overrides(&self, _overrides: &RunOverrides) -> Result<(), AgentError> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_context_overflow_switches_to_fallback_model() {
+    let config = AgentLoopConfig {
+        context_fallback_model: Some("long".to_string()),
+        ..Default::default()
+    };
+    let agent_loop = AgentLoop::new(
+        Arc::new(ProviderRegistry::new()),
+        Arc::new(ToolRegistry::new()),
+        config,
+    );
+    let agent = SmallContextAgent::new();
+    let messages = agent_loop
+        .run(&agent, AgentContext::new("overflow"), Message::user("A long task"))
+        .await
+        .unwrap();
+
+    assert_eq!(messages.last().unwrap().content.text(), "fits now");
+    assert_eq!(*agent.models.lock().await, vec!["small", "long"]);
+}
+
+#[tokio::test]
+async fn test_context_overflow_without_fallback_fails_cleanly() {
+    let agent_loop = AgentLoop::new(
+        Arc::new(ProviderRegistry::new()),
+        Arc::new(ToolRegistry::new()),
+        AgentLoopConfig::default(),
+    );
+    let agent = SmallContextAgent::new();
+    let err = agent_loop
+        .run(&agent, AgentContext::new("overflow"), Message::user("A long task"))
+        .await
+        .unwrap_err();
+
+    // Compressed once, then reported without the raw provider error
+    assert!(matches!(err, AgentError::ExecutionFailed(_)));
+    assert!(err.to_string().contains("context window"));
+    assert_eq!(agent.models.lock().await.len(), 2);
+}
+
+/// Agent calling a tool on its first turn, then failing mid-stream.
+struct ToolThenInterruptedAgent {
+    config: AgentConfig,
+    calls: AtomicU32,
+}
+
+#[async_trait]
+impl Agent for ToolThenInterruptedAgent {
+    fn id(&self) -> &str {
+        &self.config.id
+    }
+
+    fn config(&self) -> &AgentConfig {
+        &self.config
+    }
+
+    async fn process(
+        &self,
+        _message: Message,
+        _ctx: AgentContext,
+    ) -> Result<AgentResponse, AgentError> {
+        if self.calls.fetch_add(1, Ordering::SeqCst) > 0 {
+            return Err(AgentError::Interrupted {
+                partial: "The answer is".to_string(),
+                source: autohands_protocols::error::ProviderError::Network("reset".to_string()),
+            });
+        }
+        let mut message = Message::assistant("looking up");
+        message.tool_calls = vec![autohands_protocols::types::ToolCall {
+            id: "call-1".to_string(),
+            name: "lookup".to_string(),
+            arguments: serde_json::json!({}),
+        }];
+        Ok(AgentResponse {
+            tool_calls: message.tool_calls.clone(),
+            message,
+            is_complete: false,
+            metadata: HashMap::new(),
+            usage: None,
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_failed_turn_checkpointed() {
+    // Interval checkpoints never fire; the failure checkpoint still does
+    let checkpoint = Arc::new(MockCheckpointSupport::new(100));
+    let agent_loop = AgentLoop::new(
+        Arc::new(ProviderRegistry::new()),
+        Arc::new(ToolRegistry::new()),
+        AgentLoopConfig::default().with_checkpoint(),
+    )
+    .with_checkpoint(checkpoint.clone());
+
+    let agent = ToolThenInterruptedAgent {
+        config: AgentConfig::new("tool-agent", "Tool Agent", "mock-model"),
+        calls: AtomicU32::new(0),
+    };
+    let ctx = AgentContext::new("failing-session");
+    let result = agent_loop.run(&agent, ctx, Message::user("Look it up")).await;
+    assert!(matches!(result, Err(AgentError::Interrupted { .. })));
+
+    // The checkpoint holds turn 1 with its tool result
+    let checkpoints = checkpoint.checkpoints.lock().await;
+    assert_eq!(checkpoints.len(), 1);
+    let (turn, messages) = &checkpoints[0];
+    assert_eq!(*turn, 1);
+    assert_eq!(messages.len(), 3);
+    assert_eq!(messages[2].role, autohands_protocols::types::MessageRole::Tool);
+    let contexts = checkpoint.contexts.lock().await;
+    assert_eq!(contexts[0]["partial_output"], "The answer is");
+
+    // A failure on the first turn has nothing to keep
+    let agent = ToolThenInterruptedAgent {
+        config: AgentConfig::new("tool-agent", "Tool Agent", "mock-model"),
+        calls: AtomicU32::new(1),
+    };
+    let ctx = AgentContext::new("failing-session-2");
+    assert!(agent_loop.run(&agent, ctx, Message::user("Hi")).await.is_err());
+    assert_eq!(checkpoint.checkpoint_count(), 1);
+}
+
+#[tokio::test]
+async fn test_progress_tracked() {
+    let progress = Arc::new(ProgressTracker::new());
+    let agent_loop = AgentLoop::new(
+        Arc::new(ProviderRegistry::new()),
+        Arc::new(ToolRegistry::new()),
+        AgentLoopConfig::default(),
+    )
+    .with_progress(progress.clone());
+
+    let agent = ToolThenAnswerAgent {
+        config: AgentConfig::new("tool-agent", "Tool Agent", "mock-model"),
+        calls: AtomicU32::new(0),
+    };
+    let ctx = AgentContext::new("progress-session");
+    agent_loop.run(&agent, ctx, Message::user("Hello")).await.unwrap();
+
+    let state = progress.get("progress-session").unwrap();
+    assert_eq!(state.turn, 2);
+    assert_eq!(state.turns_completed, 2);
+    assert_eq!(state.last_tool.as_deref(), Some("lookup"));
+    assert_eq!(state.tool_calls, 1);
+    assert_eq!(state.tokens_used, 30);
+    assert_eq!(state.prompt_tokens, 20);
+    assert!(state.finished);
+    assert_eq!(state.eta_seconds, Some(0));
+}
+
+#[tokio::test]
+async fn test_trace_recorded_under_correlation_id() {
+    use crate::trace::{TraceEventKind, TraceLog};
+
+    let trace_log = Arc::new(TraceLog::new());
+    let agent_loop = AgentLoop::new(
+        Arc::new(ProviderRegistry::new()),
+        Arc::new(ToolRegistry::new()),
+        AgentLoopConfig::default(),
+    )
+    .with_trace_log(trace_log.clone());
+
+    let agent = ToolThenAnswerAgent {
+        config: AgentConfig::new("tool-agent", "Tool Agent", "mock-model"),
+        calls: AtomicU32::new(0),
+    };
+    let ctx = AgentContext::new("trace-session").with_correlation_id("corr-1");
+    agent_loop.run(&agent, ctx, Message::user("Hello")).await.unwrap();
+
+    let trace = trace_log.get("corr-1").unwrap();
+    let kinds: Vec<_> = trace.events.iter().map(|e| e.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            TraceEventKind::TurnStarted,
+            TraceEventKind::ProviderCall,
+            TraceEventKind::ToolCall,
+            TraceEventKind::TurnCompleted,
+            TraceEventKind::TurnStarted,
+            TraceEventKind::ProviderCall,
+            TraceEventKind::TurnCompleted,
+        ]
+    );
+    let tool = &trace.events[2];
+    assert_eq!(tool.name.as_deref(), Some("lookup"));
+    assert_eq!(tool.session_id.as_deref(), Some("trace-session"));
+    assert_eq!(tool.turn, Some(1));
+    // The tool is not registered
+    assert!(tool.failed);
+
+    // Runs without a correlation ID are not traced
+    let agent = MockAgent::new(true);
+    agent_loop
+        .run(&agent, AgentContext::new("untraced"), Message::user("Hi"))
+        .await
+        .unwrap();
+    assert_eq!(trace_log.len(), 1);
+}
+
+// ---- Feature flags ----
+
+#[tokio::test]
+async fn test_auto_approval_flag_per_workspace() {
+    let flags = Arc::new(FeatureFlags::new());
+    flags.set_override(AUTO_APPROVAL, Some("/repo"), true);
+    let (agent_loop, gate) = approval_loop(false);
+    let agent_loop = agent_loop.with_feature_flags(flags);
+
+    let mut ctx = AgentContext::new("test-session");
+    ctx.work_dir = Some(PathBuf::from("/repo"));
+    assert_eq!(execute_tool(&agent_loop, &tool_call("exec"), &ctx).await, "done");
+    assert!(gate.requests.lock().await.is_empty());
+
+    let ctx = AgentContext::new("test-session");
+    let result = execute_tool(&agent_loop, &tool_call("exec"), &ctx).await;
+    assert!(result.starts_with("Tool 'exec' was not run"));
+    assert_eq!(gate.requests.lock().await.len(), 1);
+}
+
+/// Tool that only returns once `parties` calls wait on it at the same time.
+struct BarrierTool {
+    definition: ToolDefinition,
+    barrier: tokio::sync::Barrier,
+}
+
+#[async_trait]
+impl Tool for BarrierTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        _params: serde_json::Value,
+        _ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        self.barrier.wait().await;
+        Ok(ToolResult::success("met"))
+    }
+}
+
+/// Agent calling `meet` twice on its first turn and answering on the second.
+struct TwoCallsAgent {
+    config: AgentConfig,
+    calls: AtomicU32,
+}
+
+#[async_trait]
+impl Agent for TwoCallsAgent {
+    fn id(&self) -> &str {
+        &self.config.id
+    }
+
+    fn config(&self) -> &AgentConfig {
+        &self.config
+    }
+
+    async fn process(
+        &self,
+        _message: Message,
+        _ctx: AgentContext,
+    ) -> Result<AgentResponse, AgentError> {
+        let first = self.calls.fetch_add(1, Ordering::SeqCst) == 0;
+        let tool_calls = if first {
+            ["call-1", "call-2"]
+                .into_iter()
+                .map(|id| autohands_protocols::types::ToolCall {
+                    id: id.to_string(),
+                    name: "meet".to_string(),
+                    arguments: serde_json::json!({}),
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        Ok(AgentResponse {
+            message: Message::assistant("working"),
+            is_complete: !first,
+            tool_calls,
+            metadata: HashMap::new(),
+            usage: None,
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_parallel_tools_flag() {
+    let tool_registry = Arc::new(ToolRegistry::new());
+    tool_registry
+        .register(Arc::new(BarrierTool {
+            definition: ToolDefinition::new("meet", "meet", "test"),
+            barrier: tokio::sync::Barrier::new(2),
+        }))
+        .unwrap();
+    let flags = Arc::new(FeatureFlags::new());
+    flags.set_override(PARALLEL_TOOLS, None, true);
+    let agent_loop = AgentLoop::new(
+        Arc::new(ProviderRegistry::new()),
+        tool_registry,
+        AgentLoopConfig::default(),
+    )
+    .with_feature_flags(flags.clone());
+
+    let agent = TwoCallsAgent {
+        config: AgentConfig::new("two-calls", "Two Calls", "mock-model"),
+        calls: AtomicU32::new(0),
+    };
+    // Sequential calls would wait on the barrier forever
+    let messages = tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        agent_loop.run(&agent, AgentContext::new("parallel"), Message::user("Hi")),
+    )
+    .await
+    .expect("tool calls ran one after another")
+    .unwrap();
+
+    let results: Vec<_> = messages
+        .iter()
+        .filter(|m| m.role == autohands_protocols::types::MessageRole::Tool)
+        .map(|m| (m.tool_call_id.clone(), m.content.text().to_string()))
+        .collect();
+    assert_eq!(
+        results,
+        vec![
+            (Some("call-1".to_string()), "met".to_string()),
+            (Some("call-2".to_string()), "met".to_string()),
+        ]
+    );
+}
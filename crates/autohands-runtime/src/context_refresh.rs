@@ -0,0 +1,302 @@
+//! Context refresh for long-lived agents.
+//!
+//! An agent answering a channel for weeks would otherwise keep the system
+//! prompt it was created with. A [`LivePrompt`] is shared with the agent and
+//! rebuilt by a [`ContextRefresher`] from [`ContextSection`]s — the prompt
+//! template, skill metadata, standing memories — on a schedule, when a
+//! watched file (the configuration, the template) changes, or on demand.
+//!
+//! A section failing to render keeps its previous text, so a flaky memory
+//! backend does not strip the prompt.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::{Mutex, RwLock};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use autohands_protocols::memory::{MemoryBackend, MemoryQuery};
+
+/// A system prompt that can change while its agent runs.
+#[derive(Debug, Default)]
+pub struct LivePrompt {
+    text: RwLock<Option<String>>,
+    generation: AtomicU64,
+    refreshed_at: RwLock<Option<DateTime<Utc>>>,
+}
+
+impl LivePrompt {
+    /// Create a prompt with its initial text.
+    pub fn new(text: Option<String>) -> Self {
+        Self {
+            text: RwLock::new(text),
+            ..Default::default()
+        }
+    }
+
+    /// Current text.
+    pub fn get(&self) -> Option<String> {
+        self.text.read().clone()
+    }
+
+    /// Number of times the text changed.
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Relaxed)
+    }
+
+    /// Time of the last refresh, changed or not.
+    pub fn refreshed_at(&self) -> Option<DateTime<Utc>> {
+        *self.refreshed_at.read()
+    }
+
+    /// Replace the text. Returns whether it changed.
+    pub fn set(&self, text: String) -> bool {
+        *self.refreshed_at.write() = Some(Utc::now());
+        let mut current = self.text.write();
+        if current.as_deref() == Some(text.as_str()) {
+            return false;
+        }
+        *current = Some(text);
+        self.generation.fetch_add(1, Ordering::Relaxed);
+        true
+    }
+}
+
+/// A part of the system prompt, rendered on every refresh.
+#[async_trait]
+pub trait ContextSection: Send + Sync {
+    /// Name for logs.
+    fn name(&self) -> &str;
+
+    /// Render the section. An empty text leaves it out.
+    async fn render(&self) -> Result<String, String>;
+}
+
+/// Prompt template read from a file, or a built-in text while the file
+/// does not exist.
+pub struct TemplateSection {
+    path: Option<PathBuf>,
+    fallback: String,
+}
+
+impl TemplateSection {
+    /// Read the template from `path`, if given.
+    pub fn new(path: Option<PathBuf>, fallback: impl Into<String>) -> Self {
+        Self {
+            path,
+            fallback: fallback.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl ContextSection for TemplateSection {
+    fn name(&self) -> &str {
+        "template"
+    }
+
+    async fn render(&self) -> Result<String, String> {
+        let Some(path) = &self.path else {
+            return Ok(self.fallback.clone());
+        };
+        match tokio::fs::read_to_string(path).await {
+            Ok(text) => Ok(text.trim_end().to_string()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(self.fallback.clone()),
+            Err(e) => Err(format!("{}: {}", path.display(), e)),
+        }
+    }
+}
+
+/// Memories matching a standing query.
+pub struct MemorySection {
+    backend: Arc<dyn MemoryBackend>,
+    query: String,
+    limit: usize,
+}
+
+impl MemorySection {
+    pub fn new(backend: Arc<dyn MemoryBackend>, query: impl Into<String>, limit: usize) -> Self {
+        Self {
+            backend,
+            query: query.into(),
+            limit,
+        }
+    }
+}
+
+#[async_trait]
+impl ContextSection for MemorySection {
+    fn name(&self) -> &str {
+        "memory"
+    }
+
+    async fn render(&self) -> Result<String, String> {
+        let results = self
+            .backend
+            .search(MemoryQuery::text(&self.query).with_limit(self.limit))
+            .await
+            .map_err(|e| e.to_string())?;
+        if results.is_empty() {
+            return Ok(String::new());
+        }
+        let mut text = String::from("## Relevant memories");
+        for result in results {
+            text.push_str(&format!("\n- {}", result.entry.content.trim()));
+        }
+        Ok(text)
+    }
+}
+
+/// Size and modification time of a watched file.
+type FileStamp = Option<(SystemTime, u64)>;
+
+fn stamp(path: &Path) -> FileStamp {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Rebuilds a [`LivePrompt`] from its sections.
+pub struct ContextRefresher {
+    prompt: Arc<LivePrompt>,
+    sections: Vec<Arc<dyn ContextSection>>,
+    /// Last text of each section, kept when a section fails.
+    rendered: Mutex<Vec<Option<String>>>,
+    interval: Option<Duration>,
+    watched: Mutex<Vec<(PathBuf, FileStamp)>>,
+    check_interval: Duration,
+    last_refresh: Mutex<Option<Instant>>,
+}
+
+impl ContextRefresher {
+    /// Create a refresher of a prompt, checking for changes every 10 seconds.
+    pub fn new(prompt: Arc<LivePrompt>) -> Self {
+        Self {
+            prompt,
+            sections: Vec::new(),
+            rendered: Mutex::new(Vec::new()),
+            interval: None,
+            watched: Mutex::new(Vec::new()),
+            check_interval: Duration::from_secs(10),
+            last_refresh: Mutex::new(None),
+        }
+    }
+
+    /// Add a section, after those added before.
+    pub fn with_section(mut self, section: Arc<dyn ContextSection>) -> Self {
+        self.sections.push(section);
+        self
+    }
+
+    /// Refresh on this schedule.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// Refresh when a file changes.
+    pub fn watch(self, path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let current = stamp(&path);
+        self.watched.lock().push((path, current));
+        self
+    }
+
+    /// Set how often watched files and the schedule are checked.
+    pub fn with_check_interval(mut self, interval: Duration) -> Self {
+        self.check_interval = interval;
+        self
+    }
+
+    /// The prompt being refreshed.
+    pub fn prompt(&self) -> &Arc<LivePrompt> {
+        &self.prompt
+    }
+
+    /// Render every section and update the prompt. Returns whether the
+    /// prompt changed.
+    pub async fn refresh(&self) -> bool {
+        let mut texts = Vec::with_capacity(self.sections.len());
+        for (i, section) in self.sections.iter().enumerate() {
+            let text = match section.render().await {
+                Ok(text) => Some(text),
+                Err(e) => {
+                    warn!("Context section '{}' failed, keeping its last text: {}", section.name(), e);
+                    self.rendered.lock().get(i).cloned().flatten()
+                }
+            };
+            texts.push(text);
+        }
+        let prompt = texts
+            .iter()
+            .flatten()
+            .filter(|text| !text.trim().is_empty())
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        *self.rendered.lock() = texts;
+        *self.last_refresh.lock() = Some(Instant::now());
+
+        let changed = self.prompt.set(prompt);
+        if changed {
+            info!("Agent context refreshed (generation {})", self.prompt.generation());
+        } else {
+            debug!("Agent context refreshed, unchanged");
+        }
+        changed
+    }
+
+    /// Refresh if a watched file changed or the schedule is due. Returns
+    /// whether the prompt changed.
+    pub async fn tick(&self) -> bool {
+        let file_changed = {
+            let mut watched = self.watched.lock();
+            let mut changed = None;
+            for (path, last) in watched.iter_mut() {
+                let current = stamp(path);
+                if current != *last {
+                    *last = current;
+                    changed.get_or_insert_with(|| path.clone());
+                }
+            }
+            changed
+        };
+        let due = match (self.interval, *self.last_refresh.lock()) {
+            (Some(interval), Some(last)) => last.elapsed() >= interval,
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        match file_changed {
+            Some(path) => info!("{} changed, refreshing agent context", path.display()),
+            None if due => debug!("Scheduled agent context refresh"),
+            None => return false,
+        }
+        self.refresh().await
+    }
+
+    /// Check for changes in a background task until it is aborted.
+    pub fn start(self: Arc<Self>) -> JoinHandle<()> {
+        match self.interval {
+            Some(interval) => info!(
+                "Agent context refresh every {:?}, watching {} file(s)",
+                interval,
+                self.watched.lock().len()
+            ),
+            None => info!("Agent context refresh watching {} file(s)", self.watched.lock().len()),
+        }
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(self.check_interval).await;
+                self.tick().await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+#[path = "context_refresh_tests.rs"]
+mod tests;
@@ -0,0 +1,152 @@
+use super::*;
+
+use autohands_protocols::error::MemoryError;
+use autohands_protocols::memory::{MemoryEntry, MemorySearchResult};
+
+/// Section returning queued results, then repeating the last.
+struct ScriptedSection {
+    results: Mutex<Vec<Result<String, String>>>,
+}
+
+impl ScriptedSection {
+    fn new(results: Vec<Result<&str, &str>>) -> Arc<Self> {
+        let mut results: Vec<_> = results
+            .into_iter()
+            .map(|r| r.map(str::to_string).map_err(str::to_string))
+            .collect();
+        results.reverse();
+        Arc::new(Self {
+            results: Mutex::new(results),
+        })
+    }
+}
+
+#[async_trait]
+impl ContextSection for ScriptedSection {
+    fn name(&self) -> &str {
+        "scripted"
+    }
+
+    async fn render(&self) -> Result<String, String> {
+        let mut results = self.results.lock();
+        if results.len() > 1 {
+            results.pop().unwrap()
+        } else {
+            results[0].clone()
+        }
+    }
+}
+
+/// Memory backend returning fixed entries for any query.
+struct FixedMemory(Vec<&'static str>);
+
+#[async_trait]
+impl MemoryBackend for FixedMemory {
+    fn id(&self) -> &str {
+        "fixed"
+    }
+
+    async fn store(&self, _entry: MemoryEntry) -> Result<String, MemoryError> {
+        Ok("m1".to_string())
+    }
+
+    async fn retrieve(&self, _id: &str) -> Result<Option<MemoryEntry>, MemoryError> {
+        Ok(None)
+    }
+
+    async fn search(&self, query: MemoryQuery) -> Result<Vec<MemorySearchResult>, MemoryError> {
+        Ok(self
+            .0
+            .iter()
+            .take(query.limit)
+            .map(|content| MemorySearchResult {
+                entry: MemoryEntry::new(*content, "fact"),
+                relevance: 1.0,
+            })
+            .collect())
+    }
+
+    async fn delete(&self, _id: &str) -> Result<(), MemoryError> {
+        Ok(())
+    }
+
+    async fn update(&self, _id: &str, _entry: MemoryEntry) -> Result<(), MemoryError> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_refresh_joins_sections_and_keeps_failed_ones() {
+    let prompt = Arc::new(LivePrompt::new(Some("initial".to_string())));
+    let refresher = ContextRefresher::new(prompt.clone())
+        .with_section(Arc::new(TemplateSection::new(None, "You are AutoHands.")))
+        .with_section(ScriptedSection::new(vec![Ok("skills v1"), Err("registry busy"), Ok("skills v2")]))
+        .with_section(ScriptedSection::new(vec![Ok("")]));
+
+    assert!(refresher.refresh().await);
+    assert_eq!(prompt.get().as_deref(), Some("You are AutoHands.\n\nskills v1"));
+    assert_eq!(prompt.generation(), 1);
+
+    // The failing section keeps its last text
+    assert!(!refresher.refresh().await);
+    assert_eq!(prompt.get().as_deref(), Some("You are AutoHands.\n\nskills v1"));
+    assert!(prompt.refreshed_at().is_some());
+
+    assert!(refresher.refresh().await);
+    assert_eq!(prompt.get().as_deref(), Some("You are AutoHands.\n\nskills v2"));
+    assert_eq!(prompt.generation(), 2);
+}
+
+#[tokio::test]
+async fn test_memory_section() {
+    let backend = Arc::new(FixedMemory(vec!["Prefers short answers", "Works in UTC", "Uses vim"]));
+    let section = MemorySection::new(backend, "user preferences", 2);
+    assert_eq!(
+        section.render().await.unwrap(),
+        "## Relevant memories\n- Prefers short answers\n- Works in UTC"
+    );
+
+    let section = MemorySection::new(Arc::new(FixedMemory(Vec::new())), "anything", 5);
+    assert_eq!(section.render().await.unwrap(), "");
+}
+
+#[tokio::test]
+async fn test_tick_refreshes_on_template_change() {
+    let dir = tempfile::tempdir().unwrap();
+    let template = dir.path().join("general.md");
+    std::fs::write(&template, "Version one.\n").unwrap();
+
+    let prompt = Arc::new(LivePrompt::default());
+    let refresher = ContextRefresher::new(prompt.clone())
+        .with_section(Arc::new(TemplateSection::new(Some(template.clone()), "fallback")))
+        .watch(&template);
+    refresher.refresh().await;
+    assert_eq!(prompt.get().as_deref(), Some("Version one."));
+
+    // Nothing changed and no schedule
+    assert!(!refresher.tick().await);
+
+    std::fs::write(&template, "Version two, longer.\n").unwrap();
+    assert!(refresher.tick().await);
+    assert_eq!(prompt.get().as_deref(), Some("Version two, longer."));
+
+    // A removed template falls back to the built-in prompt
+    std::fs::remove_file(&template).unwrap();
+    assert!(refresher.tick().await);
+    assert_eq!(prompt.get().as_deref(), Some("fallback"));
+}
+
+#[tokio::test]
+async fn test_tick_on_schedule() {
+    let prompt = Arc::new(LivePrompt::default());
+    let refresher = ContextRefresher::new(prompt.clone())
+        .with_section(ScriptedSection::new(vec![Ok("one"), Ok("two")]))
+        .with_interval(Duration::from_secs(3600));
+
+    // Due before the first refresh
+    assert!(refresher.tick().await);
+    assert_eq!(prompt.get().as_deref(), Some("one"));
+    // Not due again for an hour
+    assert!(!refresher.tick().await);
+    assert_eq!(prompt.get().as_deref(), Some("one"));
+}
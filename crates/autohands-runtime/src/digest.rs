@@ -0,0 +1,183 @@
+//! Daily memory digests.
+//!
+//! A [`DailyDigest`] reads the transcripts of every session active on a given
+//! (local) day, summarizes their conversation into one text and stores it as a
+//! single `digest` memory entry tagged `digest:<date>`.
+
+use std::sync::Arc;
+
+use chrono::{Local, NaiveDate};
+use thiserror::Error;
+use tracing::{debug, info, warn};
+
+use autohands_protocols::error::ProviderError;
+use autohands_protocols::memory::{MemoryBackend, MemoryEntry};
+use autohands_protocols::types::Message;
+
+use crate::summarizer::Summarizer;
+use crate::transcript::{TranscriptEntry, TranscriptManager};
+
+/// Memory type of digest entries.
+pub const DIGEST_MEMORY_TYPE: &str = "digest";
+
+/// Errors from building a digest.
+#[derive(Debug, Error)]
+pub enum DigestError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Summarization failed: {0}")]
+    Summarize(#[from] ProviderError),
+
+    #[error("Memory error: {0}")]
+    Memory(String),
+}
+
+/// Result of a digest run.
+#[derive(Debug, Clone)]
+pub struct DigestReport {
+    /// Day the digest covers.
+    pub date: NaiveDate,
+    /// Sessions included, sorted by ID.
+    pub sessions: Vec<String>,
+    /// Digest text.
+    pub summary: String,
+    /// ID of the stored memory entry, if a memory backend is set.
+    pub memory_id: Option<String>,
+}
+
+/// Summarizes a day's sessions into one memory entry.
+pub struct DailyDigest {
+    transcripts: Arc<TranscriptManager>,
+    summarizer: Arc<dyn Summarizer>,
+    memory: Option<Arc<dyn MemoryBackend>>,
+}
+
+impl DailyDigest {
+    /// Create a digest over the given transcripts.
+    pub fn new(transcripts: Arc<TranscriptManager>, summarizer: Arc<dyn Summarizer>) -> Self {
+        Self {
+            transcripts,
+            summarizer,
+            memory: None,
+        }
+    }
+
+    /// Store digests in a memory backend.
+    pub fn with_memory(mut self, memory: Arc<dyn MemoryBackend>) -> Self {
+        self.memory = Some(memory);
+        self
+    }
+
+    /// Build the digest for `date`.
+    ///
+    /// Returns `Ok(None)` when no session had messages on that day.
+    pub async fn run(&self, date: NaiveDate) -> Result<Option<DigestReport>, DigestError> {
+        let mut session_ids = self.transcripts.list_transcripts().await?;
+        session_ids.sort();
+
+        let mut sessions = Vec::new();
+        let mut messages = Vec::new();
+        for session_id in session_ids {
+            let day_messages = self.read_session(&session_id, date).await?;
+            if day_messages.is_empty() {
+                continue;
+            }
+            messages.push(Message::system(format!("Session {}", session_id)));
+            messages.extend(day_messages);
+            sessions.push(session_id);
+        }
+
+        if sessions.is_empty() {
+            debug!("No sessions on {}, skipping digest", date);
+            return Ok(None);
+        }
+
+        let summary = self.summarizer.summarize(&messages).await?;
+        let memory_id = match &self.memory {
+            Some(memory) => {
+                let entry = MemoryEntry::new(summary.clone(), DIGEST_MEMORY_TYPE).with_tags(vec![
+                    DIGEST_MEMORY_TYPE.to_string(),
+                    digest_tag(date),
+                ]);
+                let id = memory
+                    .store(entry)
+                    .await
+                    .map_err(|e| DigestError::Memory(e.to_string()))?;
+                Some(id)
+            }
+            None => None,
+        };
+
+        info!("Built digest for {} over {} sessions", date, sessions.len());
+        Ok(Some(DigestReport {
+            date,
+            sessions,
+            summary,
+            memory_id,
+        }))
+    }
+
+    /// User and assistant messages of a session sent on `date` (local time).
+    async fn read_session(
+        &self,
+        session_id: &str,
+        date: NaiveDate,
+    ) -> Result<Vec<Message>, DigestError> {
+        let raw = tokio::fs::read_to_string(self.transcripts.transcript_path(session_id)).await?;
+        let mut messages = Vec::new();
+        for line in raw.lines().filter(|line| !line.trim().is_empty()) {
+            let entry = match serde_json::from_str::<TranscriptEntry>(line) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    warn!("Skipping malformed transcript line in {}: {}", session_id, e);
+                    continue;
+                }
+            };
+            let (timestamp, message, is_user) = match entry {
+                TranscriptEntry::User {
+                    timestamp, message, ..
+                } => (timestamp, message, true),
+                TranscriptEntry::Assistant {
+                    timestamp, message, ..
+                } => (timestamp, message, false),
+                _ => continue,
+            };
+            if timestamp.with_timezone(&Local).date_naive() != date {
+                continue;
+            }
+            let text = content_text(&message.content);
+            if text.is_empty() {
+                continue;
+            }
+            messages.push(if is_user {
+                Message::user(text)
+            } else {
+                Message::assistant(text)
+            });
+        }
+        Ok(messages)
+    }
+}
+
+/// Tag marking the digest of a day.
+pub fn digest_tag(date: NaiveDate) -> String {
+    format!("digest:{}", date)
+}
+
+/// Text of a transcript message: a plain string or the text blocks of an array.
+fn content_text(content: &serde_json::Value) -> String {
+    match content {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Array(blocks) => blocks
+            .iter()
+            .filter_map(|b| b.get("text").and_then(|t| t.as_str()))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+#[path = "digest_tests.rs"]
+mod tests;
@@ -0,0 +1,125 @@
+use super::*;
+
+use async_trait::async_trait;
+use autohands_protocols::error::MemoryError;
+use autohands_protocols::memory::{MemoryQuery, MemorySearchResult};
+use chrono::{Duration, Utc};
+use tokio::sync::Mutex;
+
+use crate::transcript::TranscriptMessage;
+
+/// Summarizer echoing its input, one message per line.
+struct EchoSummarizer;
+
+#[async_trait]
+impl Summarizer for EchoSummarizer {
+    async fn summarize(&self, messages: &[Message]) -> Result<String, ProviderError> {
+        Ok(messages
+            .iter()
+            .map(|m| m.content.text())
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    fn needs_summarization(&self, _message_count: usize) -> bool {
+        false
+    }
+}
+
+#[derive(Default)]
+struct VecMemory {
+    entries: Mutex<Vec<MemoryEntry>>,
+}
+
+#[async_trait]
+impl MemoryBackend for VecMemory {
+    fn id(&self) -> &str {
+        "vec-memory"
+    }
+
+    async fn store(&self, entry: MemoryEntry) -> Result<String, MemoryError> {
+        let mut entries = self.entries.lock().await;
+        entries.push(entry);
+        Ok(format!("m{}", entries.len()))
+    }
+
+    async fn retrieve(&self, _id: &str) -> Result<Option<MemoryEntry>, MemoryError> {
+        Ok(None)
+    }
+
+    async fn search(&self, _query: MemoryQuery) -> Result<Vec<MemorySearchResult>, MemoryError> {
+        Ok(Vec::new())
+    }
+
+    async fn delete(&self, _id: &str) -> Result<(), MemoryError> {
+        Ok(())
+    }
+
+    async fn update(&self, _id: &str, _entry: MemoryEntry) -> Result<(), MemoryError> {
+        Ok(())
+    }
+}
+
+async fn record(manager: &TranscriptManager, session_id: &str, user: &str, assistant: &str) {
+    let writer = manager.get_writer(session_id).await.unwrap();
+    writer.record_user_message(serde_json::json!(user)).await.unwrap();
+    writer
+        .record_assistant_message(
+            serde_json::json!([{"type": "text", "text": assistant}]),
+            Some("end_turn"),
+        )
+        .await
+        .unwrap();
+    manager.remove_writer(session_id).await;
+}
+
+#[tokio::test]
+async fn test_digest_stores_day_summary() {
+    let dir = tempfile::tempdir().unwrap();
+    let transcripts = Arc::new(TranscriptManager::new(dir.path().to_path_buf()));
+    record(&transcripts, "b", "deploy the app", "deployed").await;
+    record(&transcripts, "a", "fix the bug", "fixed").await;
+
+    // A message from two days ago is left out
+    let writer = transcripts.get_writer("old").await.unwrap();
+    writer
+        .write(&TranscriptEntry::User {
+            uuid: "u".to_string(),
+            session_id: "old".to_string(),
+            timestamp: Utc::now() - Duration::days(2),
+            parent_uuid: None,
+            message: TranscriptMessage {
+                role: "user".to_string(),
+                content: serde_json::json!("ancient history"),
+            },
+        })
+        .await
+        .unwrap();
+    transcripts.remove_writer("old").await;
+
+    let memory = Arc::new(VecMemory::default());
+    let digest =
+        DailyDigest::new(transcripts, Arc::new(EchoSummarizer)).with_memory(memory.clone());
+    let today = Local::now().date_naive();
+    let report = digest.run(today).await.unwrap().unwrap();
+
+    assert_eq!(report.sessions, vec!["a", "b"]);
+    assert_eq!(report.memory_id.as_deref(), Some("m1"));
+    assert_eq!(
+        report.summary,
+        "Session a\nfix the bug\nfixed\nSession b\ndeploy the app\ndeployed"
+    );
+
+    let entries = memory.entries.lock().await;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].memory_type, DIGEST_MEMORY_TYPE);
+    assert!(entries[0].tags.contains(&digest_tag(today)));
+}
+
+#[tokio::test]
+async fn test_digest_without_sessions() {
+    let dir = tempfile::tempdir().unwrap();
+    let transcripts = Arc::new(TranscriptManager::new(dir.path().join("missing")));
+    let digest = DailyDigest::new(transcripts, Arc::new(EchoSummarizer));
+    assert!(digest.run(Local::now().date_naive()).await.unwrap().is_none());
+}
@@ -0,0 +1,149 @@
+//! Provider failover.
+//!
+//! [`FailoverProvider`] sends requests to the first available of several
+//! providers, skipping those the [`ProviderHealthTable`] marks unhealthy.
+//! A request failing for a transient reason (network, timeout, overload,
+//! server error) moves on to the next provider; the outcome of every
+//! attempt is recorded in the table.
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use tracing::warn;
+
+use autohands_protocols::error::ProviderError;
+use autohands_protocols::provider::{
+    CompletionRequest, CompletionResponse, CompletionStream, LLMProvider, ModelDefinition,
+    ProviderCapabilities,
+};
+use autohands_protocols::types::Message;
+
+use crate::provider_health::ProviderHealthTable;
+use crate::retry::is_retryable;
+
+/// Provider requests may fail over to.
+#[derive(Clone)]
+pub struct FailoverCandidate {
+    /// The provider.
+    pub provider: Arc<dyn LLMProvider>,
+    /// Model replacing the requested one on this provider. The primary
+    /// provider always keeps the requested model.
+    pub model: Option<String>,
+}
+
+/// Provider failing over between providers by health.
+///
+/// Presents itself as the primary provider (ID, models, capabilities).
+pub struct FailoverProvider {
+    primary: Arc<dyn LLMProvider>,
+    fallbacks: Vec<FailoverCandidate>,
+    health: Arc<ProviderHealthTable>,
+}
+
+impl FailoverProvider {
+    /// Create a failover provider over a primary provider.
+    pub fn new(primary: Arc<dyn LLMProvider>, health: Arc<ProviderHealthTable>) -> Self {
+        Self {
+            primary,
+            fallbacks: Vec::new(),
+            health,
+        }
+    }
+
+    /// Add a fallback, tried after the primary and earlier fallbacks.
+    pub fn with_fallback(mut self, provider: Arc<dyn LLMProvider>, model: Option<String>) -> Self {
+        self.fallbacks.push(FailoverCandidate { provider, model });
+        self
+    }
+
+    /// Requests to try, in order: available providers first, then the
+    /// unhealthy ones, since a provider may have recovered.
+    fn attempts(&self, request: &CompletionRequest) -> Vec<(Arc<dyn LLMProvider>, CompletionRequest)> {
+        let mut all = vec![(self.primary.clone(), request.clone())];
+        for candidate in &self.fallbacks {
+            let mut request = request.clone();
+            if let Some(model) = &candidate.model {
+                request.model = model.clone();
+            }
+            all.push((candidate.provider.clone(), request));
+        }
+        let (mut available, unhealthy): (Vec<_>, Vec<_>) = all
+            .into_iter()
+            .partition(|(provider, _)| self.health.is_available(provider.id()));
+        available.extend(unhealthy);
+        available
+    }
+
+    /// Run an operation against each provider until one succeeds or fails
+    /// for a reason another provider would not fix.
+    async fn run<T, F, Fut>(&self, request: &CompletionRequest, operation: F) -> Result<T, ProviderError>
+    where
+        F: Fn(Arc<dyn LLMProvider>, CompletionRequest) -> Fut,
+        Fut: std::future::Future<Output = Result<T, ProviderError>>,
+    {
+        let attempts = self.attempts(request);
+        let count = attempts.len();
+        let mut last_error = None;
+        for (i, (provider, request)) in attempts.into_iter().enumerate() {
+            let id = provider.id().to_string();
+            let started = Instant::now();
+            match operation(provider, request).await {
+                Ok(result) => {
+                    self.health.record_success(&id, started.elapsed());
+                    return Ok(result);
+                }
+                Err(e) if should_fail_over(&e) => {
+                    self.health.record_failure(&id, &e);
+                    if i + 1 < count {
+                        warn!("Provider {} failed: {}, failing over", id, e);
+                    }
+                    last_error = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| ProviderError::Network("No provider available".to_string())))
+    }
+}
+
+/// Whether another provider may succeed where one failed.
+fn should_fail_over(error: &ProviderError) -> bool {
+    is_retryable(error) || matches!(error, ProviderError::StreamError(_))
+}
+
+#[async_trait]
+impl LLMProvider for FailoverProvider {
+    fn id(&self) -> &str {
+        self.primary.id()
+    }
+
+    fn models(&self) -> &[ModelDefinition] {
+        self.primary.models()
+    }
+
+    fn capabilities(&self) -> &ProviderCapabilities {
+        self.primary.capabilities()
+    }
+
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse, ProviderError> {
+        self.run(&request, |provider, request| async move { provider.complete(request).await })
+            .await
+    }
+
+    /// Fails over on the initial connection only.
+    async fn complete_stream(&self, request: CompletionRequest) -> Result<CompletionStream, ProviderError> {
+        self.run(&request, |provider, request| async move {
+            provider.complete_stream(request).await
+        })
+        .await
+    }
+
+    async fn count_tokens(&self, messages: &[Message], model: &str) -> Result<u32, ProviderError> {
+        self.primary.count_tokens(messages, model).await
+    }
+}
+
+#[cfg(test)]
+#[path = "failover_tests.rs"]
+mod tests;
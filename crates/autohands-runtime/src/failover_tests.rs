@@ -0,0 +1,129 @@
+use super::*;
+use crate::provider_health::ProviderStatus;
+use autohands_protocols::provider::ProviderCapabilities;
+use autohands_protocols::types::{StopReason, Usage};
+use parking_lot::Mutex;
+use std::time::Duration;
+
+/// Provider failing with a fixed error, or answering with its ID.
+struct FixedProvider {
+    id: String,
+    error: Mutex<Option<fn() -> ProviderError>>,
+    models: Mutex<Vec<String>>,
+}
+
+impl FixedProvider {
+    fn new(id: &str, error: Option<fn() -> ProviderError>) -> Arc<Self> {
+        Arc::new(Self {
+            id: id.to_string(),
+            error: Mutex::new(error),
+            models: Mutex::new(Vec::new()),
+        })
+    }
+}
+
+#[async_trait]
+impl LLMProvider for FixedProvider {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn models(&self) -> &[ModelDefinition] {
+        &[]
+    }
+
+    fn capabilities(&self) -> &ProviderCapabilities {
+        &ProviderCapabilities {
+            streaming: false,
+            tool_calling: true,
+            vision: false,
+            json_mode: false,
+            prompt_caching: false,
+            batching: false,
+            max_concurrent: None,
+        }
+    }
+
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse, ProviderError> {
+        self.models.lock().push(request.model.clone());
+        if let Some(error) = *self.error.lock() {
+            return Err(error());
+        }
+        Ok(CompletionResponse {
+            id: "response".to_string(),
+            model: request.model,
+            message: Message::assistant(self.id.clone()),
+            stop_reason: StopReason::EndTurn,
+            usage: Usage::default(),
+            metadata: Default::default(),
+        })
+    }
+
+    async fn complete_stream(&self, _: CompletionRequest) -> Result<CompletionStream, ProviderError> {
+        Err(ProviderError::Network("down".to_string()))
+    }
+}
+
+fn overloaded() -> ProviderError {
+    ProviderError::ApiError {
+        status: 529,
+        message: "Overloaded".to_string(),
+    }
+}
+
+fn request() -> CompletionRequest {
+    CompletionRequest::new("primary-model", vec![Message::user("Hello")])
+}
+
+#[tokio::test]
+async fn test_fails_over_on_transient_errors() {
+    let primary = FixedProvider::new("primary", Some(overloaded));
+    let backup = FixedProvider::new("backup", None);
+    let health = Arc::new(ProviderHealthTable::new(1));
+    let failover = FailoverProvider::new(primary.clone(), health.clone())
+        .with_fallback(backup.clone(), Some("backup-model".to_string()));
+    assert_eq!(failover.id(), "primary");
+
+    let response = failover.complete(request()).await.unwrap();
+    assert_eq!(response.message.content.text(), "backup");
+    assert_eq!(*backup.models.lock(), vec!["backup-model"]);
+    assert_eq!(health.status("primary"), ProviderStatus::Unhealthy);
+    assert_eq!(health.status("backup"), ProviderStatus::Healthy);
+
+    // The unhealthy primary is skipped, before a request reaches it
+    failover.complete(request()).await.unwrap();
+    assert_eq!(primary.models.lock().len(), 1);
+
+    // Once it recovers, the primary is used again with the requested model
+    health.record_success("primary", Duration::from_millis(10));
+    *primary.error.lock() = None;
+    let response = failover.complete(request()).await.unwrap();
+    assert_eq!(response.message.content.text(), "primary");
+    assert_eq!(primary.models.lock()[1], "primary-model");
+}
+
+#[tokio::test]
+async fn test_keeps_request_errors() {
+    let primary = FixedProvider::new(
+        "primary",
+        Some(|| ProviderError::InvalidRequest("bad tool schema".to_string())),
+    );
+    let backup = FixedProvider::new("backup", None);
+    let health = Arc::new(ProviderHealthTable::new(1));
+    let failover = FailoverProvider::new(primary, health.clone()).with_fallback(backup.clone(), None);
+
+    // Another provider would reject the request too
+    let err = failover.complete(request()).await.unwrap_err();
+    assert!(matches!(err, ProviderError::InvalidRequest(_)));
+    assert!(backup.models.lock().is_empty());
+    assert_eq!(health.status("primary"), ProviderStatus::Unknown);
+
+    // With every provider down, the last error is returned
+    let failover = FailoverProvider::new(FixedProvider::new("a", Some(overloaded)), health.clone())
+        .with_fallback(
+            FixedProvider::new("b", Some(|| ProviderError::Timeout(30))),
+            None,
+        );
+    let err = failover.complete(request()).await.unwrap_err();
+    assert!(matches!(err, ProviderError::Timeout(_)));
+}
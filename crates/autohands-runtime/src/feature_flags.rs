@@ -0,0 +1,213 @@
+//! Runtime feature flags.
+//!
+//! Risky capabilities are gated by named flags evaluated on every use, so
+//! operators can roll them on or off without redeploying the daemon. A flag
+//! resolves, most specific first, from:
+//!
+//! 1. an admin override for the workspace,
+//! 2. a global admin override,
+//! 3. the configured value for the workspace,
+//! 4. the configured global value,
+//! 5. the flag's built-in default.
+//!
+//! A workspace is the working directory an agent runs in.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+
+/// Run a turn's tool calls concurrently instead of one after another.
+pub const PARALLEL_TOOLS: &str = "parallel_tools";
+
+/// Run risky tools without asking for human approval.
+pub const AUTO_APPROVAL: &str = "auto_approval";
+
+/// Prefix of the flags gating providers, e.g. `provider:gemini`.
+pub const PROVIDER_FLAG_PREFIX: &str = "provider:";
+
+/// Provider request metadata naming the workspace, so providers can be
+/// gated per workspace.
+pub const WORKSPACE_METADATA: &str = "workspace";
+
+/// Flag gating a provider.
+pub fn provider_flag(provider_id: &str) -> String {
+    format!("{}{}", PROVIDER_FLAG_PREFIX, provider_id)
+}
+
+/// Workspace key of a working directory.
+pub fn workspace_key(work_dir: &Path) -> String {
+    work_dir.display().to_string()
+}
+
+/// Built-in default of a flag.
+///
+/// Providers are enabled unless turned off; every other flag is off unless
+/// turned on.
+pub fn default_value(flag: &str) -> bool {
+    flag.starts_with(PROVIDER_FLAG_PREFIX)
+}
+
+/// Where a flag's value comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FlagSource {
+    /// Built-in default.
+    Default,
+    /// Configuration file.
+    Config,
+    /// Admin API override.
+    Override,
+}
+
+/// Resolved value of a flag.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlagState {
+    /// Flag name.
+    pub name: String,
+    /// Whether the flag is on.
+    pub enabled: bool,
+    /// Where the value comes from.
+    pub source: FlagSource,
+    /// Workspace the value applies to; global when absent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+}
+
+/// Flag values of one scope, global or a workspace.
+type Scope = HashMap<String, bool>;
+
+/// Feature flags with configured values and admin overrides.
+#[derive(Default)]
+pub struct FeatureFlags {
+    config: Scope,
+    workspace_config: HashMap<String, Scope>,
+    overrides: RwLock<Scope>,
+    workspace_overrides: RwLock<HashMap<String, Scope>>,
+}
+
+impl FeatureFlags {
+    /// Create flags with only built-in defaults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create flags from configured global and per-workspace values.
+    pub fn from_config(flags: Scope, workspaces: HashMap<String, Scope>) -> Self {
+        Self {
+            config: flags,
+            workspace_config: workspaces,
+            ..Self::default()
+        }
+    }
+
+    /// Whether a flag is on, for a workspace or globally.
+    pub fn is_enabled(&self, flag: &str, workspace: Option<&str>) -> bool {
+        self.resolve(flag, workspace).0
+    }
+
+    /// Whether a flag is on for an agent's working directory.
+    pub fn is_enabled_in(&self, flag: &str, work_dir: Option<&Path>) -> bool {
+        let workspace = work_dir.map(workspace_key);
+        self.is_enabled(flag, workspace.as_deref())
+    }
+
+    /// Resolved value of a flag.
+    pub fn state(&self, flag: &str, workspace: Option<&str>) -> FlagState {
+        let (enabled, source, scoped) = self.resolve(flag, workspace);
+        FlagState {
+            name: flag.to_string(),
+            enabled,
+            source,
+            workspace: workspace.filter(|_| scoped).map(str::to_string),
+        }
+    }
+
+    /// Resolved values of the known flags and every configured or
+    /// overridden one, sorted by name.
+    pub fn list(&self, workspace: Option<&str>) -> Vec<FlagState> {
+        let mut names: Vec<String> = vec![PARALLEL_TOOLS.to_string(), AUTO_APPROVAL.to_string()];
+        names.extend(self.config.keys().cloned());
+        names.extend(self.overrides.read().keys().cloned());
+        if let Some(workspace) = workspace {
+            if let Some(scope) = self.workspace_config.get(workspace) {
+                names.extend(scope.keys().cloned());
+            }
+            if let Some(scope) = self.workspace_overrides.read().get(workspace) {
+                names.extend(scope.keys().cloned());
+            }
+        }
+        names.sort();
+        names.dedup();
+        names.iter().map(|name| self.state(name, workspace)).collect()
+    }
+
+    /// Override a flag, for a workspace or globally.
+    pub fn set_override(&self, flag: &str, workspace: Option<&str>, enabled: bool) {
+        match workspace {
+            Some(workspace) => {
+                self.workspace_overrides
+                    .write()
+                    .entry(workspace.to_string())
+                    .or_default()
+                    .insert(flag.to_string(), enabled);
+            }
+            None => {
+                self.overrides.write().insert(flag.to_string(), enabled);
+            }
+        }
+    }
+
+    /// Drop an override, returning whether there was one.
+    pub fn clear_override(&self, flag: &str, workspace: Option<&str>) -> bool {
+        match workspace {
+            Some(workspace) => {
+                let mut overrides = self.workspace_overrides.write();
+                let Some(scope) = overrides.get_mut(workspace) else {
+                    return false;
+                };
+                let removed = scope.remove(flag).is_some();
+                if scope.is_empty() {
+                    overrides.remove(workspace);
+                }
+                removed
+            }
+            None => self.overrides.write().remove(flag).is_some(),
+        }
+    }
+
+    /// Value, source, and whether it is workspace-specific.
+    fn resolve(&self, flag: &str, workspace: Option<&str>) -> (bool, FlagSource, bool) {
+        if let Some(workspace) = workspace {
+            let value = self
+                .workspace_overrides
+                .read()
+                .get(workspace)
+                .and_then(|scope| scope.get(flag).copied());
+            if let Some(enabled) = value {
+                return (enabled, FlagSource::Override, true);
+            }
+        }
+        if let Some(&enabled) = self.overrides.read().get(flag) {
+            return (enabled, FlagSource::Override, false);
+        }
+        if let Some(workspace) = workspace {
+            let value = self
+                .workspace_config
+                .get(workspace)
+                .and_then(|scope| scope.get(flag).copied());
+            if let Some(enabled) = value {
+                return (enabled, FlagSource::Config, true);
+            }
+        }
+        if let Some(&enabled) = self.config.get(flag) {
+            return (enabled, FlagSource::Config, false);
+        }
+        (default_value(flag), FlagSource::Default, false)
+    }
+}
+
+#[cfg(test)]
+#[path = "feature_flags_tests.rs"]
+mod tests;
@@ -0,0 +1,85 @@
+use super::*;
+
+fn scope(entries: &[(&str, bool)]) -> HashMap<String, bool> {
+    entries.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+}
+
+#[test]
+fn test_builtin_defaults() {
+    let flags = FeatureFlags::new();
+    assert!(!flags.is_enabled(PARALLEL_TOOLS, None));
+    assert!(!flags.is_enabled(AUTO_APPROVAL, Some("/repo")));
+    assert!(flags.is_enabled(&provider_flag("gemini"), None));
+    assert_eq!(flags.state(PARALLEL_TOOLS, None).source, FlagSource::Default);
+}
+
+#[test]
+fn test_config_per_workspace() {
+    let flags = FeatureFlags::from_config(
+        scope(&[(PARALLEL_TOOLS, true), ("provider:gemini", false)]),
+        HashMap::from([(
+            "/repo".to_string(),
+            scope(&[(PARALLEL_TOOLS, false), ("provider:gemini", true)]),
+        )]),
+    );
+
+    assert!(flags.is_enabled(PARALLEL_TOOLS, None));
+    assert!(flags.is_enabled(PARALLEL_TOOLS, Some("/other")));
+    assert!(!flags.is_enabled(PARALLEL_TOOLS, Some("/repo")));
+    assert!(!flags.is_enabled(&provider_flag("gemini"), None));
+    assert!(flags.is_enabled_in(&provider_flag("gemini"), Some(Path::new("/repo"))));
+
+    let state = flags.state(PARALLEL_TOOLS, Some("/repo"));
+    assert_eq!(state.source, FlagSource::Config);
+    assert_eq!(state.workspace.as_deref(), Some("/repo"));
+    assert!(flags.state(PARALLEL_TOOLS, Some("/other")).workspace.is_none());
+}
+
+#[test]
+fn test_overrides_take_precedence() {
+    let flags = FeatureFlags::from_config(
+        scope(&[(AUTO_APPROVAL, false)]),
+        HashMap::from([("/repo".to_string(), scope(&[(AUTO_APPROVAL, true)]))]),
+    );
+
+    flags.set_override(AUTO_APPROVAL, None, true);
+    assert!(flags.is_enabled(AUTO_APPROVAL, None));
+    assert_eq!(flags.state(AUTO_APPROVAL, None).source, FlagSource::Override);
+
+    flags.set_override(AUTO_APPROVAL, Some("/repo"), false);
+    assert!(!flags.is_enabled(AUTO_APPROVAL, Some("/repo")));
+    assert!(flags.is_enabled(AUTO_APPROVAL, Some("/other")));
+
+    assert!(flags.clear_override(AUTO_APPROVAL, Some("/repo")));
+    assert!(!flags.clear_override(AUTO_APPROVAL, Some("/repo")));
+    // The global override still beats the workspace config
+    assert!(flags.is_enabled(AUTO_APPROVAL, Some("/repo")));
+
+    assert!(flags.clear_override(AUTO_APPROVAL, None));
+    assert!(flags.is_enabled(AUTO_APPROVAL, Some("/repo")));
+    assert!(!flags.is_enabled(AUTO_APPROVAL, None));
+}
+
+#[test]
+fn test_list() {
+    let flags = FeatureFlags::from_config(scope(&[("provider:ark", false)]), HashMap::new());
+    flags.set_override("beta_ui", Some("/repo"), true);
+
+    let global: Vec<String> = flags.list(None).into_iter().map(|s| s.name).collect();
+    assert_eq!(global, vec![AUTO_APPROVAL, PARALLEL_TOOLS, "provider:ark"]);
+
+    let scoped = flags.list(Some("/repo"));
+    let beta = scoped.iter().find(|s| s.name == "beta_ui").unwrap();
+    assert!(beta.enabled);
+    assert_eq!(beta.source, FlagSource::Override);
+}
+
+#[test]
+fn test_flag_state_serialization() {
+    let flags = FeatureFlags::new();
+    let json = serde_json::to_value(flags.state(PARALLEL_TOOLS, None)).unwrap();
+    assert_eq!(json["name"], "parallel_tools");
+    assert_eq!(json["enabled"], false);
+    assert_eq!(json["source"], "default");
+    assert!(json.get("workspace").is_none());
+}
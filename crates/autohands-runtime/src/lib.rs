@@ -5,28 +5,67 @@
 pub mod agent_loop;
 pub mod checkpoint;
 pub mod context_builder;
+pub mod context_refresh;
+pub mod digest;
+pub mod failover;
+pub mod feature_flags;
 pub mod history;
 pub mod memory_persistence;
+pub mod overrides;
+pub mod progress;
+pub mod project;
+pub mod provider_health;
+pub mod redaction;
 pub mod retry;
 pub mod runtime;
 pub mod session;
+pub mod session_bundle;
 pub mod session_store;
+pub mod stream_buffer;
 pub mod streaming;
 pub mod summarizer;
+pub mod summary_strategy;
+mod tool_pipeline;
+pub mod tool_invoker;
+pub mod trace;
 pub mod transcript;
+pub mod turn_observer;
 
-pub use agent_loop::{AgentLoop, AgentLoopConfig};
+pub use agent_loop::{AgentLoop, AgentLoopConfig, ToolApproval};
 pub use checkpoint::{CheckpointData, CheckpointSupport};
 pub use context_builder::{ContextBuilder, ContextConfig};
+pub use context_refresh::{
+    ContextRefresher, ContextSection, LivePrompt, MemorySection, TemplateSection,
+};
+pub use digest::{DailyDigest, DigestError, DigestReport, DIGEST_MEMORY_TYPE};
+pub use failover::{FailoverCandidate, FailoverProvider};
+pub use feature_flags::{FeatureFlags, FlagSource, FlagState};
 pub use history::HistoryManager;
+pub use overrides::OverridePolicy;
+pub use progress::{PlanStep, ProgressTracker, TaskProgress, OUTPUT_TAIL_BYTES, PROGRESS_CAPACITY};
+pub use project::{ProjectBrief, ProjectDetector, DEFAULT_BRIEF_TOKENS};
+pub use provider_health::{
+    probe_provider, ProbeConfig, ProviderHealth, ProviderHealthTable, ProviderProber, ProviderStatus,
+};
+pub use redaction::RedactingProvider;
 pub use retry::{is_retryable, RetryConfig, RetryProvider};
-pub use runtime::{AgentRuntime, AgentRuntimeConfig};
+pub use runtime::{AgentRuntime, AgentRuntimeConfig, ShutdownReport};
 pub use session::{Session, SessionManager};
+pub use session_bundle::{
+    export_session, import_session, BundleError, ImportReport, SessionArtifact, SessionBundle,
+};
 pub use session_store::{
     FileSessionStore, MemorySessionStore, SessionCleaner, SessionStore, SessionStoreError,
 };
+pub use stream_buffer::{complete_buffered, StreamBuffer};
 pub use streaming::{AgentEventStream, ChunkProcessor, StreamEvent, StreamingAgentLoop};
 pub use summarizer::{
     ConversationSummary, HistoryCompressor, LLMSummarizer, Summarizer, SummarizerConfig,
 };
+pub use summary_strategy::{StrategySummarizer, SummaryStrategy};
+pub use tool_invoker::{InvokeError, ToolInvoker, AUDIT_TARGET, DEFAULT_INVOKE_TIMEOUT};
+pub use trace::{
+    Trace, TraceEvent, TraceEventKind, TraceLog, MAX_TRACE_EVENTS, TRACE_LOG_CAPACITY,
+};
 pub use transcript::{TranscriptEntry, TranscriptManager, TranscriptWriter};
+pub use turn_observer::TurnObserver;
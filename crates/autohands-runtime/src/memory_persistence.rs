@@ -40,11 +40,13 @@ pub(crate) fn format_memory_context(results: &[MemorySearchResult]) -> String {
 /// Extract key information from messages and persist to memory backend.
 ///
 /// `flush_tag` distinguishes the source: `"auto-flush"` (context overflow) vs
-/// `"session-end-flush"` (normal/max-turns exit).
+/// `"session-end-flush"` (normal/max-turns exit). Entries are also tagged with
+/// the session so they can be exported with it.
 pub(crate) async fn flush_memories_to_backend(
     messages: &[Message],
     memory: &Arc<dyn MemoryBackend>,
     flush_tag: &str,
+    session_id: &str,
 ) {
     use autohands_protocols::memory::MemoryEntry;
 
@@ -87,7 +89,7 @@ pub(crate) async fn flush_memories_to_backend(
                 if content.len() > 10 && content.len() < 2000 {
                     let entry = MemoryEntry::new(content, *memory_type)
                         .with_importance(0.6)
-                        .with_tags(vec![flush_tag.to_string()]);
+                        .with_tags(vec![flush_tag.to_string(), session_tag(session_id)]);
                     match memory.store(entry).await {
                         Ok(id) => {
                             debug!("Memory flush: stored {} entry (id: {})", memory_type, id);
@@ -173,7 +175,7 @@ pub(crate) async fn store_session_summary(
         .with_importance(0.4)
         .with_tags(vec![
             "session-summary".to_string(),
-            format!("session:{}", session_id),
+            session_tag(session_id),
         ]);
 
     match memory.store(entry).await {
@@ -189,6 +191,11 @@ pub(crate) async fn store_session_summary(
     }
 }
 
+/// Tag marking memory entries created during a session.
+pub fn session_tag(session_id: &str) -> String {
+    format!("session:{}", session_id)
+}
+
 /// Find the nearest char boundary at or before `index` (stable replacement for
 /// `str::floor_char_boundary`).
 pub(crate) fn floor_char_boundary(s: &str, index: usize) -> usize {
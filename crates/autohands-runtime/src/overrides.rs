@@ -0,0 +1,65 @@
+//! Policy for per-run overrides.
+//!
+//! Callers may override an agent's model, temperature, output token limit
+//! and tools for a single run (see [`RunOverrides`]). The agent checks the
+//! overrides against its provider; the [`OverridePolicy`] lets the operator
+//! narrow them further.
+
+use autohands_protocols::agent::RunOverrides;
+use autohands_protocols::error::AgentError;
+
+/// Limits on per-run overrides.
+#[derive(Debug, Clone)]
+pub struct OverridePolicy {
+    /// Whether runs may override the agent's configuration at all.
+    pub enabled: bool,
+    /// Models runs may switch to (empty = any model the agent supports).
+    pub allowed_models: Vec<String>,
+    /// Highest output token limit a run may ask for.
+    pub max_tokens: Option<u32>,
+}
+
+impl Default for OverridePolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            allowed_models: Vec::new(),
+            max_tokens: None,
+        }
+    }
+}
+
+impl OverridePolicy {
+    /// Check a run's overrides against the policy.
+    pub fn check(&self, overrides: &RunOverrides) -> Result<(), AgentError> {
+        if overrides.is_empty() {
+            return Ok(());
+        }
+        if !self.enabled {
+            return Err(AgentError::InvalidOverride(
+                "per-run overrides are disabled".to_string(),
+            ));
+        }
+        if let Some(model) = &overrides.model {
+            if !self.allowed_models.is_empty() && !self.allowed_models.contains(model) {
+                return Err(AgentError::InvalidOverride(format!(
+                    "model '{}' is not allowed",
+                    model
+                )));
+            }
+        }
+        if let (Some(requested), Some(max)) = (overrides.max_tokens, self.max_tokens) {
+            if requested > max {
+                return Err(AgentError::InvalidOverride(format!(
+                    "max_tokens {} exceeds the allowed {}",
+                    requested, max
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "overrides_tests.rs"]
+mod tests;
@@ -0,0 +1,44 @@
+use super::*;
+
+fn model(model: &str) -> RunOverrides {
+    RunOverrides {
+        model: Some(model.to_string()),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_default_policy_allows_overrides() {
+    let policy = OverridePolicy::default();
+    assert!(policy.check(&model("any")).is_ok());
+    assert!(policy.check(&RunOverrides::default()).is_ok());
+}
+
+#[test]
+fn test_disabled_policy_rejects_overrides() {
+    let policy = OverridePolicy {
+        enabled: false,
+        ..Default::default()
+    };
+    assert!(policy.check(&RunOverrides::default()).is_ok());
+    let err = policy.check(&model("any")).unwrap_err();
+    assert!(err.to_string().contains("disabled"));
+}
+
+#[test]
+fn test_policy_limits() {
+    let policy = OverridePolicy {
+        enabled: true,
+        allowed_models: vec!["fast".to_string()],
+        max_tokens: Some(2048),
+    };
+    assert!(policy.check(&model("fast")).is_ok());
+    assert!(matches!(policy.check(&model("slow")), Err(AgentError::InvalidOverride(_))));
+
+    let tokens = |n| RunOverrides {
+        max_tokens: Some(n),
+        ..Default::default()
+    };
+    assert!(policy.check(&tokens(2048)).is_ok());
+    assert!(policy.check(&tokens(4096)).is_err());
+}
@@ -0,0 +1,235 @@
+//! Per-session execution progress.
+//!
+//! A [`TaskProgress`] is assembled from the [`StreamEvent`]s an agent loop
+//! emits: the current turn, the last tool invoked, tokens used so far, the
+//! plan step (for agents that report one), the tail of the running tool's
+//! output and a rough ETA. The
+//! [`ProgressTracker`] keeps the progress of recent sessions so status APIs
+//! can report more than whether a session runs.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::streaming::StreamEvent;
+
+/// Number of sessions whose progress is kept.
+pub const PROGRESS_CAPACITY: usize = 1000;
+
+/// Bytes of a running tool's output kept in the progress.
+pub const OUTPUT_TAIL_BYTES: usize = 4096;
+
+/// A step of the agent's plan.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanStep {
+    /// 1-based index of the current step.
+    pub index: u32,
+    /// Number of steps in the plan.
+    pub total: u32,
+    /// Step description.
+    pub title: String,
+}
+
+/// Progress of an agent session.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskProgress {
+    /// Current (or last) turn.
+    pub turn: u32,
+    /// Turns finished so far.
+    pub turns_completed: u32,
+    /// Name of the last tool invoked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_tool: Option<String>,
+    /// Tool calls made so far.
+    pub tool_calls: u32,
+    /// Last output of the current tool, for tools that stream it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_output: Option<String>,
+    /// Prompt tokens used so far.
+    pub prompt_tokens: u64,
+    /// Completion tokens used so far.
+    pub completion_tokens: u64,
+    /// Total tokens used so far.
+    pub tokens_used: u64,
+    /// Current plan step, when the agent runs with a plan.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plan_step: Option<PlanStep>,
+    /// Estimated seconds until the agent answers.
+    ///
+    /// Average turn duration times the turns left: the plan steps left when
+    /// there is a plan, otherwise one. Unknown before the first turn ends.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eta_seconds: Option<u64>,
+    /// Whether the session finished (completed or failed).
+    pub finished: bool,
+    /// When the session started.
+    pub started_at: DateTime<Utc>,
+    /// When the progress last changed.
+    pub updated_at: DateTime<Utc>,
+    /// Start of the current turn.
+    #[serde(skip)]
+    turn_started_at: Option<DateTime<Utc>>,
+    /// Time spent in finished turns.
+    #[serde(skip)]
+    turn_time_ms: i64,
+}
+
+impl TaskProgress {
+    /// Progress of a session starting now.
+    pub fn new() -> Self {
+        Self::starting_at(Utc::now())
+    }
+
+    fn starting_at(at: DateTime<Utc>) -> Self {
+        Self {
+            turn: 0,
+            turns_completed: 0,
+            last_tool: None,
+            tool_calls: 0,
+            tool_output: None,
+            prompt_tokens: 0,
+            completion_tokens: 0,
+            tokens_used: 0,
+            plan_step: None,
+            eta_seconds: None,
+            finished: false,
+            started_at: at,
+            updated_at: at,
+            turn_started_at: None,
+            turn_time_ms: 0,
+        }
+    }
+
+    /// Update the progress with an event.
+    pub fn apply(&mut self, event: &StreamEvent) {
+        self.apply_at(event, Utc::now());
+    }
+
+    pub(crate) fn apply_at(&mut self, event: &StreamEvent, at: DateTime<Utc>) {
+        match event {
+            StreamEvent::TurnStart { turn } => {
+                self.turn = *turn;
+                self.turn_started_at = Some(at);
+            }
+            StreamEvent::ToolCallStart { name, .. } => {
+                self.last_tool = Some(name.clone());
+                self.tool_calls += 1;
+                self.tool_output = None;
+            }
+            StreamEvent::ToolOutput { content, .. } => {
+                let output = self.tool_output.get_or_insert_with(String::new);
+                output.push_str(content);
+                if output.len() > OUTPUT_TAIL_BYTES {
+                    let mut start = output.len() - OUTPUT_TAIL_BYTES;
+                    while !output.is_char_boundary(start) {
+                        start += 1;
+                    }
+                    output.drain(..start);
+                }
+            }
+            StreamEvent::Usage { usage } => {
+                self.prompt_tokens += u64::from(usage.prompt_tokens);
+                self.completion_tokens += u64::from(usage.completion_tokens);
+                self.tokens_used += u64::from(usage.total_tokens);
+            }
+            StreamEvent::PlanStep { index, total, title } => {
+                self.plan_step = Some(PlanStep {
+                    index: *index,
+                    total: *total,
+                    title: title.clone(),
+                });
+            }
+            StreamEvent::TurnComplete { .. } => {
+                if let Some(started) = self.turn_started_at.take() {
+                    self.turn_time_ms += (at - started).num_milliseconds().max(0);
+                    self.turns_completed += 1;
+                }
+                self.eta_seconds = self.estimate();
+            }
+            StreamEvent::Complete { .. } => {
+                self.finished = true;
+                self.eta_seconds = Some(0);
+            }
+            StreamEvent::Error { .. } => {
+                self.finished = true;
+                self.eta_seconds = None;
+            }
+            StreamEvent::TextDelta { .. }
+            | StreamEvent::ToolCallDelta { .. }
+            | StreamEvent::ToolCallComplete { .. } => {}
+        }
+        self.updated_at = at;
+    }
+
+    fn estimate(&self) -> Option<u64> {
+        if self.turns_completed == 0 {
+            return None;
+        }
+        let turns_left = match &self.plan_step {
+            Some(step) => u64::from(step.total.saturating_sub(step.index).max(1)),
+            None => 1,
+        };
+        let avg_ms = self.turn_time_ms as u64 / u64::from(self.turns_completed);
+        Some((avg_ms * turns_left).div_ceil(1000))
+    }
+}
+
+impl Default for TaskProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Progress of recent sessions.
+///
+/// Only the last [`PROGRESS_CAPACITY`] sessions are kept; finished ones are
+/// evicted first.
+#[derive(Default)]
+pub struct ProgressTracker {
+    sessions: Mutex<HashMap<String, TaskProgress>>,
+}
+
+impl ProgressTracker {
+    /// Create an empty tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reset a session's progress as it starts running.
+    pub fn start(&self, session_id: &str) {
+        let mut sessions = self.sessions.lock();
+        if sessions.len() >= PROGRESS_CAPACITY && !sessions.contains_key(session_id) {
+            let oldest = sessions
+                .iter()
+                .min_by_key(|(_, progress)| (!progress.finished, progress.updated_at))
+                .map(|(id, _)| id.clone());
+            if let Some(oldest) = oldest {
+                sessions.remove(&oldest);
+            }
+        }
+        sessions.insert(session_id.to_string(), TaskProgress::new());
+    }
+
+    /// Update a session's progress with an event.
+    pub fn record(&self, session_id: &str, event: &StreamEvent) {
+        if let Some(progress) = self.sessions.lock().get_mut(session_id) {
+            progress.apply(event);
+        }
+    }
+
+    /// Progress of a session.
+    pub fn get(&self, session_id: &str) -> Option<TaskProgress> {
+        self.sessions.lock().get(session_id).cloned()
+    }
+
+    /// Forget a session's progress.
+    pub fn remove(&self, session_id: &str) -> Option<TaskProgress> {
+        self.sessions.lock().remove(session_id)
+    }
+}
+
+#[cfg(test)]
+#[path = "progress_tests.rs"]
+mod tests;
@@ -0,0 +1,153 @@
+use super::*;
+use autohands_protocols::types::{Message, Usage};
+use chrono::Duration;
+
+fn usage(total: u32) -> Usage {
+    Usage {
+        prompt_tokens: total - 1,
+        completion_tokens: 1,
+        total_tokens: total,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_progress_from_events() {
+    let start = Utc::now();
+    let mut progress = TaskProgress::starting_at(start);
+
+    progress.apply_at(&StreamEvent::TurnStart { turn: 1 }, start);
+    progress.apply_at(&StreamEvent::Usage { usage: usage(100) }, start);
+    progress.apply_at(
+        &StreamEvent::ToolCallStart {
+            id: "1".to_string(),
+            name: "read_file".to_string(),
+        },
+        start,
+    );
+    assert_eq!(progress.turn, 1);
+    assert_eq!(progress.last_tool.as_deref(), Some("read_file"));
+    assert_eq!(progress.tokens_used, 100);
+    assert_eq!(progress.eta_seconds, None);
+
+    progress.apply_at(
+        &StreamEvent::TurnComplete { turn: 1 },
+        start + Duration::seconds(4),
+    );
+    assert_eq!(progress.turns_completed, 1);
+    assert_eq!(progress.eta_seconds, Some(4));
+    assert!(!progress.finished);
+}
+
+#[test]
+fn test_progress_eta_uses_plan() {
+    let start = Utc::now();
+    let mut progress = TaskProgress::starting_at(start);
+
+    progress.apply_at(
+        &StreamEvent::PlanStep {
+            index: 2,
+            total: 5,
+            title: "Write tests".to_string(),
+        },
+        start,
+    );
+    progress.apply_at(&StreamEvent::TurnStart { turn: 1 }, start);
+    progress.apply_at(
+        &StreamEvent::TurnComplete { turn: 1 },
+        start + Duration::seconds(2),
+    );
+
+    assert_eq!(progress.plan_step.as_ref().unwrap().title, "Write tests");
+    // Three steps left at two seconds a turn
+    assert_eq!(progress.eta_seconds, Some(6));
+}
+
+#[test]
+fn test_progress_finished() {
+    let mut progress = TaskProgress::new();
+    progress.apply(&StreamEvent::Complete {
+        message: Message::assistant("done"),
+    });
+    assert!(progress.finished);
+    assert_eq!(progress.eta_seconds, Some(0));
+
+    let mut progress = TaskProgress::new();
+    progress.apply(&StreamEvent::Error {
+        error: "boom".to_string(),
+    });
+    assert!(progress.finished);
+    assert_eq!(progress.eta_seconds, None);
+}
+
+#[test]
+fn test_progress_tool_output_tail() {
+    let output = |content: String| StreamEvent::ToolOutput {
+        id: "1".to_string(),
+        stream: autohands_protocols::tool::OutputStream::Stdout,
+        content,
+    };
+    let mut progress = TaskProgress::new();
+    progress.apply(&output("Compiling".to_string()));
+    progress.apply(&output("é".repeat(OUTPUT_TAIL_BYTES)));
+    let tail = progress.tool_output.clone().unwrap();
+    assert_eq!(tail.len(), OUTPUT_TAIL_BYTES);
+    assert!(tail.chars().all(|c| c == 'é'));
+
+    // A new tool call starts with no output
+    progress.apply(&StreamEvent::ToolCallStart {
+        id: "2".to_string(),
+        name: "exec".to_string(),
+    });
+    assert_eq!(progress.tool_output, None);
+}
+
+#[test]
+fn test_progress_serialization() {
+    let mut progress = TaskProgress::new();
+    progress.apply(&StreamEvent::TurnStart { turn: 3 });
+
+    let json = serde_json::to_value(&progress).unwrap();
+    assert_eq!(json["turn"], 3);
+    assert_eq!(json["tokens_used"], 0);
+    assert!(json.get("last_tool").is_none());
+    assert!(json.get("turn_started_at").is_none());
+}
+
+#[test]
+fn test_tracker_records_started_sessions() {
+    let tracker = ProgressTracker::new();
+
+    // Events for sessions that did not start are ignored
+    tracker.record("s1", &StreamEvent::TurnStart { turn: 1 });
+    assert!(tracker.get("s1").is_none());
+
+    tracker.start("s1");
+    tracker.record("s1", &StreamEvent::TurnStart { turn: 1 });
+    assert_eq!(tracker.get("s1").unwrap().turn, 1);
+
+    // Starting again resets the progress
+    tracker.start("s1");
+    assert_eq!(tracker.get("s1").unwrap().turn, 0);
+
+    assert!(tracker.remove("s1").is_some());
+    assert!(tracker.get("s1").is_none());
+}
+
+#[test]
+fn test_tracker_evicts_finished_first() {
+    let tracker = ProgressTracker::new();
+    tracker.start("running");
+    tracker.start("done");
+    tracker.record("done", &StreamEvent::Error {
+        error: "boom".to_string(),
+    });
+    for i in 2..PROGRESS_CAPACITY {
+        tracker.start(&format!("s{}", i));
+    }
+
+    tracker.start("new");
+    assert!(tracker.get("done").is_none());
+    assert!(tracker.get("running").is_some());
+    assert!(tracker.get("new").is_some());
+}
@@ -0,0 +1,186 @@
+//! Project detection for coding sessions.
+//!
+//! When a session starts, the agent knows nothing about the workspace and
+//! typically spends its first turns listing files and reading manifests.
+//! A [`ProjectDetector`] does that up front: it identifies the stack from
+//! the manifests in the working directory, reads them and the README, and
+//! renders a [`ProjectBrief`] that fits a token budget.
+
+use std::path::{Path, PathBuf};
+
+use tracing::debug;
+
+/// Manifests identifying a stack, in the order they are read.
+const MANIFESTS: &[(&str, &str)] = &[
+    ("Cargo.toml", "Rust"),
+    ("package.json", "Node.js"),
+    ("pyproject.toml", "Python"),
+    ("setup.py", "Python"),
+    ("requirements.txt", "Python"),
+    ("go.mod", "Go"),
+];
+
+/// README file names, most common first.
+const READMES: &[&str] = &["README.md", "README", "README.rst", "README.txt"];
+
+/// Default token budget of a brief.
+pub const DEFAULT_BRIEF_TOKENS: usize = 1500;
+
+/// Characters per token used to turn the budget into a length.
+const CHARS_PER_TOKEN: usize = 4;
+
+/// What a session needs to know about its workspace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectBrief {
+    /// Working directory of the session.
+    pub work_dir: PathBuf,
+    /// Detected stacks, e.g. `["Rust", "Node.js"]`.
+    pub stacks: Vec<String>,
+    /// Excerpts of the manifests and the README, by file name.
+    pub excerpts: Vec<(String, String)>,
+}
+
+impl ProjectBrief {
+    /// The brief as a system message.
+    pub fn render(&self) -> String {
+        let mut text = format!("## Project brief\nWorkspace: {}", self.work_dir.display());
+        if !self.stacks.is_empty() {
+            text.push_str(&format!("\nStack: {}", self.stacks.join(", ")));
+        }
+        for (file, excerpt) in &self.excerpts {
+            text.push_str(&format!("\n\n### {}\n{}", file, excerpt));
+        }
+        text
+    }
+}
+
+/// Builds project briefs of working directories.
+#[derive(Debug, Clone)]
+pub struct ProjectDetector {
+    max_tokens: usize,
+}
+
+impl Default for ProjectDetector {
+    fn default() -> Self {
+        Self::new(DEFAULT_BRIEF_TOKENS)
+    }
+}
+
+impl ProjectDetector {
+    /// Create a detector whose briefs stay within `max_tokens`.
+    pub fn new(max_tokens: usize) -> Self {
+        Self { max_tokens }
+    }
+
+    /// Brief of `work_dir`, or `None` when it has neither a known manifest
+    /// nor a README.
+    pub async fn detect(&self, work_dir: &Path) -> Option<ProjectBrief> {
+        let mut budget = self.max_tokens * CHARS_PER_TOKEN;
+        let mut stacks: Vec<String> = Vec::new();
+        let mut excerpts = Vec::new();
+
+        for (file, stack) in MANIFESTS {
+            let Ok(text) = tokio::fs::read_to_string(work_dir.join(file)).await else {
+                continue;
+            };
+            if !stacks.iter().any(|s| s == stack) {
+                stacks.push(stack.to_string());
+            }
+            let summary = if *file == "package.json" {
+                summarize_package_json(&text)
+            } else {
+                strip_comments(&text)
+            };
+            // Half of what is left, so the README and later manifests fit
+            let excerpt = excerpt(&summary, budget / 2);
+            budget = budget.saturating_sub(excerpt.len());
+            if !excerpt.is_empty() {
+                excerpts.push((file.to_string(), excerpt));
+            }
+        }
+
+        for file in READMES {
+            if let Ok(text) = tokio::fs::read_to_string(work_dir.join(file)).await {
+                let excerpt = excerpt(&text, budget);
+                if !excerpt.is_empty() {
+                    excerpts.push((file.to_string(), excerpt));
+                }
+                break;
+            }
+        }
+
+        if stacks.is_empty() && excerpts.is_empty() {
+            return None;
+        }
+        debug!("Detected project in {}: {:?}", work_dir.display(), stacks);
+        Some(ProjectBrief {
+            work_dir: work_dir.to_path_buf(),
+            stacks,
+            excerpts,
+        })
+    }
+}
+
+/// Name, description, scripts and dependencies of a `package.json`; the
+/// file itself if it does not parse.
+fn summarize_package_json(text: &str) -> String {
+    let Ok(manifest) = serde_json::from_str::<serde_json::Value>(text) else {
+        return text.to_string();
+    };
+    let mut lines = Vec::new();
+    for key in ["name", "description"] {
+        if let Some(value) = manifest[key].as_str() {
+            lines.push(format!("{}: {}", key, value));
+        }
+    }
+    if let Some(scripts) = manifest["scripts"].as_object() {
+        lines.push("scripts:".to_string());
+        for (name, command) in scripts {
+            lines.push(format!("  {}: {}", name, command.as_str().unwrap_or_default()));
+        }
+    }
+    for key in ["dependencies", "devDependencies"] {
+        if let Some(deps) = manifest[key].as_object() {
+            let names: Vec<&str> = deps.keys().map(String::as_str).collect();
+            lines.push(format!("{}: {}", key, names.join(", ")));
+        }
+    }
+    lines.join("\n")
+}
+
+/// Text without blank and `#` comment lines.
+fn strip_comments(text: &str) -> String {
+    text.lines()
+        .filter(|line| {
+            let line = line.trim();
+            !line.is_empty() && !line.starts_with('#')
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Whole lines of `text` up to `max_chars`, marked when cut.
+fn excerpt(text: &str, max_chars: usize) -> String {
+    let text = text.trim();
+    if text.len() <= max_chars {
+        return text.to_string();
+    }
+    let mut cut = String::new();
+    for line in text.lines() {
+        // Leave room for the marker
+        if cut.len() + line.len() + 5 > max_chars {
+            break;
+        }
+        cut.push_str(line);
+        cut.push('\n');
+    }
+    if cut.is_empty() {
+        return cut;
+    }
+    cut.push('…');
+    cut
+}
+
+#[cfg(test)]
+#[path = "project_tests.rs"]
+mod tests;
@@ -0,0 +1,52 @@
+use super::*;
+
+#[tokio::test]
+async fn test_detect_rust_and_node_project() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("Cargo.toml"),
+        "# The app\n[package]\nname = \"app\"\n\n[dependencies]\ntokio = \"1\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("package.json"),
+        r#"{"name": "web", "scripts": {"build": "vite build"}, "dependencies": {"vue": "^3"}}"#,
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("README.md"), "# App\n\nDoes things.\n").unwrap();
+
+    let brief = ProjectDetector::default().detect(dir.path()).await.unwrap();
+    assert_eq!(brief.stacks, vec!["Rust", "Node.js"]);
+    let files: Vec<_> = brief.excerpts.iter().map(|(file, _)| file.as_str()).collect();
+    assert_eq!(files, vec!["Cargo.toml", "package.json", "README.md"]);
+    assert_eq!(brief.excerpts[0].1, "[package]\nname = \"app\"\n[dependencies]\ntokio = \"1\"");
+    assert!(brief.excerpts[1].1.contains("  build: vite build"));
+    assert!(brief.excerpts[1].1.contains("dependencies: vue"));
+
+    let text = brief.render();
+    assert!(text.starts_with("## Project brief\nWorkspace: "));
+    assert!(text.contains("Stack: Rust, Node.js"));
+    assert!(text.contains("### README.md\n# App\n\nDoes things."));
+}
+
+#[tokio::test]
+async fn test_brief_stays_within_budget() {
+    let dir = tempfile::tempdir().unwrap();
+    let requirements: String = (0..500).map(|i| format!("package{}==1.0\n", i)).collect();
+    std::fs::write(dir.path().join("requirements.txt"), requirements).unwrap();
+    std::fs::write(dir.path().join("README"), "Intro line\n".repeat(500)).unwrap();
+
+    let brief = ProjectDetector::new(100).detect(dir.path()).await.unwrap();
+    assert_eq!(brief.stacks, vec!["Python"]);
+    let total: usize = brief.excerpts.iter().map(|(_, excerpt)| excerpt.len()).sum();
+    assert!(total <= 400);
+    assert!(brief.excerpts.iter().all(|(_, excerpt)| excerpt.ends_with('…')));
+    assert_eq!(brief.excerpts[1].0, "README");
+}
+
+#[tokio::test]
+async fn test_no_project() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("notes.txt"), "hello").unwrap();
+    assert!(ProjectDetector::default().detect(dir.path()).await.is_none());
+}
@@ -0,0 +1,270 @@
+//! Provider health probing.
+//!
+//! [`ProviderProber`] pings every registered provider on an interval with a
+//! one-token completion on its cheapest model, recording the outcome in a
+//! [`ProviderHealthTable`]. Real requests through a
+//! [`FailoverProvider`](crate::failover::FailoverProvider) are recorded there
+//! too, so a dead provider is skipped before a user request reaches it, and
+//! the table is reported by the `/health` endpoint.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use autohands_core::registry::ProviderRegistry;
+use autohands_protocols::error::ProviderError;
+use autohands_protocols::provider::{CompletionRequest, LLMProvider};
+use autohands_protocols::types::Message;
+
+/// Health of a provider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderStatus {
+    /// Not checked yet.
+    Unknown,
+    /// The last request or probe succeeded.
+    Healthy,
+    /// Recent requests or probes kept failing.
+    Unhealthy,
+}
+
+/// Health record of a provider.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderHealth {
+    /// Provider ID.
+    pub provider_id: String,
+    /// Current status.
+    pub status: ProviderStatus,
+    /// Failures since the last success.
+    pub consecutive_failures: u32,
+    /// Latency of the last success, in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    /// Error of the last failure.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// When the provider was last checked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checked_at: Option<DateTime<Utc>>,
+}
+
+impl ProviderHealth {
+    fn new(provider_id: &str) -> Self {
+        Self {
+            provider_id: provider_id.to_string(),
+            status: ProviderStatus::Unknown,
+            consecutive_failures: 0,
+            latency_ms: None,
+            last_error: None,
+            checked_at: None,
+        }
+    }
+}
+
+/// Health of the providers, fed by probes and real requests.
+pub struct ProviderHealthTable {
+    entries: RwLock<HashMap<String, ProviderHealth>>,
+    /// Consecutive failures that make a provider unhealthy.
+    unhealthy_after: u32,
+}
+
+impl ProviderHealthTable {
+    /// Create a table marking providers unhealthy after `unhealthy_after`
+    /// consecutive failures (at least one).
+    pub fn new(unhealthy_after: u32) -> Self {
+        Self {
+            entries: RwLock::new(HashMap::new()),
+            unhealthy_after: unhealthy_after.max(1),
+        }
+    }
+
+    /// Record a successful request or probe.
+    pub fn record_success(&self, provider_id: &str, latency: Duration) {
+        let mut entries = self.entries.write();
+        let entry = entries
+            .entry(provider_id.to_string())
+            .or_insert_with(|| ProviderHealth::new(provider_id));
+        if entry.status == ProviderStatus::Unhealthy {
+            info!("Provider {} is healthy again", provider_id);
+        }
+        entry.status = ProviderStatus::Healthy;
+        entry.consecutive_failures = 0;
+        entry.latency_ms = Some(latency.as_millis() as u64);
+        entry.checked_at = Some(Utc::now());
+    }
+
+    /// Record a failed request or probe.
+    pub fn record_failure(&self, provider_id: &str, error: &ProviderError) {
+        let mut entries = self.entries.write();
+        let entry = entries
+            .entry(provider_id.to_string())
+            .or_insert_with(|| ProviderHealth::new(provider_id));
+        entry.consecutive_failures += 1;
+        entry.last_error = Some(error.to_string());
+        entry.checked_at = Some(Utc::now());
+        if entry.consecutive_failures >= self.unhealthy_after
+            && entry.status != ProviderStatus::Unhealthy
+        {
+            warn!(
+                "Provider {} is unhealthy after {} failure(s): {}",
+                provider_id, entry.consecutive_failures, error
+            );
+            entry.status = ProviderStatus::Unhealthy;
+        }
+    }
+
+    /// Health record of a provider.
+    pub fn get(&self, provider_id: &str) -> Option<ProviderHealth> {
+        self.entries.read().get(provider_id).cloned()
+    }
+
+    /// Status of a provider (`Unknown` when never checked).
+    pub fn status(&self, provider_id: &str) -> ProviderStatus {
+        self.entries
+            .read()
+            .get(provider_id)
+            .map(|entry| entry.status)
+            .unwrap_or(ProviderStatus::Unknown)
+    }
+
+    /// Whether requests should go to a provider.
+    pub fn is_available(&self, provider_id: &str) -> bool {
+        self.status(provider_id) != ProviderStatus::Unhealthy
+    }
+
+    /// All health records, ordered by provider ID.
+    pub fn snapshot(&self) -> Vec<ProviderHealth> {
+        let mut entries: Vec<_> = self.entries.read().values().cloned().collect();
+        entries.sort_by(|a, b| a.provider_id.cmp(&b.provider_id));
+        entries
+    }
+}
+
+impl Default for ProviderHealthTable {
+    fn default() -> Self {
+        Self::new(2)
+    }
+}
+
+/// Probe configuration.
+#[derive(Debug, Clone)]
+pub struct ProbeConfig {
+    /// Time between probe rounds.
+    pub interval: Duration,
+    /// Time a probe may take before it counts as failed.
+    pub timeout: Duration,
+    /// Model to probe, by provider ID. Otherwise the provider's cheapest
+    /// model is used.
+    pub models: HashMap<String, String>,
+}
+
+impl Default for ProbeConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(60),
+            timeout: Duration::from_secs(15),
+            models: HashMap::new(),
+        }
+    }
+}
+
+/// Cheapest model of a provider by input price, or its first model.
+fn cheapest_model(provider: &dyn LLMProvider) -> Option<String> {
+    let models = provider.models();
+    models
+        .iter()
+        .filter(|m| m.input_cost_per_million.is_some())
+        .min_by(|a, b| {
+            a.input_cost_per_million
+                .partial_cmp(&b.input_cost_per_million)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .or_else(|| models.first())
+        .map(|m| m.id.clone())
+}
+
+/// Send a one-token completion to a provider, returning its latency.
+pub async fn probe_provider(
+    provider: &dyn LLMProvider,
+    model: &str,
+    timeout: Duration,
+) -> Result<Duration, ProviderError> {
+    let request = CompletionRequest::new(model, vec![Message::user("ping")]).with_max_tokens(1);
+    let started = Instant::now();
+    match tokio::time::timeout(timeout, provider.complete(request)).await {
+        Ok(Ok(_)) => Ok(started.elapsed()),
+        Ok(Err(e)) => Err(e),
+        Err(_) => Err(ProviderError::Timeout(timeout.as_secs())),
+    }
+}
+
+/// Periodically probes the registered providers.
+pub struct ProviderProber {
+    registry: Arc<ProviderRegistry>,
+    table: Arc<ProviderHealthTable>,
+    config: ProbeConfig,
+}
+
+impl ProviderProber {
+    /// Create a prober recording into a table.
+    pub fn new(
+        registry: Arc<ProviderRegistry>,
+        table: Arc<ProviderHealthTable>,
+        config: ProbeConfig,
+    ) -> Self {
+        Self {
+            registry,
+            table,
+            config,
+        }
+    }
+
+    /// Probe every registered provider once, concurrently.
+    pub async fn probe_all(&self) {
+        let probes = self.registry.list_ids().into_iter().filter_map(|id| {
+            let provider = self.registry.get(&id)?;
+            let model = self
+                .config
+                .models
+                .get(&id)
+                .cloned()
+                .or_else(|| cheapest_model(provider.as_ref()));
+            let Some(model) = model else {
+                debug!("Provider {} has no model to probe", id);
+                return None;
+            };
+            Some(async move {
+                let result = probe_provider(provider.as_ref(), &model, self.config.timeout).await;
+                match result {
+                    Ok(latency) => {
+                        debug!("Provider {} probe ok in {:?}", id, latency);
+                        self.table.record_success(&id, latency);
+                    }
+                    Err(e) => self.table.record_failure(&id, &e),
+                }
+            })
+        });
+        futures::future::join_all(probes).await;
+    }
+
+    /// Probe in a background task until it is aborted.
+    pub fn start(self) -> JoinHandle<()> {
+        info!("Provider health probes every {:?}", self.config.interval);
+        tokio::spawn(async move {
+            loop {
+                self.probe_all().await;
+                tokio::time::sleep(self.config.interval).await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+#[path = "provider_health_tests.rs"]
+mod tests;
@@ -0,0 +1,133 @@
+use super::*;
+use async_trait::async_trait;
+use autohands_protocols::provider::{
+    CompletionResponse, CompletionStream, ModelDefinition, ProviderCapabilities,
+};
+use autohands_protocols::types::{StopReason, Usage};
+
+/// Provider answering or failing every request, recording the requests.
+struct ProbedProvider {
+    id: String,
+    models: Vec<ModelDefinition>,
+    error: Option<fn() -> ProviderError>,
+    requests: parking_lot::Mutex<Vec<CompletionRequest>>,
+}
+
+impl ProbedProvider {
+    fn new(id: &str, error: Option<fn() -> ProviderError>) -> Self {
+        let priced = |id: &str, cost: f64| ModelDefinition {
+            input_cost_per_million: Some(cost),
+            ..ModelDefinition::new(id, id)
+        };
+        Self {
+            id: id.to_string(),
+            models: vec![
+                ModelDefinition::new("unpriced", "Unpriced"),
+                priced("large", 15.0),
+                priced("small", 0.25),
+            ],
+            error,
+            requests: parking_lot::Mutex::new(Vec::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl LLMProvider for ProbedProvider {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn models(&self) -> &[ModelDefinition] {
+        &self.models
+    }
+
+    fn capabilities(&self) -> &ProviderCapabilities {
+        &ProviderCapabilities {
+            streaming: false,
+            tool_calling: true,
+            vision: false,
+            json_mode: false,
+            prompt_caching: false,
+            batching: false,
+            max_concurrent: None,
+        }
+    }
+
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse, ProviderError> {
+        self.requests.lock().push(request.clone());
+        if let Some(error) = self.error {
+            return Err(error());
+        }
+        Ok(CompletionResponse {
+            id: "probe".to_string(),
+            model: request.model,
+            message: Message::assistant("pong"),
+            stop_reason: StopReason::MaxTokens,
+            usage: Usage::default(),
+            metadata: Default::default(),
+        })
+    }
+
+    async fn complete_stream(&self, _: CompletionRequest) -> Result<CompletionStream, ProviderError> {
+        Err(ProviderError::InvalidRequest("not streaming".to_string()))
+    }
+}
+
+#[test]
+fn test_health_table_transitions() {
+    let table = ProviderHealthTable::new(2);
+    assert_eq!(table.status("openai"), ProviderStatus::Unknown);
+    assert!(table.is_available("openai"));
+
+    let error = ProviderError::Network("connection refused".to_string());
+    table.record_failure("openai", &error);
+    // One failure is not enough
+    assert!(table.is_available("openai"));
+    table.record_failure("openai", &error);
+    assert_eq!(table.status("openai"), ProviderStatus::Unhealthy);
+    let health = table.get("openai").unwrap();
+    assert_eq!(health.consecutive_failures, 2);
+    assert!(health.last_error.unwrap().contains("connection refused"));
+
+    table.record_success("openai", Duration::from_millis(120));
+    let health = table.get("openai").unwrap();
+    assert_eq!(health.status, ProviderStatus::Healthy);
+    assert_eq!(health.consecutive_failures, 0);
+    assert_eq!(health.latency_ms, Some(120));
+
+    table.record_success("anthropic", Duration::from_millis(80));
+    let ids: Vec<_> = table.snapshot().into_iter().map(|h| h.provider_id).collect();
+    assert_eq!(ids, vec!["anthropic", "openai"]);
+}
+
+#[tokio::test]
+async fn test_probe_all() {
+    let registry = Arc::new(ProviderRegistry::new());
+    let up = Arc::new(ProbedProvider::new("up", None));
+    let down = Arc::new(ProbedProvider::new(
+        "down",
+        Some(|| ProviderError::ApiError {
+            status: 503,
+            message: "unavailable".to_string(),
+        }),
+    ));
+    registry.register(up.clone()).unwrap();
+    registry.register(down.clone()).unwrap();
+
+    let table = Arc::new(ProviderHealthTable::new(1));
+    let config = ProbeConfig {
+        models: HashMap::from([("down".to_string(), "large".to_string())]),
+        ..Default::default()
+    };
+    ProviderProber::new(registry, table.clone(), config).probe_all().await;
+
+    assert_eq!(table.status("up"), ProviderStatus::Healthy);
+    assert_eq!(table.status("down"), ProviderStatus::Unhealthy);
+
+    // The cheapest model, unless configured, with a single output token
+    let request = up.requests.lock()[0].clone();
+    assert_eq!(request.model, "small");
+    assert_eq!(request.max_tokens, Some(1));
+    assert_eq!(down.requests.lock()[0].model, "large");
+}
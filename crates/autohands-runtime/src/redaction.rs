@@ -0,0 +1,73 @@
+//! Redacted provider context.
+//!
+//! [`RedactingProvider`] scrubs secrets from the system prompt and messages
+//! of every request before passing it on, so credentials that reach the
+//! conversation (pasted by a user, echoed by a tool) are not sent to the
+//! LLM provider.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use autohands_core::redact::{redact_message, Redactor};
+use autohands_protocols::error::ProviderError;
+use autohands_protocols::provider::{
+    CompletionRequest, CompletionResponse, CompletionStream, LLMProvider, ModelDefinition,
+    ProviderCapabilities,
+};
+use autohands_protocols::types::Message;
+
+/// Provider redacting requests before they reach another provider.
+///
+/// Presents itself as the provider it wraps (ID, models, capabilities).
+pub struct RedactingProvider {
+    inner: Arc<dyn LLMProvider>,
+    redactor: Arc<dyn Redactor>,
+}
+
+impl RedactingProvider {
+    pub fn new(inner: Arc<dyn LLMProvider>, redactor: Arc<dyn Redactor>) -> Self {
+        Self { inner, redactor }
+    }
+
+    fn redact(&self, mut request: CompletionRequest) -> CompletionRequest {
+        if let Some(system) = &request.system {
+            request.system = Some(self.redactor.redact(system).into_owned());
+        }
+        for message in &mut request.messages {
+            redact_message(self.redactor.as_ref(), message);
+        }
+        request
+    }
+}
+
+#[async_trait]
+impl LLMProvider for RedactingProvider {
+    fn id(&self) -> &str {
+        self.inner.id()
+    }
+
+    fn models(&self) -> &[ModelDefinition] {
+        self.inner.models()
+    }
+
+    fn capabilities(&self) -> &ProviderCapabilities {
+        self.inner.capabilities()
+    }
+
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse, ProviderError> {
+        self.inner.complete(self.redact(request)).await
+    }
+
+    async fn complete_stream(&self, request: CompletionRequest) -> Result<CompletionStream, ProviderError> {
+        self.inner.complete_stream(self.redact(request)).await
+    }
+
+    async fn count_tokens(&self, messages: &[Message], model: &str) -> Result<u32, ProviderError> {
+        self.inner.count_tokens(messages, model).await
+    }
+}
+
+#[cfg(test)]
+#[path = "redaction_tests.rs"]
+mod tests;
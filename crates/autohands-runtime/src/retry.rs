@@ -77,9 +77,9 @@ pub fn is_retryable(error: &ProviderError) -> bool {
     }
 }
 
-/// Check if HTTP status code is retryable.
+/// Check if HTTP status code is retryable (529: provider overloaded).
 fn is_retryable_status(status: u16) -> bool {
-    matches!(status, 429 | 500 | 502 | 503 | 504)
+    matches!(status, 429 | 500 | 502 | 503 | 504 | 529)
 }
 
 /// Provider wrapper with retry capability.
@@ -104,6 +104,7 @@
         assert!(is_retryable_status(502));
         assert!(is_retryable_status(503));
         assert!(is_retryable_status(504));
+        assert!(is_retryable_status(529));
         assert!(!is_retryable_status(400));
         assert!(!is_retryable_status(401));
         assert!(!is_retryable_status(404));
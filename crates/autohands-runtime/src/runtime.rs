@@ -5,16 +5,24 @@ use std::sync::Arc;
 use dashmap::DashMap;
 use tokio::sync::Semaphore;
 
+use autohands_core::audit::AuditLog;
+use autohands_core::redact::Redactor;
 use autohands_core::registry::{ProviderRegistry, ToolRegistry};
 use autohands_protocols::agent::Agent;
 use autohands_protocols::memory::MemoryBackend;
-use autohands_protocols::tool::AbortSignal;
+use autohands_protocols::tool::{AbortSignal, SandboxPolicy};
 
-use crate::agent_loop::AgentLoopConfig;
+use crate::agent_loop::{AgentLoopConfig, ToolApproval};
 use crate::checkpoint::CheckpointSupport;
+use crate::feature_flags::FeatureFlags;
 use crate::summarizer::HistoryCompressor;
 use crate::history::HistoryManager;
+use crate::overrides::OverridePolicy;
+use crate::progress::ProgressTracker;
+use crate::project::ProjectDetector;
+use crate::trace::TraceLog;
 use crate::session::SessionManager;
+use crate::turn_observer::TurnObserver;
 
 #[path = "runtime_impl.rs"]
 mod runtime_impl;
@@ -51,6 +59,22 @@ pub struct AgentHandle {
     pub abort_signal: Arc<AbortSignal>,
 }
 
+/// Outcome of shutting the runtime down.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShutdownReport {
+    /// Sessions running when shutdown began, all of them cancelled.
+    pub cancelled: Vec<String>,
+    /// Sessions still running when the grace period ran out.
+    pub forced: Vec<String>,
+}
+
+impl ShutdownReport {
+    /// Whether every run stopped within the grace period.
+    pub fn is_clean(&self) -> bool {
+        self.forced.is_empty()
+    }
+}
+
 /// The agent runtime manages agent execution.
 pub struct AgentRuntime {
     provider_registry: Arc<ProviderRegistry>,
@@ -59,9 +83,22 @@ pub struct AgentRuntime {
     history_manager: Arc<HistoryManager>,
     agents: DashMap<String, Arc<dyn Agent>>,
     running: DashMap<String, AgentHandle>,
+    /// Parent of every run's abort signal, aborted on shutdown.
+    root_signal: Arc<AbortSignal>,
     concurrency_semaphore: Arc<Semaphore>,
     config: AgentRuntimeConfig,
     checkpoint: Option<Arc<dyn CheckpointSupport>>,
     compressor: Option<Arc<HistoryCompressor>>,
     memory_backend: Option<Arc<dyn MemoryBackend>>,
+    approval: Option<ToolApproval>,
+    turn_observer: Option<Arc<dyn TurnObserver>>,
+    progress: Arc<ProgressTracker>,
+    trace_log: Arc<TraceLog>,
+    feature_flags: Arc<FeatureFlags>,
+    override_policy: OverridePolicy,
+    project_detector: Option<Arc<ProjectDetector>>,
+    audit_log: Option<Arc<AuditLog>>,
+    redactor: Option<Arc<dyn Redactor>>,
+    /// Sandbox of direct tool invocations.
+    invoke_sandbox: Option<Arc<SandboxPolicy>>,
 }
@@ -4,20 +4,44 @@ use std::sync::Arc;
 
 use tracing::{info, warn};
 
-use autohands_protocols::agent::AgentContext;
+use autohands_core::audit::AuditLog;
+use autohands_core::redact::Redactor;
+use autohands_protocols::agent::{AgentContext, RunOverrides, RunScheduling};
 use autohands_protocols::error::AgentError;
 use autohands_protocols::memory::MemoryBackend;
-use autohands_protocols::tool::AbortSignal;
+use autohands_protocols::tool::{AbortSignal, SandboxPolicy};
 use autohands_protocols::types::Message;
 
-use crate::agent_loop::AgentLoop;
+use crate::agent_loop::{AgentLoop, ToolApproval};
 use crate::checkpoint::CheckpointSupport;
+use crate::feature_flags::FeatureFlags;
 use crate::summarizer::HistoryCompressor;
 use crate::history::HistoryManager;
+use crate::overrides::OverridePolicy;
+use crate::progress::{ProgressTracker, TaskProgress};
+use crate::project::ProjectDetector;
 use crate::session::SessionManager;
+use crate::tool_invoker::ToolInvoker;
+use crate::trace::TraceLog;
 use crate::transcript::TranscriptWriter;
-
-use super::{AgentHandle, AgentRuntime, AgentRuntimeConfig};
+use crate::turn_observer::TurnObserver;
+
+use super::{AgentHandle, AgentRuntime, AgentRuntimeConfig, ShutdownReport};
+
+/// How often shutdown checks whether aborted runs have stopped.
+const SHUTDOWN_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(20);
+
+/// Per-run settings of an execution.
+#[derive(Default)]
+struct RunSettings {
+    transcript: Option<Arc<TranscriptWriter>>,
+    correlation_id: Option<String>,
+    overrides: RunOverrides,
+    scheduling: RunScheduling,
+    /// Signal of the run that started this one.
+    parent_signal: Option<Arc<AbortSignal>>,
+    dry_run: bool,
+}
 
 impl AgentRuntime {
     /// Create a new agent runtime.
@@ -33,14 +57,43 @@ impl AgentRuntime {
             history_manager: Arc::new(HistoryManager::new()),
             agents: dashmap::DashMap::new(),
             running: dashmap::DashMap::new(),
+            root_signal: Arc::new(AbortSignal::new()),
             concurrency_semaphore: Arc::new(tokio::sync::Semaphore::new(config.max_concurrent)),
             config,
             checkpoint: None,
             compressor: None,
             memory_backend: None,
+            approval: None,
+            turn_observer: None,
+            progress: Arc::new(ProgressTracker::new()),
+            trace_log: Arc::new(TraceLog::new()),
+            feature_flags: Arc::new(FeatureFlags::new()),
+            override_policy: OverridePolicy::default(),
+            project_detector: None,
+            audit_log: None,
+            redactor: None,
+            invoke_sandbox: None,
         }
     }
 
+    /// Record agent steps in a shared trace log.
+    pub fn with_trace_log(mut self, trace_log: Arc<TraceLog>) -> Self {
+        self.trace_log = trace_log;
+        self
+    }
+
+    /// Gate risky agent loop capabilities with feature flags.
+    pub fn with_feature_flags(mut self, flags: Arc<FeatureFlags>) -> Self {
+        self.feature_flags = flags;
+        self
+    }
+
+    /// Limit the overrides runs may ask for.
+    pub fn with_override_policy(mut self, policy: OverridePolicy) -> Self {
+        self.override_policy = policy;
+        self
+    }
+
     /// Set checkpoint support for agent loops.
     pub fn with_checkpoint(mut self, checkpoint: Arc<dyn CheckpointSupport>) -> Self {
         self.checkpoint = Some(checkpoint);
@@ -59,6 +112,72 @@ impl AgentRuntime {
         self
     }
 
+    /// Require human approval for risky tool calls.
+    pub fn with_approval(mut self, approval: ToolApproval) -> Self {
+        self.approval = Some(approval);
+        self
+    }
+
+    /// Notify an observer after every agent turn.
+    pub fn with_turn_observer(mut self, observer: Arc<dyn TurnObserver>) -> Self {
+        self.turn_observer = Some(observer);
+        self
+    }
+
+    /// Brief new sessions on the project in their working directory.
+    pub fn with_project_detector(mut self, detector: Arc<ProjectDetector>) -> Self {
+        self.project_detector = Some(detector);
+        self
+    }
+
+    /// Record tool calls and agent runs in an audit log.
+    pub fn with_audit_log(mut self, audit_log: Arc<AuditLog>) -> Self {
+        self.audit_log = Some(audit_log);
+        self
+    }
+
+    /// Confine direct tool invocations (API, workflow steps) to a sandbox;
+    /// runs use their agent's.
+    pub fn with_invoke_sandbox(mut self, sandbox: SandboxPolicy) -> Self {
+        self.invoke_sandbox = Some(Arc::new(sandbox));
+        self
+    }
+
+    /// Scrub secrets from tool results of runs and direct invocations.
+    pub fn with_redactor(mut self, redactor: Arc<dyn Redactor>) -> Self {
+        self.redactor = Some(redactor);
+        self
+    }
+
+    /// Invoker running single tool calls under the same approval policy,
+    /// audit log and redaction as agent tool calls.
+    pub fn tool_invoker(&self) -> ToolInvoker {
+        let mut invoker = ToolInvoker::new(self.tool_registry.clone());
+        if let Some(approval) = &self.approval {
+            invoker = invoker.with_approval(approval.clone());
+        }
+        if let Some(audit_log) = &self.audit_log {
+            invoker = invoker.with_audit_log(audit_log.clone());
+        }
+        if let Some(redactor) = &self.redactor {
+            invoker = invoker.with_redactor(redactor.clone());
+        }
+        if let Some(sandbox) = &self.invoke_sandbox {
+            invoker = invoker.with_sandbox(sandbox.clone());
+        }
+        invoker
+    }
+
+    /// Memory backend, if one is configured.
+    pub fn memory_backend(&self) -> Option<&Arc<dyn MemoryBackend>> {
+        self.memory_backend.as_ref()
+    }
+
+    /// Redactor scrubbing secrets, if redaction is enabled.
+    pub fn redactor(&self) -> Option<&Arc<dyn Redactor>> {
+        self.redactor.as_ref()
+    }
+
     /// Get history manager.
     pub fn history_manager(&self) -> &Arc<HistoryManager> {
         &self.history_manager
@@ -104,6 +223,132 @@ impl AgentRuntime {
         message: Message,
         transcript: Option<Arc<TranscriptWriter>>,
     ) -> Result<Vec<Message>, AgentError> {
+        let settings = RunSettings {
+            transcript,
+            ..Default::default()
+        };
+        self.execute_inner(agent_id, session_id, message, settings).await
+    }
+
+    /// Execute an agent as part of traced work.
+    ///
+    /// The correlation ID reaches tool contexts, provider requests and
+    /// tracing spans, and the run's steps are recorded in the trace log.
+    pub async fn execute_with_correlation(
+        &self,
+        agent_id: &str,
+        session_id: &str,
+        message: Message,
+        correlation_id: &str,
+    ) -> Result<Vec<Message>, AgentError> {
+        let settings = RunSettings {
+            correlation_id: Some(correlation_id.to_string()),
+            ..Default::default()
+        };
+        self.execute_inner(agent_id, session_id, message, settings).await
+    }
+
+    /// Check that an agent can run with the given overrides, under the
+    /// runtime's policy.
+    pub fn check_overrides(&self, agent_id: &str, overrides: &RunOverrides) -> Result<(), AgentError> {
+        let agent = self
+            .agents
+            .get(agent_id)
+            .ok_or_else(|| AgentError::NotFound(agent_id.to_string()))?
+            .clone();
+        self.override_policy.check(overrides)?;
+        agent.check_overrides(overrides)
+    }
+
+    /// Execute an agent with per-run overrides of its model, generation
+    /// parameters and tools.
+    pub async fn execute_with_overrides(
+        &self,
+        agent_id: &str,
+        session_id: &str,
+        message: Message,
+        transcript: Option<Arc<TranscriptWriter>>,
+        correlation_id: Option<String>,
+        overrides: RunOverrides,
+    ) -> Result<Vec<Message>, AgentError> {
+        self.check_overrides(agent_id, &overrides)?;
+        let settings = RunSettings {
+            transcript,
+            correlation_id,
+            overrides,
+            ..Default::default()
+        };
+        self.execute_inner(agent_id, session_id, message, settings).await
+    }
+
+    /// Execute an agent for a task with the task's priority and deadline.
+    ///
+    /// The run's tools hand both on to the sub-agents and tasks they spawn,
+    /// and a run still going at the deadline fails with a timeout. In a dry
+    /// run, tools with side effects only describe what they would do.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn execute_scheduled(
+        &self,
+        agent_id: &str,
+        session_id: &str,
+        message: Message,
+        correlation_id: Option<String>,
+        overrides: RunOverrides,
+        scheduling: RunScheduling,
+        dry_run: bool,
+    ) -> Result<Vec<Message>, AgentError> {
+        self.check_overrides(agent_id, &overrides)?;
+        let settings = RunSettings {
+            transcript: None,
+            correlation_id,
+            overrides,
+            scheduling,
+            parent_signal: None,
+            dry_run,
+        };
+        self.execute_inner(agent_id, session_id, message, settings).await
+    }
+
+    /// Execute an agent on behalf of another run, such as a sub-agent.
+    ///
+    /// The run is aborted along with `parent`, so cancelling a session
+    /// also cancels everything it spawned.
+    pub async fn execute_child(
+        &self,
+        agent_id: &str,
+        session_id: &str,
+        message: Message,
+        scheduling: RunScheduling,
+        parent: Arc<AbortSignal>,
+    ) -> Result<Vec<Message>, AgentError> {
+        let settings = RunSettings {
+            scheduling,
+            parent_signal: Some(parent),
+            ..Default::default()
+        };
+        self.execute_inner(agent_id, session_id, message, settings).await
+    }
+
+    async fn execute_inner(
+        &self,
+        agent_id: &str,
+        session_id: &str,
+        message: Message,
+        settings: RunSettings,
+    ) -> Result<Vec<Message>, AgentError> {
+        let RunSettings {
+            transcript,
+            correlation_id,
+            overrides,
+            scheduling,
+            parent_signal,
+            dry_run,
+        } = settings;
+        let time_left = scheduling.time_left();
+        if time_left == Some(std::time::Duration::ZERO) {
+            return Err(AgentError::Timeout(0));
+        }
+
         let agent = self
             .agents
             .get(agent_id)
@@ -115,8 +360,11 @@ impl AgentRuntime {
             AgentError::ExecutionFailed("Failed to acquire concurrency permit".to_string())
         })?;
 
-        // Create abort signal
-        let abort_signal = Arc::new(AbortSignal::new());
+        // Aborted with the parent run, or with the runtime on shutdown
+        let abort_signal = parent_signal.as_ref().unwrap_or(&self.root_signal).child();
+        if abort_signal.is_aborted() {
+            return Err(AgentError::Aborted);
+        }
 
         // Register as running — use a RAII guard to ensure cleanup on all paths
         // (including panics, early returns, and errors).
@@ -142,6 +390,11 @@ impl AgentRuntime {
         let ctx = AgentContext::new(session_id).with_history(history_messages);
         let ctx = AgentContext {
             abort_signal,
+            correlation_id,
+            overrides,
+            sandbox: agent.config().sandbox.clone().map(Arc::new),
+            scheduling,
+            dry_run,
             ..ctx
         };
 
@@ -165,8 +418,33 @@ impl AgentRuntime {
         if let Some(ref memory) = self.memory_backend {
             agent_loop = agent_loop.with_memory(memory.clone());
         }
-
-        let result = agent_loop.run_with_recovery(agent.as_ref(), ctx, message).await;
+        if let Some(ref approval) = self.approval {
+            agent_loop = agent_loop.with_approval(approval.clone());
+        }
+        if let Some(ref observer) = self.turn_observer {
+            agent_loop = agent_loop.with_turn_observer(observer.clone());
+        }
+        if let Some(ref detector) = self.project_detector {
+            agent_loop = agent_loop.with_project_detector(detector.clone());
+        }
+        if let Some(ref audit_log) = self.audit_log {
+            agent_loop = agent_loop.with_audit_log(audit_log.clone());
+        }
+        if let Some(ref redactor) = self.redactor {
+            agent_loop = agent_loop.with_redactor(redactor.clone());
+        }
+        agent_loop = agent_loop
+            .with_progress(self.progress.clone())
+            .with_trace_log(self.trace_log.clone())
+            .with_feature_flags(self.feature_flags.clone());
+
+        let run = agent_loop.run_with_recovery(agent.as_ref(), ctx, message);
+        let result = match time_left {
+            Some(left) => tokio::time::timeout(left, run)
+                .await
+                .unwrap_or(Err(AgentError::Timeout(left.as_secs()))),
+            None => run.await,
+        };
 
         // Record agent response messages to history
         if let Ok(ref messages) = result {
@@ -194,11 +472,61 @@ impl AgentRuntime {
         }
     }
 
+    /// Abort every running execution and wait for them to stop.
+    ///
+    /// Runs stop at their next turn or tool call; tools in flight are
+    /// dropped, ending the sub-agents, processes and browser operations
+    /// tied to the run. Sessions still running after `grace` are reported
+    /// as forced. No new runs start afterwards.
+    pub async fn shutdown(&self, grace: std::time::Duration) -> ShutdownReport {
+        let mut cancelled: Vec<String> = self.running_sessions().into_iter().collect();
+        cancelled.sort();
+        self.root_signal.abort();
+        info!("Shutting down agent runtime: {} running", cancelled.len());
+
+        let deadline = tokio::time::Instant::now() + grace;
+        while !self.running.is_empty() && tokio::time::Instant::now() < deadline {
+            tokio::time::sleep(SHUTDOWN_POLL_INTERVAL).await;
+        }
+
+        let mut forced: Vec<String> = self.running_sessions().into_iter().collect();
+        forced.sort();
+        if !forced.is_empty() {
+            warn!(
+                "{} agent runs did not stop within {:?}: {}",
+                forced.len(),
+                grace,
+                forced.join(", ")
+            );
+        }
+        ShutdownReport { cancelled, forced }
+    }
+
     /// Check if an agent is running.
     pub fn is_running(&self, session_id: &str) -> bool {
         self.running.contains_key(session_id)
     }
 
+    /// Progress of a session's current or last execution.
+    pub fn progress(&self, session_id: &str) -> Option<TaskProgress> {
+        self.progress.get(session_id)
+    }
+
+    /// Progress tracker shared by the agent loops.
+    pub fn progress_tracker(&self) -> &Arc<ProgressTracker> {
+        &self.progress
+    }
+
+    /// Trace log the agent loops record in.
+    pub fn trace_log(&self) -> &Arc<TraceLog> {
+        &self.trace_log
+    }
+
+    /// Feature flags gating the agent loops.
+    pub fn feature_flags(&self) -> &Arc<FeatureFlags> {
+        &self.feature_flags
+    }
+
     /// Get the number of currently running agents.
     pub fn running_count(&self) -> usize {
         self.running.len()
@@ -4,12 +4,13 @@ use std::collections::HashMap;
 use async_trait::async_trait;
 
 use autohands_core::registry::{ProviderRegistry, ToolRegistry};
-use autohands_protocols::agent::{Agent, AgentConfig, AgentContext, AgentResponse};
+use autohands_protocols::agent::{Agent, AgentConfig, AgentContext, AgentResponse, RunOverrides};
 use autohands_protocols::error::AgentError;
 use autohands_protocols::tool::AbortSignal;
 use autohands_protocols::types::Message;
 
 use crate::agent_loop::AgentLoopConfig;
+use crate::overrides::OverridePolicy;
 
 struct MockAgent {
     config: AgentConfig,
@@ -159,6 +160,7 @@ fn test_runtime_config_custom() {
         default_loop_config: AgentLoopConfig {
             checkpoint_enabled: false,
             max_tool_output_chars: 50_000,
+            context_fallback_model: None,
         },
     };
     assert_eq!(config.max_concurrent, 5);
@@ -254,3 +256,205 @@ async fn test_execute_records_history() {
     let history = runtime.history_manager().get("session-1");
     assert!(history.len() >= 2); // At least user message + agent response
 }
+
+/// Agent accepting any overrides, recording those of its runs.
+struct OverridableAgent {
+    config: AgentConfig,
+    seen: parking_lot::Mutex<Vec<RunOverrides>>,
+}
+
+#[async_trait]
+impl Agent for OverridableAgent {
+    fn id(&self) -> &str {
+        &self.config.id
+    }
+
+    fn config(&self) -> &AgentConfig {
+        &self.config
+    }
+
+    async fn process(&self, _message: Message, ctx: AgentContext) -> Result<AgentResponse, AgentError> {
+        self.seen.lock().push(ctx.overrides);
+        Ok(AgentResponse {
+            message: Message::assistant("done"),
+            is_complete: true,
+            tool_calls: Vec::new(),
+            metadata: HashMap::new(),
+            usage: None,
+        })
+    }
+
+    fn check_overrides(&self, _overrides: &RunOverrides) -> Result<(), AgentError> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_execute_with_overrides() {
+    let policy = OverridePolicy {
+        allowed_models: vec!["fast".to_string()],
+        ..Default::default()
+    };
+    let runtime = AgentRuntime::new(
+        Arc::new(ProviderRegistry::new()),
+        Arc::new(ToolRegistry::new()),
+        Default::default(),
+    )
+    .with_override_policy(policy);
+    let agent = Arc::new(OverridableAgent {
+        config: AgentConfig::new("custom", "Custom", "slow"),
+        seen: parking_lot::Mutex::new(Vec::new()),
+    });
+    runtime.register_agent(agent.clone());
+    runtime.register_agent(Arc::new(MockAgent::new("mock")));
+
+    let overrides = RunOverrides {
+        model: Some("fast".to_string()),
+        temperature: Some(0.1),
+        ..Default::default()
+    };
+    runtime
+        .execute_with_overrides("custom", "s1", Message::user("Hi"), None, None, overrides.clone())
+        .await
+        .unwrap();
+    assert_eq!(*agent.seen.lock(), vec![overrides.clone()]);
+
+    // The policy and the agent may each refuse overrides
+    let slow = RunOverrides {
+        model: Some("slow".to_string()),
+        ..Default::default()
+    };
+    assert!(matches!(runtime.check_overrides("custom", &slow), Err(AgentError::InvalidOverride(_))));
+    let err = runtime.check_overrides("mock", &overrides).unwrap_err();
+    assert!(err.to_string().contains("does not support per-run overrides"));
+    assert!(runtime.check_overrides("mock", &RunOverrides::default()).is_ok());
+    assert!(matches!(
+        runtime.check_overrides("missing", &overrides),
+        Err(AgentError::NotFound(_))
+    ));
+    assert_eq!(agent.seen.lock().len(), 1);
+}
+
+#[tokio::test]
+async fn test_execute_scheduled_deadline() {
+    let runtime = AgentRuntime::new(
+        Arc::new(ProviderRegistry::new()),
+        Arc::new(ToolRegistry::new()),
+        Default::default(),
+    );
+    runtime.register_agent(Arc::new(MockAgent::new("mock")));
+    let scheduling = |secs: i64| autohands_protocols::agent::RunScheduling {
+        priority: Some("high".to_string()),
+        deadline: Some(chrono::Utc::now() + chrono::Duration::seconds(secs)),
+    };
+
+    let run = |scheduling| {
+        runtime.execute_scheduled(
+            "mock",
+            "s1",
+            Message::user("Hi"),
+            None,
+            RunOverrides::default(),
+            scheduling,
+            false,
+        )
+    };
+    assert!(run(scheduling(60)).await.is_ok());
+    assert!(matches!(run(scheduling(-1)).await, Err(AgentError::Timeout(0))));
+}
+
+/// Agent that answers once its run is aborted, or never if `stuck`.
+struct WaitingAgent {
+    config: AgentConfig,
+    stuck: bool,
+}
+
+#[async_trait]
+impl Agent for WaitingAgent {
+    fn id(&self) -> &str {
+        &self.config.id
+    }
+
+    fn config(&self) -> &AgentConfig {
+        &self.config
+    }
+
+    async fn process(
+        &self,
+        _message: Message,
+        ctx: AgentContext,
+    ) -> Result<AgentResponse, AgentError> {
+        if self.stuck {
+            std::future::pending::<()>().await;
+        }
+        ctx.abort_signal.aborted().await;
+        Err(AgentError::Aborted)
+    }
+}
+
+async fn start_waiting(stuck: bool) -> Arc<AgentRuntime> {
+    let runtime = Arc::new(AgentRuntime::new(
+        Arc::new(ProviderRegistry::new()),
+        Arc::new(ToolRegistry::new()),
+        Default::default(),
+    ));
+    runtime.register_agent(Arc::new(WaitingAgent {
+        config: AgentConfig::new("waiting", "Waiting", "mock-model"),
+        stuck,
+    }));
+    let background = runtime.clone();
+    tokio::spawn(async move {
+        background
+            .execute("waiting", "s1", Message::user("Hi"))
+            .await
+    });
+    while runtime.running_count() == 0 {
+        tokio::task::yield_now().await;
+    }
+    runtime
+}
+
+#[tokio::test]
+async fn test_shutdown_cancels_runs() {
+    let runtime = start_waiting(false).await;
+    let report = runtime.shutdown(std::time::Duration::from_secs(5)).await;
+    assert_eq!(report.cancelled, vec!["s1".to_string()]);
+    assert!(report.is_clean());
+    assert_eq!(runtime.running_count(), 0);
+
+    // Nothing starts after shutdown
+    runtime.register_agent(Arc::new(MockAgent::new("mock")));
+    let result = runtime.execute("mock", "s2", Message::user("Hi")).await;
+    assert!(matches!(result, Err(AgentError::Aborted)));
+}
+
+#[tokio::test]
+async fn test_shutdown_reports_forced_runs() {
+    let runtime = start_waiting(true).await;
+    let report = runtime.shutdown(std::time::Duration::from_millis(50)).await;
+    assert_eq!(report.forced, vec!["s1".to_string()]);
+}
+
+#[tokio::test]
+async fn test_execute_child_follows_parent() {
+    let runtime = AgentRuntime::new(
+        Arc::new(ProviderRegistry::new()),
+        Arc::new(ToolRegistry::new()),
+        Default::default(),
+    );
+    runtime.register_agent(Arc::new(MockAgent::new("mock")));
+    let parent = Arc::new(AbortSignal::new());
+    let run = |parent| {
+        runtime.execute_child(
+            "mock",
+            "child",
+            Message::user("Hi"),
+            Default::default(),
+            parent,
+        )
+    };
+
+    assert!(run(parent.clone()).await.is_ok());
+    parent.abort();
+    assert!(matches!(run(parent).await, Err(AgentError::Aborted)));
+}
@@ -0,0 +1,370 @@
+//! Portable session bundles.
+//!
+//! A bundle packs everything needed to continue or inspect a session on
+//! another deployment: the JSONL transcript, the files the session wrote
+//! (artifacts) and the memory entries tagged with the session. It is a single
+//! JSON document, so it can be copied between machines or attached to a bug
+//! report.
+
+use std::path::{Component, Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{debug, warn};
+
+use autohands_protocols::memory::{MemoryBackend, MemoryEntry, MemoryQuery};
+
+use crate::memory_persistence::session_tag;
+use crate::transcript::{TranscriptEntry, TranscriptManager};
+
+/// Bundle format version written by this build.
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// Tools whose `path` argument names a file the session produced.
+pub const ARTIFACT_TOOLS: &[&str] = &["write_file", "edit_file"];
+
+/// Largest artifact whose content is included in a bundle.
+pub const MAX_ARTIFACT_BYTES: u64 = 1024 * 1024;
+
+/// Most memory entries exported per session.
+const MAX_MEMORIES: usize = 10_000;
+
+/// Errors from exporting or importing a bundle.
+#[derive(Debug, Error)]
+pub enum BundleError {
+    #[error("No transcript for session '{0}'")]
+    NotFound(String),
+
+    #[error("Session '{0}' already exists")]
+    AlreadyExists(String),
+
+    #[error("Unsupported bundle format version {0}")]
+    UnsupportedVersion(u32),
+
+    #[error("Invalid session ID '{0}'")]
+    InvalidSessionId(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid transcript: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Memory error: {0}")]
+    Memory(String),
+}
+
+/// A file written during the session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionArtifact {
+    /// Path as the tool saw it.
+    pub path: String,
+    /// File content; `None` if the file was missing, binary or too large.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+}
+
+/// A portable session export.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionBundle {
+    /// Bundle format version.
+    pub format_version: u32,
+    /// Exported session.
+    pub session_id: String,
+    /// When the bundle was made.
+    pub exported_at: DateTime<Utc>,
+    /// AutoHands version that made the bundle.
+    pub autohands_version: String,
+    /// Transcript entries, in order.
+    pub transcript: Vec<TranscriptEntry>,
+    /// Files written during the session.
+    #[serde(default)]
+    pub artifacts: Vec<SessionArtifact>,
+    /// Memory entries created during the session.
+    #[serde(default)]
+    pub memories: Vec<MemoryEntry>,
+}
+
+/// What an import wrote.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportReport {
+    /// Imported session.
+    pub session_id: String,
+    /// Transcript entries written.
+    pub transcript_entries: usize,
+    /// Artifacts restored.
+    pub artifacts: usize,
+    /// Directory the artifacts were restored to.
+    pub artifact_dir: PathBuf,
+    /// Memory entries stored.
+    pub memories: usize,
+}
+
+/// Export a session from its transcript, artifacts and memory entries.
+///
+/// Only artifacts inside the session's cwd are exported, or for an imported
+/// session, inside the directory its artifacts were restored to; paths that
+/// lead elsewhere (`/etc/passwd`, `../..`, symlinks) are skipped.
+pub async fn export_session(
+    transcripts: &TranscriptManager,
+    session_id: &str,
+    memory: Option<&dyn MemoryBackend>,
+) -> Result<SessionBundle, BundleError> {
+    check_session_id(session_id)?;
+    let path = transcripts.transcript_path(session_id);
+    let raw = match tokio::fs::read_to_string(&path).await {
+        Ok(raw) => raw,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(BundleError::NotFound(session_id.to_string()))
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let transcript = raw
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(serde_json::from_str)
+        .collect::<Result<Vec<TranscriptEntry>, _>>()?;
+
+    let mut artifacts = Vec::new();
+    if let Some(root) = artifact_root(transcripts, session_id, &transcript).await {
+        for artifact_path in artifact_paths(&transcript) {
+            match read_artifact(&root, artifact_path).await {
+                Some(artifact) => artifacts.push(artifact),
+                None => debug!(
+                    "Skipped artifact {} of session {}",
+                    artifact_path, session_id
+                ),
+            }
+        }
+    }
+
+    let memories = match memory {
+        Some(memory) => memory
+            .search(MemoryQuery {
+                text: None,
+                memory_type: None,
+                tags: vec![session_tag(session_id)],
+                limit: MAX_MEMORIES,
+                min_relevance: None,
+            })
+            .await
+            .map_err(|e| BundleError::Memory(e.to_string()))?
+            .into_iter()
+            .map(|result| result.entry)
+            .collect(),
+        None => Vec::new(),
+    };
+
+    debug!(
+        "Exported session {}: {} entries, {} artifacts, {} memories",
+        session_id,
+        transcript.len(),
+        artifacts.len(),
+        memories.len()
+    );
+    Ok(SessionBundle {
+        format_version: BUNDLE_FORMAT_VERSION,
+        session_id: session_id.to_string(),
+        exported_at: Utc::now(),
+        autohands_version: env!("CARGO_PKG_VERSION").to_string(),
+        transcript,
+        artifacts,
+        memories,
+    })
+}
+
+/// Import a bundle: write its transcript, restore artifacts under
+/// `artifact_dir` and store its memory entries.
+///
+/// Artifacts are never written to their original paths; each lands under
+/// `artifact_dir` at its path with the root and any `..` removed.
+pub async fn import_session(
+    transcripts: &TranscriptManager,
+    bundle: SessionBundle,
+    artifact_dir: &Path,
+    memory: Option<&dyn MemoryBackend>,
+    overwrite: bool,
+) -> Result<ImportReport, BundleError> {
+    if bundle.format_version > BUNDLE_FORMAT_VERSION {
+        return Err(BundleError::UnsupportedVersion(bundle.format_version));
+    }
+    check_session_id(&bundle.session_id)?;
+    let path = transcripts.transcript_path(&bundle.session_id);
+    if !overwrite && tokio::fs::try_exists(&path).await? {
+        return Err(BundleError::AlreadyExists(bundle.session_id));
+    }
+
+    let mut jsonl = String::new();
+    for entry in &bundle.transcript {
+        jsonl.push_str(&serde_json::to_string(entry)?);
+        jsonl.push('\n');
+    }
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(&path, jsonl).await?;
+
+    let mut artifacts = 0;
+    for artifact in &bundle.artifacts {
+        let Some(content) = &artifact.content else {
+            continue;
+        };
+        let target = artifact_dir.join(relative_path(&artifact.path));
+        if let Some(parent) = target.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&target, content).await?;
+        artifacts += 1;
+    }
+    // Exporting the session again reads its artifacts from there
+    tokio::fs::write(
+        import_marker_path(transcripts, &bundle.session_id),
+        artifact_dir.to_string_lossy().as_bytes(),
+    )
+    .await?;
+
+    let mut memories = 0;
+    if let Some(memory) = memory {
+        for mut entry in bundle.memories {
+            // Let the target backend assign its own IDs
+            entry.id = None;
+            memory
+                .store(entry)
+                .await
+                .map_err(|e| BundleError::Memory(e.to_string()))?;
+            memories += 1;
+        }
+    } else if !bundle.memories.is_empty() {
+        warn!(
+            "No memory backend; skipped {} memory entries of session {}",
+            bundle.memories.len(),
+            bundle.session_id
+        );
+    }
+
+    Ok(ImportReport {
+        session_id: bundle.session_id,
+        transcript_entries: bundle.transcript.len(),
+        artifacts,
+        artifact_dir: artifact_dir.to_path_buf(),
+        memories,
+    })
+}
+
+/// Session IDs become file names, so they must not contain path parts.
+fn check_session_id(session_id: &str) -> Result<(), BundleError> {
+    let valid = !session_id.is_empty()
+        && session_id != "."
+        && session_id != ".."
+        && !session_id.contains(['/', '\\']);
+    if valid {
+        Ok(())
+    } else {
+        Err(BundleError::InvalidSessionId(session_id.to_string()))
+    }
+}
+
+/// Distinct paths written by artifact tools, in first-write order.
+fn artifact_paths(transcript: &[TranscriptEntry]) -> Vec<&str> {
+    let mut paths: Vec<&str> = Vec::new();
+    for entry in transcript {
+        if let TranscriptEntry::ToolUse { tool_name, tool_input, .. } = entry {
+            if !ARTIFACT_TOOLS.contains(&tool_name.as_str()) {
+                continue;
+            }
+            if let Some(path) = tool_input.get("path").and_then(|p| p.as_str()) {
+                if !paths.contains(&path) {
+                    paths.push(path);
+                }
+            }
+        }
+    }
+    paths
+}
+
+/// File next to an imported session's transcript naming its artifact dir.
+fn import_marker_path(transcripts: &TranscriptManager, session_id: &str) -> PathBuf {
+    transcripts
+        .transcript_path(session_id)
+        .with_extension("artifacts")
+}
+
+/// Directory a session's artifacts are read from, canonicalized.
+enum ArtifactRoot {
+    /// The cwd of a session that ran here.
+    Cwd(PathBuf),
+    /// The directory an imported session's artifacts were restored to.
+    Imported(PathBuf),
+}
+
+impl ArtifactRoot {
+    fn dir(&self) -> &Path {
+        match self {
+            Self::Cwd(dir) | Self::Imported(dir) => dir,
+        }
+    }
+
+    /// Where the artifact a tool wrote at `path` is now.
+    fn locate(&self, path: &str) -> PathBuf {
+        match self {
+            Self::Cwd(cwd) => cwd.join(path),
+            Self::Imported(dir) => dir.join(relative_path(path)),
+        }
+    }
+}
+
+/// Artifact root of a session; `None` if it has no cwd that still exists.
+async fn artifact_root(
+    transcripts: &TranscriptManager,
+    session_id: &str,
+    transcript: &[TranscriptEntry],
+) -> Option<ArtifactRoot> {
+    if let Ok(dir) = tokio::fs::read_to_string(import_marker_path(transcripts, session_id)).await {
+        return tokio::fs::canonicalize(dir)
+            .await
+            .ok()
+            .map(ArtifactRoot::Imported);
+    }
+    let cwd = transcript.iter().find_map(|entry| match entry {
+        TranscriptEntry::SessionStart { cwd, .. } => Some(cwd.as_str()),
+        _ => None,
+    })?;
+    tokio::fs::canonicalize(cwd)
+        .await
+        .ok()
+        .map(ArtifactRoot::Cwd)
+}
+
+/// Read an artifact; `None` if it does not resolve to a path inside `root`.
+async fn read_artifact(root: &ArtifactRoot, path: &str) -> Option<SessionArtifact> {
+    let full = tokio::fs::canonicalize(root.locate(path)).await.ok()?;
+    if !full.starts_with(root.dir()) {
+        return None;
+    }
+    let content = match tokio::fs::metadata(&full).await {
+        Ok(meta) if meta.is_file() && meta.len() <= MAX_ARTIFACT_BYTES => {
+            tokio::fs::read_to_string(&full).await.ok()
+        }
+        _ => None,
+    };
+    Some(SessionArtifact {
+        path: path.to_string(),
+        content,
+    })
+}
+
+/// Drop the root, prefixes and `..` so a path stays inside the target dir.
+fn relative_path(path: &str) -> PathBuf {
+    Path::new(path)
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(part) => Some(part),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "session_bundle_tests.rs"]
+mod tests;
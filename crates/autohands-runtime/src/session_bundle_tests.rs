@@ -0,0 +1,193 @@
+use super::*;
+
+use async_trait::async_trait;
+use autohands_protocols::error::MemoryError;
+use autohands_protocols::memory::MemorySearchResult;
+use tokio::sync::Mutex;
+
+/// In-memory backend that filters searches by tag.
+#[derive(Default)]
+struct TagMemory {
+    entries: Mutex<Vec<MemoryEntry>>,
+}
+
+#[async_trait]
+impl MemoryBackend for TagMemory {
+    fn id(&self) -> &str {
+        "tag-memory"
+    }
+
+    async fn store(&self, mut entry: MemoryEntry) -> Result<String, MemoryError> {
+        let mut entries = self.entries.lock().await;
+        let id = format!("m{}", entries.len());
+        entry.id = Some(id.clone());
+        entries.push(entry);
+        Ok(id)
+    }
+
+    async fn retrieve(&self, _id: &str) -> Result<Option<MemoryEntry>, MemoryError> {
+        Ok(None)
+    }
+
+    async fn search(&self, query: MemoryQuery) -> Result<Vec<MemorySearchResult>, MemoryError> {
+        Ok(self
+            .entries
+            .lock()
+            .await
+            .iter()
+            .filter(|e| query.tags.iter().any(|t| e.tags.contains(t)))
+            .map(|e| MemorySearchResult { entry: e.clone(), relevance: 1.0 })
+            .collect())
+    }
+
+    async fn delete(&self, _id: &str) -> Result<(), MemoryError> {
+        Ok(())
+    }
+
+    async fn update(&self, _id: &str, _entry: MemoryEntry) -> Result<(), MemoryError> {
+        Ok(())
+    }
+}
+
+async fn record_session(manager: &TranscriptManager, work_dir: &Path) {
+    std::fs::write(work_dir.join("report.md"), "# Report").unwrap();
+    let writer = manager.get_writer("s1").await.unwrap();
+    writer
+        .write(&TranscriptEntry::SessionStart {
+            session_id: "s1".to_string(),
+            timestamp: Utc::now(),
+            cwd: work_dir.display().to_string(),
+            version: "test".to_string(),
+            task: Some("write a report".to_string()),
+        })
+        .await
+        .unwrap();
+    writer.record_user_message(serde_json::json!("write a report")).await.unwrap();
+    writer
+        .record_tool_use("t1", "write_file", serde_json::json!({"path": "report.md"}))
+        .await
+        .unwrap();
+    writer
+        .record_tool_use("t2", "write_file", serde_json::json!({"path": "/nonexistent/x.txt"}))
+        .await
+        .unwrap();
+    manager.remove_writer("s1").await;
+}
+
+#[tokio::test]
+async fn test_export_and_import_roundtrip() {
+    let src = tempfile::tempdir().unwrap();
+    let source = TranscriptManager::new(src.path().join("sessions"));
+    record_session(&source, src.path()).await;
+
+    let source_memory = TagMemory::default();
+    source_memory
+        .store(MemoryEntry::new("Use Redis", "decision").with_tags(vec![session_tag("s1")]))
+        .await
+        .unwrap();
+    source_memory
+        .store(MemoryEntry::new("Other session", "fact").with_tags(vec![session_tag("s2")]))
+        .await
+        .unwrap();
+
+    let bundle = export_session(&source, "s1", Some(&source_memory)).await.unwrap();
+    assert_eq!(bundle.format_version, BUNDLE_FORMAT_VERSION);
+    assert_eq!(bundle.transcript.len(), 4);
+    // The file outside the session's cwd is skipped
+    assert_eq!(bundle.artifacts.len(), 1);
+    assert_eq!(bundle.artifacts[0].content.as_deref(), Some("# Report"));
+    assert_eq!(bundle.memories.len(), 1);
+
+    // Round-trip through JSON, as the CLI does
+    let bundle: SessionBundle =
+        serde_json::from_str(&serde_json::to_string(&bundle).unwrap()).unwrap();
+
+    let dst = tempfile::tempdir().unwrap();
+    let target = TranscriptManager::new(dst.path().join("sessions"));
+    let target_memory = TagMemory::default();
+    let artifact_dir = dst.path().join("artifacts").join("s1");
+    let report = import_session(&target, bundle.clone(), &artifact_dir, Some(&target_memory), false)
+        .await
+        .unwrap();
+    assert_eq!(report.transcript_entries, 4);
+    assert_eq!(report.artifacts, 1);
+    assert_eq!(report.memories, 1);
+    assert_eq!(
+        std::fs::read_to_string(artifact_dir.join("report.md")).unwrap(),
+        "# Report"
+    );
+    assert_eq!(target.list_transcripts().await.unwrap(), vec!["s1".to_string()]);
+
+    // Exporting the imported session reads the restored artifacts
+    let exported = export_session(&target, "s1", None).await.unwrap();
+    assert_eq!(exported.artifacts.len(), 1);
+    assert_eq!(exported.artifacts[0].content.as_deref(), Some("# Report"));
+
+    let err = import_session(&target, bundle.clone(), &artifact_dir, None, false)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, BundleError::AlreadyExists(_)));
+    assert!(import_session(&target, bundle, &artifact_dir, None, true).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_export_skips_artifacts_outside_cwd() {
+    let dir = tempfile::tempdir().unwrap();
+    let work_dir = dir.path().join("work");
+    std::fs::create_dir(&work_dir).unwrap();
+    let secret = dir.path().join("secret.txt");
+    std::fs::write(&secret, "token").unwrap();
+    let manager = TranscriptManager::new(dir.path().join("sessions"));
+    record_session(&manager, &work_dir).await;
+    let writer = manager.get_writer("s1").await.unwrap();
+    for (id, path) in [("t3", "../secret.txt"), ("t4", secret.to_str().unwrap())] {
+        writer
+            .record_tool_use(id, "write_file", serde_json::json!({"path": path}))
+            .await
+            .unwrap();
+    }
+    manager.remove_writer("s1").await;
+
+    let bundle = export_session(&manager, "s1", None).await.unwrap();
+    let paths: Vec<_> = bundle.artifacts.iter().map(|a| a.path.as_str()).collect();
+    assert_eq!(paths, vec!["report.md"]);
+}
+
+#[tokio::test]
+async fn test_export_missing_session() {
+    let dir = tempfile::tempdir().unwrap();
+    let manager = TranscriptManager::new(dir.path().to_path_buf());
+    assert!(matches!(
+        export_session(&manager, "nope", None).await,
+        Err(BundleError::NotFound(_))
+    ));
+    assert!(matches!(
+        export_session(&manager, "../etc", None).await,
+        Err(BundleError::InvalidSessionId(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_import_rejects_newer_format() {
+    let dir = tempfile::tempdir().unwrap();
+    let manager = TranscriptManager::new(dir.path().to_path_buf());
+    let bundle = SessionBundle {
+        format_version: BUNDLE_FORMAT_VERSION + 1,
+        session_id: "s1".to_string(),
+        exported_at: Utc::now(),
+        autohands_version: "99.0.0".to_string(),
+        transcript: Vec::new(),
+        artifacts: Vec::new(),
+        memories: Vec::new(),
+    };
+    assert!(matches!(
+        import_session(&manager, bundle, dir.path(), None, false).await,
+        Err(BundleError::UnsupportedVersion(_))
+    ));
+}
+
+#[test]
+fn test_relative_path_stays_inside() {
+    assert_eq!(relative_path("/etc/passwd"), PathBuf::from("etc/passwd"));
+    assert_eq!(relative_path("../../x/./y.txt"), PathBuf::from("x/y.txt"));
+}
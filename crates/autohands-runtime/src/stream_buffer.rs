@@ -0,0 +1,226 @@
+//! Buffered streaming completions.
+//!
+//! [`complete_buffered`] consumes a provider stream into a regular
+//! [`CompletionResponse`], keeping what arrived so far. When the stream dies
+//! halfway (network blip, provider 529), the text received is sent back as
+//! an assistant prefix and the model is asked to continue from there, so the
+//! turn is not lost. Tool calls cannot be continued that way: a stream that
+//! fails after starting one, or that keeps failing, ends in
+//! [`AgentError::Interrupted`] carrying the partial text.
+
+use std::time::Duration;
+
+use futures::StreamExt;
+use tracing::{debug, warn};
+
+use autohands_protocols::error::{AgentError, ProviderError};
+use autohands_protocols::provider::{
+    ChunkType, CompletionChunk, CompletionRequest, CompletionResponse, LLMProvider,
+};
+use autohands_protocols::types::{Message, StopReason, ToolCall, Usage};
+
+use crate::retry::{is_retryable, RetryConfig};
+
+/// Tool call whose input is still streaming.
+#[derive(Debug)]
+struct OpenToolCall {
+    id: String,
+    name: String,
+    input: String,
+}
+
+/// Output of a streamed completion, accumulated chunk by chunk.
+#[derive(Debug, Default)]
+pub struct StreamBuffer {
+    text: String,
+    tool_calls: Vec<ToolCall>,
+    open_tool: Option<OpenToolCall>,
+    stop_reason: Option<StopReason>,
+    usage: Usage,
+}
+
+impl StreamBuffer {
+    /// Create an empty buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a chunk.
+    pub fn push(&mut self, chunk: &CompletionChunk) {
+        match chunk.chunk_type {
+            ChunkType::ContentDelta => {
+                if let Some(ref delta) = chunk.delta {
+                    self.text.push_str(delta);
+                }
+            }
+            ChunkType::ToolUseStart | ChunkType::ToolUseDelta => {
+                let Some(ref tc) = chunk.tool_call else { return };
+                // A new ID starts the next tool call
+                let starts = chunk.chunk_type == ChunkType::ToolUseStart
+                    || self.open_tool.is_none()
+                    || tc.id.as_ref().is_some_and(|id| {
+                        self.open_tool.as_ref().is_some_and(|open| &open.id != id)
+                    });
+                if starts {
+                    self.close_tool();
+                    self.open_tool = Some(OpenToolCall {
+                        id: tc.id.clone().unwrap_or_default(),
+                        name: tc.name.clone().unwrap_or_default(),
+                        input: String::new(),
+                    });
+                }
+                if let (Some(open), Some(input)) = (self.open_tool.as_mut(), &tc.input_delta) {
+                    open.input.push_str(input);
+                }
+            }
+            ChunkType::MessageEnd => {
+                self.close_tool();
+                if chunk.stop_reason.is_some() {
+                    self.stop_reason = chunk.stop_reason;
+                }
+            }
+            ChunkType::MessageStart => {}
+        }
+        if let Some(ref usage) = chunk.usage {
+            self.add_usage(usage);
+        }
+    }
+
+    /// Text received so far.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Whether a tool call has started.
+    pub fn has_tool_calls(&self) -> bool {
+        !self.tool_calls.is_empty() || self.open_tool.is_some()
+    }
+
+    fn close_tool(&mut self) {
+        if let Some(open) = self.open_tool.take() {
+            let arguments = if open.input.trim().is_empty() {
+                serde_json::json!({})
+            } else {
+                serde_json::from_str(&open.input)
+                    .unwrap_or(serde_json::Value::String(open.input))
+            };
+            self.tool_calls.push(ToolCall {
+                id: open.id,
+                name: open.name,
+                arguments,
+            });
+        }
+    }
+
+    fn add_usage(&mut self, usage: &Usage) {
+        self.usage.prompt_tokens += usage.prompt_tokens;
+        self.usage.completion_tokens += usage.completion_tokens;
+        self.usage.total_tokens += usage.total_tokens;
+    }
+
+    /// Build the response from everything received.
+    pub fn into_response(mut self, model: &str) -> CompletionResponse {
+        self.close_tool();
+        let stop_reason = self.stop_reason.unwrap_or(if self.tool_calls.is_empty() {
+            StopReason::EndTurn
+        } else {
+            StopReason::ToolUse
+        });
+        let mut message = Message::assistant(self.text);
+        message.tool_calls = self.tool_calls;
+        CompletionResponse {
+            id: uuid::Uuid::new_v4().to_string(),
+            model: model.to_string(),
+            message,
+            stop_reason,
+            usage: self.usage,
+            metadata: Default::default(),
+        }
+    }
+}
+
+/// Whether a failed stream is worth continuing.
+fn is_transient(error: &ProviderError) -> bool {
+    is_retryable(error) || matches!(error, ProviderError::StreamError(_))
+}
+
+/// Complete a request over a stream, continuing after mid-stream failures.
+///
+/// Up to `retry.max_retries` times, a failed stream is resumed with a
+/// continuation request: the original messages plus the text received so
+/// far as an assistant prefix. Failures before any output are retried as
+/// they are. Usage of every attempt is added up.
+pub async fn complete_buffered(
+    provider: &dyn LLMProvider,
+    request: CompletionRequest,
+    retry: &RetryConfig,
+) -> Result<CompletionResponse, AgentError> {
+    let mut buffer = StreamBuffer::new();
+    let mut attempt = 0;
+
+    loop {
+        let error = match stream_into(provider, continuation(&request, &mut buffer), &mut buffer).await {
+            Ok(()) => return Ok(buffer.into_response(&request.model)),
+            Err(e) => e,
+        };
+
+        if !is_transient(&error) || buffer.has_tool_calls() || attempt >= retry.max_retries {
+            if buffer.text.is_empty() && !buffer.has_tool_calls() {
+                return Err(AgentError::ProviderError(error));
+            }
+            return Err(AgentError::Interrupted {
+                partial: buffer.text,
+                source: error,
+            });
+        }
+
+        let delay = match &error {
+            ProviderError::RateLimited { retry_after_seconds } => {
+                Duration::from_secs(*retry_after_seconds)
+            }
+            _ => retry.delay_for_attempt(attempt),
+        };
+        warn!(
+            "Stream failed after {} characters (attempt {}/{}): {}, continuing in {:?}",
+            buffer.text.len(),
+            attempt + 1,
+            retry.max_retries + 1,
+            error,
+            delay
+        );
+        attempt += 1;
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// Request continuing after the buffered text, if any.
+///
+/// Trailing whitespace is dropped from the prefix (and the buffer), since
+/// some providers reject assistant prefixes ending in it.
+fn continuation(request: &CompletionRequest, buffer: &mut StreamBuffer) -> CompletionRequest {
+    let mut request = request.clone();
+    let prefix_len = buffer.text.trim_end().len();
+    buffer.text.truncate(prefix_len);
+    if !buffer.text.is_empty() {
+        debug!("Continuing stream after {} characters", buffer.text.len());
+        request.messages.push(Message::assistant(buffer.text.clone()));
+    }
+    request
+}
+
+/// Read one stream into the buffer until it ends or fails.
+async fn stream_into(
+    provider: &dyn LLMProvider,
+    request: CompletionRequest,
+    buffer: &mut StreamBuffer,
+) -> Result<(), ProviderError> {
+    let mut stream = provider.complete_stream(request).await?;
+    while let Some(chunk) = stream.next().await {
+        buffer.push(&chunk?);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "stream_buffer_tests.rs"]
+mod tests;
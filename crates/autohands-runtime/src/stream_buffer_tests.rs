@@ -0,0 +1,199 @@
+use super::*;
+use async_trait::async_trait;
+use autohands_protocols::provider::{
+    CompletionStream, ModelDefinition, ProviderCapabilities, ToolCallChunk,
+};
+use parking_lot::Mutex;
+
+type Script = Vec<Result<CompletionChunk, ProviderError>>;
+
+/// Provider playing one scripted stream per request.
+struct ScriptedProvider {
+    scripts: Mutex<Vec<Script>>,
+    requests: Mutex<Vec<CompletionRequest>>,
+}
+
+impl ScriptedProvider {
+    fn new(scripts: Vec<Script>) -> Self {
+        Self {
+            scripts: Mutex::new(scripts),
+            requests: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl LLMProvider for ScriptedProvider {
+    fn id(&self) -> &str {
+        "scripted"
+    }
+
+    fn models(&self) -> &[ModelDefinition] {
+        &[]
+    }
+
+    fn capabilities(&self) -> &ProviderCapabilities {
+        &ProviderCapabilities {
+            streaming: true,
+            tool_calling: true,
+            vision: false,
+            json_mode: false,
+            prompt_caching: false,
+            batching: false,
+            max_concurrent: None,
+        }
+    }
+
+    async fn complete(&self, _: CompletionRequest) -> Result<CompletionResponse, ProviderError> {
+        Err(ProviderError::InvalidRequest("streaming only".to_string()))
+    }
+
+    async fn complete_stream(&self, request: CompletionRequest) -> Result<CompletionStream, ProviderError> {
+        self.requests.lock().push(request);
+        let script = self.scripts.lock().remove(0);
+        Ok(Box::pin(futures::stream::iter(script)))
+    }
+}
+
+fn chunk(chunk_type: ChunkType) -> CompletionChunk {
+    CompletionChunk {
+        chunk_type,
+        delta: None,
+        tool_call: None,
+        stop_reason: None,
+        usage: None,
+    }
+}
+
+fn text(delta: &str) -> Result<CompletionChunk, ProviderError> {
+    Ok(CompletionChunk {
+        delta: Some(delta.to_string()),
+        ..chunk(ChunkType::ContentDelta)
+    })
+}
+
+fn end(completion_tokens: u32) -> Result<CompletionChunk, ProviderError> {
+    Ok(CompletionChunk {
+        stop_reason: Some(StopReason::EndTurn),
+        usage: Some(Usage {
+            completion_tokens,
+            total_tokens: completion_tokens,
+            ..Default::default()
+        }),
+        ..chunk(ChunkType::MessageEnd)
+    })
+}
+
+fn tool(id: Option<&str>, name: Option<&str>, input: &str) -> Result<CompletionChunk, ProviderError> {
+    Ok(CompletionChunk {
+        tool_call: Some(ToolCallChunk {
+            id: id.map(str::to_string),
+            name: name.map(str::to_string),
+            input_delta: Some(input.to_string()),
+        }),
+        ..chunk(ChunkType::ToolUseDelta)
+    })
+}
+
+fn overloaded() -> Result<CompletionChunk, ProviderError> {
+    Err(ProviderError::ApiError {
+        status: 529,
+        message: "Overloaded".to_string(),
+    })
+}
+
+fn request() -> CompletionRequest {
+    CompletionRequest::new("model", vec![Message::user("Write a haiku")])
+}
+
+fn retry() -> RetryConfig {
+    RetryConfig {
+        max_retries: 2,
+        base_delay: Duration::from_millis(1),
+        jitter: false,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_buffer_assembles_tool_calls() {
+    let mut buffer = StreamBuffer::new();
+    for c in [
+        text("Reading it."),
+        tool(Some("call-1"), Some("read_file"), "{\"path\":"),
+        tool(None, None, " \"a.rs\"}"),
+        tool(Some("call-2"), Some("list_dir"), ""),
+        Ok(chunk(ChunkType::MessageEnd)),
+    ] {
+        buffer.push(&c.unwrap());
+    }
+
+    let response = buffer.into_response("model");
+    assert_eq!(response.message.content.text(), "Reading it.");
+    assert_eq!(response.stop_reason, StopReason::ToolUse);
+    let calls = &response.message.tool_calls;
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[0].name, "read_file");
+    assert_eq!(calls[0].arguments["path"], "a.rs");
+    assert_eq!(calls[1].id, "call-2");
+    assert_eq!(calls[1].arguments, serde_json::json!({}));
+}
+
+#[tokio::test]
+async fn test_complete_buffered_continues_after_failure() {
+    let provider = ScriptedProvider::new(vec![
+        vec![text("Autumn moonlight "), text("- "), overloaded()],
+        vec![text(" a worm digs silently"), end(5)],
+    ]);
+
+    let response = complete_buffered(&provider, request(), &retry()).await.unwrap();
+    assert_eq!(
+        response.message.content.text(),
+        "Autumn moonlight - a worm digs silently"
+    );
+    assert_eq!(response.usage.completion_tokens, 5);
+
+    // The continuation carries the partial output as an assistant prefix
+    let requests = provider.requests.lock();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].messages.len(), 1);
+    let prefix = requests[1].messages.last().unwrap();
+    assert_eq!(prefix.role, autohands_protocols::types::MessageRole::Assistant);
+    assert_eq!(prefix.content.text(), "Autumn moonlight -");
+}
+
+#[tokio::test]
+async fn test_complete_buffered_keeps_partial_output() {
+    // Gives up after the retries, returning what arrived
+    let provider = ScriptedProvider::new(vec![
+        vec![text("Autumn"), overloaded()],
+        vec![overloaded()],
+        vec![text(" moonlight"), overloaded()],
+    ]);
+    match complete_buffered(&provider, request(), &retry()).await {
+        Err(AgentError::Interrupted { partial, .. }) => assert_eq!(partial, "Autumn moonlight"),
+        other => panic!("expected an interrupted turn, got {:?}", other),
+    }
+
+    // Tool calls are not continued
+    let provider = ScriptedProvider::new(vec![vec![
+        text("Let me look."),
+        tool(Some("call-1"), Some("read_file"), "{\"pa"),
+        Err(ProviderError::StreamError("connection reset".to_string())),
+    ]]);
+    match complete_buffered(&provider, request(), &retry()).await {
+        Err(AgentError::Interrupted { partial, .. }) => assert_eq!(partial, "Let me look."),
+        other => panic!("expected an interrupted turn, got {:?}", other),
+    }
+    assert_eq!(provider.requests.lock().len(), 1);
+
+    // Without output, the provider error is returned as it is
+    let provider = ScriptedProvider::new(vec![vec![Err(ProviderError::AuthenticationFailed(
+        "bad key".to_string(),
+    ))]]);
+    let err = complete_buffered(&provider, request(), &retry()).await.unwrap_err();
+    assert!(matches!(
+        err,
+        AgentError::ProviderError(ProviderError::AuthenticationFailed(_))
+    ));
+}
@@ -8,14 +8,19 @@ use futures::Stream;
 use tokio::sync::mpsc;
 use tracing::debug;
 
+use autohands_core::audit::AuditLog;
+use autohands_core::redact::Redactor;
 use autohands_core::registry::{ProviderRegistry, ToolRegistry};
 use autohands_protocols::agent::{Agent, AgentContext};
 use autohands_protocols::error::AgentError;
 use autohands_protocols::provider::{ChunkType, CompletionChunk};
-use autohands_protocols::tool::ToolContext;
-use autohands_protocols::types::{Message, ToolCall};
+use autohands_protocols::tool::{OutputStream, ToolOutputChunk, ToolOutputSink};
+use autohands_protocols::types::{Message, ToolCall, Usage};
 
-use crate::AgentLoopConfig;
+use crate::feature_flags::FeatureFlags;
+use crate::progress::ProgressTracker;
+use crate::tool_pipeline::ToolPipeline;
+use crate::{AgentLoopConfig, ToolApproval};
 
 /// Event emitted during streaming execution.
 #[derive(Debug, Clone)]
@@ -28,8 +33,14 @@ pub enum StreamEvent {
     ToolCallStart { id: String, name: String },
     /// Tool call input delta.
     ToolCallDelta { id: String, input_delta: String },
+    /// Output a running tool produced so far.
+    ToolOutput { id: String, stream: OutputStream, content: String },
     /// Tool call completed.
     ToolCallComplete { id: String, result: String },
+    /// Tokens used by a model call.
+    Usage { usage: Usage },
+    /// The agent moved to a step of its plan.
+    PlanStep { index: u32, total: u32, title: String },
     /// Turn completed.
     TurnComplete { turn: u32 },
     /// Agent completed.
@@ -52,21 +63,57 @@ impl Stream for AgentEventStream {
 }
 
 /// Streaming agent loop executor.
+///
+/// Tool calls go through the same checks as in [`AgentLoop`](crate::AgentLoop).
 pub struct StreamingAgentLoop {
-    tool_registry: Arc<ToolRegistry>,
+    tools: ToolPipeline,
+    progress: Option<Arc<ProgressTracker>>,
 }
 
 impl StreamingAgentLoop {
     pub fn new(
         _provider_registry: Arc<ProviderRegistry>,
         tool_registry: Arc<ToolRegistry>,
-        _config: AgentLoopConfig,
+        config: AgentLoopConfig,
     ) -> Self {
         Self {
-            tool_registry,
+            tools: ToolPipeline::new(tool_registry, config.max_tool_output_chars),
+            progress: None,
         }
     }
 
+    /// Require human approval for risky tool calls.
+    pub fn with_approval(mut self, approval: ToolApproval) -> Self {
+        self.tools.approval = Some(approval);
+        self
+    }
+
+    /// Gate auto-approval with feature flags, evaluated for the context's
+    /// working directory.
+    pub fn with_feature_flags(mut self, flags: Arc<FeatureFlags>) -> Self {
+        self.tools.feature_flags = Some(flags);
+        self
+    }
+
+    /// Record tool calls in an audit log.
+    pub fn with_audit_log(mut self, audit_log: Arc<AuditLog>) -> Self {
+        self.tools.audit_log = Some(audit_log);
+        self
+    }
+
+    /// Scrub secrets from tool results before the model and the event
+    /// stream see them.
+    pub fn with_redactor(mut self, redactor: Arc<dyn Redactor>) -> Self {
+        self.tools.redactor = Some(redactor);
+        self
+    }
+
+    /// Track session progress from the emitted events.
+    pub fn with_progress(mut self, progress: Arc<ProgressTracker>) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
     /// Run the streaming agent loop.
     pub fn run_stream(
         &self,
@@ -76,13 +123,20 @@ impl StreamingAgentLoop {
     ) -> AgentEventStream {
         let (tx, rx) = mpsc::channel(100);
 
-        let tool_registry = self.tool_registry.clone();
+        let tools = self.tools.clone();
+        if let Some(ref progress) = self.progress {
+            progress.start(&ctx.session_id);
+        }
+        let progress = self.progress.clone();
+        let session_id = ctx.session_id.clone();
 
         let error_tx = tx.clone();
         tokio::spawn(async move {
             let executor = StreamExecutor {
-                tool_registry,
+                tools,
                 tx,
+                progress,
+                session_id,
             };
             if let Err(e) = executor.execute(agent, ctx, initial_message).await {
                 let _ = error_tx.send(StreamEvent::Error { error: e.to_string() }).await;
@@ -94,8 +148,10 @@ impl StreamingAgentLoop {
 }
 
 struct StreamExecutor {
-    tool_registry: Arc<ToolRegistry>,
+    tools: ToolPipeline,
     tx: mpsc::Sender<StreamEvent>,
+    progress: Option<Arc<ProgressTracker>>,
+    session_id: String,
 }
 
 impl StreamExecutor {
@@ -148,6 +204,10 @@ impl StreamExecutor {
 
             messages.push(response.message.clone());
 
+            if let Some(usage) = response.usage.clone() {
+                self.send(StreamEvent::Usage { usage }).await;
+            }
+
             // Emit text content
             let text = response.message.content.text();
             if !text.is_empty() {
@@ -171,7 +231,11 @@ impl StreamExecutor {
                 })
                 .await;
 
-                let result = self.execute_tool(tool_call, &ctx).await;
+                let result = self
+                    .tools
+                    .run(agent.id(), tool_call, &ctx, Some(self.tool_output(tool_call)))
+                    .await
+                    .content;
 
                 self.send(StreamEvent::ToolCallComplete {
                     id: tool_call.id.clone(),
@@ -190,24 +254,30 @@ impl StreamExecutor {
     }
 
     async fn send(&self, event: StreamEvent) {
+        if let Some(ref progress) = self.progress {
+            progress.record(&self.session_id, &event);
+        }
         let _ = self.tx.send(event).await;
     }
 
-    async fn execute_tool(&self, tool_call: &ToolCall, ctx: &AgentContext) -> String {
-        let tool = match self.tool_registry.get(&tool_call.name) {
-            Some(t) => t,
-            None => return format!("Tool not found: {}", tool_call.name),
-        };
-
-        let work_dir = ctx.work_dir.clone().unwrap_or_else(||
-            std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."))
-        );
-        let tool_ctx = ToolContext::new(&ctx.session_id, work_dir);
-
-        match tool.execute(tool_call.arguments.clone(), tool_ctx).await {
-            Ok(result) => result.content,
-            Err(e) => format!("Tool error: {}", e),
-        }
+    /// Sink streaming a running tool's output as events.
+    fn tool_output(&self, tool_call: &ToolCall) -> ToolOutputSink {
+        let tx = self.tx.clone();
+        let progress = self.progress.clone();
+        let session_id = self.session_id.clone();
+        let id = tool_call.id.clone();
+        Arc::new(move |chunk: ToolOutputChunk| {
+            let event = StreamEvent::ToolOutput {
+                id: id.clone(),
+                stream: chunk.stream,
+                content: chunk.content,
+            };
+            if let Some(ref progress) = progress {
+                progress.record(&session_id, &event);
+            }
+            // Output is dropped rather than stalling the tool on a slow reader
+            let _ = tx.try_send(event);
+        })
     }
 }
 
@@ -0,0 +1,175 @@
+//! Summarization strategies for long inputs.
+//!
+//! A [`StrategySummarizer`] wraps any [`Summarizer`] and decides how a long
+//! message list is fed to it:
+//!
+//! - `single`: everything in one call (the wrapped summarizer's behavior)
+//! - `map_reduce`: chunks summarized concurrently, then the partial
+//!   summaries summarized once
+//! - `rolling`: chunks summarized in order, each call seeing the summary so far
+//! - `hierarchical`: chunks summarized, then groups of summaries summarized
+//!   level by level until one remains
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use autohands_protocols::error::ProviderError;
+use autohands_protocols::types::Message;
+
+use crate::summarizer::Summarizer;
+
+/// Default number of messages per chunk.
+pub const DEFAULT_CHUNK_SIZE: usize = 20;
+
+/// How a long input is split across summarization calls.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SummaryStrategy {
+    /// One call over all messages.
+    #[default]
+    Single,
+    /// Summarize chunks concurrently, then combine once.
+    MapReduce,
+    /// Summarize chunks in order, carrying the summary forward.
+    Rolling,
+    /// Summarize chunks, then summaries of summaries until one remains.
+    Hierarchical,
+}
+
+impl FromStr for SummaryStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "single" => Ok(Self::Single),
+            "map_reduce" => Ok(Self::MapReduce),
+            "rolling" => Ok(Self::Rolling),
+            "hierarchical" => Ok(Self::Hierarchical),
+            other => Err(format!(
+                "Unknown summary strategy '{}' (expected single, map_reduce, rolling or hierarchical)",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for SummaryStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Single => "single",
+            Self::MapReduce => "map_reduce",
+            Self::Rolling => "rolling",
+            Self::Hierarchical => "hierarchical",
+        })
+    }
+}
+
+/// Summarizer applying a [`SummaryStrategy`] on top of another summarizer.
+pub struct StrategySummarizer {
+    inner: Arc<dyn Summarizer>,
+    strategy: SummaryStrategy,
+    chunk_size: usize,
+}
+
+impl StrategySummarizer {
+    /// Wrap a summarizer.
+    pub fn new(inner: Arc<dyn Summarizer>, strategy: SummaryStrategy) -> Self {
+        Self {
+            inner,
+            strategy,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+        }
+    }
+
+    /// Set the messages per chunk (at least 2).
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(2);
+        self
+    }
+
+    /// The strategy in use.
+    pub fn strategy(&self) -> SummaryStrategy {
+        self.strategy
+    }
+
+    async fn summarize_chunks(&self, messages: &[Message]) -> Result<Vec<String>, ProviderError> {
+        let calls = messages
+            .chunks(self.chunk_size)
+            .map(|chunk| self.inner.summarize(chunk));
+        futures::future::try_join_all(calls).await
+    }
+
+    async fn map_reduce(&self, messages: &[Message]) -> Result<String, ProviderError> {
+        let partials = self.summarize_chunks(messages).await?;
+        self.inner.summarize(&as_messages(&partials)).await
+    }
+
+    async fn rolling(&self, messages: &[Message]) -> Result<String, ProviderError> {
+        let mut summary = String::new();
+        for chunk in messages.chunks(self.chunk_size) {
+            let mut input = Vec::with_capacity(chunk.len() + 1);
+            if !summary.is_empty() {
+                input.push(Message::system(format!("Summary so far:\n{}", summary)));
+            }
+            input.extend(chunk.iter().cloned());
+            summary = self.inner.summarize(&input).await?;
+        }
+        Ok(summary)
+    }
+
+    async fn hierarchical(&self, messages: &[Message]) -> Result<String, ProviderError> {
+        let mut level = self.summarize_chunks(messages).await?;
+        let mut depth = 1;
+        while level.len() > 1 {
+            debug!("Hierarchical summary level {}: {} summaries", depth, level.len());
+            level = self.summarize_chunks(&as_messages(&level)).await?;
+            depth += 1;
+        }
+        Ok(level.pop().unwrap_or_default())
+    }
+}
+
+#[async_trait]
+impl Summarizer for StrategySummarizer {
+    async fn summarize(&self, messages: &[Message]) -> Result<String, ProviderError> {
+        // Short inputs need no splitting whatever the strategy
+        if messages.len() <= self.chunk_size {
+            return self.inner.summarize(messages).await;
+        }
+
+        debug!(
+            "Summarizing {} messages with {} strategy (chunk size {})",
+            messages.len(),
+            self.strategy,
+            self.chunk_size
+        );
+        match self.strategy {
+            SummaryStrategy::Single => self.inner.summarize(messages).await,
+            SummaryStrategy::MapReduce => self.map_reduce(messages).await,
+            SummaryStrategy::Rolling => self.rolling(messages).await,
+            SummaryStrategy::Hierarchical => self.hierarchical(messages).await,
+        }
+    }
+
+    fn needs_summarization(&self, message_count: usize) -> bool {
+        self.inner.needs_summarization(message_count)
+    }
+}
+
+/// Present partial summaries as messages for the next pass.
+fn as_messages(summaries: &[String]) -> Vec<Message> {
+    summaries
+        .iter()
+        .enumerate()
+        .map(|(i, s)| Message::user(format!("Summary of part {}:\n{}", i + 1, s)))
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "summary_strategy_tests.rs"]
+mod tests;
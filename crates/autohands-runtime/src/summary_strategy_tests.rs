@@ -0,0 +1,103 @@
+//! Tests for summarization strategies.
+
+use std::sync::Mutex;
+
+use super::*;
+
+/// Records the size of every call and summarizes as `S<n>` (or the summary
+/// so far plus the new messages for rolling inputs).
+struct CountingSummarizer {
+    calls: Mutex<Vec<usize>>,
+}
+
+impl CountingSummarizer {
+    fn new() -> Arc<Self> {
+        Arc::new(Self {
+            calls: Mutex::new(Vec::new()),
+        })
+    }
+
+    fn calls(&self) -> Vec<usize> {
+        self.calls.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl Summarizer for CountingSummarizer {
+    async fn summarize(&self, messages: &[Message]) -> Result<String, ProviderError> {
+        self.calls.lock().unwrap().push(messages.len());
+        Ok(format!("S{}", messages.len()))
+    }
+
+    fn needs_summarization(&self, message_count: usize) -> bool {
+        message_count > 3
+    }
+}
+
+fn messages(n: usize) -> Vec<Message> {
+    (0..n).map(|i| Message::user(format!("message {}", i))).collect()
+}
+
+async fn run(strategy: SummaryStrategy, n: usize) -> (String, Vec<usize>) {
+    let inner = CountingSummarizer::new();
+    let summarizer = StrategySummarizer::new(inner.clone(), strategy).with_chunk_size(3);
+    let summary = summarizer.summarize(&messages(n)).await.unwrap();
+    (summary, inner.calls())
+}
+
+#[tokio::test]
+async fn test_single() {
+    assert_eq!(run(SummaryStrategy::Single, 10).await, ("S10".to_string(), vec![10]));
+}
+
+#[tokio::test]
+async fn test_short_input_is_not_split() {
+    for strategy in [
+        SummaryStrategy::MapReduce,
+        SummaryStrategy::Rolling,
+        SummaryStrategy::Hierarchical,
+    ] {
+        assert_eq!(run(strategy, 3).await.1, vec![3]);
+    }
+}
+
+#[tokio::test]
+async fn test_map_reduce() {
+    // Four chunks (3+3+3+1), then one call over the four partials
+    let (summary, mut calls) = run(SummaryStrategy::MapReduce, 10).await;
+    assert_eq!(summary, "S4");
+    let reduce = calls.pop().unwrap();
+    calls.sort();
+    assert_eq!((calls, reduce), (vec![1, 3, 3, 3], 4));
+}
+
+#[tokio::test]
+async fn test_rolling() {
+    // Later chunks also see the summary so far
+    let (summary, calls) = run(SummaryStrategy::Rolling, 10).await;
+    assert_eq!(calls, vec![3, 4, 4, 2]);
+    assert_eq!(summary, "S2");
+}
+
+#[tokio::test]
+async fn test_hierarchical() {
+    // 10 messages -> 4 summaries -> 2 -> 1
+    let (summary, calls) = run(SummaryStrategy::Hierarchical, 10).await;
+    assert_eq!(calls.len(), 4 + 2 + 1);
+    assert_eq!(summary, "S2");
+}
+
+#[test]
+fn test_strategy_parse() {
+    assert_eq!("map-reduce".parse::<SummaryStrategy>().unwrap(), SummaryStrategy::MapReduce);
+    assert_eq!("Rolling".parse::<SummaryStrategy>().unwrap(), SummaryStrategy::Rolling);
+    assert_eq!(SummaryStrategy::Hierarchical.to_string(), "hierarchical");
+    assert!("tree".parse::<SummaryStrategy>().is_err());
+}
+
+#[test]
+fn test_delegates_needs_summarization() {
+    let summarizer = StrategySummarizer::new(CountingSummarizer::new(), SummaryStrategy::Rolling);
+    assert!(summarizer.needs_summarization(4));
+    assert!(!summarizer.needs_summarization(3));
+}
@@ -0,0 +1,208 @@
+//! Direct tool invocation.
+//!
+//! A [`ToolInvoker`] runs a single tool call without a model, applying the
+//! same checks as tool calls in an agent loop: only enabled tools run, risky
+//! tools go through the approval gate, and usage stats are recorded. On top
+//! of that each call is bounded by a timeout and logged (tracing target
+//! [`AUDIT_TARGET`] and, if set, the [`AuditLog`]). Calls wait for the
+//! exclusive resources their tool declares, like calls in an agent loop, and
+//! are refused while one of them is suspended. With a [`Redactor`], secrets
+//! are scrubbed from results before they are returned or audited. With a
+//! [`SandboxPolicy`], every call runs under it, whichever entry point (API,
+//! workflow step) made it.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use thiserror::Error;
+use tracing::info;
+
+use autohands_core::audit::{AuditEntry, AuditKind, AuditLog};
+use autohands_core::redact::{redact_json, Redactor};
+use autohands_core::registry::ToolRegistry;
+use autohands_core::ExecutionContext;
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{SandboxPolicy, ToolResult};
+
+use crate::agent_loop::ToolApproval;
+
+/// Default limit on one invocation.
+pub const DEFAULT_INVOKE_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Tracing target of audit records.
+pub const AUDIT_TARGET: &str = "autohands::audit";
+
+/// Why a tool invocation did not produce a result.
+#[derive(Debug, Error)]
+pub enum InvokeError {
+    #[error("Tool not found: {0}")]
+    NotFound(String),
+
+    #[error("{0}")]
+    Refused(String),
+
+    #[error("Tool '{0}' timed out after {1:?}")]
+    Timeout(String, Duration),
+
+    #[error(transparent)]
+    Tool(#[from] ToolError),
+}
+
+/// Runs single tool calls outside an agent loop.
+#[derive(Clone)]
+pub struct ToolInvoker {
+    tool_registry: Arc<ToolRegistry>,
+    approval: Option<ToolApproval>,
+    audit_log: Option<Arc<AuditLog>>,
+    redactor: Option<Arc<dyn Redactor>>,
+    sandbox: Option<Arc<SandboxPolicy>>,
+    timeout: Duration,
+}
+
+impl ToolInvoker {
+    /// Create an invoker over the given tools.
+    pub fn new(tool_registry: Arc<ToolRegistry>) -> Self {
+        Self {
+            tool_registry,
+            approval: None,
+            audit_log: None,
+            redactor: None,
+            sandbox: None,
+            timeout: DEFAULT_INVOKE_TIMEOUT,
+        }
+    }
+
+    /// Require human approval for risky tools.
+    pub fn with_approval(mut self, approval: ToolApproval) -> Self {
+        self.approval = Some(approval);
+        self
+    }
+
+    /// Record every call in an audit log.
+    pub fn with_audit_log(mut self, audit_log: Arc<AuditLog>) -> Self {
+        self.audit_log = Some(audit_log);
+        self
+    }
+
+    /// Scrub secrets from results.
+    pub fn with_redactor(mut self, redactor: Arc<dyn Redactor>) -> Self {
+        self.redactor = Some(redactor);
+        self
+    }
+
+    /// Confine every call to a sandbox.
+    pub fn with_sandbox(mut self, sandbox: Arc<SandboxPolicy>) -> Self {
+        self.sandbox = Some(sandbox);
+        self
+    }
+
+    /// Set the timeout used when a call does not give one.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Run a tool in the given context.
+    ///
+    /// On timeout the context is aborted, so tools checking their abort
+    /// signal stop promptly.
+    pub async fn invoke(
+        &self,
+        tool_id: &str,
+        params: serde_json::Value,
+        ctx: &ExecutionContext,
+        timeout: Option<Duration>,
+    ) -> Result<ToolResult, InvokeError> {
+        let started = Instant::now();
+        let result = self.run(tool_id, params.clone(), ctx, timeout).await;
+
+        let outcome = match &result {
+            Ok(r) if r.success => "success".to_string(),
+            Ok(_) => "failed".to_string(),
+            Err(e) => format!("error: {}", e),
+        };
+        if let Some(audit_log) = &self.audit_log {
+            let (outcome, summary) = match &result {
+                Ok(r) if r.success => ("success", r.content.clone()),
+                Ok(r) => ("failed", r.content.clone()),
+                Err(e @ InvokeError::Refused(_)) => ("refused", e.to_string()),
+                Err(e) => ("error", e.to_string()),
+            };
+            audit_log.record(
+                AuditEntry::new(AuditKind::ToolCall, outcome)
+                    .with_session(ctx.session_id.clone())
+                    .with_correlation_id(ctx.correlation_id.clone())
+                    .with_name(tool_id)
+                    .with_params(params)
+                    .with_summary(&summary)
+                    .with_duration_ms(started.elapsed().as_millis() as u64),
+            );
+        }
+        info!(
+            target: AUDIT_TARGET,
+            "tool={} session={} correlation_id={} outcome={} duration_ms={}",
+            tool_id,
+            ctx.session_id,
+            ctx.correlation_id,
+            outcome,
+            started.elapsed().as_millis()
+        );
+        result
+    }
+
+    async fn run(
+        &self,
+        tool_id: &str,
+        params: serde_json::Value,
+        ctx: &ExecutionContext,
+        timeout: Option<Duration>,
+    ) -> Result<ToolResult, InvokeError> {
+        let tool = self
+            .tool_registry
+            .get(tool_id)
+            .ok_or_else(|| InvokeError::NotFound(tool_id.to_string()))?;
+
+        if let Some(approval) = &self.approval {
+            if let Some(refusal) = approval
+                .check(tool_id, tool.risk_level(), &params, &ctx.session_id)
+                .await
+            {
+                return Err(InvokeError::Refused(refusal));
+            }
+        }
+
+        // Waiting for a busy exclusive resource counts towards the timeout
+        let timeout = timeout.unwrap_or(self.timeout);
+        let mut tool_ctx = ctx.tool_context();
+        if let Some(sandbox) = &self.sandbox {
+            tool_ctx = tool_ctx.with_sandbox(sandbox.clone());
+        }
+        let call = async move {
+            let _resources = self.tool_registry.lock_resources(tool.definition(), &params).await?;
+            tool.execute(params, tool_ctx).await
+        };
+        let result = match tokio::time::timeout(timeout, call).await {
+            Ok(result) => result.map_err(InvokeError::from),
+            Err(_) => {
+                ctx.abort();
+                Err(InvokeError::Timeout(tool_id.to_string(), timeout))
+            }
+        };
+        self.tool_registry.record_usage(tool_id, result.is_ok());
+        match (&self.redactor, result) {
+            (Some(redactor), Ok(mut result)) => {
+                result.content = redactor.redact(&result.content).into_owned();
+                result.error = result.error.map(|e| redactor.redact(&e).into_owned());
+                if let Some(output) = &mut result.structured_output {
+                    redact_json(redactor.as_ref(), output);
+                }
+                Ok(result)
+            }
+            (_, result) => result,
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tool_invoker_tests.rs"]
+mod tests;
@@ -0,0 +1,235 @@
+use super::*;
+use async_trait::async_trait;
+use autohands_core::audit::AuditLog;
+use autohands_protocols::approval::{
+    ApprovalDecision, ApprovalGate, ApprovalRequest, ToolApprovalPolicy,
+};
+use autohands_protocols::tool::{PathAccess, SandboxPolicy, Tool, ToolContext, ToolDefinition};
+use autohands_protocols::types::RiskLevel;
+use std::path::PathBuf;
+
+struct TestTool {
+    definition: ToolDefinition,
+    delay: Duration,
+}
+
+impl TestTool {
+    fn new(id: &str, risk: RiskLevel) -> Self {
+        Self {
+            definition: ToolDefinition::new(id, id, "test").with_risk_level(risk),
+            delay: Duration::ZERO,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for TestTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        tokio::time::sleep(self.delay).await;
+        if let Some(path) = params.get("write").and_then(|p| p.as_str()) {
+            ctx.resolve_path(path, PathAccess::Write)?;
+        }
+        match params.get("text").and_then(|t| t.as_str()) {
+            Some(text) => Ok(ToolResult::success(text)),
+            None => Err(ToolError::InvalidParameters("missing text".to_string())),
+        }
+    }
+}
+
+struct RejectingGate;
+
+#[async_trait]
+impl ApprovalGate for RejectingGate {
+    async fn request_approval(&self, _request: ApprovalRequest) -> ApprovalDecision {
+        ApprovalDecision::rejected().with_comment("not now")
+    }
+
+    async fn pending(&self) -> Vec<ApprovalRequest> {
+        Vec::new()
+    }
+
+    async fn decide(&self, _request_id: &str, _decision: ApprovalDecision) -> bool {
+        false
+    }
+}
+
+fn registry(tools: Vec<TestTool>) -> Arc<ToolRegistry> {
+    let registry = Arc::new(ToolRegistry::new());
+    for tool in tools {
+        registry.register(Arc::new(tool)).unwrap();
+    }
+    registry
+}
+
+fn context() -> ExecutionContext {
+    ExecutionContext::new("test-session", PathBuf::from("."))
+}
+
+#[tokio::test]
+async fn test_invoke_success_records_usage() {
+    let tools = registry(vec![TestTool::new("echo", RiskLevel::Low)]);
+    let invoker = ToolInvoker::new(tools.clone());
+
+    let result = invoker
+        .invoke("echo", serde_json::json!({"text": "hi"}), &context(), None)
+        .await
+        .unwrap();
+    assert!(result.success);
+    assert_eq!(result.content, "hi");
+    assert_eq!(tools.usage("echo").calls, 1);
+    assert_eq!(tools.usage("echo").errors, 0);
+}
+
+#[tokio::test]
+async fn test_invoke_tool_error() {
+    let tools = registry(vec![TestTool::new("echo", RiskLevel::Low)]);
+    let invoker = ToolInvoker::new(tools.clone());
+
+    let err = invoker
+        .invoke("echo", serde_json::json!({}), &context(), None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, InvokeError::Tool(ToolError::InvalidParameters(_))));
+    assert_eq!(tools.usage("echo").errors, 1);
+}
+
+#[tokio::test]
+async fn test_invoke_unknown_and_disabled_tool() {
+    let tools = registry(vec![TestTool::new("echo", RiskLevel::Low)]);
+    tools.set_enabled("echo", false).unwrap();
+    let invoker = ToolInvoker::new(tools);
+
+    for id in ["missing", "echo"] {
+        let err = invoker
+            .invoke(id, serde_json::json!({"text": "hi"}), &context(), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, InvokeError::NotFound(ref t) if t == id));
+    }
+}
+
+#[tokio::test]
+async fn test_invoke_timeout_aborts_context() {
+    let mut slow = TestTool::new("slow", RiskLevel::Low);
+    slow.delay = Duration::from_secs(5);
+    let invoker = ToolInvoker::new(registry(vec![slow])).with_timeout(Duration::from_millis(20));
+    let ctx = context();
+
+    let err = invoker
+        .invoke("slow", serde_json::json!({"text": "hi"}), &ctx, None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, InvokeError::Timeout(_, t) if t == Duration::from_millis(20)));
+    assert!(ctx.is_aborted());
+}
+
+#[tokio::test]
+async fn test_invoke_waits_for_exclusive_resources() {
+    let mut mouse = TestTool::new("mouse", RiskLevel::Low);
+    mouse.definition = mouse.definition.with_exclusive_resource("desktop-input");
+    let tools = registry(vec![mouse]);
+    let invoker = ToolInvoker::new(tools.clone()).with_timeout(Duration::from_millis(20));
+
+    // Another session holds the input
+    let tool = tools.get("mouse").unwrap();
+    let held = tools
+        .lock_resources(tool.definition(), &serde_json::json!({}))
+        .await
+        .unwrap();
+    let err = invoker
+        .invoke("mouse", serde_json::json!({"text": "hi"}), &context(), None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, InvokeError::Timeout(..)));
+
+    drop(held);
+    let result = invoker
+        .invoke("mouse", serde_json::json!({"text": "hi"}), &context(), None)
+        .await
+        .unwrap();
+    assert_eq!(result.content, "hi");
+    assert!(tools.resource_locks().is_empty());
+
+    // A suspended resource refuses calls
+    tools.resource_locks().suspend("desktop-input", "paused by the user");
+    let err = invoker
+        .invoke("mouse", serde_json::json!({"text": "hi"}), &context(), None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, InvokeError::Tool(ToolError::PermissionDenied(_))));
+}
+
+#[tokio::test]
+async fn test_invoke_risky_tool_refused() {
+    let tools = registry(vec![
+        TestTool::new("exec", RiskLevel::High),
+        TestTool::new("echo", RiskLevel::Low),
+    ]);
+    let invoker = ToolInvoker::new(tools.clone()).with_approval(ToolApproval {
+        gate: Arc::new(RejectingGate),
+        policy: ToolApprovalPolicy::threshold(RiskLevel::High),
+    });
+
+    let err = invoker
+        .invoke("exec", serde_json::json!({"text": "hi"}), &context(), None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, InvokeError::Refused(ref msg) if msg.contains("not now")));
+    assert_eq!(tools.usage("exec").calls, 0);
+
+    assert!(invoker
+        .invoke("echo", serde_json::json!({"text": "hi"}), &context(), None)
+        .await
+        .is_ok());
+}
+
+#[tokio::test]
+async fn test_invocations_audited() {
+    let dir = tempfile::tempdir().unwrap();
+    let audit = Arc::new(AuditLog::open(dir.path().join("audit.jsonl")).unwrap());
+    let tools = registry(vec![
+        TestTool::new("exec", RiskLevel::High),
+        TestTool::new("echo", RiskLevel::Low),
+    ]);
+    let invoker = ToolInvoker::new(tools)
+        .with_approval(ToolApproval {
+            gate: Arc::new(RejectingGate),
+            policy: ToolApprovalPolicy::threshold(RiskLevel::High),
+        })
+        .with_audit_log(audit.clone());
+
+    let params = serde_json::json!({"text": "hi", "token": "t0p"});
+    let _ = invoker.invoke("exec", params.clone(), &context(), None).await;
+    let _ = invoker.invoke("echo", params, &context(), None).await;
+
+    let entries = audit.query(&Default::default()).unwrap();
+    let outcomes: Vec<_> = entries.iter().map(|e| e.outcome.as_str()).collect();
+    assert_eq!(outcomes, vec!["refused", "success"]);
+    assert_eq!(entries[1].name.as_deref(), Some("echo"));
+    assert_eq!(entries[1].params["token"], "<redacted>");
+    assert_eq!(entries[1].summary.as_deref(), Some("hi"));
+}
+
+#[tokio::test]
+async fn test_invocations_run_under_sandbox() {
+    let tools = registry(vec![TestTool::new("write", RiskLevel::Low)]);
+    let params = serde_json::json!({"text": "ok", "write": "out.txt"});
+
+    let unconfined = ToolInvoker::new(tools.clone());
+    assert!(unconfined.invoke("write", params.clone(), &context(), None).await.is_ok());
+
+    let sandboxed = ToolInvoker::new(tools).with_sandbox(Arc::new(SandboxPolicy {
+        read_only: true,
+        ..Default::default()
+    }));
+    let err = sandboxed.invoke("write", params, &context(), None).await.unwrap_err();
+    assert!(matches!(err, InvokeError::Tool(ToolError::PermissionDenied(_))));
+}
@@ -0,0 +1,251 @@
+//! Tool call pipeline shared by the agent loops.
+//!
+//! [`AgentLoop`](crate::AgentLoop) and
+//! [`StreamingAgentLoop`](crate::StreamingAgentLoop) run a model's tool calls
+//! through the same steps: the run's tool allowlist, the approval gate, a
+//! tool context carrying the run's sandbox, the tool's exclusive resources,
+//! execution (simulation in a dry run), usage stats, secret redaction and
+//! output truncation. Each call is then recorded in the audit log.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Instant;
+
+use tracing::{debug, warn};
+
+use autohands_core::audit::{AuditEntry, AuditKind, AuditLog};
+use autohands_core::redact::Redactor;
+use autohands_core::registry::ToolRegistry;
+use autohands_protocols::agent::AgentContext;
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{execute_or_simulate, ToolContext, ToolOutputSink};
+use autohands_protocols::types::{RiskLevel, ToolCall};
+
+use crate::agent_loop::ToolApproval;
+use crate::feature_flags::{self, FeatureFlags, AUTO_APPROVAL};
+use crate::memory_persistence;
+
+/// How a tool call ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ToolOutcome {
+    Success,
+    /// Described instead of run, in a dry run.
+    Simulated,
+    /// Not allowed in the run, or refused by the approval policy or reviewer.
+    Refused,
+    /// Unknown tool, or the tool failed.
+    Error,
+}
+
+impl ToolOutcome {
+    /// Outcome recorded in the audit log.
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Simulated => "simulated",
+            Self::Refused => "refused",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// A tool call's result, as reported to the model.
+pub(crate) struct ToolCallResult {
+    pub content: String,
+    pub outcome: ToolOutcome,
+    pub duration_ms: u64,
+}
+
+/// Runs the tool calls of an agent loop.
+#[derive(Clone)]
+pub(crate) struct ToolPipeline {
+    pub tool_registry: Arc<ToolRegistry>,
+    pub approval: Option<ToolApproval>,
+    pub feature_flags: Option<Arc<FeatureFlags>>,
+    pub audit_log: Option<Arc<AuditLog>>,
+    pub redactor: Option<Arc<dyn Redactor>>,
+    /// Longest tool output passed on, in bytes; 0 for no limit.
+    pub max_output_chars: usize,
+}
+
+impl ToolPipeline {
+    pub(crate) fn new(tool_registry: Arc<ToolRegistry>, max_output_chars: usize) -> Self {
+        Self {
+            tool_registry,
+            approval: None,
+            feature_flags: None,
+            audit_log: None,
+            redactor: None,
+            max_output_chars,
+        }
+    }
+
+    /// Whether a feature flag is on for the run's workspace.
+    pub(crate) fn flag(&self, flag: &str, ctx: &AgentContext) -> bool {
+        match self.feature_flags {
+            Some(ref flags) => flags.is_enabled_in(flag, ctx.work_dir.as_deref()),
+            None => feature_flags::default_value(flag),
+        }
+    }
+
+    /// Record an audit entry of the run.
+    pub(crate) fn audit(&self, ctx: &AgentContext, mut entry: AuditEntry) {
+        let Some(ref audit_log) = self.audit_log else {
+            return;
+        };
+        entry = entry.with_session(ctx.session_id.clone());
+        if let Some(ref correlation_id) = ctx.correlation_id {
+            entry = entry.with_correlation_id(correlation_id.clone());
+        }
+        audit_log.record(entry);
+    }
+
+    /// Run a tool call for an agent and audit it. Running tools report their
+    /// output to `output`.
+    pub(crate) async fn run(
+        &self,
+        agent_id: &str,
+        tool_call: &ToolCall,
+        ctx: &AgentContext,
+        output: Option<ToolOutputSink>,
+    ) -> ToolCallResult {
+        let started = Instant::now();
+        let (content, outcome) = self.execute(tool_call, ctx, output).await;
+        let duration_ms = started.elapsed().as_millis() as u64;
+        self.audit(
+            ctx,
+            AuditEntry::new(AuditKind::ToolCall, outcome.as_str())
+                .with_agent(agent_id)
+                .with_name(tool_call.name.clone())
+                .with_params(tool_call.arguments.clone())
+                .with_summary(&content)
+                .with_duration_ms(duration_ms),
+        );
+        ToolCallResult {
+            content,
+            outcome,
+            duration_ms,
+        }
+    }
+
+    /// Run a tool call, returning the text reported to the model.
+    pub(crate) async fn execute(
+        &self,
+        tool_call: &ToolCall,
+        ctx: &AgentContext,
+        output: Option<ToolOutputSink>,
+    ) -> (String, ToolOutcome) {
+        if !ctx.overrides.allows_tool(&tool_call.name) {
+            return (
+                format!("Tool not allowed in this run: {}", tool_call.name),
+                ToolOutcome::Refused,
+            );
+        }
+        let tool = match self.tool_registry.get(&tool_call.name) {
+            Some(t) => t,
+            None => return (format!("Tool not found: {}", tool_call.name), ToolOutcome::Error),
+        };
+
+        // Simulated calls change nothing, so they need no approval
+        let simulated = ctx.dry_run && !tool.definition().read_only;
+        if !simulated {
+            if let Some(rejection) = self.check_approval(tool.risk_level(), tool_call, ctx).await {
+                return (rejection, ToolOutcome::Refused);
+            }
+        }
+
+        let work_dir = ctx
+            .work_dir
+            .clone()
+            .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+        let mut tool_ctx = ToolContext::new(&ctx.session_id, work_dir);
+        if let Some(ref correlation_id) = ctx.correlation_id {
+            tool_ctx.correlation_id = correlation_id.clone();
+        }
+        tool_ctx.sandbox = ctx.sandbox.clone();
+        tool_ctx.abort_signal = ctx.abort_signal.child();
+        tool_ctx.scheduling = ctx.scheduling.clone();
+        tool_ctx.dry_run = ctx.dry_run;
+        tool_ctx.output = output;
+
+        let resources = self
+            .tool_registry
+            .lock_resources(tool.definition(), &tool_call.arguments)
+            .await;
+        let params = tool_call.arguments.clone();
+        let result = match resources {
+            // Dropping the call on abort stops the work it was awaiting
+            Ok(resources) => tokio::select! {
+                result = execute_or_simulate(tool.as_ref(), params, tool_ctx) => result,
+                _ = ctx.abort_signal.aborted() => Err(ToolError::Cancelled),
+                // An aborted resource, e.g. the desktop kill switch, ends the run
+                _ = resources.revoked() => {
+                    ctx.abort_signal.abort();
+                    Err(ToolError::Cancelled)
+                }
+            },
+            Err(ToolError::Cancelled) => {
+                ctx.abort_signal.abort();
+                Err(ToolError::Cancelled)
+            }
+            Err(e) => Err(e),
+        };
+        self.tool_registry.record_usage(&tool_call.name, result.is_ok());
+        let (result, outcome) = match result {
+            Ok(result) if simulated => (result.content, ToolOutcome::Simulated),
+            Ok(result) => (result.content, ToolOutcome::Success),
+            Err(e) => (format!("Tool error: {}", e), ToolOutcome::Error),
+        };
+        let result = match &self.redactor {
+            Some(redactor) => redactor.redact(&result).into_owned(),
+            None => result,
+        };
+
+        (self.truncate_output(result), outcome)
+    }
+
+    /// Ask for approval if the tool is risky enough.
+    ///
+    /// Returns the message reported to the model when the call is refused.
+    async fn check_approval(
+        &self,
+        risk_level: RiskLevel,
+        tool_call: &ToolCall,
+        ctx: &AgentContext,
+    ) -> Option<String> {
+        let approval = self.approval.as_ref()?;
+        if self.flag(AUTO_APPROVAL, ctx) {
+            debug!("Tool call {} auto-approved", tool_call.name);
+            return None;
+        }
+        approval
+            .check(&tool_call.name, risk_level, &tool_call.arguments, &ctx.session_id)
+            .await
+    }
+
+    /// 截断过大的工具输出，防止撑爆上下文。
+    fn truncate_output(&self, content: String) -> String {
+        let max = self.max_output_chars;
+        if max == 0 || content.len() <= max {
+            return content;
+        }
+        warn!(
+            "Tool output truncated: {} chars -> {} chars",
+            content.len(),
+            max
+        );
+        // 确保在 char 边界上截断
+        let boundary = memory_persistence::floor_char_boundary(&content, max);
+        let truncated = &content[..boundary];
+        format!(
+            "{}\n\n[OUTPUT TRUNCATED: original {} chars, showing first {}. Use more specific queries to reduce output size.]",
+            truncated,
+            content.len(),
+            max
+        )
+    }
+}
+
+#[cfg(test)]
+#[path = "tool_pipeline_tests.rs"]
+mod tests;
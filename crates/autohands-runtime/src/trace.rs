@@ -0,0 +1,214 @@
+//! Task trace timelines.
+//!
+//! Work is traced under a correlation ID that follows it from the inbound
+//! message through the RunLoop task, each agent turn, provider call and tool
+//! execution, to the outbound reply. Each stage records a [`TraceEvent`] in
+//! the [`TraceLog`], so the whole timeline of a task can be read back by its
+//! correlation ID or by the ID of any task traced under it.
+
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Number of traces kept.
+pub const TRACE_LOG_CAPACITY: usize = 1000;
+
+/// Events kept per trace; later ones are dropped.
+pub const MAX_TRACE_EVENTS: usize = 500;
+
+/// Stage of traced work.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceEventKind {
+    /// An inbound message arrived and became a task.
+    MessageReceived,
+    /// The RunLoop started the task.
+    TaskStarted,
+    /// An agent turn started.
+    TurnStarted,
+    /// The agent called its model.
+    ProviderCall,
+    /// A tool ran.
+    ToolCall,
+    /// An agent turn ended.
+    TurnCompleted,
+    /// The task finished.
+    TaskFinished,
+    /// The result was sent to a channel.
+    MessageSent,
+}
+
+/// One step of a trace.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraceEvent {
+    /// When the step happened (or started, for timed steps).
+    pub at: DateTime<Utc>,
+    /// Stage.
+    pub kind: TraceEventKind,
+    /// Task the step belongs to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<Uuid>,
+    /// Agent session the step ran in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// Turn the step belongs to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub turn: Option<u32>,
+    /// Tool, channel or other target of the step.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// How long the step took.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    /// Whether the step failed.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub failed: bool,
+    /// Step details.
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub detail: serde_json::Value,
+}
+
+impl TraceEvent {
+    /// Event of a stage happening now.
+    pub fn new(kind: TraceEventKind) -> Self {
+        Self {
+            at: Utc::now(),
+            kind,
+            task_id: None,
+            session_id: None,
+            turn: None,
+            name: None,
+            duration_ms: None,
+            failed: false,
+            detail: serde_json::Value::Null,
+        }
+    }
+
+    /// Set the task.
+    pub fn with_task(mut self, task_id: Uuid) -> Self {
+        self.task_id = Some(task_id);
+        self
+    }
+
+    /// Set the agent session.
+    pub fn with_session(mut self, session_id: impl Into<String>) -> Self {
+        self.session_id = Some(session_id.into());
+        self
+    }
+
+    /// Set the turn.
+    pub fn with_turn(mut self, turn: u32) -> Self {
+        self.turn = Some(turn);
+        self
+    }
+
+    /// Set the target name.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Mark a timed step that started at `started`.
+    pub fn started_at(mut self, started: DateTime<Utc>) -> Self {
+        self.duration_ms = Some((self.at - started).num_milliseconds().max(0) as u64);
+        self.at = started;
+        self
+    }
+
+    /// Mark the step failed.
+    pub fn failed(mut self) -> Self {
+        self.failed = true;
+        self
+    }
+
+    /// Set the details.
+    pub fn with_detail(mut self, detail: serde_json::Value) -> Self {
+        self.detail = detail;
+        self
+    }
+}
+
+/// Timeline of a correlation ID.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Trace {
+    /// Correlation ID.
+    pub correlation_id: String,
+    /// Tasks traced under the ID.
+    pub task_ids: Vec<Uuid>,
+    /// Steps in order of recording.
+    pub events: Vec<TraceEvent>,
+}
+
+/// Recent traces, keyed by correlation ID.
+///
+/// Only the last [`TRACE_LOG_CAPACITY`] traces are kept.
+#[derive(Default)]
+pub struct TraceLog {
+    traces: Mutex<VecDeque<Trace>>,
+}
+
+impl TraceLog {
+    /// Create an empty log.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a step under a correlation ID.
+    pub fn record(&self, correlation_id: &str, event: TraceEvent) {
+        let mut traces = self.traces.lock();
+        let index = match traces.iter().position(|t| t.correlation_id == correlation_id) {
+            Some(index) => index,
+            None => {
+                if traces.len() >= TRACE_LOG_CAPACITY {
+                    traces.pop_front();
+                }
+                traces.push_back(Trace {
+                    correlation_id: correlation_id.to_string(),
+                    task_ids: Vec::new(),
+                    events: Vec::new(),
+                });
+                traces.len() - 1
+            }
+        };
+        let trace = &mut traces[index];
+        if let Some(task_id) = event.task_id {
+            if !trace.task_ids.contains(&task_id) {
+                trace.task_ids.push(task_id);
+            }
+        }
+        if trace.events.len() < MAX_TRACE_EVENTS {
+            trace.events.push(event);
+        }
+    }
+
+    /// Trace of a correlation ID, or of the correlation ID a task was
+    /// traced under.
+    pub fn get(&self, id: &str) -> Option<Trace> {
+        let traces = self.traces.lock();
+        if let Some(trace) = traces.iter().find(|t| t.correlation_id == id) {
+            return Some(trace.clone());
+        }
+        let task_id = Uuid::parse_str(id).ok()?;
+        traces
+            .iter()
+            .find(|t| t.task_ids.contains(&task_id))
+            .cloned()
+    }
+
+    /// Number of traces kept.
+    pub fn len(&self) -> usize {
+        self.traces.lock().len()
+    }
+
+    /// Whether no trace is kept.
+    pub fn is_empty(&self) -> bool {
+        self.traces.lock().is_empty()
+    }
+}
+
+#[cfg(test)]
+#[path = "trace_tests.rs"]
+mod tests;
@@ -0,0 +1,74 @@
+use super::*;
+
+#[test]
+fn test_trace_records_in_order() {
+    let log = TraceLog::new();
+    let task_id = Uuid::new_v4();
+
+    log.record("corr-1", TraceEvent::new(TraceEventKind::TaskStarted).with_task(task_id));
+    log.record(
+        "corr-1",
+        TraceEvent::new(TraceEventKind::ToolCall)
+            .with_session("s1")
+            .with_turn(1)
+            .with_name("read_file"),
+    );
+    log.record("corr-2", TraceEvent::new(TraceEventKind::TaskStarted));
+
+    let trace = log.get("corr-1").unwrap();
+    assert_eq!(trace.task_ids, vec![task_id]);
+    assert_eq!(trace.events.len(), 2);
+    assert_eq!(trace.events[0].kind, TraceEventKind::TaskStarted);
+    assert_eq!(trace.events[1].name.as_deref(), Some("read_file"));
+    assert_eq!(log.len(), 2);
+}
+
+#[test]
+fn test_trace_lookup_by_task_id() {
+    let log = TraceLog::new();
+    let task_id = Uuid::new_v4();
+    log.record("corr-1", TraceEvent::new(TraceEventKind::TaskStarted).with_task(task_id));
+
+    let trace = log.get(&task_id.to_string()).unwrap();
+    assert_eq!(trace.correlation_id, "corr-1");
+    assert!(log.get(&Uuid::new_v4().to_string()).is_none());
+    assert!(log.get("unknown").is_none());
+}
+
+#[test]
+fn test_trace_event_duration() {
+    let started = Utc::now() - chrono::Duration::milliseconds(250);
+    let event = TraceEvent::new(TraceEventKind::ProviderCall).started_at(started);
+
+    assert_eq!(event.at, started);
+    assert!(event.duration_ms.unwrap() >= 250);
+}
+
+#[test]
+fn test_trace_event_serialization() {
+    let event = TraceEvent::new(TraceEventKind::MessageSent)
+        .with_name("slack")
+        .failed();
+    let json = serde_json::to_value(&event).unwrap();
+
+    assert_eq!(json["kind"], "message_sent");
+    assert_eq!(json["name"], "slack");
+    assert_eq!(json["failed"], true);
+    assert!(json.get("task_id").is_none());
+    assert!(json.get("detail").is_none());
+}
+
+#[test]
+fn test_trace_log_bounded() {
+    let log = TraceLog::new();
+    for i in 0..=TRACE_LOG_CAPACITY {
+        log.record(&format!("corr-{}", i), TraceEvent::new(TraceEventKind::TaskStarted));
+    }
+    assert_eq!(log.len(), TRACE_LOG_CAPACITY);
+    assert!(log.get("corr-0").is_none());
+
+    for _ in 0..MAX_TRACE_EVENTS + 10 {
+        log.record("busy", TraceEvent::new(TraceEventKind::ToolCall));
+    }
+    assert_eq!(log.get("busy").unwrap().events.len(), MAX_TRACE_EVENTS);
+}
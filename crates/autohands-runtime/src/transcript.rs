@@ -1,9 +1,10 @@
 //! Session transcript recording in JSONL format.
 //!
 //! Records all conversation events (messages, tool calls, tool results) to JSONL files
-//! similar to Claude Code's session transcripts.
+//! similar to Claude Code's session transcripts. With a [`Redactor`], secrets are
+//! scrubbed from every entry before it is written.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
@@ -14,6 +15,8 @@ use tokio::sync::Mutex;
 use tracing::debug;
 use uuid::Uuid;
 
+use autohands_core::redact::{redact_json, Redactor};
+
 /// Transcript entry types.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -111,6 +114,7 @@ pub struct TranscriptWriter {
     session_id: String,
     file: Mutex<File>,
     last_uuid: Mutex<Option<String>>,
+    redactor: Option<Arc<dyn Redactor>>,
 }
 
 impl TranscriptWriter {
@@ -132,12 +136,26 @@ impl TranscriptWriter {
             session_id: session_id.to_string(),
             file: Mutex::new(file),
             last_uuid: Mutex::new(None),
+            redactor: None,
         })
     }
 
+    /// Redact entries before they are written.
+    pub fn with_redactor(mut self, redactor: Arc<dyn Redactor>) -> Self {
+        self.redactor = Some(redactor);
+        self
+    }
+
     /// Write an entry to the transcript.
     pub async fn write(&self, entry: &TranscriptEntry) -> std::io::Result<()> {
-        let json = serde_json::to_string(entry)?;
+        let json = match &self.redactor {
+            Some(redactor) => {
+                let mut value = serde_json::to_value(entry)?;
+                redact_json(redactor.as_ref(), &mut value);
+                serde_json::to_string(&value)?
+            }
+            None => serde_json::to_string(entry)?,
+        };
         let mut file = self.file.lock().await;
         file.write_all(json.as_bytes()).await?;
         file.write_all(b"\n").await?;
@@ -299,6 +317,7 @@ impl TranscriptWriter {
 pub struct TranscriptManager {
     base_dir: PathBuf,
     writers: Mutex<std::collections::HashMap<String, Arc<TranscriptWriter>>>,
+    redactor: Option<Arc<dyn Redactor>>,
 }
 
 impl TranscriptManager {
@@ -307,9 +326,16 @@ impl TranscriptManager {
         Self {
             base_dir,
             writers: Mutex::new(std::collections::HashMap::new()),
+            redactor: None,
         }
     }
 
+    /// Redact the entries of every transcript written.
+    pub fn with_redactor(mut self, redactor: Arc<dyn Redactor>) -> Self {
+        self.redactor = Some(redactor);
+        self
+    }
+
     /// Get or create a transcript writer for a session.
     pub async fn get_writer(&self, session_id: &str) -> std::io::Result<Arc<TranscriptWriter>> {
         let mut writers = self.writers.lock().await;
@@ -318,7 +344,11 @@ impl TranscriptManager {
             return Ok(writer.clone());
         }
 
-        let writer = Arc::new(TranscriptWriter::new(session_id, &self.base_dir).await?);
+        let mut writer = TranscriptWriter::new(session_id, &self.base_dir).await?;
+        if let Some(redactor) = &self.redactor {
+            writer = writer.with_redactor(redactor.clone());
+        }
+        let writer = Arc::new(writer);
         writers.insert(session_id.to_string(), writer.clone());
         Ok(writer)
     }
@@ -328,6 +358,11 @@ impl TranscriptManager {
         self.writers.lock().await.remove(session_id);
     }
 
+    /// Directory holding the transcripts.
+    pub fn base_dir(&self) -> &Path {
+        &self.base_dir
+    }
+
     /// Get the transcript file path for a session.
     pub fn transcript_path(&self, session_id: &str) -> PathBuf {
         self.base_dir.join(format!("{}.jsonl", session_id))
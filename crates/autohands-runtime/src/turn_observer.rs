@@ -0,0 +1,10 @@
+//! Turn notifications for the agent loop.
+
+use autohands_protocols::types::Message;
+
+/// Told about every completed agent turn (optional integration).
+#[async_trait::async_trait]
+pub trait TurnObserver: Send + Sync {
+    /// Called after the model answered, before its tool calls run.
+    async fn on_turn_completed(&self, session_id: &str, turn: u32, message: &Message);
+}
@@ -7,11 +7,13 @@
 use async_trait::async_trait;
 use std::sync::Arc;
 
-use autohands_protocols::agent::{Agent, AgentConfig, AgentContext, AgentResponse};
+use autohands_protocols::agent::{Agent, AgentConfig, AgentContext, AgentResponse, RunOverrides};
 use autohands_protocols::error::AgentError;
 use autohands_protocols::provider::LLMProvider;
 use autohands_protocols::tool::Tool;
 use autohands_protocols::types::Message;
+use autohands_runtime::feature_flags::workspace_key;
+use autohands_runtime::LivePrompt;
 
 use crate::executor::SingleTurnExecutor;
 
@@ -32,6 +34,8 @@ pub struct GeneralAgent {
     config: AgentConfig,
     provider: Arc<dyn LLMProvider>,
     tools: Vec<Arc<dyn Tool>>,
+    streaming: bool,
+    live_prompt: Option<Arc<LivePrompt>>,
 }
 
 impl GeneralAgent {
@@ -45,16 +49,37 @@ impl GeneralAgent {
             config,
             provider,
             tools,
+            streaming: false,
+            live_prompt: None,
         }
     }
 
+    /// Stream turns from providers that support it, so a stream failing
+    /// halfway is continued rather than losing its output.
+    pub fn with_streaming(mut self, streaming: bool) -> Self {
+        self.streaming = streaming;
+        self
+    }
+
+    /// Take the system prompt from a [`LivePrompt`] on every turn, so a
+    /// refreshed context reaches the running agent.
+    pub fn with_live_prompt(mut self, prompt: Arc<LivePrompt>) -> Self {
+        self.live_prompt = Some(prompt);
+        self
+    }
+
     /// Create a single-turn executor for this agent.
     fn executor(&self) -> SingleTurnExecutor {
+        let mut config = self.config.clone();
+        if let Some(prompt) = self.live_prompt.as_ref().and_then(|p| p.get()) {
+            config.system_prompt = Some(prompt);
+        }
         SingleTurnExecutor::new(
-            self.config.clone(),
+            config,
             self.provider.clone(),
             self.tools.clone(),
         )
+        .with_streaming(self.streaming)
     }
 }
 
@@ -85,9 +110,30 @@ impl Agent for GeneralAgent {
         message: Message,
         ctx: AgentContext,
     ) -> Result<AgentResponse, AgentError> {
-        let executor = self.executor();
+        let executor = self
+            .executor()
+            .with_correlation_id(ctx.correlation_id)
+            .with_workspace(ctx.work_dir.as_deref().map(workspace_key))
+            .with_overrides(ctx.overrides);
         executor.execute(message, ctx.history).await
     }
+
+    /// Overrides must suit the provider, and allowlisted tools must be
+    /// among the agent's own.
+    fn check_overrides(&self, overrides: &RunOverrides) -> Result<(), AgentError> {
+        overrides.validate(
+            &self.config.default_model,
+            self.provider.models(),
+            self.provider.capabilities(),
+        )?;
+        let unknown = overrides.tools.iter().flatten().find(|id| {
+            !self.tools.iter().any(|t| &t.definition().id == *id)
+        });
+        match unknown {
+            Some(id) => Err(AgentError::InvalidOverride(format!("unknown tool '{}'", id))),
+            None => Ok(()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -122,6 +122,21 @@
         // Just verifies that executor can be created without panic
     }
 
+    #[test]
+    fn test_general_agent_live_prompt() {
+        let mut config = AgentConfig::new("test-agent", "Test Agent", "mock-model");
+        config.system_prompt = Some("built-in".to_string());
+        let provider: Arc<dyn LLMProvider> = Arc::new(MockProvider::new());
+        let prompt = Arc::new(LivePrompt::default());
+        let agent = GeneralAgent::new(config, provider, vec![]).with_live_prompt(prompt.clone());
+
+        // Nothing refreshed yet: the configured prompt
+        assert_eq!(agent.executor().config.system_prompt.as_deref(), Some("built-in"));
+
+        prompt.set("refreshed".to_string());
+        assert_eq!(agent.executor().config.system_prompt.as_deref(), Some("refreshed"));
+    }
+
     #[tokio::test]
     async fn test_general_agent_process() {
         let config = AgentConfig::new("test-agent", "Test Agent", "mock-model");
@@ -156,6 +171,36 @@
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_general_agent_check_overrides() {
+        let config = AgentConfig::new("test-agent", "Test Agent", "mock-model");
+        let provider: Arc<dyn LLMProvider> = Arc::new(MockProvider::new());
+        let agent = GeneralAgent::new(config, provider, vec![]);
+
+        let overrides = RunOverrides {
+            model: Some("other-model".to_string()),
+            temperature: Some(0.5),
+            ..Default::default()
+        };
+        assert!(agent.check_overrides(&overrides).is_ok());
+
+        let overrides = RunOverrides {
+            temperature: Some(3.0),
+            ..Default::default()
+        };
+        assert!(matches!(
+            agent.check_overrides(&overrides),
+            Err(AgentError::InvalidOverride(_))
+        ));
+
+        let overrides = RunOverrides {
+            tools: Some(vec!["exec".to_string()]),
+            ..Default::default()
+        };
+        let err = agent.check_overrides(&overrides).unwrap_err();
+        assert!(err.to_string().contains("unknown tool 'exec'"));
+    }
+
     // Note: Abort signal checking is now handled by AgentLoop, not by GeneralAgent.
     // The agent's process() method handles a single turn and does not check abort signals.
     // See autohands-runtime/src/agent_loop.rs for abort handling tests.
@@ -10,7 +10,7 @@ mod tests;
 
 use std::sync::Arc;
 
-use autohands_protocols::agent::AgentConfig;
+use autohands_protocols::agent::{AgentConfig, RunOverrides};
 use autohands_protocols::provider::LLMProvider;
 use autohands_protocols::tool::Tool;
 use autohands_protocols::types::{Message, StopReason, ToolCall};
@@ -59,6 +59,10 @@ pub struct SingleTurnExecutor {
     pub(crate) provider: Arc<dyn LLMProvider>,
     pub(crate) tools: Vec<Arc<dyn Tool>>,
     pub(crate) transcript: Option<Arc<TranscriptWriter>>,
+    pub(crate) correlation_id: Option<String>,
+    pub(crate) workspace: Option<String>,
+    pub(crate) overrides: RunOverrides,
+    pub(crate) streaming: bool,
 }
 
 impl SingleTurnExecutor {
@@ -73,6 +77,35 @@ impl SingleTurnExecutor {
             provider,
             tools,
             transcript: None,
+            correlation_id: None,
+            workspace: None,
+            overrides: RunOverrides::default(),
+            streaming: false,
         }
     }
+
+    /// Tag provider requests with the correlation ID of the traced work.
+    pub fn with_correlation_id(mut self, correlation_id: Option<String>) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
+
+    /// Tag provider requests with the workspace the agent runs in.
+    pub fn with_workspace(mut self, workspace: Option<String>) -> Self {
+        self.workspace = workspace;
+        self
+    }
+
+    /// Apply the run's model, generation parameter and tool overrides.
+    pub fn with_overrides(mut self, overrides: RunOverrides) -> Self {
+        self.overrides = overrides;
+        self
+    }
+
+    /// Stream completions from providers that support it, continuing
+    /// streams that fail halfway instead of losing the partial output.
+    pub fn with_streaming(mut self, streaming: bool) -> Self {
+        self.streaming = streaming;
+        self
+    }
 }
@@ -11,6 +11,8 @@ use autohands_protocols::agent::AgentResponse;
 use autohands_protocols::error::AgentError;
 use autohands_protocols::provider::{CompletionRequest, CompletionResponse};
 use autohands_protocols::types::{Message, StopReason};
+use autohands_runtime::feature_flags::WORKSPACE_METADATA;
+use autohands_runtime::{complete_buffered, RetryConfig};
 
 use crate::executor::{SingleTurnExecutor, SingleTurnResult};
 
@@ -102,8 +104,11 @@ impl SingleTurnExecutor {
         })
     }
 
-    /// Call the LLM provider.
+    /// Call the LLM provider, streaming when enabled and supported.
     pub(crate) async fn call_llm(&self, request: CompletionRequest) -> Result<CompletionResponse, AgentError> {
+        if self.streaming && self.provider.capabilities().streaming {
+            return complete_buffered(self.provider.as_ref(), request, &RetryConfig::default()).await;
+        }
         self.provider.complete(request).await.map_err(AgentError::from)
     }
 
@@ -125,16 +130,34 @@ impl SingleTurnExecutor {
         let tool_definitions: Vec<_> = self
             .tools
             .iter()
-            .map(|t| t.definition().clone())
+            .map(|t| t.definition())
+            .filter(|d| self.overrides.allows_tool(&d.id))
+            .cloned()
             .collect();
 
-        let mut request =
-            CompletionRequest::new(self.config.default_model.clone(), messages.to_vec())
-                .with_tools(tool_definitions);
+        let model = self.overrides.model_or(&self.config.default_model);
+        let mut request = CompletionRequest::new(model.to_string(), messages.to_vec())
+            .with_tools(tool_definitions);
 
+        if let Some(temperature) = self.overrides.temperature {
+            request = request.with_temperature(temperature);
+        }
+        if let Some(max_tokens) = self.overrides.max_tokens {
+            request = request.with_max_tokens(max_tokens);
+        }
         if let Some(ref system) = self.config.system_prompt {
             request = request.with_system(system.clone());
         }
+        if let Some(ref correlation_id) = self.correlation_id {
+            request
+                .metadata
+                .insert("correlation_id".to_string(), correlation_id.clone().into());
+        }
+        if let Some(ref workspace) = self.workspace {
+            request
+                .metadata
+                .insert(WORKSPACE_METADATA.to_string(), workspace.clone().into());
+        }
 
         request
     }
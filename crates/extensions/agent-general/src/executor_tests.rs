@@ -273,6 +273,33 @@ fn test_build_request_with_system() {
     assert_eq!(request.system.unwrap(), "You are a helpful assistant");
 }
 
+#[test]
+fn test_build_request_with_correlation_id() {
+    let config = AgentConfig::new("test", "Test Agent", "mock-model");
+    let provider: Arc<dyn LLMProvider> = Arc::new(MockProvider::new(StopReason::EndTurn));
+    let tools: Vec<Arc<dyn Tool>> = vec![];
+
+    let executor = SingleTurnExecutor::new(config.clone(), provider.clone(), tools.clone());
+    let request = executor.build_request(&[Message::user("Hello")]);
+    assert!(!request.metadata.contains_key("correlation_id"));
+
+    let executor = SingleTurnExecutor::new(config, provider, tools)
+        .with_correlation_id(Some("corr-1".to_string()));
+    let request = executor.build_request(&[Message::user("Hello")]);
+    assert_eq!(request.metadata["correlation_id"], "corr-1");
+}
+
+#[test]
+fn test_build_request_with_workspace() {
+    let config = AgentConfig::new("test", "Test Agent", "mock-model");
+    let provider: Arc<dyn LLMProvider> = Arc::new(MockProvider::new(StopReason::EndTurn));
+
+    let executor = SingleTurnExecutor::new(config, provider, vec![])
+        .with_workspace(Some("/srv/repo".to_string()));
+    let request = executor.build_request(&[Message::user("Hello")]);
+    assert_eq!(request.metadata["workspace"], "/srv/repo");
+}
+
 #[test]
 fn test_build_request_with_tools() {
     let config = AgentConfig::new("test", "Test Agent", "mock-model");
@@ -504,3 +531,97 @@ fn test_agent_error_display() {
     let err = AgentError::ExecutionFailed("reason".to_string());
     assert!(err.to_string().contains("reason"));
 }
+
+#[test]
+fn test_build_request_with_overrides() {
+    let config = AgentConfig::new("test", "Test Agent", "mock-model");
+    let provider: Arc<dyn LLMProvider> = Arc::new(MockProvider::new(StopReason::EndTurn));
+    let tools: Vec<Arc<dyn Tool>> = vec![
+        Arc::new(MockTool::new("read_file", "content")),
+        Arc::new(MockTool::new("exec", "output")),
+    ];
+
+    let executor = SingleTurnExecutor::new(config, provider, tools).with_overrides(RunOverrides {
+        model: Some("fast-model".to_string()),
+        temperature: Some(0.2),
+        max_tokens: Some(512),
+        tools: Some(vec!["read_file".to_string()]),
+    });
+    let request = executor.build_request(&[Message::user("Hello")]);
+
+    assert_eq!(request.model, "fast-model");
+    assert_eq!(request.temperature, Some(0.2));
+    assert_eq!(request.max_tokens, Some(512));
+    assert_eq!(request.tools.len(), 1);
+    assert_eq!(request.tools[0].id, "read_file");
+}
+
+/// Provider that only streams, failing once halfway.
+struct FlakyStreamProvider {
+    streams: std::sync::atomic::AtomicU32,
+}
+
+#[async_trait]
+impl LLMProvider for FlakyStreamProvider {
+    fn id(&self) -> &str {
+        "flaky-stream"
+    }
+    fn models(&self) -> &[ModelDefinition] {
+        &[]
+    }
+    fn capabilities(&self) -> &ProviderCapabilities {
+        &ProviderCapabilities {
+            streaming: true,
+            tool_calling: false,
+            vision: false,
+            json_mode: false,
+            prompt_caching: false,
+            batching: false,
+            max_concurrent: None,
+        }
+    }
+    async fn complete(
+        &self,
+        _req: CompletionRequest,
+    ) -> Result<CompletionResponse, ProviderError> {
+        Err(ProviderError::InvalidRequest("streaming only".to_string()))
+    }
+    async fn complete_stream(
+        &self,
+        _req: CompletionRequest,
+    ) -> Result<CompletionStream, ProviderError> {
+        use autohands_protocols::provider::{ChunkType, CompletionChunk};
+        let text = |delta: &str| {
+            Ok(CompletionChunk {
+                chunk_type: ChunkType::ContentDelta,
+                delta: Some(delta.to_string()),
+                tool_call: None,
+                stop_reason: None,
+                usage: None,
+            })
+        };
+        let chunks = if self.streams.fetch_add(1, std::sync::atomic::Ordering::SeqCst) == 0 {
+            vec![text("Hello"), Err(ProviderError::StreamError("reset".to_string()))]
+        } else {
+            vec![text(" world")]
+        };
+        Ok(Box::pin(futures::stream::iter(chunks)))
+    }
+}
+
+#[tokio::test]
+async fn test_execute_turn_streaming() {
+    let config = AgentConfig::new("test", "Test Agent", "mock-model");
+    let provider: Arc<dyn LLMProvider> = Arc::new(FlakyStreamProvider {
+        streams: std::sync::atomic::AtomicU32::new(0),
+    });
+
+    let executor = SingleTurnExecutor::new(config.clone(), provider.clone(), vec![]);
+    assert!(executor.execute_turn(&[Message::user("Hi")]).await.is_err());
+
+    // The stream failing halfway is continued
+    let executor = SingleTurnExecutor::new(config, provider, vec![]).with_streaming(true);
+    let result = executor.execute_turn(&[Message::user("Hi")]).await.unwrap();
+    assert!(result.is_complete);
+    assert_eq!(result.message.content.text(), "Hello world");
+}
@@ -22,7 +22,7 @@ impl GeneralAgentExtension {
         );
         manifest.description = "General purpose agentic execution".to_string();
         manifest.provides = Provides {
-            agents: vec!["general".to_string()],
+            agents: vec!["general".to_string(), "review".to_string()],
             ..Default::default()
         };
 
@@ -109,8 +109,9 @@ mod tests {
     #[test]
     fn test_provides_agents() {
         let ext = GeneralAgentExtension::new();
-        assert_eq!(ext.manifest().provides.agents.len(), 1);
+        assert_eq!(ext.manifest().provides.agents.len(), 2);
         assert_eq!(ext.manifest().provides.agents[0], "general");
+        assert_eq!(ext.manifest().provides.agents[1], "review");
     }
 
     #[test]
@@ -6,6 +6,8 @@ mod agent;
 mod executor;
 mod executor_impl;
 mod extension;
+pub mod review;
 
 pub use agent::GeneralAgent;
 pub use extension::GeneralAgentExtension;
+pub use review::{review_agent_config, REVIEW_AGENT_ID, REVIEW_TOOLS};
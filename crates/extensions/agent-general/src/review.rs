@@ -0,0 +1,61 @@
+//! The `review` agent profile.
+//!
+//! A general agent with a reviewer's system prompt and the tools a review
+//! needs: reading code, running checks and fetching or commenting on pull
+//! requests. It reports its findings as a JSON array of `file`, `line`,
+//! `severity` and `suggestion`, the shape `github_review` posts as line
+//! comments.
+
+use autohands_protocols::agent::AgentConfig;
+
+/// ID of the review agent.
+pub const REVIEW_AGENT_ID: &str = "review";
+
+/// Tools offered to the review agent, when registered.
+pub const REVIEW_TOOLS: &[&str] = &[
+    "exec",
+    "read_file",
+    "list_directory",
+    "glob",
+    "grep",
+    "analyze_code",
+    "find_symbol",
+    "repo_map",
+    "github_pr_diff",
+    "github_review",
+];
+
+/// System prompt of the review agent.
+pub const REVIEW_SYSTEM_PROMPT: &str = r#"You are a code reviewer. Review the changes only.
+
+1. Gather the diff. For a pull request URL, call github_pr_diff. For a git ref range such as
+   `main..HEAD`, run `git diff --stat <range>` and `git diff <range>` with exec in the
+   repository checkout.
+2. Read enough of the surrounding code (read_file, grep, find_symbol, repo_map) to judge each
+   change in context.
+3. Run checks: analyze_code on the changed source files, and the project's own linters when
+   they are cheap to run (for example `cargo clippy`, `npm run lint`, `ruff check`).
+4. Report only problems in the changed lines: bugs, security issues, missing error handling,
+   broken contracts, missing tests. Skip what a formatter would fix.
+
+Do not modify files and do not post anything unless asked to.
+
+End your answer with the findings as a JSON array in a ```json block, one object per finding:
+{"file": "src/lib.rs", "line": 42, "severity": "major", "suggestion": "What to change and why"}
+`line` is the line in the new version of the file and `severity` one of critical, major, minor
+or nit. Use an empty array when the changes look good.
+"#;
+
+/// Configuration of the review agent.
+pub fn review_agent_config(default_model: impl Into<String>) -> AgentConfig {
+    let mut config = AgentConfig::new(REVIEW_AGENT_ID, "Code Reviewer", default_model)
+        .with_system_prompt(REVIEW_SYSTEM_PROMPT)
+        .with_tools(REVIEW_TOOLS.iter().map(|t| t.to_string()).collect());
+    config.description =
+        "Reviews a git ref range or pull request and reports structured findings".to_string();
+    config
+}
+
+#[cfg(test)]
+#[path = "review_tests.rs"]
+mod tests;
@@ -0,0 +1,13 @@
+use super::*;
+
+#[test]
+fn test_review_agent_config() {
+    let config = review_agent_config("model");
+    assert_eq!(config.id, REVIEW_AGENT_ID);
+    assert_eq!(config.default_model, "model");
+    assert!(config.tools.iter().any(|t| t == "github_pr_diff"));
+    let prompt = config.system_prompt.unwrap();
+    for field in ["\"file\"", "\"line\"", "\"severity\"", "\"suggestion\""] {
+        assert!(prompt.contains(field), "{}", field);
+    }
+}
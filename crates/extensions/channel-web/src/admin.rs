@@ -0,0 +1,238 @@
+//! Skill, tool and task template management endpoints.
+//!
+//! The web channel has no direct access to the skill or tool registries;
+//! the host wires them in through an [`AdminBackend`]. Without a backend
+//! the endpoints answer `503 Service Unavailable`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use autohands_protocols::skill::{Skill, SkillDefinition};
+use autohands_protocols::types::RiskLevel;
+
+use crate::WebChannelState;
+
+/// Tool information shown on the tools page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolSummary {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub risk_level: RiskLevel,
+    pub enabled: bool,
+    /// Total executions since startup.
+    pub calls: u64,
+    /// Failed executions since startup.
+    pub errors: u64,
+    pub last_used: Option<DateTime<Utc>>,
+}
+
+/// Task template shown on the chat and admin pages.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateSummary {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub prompt: String,
+    /// Variables that must be filled in before running.
+    pub variables: Vec<String>,
+    /// Default agent profile.
+    pub agent: Option<String>,
+}
+
+/// Access to the host's skill and tool registries.
+#[async_trait]
+pub trait AdminBackend: Send + Sync {
+    /// List installed skills.
+    async fn list_skills(&self) -> Vec<SkillDefinition>;
+
+    /// Get a skill including its content.
+    async fn get_skill(&self, id: &str) -> Option<Skill>;
+
+    /// Enable or disable a skill. Returns `false` if it does not exist.
+    async fn set_skill_enabled(&self, id: &str, enabled: bool) -> bool;
+
+    /// List registered tools with usage statistics.
+    async fn list_tools(&self) -> Vec<ToolSummary>;
+
+    /// Enable or disable a tool. Returns `false` if it does not exist.
+    async fn set_tool_enabled(&self, id: &str, enabled: bool) -> bool;
+
+    /// List saved task templates.
+    async fn list_templates(&self) -> Vec<TemplateSummary> {
+        Vec::new()
+    }
+
+    /// Render a template into a prompt.
+    ///
+    /// Returns `None` if the template does not exist and `Some(Err(..))` if
+    /// it cannot be rendered, e.g. because a variable is missing.
+    async fn render_template(
+        &self,
+        id: &str,
+        variables: &HashMap<String, String>,
+    ) -> Option<Result<String, String>> {
+        let _ = (id, variables);
+        None
+    }
+
+    /// Delete a template. Returns `false` if it does not exist.
+    async fn delete_template(&self, id: &str) -> bool {
+        let _ = id;
+        false
+    }
+}
+
+/// Request body for enable/disable endpoints.
+#[derive(Debug, Deserialize)]
+pub struct SetEnabledRequest {
+    pub enabled: bool,
+}
+
+/// Request body for rendering a template.
+#[derive(Debug, Deserialize)]
+pub struct RenderTemplateRequest {
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+}
+
+fn unavailable() -> Response {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(serde_json::json!({"error": "Admin backend not configured"})),
+    )
+        .into_response()
+}
+
+fn not_found(kind: &str, id: &str) -> Response {
+    (
+        StatusCode::NOT_FOUND,
+        Json(serde_json::json!({"error": format!("{} not found: {}", kind, id)})),
+    )
+        .into_response()
+}
+
+/// GET /api/skills
+pub(crate) async fn list_skills(State(state): State<Arc<WebChannelState>>) -> Response {
+    let Some(admin) = state.admin.get() else {
+        return unavailable();
+    };
+    let mut skills = admin.list_skills().await;
+    skills.sort_by(|a, b| a.id.cmp(&b.id));
+    Json(skills).into_response()
+}
+
+/// GET /api/skills/{id}
+pub(crate) async fn get_skill(
+    State(state): State<Arc<WebChannelState>>,
+    Path(id): Path<String>,
+) -> Response {
+    let Some(admin) = state.admin.get() else {
+        return unavailable();
+    };
+    match admin.get_skill(&id).await {
+        Some(skill) => Json(skill).into_response(),
+        None => not_found("Skill", &id),
+    }
+}
+
+/// PUT /api/skills/{id}/enabled
+pub(crate) async fn set_skill_enabled(
+    State(state): State<Arc<WebChannelState>>,
+    Path(id): Path<String>,
+    Json(req): Json<SetEnabledRequest>,
+) -> Response {
+    let Some(admin) = state.admin.get() else {
+        return unavailable();
+    };
+    if admin.set_skill_enabled(&id, req.enabled).await {
+        Json(serde_json::json!({"id": id, "enabled": req.enabled})).into_response()
+    } else {
+        not_found("Skill", &id)
+    }
+}
+
+/// GET /api/tools
+pub(crate) async fn list_tools(State(state): State<Arc<WebChannelState>>) -> Response {
+    let Some(admin) = state.admin.get() else {
+        return unavailable();
+    };
+    let mut tools = admin.list_tools().await;
+    tools.sort_by(|a, b| a.id.cmp(&b.id));
+    Json(tools).into_response()
+}
+
+/// PUT /api/tools/{id}/enabled
+pub(crate) async fn set_tool_enabled(
+    State(state): State<Arc<WebChannelState>>,
+    Path(id): Path<String>,
+    Json(req): Json<SetEnabledRequest>,
+) -> Response {
+    let Some(admin) = state.admin.get() else {
+        return unavailable();
+    };
+    if admin.set_tool_enabled(&id, req.enabled).await {
+        Json(serde_json::json!({"id": id, "enabled": req.enabled})).into_response()
+    } else {
+        not_found("Tool", &id)
+    }
+}
+
+/// GET /api/templates
+pub(crate) async fn list_templates(State(state): State<Arc<WebChannelState>>) -> Response {
+    let Some(admin) = state.admin.get() else {
+        return unavailable();
+    };
+    let mut templates = admin.list_templates().await;
+    templates.sort_by(|a, b| a.id.cmp(&b.id));
+    Json(templates).into_response()
+}
+
+/// POST /api/templates/{id}/render
+pub(crate) async fn render_template(
+    State(state): State<Arc<WebChannelState>>,
+    Path(id): Path<String>,
+    Json(req): Json<RenderTemplateRequest>,
+) -> Response {
+    let Some(admin) = state.admin.get() else {
+        return unavailable();
+    };
+    match admin.render_template(&id, &req.variables).await {
+        Some(Ok(prompt)) => Json(serde_json::json!({"id": id, "prompt": prompt})).into_response(),
+        Some(Err(error)) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": error})),
+        )
+            .into_response(),
+        None => not_found("Template", &id),
+    }
+}
+
+/// DELETE /api/templates/{id}
+pub(crate) async fn delete_template(
+    State(state): State<Arc<WebChannelState>>,
+    Path(id): Path<String>,
+) -> Response {
+    let Some(admin) = state.admin.get() else {
+        return unavailable();
+    };
+    if admin.delete_template(&id).await {
+        StatusCode::NO_CONTENT.into_response()
+    } else {
+        not_found("Template", &id)
+    }
+}
+
+#[cfg(test)]
+#[path = "admin_tests.rs"]
+mod tests;
@@ -0,0 +1,228 @@
+use super::*;
+use crate::server::create_router;
+use axum::body::Body;
+use axum::http::Request;
+use std::sync::Mutex;
+use tower::ServiceExt;
+
+struct MockBackend {
+    skills: Mutex<Vec<SkillDefinition>>,
+    tools: Mutex<Vec<ToolSummary>>,
+}
+
+impl MockBackend {
+    fn new() -> Self {
+        Self {
+            skills: Mutex::new(vec![SkillDefinition::new("review", "Code Review")]),
+            tools: Mutex::new(vec![ToolSummary {
+                id: "exec".to_string(),
+                name: "Exec".to_string(),
+                description: "Run a command".to_string(),
+                risk_level: RiskLevel::High,
+                enabled: true,
+                calls: 3,
+                errors: 1,
+                last_used: None,
+            }]),
+        }
+    }
+}
+
+#[async_trait]
+impl AdminBackend for MockBackend {
+    async fn list_skills(&self) -> Vec<SkillDefinition> {
+        self.skills.lock().unwrap().clone()
+    }
+
+    async fn get_skill(&self, id: &str) -> Option<Skill> {
+        let skills = self.skills.lock().unwrap();
+        let def = skills.iter().find(|s| s.id == id)?.clone();
+        Some(Skill::new(def, "Review the diff"))
+    }
+
+    async fn set_skill_enabled(&self, id: &str, enabled: bool) -> bool {
+        let mut skills = self.skills.lock().unwrap();
+        match skills.iter_mut().find(|s| s.id == id) {
+            Some(skill) => {
+                skill.enabled = enabled;
+                true
+            }
+            None => false,
+        }
+    }
+
+    async fn list_tools(&self) -> Vec<ToolSummary> {
+        self.tools.lock().unwrap().clone()
+    }
+
+    async fn set_tool_enabled(&self, id: &str, enabled: bool) -> bool {
+        let mut tools = self.tools.lock().unwrap();
+        match tools.iter_mut().find(|t| t.id == id) {
+            Some(tool) => {
+                tool.enabled = enabled;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+fn state_with_backend() -> (Arc<WebChannelState>, Arc<MockBackend>) {
+    let state = Arc::new(WebChannelState::new("web"));
+    let backend = Arc::new(MockBackend::new());
+    let _ = state.admin.set(backend.clone());
+    (state, backend)
+}
+
+async fn json_body(resp: Response) -> serde_json::Value {
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_list_skills_without_backend() {
+    let router = create_router(Arc::new(WebChannelState::new("web")));
+    let resp = router
+        .oneshot(Request::get("/api/skills").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test]
+async fn test_get_skill_content() {
+    let (state, _) = state_with_backend();
+    let resp = create_router(state)
+        .oneshot(Request::get("/api/skills/review").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(json_body(resp).await["content"], "Review the diff");
+}
+
+#[tokio::test]
+async fn test_disable_tool() {
+    let (state, backend) = state_with_backend();
+    let resp = create_router(state)
+        .oneshot(
+            Request::put("/api/tools/exec/enabled")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"enabled":false}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(!backend.tools.lock().unwrap()[0].enabled);
+}
+
+#[tokio::test]
+async fn test_disable_unknown_skill() {
+    let (state, _) = state_with_backend();
+    let resp = create_router(state)
+        .oneshot(
+            Request::put("/api/skills/missing/enabled")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"enabled":false}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_list_tools() {
+    let (state, _) = state_with_backend();
+    let resp = create_router(state)
+        .oneshot(Request::get("/api/tools").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let body = json_body(resp).await;
+    assert_eq!(body[0]["id"], "exec");
+    assert_eq!(body[0]["calls"], 3);
+}
+
+#[tokio::test]
+async fn test_templates_default_to_empty() {
+    let (state, _) = state_with_backend();
+    let resp = create_router(state.clone())
+        .oneshot(Request::get("/api/templates").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(json_body(resp).await, serde_json::json!([]));
+
+    let resp = create_router(state)
+        .oneshot(
+            Request::post("/api/templates/weekly/render")
+                .header("content-type", "application/json")
+                .body(Body::from("{}"))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+struct TemplateBackend;
+
+#[async_trait]
+impl AdminBackend for TemplateBackend {
+    async fn list_skills(&self) -> Vec<SkillDefinition> {
+        Vec::new()
+    }
+
+    async fn get_skill(&self, _id: &str) -> Option<Skill> {
+        None
+    }
+
+    async fn set_skill_enabled(&self, _id: &str, _enabled: bool) -> bool {
+        false
+    }
+
+    async fn list_tools(&self) -> Vec<ToolSummary> {
+        Vec::new()
+    }
+
+    async fn set_tool_enabled(&self, _id: &str, _enabled: bool) -> bool {
+        false
+    }
+
+    async fn render_template(
+        &self,
+        id: &str,
+        variables: &HashMap<String, String>,
+    ) -> Option<Result<String, String>> {
+        if id != "weekly" {
+            return None;
+        }
+        Some(match variables.get("team") {
+            Some(team) => Ok(format!("Report for {}", team)),
+            None => Err("Missing template variables: team".to_string()),
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_render_template() {
+    let state = Arc::new(WebChannelState::new("web"));
+    let _ = state.admin.set(Arc::new(TemplateBackend));
+
+    let render = |body: &'static str| {
+        Request::post("/api/templates/weekly/render")
+            .header("content-type", "application/json")
+            .body(Body::from(body))
+            .unwrap()
+    };
+
+    let resp = create_router(state.clone())
+        .oneshot(render(r#"{"variables":{"team":"infra"}}"#))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(json_body(resp).await["prompt"], "Report for infra");
+
+    let resp = create_router(state).oneshot(render("{}")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
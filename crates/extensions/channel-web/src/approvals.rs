@@ -0,0 +1,91 @@
+//! Approval endpoints backing the approvals page.
+//!
+//! The page polls `GET /api/approvals` for pending requests and answers them
+//! through `POST /api/approvals/{id}/approve` or `/reject`. Without an
+//! [`ApprovalGate`] the endpoints answer `503 Service Unavailable`.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Deserialize;
+
+use autohands_protocols::approval::ApprovalDecision;
+
+use crate::WebChannelState;
+
+/// Optional body when deciding a request.
+#[derive(Debug, Default, Deserialize)]
+pub struct DecideRequest {
+    /// Who decided.
+    pub decided_by: Option<String>,
+    /// Reason or note.
+    pub comment: Option<String>,
+}
+
+fn unavailable() -> Response {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(serde_json::json!({"error": "Approvals are not configured"})),
+    )
+        .into_response()
+}
+
+/// GET /api/approvals
+pub(crate) async fn list_approvals(State(state): State<Arc<WebChannelState>>) -> Response {
+    let Some(gate) = state.approvals.get() else {
+        return unavailable();
+    };
+    let approvals = gate.pending().await;
+    Json(serde_json::json!({"count": approvals.len(), "approvals": approvals})).into_response()
+}
+
+/// POST /api/approvals/{id}/approve
+pub(crate) async fn approve(
+    state: State<Arc<WebChannelState>>,
+    id: Path<String>,
+    req: Option<Json<DecideRequest>>,
+) -> Response {
+    decide(state, id, req, ApprovalDecision::approved()).await
+}
+
+/// POST /api/approvals/{id}/reject
+pub(crate) async fn reject(
+    state: State<Arc<WebChannelState>>,
+    id: Path<String>,
+    req: Option<Json<DecideRequest>>,
+) -> Response {
+    decide(state, id, req, ApprovalDecision::rejected()).await
+}
+
+async fn decide(
+    State(state): State<Arc<WebChannelState>>,
+    Path(id): Path<String>,
+    req: Option<Json<DecideRequest>>,
+    mut decision: ApprovalDecision,
+) -> Response {
+    let Some(gate) = state.approvals.get() else {
+        return unavailable();
+    };
+    let req = req.map(|Json(req)| req).unwrap_or_default();
+    decision.decided_by = req.decided_by;
+    decision.comment = req.comment;
+    let status = decision.status;
+
+    if !gate.decide(&id, decision).await {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("No pending approval '{}'", id)})),
+        )
+            .into_response();
+    }
+    Json(serde_json::json!({"id": id, "status": status})).into_response()
+}
+
+#[cfg(test)]
+#[path = "approvals_tests.rs"]
+mod tests;
@@ -0,0 +1,102 @@
+use super::*;
+use crate::server::create_router;
+use async_trait::async_trait;
+use autohands_protocols::approval::{ApprovalGate, ApprovalRequest, ApprovalStatus};
+use axum::body::Body;
+use axum::http::Request;
+use std::sync::Mutex;
+use tower::ServiceExt;
+
+/// Gate holding a single pending request and recording the decision.
+struct StubGate {
+    request: ApprovalRequest,
+    decision: Mutex<Option<ApprovalDecision>>,
+}
+
+#[async_trait]
+impl ApprovalGate for StubGate {
+    async fn request_approval(&self, _request: ApprovalRequest) -> ApprovalDecision {
+        ApprovalDecision::approved()
+    }
+
+    async fn pending(&self) -> Vec<ApprovalRequest> {
+        vec![self.request.clone()]
+    }
+
+    async fn decide(&self, request_id: &str, decision: ApprovalDecision) -> bool {
+        if request_id != self.request.id {
+            return false;
+        }
+        *self.decision.lock().unwrap() = Some(decision);
+        true
+    }
+}
+
+fn stub_state() -> (Arc<WebChannelState>, Arc<StubGate>) {
+    let gate = Arc::new(StubGate {
+        request: ApprovalRequest::new("Deploy", "workflow:release/confirm"),
+        decision: Mutex::new(None),
+    });
+    let state = Arc::new(WebChannelState::new("web"));
+    let _ = state.approvals.set(gate.clone());
+    (state, gate)
+}
+
+#[tokio::test]
+async fn test_approvals_without_gate() {
+    let router = create_router(Arc::new(WebChannelState::new("web")));
+    let resp = router
+        .oneshot(Request::get("/api/approvals").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test]
+async fn test_list_approvals() {
+    let (state, gate) = stub_state();
+    let resp = create_router(state)
+        .oneshot(Request::get("/api/approvals").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["count"], 1);
+    assert_eq!(body["approvals"][0]["id"], gate.request.id.as_str());
+}
+
+#[tokio::test]
+async fn test_reject_with_comment() {
+    let (state, gate) = stub_state();
+    let resp = create_router(state)
+        .oneshot(
+            Request::post(format!("/api/approvals/{}/reject", gate.request.id))
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"decided_by":"alice","comment":"not today"}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let decision = gate.decision.lock().unwrap().clone().unwrap();
+    assert_eq!(decision.status, ApprovalStatus::Rejected);
+    assert_eq!(decision.decided_by.as_deref(), Some("alice"));
+    assert_eq!(decision.comment.as_deref(), Some("not today"));
+}
+
+#[tokio::test]
+async fn test_approve_unknown_request() {
+    let (state, _gate) = stub_state();
+    let resp = create_router(state)
+        .oneshot(
+            Request::post("/api/approvals/missing/approve")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
@@ -6,6 +6,14 @@
 //! - Serves a simple HTML/JS UI embedded in the binary
 //! - Accepts WebSocket connections for real-time bidirectional communication
 //! - Converts user messages to `InboundMessage` and routes agent responses back
+//! - Serves skill, tool and task template management pages at `/admin` when an
+//!   [`AdminBackend`] is configured
+//! - Serves a live metrics dashboard at `/dashboard` when a [`MetricsSource`]
+//!   is configured
+//! - Serves pending human approvals at `/approvals` when an [`ApprovalGate`]
+//!   is configured
+//! - Serves the task trees of multi-agent runs at `/tasks` when a
+//!   [`TaskTreeSource`] is configured
 //!
 //! ## Usage
 //!
@@ -20,11 +28,15 @@
 //! channel.start().await?;
 //! ```
 
+mod admin;
+mod approvals;
 mod connection;
+mod metrics;
 mod server;
+mod tasks;
 
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
 use async_trait::async_trait;
 use dashmap::DashMap;
@@ -32,14 +44,20 @@ use serde::{Deserialize, Serialize};
 use tokio::sync::{broadcast, Mutex};
 use tracing::{debug, info};
 
+use autohands_protocols::approval::ApprovalGate;
 use autohands_protocols::channel::{
     Channel, ChannelCapabilities, ChannelId, InboundMessage, OutboundMessage, ReplyAddress,
     SentMessage,
 };
 use autohands_protocols::error::ChannelError;
 
+pub use admin::{
+    AdminBackend, RenderTemplateRequest, SetEnabledRequest, TemplateSummary, ToolSummary,
+};
 pub use connection::WebSocketConnection;
+pub use metrics::MetricsSource;
 pub use server::create_router;
+pub use tasks::TaskTreeSource;
 
 /// Web channel configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -79,6 +97,14 @@ pub struct WebChannelState {
     pub inbound_tx: broadcast::Sender<InboundMessage>,
     /// Channel started flag.
     pub started: AtomicBool,
+    /// Skill/tool management backend.
+    pub admin: OnceLock<Arc<dyn AdminBackend>>,
+    /// Live metrics for the dashboard.
+    pub metrics: OnceLock<Arc<dyn MetricsSource>>,
+    /// Gate whose pending requests are shown on the approvals page.
+    pub approvals: OnceLock<Arc<dyn ApprovalGate>>,
+    /// Task trees shown on the tasks page.
+    pub tasks: OnceLock<Arc<dyn TaskTreeSource>>,
 }
 
 impl WebChannelState {
@@ -90,6 +116,10 @@ impl WebChannelState {
             connections: DashMap::new(),
             inbound_tx,
             started: AtomicBool::new(false),
+            admin: OnceLock::new(),
+            metrics: OnceLock::new(),
+            approvals: OnceLock::new(),
+            tasks: OnceLock::new(),
         }
     }
 }
@@ -133,6 +163,38 @@ impl WebChannel {
         }
     }
 
+    /// Attach a backend for the skill and tool management pages.
+    ///
+    /// Only the first backend is kept; later calls are ignored.
+    pub fn with_admin_backend(self, backend: Arc<dyn AdminBackend>) -> Self {
+        let _ = self.state.admin.set(backend);
+        self
+    }
+
+    /// Attach a metrics source for the dashboard page.
+    ///
+    /// Only the first source is kept; later calls are ignored.
+    pub fn with_metrics_source(self, source: Arc<dyn MetricsSource>) -> Self {
+        let _ = self.state.metrics.set(source);
+        self
+    }
+
+    /// Attach an approval gate for the approvals page.
+    ///
+    /// Only the first gate is kept; later calls are ignored.
+    pub fn with_approval_gate(self, gate: Arc<dyn ApprovalGate>) -> Self {
+        let _ = self.state.approvals.set(gate);
+        self
+    }
+
+    /// Attach a task tree source for the tasks page.
+    ///
+    /// Only the first source is kept; later calls are ignored.
+    pub fn with_task_tree_source(self, source: Arc<dyn TaskTreeSource>) -> Self {
+        let _ = self.state.tasks.set(source);
+        self
+    }
+
     /// Get the channel's listen address.
     pub fn address(&self) -> String {
         format!("{}:{}", self.config.host, self.config.port)
@@ -0,0 +1,48 @@
+//! Metrics endpoint backing the dashboard page.
+//!
+//! The host decides what goes into the snapshot through a [`MetricsSource`];
+//! the dashboard polls `GET /api/metrics` and renders whatever fields it
+//! recognises. Without a source the endpoint answers `503 Service Unavailable`.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use crate::WebChannelState;
+
+/// Provider of live metrics for the dashboard.
+#[async_trait]
+pub trait MetricsSource: Send + Sync {
+    /// Current metrics as a JSON object.
+    ///
+    /// The dashboard understands `queue_depth`, `active_sessions`,
+    /// `tokens_today`, `recent_failures` and `providers`.
+    async fn snapshot(&self) -> serde_json::Value;
+}
+
+/// GET /api/metrics
+pub(crate) async fn get_metrics(State(state): State<Arc<WebChannelState>>) -> Response {
+    let Some(source) = state.metrics.get() else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({"error": "Metrics source not configured"})),
+        )
+            .into_response();
+    };
+    let mut snapshot = source.snapshot().await;
+    if let Some(obj) = snapshot.as_object_mut() {
+        obj.insert("connections".to_string(), state.connections.len().into());
+        obj.insert("timestamp".to_string(), chrono::Utc::now().to_rfc3339().into());
+    }
+    Json(snapshot).into_response()
+}
+
+#[cfg(test)]
+#[path = "metrics_tests.rs"]
+mod tests;
@@ -0,0 +1,52 @@
+use super::*;
+use crate::server::create_router;
+use axum::body::Body;
+use axum::http::Request;
+use tower::ServiceExt;
+
+struct FixedSource;
+
+#[async_trait]
+impl MetricsSource for FixedSource {
+    async fn snapshot(&self) -> serde_json::Value {
+        serde_json::json!({"queue_depth": 4, "active_sessions": 2})
+    }
+}
+
+#[tokio::test]
+async fn test_metrics_without_source() {
+    let router = create_router(Arc::new(WebChannelState::new("web")));
+    let resp = router
+        .oneshot(Request::get("/api/metrics").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test]
+async fn test_metrics_snapshot() {
+    let state = Arc::new(WebChannelState::new("web"));
+    let _ = state.metrics.set(Arc::new(FixedSource));
+    let resp = create_router(state)
+        .oneshot(Request::get("/api/metrics").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["queue_depth"], 4);
+    assert_eq!(body["active_sessions"], 2);
+    assert_eq!(body["connections"], 0);
+    assert!(body["timestamp"].is_string());
+}
+
+#[tokio::test]
+async fn test_dashboard_page_served() {
+    let router = create_router(Arc::new(WebChannelState::new("web")));
+    let resp = router
+        .oneshot(Request::get("/dashboard").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+}
@@ -9,12 +9,13 @@ use axum::{
     },
     http::{header, StatusCode},
     response::{Html, IntoResponse, Response},
-    routing::get,
+    routing::{delete, get, post, put},
     Router,
 };
 use rust_embed::RustEmbed;
 use tracing::debug;
 
+use crate::{admin, approvals, metrics, tasks};
 use crate::{WebChannelState, WebSocketConnection};
 
 /// Embedded static assets.
@@ -29,12 +30,38 @@ pub fn create_router(state: Arc<WebChannelState>) -> Router {
         .route("/", get(serve_index))
         .route("/style.css", get(serve_css))
         .route("/app.js", get(serve_js))
+        .route("/admin", get(serve_admin))
+        .route("/admin.js", get(serve_admin_js))
+        .route("/dashboard", get(serve_dashboard))
+        .route("/dashboard.js", get(serve_dashboard_js))
+        .route("/approvals", get(serve_approvals))
+        .route("/approvals.js", get(serve_approvals_js))
+        .route("/tasks", get(serve_tasks))
+        .route("/tasks.js", get(serve_tasks_js))
         // WebSocket endpoint
         .route("/ws", get(ws_handler))
         // Health check
         .route("/health", get(health_check))
         // API info
         .route("/api/info", get(api_info))
+        // Skill and tool management
+        .route("/api/skills", get(admin::list_skills))
+        .route("/api/skills/{id}", get(admin::get_skill))
+        .route("/api/skills/{id}/enabled", put(admin::set_skill_enabled))
+        .route("/api/tools", get(admin::list_tools))
+        .route("/api/tools/{id}/enabled", put(admin::set_tool_enabled))
+        .route("/api/templates", get(admin::list_templates))
+        .route("/api/templates/{id}", delete(admin::delete_template))
+        .route("/api/templates/{id}/render", post(admin::render_template))
+        // Live metrics
+        .route("/api/metrics", get(metrics::get_metrics))
+        // Human approvals
+        .route("/api/approvals", get(approvals::list_approvals))
+        .route("/api/approvals/{id}/approve", post(approvals::approve))
+        .route("/api/approvals/{id}/reject", post(approvals::reject))
+        // Multi-agent task trees
+        .route("/api/tasks", get(tasks::list_tasks))
+        .route("/api/tasks/{id}/tree", get(tasks::get_task_tree))
         .with_state(state)
 }
 
@@ -74,6 +101,77 @@ async fn serve_js() -> impl IntoResponse {
     }
 }
 
+/// Serve the skill and tool management page.
+async fn serve_admin() -> Response {
+    match StaticAssets::get("admin.html") {
+        Some(content) => {
+            Html(String::from_utf8_lossy(content.data.as_ref()).to_string()).into_response()
+        }
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+/// Serve the management page script.
+async fn serve_admin_js() -> Response {
+    serve_script("admin.js")
+}
+
+/// Serve the metrics dashboard page.
+async fn serve_dashboard() -> Response {
+    match StaticAssets::get("dashboard.html") {
+        Some(content) => {
+            Html(String::from_utf8_lossy(content.data.as_ref()).to_string()).into_response()
+        }
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+/// Serve the dashboard script.
+async fn serve_dashboard_js() -> Response {
+    serve_script("dashboard.js")
+}
+
+/// Serve the pending approvals page.
+async fn serve_approvals() -> Response {
+    match StaticAssets::get("approvals.html") {
+        Some(content) => {
+            Html(String::from_utf8_lossy(content.data.as_ref()).to_string()).into_response()
+        }
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+/// Serve the approvals page script.
+async fn serve_approvals_js() -> Response {
+    serve_script("approvals.js")
+}
+
+/// Serve the task trees page.
+async fn serve_tasks() -> Response {
+    match StaticAssets::get("tasks.html") {
+        Some(content) => {
+            Html(String::from_utf8_lossy(content.data.as_ref()).to_string()).into_response()
+        }
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+/// Serve the task trees page script.
+async fn serve_tasks_js() -> Response {
+    serve_script("tasks.js")
+}
+
+fn serve_script(name: &str) -> Response {
+    match StaticAssets::get(name) {
+        Some(content) => (
+            [(header::CONTENT_TYPE, "application/javascript")],
+            String::from_utf8_lossy(content.data.as_ref()).to_string(),
+        )
+            .into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
 /// WebSocket upgrade handler.
 async fn ws_handler(
     ws: WebSocketUpgrade,
@@ -124,7 +222,17 @@ async fn api_info(State(state): State<Arc<WebChannelState>>) -> impl IntoRespons
             "endpoints": {
                 "websocket": "/ws",
                 "health": "/health",
-                "info": "/api/info"
+                "info": "/api/info",
+                "admin": "/admin",
+                "skills": "/api/skills",
+                "tools": "/api/tools",
+                "templates": "/api/templates",
+                "dashboard": "/dashboard",
+                "metrics": "/api/metrics",
+                "approvals": "/approvals",
+                "approvals_api": "/api/approvals",
+                "tasks": "/tasks",
+                "tasks_api": "/api/tasks"
             }
         })
         .to_string(),
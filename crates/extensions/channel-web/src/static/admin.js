@@ -0,0 +1,177 @@
+// AutoHands skill and tool management
+const skillsBody = document.getElementById('skills-body');
+const toolsBody = document.getElementById('tools-body');
+const skillContent = document.getElementById('skill-content');
+const templatesBody = document.getElementById('templates-body');
+
+function cell(text) {
+    const td = document.createElement('td');
+    td.textContent = text;
+    return td;
+}
+
+function toggle(checked, onChange) {
+    const td = document.createElement('td');
+    const input = document.createElement('input');
+    input.type = 'checkbox';
+    input.checked = checked;
+    input.onchange = () => onChange(input.checked).catch((e) => {
+        input.checked = !input.checked;
+        alert(e.message);
+    });
+    td.appendChild(input);
+    return td;
+}
+
+async function setEnabled(kind, id, enabled) {
+    const resp = await fetch(`/api/${kind}/${encodeURIComponent(id)}/enabled`, {
+        method: 'PUT',
+        headers: { 'Content-Type': 'application/json' },
+        body: JSON.stringify({ enabled }),
+    });
+    if (!resp.ok) {
+        throw new Error((await resp.json()).error || resp.statusText);
+    }
+}
+
+async function showSkill(id) {
+    const resp = await fetch(`/api/skills/${encodeURIComponent(id)}`);
+    const skill = await resp.json();
+    skillContent.textContent = skill.content || skill.error;
+    skillContent.hidden = false;
+}
+
+async function loadSkills() {
+    const resp = await fetch('/api/skills');
+    const skills = await resp.json();
+    skillsBody.replaceChildren();
+    if (!Array.isArray(skills)) {
+        skillsBody.appendChild(cell(skills.error));
+        return;
+    }
+    for (const skill of skills) {
+        const tr = document.createElement('tr');
+        const name = cell(skill.name);
+        name.title = skill.description;
+        tr.appendChild(name);
+        tr.appendChild(cell(skill.category || ''));
+        tr.appendChild(toggle(skill.enabled, (on) => setEnabled('skills', skill.id, on)));
+        const view = document.createElement('td');
+        const button = document.createElement('button');
+        button.textContent = 'View';
+        button.onclick = () => showSkill(skill.id);
+        view.appendChild(button);
+        tr.appendChild(view);
+        skillsBody.appendChild(tr);
+    }
+}
+
+async function loadTools() {
+    const resp = await fetch('/api/tools');
+    const tools = await resp.json();
+    toolsBody.replaceChildren();
+    if (!Array.isArray(tools)) {
+        toolsBody.appendChild(cell(tools.error));
+        return;
+    }
+    for (const tool of tools) {
+        const tr = document.createElement('tr');
+        const name = cell(tool.name);
+        name.title = tool.description;
+        tr.appendChild(name);
+        tr.appendChild(cell(tool.risk_level));
+        tr.appendChild(cell(tool.calls));
+        tr.appendChild(cell(tool.errors));
+        tr.appendChild(cell(tool.last_used ? new Date(tool.last_used).toLocaleString() : '-'));
+        tr.appendChild(toggle(tool.enabled, (on) => setEnabled('tools', tool.id, on)));
+        toolsBody.appendChild(tr);
+    }
+}
+
+async function deleteTemplate(id) {
+    if (!confirm(`Delete template ${id}?`)) {
+        return;
+    }
+    const resp = await fetch(`/api/templates/${encodeURIComponent(id)}`, { method: 'DELETE' });
+    if (!resp.ok) {
+        alert((await resp.json()).error || resp.statusText);
+    }
+    loadTemplates();
+}
+
+async function loadTemplates() {
+    const resp = await fetch('/api/templates');
+    const templates = await resp.json();
+    templatesBody.replaceChildren();
+    if (!Array.isArray(templates)) {
+        templatesBody.appendChild(cell(templates.error));
+        return;
+    }
+    for (const template of templates) {
+        const tr = document.createElement('tr');
+        const name = cell(template.name);
+        name.title = template.prompt;
+        tr.appendChild(name);
+        tr.appendChild(cell(template.variables.join(', ') || '-'));
+        tr.appendChild(cell(template.agent || '-'));
+        const actions = document.createElement('td');
+        const button = document.createElement('button');
+        button.textContent = 'Delete';
+        button.onclick = () => deleteTemplate(template.id);
+        actions.appendChild(button);
+        tr.appendChild(actions);
+        templatesBody.appendChild(tr);
+    }
+}
+
+document.querySelectorAll('.tab').forEach((tab) => {
+    tab.onclick = () => {
+        async function deleteTemplate(id) {
+    if (!confirm(`Delete template ${id}?`)) {
+        return;
+    }
+    const resp = await fetch(`/api/templates/${encodeURIComponent(id)}`, { method: 'DELETE' });
+    if (!resp.ok) {
+        alert((await resp.json()).error || resp.statusText);
+    }
+    loadTemplates();
+}
+
+async function loadTemplates() {
+    const resp = await fetch('/api/templates');
+    const templates = await resp.json();
+    templatesBody.replaceChildren();
+    if (!Array.isArray(templates)) {
+        templatesBody.appendChild(cell(templates.error));
+        return;
+    }
+    for (const template of templates) {
+        const tr = document.createElement('tr');
+        const name = cell(template.name);
+        name.title = template.prompt;
+        tr.appendChild(name);
+        tr.appendChild(cell(template.variables.join(', ') || '-'));
+        tr.appendChild(cell(template.agent || '-'));
+        const actions = document.createElement('td');
+        const button = document.createElement('button');
+        button.textContent = 'Delete';
+        button.onclick = () => deleteTemplate(template.id);
+        actions.appendChild(button);
+        tr.appendChild(actions);
+        templatesBody.appendChild(tr);
+    }
+}
+
+document.querySelectorAll('.tab').forEach((t) => t.classList.toggle('active', t === tab));
+        document.querySelectorAll('.panel').forEach((p) => { p.hidden = p.id !== tab.dataset.tab; });
+        if (tab.dataset.tab === 'tools') {
+            loadTools();
+        } else if (tab.dataset.tab === 'templates') {
+            loadTemplates();
+        } else {
+            loadSkills();
+        }
+    };
+});
+
+loadSkills();
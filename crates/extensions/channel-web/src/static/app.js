@@ -68,5 +68,58 @@ form.onsubmit = (e) => {
     }
 };
 
+// Task templates: pick one, fill in its variables, send the rendered prompt
+const templateSelect = document.getElementById('template-select');
+let templates = [];
+
+async function loadTemplates() {
+    try {
+        const resp = await fetch('/api/templates');
+        if (!resp.ok) {
+            return;
+        }
+        templates = await resp.json();
+    } catch (e) {
+        return;
+    }
+    for (const template of templates) {
+        const option = document.createElement('option');
+        option.value = template.id;
+        option.textContent = template.name;
+        option.title = template.description || template.prompt;
+        templateSelect.appendChild(option);
+    }
+    templateSelect.hidden = templates.length === 0;
+}
+
+templateSelect.onchange = async () => {
+    const template = templates.find((t) => t.id === templateSelect.value);
+    templateSelect.value = '';
+    if (!template) {
+        return;
+    }
+    const variables = {};
+    for (const name of template.variables) {
+        const value = window.prompt(`${template.name}: ${name}`);
+        if (value === null) {
+            return;
+        }
+        variables[name] = value;
+    }
+    const resp = await fetch(`/api/templates/${encodeURIComponent(template.id)}/render`, {
+        method: 'POST',
+        headers: { 'Content-Type': 'application/json' },
+        body: JSON.stringify({ variables }),
+    });
+    const data = await resp.json();
+    if (!resp.ok) {
+        alert(data.error || resp.statusText);
+        return;
+    }
+    input.value = data.prompt;
+    form.requestSubmit();
+};
+
 // Start connection
 connect();
+loadTemplates();
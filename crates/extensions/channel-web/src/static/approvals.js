@@ -0,0 +1,85 @@
+// AutoHands pending approvals
+const pollInterval = 3000;
+const updated = document.getElementById('updated');
+const approvalsBody = document.getElementById('approvals-body');
+const details = document.getElementById('approval-details');
+
+function cell(text) {
+    const td = document.createElement('td');
+    td.textContent = text;
+    return td;
+}
+
+function expires(request) {
+    if (!request.timeout_secs) {
+        return '-';
+    }
+    const at = new Date(request.created_at).getTime() + request.timeout_secs * 1000;
+    return `${new Date(at).toLocaleTimeString()} (${request.on_timeout})`;
+}
+
+async function decide(id, action) {
+    const comment = prompt(`Comment for ${action} (optional)`);
+    if (comment === null) {
+        return;
+    }
+    const resp = await fetch(`/api/approvals/${encodeURIComponent(id)}/${action}`, {
+        method: 'POST',
+        headers: { 'Content-Type': 'application/json' },
+        body: JSON.stringify({ decided_by: 'web', comment: comment || null }),
+    });
+    if (!resp.ok) {
+        alert((await resp.json()).error || resp.statusText);
+    }
+    details.hidden = true;
+    refresh();
+}
+
+function button(label, onClick) {
+    const b = document.createElement('button');
+    b.textContent = label;
+    b.onclick = onClick;
+    return b;
+}
+
+function render(approvals) {
+    approvalsBody.replaceChildren();
+    for (const a of approvals) {
+        const tr = document.createElement('tr');
+        tr.appendChild(cell(new Date(a.created_at).toLocaleTimeString()));
+        const title = cell(a.title);
+        title.title = a.description || '';
+        tr.appendChild(title);
+        tr.appendChild(cell(a.source));
+        tr.appendChild(cell(a.risk_level || '-'));
+        tr.appendChild(cell(expires(a)));
+        const actions = document.createElement('td');
+        actions.appendChild(button('Details', () => {
+            details.textContent = JSON.stringify(a, null, 2);
+            details.hidden = false;
+        }));
+        actions.appendChild(button('Approve', () => decide(a.id, 'approve')));
+        actions.appendChild(button('Reject', () => decide(a.id, 'reject')));
+        tr.appendChild(actions);
+        approvalsBody.appendChild(tr);
+    }
+}
+
+async function refresh() {
+    try {
+        const resp = await fetch('/api/approvals');
+        const data = await resp.json();
+        if (!resp.ok) {
+            throw new Error(data.error || resp.statusText);
+        }
+        render(data.approvals);
+        updated.textContent = `${data.count} pending`;
+        updated.className = 'status connected';
+    } catch (e) {
+        updated.textContent = e.message;
+        updated.className = 'status disconnected';
+    }
+}
+
+refresh();
+setInterval(refresh, pollInterval);
@@ -0,0 +1,64 @@
+// AutoHands metrics dashboard
+const pollInterval = 5000;
+const updated = document.getElementById('updated');
+const providersBody = document.getElementById('providers-body');
+const failuresBody = document.getElementById('failures-body');
+
+function cell(text) {
+    const td = document.createElement('td');
+    td.textContent = text;
+    return td;
+}
+
+function setValue(id, value) {
+    document.getElementById(id).textContent = value ?? '-';
+}
+
+function renderProviders(providers) {
+    providersBody.replaceChildren();
+    for (const p of providers || []) {
+        const tr = document.createElement('tr');
+        tr.appendChild(cell(p.provider));
+        tr.appendChild(cell(p.calls));
+        tr.appendChild(cell(p.errors));
+        tr.appendChild(cell(Math.round(p.avg_ms)));
+        tr.appendChild(cell(p.last_ms));
+        tr.appendChild(cell(p.max_ms));
+        providersBody.appendChild(tr);
+    }
+}
+
+function renderFailures(failures) {
+    failuresBody.replaceChildren();
+    for (const f of failures || []) {
+        const tr = document.createElement('tr');
+        tr.appendChild(cell(new Date(f.timestamp).toLocaleTimeString()));
+        tr.appendChild(cell(f.source));
+        tr.appendChild(cell(f.message));
+        failuresBody.appendChild(tr);
+    }
+}
+
+async function refresh() {
+    try {
+        const resp = await fetch('/api/metrics');
+        const data = await resp.json();
+        if (!resp.ok) {
+            throw new Error(data.error || resp.statusText);
+        }
+        setValue('queue-depth', data.queue_depth);
+        setValue('active-sessions', data.active_sessions);
+        setValue('tokens-today', data.tokens_today?.total_tokens?.toLocaleString());
+        setValue('connections', data.connections);
+        renderProviders(data.providers);
+        renderFailures(data.recent_failures);
+        updated.textContent = `Updated ${new Date(data.timestamp).toLocaleTimeString()}`;
+        updated.className = 'status connected';
+    } catch (e) {
+        updated.textContent = e.message;
+        updated.className = 'status disconnected';
+    }
+}
+
+refresh();
+setInterval(refresh, pollInterval);
@@ -0,0 +1,121 @@
+// AutoHands multi-agent task trees
+const pollInterval = 3000;
+const updated = document.getElementById('updated');
+const tasksBody = document.getElementById('tasks-body');
+const treeTitle = document.getElementById('tree-title');
+const treeTotals = document.getElementById('tree-totals');
+const treeRoot = document.getElementById('task-tree');
+let selected = new URLSearchParams(location.search).get('id');
+
+function cell(text) {
+    const td = document.createElement('td');
+    td.textContent = text;
+    return td;
+}
+
+function link(label, href) {
+    const a = document.createElement('a');
+    a.textContent = label;
+    a.href = href;
+    a.target = '_blank';
+    return a;
+}
+
+function card(label, value) {
+    const div = document.createElement('div');
+    div.className = 'card';
+    const l = document.createElement('span');
+    l.className = 'label';
+    l.textContent = label;
+    const v = document.createElement('span');
+    v.className = 'value';
+    v.textContent = value;
+    div.append(l, v);
+    return div;
+}
+
+function renderNode(node) {
+    const li = document.createElement('li');
+    const line = document.createElement('div');
+    line.className = `node ${node.status}`;
+    const kind = node.kind === 'agent' ? 'agent' : 'task';
+    line.append(`[${kind}] ${node.label} — ${node.status}`);
+    if (node.tokens) {
+        line.append(` — ${node.tokens.total} tokens`);
+    }
+    if (node.error) {
+        line.append(` — ${node.error}`);
+    }
+    if (node.links.trace) {
+        line.append(' ', link('trace', node.links.trace));
+    }
+    if (node.links.transcript) {
+        line.append(' ', link('transcript', node.links.transcript));
+    }
+    line.title = node.description || node.id;
+    li.appendChild(line);
+    if (node.children.length > 0) {
+        const ul = document.createElement('ul');
+        for (const child of node.children) {
+            ul.appendChild(renderNode(child));
+        }
+        li.appendChild(ul);
+    }
+    return li;
+}
+
+function renderTree(tree) {
+    treeTitle.textContent = `Run ${tree.root.id}`;
+    treeTitle.hidden = false;
+    treeTotals.replaceChildren(
+        card('Tasks', tree.tasks),
+        card('Sub-agents', tree.agents),
+        card('Tokens', tree.tokens.total),
+    );
+    treeTotals.hidden = false;
+    treeRoot.replaceChildren(renderNode(tree.root));
+}
+
+function renderTasks(tasks) {
+    tasksBody.replaceChildren();
+    for (const t of tasks) {
+        const tr = document.createElement('tr');
+        tr.appendChild(cell(new Date(t.created_at).toLocaleTimeString()));
+        tr.appendChild(cell(t.task_type));
+        tr.appendChild(cell(t.status));
+        tr.appendChild(cell(t.id));
+        tr.onclick = () => {
+            selected = t.id;
+            history.replaceState(null, '', `?id=${encodeURIComponent(t.id)}`);
+            refresh();
+        };
+        tasksBody.appendChild(tr);
+    }
+}
+
+async function fetchJson(url) {
+    const resp = await fetch(url);
+    const data = await resp.json();
+    if (!resp.ok) {
+        throw new Error(data.error || resp.statusText);
+    }
+    return data;
+}
+
+async function refresh() {
+    try {
+        const data = await fetchJson('/api/tasks');
+        renderTasks(data.tasks);
+        if (selected) {
+            renderTree(await fetchJson(`/api/tasks/${encodeURIComponent(selected)}/tree`));
+        }
+        updated.textContent = `${data.count} runs`;
+        updated.className = 'status connected';
+    } catch (e) {
+        updated.textContent = e.message;
+        updated.className = 'status disconnected';
+    }
+}
+
+refresh();
+setInterval(refresh, pollInterval);
@@ -0,0 +1,85 @@
+//! Task tree endpoints backing the tasks page.
+//!
+//! The page lists recent multi-agent runs through `GET /api/tasks` and shows
+//! the parent/child graph of one through `GET /api/tasks/{id}/tree`, both
+//! provided by a [`TaskTreeSource`]. Without a source the endpoints answer
+//! `503 Service Unavailable`.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Deserialize;
+
+use crate::WebChannelState;
+
+/// Runs listed when the page does not ask for a number.
+const DEFAULT_RECENT: usize = 20;
+
+/// Provider of task trees for the tasks page.
+#[async_trait]
+pub trait TaskTreeSource: Send + Sync {
+    /// Root tasks of recent runs, newest first.
+    ///
+    /// The page understands `id`, `task_type`, `status` and `created_at`.
+    async fn recent(&self, limit: usize) -> Vec<serde_json::Value>;
+
+    /// Tree of the run holding a task, `None` if the task is unknown.
+    ///
+    /// The page understands a `root` node with `id`, `kind`, `label`,
+    /// `status`, `tokens`, `links` and `children`, and run totals.
+    async fn tree(&self, id: &str) -> Option<serde_json::Value>;
+}
+
+/// Query of the recent runs.
+#[derive(Debug, Deserialize)]
+pub struct RecentQuery {
+    pub limit: Option<usize>,
+}
+
+fn unavailable() -> Response {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(serde_json::json!({"error": "Task trees are not configured"})),
+    )
+        .into_response()
+}
+
+/// GET /api/tasks
+pub(crate) async fn list_tasks(
+    State(state): State<Arc<WebChannelState>>,
+    Query(query): Query<RecentQuery>,
+) -> Response {
+    let Some(source) = state.tasks.get() else {
+        return unavailable();
+    };
+    let tasks = source.recent(query.limit.unwrap_or(DEFAULT_RECENT)).await;
+    Json(serde_json::json!({"count": tasks.len(), "tasks": tasks})).into_response()
+}
+
+/// GET /api/tasks/{id}/tree
+pub(crate) async fn get_task_tree(
+    State(state): State<Arc<WebChannelState>>,
+    Path(id): Path<String>,
+) -> Response {
+    let Some(source) = state.tasks.get() else {
+        return unavailable();
+    };
+    match source.tree(&id).await {
+        Some(tree) => Json(tree).into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("Unknown task '{}'", id)})),
+        )
+            .into_response(),
+    }
+}
+
+#[cfg(test)]
+#[path = "tasks_tests.rs"]
+mod tests;
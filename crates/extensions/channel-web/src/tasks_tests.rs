@@ -0,0 +1,60 @@
+use super::*;
+use crate::server::create_router;
+use axum::body::Body;
+use axum::http::Request;
+use tower::ServiceExt;
+
+/// Source knowing a single run.
+struct StubSource;
+
+#[async_trait]
+impl TaskTreeSource for StubSource {
+    async fn recent(&self, limit: usize) -> Vec<serde_json::Value> {
+        vec![serde_json::json!({"id": "task-1", "status": "running"})]
+            .into_iter()
+            .take(limit)
+            .collect()
+    }
+
+    async fn tree(&self, id: &str) -> Option<serde_json::Value> {
+        (id == "task-1").then(|| serde_json::json!({"root": {"id": "task-1", "children": []}}))
+    }
+}
+
+async fn get(state: Arc<WebChannelState>, uri: &str) -> (StatusCode, serde_json::Value) {
+    let resp = create_router(state)
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = resp.status();
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn test_tasks_without_source() {
+    let state = Arc::new(WebChannelState::new("web"));
+    let (status, _) = get(state.clone(), "/api/tasks").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    let (status, _) = get(state, "/api/tasks/task-1/tree").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test]
+async fn test_task_trees() {
+    let state = Arc::new(WebChannelState::new("web"));
+    let _ = state.tasks.set(Arc::new(StubSource));
+
+    let (status, body) = get(state.clone(), "/api/tasks").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["count"], 1);
+    assert_eq!(body["tasks"][0]["id"], "task-1");
+    let (_, body) = get(state.clone(), "/api/tasks?limit=0").await;
+    assert_eq!(body["count"], 0);
+
+    let (status, body) = get(state.clone(), "/api/tasks/task-1/tree").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["root"]["id"], "task-1");
+    let (status, _) = get(state, "/api/tasks/task-2/tree").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
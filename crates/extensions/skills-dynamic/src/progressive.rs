@@ -38,7 +38,8 @@ impl SkillMetadataInjector {
     /// </available_skills>
     /// ```
     pub async fn generate_metadata_section(&self) -> String {
-        let skills = self.registry.list().await;
+        let mut skills = self.registry.list().await;
+        skills.retain(|skill| skill.enabled);
 
         if skills.is_empty() {
             return String::new();
@@ -34,6 +34,17 @@ async fn test_generate_metadata_section() {
     assert!(section.contains("<id>security-audit</id>"));
 }
 
+#[tokio::test]
+async fn test_metadata_section_skips_disabled() {
+    let registry = create_test_registry().await;
+    registry.set_enabled("security-audit", false).await;
+    let injector = SkillMetadataInjector::new(registry);
+
+    let section = injector.generate_metadata_section().await;
+    assert!(section.contains("<id>code-review</id>"));
+    assert!(!section.contains("<id>security-audit</id>"));
+}
+
 #[tokio::test]
 async fn test_empty_registry() {
     let registry = Arc::new(SkillRegistry::new());
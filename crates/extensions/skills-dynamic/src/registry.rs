@@ -1,12 +1,18 @@
 //! Skill registry for managing loaded skills.
 //!
 //! Provides a thread-safe registry for accessing skills by ID or tag.
+//!
+//! With a state file, skills disabled through [`SkillRegistry::set_enabled`]
+//! stay disabled across restarts: the file lists their IDs, and skills
+//! registered under those IDs start disabled.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 use std::sync::Arc;
 
+use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
-use tracing::debug;
+use tracing::{debug, warn};
 
 use autohands_protocols::skill::{Skill, SkillDefinition};
 
@@ -18,6 +24,17 @@ pub struct SkillRegistry {
     tags_index: Arc<RwLock<HashMap<String, Vec<String>>>>,
     /// Skills indexed by category.
     category_index: Arc<RwLock<HashMap<String, Vec<String>>>>,
+    /// IDs of disabled skills.
+    disabled: Arc<RwLock<HashSet<String>>>,
+    /// File keeping the disabled skills across restarts.
+    state_file: Option<PathBuf>,
+}
+
+/// Content of the state file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SkillState {
+    #[serde(default)]
+    disabled: Vec<String>,
 }
 
 impl SkillRegistry {
@@ -27,12 +44,34 @@ impl SkillRegistry {
             skills: Arc::new(RwLock::new(HashMap::new())),
             tags_index: Arc::new(RwLock::new(HashMap::new())),
             category_index: Arc::new(RwLock::new(HashMap::new())),
+            disabled: Arc::new(RwLock::new(HashSet::new())),
+            state_file: None,
+        }
+    }
+
+    /// Keep the enabled state of skills in `path`, loading it now.
+    pub fn with_state_file(mut self, path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        match std::fs::read_to_string(&path) {
+            Ok(raw) => match serde_json::from_str::<SkillState>(&raw) {
+                Ok(state) => {
+                    self.disabled = Arc::new(RwLock::new(state.disabled.into_iter().collect()));
+                }
+                Err(e) => warn!("Ignoring invalid skill state {}: {}", path.display(), e),
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => warn!("Failed to read skill state {}: {}", path.display(), e),
         }
+        self.state_file = Some(path);
+        self
     }
 
     /// Register a skill.
-    pub async fn register(&self, skill: Skill) {
+    pub async fn register(&self, mut skill: Skill) {
         let id = skill.definition.id.clone();
+        if self.disabled.read().await.contains(&id) {
+            skill.definition.enabled = false;
+        }
 
         // Update indexes
         let tags = skill.definition.tags.clone();
@@ -115,6 +154,47 @@ impl SkillRegistry {
         skills.values().map(|s| s.definition.clone()).collect()
     }
 
+    /// Enable or disable a skill, saving the change to the state file.
+    ///
+    /// Returns `false` if the skill is not registered.
+    pub async fn set_enabled(&self, skill_id: &str, enabled: bool) -> bool {
+        {
+            let mut skills = self.skills.write().await;
+            let Some(skill) = skills.get_mut(skill_id) else {
+                return false;
+            };
+            skill.definition.enabled = enabled;
+        }
+        debug!("Skill {} enabled={}", skill_id, enabled);
+
+        let state = {
+            let mut disabled = self.disabled.write().await;
+            if enabled {
+                disabled.remove(skill_id);
+            } else {
+                disabled.insert(skill_id.to_string());
+            }
+            let mut disabled: Vec<String> = disabled.iter().cloned().collect();
+            disabled.sort();
+            SkillState { disabled }
+        };
+        if let Some(path) = &self.state_file {
+            if let Err(e) = save_state(path, &state).await {
+                warn!("Failed to save skill state {}: {}", path.display(), e);
+            }
+        }
+        true
+    }
+
+    /// Whether a skill may be used: registered skills by their enabled
+    /// flag, others unless they were disabled.
+    pub async fn is_enabled(&self, skill_id: &str) -> bool {
+        match self.skills.read().await.get(skill_id) {
+            Some(skill) => skill.definition.enabled,
+            None => !self.disabled.read().await.contains(skill_id),
+        }
+    }
+
     /// List all skill IDs.
     pub async fn list_ids(&self) -> Vec<String> {
         let skills = self.skills.read().await;
@@ -200,6 +280,14 @@ impl SkillRegistry {
     }
 }
 
+async fn save_state(path: &std::path::Path, state: &SkillState) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let json = serde_json::to_string_pretty(state).map_err(std::io::Error::other)?;
+    tokio::fs::write(path, json).await
+}
+
 impl Default for SkillRegistry {
     fn default() -> Self {
         Self::new()
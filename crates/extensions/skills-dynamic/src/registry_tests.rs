@@ -123,3 +123,37 @@ async fn test_contains() {
     assert!(registry.contains("exists").await);
     assert!(!registry.contains("not-exists").await);
 }
+
+#[tokio::test]
+async fn test_set_enabled() {
+    let registry = SkillRegistry::new();
+    registry.register(create_test_skill("toggle", vec![], None)).await;
+
+    assert!(registry.set_enabled("toggle", false).await);
+    assert!(!registry.get("toggle").await.unwrap().definition.enabled);
+    assert!(!registry.set_enabled("missing", false).await);
+}
+
+#[tokio::test]
+async fn test_enabled_state_persisted() {
+    let dir = tempfile::tempdir().unwrap();
+    let state = dir.path().join("state").join("skills.json");
+
+    let registry = SkillRegistry::new().with_state_file(&state);
+    registry.register(create_test_skill("toggle", vec![], None)).await;
+    registry.register(create_test_skill("other", vec![], None)).await;
+    assert!(registry.set_enabled("toggle", false).await);
+    assert!(!registry.is_enabled("toggle").await);
+
+    // A restarted registry disables the skill when it is registered again.
+    let restarted = SkillRegistry::new().with_state_file(&state);
+    assert!(!restarted.is_enabled("toggle").await);
+    restarted.register(create_test_skill("toggle", vec![], None)).await;
+    restarted.register(create_test_skill("other", vec![], None)).await;
+    assert!(!restarted.get("toggle").await.unwrap().definition.enabled);
+    assert!(restarted.is_enabled("other").await);
+
+    assert!(restarted.set_enabled("toggle", true).await);
+    let again = SkillRegistry::new().with_state_file(&state);
+    assert!(again.is_enabled("toggle").await);
+}
@@ -21,6 +21,7 @@ impl SkillContentTool {
             "skill_content",
             "Get the full content/prompt of a skill for use as system prompt",
         )
+        .with_read_only()
         .with_parameters_schema(serde_json::json!({
             "type": "object",
             "properties": {
@@ -21,6 +21,7 @@ impl SkillInfoTool {
             "skill_info",
             "Get detailed information about a specific skill",
         )
+        .with_read_only()
         .with_parameters_schema(serde_json::json!({
             "type": "object",
             "properties": {
@@ -21,6 +21,7 @@ impl SkillListTool {
             "skill_list",
             "List all available dynamic skills",
         )
+        .with_read_only()
         .with_parameters_schema(serde_json::json!({
             "type": "object",
             "properties": {
@@ -22,6 +22,7 @@ impl SkillReadTool {
             "skill_read",
             "Read a file from a skill's directory",
         )
+        .with_read_only()
         .with_parameters_schema(serde_json::json!({
             "type": "object",
             "properties": {
@@ -7,6 +7,7 @@ use async_trait::async_trait;
 
 use autohands_protocols::error::ExtensionError;
 use autohands_protocols::extension::{Extension, ExtensionContext, ExtensionManifest, Provides};
+use autohands_protocols::question::QuestionGate;
 use autohands_protocols::types::Version;
 use autohands_runtime::AgentRuntime;
 
@@ -31,6 +32,7 @@ pub struct AgentToolsExtension {
     manifest: ExtensionManifest,
     config: AgentToolsConfig,
     manager: Option<Arc<AgentManager>>,
+    question_gate: Option<Arc<dyn QuestionGate>>,
 }
 
 impl AgentToolsExtension {
@@ -57,6 +59,7 @@ impl AgentToolsExtension {
             manifest,
             config: AgentToolsConfig::default(),
             manager: None,
+            question_gate: None,
         }
     }
 
@@ -66,6 +69,13 @@ impl AgentToolsExtension {
         self
     }
 
+    /// Also provide `ask_user`, asking questions through this gate.
+    pub fn with_question_gate(mut self, gate: Arc<dyn QuestionGate>) -> Self {
+        self.manifest.provides.tools.push("ask_user".to_string());
+        self.question_gate = Some(gate);
+        self
+    }
+
     /// Set the agent runtime for spawning agents.
     ///
     /// This must be called before the extension is initialized if you want
@@ -117,6 +127,10 @@ impl Extension for AgentToolsExtension {
             .register_tool(Arc::new(AgentTerminateTool::new(manager.clone())))?;
         ctx.tool_registry
             .register_tool(Arc::new(AgentListTool::new(manager.clone())))?;
+        if let Some(gate) = &self.question_gate {
+            ctx.tool_registry
+                .register_tool(Arc::new(AskUserTool::new(gate.clone())))?;
+        }
 
         self.manager = Some(manager);
 
@@ -56,3 +56,13 @@ fn test_manifest_description() {
         .description
         .contains("Sub-agent"));
 }
+
+#[test]
+fn test_question_gate_provides_ask_user() {
+    let ext = AgentToolsExtension::new();
+    assert!(!ext.manifest().provides.tools.contains(&"ask_user".to_string()));
+
+    let gate = Arc::new(autohands_core::QuestionService::new());
+    let ext = AgentToolsExtension::new().with_question_gate(gate);
+    assert!(ext.manifest().provides.tools.contains(&"ask_user".to_string()));
+}
@@ -10,6 +10,8 @@
 //! - `agent_status` - Query the status of a spawned agent
 //! - `agent_message` - Send a message to a running agent
 //! - `agent_terminate` - Terminate a running agent
+//! - `ask_user` - Ask the user a question and wait for the reply (when a
+//!   question gate is configured)
 //!
 //! ## Agent Communication
 //!
@@ -10,6 +10,8 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tracing::{debug, error, info, warn};
 
+use autohands_protocols::agent::RunScheduling;
+use autohands_protocols::error::AgentError;
 use autohands_protocols::tool::AbortSignal;
 use autohands_protocols::types::Message;
 use autohands_runtime::AgentRuntime;
@@ -81,6 +83,9 @@ pub struct SpawnedAgent {
     pub tools: Vec<String>,
     /// Custom metadata.
     pub metadata: HashMap<String, serde_json::Value>,
+    /// Priority and deadline inherited from the spawning run.
+    #[serde(default, skip_serializing_if = "RunScheduling::is_empty")]
+    pub scheduling: RunScheduling,
 }
 
 /// Internal state for a running agent.
@@ -123,6 +128,12 @@ impl AgentManager {
     }
 
     /// Spawn a new sub-agent.
+    ///
+    /// The sub-agent runs under `scheduling`, normally the spawning run's
+    /// own, so it gives up once the parent's deadline passes. It is aborted
+    /// along with `parent_signal`, so terminating the spawning session
+    /// terminates its sub-agents too.
+    #[allow(clippy::too_many_arguments)]
     pub async fn spawn(
         &self,
         agent_id: &str,
@@ -130,6 +141,8 @@ impl AgentManager {
         parent_id: Option<&str>,
         tools: Vec<String>,
         metadata: HashMap<String, serde_json::Value>,
+        scheduling: RunScheduling,
+        parent_signal: &AbortSignal,
     ) -> Result<SpawnedAgent, AgentManagerError> {
         // Check concurrent limit
         let running_count = self
@@ -180,9 +193,10 @@ impl AgentManager {
             error: None,
             tools,
             metadata,
+            scheduling: scheduling.clone(),
         };
 
-        let abort_signal = Arc::new(AbortSignal::new());
+        let abort_signal = parent_signal.child();
 
         // Store running agent
         self.agents.insert(
@@ -200,6 +214,7 @@ impl AgentManager {
         let task_clone = task.to_string();
         let agent_id_clone = agent_id.to_string();
         let session_id_clone = session_id.clone();
+        let run_signal = abort_signal.clone();
 
         tokio::spawn(async move {
             // Update status to running
@@ -215,10 +230,17 @@ impl AgentManager {
             // Execute agent
             let message = Message::user(&task_clone);
             let result = runtime
-                .execute(&agent_id_clone, &session_id_clone, message)
+                .execute_child(
+                    &agent_id_clone,
+                    &session_id_clone,
+                    message,
+                    scheduling,
+                    run_signal,
+                )
                 .await;
 
             // Process result
+            let aborted = matches!(result, Err(AgentError::Aborted));
             let (success, messages, error) = match result {
                 Ok(msgs) => (true, msgs, None),
                 Err(e) => (false, vec![], Some(e.to_string())),
@@ -228,10 +250,12 @@ impl AgentManager {
             if let Some(mut agent) = manager_agents.get_mut(&spawn_id_clone) {
                 agent.info.status = if success {
                     SpawnedAgentStatus::Completed
+                } else if aborted {
+                    SpawnedAgentStatus::Terminated
                 } else {
                     SpawnedAgentStatus::Failed
                 };
-                agent.info.completed_at = Some(Utc::now());
+                agent.info.completed_at.get_or_insert_with(Utc::now);
                 agent.info.error = error.clone();
 
                 if let Some(last) = messages.last() {
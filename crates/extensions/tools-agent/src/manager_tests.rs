@@ -67,6 +67,7 @@ fn test_spawned_agent_serialize() {
         error: None,
         tools: vec!["read_file".to_string()],
         metadata: HashMap::new(),
+        scheduling: Default::default(),
     };
 
     let json = serde_json::to_string(&agent).unwrap();
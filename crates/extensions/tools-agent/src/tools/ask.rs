@@ -0,0 +1,120 @@
+//! Ask user tool.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::question::{Answer, AnswerStatus, Question, QuestionGate};
+use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::types::RiskLevel;
+
+#[derive(Debug, Deserialize)]
+pub struct AskUserParams {
+    /// The question.
+    pub question: String,
+    /// Choices to offer.
+    #[serde(default)]
+    pub options: Vec<String>,
+    /// Answer to assume when nobody replies in time.
+    #[serde(default)]
+    pub default: Option<String>,
+    /// Seconds to wait for a reply.
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+}
+
+/// Ask the user a question and wait for the reply.
+pub struct AskUserTool {
+    definition: ToolDefinition,
+    gate: Arc<dyn QuestionGate>,
+}
+
+impl AskUserTool {
+    pub fn new(gate: Arc<dyn QuestionGate>) -> Self {
+        let mut definition = ToolDefinition::new(
+            "ask_user",
+            "Ask User",
+            "Ask the user a clarifying question in the conversation the task came \
+             from and wait for the reply. Use this instead of guessing when the \
+             request is ambiguous or a decision belongs to the user. Offer options \
+             for multiple-choice questions and a default for when nobody replies.",
+        );
+        definition.parameters_schema = Some(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "question": {
+                    "type": "string",
+                    "description": "The question to ask"
+                },
+                "options": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Choices to offer; the user may pick one by number or text"
+                },
+                "default": {
+                    "type": "string",
+                    "description": "Answer to assume when nobody replies in time"
+                },
+                "timeout_secs": {
+                    "type": "integer",
+                    "description": "Seconds to wait for a reply"
+                }
+            },
+            "required": ["question"]
+        }));
+        definition.risk_level = RiskLevel::Low;
+
+        Self { definition, gate }
+    }
+}
+
+/// The answer as reported to the model.
+pub(super) fn describe(answer: &Answer) -> String {
+    match (answer.status, &answer.text) {
+        (AnswerStatus::Answered, Some(text)) => match answer.option {
+            Some(i) => format!("The user chose option {}: {}", i + 1, text),
+            None => format!("The user replied: {}", text),
+        },
+        (AnswerStatus::Answered, None) => "The user replied without an answer".to_string(),
+        (AnswerStatus::TimedOut, Some(default)) => {
+            format!("No reply in time; proceeding with the default: {}", default)
+        }
+        (AnswerStatus::TimedOut, None) => {
+            "No reply in time and no default; decide conservatively or stop".to_string()
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for AskUserTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: AskUserParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::ExecutionFailed(format!("Invalid params: {}", e)))?;
+        if params.question.trim().is_empty() {
+            return Err(ToolError::ExecutionFailed("Question is empty".to_string()));
+        }
+
+        let mut question = Question::new(params.question)
+            .with_options(params.options)
+            .with_session(ctx.session_id);
+        if let Some(default) = params.default {
+            question = question.with_default(default);
+        }
+        if let Some(timeout_secs) = params.timeout_secs {
+            question = question.with_timeout(timeout_secs);
+        }
+
+        let answer = self.gate.ask(question).await;
+        Ok(ToolResult::success(describe(&answer)).with_metadata("answer", serde_json::json!(answer)))
+    }
+}
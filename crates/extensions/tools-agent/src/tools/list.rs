@@ -36,7 +36,8 @@ impl AgentListTool {
             "agent_list",
             "Agent List",
             "List all spawned sub-agents, optionally filtered by status or parent.",
-        );
+        )
+        .with_read_only();
         definition.parameters_schema = Some(serde_json::json!({
             "type": "object",
             "properties": {
@@ -1,11 +1,13 @@
-//! Sub-agent management tools.
+//! Sub-agent management tools, and asking the user.
 
+mod ask;
 mod list;
 mod message;
 mod spawn;
 mod status;
 mod terminate;
 
+pub use ask::*;
 pub use list::*;
 pub use message::*;
 pub use spawn::*;
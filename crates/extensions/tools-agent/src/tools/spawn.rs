@@ -113,9 +113,15 @@ impl Tool for AgentSpawnTool {
             .spawn(
                 &params.agent_id,
                 &params.task,
-                ctx.data.get("agent_id").and_then(|v| v.as_str()),
+                // The spawning session, unless the caller names its agent
+                ctx.data
+                    .get("agent_id")
+                    .and_then(|v| v.as_str())
+                    .or(Some(ctx.session_id.as_str())),
                 params.tools,
                 metadata,
+                ctx.scheduling.clone(),
+                &ctx.abort_signal,
             )
             .await
             .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
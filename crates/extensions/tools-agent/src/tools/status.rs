@@ -45,7 +45,8 @@ impl AgentStatusTool {
             "Agent Status",
             "Query the status of a spawned sub-agent. Returns current status, \
              progress information, and result if completed.",
-        );
+        )
+        .with_read_only();
         definition.parameters_schema = Some(serde_json::json!({
             "type": "object",
             "properties": {
@@ -71,3 +71,26 @@ fn test_list_result_serialize() {
     let json = serde_json::to_string(&result).unwrap();
     assert!(json.contains("\"count\":0"));
 }
+
+#[test]
+fn test_ask_params_deserialize() {
+    let json = r#"{"question": "Which branch?", "options": ["main", "dev"], "default": "main"}"#;
+    let params: AskUserParams = serde_json::from_str(json).unwrap();
+    assert_eq!(params.question, "Which branch?");
+    assert_eq!(params.options, vec!["main", "dev"]);
+    assert_eq!(params.default.as_deref(), Some("main"));
+    assert!(params.timeout_secs.is_none());
+}
+
+#[test]
+fn test_ask_describe_answer() {
+    use super::ask::describe;
+    use autohands_protocols::question::Answer;
+
+    assert_eq!(
+        describe(&Answer::answered("dev").with_option(1)),
+        "The user chose option 2: dev"
+    );
+    assert_eq!(describe(&Answer::answered("later")), "The user replied: later");
+    assert!(describe(&Answer::timed_out(Some("main".to_string()))).contains("default: main"));
+}
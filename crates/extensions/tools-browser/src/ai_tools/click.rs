@@ -11,7 +11,9 @@ use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
 use autohands_protocols::types::RiskLevel;
 
 use crate::manager::BrowserManager;
+use crate::tools::PAGE_RESOURCE;
 
+use super::marks::locate_marked;
 use super::{parse_coordinates, ElementCoordinates, VisionProvider};
 
 #[derive(Debug, Deserialize)]
@@ -26,6 +28,9 @@ pub struct AiClickParams {
 pub struct AiClickResult {
     pub success: bool,
     pub clicked_at: ElementCoordinates,
+    /// Number of the marked element picked, if the page had one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mark: Option<u32>,
     pub description: String,
 }
 
@@ -42,7 +47,9 @@ impl AiClickTool {
             "browser_ai_click",
             "Browser AI Click",
             "Click an element identified by natural language description using AI vision. \
-             Use this when you don't know the CSS selector but can describe what to click.",
+             The page's interactive elements are outlined with numbered labels for the model \
+             to pick from. Use this when you don't know the CSS selector but can describe \
+             what to click.",
         );
         definition.parameters_schema = Some(serde_json::json!({
             "type": "object",
@@ -59,6 +66,7 @@ impl AiClickTool {
             "required": ["page_id", "target"]
         }));
         definition.risk_level = RiskLevel::Medium;
+        definition.exclusive_resources.push(PAGE_RESOURCE.to_string());
 
         Self {
             definition,
@@ -66,29 +74,17 @@ impl AiClickTool {
             vision,
         }
     }
-}
-
-#[async_trait]
-impl Tool for AiClickTool {
-    fn definition(&self) -> &ToolDefinition {
-        &self.definition
-    }
-
-    fn risk_level(&self) -> RiskLevel {
-        RiskLevel::Medium
-    }
 
-    async fn execute(
+    /// Ask the vision model for the coordinates of an element on an
+    /// unmarked screenshot.
+    async fn locate_by_coordinates(
         &self,
-        params: serde_json::Value,
-        _ctx: ToolContext,
-    ) -> Result<ToolResult, ToolError> {
-        let params: AiClickParams = serde_json::from_value(params)
-            .map_err(|e| ToolError::ExecutionFailed(format!("Invalid params: {}", e)))?;
-
+        page_id: &str,
+        target: &str,
+    ) -> Result<ElementCoordinates, ToolError> {
         let screenshot_base64 = self
             .manager
-            .screenshot(&params.page_id, false)
+            .screenshot(page_id, false)
             .await
             .map_err(|e| ToolError::ExecutionFailed(format!("Screenshot failed: {}", e)))?;
 
@@ -101,7 +97,7 @@ If you cannot find the element, respond with:
 {{"error": "Element not found", "reason": "<explanation>"}}
 
 Only respond with the JSON, no other text."#,
-            params.target
+            target
         );
 
         let response = self.vision.analyze(&screenshot_base64, &prompt).await?;
@@ -114,7 +110,40 @@ Only respond with the JSON, no other text."#,
             )));
         }
 
-        let coords = parse_coordinates(&response)?;
+        parse_coordinates(&response)
+    }
+}
+
+#[async_trait]
+impl Tool for AiClickTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        _ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: AiClickParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::ExecutionFailed(format!("Invalid params: {}", e)))?;
+
+        // Unmarked elements, like controls drawn on a canvas, are located
+        // by coordinates instead
+        let marked =
+            locate_marked(&self.manager, &self.vision, &params.page_id, &params.target).await?;
+        let (mark, coords) = match marked {
+            Some((mark, coords)) => (Some(mark), coords),
+            None => (
+                None,
+                self.locate_by_coordinates(&params.page_id, &params.target)
+                    .await?,
+            ),
+        };
 
         if coords.confidence < 0.5 {
             return Err(ToolError::ExecutionFailed(format!(
@@ -133,6 +162,7 @@ Only respond with the JSON, no other text."#,
         let result = AiClickResult {
             success: true,
             clicked_at: coords,
+            mark,
             description: format!("Clicked '{}' at identified location", params.target),
         };
 
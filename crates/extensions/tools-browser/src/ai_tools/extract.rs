@@ -42,7 +42,8 @@ impl AiExtractTool {
             "Browser AI Extract",
             "Extract structured data from a web page using AI vision. \
              Use this to extract tables, lists, product info, or any structured content.",
-        );
+        )
+        .with_read_only();
         definition.parameters_schema = Some(serde_json::json!({
             "type": "object",
             "properties": {
@@ -11,7 +11,9 @@ use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
 use autohands_protocols::types::RiskLevel;
 
 use crate::manager::BrowserManager;
+use crate::tools::PAGE_RESOURCE;
 
+use super::marks::locate_marked;
 use super::{parse_coordinates, ElementCoordinates, VisionProvider};
 
 #[derive(Debug, Deserialize)]
@@ -35,6 +37,9 @@ fn default_clear() -> bool {
 pub struct AiFillResult {
     pub success: bool,
     pub field_coordinates: ElementCoordinates,
+    /// Number of the marked field picked, if the page had one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mark: Option<u32>,
     pub value_entered: String,
 }
 
@@ -51,7 +56,9 @@ impl AiFillTool {
             "browser_ai_fill",
             "Browser AI Fill",
             "Fill a form field identified by natural language description using AI vision. \
-             Use this when you don't know the CSS selector but can describe the field.",
+             The page's interactive elements are outlined with numbered labels for the model \
+             to pick from. Use this when you don't know the CSS selector but can describe \
+             the field.",
         );
         definition.parameters_schema = Some(serde_json::json!({
             "type": "object",
@@ -76,6 +83,7 @@ impl AiFillTool {
             "required": ["page_id", "field", "value"]
         }));
         definition.risk_level = RiskLevel::Medium;
+        definition.exclusive_resources.push(PAGE_RESOURCE.to_string());
 
         Self {
             definition,
@@ -83,29 +91,17 @@ impl AiFillTool {
             vision,
         }
     }
-}
-
-#[async_trait]
-impl Tool for AiFillTool {
-    fn definition(&self) -> &ToolDefinition {
-        &self.definition
-    }
-
-    fn risk_level(&self) -> RiskLevel {
-        RiskLevel::Medium
-    }
 
-    async fn execute(
+    /// Ask the vision model for the coordinates of a field on an unmarked
+    /// screenshot.
+    async fn locate_by_coordinates(
         &self,
-        params: serde_json::Value,
-        _ctx: ToolContext,
-    ) -> Result<ToolResult, ToolError> {
-        let params: AiFillParams = serde_json::from_value(params)
-            .map_err(|e| ToolError::ExecutionFailed(format!("Invalid params: {}", e)))?;
-
+        page_id: &str,
+        field: &str,
+    ) -> Result<ElementCoordinates, ToolError> {
         let screenshot_base64 = self
             .manager
-            .screenshot(&params.page_id, false)
+            .screenshot(page_id, false)
             .await
             .map_err(|e| ToolError::ExecutionFailed(format!("Screenshot failed: {}", e)))?;
 
@@ -118,7 +114,7 @@ If you cannot find the field, respond with:
 {{"error": "Field not found", "reason": "<explanation>"}}
 
 Only respond with the JSON, no other text."#,
-            params.field
+            field
         );
 
         let response = self.vision.analyze(&screenshot_base64, &prompt).await?;
@@ -131,7 +127,40 @@ Only respond with the JSON, no other text."#,
             )));
         }
 
-        let coords = parse_coordinates(&response)?;
+        parse_coordinates(&response)
+    }
+}
+
+#[async_trait]
+impl Tool for AiFillTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        _ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: AiFillParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::ExecutionFailed(format!("Invalid params: {}", e)))?;
+
+        // Unmarked fields, like inputs drawn on a canvas, are located by
+        // coordinates instead
+        let marked =
+            locate_marked(&self.manager, &self.vision, &params.page_id, &params.field).await?;
+        let (mark, coords) = match marked {
+            Some((mark, coords)) => (Some(mark), coords),
+            None => (
+                None,
+                self.locate_by_coordinates(&params.page_id, &params.field)
+                    .await?,
+            ),
+        };
 
         self.manager
             .click(&params.page_id, coords.x as f64, coords.y as f64)
@@ -148,6 +177,7 @@ Only respond with the JSON, no other text."#,
         let result = AiFillResult {
             success: true,
             field_coordinates: coords,
+            mark,
             value_entered: params.value,
         };
 
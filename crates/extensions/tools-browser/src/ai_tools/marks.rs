@@ -0,0 +1,265 @@
+//! Set-of-marks grounding.
+//!
+//! Instead of asking the vision model for coordinates, the page's
+//! interactive elements (including those inside shadow roots) are outlined
+//! with numbered labels for the screenshot, the model names the number of
+//! the element it means, and the element's box is clicked. Picking a label
+//! is far more reliable than estimating pixels, and the boxes come from the
+//! page itself, in the same CSS pixels clicks are dispatched in.
+
+use serde::Deserialize;
+use tracing::{debug, info};
+
+use autohands_protocols::error::ToolError;
+
+use crate::manager::BrowserManager;
+
+use super::{ElementCoordinates, VisionProvider};
+
+/// ID of the overlay element.
+const OVERLAY_ID: &str = "__autohands_marks";
+
+/// Most elements marked on one screenshot; past this, labels overlap too
+/// much to read.
+const MAX_MARKS: usize = 200;
+
+/// An interactive element outlined on the page, in viewport CSS pixels.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Mark {
+    /// Number on the element's label, from 1.
+    pub number: u32,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    /// Tag name (lowercase).
+    pub tag: String,
+    /// Start of the element's text, value or label.
+    #[serde(default)]
+    pub text: String,
+}
+
+impl Mark {
+    /// Center of the element, where it is clicked.
+    pub fn center(&self) -> (f64, f64) {
+        (self.x + self.width / 2.0, self.y + self.height / 2.0)
+    }
+
+    /// Coordinates of the element's center, with its size.
+    pub fn coordinates(&self, confidence: f32) -> ElementCoordinates {
+        let (x, y) = self.center();
+        ElementCoordinates {
+            x: x.round() as i32,
+            y: y.round() as i32,
+            width: Some(self.width.round() as i32),
+            height: Some(self.height.round() as i32),
+            confidence,
+        }
+    }
+}
+
+/// The vision model's pick among the marks.
+#[derive(Debug, Deserialize)]
+pub(crate) struct MarkChoice {
+    /// Number of the picked element, `None` when it is not marked.
+    pub mark: Option<u32>,
+    #[serde(default = "default_confidence")]
+    pub confidence: f32,
+}
+
+fn default_confidence() -> f32 {
+    0.8
+}
+
+/// Locate the element described as `target` by marking the page, and
+/// return its mark number and coordinates.
+///
+/// Returns `None` when the page has nothing to mark or the model finds
+/// the element among none of the marks, as for controls drawn on a canvas,
+/// so callers can fall back to asking for coordinates.
+pub(crate) async fn locate_marked(
+    manager: &BrowserManager,
+    vision: &VisionProvider,
+    page_id: &str,
+    target: &str,
+) -> Result<Option<(u32, ElementCoordinates)>, ToolError> {
+    let marked = manager
+        .evaluate(page_id, &mark_script())
+        .await
+        .map_err(|e| ToolError::ExecutionFailed(format!("Marking elements failed: {}", e)))?;
+    let marks: Vec<Mark> = serde_json::from_str(marked.as_str().unwrap_or("[]"))
+        .map_err(|e| ToolError::ExecutionFailed(format!("Invalid marks: {}", e)))?;
+
+    // The overlay is removed before anything else can fail
+    let screenshot = if marks.is_empty() {
+        None
+    } else {
+        Some(manager.screenshot(page_id, false).await)
+    };
+    if let Err(e) = manager.evaluate(page_id, &remove_marks_script()).await {
+        debug!("Failed to remove marks: {}", e);
+    }
+    let Some(screenshot) = screenshot else {
+        return Ok(None);
+    };
+    let screenshot =
+        screenshot.map_err(|e| ToolError::ExecutionFailed(format!("Screenshot failed: {}", e)))?;
+
+    let response = vision
+        .analyze(&screenshot, &marks_prompt(target, &marks))
+        .await?;
+    info!("Vision response: {}", response);
+
+    let choice = parse_mark_choice(&response)?;
+    let Some(number) = choice.mark else {
+        return Ok(None);
+    };
+    let mark = marks.iter().find(|m| m.number == number).ok_or_else(|| {
+        ToolError::ExecutionFailed(format!(
+            "Vision model picked mark {}, but only 1-{} were drawn",
+            number,
+            marks.len()
+        ))
+    })?;
+    Ok(Some((number, mark.coordinates(choice.confidence))))
+}
+
+/// Parse the vision model's pick from its response.
+pub(crate) fn parse_mark_choice(response: &str) -> Result<MarkChoice, ToolError> {
+    // Models sometimes wrap the JSON in prose or a code fence
+    let json = match (response.find('{'), response.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &response[start..=end],
+        _ => response,
+    };
+    if let Ok(choice) = serde_json::from_str::<MarkChoice>(json) {
+        return Ok(choice);
+    }
+
+    let number = regex::Regex::new(r"(?i)mark\D{0,3}(\d+)")
+        .ok()
+        .and_then(|re| re.captures(response))
+        .and_then(|caps| caps[1].parse().ok());
+    match number {
+        Some(number) => Ok(MarkChoice {
+            mark: Some(number),
+            confidence: default_confidence(),
+        }),
+        None => Err(ToolError::ExecutionFailed(format!(
+            "Could not parse mark from response: {}",
+            response
+        ))),
+    }
+}
+
+/// Prompt asking for the number of the element described as `target`.
+pub(crate) fn marks_prompt(target: &str, marks: &[Mark]) -> String {
+    let legend: Vec<String> = marks
+        .iter()
+        .map(|m| {
+            if m.text.is_empty() {
+                format!("{}: <{}>", m.number, m.tag)
+            } else {
+                format!("{}: <{}> {:?}", m.number, m.tag, m.text)
+            }
+        })
+        .collect();
+    format!(
+        r#"The interactive elements in this screenshot are outlined, each with a numbered label at its top-left corner:
+{}
+
+Which numbered element is the one described as "{}"?
+Respond in JSON format:
+{{"mark": <number>, "confidence": <0.0-1.0>}}
+
+If the element is visible but not outlined, respond with:
+{{"mark": null}}
+
+Only respond with the JSON, no other text."#,
+        legend.join("\n"),
+        target
+    )
+}
+
+/// Script outlining the interactive elements in the viewport, returning
+/// their marks as a JSON string.
+fn mark_script() -> String {
+    format!(
+        r#"(() => {{
+    const selector = 'a[href], button, input:not([type=hidden]), select, textarea, summary, '
+        + '[role=button], [role=link], [role=checkbox], [role=radio], [role=menuitem], '
+        + '[role=tab], [role=option], [role=switch], [role=combobox], [role=textbox], '
+        + '[contenteditable=""], [contenteditable=true], [onclick], [tabindex]:not([tabindex="-1"])';
+    const found = [];
+    const visit = (root) => {{
+        for (const el of root.querySelectorAll('*')) {{
+            if (el.matches(selector)) found.push(el);
+            if (el.shadowRoot) visit(el.shadowRoot);
+        }}
+    }};
+    visit(document);
+
+    // Innermost element at a point, looking into shadow roots
+    const hitAt = (x, y) => {{
+        let hit = document.elementFromPoint(x, y);
+        while (hit && hit.shadowRoot) {{
+            const inner = hit.shadowRoot.elementFromPoint(x, y);
+            if (!inner || inner === hit) break;
+            hit = inner;
+        }}
+        return hit;
+    }};
+
+    document.getElementById('{id}')?.remove();
+    const overlay = document.createElement('div');
+    overlay.id = '{id}';
+    const colors = ['#e6194b', '#3cb44b', '#4363d8', '#f58231', '#911eb4', '#008080', '#9a6324'];
+    const marks = [];
+    for (const el of found) {{
+        if (marks.length >= {max}) break;
+        const rect = el.getBoundingClientRect();
+        if (rect.width < 1 || rect.height < 1 || rect.bottom < 0 || rect.right < 0
+            || rect.top > innerHeight || rect.left > innerWidth) continue;
+        const style = getComputedStyle(el);
+        if (style.visibility === 'hidden' || style.opacity === '0') continue;
+        const x = Math.min(Math.max(rect.left + rect.width / 2, 0), innerWidth - 1);
+        const y = Math.min(Math.max(rect.top + rect.height / 2, 0), innerHeight - 1);
+        const hit = hitAt(x, y);
+        if (hit && hit !== el && !el.contains(hit) && !hit.contains(el)) continue;
+
+        const number = marks.length + 1;
+        const color = colors[number % colors.length];
+        const box = document.createElement('div');
+        box.style.cssText = 'position:fixed;pointer-events:none;z-index:2147483646;box-sizing:border-box;'
+            + `left:${{rect.left}}px;top:${{rect.top}}px;width:${{rect.width}}px;height:${{rect.height}}px;`
+            + `border:2px solid ${{color}};`;
+        const label = document.createElement('span');
+        label.textContent = number;
+        label.style.cssText = 'position:absolute;left:-2px;top:-2px;padding:0 3px;color:#fff;'
+            + `background:${{color}};font:bold 12px/16px monospace;`;
+        box.appendChild(label);
+        overlay.appendChild(box);
+
+        const text = (el.innerText || el.value || el.getAttribute('aria-label')
+            || el.getAttribute('placeholder') || el.getAttribute('title') || '').trim();
+        marks.push({{
+            number,
+            x: rect.left,
+            y: rect.top,
+            width: rect.width,
+            height: rect.height,
+            tag: el.tagName.toLowerCase(),
+            text: text.replace(/\s+/g, ' ').slice(0, 40),
+        }});
+    }}
+    if (marks.length) document.documentElement.appendChild(overlay);
+    return JSON.stringify(marks);
+}})()"#,
+        id = OVERLAY_ID,
+        max = MAX_MARKS
+    )
+}
+
+/// Script removing the marks.
+fn remove_marks_script() -> String {
+    format!("document.getElementById('{}')?.remove()", OVERLAY_ID)
+}
@@ -2,10 +2,12 @@
 //!
 //! These tools use vision LLM capabilities to interact with web pages
 //! based on natural language descriptions rather than CSS selectors.
+//! Click and fill targets are grounded with set-of-marks (see [`marks`]).
 
 mod click;
 mod extract;
 mod fill;
+mod marks;
 mod vision_provider;
 
 pub use click::*;
@@ -1,3 +1,4 @@
+use super::marks::{marks_prompt, parse_mark_choice, Mark};
 use super::*;
 
 #[test]
@@ -94,3 +95,49 @@ fn test_ai_extract_params_with_format() {
     let params: AiExtractParams = serde_json::from_str(json).unwrap();
     assert_eq!(params.format, "list");
 }
+
+fn mark(number: u32, text: &str) -> Mark {
+    Mark {
+        number,
+        x: 10.0,
+        y: 20.0,
+        width: 100.0,
+        height: 31.0,
+        tag: "button".to_string(),
+        text: text.to_string(),
+    }
+}
+
+#[test]
+fn test_mark_coordinates() {
+    let coords = mark(1, "Sign in").coordinates(0.9);
+    assert_eq!((coords.x, coords.y), (60, 36));
+    assert_eq!(coords.width, Some(100));
+    assert_eq!(coords.height, Some(31));
+}
+
+#[test]
+fn test_parse_mark_choice() {
+    let choice = parse_mark_choice(r#"{"mark": 7, "confidence": 0.9}"#).unwrap();
+    assert_eq!(choice.mark, Some(7));
+    assert!((choice.confidence - 0.9).abs() < 0.01);
+
+    let choice = parse_mark_choice("```json\n{\"mark\": 3}\n```").unwrap();
+    assert_eq!(choice.mark, Some(3));
+
+    let choice = parse_mark_choice("The login button is mark #12.").unwrap();
+    assert_eq!(choice.mark, Some(12));
+
+    let choice = parse_mark_choice(r#"{"mark": null}"#).unwrap();
+    assert_eq!(choice.mark, None);
+
+    assert!(parse_mark_choice("I cannot tell").is_err());
+}
+
+#[test]
+fn test_marks_prompt() {
+    let prompt = marks_prompt("login button", &[mark(1, "Sign in"), mark(2, "")]);
+    assert!(prompt.contains("1: <button> \"Sign in\""));
+    assert!(prompt.contains("2: <button>\n"));
+    assert!(prompt.contains("\"login button\""));
+}
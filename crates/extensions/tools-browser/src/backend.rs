@@ -0,0 +1,150 @@
+//! Browser backends.
+//!
+//! The basic page tools (open, navigate, click, type, screenshot, ...) run
+//! on any [`BrowserBackend`]: [`BrowserManager`] drives Chrome over CDP,
+//! [`FirefoxManager`](crate::bidi::FirefoxManager) drives Firefox over
+//! WebDriver BiDi. Tools for tabs, downloads, network capture, cookies,
+//! storage and the DOM need CDP and take a [`BrowserManager`].
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::manager::{BrowserError, BrowserManager};
+
+/// A browser the basic page tools can drive.
+#[async_trait]
+pub trait BrowserBackend: Send + Sync {
+    /// Open a page in a named profile, the default one if `None`, and
+    /// navigate it to `url`. Returns its page id.
+    async fn new_page_in(&self, url: &str, profile: Option<&str>) -> Result<String, BrowserError>;
+
+    /// Close a page.
+    async fn close_page(&self, page_id: &str) -> Result<(), BrowserError>;
+
+    /// Ids of the open pages.
+    async fn list_pages(&self) -> Vec<String>;
+
+    /// Navigate a page to `url` and wait for it to load.
+    async fn navigate(&self, page_id: &str, url: &str) -> Result<(), BrowserError>;
+
+    /// Go back in a page's history.
+    async fn go_back(&self, page_id: &str) -> Result<(), BrowserError>;
+
+    /// Go forward in a page's history.
+    async fn go_forward(&self, page_id: &str) -> Result<(), BrowserError>;
+
+    /// Reload a page.
+    async fn reload(&self, page_id: &str) -> Result<(), BrowserError>;
+
+    /// Current URL of a page.
+    async fn get_url(&self, page_id: &str) -> Result<String, BrowserError>;
+
+    /// Click the element matching a CSS selector.
+    async fn click_selector(&self, page_id: &str, selector: &str) -> Result<(), BrowserError>;
+
+    /// Set the value of the input matching a CSS selector.
+    async fn fill(&self, page_id: &str, selector: &str, value: &str) -> Result<(), BrowserError>;
+
+    /// Press and release a key, named as in `KeyboardEvent.key`.
+    async fn press_key(&self, page_id: &str, key: &str) -> Result<(), BrowserError>;
+
+    /// Scroll a page by `x`, `y` pixels.
+    async fn scroll(&self, page_id: &str, x: f64, y: f64) -> Result<(), BrowserError>;
+
+    /// Evaluate JavaScript in a page and return its result as JSON.
+    async fn evaluate(&self, page_id: &str, script: &str) -> Result<Value, BrowserError>;
+
+    /// Wait for an element matching a CSS selector to appear, for 30
+    /// seconds unless `timeout_ms` is given.
+    async fn wait_for_selector(
+        &self,
+        page_id: &str,
+        selector: &str,
+        timeout_ms: Option<u32>,
+    ) -> Result<(), BrowserError>;
+
+    /// Screenshot of a page as base64 JPEG, with privacy masks applied.
+    async fn screenshot(&self, page_id: &str, full_page: bool) -> Result<String, BrowserError>;
+
+    /// HTML of a page.
+    async fn get_content(&self, page_id: &str) -> Result<String, BrowserError>;
+
+    /// Close the browser, and quit it if the backend launched it.
+    async fn shutdown(&self) -> Result<(), BrowserError>;
+}
+
+#[async_trait]
+impl BrowserBackend for BrowserManager {
+    async fn new_page_in(&self, url: &str, profile: Option<&str>) -> Result<String, BrowserError> {
+        BrowserManager::new_page_in(self, url, profile).await
+    }
+
+    async fn close_page(&self, page_id: &str) -> Result<(), BrowserError> {
+        BrowserManager::close_page(self, page_id).await
+    }
+
+    async fn list_pages(&self) -> Vec<String> {
+        BrowserManager::list_pages(self).await
+    }
+
+    async fn navigate(&self, page_id: &str, url: &str) -> Result<(), BrowserError> {
+        BrowserManager::navigate(self, page_id, url).await
+    }
+
+    async fn go_back(&self, page_id: &str) -> Result<(), BrowserError> {
+        BrowserManager::go_back(self, page_id).await
+    }
+
+    async fn go_forward(&self, page_id: &str) -> Result<(), BrowserError> {
+        BrowserManager::go_forward(self, page_id).await
+    }
+
+    async fn reload(&self, page_id: &str) -> Result<(), BrowserError> {
+        BrowserManager::reload(self, page_id).await
+    }
+
+    async fn get_url(&self, page_id: &str) -> Result<String, BrowserError> {
+        BrowserManager::get_url(self, page_id).await
+    }
+
+    async fn click_selector(&self, page_id: &str, selector: &str) -> Result<(), BrowserError> {
+        BrowserManager::click_selector(self, page_id, selector).await
+    }
+
+    async fn fill(&self, page_id: &str, selector: &str, value: &str) -> Result<(), BrowserError> {
+        BrowserManager::fill(self, page_id, selector, value).await
+    }
+
+    async fn press_key(&self, page_id: &str, key: &str) -> Result<(), BrowserError> {
+        BrowserManager::press_key(self, page_id, key).await
+    }
+
+    async fn scroll(&self, page_id: &str, x: f64, y: f64) -> Result<(), BrowserError> {
+        BrowserManager::scroll(self, page_id, x, y).await
+    }
+
+    async fn evaluate(&self, page_id: &str, script: &str) -> Result<Value, BrowserError> {
+        BrowserManager::evaluate(self, page_id, script).await
+    }
+
+    async fn wait_for_selector(
+        &self,
+        page_id: &str,
+        selector: &str,
+        timeout_ms: Option<u32>,
+    ) -> Result<(), BrowserError> {
+        BrowserManager::wait_for_selector(self, page_id, selector, timeout_ms).await
+    }
+
+    async fn screenshot(&self, page_id: &str, full_page: bool) -> Result<String, BrowserError> {
+        BrowserManager::screenshot(self, page_id, full_page).await
+    }
+
+    async fn get_content(&self, page_id: &str) -> Result<String, BrowserError> {
+        BrowserManager::get_content(self, page_id).await
+    }
+
+    async fn shutdown(&self) -> Result<(), BrowserError> {
+        self.shutdown_chrome().await
+    }
+}
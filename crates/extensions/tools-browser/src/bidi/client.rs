@@ -0,0 +1,182 @@
+//! WebDriver BiDi WebSocket client.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+
+use futures::stream::{SplitSink, SplitStream};
+use futures::{SinkExt, StreamExt};
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use tokio::net::TcpStream;
+use tokio::sync::oneshot;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+use tracing::{debug, error, trace, warn};
+
+use super::error::BidiError;
+use super::protocol::{BidiCommand, BidiMessage};
+
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+type WsSink = SplitSink<WsStream, Message>;
+type WsSource = SplitStream<WsStream>;
+
+type Pending = Arc<Mutex<HashMap<u64, oneshot::Sender<Result<Value, BidiError>>>>>;
+
+/// BiDi client for a browser's remote agent.
+///
+/// Holds one BiDi session, started on connect.
+pub struct BidiClient {
+    /// WebSocket sender.
+    ws_tx: tokio::sync::Mutex<WsSink>,
+    /// Command ID counter.
+    request_id: AtomicU64,
+    /// Commands waiting for their result.
+    pending: Pending,
+    /// Whether the WebSocket is still open.
+    connected: Arc<AtomicBool>,
+    /// Background task handle.
+    _recv_task: tokio::task::JoinHandle<()>,
+}
+
+impl BidiClient {
+    /// Connect to a remote agent and start a session.
+    ///
+    /// # Arguments
+    ///
+    /// * `ws_url` - BiDi endpoint (e.g., "ws://127.0.0.1:9222/session")
+    pub async fn connect(ws_url: &str) -> Result<Self, BidiError> {
+        let (ws_stream, _) = tokio_tungstenite::connect_async(ws_url)
+            .await
+            .map_err(|e| BidiError::ConnectionFailed(format!("{}: {}", ws_url, e)))?;
+
+        let (ws_sink, ws_source) = ws_stream.split();
+        let pending: Pending = Arc::new(Mutex::new(HashMap::new()));
+        let connected = Arc::new(AtomicBool::new(true));
+
+        let recv_task = {
+            let pending = pending.clone();
+            let connected = connected.clone();
+            tokio::spawn(async move {
+                Self::receive_loop(ws_source, pending.clone()).await;
+                // Fail the commands still waiting instead of letting them time out
+                connected.store(false, Ordering::SeqCst);
+                pending.lock().clear();
+            })
+        };
+
+        let client = Self {
+            ws_tx: tokio::sync::Mutex::new(ws_sink),
+            request_id: AtomicU64::new(1),
+            pending,
+            connected,
+            _recv_task: recv_task,
+        };
+
+        let session = client
+            .call("session.new", json!({ "capabilities": {} }))
+            .await?;
+        debug!(
+            "BiDi session {} started with {} {}",
+            session["sessionId"].as_str().unwrap_or_default(),
+            session["capabilities"]["browserName"]
+                .as_str()
+                .unwrap_or("browser"),
+            session["capabilities"]["browserVersion"]
+                .as_str()
+                .unwrap_or_default()
+        );
+        Ok(client)
+    }
+
+    /// WebSocket receive loop.
+    async fn receive_loop(mut ws_source: WsSource, pending: Pending) {
+        while let Some(msg) = ws_source.next().await {
+            match msg {
+                Ok(Message::Text(text)) => {
+                    trace!("BiDi recv: {}", text);
+                    match serde_json::from_str::<BidiMessage>(&text) {
+                        Ok(BidiMessage::Success { id, result }) => {
+                            if let Some(tx) = pending.lock().remove(&id) {
+                                let _ = tx.send(Ok(result));
+                            }
+                        }
+                        Ok(BidiMessage::Error { id, error, message }) => {
+                            let tx = id.and_then(|id| pending.lock().remove(&id));
+                            match tx {
+                                Some(tx) => {
+                                    let _ = tx.send(Err(BidiError::Protocol { error, message }));
+                                }
+                                None => warn!("BiDi error: {}: {}", error, message),
+                            }
+                        }
+                        // No events are subscribed to
+                        Ok(BidiMessage::Event { method, .. }) => {
+                            trace!("Ignoring BiDi event {}", method);
+                        }
+                        Err(e) => {
+                            warn!("Failed to parse BiDi message: {}", e);
+                        }
+                    }
+                }
+                Ok(Message::Close(_)) => {
+                    debug!("WebSocket closed");
+                    break;
+                }
+                Err(e) => {
+                    error!("WebSocket error: {}", e);
+                    break;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Send a BiDi command and wait for its result.
+    pub async fn call(&self, method: &str, params: Value) -> Result<Value, BidiError> {
+        let id = self.request_id.fetch_add(1, Ordering::SeqCst);
+
+        let command = BidiCommand {
+            id,
+            method: method.to_string(),
+            params,
+        };
+
+        let json = serde_json::to_string(&command)?;
+        trace!("BiDi send: {}", json);
+
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().insert(id, tx);
+
+        {
+            let mut ws = self.ws_tx.lock().await;
+            ws.send(Message::Text(json.into())).await?;
+        }
+
+        match tokio::time::timeout(std::time::Duration::from_secs(30), rx).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(_)) => Err(BidiError::SessionClosed),
+            Err(_) => {
+                self.pending.lock().remove(&id);
+                Err(BidiError::Timeout(format!("Command {} timed out", method)))
+            }
+        }
+    }
+
+    /// Whether the connection to the browser is still open.
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::SeqCst)
+    }
+
+    /// End the session, letting another client start one.
+    pub async fn end(&self) -> Result<(), BidiError> {
+        self.call("session.end", json!({})).await?;
+        Ok(())
+    }
+}
+
+impl Drop for BidiClient {
+    fn drop(&mut self) {
+        self._recv_task.abort();
+    }
+}
@@ -0,0 +1,41 @@
+//! WebDriver BiDi error types.
+
+use thiserror::Error;
+
+/// BiDi client errors.
+#[derive(Debug, Error)]
+pub enum BidiError {
+    /// Failed to connect to Firefox.
+    #[error("Connection failed: {0}")]
+    ConnectionFailed(String),
+
+    /// WebSocket error.
+    #[error("WebSocket error: {0}")]
+    WebSocket(String),
+
+    /// Error returned for a command, e.g. `no such frame`.
+    #[error("BiDi error: {error}: {message}")]
+    Protocol { error: String, message: String },
+
+    /// Serialization error.
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    /// Timeout.
+    #[error("Timeout: {0}")]
+    Timeout(String),
+
+    /// Session closed.
+    #[error("Session closed")]
+    SessionClosed,
+
+    /// Invalid response.
+    #[error("Invalid response: {0}")]
+    InvalidResponse(String),
+}
+
+impl From<tokio_tungstenite::tungstenite::Error> for BidiError {
+    fn from(e: tokio_tungstenite::tungstenite::Error) -> Self {
+        BidiError::WebSocket(e.to_string())
+    }
+}
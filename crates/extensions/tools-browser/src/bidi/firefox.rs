@@ -0,0 +1,619 @@
+//! Firefox manager: launches Firefox and drives its pages over BiDi.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use tokio::process::{Child, Command};
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+use crate::backend::BrowserBackend;
+use crate::manager::{
+    mask_overlay_script, remove_overlay_script, BrowserError, BrowserManagerConfig,
+};
+
+use super::client::BidiClient;
+use super::value::{script_result, webdriver_key};
+
+/// Manages a Firefox instance and its pages.
+///
+/// Firefox is launched on first use with its remote agent on the
+/// configured debug port. Pages are top-level browsing contexts (tabs).
+/// Named profiles, and the CDP-only tools, need Chrome.
+pub struct FirefoxManager {
+    config: BrowserManagerConfig,
+    client: RwLock<Option<Arc<BidiClient>>>,
+    /// Browsing context of each page.
+    pages: RwLock<HashMap<String, String>>,
+    page_counter: AtomicU64,
+    /// Firefox process handle (if we launched it).
+    firefox_process: RwLock<Option<Child>>,
+}
+
+impl FirefoxManager {
+    /// Create a new Firefox manager.
+    pub fn new(config: BrowserManagerConfig) -> Self {
+        Self {
+            config,
+            client: RwLock::new(None),
+            pages: RwLock::new(HashMap::new()),
+            page_counter: AtomicU64::new(0),
+            firefox_process: RwLock::new(None),
+        }
+    }
+
+    /// Find Firefox executable path.
+    pub fn find_firefox() -> Option<PathBuf> {
+        #[cfg(target_os = "macos")]
+        let paths: &[&str] = &[
+            "/Applications/Firefox.app/Contents/MacOS/firefox",
+            "/Applications/Firefox Developer Edition.app/Contents/MacOS/firefox",
+            "/Applications/Firefox Nightly.app/Contents/MacOS/firefox",
+        ];
+
+        #[cfg(target_os = "linux")]
+        let paths: &[&str] = &[
+            "/usr/bin/firefox",
+            "/usr/bin/firefox-esr",
+            "/usr/lib/firefox/firefox",
+            "/snap/bin/firefox",
+        ];
+
+        #[cfg(target_os = "windows")]
+        let paths: &[&str] = &[
+            r"C:\Program Files\Mozilla Firefox\firefox.exe",
+            r"C:\Program Files (x86)\Mozilla Firefox\firefox.exe",
+        ];
+
+        #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+        let paths: &[&str] = &[];
+
+        paths.iter().map(PathBuf::from).find(|p| p.exists())
+    }
+
+    /// BiDi endpoint of Firefox's remote agent.
+    fn ws_url(&self) -> String {
+        format!("ws://127.0.0.1:{}/session", self.config.debug_port)
+    }
+
+    /// Launch Firefox with its remote agent enabled.
+    async fn launch_firefox(&self) -> Result<Child, BrowserError> {
+        let firefox_path = Self::find_firefox().ok_or(BrowserError::FirefoxNotFound)?;
+        let profile_dir = self.config.get_firefox_profile_dir();
+        let download_dir = self.config.get_download_dir();
+
+        if let Err(e) = std::fs::create_dir_all(&profile_dir) {
+            warn!("Failed to create profile directory: {}", e);
+        }
+        if let Err(e) = std::fs::create_dir_all(&download_dir) {
+            warn!("Failed to create download directory: {}", e);
+        }
+        // Firefox applies user.js over the profile's prefs at every start
+        std::fs::write(profile_dir.join("user.js"), user_prefs(&download_dir))
+            .map_err(|e| BrowserError::LaunchFailed(format!("cannot write user.js: {}", e)))?;
+
+        info!(
+            "Launching Firefox with profile at: {}",
+            profile_dir.display()
+        );
+
+        let mut cmd = Command::new(&firefox_path);
+        cmd.arg(format!("--remote-debugging-port={}", self.config.debug_port))
+            .arg("--profile")
+            .arg(&profile_dir)
+            .arg("--no-remote")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            // The manager owns the browser it launches
+            .kill_on_drop(true);
+
+        if self.config.headless {
+            cmd.arg("--headless");
+        }
+
+        let child = cmd
+            .spawn()
+            .map_err(|e| BrowserError::LaunchFailed(e.to_string()))?;
+
+        info!("Firefox launched with PID: {:?}", child.id());
+        Ok(child)
+    }
+
+    /// Connect to Firefox, launching it if necessary.
+    pub async fn connect(&self) -> Result<(), BrowserError> {
+        if self.client.read().await.is_some() {
+            return Ok(());
+        }
+
+        let ws_url = self.ws_url();
+        let client = match BidiClient::connect(&ws_url).await {
+            Ok(client) => {
+                info!("Firefox already running on port {}", self.config.debug_port);
+                client
+            }
+            Err(_) => {
+                info!(
+                    "Firefox not running on port {}, launching...",
+                    self.config.debug_port
+                );
+                let child = self.launch_firefox().await?;
+                *self.firefox_process.write().await = Some(child);
+
+                // A new profile makes the first start slower than Chrome's
+                let mut attempts = 0;
+                let max_attempts = 50;
+                loop {
+                    tokio::time::sleep(Duration::from_millis(200)).await;
+                    match BidiClient::connect(&ws_url).await {
+                        Ok(client) => break client,
+                        Err(_) if attempts < max_attempts => attempts += 1,
+                        Err(e) => {
+                            return Err(BrowserError::LaunchFailed(format!(
+                                "Firefox failed to start within timeout: {}",
+                                e
+                            )))
+                        }
+                    }
+                }
+            }
+        };
+        *self.client.write().await = Some(Arc::new(client));
+
+        info!("Connected to Firefox at {}", ws_url);
+        Ok(())
+    }
+
+    /// Ensure Firefox is connected before use.
+    ///
+    /// Relaunches Firefox if the connection to it was lost; the pages open
+    /// before are gone.
+    pub async fn ensure_connected(&self) -> Result<(), BrowserError> {
+        let lost = self
+            .client
+            .read()
+            .await
+            .as_ref()
+            .is_some_and(|client| !client.is_connected());
+        if lost {
+            warn!("Lost the connection to Firefox, relaunching it");
+            self.pages.write().await.clear();
+            let _ = self.client.write().await.take();
+            if let Some(mut child) = self.firefox_process.write().await.take() {
+                let _ = child.kill().await;
+            }
+        }
+        self.connect().await
+    }
+
+    /// Get the BiDi client.
+    async fn client(&self) -> Result<Arc<BidiClient>, BrowserError> {
+        self.client
+            .read()
+            .await
+            .clone()
+            .ok_or(BrowserError::NotConnected)
+    }
+
+    /// Send a BiDi command.
+    async fn call(&self, method: &str, params: Value) -> Result<Value, BrowserError> {
+        Ok(self.client().await?.call(method, params).await?)
+    }
+
+    /// Browsing context of a page.
+    async fn context(&self, page_id: &str) -> Result<String, BrowserError> {
+        self.pages
+            .read()
+            .await
+            .get(page_id)
+            .cloned()
+            .ok_or_else(|| BrowserError::PageNotFound(page_id.to_string()))
+    }
+
+    /// Reference to the first element matching `selector`, if any.
+    async fn locate(&self, context: &str, selector: &str) -> Result<Option<Value>, BrowserError> {
+        let result = self
+            .call(
+                "browsingContext.locateNodes",
+                json!({
+                    "context": context,
+                    "locator": { "type": "css", "value": selector },
+                    "maxNodeCount": 1,
+                }),
+            )
+            .await?;
+        Ok(result["nodes"][0]["sharedId"]
+            .as_str()
+            .map(|id| json!({ "sharedId": id })))
+    }
+
+    /// Reference to the first element matching `selector`.
+    async fn element(&self, context: &str, selector: &str) -> Result<Value, BrowserError> {
+        self.locate(context, selector)
+            .await?
+            .ok_or_else(|| BrowserError::ElementNotFound(selector.to_string()))
+    }
+
+    /// Evaluate an expression in a browsing context.
+    async fn evaluate_in(&self, context: &str, script: &str) -> Result<Value, BrowserError> {
+        let result = self
+            .call(
+                "script.evaluate",
+                json!({
+                    "expression": script,
+                    "target": { "context": context },
+                    "awaitPromise": true,
+                    "resultOwnership": "none",
+                }),
+            )
+            .await?;
+        script_result(&result).map_err(|e| BrowserError::ActionFailed(format!("JS error: {}", e)))
+    }
+
+    /// Call a function in a browsing context with BiDi local values as
+    /// arguments.
+    async fn call_function(
+        &self,
+        context: &str,
+        function: &str,
+        arguments: Value,
+    ) -> Result<Value, BrowserError> {
+        let result = self
+            .call(
+                "script.callFunction",
+                json!({
+                    "functionDeclaration": function,
+                    "target": { "context": context },
+                    "arguments": arguments,
+                    "awaitPromise": true,
+                    "resultOwnership": "none",
+                }),
+            )
+            .await?;
+        script_result(&result).map_err(|e| BrowserError::ActionFailed(format!("JS error: {}", e)))
+    }
+
+    /// Perform input actions in a browsing context.
+    async fn perform_actions(&self, context: &str, actions: Value) -> Result<(), BrowserError> {
+        self.call(
+            "input.performActions",
+            json!({ "context": context, "actions": actions }),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Navigate a browsing context and wait for it to load.
+    async fn navigate_context(&self, context: &str, url: &str) -> Result<(), BrowserError> {
+        self.call(
+            "browsingContext.navigate",
+            json!({ "context": context, "url": url, "wait": "complete" }),
+        )
+        .await
+        .map_err(|e| BrowserError::NavigationFailed(format!("{}: {}", url, e)))?;
+        Ok(())
+    }
+
+    /// Move through a page's history.
+    async fn traverse_history(&self, page_id: &str, delta: i32) -> Result<(), BrowserError> {
+        let context = self.context(page_id).await?;
+        self.call(
+            "browsingContext.traverseHistory",
+            json!({ "context": context, "delta": delta }),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Capture the screenshot of a browsing context.
+    async fn capture(&self, context: &str, full_page: bool) -> Result<String, BrowserError> {
+        let origin = if full_page { "document" } else { "viewport" };
+        let result = self
+            .call(
+                "browsingContext.captureScreenshot",
+                json!({
+                    "context": context,
+                    "origin": origin,
+                    "format": { "type": "image/jpeg", "quality": 0.6 },
+                }),
+            )
+            .await
+            .map_err(|e| BrowserError::ScreenshotFailed(e.to_string()))?;
+        result["data"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| BrowserError::ScreenshotFailed("no image data".to_string()))
+    }
+}
+
+#[async_trait]
+impl BrowserBackend for FirefoxManager {
+    async fn new_page_in(&self, url: &str, profile: Option<&str>) -> Result<String, BrowserError> {
+        if let Some(profile) = profile {
+            return Err(BrowserError::ActionFailed(format!(
+                "Profile {} needs the Chrome engine",
+                profile
+            )));
+        }
+        self.ensure_connected().await?;
+
+        let result = self
+            .call("browsingContext.create", json!({ "type": "tab" }))
+            .await?;
+        let context = result["context"]
+            .as_str()
+            .ok_or_else(|| BrowserError::ActionFailed("Firefox opened no tab".to_string()))?
+            .to_string();
+
+        let opened = async {
+            self.call(
+                "browsingContext.setViewport",
+                json!({
+                    "context": context,
+                    "viewport": {
+                        "width": self.config.viewport_width,
+                        "height": self.config.viewport_height,
+                    },
+                }),
+            )
+            .await?;
+            self.navigate_context(&context, url).await
+        }
+        .await;
+        if let Err(e) = opened {
+            let _ = self
+                .call("browsingContext.close", json!({ "context": context }))
+                .await;
+            return Err(e);
+        }
+
+        let page_id = format!(
+            "page_{}",
+            self.page_counter.fetch_add(1, Ordering::SeqCst) + 1
+        );
+        self.pages.write().await.insert(page_id.clone(), context);
+
+        debug!("Created page {}: {}", page_id, url);
+        Ok(page_id)
+    }
+
+    async fn close_page(&self, page_id: &str) -> Result<(), BrowserError> {
+        let context = self.pages.write().await.remove(page_id);
+        if let Some(context) = context {
+            self.call("browsingContext.close", json!({ "context": context }))
+                .await?;
+        }
+        debug!("Closed page {}", page_id);
+        Ok(())
+    }
+
+    async fn list_pages(&self) -> Vec<String> {
+        self.pages.read().await.keys().cloned().collect()
+    }
+
+    async fn navigate(&self, page_id: &str, url: &str) -> Result<(), BrowserError> {
+        let context = self.context(page_id).await?;
+        self.navigate_context(&context, url).await?;
+        debug!("Navigated {} to {}", page_id, url);
+        Ok(())
+    }
+
+    async fn go_back(&self, page_id: &str) -> Result<(), BrowserError> {
+        self.traverse_history(page_id, -1).await
+    }
+
+    async fn go_forward(&self, page_id: &str) -> Result<(), BrowserError> {
+        self.traverse_history(page_id, 1).await
+    }
+
+    async fn reload(&self, page_id: &str) -> Result<(), BrowserError> {
+        let context = self.context(page_id).await?;
+        self.call(
+            "browsingContext.reload",
+            json!({ "context": context, "wait": "complete" }),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn get_url(&self, page_id: &str) -> Result<String, BrowserError> {
+        let context = self.context(page_id).await?;
+        let result = self
+            .call(
+                "browsingContext.getTree",
+                json!({ "root": context, "maxDepth": 0 }),
+            )
+            .await?;
+        result["contexts"][0]["url"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| BrowserError::PageNotFound(page_id.to_string()))
+    }
+
+    async fn click_selector(&self, page_id: &str, selector: &str) -> Result<(), BrowserError> {
+        let context = self.context(page_id).await?;
+        let element = self.element(&context, selector).await?;
+        // Pointer moves to an element fail when it is out of view
+        self.call_function(
+            &context,
+            "el => el.scrollIntoView({block: 'center', inline: 'center'})",
+            json!([element]),
+        )
+        .await?;
+        self.perform_actions(
+            &context,
+            json!([{
+                "type": "pointer",
+                "id": "mouse",
+                "parameters": { "pointerType": "mouse" },
+                "actions": [
+                    {
+                        "type": "pointerMove",
+                        "x": 0,
+                        "y": 0,
+                        "origin": { "type": "element", "element": element },
+                    },
+                    { "type": "pointerDown", "button": 0 },
+                    { "type": "pointerUp", "button": 0 },
+                ],
+            }]),
+        )
+        .await
+    }
+
+    async fn fill(&self, page_id: &str, selector: &str, value: &str) -> Result<(), BrowserError> {
+        let context = self.context(page_id).await?;
+        let element = self.element(&context, selector).await?;
+        self.call_function(
+            &context,
+            "(el, value) => { el.focus(); el.value = value; \
+             el.dispatchEvent(new Event('input', {bubbles: true})); \
+             el.dispatchEvent(new Event('change', {bubbles: true})); }",
+            json!([element, { "type": "string", "value": value }]),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn press_key(&self, page_id: &str, key: &str) -> Result<(), BrowserError> {
+        let context = self.context(page_id).await?;
+        let value = webdriver_key(key)
+            .ok_or_else(|| BrowserError::ActionFailed(format!("Unknown key: {}", key)))?;
+        self.perform_actions(
+            &context,
+            json!([{
+                "type": "key",
+                "id": "keyboard",
+                "actions": [
+                    { "type": "keyDown", "value": value },
+                    { "type": "keyUp", "value": value },
+                ],
+            }]),
+        )
+        .await
+    }
+
+    async fn scroll(&self, page_id: &str, x: f64, y: f64) -> Result<(), BrowserError> {
+        let context = self.context(page_id).await?;
+        self.perform_actions(
+            &context,
+            json!([{
+                "type": "wheel",
+                "id": "wheel",
+                "actions": [{
+                    "type": "scroll",
+                    "x": self.config.viewport_width / 2,
+                    "y": self.config.viewport_height / 2,
+                    "deltaX": x as i64,
+                    "deltaY": y as i64,
+                }],
+            }]),
+        )
+        .await
+    }
+
+    async fn evaluate(&self, page_id: &str, script: &str) -> Result<Value, BrowserError> {
+        let context = self.context(page_id).await?;
+        self.evaluate_in(&context, script).await
+    }
+
+    async fn wait_for_selector(
+        &self,
+        page_id: &str,
+        selector: &str,
+        timeout_ms: Option<u32>,
+    ) -> Result<(), BrowserError> {
+        let context = self.context(page_id).await?;
+        let deadline = Instant::now() + Duration::from_millis(timeout_ms.unwrap_or(30000) as u64);
+        loop {
+            if self.locate(&context, selector).await?.is_some() {
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                return Err(BrowserError::ActionFailed(format!(
+                    "Timeout: waiting for selector {}",
+                    selector
+                )));
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+
+    async fn screenshot(&self, page_id: &str, full_page: bool) -> Result<String, BrowserError> {
+        let context = self.context(page_id).await?;
+        let masks = &self.config.privacy_masks;
+        if masks.is_empty() {
+            return self.capture(&context, full_page).await;
+        }
+
+        let title = self.evaluate_in(&context, "document.title").await?;
+        let Some(overlay) = mask_overlay_script(masks, title.as_str().unwrap_or_default()) else {
+            return self.capture(&context, full_page).await;
+        };
+        self.evaluate_in(&context, &overlay).await?;
+        let screenshot = self.capture(&context, full_page).await;
+        if let Err(e) = self.evaluate_in(&context, &remove_overlay_script()).await {
+            warn!(
+                "Failed to remove the privacy mask overlay of {}: {}",
+                page_id, e
+            );
+        }
+        screenshot
+    }
+
+    async fn get_content(&self, page_id: &str) -> Result<String, BrowserError> {
+        let html = self
+            .evaluate(page_id, "document.documentElement.outerHTML")
+            .await?;
+        Ok(html.as_str().unwrap_or_default().to_string())
+    }
+
+    async fn shutdown(&self) -> Result<(), BrowserError> {
+        self.pages.write().await.clear();
+        if let Some(client) = self.client.write().await.take() {
+            if let Err(e) = client.end().await {
+                debug!("Failed to end the BiDi session: {}", e);
+            }
+        }
+        if let Some(mut child) = self.firefox_process.write().await.take() {
+            info!("Shutting down Firefox...");
+            let _ = child.kill().await;
+        }
+        Ok(())
+    }
+}
+
+/// Prefs written to the profile's `user.js`: no first-run pages, and
+/// downloads saved to `download_dir` without asking.
+pub(super) fn user_prefs(download_dir: &Path) -> String {
+    let prefs = [
+        ("browser.shell.checkDefaultBrowser", json!(false)),
+        ("browser.startup.homepage_override.mstone", json!("ignore")),
+        ("browser.aboutwelcome.enabled", json!(false)),
+        ("datareporting.policy.dataSubmissionEnabled", json!(false)),
+        ("toolkit.telemetry.reportingpolicy.firstRun", json!(false)),
+        ("browser.download.folderList", json!(2)),
+        (
+            "browser.download.dir",
+            json!(download_dir.display().to_string()),
+        ),
+        ("browser.download.useDownloadDir", json!(true)),
+        (
+            "browser.download.always_ask_before_handling_new_types",
+            json!(false),
+        ),
+    ];
+    prefs
+        .iter()
+        .map(|(name, value)| format!("user_pref(\"{}\", {});\n", name, value))
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "firefox_tests.rs"]
+mod tests;
@@ -0,0 +1,42 @@
+use super::*;
+
+#[test]
+fn test_user_prefs() {
+    let prefs = user_prefs(Path::new("/tmp/downloads"));
+    assert!(prefs.contains("user_pref(\"browser.download.dir\", \"/tmp/downloads\");\n"));
+    assert!(prefs.contains("user_pref(\"browser.download.folderList\", 2);\n"));
+    assert!(prefs.contains("user_pref(\"browser.shell.checkDefaultBrowser\", false);\n"));
+}
+
+#[test]
+fn test_ws_url() {
+    let manager = FirefoxManager::new(BrowserManagerConfig {
+        debug_port: 9333,
+        ..Default::default()
+    });
+    assert_eq!(manager.ws_url(), "ws://127.0.0.1:9333/session");
+}
+
+#[tokio::test]
+async fn test_named_profiles_need_chrome() {
+    let manager = FirefoxManager::new(BrowserManagerConfig {
+        profiles: vec!["work".to_string()],
+        ..Default::default()
+    });
+    let err = manager
+        .new_page_in("https://example.com", Some("work"))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Chrome"));
+    assert!(manager.list_pages().await.is_empty());
+}
+
+#[tokio::test]
+async fn test_unknown_page() {
+    let manager = FirefoxManager::new(BrowserManagerConfig::default());
+    assert!(matches!(
+        manager.navigate("page_1", "https://example.com").await,
+        Err(BrowserError::PageNotFound(_))
+    ));
+    assert!(manager.close_page("page_1").await.is_ok());
+}
@@ -0,0 +1,30 @@
+//! WebDriver BiDi client and Firefox backend.
+//!
+//! Firefox's remote agent speaks WebDriver BiDi over WebSocket, without
+//! geckodriver in between: commands are `{id, method, params}` messages
+//! answered by `success` or `error` messages with the same id.
+//! [`FirefoxManager`] launches Firefox with the agent enabled and drives
+//! the basic page tools through [`BrowserBackend`](crate::backend::BrowserBackend).
+//!
+//! ## Usage
+//!
+//! ```bash
+//! firefox --remote-debugging-port=9222
+//! ```
+//!
+//! ```rust,ignore
+//! let client = BidiClient::connect("ws://127.0.0.1:9222/session").await?;
+//! let tab = client.call("browsingContext.create", json!({"type": "tab"})).await?;
+//! ```
+
+mod client;
+mod error;
+mod firefox;
+mod protocol;
+mod value;
+
+pub use client::BidiClient;
+pub use error::BidiError;
+pub use firefox::FirefoxManager;
+pub use protocol::{BidiCommand, BidiMessage};
+pub use value::{remote_value_to_json, webdriver_key};
@@ -0,0 +1,33 @@
+//! WebDriver BiDi message types.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// BiDi command message.
+#[derive(Debug, Serialize)]
+pub struct BidiCommand {
+    pub id: u64,
+    pub method: String,
+    pub params: Value,
+}
+
+/// Message received from the browser.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum BidiMessage {
+    /// Result of a command.
+    Success { id: u64, result: Value },
+    /// Failure of a command; `id` is missing when the command could not be
+    /// parsed.
+    Error {
+        id: Option<u64>,
+        error: String,
+        message: String,
+    },
+    /// Event of a subscribed module.
+    Event { method: String, params: Value },
+}
+
+#[cfg(test)]
+#[path = "protocol_tests.rs"]
+mod tests;
@@ -0,0 +1,42 @@
+use super::*;
+
+#[test]
+fn test_command_serialization() {
+    let command = BidiCommand {
+        id: 3,
+        method: "browsingContext.create".to_string(),
+        params: serde_json::json!({"type": "tab"}),
+    };
+    let json: Value = serde_json::to_value(&command).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({"id": 3, "method": "browsingContext.create", "params": {"type": "tab"}})
+    );
+}
+
+#[test]
+fn test_message_parsing() {
+    let message: BidiMessage =
+        serde_json::from_str(r#"{"type": "success", "id": 1, "result": {"context": "abc"}}"#)
+            .unwrap();
+    assert!(
+        matches!(message, BidiMessage::Success { id: 1, result } if result["context"] == "abc")
+    );
+
+    let message: BidiMessage = serde_json::from_str(
+        r#"{"type": "error", "id": 2, "error": "no such frame", "message": "gone", "stacktrace": ""}"#,
+    )
+    .unwrap();
+    assert!(matches!(
+        message,
+        BidiMessage::Error { id: Some(2), ref error, .. } if error == "no such frame"
+    ));
+
+    let message: BidiMessage = serde_json::from_str(
+        r#"{"type": "event", "method": "browsingContext.load", "params": {}}"#,
+    )
+    .unwrap();
+    assert!(
+        matches!(message, BidiMessage::Event { ref method, .. } if method == "browsingContext.load")
+    );
+}
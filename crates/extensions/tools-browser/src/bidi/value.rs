@@ -0,0 +1,105 @@
+//! Conversions between BiDi values and what the page tools use.
+
+use serde_json::{Map, Value};
+
+/// JSON for a BiDi remote value, as CDP's `returnByValue` would give it.
+///
+/// Values JSON cannot hold (NaN, Infinity) become `null`, and nodes,
+/// windows and other objects passed by reference become `{}`.
+pub fn remote_value_to_json(value: &Value) -> Value {
+    let inner = &value["value"];
+    match value["type"].as_str() {
+        Some("string") | Some("boolean") | Some("bigint") | Some("date") => inner.clone(),
+        Some("number") => match inner {
+            Value::String(special) if special == "-0" => Value::from(0),
+            Value::String(_) => Value::Null,
+            number => number.clone(),
+        },
+        Some("array") | Some("set") => Value::Array(
+            inner
+                .as_array()
+                .map(|items| items.iter().map(remote_value_to_json).collect())
+                .unwrap_or_default(),
+        ),
+        Some("object") | Some("map") => {
+            let mut object = Map::new();
+            for entry in inner.as_array().into_iter().flatten() {
+                let key = match &entry[0] {
+                    Value::String(key) => key.clone(),
+                    key => match remote_value_to_json(key) {
+                        Value::String(key) => key,
+                        key => key.to_string(),
+                    },
+                };
+                object.insert(key, remote_value_to_json(&entry[1]));
+            }
+            Value::Object(object)
+        }
+        Some("regexp") => Value::String(format!(
+            "/{}/{}",
+            inner["pattern"].as_str().unwrap_or_default(),
+            inner["flags"].as_str().unwrap_or_default()
+        )),
+        Some("undefined") | Some("null") | None => Value::Null,
+        Some(_) => Value::Object(Map::new()),
+    }
+}
+
+/// Value of a `script.evaluate` or `script.callFunction` result, or the
+/// message of the exception it threw.
+pub fn script_result(result: &Value) -> Result<Value, String> {
+    if result["type"] == "exception" {
+        let details = &result["exceptionDetails"];
+        return Err(details["text"]
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| details.to_string()));
+    }
+    Ok(remote_value_to_json(&result["result"]))
+}
+
+/// Value of a key action for a key named as in `KeyboardEvent.key`:
+/// the character itself, or the WebDriver code point of a special key.
+pub fn webdriver_key(key: &str) -> Option<String> {
+    let mut chars = key.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(c.to_string());
+    }
+    let code = match key {
+        "Cancel" => '\u{E001}',
+        "Help" => '\u{E002}',
+        "Backspace" => '\u{E003}',
+        "Tab" => '\u{E004}',
+        "Clear" => '\u{E005}',
+        "Enter" => '\u{E007}',
+        "Shift" => '\u{E008}',
+        "Control" => '\u{E009}',
+        "Alt" => '\u{E00A}',
+        "Pause" => '\u{E00B}',
+        "Escape" => '\u{E00C}',
+        "Space" => ' ',
+        "PageUp" => '\u{E00E}',
+        "PageDown" => '\u{E00F}',
+        "End" => '\u{E010}',
+        "Home" => '\u{E011}',
+        "ArrowLeft" => '\u{E012}',
+        "ArrowUp" => '\u{E013}',
+        "ArrowRight" => '\u{E014}',
+        "ArrowDown" => '\u{E015}',
+        "Insert" => '\u{E016}',
+        "Delete" => '\u{E017}',
+        "Meta" => '\u{E03D}',
+        function => {
+            let n: u32 = function.strip_prefix('F')?.parse().ok()?;
+            if !(1..=12).contains(&n) {
+                return None;
+            }
+            char::from_u32(0xE031 + n - 1)?
+        }
+    };
+    Some(code.to_string())
+}
+
+#[cfg(test)]
+#[path = "value_tests.rs"]
+mod tests;
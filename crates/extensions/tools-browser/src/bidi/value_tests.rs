@@ -0,0 +1,70 @@
+use super::*;
+use serde_json::json;
+
+#[test]
+fn test_primitive_values() {
+    assert_eq!(
+        remote_value_to_json(&json!({"type": "string", "value": "hi"})),
+        json!("hi")
+    );
+    assert_eq!(
+        remote_value_to_json(&json!({"type": "number", "value": 1.5})),
+        json!(1.5)
+    );
+    assert_eq!(
+        remote_value_to_json(&json!({"type": "number", "value": "NaN"})),
+        Value::Null
+    );
+    assert_eq!(
+        remote_value_to_json(&json!({"type": "number", "value": "-0"})),
+        json!(0)
+    );
+    assert_eq!(
+        remote_value_to_json(&json!({"type": "undefined"})),
+        Value::Null
+    );
+}
+
+#[test]
+fn test_nested_values() {
+    let value = json!({
+        "type": "object",
+        "value": [
+            ["items", {"type": "array", "value": [
+                {"type": "boolean", "value": true},
+                {"type": "null"}
+            ]}],
+            ["node", {"type": "node", "sharedId": "abc"}],
+            [{"type": "number", "value": 2}, {"type": "regexp", "value": {"pattern": "a+", "flags": "g"}}]
+        ]
+    });
+    assert_eq!(
+        remote_value_to_json(&value),
+        json!({"items": [true, null], "node": {}, "2": "/a+/g"})
+    );
+}
+
+#[test]
+fn test_script_result() {
+    let result = json!({"type": "success", "result": {"type": "string", "value": "ok"}});
+    assert_eq!(script_result(&result).unwrap(), json!("ok"));
+
+    let result = json!({
+        "type": "exception",
+        "exceptionDetails": {"text": "ReferenceError: x is not defined"}
+    });
+    assert_eq!(
+        script_result(&result).unwrap_err(),
+        "ReferenceError: x is not defined"
+    );
+}
+
+#[test]
+fn test_webdriver_key() {
+    assert_eq!(webdriver_key("a").as_deref(), Some("a"));
+    assert_eq!(webdriver_key("Enter").as_deref(), Some("\u{E007}"));
+    assert_eq!(webdriver_key("ArrowDown").as_deref(), Some("\u{E015}"));
+    assert_eq!(webdriver_key("F12").as_deref(), Some("\u{E03C}"));
+    assert_eq!(webdriver_key("F13"), None);
+    assert_eq!(webdriver_key("Hyper"), None);
+}
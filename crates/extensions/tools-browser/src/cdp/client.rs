@@ -1,7 +1,8 @@
 //! CDP WebSocket client.
 
-use std::collections::HashMap;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 
 use futures::stream::{SplitSink, SplitStream};
@@ -14,7 +15,9 @@ use tokio_tungstenite::tungstenite::Message;
 use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
 use tracing::{debug, error, trace, warn};
 
+use super::download::Downloads;
 use super::error::CdpError;
+use super::network::NetworkCapture;
 use super::protocol::{BrowserVersion, CdpRequest, CdpResponse, PageInfo, TargetInfo};
 use super::session::PageSession;
 
@@ -25,6 +28,8 @@ type WsSource = SplitStream<WsStream>;
 /// Pending request waiting for response.
 pub(crate) struct PendingRequest {
     pub tx: oneshot::Sender<Result<Value, CdpError>>,
+    /// Session the request was sent to, if any.
+    pub session_id: Option<String>,
 }
 
 /// CDP client for browser automation.
@@ -44,6 +49,14 @@ pub struct CdpClient {
     /// Event handlers by session ID.
     #[allow(clippy::type_complexity)]
     event_handlers: Arc<RwLock<HashMap<String, mpsc::UnboundedSender<CdpResponse>>>>,
+    /// Whether the WebSocket is still open.
+    connected: Arc<AtomicBool>,
+    /// Sessions whose renderer crashed.
+    crashed: Arc<Mutex<HashSet<String>>>,
+    /// Downloads the browser started.
+    downloads: Arc<Downloads>,
+    /// Network captures by session ID.
+    captures: Arc<Mutex<HashMap<String, Arc<NetworkCapture>>>>,
     /// Background task handle.
     _recv_task: tokio::task::JoinHandle<()>,
 }
@@ -89,12 +102,33 @@ impl CdpClient {
         let event_handlers: Arc<RwLock<HashMap<String, mpsc::UnboundedSender<CdpResponse>>>> =
             Arc::new(RwLock::new(HashMap::new()));
 
+        let connected = Arc::new(AtomicBool::new(true));
+        let crashed = Arc::new(Mutex::new(HashSet::new()));
+        let downloads = Arc::new(Downloads::default());
+        let captures: Arc<Mutex<HashMap<String, Arc<NetworkCapture>>>> =
+            Arc::new(Mutex::new(HashMap::new()));
+
         // Start receive task
         let recv_task = {
             let pending = pending.clone();
             let event_handlers = event_handlers.clone();
+            let connected = connected.clone();
+            let crashed = crashed.clone();
+            let downloads = downloads.clone();
+            let captures = captures.clone();
             tokio::spawn(async move {
-                Self::receive_loop(ws_source, pending, event_handlers).await;
+                Self::receive_loop(
+                    ws_source,
+                    pending.clone(),
+                    event_handlers,
+                    crashed,
+                    downloads,
+                    captures,
+                )
+                .await;
+                // Fail the requests still waiting instead of letting them time out
+                connected.store(false, Ordering::SeqCst);
+                pending.lock().clear();
             })
         };
 
@@ -107,6 +141,10 @@ impl CdpClient {
             request_id: Arc::new(AtomicU64::new(1)),
             pending,
             event_handlers,
+            connected,
+            crashed,
+            downloads,
+            captures,
             _recv_task: recv_task,
         })
     }
@@ -116,6 +154,9 @@ impl CdpClient {
         mut ws_source: WsSource,
         pending: Arc<Mutex<HashMap<u64, PendingRequest>>>,
         event_handlers: Arc<RwLock<HashMap<String, mpsc::UnboundedSender<CdpResponse>>>>,
+        crashed: Arc<Mutex<HashSet<String>>>,
+        downloads: Arc<Downloads>,
+        captures: Arc<Mutex<HashMap<String, Arc<NetworkCapture>>>>,
     ) {
         while let Some(msg) = ws_source.next().await {
             match msg {
@@ -140,6 +181,25 @@ impl CdpClient {
                             } else if resp.method.is_some() {
                                 // It's an event
                                 let session_id = resp.session_id.clone().unwrap_or_default();
+                                if resp.method.as_deref() == Some("Inspector.targetCrashed") {
+                                    warn!("Renderer of session {} crashed", session_id);
+                                    pending.lock().retain(|_, req| {
+                                        req.session_id.as_deref() != Some(session_id.as_str())
+                                    });
+                                    crashed.lock().insert(session_id.clone());
+                                }
+                                if let (Some(method), Some(params)) =
+                                    (&resp.method, &resp.params)
+                                {
+                                    if session_id.is_empty() {
+                                        downloads.handle_event(method, params);
+                                    } else if method.starts_with("Network.") {
+                                        let capture = captures.lock().get(&session_id).cloned();
+                                        if let Some(capture) = capture {
+                                            capture.handle_event(method, params);
+                                        }
+                                    }
+                                }
                                 let handlers = event_handlers.read().await;
                                 if let Some(tx) = handlers.get(&session_id) {
                                     let _ = tx.send(resp);
@@ -185,7 +245,13 @@ impl CdpClient {
 
         // Create response channel
         let (tx, rx) = oneshot::channel();
-        self.pending.lock().insert(id, PendingRequest { tx });
+        self.pending.lock().insert(
+            id,
+            PendingRequest {
+                tx,
+                session_id: session_id.map(|s| s.to_string()),
+            },
+        );
 
         // Send request
         {
@@ -209,6 +275,61 @@ impl CdpClient {
         &self.browser_ws_url
     }
 
+    /// Whether the connection to the browser is still open.
+    ///
+    /// Turns false when Chrome exits or crashes, or closes the WebSocket.
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::SeqCst)
+    }
+
+    /// Whether the renderer of a page session crashed.
+    pub fn is_crashed(&self, session_id: &str) -> bool {
+        self.crashed.lock().contains(session_id)
+    }
+
+    /// Downloads the browser started.
+    pub fn downloads(&self) -> Arc<Downloads> {
+        self.downloads.clone()
+    }
+
+    /// Save downloads to `dir` instead of the browser's default folder,
+    /// and report their progress. Without `context_id` this applies to the
+    /// default browser context.
+    pub async fn enable_downloads(
+        &self,
+        dir: &Path,
+        context_id: Option<&str>,
+    ) -> Result<(), CdpError> {
+        let mut params = json!({
+            "behavior": "allowAndName",
+            "downloadPath": dir.to_string_lossy(),
+            "eventsEnabled": true
+        });
+        if let Some(context_id) = context_id {
+            params["browserContextId"] = json!(context_id);
+        }
+        self.call("Browser.setDownloadBehavior", Some(params), None)
+            .await?;
+        self.downloads.set_dir(dir.to_path_buf());
+        Ok(())
+    }
+
+    /// Record the network requests of a page session, replacing any
+    /// capture already running on it.
+    pub fn start_capture(&self, session_id: &str, capture: Arc<NetworkCapture>) {
+        self.captures.lock().insert(session_id.to_string(), capture);
+    }
+
+    /// Stop recording a page session's requests, returning the capture.
+    pub fn stop_capture(&self, session_id: &str) -> Option<Arc<NetworkCapture>> {
+        self.captures.lock().remove(session_id)
+    }
+
+    /// The capture running on a page session.
+    pub fn capture(&self, session_id: &str) -> Option<Arc<NetworkCapture>> {
+        self.captures.lock().get(session_id).cloned()
+    }
+
     // ========================================================================
     // Target Management
     // ========================================================================
@@ -311,6 +432,82 @@ impl CdpClient {
         Ok(session)
     }
 
+    /// Create a browser context: a set of pages with their own cookies,
+    /// storage and cache, like an incognito window.
+    pub async fn create_browser_context(&self) -> Result<String, CdpError> {
+        let result = self
+            .call(
+                "Target.createBrowserContext",
+                Some(json!({ "disposeOnDetach": false })),
+                None,
+            )
+            .await?;
+        result["browserContextId"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| CdpError::InvalidResponse("Missing browserContextId".to_string()))
+    }
+
+    /// Create a page in a browser context and attach to it.
+    pub async fn new_context_page(
+        &self,
+        url: &str,
+        context_id: &str,
+    ) -> Result<PageSession, CdpError> {
+        let result = self
+            .call(
+                "Target.createTarget",
+                Some(json!({
+                    "url": url,
+                    "browserContextId": context_id
+                })),
+                None,
+            )
+            .await?;
+        let target_id = result["targetId"]
+            .as_str()
+            .ok_or_else(|| CdpError::InvalidResponse("Missing targetId".to_string()))?;
+        debug!("Created page {} in context {}: {}", target_id, context_id, url);
+        self.attach_page(target_id).await
+    }
+
+    /// Cookies of every site in a browser context, the default one if
+    /// `context_id` is `None`.
+    pub async fn get_context_cookies(
+        &self,
+        context_id: Option<&str>,
+    ) -> Result<Vec<Value>, CdpError> {
+        let params = context_id.map(|id| json!({ "browserContextId": id }));
+        let result = self.call("Storage.getCookies", params, None).await?;
+        Ok(result["cookies"].as_array().cloned().unwrap_or_default())
+    }
+
+    /// Remove every cookie of a browser context, the default one if
+    /// `context_id` is `None`.
+    pub async fn clear_context_cookies(&self, context_id: Option<&str>) -> Result<(), CdpError> {
+        let params = context_id.map(|id| json!({ "browserContextId": id }));
+        self.call("Storage.clearCookies", params, None).await?;
+        Ok(())
+    }
+
+    /// Add cookies to a browser context.
+    pub async fn set_context_cookies(
+        &self,
+        context_id: &str,
+        cookies: &[Value],
+    ) -> Result<(), CdpError> {
+        self.call(
+            "Storage.setCookies",
+            Some(json!({
+                "cookies": cookies,
+                "browserContextId": context_id
+            })),
+            None,
+        )
+        .await?;
+        Ok(())
+    }
+
     /// Get all targets.
     pub async fn get_targets(&self) -> Result<Vec<TargetInfo>, CdpError> {
         let result = self.call("Target.getTargets", None, None).await?;
@@ -318,6 +515,17 @@ impl CdpClient {
         Ok(targets)
     }
 
+    /// Bring a page/target to the front.
+    pub async fn activate_page(&self, target_id: &str) -> Result<(), CdpError> {
+        self.call(
+            "Target.activateTarget",
+            Some(json!({"targetId": target_id})),
+            None,
+        )
+        .await?;
+        Ok(())
+    }
+
     /// Close a page/target.
     pub async fn close_page(&self, target_id: &str) -> Result<(), CdpError> {
         self.call(
@@ -0,0 +1,200 @@
+//! Download tracking from `Browser.downloadWillBegin` and
+//! `Browser.downloadProgress` events.
+//!
+//! Chrome saves each download under its guid in the download directory
+//! (`allowAndName` behavior); completed files are renamed to the name the
+//! page suggested, made unique within the directory.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use serde_json::Value;
+use tokio::sync::watch;
+use tracing::{debug, warn};
+
+/// State of a download.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadState {
+    InProgress,
+    Completed,
+    Canceled,
+}
+
+/// A download the browser started.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Download {
+    pub guid: String,
+    pub url: String,
+    pub suggested_filename: String,
+    pub received_bytes: u64,
+    /// 0 when the server did not say.
+    pub total_bytes: u64,
+    pub state: DownloadState,
+    /// Where the file was saved, once completed.
+    pub path: Option<PathBuf>,
+}
+
+/// Downloads of a browser connection.
+pub struct Downloads {
+    dir: Mutex<Option<PathBuf>>,
+    entries: Mutex<Vec<Download>>,
+    /// Downloads already handed out by [`Downloads::next_finished`].
+    claimed: Mutex<HashSet<String>>,
+    /// Bumped on every change, to wake waiters.
+    changes: watch::Sender<u64>,
+}
+
+impl Default for Downloads {
+    fn default() -> Self {
+        Self {
+            dir: Mutex::new(None),
+            entries: Mutex::new(Vec::new()),
+            claimed: Mutex::new(HashSet::new()),
+            changes: watch::channel(0).0,
+        }
+    }
+}
+
+impl Downloads {
+    /// Directory downloads are saved to, once downloads are enabled.
+    pub fn dir(&self) -> Option<PathBuf> {
+        self.dir.lock().clone()
+    }
+
+    pub(crate) fn set_dir(&self, dir: PathBuf) {
+        *self.dir.lock() = Some(dir);
+    }
+
+    /// Downloads in the order they began.
+    pub fn list(&self) -> Vec<Download> {
+        self.entries.lock().clone()
+    }
+
+    /// Receiver notified whenever a download begins or progresses.
+    pub fn subscribe(&self) -> watch::Receiver<u64> {
+        self.changes.subscribe()
+    }
+
+    /// The oldest download not yet handed out, once it finished.
+    ///
+    /// `Ok(None)` while it is in progress or none began; a canceled
+    /// download is handed out as an error.
+    pub fn next_finished(&self) -> Result<Option<Download>, String> {
+        let mut claimed = self.claimed.lock();
+        let entries = self.entries.lock();
+        let Some(download) = entries.iter().find(|d| !claimed.contains(&d.guid)) else {
+            return Ok(None);
+        };
+        match download.state {
+            DownloadState::InProgress => Ok(None),
+            DownloadState::Completed => {
+                claimed.insert(download.guid.clone());
+                Ok(Some(download.clone()))
+            }
+            DownloadState::Canceled => {
+                claimed.insert(download.guid.clone());
+                Err(format!(
+                    "Download of {} from {} was canceled",
+                    download.suggested_filename, download.url
+                ))
+            }
+        }
+    }
+
+    /// Apply a browser event, ignoring other than download events.
+    pub(crate) fn handle_event(&self, method: &str, params: &Value) {
+        let str_param = |name: &str| params[name].as_str().unwrap_or_default().to_string();
+        match method {
+            "Browser.downloadWillBegin" => {
+                let download = Download {
+                    guid: str_param("guid"),
+                    url: str_param("url"),
+                    suggested_filename: str_param("suggestedFilename"),
+                    received_bytes: 0,
+                    total_bytes: 0,
+                    state: DownloadState::InProgress,
+                    path: None,
+                };
+                debug!("Download {} began: {}", download.guid, download.url);
+                self.entries.lock().push(download);
+            }
+            "Browser.downloadProgress" => {
+                let guid = str_param("guid");
+                let dir = self.dir();
+                let mut entries = self.entries.lock();
+                let Some(download) = entries.iter_mut().find(|d| d.guid == guid) else {
+                    return;
+                };
+                download.received_bytes = params["receivedBytes"].as_f64().unwrap_or(0.0) as u64;
+                download.total_bytes = params["totalBytes"].as_f64().unwrap_or(0.0) as u64;
+                download.state = match params["state"].as_str() {
+                    Some("completed") => DownloadState::Completed,
+                    Some("canceled") => DownloadState::Canceled,
+                    _ => DownloadState::InProgress,
+                };
+                if download.state == DownloadState::Completed && download.path.is_none() {
+                    download.path = dir.map(|dir| save(&dir, download));
+                }
+            }
+            _ => return,
+        }
+        self.changes.send_modify(|n| *n += 1);
+    }
+}
+
+/// Move a completed download from its guid to its suggested name.
+fn save(dir: &Path, download: &Download) -> PathBuf {
+    let source = dir.join(&download.guid);
+    let target = unique_path(dir, &file_name(&download.suggested_filename));
+    match std::fs::rename(&source, &target) {
+        Ok(()) => target,
+        Err(e) => {
+            warn!("Failed to rename download {}: {}", source.display(), e);
+            source
+        }
+    }
+}
+
+/// A safe file name for a suggested one.
+pub(crate) fn file_name(suggested: &str) -> String {
+    let name: String = suggested
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .map(|c| if c.is_control() || ":*?\"<>|".contains(c) { '_' } else { c })
+        .collect();
+    let name = name.trim().trim_start_matches('.');
+    if name.is_empty() {
+        "download".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// `dir/name`, or `dir/stem (n).ext` when that exists.
+pub(crate) fn unique_path(dir: &Path, name: &str) -> PathBuf {
+    let path = dir.join(name);
+    if !path.exists() {
+        return path;
+    }
+    let (stem, extension) = match name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, format!(".{}", extension)),
+        _ => (name, String::new()),
+    };
+    let mut n = 1;
+    loop {
+        let path = dir.join(format!("{} ({}){}", stem, n, extension));
+        if !path.exists() {
+            return path;
+        }
+        n += 1;
+    }
+}
+
+#[cfg(test)]
+#[path = "download_tests.rs"]
+mod tests;
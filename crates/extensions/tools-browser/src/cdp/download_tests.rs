@@ -0,0 +1,91 @@
+use super::*;
+use serde_json::json;
+
+fn begin(downloads: &Downloads, guid: &str, name: &str) {
+    downloads.handle_event(
+        "Browser.downloadWillBegin",
+        &json!({"frameId": "f", "guid": guid, "url": "https://example.com/file", "suggestedFilename": name}),
+    );
+}
+
+fn progress(downloads: &Downloads, guid: &str, state: &str) {
+    downloads.handle_event(
+        "Browser.downloadProgress",
+        &json!({"guid": guid, "totalBytes": 10.0, "receivedBytes": 10.0, "state": state}),
+    );
+}
+
+#[test]
+fn test_file_name() {
+    assert_eq!(file_name("invoice.pdf"), "invoice.pdf");
+    assert_eq!(file_name("../../etc/passwd"), "passwd");
+    assert_eq!(file_name("C:\\temp\\a:b?.txt"), "a_b_.txt");
+    assert_eq!(file_name(".hidden"), "hidden");
+    assert_eq!(file_name(""), "download");
+}
+
+#[test]
+fn test_unique_path() {
+    let dir = tempfile::tempdir().unwrap();
+    assert_eq!(unique_path(dir.path(), "a.pdf"), dir.path().join("a.pdf"));
+    std::fs::write(dir.path().join("a.pdf"), "").unwrap();
+    std::fs::write(dir.path().join("a (1).pdf"), "").unwrap();
+    assert_eq!(unique_path(dir.path(), "a.pdf"), dir.path().join("a (2).pdf"));
+    std::fs::write(dir.path().join("README"), "").unwrap();
+    assert_eq!(unique_path(dir.path(), "README"), dir.path().join("README (1)"));
+}
+
+#[test]
+fn test_completed_download_is_renamed() {
+    let dir = tempfile::tempdir().unwrap();
+    let downloads = Downloads::default();
+    downloads.set_dir(dir.path().to_path_buf());
+    let mut changes = downloads.subscribe();
+
+    begin(&downloads, "g1", "invoice.pdf");
+    assert!(changes.has_changed().unwrap());
+    changes.mark_unchanged();
+    assert_eq!(downloads.next_finished(), Ok(None));
+
+    std::fs::write(dir.path().join("g1"), "%PDF").unwrap();
+    progress(&downloads, "g1", "completed");
+    assert!(changes.has_changed().unwrap());
+
+    let download = downloads.next_finished().unwrap().unwrap();
+    assert_eq!(download.state, DownloadState::Completed);
+    assert_eq!(download.received_bytes, 10);
+    let path = download.path.unwrap();
+    assert_eq!(path, dir.path().join("invoice.pdf"));
+    assert_eq!(std::fs::read_to_string(path).unwrap(), "%PDF");
+
+    // Handed out once
+    assert_eq!(downloads.next_finished(), Ok(None));
+}
+
+#[test]
+fn test_downloads_are_handed_out_in_order() {
+    let downloads = Downloads::default();
+    begin(&downloads, "g1", "a.csv");
+    begin(&downloads, "g2", "b.csv");
+    progress(&downloads, "g2", "completed");
+    // The first one is still running
+    assert_eq!(downloads.next_finished(), Ok(None));
+
+    progress(&downloads, "g1", "canceled");
+    assert!(downloads.next_finished().unwrap_err().contains("a.csv"));
+    let second = downloads.next_finished().unwrap().unwrap();
+    assert_eq!(second.guid, "g2");
+    // Without a download directory no file is moved
+    assert!(second.path.is_none());
+    assert_eq!(downloads.list().len(), 2);
+}
+
+#[test]
+fn test_other_events_are_ignored() {
+    let downloads = Downloads::default();
+    let changes = downloads.subscribe();
+    downloads.handle_event("Page.loadEventFired", &json!({}));
+    progress(&downloads, "unknown", "completed");
+    assert!(!changes.has_changed().unwrap());
+    assert!(downloads.list().is_empty());
+}
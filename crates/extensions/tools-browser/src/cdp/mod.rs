@@ -19,11 +19,15 @@
 //!    ```
 
 mod client;
+mod download;
 mod error;
+mod network;
 mod protocol;
 mod session;
 
 pub use client::CdpClient;
+pub use download::{Download, DownloadState, Downloads};
 pub use error::CdpError;
+pub use network::{to_har, CapturedRequest, NetworkCapture, DEFAULT_MAX_REQUESTS};
 pub use protocol::*;
 pub use session::PageSession;
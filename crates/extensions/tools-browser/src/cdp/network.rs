@@ -0,0 +1,331 @@
+//! Network capture from Network domain events, with HAR export.
+//!
+//! A capture records the requests of one page session whose URL matches
+//! its pattern, from `Network.requestWillBeSent` through
+//! `Network.responseReceived` to `Network.loadingFinished` or
+//! `Network.loadingFailed`. Redirects are recorded as separate requests.
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, SecondsFormat};
+use parking_lot::Mutex;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// Requests recorded per capture, at most.
+pub const DEFAULT_MAX_REQUESTS: usize = 1000;
+
+/// A request a page made.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CapturedRequest {
+    pub request_id: String,
+    pub url: String,
+    pub method: String,
+    /// Document, XHR, Fetch, Script, Image and so on.
+    pub resource_type: Option<String>,
+    pub request_headers: BTreeMap<String, String>,
+    pub post_data: Option<String>,
+    /// Unix time the request was sent, in seconds.
+    pub started_at: f64,
+    pub status: Option<u16>,
+    pub status_text: Option<String>,
+    pub response_headers: BTreeMap<String, String>,
+    pub mime_type: Option<String>,
+    pub protocol: Option<String>,
+    pub remote_address: Option<String>,
+    /// Bytes received, as transferred.
+    pub encoded_bytes: Option<u64>,
+    /// Time from sending to the last byte, in milliseconds.
+    pub duration_ms: Option<f64>,
+    /// Why the request failed, when it did.
+    pub error: Option<String>,
+    /// Response body, when asked for and textual.
+    pub response_body: Option<String>,
+    /// Monotonic time the request was sent, for durations.
+    #[serde(skip)]
+    timestamp: f64,
+}
+
+impl CapturedRequest {
+    /// Whether the request completed or failed.
+    pub fn is_finished(&self) -> bool {
+        self.duration_ms.is_some() || self.error.is_some()
+    }
+
+    /// Whether the response is text worth returning as a body.
+    pub fn has_text_body(&self) -> bool {
+        self.error.is_none()
+            && self.mime_type.as_deref().is_some_and(|mime| {
+                mime.starts_with("text/")
+                    || ["json", "javascript", "xml", "x-www-form-urlencoded"]
+                        .iter()
+                        .any(|kind| mime.contains(kind))
+            })
+    }
+}
+
+/// Requests recorded from a page session.
+pub struct NetworkCapture {
+    pattern: Option<Regex>,
+    max_requests: usize,
+    state: Mutex<CaptureState>,
+}
+
+#[derive(Default)]
+struct CaptureState {
+    requests: Vec<CapturedRequest>,
+    /// Latest request recorded for each CDP request id.
+    index: HashMap<String, usize>,
+    /// Requests left out for lack of room.
+    dropped: usize,
+}
+
+impl NetworkCapture {
+    /// A capture of requests whose URL matches `pattern`: a substring, or
+    /// a whole-URL pattern when it holds `*` wildcards.
+    pub fn new(pattern: Option<&str>, max_requests: usize) -> Result<Self, String> {
+        let pattern = pattern
+            .filter(|p| !p.is_empty())
+            .map(|p| {
+                let regex = if p.contains('*') {
+                    let parts: Vec<String> = p.split('*').map(regex::escape).collect();
+                    format!("^{}$", parts.join(".*"))
+                } else {
+                    regex::escape(p)
+                };
+                Regex::new(&regex).map_err(|e| format!("Invalid URL pattern '{}': {}", p, e))
+            })
+            .transpose()?;
+        Ok(Self {
+            pattern,
+            max_requests,
+            state: Mutex::new(CaptureState::default()),
+        })
+    }
+
+    /// Whether requests to `url` are recorded.
+    pub fn matches(&self, url: &str) -> bool {
+        self.pattern.as_ref().is_none_or(|p| p.is_match(url))
+    }
+
+    /// Recorded requests, in the order they were sent.
+    pub fn requests(&self) -> Vec<CapturedRequest> {
+        self.state.lock().requests.clone()
+    }
+
+    /// Requests left out because the capture was full.
+    pub fn dropped(&self) -> usize {
+        self.state.lock().dropped
+    }
+
+    /// Apply a Network domain event of the page.
+    pub(crate) fn handle_event(&self, method: &str, params: &Value) {
+        let Some(request_id) = params["requestId"].as_str() else {
+            return;
+        };
+        let mut guard = self.state.lock();
+        let state = &mut *guard;
+        match method {
+            "Network.requestWillBeSent" => {
+                // A redirect ends the previous request under this id
+                if let Some(&i) = state.index.get(request_id) {
+                    let request = &mut state.requests[i];
+                    if !params["redirectResponse"].is_null() {
+                        apply_response(request, &params["redirectResponse"]);
+                        finish(request, params["timestamp"].as_f64(), None);
+                    }
+                }
+                let url = params["request"]["url"].as_str().unwrap_or_default();
+                if !self.matches(url) {
+                    state.index.remove(request_id);
+                    return;
+                }
+                if state.requests.len() >= self.max_requests {
+                    state.dropped += 1;
+                    state.index.remove(request_id);
+                    return;
+                }
+                let request = CapturedRequest {
+                    request_id: request_id.to_string(),
+                    url: url.to_string(),
+                    method: str_value(&params["request"]["method"]).unwrap_or_default(),
+                    resource_type: str_value(&params["type"]),
+                    request_headers: headers(&params["request"]["headers"]),
+                    post_data: str_value(&params["request"]["postData"]),
+                    started_at: params["wallTime"].as_f64().unwrap_or_default(),
+                    timestamp: params["timestamp"].as_f64().unwrap_or_default(),
+                    ..Default::default()
+                };
+                let i = state.requests.len();
+                state.requests.push(request);
+                state.index.insert(request_id.to_string(), i);
+            }
+            "Network.responseReceived" => {
+                if let Some(&i) = state.index.get(request_id) {
+                    apply_response(&mut state.requests[i], &params["response"]);
+                }
+            }
+            "Network.loadingFinished" => {
+                if let Some(&i) = state.index.get(request_id) {
+                    let request = &mut state.requests[i];
+                    finish(request, params["timestamp"].as_f64(), None);
+                    if let Some(bytes) = params["encodedDataLength"].as_f64() {
+                        request.encoded_bytes = Some(bytes as u64);
+                    }
+                }
+            }
+            "Network.loadingFailed" => {
+                if let Some(&i) = state.index.get(request_id) {
+                    let error = str_value(&params["errorText"])
+                        .filter(|e| !e.is_empty())
+                        .unwrap_or_else(|| "failed".to_string());
+                    finish(
+                        &mut state.requests[i],
+                        params["timestamp"].as_f64(),
+                        Some(error),
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn str_value(value: &Value) -> Option<String> {
+    value.as_str().map(str::to_string)
+}
+
+fn headers(value: &Value) -> BTreeMap<String, String> {
+    value
+        .as_object()
+        .map(|headers| {
+            headers
+                .iter()
+                .map(|(name, value)| {
+                    let value = value
+                        .as_str()
+                        .map_or_else(|| value.to_string(), str::to_string);
+                    (name.clone(), value)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn apply_response(request: &mut CapturedRequest, response: &Value) {
+    request.status = response["status"].as_u64().map(|s| s as u16);
+    request.status_text = str_value(&response["statusText"]);
+    request.response_headers = headers(&response["headers"]);
+    request.mime_type = str_value(&response["mimeType"]);
+    request.protocol = str_value(&response["protocol"]);
+    request.remote_address = str_value(&response["remoteIPAddress"]);
+}
+
+fn finish(request: &mut CapturedRequest, timestamp: Option<f64>, error: Option<String>) {
+    if let Some(timestamp) = timestamp {
+        request.duration_ms = Some(((timestamp - request.timestamp) * 1000.0).max(0.0));
+    }
+    request.error = error;
+}
+
+/// Requests as an HTTP Archive (HAR 1.2) log.
+pub fn to_har(requests: &[CapturedRequest]) -> Value {
+    let entries: Vec<Value> = requests.iter().map(har_entry).collect();
+    json!({
+        "log": {
+            "version": "1.2",
+            "creator": {"name": "AutoHands", "version": env!("CARGO_PKG_VERSION")},
+            "pages": [],
+            "entries": entries,
+        }
+    })
+}
+
+fn har_entry(request: &CapturedRequest) -> Value {
+    let started = DateTime::from_timestamp_millis((request.started_at * 1000.0) as i64)
+        .unwrap_or_default()
+        .to_rfc3339_opts(SecondsFormat::Millis, true);
+    let time = request.duration_ms.unwrap_or(-1.0);
+    let http_version = request
+        .protocol
+        .clone()
+        .unwrap_or_else(|| "HTTP/1.1".to_string());
+    let query: Vec<Value> = url::Url::parse(&request.url)
+        .map(|url| {
+            url.query_pairs()
+                .map(|(name, value)| json!({"name": name, "value": value}))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut har_request = json!({
+        "method": request.method,
+        "url": request.url,
+        "httpVersion": http_version,
+        "cookies": [],
+        "headers": har_headers(&request.request_headers),
+        "queryString": query,
+        "headersSize": -1,
+        "bodySize": request.post_data.as_ref().map_or(0, |d| d.len() as i64),
+    });
+    if let Some(data) = &request.post_data {
+        let mime = header(&request.request_headers, "content-type").unwrap_or_default();
+        har_request["postData"] = json!({"mimeType": mime, "text": data});
+    }
+
+    let mut content = json!({
+        "size": request.response_body.as_ref().map_or(-1, |b| b.len() as i64),
+        "mimeType": request.mime_type.clone().unwrap_or_default(),
+    });
+    if let Some(body) = &request.response_body {
+        content["text"] = json!(body);
+    }
+    let mut entry = json!({
+        "startedDateTime": started,
+        "time": time,
+        "request": har_request,
+        "response": {
+            "status": request.status.unwrap_or(0),
+            "statusText": request.status_text.clone().unwrap_or_default(),
+            "httpVersion": http_version,
+            "cookies": [],
+            "headers": har_headers(&request.response_headers),
+            "content": content,
+            "redirectURL": header(&request.response_headers, "location").unwrap_or_default(),
+            "headersSize": -1,
+            "bodySize": request.encoded_bytes.map_or(-1, |b| b as i64),
+        },
+        "cache": {},
+        "timings": {"send": 0, "wait": time.max(0.0), "receive": 0},
+    });
+    if let Some(resource_type) = &request.resource_type {
+        entry["_resourceType"] = json!(resource_type.to_lowercase());
+    }
+    if let Some(error) = &request.error {
+        entry["_error"] = json!(error);
+    }
+    if let Some(address) = &request.remote_address {
+        entry["serverIPAddress"] = json!(address);
+    }
+    entry
+}
+
+fn har_headers(headers: &BTreeMap<String, String>) -> Vec<Value> {
+    headers
+        .iter()
+        .map(|(name, value)| json!({"name": name, "value": value}))
+        .collect()
+}
+
+/// A header's value, whatever the case of its name.
+fn header(headers: &BTreeMap<String, String>, name: &str) -> Option<String> {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.clone())
+}
+
+#[cfg(test)]
+#[path = "network_tests.rs"]
+mod tests;
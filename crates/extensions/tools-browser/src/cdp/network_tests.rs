@@ -0,0 +1,133 @@
+use super::*;
+
+fn send(capture: &NetworkCapture, id: &str, url: &str, timestamp: f64) {
+    capture.handle_event(
+        "Network.requestWillBeSent",
+        &json!({
+            "requestId": id,
+            "request": {"url": url, "method": "POST", "headers": {"Content-Type": "application/json"}, "postData": "{\"q\":1}"},
+            "timestamp": timestamp,
+            "wallTime": 1_767_225_600.5,
+            "type": "Fetch"
+        }),
+    );
+}
+
+fn respond(capture: &NetworkCapture, id: &str, status: u16) {
+    capture.handle_event(
+        "Network.responseReceived",
+        &json!({
+            "requestId": id,
+            "response": {
+                "status": status, "statusText": "OK", "mimeType": "application/json",
+                "headers": {"content-type": "application/json"}, "protocol": "h2",
+                "remoteIPAddress": "93.184.216.34"
+            }
+        }),
+    );
+}
+
+#[test]
+fn test_url_patterns() {
+    let capture = NetworkCapture::new(Some("/api/"), 10).unwrap();
+    assert!(capture.matches("https://example.com/api/items?page=2"));
+    assert!(!capture.matches("https://example.com/static/app.js"));
+
+    let capture = NetworkCapture::new(Some("https://*.example.com/*.json"), 10).unwrap();
+    assert!(capture.matches("https://cdn.example.com/data/items.json"));
+    assert!(!capture.matches("https://cdn.example.com/data/items.json?v=1"));
+
+    let capture = NetworkCapture::new(None, 10).unwrap();
+    assert!(capture.matches("anything"));
+}
+
+#[test]
+fn test_request_lifecycle() {
+    let capture = NetworkCapture::new(Some("/api/"), 10).unwrap();
+    send(
+        &capture,
+        "1",
+        "https://example.com/api/search?q=rust",
+        100.0,
+    );
+    send(&capture, "2", "https://example.com/logo.png", 100.0);
+    respond(&capture, "1", 200);
+    respond(&capture, "2", 200);
+    capture.handle_event(
+        "Network.loadingFinished",
+        &json!({"requestId": "1", "timestamp": 100.25, "encodedDataLength": 512.0}),
+    );
+
+    let requests = capture.requests();
+    assert_eq!(requests.len(), 1);
+    let request = &requests[0];
+    assert_eq!(request.method, "POST");
+    assert_eq!(request.resource_type.as_deref(), Some("Fetch"));
+    assert_eq!(request.post_data.as_deref(), Some("{\"q\":1}"));
+    assert_eq!(request.status, Some(200));
+    assert_eq!(request.encoded_bytes, Some(512));
+    assert_eq!(request.duration_ms, Some(250.0));
+    assert!(request.is_finished());
+    assert!(request.has_text_body());
+}
+
+#[test]
+fn test_failures_redirects_and_limit() {
+    let capture = NetworkCapture::new(None, 3).unwrap();
+    send(&capture, "1", "https://example.com/old", 1.0);
+    capture.handle_event(
+        "Network.requestWillBeSent",
+        &json!({
+            "requestId": "1",
+            "request": {"url": "https://example.com/new", "method": "GET", "headers": {}},
+            "redirectResponse": {"status": 301, "headers": {"Location": "/new"}},
+            "timestamp": 1.1,
+            "wallTime": 1.0
+        }),
+    );
+    capture.handle_event(
+        "Network.loadingFailed",
+        &json!({"requestId": "1", "timestamp": 1.5, "errorText": "net::ERR_CONNECTION_RESET"}),
+    );
+    send(&capture, "2", "https://example.com/a", 2.0);
+    send(&capture, "3", "https://example.com/b", 3.0);
+
+    let requests = capture.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[0].status, Some(301));
+    assert!(requests[0].error.is_none());
+    assert_eq!(requests[1].url, "https://example.com/new");
+    assert_eq!(
+        requests[1].error.as_deref(),
+        Some("net::ERR_CONNECTION_RESET")
+    );
+    assert!(!requests[1].has_text_body());
+    assert_eq!(capture.dropped(), 1);
+}
+
+#[test]
+fn test_to_har() {
+    let capture = NetworkCapture::new(None, 10).unwrap();
+    send(&capture, "1", "https://example.com/api?q=a%20b", 100.0);
+    respond(&capture, "1", 200);
+    capture.handle_event(
+        "Network.loadingFinished",
+        &json!({"requestId": "1", "timestamp": 100.1, "encodedDataLength": 20.0}),
+    );
+    let mut requests = capture.requests();
+    requests[0].response_body = Some("{\"ok\":true}".to_string());
+
+    let har = to_har(&requests);
+    assert_eq!(har["log"]["version"], "1.2");
+    let entry = &har["log"]["entries"][0];
+    assert_eq!(entry["startedDateTime"], "2026-01-01T00:00:00.500Z");
+    assert_eq!(entry["request"]["method"], "POST");
+    assert_eq!(entry["request"]["queryString"][0]["value"], "a b");
+    assert_eq!(entry["request"]["postData"]["mimeType"], "application/json");
+    assert_eq!(entry["response"]["status"], 200);
+    assert_eq!(entry["response"]["httpVersion"], "h2");
+    assert_eq!(entry["response"]["content"]["text"], "{\"ok\":true}");
+    assert_eq!(entry["response"]["bodySize"], 20);
+    assert_eq!(entry["serverIPAddress"], "93.184.216.34");
+    assert_eq!(entry["_resourceType"], "fetch");
+}
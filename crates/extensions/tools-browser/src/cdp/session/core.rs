@@ -76,7 +76,13 @@ impl PageSession {
         trace!("CDP session send: {}", json);
 
         let (tx, rx) = tokio::sync::oneshot::channel();
-        self.pending.lock().insert(id, PendingRequest { tx });
+        self.pending.lock().insert(
+            id,
+            PendingRequest {
+                tx,
+                session_id: Some(self.session_id.clone()),
+            },
+        );
 
         {
             let mut ws = self.ws_tx.lock().await;
@@ -100,6 +106,8 @@ impl PageSession {
         self.call("Runtime.enable", None).await?;
         self.call("Network.enable", None).await?;
         self.call("CSS.enable", None).await?;
+        // Reports renderer crashes as Inspector.targetCrashed
+        self.call("Inspector.enable", None).await?;
 
         debug!("Enabled CDP domains for session {}", self.session_id);
         Ok(())
@@ -0,0 +1,88 @@
+//! Changes to a page's interactive elements between two DOM scans.
+
+use serde::{Deserialize, Serialize};
+
+/// An interactive element in a DOM diff.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiffElement {
+    /// Stable element ID, kept for as long as the element is on the page.
+    pub id: String,
+
+    /// LLM-friendly description of the element.
+    pub description: String,
+
+    /// CSS selector for the element.
+    pub css_selector: String,
+}
+
+/// Interactive elements added, removed and changed since the previous scan.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DomDiff {
+    /// Page URL.
+    pub url: String,
+
+    /// Page title.
+    pub title: String,
+
+    /// Whether there was no previous scan of this document, so every
+    /// element is listed as added.
+    pub full: bool,
+
+    /// Elements not in the previous scan.
+    pub added: Vec<DiffElement>,
+
+    /// Elements of the previous scan no longer on the page.
+    pub removed: Vec<DiffElement>,
+
+    /// Elements whose description changed, with the new description.
+    pub changed: Vec<DiffElement>,
+}
+
+impl DomDiff {
+    /// Whether nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Generate LLM-friendly representation of the changes.
+    pub fn to_llm_string(&self) -> String {
+        let mut output = String::new();
+
+        output.push_str(&format!("Page: {}\n", self.title));
+        output.push_str(&format!("URL: {}\n\n", self.url));
+
+        if self.full {
+            output.push_str("Interactive Elements:\n");
+            for element in &self.added {
+                output.push_str(&format!("[{}] {}\n", element.id, element.description));
+            }
+            return output;
+        }
+
+        if self.is_empty() {
+            output.push_str("No changes since last scan\n");
+            return output;
+        }
+
+        output.push_str(&format!(
+            "Changes since last scan: {} added, {} removed, {} changed\n",
+            self.added.len(),
+            self.removed.len(),
+            self.changed.len()
+        ));
+        for (sign, elements) in [
+            ("+", &self.added),
+            ("-", &self.removed),
+            ("~", &self.changed),
+        ] {
+            for element in elements {
+                output.push_str(&format!(
+                    "{} [{}] {}\n",
+                    sign, element.id, element.description
+                ));
+            }
+        }
+
+        output
+    }
+}
@@ -91,9 +91,12 @@ impl EnhancedNode {
 
     /// Generate LLM-friendly description of this node.
     pub fn to_llm_string(&self, index: usize) -> String {
-        let mut parts = vec![];
+        format!("[{}] {}", index, self.describe())
+    }
 
-        parts.push(format!("[{}]", index));
+    /// Describe this node for an LLM, without an index.
+    pub fn describe(&self) -> String {
+        let mut parts = vec![];
 
         let type_str = if let Some(ref t) = self.attributes.r#type {
             format!("<{} type={}>", self.tag_name, t)
@@ -13,18 +13,105 @@
 //! 8. ARIA roles (button, link, checkbox, etc.)
 //! 9. Icon size heuristics
 //! 10. Cursor: pointer style
+//!
+//! ## Incremental Scans
+//!
+//! A processor remembers the interactive elements of the previous scan of a
+//! page, so a re-scan can return only the elements added, removed or changed,
+//! each with an ID that stays the same across scans.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
+use super::dom_diff::{DiffElement, DomDiff};
+use super::dom_node::EnhancedNode;
+use super::dom_tree::EnhancedNodeTree;
 use super::dom_types::NodeAttributes;
 
 /// DOM processor for merging CDP trees.
-pub struct DomProcessor;
+#[derive(Debug, Default)]
+pub struct DomProcessor {
+    /// URL of the previous scan, `None` before the first.
+    url: Option<String>,
+    /// Elements of the previous scan, by stable ID.
+    elements: BTreeMap<u64, DiffElement>,
+    /// Stable IDs of the previous scan's elements, by node key.
+    ids: HashMap<String, u64>,
+    /// Last stable ID given out.
+    last_id: u64,
+}
 
 impl DomProcessor {
     /// Create a new DOM processor.
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Compare a scan of the page with the previous one, giving its
+    /// interactive elements stable IDs.
+    ///
+    /// A scan of a different URL starts over, listing every element as added.
+    pub fn diff(&mut self, tree: &EnhancedNodeTree) -> DomDiff {
+        let full = self.url.as_deref() != Some(tree.url.as_str());
+        if full {
+            self.reset();
+            self.url = Some(tree.url.clone());
+        }
+
+        let mut nodes = tree.interactive_elements();
+        nodes.sort_by(|a, b| {
+            let a = (a.bounding_box.y, a.bounding_box.x);
+            let b = (b.bounding_box.y, b.bounding_box.x);
+            a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let mut diff = DomDiff {
+            url: tree.url.clone(),
+            title: tree.title.clone(),
+            full,
+            ..Default::default()
+        };
+        let mut previous = std::mem::take(&mut self.elements);
+        let mut ids = HashMap::new();
+        for node in nodes {
+            let key = node_key(node);
+            if ids.contains_key(&key) {
+                continue;
+            }
+            let id = match self.ids.get(&key) {
+                Some(&id) => id,
+                None => {
+                    self.last_id += 1;
+                    self.last_id
+                }
+            };
+            let element = DiffElement {
+                id: format!("e{}", id),
+                description: node.describe(),
+                css_selector: node.css_selector.clone(),
+            };
+            match previous.remove(&id) {
+                None => diff.added.push(element.clone()),
+                Some(old) if old.description != element.description => {
+                    diff.changed.push(element.clone())
+                }
+                Some(_) => {}
+            }
+            ids.insert(key, id);
+            self.elements.insert(id, element);
+        }
+        diff.removed = previous.into_values().collect();
+        self.ids = ids;
+
+        diff
+    }
+
+    /// Forget the previous scan, so the next one lists every element.
+    ///
+    /// Stable IDs are not reused.
+    pub fn reset(&mut self) {
+        self.url = None;
+        self.elements.clear();
+        self.ids.clear();
     }
 
     /// Calculate clickability score using 10-layer detection.
@@ -132,8 +219,11 @@ impl DomProcessor {
     }
 }
 
-impl Default for DomProcessor {
-    fn default() -> Self {
-        Self::new()
+/// Key identifying a node across scans of the same document.
+fn node_key(node: &EnhancedNode) -> String {
+    if node.backend_node_id > 0 {
+        format!("backend:{}", node.backend_node_id)
+    } else {
+        format!("css:{}", node.css_selector)
     }
 }
@@ -139,3 +139,93 @@ fn test_viewport_default() {
     assert_eq!(viewport.height, 720);
     assert_eq!(viewport.device_pixel_ratio, 1.0);
 }
+
+fn button(backend_node_id: i64, text: &str, y: f64) -> EnhancedNode {
+    EnhancedNode {
+        id: format!("node_{}", backend_node_id),
+        backend_node_id,
+        tag_name: "button".to_string(),
+        attributes: NodeAttributes::default(),
+        text_content: text.to_string(),
+        bounding_box: BoundingBox {
+            x: 0.0,
+            y,
+            width: 80.0,
+            height: 20.0,
+        },
+        is_visible: true,
+        is_in_viewport: true,
+        clickability_score: 0.9,
+        clickability_reasons: vec![],
+        paint_order: 1,
+        is_interactive: true,
+        is_focusable: true,
+        parent_id: None,
+        children: vec![],
+        xpath: String::new(),
+        css_selector: format!("#b{}", backend_node_id),
+        computed_styles: HashMap::new(),
+    }
+}
+
+fn tree(url: &str, nodes: Vec<EnhancedNode>) -> EnhancedNodeTree {
+    EnhancedNodeTree {
+        nodes: nodes.into_iter().map(|n| (n.id.clone(), n)).collect(),
+        url: url.to_string(),
+        title: "Test".to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_dom_diff() {
+    let mut processor = DomProcessor::new();
+
+    let diff = processor.diff(&tree(
+        "https://example.com",
+        vec![button(2, "Save", 50.0), button(1, "Menu", 10.0)],
+    ));
+    assert!(diff.full);
+    let ids: Vec<_> = diff.added.iter().map(|e| e.id.as_str()).collect();
+    assert_eq!(ids, ["e1", "e2"]);
+    assert_eq!(diff.added[0].description, "<button> \"Menu\" \u{2b24}");
+    assert!(diff.to_llm_string().contains("[e2] <button> \"Save\""));
+
+    let diff = processor.diff(&tree(
+        "https://example.com",
+        vec![button(2, "Saving", 50.0), button(3, "Cancel", 80.0)],
+    ));
+    assert!(!diff.full);
+    assert_eq!(diff.added.len(), 1);
+    assert_eq!(diff.added[0].id, "e3");
+    assert_eq!(diff.removed.len(), 1);
+    assert_eq!(diff.removed[0].id, "e1");
+    assert_eq!(diff.changed.len(), 1);
+    assert_eq!(diff.changed[0].id, "e2");
+    let output = diff.to_llm_string();
+    assert!(output.contains("1 added, 1 removed, 1 changed"));
+    assert!(output.contains("- [e1] <button> \"Menu\""));
+    assert!(output.contains("~ [e2] <button> \"Saving\""));
+
+    let diff = processor.diff(&tree(
+        "https://example.com",
+        vec![button(2, "Saving", 50.0), button(3, "Cancel", 80.0)],
+    ));
+    assert!(diff.is_empty());
+    assert!(diff.to_llm_string().contains("No changes since last scan"));
+}
+
+#[test]
+fn test_dom_diff_new_url() {
+    let mut processor = DomProcessor::new();
+    processor.diff(&tree("https://example.com", vec![button(1, "Next", 10.0)]));
+
+    let diff = processor.diff(&tree(
+        "https://example.com/2",
+        vec![button(1, "Back", 10.0)],
+    ));
+    assert!(diff.full);
+    assert!(diff.removed.is_empty());
+    assert_eq!(diff.added.len(), 1);
+    assert_eq!(diff.added[0].id, "e2");
+}
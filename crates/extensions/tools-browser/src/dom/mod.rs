@@ -4,11 +4,13 @@
 //! It merges information from multiple CDP trees to produce enhanced nodes with
 //! accurate clickability detection.
 
+mod dom_diff;
 mod dom_node;
 mod dom_processor;
 mod dom_tree;
 mod dom_types;
 
+pub use dom_diff::{DiffElement, DomDiff};
 pub use dom_node::EnhancedNode;
 pub use dom_processor::DomProcessor;
 pub use dom_tree::EnhancedNodeTree;
@@ -6,13 +6,16 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 
-use autohands_protocols::error::ExtensionError;
+use autohands_protocols::error::{ExtensionError, ToolError};
 use autohands_protocols::extension::{Extension, ExtensionContext, ExtensionManifest, Provides};
 use autohands_protocols::provider::LLMProvider;
-use autohands_protocols::types::Version;
+use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::types::{PrivacyMasks, Version};
 
 use crate::ai_tools::{AiClickTool, AiExtractTool, AiFillTool, VisionProvider};
-use crate::manager::{BrowserManager, BrowserManagerConfig};
+use crate::backend::BrowserBackend;
+use crate::bidi::FirefoxManager;
+use crate::manager::{BrowserEngine, BrowserManager, BrowserManagerConfig};
 use crate::tools::*;
 
 /// Configuration for AI-powered browser tools.
@@ -28,12 +31,16 @@ pub struct AiToolsConfig {
 ///
 /// Provides browser automation tools via Chrome DevTools Protocol (CDP).
 /// Chrome is automatically launched when tools are first used, using a
-/// persistent profile at `~/.autohands/browser-profile` to preserve logins.
+/// persistent profile at `~/.autohands/browser-profile` to preserve logins,
+/// and relaunched with the same profile and pages if it crashes. With the
+/// Firefox engine, only the basic page tools work, over WebDriver BiDi; the
+/// others are registered but fail, naming the engine.
 pub struct BrowserToolsExtension {
     manifest: ExtensionManifest,
     config: BrowserManagerConfig,
     ai_config: Option<AiToolsConfig>,
     manager: Option<Arc<BrowserManager>>,
+    backend: Option<Arc<dyn BrowserBackend>>,
 }
 
 impl BrowserToolsExtension {
@@ -50,6 +57,10 @@ impl BrowserToolsExtension {
                 "browser_open".to_string(),
                 "browser_close".to_string(),
                 "browser_list_pages".to_string(),
+                "browser_tab_new".to_string(),
+                "browser_tab_list".to_string(),
+                "browser_tab_switch".to_string(),
+                "browser_tab_close".to_string(),
                 "browser_navigate".to_string(),
                 "browser_click".to_string(),
                 "browser_type".to_string(),
@@ -58,11 +69,20 @@ impl BrowserToolsExtension {
                 "browser_get_url".to_string(),
                 "browser_execute_js".to_string(),
                 "browser_wait_for".to_string(),
+                "browser_wait_for_download".to_string(),
                 "browser_scroll".to_string(),
                 "browser_press_key".to_string(),
                 "browser_back".to_string(),
                 "browser_forward".to_string(),
                 "browser_refresh".to_string(),
+                "browser_network_start".to_string(),
+                "browser_network_stop".to_string(),
+                "browser_cookies_get".to_string(),
+                "browser_cookies_set".to_string(),
+                "browser_cookies_clear".to_string(),
+                "browser_storage_get".to_string(),
+                "browser_storage_set".to_string(),
+                "browser_storage_clear".to_string(),
                 // DOM analysis tool (Browser-Use style)
                 "browser_get_dom".to_string(),
                 // AI-powered tools (optional, require vision provider)
@@ -78,9 +98,17 @@ impl BrowserToolsExtension {
             config: BrowserManagerConfig::default(),
             ai_config: None,
             manager: None,
+            backend: None,
         }
     }
 
+    /// Set the browser to drive. Firefox supports the basic page tools only.
+    /// Default: Chrome
+    pub fn engine(mut self, engine: BrowserEngine) -> Self {
+        self.config.engine = engine;
+        self
+    }
+
     /// Set viewport size.
     pub fn viewport(mut self, width: u32, height: u32) -> Self {
         self.config.viewport_width = width;
@@ -88,7 +116,7 @@ impl BrowserToolsExtension {
         self
     }
 
-    /// Set the Chrome debugging port.
+    /// Set the Chrome debugging port, or Firefox remote agent port.
     /// Default: 9222
     pub fn debug_port(mut self, port: u16) -> Self {
         self.config.debug_port = port;
@@ -102,12 +130,67 @@ impl BrowserToolsExtension {
         self
     }
 
+    /// Set the directory downloads are saved to.
+    /// Default: ~/.autohands/downloads
+    pub fn download_dir(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config.download_dir = Some(path.into());
+        self
+    }
+
+    /// Set the named profiles pages can be opened in, each with cookies and
+    /// storage apart from the default profile and from each other.
+    pub fn profiles<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.config.profiles = names.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the directory the cookies of named profiles are saved in.
+    /// Default: ~/.autohands/browser-profiles
+    pub fn profiles_dir(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config.profiles_dir = Some(path.into());
+        self
+    }
+
+    /// Download a pinned headless Chromium when no Chrome is installed.
+    /// It runs with a temporary profile unless `profile_dir` is set.
+    /// Default: false
+    pub fn download_chromium(mut self, enabled: bool) -> Self {
+        self.config.download_chromium = enabled;
+        self
+    }
+
+    /// Set the directory downloaded Chromium builds are kept in.
+    /// Default: ~/.autohands/chromium
+    pub fn chromium_dir(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config.chromium_dir = Some(path.into());
+        self
+    }
+
     /// Enable headless mode.
     pub fn headless(mut self, headless: bool) -> Self {
         self.config.headless = headless;
         self
     }
 
+    /// Black out regions of the viewport, or whole pages by title, in
+    /// screenshots.
+    pub fn privacy_masks(mut self, masks: PrivacyMasks) -> Self {
+        self.config.privacy_masks = masks;
+        self
+    }
+
+    /// Set how many times Chrome or a crashed page is restarted within ten
+    /// minutes before tools report the crash instead; 0 disables restarts.
+    /// Default: 3
+    pub fn max_restarts(mut self, max_restarts: u32) -> Self {
+        self.config.max_restarts = max_restarts;
+        self
+    }
+
     /// Configure AI-powered browser tools with a vision-capable LLM provider.
     ///
     /// This enables `browser_ai_click`, `browser_ai_fill`, and `browser_ai_extract`
@@ -132,10 +215,47 @@ impl BrowserToolsExtension {
         self
     }
 
-    /// Get the browser manager.
+    /// Get the browser manager, `None` with the Firefox engine.
     pub fn manager(&self) -> Option<Arc<BrowserManager>> {
         self.manager.clone()
     }
+
+    /// Get the browser backend the page tools run on.
+    pub fn backend(&self) -> Option<Arc<dyn BrowserBackend>> {
+        self.backend.clone()
+    }
+
+    /// The tools that need Chrome.
+    fn chrome_tools(&self, manager: &Arc<BrowserManager>) -> Vec<Arc<dyn Tool>> {
+        let mut tools: Vec<Arc<dyn Tool>> = vec![
+            Arc::new(TabNewTool::new(manager.clone())),
+            Arc::new(TabListTool::new(manager.clone())),
+            Arc::new(TabSwitchTool::new(manager.clone())),
+            Arc::new(TabCloseTool::new(manager.clone())),
+            Arc::new(WaitForDownloadTool::new(manager.clone())),
+            Arc::new(NetworkStartTool::new(manager.clone())),
+            Arc::new(NetworkStopTool::new(manager.clone())),
+            Arc::new(CookiesGetTool::new(manager.clone())),
+            Arc::new(CookiesSetTool::new(manager.clone())),
+            Arc::new(CookiesClearTool::new(manager.clone())),
+            Arc::new(StorageGetTool::new(manager.clone())),
+            Arc::new(StorageSetTool::new(manager.clone())),
+            Arc::new(StorageClearTool::new(manager.clone())),
+            // DOM analysis tool (Browser-Use style)
+            Arc::new(GetDomTool::new(manager.clone())),
+        ];
+
+        // AI-powered tools if vision provider is configured
+        if let Some(ref ai_config) = self.ai_config {
+            let vision =
+                Arc::new(VisionProvider::new(ai_config.provider.clone(), &ai_config.model));
+
+            tools.push(Arc::new(AiClickTool::new(manager.clone(), vision.clone())));
+            tools.push(Arc::new(AiFillTool::new(manager.clone(), vision.clone())));
+            tools.push(Arc::new(AiExtractTool::new(manager.clone(), vision.clone())));
+        }
+        tools
+    }
 }
 
 impl Default for BrowserToolsExtension {
@@ -151,79 +271,99 @@ impl Extension for BrowserToolsExtension {
     }
 
     async fn initialize(&mut self, ctx: ExtensionContext) -> Result<(), ExtensionError> {
-        // Create browser manager but DO NOT connect yet.
-        // Chrome will be lazily launched on first tool use.
-        let manager = Arc::new(BrowserManager::new(self.config.clone()));
+        if let Some(engine) = ctx.config.get("engine") {
+            self.config.engine = serde_json::from_value(engine.clone()).map_err(|e| {
+                ExtensionError::InitializationFailed(format!("Invalid engine: {}", e))
+            })?;
+        }
+        if let Some(masks) = ctx.config.get("privacy_masks") {
+            self.config.privacy_masks = serde_json::from_value(masks.clone()).map_err(|e| {
+                ExtensionError::InitializationFailed(format!("Invalid privacy_masks: {}", e))
+            })?;
+        }
+        if let Some(dir) = ctx.config.get("download_dir").and_then(|v| v.as_str()) {
+            self.config.download_dir = Some(PathBuf::from(dir));
+        }
+        if let Some(profiles) = ctx.config.get("profiles") {
+            self.config.profiles = serde_json::from_value(profiles.clone()).map_err(|e| {
+                ExtensionError::InitializationFailed(format!("Invalid profiles: {}", e))
+            })?;
+        }
+        if let Some(name) = self.config.profiles.iter().find(|name| !is_profile_name(name)) {
+            return Err(ExtensionError::InitializationFailed(format!(
+                "Invalid profile name '{}': use letters, digits, '-' and '_'",
+                name
+            )));
+        }
+        if let Some(dir) = ctx.config.get("profiles_dir").and_then(|v| v.as_str()) {
+            self.config.profiles_dir = Some(PathBuf::from(dir));
+        }
+        if let Some(enabled) = ctx.config.get("download_chromium").and_then(|v| v.as_bool()) {
+            self.config.download_chromium = enabled;
+        }
+        if let Some(dir) = ctx.config.get("chromium_dir").and_then(|v| v.as_str()) {
+            self.config.chromium_dir = Some(PathBuf::from(dir));
+        }
+        if let Some(version) = ctx.config.get("chromium_version").and_then(|v| v.as_str()) {
+            self.config.chromium_version = Some(version.to_string());
+        }
+        if let Some(max_restarts) = ctx.config.get("max_restarts").and_then(|v| v.as_u64()) {
+            self.config.max_restarts = max_restarts.min(u32::MAX as u64) as u32;
+        }
 
-        // Register tools - browser will connect when browser_open is first used
-        ctx.tool_registry
-            .register_tool(Arc::new(OpenPageTool::new(manager.clone())))?;
-        ctx.tool_registry
-            .register_tool(Arc::new(ClosePageTool::new(manager.clone())))?;
-        ctx.tool_registry
-            .register_tool(Arc::new(ListPagesTool::new(manager.clone())))?;
-        ctx.tool_registry
-            .register_tool(Arc::new(NavigateTool::new(manager.clone())))?;
-        ctx.tool_registry
-            .register_tool(Arc::new(ClickTool::new(manager.clone())))?;
-        ctx.tool_registry
-            .register_tool(Arc::new(TypeTextTool::new(manager.clone())))?;
-        ctx.tool_registry
-            .register_tool(Arc::new(ScreenshotTool::new(manager.clone())))?;
-        ctx.tool_registry
-            .register_tool(Arc::new(GetContentTool::new(manager.clone())))?;
-        ctx.tool_registry
-            .register_tool(Arc::new(GetUrlTool::new(manager.clone())))?;
-        ctx.tool_registry
-            .register_tool(Arc::new(ExecuteJsTool::new(manager.clone())))?;
-        ctx.tool_registry
-            .register_tool(Arc::new(WaitForTool::new(manager.clone())))?;
-        ctx.tool_registry
-            .register_tool(Arc::new(ScrollTool::new(manager.clone())))?;
-        ctx.tool_registry
-            .register_tool(Arc::new(PressKeyTool::new(manager.clone())))?;
-        ctx.tool_registry
-            .register_tool(Arc::new(BackTool::new(manager.clone())))?;
-        ctx.tool_registry
-            .register_tool(Arc::new(ForwardTool::new(manager.clone())))?;
-        ctx.tool_registry
-            .register_tool(Arc::new(RefreshTool::new(manager.clone())))?;
-
-        // Register DOM analysis tool (Browser-Use style)
-        ctx.tool_registry
-            .register_tool(Arc::new(GetDomTool::new(manager.clone())))?;
-
-        // Register AI-powered tools if vision provider is configured
-        if let Some(ref ai_config) = self.ai_config {
-            let vision =
-                Arc::new(VisionProvider::new(ai_config.provider.clone(), &ai_config.model));
+        // Create the browser backend but DO NOT connect yet.
+        // The browser will be lazily launched on first tool use.
+        let manager = match self.config.engine {
+            BrowserEngine::Chrome => Some(Arc::new(BrowserManager::new(self.config.clone()))),
+            BrowserEngine::Firefox => None,
+        };
+        let backend: Arc<dyn BrowserBackend> = match &manager {
+            Some(manager) => manager.clone(),
+            None => Arc::new(FirefoxManager::new(self.config.clone())),
+        };
 
-            ctx.tool_registry
-                .register_tool(Arc::new(AiClickTool::new(manager.clone(), vision.clone())))?;
-            ctx.tool_registry
-                .register_tool(Arc::new(AiFillTool::new(manager.clone(), vision.clone())))?;
-            ctx.tool_registry
-                .register_tool(Arc::new(AiExtractTool::new(manager.clone(), vision.clone())))?;
+        // Register tools - browser will connect when browser_open is first used
+        for tool in page_tools(&backend) {
+            ctx.tool_registry.register_tool(tool)?;
+        }
 
-            tracing::info!(
-                "AI browser tools enabled with model: {}",
-                ai_config.model
-            );
+        let chrome_tools = match &manager {
+            Some(manager) => self.chrome_tools(manager),
+            // Only for their definitions: this manager is never connected
+            None => self
+                .chrome_tools(&Arc::new(BrowserManager::new(self.config.clone())))
+                .into_iter()
+                .map(|tool| -> Arc<dyn Tool> {
+                    Arc::new(UnsupportedTool::new(tool.definition().clone(), self.config.engine))
+                })
+                .collect(),
+        };
+        for tool in chrome_tools {
+            ctx.tool_registry.register_tool(tool)?;
+        }
+        if let (Some(_), Some(ai_config)) = (&manager, &self.ai_config) {
+            tracing::info!("AI browser tools enabled with model: {}", ai_config.model);
         }
 
-        self.manager = Some(manager);
+        self.backend = Some(backend);
+        self.manager = manager;
 
         tracing::info!(
-            "Browser tools extension initialized (profile: {})",
-            self.config.get_profile_dir().display()
+            "Browser tools extension initialized ({:?}, profile: {})",
+            self.config.engine,
+            match self.config.engine {
+                BrowserEngine::Chrome => self.config.get_profile_dir(),
+                BrowserEngine::Firefox => self.config.get_firefox_profile_dir(),
+            }
+            .display()
         );
         Ok(())
     }
 
     async fn shutdown(&self) -> Result<(), ExtensionError> {
-        if let Some(ref manager) = self.manager {
-            manager
-                .shutdown_chrome()
+        if let Some(ref backend) = self.backend {
+            backend
+                .shutdown()
                 .await
                 .map_err(|e| ExtensionError::ShutdownFailed(e.to_string()))?;
         }
@@ -239,6 +379,67 @@ impl Extension for BrowserToolsExtension {
     }
 }
 
+/// Tools any browser backend can serve.
+fn page_tools(backend: &Arc<dyn BrowserBackend>) -> Vec<Arc<dyn Tool>> {
+    vec![
+        Arc::new(OpenPageTool::new(backend.clone())),
+        Arc::new(ClosePageTool::new(backend.clone())),
+        Arc::new(ListPagesTool::new(backend.clone())),
+        Arc::new(NavigateTool::new(backend.clone())),
+        Arc::new(ClickTool::new(backend.clone())),
+        Arc::new(TypeTextTool::new(backend.clone())),
+        Arc::new(ScreenshotTool::new(backend.clone())),
+        Arc::new(GetContentTool::new(backend.clone())),
+        Arc::new(GetUrlTool::new(backend.clone())),
+        Arc::new(ExecuteJsTool::new(backend.clone())),
+        Arc::new(WaitForTool::new(backend.clone())),
+        Arc::new(ScrollTool::new(backend.clone())),
+        Arc::new(PressKeyTool::new(backend.clone())),
+        Arc::new(BackTool::new(backend.clone())),
+        Arc::new(ForwardTool::new(backend.clone())),
+        Arc::new(RefreshTool::new(backend.clone())),
+    ]
+}
+
+/// A tool the engine cannot serve, failing with an error that says so
+/// rather than missing from the agent's tools.
+struct UnsupportedTool {
+    definition: ToolDefinition,
+    engine: BrowserEngine,
+}
+
+impl UnsupportedTool {
+    fn new(definition: ToolDefinition, engine: BrowserEngine) -> Self {
+        Self { definition, engine }
+    }
+}
+
+#[async_trait]
+impl Tool for UnsupportedTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        _params: serde_json::Value,
+        _ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        Err(ToolError::ExecutionFailed(format!(
+            "{} is not supported with the {:?} browser engine; it needs Chrome",
+            self.definition.id, self.engine
+        )))
+    }
+}
+
+/// Whether a profile name is safe to use as a directory name.
+fn is_profile_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
 #[cfg(test)]
 #[path = "extension_tests.rs"]
 mod tests;
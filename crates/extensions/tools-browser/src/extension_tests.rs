@@ -17,6 +17,12 @@ fn test_builder_methods() {
 
     assert_eq!(ext.config.viewport_width, 1920);
     assert_eq!(ext.config.viewport_height, 1080);
+
+    let ext = BrowserToolsExtension::new().download_dir("/tmp/downloads");
+    assert_eq!(
+        ext.config.get_download_dir(),
+        std::path::PathBuf::from("/tmp/downloads")
+    );
 }
 
 #[test]
@@ -55,13 +61,15 @@ fn test_all_tools_provided() {
     assert!(tools.contains(&"browser_execute_js".to_string()));
     assert!(tools.contains(&"browser_wait_for".to_string()));
     assert!(tools.contains(&"browser_get_dom".to_string()));
+    assert!(tools.contains(&"browser_tab_new".to_string()));
+    assert!(tools.contains(&"browser_tab_switch".to_string()));
 }
 
 #[test]
 fn test_tools_count() {
     let ext = BrowserToolsExtension::new();
-    // 16 basic + 1 DOM + 3 AI = 20 tools
-    assert_eq!(ext.manifest().provides.tools.len(), 20);
+    // 17 basic + 4 tab + 2 network + 3 cookie + 3 storage + 1 DOM + 3 AI = 33 tools
+    assert_eq!(ext.manifest().provides.tools.len(), 33);
 }
 
 #[test]
@@ -109,3 +117,80 @@ fn test_profile_dir() {
         .profile_dir("/custom/profile");
     assert_eq!(ext.config.profile_dir, Some(PathBuf::from("/custom/profile")));
 }
+
+#[test]
+fn test_profiles() {
+    let ext = BrowserToolsExtension::new()
+        .profiles(["work", "personal"])
+        .profiles_dir("/tmp/profiles");
+    assert_eq!(
+        ext.config.profiles,
+        vec!["work".to_string(), "personal".to_string()]
+    );
+    assert_eq!(
+        ext.config.get_profiles_dir(),
+        PathBuf::from("/tmp/profiles")
+    );
+
+    assert!(is_profile_name("agent_1-work"));
+    assert!(!is_profile_name(""));
+    assert!(!is_profile_name("../work"));
+}
+
+#[test]
+fn test_download_chromium() {
+    let ext = BrowserToolsExtension::new();
+    assert!(!ext.config.download_chromium);
+
+    let ext = BrowserToolsExtension::new()
+        .download_chromium(true)
+        .chromium_dir("/tmp/chromium");
+    assert!(ext.config.download_chromium);
+    assert!(ext
+        .config
+        .chromium_fetcher()
+        .executable("linux64")
+        .starts_with(format!("/tmp/chromium/{}", crate::CHROMIUM_VERSION)));
+}
+
+#[test]
+fn test_engine() {
+    let ext = BrowserToolsExtension::new();
+    assert_eq!(ext.config.engine, BrowserEngine::Chrome);
+
+    let ext = BrowserToolsExtension::new().engine(BrowserEngine::Firefox);
+    assert_eq!(ext.config.engine, BrowserEngine::Firefox);
+    assert!(ext.backend().is_none());
+}
+
+#[tokio::test]
+async fn test_chrome_tools_unsupported_with_firefox() {
+    let ext = BrowserToolsExtension::new().engine(BrowserEngine::Firefox);
+    let tools = ext.chrome_tools(&Arc::new(BrowserManager::new(ext.config.clone())));
+    // 4 tab + 1 download + 2 network + 3 cookie + 3 storage + 1 DOM
+    assert_eq!(tools.len(), 14);
+
+    let tool = UnsupportedTool::new(tools[0].definition().clone(), BrowserEngine::Firefox);
+    assert_eq!(tool.definition().id, "browser_tab_new");
+    let err = tool
+        .execute(serde_json::json!({}), ToolContext::new("s", PathBuf::from(".")))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("browser_tab_new is not supported with the Firefox"));
+}
+
+#[test]
+fn test_page_tools() {
+    let ext = BrowserToolsExtension::new();
+    let backend: Arc<dyn BrowserBackend> =
+        Arc::new(FirefoxManager::new(BrowserManagerConfig::default()));
+    let tools = page_tools(&backend);
+    assert_eq!(tools.len(), 16);
+    for tool in &tools {
+        assert!(ext
+            .manifest()
+            .provides
+            .tools
+            .contains(&tool.definition().id));
+    }
+}
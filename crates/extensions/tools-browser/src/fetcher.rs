@@ -0,0 +1,205 @@
+//! Headless Chromium fetcher, for machines without Chrome.
+//!
+//! Downloads a pinned build of Chrome for Testing's `chrome-headless-shell`
+//! into a cache directory, once per version, and returns its executable.
+//! Builds of other versions in the directory are removed after a new one is
+//! unpacked, so updating the pin does not leave old browsers behind; other
+//! files in the directory are left alone.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use tracing::{debug, info, warn};
+
+/// Chromium version fetched unless another is configured.
+pub const CHROMIUM_VERSION: &str = "131.0.6778.85";
+
+/// Where Chrome for Testing builds are published.
+const DOWNLOAD_HOST: &str = "https://storage.googleapis.com/chrome-for-testing-public";
+
+/// Downloads and caches headless Chromium builds.
+#[derive(Debug, Clone)]
+pub struct ChromiumFetcher {
+    dir: PathBuf,
+    version: String,
+}
+
+impl ChromiumFetcher {
+    /// A fetcher keeping builds in `dir`.
+    pub fn new(dir: impl Into<PathBuf>, version: impl Into<String>) -> Self {
+        Self {
+            dir: dir.into(),
+            version: version.into(),
+        }
+    }
+
+    /// Chrome for Testing's name for this platform, if builds exist for it.
+    pub fn platform() -> Option<&'static str> {
+        match (std::env::consts::OS, std::env::consts::ARCH) {
+            ("linux", "x86_64") => Some("linux64"),
+            ("macos", "aarch64") => Some("mac-arm64"),
+            ("macos", "x86_64") => Some("mac-x64"),
+            ("windows", "x86_64") => Some("win64"),
+            ("windows", "x86") => Some("win32"),
+            _ => None,
+        }
+    }
+
+    /// Archive of the build for `platform`.
+    pub fn download_url(&self, platform: &str) -> String {
+        format!(
+            "{}/{}/{}/chrome-headless-shell-{}.zip",
+            DOWNLOAD_HOST, self.version, platform, platform
+        )
+    }
+
+    /// Where the executable of the build for `platform` is unpacked.
+    pub fn executable(&self, platform: &str) -> PathBuf {
+        let name = if platform.starts_with("win") {
+            "chrome-headless-shell.exe"
+        } else {
+            "chrome-headless-shell"
+        };
+        self.dir
+            .join(&self.version)
+            .join(format!("chrome-headless-shell-{}", platform))
+            .join(name)
+    }
+
+    /// The executable of the pinned build, downloading it first if it is
+    /// not cached.
+    pub async fn fetch(&self) -> Result<PathBuf, String> {
+        let platform = Self::platform().ok_or_else(|| {
+            format!(
+                "no headless Chromium builds for {}-{}",
+                std::env::consts::OS,
+                std::env::consts::ARCH
+            )
+        })?;
+        let executable = self.executable(platform);
+        if executable.exists() {
+            debug!("Using cached Chromium at {}", executable.display());
+            return Ok(executable);
+        }
+
+        std::fs::create_dir_all(&self.dir)
+            .map_err(|e| format!("cannot create {}: {}", self.dir.display(), e))?;
+        let archive = self.dir.join(format!("{}.zip.part", self.version));
+        let url = self.download_url(platform);
+        info!(
+            "Downloading headless Chromium {} from {}",
+            self.version, url
+        );
+        let result = download(&url, &archive).await;
+        let result = match result {
+            Ok(()) => {
+                let archive = archive.clone();
+                let staging = self.dir.join(format!("{}.part", self.version));
+                let target = self.dir.join(&self.version);
+                tokio::task::spawn_blocking(move || unpack(&archive, &staging, &target))
+                    .await
+                    .map_err(|e| e.to_string())
+                    .and_then(|result| result)
+            }
+            Err(e) => Err(e),
+        };
+        let _ = std::fs::remove_file(&archive);
+        result?;
+
+        if !executable.exists() {
+            return Err(format!("the download has no {}", executable.display()));
+        }
+        self.remove_other_versions();
+        info!(
+            "Headless Chromium {} ready at {}",
+            self.version,
+            executable.display()
+        );
+        Ok(executable)
+    }
+
+    /// Delete cached builds other than the pinned one.
+    ///
+    /// `dir` may be shared with other files, so only directories this
+    /// fetcher unpacked are touched: named after a version and holding a
+    /// `chrome-headless-shell-*` build.
+    fn remove_other_versions(&self) {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name();
+            if name == self.version.as_str() || !is_build_dir(&path) {
+                continue;
+            }
+            debug!("Removing old Chromium build {}", path.display());
+            if let Err(e) = std::fs::remove_dir_all(&path) {
+                warn!("Failed to remove {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+/// Whether `path` is a build unpacked by the fetcher: a directory named
+/// like `131.0.6778.85` holding a `chrome-headless-shell-<platform>`
+/// directory.
+fn is_build_dir(path: &Path) -> bool {
+    let is_version = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| {
+            name.split('.').count() > 1
+                && name
+                    .split('.')
+                    .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()))
+        });
+    if !is_version || !path.is_dir() {
+        return false;
+    }
+    std::fs::read_dir(path).is_ok_and(|entries| {
+        entries.flatten().any(|entry| {
+            entry.path().is_dir()
+                && entry
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with("chrome-headless-shell-")
+        })
+    })
+}
+
+/// Stream `url` to `path`.
+async fn download(url: &str, path: &Path) -> Result<(), String> {
+    let mut response = reqwest::get(url)
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("download failed: {}", e))?;
+    let mut file = std::fs::File::create(path)
+        .map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("download failed: {}", e))?
+    {
+        file.write_all(&chunk)
+            .map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
+/// Unpack the zip at `archive` into `target`, through `staging` so an
+/// interrupted unpack is never mistaken for a build.
+fn unpack(archive: &Path, staging: &Path, target: &Path) -> Result<(), String> {
+    let file = std::fs::File::open(archive).map_err(|e| e.to_string())?;
+    let mut zip = zip::ZipArchive::new(file).map_err(|e| format!("bad archive: {}", e))?;
+    let _ = std::fs::remove_dir_all(staging);
+    // Keeps the executable bits of the archived files on Unix
+    zip.extract(staging)
+        .map_err(|e| format!("cannot unpack: {}", e))?;
+    let _ = std::fs::remove_dir_all(target);
+    std::fs::rename(staging, target).map_err(|e| format!("cannot unpack: {}", e))
+}
+
+#[cfg(test)]
+#[path = "fetcher_tests.rs"]
+mod tests;
@@ -0,0 +1,71 @@
+use super::*;
+
+#[test]
+fn test_download_url() {
+    let fetcher = ChromiumFetcher::new("/tmp/chromium", "131.0.6778.85");
+    assert_eq!(
+        fetcher.download_url("linux64"),
+        "https://storage.googleapis.com/chrome-for-testing-public/131.0.6778.85/linux64/chrome-headless-shell-linux64.zip"
+    );
+}
+
+#[test]
+fn test_executable() {
+    let fetcher = ChromiumFetcher::new("/tmp/chromium", "131.0.6778.85");
+    assert_eq!(
+        fetcher.executable("mac-arm64"),
+        PathBuf::from(
+            "/tmp/chromium/131.0.6778.85/chrome-headless-shell-mac-arm64/chrome-headless-shell"
+        )
+    );
+    assert!(fetcher
+        .executable("win64")
+        .ends_with("chrome-headless-shell-win64/chrome-headless-shell.exe"));
+}
+
+#[tokio::test]
+async fn test_fetch_uses_cached_build() {
+    let Some(platform) = ChromiumFetcher::platform() else {
+        return;
+    };
+    let dir = tempfile::tempdir().unwrap();
+    let fetcher = ChromiumFetcher::new(dir.path(), "1.0.0");
+    let executable = fetcher.executable(platform);
+    std::fs::create_dir_all(executable.parent().unwrap()).unwrap();
+    std::fs::write(&executable, "").unwrap();
+
+    assert_eq!(fetcher.fetch().await.unwrap(), executable);
+}
+
+#[test]
+fn test_unpack_and_remove_other_versions() {
+    use zip::write::SimpleFileOptions;
+
+    let dir = tempfile::tempdir().unwrap();
+    let archive = dir.path().join("2.0.0.zip.part");
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(&archive).unwrap());
+    zip.start_file(
+        "chrome-headless-shell-linux64/chrome-headless-shell",
+        SimpleFileOptions::default(),
+    )
+    .unwrap();
+    zip.write_all(b"binary").unwrap();
+    zip.finish().unwrap();
+
+    std::fs::create_dir_all(dir.path().join("1.0.0/chrome-headless-shell-linux64")).unwrap();
+    // Unrelated directories sharing the cache directory
+    std::fs::create_dir_all(dir.path().join("projects/notes")).unwrap();
+    std::fs::create_dir_all(dir.path().join("3.1/src")).unwrap();
+    let target = dir.path().join("2.0.0");
+    unpack(&archive, &dir.path().join("2.0.0.part"), &target).unwrap();
+    assert!(target
+        .join("chrome-headless-shell-linux64/chrome-headless-shell")
+        .exists());
+    assert!(!dir.path().join("2.0.0.part").exists());
+
+    ChromiumFetcher::new(dir.path(), "2.0.0").remove_other_versions();
+    assert!(!dir.path().join("1.0.0").exists());
+    assert!(target.exists());
+    assert!(dir.path().join("projects/notes").exists());
+    assert!(dir.path().join("3.1/src").exists());
+}
@@ -29,11 +29,30 @@
 //!
 //! This allows AutoHands to use your existing browser sessions with all logins preserved.
 //!
+//! On machines without Chrome, set `download_chromium` to have a pinned headless
+//! Chromium downloaded to `~/.autohands/chromium` on first use (see [`fetcher`]). It runs
+//! with a temporary profile that is removed on shutdown, unless `profile_dir` is set.
+//!
+//! ## Firefox
+//!
+//! With `engine` set to `firefox`, Firefox is launched instead and driven over
+//! WebDriver BiDi (see [`bidi`]), with a profile at `~/.autohands/firefox-profile`.
+//! Only the basic page tools are available: tabs, downloads, network capture,
+//! cookies, storage, the DOM and AI tools, named profiles and crash recovery need
+//! Chrome.
+//!
 //! ## Lazy Initialization
 //!
 //! The browser is NOT connected when the extension is loaded. It is lazily
 //! initialized when `browser_open` is first called, ensuring fast startup.
 //!
+//! ## Crash Recovery
+//!
+//! If Chrome exits or a page's renderer crashes, the next page call
+//! relaunches Chrome (or re-opens the page) with the same profile, restores
+//! the open pages at their last URLs under the same page ids, and retries
+//! once. Restarts are capped by `max_restarts` per ten minutes.
+//!
 //! ## Tools
 //!
 //! ### Basic Tools
@@ -44,13 +63,41 @@
 //! - `browser_screenshot` - Take a screenshot
 //! - `browser_get_content` - Get page/element content
 //! - `browser_execute_js` - Execute JavaScript
-//! - `browser_wait_for` - Wait for an element
+//! - `browser_wait_for` - Wait for an element's state, a URL, text, a JS predicate or
+//!   network idle (see [`wait`])
+//! - `browser_wait_for_download` - Wait for a download and get its saved path
+//!
+//! ### Tabs
+//! - `browser_tab_new` - Open a tab, in the foreground or background
+//! - `browser_tab_list` - List open tabs and which one is active
+//! - `browser_tab_switch` - Bring a tab to the front
+//! - `browser_tab_close` - Close a tab, by default the active one
+//!
+//! Tab ids are page ids: every page tool takes them as `page_id`.
+//!
+//! ### Cookies and Storage
+//! - `browser_cookies_get` / `browser_cookies_set` / `browser_cookies_clear` - Manage cookies
+//! - `browser_storage_get` / `browser_storage_set` / `browser_storage_clear` - Manage
+//!   localStorage or sessionStorage
+//!
+//! `browser_open` and `browser_tab_new` take a `profile` to open the page in one of the
+//! configured named profiles, whose cookies and storage are kept apart from the others.
+//!
+//! ### Network
+//! - `browser_network_start` - Record a page's requests, filtered by URL pattern
+//! - `browser_network_stop` - Stop recording and get the requests, or an HAR file
 //!
 //! ### AI-Powered Tools (requires vision-capable LLM)
 //! - `browser_ai_click` - Click an element by natural language description
 //! - `browser_ai_fill` - Fill a form field by natural language description
 //! - `browser_ai_extract` - Extract structured data from page using AI
 //!
+//! Click and fill targets are located with set-of-marks: the page's interactive
+//! elements, shadow DOM included, are outlined with numbered labels on the
+//! screenshot, and the number the model picks is mapped back to the element's
+//! box. Targets outside any mark, such as controls drawn on a canvas, fall back
+//! to asking the model for coordinates.
+//!
 //! ### DOM Analysis (Browser-Use Style)
 //! - `browser_get_dom` - Get enhanced DOM tree with clickability scores
 //!
@@ -60,17 +107,30 @@
 //! - 10-layer clickable detection (event listeners, ARIA roles, cursor, etc.)
 //! - LLM-friendly element serialization
 //! - Accurate bounding boxes for coordinate-based clicks
+//! - Incremental re-scans returning only the interactive elements added,
+//!   removed or changed since the last one, with stable element IDs
 
 mod ai_tools;
+pub mod backend;
+pub mod bidi;
 pub mod cdp;
 mod dom;
 mod extension;
+pub mod fetcher;
 pub mod manager;
 mod tools;
+pub mod wait;
 
 pub use ai_tools::{AiClickTool, AiExtractTool, AiFillTool, VisionProvider};
+pub use backend::BrowserBackend;
+pub use bidi::{BidiClient, BidiError, FirefoxManager};
 pub use cdp::{CdpClient, CdpError, PageSession};
-pub use dom::{DomProcessor, EnhancedNode, EnhancedNodeTree, NodeAttributes, ViewportInfo};
+pub use dom::{
+    DiffElement, DomDiff, DomProcessor, EnhancedNode, EnhancedNodeTree, NodeAttributes,
+    ViewportInfo,
+};
 pub use extension::BrowserToolsExtension;
-pub use manager::{BrowserError, BrowserManager, BrowserManagerConfig};
+pub use fetcher::{ChromiumFetcher, CHROMIUM_VERSION};
+pub use manager::{BrowserEngine, BrowserError, BrowserManager, BrowserManagerConfig};
 pub use tools::*;
+pub use wait::{ElementState, WaitCondition};
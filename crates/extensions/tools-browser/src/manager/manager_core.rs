@@ -1,21 +1,33 @@
 //! BrowserManager core: struct definition, new, connect, chrome management.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::path::PathBuf;
 use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Instant;
 
 use tokio::process::{Child, Command};
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
 use tracing::{info, warn};
 
 use crate::cdp::{CdpClient, PageSession};
+use crate::dom::DomProcessor;
 use super::{BrowserError, BrowserManagerConfig};
 
 /// Page state tracking.
 pub(super) struct PageState {
     pub(super) session: Arc<PageSession>,
     pub(super) url: String,
+    /// Position among opened pages, for listing tabs in order.
+    pub(super) opened: u64,
+    /// When the page was last brought to the front; the greatest is the
+    /// active tab.
+    pub(super) last_active: u64,
+    /// Named profile the page belongs to, `None` for the default one.
+    pub(super) profile: Option<String>,
+    /// Interactive elements of the page's last incremental DOM scan.
+    pub(super) dom: DomProcessor,
 }
 
 /// Manages browser connections and pages.
@@ -24,8 +36,18 @@ pub struct BrowserManager {
     pub(super) client: RwLock<Option<Arc<CdpClient>>>,
     pub(super) pages: RwLock<HashMap<String, PageState>>,
     pub(super) page_counter: RwLock<u64>,
+    /// Ticks for [`PageState::last_active`].
+    pub(super) activations: AtomicU64,
     /// Chrome process handle (if we launched it).
     pub(super) chrome_process: RwLock<Option<Child>>,
+    /// When Chrome or a tab was last restarted after a crash.
+    pub(super) restarts: parking_lot::Mutex<VecDeque<Instant>>,
+    /// Held while recovering, so concurrent calls recover once.
+    pub(super) recovery: Mutex<()>,
+    /// Browser contexts of the named profiles opened in this browser.
+    pub(super) profile_contexts: Mutex<HashMap<String, String>>,
+    /// Temporary profile of a downloaded Chromium, kept across restarts.
+    pub(super) managed_profile: parking_lot::Mutex<Option<tempfile::TempDir>>,
 }
 
 impl BrowserManager {
@@ -36,7 +58,12 @@ impl BrowserManager {
             client: RwLock::new(None),
             pages: RwLock::new(HashMap::new()),
             page_counter: RwLock::new(0),
+            activations: AtomicU64::new(0),
             chrome_process: RwLock::new(None),
+            restarts: parking_lot::Mutex::new(VecDeque::new()),
+            recovery: Mutex::new(()),
+            profile_contexts: Mutex::new(HashMap::new()),
+            managed_profile: parking_lot::Mutex::new(None),
         }
     }
 
@@ -99,9 +126,20 @@ impl BrowserManager {
     }
 
     /// Launch Chrome with remote debugging enabled.
+    ///
+    /// Without an installed Chrome, a headless Chromium is downloaded if
+    /// configured and launched with a managed profile.
     pub(super) async fn launch_chrome(&self) -> Result<Child, BrowserError> {
-        let chrome_path = Self::find_chrome().ok_or(BrowserError::ChromeNotFound)?;
-        let profile_dir = self.config.get_profile_dir();
+        let (chrome_path, profile_dir, fetched) = match Self::find_chrome() {
+            Some(path) => (path, self.config.get_profile_dir(), false),
+            None if self.config.download_chromium => {
+                let path = self.config.chromium_fetcher().fetch().await.map_err(|e| {
+                    BrowserError::LaunchFailed(format!("cannot download Chromium: {}", e))
+                })?;
+                (path, self.managed_profile_dir()?, true)
+            }
+            None => return Err(BrowserError::ChromeNotFound),
+        };
 
         if let Err(e) = std::fs::create_dir_all(&profile_dir) {
             warn!("Failed to create profile directory: {}", e);
@@ -119,9 +157,12 @@ impl BrowserManager {
             .arg("--disable-translate")
             .arg("--metrics-recording-only")
             .stdout(Stdio::null())
-            .stderr(Stdio::null());
+            .stderr(Stdio::null())
+            // The manager owns the browser it launches
+            .kill_on_drop(true);
 
-        if self.config.headless {
+        // The downloaded shell is always headless
+        if self.config.headless && !fetched {
             cmd.arg("--headless=new");
         }
 
@@ -133,6 +174,25 @@ impl BrowserManager {
         Ok(child)
     }
 
+    /// Profile directory for a downloaded Chromium: the configured one, or
+    /// a temporary one removed on shutdown.
+    pub(super) fn managed_profile_dir(&self) -> Result<PathBuf, BrowserError> {
+        if let Some(dir) = &self.config.profile_dir {
+            return Ok(dir.clone());
+        }
+        let mut managed = self.managed_profile.lock();
+        if let Some(dir) = managed.as_ref() {
+            return Ok(dir.path().to_path_buf());
+        }
+        let dir = tempfile::Builder::new()
+            .prefix("autohands-chromium-")
+            .tempdir()
+            .map_err(|e| BrowserError::LaunchFailed(format!("cannot create profile: {}", e)))?;
+        let path = dir.path().to_path_buf();
+        *managed = Some(dir);
+        Ok(path)
+    }
+
     /// Connect to the browser, launching it if necessary.
     pub async fn connect(&self) -> Result<(), BrowserError> {
         if self.client.read().await.is_some() {
@@ -165,6 +225,14 @@ impl BrowserManager {
         }
 
         let client = CdpClient::connect(&self.config.endpoint()).await?;
+        let download_dir = self.config.get_download_dir();
+        let enabled = match std::fs::create_dir_all(&download_dir) {
+            Ok(()) => client.enable_downloads(&download_dir, None).await.map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = enabled {
+            warn!("Downloads go to Chrome's default folder: {}", e);
+        }
         *self.client.write().await = Some(Arc::new(client));
 
         info!("Connected to Chrome at {}", self.config.endpoint());
@@ -172,9 +240,19 @@ impl BrowserManager {
     }
 
     /// Ensure the browser is connected before use.
+    ///
+    /// Restarts Chrome if the connection to it was lost.
     pub async fn ensure_connected(&self) -> Result<(), BrowserError> {
-        if self.client.read().await.is_none() {
-            self.connect().await?;
+        let client = self.client.read().await.clone();
+        match client {
+            None => self.connect().await?,
+            Some(client) if !client.is_connected() && self.config.max_restarts > 0 => {
+                let _recovery = self.recovery.lock().await;
+                if self.client.read().await.as_ref().is_some_and(|c| !c.is_connected()) {
+                    self.restart_browser().await?;
+                }
+            }
+            Some(_) => {}
         }
         Ok(())
     }
@@ -188,6 +266,11 @@ impl BrowserManager {
             .ok_or(BrowserError::NotConnected)
     }
 
+    /// Next tick for marking a page active.
+    pub(super) fn next_activation(&self) -> u64 {
+        self.activations.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
     /// Get page session by ID (clones the Arc).
     pub(super) async fn get_session(&self, page_id: &str) -> Result<Arc<PageSession>, BrowserError> {
         let pages = self.pages.read().await;
@@ -199,19 +282,24 @@ impl BrowserManager {
 
     /// Close the browser connection.
     pub async fn close(&self) -> Result<(), BrowserError> {
+        self.save_profiles().await;
+        self.profile_contexts.lock().await.clear();
         self.pages.write().await.clear();
         let _ = self.client.write().await.take();
         info!("Browser connection closed");
         Ok(())
     }
 
-    /// Shutdown Chrome if we launched it.
+    /// Shutdown Chrome if we launched it, and remove the managed profile of
+    /// a downloaded Chromium.
     pub async fn shutdown_chrome(&self) -> Result<(), BrowserError> {
         self.close().await?;
         if let Some(mut child) = self.chrome_process.write().await.take() {
             info!("Shutting down Chrome...");
             let _ = child.kill().await;
         }
+        // Deletes the profile now that nothing uses it
+        self.managed_profile.lock().take();
         Ok(())
     }
 }
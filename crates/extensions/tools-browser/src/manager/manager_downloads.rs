@@ -0,0 +1,69 @@
+//! BrowserManager downloads: waiting for the files pages download.
+
+use std::time::Duration;
+
+use tracing::debug;
+
+use super::{BrowserError, BrowserManager};
+use crate::cdp::{Download, DownloadState};
+
+impl BrowserManager {
+    /// Downloads of the current browser connection, in the order they began.
+    pub async fn list_downloads(&self) -> Vec<Download> {
+        match self.client.read().await.clone() {
+            Some(client) => client.downloads().list(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Wait for a download to complete and return it with its saved path.
+    ///
+    /// Downloads are handed out once each, oldest first, so one that
+    /// finished before the call is returned at once.
+    pub async fn wait_for_download(&self, timeout: Duration) -> Result<Download, BrowserError> {
+        let downloads = self.client().await?.downloads();
+        // Subscribe before looking, so no change is missed
+        let mut changes = downloads.subscribe();
+        let wait = async {
+            loop {
+                if let Some(download) = downloads
+                    .next_finished()
+                    .map_err(BrowserError::ActionFailed)?
+                {
+                    return Ok(download);
+                }
+                if changes.changed().await.is_err() {
+                    return Err(BrowserError::NotConnected);
+                }
+            }
+        };
+        let download = match tokio::time::timeout(timeout, wait).await {
+            Ok(result) => result?,
+            Err(_) => {
+                let running: Vec<String> = downloads
+                    .list()
+                    .into_iter()
+                    .filter(|d| d.state == DownloadState::InProgress)
+                    .map(|d| {
+                        format!(
+                            "{} ({} of {} bytes)",
+                            d.suggested_filename, d.received_bytes, d.total_bytes
+                        )
+                    })
+                    .collect();
+                let seconds = timeout.as_secs_f64();
+                return Err(BrowserError::ActionFailed(if running.is_empty() {
+                    format!("No download started within {}s", seconds)
+                } else {
+                    format!(
+                        "Download still running after {}s: {}",
+                        seconds,
+                        running.join(", ")
+                    )
+                }));
+            }
+        };
+        debug!("Download {} saved to {:?}", download.guid, download.path);
+        Ok(download)
+    }
+}
@@ -0,0 +1,87 @@
+//! BrowserManager network capture: recording the requests a page makes.
+
+use std::sync::Arc;
+
+use serde_json::json;
+use tracing::debug;
+
+use super::{BrowserError, BrowserManager};
+use crate::cdp::{CapturedRequest, NetworkCapture};
+
+/// Longest response body returned per request, in bytes.
+const MAX_BODY_BYTES: usize = 64 * 1024;
+
+impl BrowserManager {
+    /// Start recording the requests of a page whose URL matches `pattern`.
+    ///
+    /// A capture already running on the page is discarded.
+    pub async fn start_capture(
+        &self,
+        page_id: &str,
+        pattern: Option<&str>,
+        max_requests: usize,
+    ) -> Result<(), BrowserError> {
+        let capture =
+            NetworkCapture::new(pattern, max_requests).map_err(BrowserError::ActionFailed)?;
+        let session = self.get_session(page_id).await?;
+        let client = self.client().await?;
+        client.start_capture(session.session_id(), Arc::new(capture));
+        debug!("Started network capture on {}", page_id);
+        Ok(())
+    }
+
+    /// Stop recording a page's requests and return them, with how many
+    /// were left out once the capture was full.
+    ///
+    /// With `include_bodies`, the text bodies of finished responses are
+    /// fetched while the page still holds them.
+    pub async fn stop_capture(
+        &self,
+        page_id: &str,
+        include_bodies: bool,
+    ) -> Result<(Vec<CapturedRequest>, usize), BrowserError> {
+        let session = self.get_session(page_id).await?;
+        let client = self.client().await?;
+        let capture = client.stop_capture(session.session_id()).ok_or_else(|| {
+            BrowserError::ActionFailed(format!("No network capture running on page {}", page_id))
+        })?;
+
+        let mut requests = capture.requests();
+        if include_bodies {
+            for request in requests.iter_mut().filter(|r| r.has_text_body()) {
+                let result = session
+                    .call(
+                        "Network.getResponseBody",
+                        Some(json!({ "requestId": request.request_id })),
+                    )
+                    .await;
+                // Bodies the page has already evicted are left out
+                let Ok(body) = result else { continue };
+                if body["base64Encoded"].as_bool().unwrap_or(false) {
+                    continue;
+                }
+                if let Some(text) = body["body"].as_str() {
+                    request.response_body = Some(truncate_body(text));
+                }
+            }
+        }
+        debug!(
+            "Stopped network capture on {}: {} requests",
+            page_id,
+            requests.len()
+        );
+        Ok((requests, capture.dropped()))
+    }
+}
+
+/// A body cut to `MAX_BODY_BYTES` on a character boundary.
+fn truncate_body(text: &str) -> String {
+    if text.len() <= MAX_BODY_BYTES {
+        return text.to_string();
+    }
+    let mut end = MAX_BODY_BYTES;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}… ({} bytes total)", &text[..end], text.len())
+}
@@ -3,32 +3,48 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use tracing::debug;
+use tracing::{debug, warn};
 
-use crate::cdp::ScreenshotFormat;
-use crate::dom::EnhancedNodeTree;
+use crate::cdp::{CdpError, PageSession, ScreenshotFormat};
+use crate::dom::{DomDiff, DomProcessor, EnhancedNodeTree};
 use super::manager_core::PageState;
+use super::manager_privacy::{mask_overlay_script, remove_overlay_script};
 use super::{BrowserError, BrowserManager};
 
 impl BrowserManager {
     /// Create a new page and navigate to URL.
     pub async fn new_page(&self, url: &str) -> Result<String, BrowserError> {
+        self.new_page_in(url, None).await
+    }
+
+    /// Create a new page in a named profile, the default one if `None`,
+    /// and navigate to URL.
+    pub async fn new_page_in(
+        &self,
+        url: &str,
+        profile: Option<&str>,
+    ) -> Result<String, BrowserError> {
         self.ensure_connected().await?;
         let client = self.client().await?;
 
-        let session = client.new_page(Some(url)).await?;
+        let session = self.open_session(&client, url, profile).await?;
 
-        let page_id = {
+        let (page_id, opened) = {
             let mut counter = self.page_counter.write().await;
             *counter += 1;
-            format!("page_{}", *counter)
+            (format!("page_{}", *counter), *counter)
         };
 
+        // Chrome opens new tabs in the foreground
         self.pages.write().await.insert(
             page_id.clone(),
             PageState {
                 session: Arc::new(session),
                 url: url.to_string(),
+                opened,
+                last_active: self.next_activation(),
+                profile: profile.map(str::to_string),
+                dom: DomProcessor::new(),
             },
         );
 
@@ -41,7 +57,13 @@ impl BrowserManager {
         let state = self.pages.write().await.remove(page_id);
         if let Some(state) = state {
             let client = self.client().await?;
+            client.stop_capture(state.session.session_id());
             client.close_page(state.session.target_id()).await?;
+            if let Some(profile) = &state.profile {
+                if let Err(e) = self.save_profile(profile).await {
+                    warn!("{}", e);
+                }
+            }
         }
         debug!("Closed page {}", page_id);
         Ok(())
@@ -54,8 +76,8 @@ impl BrowserManager {
 
     /// Navigate to URL.
     pub async fn navigate(&self, page_id: &str, url: &str) -> Result<(), BrowserError> {
-        let session = self.get_session(page_id).await?;
-        session.navigate(url).await?;
+        self.with_page(page_id, |session| async move { session.navigate(url).await })
+            .await?;
 
         if let Some(state) = self.pages.write().await.get_mut(page_id) {
             state.url = url.to_string();
@@ -67,124 +89,156 @@ impl BrowserManager {
 
     /// Go back.
     pub async fn go_back(&self, page_id: &str) -> Result<(), BrowserError> {
-        let session = self.get_session(page_id).await?;
-        session.go_back().await?;
-        Ok(())
+        self.with_page(page_id, |session| async move { session.go_back().await })
+            .await
     }
 
     /// Go forward.
     pub async fn go_forward(&self, page_id: &str) -> Result<(), BrowserError> {
-        let session = self.get_session(page_id).await?;
-        session.go_forward().await?;
-        Ok(())
+        self.with_page(page_id, |session| async move { session.go_forward().await })
+            .await
     }
 
     /// Reload page.
     pub async fn reload(&self, page_id: &str) -> Result<(), BrowserError> {
-        let session = self.get_session(page_id).await?;
-        session.reload().await?;
-        Ok(())
+        self.with_page(page_id, |session| async move { session.reload().await })
+            .await
     }
 
     /// Get current URL.
+    ///
+    /// Also remembers it as where to re-open the page after a crash.
     pub async fn get_url(&self, page_id: &str) -> Result<String, BrowserError> {
-        let session = self.get_session(page_id).await?;
-        Ok(session.get_url().await?)
+        let url = self
+            .with_page(page_id, |session| async move { session.get_url().await })
+            .await?;
+        if let Some(state) = self.pages.write().await.get_mut(page_id) {
+            state.url = url.clone();
+        }
+        Ok(url)
     }
 
     /// Get page title.
     pub async fn get_title(&self, page_id: &str) -> Result<String, BrowserError> {
-        let session = self.get_session(page_id).await?;
-        Ok(session.get_title().await?)
+        self.with_page(page_id, |session| async move { session.get_title().await })
+            .await
     }
 
     /// Click at coordinates.
     pub async fn click(&self, page_id: &str, x: f64, y: f64) -> Result<(), BrowserError> {
-        let session = self.get_session(page_id).await?;
-        session.click(x, y).await?;
-        Ok(())
+        self.with_page(page_id, |session| async move { session.click(x, y).await })
+            .await
     }
 
     /// Click on selector.
     pub async fn click_selector(&self, page_id: &str, selector: &str) -> Result<(), BrowserError> {
-        let session = self.get_session(page_id).await?;
-        session.click_selector(selector).await?;
-        Ok(())
+        self.with_page(page_id, |session| async move {
+            session.click_selector(selector).await
+        })
+        .await
     }
 
     /// Type text.
     pub async fn type_text(&self, page_id: &str, text: &str) -> Result<(), BrowserError> {
-        let session = self.get_session(page_id).await?;
-        session.type_text(text).await?;
-        Ok(())
+        self.with_page(page_id, |session| async move { session.type_text(text).await })
+            .await
     }
 
     /// Fill input field.
     pub async fn fill(&self, page_id: &str, selector: &str, value: &str) -> Result<(), BrowserError> {
-        let session = self.get_session(page_id).await?;
-        session.fill(selector, value).await?;
-        Ok(())
+        self.with_page(page_id, |session| async move {
+            session.fill(selector, value).await
+        })
+        .await
     }
 
     /// Press key.
     pub async fn press_key(&self, page_id: &str, key: &str) -> Result<(), BrowserError> {
-        let session = self.get_session(page_id).await?;
-        session.press_key(key).await?;
-        Ok(())
+        self.with_page(page_id, |session| async move { session.press_key(key).await })
+            .await
     }
 
     /// Scroll page.
     pub async fn scroll(&self, page_id: &str, x: f64, y: f64) -> Result<(), BrowserError> {
-        let session = self.get_session(page_id).await?;
         let center_x = self.config.viewport_width as f64 / 2.0;
         let center_y = self.config.viewport_height as f64 / 2.0;
-        session.scroll(center_x, center_y, x, y).await?;
-        Ok(())
+        self.with_page(page_id, |session| async move {
+            session.scroll(center_x, center_y, x, y).await
+        })
+        .await
     }
 
     /// Take screenshot (returns base64 JPEG with quality compression).
     pub async fn screenshot(&self, page_id: &str, full_page: bool) -> Result<String, BrowserError> {
-        let session = self.get_session(page_id).await?;
-        Ok(session
-            .screenshot(ScreenshotFormat::Jpeg, Some(60), full_page, None)
-            .await?)
+        self.screenshot_with_options(page_id, full_page, ScreenshotFormat::Jpeg, Some(60))
+            .await
     }
 
     /// Take screenshot with custom format and quality.
+    ///
+    /// The configured privacy masks are blacked out of it.
     pub async fn screenshot_with_options(
         &self, page_id: &str, full_page: bool,
         format: ScreenshotFormat, quality: Option<u8>,
     ) -> Result<String, BrowserError> {
-        let session = self.get_session(page_id).await?;
-        Ok(session.screenshot(format, quality, full_page, None).await?)
+        self.with_page(page_id, |session| {
+            self.capture(page_id, session, full_page, format, quality)
+        })
+        .await
+    }
+
+    /// Take a screenshot of a page session, masking it.
+    async fn capture(
+        &self, page_id: &str, session: Arc<PageSession>, full_page: bool,
+        format: ScreenshotFormat, quality: Option<u8>,
+    ) -> Result<String, BrowserError> {
+        let masks = &self.config.privacy_masks;
+        if masks.is_empty() {
+            return Ok(session.screenshot(format, quality, full_page, None).await?);
+        }
+
+        let title = session.get_title().await?;
+        let Some(overlay) = mask_overlay_script(masks, &title) else {
+            return Ok(session.screenshot(format, quality, full_page, None).await?);
+        };
+        session.evaluate(&overlay).await?;
+        let screenshot = session.screenshot(format, quality, full_page, None).await;
+        if let Err(e) = session.evaluate(&remove_overlay_script()).await {
+            warn!("Failed to remove the privacy mask overlay of {}: {}", page_id, e);
+        }
+        Ok(screenshot?)
     }
 
     /// Get page HTML content.
     pub async fn get_content(&self, page_id: &str) -> Result<String, BrowserError> {
-        let session = self.get_session(page_id).await?;
-        Ok(session.get_content().await?)
+        self.with_page(page_id, |session| async move { session.get_content().await })
+            .await
     }
 
     /// Execute JavaScript.
     pub async fn evaluate(&self, page_id: &str, script: &str) -> Result<serde_json::Value, BrowserError> {
-        let session = self.get_session(page_id).await?;
-        Ok(session.evaluate(script).await?)
+        self.with_page(page_id, |session| async move { session.evaluate(script).await })
+            .await
     }
 
     /// Wait for selector.
     pub async fn wait_for_selector(
         &self, page_id: &str, selector: &str, timeout_ms: Option<u32>,
     ) -> Result<(), BrowserError> {
-        let session = self.get_session(page_id).await?;
-        session.wait_for_selector(selector, timeout_ms).await?;
+        self.with_page(page_id, |session| async move {
+            session.wait_for_selector(selector, timeout_ms).await
+        })
+        .await?;
         Ok(())
     }
 
     /// Get enhanced DOM tree with clickability analysis.
     pub async fn get_dom_tree(&self, page_id: &str) -> Result<EnhancedNodeTree, BrowserError> {
-        let session = self.get_session(page_id).await?;
-        let url = session.get_url().await?;
-        let title = session.get_title().await?;
+        let (url, title) = self
+            .with_page(page_id, |session| async move {
+                Ok::<_, CdpError>((session.get_url().await?, session.get_title().await?))
+            })
+            .await?;
 
         Ok(EnhancedNodeTree {
             roots: vec![],
@@ -209,8 +263,6 @@ impl BrowserManager {
     pub async fn element_at(
         &self, page_id: &str, x: f64, y: f64,
     ) -> Result<Option<serde_json::Value>, BrowserError> {
-        let session = self.get_session(page_id).await?;
-
         let script = format!(
             "JSON.stringify((function() {{
                 const el = document.elementFromPoint({}, {});
@@ -225,7 +277,10 @@ impl BrowserManager {
             x, y
         );
 
-        let result = session.evaluate(&script).await?;
+        let script = script.as_str();
+        let result = self
+            .with_page(page_id, |session| async move { session.evaluate(script).await })
+            .await?;
         if result.is_null() {
             Ok(None)
         } else if let Some(s) = result.as_str() {
@@ -240,4 +295,15 @@ impl BrowserManager {
         let dom_tree = self.get_dom_tree(page_id).await?;
         Ok(dom_tree.to_llm_string())
     }
+
+    /// Get the interactive elements added, removed and changed since the
+    /// page's last incremental DOM scan.
+    pub async fn get_dom_diff(&self, page_id: &str) -> Result<DomDiff, BrowserError> {
+        let dom_tree = self.get_dom_tree(page_id).await?;
+        let mut pages = self.pages.write().await;
+        let state = pages
+            .get_mut(page_id)
+            .ok_or_else(|| BrowserError::PageNotFound(page_id.to_string()))?;
+        Ok(state.dom.diff(&dom_tree))
+    }
 }
@@ -0,0 +1,54 @@
+//! Privacy masks drawn over a page while it is captured.
+//!
+//! Screenshots are taken by the browser, so instead of editing the image,
+//! black boxes are laid over the masked regions (or the whole page, when
+//! its title is masked) for the duration of the capture.
+
+use autohands_protocols::types::PrivacyMasks;
+
+/// ID of the overlay element.
+const OVERLAY_ID: &str = "__autohands_privacy_mask";
+
+/// Script adding the overlay for `masks` to a page titled `title`, or
+/// `None` when nothing on the page is masked.
+pub(crate) fn mask_overlay_script(masks: &PrivacyMasks, title: &str) -> Option<String> {
+    let boxes: Vec<String> = if masks.masks_title(title) {
+        vec!["inset:0".to_string()]
+    } else {
+        masks
+            .regions
+            .iter()
+            .map(|r| {
+                format!(
+                    "left:{}px;top:{}px;width:{}px;height:{}px",
+                    r.x, r.y, r.width, r.height
+                )
+            })
+            .collect()
+    };
+    if boxes.is_empty() {
+        return None;
+    }
+    let boxes = serde_json::to_string(&boxes).unwrap_or_default();
+    Some(format!(
+        "(function() {{
+            const overlay = document.createElement('div');
+            overlay.id = '{id}';
+            for (const style of {boxes}) {{
+                const mask = document.createElement('div');
+                mask.style.cssText = 'position:fixed;background:#000;pointer-events:none;'
+                    + 'z-index:2147483647;' + style;
+                overlay.appendChild(mask);
+            }}
+            document.documentElement.appendChild(overlay);
+            return true;
+        }})()",
+        id = OVERLAY_ID,
+        boxes = boxes
+    ))
+}
+
+/// Script removing the overlay.
+pub(crate) fn remove_overlay_script() -> String {
+    format!("document.getElementById('{}')?.remove()", OVERLAY_ID)
+}
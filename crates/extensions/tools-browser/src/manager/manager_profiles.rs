@@ -0,0 +1,190 @@
+//! BrowserManager named profiles: isolated login states in one browser.
+//!
+//! Pages opened in a named profile share a browser context of their own,
+//! so their cookies, storage and cache are apart from the default profile
+//! and from every other named profile. A context lives as long as Chrome
+//! does, so its cookies are saved to `<profiles_dir>/<name>/cookies.json`
+//! when its pages or the browser close and when cookie tools change them,
+//! and loaded back when the profile is next opened. Local and session
+//! storage are not kept.
+
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+use tracing::{debug, warn};
+
+use super::{BrowserError, BrowserManager};
+use crate::cdp::{CdpClient, PageSession};
+
+/// Fields of a cookie that `Storage.setCookies` takes back.
+const COOKIE_FIELDS: &[&str] = &[
+    "name",
+    "value",
+    "domain",
+    "path",
+    "secure",
+    "httpOnly",
+    "sameSite",
+    "priority",
+    "sourceScheme",
+    "sourcePort",
+    "partitionKey",
+];
+
+impl BrowserManager {
+    /// Named profiles pages can be opened in.
+    pub fn profiles(&self) -> &[String] {
+        &self.config.profiles
+    }
+
+    /// Open a page at `url` in a profile, the default one if `None`.
+    pub(super) async fn open_session(
+        &self,
+        client: &CdpClient,
+        url: &str,
+        profile: Option<&str>,
+    ) -> Result<PageSession, BrowserError> {
+        match profile {
+            None => Ok(client.new_page(Some(url)).await?),
+            Some(name) => {
+                let context_id = self.profile_context(client, name).await?;
+                Ok(client.new_context_page(url, &context_id).await?)
+            }
+        }
+    }
+
+    /// The browser context of a named profile, created with its saved
+    /// cookies the first time the profile is used.
+    async fn profile_context(
+        &self,
+        client: &CdpClient,
+        name: &str,
+    ) -> Result<String, BrowserError> {
+        if !self.config.profiles.iter().any(|p| p == name) {
+            return Err(BrowserError::ActionFailed(
+                if self.config.profiles.is_empty() {
+                    format!(
+                        "Unknown profile '{}': no browser profiles are configured",
+                        name
+                    )
+                } else {
+                    format!(
+                        "Unknown profile '{}' (known: {})",
+                        name,
+                        self.config.profiles.join(", ")
+                    )
+                },
+            ));
+        }
+
+        let mut contexts = self.profile_contexts.lock().await;
+        if let Some(context_id) = contexts.get(name) {
+            return Ok(context_id.clone());
+        }
+        let context_id = client.create_browser_context().await?;
+
+        let dir = self.profile_dir(name);
+        if let Err(e) = client
+            .enable_downloads(&self.config.get_download_dir(), Some(&context_id))
+            .await
+        {
+            warn!(
+                "Downloads of profile {} go to Chrome's default folder: {}",
+                name, e
+            );
+        }
+        let cookies = load_cookies(&dir.join("cookies.json"));
+        if !cookies.is_empty() {
+            if let Err(e) = client.set_context_cookies(&context_id, &cookies).await {
+                warn!("Failed to restore the cookies of profile {}: {}", name, e);
+            }
+        }
+        debug!(
+            "Opened profile {} in context {} with {} cookies",
+            name,
+            context_id,
+            cookies.len()
+        );
+        contexts.insert(name.to_string(), context_id.clone());
+        Ok(context_id)
+    }
+
+    /// Save the cookies of a named profile opened in this browser.
+    pub async fn save_profile(&self, name: &str) -> Result<(), BrowserError> {
+        let context_id = self.profile_contexts.lock().await.get(name).cloned();
+        let Some(context_id) = context_id else {
+            return Ok(());
+        };
+        let cookies = self
+            .client()
+            .await?
+            .get_context_cookies(Some(&context_id))
+            .await?;
+
+        let dir = self.profile_dir(name);
+        let text = serde_json::to_string_pretty(&cookies)
+            .map_err(|e| BrowserError::ActionFailed(e.to_string()))?;
+        std::fs::create_dir_all(&dir)
+            .and_then(|()| std::fs::write(dir.join("cookies.json"), text))
+            .map_err(|e| {
+                BrowserError::ActionFailed(format!(
+                    "Failed to save profile {} to {}: {}",
+                    name,
+                    dir.display(),
+                    e
+                ))
+            })?;
+        debug!("Saved {} cookies of profile {}", cookies.len(), name);
+        Ok(())
+    }
+
+    /// Save the cookies of every named profile opened in this browser.
+    pub async fn save_profiles(&self) {
+        let names: Vec<String> = self.profile_contexts.lock().await.keys().cloned().collect();
+        for name in names {
+            if let Err(e) = self.save_profile(&name).await {
+                warn!("{}", e);
+            }
+        }
+    }
+
+    /// Where a named profile's state is kept.
+    fn profile_dir(&self, name: &str) -> PathBuf {
+        self.config.get_profiles_dir().join(name)
+    }
+}
+
+/// Cookies saved at `path`, as `Storage.setCookies` takes them.
+///
+/// A missing or unreadable file is an empty profile.
+pub(super) fn load_cookies(path: &Path) -> Vec<Value> {
+    let Ok(text) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let cookies: Vec<Value> = match serde_json::from_str(&text) {
+        Ok(cookies) => cookies,
+        Err(e) => {
+            warn!("Ignoring unreadable cookies at {}: {}", path.display(), e);
+            return Vec::new();
+        }
+    };
+    cookies.iter().map(cookie_param).collect()
+}
+
+/// A cookie as read from the browser turned into one it can be given.
+///
+/// Session cookies are kept, as the browser session they belonged to is
+/// being resumed; expired ones are left for the browser to drop.
+pub(super) fn cookie_param(cookie: &Value) -> Value {
+    let mut param = serde_json::Map::new();
+    for field in COOKIE_FIELDS {
+        if let Some(value) = cookie.get(*field) {
+            param.insert(field.to_string(), value.clone());
+        }
+    }
+    let session = cookie["session"].as_bool().unwrap_or(false);
+    if let Some(expires) = cookie["expires"].as_f64().filter(|e| !session && *e > 0.0) {
+        param.insert("expires".to_string(), json!(expires));
+    }
+    Value::Object(param)
+}
@@ -0,0 +1,185 @@
+//! BrowserManager crash recovery: restarting Chrome and re-opening pages.
+//!
+//! When the CDP connection drops (Chrome exited, crashed or hung up) or a
+//! page's renderer crashes, the failed page call restarts what broke and
+//! is retried once. Chrome is relaunched with the same persistent profile,
+//! so logins survive, and every tracked page is re-opened at its last
+//! known URL under the same page id; pages of named profiles get a new
+//! browser context with the profile's last saved cookies. Restarts are capped by
+//! [`BrowserManagerConfig::max_restarts`](super::BrowserManagerConfig::max_restarts)
+//! so a browser that keeps crashing is reported instead of looped on.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tracing::{info, warn};
+
+use super::{BrowserError, BrowserManager};
+use crate::cdp::{CdpClient, PageSession};
+
+/// Window over which restarts are counted.
+pub const RESTART_WINDOW: Duration = Duration::from_secs(600);
+
+impl BrowserManager {
+    /// Run `op` on a page's session.
+    ///
+    /// If it fails because the browser or the page's renderer went away,
+    /// recover and run it once more on the new session.
+    pub(super) async fn with_page<T, E, F, Fut>(
+        &self,
+        page_id: &str,
+        op: F,
+    ) -> Result<T, BrowserError>
+    where
+        F: Fn(Arc<PageSession>) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: Into<BrowserError>,
+    {
+        let session = self.get_session(page_id).await?;
+        let error = match op(session.clone()).await {
+            Ok(value) => return Ok(value),
+            Err(e) => e.into(),
+        };
+        if self.config.max_restarts == 0 || !self.recover(page_id, &session).await? {
+            return Err(error);
+        }
+        info!("Retrying the call on {} after: {}", page_id, error);
+        let session = self.get_session(page_id).await?;
+        op(session).await.map_err(Into::into)
+    }
+
+    /// Recover a page whose `failed` session stopped working.
+    ///
+    /// Returns whether the page has a new session to retry on.
+    async fn recover(
+        &self,
+        page_id: &str,
+        failed: &Arc<PageSession>,
+    ) -> Result<bool, BrowserError> {
+        let _recovery = self.recovery.lock().await;
+
+        // Another call recovered (or closed) the page meanwhile
+        let current = self.get_session(page_id).await.ok();
+        if !current.is_some_and(|session| Arc::ptr_eq(&session, failed)) {
+            return Ok(true);
+        }
+        let Some(client) = self.client.read().await.clone() else {
+            return Ok(false);
+        };
+        if !client.is_connected() {
+            self.restart_browser().await?;
+            return Ok(true);
+        }
+        if client.is_crashed(failed.session_id()) {
+            self.reopen_page(&client, page_id, failed).await?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// Relaunch Chrome and re-open the tracked pages.
+    ///
+    /// Pages that cannot be re-opened are dropped.
+    pub(super) async fn restart_browser(&self) -> Result<(), BrowserError> {
+        self.take_restart()?;
+        warn!("Lost the connection to Chrome, restarting it");
+
+        let _ = self.client.write().await.take();
+        if let Some(mut child) = self.chrome_process.write().await.take() {
+            // Still running if it hung up rather than exited
+            let _ = child.kill().await;
+        }
+        // Browser contexts went with the old browser
+        self.profile_contexts.lock().await.clear();
+        self.connect().await?;
+        let client = self.client().await?;
+
+        let tracked: Vec<(String, String, Option<String>)> = self
+            .pages
+            .read()
+            .await
+            .iter()
+            .map(|(id, state)| (id.clone(), state.url.clone(), state.profile.clone()))
+            .collect();
+        for (page_id, url, profile) in tracked {
+            match self.open_session(&client, &url, profile.as_deref()).await {
+                Ok(session) => {
+                    if let Some(state) = self.pages.write().await.get_mut(&page_id) {
+                        state.session = Arc::new(session);
+                        state.dom.reset();
+                    }
+                }
+                Err(e) => {
+                    warn!("Failed to re-open {} at {}: {}", page_id, url, e);
+                    self.pages.write().await.remove(&page_id);
+                }
+            }
+        }
+        // Pages were re-opened in no particular order
+        if let Some(active) = self.active_tab().await {
+            if let Ok(session) = self.get_session(&active).await {
+                let _ = client.activate_page(session.target_id()).await;
+            }
+        }
+        info!("Chrome restarted");
+        Ok(())
+    }
+
+    /// Replace the page of a crashed renderer with a new one at its URL.
+    async fn reopen_page(
+        &self,
+        client: &CdpClient,
+        page_id: &str,
+        crashed: &PageSession,
+    ) -> Result<(), BrowserError> {
+        self.take_restart()?;
+
+        // The target still knows where it was, even after clicks
+        let (tracked, profile) = match self.pages.read().await.get(page_id) {
+            Some(state) => (Some(state.url.clone()), state.profile.clone()),
+            None => (None, None),
+        };
+        let url = client
+            .list_pages()
+            .await
+            .ok()
+            .and_then(|pages| pages.into_iter().find(|p| p.id == crashed.target_id()))
+            .map(|page| page.url)
+            .filter(|url| !url.is_empty())
+            .or(tracked)
+            .unwrap_or_else(|| "about:blank".to_string());
+        warn!("Renderer of {} crashed, re-opening it at {}", page_id, url);
+
+        let _ = client.close_page(crashed.target_id()).await;
+        let session = self.open_session(client, &url, profile.as_deref()).await?;
+        if let Some(state) = self.pages.write().await.get_mut(page_id) {
+            state.session = Arc::new(session);
+            state.url = url;
+            state.dom.reset();
+        }
+        Ok(())
+    }
+
+    /// Count a restart, refusing it once `max_restarts` were made within
+    /// [`RESTART_WINDOW`].
+    pub(super) fn take_restart(&self) -> Result<(), BrowserError> {
+        let now = Instant::now();
+        let mut restarts = self.restarts.lock();
+        while restarts
+            .front()
+            .is_some_and(|at| now.duration_since(*at) >= RESTART_WINDOW)
+        {
+            restarts.pop_front();
+        }
+        if restarts.len() >= self.config.max_restarts as usize {
+            return Err(BrowserError::Crashed(format!(
+                "restarted {} times in the last {} minutes, not restarting again",
+                restarts.len(),
+                RESTART_WINDOW.as_secs() / 60
+            )));
+        }
+        restarts.push_back(now);
+        Ok(())
+    }
+}
@@ -0,0 +1,258 @@
+//! BrowserManager cookies and web storage of pages.
+//!
+//! Cookies are read and written through the page's session, so they are
+//! those of the page's profile. Changing the cookies of a named profile
+//! saves it.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tracing::{debug, warn};
+
+use super::{BrowserError, BrowserManager};
+
+/// Which web storage of a page to use.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageKind {
+    /// `localStorage`, kept across visits.
+    #[default]
+    Local,
+    /// `sessionStorage`, kept for the tab.
+    Session,
+}
+
+impl StorageKind {
+    fn object(self) -> &'static str {
+        match self {
+            StorageKind::Local => "window.localStorage",
+            StorageKind::Session => "window.sessionStorage",
+        }
+    }
+}
+
+impl BrowserManager {
+    /// Cookies sent to the page's URL, or to `urls` when given.
+    pub async fn get_cookies(
+        &self,
+        page_id: &str,
+        urls: &[String],
+    ) -> Result<Vec<Value>, BrowserError> {
+        let params = (!urls.is_empty()).then(|| json!({ "urls": urls }));
+        let params = &params;
+        let result = self
+            .with_page(page_id, |session| async move {
+                session.call("Network.getCookies", params.clone()).await
+            })
+            .await?;
+        Ok(result["cookies"].as_array().cloned().unwrap_or_default())
+    }
+
+    /// Set cookies on a page's profile.
+    ///
+    /// Cookies naming neither a `url` nor a `domain` are set for the page's
+    /// URL.
+    pub async fn set_cookies(&self, page_id: &str, cookies: &[Value]) -> Result<(), BrowserError> {
+        let page_url = self.get_url(page_id).await?;
+        let cookies = cookie_params(cookies, &page_url).map_err(BrowserError::ActionFailed)?;
+        let params = json!({ "cookies": cookies });
+        let params = &params;
+        self.with_page(page_id, |session| async move {
+            session
+                .call("Network.setCookies", Some(params.clone()))
+                .await
+        })
+        .await?;
+        debug!("Set {} cookies on {}", cookies.len(), page_id);
+        self.save_page_profile(page_id).await;
+        Ok(())
+    }
+
+    /// Delete the cookies sent to a page's URL, only those called `name`
+    /// if given, or with `all` every cookie of the page's profile.
+    ///
+    /// Returns how many were deleted.
+    pub async fn clear_cookies(
+        &self,
+        page_id: &str,
+        name: Option<&str>,
+        all: bool,
+    ) -> Result<usize, BrowserError> {
+        let cleared = if all {
+            let context_id = self.page_context(page_id).await?;
+            let client = self.client().await?;
+            let count = client
+                .get_context_cookies(context_id.as_deref())
+                .await?
+                .len();
+            client.clear_context_cookies(context_id.as_deref()).await?;
+            count
+        } else {
+            let cookies: Vec<Value> = self
+                .get_cookies(page_id, &[])
+                .await?
+                .into_iter()
+                .filter(|cookie| name.is_none_or(|name| cookie["name"] == name))
+                .collect();
+            for cookie in &cookies {
+                let params = json!({
+                    "name": cookie["name"],
+                    "domain": cookie["domain"],
+                    "path": cookie["path"],
+                });
+                let params = &params;
+                self.with_page(page_id, |session| async move {
+                    session
+                        .call("Network.deleteCookies", Some(params.clone()))
+                        .await
+                })
+                .await?;
+            }
+            cookies.len()
+        };
+        debug!("Cleared {} cookies of {}", cleared, page_id);
+        self.save_page_profile(page_id).await;
+        Ok(cleared)
+    }
+
+    /// Items of a page's storage as an object, or the value of `key`
+    /// (`null` when unset).
+    pub async fn get_storage(
+        &self,
+        page_id: &str,
+        kind: StorageKind,
+        key: Option<&str>,
+    ) -> Result<Value, BrowserError> {
+        self.evaluate(page_id, &storage_get_script(kind, key)).await
+    }
+
+    /// Set an item of a page's storage.
+    pub async fn set_storage(
+        &self,
+        page_id: &str,
+        kind: StorageKind,
+        key: &str,
+        value: &str,
+    ) -> Result<(), BrowserError> {
+        self.evaluate(page_id, &storage_set_script(kind, key, value))
+            .await?;
+        Ok(())
+    }
+
+    /// Remove an item of a page's storage, or every item if `key` is
+    /// `None`.
+    ///
+    /// Returns how many were removed.
+    pub async fn clear_storage(
+        &self,
+        page_id: &str,
+        kind: StorageKind,
+        key: Option<&str>,
+    ) -> Result<u64, BrowserError> {
+        let removed = self
+            .evaluate(page_id, &storage_clear_script(kind, key))
+            .await?;
+        Ok(removed.as_u64().unwrap_or(0))
+    }
+
+    /// Browser context of a page's profile, `None` for the default one.
+    async fn page_context(&self, page_id: &str) -> Result<Option<String>, BrowserError> {
+        let profile = self
+            .pages
+            .read()
+            .await
+            .get(page_id)
+            .ok_or_else(|| BrowserError::PageNotFound(page_id.to_string()))?
+            .profile
+            .clone();
+        match profile {
+            Some(profile) => self
+                .profile_contexts
+                .lock()
+                .await
+                .get(&profile)
+                .cloned()
+                .map(Some)
+                .ok_or_else(|| {
+                    BrowserError::ActionFailed(format!("Profile {} is not open", profile))
+                }),
+            None => Ok(None),
+        }
+    }
+
+    /// Save the page's profile, if it is a named one.
+    async fn save_page_profile(&self, page_id: &str) {
+        let profile = self
+            .pages
+            .read()
+            .await
+            .get(page_id)
+            .and_then(|state| state.profile.clone());
+        if let Some(profile) = profile {
+            if let Err(e) = self.save_profile(&profile).await {
+                warn!("{}", e);
+            }
+        }
+    }
+}
+
+/// Cookies as `Network.setCookies` takes them, set for `page_url` unless
+/// they name a `url` or `domain`.
+pub(super) fn cookie_params(cookies: &[Value], page_url: &str) -> Result<Vec<Value>, String> {
+    cookies
+        .iter()
+        .enumerate()
+        .map(|(i, cookie)| {
+            let mut cookie = cookie
+                .as_object()
+                .cloned()
+                .ok_or_else(|| format!("Cookie {} is not an object", i))?;
+            if !cookie.get("name").is_some_and(Value::is_string)
+                || !cookie.get("value").is_some_and(Value::is_string)
+            {
+                return Err(format!("Cookie {} needs a string name and value", i));
+            }
+            if !cookie.contains_key("url") && !cookie.contains_key("domain") {
+                cookie.insert("url".to_string(), json!(page_url));
+            }
+            Ok(Value::Object(cookie))
+        })
+        .collect()
+}
+
+pub(super) fn storage_get_script(kind: StorageKind, key: Option<&str>) -> String {
+    let storage = kind.object();
+    match key {
+        Some(key) => format!("{}.getItem({})", storage, json!(key)),
+        None => format!(
+            "(() => {{ const s = {}; const items = {{}}; \
+             for (let i = 0; i < s.length; i++) {{ const k = s.key(i); items[k] = s.getItem(k); }} \
+             return items; }})()",
+            storage
+        ),
+    }
+}
+
+pub(super) fn storage_set_script(kind: StorageKind, key: &str, value: &str) -> String {
+    format!(
+        "{}.setItem({}, {})",
+        kind.object(),
+        json!(key),
+        json!(value)
+    )
+}
+
+pub(super) fn storage_clear_script(kind: StorageKind, key: Option<&str>) -> String {
+    let storage = kind.object();
+    match key {
+        Some(key) => format!(
+            "(() => {{ const s = {}; const had = s.getItem({key}) !== null; \
+             s.removeItem({key}); return had ? 1 : 0; }})()",
+            storage,
+            key = json!(key)
+        ),
+        None => format!(
+            "(() => {{ const s = {}; const n = s.length; s.clear(); return n; }})()",
+            storage
+        ),
+    }
+}
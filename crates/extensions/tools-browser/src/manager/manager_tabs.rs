@@ -0,0 +1,170 @@
+//! BrowserManager tab management: opening, listing, switching and closing tabs.
+//!
+//! A tab is a tracked page: its page id is its tab id, valid with every page
+//! tool until the tab is closed, including across crash recovery. The active
+//! tab is the one last opened or switched to.
+
+use std::sync::Arc;
+
+use serde::Serialize;
+use tracing::{debug, warn};
+
+use super::{BrowserError, BrowserManager};
+use crate::cdp::PageSession;
+
+/// An open tab.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TabInfo {
+    /// Page id of the tab.
+    pub tab_id: String,
+    pub url: String,
+    pub title: String,
+    pub active: bool,
+    /// Named profile of the tab, `None` for the default one.
+    pub profile: Option<String>,
+}
+
+impl BrowserManager {
+    /// Open a tab at `url`, in a named profile or the default one.
+    ///
+    /// Unless `activate` is set, the active tab is brought back to the front
+    /// so the new one stays in the background.
+    pub async fn new_tab(
+        &self,
+        url: &str,
+        activate: bool,
+        profile: Option<&str>,
+    ) -> Result<String, BrowserError> {
+        let previous = self.active_tab().await;
+        let tab_id = self.new_page_in(url, profile).await?;
+        if !activate {
+            if let Some(previous) = previous {
+                self.activate(&previous).await?;
+            }
+        }
+        Ok(tab_id)
+    }
+
+    /// Id of the active tab, if any tab is open.
+    pub async fn active_tab(&self) -> Option<String> {
+        self.pages
+            .read()
+            .await
+            .iter()
+            .max_by_key(|(_, state)| state.last_active)
+            .map(|(id, _)| id.clone())
+    }
+
+    /// Open tabs, in the order they were opened.
+    ///
+    /// A tab that does not answer is listed at its last known URL.
+    pub async fn list_tabs(&self) -> Vec<TabInfo> {
+        let active = self.active_tab().await;
+        let mut tabs: Vec<(u64, String, Arc<PageSession>, String, Option<String>)> = self
+            .pages
+            .read()
+            .await
+            .iter()
+            .map(|(id, state)| {
+                (
+                    state.opened,
+                    id.clone(),
+                    state.session.clone(),
+                    state.url.clone(),
+                    state.profile.clone(),
+                )
+            })
+            .collect();
+        tabs.sort_by_key(|(opened, ..)| *opened);
+
+        let mut list = Vec::with_capacity(tabs.len());
+        for (_, tab_id, session, known_url, profile) in tabs {
+            let url = match session.get_url().await {
+                Ok(url) => {
+                    if let Some(state) = self.pages.write().await.get_mut(&tab_id) {
+                        state.url = url.clone();
+                    }
+                    url
+                }
+                Err(_) => known_url,
+            };
+            let title = session.get_title().await.unwrap_or_default();
+            list.push(TabInfo {
+                active: active.as_deref() == Some(tab_id.as_str()),
+                tab_id,
+                url,
+                title,
+                profile,
+            });
+        }
+        list
+    }
+
+    /// Bring a tab to the front and make it the active tab.
+    pub async fn switch_tab(&self, tab_id: &str) -> Result<TabInfo, BrowserError> {
+        self.activate(tab_id).await?;
+        let url = self.get_url(tab_id).await?;
+        let title = self.get_title(tab_id).await?;
+        let profile = self
+            .pages
+            .read()
+            .await
+            .get(tab_id)
+            .and_then(|state| state.profile.clone());
+        debug!("Switched to tab {}: {}", tab_id, url);
+        Ok(TabInfo {
+            tab_id: tab_id.to_string(),
+            url,
+            title,
+            active: true,
+            profile,
+        })
+    }
+
+    /// Close a tab, the active one when `tab_id` is `None`.
+    ///
+    /// Returns the closed tab's id and the tab active afterwards: when the
+    /// active tab is closed, the one used before it is brought to the front.
+    pub async fn close_tab(
+        &self,
+        tab_id: Option<&str>,
+    ) -> Result<(String, Option<String>), BrowserError> {
+        let active = self.active_tab().await;
+        let tab_id = match tab_id {
+            Some(tab_id) => tab_id.to_string(),
+            None => active
+                .clone()
+                .ok_or_else(|| BrowserError::PageNotFound("no open tabs".to_string()))?,
+        };
+        if !self.pages.read().await.contains_key(&tab_id) {
+            return Err(BrowserError::PageNotFound(tab_id));
+        }
+
+        self.close_page(&tab_id).await?;
+        let next = self.active_tab().await;
+        if active.as_deref() == Some(tab_id.as_str()) {
+            if let Some(next) = &next {
+                if let Err(e) = self.activate(next).await {
+                    warn!("Failed to bring tab {} to the front: {}", next, e);
+                }
+            }
+        }
+        Ok((tab_id, next))
+    }
+
+    /// Bring a tab to the front and mark it active.
+    async fn activate(&self, tab_id: &str) -> Result<(), BrowserError> {
+        // Fail on unknown tabs before launching or restarting Chrome
+        self.get_session(tab_id).await?;
+        self.ensure_connected().await?;
+        let session = self.get_session(tab_id).await?;
+        self.client()
+            .await?
+            .activate_page(session.target_id())
+            .await?;
+        if let Some(state) = self.pages.write().await.get_mut(tab_id) {
+            state.last_active = self.next_activation();
+        }
+        Ok(())
+    }
+}
@@ -7,6 +7,7 @@ fn test_config_default() {
     assert_eq!(config.viewport_width, 1280);
     assert_eq!(config.viewport_height, 720);
     assert!(!config.headless);
+    assert_eq!(config.max_restarts, 3);
 }
 
 #[test]
@@ -22,6 +23,49 @@ fn test_config_profile_dir() {
     assert!(profile.ends_with(".autohands/browser-profile"));
 }
 
+#[test]
+fn test_config_download_dir() {
+    let config = BrowserManagerConfig::default();
+    assert!(config.get_download_dir().ends_with(".autohands/downloads"));
+
+    let config = BrowserManagerConfig {
+        download_dir: Some(std::path::PathBuf::from("/tmp/invoices")),
+        ..Default::default()
+    };
+    assert_eq!(config.get_download_dir(), std::path::PathBuf::from("/tmp/invoices"));
+}
+
+#[test]
+fn test_config_profiles_dir() {
+    let config = BrowserManagerConfig::default();
+    assert!(config.profiles.is_empty());
+    assert!(config
+        .get_profiles_dir()
+        .ends_with(".autohands/browser-profiles"));
+}
+
+#[test]
+fn test_config_engine() {
+    let config = BrowserManagerConfig::default();
+    assert_eq!(config.engine, BrowserEngine::Chrome);
+    assert!(config
+        .get_firefox_profile_dir()
+        .ends_with(".autohands/firefox-profile"));
+
+    let engine: BrowserEngine = serde_json::from_value(serde_json::json!("firefox")).unwrap();
+    assert_eq!(engine, BrowserEngine::Firefox);
+}
+
+#[tokio::test]
+async fn test_downloads_without_connect() {
+    let manager = BrowserManager::new(BrowserManagerConfig::default());
+    assert!(manager.list_downloads().await.is_empty());
+    let result = manager
+        .wait_for_download(std::time::Duration::from_millis(10))
+        .await;
+    assert!(matches!(result, Err(BrowserError::NotConnected)));
+}
+
 #[test]
 fn test_browser_error_display() {
     let err = BrowserError::ConnectionFailed("timeout".to_string());
@@ -34,6 +78,20 @@ fn test_browser_error_display() {
     assert_eq!(err.to_string(), "Failed to launch Chrome: permission denied");
 }
 
+#[test]
+fn test_browser_error_from_bidi() {
+    use crate::bidi::BidiError;
+
+    let err = BrowserError::from(BidiError::Protocol {
+        error: "no such frame".to_string(),
+        message: "context abc".to_string(),
+    });
+    assert!(matches!(err, BrowserError::PageNotFound(_)));
+
+    let err = BrowserError::from(BidiError::SessionClosed);
+    assert!(matches!(err, BrowserError::NotConnected));
+}
+
 #[test]
 fn test_find_chrome() {
     let _result = BrowserManager::find_chrome();
@@ -51,3 +109,181 @@ async fn test_list_pages_empty() {
     let manager = BrowserManager::new(BrowserManagerConfig::default());
     assert!(manager.list_pages().await.is_empty());
 }
+
+#[test]
+fn test_privacy_mask_overlay() {
+    use autohands_protocols::types::{MaskRegion, PrivacyMasks};
+    use super::manager_privacy::mask_overlay_script;
+
+    let masks = PrivacyMasks {
+        regions: vec![MaskRegion::new(10, 20, 300, 40)],
+        window_titles: vec!["Inbox".to_string()],
+    };
+    let script = mask_overlay_script(&masks, "Search").unwrap();
+    assert!(script.contains("left:10px;top:20px;width:300px;height:40px"));
+    assert!(!script.contains("inset:0"));
+
+    let script = mask_overlay_script(&masks, "Inbox (3) - Mail").unwrap();
+    assert!(script.contains("inset:0"));
+
+    assert!(mask_overlay_script(&PrivacyMasks::default(), "Inbox").is_none());
+}
+
+#[test]
+fn test_restart_budget() {
+    let manager = BrowserManager::new(BrowserManagerConfig {
+        max_restarts: 2,
+        ..Default::default()
+    });
+    assert!(manager.take_restart().is_ok());
+    assert!(manager.take_restart().is_ok());
+    let err = manager.take_restart().unwrap_err();
+    assert!(matches!(err, BrowserError::Crashed(_)));
+    assert!(err.to_string().contains("restarted 2 times"));
+
+    // Restarts older than the window no longer count
+    if let Some(expired) = std::time::Instant::now().checked_sub(RESTART_WINDOW) {
+        manager.restarts.lock().iter_mut().for_each(|at| *at = expired);
+        assert!(manager.take_restart().is_ok());
+    }
+}
+
+#[tokio::test]
+async fn test_with_page_unknown_page() {
+    let manager = BrowserManager::new(BrowserManagerConfig::default());
+    let result = manager.navigate("page_1", "https://example.com").await;
+    assert!(matches!(result, Err(BrowserError::PageNotFound(_))));
+}
+
+#[tokio::test]
+async fn test_tabs_without_pages() {
+    let manager = BrowserManager::new(BrowserManagerConfig::default());
+    assert!(manager.active_tab().await.is_none());
+    assert!(manager.list_tabs().await.is_empty());
+
+    // Unknown tabs fail without launching Chrome
+    let result = manager.switch_tab("page_1").await;
+    assert!(matches!(result, Err(BrowserError::PageNotFound(_))));
+    let result = manager.close_tab(Some("page_1")).await;
+    assert!(matches!(result, Err(BrowserError::PageNotFound(_))));
+    let err = manager.close_tab(None).await.unwrap_err();
+    assert!(err.to_string().contains("no open tabs"));
+}
+
+#[test]
+fn test_tab_info_serialize() {
+    let tab = TabInfo {
+        tab_id: "page_2".to_string(),
+        url: "https://example.com/".to_string(),
+        title: "Example".to_string(),
+        active: true,
+        profile: Some("work".to_string()),
+    };
+    let json = serde_json::to_value(&tab).unwrap();
+    assert_eq!(json["tab_id"], "page_2");
+    assert_eq!(json["active"], true);
+    assert_eq!(json["profile"], "work");
+}
+
+#[tokio::test]
+async fn test_capture_unknown_page() {
+    let manager = BrowserManager::new(BrowserManagerConfig::default());
+    let result = manager.start_capture("page_1", Some("*/api/*"), 100).await;
+    assert!(matches!(result, Err(BrowserError::PageNotFound(_))));
+    let result = manager.stop_capture("page_1", false).await;
+    assert!(matches!(result, Err(BrowserError::PageNotFound(_))));
+}
+
+#[test]
+fn test_saved_cookies_restore() {
+    use super::manager_profiles::load_cookies;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("cookies.json");
+    assert!(load_cookies(&path).is_empty());
+
+    let saved = serde_json::json!([
+        {"name": "sid", "value": "abc", "domain": ".example.com", "path": "/",
+         "expires": 1893456000.0, "size": 6, "httpOnly": true, "secure": true,
+         "session": false, "sameSite": "Lax", "priority": "Medium"},
+        {"name": "tmp", "value": "1", "domain": "example.com", "path": "/",
+         "expires": -1, "size": 4, "httpOnly": false, "secure": false, "session": true}
+    ]);
+    std::fs::write(&path, saved.to_string()).unwrap();
+    let cookies = load_cookies(&path);
+    assert_eq!(cookies.len(), 2);
+    assert_eq!(cookies[0]["expires"], 1893456000.0);
+    assert_eq!(cookies[0]["sameSite"], "Lax");
+    assert!(cookies[0].get("size").is_none());
+    assert!(cookies[0].get("session").is_none());
+    assert!(cookies[1].get("expires").is_none());
+
+    std::fs::write(&path, "not json").unwrap();
+    assert!(load_cookies(&path).is_empty());
+}
+
+#[test]
+fn test_cookie_params() {
+    use super::manager_storage::cookie_params;
+
+    let cookies = vec![
+        serde_json::json!({"name": "a", "value": "1"}),
+        serde_json::json!({"name": "b", "value": "2", "domain": ".example.com"}),
+    ];
+    let params = cookie_params(&cookies, "https://example.com/app").unwrap();
+    assert_eq!(params[0]["url"], "https://example.com/app");
+    assert!(params[1].get("url").is_none());
+
+    let err =
+        cookie_params(&[serde_json::json!({"name": "a"})], "https://example.com").unwrap_err();
+    assert!(err.contains("Cookie 0"));
+    assert!(cookie_params(&[serde_json::json!("a=1")], "https://example.com").is_err());
+}
+
+#[test]
+fn test_storage_scripts() {
+    use super::manager_storage::{storage_clear_script, storage_get_script, storage_set_script};
+
+    let script = storage_get_script(StorageKind::Session, Some("it's \"quoted\""));
+    assert_eq!(
+        script,
+        r#"window.sessionStorage.getItem("it's \"quoted\"")"#
+    );
+    assert!(storage_get_script(StorageKind::Local, None).contains("window.localStorage"));
+
+    let script = storage_set_script(StorageKind::Local, "theme", "dark");
+    assert_eq!(script, r#"window.localStorage.setItem("theme", "dark")"#);
+
+    assert!(
+        storage_clear_script(StorageKind::Local, Some("theme")).contains(r#"removeItem("theme")"#)
+    );
+    assert!(storage_clear_script(StorageKind::Local, None).contains("s.clear()"));
+}
+
+#[tokio::test]
+async fn test_cookies_unknown_page() {
+    let manager = BrowserManager::new(BrowserManagerConfig::default());
+    let result = manager.get_cookies("page_1", &[]).await;
+    assert!(matches!(result, Err(BrowserError::PageNotFound(_))));
+    let result = manager.clear_cookies("page_1", None, true).await;
+    assert!(matches!(result, Err(BrowserError::PageNotFound(_))));
+}
+
+#[test]
+fn test_managed_profile_dir() {
+    let manager = BrowserManager::new(BrowserManagerConfig::default());
+    let dir = manager.managed_profile_dir().unwrap();
+    assert!(dir.exists());
+    assert_eq!(manager.managed_profile_dir().unwrap(), dir);
+    manager.managed_profile.lock().take();
+    assert!(!dir.exists());
+
+    let manager = BrowserManager::new(BrowserManagerConfig {
+        profile_dir: Some(std::path::PathBuf::from("/tmp/agent-profile")),
+        ..Default::default()
+    });
+    assert_eq!(
+        manager.managed_profile_dir().unwrap(),
+        std::path::PathBuf::from("/tmp/agent-profile")
+    );
+}
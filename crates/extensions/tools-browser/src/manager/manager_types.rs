@@ -2,9 +2,14 @@
 
 use std::path::PathBuf;
 
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use autohands_protocols::types::PrivacyMasks;
+
+use crate::bidi::BidiError;
 use crate::cdp::CdpError;
+use crate::fetcher::{ChromiumFetcher, CHROMIUM_VERSION};
 
 /// Browser manager errors.
 #[derive(Debug, Error)]
@@ -33,8 +38,14 @@ pub enum BrowserError {
     #[error("Chrome not found. Please install Google Chrome.")]
     ChromeNotFound,
 
+    #[error("Firefox not found. Please install Firefox.")]
+    FirefoxNotFound,
+
     #[error("Failed to launch Chrome: {0}")]
     LaunchFailed(String),
+
+    #[error("Browser crashed: {0}")]
+    Crashed(String),
 }
 
 impl From<CdpError> for BrowserError {
@@ -53,10 +64,40 @@ impl From<CdpError> for BrowserError {
     }
 }
 
+impl From<BidiError> for BrowserError {
+    fn from(e: BidiError) -> Self {
+        match e {
+            BidiError::ConnectionFailed(msg) => BrowserError::ConnectionFailed(msg),
+            BidiError::Protocol { error, message } => match error.as_str() {
+                "no such frame" => BrowserError::PageNotFound(message),
+                "no such element" | "no such node" => BrowserError::ElementNotFound(message),
+                _ => BrowserError::ActionFailed(format!("{}: {}", error, message)),
+            },
+            BidiError::Timeout(msg) => BrowserError::ActionFailed(format!("Timeout: {}", msg)),
+            BidiError::SessionClosed => BrowserError::NotConnected,
+            _ => BrowserError::ActionFailed(e.to_string()),
+        }
+    }
+}
+
+/// Browser the tools drive.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BrowserEngine {
+    /// Chrome, Chromium or Edge over CDP, with every tool.
+    #[default]
+    Chrome,
+    /// Firefox over WebDriver BiDi, with the basic page tools only; the
+    /// other tools fail with an unsupported error.
+    Firefox,
+}
+
 /// Browser configuration.
 #[derive(Debug, Clone)]
 pub struct BrowserManagerConfig {
-    /// Chrome debugging port.
+    /// Browser to launch and drive.
+    pub engine: BrowserEngine,
+    /// Chrome debugging port, or Firefox remote agent port.
     pub debug_port: u16,
     /// Default viewport width.
     pub viewport_width: u32,
@@ -64,18 +105,47 @@ pub struct BrowserManagerConfig {
     pub viewport_height: u32,
     /// Profile directory for persistent login state.
     pub profile_dir: Option<PathBuf>,
+    /// Directory downloads are saved to.
+    pub download_dir: Option<PathBuf>,
+    /// Named profiles pages can be opened in, each with its own cookies
+    /// and storage apart from the default profile's.
+    pub profiles: Vec<String>,
+    /// Directory the cookies of named profiles are saved in.
+    pub profiles_dir: Option<PathBuf>,
     /// Whether to run Chrome in headless mode.
     pub headless: bool,
+    /// Download a headless Chromium when no Chrome is installed.
+    pub download_chromium: bool,
+    /// Directory downloaded Chromium builds are kept in.
+    pub chromium_dir: Option<PathBuf>,
+    /// Chromium version to download, [`CHROMIUM_VERSION`](crate::fetcher::CHROMIUM_VERSION)
+    /// if not specified.
+    pub chromium_version: Option<String>,
+    /// Regions and pages blacked out of screenshots.
+    pub privacy_masks: PrivacyMasks,
+    /// How many times Chrome or a crashed tab is restarted within
+    /// [`RESTART_WINDOW`](super::RESTART_WINDOW) before giving up; 0
+    /// disables crash recovery.
+    pub max_restarts: u32,
 }
 
 impl Default for BrowserManagerConfig {
     fn default() -> Self {
         Self {
+            engine: BrowserEngine::Chrome,
             debug_port: 9222,
             viewport_width: 1280,
             viewport_height: 720,
             profile_dir: None,
+            download_dir: None,
+            profiles: Vec::new(),
+            profiles_dir: None,
             headless: false,
+            download_chromium: false,
+            chromium_dir: None,
+            chromium_version: None,
+            privacy_masks: PrivacyMasks::default(),
+            max_restarts: 3,
         }
     }
 }
@@ -91,6 +161,57 @@ impl BrowserManagerConfig {
         })
     }
 
+    /// Get the Firefox profile directory, `~/.autohands/firefox-profile`
+    /// if not specified. Firefox cannot use Chrome's default profile.
+    pub fn get_firefox_profile_dir(&self) -> PathBuf {
+        self.profile_dir.clone().unwrap_or_else(|| {
+            dirs::home_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join(".autohands")
+                .join("firefox-profile")
+        })
+    }
+
+    /// Get the download directory, `~/.autohands/downloads` if not specified.
+    pub fn get_download_dir(&self) -> PathBuf {
+        self.download_dir.clone().unwrap_or_else(|| {
+            dirs::home_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join(".autohands")
+                .join("downloads")
+        })
+    }
+
+    /// Get the directory of named profiles, `~/.autohands/browser-profiles`
+    /// if not specified.
+    pub fn get_profiles_dir(&self) -> PathBuf {
+        self.profiles_dir.clone().unwrap_or_else(|| {
+            dirs::home_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join(".autohands")
+                .join("browser-profiles")
+        })
+    }
+
+    /// Get the directory of downloaded Chromium builds,
+    /// `~/.autohands/chromium` if not specified.
+    pub fn get_chromium_dir(&self) -> PathBuf {
+        self.chromium_dir.clone().unwrap_or_else(|| {
+            dirs::home_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join(".autohands")
+                .join("chromium")
+        })
+    }
+
+    /// Fetcher of the headless Chromium to use when Chrome is missing.
+    pub fn chromium_fetcher(&self) -> ChromiumFetcher {
+        ChromiumFetcher::new(
+            self.get_chromium_dir(),
+            self.chromium_version.as_deref().unwrap_or(CHROMIUM_VERSION),
+        )
+    }
+
     /// Get the CDP endpoint URL.
     pub fn endpoint(&self) -> String {
         format!("http://localhost:{}", self.debug_port)
@@ -1,14 +1,29 @@
 //! Browser instance manager.
 //!
 //! This module provides a unified browser manager interface using CDP.
-//! It automatically launches Chrome with a persistent profile for login state preservation.
+//! It automatically launches Chrome with a persistent profile for login state preservation
+//! and a known download directory, and restarts it, re-opening the tracked pages, when it or a page's renderer crashes.
+//! Pages are the browser's tabs: their ids stay the same for as long as they are open,
+//! and one of them is the active tab. Pages can be opened in named profiles, each with
+//! cookies and storage of its own.
 
 mod manager_core;
+mod manager_downloads;
+mod manager_network;
 mod manager_pages;
+mod manager_privacy;
+mod manager_profiles;
+mod manager_recovery;
+mod manager_storage;
+mod manager_tabs;
 mod manager_types;
 
 pub use manager_core::BrowserManager;
-pub use manager_types::{BrowserError, BrowserManagerConfig};
+pub(crate) use manager_privacy::{mask_overlay_script, remove_overlay_script};
+pub use manager_recovery::RESTART_WINDOW;
+pub use manager_storage::StorageKind;
+pub use manager_tabs::TabInfo;
+pub use manager_types::{BrowserEngine, BrowserError, BrowserManagerConfig};
 
 #[cfg(test)]
 #[path = "manager_tests.rs"]
@@ -9,9 +9,10 @@ use tracing::debug;
 use autohands_protocols::error::ToolError;
 use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
 
+use crate::backend::BrowserBackend;
 use crate::manager::BrowserManager;
 
-use super::{default_compact, default_content_type};
+use super::{default_compact, default_content_type, PAGE_RESOURCE};
 
 // ============================================================================
 // Screenshot Tool
@@ -35,17 +36,18 @@ pub struct ScreenshotResult {
 /// Take screenshot tool.
 pub struct ScreenshotTool {
     definition: ToolDefinition,
-    manager: Arc<BrowserManager>,
+    manager: Arc<dyn BrowserBackend>,
 }
 
 impl ScreenshotTool {
-    pub fn new(manager: Arc<BrowserManager>) -> Self {
+    pub fn new(manager: Arc<dyn BrowserBackend>) -> Self {
         Self {
             definition: ToolDefinition::new(
                 "browser_screenshot",
                 "Browser Screenshot",
                 "Take a screenshot of the page or element",
-            ),
+            )
+            .with_read_only(),
             manager,
         }
     }
@@ -100,17 +102,18 @@ pub struct GetContentParams {
 /// Get page content tool.
 pub struct GetContentTool {
     definition: ToolDefinition,
-    manager: Arc<BrowserManager>,
+    manager: Arc<dyn BrowserBackend>,
 }
 
 impl GetContentTool {
-    pub fn new(manager: Arc<BrowserManager>) -> Self {
+    pub fn new(manager: Arc<dyn BrowserBackend>) -> Self {
         Self {
             definition: ToolDefinition::new(
                 "browser_get_content",
                 "Browser Get Content",
                 "Get text or HTML content from page or element",
-            ),
+            )
+            .with_read_only(),
             manager,
         }
     }
@@ -172,17 +175,18 @@ pub struct ExecuteJsParams {
 /// Execute JavaScript tool.
 pub struct ExecuteJsTool {
     definition: ToolDefinition,
-    manager: Arc<BrowserManager>,
+    manager: Arc<dyn BrowserBackend>,
 }
 
 impl ExecuteJsTool {
-    pub fn new(manager: Arc<BrowserManager>) -> Self {
+    pub fn new(manager: Arc<dyn BrowserBackend>) -> Self {
         Self {
             definition: ToolDefinition::new(
                 "browser_execute_js",
                 "Browser Execute JavaScript",
                 "Execute JavaScript code on the page",
-            ),
+            )
+            .with_exclusive_resource(PAGE_RESOURCE),
             manager,
         }
     }
@@ -225,6 +229,10 @@ pub struct GetDomParams {
     /// Return compact LLM-friendly format instead of full JSON
     #[serde(default = "default_compact")]
     pub compact: bool,
+    /// Return only the interactive elements changed since the last
+    /// incremental call, with stable element IDs
+    #[serde(default)]
+    pub incremental: bool,
 }
 
 /// Get enhanced DOM tree tool with clickability analysis.
@@ -238,8 +246,9 @@ impl GetDomTool {
         let mut definition = ToolDefinition::new(
             "browser_get_dom",
             "Browser Get DOM",
-            "Get enhanced DOM tree with interactive elements and clickability scores. Use compact=true for LLM-friendly output.",
-        );
+            "Get enhanced DOM tree with interactive elements and clickability scores. Use compact=true for LLM-friendly output, and incremental=true on re-scans to get only the elements added, removed or changed since the last incremental call.",
+        )
+        .with_read_only();
         definition.parameters_schema = Some(serde_json::json!({
             "type": "object",
             "properties": {
@@ -251,6 +260,11 @@ impl GetDomTool {
                     "type": "boolean",
                     "description": "Return compact LLM-friendly format (default: true)",
                     "default": true
+                },
+                "incremental": {
+                    "type": "boolean",
+                    "description": "Return only the interactive elements added, removed or changed since the last incremental call on this page, with IDs that stay the same across calls. The first call lists every element (default: false)",
+                    "default": false
                 }
             },
             "required": ["page_id"]
@@ -273,7 +287,22 @@ impl Tool for GetDomTool {
         let params: GetDomParams = serde_json::from_value(params)
             .map_err(|e| ToolError::ExecutionFailed(format!("Invalid params: {}", e)))?;
 
-        if params.compact {
+        if params.incremental {
+            let diff = self
+                .manager
+                .get_dom_diff(&params.page_id)
+                .await
+                .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
+            let output = if params.compact {
+                diff.to_llm_string()
+            } else {
+                serde_json::to_string_pretty(&diff).unwrap_or_default()
+            };
+            Ok(ToolResult::success(output)
+                .with_metadata("added", serde_json::json!(diff.added.len()))
+                .with_metadata("removed", serde_json::json!(diff.removed.len()))
+                .with_metadata("changed", serde_json::json!(diff.changed.len())))
+        } else if params.compact {
             // Return LLM-friendly format
             let output = self
                 .manager
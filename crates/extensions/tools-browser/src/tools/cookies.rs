@@ -0,0 +1,260 @@
+//! Cookie tools: read, set and clear the cookies of a page's profile.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+
+use crate::manager::BrowserManager;
+
+use super::PAGE_RESOURCE;
+
+// ============================================================================
+// Get Cookies Tool
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct CookiesGetParams {
+    pub page_id: String,
+    /// URLs to get the cookies of instead of the page's.
+    #[serde(default)]
+    pub urls: Vec<String>,
+}
+
+/// Get cookies tool.
+pub struct CookiesGetTool {
+    definition: ToolDefinition,
+    manager: Arc<BrowserManager>,
+}
+
+impl CookiesGetTool {
+    pub fn new(manager: Arc<BrowserManager>) -> Self {
+        let definition = ToolDefinition::new(
+            "browser_cookies_get",
+            "Browser Get Cookies",
+            "Get the cookies the browser sends to a page's URL, or to the given URLs, with their \
+             domain, path, expiry and flags.",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "page_id": {
+                    "type": "string",
+                    "description": "The page ID"
+                },
+                "urls": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "URLs to get the cookies of instead of the page's"
+                }
+            },
+            "required": ["page_id"]
+        }))
+        .with_read_only();
+        Self {
+            definition,
+            manager,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for CookiesGetTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        _ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: CookiesGetParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::ExecutionFailed(format!("Invalid params: {}", e)))?;
+
+        let cookies = self
+            .manager
+            .get_cookies(&params.page_id, &params.urls)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
+
+        Ok(
+            ToolResult::success(serde_json::to_string(&cookies).unwrap())
+                .with_metadata("count", serde_json::json!(cookies.len())),
+        )
+    }
+}
+
+// ============================================================================
+// Set Cookies Tool
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct CookiesSetParams {
+    pub page_id: String,
+    pub cookies: Vec<serde_json::Value>,
+}
+
+/// Set cookies tool.
+pub struct CookiesSetTool {
+    definition: ToolDefinition,
+    manager: Arc<BrowserManager>,
+}
+
+impl CookiesSetTool {
+    pub fn new(manager: Arc<BrowserManager>) -> Self {
+        let definition = ToolDefinition::new(
+            "browser_cookies_set",
+            "Browser Set Cookies",
+            "Set cookies in a page's browser profile. Cookies without a url or domain are set \
+             for the page's URL.",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "page_id": {
+                    "type": "string",
+                    "description": "The page ID"
+                },
+                "cookies": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "name": {"type": "string"},
+                            "value": {"type": "string"},
+                            "url": {"type": "string"},
+                            "domain": {"type": "string"},
+                            "path": {"type": "string"},
+                            "secure": {"type": "boolean"},
+                            "httpOnly": {"type": "boolean"},
+                            "sameSite": {"type": "string", "enum": ["Strict", "Lax", "None"]},
+                            "expires": {
+                                "type": "number",
+                                "description": "Unix time in seconds; omit for a session cookie"
+                            }
+                        },
+                        "required": ["name", "value"]
+                    },
+                    "description": "Cookies to set"
+                }
+            },
+            "required": ["page_id", "cookies"]
+        }))
+        .with_exclusive_resource(PAGE_RESOURCE);
+        Self {
+            definition,
+            manager,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for CookiesSetTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        _ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: CookiesSetParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::ExecutionFailed(format!("Invalid params: {}", e)))?;
+
+        self.manager
+            .set_cookies(&params.page_id, &params.cookies)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
+
+        Ok(ToolResult::success(format!(
+            "Set {} cookies",
+            params.cookies.len()
+        )))
+    }
+}
+
+// ============================================================================
+// Clear Cookies Tool
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct CookiesClearParams {
+    pub page_id: String,
+    /// Only delete cookies with this name.
+    pub name: Option<String>,
+    /// Delete every cookie of the page's profile, not just the page's.
+    #[serde(default)]
+    pub all: bool,
+}
+
+/// Clear cookies tool.
+pub struct CookiesClearTool {
+    definition: ToolDefinition,
+    manager: Arc<BrowserManager>,
+}
+
+impl CookiesClearTool {
+    pub fn new(manager: Arc<BrowserManager>) -> Self {
+        let definition = ToolDefinition::new(
+            "browser_cookies_clear",
+            "Browser Clear Cookies",
+            "Delete the cookies sent to a page's URL, optionally only those with a given name, \
+             or with all=true every cookie of the page's browser profile (logging it out of \
+             every site).",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "page_id": {
+                    "type": "string",
+                    "description": "The page ID"
+                },
+                "name": {
+                    "type": "string",
+                    "description": "Only delete cookies with this name"
+                },
+                "all": {
+                    "type": "boolean",
+                    "description": "Delete every cookie of the page's profile",
+                    "default": false
+                }
+            },
+            "required": ["page_id"]
+        }))
+        .with_exclusive_resource(PAGE_RESOURCE);
+        Self {
+            definition,
+            manager,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for CookiesClearTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        _ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: CookiesClearParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::ExecutionFailed(format!("Invalid params: {}", e)))?;
+
+        let cleared = self
+            .manager
+            .clear_cookies(&params.page_id, params.name.as_deref(), params.all)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
+
+        Ok(ToolResult::success(format!("Deleted {} cookies", cleared))
+            .with_metadata("count", serde_json::json!(cleared)))
+    }
+}
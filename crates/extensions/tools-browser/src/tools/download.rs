@@ -0,0 +1,93 @@
+//! Download tool: wait for a file the page downloads.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+
+use crate::manager::BrowserManager;
+
+fn default_download_timeout() -> u64 {
+    60000
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WaitForDownloadParams {
+    #[serde(default = "default_download_timeout")]
+    pub timeout_ms: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WaitForDownloadResult {
+    /// Where the file was saved.
+    pub path: Option<String>,
+    pub filename: String,
+    pub url: String,
+    pub bytes: u64,
+}
+
+/// Wait for a download to complete tool.
+pub struct WaitForDownloadTool {
+    definition: ToolDefinition,
+    manager: Arc<BrowserManager>,
+}
+
+impl WaitForDownloadTool {
+    pub fn new(manager: Arc<BrowserManager>) -> Self {
+        let definition = ToolDefinition::new(
+            "browser_wait_for_download",
+            "Browser Wait For Download",
+            "Wait for a download started by a page (for example after clicking a download link) \
+             to complete, and return the path it was saved to. Downloads are returned once each, \
+             oldest first.",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "timeout_ms": {
+                    "type": "integer",
+                    "description": "How long to wait in milliseconds",
+                    "default": 60000
+                }
+            }
+        }));
+        Self {
+            definition,
+            manager,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for WaitForDownloadTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        _ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: WaitForDownloadParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::ExecutionFailed(format!("Invalid params: {}", e)))?;
+
+        let download = self
+            .manager
+            .wait_for_download(Duration::from_millis(params.timeout_ms))
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
+
+        let result = WaitForDownloadResult {
+            path: download.path.map(|p| p.display().to_string()),
+            filename: download.suggested_filename,
+            url: download.url,
+            bytes: download.received_bytes,
+        };
+        Ok(ToolResult::success(serde_json::to_string(&result).unwrap()))
+    }
+}
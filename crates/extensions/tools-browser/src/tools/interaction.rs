@@ -1,17 +1,20 @@
-//! User interaction tools: click, type, press key, scroll, wait for.
+//! User interaction tools: click, type, press key, scroll, wait for conditions.
 
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
+use regex::Regex;
 use serde::Deserialize;
 use tracing::debug;
 
 use autohands_protocols::error::ToolError;
 use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
 
-use crate::manager::BrowserManager;
+use crate::backend::BrowserBackend;
+use crate::wait::{wait_for, ElementState, WaitCondition};
 
-use super::default_timeout;
+use super::{default_poll_interval, default_timeout, PAGE_RESOURCE};
 
 // ============================================================================
 // Click Tool
@@ -26,17 +29,18 @@ pub struct ClickParams {
 /// Click element tool.
 pub struct ClickTool {
     definition: ToolDefinition,
-    manager: Arc<BrowserManager>,
+    manager: Arc<dyn BrowserBackend>,
 }
 
 impl ClickTool {
-    pub fn new(manager: Arc<BrowserManager>) -> Self {
+    pub fn new(manager: Arc<dyn BrowserBackend>) -> Self {
         Self {
             definition: ToolDefinition::new(
                 "browser_click",
                 "Browser Click",
                 "Click an element on the page using CSS selector",
-            ),
+            )
+            .with_exclusive_resource(PAGE_RESOURCE),
             manager,
         }
     }
@@ -82,17 +86,18 @@ pub struct TypeTextParams {
 /// Type text into element tool.
 pub struct TypeTextTool {
     definition: ToolDefinition,
-    manager: Arc<BrowserManager>,
+    manager: Arc<dyn BrowserBackend>,
 }
 
 impl TypeTextTool {
-    pub fn new(manager: Arc<BrowserManager>) -> Self {
+    pub fn new(manager: Arc<dyn BrowserBackend>) -> Self {
         Self {
             definition: ToolDefinition::new(
                 "browser_type",
                 "Browser Type",
                 "Type text into an input element",
-            ),
+            )
+            .with_exclusive_resource(PAGE_RESOURCE),
             manager,
         }
     }
@@ -140,17 +145,18 @@ pub struct PressKeyParams {
 /// Press keyboard key tool.
 pub struct PressKeyTool {
     definition: ToolDefinition,
-    manager: Arc<BrowserManager>,
+    manager: Arc<dyn BrowserBackend>,
 }
 
 impl PressKeyTool {
-    pub fn new(manager: Arc<BrowserManager>) -> Self {
+    pub fn new(manager: Arc<dyn BrowserBackend>) -> Self {
         Self {
             definition: ToolDefinition::new(
                 "browser_press_key",
                 "Browser Press Key",
                 "Press a keyboard key (Enter, Tab, Escape, ArrowDown, etc.)",
-            ),
+            )
+            .with_exclusive_resource(PAGE_RESOURCE),
             manager,
         }
     }
@@ -198,17 +204,18 @@ pub struct ScrollParams {
 /// Scroll page tool.
 pub struct ScrollTool {
     definition: ToolDefinition,
-    manager: Arc<BrowserManager>,
+    manager: Arc<dyn BrowserBackend>,
 }
 
 impl ScrollTool {
-    pub fn new(manager: Arc<BrowserManager>) -> Self {
+    pub fn new(manager: Arc<dyn BrowserBackend>) -> Self {
         Self {
             definition: ToolDefinition::new(
                 "browser_scroll",
                 "Browser Scroll",
                 "Scroll the page by x,y pixels or scroll to an element",
-            ),
+            )
+            .with_exclusive_resource(PAGE_RESOURCE),
             manager,
         }
     }
@@ -254,31 +261,129 @@ impl Tool for ScrollTool {
 }
 
 // ============================================================================
-// Wait For Selector Tool
+// Wait For Tool
 // ============================================================================
 
 #[derive(Debug, Deserialize)]
 pub struct WaitForParams {
     pub page_id: String,
-    pub selector: String,
+    pub selector: Option<String>,
+    /// State to wait for the element in.
+    #[serde(default)]
+    pub state: ElementState,
+    /// Regex the page's URL must match.
+    pub url: Option<String>,
+    /// Text the page, or the element, must contain.
+    pub text: Option<String>,
+    /// JavaScript expression that must be truthy.
+    pub predicate: Option<String>,
+    #[serde(default)]
+    pub network_idle: bool,
     #[serde(default = "default_timeout")]
     pub timeout_ms: u64,
+    #[serde(default = "default_poll_interval")]
+    pub poll_interval_ms: u64,
 }
 
-/// Wait for element tool.
+impl WaitForParams {
+    /// The conditions to wait for, all of them at once.
+    pub fn conditions(&self) -> Result<Vec<WaitCondition>, String> {
+        let mut conditions = Vec::new();
+        match (&self.text, &self.selector) {
+            (Some(text), selector) => conditions.push(WaitCondition::Text {
+                text: text.clone(),
+                selector: selector.clone(),
+            }),
+            (None, Some(selector)) => conditions.push(WaitCondition::Element {
+                selector: selector.clone(),
+                state: self.state,
+            }),
+            (None, None) => {}
+        }
+        if let Some(url) = &self.url {
+            let regex = Regex::new(url).map_err(|e| format!("Invalid url regex: {}", e))?;
+            conditions.push(WaitCondition::Url(regex));
+        }
+        if let Some(predicate) = &self.predicate {
+            conditions.push(WaitCondition::Predicate(predicate.clone()));
+        }
+        if self.network_idle {
+            conditions.push(WaitCondition::NetworkIdle);
+        }
+        if conditions.is_empty() {
+            return Err(
+                "Give a selector, url, text, predicate or network_idle to wait for".to_string(),
+            );
+        }
+        Ok(conditions)
+    }
+}
+
+/// Wait for page conditions tool.
 pub struct WaitForTool {
     definition: ToolDefinition,
-    manager: Arc<BrowserManager>,
+    manager: Arc<dyn BrowserBackend>,
 }
 
 impl WaitForTool {
-    pub fn new(manager: Arc<BrowserManager>) -> Self {
+    pub fn new(manager: Arc<dyn BrowserBackend>) -> Self {
+        let definition = ToolDefinition::new(
+            "browser_wait_for",
+            "Browser Wait For",
+            "Wait until conditions hold on the page, instead of sleeping: an element is present, \
+             visible, hidden or removed; the URL matches a regex; text appears; a JavaScript \
+             expression is truthy; or the network is idle. With several conditions, waits for \
+             all of them.",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "page_id": {
+                    "type": "string",
+                    "description": "The page ID"
+                },
+                "selector": {
+                    "type": "string",
+                    "description": "CSS selector of the element to wait for, or to look for text in"
+                },
+                "state": {
+                    "type": "string",
+                    "enum": ["present", "visible", "hidden", "removed"],
+                    "description": "State to wait for the element in",
+                    "default": "present"
+                },
+                "url": {
+                    "type": "string",
+                    "description": "Regex the page's URL must match"
+                },
+                "text": {
+                    "type": "string",
+                    "description": "Text the page, or the selector's element, must contain"
+                },
+                "predicate": {
+                    "type": "string",
+                    "description": "JavaScript expression that must evaluate to a truthy value"
+                },
+                "network_idle": {
+                    "type": "boolean",
+                    "description": "Wait until the page has loaded and made no requests for 500ms",
+                    "default": false
+                },
+                "timeout_ms": {
+                    "type": "integer",
+                    "description": "How long to wait before failing",
+                    "default": 30000
+                },
+                "poll_interval_ms": {
+                    "type": "integer",
+                    "description": "How often to check the conditions",
+                    "default": 100
+                }
+            },
+            "required": ["page_id"]
+        }));
         Self {
-            definition: ToolDefinition::new(
-                "browser_wait_for",
-                "Browser Wait For",
-                "Wait for an element to appear on the page",
-            ),
+            definition,
             manager,
         }
     }
@@ -297,15 +402,23 @@ impl Tool for WaitForTool {
     ) -> Result<ToolResult, ToolError> {
         let params: WaitForParams = serde_json::from_value(params)
             .map_err(|e| ToolError::ExecutionFailed(format!("Invalid params: {}", e)))?;
-
-        self.manager
-            .wait_for_selector(&params.page_id, &params.selector, Some(params.timeout_ms as u32))
-            .await
-            .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
-
-        Ok(ToolResult::success(format!(
-            "Element {} found",
-            params.selector
-        )))
+        let conditions = params.conditions().map_err(ToolError::ExecutionFailed)?;
+
+        let started = Instant::now();
+        wait_for(
+            self.manager.as_ref(),
+            &params.page_id,
+            &conditions,
+            Duration::from_millis(params.timeout_ms),
+            Duration::from_millis(params.poll_interval_ms.max(10)),
+        )
+        .await
+        .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
+
+        let waited = started.elapsed().as_millis() as u64;
+        let met: Vec<String> = conditions.iter().map(ToString::to_string).collect();
+        let message = format!("Waited {}ms for {}", waited, met.join(", "));
+        debug!("{}", message);
+        Ok(ToolResult::success(message).with_metadata("waited_ms", serde_json::json!(waited)))
     }
 }
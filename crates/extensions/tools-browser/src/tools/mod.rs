@@ -1,14 +1,28 @@
 //! Browser automation tools.
 
 mod content;
+mod cookies;
+mod download;
 mod interaction;
 mod navigation;
+mod network;
 mod page;
+mod storage;
+mod tabs;
 
 pub use content::*;
+pub use cookies::*;
+pub use download::*;
 pub use interaction::*;
 pub use navigation::*;
+pub use network::*;
 pub use page::*;
+pub use storage::*;
+pub use tabs::*;
+
+/// Resource held by tools driving a page, so two sessions cannot drive the
+/// same page at once.
+pub(crate) const PAGE_RESOURCE: &str = "browser-page:{page_id}";
 
 // Shared default value helpers used by multiple submodules.
 
@@ -16,6 +30,10 @@ pub(crate) fn default_timeout() -> u64 {
     30000
 }
 
+pub(crate) fn default_poll_interval() -> u64 {
+    100
+}
+
 pub(crate) fn default_content_type() -> String {
     "text".to_string()
 }
@@ -9,9 +9,9 @@ use tracing::debug;
 use autohands_protocols::error::ToolError;
 use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
 
-use crate::manager::BrowserManager;
+use crate::backend::BrowserBackend;
 
-use super::default_timeout;
+use super::{default_timeout, PAGE_RESOURCE};
 
 // ============================================================================
 // Navigate Tool
@@ -28,17 +28,18 @@ pub struct NavigateParams {
 /// Navigate to URL tool.
 pub struct NavigateTool {
     definition: ToolDefinition,
-    manager: Arc<BrowserManager>,
+    manager: Arc<dyn BrowserBackend>,
 }
 
 impl NavigateTool {
-    pub fn new(manager: Arc<BrowserManager>) -> Self {
+    pub fn new(manager: Arc<dyn BrowserBackend>) -> Self {
         Self {
             definition: ToolDefinition::new(
                 "browser_navigate",
                 "Browser Navigate",
                 "Navigate a browser page to a URL",
-            ),
+            )
+            .with_exclusive_resource(PAGE_RESOURCE),
             manager,
         }
     }
@@ -80,17 +81,18 @@ pub struct NavigationParams {
 /// Go back tool.
 pub struct BackTool {
     definition: ToolDefinition,
-    manager: Arc<BrowserManager>,
+    manager: Arc<dyn BrowserBackend>,
 }
 
 impl BackTool {
-    pub fn new(manager: Arc<BrowserManager>) -> Self {
+    pub fn new(manager: Arc<dyn BrowserBackend>) -> Self {
         Self {
             definition: ToolDefinition::new(
                 "browser_back",
                 "Browser Back",
                 "Go back to the previous page",
-            ),
+            )
+            .with_exclusive_resource(PAGE_RESOURCE),
             manager,
         }
     }
@@ -122,17 +124,18 @@ impl Tool for BackTool {
 /// Go forward tool.
 pub struct ForwardTool {
     definition: ToolDefinition,
-    manager: Arc<BrowserManager>,
+    manager: Arc<dyn BrowserBackend>,
 }
 
 impl ForwardTool {
-    pub fn new(manager: Arc<BrowserManager>) -> Self {
+    pub fn new(manager: Arc<dyn BrowserBackend>) -> Self {
         Self {
             definition: ToolDefinition::new(
                 "browser_forward",
                 "Browser Forward",
                 "Go forward to the next page",
-            ),
+            )
+            .with_exclusive_resource(PAGE_RESOURCE),
             manager,
         }
     }
@@ -164,17 +167,18 @@ impl Tool for ForwardTool {
 /// Refresh page tool.
 pub struct RefreshTool {
     definition: ToolDefinition,
-    manager: Arc<BrowserManager>,
+    manager: Arc<dyn BrowserBackend>,
 }
 
 impl RefreshTool {
-    pub fn new(manager: Arc<BrowserManager>) -> Self {
+    pub fn new(manager: Arc<dyn BrowserBackend>) -> Self {
         Self {
             definition: ToolDefinition::new(
                 "browser_refresh",
                 "Browser Refresh",
                 "Refresh the current page",
-            ),
+            )
+            .with_exclusive_resource(PAGE_RESOURCE),
             manager,
         }
     }
@@ -215,11 +219,11 @@ pub struct GetUrlParams {
 /// Get current URL tool.
 pub struct GetUrlTool {
     definition: ToolDefinition,
-    manager: Arc<BrowserManager>,
+    manager: Arc<dyn BrowserBackend>,
 }
 
 impl GetUrlTool {
-    pub fn new(manager: Arc<BrowserManager>) -> Self {
+    pub fn new(manager: Arc<dyn BrowserBackend>) -> Self {
         Self {
             definition: ToolDefinition::new(
                 "browser_get_url",
@@ -0,0 +1,270 @@
+//! Network tools: record the requests a page makes and export them.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{PathAccess, Tool, ToolContext, ToolDefinition, ToolResult};
+
+use crate::cdp::{to_har, CapturedRequest, DEFAULT_MAX_REQUESTS};
+use crate::manager::BrowserManager;
+
+use super::PAGE_RESOURCE;
+
+fn default_max_requests() -> usize {
+    DEFAULT_MAX_REQUESTS
+}
+
+fn default_network_format() -> String {
+    "summary".to_string()
+}
+
+// ============================================================================
+// Network Start Tool
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct NetworkStartParams {
+    pub page_id: String,
+    /// Substring of the URLs to record, or a pattern with `*` wildcards.
+    pub url_pattern: Option<String>,
+    #[serde(default = "default_max_requests")]
+    pub max_requests: usize,
+}
+
+/// Start recording a page's network requests tool.
+pub struct NetworkStartTool {
+    definition: ToolDefinition,
+    manager: Arc<BrowserManager>,
+}
+
+impl NetworkStartTool {
+    pub fn new(manager: Arc<BrowserManager>) -> Self {
+        let definition = ToolDefinition::new(
+            "browser_network_start",
+            "Browser Network Start",
+            "Start recording the network requests a page makes, optionally only those whose \
+             URL matches a pattern. Stop with browser_network_stop to get the requests.",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "page_id": {
+                    "type": "string",
+                    "description": "The page ID"
+                },
+                "url_pattern": {
+                    "type": "string",
+                    "description": "Only record URLs containing this text, or matching it \
+                                    with * as a wildcard (e.g. 'https://api.example.com/*')"
+                },
+                "max_requests": {
+                    "type": "integer",
+                    "description": "Most requests to record",
+                    "default": 1000
+                }
+            },
+            "required": ["page_id"]
+        }))
+        .with_exclusive_resource(PAGE_RESOURCE);
+        Self {
+            definition,
+            manager,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for NetworkStartTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        _ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: NetworkStartParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::ExecutionFailed(format!("Invalid params: {}", e)))?;
+
+        self.manager
+            .start_capture(
+                &params.page_id,
+                params.url_pattern.as_deref(),
+                params.max_requests,
+            )
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
+
+        Ok(ToolResult::success(match &params.url_pattern {
+            Some(pattern) => format!(
+                "Recording requests of {} matching '{}'",
+                params.page_id, pattern
+            ),
+            None => format!("Recording requests of {}", params.page_id),
+        }))
+    }
+}
+
+// ============================================================================
+// Network Stop Tool
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct NetworkStopParams {
+    pub page_id: String,
+    /// "summary" or "har".
+    #[serde(default = "default_network_format")]
+    pub format: String,
+    #[serde(default)]
+    pub include_bodies: bool,
+    /// File to write the HAR to, instead of returning it.
+    pub path: Option<String>,
+}
+
+/// Stop recording a page's network requests tool.
+pub struct NetworkStopTool {
+    definition: ToolDefinition,
+    manager: Arc<BrowserManager>,
+}
+
+impl NetworkStopTool {
+    pub fn new(manager: Arc<BrowserManager>) -> Self {
+        let definition = ToolDefinition::new(
+            "browser_network_stop",
+            "Browser Network Stop",
+            "Stop recording a page's network requests and return them: a summary of each \
+             request's method, status, type, size and timing with the full metadata as \
+             structured output, or an HAR file.",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "page_id": {
+                    "type": "string",
+                    "description": "The page ID"
+                },
+                "format": {
+                    "type": "string",
+                    "enum": ["summary", "har"],
+                    "description": "Return a summary of the requests or an HAR 1.2 log",
+                    "default": "summary"
+                },
+                "include_bodies": {
+                    "type": "boolean",
+                    "description": "Include text response bodies (JSON, HTML, ...)",
+                    "default": false
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Write the HAR to this file instead of returning it"
+                }
+            },
+            "required": ["page_id"]
+        }))
+        .with_exclusive_resource(PAGE_RESOURCE);
+        Self {
+            definition,
+            manager,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for NetworkStopTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: NetworkStopParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::ExecutionFailed(format!("Invalid params: {}", e)))?;
+        if params.format != "summary" && params.format != "har" {
+            return Err(ToolError::ExecutionFailed(format!(
+                "Unknown format '{}': expected 'summary' or 'har'",
+                params.format
+            )));
+        }
+        // Check the path before the capture is given up
+        let path = params
+            .path
+            .as_deref()
+            .map(|path| ctx.resolve_path(path, PathAccess::Write))
+            .transpose()?;
+
+        let (requests, dropped) = self
+            .manager
+            .stop_capture(&params.page_id, params.include_bodies)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
+
+        let mut header = format!("Captured {} requests", requests.len());
+        if dropped > 0 {
+            header.push_str(&format!(" ({} more were not recorded)", dropped));
+        }
+
+        let result = if params.format == "har" {
+            let har = to_har(&requests);
+            match path {
+                Some(path) => {
+                    let text = serde_json::to_string_pretty(&har)
+                        .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
+                    tokio::fs::write(&path, text).await.map_err(|e| {
+                        ToolError::ExecutionFailed(format!(
+                            "Failed to write {}: {}",
+                            path.display(),
+                            e
+                        ))
+                    })?;
+                    ToolResult::success(format!("{}; HAR saved to {}", header, path.display()))
+                        .with_metadata("path", serde_json::json!(path.display().to_string()))
+                }
+                None => ToolResult::success(serde_json::to_string(&har).unwrap()),
+            }
+        } else {
+            let mut lines = vec![header];
+            lines.extend(requests.iter().map(summary_line));
+            ToolResult::success_json(lines.join("\n"), serde_json::json!(requests))
+        };
+        Ok(result.with_metadata("dropped", serde_json::json!(dropped)))
+    }
+}
+
+/// One request as "GET 200 text/html 12.3 KB 85ms https://...".
+pub(super) fn summary_line(request: &CapturedRequest) -> String {
+    let outcome = match (&request.error, request.status) {
+        (Some(error), _) => format!("failed ({})", error),
+        (None, Some(status)) => {
+            let mut parts = vec![status.to_string()];
+            if let Some(mime) = &request.mime_type {
+                parts.push(mime.clone());
+            }
+            if let Some(bytes) = request.encoded_bytes {
+                parts.push(format_bytes(bytes));
+            }
+            if let Some(ms) = request.duration_ms {
+                parts.push(format!("{:.0}ms", ms));
+            }
+            parts.join(" ")
+        }
+        (None, None) => "pending".to_string(),
+    };
+    format!("{} {} {}", request.method, outcome, request.url)
+}
+
+fn format_bytes(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{} B", bytes)
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
@@ -9,7 +9,9 @@ use tracing::debug;
 use autohands_protocols::error::ToolError;
 use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
 
-use crate::manager::BrowserManager;
+use crate::backend::BrowserBackend;
+
+use super::PAGE_RESOURCE;
 
 // ============================================================================
 // Open Page Tool (creates new page and triggers lazy browser init)
@@ -18,6 +20,8 @@ use crate::manager::BrowserManager;
 #[derive(Debug, Deserialize)]
 pub struct OpenPageParams {
     pub url: String,
+    /// Named profile to open the page in.
+    pub profile: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -32,11 +36,11 @@ pub struct OpenPageResult {
 /// The browser is lazily initialized when this tool is first called.
 pub struct OpenPageTool {
     definition: ToolDefinition,
-    manager: Arc<BrowserManager>,
+    manager: Arc<dyn BrowserBackend>,
 }
 
 impl OpenPageTool {
-    pub fn new(manager: Arc<BrowserManager>) -> Self {
+    pub fn new(manager: Arc<dyn BrowserBackend>) -> Self {
         let mut definition = ToolDefinition::new(
             "browser_open",
             "Browser Open",
@@ -48,6 +52,10 @@ impl OpenPageTool {
                 "url": {
                     "type": "string",
                     "description": "The URL to navigate to"
+                },
+                "profile": {
+                    "type": "string",
+                    "description": "Named profile to open the page in, with its own cookies and storage"
                 }
             },
             "required": ["url"]
@@ -73,7 +81,7 @@ impl Tool for OpenPageTool {
         // This will lazily initialize the browser if not already running
         let page_id = self
             .manager
-            .new_page(&params.url)
+            .new_page_in(&params.url, params.profile.as_deref())
             .await
             .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
 
@@ -101,17 +109,18 @@ pub struct ClosePageParams {
 /// Close a browser page tool.
 pub struct ClosePageTool {
     definition: ToolDefinition,
-    manager: Arc<BrowserManager>,
+    manager: Arc<dyn BrowserBackend>,
 }
 
 impl ClosePageTool {
-    pub fn new(manager: Arc<BrowserManager>) -> Self {
+    pub fn new(manager: Arc<dyn BrowserBackend>) -> Self {
         Self {
             definition: ToolDefinition::new(
                 "browser_close",
                 "Browser Close",
                 "Close a browser page",
-            ),
+            )
+            .with_exclusive_resource(PAGE_RESOURCE),
             manager,
         }
     }
@@ -148,11 +157,11 @@ impl Tool for ClosePageTool {
 /// List all open browser pages tool.
 pub struct ListPagesTool {
     definition: ToolDefinition,
-    manager: Arc<BrowserManager>,
+    manager: Arc<dyn BrowserBackend>,
 }
 
 impl ListPagesTool {
-    pub fn new(manager: Arc<BrowserManager>) -> Self {
+    pub fn new(manager: Arc<dyn BrowserBackend>) -> Self {
         Self {
             definition: ToolDefinition::new(
                 "browser_list_pages",
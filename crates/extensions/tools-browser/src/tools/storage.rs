@@ -0,0 +1,249 @@
+//! Web storage tools: read, set and clear a page's localStorage or
+//! sessionStorage.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+
+use crate::manager::{BrowserManager, StorageKind};
+
+use super::PAGE_RESOURCE;
+
+// ============================================================================
+// Get Storage Tool
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct StorageGetParams {
+    pub page_id: String,
+    #[serde(default)]
+    pub storage: StorageKind,
+    /// Item to get; every item when omitted.
+    pub key: Option<String>,
+}
+
+/// Get web storage items tool.
+pub struct StorageGetTool {
+    definition: ToolDefinition,
+    manager: Arc<BrowserManager>,
+}
+
+impl StorageGetTool {
+    pub fn new(manager: Arc<BrowserManager>) -> Self {
+        let definition = ToolDefinition::new(
+            "browser_storage_get",
+            "Browser Get Storage",
+            "Get an item of a page's localStorage or sessionStorage, or every item as an object.",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "page_id": {
+                    "type": "string",
+                    "description": "The page ID"
+                },
+                "storage": {
+                    "type": "string",
+                    "enum": ["local", "session"],
+                    "default": "local"
+                },
+                "key": {
+                    "type": "string",
+                    "description": "Item to get; every item when omitted"
+                }
+            },
+            "required": ["page_id"]
+        }))
+        .with_read_only();
+        Self {
+            definition,
+            manager,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for StorageGetTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        _ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: StorageGetParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::ExecutionFailed(format!("Invalid params: {}", e)))?;
+
+        let value = self
+            .manager
+            .get_storage(&params.page_id, params.storage, params.key.as_deref())
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
+
+        Ok(ToolResult::success(serde_json::to_string(&value).unwrap()))
+    }
+}
+
+// ============================================================================
+// Set Storage Tool
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct StorageSetParams {
+    pub page_id: String,
+    #[serde(default)]
+    pub storage: StorageKind,
+    pub key: String,
+    pub value: String,
+}
+
+/// Set a web storage item tool.
+pub struct StorageSetTool {
+    definition: ToolDefinition,
+    manager: Arc<BrowserManager>,
+}
+
+impl StorageSetTool {
+    pub fn new(manager: Arc<BrowserManager>) -> Self {
+        let definition = ToolDefinition::new(
+            "browser_storage_set",
+            "Browser Set Storage",
+            "Set an item of a page's localStorage or sessionStorage.",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "page_id": {
+                    "type": "string",
+                    "description": "The page ID"
+                },
+                "storage": {
+                    "type": "string",
+                    "enum": ["local", "session"],
+                    "default": "local"
+                },
+                "key": {
+                    "type": "string",
+                    "description": "Item to set"
+                },
+                "value": {
+                    "type": "string",
+                    "description": "Value to store; serialize objects as JSON"
+                }
+            },
+            "required": ["page_id", "key", "value"]
+        }))
+        .with_exclusive_resource(PAGE_RESOURCE);
+        Self {
+            definition,
+            manager,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for StorageSetTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        _ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: StorageSetParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::ExecutionFailed(format!("Invalid params: {}", e)))?;
+
+        self.manager
+            .set_storage(&params.page_id, params.storage, &params.key, &params.value)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
+
+        Ok(ToolResult::success(format!("Set {}", params.key)))
+    }
+}
+
+// ============================================================================
+// Clear Storage Tool
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct StorageClearParams {
+    pub page_id: String,
+    #[serde(default)]
+    pub storage: StorageKind,
+    /// Item to remove; every item when omitted.
+    pub key: Option<String>,
+}
+
+/// Clear web storage tool.
+pub struct StorageClearTool {
+    definition: ToolDefinition,
+    manager: Arc<BrowserManager>,
+}
+
+impl StorageClearTool {
+    pub fn new(manager: Arc<BrowserManager>) -> Self {
+        let definition = ToolDefinition::new(
+            "browser_storage_clear",
+            "Browser Clear Storage",
+            "Remove an item of a page's localStorage or sessionStorage, or every item of it.",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "page_id": {
+                    "type": "string",
+                    "description": "The page ID"
+                },
+                "storage": {
+                    "type": "string",
+                    "enum": ["local", "session"],
+                    "default": "local"
+                },
+                "key": {
+                    "type": "string",
+                    "description": "Item to remove; every item when omitted"
+                }
+            },
+            "required": ["page_id"]
+        }))
+        .with_exclusive_resource(PAGE_RESOURCE);
+        Self {
+            definition,
+            manager,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for StorageClearTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        _ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: StorageClearParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::ExecutionFailed(format!("Invalid params: {}", e)))?;
+
+        let removed = self
+            .manager
+            .clear_storage(&params.page_id, params.storage, params.key.as_deref())
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
+
+        Ok(ToolResult::success(format!("Removed {} items", removed))
+            .with_metadata("count", serde_json::json!(removed)))
+    }
+}
@@ -0,0 +1,292 @@
+//! Tab tools: open, list, switch, close.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+
+use crate::manager::BrowserManager;
+
+/// Same resource as [`PAGE_RESOURCE`](super::PAGE_RESOURCE), named by tab id.
+const TAB_RESOURCE: &str = "browser-page:{tab_id}";
+
+fn default_tab_url() -> String {
+    "about:blank".to_string()
+}
+
+fn default_activate() -> bool {
+    true
+}
+
+// ============================================================================
+// New Tab Tool
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct TabNewParams {
+    #[serde(default = "default_tab_url")]
+    pub url: String,
+    #[serde(default = "default_activate")]
+    pub activate: bool,
+    /// Named profile to open the tab in.
+    pub profile: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TabNewResult {
+    pub tab_id: String,
+    pub url: String,
+    pub active: bool,
+}
+
+/// Open a new tab tool.
+pub struct TabNewTool {
+    definition: ToolDefinition,
+    manager: Arc<BrowserManager>,
+}
+
+impl TabNewTool {
+    pub fn new(manager: Arc<BrowserManager>) -> Self {
+        let definition = ToolDefinition::new(
+            "browser_tab_new",
+            "Browser New Tab",
+            "Open a new browser tab. Returns a tab_id that stays valid until the tab is closed and \
+             can be used as page_id with the other browser tools.",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "url": {
+                    "type": "string",
+                    "description": "The URL to open",
+                    "default": "about:blank"
+                },
+                "activate": {
+                    "type": "boolean",
+                    "description": "Bring the tab to the front; false keeps the current tab active",
+                    "default": true
+                },
+                "profile": {
+                    "type": "string",
+                    "description": "Named profile to open the tab in, with its own cookies and storage"
+                }
+            }
+        }));
+        Self {
+            definition,
+            manager,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for TabNewTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        _ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: TabNewParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::ExecutionFailed(format!("Invalid params: {}", e)))?;
+
+        let tab_id = self
+            .manager
+            .new_tab(&params.url, params.activate, params.profile.as_deref())
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
+
+        debug!("Opened tab {}: {}", tab_id, params.url);
+
+        let result = TabNewResult {
+            tab_id: tab_id.clone(),
+            url: params.url,
+            active: params.activate,
+        };
+
+        Ok(ToolResult::success(serde_json::to_string(&result).unwrap())
+            .with_metadata("page_id", serde_json::json!(tab_id)))
+    }
+}
+
+// ============================================================================
+// List Tabs Tool
+// ============================================================================
+
+/// List open tabs tool.
+pub struct TabListTool {
+    definition: ToolDefinition,
+    manager: Arc<BrowserManager>,
+}
+
+impl TabListTool {
+    pub fn new(manager: Arc<BrowserManager>) -> Self {
+        Self {
+            definition: ToolDefinition::new(
+                "browser_tab_list",
+                "Browser List Tabs",
+                "List open browser tabs in the order they were opened, with their URL, title \
+                 and which one is active",
+            )
+            .with_read_only(),
+            manager,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for TabListTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        _params: serde_json::Value,
+        _ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let tabs = self.manager.list_tabs().await;
+        Ok(ToolResult::success(serde_json::to_string(&tabs).unwrap()))
+    }
+}
+
+// ============================================================================
+// Switch Tab Tool
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct TabSwitchParams {
+    pub tab_id: String,
+}
+
+/// Switch to a tab tool.
+pub struct TabSwitchTool {
+    definition: ToolDefinition,
+    manager: Arc<BrowserManager>,
+}
+
+impl TabSwitchTool {
+    pub fn new(manager: Arc<BrowserManager>) -> Self {
+        let definition = ToolDefinition::new(
+            "browser_tab_switch",
+            "Browser Switch Tab",
+            "Bring a browser tab to the front and make it the active tab",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "tab_id": {
+                    "type": "string",
+                    "description": "The tab to switch to"
+                }
+            },
+            "required": ["tab_id"]
+        }));
+        Self {
+            definition,
+            manager,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for TabSwitchTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        _ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: TabSwitchParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::ExecutionFailed(format!("Invalid params: {}", e)))?;
+
+        let tab = self
+            .manager
+            .switch_tab(&params.tab_id)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
+
+        Ok(ToolResult::success(serde_json::to_string(&tab).unwrap()))
+    }
+}
+
+// ============================================================================
+// Close Tab Tool
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct TabCloseParams {
+    pub tab_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TabCloseResult {
+    pub closed: String,
+    pub active: Option<String>,
+}
+
+/// Close a tab tool.
+pub struct TabCloseTool {
+    definition: ToolDefinition,
+    manager: Arc<BrowserManager>,
+}
+
+impl TabCloseTool {
+    pub fn new(manager: Arc<BrowserManager>) -> Self {
+        let definition = ToolDefinition::new(
+            "browser_tab_close",
+            "Browser Close Tab",
+            "Close a browser tab, by default the active one. Returns the tab active afterwards.",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "tab_id": {
+                    "type": "string",
+                    "description": "The tab to close (default: the active tab)"
+                }
+            }
+        }))
+        .with_exclusive_resource(TAB_RESOURCE);
+        Self {
+            definition,
+            manager,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for TabCloseTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        _ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: TabCloseParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::ExecutionFailed(format!("Invalid params: {}", e)))?;
+
+        let (closed, active) = self
+            .manager
+            .close_tab(params.tab_id.as_deref())
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
+
+        debug!("Closed tab {}", closed);
+        let result = TabCloseResult { closed, active };
+        Ok(ToolResult::success(serde_json::to_string(&result).unwrap()))
+    }
+}
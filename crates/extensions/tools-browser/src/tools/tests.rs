@@ -135,8 +135,16 @@ fn test_wait_for_params() {
     });
     let params: WaitForParams = serde_json::from_value(json).unwrap();
     assert_eq!(params.page_id, "page_1");
-    assert_eq!(params.selector, "#loading");
+    assert_eq!(params.selector.as_deref(), Some("#loading"));
     assert_eq!(params.timeout_ms, 30000);
+    assert_eq!(params.poll_interval_ms, 100);
+    assert!(matches!(
+        params.conditions().unwrap()[..],
+        [crate::wait::WaitCondition::Element {
+            state: crate::wait::ElementState::Present,
+            ..
+        }]
+    ));
 }
 
 #[test]
@@ -150,6 +158,43 @@ fn test_wait_for_params_with_timeout() {
     assert_eq!(params.timeout_ms, 5000);
 }
 
+#[test]
+fn test_wait_for_conditions() {
+    let json = serde_json::json!({
+        "page_id": "page_1",
+        "selector": "#cart",
+        "text": "3 items",
+        "url": "/checkout$",
+        "network_idle": true
+    });
+    let params: WaitForParams = serde_json::from_value(json).unwrap();
+    let conditions: Vec<String> = params
+        .conditions()
+        .unwrap()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(
+        conditions,
+        vec![
+            "#cart to contain \"3 items\"",
+            "URL to match /checkout$",
+            "network idle"
+        ]
+    );
+
+    let params: WaitForParams =
+        serde_json::from_value(serde_json::json!({"page_id": "page_1"})).unwrap();
+    assert!(params.conditions().is_err());
+
+    let json = serde_json::json!({"page_id": "page_1", "url": "("});
+    let params: WaitForParams = serde_json::from_value(json).unwrap();
+    assert!(params
+        .conditions()
+        .unwrap_err()
+        .contains("Invalid url regex"));
+}
+
 #[test]
 fn test_screenshot_result_serialize() {
     let result = ScreenshotResult {
@@ -171,6 +216,7 @@ fn test_get_dom_params() {
     let params: GetDomParams = serde_json::from_value(json).unwrap();
     assert_eq!(params.page_id, "page_1");
     assert!(params.compact); // default
+    assert!(!params.incremental); // default
 }
 
 #[test]
@@ -182,3 +228,120 @@ fn test_get_dom_params_full() {
     let params: GetDomParams = serde_json::from_value(json).unwrap();
     assert!(!params.compact);
 }
+
+#[test]
+fn test_tab_new_params_defaults() {
+    let params: TabNewParams = serde_json::from_value(serde_json::json!({})).unwrap();
+    assert_eq!(params.url, "about:blank");
+    assert!(params.activate);
+
+    let json = serde_json::json!({"url": "https://example.com", "activate": false});
+    let params: TabNewParams = serde_json::from_value(json).unwrap();
+    assert_eq!(params.url, "https://example.com");
+    assert!(!params.activate);
+}
+
+#[test]
+fn test_tab_close_params() {
+    let params: TabCloseParams = serde_json::from_value(serde_json::json!({})).unwrap();
+    assert!(params.tab_id.is_none());
+
+    let json = serde_json::json!({"tab_id": "page_3"});
+    let params: TabCloseParams = serde_json::from_value(json).unwrap();
+    assert_eq!(params.tab_id.as_deref(), Some("page_3"));
+}
+
+#[test]
+fn test_tab_close_locks_the_page() {
+    use autohands_protocols::tool::Tool;
+
+    let manager = std::sync::Arc::new(crate::manager::BrowserManager::new(Default::default()));
+    let tool = TabCloseTool::new(manager);
+    let resources = tool
+        .definition()
+        .exclusive_resources_for(&serde_json::json!({"tab_id": "page_3"}));
+    assert_eq!(resources, vec!["browser-page:page_3".to_string()]);
+}
+
+#[test]
+fn test_wait_for_download_params() {
+    let params: WaitForDownloadParams = serde_json::from_value(serde_json::json!({})).unwrap();
+    assert_eq!(params.timeout_ms, 60000);
+
+    let json = serde_json::json!({"timeout_ms": 5000});
+    let params: WaitForDownloadParams = serde_json::from_value(json).unwrap();
+    assert_eq!(params.timeout_ms, 5000);
+}
+
+#[test]
+fn test_network_params() {
+    let json = serde_json::json!({"page_id": "page_1"});
+    let params: NetworkStartParams = serde_json::from_value(json).unwrap();
+    assert!(params.url_pattern.is_none());
+    assert_eq!(params.max_requests, 1000);
+
+    let params: NetworkStopParams =
+        serde_json::from_value(serde_json::json!({"page_id": "page_1"})).unwrap();
+    assert_eq!(params.format, "summary");
+    assert!(!params.include_bodies);
+    assert!(params.path.is_none());
+}
+
+#[test]
+fn test_network_summary_line() {
+    use super::network::summary_line;
+    use crate::cdp::CapturedRequest;
+
+    let request = CapturedRequest {
+        url: "https://api.example.com/items".to_string(),
+        method: "POST".to_string(),
+        status: Some(200),
+        mime_type: Some("application/json".to_string()),
+        encoded_bytes: Some(2048),
+        duration_ms: Some(85.4),
+        ..Default::default()
+    };
+    assert_eq!(
+        summary_line(&request),
+        "POST 200 application/json 2.0 KB 85ms https://api.example.com/items"
+    );
+
+    let request = CapturedRequest {
+        url: "https://cdn.example.com/app.js".to_string(),
+        method: "GET".to_string(),
+        error: Some("net::ERR_BLOCKED_BY_CLIENT".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        summary_line(&request),
+        "GET failed (net::ERR_BLOCKED_BY_CLIENT) https://cdn.example.com/app.js"
+    );
+}
+
+#[test]
+fn test_storage_params() {
+    let json = serde_json::json!({"page_id": "page_1"});
+    let params: StorageGetParams = serde_json::from_value(json).unwrap();
+    assert_eq!(params.storage, crate::manager::StorageKind::Local);
+    assert!(params.key.is_none());
+
+    let json =
+        serde_json::json!({"page_id": "page_1", "storage": "session", "key": "k", "value": "v"});
+    let params: StorageSetParams = serde_json::from_value(json).unwrap();
+    assert_eq!(params.storage, crate::manager::StorageKind::Session);
+
+    let json = serde_json::json!({"page_id": "page_1", "storage": "cookies"});
+    assert!(serde_json::from_value::<StorageClearParams>(json).is_err());
+}
+
+#[test]
+fn test_cookies_clear_params() {
+    let json = serde_json::json!({"page_id": "page_1"});
+    let params: CookiesClearParams = serde_json::from_value(json).unwrap();
+    assert!(params.name.is_none());
+    assert!(!params.all);
+
+    let json = serde_json::json!({"page_id": "page_1", "urls": ["https://example.com"]});
+    let params: CookiesGetParams = serde_json::from_value(json).unwrap();
+    assert_eq!(params.urls, vec!["https://example.com".to_string()]);
+}
@@ -0,0 +1,257 @@
+//! Waiting for page conditions.
+//!
+//! Conditions are checked by polling the page through a
+//! [`BrowserBackend`], so they work with every engine. Network idle is
+//! detected in the page: fetch and XMLHttpRequest are wrapped to count the
+//! requests in flight, and finished resource loads are observed, so the
+//! page is idle once it has loaded, nothing is in flight and no load has
+//! finished for [`NETWORK_IDLE`].
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tracing::debug;
+
+use crate::backend::BrowserBackend;
+use crate::manager::BrowserError;
+
+/// How long the network must be quiet to count as idle.
+pub const NETWORK_IDLE: Duration = Duration::from_millis(500);
+
+/// State an element is waited for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ElementState {
+    /// In the DOM, visible or not.
+    #[default]
+    Present,
+    /// In the DOM with a size, and not hidden by CSS.
+    Visible,
+    /// Not visible, or not in the DOM.
+    Hidden,
+    /// Not in the DOM.
+    Removed,
+}
+
+impl ElementState {
+    fn name(self) -> &'static str {
+        match self {
+            ElementState::Present => "present",
+            ElementState::Visible => "visible",
+            ElementState::Hidden => "hidden",
+            ElementState::Removed => "removed",
+        }
+    }
+}
+
+/// Something to wait for on a page.
+#[derive(Debug, Clone)]
+pub enum WaitCondition {
+    /// The first element matching a selector is in a state.
+    Element {
+        selector: String,
+        state: ElementState,
+    },
+    /// The page's URL matches a regex.
+    Url(Regex),
+    /// The text of the page, or of the element matching `selector`,
+    /// contains `text`.
+    Text {
+        text: String,
+        selector: Option<String>,
+    },
+    /// A JavaScript expression evaluates to a truthy value.
+    Predicate(String),
+    /// The page has loaded and made no requests for [`NETWORK_IDLE`].
+    NetworkIdle,
+}
+
+impl WaitCondition {
+    /// Expression evaluating whether the condition holds, or for network
+    /// idle, the page's network activity.
+    fn script(&self) -> Option<String> {
+        match self {
+            WaitCondition::Element { selector, state } => Some(element_script(selector, *state)),
+            WaitCondition::Url(_) => None,
+            WaitCondition::Text { text, selector } => Some(match selector {
+                Some(selector) => format!(
+                    "(document.querySelector({})?.innerText ?? '').includes({})",
+                    json!(selector),
+                    json!(text)
+                ),
+                None => format!("(document.body?.innerText ?? '').includes({})", json!(text)),
+            }),
+            WaitCondition::Predicate(script) => Some(script.clone()),
+            WaitCondition::NetworkIdle => Some(NETWORK_ACTIVITY_SCRIPT.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for WaitCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WaitCondition::Element { selector, state } => {
+                write!(f, "{} to be {}", selector, state.name())
+            }
+            WaitCondition::Url(regex) => write!(f, "URL to match {}", regex),
+            WaitCondition::Text {
+                text,
+                selector: Some(selector),
+            } => write!(f, "{} to contain {:?}", selector, text),
+            WaitCondition::Text { text, .. } => write!(f, "page to contain {:?}", text),
+            WaitCondition::Predicate(script) => write!(f, "{} to be true", script),
+            WaitCondition::NetworkIdle => write!(f, "network idle"),
+        }
+    }
+}
+
+/// Wait until every condition holds on a page at once, checking every
+/// `interval` until `timeout`.
+pub async fn wait_for(
+    backend: &dyn BrowserBackend,
+    page_id: &str,
+    conditions: &[WaitCondition],
+    timeout: Duration,
+    interval: Duration,
+) -> Result<(), BrowserError> {
+    let deadline = Instant::now() + timeout;
+    let mut network = NetworkIdle::default();
+    loop {
+        let mut pending = None;
+        for condition in conditions {
+            if !check(backend, page_id, condition, &mut network).await? {
+                pending = Some(condition);
+                break;
+            }
+        }
+        let Some(pending) = pending else {
+            return Ok(());
+        };
+        if Instant::now() >= deadline {
+            return Err(BrowserError::ActionFailed(format!(
+                "Timeout after {}ms waiting for {}",
+                timeout.as_millis(),
+                pending
+            )));
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
+/// Whether a condition holds now.
+///
+/// Failures to evaluate, as while the page navigates, count as not
+/// holding; a closed page or browser fails the wait.
+async fn check(
+    backend: &dyn BrowserBackend,
+    page_id: &str,
+    condition: &WaitCondition,
+    network: &mut NetworkIdle,
+) -> Result<bool, BrowserError> {
+    let result = match condition.script() {
+        None => backend.get_url(page_id).await.map(Value::String),
+        Some(script) => backend.evaluate(page_id, &script).await,
+    };
+    let value = match result {
+        Ok(value) => value,
+        Err(e @ (BrowserError::PageNotFound(_) | BrowserError::NotConnected)) => return Err(e),
+        Err(e) => {
+            debug!("Wait condition not checked: {}", e);
+            return Ok(false);
+        }
+    };
+    Ok(match condition {
+        WaitCondition::Url(regex) => regex.is_match(value.as_str().unwrap_or_default()),
+        WaitCondition::NetworkIdle => network.update(&value, Instant::now()),
+        _ => is_truthy(&value),
+    })
+}
+
+/// Tracks when a page's network activity last changed.
+#[derive(Debug, Default)]
+struct NetworkIdle {
+    finished: Option<u64>,
+    quiet_since: Option<Instant>,
+}
+
+impl NetworkIdle {
+    /// Record the activity reported by the network activity script at
+    /// `now`, and return whether the network is idle.
+    fn update(&mut self, activity: &Value, now: Instant) -> bool {
+        let ready = activity["ready"].as_bool().unwrap_or(false);
+        let pending = activity["pending"].as_u64().unwrap_or(0);
+        let finished = activity["finished"].as_u64();
+        if !ready || pending > 0 || finished != self.finished {
+            self.finished = finished;
+            self.quiet_since = None;
+            return false;
+        }
+        let since = *self.quiet_since.get_or_insert(now);
+        now.duration_since(since) >= NETWORK_IDLE
+    }
+}
+
+/// JavaScript truthiness of a JSON result.
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(_) | Value::Object(_) => true,
+    }
+}
+
+fn element_script(selector: &str, state: ElementState) -> String {
+    let find = format!("document.querySelector({})", json!(selector));
+    let visible = format!(
+        "(() => {{ const el = {}; if (!el) return false; \
+         const style = getComputedStyle(el); const rect = el.getBoundingClientRect(); \
+         return style.display !== 'none' && style.visibility !== 'hidden' \
+         && rect.width > 0 && rect.height > 0; }})()",
+        find
+    );
+    match state {
+        ElementState::Present => format!("{} !== null", find),
+        ElementState::Visible => visible,
+        ElementState::Hidden => format!("!{}", visible),
+        ElementState::Removed => format!("{} === null", find),
+    }
+}
+
+/// Counts the page's requests in flight and finished resource loads,
+/// installing the counters on first use.
+const NETWORK_ACTIVITY_SCRIPT: &str = r#"(() => {
+    if (!window.__autohandsNetwork) {
+        const net = { pending: 0, finished: 0 };
+        window.__autohandsNetwork = net;
+        const fetch = window.fetch;
+        if (fetch) {
+            window.fetch = function (...args) {
+                net.pending++;
+                return fetch.apply(this, args).finally(() => net.pending--);
+            };
+        }
+        const send = XMLHttpRequest.prototype.send;
+        XMLHttpRequest.prototype.send = function (...args) {
+            net.pending++;
+            this.addEventListener('loadend', () => net.pending--, { once: true });
+            return send.apply(this, args);
+        };
+        new PerformanceObserver((list) => { net.finished += list.getEntries().length; })
+            .observe({ type: 'resource', buffered: true });
+    }
+    const net = window.__autohandsNetwork;
+    return {
+        ready: document.readyState === 'complete',
+        pending: Math.max(net.pending, 0),
+        finished: net.finished,
+    };
+})()"#;
+
+#[cfg(test)]
+#[path = "wait_tests.rs"]
+mod tests;
@@ -0,0 +1,62 @@
+use super::*;
+
+#[test]
+fn test_element_scripts() {
+    let script = element_script("#it's", ElementState::Present);
+    assert_eq!(script, r#"document.querySelector("#it's") !== null"#);
+    assert_eq!(
+        element_script("#spinner", ElementState::Removed),
+        r##"document.querySelector("#spinner") === null"##
+    );
+    assert!(element_script("#spinner", ElementState::Hidden).starts_with("!(() =>"));
+}
+
+#[test]
+fn test_condition_display() {
+    let condition = WaitCondition::Element {
+        selector: "#spinner".to_string(),
+        state: ElementState::Hidden,
+    };
+    assert_eq!(condition.to_string(), "#spinner to be hidden");
+
+    let condition = WaitCondition::Url(Regex::new("/checkout$").unwrap());
+    assert_eq!(condition.to_string(), "URL to match /checkout$");
+    assert!(condition.script().is_none());
+
+    let condition = WaitCondition::Text {
+        text: "Saved".to_string(),
+        selector: None,
+    };
+    assert_eq!(condition.to_string(), "page to contain \"Saved\"");
+}
+
+#[test]
+fn test_is_truthy() {
+    assert!(is_truthy(&json!(true)));
+    assert!(is_truthy(&json!(2)));
+    assert!(is_truthy(&json!("x")));
+    assert!(is_truthy(&json!([])));
+    assert!(!is_truthy(&json!(0)));
+    assert!(!is_truthy(&json!("")));
+    assert!(!is_truthy(&Value::Null));
+}
+
+#[test]
+fn test_network_idle() {
+    let mut network = NetworkIdle::default();
+    let start = Instant::now();
+    let busy = json!({"ready": true, "pending": 1, "finished": 3});
+    let quiet = json!({"ready": true, "pending": 0, "finished": 4});
+
+    assert!(!network.update(&busy, start));
+    assert!(!network.update(&quiet, start));
+    // Quiet since the first poll without changes
+    assert!(!network.update(&quiet, start + Duration::from_millis(100)));
+    assert!(!network.update(&quiet, start + Duration::from_millis(500)));
+    assert!(network.update(&quiet, start + Duration::from_millis(600)));
+
+    // A finished load restarts the quiet period
+    let loaded = json!({"ready": true, "pending": 0, "finished": 5});
+    assert!(!network.update(&loaded, start + Duration::from_millis(700)));
+    assert!(!network.update(&loaded, start + Duration::from_millis(800)));
+}
@@ -13,6 +13,7 @@ fn test_config() -> BrowserManagerConfig {
         viewport_height: 720,
         profile_dir: Some(std::path::PathBuf::from("/tmp/autohands-test-profile")),
         headless: true, // Use headless for CI
+        ..Default::default()
     }
 }
 
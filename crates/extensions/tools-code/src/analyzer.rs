@@ -167,6 +167,58 @@ impl PatternAnalyzer {
         elements
     }
 
+    /// Number of top-level definitions (functions, types, classes) in
+    /// `content`, or `None` for languages without definitions.
+    ///
+    /// Rust uses [`Self::analyze_rust`]; other languages count lines
+    /// starting with a definition keyword.
+    pub fn count_symbols(language: &str, content: &str) -> Option<usize> {
+        let keywords: &[&str] = match language {
+            "rust" => return Some(Self::analyze_rust(content).len()),
+            "python" => &["def ", "async def ", "class "],
+            "javascript" | "javascript-react" | "typescript" | "typescript-react" => &[
+                "function ",
+                "async function ",
+                "class ",
+                "interface ",
+                "type ",
+                "enum ",
+            ],
+            "go" => &["func ", "type "],
+            "java" | "kotlin" | "csharp" | "scala" | "swift" => &[
+                "class ",
+                "interface ",
+                "enum ",
+                "record ",
+                "struct ",
+                "object ",
+                "trait ",
+                "fun ",
+                "func ",
+                "def ",
+            ],
+            "ruby" => &["def ", "class ", "module "],
+            "php" => &["function ", "class ", "interface ", "trait "],
+            _ => return None,
+        };
+        let count = content
+            .lines()
+            .map(|line| {
+                let mut line = line.trim_start();
+                for modifier in [
+                    "export ", "default ", "pub ", "public ", "private ", "protected ",
+                    "internal ", "static ", "abstract ", "final ", "sealed ", "data ",
+                    "open ", "declare ",
+                ] {
+                    line = line.strip_prefix(modifier).unwrap_or(line);
+                }
+                line
+            })
+            .filter(|line| keywords.iter().any(|k| line.starts_with(k)))
+            .count();
+        Some(count)
+    }
+
     fn extract_rust_fn_name(line: &str) -> Option<String> {
         let mut line = line;
         // Strip pub if present
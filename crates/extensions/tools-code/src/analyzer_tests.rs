@@ -160,6 +160,17 @@
         assert_eq!(detect_language(&PathBuf::from("")), None);
     }
 
+    #[test]
+    fn test_count_symbols() {
+        let rust = "pub mod a;\npub fn f() {}\nstruct S;";
+        assert_eq!(PatternAnalyzer::count_symbols("rust", rust), Some(3));
+        let python = "import os\n\nclass A:\n    def m(self):\n        pass\n\nasync def f():\n    pass";
+        assert_eq!(PatternAnalyzer::count_symbols("python", python), Some(3));
+        let ts = "export default function f() {}\nexport interface I {}\nconst x = 1;";
+        assert_eq!(PatternAnalyzer::count_symbols("typescript", ts), Some(2));
+        assert_eq!(PatternAnalyzer::count_symbols("json", "{}"), None);
+    }
+
     #[test]
     fn test_analyze_rust_module() {
         let code = "mod utils;\npub mod helpers;";
@@ -3,7 +3,9 @@
 //! Code analysis tools for AutoHands.
 
 pub mod analyzer;
+pub mod repo_map;
 pub mod tools;
 
 pub use analyzer::{detect_language, CodeElement, ElementType, FileAnalysis, PatternAnalyzer};
+pub use repo_map::RepoMapTool;
 pub use tools::{AnalyzeCodeTool, FindSymbolTool};
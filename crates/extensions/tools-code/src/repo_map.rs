@@ -0,0 +1,364 @@
+//! Repository map tool.
+//!
+//! Before touching a codebase an agent needs its layout. Instead of a
+//! series of directory listings, `repo_map` returns the workspace as one
+//! compressed tree: directories with their file counts and sizes, files
+//! with their sizes and symbol counts, and key files (manifests, READMEs,
+//! entry points) marked. Directories below the requested depth are folded
+//! into a single line, and `.gitignore` rules are honored, so the map stays
+//! small on large repositories.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use ignore::overrides::OverrideBuilder;
+use ignore::WalkBuilder;
+use serde::Deserialize;
+use tracing::debug;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{PathAccess, Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::types::RiskLevel;
+
+use crate::analyzer::{detect_language, PatternAnalyzer};
+
+/// Files walked at most; larger trees are mapped partially.
+const MAX_FILES: usize = 50_000;
+
+/// Files larger than this are not read for symbols.
+const MAX_SYMBOL_FILE_BYTES: u64 = 512 * 1024;
+
+/// Manifests, READMEs and entry points, marked with `*` in the map.
+const KEY_FILES: &[&str] = &[
+    "Cargo.toml",
+    "package.json",
+    "pyproject.toml",
+    "setup.py",
+    "requirements.txt",
+    "go.mod",
+    "pom.xml",
+    "build.gradle",
+    "build.gradle.kts",
+    "Gemfile",
+    "composer.json",
+    "Makefile",
+    "Dockerfile",
+    "main.rs",
+    "lib.rs",
+    "main.go",
+    "main.py",
+    "__main__.py",
+    "app.py",
+    "index.js",
+    "index.ts",
+    "main.ts",
+];
+
+/// Parameters for the repo_map tool.
+#[derive(Debug, Deserialize)]
+pub struct RepoMapParams {
+    /// Directory to map, relative to the working directory.
+    #[serde(default = "default_path")]
+    pub path: String,
+    /// Directory levels listed before they are folded.
+    #[serde(default = "default_depth")]
+    pub depth: usize,
+    /// Extra glob patterns of paths to leave out.
+    #[serde(default)]
+    pub ignore: Vec<String>,
+    /// Honor `.gitignore` (and `.ignore`) files.
+    #[serde(default = "default_true")]
+    pub respect_gitignore: bool,
+    /// Include hidden files and directories.
+    #[serde(default)]
+    pub include_hidden: bool,
+    /// Files listed per directory; key files are always listed.
+    #[serde(default = "default_max_entries")]
+    pub max_entries: usize,
+}
+
+fn default_path() -> String {
+    ".".to_string()
+}
+
+fn default_depth() -> usize {
+    3
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_max_entries() -> usize {
+    25
+}
+
+/// Repository map tool.
+pub struct RepoMapTool {
+    definition: ToolDefinition,
+}
+
+impl RepoMapTool {
+    pub fn new() -> Self {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Directory to map (default: the working directory)"
+                },
+                "depth": {
+                    "type": "integer",
+                    "description": "Directory levels to list, deeper ones summarized (default: 3)"
+                },
+                "ignore": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Glob patterns of paths to leave out, e.g. \"*.lock\""
+                },
+                "respect_gitignore": {
+                    "type": "boolean",
+                    "description": "Leave out paths ignored by .gitignore (default: true)"
+                },
+                "include_hidden": {
+                    "type": "boolean",
+                    "description": "Include hidden files and directories (default: false)"
+                },
+                "max_entries": {
+                    "type": "integer",
+                    "description": "Files listed per directory (default: 25)"
+                }
+            }
+        });
+
+        Self {
+            definition: ToolDefinition::new(
+                "repo_map",
+                "Repository Map",
+                "Compressed tree of the workspace: directories with file counts and sizes, \
+                 files with sizes and symbol counts, key files marked with *. \
+                 Use it first to get oriented in a codebase.",
+            )
+            .with_parameters_schema(schema)
+            .with_risk_level(RiskLevel::Low)
+            .with_read_only(),
+        }
+    }
+}
+
+impl Default for RepoMapTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Tool for RepoMapTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: RepoMapParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
+
+        let root = ctx.resolve_path(&params.path, PathAccess::Read)?;
+        if !root.is_dir() {
+            return Err(ToolError::ResourceNotFound(format!(
+                "Directory not found: {}",
+                params.path
+            )));
+        }
+        debug!("Mapping repository: {:?}", root);
+
+        let sandbox = ctx.sandbox.clone();
+        let map = tokio::task::spawn_blocking(move || {
+            let tree = scan(&root, &params, move |path| {
+                sandbox.as_ref().is_none_or(|s| s.check_denied(path).is_ok())
+            })?;
+            Ok::<_, ToolError>(tree.render(&root, &params))
+        })
+        .await
+        .map_err(|e| ToolError::ExecutionFailed(e.to_string()))??;
+
+        Ok(ToolResult::success(map))
+    }
+}
+
+/// A directory of the map, with the totals of everything below it.
+#[derive(Debug, Default)]
+pub(crate) struct DirNode {
+    dirs: BTreeMap<String, DirNode>,
+    files: Vec<FileNode>,
+    file_count: usize,
+    bytes: u64,
+    /// Whether the scan stopped at [`MAX_FILES`].
+    truncated: bool,
+}
+
+#[derive(Debug)]
+struct FileNode {
+    name: String,
+    path: PathBuf,
+    bytes: u64,
+}
+
+impl DirNode {
+    fn insert(&mut self, relative: &Path, path: PathBuf, bytes: u64) {
+        self.file_count += 1;
+        self.bytes += bytes;
+        let mut components = relative.components();
+        let Some(first) = components.next() else {
+            return;
+        };
+        let rest = components.as_path();
+        let name = first.as_os_str().to_string_lossy().into_owned();
+        if rest.as_os_str().is_empty() {
+            self.files.push(FileNode { name, path, bytes });
+        } else {
+            self.dirs.entry(name).or_default().insert(rest, path, bytes);
+        }
+    }
+
+    /// The map of the tree rooted at `root`.
+    pub(crate) fn render(&self, root: &Path, params: &RepoMapParams) -> String {
+        let name = root
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| root.display().to_string());
+        let mut out = format!("{}/  {}\n", name, self.totals());
+        self.render_children(params, 1, &mut out);
+        if self.truncated {
+            out.push_str(&format!("(stopped after {} files)\n", MAX_FILES));
+        }
+        out
+    }
+
+    fn render_children(&self, params: &RepoMapParams, level: usize, out: &mut String) {
+        let indent = "  ".repeat(level);
+        for (name, dir) in &self.dirs {
+            if level < params.depth {
+                out.push_str(&format!("{}{}/  {}\n", indent, name, dir.totals()));
+                dir.render_children(params, level + 1, out);
+            } else {
+                out.push_str(&format!("{}{}/  {} …\n", indent, name, dir.totals()));
+            }
+        }
+
+        let mut listed = 0;
+        let (mut hidden_count, mut hidden_bytes) = (0, 0);
+        for file in &self.files {
+            let key = is_key_file(&file.name);
+            if !key && listed >= params.max_entries {
+                hidden_count += 1;
+                hidden_bytes += file.bytes;
+                continue;
+            }
+            if !key {
+                listed += 1;
+            }
+            let mut line = format!("{}{}", indent, file.name);
+            if key {
+                line.push_str(" *");
+            }
+            line.push_str(&format!("  {}", format_size(file.bytes)));
+            if let Some(symbols) = file_symbols(file) {
+                line.push_str(&format!(", {} symbols", symbols));
+            }
+            out.push_str(&line);
+            out.push('\n');
+        }
+        if hidden_count > 0 {
+            out.push_str(&format!(
+                "{}… {} more files ({})\n",
+                indent,
+                hidden_count,
+                format_size(hidden_bytes)
+            ));
+        }
+    }
+
+    fn totals(&self) -> String {
+        let noun = if self.file_count == 1 { "file" } else { "files" };
+        format!("({} {}, {})", self.file_count, noun, format_size(self.bytes))
+    }
+}
+
+/// Walk `root`, leaving out ignored paths and those `allowed` refuses.
+pub(crate) fn scan(
+    root: &Path,
+    params: &RepoMapParams,
+    allowed: impl Fn(&Path) -> bool + Send + Sync + 'static,
+) -> Result<DirNode, ToolError> {
+    let mut overrides = OverrideBuilder::new(root);
+    for pattern in &params.ignore {
+        overrides
+            .add(&format!("!{}", pattern))
+            .map_err(|e| ToolError::InvalidParameters(format!("Invalid ignore pattern: {}", e)))?;
+    }
+    let overrides = overrides
+        .build()
+        .map_err(|e| ToolError::InvalidParameters(format!("Invalid ignore pattern: {}", e)))?;
+
+    let walk = WalkBuilder::new(root)
+        .hidden(!params.include_hidden)
+        .git_ignore(params.respect_gitignore)
+        .git_exclude(params.respect_gitignore)
+        .ignore(params.respect_gitignore)
+        .parents(params.respect_gitignore)
+        .git_global(false)
+        .require_git(false)
+        .overrides(overrides)
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .filter_entry(move |entry| entry.file_name() != ".git" && allowed(entry.path()))
+        .build();
+
+    let mut tree = DirNode::default();
+    for entry in walk.filter_map(Result::ok) {
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        if tree.file_count >= MAX_FILES {
+            tree.truncated = true;
+            break;
+        }
+        let Ok(relative) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+        let bytes = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        tree.insert(relative, entry.path().to_path_buf(), bytes);
+    }
+    Ok(tree)
+}
+
+fn is_key_file(name: &str) -> bool {
+    KEY_FILES.contains(&name) || name.to_ascii_lowercase().starts_with("readme")
+}
+
+/// Symbol count of a source file, if its language has symbols.
+fn file_symbols(file: &FileNode) -> Option<usize> {
+    let language = detect_language(&file.path)?;
+    if file.bytes > MAX_SYMBOL_FILE_BYTES {
+        return None;
+    }
+    let content = std::fs::read_to_string(&file.path).ok()?;
+    PatternAnalyzer::count_symbols(&language, &content).filter(|n| *n > 0)
+}
+
+/// Size in B, KB or MB.
+fn format_size(bytes: u64) -> String {
+    match bytes {
+        b if b < 1024 => format!("{} B", b),
+        b if b < 1024 * 1024 => format!("{:.1} KB", b as f64 / 1024.0),
+        b => format!("{:.1} MB", b as f64 / (1024.0 * 1024.0)),
+    }
+}
+
+#[cfg(test)]
+#[path = "repo_map_tests.rs"]
+mod tests;
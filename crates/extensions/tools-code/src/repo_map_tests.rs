@@ -0,0 +1,120 @@
+use super::*;
+use std::sync::Arc;
+
+use autohands_protocols::tool::SandboxPolicy;
+use tempfile::TempDir;
+
+fn write(dir: &Path, path: &str, content: &str) {
+    let path = dir.join(path);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, content).unwrap();
+}
+
+fn project() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    write(dir.path(), "Cargo.toml", "[package]\nname = \"demo\"\n");
+    write(dir.path(), ".gitignore", "target/\n*.log\n");
+    write(dir.path(), "src/lib.rs", "pub mod util;\npub fn run() {}\npub struct Config;\n");
+    write(dir.path(), "src/util/mod.rs", "fn helper() {}\n");
+    write(dir.path(), "src/util/deep/inner.rs", "fn inner() {}\n");
+    write(dir.path(), "target/debug/demo", "binary");
+    write(dir.path(), "debug.log", "log");
+    write(dir.path(), "fixtures/data.json", "{}");
+    write(dir.path(), ".env", "TOKEN=secret");
+    dir
+}
+
+async fn map(dir: &TempDir, params: serde_json::Value) -> String {
+    let ctx = ToolContext::new("test", dir.path().to_path_buf());
+    RepoMapTool::new().execute(params, ctx).await.unwrap().content
+}
+
+#[test]
+fn test_repo_map_tool_creation() {
+    let tool = RepoMapTool::default();
+    assert_eq!(tool.definition().id, "repo_map");
+    assert_eq!(tool.definition().risk_level, RiskLevel::Low);
+}
+
+#[tokio::test]
+async fn test_repo_map_honors_gitignore() {
+    let dir = project();
+    let map = map(&dir, serde_json::json!({})).await;
+
+    assert!(map.contains("Cargo.toml *"), "{}", map);
+    assert!(map.contains("lib.rs *"), "{}", map);
+    assert!(map.contains("3 symbols"), "{}", map);
+    assert!(map.contains("fixtures/  (1 file, 2 B)"), "{}", map);
+    assert!(!map.contains("target"), "{}", map);
+    assert!(!map.contains("debug.log"), "{}", map);
+    assert!(!map.contains(".env"), "{}", map);
+
+    let map = self::map(&dir, serde_json::json!({"respect_gitignore": false})).await;
+    assert!(map.contains("target/"), "{}", map);
+    assert!(map.contains("debug.log"), "{}", map);
+}
+
+#[tokio::test]
+async fn test_repo_map_depth_and_ignore() {
+    let dir = project();
+    let map = map(&dir, serde_json::json!({"depth": 1, "ignore": ["fixtures/"]})).await;
+
+    assert!(map.contains("  src/  (3 files,"), "{}", map);
+    assert!(map.contains("…"), "{}", map);
+    assert!(!map.contains("lib.rs"), "{}", map);
+    assert!(!map.contains("fixtures"), "{}", map);
+
+    let map = self::map(&dir, serde_json::json!({"depth": 2})).await;
+    assert!(map.contains("    util/  (2 files,"), "{}", map);
+    assert!(!map.contains("mod.rs"), "{}", map);
+}
+
+#[tokio::test]
+async fn test_repo_map_folds_long_directories() {
+    let dir = TempDir::new().unwrap();
+    for i in 0..5 {
+        write(dir.path(), &format!("notes/{}.txt", i), "note");
+    }
+    write(dir.path(), "notes/README.md", "# Notes");
+    let map = map(&dir, serde_json::json!({"max_entries": 2})).await;
+
+    assert!(map.contains("1.txt"), "{}", map);
+    assert!(!map.contains("2.txt"), "{}", map);
+    assert!(map.contains("README.md *"), "{}", map);
+    assert!(map.contains("… 3 more files (12 B)"), "{}", map);
+}
+
+#[tokio::test]
+async fn test_repo_map_skips_denied_paths() {
+    let dir = project();
+    let sandbox = SandboxPolicy {
+        denied: vec!["fixtures".to_string()],
+        ..Default::default()
+    };
+    let ctx = ToolContext::new("test", dir.path().to_path_buf()).with_sandbox(Arc::new(sandbox));
+    let map = RepoMapTool::new().execute(serde_json::json!({}), ctx).await.unwrap().content;
+    assert!(!map.contains("fixtures"), "{}", map);
+    assert!(map.contains("src/"), "{}", map);
+}
+
+#[tokio::test]
+async fn test_repo_map_errors() {
+    let dir = project();
+    let ctx = ToolContext::new("test", dir.path().to_path_buf());
+    let result = RepoMapTool::new()
+        .execute(serde_json::json!({"path": "missing"}), ctx.clone())
+        .await;
+    assert!(result.is_err());
+
+    let result = RepoMapTool::new()
+        .execute(serde_json::json!({"ignore": ["a{"]}), ctx)
+        .await;
+    assert!(matches!(result, Err(ToolError::InvalidParameters(_))));
+}
+
+#[test]
+fn test_format_size() {
+    assert_eq!(format_size(512), "512 B");
+    assert_eq!(format_size(1536), "1.5 KB");
+    assert_eq!(format_size(3 * 1024 * 1024), "3.0 MB");
+}
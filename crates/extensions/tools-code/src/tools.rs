@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 use tracing::debug;
 
 use autohands_protocols::error::ToolError;
-use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::tool::{PathAccess, Tool, ToolContext, ToolDefinition, ToolResult};
 
 use crate::analyzer::{detect_language, FileAnalysis, PatternAnalyzer};
 
@@ -29,7 +29,8 @@ impl AnalyzeCodeTool {
                 "analyze_code",
                 "Analyze Code",
                 "Analyze source code to extract functions, classes, structs, and other elements",
-            ),
+            )
+            .with_read_only(),
         }
     }
 }
@@ -54,7 +55,7 @@ impl Tool for AnalyzeCodeTool {
         let params: AnalyzeCodeParams = serde_json::from_value(params)
             .map_err(|e| ToolError::ExecutionFailed(format!("Invalid input: {}", e)))?;
 
-        let path = ctx.work_dir.join(&params.path);
+        let path = ctx.resolve_path(&params.path, PathAccess::Read)?;
         debug!("Analyzing code: {:?}", path);
 
         if !path.exists() {
@@ -121,7 +122,8 @@ impl FindSymbolTool {
                 "find_symbol",
                 "Find Symbol",
                 "Search for a symbol (function, class, variable) in the codebase",
-            ),
+            )
+            .with_read_only(),
         }
     }
 }
@@ -146,7 +148,7 @@ impl Tool for FindSymbolTool {
         let params: FindSymbolParams = serde_json::from_value(params)
             .map_err(|e| ToolError::ExecutionFailed(format!("Invalid input: {}", e)))?;
 
-        let path = ctx.work_dir.join(&params.path);
+        let path = ctx.resolve_path(&params.path, PathAccess::Read)?;
         debug!("Finding symbol '{}' in {:?}", params.symbol, path);
 
         if !path.exists() {
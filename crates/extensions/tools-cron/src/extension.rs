@@ -5,18 +5,26 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 
+use autohands_api::{JobStore, MemoryJobStore};
 use autohands_protocols::error::ExtensionError;
 use autohands_protocols::extension::{Extension, ExtensionContext, ExtensionManifest, Provides};
 use autohands_protocols::types::Version;
 
+use crate::limits::CronLimits;
 use crate::tools::{CronCreateTool, CronDeleteTool, CronListTool, CronStatusTool};
 
 /// Cron tools extension providing cron job management for agents.
 pub struct CronToolsExtension {
     manifest: ExtensionManifest,
+    store: Arc<dyn JobStore>,
+    limits: CronLimits,
 }
 
 impl CronToolsExtension {
+    /// Create the extension over an in-memory store.
+    ///
+    /// Jobs in it are not run unless a scheduler shares the store; see
+    /// [`with_store`](Self::with_store).
     pub fn new() -> Self {
         let mut manifest = ExtensionManifest::new(
             "tools-cron",
@@ -35,7 +43,23 @@ impl CronToolsExtension {
             ..Default::default()
         };
 
-        Self { manifest }
+        Self {
+            manifest,
+            store: Arc::new(MemoryJobStore::new()),
+            limits: CronLimits::default(),
+        }
+    }
+
+    /// Manage jobs in the given store, typically the job scheduler's.
+    pub fn with_store(mut self, store: Arc<dyn JobStore>) -> Self {
+        self.store = store;
+        self
+    }
+
+    /// Set the limits on agent-created jobs.
+    pub fn with_limits(mut self, limits: CronLimits) -> Self {
+        self.limits = limits;
+        self
     }
 }
 
@@ -53,14 +77,16 @@ impl Extension for CronToolsExtension {
 
     async fn initialize(&mut self, ctx: ExtensionContext) -> Result<(), ExtensionError> {
         // Register tools
+        ctx.tool_registry.register_tool(Arc::new(CronCreateTool::new(
+            self.store.clone(),
+            self.limits,
+        )))?;
         ctx.tool_registry
-            .register_tool(Arc::new(CronCreateTool::new()))?;
-        ctx.tool_registry
-            .register_tool(Arc::new(CronListTool::new()))?;
+            .register_tool(Arc::new(CronListTool::new(self.store.clone())))?;
         ctx.tool_registry
-            .register_tool(Arc::new(CronDeleteTool::new()))?;
+            .register_tool(Arc::new(CronDeleteTool::new(self.store.clone())))?;
         ctx.tool_registry
-            .register_tool(Arc::new(CronStatusTool::new()))?;
+            .register_tool(Arc::new(CronStatusTool::new(self.store.clone())))?;
 
         Ok(())
     }
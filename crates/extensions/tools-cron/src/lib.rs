@@ -2,15 +2,19 @@
 //!
 //! This extension provides tools for agents to manage scheduled tasks (cron jobs).
 //! It allows agents to create, list, and delete their own scheduled tasks.
+//! Tasks are jobs in the shared [`JobStore`](autohands_api::JobStore), run by
+//! the API's job scheduler.
 //!
 //! ## Tools
 //!
-//! - `cron_create`: Create a new scheduled task
+//! - `cron_create`: Create a new scheduled task (cron, `every` interval or one-shot `at`)
 //! - `cron_list`: List all scheduled tasks
 //! - `cron_delete`: Delete a scheduled task
 //! - `cron_status`: Get status of a scheduled task
 
 pub mod extension;
+pub mod limits;
 pub mod tools;
 
 pub use extension::CronToolsExtension;
+pub use limits::CronLimits;
@@ -0,0 +1,35 @@
+//! Limits on jobs created through the cron tools.
+//!
+//! Agents can schedule their own work, and a job's run can create more jobs.
+//! These limits, together with the scheduler's global concurrency limit, keep
+//! that from growing without bound.
+
+/// Default most jobs in the store for `cron_create` to add another.
+pub const DEFAULT_MAX_JOBS: usize = 20;
+
+/// Default longest start jitter an agent may request, in seconds.
+pub const DEFAULT_MAX_JITTER_SECS: u64 = 3600;
+
+/// Default shortest interval of `every` schedules, in seconds.
+pub const DEFAULT_MIN_INTERVAL_SECS: u64 = 60;
+
+/// Limits enforced by `cron_create`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CronLimits {
+    /// `cron_create` refuses to add a job once the store holds this many.
+    pub max_jobs: usize,
+    /// Longest start jitter, in seconds.
+    pub max_jitter_secs: u64,
+    /// Shortest interval of `every` schedules, in seconds.
+    pub min_interval_secs: u64,
+}
+
+impl Default for CronLimits {
+    fn default() -> Self {
+        Self {
+            max_jobs: DEFAULT_MAX_JOBS,
+            max_jitter_secs: DEFAULT_MAX_JITTER_SECS,
+            min_interval_secs: DEFAULT_MIN_INTERVAL_SECS,
+        }
+    }
+}
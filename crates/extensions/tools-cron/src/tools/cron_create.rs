@@ -1,12 +1,22 @@
 //! Create cron job tool.
 
+use std::str::FromStr;
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
+use autohands_api::{CatchUpPolicy, Job, JobDefinition, JobSchedule, JobStore, OverlapPolicy};
 use autohands_protocols::error::ToolError;
 use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
 use autohands_protocols::types::RiskLevel;
 
+use super::store_error;
+use crate::limits::CronLimits;
+
+/// Agent running jobs that do not name one.
+const DEFAULT_AGENT: &str = "general";
+
 /// Parameters for cron_create tool.
 #[derive(Debug, Deserialize)]
 struct CronCreateParams {
@@ -14,12 +24,31 @@ struct CronCreateParams {
     name: String,
     /// Cron expression (e.g., "0 0 * * *" for daily at midnight).
     /// Supports 5-field (standard) or 6-field (with seconds) format.
-    schedule: String,
+    #[serde(default)]
+    schedule: Option<String>,
+    /// Interval between runs (e.g., "15m", "1h30m").
+    #[serde(default)]
+    every: Option<String>,
+    /// Single run time (RFC 3339 timestamp).
+    #[serde(default)]
+    at: Option<String>,
     /// Command or prompt to execute when the task runs.
     command: String,
+    /// Optional description.
+    #[serde(default)]
+    description: Option<String>,
     /// Whether the task is enabled (default: true).
     #[serde(default = "default_enabled")]
     enabled: bool,
+    /// Agent to run the task.
+    #[serde(default)]
+    agent: Option<String>,
+    /// Upper bound of the random start delay, in seconds.
+    #[serde(default)]
+    jitter_secs: u64,
+    /// What to do when a run is due while the previous one is still running.
+    #[serde(default)]
+    overlap: OverlapPolicy,
 }
 
 fn default_enabled() -> bool {
@@ -44,10 +73,12 @@ struct CronCreateResponse {
 /// Create cron job tool implementation.
 pub struct CronCreateTool {
     definition: ToolDefinition,
+    store: Arc<dyn JobStore>,
+    limits: CronLimits,
 }
 
 impl CronCreateTool {
-    pub fn new() -> Self {
+    pub fn new(store: Arc<dyn JobStore>, limits: CronLimits) -> Self {
         let schema = serde_json::json!({
             "type": "object",
             "properties": {
@@ -59,6 +90,14 @@ impl CronCreateTool {
                     "type": "string",
                     "description": "Cron expression. Examples: '0 0 * * *' (daily at midnight), '*/5 * * * *' (every 5 minutes), '0 9 * * 1-5' (weekdays at 9am)"
                 },
+                "every": {
+                    "type": "string",
+                    "description": format!("Run repeatedly at this interval instead of a cron schedule, e.g. '15m', '2h', '1h30m' (at least {}s)", limits.min_interval_secs)
+                },
+                "at": {
+                    "type": "string",
+                    "description": "Run once at this time instead of on a schedule, as an RFC 3339 timestamp, e.g. '2026-01-01T09:00:00Z'"
+                },
                 "command": {
                     "type": "string",
                     "description": "The command or prompt to execute when the task runs"
@@ -71,29 +110,83 @@ impl CronCreateTool {
                     "type": "boolean",
                     "description": "Whether the task is enabled (default: true)"
                 },
+                "agent": {
+                    "type": "string",
+                    "description": "Agent to run the task (default: general)"
+                },
+                "jitter_secs": {
+                    "type": "integer",
+                    "description": format!("Random delay of up to this many seconds before each run (max {})", limits.max_jitter_secs)
+                },
+                "overlap": {
+                    "type": "string",
+                    "enum": ["skip", "queue", "kill_previous"],
+                    "description": "When a run is due while the previous one is still running: skip the new run (default), queue it, or kill the previous one"
+                },
                 "timezone": {
                     "type": "string",
                     "description": "Optional timezone (e.g., 'America/New_York', 'Asia/Shanghai')"
                 }
             },
-            "required": ["name", "schedule", "command"]
+            "required": ["name", "command"]
         });
 
         Self {
             definition: ToolDefinition::new(
                 "cron_create",
                 "Create Cron Job",
-                "Create a new scheduled task that will run at specified times",
+                "Create a new scheduled task that runs on a cron schedule, at a fixed interval, or once at a given time",
             )
             .with_parameters_schema(schema)
-            .with_risk_level(RiskLevel::Medium),
+            .with_risk_level(RiskLevel::Medium)
+            .with_example(
+                "Summarize new issues every weekday at 9am",
+                serde_json::json!({
+                    "name": "issue-digest",
+                    "schedule": "0 0 9 * * 1-5",
+                    "command": "Summarize the issues opened since yesterday",
+                    "jitter_secs": 60
+                }),
+            )
+            .with_example(
+                "Remind about a meeting once",
+                serde_json::json!({
+                    "name": "standup-reminder",
+                    "at": "2026-01-05T08:55:00Z",
+                    "command": "Remind me that standup starts in 5 minutes"
+                }),
+            ),
+            store,
+            limits,
         }
     }
-}
 
-impl Default for CronCreateTool {
-    fn default() -> Self {
-        Self::new()
+    /// The schedule given by exactly one of `schedule`, `every` and `at`.
+    fn parse_schedule(&self, params: &CronCreateParams) -> Result<JobSchedule, ToolError> {
+        let schedule = match (&params.schedule, &params.every, &params.at) {
+            (Some(expr), None, None) => {
+                // Only plain cron expressions; intervals and times have their own fields
+                cron::Schedule::from_str(expr)
+                    .map(|s| JobSchedule::Cron(Box::new(s)))
+                    .map_err(|e| format!("Invalid cron expression '{}': {}", expr, e))
+            }
+            (None, Some(every), None) => autohands_api::parse_interval(every).map(JobSchedule::Every),
+            (None, None, Some(at)) => chrono::DateTime::parse_from_rfc3339(at)
+                .map(|t| JobSchedule::At(t.with_timezone(&chrono::Utc)))
+                .map_err(|e| format!("Invalid 'at' timestamp '{}': {}", at, e)),
+            _ => Err("Give exactly one of 'schedule', 'every' or 'at'".to_string()),
+        }
+        .map_err(ToolError::InvalidParameters)?;
+
+        if let JobSchedule::Every(interval) = schedule {
+            if interval.as_secs() < self.limits.min_interval_secs {
+                return Err(ToolError::InvalidParameters(format!(
+                    "'every' must be at least {}s",
+                    self.limits.min_interval_secs
+                )));
+            }
+        }
+        Ok(schedule)
     }
 }
 
@@ -111,39 +204,73 @@ impl Tool for CronCreateTool {
         let params: CronCreateParams = serde_json::from_value(params)
             .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
 
-        // Validate cron expression
-        let schedule = cron::Schedule::from_str(&params.schedule).map_err(|e| {
-            ToolError::InvalidParameters(format!("Invalid cron expression '{}': {}", params.schedule, e))
-        })?;
-
-        // Calculate next run time
-        let next_run = schedule.upcoming(chrono::Utc).next().map(|t| t.to_rfc3339());
+        let schedule = self.parse_schedule(&params)?;
+        let now = chrono::Utc::now();
+        let next_run = schedule.after(&now).next();
+        if schedule.is_one_shot() && next_run.is_none() {
+            return Err(ToolError::InvalidParameters(
+                "'at' must be in the future".to_string(),
+            ));
+        }
+        if params.jitter_secs > self.limits.max_jitter_secs {
+            return Err(ToolError::InvalidParameters(format!(
+                "jitter_secs must be at most {}",
+                self.limits.max_jitter_secs
+            )));
+        }
 
-        // Generate a unique ID for this task
-        let id = uuid::Uuid::new_v4().to_string();
+        let jobs = self.store.load_all().await.map_err(store_error)?;
+        if jobs.iter().any(|job| job.definition.id == params.name) {
+            return Err(ToolError::InvalidParameters(format!(
+                "A scheduled task named '{}' already exists",
+                params.name
+            )));
+        }
+        if jobs.len() >= self.limits.max_jobs {
+            return Err(ToolError::PermissionDenied(format!(
+                "Scheduled task limit reached ({} tasks); delete one first",
+                self.limits.max_jobs
+            )));
+        }
 
-        // In a real implementation, this would store the task in the scheduler
-        // For now, we return a success response indicating what would be created
+        let schedule_expr = schedule.to_string();
+        let mut definition = JobDefinition::new(
+            &params.name,
+            &schedule_expr,
+            params.agent.as_deref().unwrap_or(DEFAULT_AGENT),
+            &params.command,
+        )
+        .with_enabled(params.enabled)
+        .with_jitter(params.jitter_secs)
+        .with_overlap(params.overlap);
+        if let Some(description) = params.description {
+            definition = definition.with_description(description);
+        }
+        // A one-shot run missed while the scheduler was down still runs
+        if schedule.is_one_shot() {
+            definition = definition.with_catch_up(CatchUpPolicy::RunOnce);
+        }
+        let mut job = Job::new(definition);
+        job.next_run = next_run;
+        self.store.save(&job).await.map_err(store_error)?;
 
         let response = CronCreateResponse {
-            id: id.clone(),
+            id: params.name.clone(),
             name: params.name.clone(),
-            schedule: params.schedule.clone(),
-            next_run,
+            schedule: schedule_expr.clone(),
+            next_run: next_run.map(|t| t.to_rfc3339()),
             message: format!(
-                "Created scheduled task '{}' with ID {}. {}",
+                "Created scheduled task '{}'. {}",
                 params.name,
-                id,
                 if params.enabled { "Task is enabled and will run on schedule." } else { "Task is disabled." }
             ),
         };
 
         // Log the creation
         tracing::info!(
-            "Created cron job: id={}, name={}, schedule={}, command={}",
-            id,
+            "Created cron job: name={}, schedule={}, command={}",
             params.name,
-            params.schedule,
+            schedule_expr,
             params.command
         );
 
@@ -151,8 +278,6 @@ impl Tool for CronCreateTool {
     }
 }
 
-use std::str::FromStr;
-
 #[cfg(test)]
 #[path = "cron_create_tests.rs"]
 mod tests;
@@ -1,20 +1,26 @@
 use super::*;
+use autohands_api::MemoryJobStore;
 use std::path::PathBuf;
 
 fn create_test_context() -> ToolContext {
     ToolContext::new("test", PathBuf::from("/tmp"))
 }
 
-#[test]
-fn test_tool_definition() {
-    let tool = CronCreateTool::new();
+fn create_tool() -> (CronCreateTool, Arc<MemoryJobStore>) {
+    let store = Arc::new(MemoryJobStore::new());
+    (CronCreateTool::new(store.clone(), CronLimits::default()), store)
+}
+
+#[tokio::test]
+async fn test_tool_definition() {
+    let (tool, _) = create_tool();
     assert_eq!(tool.definition().id, "cron_create");
     assert_eq!(tool.definition().risk_level, RiskLevel::Medium);
 }
 
 #[tokio::test]
 async fn test_create_cron_job() {
-    let tool = CronCreateTool::new();
+    let (tool, store) = create_tool();
     let ctx = create_test_context();
     let params = serde_json::json!({
         "name": "daily-backup",
@@ -27,11 +33,16 @@ async fn test_create_cron_job() {
     assert!(result.success);
     assert!(result.content.contains("daily-backup"));
     assert!(result.content.contains("enabled and will run"));
+
+    let job = store.load("daily-backup").await.unwrap().unwrap();
+    assert_eq!(job.definition.prompt, "backup all files");
+    assert_eq!(job.definition.agent, "general");
+    assert_eq!(job.definition.description.as_deref(), Some("Daily backup task"));
 }
 
 #[tokio::test]
 async fn test_create_disabled_cron_job() {
-    let tool = CronCreateTool::new();
+    let (tool, _) = create_tool();
     let ctx = create_test_context();
     let params = serde_json::json!({
         "name": "disabled-task",
@@ -47,7 +58,7 @@ async fn test_create_disabled_cron_job() {
 
 #[tokio::test]
 async fn test_invalid_cron_expression() {
-    let tool = CronCreateTool::new();
+    let (tool, _) = create_tool();
     let ctx = create_test_context();
     let params = serde_json::json!({
         "name": "invalid-task",
@@ -67,7 +78,7 @@ async fn test_invalid_cron_expression() {
 
 #[tokio::test]
 async fn test_missing_required_params() {
-    let tool = CronCreateTool::new();
+    let (tool, _) = create_tool();
     let ctx = create_test_context();
     let params = serde_json::json!({
         "name": "test-task"
@@ -82,3 +93,135 @@ async fn test_missing_required_params() {
 fn test_default_enabled() {
     assert!(default_enabled());
 }
+
+#[tokio::test]
+async fn test_create_with_run_options() {
+    let (tool, store) = create_tool();
+    let params = serde_json::json!({
+        "name": "poll",
+        "schedule": "0 */5 * * * *",
+        "command": "check the queue",
+        "jitter_secs": 30,
+        "overlap": "kill_previous"
+    });
+
+    tool.execute(params, create_test_context()).await.unwrap();
+    let job = store.load("poll").await.unwrap().unwrap();
+    assert_eq!(job.definition.jitter_secs, 30);
+    assert_eq!(job.definition.overlap, OverlapPolicy::KillPrevious);
+}
+
+#[tokio::test]
+async fn test_jitter_limit() {
+    let (tool, _) = create_tool();
+    let params = serde_json::json!({
+        "name": "poll",
+        "schedule": "0 */5 * * * *",
+        "command": "check the queue",
+        "jitter_secs": CronLimits::default().max_jitter_secs + 1
+    });
+
+    let result = tool.execute(params, create_test_context()).await;
+    assert!(matches!(result, Err(ToolError::InvalidParameters(_))));
+}
+
+#[tokio::test]
+async fn test_duplicate_name() {
+    let (tool, _) = create_tool();
+    let params = serde_json::json!({
+        "name": "poll",
+        "schedule": "0 */5 * * * *",
+        "command": "check the queue"
+    });
+
+    tool.execute(params.clone(), create_test_context()).await.unwrap();
+    let result = tool.execute(params, create_test_context()).await;
+    assert!(matches!(result, Err(ToolError::InvalidParameters(msg)) if msg.contains("already exists")));
+}
+
+#[tokio::test]
+async fn test_job_limit() {
+    let store = Arc::new(MemoryJobStore::new());
+    let limits = CronLimits {
+        max_jobs: 2,
+        ..CronLimits::default()
+    };
+    let tool = CronCreateTool::new(store.clone(), limits);
+
+    for i in 0..3 {
+        let params = serde_json::json!({
+            "name": format!("job-{}", i),
+            "schedule": "0 */5 * * * *",
+            "command": "spawn more jobs"
+        });
+        let result = tool.execute(params, create_test_context()).await;
+        if i < 2 {
+            assert!(result.is_ok());
+        } else {
+            assert!(matches!(result, Err(ToolError::PermissionDenied(_))));
+        }
+    }
+    assert_eq!(store.load_all().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_create_interval_job() {
+    let (tool, store) = create_tool();
+    let params = serde_json::json!({
+        "name": "poll",
+        "every": "90m",
+        "command": "check the queue"
+    });
+
+    let result = tool.execute(params, create_test_context()).await.unwrap();
+    assert!(result.content.contains("@every 1h30m"));
+    let job = store.load("poll").await.unwrap().unwrap();
+    assert_eq!(job.definition.schedule, "@every 1h30m");
+    let next = job.next_run.unwrap() - chrono::Utc::now();
+    assert!(next > chrono::Duration::minutes(89) && next <= chrono::Duration::minutes(90));
+}
+
+#[tokio::test]
+async fn test_create_one_shot_job() {
+    let (tool, store) = create_tool();
+    let at = chrono::Utc::now() + chrono::Duration::minutes(20);
+    let params = serde_json::json!({
+        "name": "reminder",
+        "at": at.to_rfc3339(),
+        "command": "remind me to stretch"
+    });
+
+    tool.execute(params, create_test_context()).await.unwrap();
+    let job = store.load("reminder").await.unwrap().unwrap();
+    assert!(job.definition.schedule.starts_with("@at "));
+    assert_eq!(job.definition.catch_up, CatchUpPolicy::RunOnce);
+    assert_eq!(job.next_run.map(|t| t.timestamp()), Some(at.timestamp()));
+}
+
+#[tokio::test]
+async fn test_invalid_interval_and_time() {
+    let (tool, _) = create_tool();
+    let past = (chrono::Utc::now() - chrono::Duration::minutes(1)).to_rfc3339();
+    for (field, value) in [
+        ("every", "soon".to_string()),
+        ("every", "30s".to_string()),
+        ("at", "tomorrow".to_string()),
+        ("at", past),
+    ] {
+        let params = serde_json::json!({"name": "bad", "command": "x", field: value});
+        let result = tool.execute(params, create_test_context()).await;
+        assert!(matches!(result, Err(ToolError::InvalidParameters(_))), "{} = {}", field, value);
+    }
+}
+
+#[tokio::test]
+async fn test_requires_exactly_one_schedule() {
+    let (tool, _) = create_tool();
+    for params in [
+        serde_json::json!({"name": "a", "command": "x"}),
+        serde_json::json!({"name": "b", "command": "x", "schedule": "0 * * * * *", "every": "5m"}),
+    ] {
+        let result = tool.execute(params, create_test_context()).await;
+        assert!(matches!(result, Err(ToolError::InvalidParameters(msg)) if msg.contains("exactly one")));
+    }
+}
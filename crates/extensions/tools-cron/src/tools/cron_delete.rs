@@ -1,12 +1,17 @@
 //! Delete cron job tool.
 
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
+use autohands_api::JobStore;
 use autohands_protocols::error::ToolError;
 use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
 use autohands_protocols::types::RiskLevel;
 
+use super::store_error;
+
 /// Parameters for cron_delete tool.
 #[derive(Debug, Deserialize)]
 struct CronDeleteParams {
@@ -33,10 +38,11 @@ struct CronDeleteResponse {
 /// Delete cron job tool implementation.
 pub struct CronDeleteTool {
     definition: ToolDefinition,
+    store: Arc<dyn JobStore>,
 }
 
 impl CronDeleteTool {
-    pub fn new() -> Self {
+    pub fn new(store: Arc<dyn JobStore>) -> Self {
         let schema = serde_json::json!({
             "type": "object",
             "properties": {
@@ -60,16 +66,11 @@ impl CronDeleteTool {
             )
             .with_parameters_schema(schema)
             .with_risk_level(RiskLevel::Medium),
+            store,
         }
     }
 }
 
-impl Default for CronDeleteTool {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 #[async_trait]
 impl Tool for CronDeleteTool {
     fn definition(&self) -> &ToolDefinition {
@@ -84,13 +85,20 @@ impl Tool for CronDeleteTool {
         let params: CronDeleteParams = serde_json::from_value(params)
             .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
 
-        // In a real implementation, this would delete the task from the scheduler
-        // For now, we simulate the response
+        let job = self
+            .store
+            .load(&params.id)
+            .await
+            .map_err(store_error)?
+            .ok_or_else(|| {
+                ToolError::ResourceNotFound(format!("Scheduled task '{}' not found", params.id))
+            })?;
+        self.store.delete(&params.id).await.map_err(store_error)?;
 
         let response = CronDeleteResponse {
             success: true,
             id: params.id.clone(),
-            name: None,
+            name: Some(job.definition.id),
             message: format!(
                 "Task '{}' has been {}deleted.",
                 params.id,
@@ -107,22 +115,30 @@ impl Tool for CronDeleteTool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use autohands_api::{Job, JobDefinition, MemoryJobStore};
     use std::path::PathBuf;
 
     fn create_test_context() -> ToolContext {
         ToolContext::new("test", PathBuf::from("/tmp"))
     }
 
-    #[test]
-    fn test_tool_definition() {
-        let tool = CronDeleteTool::new();
+    async fn create_tool() -> (CronDeleteTool, Arc<MemoryJobStore>) {
+        let store = Arc::new(MemoryJobStore::new());
+        let def = JobDefinition::new("abc123", "0 0 0 * * *", "general", "prompt");
+        store.save(&Job::new(def)).await.unwrap();
+        (CronDeleteTool::new(store.clone()), store)
+    }
+
+    #[tokio::test]
+    async fn test_tool_definition() {
+        let (tool, _) = create_tool().await;
         assert_eq!(tool.definition().id, "cron_delete");
         assert_eq!(tool.definition().risk_level, RiskLevel::Medium);
     }
 
     #[tokio::test]
     async fn test_delete_job() {
-        let tool = CronDeleteTool::new();
+        let (tool, store) = create_tool().await;
         let ctx = create_test_context();
         let params = serde_json::json!({
             "id": "abc123"
@@ -132,11 +148,12 @@ mod tests {
         assert!(result.success);
         assert!(result.content.contains("abc123"));
         assert!(result.content.contains("deleted"));
+        assert!(store.load("abc123").await.unwrap().is_none());
     }
 
     #[tokio::test]
     async fn test_force_delete() {
-        let tool = CronDeleteTool::new();
+        let (tool, _) = create_tool().await;
         let ctx = create_test_context();
         let params = serde_json::json!({
             "id": "abc123",
@@ -148,9 +165,21 @@ mod tests {
         assert!(result.content.contains("forcefully"));
     }
 
+    #[tokio::test]
+    async fn test_delete_not_found() {
+        let (tool, _) = create_tool().await;
+        let ctx = create_test_context();
+        let params = serde_json::json!({
+            "id": "nonexistent"
+        });
+
+        let result = tool.execute(params, ctx).await;
+        assert!(matches!(result, Err(ToolError::ResourceNotFound(_))));
+    }
+
     #[tokio::test]
     async fn test_missing_id() {
-        let tool = CronDeleteTool::new();
+        let (tool, _) = create_tool().await;
         let ctx = create_test_context();
         let params = serde_json::json!({});
 
@@ -1,12 +1,17 @@
 //! List cron jobs tool.
 
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
+use autohands_api::{Job, JobStore, OverlapPolicy};
 use autohands_protocols::error::ToolError;
 use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
 use autohands_protocols::types::RiskLevel;
 
+use super::store_error;
+
 /// Parameters for cron_list tool.
 #[derive(Debug, Deserialize)]
 struct CronListParams {
@@ -39,7 +44,28 @@ struct CronTask {
     /// When the task last ran.
     last_run: Option<String>,
     /// Number of times the task has run.
-    run_count: u32,
+    run_count: u64,
+    /// Upper bound of the random start delay, in seconds.
+    jitter_secs: u64,
+    /// Handling of runs due while the previous one is still running.
+    overlap: OverlapPolicy,
+}
+
+impl From<Job> for CronTask {
+    fn from(job: Job) -> Self {
+        Self {
+            name: job.definition.id.clone(),
+            id: job.definition.id,
+            schedule: job.definition.schedule,
+            command: job.definition.prompt,
+            enabled: job.definition.enabled,
+            next_run: job.next_run.map(|t| t.to_rfc3339()),
+            last_run: job.last_run.map(|t| t.to_rfc3339()),
+            run_count: job.run_count,
+            jitter_secs: job.definition.jitter_secs,
+            overlap: job.definition.overlap,
+        }
+    }
 }
 
 /// Response from cron_list.
@@ -58,10 +84,11 @@ struct CronListResponse {
 /// List cron jobs tool implementation.
 pub struct CronListTool {
     definition: ToolDefinition,
+    store: Arc<dyn JobStore>,
 }
 
 impl CronListTool {
-    pub fn new() -> Self {
+    pub fn new(store: Arc<dyn JobStore>) -> Self {
         let schema = serde_json::json!({
             "type": "object",
             "properties": {
@@ -87,17 +114,13 @@ impl CronListTool {
                 "List all scheduled tasks with their status and next run times",
             )
             .with_parameters_schema(schema)
-            .with_risk_level(RiskLevel::Low),
+            .with_risk_level(RiskLevel::Low)
+            .with_read_only(),
+            store,
         }
     }
 }
 
-impl Default for CronListTool {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 #[async_trait]
 impl Tool for CronListTool {
     fn definition(&self) -> &ToolDefinition {
@@ -112,14 +135,29 @@ impl Tool for CronListTool {
         let params: CronListParams = serde_json::from_value(params)
             .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
 
-        // In a real implementation, this would fetch tasks from the scheduler
-        // For now, we return an empty list with the filter info
+        let mut jobs = self.store.load_all().await.map_err(store_error)?;
+        jobs.sort_by(|a, b| a.definition.id.cmp(&b.definition.id));
+
+        let total = jobs.len();
+        let enabled = jobs.iter().filter(|j| j.definition.enabled).count();
+        let tasks = jobs
+            .into_iter()
+            .filter(|j| !params.enabled_only || j.definition.enabled)
+            .filter(|j| {
+                params
+                    .filter
+                    .as_deref()
+                    .is_none_or(|pattern| matches_pattern(pattern, &j.definition.id))
+            })
+            .take(params.limit.unwrap_or(usize::MAX))
+            .map(CronTask::from)
+            .collect();
 
         let response = CronListResponse {
-            tasks: vec![],
-            total: 0,
-            enabled: 0,
-            disabled: 0,
+            tasks,
+            total,
+            enabled,
+            disabled: total - enabled,
         };
 
         let mut output = serde_json::to_string_pretty(&response).unwrap();
@@ -144,37 +182,71 @@ impl Tool for CronListTool {
     }
 }
 
+/// Whether `name` matches `pattern`, where `*` matches any run of
+/// characters; a pattern without `*` matches names containing it.
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    if !pattern.contains('*') {
+        return name.contains(pattern);
+    }
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if !name.starts_with(first) || name.len() < first.len() + last.len() {
+        return false;
+    }
+    let mut rest = &name[first.len()..name.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    name.ends_with(last)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use autohands_api::{JobDefinition, MemoryJobStore};
     use std::path::PathBuf;
 
     fn create_test_context() -> ToolContext {
         ToolContext::new("test", PathBuf::from("/tmp"))
     }
 
-    #[test]
-    fn test_tool_definition() {
-        let tool = CronListTool::new();
+    async fn create_tool() -> CronListTool {
+        let store = Arc::new(MemoryJobStore::new());
+        for (id, enabled) in [("backup-db", true), ("backup-files", false), ("report", true)] {
+            let def = JobDefinition::new(id, "0 0 0 * * *", "general", "prompt").with_enabled(enabled);
+            store.save(&Job::new(def)).await.unwrap();
+        }
+        CronListTool::new(store)
+    }
+
+    #[tokio::test]
+    async fn test_tool_definition() {
+        let tool = create_tool().await;
         assert_eq!(tool.definition().id, "cron_list");
         assert_eq!(tool.definition().risk_level, RiskLevel::Low);
     }
 
     #[tokio::test]
     async fn test_list_all_jobs() {
-        let tool = CronListTool::new();
+        let tool = create_tool().await;
         let ctx = create_test_context();
         let params = serde_json::json!({});
 
         let result = tool.execute(params, ctx).await.unwrap();
         assert!(result.success);
-        assert!(result.content.contains("tasks"));
-        assert!(result.content.contains("total"));
+        let response: serde_json::Value = serde_json::from_str(&result.content).unwrap();
+        assert_eq!(response["total"], 3);
+        assert_eq!(response["disabled"], 1);
+        assert_eq!(response["tasks"][0]["id"], "backup-db");
+        assert_eq!(response["tasks"][0]["overlap"], "skip");
     }
 
     #[tokio::test]
     async fn test_list_with_filter() {
-        let tool = CronListTool::new();
+        let tool = create_tool().await;
         let ctx = create_test_context();
         let params = serde_json::json!({
             "filter": "backup*",
@@ -186,5 +258,18 @@ mod tests {
         assert!(result.success);
         assert!(result.content.contains("Filters applied"));
         assert!(result.content.contains("name=backup*"));
+        assert!(result.content.contains("backup-db"));
+        assert!(!result.content.contains("backup-files"));
+        assert!(!result.content.contains("\"report\""));
+    }
+
+    #[test]
+    fn test_matches_pattern() {
+        assert!(matches_pattern("backup*", "backup-db"));
+        assert!(matches_pattern("*-db", "backup-db"));
+        assert!(matches_pattern("b*u*db", "backup-db"));
+        assert!(matches_pattern("kup", "backup-db"));
+        assert!(!matches_pattern("report*", "backup-db"));
+        assert!(!matches_pattern("backup*db*x", "backup-db"));
     }
 }
@@ -1,26 +1,48 @@
 //! Cron job status tool.
 
+use std::sync::Arc;
+
 use async_trait::async_trait;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
+use autohands_api::{Job, JobRun, JobStore};
 use autohands_protocols::error::ToolError;
 use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
 use autohands_protocols::types::RiskLevel;
 
+use super::store_error;
+
+/// Most history entries returned.
+const HISTORY_LIMIT: usize = 10;
+
 /// Parameters for cron_status tool.
 #[derive(Debug, Deserialize)]
 struct CronStatusParams {
     /// ID or name of the task to check.
     id: String,
+    /// Include recent runs (default: false).
+    #[serde(default)]
+    include_history: bool,
+}
+
+/// Response from cron_status.
+#[derive(Debug, Serialize)]
+struct CronStatusResponse {
+    /// The task and its current state.
+    job: Job,
+    /// Recent runs, newest first.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    history: Option<Vec<JobRun>>,
 }
 
 /// Get cron job status tool implementation.
 pub struct CronStatusTool {
     definition: ToolDefinition,
+    store: Arc<dyn JobStore>,
 }
 
 impl CronStatusTool {
-    pub fn new() -> Self {
+    pub fn new(store: Arc<dyn JobStore>) -> Self {
         let schema = serde_json::json!({
             "type": "object",
             "properties": {
@@ -43,17 +65,13 @@ impl CronStatusTool {
                 "Get detailed status and execution history of a scheduled task",
             )
             .with_parameters_schema(schema)
-            .with_risk_level(RiskLevel::Low),
+            .with_risk_level(RiskLevel::Low)
+            .with_read_only(),
+            store,
         }
     }
 }
 
-impl Default for CronStatusTool {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 #[async_trait]
 impl Tool for CronStatusTool {
     fn definition(&self) -> &ToolDefinition {
@@ -68,35 +86,77 @@ impl Tool for CronStatusTool {
         let params: CronStatusParams = serde_json::from_value(params)
             .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
 
-        // In a real implementation, this would fetch task status from the scheduler
-        // For now, we return a not-found response
-
-        Err(ToolError::ResourceNotFound(format!(
-            "Scheduled task '{}' not found",
-            params.id
-        )))
+        let job = self
+            .store
+            .load(&params.id)
+            .await
+            .map_err(store_error)?
+            .ok_or_else(|| {
+                ToolError::ResourceNotFound(format!("Scheduled task '{}' not found", params.id))
+            })?;
+
+        let history = if params.include_history {
+            let runs = self.store.load_history(&params.id).await.map_err(store_error)?;
+            Some(runs.into_iter().rev().take(HISTORY_LIMIT).collect())
+        } else {
+            None
+        };
+
+        let response = CronStatusResponse { job, history };
+        Ok(ToolResult::success(serde_json::to_string_pretty(&response).unwrap()))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use autohands_api::{JobDefinition, JobRunStatus, MemoryJobStore};
     use std::path::PathBuf;
 
     fn create_test_context() -> ToolContext {
         ToolContext::new("test", PathBuf::from("/tmp"))
     }
 
-    #[test]
-    fn test_tool_definition() {
-        let tool = CronStatusTool::new();
+    async fn create_tool() -> CronStatusTool {
+        let store = Arc::new(MemoryJobStore::new());
+        let def = JobDefinition::new("nightly", "0 0 0 * * *", "general", "prompt");
+        store.save(&Job::new(def)).await.unwrap();
+        let run = JobRun {
+            scheduled_for: chrono::Utc::now(),
+            started_at: chrono::Utc::now(),
+            status: JobRunStatus::Submitted,
+            catch_up: false,
+            task_id: None,
+            session_id: Some("job:nightly:1".to_string()),
+            message: None,
+        };
+        store.record_run("nightly", &run).await.unwrap();
+        CronStatusTool::new(store)
+    }
+
+    #[tokio::test]
+    async fn test_tool_definition() {
+        let tool = create_tool().await;
         assert_eq!(tool.definition().id, "cron_status");
         assert_eq!(tool.definition().risk_level, RiskLevel::Low);
     }
 
+    #[tokio::test]
+    async fn test_status_with_history() {
+        let tool = create_tool().await;
+        let params = serde_json::json!({"id": "nightly"});
+        let result = tool.execute(params, create_test_context()).await.unwrap();
+        assert!(result.content.contains("nightly"));
+        assert!(!result.content.contains("history"));
+
+        let params = serde_json::json!({"id": "nightly", "include_history": true});
+        let result = tool.execute(params, create_test_context()).await.unwrap();
+        assert!(result.content.contains("job:nightly:1"));
+    }
+
     #[tokio::test]
     async fn test_status_not_found() {
-        let tool = CronStatusTool::new();
+        let tool = create_tool().await;
         let ctx = create_test_context();
         let params = serde_json::json!({
             "id": "nonexistent"
@@ -114,7 +174,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_missing_id() {
-        let tool = CronStatusTool::new();
+        let tool = create_tool().await;
         let ctx = create_test_context();
         let params = serde_json::json!({});
 
@@ -9,3 +9,11 @@ pub use cron_create::CronCreateTool;
 pub use cron_delete::CronDeleteTool;
 pub use cron_list::CronListTool;
 pub use cron_status::CronStatusTool;
+
+use autohands_api::InterfaceError;
+use autohands_protocols::error::ToolError;
+
+/// Map a job store failure to a tool error.
+pub(crate) fn store_error(e: InterfaceError) -> ToolError {
+    ToolError::ExecutionFailed(format!("Job store error: {}", e))
+}
@@ -0,0 +1,78 @@
+//! Data tools extension definition.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::debug;
+
+use autohands_protocols::error::ExtensionError;
+use autohands_protocols::extension::{Extension, ExtensionContext, ExtensionManifest, Provides};
+use autohands_protocols::types::Version;
+
+use crate::query::DataQueryTool;
+
+/// Data tools extension.
+pub struct DataToolsExtension {
+    manifest: ExtensionManifest,
+}
+
+impl DataToolsExtension {
+    pub fn new() -> Self {
+        let mut manifest =
+            ExtensionManifest::new("tools-data", "Data Tools", Version::new(0, 1, 0));
+        manifest.description = "SQL queries over CSV, TSV, JSON and Parquet files".to_string();
+        manifest.provides = Provides {
+            tools: vec!["data_query".to_string()],
+            ..Default::default()
+        };
+
+        Self { manifest }
+    }
+}
+
+impl Default for DataToolsExtension {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Extension for DataToolsExtension {
+    fn manifest(&self) -> &ExtensionManifest {
+        &self.manifest
+    }
+
+    async fn initialize(&mut self, ctx: ExtensionContext) -> Result<(), ExtensionError> {
+        debug!("Data tools initialized");
+
+        ctx.tool_registry
+            .register_tool(Arc::new(DataQueryTool::new()))?;
+
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extension_manifest() {
+        let ext = DataToolsExtension::new();
+        assert_eq!(ext.manifest().id, "tools-data");
+        assert_eq!(ext.manifest().name, "Data Tools");
+        assert_eq!(
+            ext.manifest().provides.tools,
+            vec!["data_query".to_string()]
+        );
+    }
+}
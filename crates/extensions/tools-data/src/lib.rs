@@ -0,0 +1,14 @@
+//! Data tools for AutoHands.
+//!
+//! Provides data_query, which loads CSV, TSV, JSON and Parquet files as
+//! tables and runs SQL over them with DataFusion, returning results a page
+//! at a time. Agents get aggregates, filters and joins over real datasets
+//! instead of grepping through them.
+
+mod extension;
+mod query;
+mod source;
+
+pub use extension::DataToolsExtension;
+pub use query::{DataQueryTool, MAX_LIMIT};
+pub use source::DataFormat;
@@ -0,0 +1,341 @@
+//! SQL query tool over data files.
+
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use datafusion::arrow::json::ArrayWriter;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::arrow::util::pretty::pretty_format_batches;
+use datafusion::execution::context::SQLOptions;
+use datafusion::prelude::SessionContext;
+use serde::Deserialize;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{PathAccess, Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::types::RiskLevel;
+
+use crate::source::{register, valid_table_name, DataFormat};
+
+/// Rows returned by a query without a limit.
+const DEFAULT_LIMIT: usize = 100;
+
+/// Most rows returned by one call.
+pub const MAX_LIMIT: usize = 1000;
+
+/// Table name of a file given as `path`.
+const DEFAULT_TABLE: &str = "data";
+
+/// A table's file, with its format when the extension does not tell it.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum TableSource {
+    Path(String),
+    File {
+        path: String,
+        #[serde(default)]
+        format: Option<DataFormat>,
+    },
+}
+
+impl TableSource {
+    fn path(&self) -> &str {
+        match self {
+            Self::Path(path) | Self::File { path, .. } => path,
+        }
+    }
+
+    fn format(&self) -> Option<DataFormat> {
+        match self {
+            Self::Path(_) => None,
+            Self::File { format, .. } => *format,
+        }
+    }
+}
+
+/// Parameters for data_query.
+#[derive(Debug, Deserialize)]
+struct QueryParams {
+    /// SQL query.
+    sql: String,
+    /// A file queried as table `data`.
+    #[serde(default)]
+    path: Option<String>,
+    /// Files by table name.
+    #[serde(default)]
+    tables: BTreeMap<String, TableSource>,
+    /// Rows to skip.
+    #[serde(default)]
+    offset: usize,
+    /// Rows to return.
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+/// Tool running SQL over CSV, JSON and Parquet files.
+pub struct DataQueryTool {
+    definition: ToolDefinition,
+}
+
+impl DataQueryTool {
+    /// Create a new query tool.
+    pub fn new() -> Self {
+        let definition = ToolDefinition::new(
+            "data_query",
+            "Query Data Files",
+            "Run a SQL query over CSV, TSV, JSON (array or one object per line) and Parquet files, each loaded as a table. Use `DESCRIBE <table>` to see the columns. Results are paginated with offset/limit",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "sql": {
+                    "type": "string",
+                    "description": "SQL query (SELECT, WITH, DESCRIBE, EXPLAIN)"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "A data file, queried as table `data`"
+                },
+                "tables": {
+                    "type": "object",
+                    "description": "Data files by table name: a path, or {\"path\", \"format\"} when the extension does not tell the format (csv, tsv, json or parquet)",
+                    "additionalProperties": {
+                        "oneOf": [
+                            {"type": "string"},
+                            {
+                                "type": "object",
+                                "properties": {
+                                    "path": {"type": "string"},
+                                    "format": {"type": "string", "enum": ["csv", "tsv", "json", "parquet"]}
+                                },
+                                "required": ["path"]
+                            }
+                        ]
+                    }
+                },
+                "offset": {
+                    "type": "integer",
+                    "description": "Result rows to skip (default 0)"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Result rows to return (default 100, at most 1000)"
+                }
+            },
+            "required": ["sql"]
+        }))
+        .with_risk_level(RiskLevel::Low)
+        .with_read_only()
+        .with_example(
+            "Top customers by revenue",
+            serde_json::json!({
+                "path": "exports/orders.csv",
+                "sql": "SELECT customer, SUM(amount) AS revenue FROM data GROUP BY customer ORDER BY revenue DESC",
+                "limit": 10
+            }),
+        )
+        .with_example(
+            "Join two files",
+            serde_json::json!({
+                "tables": {"users": "users.parquet", "events": "events.jsonl"},
+                "sql": "SELECT u.name, COUNT(*) FROM events e JOIN users u ON e.user_id = u.id GROUP BY u.name"
+            }),
+        );
+
+        Self { definition }
+    }
+}
+
+impl Default for DataQueryTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Tool for DataQueryTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let mut params: QueryParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
+        if let Some(path) = params.path.take() {
+            if params.tables.contains_key(DEFAULT_TABLE) {
+                return Err(ToolError::InvalidParameters(format!(
+                    "`path` is queried as table `{}`, which `tables` also names",
+                    DEFAULT_TABLE
+                )));
+            }
+            params
+                .tables
+                .insert(DEFAULT_TABLE.to_string(), TableSource::Path(path));
+        }
+        if params.tables.is_empty() {
+            return Err(ToolError::InvalidParameters(
+                "Give a data file as `path`, or several as `tables`".to_string(),
+            ));
+        }
+        let limit = params.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+        let session = SessionContext::new();
+        // Temporary copies of JSON arrays, kept until the query is done
+        let mut copies = Vec::new();
+        for (name, source) in &params.tables {
+            if !valid_table_name(name) {
+                return Err(ToolError::InvalidParameters(format!(
+                    "Invalid table name '{}': use letters, digits and underscores",
+                    name
+                )));
+            }
+            let path = ctx.resolve_path(source.path(), PathAccess::Read)?;
+            if !path.is_file() {
+                return Err(ToolError::ResourceNotFound(format!(
+                    "Data file not found: {}",
+                    path.display()
+                )));
+            }
+            let format = source
+                .format()
+                .or_else(|| DataFormat::from_path(&path))
+                .ok_or_else(|| {
+                    ToolError::InvalidParameters(format!(
+                        "Cannot tell the format of {}; give it as {{\"path\", \"format\"}}",
+                        path.display()
+                    ))
+                })?;
+            copies.extend(register(&session, name, &path, format).await?);
+        }
+
+        let run = query(&session, &params.sql, params.offset, limit);
+        let page = tokio::select! {
+            page = run => page?,
+            _ = ctx.abort_signal.aborted() => return Err(ToolError::Cancelled),
+        };
+        drop(copies);
+        Ok(page.into_result(params.offset))
+    }
+}
+
+/// A page of query results.
+#[derive(Debug)]
+struct ResultPage {
+    columns: Vec<(String, String)>,
+    batches: Vec<RecordBatch>,
+    rows: usize,
+    /// Whether rows follow the page.
+    more: bool,
+}
+
+/// Run `sql` and collect `limit` rows from `offset`, and whether more follow.
+async fn query(
+    session: &SessionContext,
+    sql: &str,
+    offset: usize,
+    limit: usize,
+) -> Result<ResultPage, ToolError> {
+    // Queries only read: no DDL, DML, COPY or SET
+    let options = SQLOptions::new()
+        .with_allow_ddl(false)
+        .with_allow_dml(false)
+        .with_allow_statements(false);
+    let frame = session
+        .sql_with_options(sql, options)
+        .await
+        .map_err(|e| ToolError::ExecutionFailed(format!("Query failed: {}", e)))?;
+    let columns = frame
+        .schema()
+        .fields()
+        .iter()
+        .map(|field| (field.name().clone(), field.data_type().to_string()))
+        .collect();
+    let batches = frame
+        .limit(offset, Some(limit + 1))
+        .map_err(|e| ToolError::ExecutionFailed(format!("Query failed: {}", e)))?
+        .collect()
+        .await
+        .map_err(|e| ToolError::ExecutionFailed(format!("Query failed: {}", e)))?;
+
+    let fetched: usize = batches.iter().map(RecordBatch::num_rows).sum();
+    let rows = fetched.min(limit);
+    Ok(ResultPage {
+        columns,
+        batches: take_rows(batches, rows),
+        rows,
+        more: fetched > limit,
+    })
+}
+
+/// The first `n` rows of `batches`.
+fn take_rows(batches: Vec<RecordBatch>, mut n: usize) -> Vec<RecordBatch> {
+    let mut taken = Vec::new();
+    for batch in batches {
+        if n == 0 {
+            break;
+        }
+        let rows = batch.num_rows().min(n);
+        taken.push(batch.slice(0, rows));
+        n -= rows;
+    }
+    taken
+}
+
+impl ResultPage {
+    fn into_result(self, offset: usize) -> ToolResult {
+        let next_offset = self.more.then_some(offset + self.rows);
+        let columns: Vec<_> = self
+            .columns
+            .iter()
+            .map(|(name, data_type)| serde_json::json!({"name": name, "type": data_type}))
+            .collect();
+        let info = serde_json::json!({
+            "columns": columns,
+            "rows": self.json_rows(),
+            "offset": offset,
+            "row_count": self.rows,
+            "next_offset": next_offset,
+        });
+        ToolResult::success_json(self.render(offset, next_offset), info)
+    }
+
+    /// Rows as JSON objects keyed by column name.
+    fn json_rows(&self) -> Vec<serde_json::Value> {
+        let mut writer = ArrayWriter::new(Vec::new());
+        let batches: Vec<&RecordBatch> = self.batches.iter().collect();
+        if writer
+            .write_batches(&batches)
+            .and_then(|_| writer.finish())
+            .is_err()
+        {
+            return Vec::new();
+        }
+        serde_json::from_slice(&writer.into_inner()).unwrap_or_default()
+    }
+
+    /// The rows as a table, with where the page sits in the results.
+    fn render(&self, offset: usize, next_offset: Option<usize>) -> String {
+        if self.rows == 0 {
+            let names: Vec<&str> = self.columns.iter().map(|(name, _)| name.as_str()).collect();
+            return format!("No rows (columns: {})", names.join(", "));
+        }
+        let table = pretty_format_batches(&self.batches)
+            .map(|table| table.to_string())
+            .unwrap_or_default();
+        let position = format!("rows {}-{}", offset + 1, offset + self.rows);
+        match next_offset {
+            Some(next) => format!(
+                "{}\n[{}; more follow, continue with offset={}]",
+                table, position, next
+            ),
+            None => format!("{}\n[{}, end of results]", table, position),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "query_tests.rs"]
+mod tests;
@@ -0,0 +1,115 @@
+use super::*;
+
+fn write_orders(dir: &std::path::Path) {
+    let mut csv = String::from("id,customer,amount\n");
+    for i in 1..=25 {
+        csv.push_str(&format!("{},c{},{}\n", i, i % 3, i * 10));
+    }
+    std::fs::write(dir.join("orders.csv"), csv).unwrap();
+}
+
+async fn run(dir: &std::path::Path, params: serde_json::Value) -> Result<ToolResult, ToolError> {
+    DataQueryTool::new()
+        .execute(params, ToolContext::new("test", dir.to_path_buf()))
+        .await
+}
+
+#[tokio::test]
+async fn test_query_csv_paginated() {
+    let dir = tempfile::tempdir().unwrap();
+    write_orders(dir.path());
+
+    let params = serde_json::json!({
+        "path": "orders.csv",
+        "sql": "SELECT id, amount FROM data ORDER BY id",
+        "limit": 10,
+        "offset": 10,
+    });
+    let result = run(dir.path(), params).await.unwrap();
+    assert!(result.success);
+    assert!(result.content.contains("rows 11-20"));
+    assert!(result.content.contains("offset=20"));
+    let info = result.structured_output.unwrap();
+    assert_eq!(info["row_count"], 10);
+    assert_eq!(info["next_offset"], 20);
+    assert_eq!(info["rows"][0]["id"], 11);
+    assert_eq!(info["columns"][1]["name"], "amount");
+
+    let params = serde_json::json!({
+        "path": "orders.csv",
+        "sql": "SELECT id FROM data ORDER BY id",
+        "limit": 10,
+        "offset": 20,
+    });
+    let info = run(dir.path(), params)
+        .await
+        .unwrap()
+        .structured_output
+        .unwrap();
+    assert_eq!(info["row_count"], 5);
+    assert!(info["next_offset"].is_null());
+}
+
+#[tokio::test]
+async fn test_query_joins_tables() {
+    let dir = tempfile::tempdir().unwrap();
+    write_orders(dir.path());
+    std::fs::write(
+        dir.path().join("customers.txt"),
+        "name\tcode\nAda\tc1\nAlan\tc2\n",
+    )
+    .unwrap();
+
+    let params = serde_json::json!({
+        "tables": {
+            "orders": "orders.csv",
+            "customers": {"path": "customers.txt", "format": "tsv"},
+        },
+        "sql": "SELECT c.name, COUNT(*) AS n FROM orders o JOIN customers c ON o.customer = c.code GROUP BY c.name ORDER BY c.name",
+    });
+    let info = run(dir.path(), params)
+        .await
+        .unwrap()
+        .structured_output
+        .unwrap();
+    assert_eq!(info["rows"][0]["name"], "Ada");
+    assert_eq!(info["rows"][0]["n"], 9);
+    assert_eq!(info["rows"][1]["n"], 8);
+}
+
+#[tokio::test]
+async fn test_query_refuses_writes() {
+    let dir = tempfile::tempdir().unwrap();
+    write_orders(dir.path());
+
+    let params = serde_json::json!({
+        "path": "orders.csv",
+        "sql": "COPY data TO 'out.csv'",
+    });
+    assert!(run(dir.path(), params).await.is_err());
+    assert!(!dir.path().join("out.csv").exists());
+}
+
+#[tokio::test]
+async fn test_query_invalid_sources() {
+    let dir = tempfile::tempdir().unwrap();
+    write_orders(dir.path());
+
+    let missing = serde_json::json!({"sql": "SELECT 1"});
+    assert!(matches!(
+        run(dir.path(), missing).await,
+        Err(ToolError::InvalidParameters(_))
+    ));
+
+    let bad_name = serde_json::json!({
+        "tables": {"my-orders": "orders.csv"},
+        "sql": "SELECT 1",
+    });
+    assert!(matches!(
+        run(dir.path(), bad_name).await,
+        Err(ToolError::InvalidParameters(_))
+    ));
+
+    let not_found = serde_json::json!({"path": "nope.csv", "sql": "SELECT 1"});
+    assert!(run(dir.path(), not_found).await.is_err());
+}
@@ -0,0 +1,167 @@
+//! Data files registered as SQL tables.
+
+use std::io::{BufWriter, Read, Write};
+use std::path::Path;
+
+use datafusion::prelude::{CsvReadOptions, NdJsonReadOptions, ParquetReadOptions, SessionContext};
+use serde::Deserialize;
+use tempfile::NamedTempFile;
+
+use autohands_protocols::error::ToolError;
+
+/// Bytes looked at to tell a JSON array from newline-delimited JSON.
+const SNIFF_BYTES: u64 = 4 * 1024;
+
+/// Format of a data file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DataFormat {
+    /// Comma-separated values with a header row.
+    Csv,
+    /// Tab-separated values with a header row.
+    Tsv,
+    /// Newline-delimited JSON objects, or one array of objects.
+    Json,
+    /// Apache Parquet.
+    Parquet,
+}
+
+impl DataFormat {
+    /// Format implied by a file's extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "csv" => Some(Self::Csv),
+            "tsv" | "tab" => Some(Self::Tsv),
+            "json" | "jsonl" | "ndjson" => Some(Self::Json),
+            "parquet" | "pq" => Some(Self::Parquet),
+            _ => None,
+        }
+    }
+}
+
+/// Whether `name` can be used unquoted as a table name.
+pub(crate) fn valid_table_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Register the file at `path` as table `name`.
+///
+/// A JSON array is rewritten as newline-delimited JSON into a temporary
+/// file, which is returned and must outlive the query.
+pub(crate) async fn register(
+    session: &SessionContext,
+    name: &str,
+    path: &Path,
+    format: DataFormat,
+) -> Result<Option<NamedTempFile>, ToolError> {
+    // Files are only read if their name ends with the extension given
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| format!(".{}", e))
+        .unwrap_or_default();
+    let location = path.to_string_lossy();
+    let failed = |e: datafusion::error::DataFusionError| {
+        ToolError::ExecutionFailed(format!("Failed to load {}: {}", path.display(), e))
+    };
+
+    match format {
+        DataFormat::Csv | DataFormat::Tsv => {
+            let delimiter = if format == DataFormat::Tsv {
+                b'\t'
+            } else {
+                b','
+            };
+            let options = CsvReadOptions::new()
+                .has_header(true)
+                .delimiter(delimiter)
+                .file_extension(&extension);
+            session
+                .register_csv(name, location.as_ref(), options)
+                .await
+                .map_err(failed)?;
+            Ok(None)
+        }
+        DataFormat::Json => {
+            if !is_json_array(path)? {
+                let options = NdJsonReadOptions::default().file_extension(&extension);
+                session
+                    .register_json(name, location.as_ref(), options)
+                    .await
+                    .map_err(failed)?;
+                return Ok(None);
+            }
+            let source = path.to_path_buf();
+            let lines = tokio::task::spawn_blocking(move || json_array_to_lines(&source))
+                .await
+                .map_err(|e| ToolError::ExecutionFailed(e.to_string()))??;
+            let options = NdJsonReadOptions::default().file_extension(".json");
+            session
+                .register_json(name, lines.path().to_string_lossy().as_ref(), options)
+                .await
+                .map_err(failed)?;
+            Ok(Some(lines))
+        }
+        DataFormat::Parquet => {
+            let options = ParquetReadOptions {
+                file_extension: &extension,
+                ..Default::default()
+            };
+            session
+                .register_parquet(name, location.as_ref(), options)
+                .await
+                .map_err(failed)?;
+            Ok(None)
+        }
+    }
+}
+
+/// Whether a JSON file holds an array rather than one object per line.
+fn is_json_array(path: &Path) -> Result<bool, ToolError> {
+    let mut head = Vec::new();
+    std::fs::File::open(path)?
+        .take(SNIFF_BYTES)
+        .read_to_end(&mut head)?;
+    // Skipping whitespace and a byte order mark
+    let first = head
+        .iter()
+        .find(|&&b| !b.is_ascii_whitespace() && !matches!(b, 0xEF | 0xBB | 0xBF));
+    Ok(first == Some(&b'['))
+}
+
+/// Rewrite a JSON array of objects as one object per line.
+fn json_array_to_lines(path: &Path) -> Result<NamedTempFile, ToolError> {
+    let file = std::io::BufReader::new(std::fs::File::open(path)?);
+    let rows: Vec<serde_json::Value> = serde_json::from_reader(file).map_err(|e| {
+        ToolError::ExecutionFailed(format!("Invalid JSON in {}: {}", path.display(), e))
+    })?;
+
+    let lines = tempfile::Builder::new()
+        .prefix("autohands-data-")
+        .suffix(".json")
+        .tempfile()?;
+    let mut out = BufWriter::new(lines.as_file());
+    for row in &rows {
+        if !row.is_object() {
+            return Err(ToolError::ExecutionFailed(format!(
+                "{} must hold an array of objects",
+                path.display()
+            )));
+        }
+        serde_json::to_writer(&mut out, row)
+            .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
+        out.write_all(b"\n")?;
+    }
+    out.flush()?;
+    drop(out);
+    Ok(lines)
+}
+
+#[cfg(test)]
+#[path = "source_tests.rs"]
+mod tests;
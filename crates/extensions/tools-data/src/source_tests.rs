@@ -0,0 +1,72 @@
+use super::*;
+
+#[test]
+fn test_format_from_path() {
+    assert_eq!(
+        DataFormat::from_path(Path::new("a.csv")),
+        Some(DataFormat::Csv)
+    );
+    assert_eq!(
+        DataFormat::from_path(Path::new("a.TSV")),
+        Some(DataFormat::Tsv)
+    );
+    assert_eq!(
+        DataFormat::from_path(Path::new("a.jsonl")),
+        Some(DataFormat::Json)
+    );
+    assert_eq!(
+        DataFormat::from_path(Path::new("dir/a.parquet")),
+        Some(DataFormat::Parquet)
+    );
+    assert_eq!(DataFormat::from_path(Path::new("a.txt")), None);
+    assert_eq!(DataFormat::from_path(Path::new("data")), None);
+}
+
+#[test]
+fn test_valid_table_name() {
+    assert!(valid_table_name("orders"));
+    assert!(valid_table_name("_tmp2"));
+    assert!(!valid_table_name("2024"));
+    assert!(!valid_table_name("order-items"));
+    assert!(!valid_table_name("a; DROP"));
+    assert!(!valid_table_name(""));
+}
+
+#[tokio::test]
+async fn test_register_json_array() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("people.json");
+    std::fs::write(
+        &path,
+        "\n[{\"name\": \"ada\", \"age\": 36}, {\"name\": \"alan\", \"age\": 41}]",
+    )
+    .unwrap();
+    assert!(is_json_array(&path).unwrap());
+
+    let session = SessionContext::new();
+    let copy = register(&session, "people", &path, DataFormat::Json)
+        .await
+        .unwrap();
+    assert!(copy.is_some());
+    let batches = session
+        .sql("SELECT name FROM people WHERE age > 40")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 1);
+}
+
+#[tokio::test]
+async fn test_register_json_array_of_scalars() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("numbers.json");
+    std::fs::write(&path, "[1, 2, 3]").unwrap();
+
+    let session = SessionContext::new();
+    let err = register(&session, "numbers", &path, DataFormat::Json)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("array of objects"));
+}
@@ -0,0 +1,476 @@
+//! Connection pools of the profiles, and running statements on them.
+//!
+//! Each profile gets a pool of read-only sessions for queries and schema
+//! introspection, and, when it allows writes, a second pool for
+//! `db_execute`. Read-only is enforced by the database: Postgres sessions
+//! start with `default_transaction_read_only`, MySQL sessions run
+//! `SET SESSION TRANSACTION READ ONLY`, and SQLite files are opened
+//! read-only. Pools connect on first use.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use futures::TryStreamExt;
+use rust_decimal::Decimal;
+use serde_json::Value;
+use sqlx::mysql::{MySqlConnectOptions, MySqlQueryResult, MySqlRow};
+use sqlx::pool::PoolOptions;
+use sqlx::postgres::{PgConnectOptions, PgQueryResult, PgRow};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteQueryResult, SqliteRow};
+use sqlx::{
+    Column, Database, Executor, IntoArguments, MySql, Pool, Postgres, Row, Sqlite, TypeInfo,
+    ValueRef,
+};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use autohands_protocols::error::ToolError;
+
+use crate::config::{Backend, DatabaseConfig, DatabaseProfile};
+use crate::rows::Rows;
+use crate::sql::valid_table_name;
+
+/// Connections a pool keeps at most.
+const MAX_CONNECTIONS: u32 = 4;
+
+/// A pool of one of the supported databases.
+#[derive(Clone)]
+enum Pools {
+    Postgres(Pool<Postgres>),
+    MySql(Pool<MySql>),
+    Sqlite(Pool<Sqlite>),
+}
+
+/// Pools of a profile.
+struct ProfilePools {
+    /// Sessions that cannot write.
+    reader: Pools,
+    /// Sessions for `db_execute`, when the profile allows writes.
+    writer: Option<Pools>,
+}
+
+/// Connections to the configured databases.
+pub struct DatabaseClients {
+    config: DatabaseConfig,
+    pools: Mutex<HashMap<String, Arc<ProfilePools>>>,
+}
+
+impl DatabaseClients {
+    pub fn new(config: DatabaseConfig) -> Self {
+        Self {
+            config,
+            pools: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn config(&self) -> &DatabaseConfig {
+        &self.config
+    }
+
+    /// Run a statement in a read-only session, returning up to `max_rows`
+    /// rows (never more than the profile's `max_rows`).
+    pub async fn query(
+        &self,
+        profile: Option<&str>,
+        sql: &str,
+        max_rows: Option<usize>,
+    ) -> Result<Rows, ToolError> {
+        let (name, settings) = self.resolve(profile)?;
+        let limit = max_rows
+            .map_or(settings.max_rows, |n| n.min(settings.max_rows))
+            .max(1);
+        let pools = self.pools(name, settings).await?;
+        let run = async {
+            match &pools.reader {
+                Pools::Postgres(pool) => fetch(pool, sql, limit, pg_cell).await,
+                Pools::MySql(pool) => fetch(pool, sql, limit, mysql_cell).await,
+                Pools::Sqlite(pool) => fetch(pool, sql, limit, sqlite_cell).await,
+            }
+        };
+        with_timeout(settings, run).await
+    }
+
+    /// Tables and views of a profile's database, or the columns of `table`.
+    pub async fn schema(
+        &self,
+        profile: Option<&str>,
+        table: Option<&str>,
+    ) -> Result<Rows, ToolError> {
+        let (name, settings) = self.resolve(profile)?;
+        let backend = backend_of(name, settings)?;
+        let sql = match table {
+            Some(table) if !valid_table_name(table) => {
+                return Err(ToolError::InvalidParameters(format!(
+                    "Invalid table name '{}'",
+                    table
+                )))
+            }
+            Some(table) => columns_sql(backend, table),
+            None => tables_sql(backend).to_string(),
+        };
+        self.query(Some(name), &sql, None).await
+    }
+
+    /// Run a statement that changes data, in a transaction rolled back if
+    /// it changes more than the profile's `max_affected_rows` rows.
+    ///
+    /// Returns the number of rows changed. Statements the database commits
+    /// implicitly, such as DDL on MySQL, cannot be rolled back.
+    pub async fn execute(&self, profile: Option<&str>, sql: &str) -> Result<u64, ToolError> {
+        let (name, settings) = self.resolve(profile)?;
+        if !settings.allow_writes {
+            return Err(ToolError::PermissionDenied(format!(
+                "Database profile '{}' does not allow writes; set allow_writes = true for it",
+                name
+            )));
+        }
+        let pools = self.pools(name, settings).await?;
+        let max = settings.max_affected_rows;
+        let run = async {
+            match &pools.writer {
+                Some(Pools::Postgres(pool)) => execute_limited(pool, sql, max).await,
+                Some(Pools::MySql(pool)) => execute_limited(pool, sql, max).await,
+                Some(Pools::Sqlite(pool)) => execute_limited(pool, sql, max).await,
+                None => Err(ToolError::PermissionDenied(format!(
+                    "Database profile '{}' does not allow writes",
+                    name
+                ))),
+            }
+        };
+        with_timeout(settings, run).await
+    }
+
+    fn resolve<'a>(
+        &'a self,
+        profile: Option<&'a str>,
+    ) -> Result<(&'a str, &'a DatabaseProfile), ToolError> {
+        self.config
+            .resolve(profile)
+            .map_err(ToolError::InvalidParameters)
+    }
+
+    /// The pools of a profile, created on first use.
+    async fn pools(
+        &self,
+        name: &str,
+        profile: &DatabaseProfile,
+    ) -> Result<Arc<ProfilePools>, ToolError> {
+        let mut pools = self.pools.lock().await;
+        if let Some(existing) = pools.get(name) {
+            return Ok(existing.clone());
+        }
+        let backend = backend_of(name, profile)?;
+        let opened = Arc::new(open(backend, profile).map_err(|e| {
+            ToolError::ExecutionFailed(format!("Invalid url of database profile '{}': {}", name, e))
+        })?);
+        pools.insert(name.to_string(), opened.clone());
+        Ok(opened)
+    }
+}
+
+fn backend_of(name: &str, profile: &DatabaseProfile) -> Result<Backend, ToolError> {
+    profile.backend().ok_or_else(|| {
+        ToolError::ExecutionFailed(format!(
+            "Database profile '{}' needs a postgres://, mysql:// or sqlite: url",
+            name
+        ))
+    })
+}
+
+fn pool_options<DB: Database>(timeout: Duration) -> PoolOptions<DB> {
+    PoolOptions::new()
+        .max_connections(MAX_CONNECTIONS)
+        .acquire_timeout(timeout)
+}
+
+/// Pools of a profile, not yet connected.
+fn open(backend: Backend, profile: &DatabaseProfile) -> Result<ProfilePools, sqlx::Error> {
+    let timeout = Duration::from_secs(profile.statement_timeout_secs.max(1));
+    let millis = timeout.as_millis();
+    let pools = match backend {
+        Backend::Postgres => {
+            let options = PgConnectOptions::from_str(&profile.url)?
+                .options([("statement_timeout", millis.to_string())]);
+            let reader = options
+                .clone()
+                .options([("default_transaction_read_only", "on")]);
+            ProfilePools {
+                reader: Pools::Postgres(pool_options(timeout).connect_lazy_with(reader)),
+                writer: profile
+                    .allow_writes
+                    .then(|| Pools::Postgres(pool_options(timeout).connect_lazy_with(options))),
+            }
+        }
+        Backend::MySql => {
+            let options = MySqlConnectOptions::from_str(&profile.url)?;
+            let reader = pool_options(timeout)
+                .after_connect(move |conn, _| {
+                    Box::pin(async move {
+                        sqlx::query("SET SESSION TRANSACTION READ ONLY")
+                            .execute(&mut *conn)
+                            .await?;
+                        // Bounds SELECT statements on the server too
+                        let limit = format!("SET SESSION MAX_EXECUTION_TIME = {}", millis);
+                        sqlx::query(&limit).execute(&mut *conn).await?;
+                        Ok(())
+                    })
+                })
+                .connect_lazy_with(options.clone());
+            ProfilePools {
+                reader: Pools::MySql(reader),
+                writer: profile
+                    .allow_writes
+                    .then(|| Pools::MySql(pool_options(timeout).connect_lazy_with(options))),
+            }
+        }
+        Backend::Sqlite => {
+            let options = SqliteConnectOptions::from_str(&profile.url)?;
+            let reader = options.clone().read_only(true);
+            ProfilePools {
+                reader: Pools::Sqlite(pool_options(timeout).connect_lazy_with(reader)),
+                writer: profile
+                    .allow_writes
+                    .then(|| Pools::Sqlite(pool_options(timeout).connect_lazy_with(options))),
+            }
+        }
+    };
+    Ok(pools)
+}
+
+async fn with_timeout<T>(
+    profile: &DatabaseProfile,
+    run: impl Future<Output = Result<T, ToolError>>,
+) -> Result<T, ToolError> {
+    let secs = profile.statement_timeout_secs.max(1);
+    tokio::time::timeout(Duration::from_secs(secs), run)
+        .await
+        .map_err(|_| ToolError::Timeout(secs))?
+}
+
+fn db_error(e: sqlx::Error) -> ToolError {
+    ToolError::ExecutionFailed(format!("Database error: {}", e))
+}
+
+/// Run `sql`, keeping the first `limit` rows.
+async fn fetch<DB>(
+    pool: &Pool<DB>,
+    sql: &str,
+    limit: usize,
+    cell: fn(&DB::Row, usize) -> Value,
+) -> Result<Rows, ToolError>
+where
+    DB: Database,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    for<'q> DB::Arguments<'q>: IntoArguments<'q, DB>,
+{
+    let mut stream = sqlx::query::<DB>(sql).fetch(pool);
+    let mut rows = Rows::default();
+    while let Some(row) = stream.try_next().await.map_err(db_error)? {
+        if rows.rows.len() == limit {
+            rows.truncated = true;
+            break;
+        }
+        if rows.columns.is_empty() {
+            rows.columns = row
+                .columns()
+                .iter()
+                .map(|column| column.name().to_string())
+                .collect();
+        }
+        rows.rows
+            .push((0..row.len()).map(|i| cell(&row, i)).collect());
+    }
+    Ok(rows)
+}
+
+/// Rows changed by a statement.
+trait RowsAffected {
+    fn rows_affected(&self) -> u64;
+}
+
+impl RowsAffected for PgQueryResult {
+    fn rows_affected(&self) -> u64 {
+        PgQueryResult::rows_affected(self)
+    }
+}
+
+impl RowsAffected for MySqlQueryResult {
+    fn rows_affected(&self) -> u64 {
+        MySqlQueryResult::rows_affected(self)
+    }
+}
+
+impl RowsAffected for SqliteQueryResult {
+    fn rows_affected(&self) -> u64 {
+        SqliteQueryResult::rows_affected(self)
+    }
+}
+
+/// Run `sql` in a transaction, committed unless it changed more than
+/// `max` rows.
+async fn execute_limited<DB>(pool: &Pool<DB>, sql: &str, max: u64) -> Result<u64, ToolError>
+where
+    DB: Database,
+    DB::QueryResult: RowsAffected,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    for<'q> DB::Arguments<'q>: IntoArguments<'q, DB>,
+{
+    let mut tx = pool.begin().await.map_err(db_error)?;
+    let affected = sqlx::query::<DB>(sql)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?
+        .rows_affected();
+    if affected > max {
+        tx.rollback().await.map_err(db_error)?;
+        return Err(ToolError::ExecutionFailed(format!(
+            "The statement changed {} rows, over the limit of {}; it was rolled back",
+            affected, max
+        )));
+    }
+    tx.commit().await.map_err(db_error)?;
+    Ok(affected)
+}
+
+/// The value of a cell as the first of the listed types its column
+/// decodes as.
+macro_rules! decode_first {
+    ($row:expr, $i:expr; $($ty:ty => $json:expr),+ $(,)?) => {
+        $(
+            if let Ok(value) = $row.try_get::<Option<$ty>, _>($i) {
+                return value.map($json).unwrap_or(Value::Null);
+            }
+        )+
+    };
+}
+
+fn text<T: ToString>(value: T) -> Value {
+    Value::String(value.to_string())
+}
+
+fn bytes(value: Vec<u8>) -> Value {
+    Value::String(format!("<{} bytes>", value.len()))
+}
+
+/// A cell of a type without a JSON form, shown by its type name.
+fn unsupported<R: Row>(row: &R, i: usize) -> Value {
+    Value::String(format!("<{}>", row.column(i).type_info().name()))
+}
+
+fn pg_cell(row: &PgRow, i: usize) -> Value {
+    decode_first!(row, i;
+        bool => Value::from,
+        i16 => Value::from,
+        i32 => Value::from,
+        i64 => Value::from,
+        f32 => Value::from,
+        f64 => Value::from,
+        Decimal => text,
+        String => Value::from,
+        DateTime<Utc> => |t: DateTime<Utc>| Value::String(t.to_rfc3339()),
+        NaiveDateTime => text,
+        NaiveDate => text,
+        NaiveTime => text,
+        Uuid => text,
+        Value => |v: Value| v,
+        Vec<u8> => bytes,
+    );
+    unsupported(row, i)
+}
+
+fn mysql_cell(row: &MySqlRow, i: usize) -> Value {
+    decode_first!(row, i;
+        i64 => Value::from,
+        u64 => Value::from,
+        f32 => Value::from,
+        f64 => Value::from,
+        Decimal => text,
+        String => Value::from,
+        DateTime<Utc> => |t: DateTime<Utc>| Value::String(t.to_rfc3339()),
+        NaiveDateTime => text,
+        NaiveDate => text,
+        NaiveTime => text,
+        Value => |v: Value| v,
+        Vec<u8> => bytes,
+    );
+    unsupported(row, i)
+}
+
+/// SQLite values are decoded by their storage class, whatever the
+/// column's declared type.
+fn sqlite_cell(row: &SqliteRow, i: usize) -> Value {
+    let class = match row.try_get_raw(i) {
+        Ok(raw) if raw.is_null() => return Value::Null,
+        Ok(raw) => raw.type_info().name().to_string(),
+        Err(_) => return Value::Null,
+    };
+    let value = match class.as_str() {
+        "INTEGER" => row.try_get_unchecked::<i64, _>(i).map(Value::from),
+        "REAL" => row.try_get_unchecked::<f64, _>(i).map(Value::from),
+        "BLOB" => row.try_get_unchecked::<Vec<u8>, _>(i).map(bytes),
+        _ => row.try_get_unchecked::<String, _>(i).map(Value::from),
+    };
+    value.unwrap_or_else(|_| unsupported(row, i))
+}
+
+fn tables_sql(backend: Backend) -> &'static str {
+    match backend {
+        Backend::Postgres => {
+            "SELECT table_schema, table_name, table_type FROM information_schema.tables \
+             WHERE table_schema NOT IN ('pg_catalog', 'information_schema') \
+             ORDER BY table_schema, table_name"
+        }
+        Backend::MySql => {
+            "SELECT table_schema AS table_schema, table_name AS table_name, \
+             table_type AS table_type FROM information_schema.tables \
+             WHERE table_schema = DATABASE() ORDER BY table_name"
+        }
+        Backend::Sqlite => {
+            "SELECT 'main' AS table_schema, name AS table_name, \
+             CASE type WHEN 'view' THEN 'VIEW' ELSE 'BASE TABLE' END AS table_type \
+             FROM sqlite_master WHERE type IN ('table', 'view') \
+             AND name NOT LIKE 'sqlite_%' ORDER BY name"
+        }
+    }
+}
+
+/// Columns of `table`, a name checked by [`valid_table_name`].
+fn columns_sql(backend: Backend, table: &str) -> String {
+    let (schema, name) = match table.split_once('.') {
+        Some((schema, name)) => (Some(schema), name),
+        None => (None, table),
+    };
+    let quoted = |s: &str| format!("'{}'", s);
+    match backend {
+        Backend::Postgres => format!(
+            "SELECT column_name, data_type, is_nullable, column_default \
+             FROM information_schema.columns WHERE table_schema = {} AND table_name = '{}' \
+             ORDER BY ordinal_position",
+            schema.map_or("current_schema()".to_string(), quoted),
+            name
+        ),
+        Backend::MySql => format!(
+            "SELECT column_name AS column_name, column_type AS data_type, \
+             is_nullable AS is_nullable, column_default AS column_default \
+             FROM information_schema.columns WHERE table_schema = {} AND table_name = '{}' \
+             ORDER BY ordinal_position",
+            schema.map_or("DATABASE()".to_string(), quoted),
+            name
+        ),
+        Backend::Sqlite => format!(
+            "SELECT name AS column_name, type AS data_type, \
+             CASE WHEN \"notnull\" = 0 THEN 'YES' ELSE 'NO' END AS is_nullable, \
+             dflt_value AS column_default FROM pragma_table_info('{}', {}) ORDER BY cid",
+            name,
+            quoted(schema.unwrap_or("main"))
+        ),
+    }
+}
+
+#[cfg(test)]
+#[path = "client_tests.rs"]
+mod tests;
@@ -0,0 +1,66 @@
+use super::*;
+
+fn config(url: &str, allow_writes: bool) -> DatabaseConfig {
+    let mut config = DatabaseConfig::default();
+    config.profiles.insert(
+        "main".to_string(),
+        DatabaseProfile {
+            url: url.to_string(),
+            allow_writes,
+            ..Default::default()
+        },
+    );
+    config
+}
+
+#[test]
+fn test_columns_sql_qualifies_schema() {
+    let sql = columns_sql(Backend::Postgres, "users");
+    assert!(sql.contains("table_schema = current_schema() AND table_name = 'users'"));
+    let sql = columns_sql(Backend::MySql, "shop.users");
+    assert!(sql.contains("table_schema = 'shop' AND table_name = 'users'"));
+    let sql = columns_sql(Backend::Sqlite, "users");
+    assert!(sql.contains("pragma_table_info('users', 'main')"));
+}
+
+#[tokio::test]
+async fn test_sqlite_cells() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let url = format!("sqlite:{}?mode=rwc", dir.path().join("cells.db").display());
+    let clients = DatabaseClients::new(config(&url, true));
+    let rows = clients
+        .query(
+            None,
+            "SELECT 1 AS i, 1.5 AS r, 'a' AS t, x'0102' AS b, NULL AS n",
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(rows.columns, vec!["i", "r", "t", "b", "n"]);
+    assert_eq!(
+        rows.rows[0],
+        vec![
+            Value::from(1),
+            Value::from(1.5),
+            Value::from("a"),
+            Value::from("<2 bytes>"),
+            Value::Null,
+        ]
+    );
+    assert!(!rows.truncated);
+}
+
+#[tokio::test]
+async fn test_profile_errors() {
+    let clients = DatabaseClients::new(config("sqlite:missing.db", false));
+    let err = clients.execute(None, "DELETE FROM t").await.unwrap_err();
+    assert!(matches!(err, ToolError::PermissionDenied(_)));
+    let err = clients
+        .query(Some("other"), "SELECT 1", None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ToolError::InvalidParameters(_)));
+    // The file is opened read-only, so it is not created
+    let err = clients.query(None, "SELECT 1", None).await.unwrap_err();
+    assert!(matches!(err, ToolError::ExecutionFailed(_)));
+}
@@ -0,0 +1,134 @@
+//! Connection profiles (`[extensions.config.tools-database]`).
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Database engine of a profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    Postgres,
+    MySql,
+    Sqlite,
+}
+
+impl Backend {
+    /// Engine named by a connection URL's scheme.
+    pub fn from_url(url: &str) -> Option<Self> {
+        let scheme = url.split_once(':')?.0.to_ascii_lowercase();
+        match scheme.as_str() {
+            "postgres" | "postgresql" => Some(Self::Postgres),
+            "mysql" | "mariadb" => Some(Self::MySql),
+            "sqlite" => Some(Self::Sqlite),
+            _ => None,
+        }
+    }
+}
+
+/// A database the tools may connect to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DatabaseProfile {
+    /// Connection URL (`postgres://`, `mysql://` or `sqlite:`). Credentials
+    /// are best given as `${VAR}`, expanded when the config file is read.
+    pub url: String,
+    /// Whether `db_execute` may change data through this profile.
+    pub allow_writes: bool,
+    /// Most rows a query returns.
+    pub max_rows: usize,
+    /// Most rows one `db_execute` statement may change; it is rolled back
+    /// beyond that.
+    pub max_affected_rows: u64,
+    /// Seconds a statement may run.
+    pub statement_timeout_secs: u64,
+}
+
+impl Default for DatabaseProfile {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            allow_writes: false,
+            max_rows: 500,
+            max_affected_rows: 1000,
+            statement_timeout_secs: 30,
+        }
+    }
+}
+
+impl DatabaseProfile {
+    /// Engine of the profile's URL.
+    pub fn backend(&self) -> Option<Backend> {
+        Backend::from_url(&self.url)
+    }
+}
+
+/// Database settings of the extension.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DatabaseConfig {
+    /// Profile used when a call names none.
+    pub default_profile: Option<String>,
+    /// Connection profiles by name.
+    pub profiles: HashMap<String, DatabaseProfile>,
+}
+
+impl DatabaseConfig {
+    /// Check that every profile names a supported database.
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, profile) in &self.profiles {
+            if profile.backend().is_none() {
+                return Err(format!(
+                    "Database profile '{}' needs a postgres://, mysql:// or sqlite: url",
+                    name
+                ));
+            }
+        }
+        if let Some(name) = &self.default_profile {
+            if !self.profiles.contains_key(name) {
+                return Err(format!("Unknown default database profile '{}'", name));
+            }
+        }
+        Ok(())
+    }
+
+    /// Profile names, sorted.
+    pub fn profile_names(&self) -> Vec<&str> {
+        let mut names: Vec<_> = self.profiles.keys().map(String::as_str).collect();
+        names.sort();
+        names
+    }
+
+    /// The profile a call names, the default one, or the only one.
+    pub fn resolve<'a>(
+        &'a self,
+        name: Option<&'a str>,
+    ) -> Result<(&'a str, &'a DatabaseProfile), String> {
+        let name = match name.or(self.default_profile.as_deref()) {
+            Some(name) => name,
+            None if self.profiles.len() == 1 => self.profile_names()[0],
+            None if self.profiles.is_empty() => {
+                return Err("No database profiles configured; add one under \
+                    [extensions.config.tools-database.profiles.<name>]"
+                    .to_string())
+            }
+            None => {
+                return Err(format!(
+                    "Name a database profile (known: {})",
+                    self.profile_names().join(", ")
+                ))
+            }
+        };
+        let profile = self.profiles.get(name).ok_or_else(|| {
+            format!(
+                "Unknown database profile '{}' (known: {})",
+                name,
+                self.profile_names().join(", ")
+            )
+        })?;
+        Ok((name, profile))
+    }
+}
+
+#[cfg(test)]
+#[path = "config_tests.rs"]
+mod tests;
@@ -0,0 +1,88 @@
+use super::*;
+
+fn profile(url: &str) -> DatabaseProfile {
+    DatabaseProfile {
+        url: url.to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_backend_from_url() {
+    assert_eq!(
+        Backend::from_url("postgres://u:p@localhost/app"),
+        Some(Backend::Postgres)
+    );
+    assert_eq!(
+        Backend::from_url("postgresql://localhost"),
+        Some(Backend::Postgres)
+    );
+    assert_eq!(
+        Backend::from_url("mysql://localhost/app"),
+        Some(Backend::MySql)
+    );
+    assert_eq!(Backend::from_url("sqlite:data.db"), Some(Backend::Sqlite));
+    assert_eq!(Backend::from_url("redis://localhost"), None);
+    assert_eq!(Backend::from_url("data.db"), None);
+}
+
+#[test]
+fn test_config_from_toml_shape() {
+    let config: DatabaseConfig = serde_json::from_value(serde_json::json!({
+        "default_profile": "sales",
+        "profiles": {
+            "sales": {"url": "postgres://report@db/sales", "max_rows": 50},
+            "local": {"url": "sqlite:app.db", "allow_writes": true},
+        }
+    }))
+    .unwrap();
+    assert!(config.validate().is_ok());
+    let sales = &config.profiles["sales"];
+    assert_eq!(sales.max_rows, 50);
+    assert!(!sales.allow_writes);
+    assert_eq!(sales.statement_timeout_secs, 30);
+    assert!(config.profiles["local"].allow_writes);
+}
+
+#[test]
+fn test_validate() {
+    let mut config = DatabaseConfig::default();
+    config
+        .profiles
+        .insert("cache".to_string(), profile("redis://localhost"));
+    assert!(config.validate().unwrap_err().contains("'cache'"));
+
+    let mut config = DatabaseConfig::default();
+    config.default_profile = Some("missing".to_string());
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn test_resolve() {
+    let mut config = DatabaseConfig::default();
+    assert!(config
+        .resolve(None)
+        .unwrap_err()
+        .contains("No database profiles"));
+
+    config
+        .profiles
+        .insert("sales".to_string(), profile("postgres://db/sales"));
+    assert_eq!(config.resolve(None).unwrap().0, "sales");
+
+    config
+        .profiles
+        .insert("local".to_string(), profile("sqlite:app.db"));
+    assert!(config
+        .resolve(None)
+        .unwrap_err()
+        .contains("known: local, sales"));
+    assert_eq!(config.resolve(Some("local")).unwrap().0, "local");
+    assert!(config
+        .resolve(Some("prod"))
+        .unwrap_err()
+        .contains("Unknown"));
+
+    config.default_profile = Some("sales".to_string());
+    assert_eq!(config.resolve(None).unwrap().0, "sales");
+}
@@ -0,0 +1,104 @@
+//! Database tools extension definition.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::debug;
+
+use autohands_protocols::error::ExtensionError;
+use autohands_protocols::extension::{Extension, ExtensionContext, ExtensionManifest, Provides};
+use autohands_protocols::types::Version;
+
+use crate::client::DatabaseClients;
+use crate::config::DatabaseConfig;
+use crate::tools::{DbExecuteTool, DbQueryTool, DbSchemaTool};
+
+/// Database tools extension.
+pub struct DatabaseToolsExtension {
+    manifest: ExtensionManifest,
+}
+
+impl DatabaseToolsExtension {
+    pub fn new() -> Self {
+        let mut manifest =
+            ExtensionManifest::new("tools-database", "Database Tools", Version::new(0, 1, 0));
+        manifest.description =
+            "Queries, schema introspection and gated writes on Postgres, MySQL and SQLite"
+                .to_string();
+        manifest.provides = Provides {
+            tools: vec![
+                "db_query".to_string(),
+                "db_schema".to_string(),
+                "db_execute".to_string(),
+            ],
+            ..Default::default()
+        };
+
+        Self { manifest }
+    }
+}
+
+impl Default for DatabaseToolsExtension {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Extension for DatabaseToolsExtension {
+    fn manifest(&self) -> &ExtensionManifest {
+        &self.manifest
+    }
+
+    async fn initialize(&mut self, ctx: ExtensionContext) -> Result<(), ExtensionError> {
+        let config: DatabaseConfig = if ctx.config.is_null() {
+            DatabaseConfig::default()
+        } else {
+            serde_json::from_value(ctx.config.clone()).map_err(|e| {
+                ExtensionError::InitializationFailed(format!(
+                    "Invalid tools-database config: {}",
+                    e
+                ))
+            })?
+        };
+        config
+            .validate()
+            .map_err(ExtensionError::InitializationFailed)?;
+        debug!(
+            "Database tools initialized with profiles: {:?}",
+            config.profile_names()
+        );
+        let clients = Arc::new(DatabaseClients::new(config));
+
+        ctx.tool_registry
+            .register_tool(Arc::new(DbQueryTool::new(clients.clone())))?;
+        ctx.tool_registry
+            .register_tool(Arc::new(DbSchemaTool::new(clients.clone())))?;
+        ctx.tool_registry
+            .register_tool(Arc::new(DbExecuteTool::new(clients)))?;
+
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extension_manifest() {
+        let ext = DatabaseToolsExtension::new();
+        assert_eq!(ext.manifest().id, "tools-database");
+        assert_eq!(ext.manifest().name, "Database Tools");
+        assert_eq!(ext.manifest().provides.tools.len(), 3);
+    }
+}
@@ -0,0 +1,26 @@
+//! Database tools for AutoHands.
+//!
+//! Connects to Postgres, MySQL and SQLite through named profiles under
+//! `[extensions.config.tools-database]`, and provides:
+//!
+//! - `db_query`: one read-only statement, in a session the database itself
+//!   keeps from writing, returning up to the profile's `max_rows` rows
+//! - `db_schema`: the tables of a database, or the columns of one
+//! - `db_execute`: one statement that changes data, only on profiles with
+//!   `allow_writes`, rolled back when it changes more than
+//!   `max_affected_rows` rows
+//!
+//! Every statement is bounded by the profile's `statement_timeout_secs`.
+
+mod client;
+mod config;
+mod extension;
+mod rows;
+mod sql;
+mod tools;
+
+pub use client::DatabaseClients;
+pub use config::{Backend, DatabaseConfig, DatabaseProfile};
+pub use extension::DatabaseToolsExtension;
+pub use rows::Rows;
+pub use tools::{DbExecuteTool, DbQueryTool, DbSchemaTool};
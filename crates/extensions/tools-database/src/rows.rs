@@ -0,0 +1,113 @@
+//! Query results and their rendering.
+
+use serde_json::Value;
+
+/// Widest cell shown in a rendered table, in characters.
+const MAX_CELL_CHARS: usize = 60;
+
+/// Rows a query returned.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Rows {
+    /// Column names, empty when no row came back.
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+    /// Whether the row limit cut the results short.
+    pub truncated: bool,
+}
+
+impl Rows {
+    /// Rows as JSON objects keyed by column name.
+    pub fn to_json(&self) -> Vec<Value> {
+        self.rows
+            .iter()
+            .map(|row| {
+                let object = self
+                    .columns
+                    .iter()
+                    .cloned()
+                    .zip(row.iter().cloned())
+                    .collect::<serde_json::Map<_, _>>();
+                Value::Object(object)
+            })
+            .collect()
+    }
+
+    /// Rows as an aligned text table, noting a cut at `limit` rows.
+    pub fn render(&self, limit: usize) -> String {
+        if self.rows.is_empty() {
+            return "No rows".to_string();
+        }
+        let cells: Vec<Vec<String>> = self
+            .rows
+            .iter()
+            .map(|row| row.iter().map(cell_text).collect())
+            .collect();
+        let widths: Vec<usize> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                cells
+                    .iter()
+                    .map(|row| row[i].chars().count())
+                    .chain([name.chars().count()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        let mut out = vec![
+            line(self.columns.iter().map(String::as_str), &widths),
+            widths
+                .iter()
+                .map(|w| "-".repeat(*w))
+                .collect::<Vec<_>>()
+                .join("-+-"),
+        ];
+        out.extend(
+            cells
+                .iter()
+                .map(|row| line(row.iter().map(String::as_str), &widths)),
+        );
+        let count = self.rows.len();
+        out.push(if self.truncated {
+            format!(
+                "({} rows shown; more were cut at the limit of {})",
+                count, limit
+            )
+        } else {
+            format!("({} row{})", count, if count == 1 { "" } else { "s" })
+        });
+        out.join("\n")
+    }
+}
+
+/// One row of a table, each value padded to its column's width.
+fn line<'a>(values: impl Iterator<Item = &'a str>, widths: &[usize]) -> String {
+    values
+        .zip(widths)
+        .map(|(value, width)| format!("{:<width$}", value, width = *width))
+        .collect::<Vec<_>>()
+        .join(" | ")
+        .trim_end()
+        .to_string()
+}
+
+/// A value as shown in a table cell.
+fn cell_text(value: &Value) -> String {
+    let text = match value {
+        Value::Null => "NULL".to_string(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    let text = text.replace(['\n', '\r', '\t'], " ");
+    if text.chars().count() <= MAX_CELL_CHARS {
+        return text;
+    }
+    let mut cut: String = text.chars().take(MAX_CELL_CHARS - 1).collect();
+    cut.push('…');
+    cut
+}
+
+#[cfg(test)]
+#[path = "rows_tests.rs"]
+mod tests;
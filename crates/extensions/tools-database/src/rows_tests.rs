@@ -0,0 +1,51 @@
+use super::*;
+
+fn sample() -> Rows {
+    Rows {
+        columns: vec!["id".to_string(), "name".to_string()],
+        rows: vec![
+            vec![Value::from(1), Value::from("Ada")],
+            vec![Value::from(2), Value::Null],
+        ],
+        truncated: false,
+    }
+}
+
+#[test]
+fn test_render_table() {
+    let table = sample().render(500);
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines[0], "id | name");
+    assert_eq!(lines[1], "---+-----");
+    assert_eq!(lines[2], "1  | Ada");
+    assert_eq!(lines[3], "2  | NULL");
+    assert_eq!(lines[4], "(2 rows)");
+}
+
+#[test]
+fn test_render_truncated_and_empty() {
+    let mut rows = sample();
+    rows.truncated = true;
+    assert!(rows.render(2).ends_with("more were cut at the limit of 2)"));
+    assert_eq!(Rows::default().render(10), "No rows");
+}
+
+#[test]
+fn test_long_cells_are_cut() {
+    let rows = Rows {
+        columns: vec!["note".to_string()],
+        rows: vec![vec![Value::from("x".repeat(100))]],
+        truncated: false,
+    };
+    let table = rows.render(10);
+    let cell = table.lines().nth(2).unwrap();
+    assert_eq!(cell.chars().count(), MAX_CELL_CHARS);
+    assert!(cell.ends_with('…'));
+}
+
+#[test]
+fn test_to_json() {
+    let json = sample().to_json();
+    assert_eq!(json[0]["name"], "Ada");
+    assert!(json[1]["name"].is_null());
+}
@@ -0,0 +1,105 @@
+//! Checks made on SQL before it reaches the database.
+//!
+//! These only give clear errors early: read-only sessions are what keep
+//! `db_query` from writing.
+
+/// Leading keywords of statements that read.
+const READ_KEYWORDS: &[&str] = &[
+    "select", "with", "show", "explain", "describe", "desc", "values", "table", "pragma",
+];
+
+/// The statement in `sql`, without surrounding comments, whitespace and
+/// trailing semicolons.
+///
+/// Fails when `sql` is empty or holds more than one statement.
+pub fn single_statement(sql: &str) -> Result<&str, String> {
+    let mut end = 0;
+    let mut terminated = false;
+    let mut i = 0;
+    while let Some(c) = sql[i..].chars().next() {
+        let rest = &sql[i..];
+        let (len, significant) = match c {
+            '\'' | '"' | '`' => (quoted_len(rest, c), true),
+            '-' if rest.starts_with("--") => (rest.find('\n').unwrap_or(rest.len()), false),
+            '/' if rest.starts_with("/*") => (rest.find("*/").map_or(rest.len(), |j| j + 2), false),
+            ';' => {
+                terminated = true;
+                (1, false)
+            }
+            c => (c.len_utf8(), !c.is_whitespace()),
+        };
+        if significant {
+            if terminated {
+                return Err("Run one statement at a time".to_string());
+            }
+            end = i + len;
+        }
+        i += len;
+    }
+    let statement = strip_leading_comments(&sql[..end]);
+    if statement.is_empty() {
+        return Err("Empty SQL statement".to_string());
+    }
+    Ok(statement)
+}
+
+/// Bytes of the quoted string or identifier `s` starts with.
+fn quoted_len(s: &str, quote: char) -> usize {
+    let mut chars = s.char_indices().skip(1).peekable();
+    while let Some((i, c)) = chars.next() {
+        if c == quote {
+            // A doubled quote stands for itself
+            if chars.peek().is_some_and(|(_, next)| *next == quote) {
+                chars.next();
+                continue;
+            }
+            return i + 1;
+        }
+    }
+    s.len()
+}
+
+fn strip_leading_comments(mut sql: &str) -> &str {
+    loop {
+        sql = sql.trim_start();
+        if sql.starts_with("--") {
+            sql = sql.find('\n').map_or("", |i| &sql[i..]);
+        } else if sql.starts_with("/*") {
+            sql = sql.find("*/").map_or("", |i| &sql[i + 2..]);
+        } else {
+            return sql;
+        }
+    }
+}
+
+/// First keyword of a statement, lowercase.
+pub fn first_keyword(statement: &str) -> String {
+    strip_leading_comments(statement)
+        .trim_start_matches('(')
+        .chars()
+        .take_while(|c| c.is_ascii_alphabetic())
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+/// Whether a statement reads rather than writes, judging by its keyword.
+pub fn is_read_statement(statement: &str) -> bool {
+    READ_KEYWORDS.contains(&first_keyword(statement).as_str())
+}
+
+/// Whether `name` is a table name, optionally schema-qualified, that is
+/// safe to put in a query.
+pub fn valid_table_name(name: &str) -> bool {
+    let parts: Vec<&str> = name.split('.').collect();
+    parts.len() <= 2
+        && parts.iter().all(|part| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+        })
+}
+
+#[cfg(test)]
+#[path = "sql_tests.rs"]
+mod tests;
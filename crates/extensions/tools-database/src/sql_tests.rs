@@ -0,0 +1,45 @@
+use super::*;
+
+#[test]
+fn test_single_statement() {
+    assert_eq!(single_statement("  SELECT 1;  ").unwrap(), "SELECT 1");
+    assert_eq!(
+        single_statement("-- totals\nSELECT 1 -- trailing\n;").unwrap(),
+        "SELECT 1"
+    );
+    assert_eq!(
+        single_statement("SELECT ';' AS a, \"b;\" FROM t /* ; */").unwrap(),
+        "SELECT ';' AS a, \"b;\" FROM t"
+    );
+    assert_eq!(
+        single_statement("SELECT 'it''s; fine'").unwrap(),
+        "SELECT 'it''s; fine'"
+    );
+    assert!(single_statement("SELECT 1; DROP TABLE users").is_err());
+    assert!(single_statement("SELECT 1; 'x'").is_err());
+    assert!(single_statement(" ; -- nothing").is_err());
+    assert!(single_statement("").is_err());
+}
+
+#[test]
+fn test_read_statements() {
+    assert_eq!(first_keyword("/* report */ Select * FROM t"), "select");
+    assert_eq!(first_keyword("(SELECT 1) UNION (SELECT 2)"), "select");
+    assert!(is_read_statement("WITH x AS (SELECT 1) SELECT * FROM x"));
+    assert!(is_read_statement("EXPLAIN SELECT 1"));
+    assert!(is_read_statement("show tables"));
+    assert!(!is_read_statement("UPDATE t SET a = 1"));
+    assert!(!is_read_statement("DELETE FROM t"));
+    assert!(!is_read_statement("CREATE TABLE t (a int)"));
+}
+
+#[test]
+fn test_valid_table_name() {
+    assert!(valid_table_name("orders"));
+    assert!(valid_table_name("public.orders"));
+    assert!(valid_table_name("order_items$2"));
+    assert!(!valid_table_name("a.b.c"));
+    assert!(!valid_table_name("orders'--"));
+    assert!(!valid_table_name("public."));
+    assert!(!valid_table_name(""));
+}
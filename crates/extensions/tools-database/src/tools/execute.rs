@@ -0,0 +1,99 @@
+//! Write statement tool.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::types::RiskLevel;
+
+use super::profile_schema;
+use crate::client::DatabaseClients;
+use crate::sql::single_statement;
+
+/// Parameters for db_execute.
+#[derive(Debug, Deserialize)]
+struct ExecuteParams {
+    /// Connection profile.
+    #[serde(default)]
+    profile: Option<String>,
+    /// SQL statement.
+    sql: String,
+}
+
+/// Tool running a statement that changes data, on profiles that allow it.
+pub struct DbExecuteTool {
+    definition: ToolDefinition,
+    clients: Arc<DatabaseClients>,
+}
+
+impl DbExecuteTool {
+    /// Create an execute tool over the given connections.
+    pub fn new(clients: Arc<DatabaseClients>) -> Self {
+        let definition = ToolDefinition::new(
+            "db_execute",
+            "Execute SQL",
+            "Run one SQL statement that changes data (INSERT, UPDATE, DELETE, DDL) on a database profile configured with allow_writes. The statement runs in a transaction that is rolled back if it changes more rows than the profile allows",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "profile": profile_schema(clients.config()),
+                "sql": {
+                    "type": "string",
+                    "description": "One SQL statement"
+                }
+            },
+            "required": ["sql"]
+        }))
+        .with_risk_level(RiskLevel::High);
+
+        Self {
+            definition,
+            clients,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for DbExecuteTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: ExecuteParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
+        let statement = single_statement(&params.sql).map_err(ToolError::InvalidParameters)?;
+        let (name, _) = self
+            .clients
+            .config()
+            .resolve(params.profile.as_deref())
+            .map_err(ToolError::InvalidParameters)?;
+
+        // Not raced against the abort signal: a dropped transaction rolls
+        // back, but a commit in flight may still land
+        if ctx.abort_signal.is_aborted() {
+            return Err(ToolError::Cancelled);
+        }
+        let affected = self.clients.execute(Some(name), statement).await?;
+
+        Ok(ToolResult::success_json(
+            format!(
+                "{} row{} affected",
+                affected,
+                if affected == 1 { "" } else { "s" }
+            ),
+            serde_json::json!({
+                "profile": name,
+                "rows_affected": affected,
+            }),
+        ))
+    }
+}
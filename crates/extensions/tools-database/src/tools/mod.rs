@@ -0,0 +1,31 @@
+//! Database tool implementations.
+
+mod execute;
+mod query;
+mod schema;
+
+pub use execute::DbExecuteTool;
+pub use query::DbQueryTool;
+pub use schema::DbSchemaTool;
+
+use crate::config::DatabaseConfig;
+
+/// Schema of the `profile` parameter, listing the configured profiles.
+fn profile_schema(config: &DatabaseConfig) -> serde_json::Value {
+    let names = config.profile_names();
+    let description = match &config.default_profile {
+        Some(default) => format!(
+            "Connection profile: {} (default {})",
+            names.join(", "),
+            default
+        ),
+        None => format!("Connection profile: {}", names.join(", ")),
+    };
+    serde_json::json!({
+        "type": "string",
+        "description": description
+    })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,120 @@
+//! Read-only query tool.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::types::RiskLevel;
+
+use super::profile_schema;
+use crate::client::DatabaseClients;
+use crate::sql::{first_keyword, is_read_statement, single_statement};
+
+/// Parameters for db_query.
+#[derive(Debug, Deserialize)]
+struct QueryParams {
+    /// Connection profile.
+    #[serde(default)]
+    profile: Option<String>,
+    /// SQL statement.
+    sql: String,
+    /// Rows to return, up to the profile's limit.
+    #[serde(default)]
+    max_rows: Option<usize>,
+}
+
+/// Tool running read-only SQL against a configured database.
+pub struct DbQueryTool {
+    definition: ToolDefinition,
+    clients: Arc<DatabaseClients>,
+}
+
+impl DbQueryTool {
+    /// Create a query tool over the given connections.
+    pub fn new(clients: Arc<DatabaseClients>) -> Self {
+        let definition = ToolDefinition::new(
+            "db_query",
+            "Query Database",
+            "Run one read-only SQL statement (SELECT, WITH, SHOW, EXPLAIN, ...) against a configured database and return the rows. The session cannot write; use db_execute for changes and db_schema to find tables and columns",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "profile": profile_schema(clients.config()),
+                "sql": {
+                    "type": "string",
+                    "description": "One SQL statement"
+                },
+                "max_rows": {
+                    "type": "integer",
+                    "description": "Rows to return (default and maximum: the profile's max_rows)"
+                }
+            },
+            "required": ["sql"]
+        }))
+        .with_risk_level(RiskLevel::Medium)
+        .with_read_only()
+        .with_example(
+            "Orders per status",
+            serde_json::json!({
+                "sql": "SELECT status, COUNT(*) FROM orders GROUP BY status"
+            }),
+        );
+
+        Self {
+            definition,
+            clients,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for DbQueryTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: QueryParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
+        let statement = single_statement(&params.sql).map_err(ToolError::InvalidParameters)?;
+        if !is_read_statement(statement) {
+            return Err(ToolError::InvalidParameters(format!(
+                "db_query only reads; run {} statements with db_execute",
+                first_keyword(statement).to_ascii_uppercase()
+            )));
+        }
+        let (name, profile) = self
+            .clients
+            .config()
+            .resolve(params.profile.as_deref())
+            .map_err(ToolError::InvalidParameters)?;
+        let limit = params
+            .max_rows
+            .map_or(profile.max_rows, |n| n.min(profile.max_rows))
+            .max(1);
+
+        let run = self.clients.query(Some(name), statement, Some(limit));
+        let rows = tokio::select! {
+            rows = run => rows?,
+            _ = ctx.abort_signal.aborted() => return Err(ToolError::Cancelled),
+        };
+
+        Ok(ToolResult::success_json(
+            rows.render(limit),
+            serde_json::json!({
+                "profile": name,
+                "columns": rows.columns,
+                "rows": rows.to_json(),
+                "truncated": rows.truncated,
+            }),
+        ))
+    }
+}
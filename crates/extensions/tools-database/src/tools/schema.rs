@@ -0,0 +1,103 @@
+//! Schema introspection tool.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::types::RiskLevel;
+
+use super::profile_schema;
+use crate::client::DatabaseClients;
+
+/// Parameters for db_schema.
+#[derive(Debug, Deserialize)]
+struct SchemaParams {
+    /// Connection profile.
+    #[serde(default)]
+    profile: Option<String>,
+    /// Table whose columns to list; all tables when absent.
+    #[serde(default)]
+    table: Option<String>,
+}
+
+/// Tool listing the tables of a database, or the columns of one table.
+pub struct DbSchemaTool {
+    definition: ToolDefinition,
+    clients: Arc<DatabaseClients>,
+}
+
+impl DbSchemaTool {
+    /// Create a schema tool over the given connections.
+    pub fn new(clients: Arc<DatabaseClients>) -> Self {
+        let definition = ToolDefinition::new(
+            "db_schema",
+            "Database Schema",
+            "List the tables and views of a configured database, or with `table` its columns, their types, nullability and defaults",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "profile": profile_schema(clients.config()),
+                "table": {
+                    "type": "string",
+                    "description": "Table to describe, optionally as schema.table"
+                }
+            }
+        }))
+        .with_risk_level(RiskLevel::Low)
+        .with_read_only();
+
+        Self {
+            definition,
+            clients,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for DbSchemaTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: SchemaParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
+        let (name, profile) = self
+            .clients
+            .config()
+            .resolve(params.profile.as_deref())
+            .map_err(ToolError::InvalidParameters)?;
+
+        let run = self.clients.schema(Some(name), params.table.as_deref());
+        let rows = tokio::select! {
+            rows = run => rows?,
+            _ = ctx.abort_signal.aborted() => return Err(ToolError::Cancelled),
+        };
+        if let Some(table) = &params.table {
+            if rows.rows.is_empty() {
+                return Err(ToolError::ResourceNotFound(format!(
+                    "Table '{}' not found in profile '{}'",
+                    table, name
+                )));
+            }
+        }
+
+        Ok(ToolResult::success_json(
+            rows.render(profile.max_rows),
+            serde_json::json!({
+                "profile": name,
+                "table": params.table,
+                "rows": rows.to_json(),
+                "truncated": rows.truncated,
+            }),
+        ))
+    }
+}
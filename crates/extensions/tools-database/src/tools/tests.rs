@@ -0,0 +1,240 @@
+//! Tests for the database tools, against a SQLite file.
+
+use std::sync::Arc;
+
+use serde_json::json;
+use tempfile::TempDir;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{Tool, ToolContext, ToolResult};
+
+use super::*;
+use crate::client::DatabaseClients;
+use crate::config::{DatabaseConfig, DatabaseProfile};
+
+/// Clients with a writable `app` profile and a read-only `report` profile
+/// over the same file.
+fn clients(dir: &TempDir) -> Arc<DatabaseClients> {
+    let url = format!("sqlite:{}?mode=rwc", dir.path().join("app.db").display());
+    let mut config = DatabaseConfig {
+        default_profile: Some("app".to_string()),
+        ..Default::default()
+    };
+    config.profiles.insert(
+        "app".to_string(),
+        DatabaseProfile {
+            url: url.clone(),
+            allow_writes: true,
+            max_rows: 2,
+            max_affected_rows: 2,
+            ..Default::default()
+        },
+    );
+    config.profiles.insert(
+        "report".to_string(),
+        DatabaseProfile {
+            url,
+            ..Default::default()
+        },
+    );
+    Arc::new(DatabaseClients::new(config))
+}
+
+fn ctx(dir: &TempDir) -> ToolContext {
+    ToolContext::new("test", dir.path().to_path_buf())
+}
+
+async fn execute(
+    clients: &Arc<DatabaseClients>,
+    dir: &TempDir,
+    sql: &str,
+) -> Result<ToolResult, ToolError> {
+    DbExecuteTool::new(clients.clone())
+        .execute(json!({"sql": sql}), ctx(dir))
+        .await
+}
+
+#[tokio::test]
+async fn test_execute_then_query() {
+    let dir = TempDir::new().unwrap();
+    let clients = clients(&dir);
+    execute(
+        &clients,
+        &dir,
+        "CREATE TABLE users (id INTEGER, name TEXT, score REAL)",
+    )
+    .await
+    .unwrap();
+    let result = execute(
+        &clients,
+        &dir,
+        "INSERT INTO users VALUES (1, 'Ada', 9.5), (2, 'Linus', NULL);",
+    )
+    .await
+    .unwrap();
+    assert_eq!(result.content, "2 rows affected");
+
+    let result = DbQueryTool::new(clients.clone())
+        .execute(
+            json!({"profile": "report", "sql": "SELECT * FROM users ORDER BY id"}),
+            ctx(&dir),
+        )
+        .await
+        .unwrap();
+    assert!(result.content.starts_with("id | name  | score"));
+    let output = result.structured_output.unwrap();
+    assert_eq!(
+        output["rows"][0],
+        json!({"id": 1, "name": "Ada", "score": 9.5})
+    );
+    assert!(output["rows"][1]["score"].is_null());
+    assert_eq!(output["truncated"], false);
+}
+
+#[tokio::test]
+async fn test_query_limits_rows() {
+    let dir = TempDir::new().unwrap();
+    let clients = clients(&dir);
+    execute(&clients, &dir, "CREATE TABLE n (v INTEGER)")
+        .await
+        .unwrap();
+    execute(&clients, &dir, "INSERT INTO n VALUES (1), (2)")
+        .await
+        .unwrap();
+    execute(&clients, &dir, "INSERT INTO n VALUES (3)")
+        .await
+        .unwrap();
+
+    let result = DbQueryTool::new(clients.clone())
+        .execute(json!({"sql": "SELECT v FROM n ORDER BY v"}), ctx(&dir))
+        .await
+        .unwrap();
+    let output = result.structured_output.unwrap();
+    assert_eq!(output["rows"].as_array().unwrap().len(), 2);
+    assert_eq!(output["truncated"], true);
+
+    let result = DbQueryTool::new(clients)
+        .execute(json!({"sql": "SELECT v FROM n", "max_rows": 1}), ctx(&dir))
+        .await
+        .unwrap();
+    assert!(result.content.ends_with("more were cut at the limit of 1)"));
+}
+
+#[tokio::test]
+async fn test_query_rejects_writes_and_batches() {
+    let dir = TempDir::new().unwrap();
+    let tool = DbQueryTool::new(clients(&dir));
+    let err = tool
+        .execute(json!({"sql": "DELETE FROM users"}), ctx(&dir))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ToolError::InvalidParameters(m) if m.contains("db_execute")));
+
+    let err = tool
+        .execute(json!({"sql": "SELECT 1; DROP TABLE users"}), ctx(&dir))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ToolError::InvalidParameters(_)));
+}
+
+#[tokio::test]
+async fn test_read_only_session() {
+    let dir = TempDir::new().unwrap();
+    let clients = clients(&dir);
+    execute(&clients, &dir, "CREATE TABLE t (v INTEGER)")
+        .await
+        .unwrap();
+    // A write hidden behind a read keyword still fails in the session
+    let err = DbQueryTool::new(clients)
+        .execute(
+            json!({"profile": "report", "sql": "WITH x AS (SELECT 1) INSERT INTO t SELECT * FROM x"}),
+            ctx(&dir),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ToolError::ExecutionFailed(_)));
+}
+
+#[tokio::test]
+async fn test_execute_gates() {
+    let dir = TempDir::new().unwrap();
+    let clients = clients(&dir);
+    let err = DbExecuteTool::new(clients.clone())
+        .execute(
+            json!({"profile": "report", "sql": "CREATE TABLE t (v INTEGER)"}),
+            ctx(&dir),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ToolError::PermissionDenied(_)));
+
+    execute(&clients, &dir, "CREATE TABLE t (v INTEGER)")
+        .await
+        .unwrap();
+    let err = execute(&clients, &dir, "INSERT INTO t VALUES (1), (2), (3)")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ToolError::ExecutionFailed(m) if m.contains("rolled back")));
+
+    let result = DbQueryTool::new(clients)
+        .execute(json!({"sql": "SELECT COUNT(*) AS n FROM t"}), ctx(&dir))
+        .await
+        .unwrap();
+    assert_eq!(result.structured_output.unwrap()["rows"][0]["n"], 0);
+}
+
+#[tokio::test]
+async fn test_schema() {
+    let dir = TempDir::new().unwrap();
+    let clients = clients(&dir);
+    execute(
+        &clients,
+        &dir,
+        "CREATE TABLE users (id INTEGER NOT NULL, name TEXT DEFAULT 'x')",
+    )
+    .await
+    .unwrap();
+
+    let tool = DbSchemaTool::new(clients);
+    let result = tool.execute(json!({}), ctx(&dir)).await.unwrap();
+    let output = result.structured_output.unwrap();
+    assert_eq!(output["rows"][0]["table_name"], "users");
+    assert_eq!(output["rows"][0]["table_type"], "BASE TABLE");
+
+    let result = tool
+        .execute(json!({"table": "users"}), ctx(&dir))
+        .await
+        .unwrap();
+    let columns = &result.structured_output.unwrap()["rows"];
+    assert_eq!(columns[0]["column_name"], "id");
+    assert_eq!(columns[0]["data_type"], "INTEGER");
+    assert_eq!(columns[0]["is_nullable"], "NO");
+    assert_eq!(columns[1]["column_default"], "'x'");
+
+    let err = tool
+        .execute(json!({"table": "missing"}), ctx(&dir))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ToolError::ResourceNotFound(_)));
+    let err = tool
+        .execute(json!({"table": "users; --"}), ctx(&dir))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ToolError::InvalidParameters(_)));
+}
+
+#[test]
+fn test_definitions() {
+    let dir = TempDir::new().unwrap();
+    let clients = clients(&dir);
+    let query = DbQueryTool::new(clients.clone());
+    assert!(query.definition().read_only);
+    let schema = query
+        .definition()
+        .parameters_schema
+        .as_ref()
+        .unwrap()
+        .to_string();
+    assert!(schema.contains("app, report (default app)"));
+    assert!(!DbExecuteTool::new(clients).definition().read_only);
+}
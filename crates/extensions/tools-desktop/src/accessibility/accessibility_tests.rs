@@ -0,0 +1,133 @@
+use super::ax_controller::classify_failure;
+use super::ax_types::normalize_role;
+use super::*;
+
+const TREE: &str = r#"{
+    "role": "AXApplication",
+    "name": "Notes",
+    "children": [{
+        "role": "AXWindow",
+        "name": "Untitled",
+        "x": 100, "y": 50, "width": 800, "height": 600,
+        "children": [
+            {"role": "AXButton", "name": "Close", "x": 107, "y": 53, "width": 14, "height": 16},
+            {"role": "AXTextField", "name": "Search", "value": "groceries",
+             "x": 600, "y": 60, "width": 200, "height": 24, "children": []},
+            {"role": "AXButton", "name": "New Note", "x": 300, "y": 55, "width": 30, "height": 20}
+        ]
+    }]
+}"#;
+
+#[test]
+fn test_normalize_role() {
+    assert_eq!(normalize_role("AXButton"), "button");
+    assert_eq!(normalize_role("AXTextField"), "text field");
+    assert_eq!(normalize_role("AXMenuBarItem"), "menu bar item");
+    assert_eq!(normalize_role("ControlType.Edit"), "edit");
+    assert_eq!(normalize_role("ControlType.TabItem"), "tab item");
+    assert_eq!(normalize_role("push button"), "push button");
+}
+
+#[test]
+fn test_parse_tree() {
+    let tree = AxNode::parse(TREE).unwrap();
+    assert_eq!(tree.role, "application");
+    assert_eq!(tree.count(), 5);
+    let window = &tree.children[0];
+    assert_eq!(window.role, "window");
+    assert_eq!(window.children[1].role, "text field");
+    assert_eq!(window.children[1].value, "groceries");
+}
+
+#[test]
+fn test_parse_invalid() {
+    assert!(matches!(
+        AxNode::parse("not json"),
+        Err(AxError::QueryFailed(_))
+    ));
+}
+
+#[test]
+fn test_outline() {
+    let outline = AxNode::parse(TREE).unwrap().to_outline();
+    let lines: Vec<&str> = outline.lines().collect();
+    assert_eq!(lines[0], "application \"Notes\"");
+    assert_eq!(lines[1], "  window \"Untitled\" (100, 50, 800x600)");
+    assert_eq!(
+        lines[3],
+        "    text field \"Search\" = \"groceries\" (600, 60, 200x24)"
+    );
+}
+
+#[test]
+fn test_find_by_role_and_name() {
+    let tree = AxNode::parse(TREE).unwrap();
+
+    let buttons = tree.find(
+        &AxQuery {
+            role: Some("AXButton".to_string()),
+            name: None,
+        },
+        10,
+    );
+    assert_eq!(buttons.len(), 2);
+    assert_eq!(buttons[0].name, "Close");
+    assert_eq!((buttons[0].center_x, buttons[0].center_y), (114, 61));
+
+    let found = tree.find(
+        &AxQuery {
+            role: Some("button".to_string()),
+            name: Some("new".to_string()),
+        },
+        10,
+    );
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].name, "New Note");
+    assert!(found[0].value.is_empty());
+}
+
+#[test]
+fn test_find_limit() {
+    let tree = AxNode::parse(TREE).unwrap();
+    assert_eq!(tree.find(&AxQuery::default(), 3).len(), 3);
+}
+
+#[test]
+fn test_target() {
+    assert_eq!(AxTarget::Frontmost.script_args(), ("front", String::new()));
+    assert_eq!(
+        AxTarget::Name("Notes".to_string()).script_args(),
+        ("name", "Notes".to_string())
+    );
+    assert_eq!(AxTarget::Pid(42).script_args(), ("pid", "42".to_string()));
+    assert_eq!(AxTarget::Pid(42).to_string(), "pid 42");
+}
+
+#[test]
+fn test_classify_failure() {
+    let target = AxTarget::Name("Notes".to_string());
+    assert!(matches!(
+        classify_failure(&target, "Error: Application not found (-2700)"),
+        AxError::AppNotFound(name) if name == "Notes"
+    ));
+    assert!(matches!(
+        classify_failure(
+            &target,
+            "osascript is not allowed assistive access. (-1719)"
+        ),
+        AxError::Unavailable(_)
+    ));
+    assert!(matches!(
+        classify_failure(&target, "ModuleNotFoundError: No module named 'gi'"),
+        AxError::Unavailable(_)
+    ));
+    assert!(matches!(
+        classify_failure(&target, "something else"),
+        AxError::QueryFailed(message) if message == "something else"
+    ));
+}
+
+#[test]
+fn test_ax_controller_new() {
+    assert!(AxController::new().is_ok());
+}
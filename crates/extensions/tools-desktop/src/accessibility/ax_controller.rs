@@ -0,0 +1,340 @@
+//! AxController: reads accessibility trees through platform scripts.
+//!
+//! Each platform script walks the target application's elements and prints
+//! them as one JSON tree of `role`, `name`, `value`, `x`, `y`, `width`,
+//! `height` and `children`, parsed by [`AxNode::parse`].
+
+use super::{AxElement, AxError, AxNode, AxQuery, AxTarget};
+
+/// Deepest a tree is read.
+const MAX_DEPTH: usize = 40;
+
+/// Most elements read from one application, bounding the time a large UI
+/// takes to walk.
+const MAX_NODES: usize = 3000;
+
+/// Accessibility controller for inspecting native application UIs.
+pub struct AxController;
+
+impl AxController {
+    /// Create a new accessibility controller.
+    pub fn new() -> Result<Self, AxError> {
+        Ok(Self)
+    }
+
+    /// Read the tree of `target`'s elements, down to `max_depth` levels
+    /// below the application.
+    pub fn tree(&self, target: &AxTarget, max_depth: usize) -> Result<AxNode, AxError> {
+        let output = run_script(target, max_depth.min(MAX_DEPTH))?;
+        if !output.status.success() {
+            return Err(classify_failure(
+                target,
+                &String::from_utf8_lossy(&output.stderr),
+            ));
+        }
+        AxNode::parse(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    /// Find up to `limit` of `target`'s elements matching `query`.
+    pub fn find(
+        &self,
+        target: &AxTarget,
+        query: &AxQuery,
+        limit: usize,
+    ) -> Result<Vec<AxElement>, AxError> {
+        Ok(self.tree(target, MAX_DEPTH)?.find(query, limit))
+    }
+}
+
+impl Default for AxController {
+    fn default() -> Self {
+        Self
+    }
+}
+
+/// Map a script's error output to an error, recognizing a missing
+/// permission or library and a missing application.
+pub(crate) fn classify_failure(target: &AxTarget, stderr: &str) -> AxError {
+    let stderr = stderr.trim();
+    if stderr.contains("Application not found") {
+        AxError::AppNotFound(target.to_string())
+    } else if stderr.contains("assistive access") || stderr.contains("-25211") {
+        AxError::Unavailable(
+            "Accessibility access is not granted. Allow it in System Settings > \
+             Privacy & Security > Accessibility"
+                .to_string(),
+        )
+    } else if stderr.contains("No module named 'gi'")
+        || stderr.contains("Namespace Atspi not available")
+    {
+        AxError::Unavailable(
+            "AT-SPI Python bindings are not installed. Install with: \
+             apt install python3-gi gir1.2-atspi-2.0"
+                .to_string(),
+        )
+    } else {
+        AxError::QueryFailed(stderr.to_string())
+    }
+}
+
+/// Run the tree script (macOS - JavaScript for Automation).
+#[cfg(target_os = "macos")]
+fn run_script(target: &AxTarget, max_depth: usize) -> Result<std::process::Output, AxError> {
+    use std::process::Command;
+
+    let (kind, value) = target.script_args();
+    Command::new("osascript")
+        .arg("-l")
+        .arg("JavaScript")
+        .arg("-e")
+        .arg(JXA_SCRIPT)
+        .arg(kind)
+        .arg(value)
+        .arg(max_depth.to_string())
+        .arg(MAX_NODES.to_string())
+        .output()
+        .map_err(|e| AxError::QueryFailed(e.to_string()))
+}
+
+/// Run the tree script (Linux - AT-SPI through Python).
+#[cfg(target_os = "linux")]
+fn run_script(target: &AxTarget, max_depth: usize) -> Result<std::process::Output, AxError> {
+    use std::process::Command;
+
+    let (kind, value) = target.script_args();
+    Command::new("python3")
+        .arg("-c")
+        .arg(ATSPI_SCRIPT)
+        .arg(kind)
+        .arg(value)
+        .arg(max_depth.to_string())
+        .arg(MAX_NODES.to_string())
+        .output()
+        .map_err(|e| AxError::Unavailable(format!("python3: {}", e)))
+}
+
+/// Run the tree script (Windows - UI Automation through PowerShell).
+#[cfg(target_os = "windows")]
+fn run_script(target: &AxTarget, max_depth: usize) -> Result<std::process::Output, AxError> {
+    use std::process::Command;
+
+    // Arguments are only passed to a script run from a file
+    let script_file = std::env::temp_dir().join(format!("ax_tree_{}.ps1", std::process::id()));
+    std::fs::write(&script_file, UIA_SCRIPT).map_err(|e| AxError::QueryFailed(e.to_string()))?;
+
+    let (kind, value) = target.script_args();
+    let output = Command::new("powershell")
+        .arg("-NoProfile")
+        .arg("-NonInteractive")
+        .arg("-ExecutionPolicy")
+        .arg("Bypass")
+        .arg("-File")
+        .arg(&script_file)
+        .arg(kind)
+        .arg(value)
+        .arg(max_depth.to_string())
+        .arg(MAX_NODES.to_string())
+        .output()
+        .map_err(|e| AxError::QueryFailed(e.to_string()));
+
+    let _ = std::fs::remove_file(&script_file);
+    output
+}
+
+/// Run the tree script (unsupported platform).
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+fn run_script(_target: &AxTarget, _max_depth: usize) -> Result<std::process::Output, AxError> {
+    Err(AxError::PlatformNotSupported)
+}
+
+#[cfg(target_os = "macos")]
+const JXA_SCRIPT: &str = r#"
+function run(argv) {
+    const [kind, target] = argv;
+    const maxDepth = parseInt(argv[2], 10);
+    const maxNodes = parseInt(argv[3], 10);
+    const events = Application('System Events');
+    let procs;
+    if (kind === 'pid') {
+        procs = events.processes.whose({ unixId: parseInt(target, 10) })();
+    } else if (kind === 'name') {
+        const wanted = target.toLowerCase();
+        procs = events.processes().filter(p => p.name().toLowerCase() === wanted);
+    } else {
+        procs = events.processes.whose({ frontmost: true })();
+    }
+    if (procs.length === 0) throw new Error('Application not found');
+
+    const text = (read) => {
+        try {
+            const value = read();
+            return value === null || value === undefined ? '' : String(value);
+        } catch (e) {
+            return '';
+        }
+    };
+    let count = 0;
+    const visit = (el, depth) => {
+        count++;
+        const node = {
+            role: text(() => el.role()),
+            name: text(() => el.name()) || text(() => el.description()),
+            value: text(() => el.value()).slice(0, 200),
+            x: 0, y: 0, width: 0, height: 0,
+            children: [],
+        };
+        try {
+            const [x, y] = el.position();
+            const [width, height] = el.size();
+            Object.assign(node, { x, y, width, height });
+        } catch (e) {}
+        if (depth < maxDepth) {
+            let children = [];
+            try { children = el.uiElements(); } catch (e) {}
+            for (const child of children) {
+                if (count >= maxNodes) break;
+                node.children.push(visit(child, depth + 1));
+            }
+        }
+        return node;
+    };
+    return JSON.stringify(visit(procs[0], 0));
+}
+"#;
+
+#[cfg(target_os = "linux")]
+const ATSPI_SCRIPT: &str = r#"
+import json
+import sys
+
+import gi
+gi.require_version('Atspi', '2.0')
+from gi.repository import Atspi
+
+kind, target = sys.argv[1], sys.argv[2]
+max_depth, max_nodes = int(sys.argv[3]), int(sys.argv[4])
+
+desktop = Atspi.get_desktop(0)
+apps = [desktop.get_child_at_index(i) for i in range(desktop.get_child_count())]
+apps = [app for app in apps if app is not None]
+
+def is_active(app):
+    for i in range(app.get_child_count()):
+        window = app.get_child_at_index(i)
+        if window is not None and window.get_state_set().contains(Atspi.StateType.ACTIVE):
+            return True
+    return False
+
+if kind == 'pid':
+    apps = [app for app in apps if app.get_process_id() == int(target)]
+elif kind == 'name':
+    apps = [app for app in apps if (app.get_name() or '').lower() == target.lower()]
+else:
+    apps = [app for app in apps if is_active(app)]
+if not apps:
+    sys.stderr.write('Application not found\n')
+    sys.exit(2)
+
+def text(read):
+    try:
+        return read() or ''
+    except Exception:
+        return ''
+
+count = 0
+
+def visit(el, depth):
+    global count
+    count += 1
+    node = {
+        'role': text(el.get_role_name),
+        'name': text(el.get_name) or text(el.get_description),
+        'value': '',
+        'x': 0, 'y': 0, 'width': 0, 'height': 0,
+        'children': [],
+    }
+    try:
+        extents = el.get_extents(Atspi.CoordType.SCREEN)
+        node.update(x=extents.x, y=extents.y,
+                    width=max(extents.width, 0), height=max(extents.height, 0))
+    except Exception:
+        pass
+    try:
+        if el.get_value_iface() is not None:
+            node['value'] = str(Atspi.Value.get_current_value(el))
+        elif el.get_editable_text_iface() is not None:
+            length = min(Atspi.Text.get_character_count(el), 200)
+            node['value'] = Atspi.Text.get_text(el, 0, length)
+    except Exception:
+        pass
+    if depth < max_depth:
+        for i in range(el.get_child_count()):
+            if count >= max_nodes:
+                break
+            child = el.get_child_at_index(i)
+            if child is not None:
+                node['children'].append(visit(child, depth + 1))
+    return node
+
+print(json.dumps(visit(apps[0], 0)))
+"#;
+
+#[cfg(target_os = "windows")]
+const UIA_SCRIPT: &str = r#"
+param([string]$Kind, [string]$Target, [int]$MaxDepth, [int]$MaxNodes)
+$ErrorActionPreference = 'Stop'
+Add-Type -AssemblyName UIAutomationClient
+Add-Type -AssemblyName UIAutomationTypes
+Add-Type -Namespace Native -Name User32 -MemberDefinition @'
+[DllImport("user32.dll")] public static extern IntPtr GetForegroundWindow();
+'@
+
+$handle = [IntPtr]::Zero
+if ($Kind -eq 'pid') {
+    $proc = Get-Process -Id ([int]$Target) -ErrorAction SilentlyContinue
+    if ($proc) { $handle = $proc.MainWindowHandle }
+} elseif ($Kind -eq 'name') {
+    $proc = Get-Process -Name $Target -ErrorAction SilentlyContinue |
+        Where-Object { $_.MainWindowHandle -ne [IntPtr]::Zero } | Select-Object -First 1
+    if ($proc) { $handle = $proc.MainWindowHandle }
+} else {
+    $handle = [Native.User32]::GetForegroundWindow()
+}
+if ($handle -eq [IntPtr]::Zero) { [Console]::Error.WriteLine('Application not found'); exit 2 }
+
+$walker = [System.Windows.Automation.TreeWalker]::ControlViewWalker
+$script:count = 0
+
+function Visit($el, $depth) {
+    $script:count++
+    $current = $el.Current
+    $node = [ordered]@{
+        role = [string]$current.ControlType.ProgrammaticName
+        name = [string]$current.Name
+        value = ''
+        x = 0; y = 0; width = 0; height = 0
+        children = New-Object System.Collections.ArrayList
+    }
+    $rect = $current.BoundingRectangle
+    if (-not $rect.IsEmpty) {
+        $node.x = [int]$rect.X; $node.y = [int]$rect.Y
+        $node.width = [int]$rect.Width; $node.height = [int]$rect.Height
+    }
+    $pattern = $null
+    if ($el.TryGetCurrentPattern([System.Windows.Automation.ValuePattern]::Pattern, [ref]$pattern)) {
+        $value = [string]$pattern.Current.Value
+        $node.value = $value.Substring(0, [Math]::Min($value.Length, 200))
+    }
+    if ($depth -lt $MaxDepth) {
+        $child = $walker.GetFirstChild($el)
+        while ($child -ne $null -and $script:count -lt $MaxNodes) {
+            [void]$node.children.Add((Visit $child ($depth + 1)))
+            $child = $walker.GetNextSibling($child)
+        }
+    }
+    $node
+}
+
+$root = [System.Windows.Automation.AutomationElement]::FromHandle($handle)
+Visit $root 0 | ConvertTo-Json -Depth 100 -Compress
+"#;
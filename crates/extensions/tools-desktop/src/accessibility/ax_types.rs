@@ -0,0 +1,239 @@
+//! Accessibility type definitions.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Errors related to accessibility operations.
+#[derive(Debug, Error)]
+pub enum AxError {
+    /// Platform not supported.
+    #[error("Accessibility inspection not supported on this platform")]
+    PlatformNotSupported,
+
+    /// Accessibility API not available or not permitted.
+    #[error("Accessibility API not available: {0}")]
+    Unavailable(String),
+
+    /// Application not found.
+    #[error("Application not found: {0}")]
+    AppNotFound(String),
+
+    /// Failed to read the tree.
+    #[error("Failed to read accessibility tree: {0}")]
+    QueryFailed(String),
+}
+
+/// Application whose UI is inspected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AxTarget {
+    /// The application with the focused window.
+    Frontmost,
+    /// An application by name, case-insensitively.
+    Name(String),
+    /// An application by process ID.
+    Pid(u32),
+}
+
+impl AxTarget {
+    /// Kind and value passed to the platform scripts.
+    pub(crate) fn script_args(&self) -> (&'static str, String) {
+        match self {
+            Self::Frontmost => ("front", String::new()),
+            Self::Name(name) => ("name", name.clone()),
+            Self::Pid(pid) => ("pid", pid.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for AxTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Frontmost => write!(f, "frontmost application"),
+            Self::Name(name) => write!(f, "{}", name),
+            Self::Pid(pid) => write!(f, "pid {}", pid),
+        }
+    }
+}
+
+/// A UI element with its descendants.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AxNode {
+    /// Role, lowercase with the platform prefix dropped (e.g. "button",
+    /// "text field").
+    #[serde(default)]
+    pub role: String,
+    /// Accessible name.
+    #[serde(default)]
+    pub name: String,
+    /// Current value, such as the text of a text field.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub value: String,
+    /// X position, in global screen coordinates.
+    #[serde(default)]
+    pub x: i32,
+    /// Y position, in global screen coordinates.
+    #[serde(default)]
+    pub y: i32,
+    #[serde(default)]
+    pub width: u32,
+    #[serde(default)]
+    pub height: u32,
+    #[serde(default)]
+    pub children: Vec<AxNode>,
+}
+
+impl AxNode {
+    /// Parse the JSON tree printed by the platform scripts.
+    pub(crate) fn parse(json: &str) -> Result<Self, AxError> {
+        let mut root: AxNode = serde_json::from_str(json.trim())
+            .map_err(|e| AxError::QueryFailed(format!("Failed to parse output: {}", e)))?;
+        root.normalize();
+        Ok(root)
+    }
+
+    fn normalize(&mut self) {
+        self.role = normalize_role(&self.role);
+        for child in &mut self.children {
+            child.normalize();
+        }
+    }
+
+    /// Number of elements in the tree.
+    pub fn count(&self) -> usize {
+        1 + self.children.iter().map(AxNode::count).sum::<usize>()
+    }
+
+    /// The element without its descendants.
+    pub fn element(&self) -> AxElement {
+        AxElement {
+            role: self.role.clone(),
+            name: self.name.clone(),
+            value: self.value.clone(),
+            x: self.x,
+            y: self.y,
+            width: self.width,
+            height: self.height,
+            center_x: self.x + (self.width / 2) as i32,
+            center_y: self.y + (self.height / 2) as i32,
+        }
+    }
+
+    /// Up to `limit` elements matching `query`, in document order.
+    pub fn find(&self, query: &AxQuery, limit: usize) -> Vec<AxElement> {
+        let mut found = Vec::new();
+        self.collect(query, limit, &mut found);
+        found
+    }
+
+    fn collect(&self, query: &AxQuery, limit: usize, found: &mut Vec<AxElement>) {
+        if found.len() >= limit {
+            return;
+        }
+        if query.matches(self) {
+            found.push(self.element());
+        }
+        for child in &self.children {
+            child.collect(query, limit, found);
+        }
+    }
+
+    /// Indented outline of the tree, one element per line.
+    pub fn to_outline(&self) -> String {
+        let mut output = String::new();
+        self.write_outline(0, &mut output);
+        output
+    }
+
+    fn write_outline(&self, depth: usize, output: &mut String) {
+        output.push_str(&"  ".repeat(depth));
+        output.push_str(&self.role);
+        if !self.name.is_empty() {
+            output.push_str(&format!(" {:?}", self.name));
+        }
+        if !self.value.is_empty() {
+            output.push_str(&format!(" = {:?}", self.value));
+        }
+        if self.width > 0 && self.height > 0 {
+            output.push_str(&format!(
+                " ({}, {}, {}x{})",
+                self.x, self.y, self.width, self.height
+            ));
+        }
+        output.push('\n');
+        for child in &self.children {
+            child.write_outline(depth + 1, output);
+        }
+    }
+}
+
+/// A UI element found in the tree.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AxElement {
+    pub role: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub value: String,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    /// X of the element's center, where it is clicked.
+    pub center_x: i32,
+    /// Y of the element's center, where it is clicked.
+    pub center_y: i32,
+}
+
+/// Elements to find; every given field must match.
+#[derive(Debug, Clone, Default)]
+pub struct AxQuery {
+    /// Text in the element's role (e.g. "button" matches "push button").
+    pub role: Option<String>,
+    /// Text in the element's name, case-insensitively.
+    pub name: Option<String>,
+}
+
+impl AxQuery {
+    /// Whether `node` matches.
+    pub fn matches(&self, node: &AxNode) -> bool {
+        let role = self
+            .role
+            .as_deref()
+            .is_none_or(|role| node.role.contains(&normalize_role(role)));
+        let name = self
+            .name
+            .as_deref()
+            .is_none_or(|name| node.name.to_lowercase().contains(&name.to_lowercase()));
+        role && name
+    }
+}
+
+/// Lowercase role with words spaced, dropping the macOS "AX" and the
+/// Windows "ControlType." prefixes, so "AXTextField" is "text field".
+pub(crate) fn normalize_role(role: &str) -> String {
+    let role = role.trim();
+    let role = role
+        .strip_prefix("ControlType.")
+        .or_else(|| {
+            role.strip_prefix("AX")
+                .filter(|rest| rest.starts_with(char::is_uppercase))
+        })
+        .unwrap_or(role);
+
+    let mut output = String::with_capacity(role.len() + 4);
+    let mut previous_lower = false;
+    for c in role.chars() {
+        if c.is_uppercase() {
+            if previous_lower {
+                output.push(' ');
+            }
+            output.extend(c.to_lowercase());
+            previous_lower = false;
+        } else {
+            output.push(c);
+            previous_lower = c.is_lowercase() || c.is_ascii_digit();
+        }
+    }
+    output
+}
@@ -0,0 +1,15 @@
+//! Accessibility tree inspection.
+//!
+//! Reads the roles, names, values and positions of a native application's
+//! UI elements through the platform accessibility API: the Accessibility
+//! API on macOS, UI Automation on Windows and AT-SPI on Linux.
+
+mod ax_controller;
+mod ax_types;
+
+pub use ax_controller::AxController;
+pub use ax_types::{AxElement, AxError, AxNode, AxQuery, AxTarget};
+
+#[cfg(test)]
+#[path = "accessibility_tests.rs"]
+mod tests;
@@ -0,0 +1,71 @@
+//! Countdown shown before an agent takes the mouse and keyboard.
+//!
+//! Each second of the countdown is a desktop notification (replacing the
+//! previous one where the notification daemon allows it), which does not
+//! take focus from the window the agent is about to use. Without a
+//! notification service, e.g. on Windows, the countdown is only logged.
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+use std::process::{Command, Stdio};
+
+use tracing::{debug, info};
+
+/// Title of the countdown notifications.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+const TITLE: &str = "AutoHands";
+
+/// Text of the countdown with `secs` seconds left.
+pub(crate) fn countdown_message(secs: u64) -> String {
+    format!(
+        "An agent takes the mouse and keyboard in {} s. Move the mouse to stop it.",
+        secs
+    )
+}
+
+/// Show the countdown with `secs` seconds left.
+pub(crate) fn show_countdown(secs: u64) {
+    let message = countdown_message(secs);
+    info!("{}", message);
+    if let Err(e) = notify(&message) {
+        debug!("Countdown notification not shown: {}", e);
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn notify(message: &str) -> Result<(), String> {
+    let script = format!(
+        "display notification \"{}\" with title \"{}\"",
+        message.replace('"', "\\\""),
+        TITLE
+    );
+    run(Command::new("osascript").arg("-e").arg(script))
+}
+
+#[cfg(target_os = "linux")]
+fn notify(message: &str) -> Result<(), String> {
+    run(Command::new("notify-send")
+        .args(["--urgency", "critical", "--expire-time", "1000"])
+        // Replaces the previous second's notification on most daemons
+        .args(["--hint", "string:x-canonical-private-synchronous:autohands-countdown"])
+        .arg(TITLE)
+        .arg(message))
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn notify(_message: &str) -> Result<(), String> {
+    Err("no notification service on this platform".to_string())
+}
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn run(command: &mut Command) -> Result<(), String> {
+    let status = command
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|e| e.to_string())?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(status.to_string())
+    }
+}
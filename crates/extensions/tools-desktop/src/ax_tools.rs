@@ -0,0 +1,204 @@
+//! Accessibility tree tools.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use tracing::debug;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+
+use crate::accessibility::{AxController, AxQuery, AxTarget};
+
+// Helper to run blocking code
+async fn run_blocking<F, T>(f: F) -> Result<T, ToolError>
+where
+    F: FnOnce() -> Result<T, String> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?
+        .map_err(ToolError::ExecutionFailed)
+}
+
+/// Application picked by name or process ID, or the frontmost one.
+fn target(app: Option<String>, pid: Option<u32>) -> Result<AxTarget, ToolError> {
+    match (app, pid) {
+        (None, None) => Ok(AxTarget::Frontmost),
+        (Some(app), None) => Ok(AxTarget::Name(app)),
+        (None, Some(pid)) => Ok(AxTarget::Pid(pid)),
+        (Some(_), Some(_)) => Err(ToolError::ExecutionFailed(
+            "Invalid params: app and pid are exclusive".to_string(),
+        )),
+    }
+}
+
+// ============================================================================
+// Accessibility Tree Tool
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct AxTreeParams {
+    /// Application name; the frontmost application if neither it nor
+    /// `pid` is set.
+    pub app: Option<String>,
+    /// Application process ID.
+    pub pid: Option<u32>,
+    /// Levels of elements below the application to read.
+    #[serde(default = "default_max_depth")]
+    pub max_depth: usize,
+}
+
+fn default_max_depth() -> usize {
+    10
+}
+
+/// Read a native application's accessibility tree.
+pub struct AxTreeTool {
+    definition: ToolDefinition,
+}
+
+impl AxTreeTool {
+    pub fn new() -> Self {
+        Self {
+            definition: ToolDefinition::new(
+                "desktop_ax_tree",
+                "Desktop Accessibility Tree",
+                "Get the accessibility tree of a native application (the frontmost one by \
+                 default): each UI element's role, name, value and position",
+            )
+            .with_read_only(),
+        }
+    }
+}
+
+impl Default for AxTreeTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Tool for AxTreeTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        _ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: AxTreeParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::ExecutionFailed(format!("Invalid params: {}", e)))?;
+        let target = target(params.app, params.pid)?;
+        let max_depth = params.max_depth;
+
+        let tree = run_blocking(move || {
+            let controller = AxController::new().map_err(|e| e.to_string())?;
+            controller
+                .tree(&target, max_depth)
+                .map_err(|e| e.to_string())
+        })
+        .await?;
+
+        let count = tree.count();
+        debug!("Read {} accessibility elements", count);
+        Ok(ToolResult::success(tree.to_outline())
+            .with_metadata("elements", serde_json::json!(count)))
+    }
+}
+
+// ============================================================================
+// Accessibility Find Tool
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct AxFindParams {
+    /// Application name; the frontmost application if neither it nor
+    /// `pid` is set.
+    pub app: Option<String>,
+    /// Application process ID.
+    pub pid: Option<u32>,
+    /// Text in the element's role, such as "button".
+    pub role: Option<String>,
+    /// Text in the element's name, case-insensitively.
+    pub name: Option<String>,
+    #[serde(default = "default_max_results")]
+    pub max_results: usize,
+}
+
+fn default_max_results() -> usize {
+    20
+}
+
+/// Find UI elements of a native application by role and name.
+pub struct AxFindTool {
+    definition: ToolDefinition,
+}
+
+impl AxFindTool {
+    pub fn new() -> Self {
+        Self {
+            definition: ToolDefinition::new(
+                "desktop_ax_find",
+                "Desktop Accessibility Find",
+                "Find UI elements of a native application (the frontmost one by default) by \
+                 role and name through the accessibility tree, returning their positions \
+                 and centers in global coordinates",
+            )
+            .with_read_only(),
+        }
+    }
+}
+
+impl Default for AxFindTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Tool for AxFindTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        _ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: AxFindParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::ExecutionFailed(format!("Invalid params: {}", e)))?;
+        if params.role.is_none() && params.name.is_none() {
+            return Err(ToolError::ExecutionFailed(
+                "Invalid params: role or name is required".to_string(),
+            ));
+        }
+        let target = target(params.app, params.pid)?;
+        let query = AxQuery {
+            role: params.role,
+            name: params.name,
+        };
+        let limit = params.max_results;
+
+        let elements = run_blocking(move || {
+            let controller = AxController::new().map_err(|e| e.to_string())?;
+            controller
+                .find(&target, &query, limit)
+                .map_err(|e| e.to_string())
+        })
+        .await?;
+
+        let json = serde_json::to_string_pretty(&elements)
+            .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
+
+        debug!("Found {} accessibility elements", elements.len());
+        Ok(ToolResult::success(json).with_metadata("count", serde_json::json!(elements.len())))
+    }
+}
+
+#[cfg(test)]
+#[path = "ax_tools_tests.rs"]
+mod tests;
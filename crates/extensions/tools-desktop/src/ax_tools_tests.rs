@@ -0,0 +1,65 @@
+use super::*;
+
+#[test]
+fn test_ax_tree_tool_definition() {
+    let tool = AxTreeTool::new();
+    assert_eq!(tool.definition().id, "desktop_ax_tree");
+}
+
+#[test]
+fn test_ax_find_tool_definition() {
+    let tool = AxFindTool::new();
+    assert_eq!(tool.definition().id, "desktop_ax_find");
+}
+
+#[test]
+fn test_ax_tree_params_defaults() {
+    let params: AxTreeParams = serde_json::from_value(serde_json::json!({})).unwrap();
+    assert!(params.app.is_none());
+    assert!(params.pid.is_none());
+    assert_eq!(params.max_depth, 10);
+}
+
+#[test]
+fn test_ax_find_params() {
+    let json = serde_json::json!({
+        "app": "Notes",
+        "role": "button",
+        "name": "new",
+        "max_results": 5
+    });
+    let params: AxFindParams = serde_json::from_value(json).unwrap();
+    assert_eq!(params.app.as_deref(), Some("Notes"));
+    assert_eq!(params.role.as_deref(), Some("button"));
+    assert_eq!(params.name.as_deref(), Some("new"));
+    assert_eq!(params.max_results, 5);
+}
+
+#[test]
+fn test_target() {
+    assert_eq!(target(None, None).unwrap(), AxTarget::Frontmost);
+    assert_eq!(
+        target(Some("Notes".to_string()), None).unwrap(),
+        AxTarget::Name("Notes".to_string())
+    );
+    assert_eq!(target(None, Some(42)).unwrap(), AxTarget::Pid(42));
+    assert!(target(Some("Notes".to_string()), Some(42)).is_err());
+}
+
+#[tokio::test]
+async fn test_ax_find_requires_query() {
+    let tool = AxFindTool::new();
+    let result = tool
+        .execute(
+            serde_json::json!({}),
+            ToolContext::new("test", std::path::PathBuf::from("/tmp")),
+        )
+        .await;
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_tools_default_impl() {
+    let _ = AxTreeTool::default();
+    let _ = AxFindTool::default();
+}
@@ -7,15 +7,21 @@ use async_trait::async_trait;
 
 use autohands_protocols::error::ExtensionError;
 use autohands_protocols::extension::{Extension, ExtensionContext, ExtensionManifest, Provides};
-use autohands_protocols::types::Version;
+use autohands_protocols::types::{PrivacyMasks, Version};
 
+use crate::ax_tools::*;
+use crate::interlock::{set_input_limits, ActivityMonitor, InterlockConfig};
 use crate::ocr_tools::*;
+use crate::privacy::set_privacy_masks;
+use crate::recording;
+use crate::template_tools::*;
 use crate::tools::*;
 use crate::window_tools::*;
 
 /// Desktop tools extension for system-level automation.
 pub struct DesktopToolsExtension {
     manifest: ExtensionManifest,
+    monitor: Option<ActivityMonitor>,
 }
 
 impl DesktopToolsExtension {
@@ -26,13 +32,17 @@ impl DesktopToolsExtension {
             Version::new(0, 1, 0),
         );
         manifest.description =
-            "Desktop automation: mouse, keyboard, screenshot, clipboard, window management, OCR"
+            "Desktop automation: mouse, keyboard, screenshot, recording, clipboard, window \
+             management, OCR, image matching, accessibility"
                 .to_string();
         manifest.provides = Provides {
             tools: vec![
                 // Screenshot & screen info
                 "desktop_screenshot".to_string(),
                 "desktop_screen_info".to_string(),
+                // Screen recording
+                "desktop_record_start".to_string(),
+                "desktop_record_stop".to_string(),
                 // Mouse
                 "desktop_mouse_move".to_string(),
                 "desktop_mouse_click".to_string(),
@@ -56,11 +66,20 @@ impl DesktopToolsExtension {
                 "desktop_ocr_screen".to_string(),
                 "desktop_ocr_region".to_string(),
                 "desktop_ocr_image".to_string(),
+                // Image matching (2 tools)
+                "desktop_find_image".to_string(),
+                "desktop_click_image".to_string(),
+                // Accessibility (2 tools)
+                "desktop_ax_tree".to_string(),
+                "desktop_ax_find".to_string(),
             ],
             ..Default::default()
         };
 
-        Self { manifest }
+        Self {
+            manifest,
+            monitor: None,
+        }
     }
 }
 
@@ -82,6 +101,10 @@ impl Extension for DesktopToolsExtension {
             .register_tool(Arc::new(DesktopScreenshotTool::new()))?;
         ctx.tool_registry
             .register_tool(Arc::new(ScreenInfoTool::new()))?;
+        ctx.tool_registry
+            .register_tool(Arc::new(RecordStartTool::new()))?;
+        ctx.tool_registry
+            .register_tool(Arc::new(RecordStopTool::new()))?;
         ctx.tool_registry
             .register_tool(Arc::new(MouseMoveTool::new()))?;
         ctx.tool_registry
@@ -123,6 +146,52 @@ impl Extension for DesktopToolsExtension {
         ctx.tool_registry
             .register_tool(Arc::new(OcrImageTool::new()))?;
 
+        // Image matching tools (2)
+        ctx.tool_registry
+            .register_tool(Arc::new(FindImageTool::new()))?;
+        ctx.tool_registry
+            .register_tool(Arc::new(ClickImageTool::new()))?;
+
+        // Accessibility tools (2)
+        ctx.tool_registry
+            .register_tool(Arc::new(AxTreeTool::new()))?;
+        ctx.tool_registry
+            .register_tool(Arc::new(AxFindTool::new()))?;
+
+        // Regions and windows blacked out of screenshots
+        let masks = match ctx.config.get("privacy_masks") {
+            Some(masks) => serde_json::from_value(masks.clone()).map_err(|e| {
+                ExtensionError::InitializationFailed(format!("Invalid privacy_masks: {}", e))
+            })?,
+            None => PrivacyMasks::default(),
+        };
+        set_privacy_masks(masks);
+
+        // Safety interlock watching for the user taking the mouse back or
+        // pulling the kill switch, and pacing agent input
+        let interlock: InterlockConfig = if ctx.config.is_null() {
+            InterlockConfig::default()
+        } else {
+            serde_json::from_value(ctx.config.clone()).map_err(|e| {
+                ExtensionError::InitializationFailed(format!("Invalid tools-desktop config: {}", e))
+            })?
+        };
+        set_input_limits(&interlock);
+        if interlock.watches_cursor() {
+            self.monitor = Some(ActivityMonitor::start(interlock, ctx.tool_registry.clone()));
+        }
+
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> Result<(), ExtensionError> {
+        if let Some(monitor) = &self.monitor {
+            monitor.stop();
+        }
+        // Finish the file of a recording still running
+        if recording::is_recording() {
+            let _ = tokio::task::spawn_blocking(recording::stop_recording).await;
+        }
         Ok(())
     }
 
@@ -158,8 +227,8 @@ mod tests {
     #[test]
     fn test_tool_count() {
         let ext = DesktopToolsExtension::new();
-        // 10 original + 7 window + 3 OCR = 20
-        assert_eq!(ext.manifest().provides.tools.len(), 20);
+        // 10 original + 2 recording + 7 window + 3 OCR + 2 image + 2 accessibility = 26
+        assert_eq!(ext.manifest().provides.tools.len(), 26);
     }
 
     #[test]
@@ -203,6 +272,9 @@ mod tests {
         assert!(tools.contains(&"desktop_clipboard_get".to_string()));
         assert!(tools.contains(&"desktop_clipboard_set".to_string()));
         assert!(tools.contains(&"desktop_screen_info".to_string()));
+        // Screen recording (2)
+        assert!(tools.contains(&"desktop_record_start".to_string()));
+        assert!(tools.contains(&"desktop_record_stop".to_string()));
         // Window management (7)
         assert!(tools.contains(&"desktop_window_list".to_string()));
         assert!(tools.contains(&"desktop_window_focus".to_string()));
@@ -215,6 +287,12 @@ mod tests {
         assert!(tools.contains(&"desktop_ocr_screen".to_string()));
         assert!(tools.contains(&"desktop_ocr_region".to_string()));
         assert!(tools.contains(&"desktop_ocr_image".to_string()));
+        // Image matching (2)
+        assert!(tools.contains(&"desktop_find_image".to_string()));
+        assert!(tools.contains(&"desktop_click_image".to_string()));
+        // Accessibility (2)
+        assert!(tools.contains(&"desktop_ax_tree".to_string()));
+        assert!(tools.contains(&"desktop_ax_find".to_string()));
     }
 
     #[test]
@@ -68,6 +68,13 @@ impl InputController {
             .map_err(|e| InputError::Failed(e.to_string()))
     }
 
+    /// Current cursor position.
+    pub fn cursor_position(&self) -> Result<(i32, i32), InputError> {
+        self.enigo
+            .location()
+            .map_err(|e| InputError::Failed(e.to_string()))
+    }
+
     /// Click mouse button.
     pub fn mouse_click(&mut self, button: MouseButton) -> Result<(), InputError> {
         self.enigo
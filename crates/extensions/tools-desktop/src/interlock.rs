@@ -0,0 +1,389 @@
+//! Safety interlock between agents and the person using the desktop.
+//!
+//! The mouse and keyboard tools hold the [`INPUT_RESOURCE`] exclusive
+//! resource, so suspending it stops all of them at once: from the API
+//! (`POST /admin/resources/desktop-input/suspend`), from the
+//! `autohands desktop suspend` command (which a global hotkey or menu-bar
+//! item can run), or by the [`ActivityMonitor`] when the cursor moves
+//! without an agent moving it, i.e. when someone takes the mouse back.
+//! Calls in progress finish; the next ones fail until the resource is
+//! resumed.
+//!
+//! Aborting the resource is the kill switch: the agent runs using the input
+//! tools are cancelled, the one whose call is in progress at once. It is
+//! pulled from the API (`POST /admin/resources/desktop-input/abort`), from
+//! `autohands desktop abort` (for a global panic hotkey), or by the monitor
+//! when the user moves the mouse into the configured abort corner.
+//!
+//! Agent input itself can be rate limited, and announced by a countdown
+//! before each burst, during which moving the mouse cancels it.
+//!
+//! Configured under `[extensions.config.tools-desktop]`, see
+//! [`InterlockConfig`]. Only cursor movement is detected; keystrokes of
+//! the user are not.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use autohands_protocols::extension::ToolRegistryAccess;
+
+use crate::announce;
+use crate::input::{InputController, InputError};
+use crate::screenshot::{list_monitors, MonitorInfo};
+use crate::window::WindowController;
+
+/// Resource held by the mouse and keyboard tools.
+pub const INPUT_RESOURCE: &str = "desktop-input";
+
+/// Cursor movement this soon after an agent's input is attributed to it.
+const AGENT_INPUT_GRACE: Duration = Duration::from_millis(500);
+
+/// Agent input after this long a pause starts a new burst, before which
+/// the focused window is recorded.
+const AGENT_BURST_GAP: Duration = Duration::from_secs(5);
+
+/// Distance from a monitor's corner, in pixels, within which the cursor
+/// counts as in the corner.
+const CORNER_SIZE: i64 = 4;
+
+/// How often the cursor is sampled during a countdown.
+const COUNTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Interlock settings.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct InterlockConfig {
+    /// Suspend the input tools when the user moves the mouse.
+    pub pause_on_user_input: bool,
+    /// How often the cursor is sampled, in milliseconds.
+    pub poll_interval_ms: u64,
+    /// On suspension, focus the window the user had before the agent's
+    /// latest burst of input.
+    pub restore_focus: bool,
+    /// Abort the input tools, cancelling the runs using them, when the
+    /// user moves the mouse into this corner of a monitor.
+    pub abort_corner: Option<ScreenCorner>,
+    /// Most agent input actions (a move, click, key or text) per second;
+    /// unlimited if 0.
+    pub max_actions_per_second: u32,
+    /// Seconds of countdown announcing an agent's burst of input, during
+    /// which moving the mouse cancels it; no countdown if 0.
+    pub announce_secs: u64,
+}
+
+impl Default for InterlockConfig {
+    fn default() -> Self {
+        Self {
+            pause_on_user_input: true,
+            poll_interval_ms: 250,
+            restore_focus: false,
+            abort_corner: None,
+            max_actions_per_second: 0,
+            announce_secs: 0,
+        }
+    }
+}
+
+impl InterlockConfig {
+    /// Whether the [`ActivityMonitor`] has anything to watch for.
+    pub fn watches_cursor(&self) -> bool {
+        self.pause_on_user_input || self.restore_focus || self.abort_corner.is_some()
+    }
+}
+
+/// Corner of a monitor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScreenCorner {
+    /// Any of the four.
+    Any,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl ScreenCorner {
+    /// Whether a point in global coordinates is in this corner of a
+    /// monitor.
+    pub fn contains(&self, monitor: &MonitorInfo, x: i32, y: i32) -> bool {
+        if !monitor.contains(x, y) {
+            return false;
+        }
+        let dx = i64::from(x) - i64::from(monitor.x);
+        let dy = i64::from(y) - i64::from(monitor.y);
+        let left = dx < CORNER_SIZE;
+        let top = dy < CORNER_SIZE;
+        let right = i64::from(monitor.width) - dx <= CORNER_SIZE;
+        let bottom = i64::from(monitor.height) - dy <= CORNER_SIZE;
+        match self {
+            Self::Any => (left || right) && (top || bottom),
+            Self::TopLeft => top && left,
+            Self::TopRight => top && right,
+            Self::BottomLeft => bottom && left,
+            Self::BottomRight => bottom && right,
+        }
+    }
+}
+
+/// Spaces agent input actions out to a maximum rate.
+#[derive(Debug)]
+struct RateLimiter {
+    /// Time between two actions; unlimited if unset.
+    interval: Option<Duration>,
+    /// When the next action may run.
+    next: Option<Instant>,
+}
+
+impl RateLimiter {
+    const fn new() -> Self {
+        Self {
+            interval: None,
+            next: None,
+        }
+    }
+
+    fn set_rate(&mut self, per_second: u32) {
+        self.interval = (per_second > 0).then(|| Duration::from_secs(1) / per_second);
+        self.next = None;
+    }
+
+    /// Reserve the next slot for an action wanted at `now`; returns how
+    /// long to wait for it.
+    fn reserve(&mut self, now: Instant) -> Duration {
+        let Some(interval) = self.interval else {
+            return Duration::ZERO;
+        };
+        let at = self.next.map_or(now, |next| next.max(now));
+        self.next = Some(at + interval);
+        at - now
+    }
+}
+
+/// The latest input of an agent.
+#[derive(Debug, Default)]
+struct AgentInput {
+    /// When it ended.
+    at: Option<Instant>,
+    /// Where it left the cursor.
+    cursor: Option<(i32, i32)>,
+    /// Window focused before the burst it belongs to.
+    user_window: Option<u64>,
+}
+
+impl AgentInput {
+    const fn new() -> Self {
+        Self {
+            at: None,
+            cursor: None,
+            user_window: None,
+        }
+    }
+}
+
+static AGENT_INPUT: Mutex<AgentInput> = parking_lot::const_mutex(AgentInput::new());
+static RESTORE_FOCUS: AtomicBool = AtomicBool::new(false);
+static ANNOUNCE_SECS: AtomicU64 = AtomicU64::new(0);
+static RATE_LIMIT: Mutex<RateLimiter> = parking_lot::const_mutex(RateLimiter::new());
+
+/// Apply the settings of agent input: its rate limit and countdown.
+pub fn set_input_limits(config: &InterlockConfig) {
+    RATE_LIMIT.lock().set_rate(config.max_actions_per_second);
+    ANNOUNCE_SECS.store(config.announce_secs, Ordering::Relaxed);
+}
+
+/// Run input of an agent, recording it so the monitor does not take it
+/// for the user's.
+///
+/// Waits for the rate limit, and before a new burst of input counts down
+/// if configured, failing if the user moves the mouse meanwhile.
+pub(crate) fn agent_input<T>(
+    f: impl FnOnce(&mut InputController) -> Result<T, InputError>,
+) -> Result<T, String> {
+    let restore_focus = RESTORE_FOCUS.load(Ordering::Relaxed);
+    let announce_secs = ANNOUNCE_SECS.load(Ordering::Relaxed);
+    if restore_focus || announce_secs > 0 {
+        let starts_burst = AGENT_INPUT
+            .lock()
+            .at
+            .is_none_or(|at| at.elapsed() > AGENT_BURST_GAP);
+        if starts_burst && restore_focus {
+            let focused = focused_window();
+            AGENT_INPUT.lock().user_window = focused;
+        }
+        if starts_burst && announce_secs > 0 {
+            count_down(announce_secs)?;
+        }
+    }
+
+    let wait = RATE_LIMIT.lock().reserve(Instant::now());
+    if !wait.is_zero() {
+        thread::sleep(wait);
+    }
+
+    let mut controller = InputController::new().map_err(|e| e.to_string())?;
+    let result = f(&mut controller);
+    let mut input = AGENT_INPUT.lock();
+    input.at = Some(Instant::now());
+    input.cursor = controller.cursor_position().ok();
+    result.map_err(|e| e.to_string())
+}
+
+/// Announce agent input `secs` seconds ahead, failing if the cursor moves
+/// meanwhile.
+fn count_down(secs: u64) -> Result<(), String> {
+    let controller = InputController::new().map_err(|e| e.to_string())?;
+    let start = controller.cursor_position().ok();
+    for left in (1..=secs).rev() {
+        announce::show_countdown(left);
+        let second = Instant::now() + Duration::from_secs(1);
+        while Instant::now() < second {
+            thread::sleep(COUNTDOWN_POLL_INTERVAL);
+            if controller.cursor_position().ok() != start {
+                info!("Agent input cancelled during its countdown");
+                return Err("Desktop input cancelled by the user during its countdown".to_string());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn focused_window() -> Option<u64> {
+    let windows = WindowController::new().ok()?.list_windows().ok()?;
+    windows.into_iter().find(|w| w.is_focused).map(|w| w.id)
+}
+
+/// Whether the cursor moving from `previous` to `current` was not the
+/// work of an agent.
+fn moved_by_user(
+    previous: (i32, i32),
+    current: (i32, i32),
+    agent: &AgentInput,
+    now: Instant,
+) -> bool {
+    if previous == current {
+        return false;
+    }
+    let agent_just_acted = agent
+        .at
+        .is_some_and(|at| now.saturating_duration_since(at) <= AGENT_INPUT_GRACE);
+    !agent_just_acted && agent.cursor != Some(current)
+}
+
+/// Watches the cursor for user activity on a background thread.
+pub struct ActivityMonitor {
+    stop: Arc<AtomicBool>,
+    handle: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl ActivityMonitor {
+    /// Start watching; suspensions go through `tools`.
+    pub fn start(config: InterlockConfig, tools: Arc<dyn ToolRegistryAccess>) -> Self {
+        RESTORE_FOCUS.store(config.restore_focus, Ordering::Relaxed);
+        let stop = Arc::new(AtomicBool::new(false));
+        let watching = stop.clone();
+        let handle = match thread::Builder::new()
+            .name("desktop-interlock".to_string())
+            .spawn(move || watch(config, tools, watching))
+        {
+            Ok(handle) => Some(handle),
+            Err(e) => {
+                warn!("Failed to start the desktop activity monitor: {}", e);
+                None
+            }
+        };
+        Self {
+            stop,
+            handle: Mutex::new(handle),
+        }
+    }
+
+    /// Stop watching.
+    pub fn stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.lock().take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for ActivityMonitor {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+fn watch(config: InterlockConfig, tools: Arc<dyn ToolRegistryAccess>, stop: Arc<AtomicBool>) {
+    let controller = match InputController::new() {
+        Ok(controller) => controller,
+        Err(e) => {
+            info!("Desktop activity monitor not running: {}", e);
+            return;
+        }
+    };
+    let interval = Duration::from_millis(config.poll_interval_ms.max(50));
+    let mut previous = controller.cursor_position().ok();
+    let mut suspended = tools.is_resource_suspended(INPUT_RESOURCE);
+    let mut aborted = false;
+
+    while !stop.load(Ordering::Relaxed) {
+        thread::sleep(interval);
+        let current = controller.cursor_position().ok();
+        if let (Some(previous), Some(current)) = (previous, current) {
+            let user = moved_by_user(previous, current, &AGENT_INPUT.lock(), Instant::now());
+            let cornered = user
+                && !aborted
+                && config
+                    .abort_corner
+                    .is_some_and(|corner| in_corner(corner, current));
+            if cornered {
+                tools.abort_resource(INPUT_RESOURCE, "mouse moved into the abort corner");
+                aborted = true;
+            }
+            if user && config.pause_on_user_input && !suspended {
+                tools.suspend_resource(INPUT_RESOURCE, "user input detected");
+            }
+        }
+        previous = current;
+
+        let now_suspended = tools.is_resource_suspended(INPUT_RESOURCE);
+        if now_suspended && !suspended && config.restore_focus {
+            restore_focus();
+        }
+        suspended = now_suspended;
+        // Armed again once resumed
+        aborted &= suspended;
+    }
+}
+
+/// Whether the cursor is in a corner of the monitor it is on.
+fn in_corner(corner: ScreenCorner, (x, y): (i32, i32)) -> bool {
+    match list_monitors() {
+        Ok(monitors) => monitors.iter().any(|m| corner.contains(m, x, y)),
+        Err(e) => {
+            warn!("Failed to list monitors for the abort corner: {}", e);
+            false
+        }
+    }
+}
+
+/// Focus the window the user had before the agent's latest input.
+fn restore_focus() {
+    let Some(window) = AGENT_INPUT.lock().user_window.take() else {
+        return;
+    };
+    match WindowController::new().and_then(|controller| controller.focus_window(window)) {
+        Ok(()) => info!("Focus given back to window {}", window),
+        Err(e) => warn!("Failed to give focus back to window {}: {}", window, e),
+    }
+}
+
+#[cfg(test)]
+#[path = "interlock_tests.rs"]
+mod tests;
@@ -0,0 +1,120 @@
+use super::*;
+
+#[test]
+fn test_config_defaults() {
+    let config: InterlockConfig = serde_json::from_value(serde_json::json!({})).unwrap();
+    assert!(config.pause_on_user_input);
+    assert_eq!(config.poll_interval_ms, 250);
+    assert!(!config.restore_focus);
+
+    assert_eq!(config.abort_corner, None);
+    assert_eq!(config.max_actions_per_second, 0);
+    assert_eq!(config.announce_secs, 0);
+
+    let config: InterlockConfig =
+        serde_json::from_value(serde_json::json!({"restore_focus": true})).unwrap();
+    assert!(config.restore_focus);
+}
+
+#[test]
+fn test_config_safety_settings() {
+    let config: InterlockConfig = serde_json::from_value(serde_json::json!({
+        "pause_on_user_input": false,
+        "abort_corner": "top_left",
+        "max_actions_per_second": 10,
+        "announce_secs": 3
+    }))
+    .unwrap();
+    assert_eq!(config.abort_corner, Some(ScreenCorner::TopLeft));
+    assert_eq!(config.max_actions_per_second, 10);
+    assert_eq!(config.announce_secs, 3);
+    // The abort corner alone needs the monitor
+    assert!(config.watches_cursor());
+
+    let config: InterlockConfig =
+        serde_json::from_value(serde_json::json!({"pause_on_user_input": false})).unwrap();
+    assert!(!config.watches_cursor());
+}
+
+#[test]
+fn test_screen_corner() {
+    // Secondary monitor left of the primary one
+    let monitor = MonitorInfo {
+        index: 1,
+        id: 2,
+        name: "left".to_string(),
+        x: -1280,
+        y: 0,
+        width: 1280,
+        height: 1024,
+        is_primary: false,
+        scale_factor: 1.0,
+    };
+    assert!(ScreenCorner::TopLeft.contains(&monitor, -1280, 0));
+    assert!(ScreenCorner::TopLeft.contains(&monitor, -1277, 3));
+    assert!(!ScreenCorner::TopLeft.contains(&monitor, -1276, 0));
+    assert!(ScreenCorner::BottomRight.contains(&monitor, -1, 1023));
+    assert!(!ScreenCorner::BottomRight.contains(&monitor, -1, 1019));
+    assert!(!ScreenCorner::TopRight.contains(&monitor, -1280, 0));
+    // Off the monitor
+    assert!(!ScreenCorner::TopRight.contains(&monitor, 0, 0));
+
+    assert!(ScreenCorner::Any.contains(&monitor, -1, 0));
+    assert!(ScreenCorner::Any.contains(&monitor, -1280, 1023));
+    assert!(!ScreenCorner::Any.contains(&monitor, -640, 0));
+}
+
+#[test]
+fn test_rate_limiter() {
+    let now = Instant::now();
+    let mut unlimited = RateLimiter::new();
+    assert_eq!(unlimited.reserve(now), Duration::ZERO);
+    assert_eq!(unlimited.reserve(now), Duration::ZERO);
+
+    let mut limiter = RateLimiter::new();
+    limiter.set_rate(4);
+    assert_eq!(limiter.reserve(now), Duration::ZERO);
+    assert_eq!(limiter.reserve(now), Duration::from_millis(250));
+    assert_eq!(limiter.reserve(now), Duration::from_millis(500));
+    // Slots left unused are not saved up
+    let later = now + Duration::from_secs(5);
+    assert_eq!(limiter.reserve(later), Duration::ZERO);
+    assert_eq!(
+        limiter.reserve(later + Duration::from_millis(100)),
+        Duration::from_millis(150)
+    );
+
+    limiter.set_rate(0);
+    assert_eq!(limiter.reserve(later), Duration::ZERO);
+}
+
+#[test]
+fn test_countdown_message() {
+    assert!(crate::announce::countdown_message(3).contains("in 3 s"));
+}
+
+#[test]
+fn test_cursor_moved_by_user() {
+    let now = Instant::now();
+    let idle = AgentInput::new();
+    assert!(moved_by_user((0, 0), (10, 0), &idle, now));
+    assert!(!moved_by_user((10, 0), (10, 0), &idle, now));
+}
+
+#[test]
+fn test_cursor_moved_by_agent() {
+    let now = Instant::now();
+    let agent = AgentInput {
+        at: Some(now - Duration::from_millis(100)),
+        cursor: Some((40, 40)),
+        user_window: None,
+    };
+    // Just after the agent's input, wherever the cursor went
+    assert!(!moved_by_user((0, 0), (40, 40), &agent, now));
+    assert!(!moved_by_user((0, 0), (41, 40), &agent, now));
+
+    // Later, only where the agent left it
+    let later = now + Duration::from_secs(2);
+    assert!(!moved_by_user((0, 0), (40, 40), &agent, later));
+    assert!(moved_by_user((40, 40), (41, 40), &agent, later));
+}
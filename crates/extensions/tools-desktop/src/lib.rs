@@ -6,6 +6,18 @@
 //! - `desktop_screenshot` - Capture full screen or region
 //! - `desktop_screen_info` - Get monitor information
 //!
+//! Configured regions and windows are blacked out of every capture, see
+//! [`set_privacy_masks`].
+//!
+//! ## Screen Recording
+//! - `desktop_record_start` - Start recording the screen to a GIF or MP4
+//! - `desktop_record_stop` - Stop recording and get the file
+//!
+//! On multi-monitor setups, coordinates are global virtual-desktop ones. The
+//! screenshot, mouse and window move tools also take a `monitor` index from
+//! `desktop_screen_info`, capturing that monitor and taking positions relative
+//! to its top-left corner.
+//!
 //! ## Mouse Control
 //! - `desktop_mouse_move` - Move cursor to position
 //! - `desktop_mouse_click` - Click mouse button
@@ -16,6 +28,12 @@
 //! - `desktop_keyboard_key` - Press a single key
 //! - `desktop_keyboard_hotkey` - Press key combination
 //!
+//! The mouse and keyboard tools are refused while the `desktop-input`
+//! resource is suspended, from the API or by the [`ActivityMonitor`] when
+//! the user moves the mouse. Aborting it, e.g. with the mouse in the abort
+//! corner, also cancels the agent runs using them. Their input can be rate
+//! limited and announced by a countdown, see [`InterlockConfig`].
+//!
 //! ## Clipboard
 //! - `desktop_clipboard_get` - Get clipboard content
 //! - `desktop_clipboard_set` - Set clipboard content
@@ -33,26 +51,54 @@
 //! - `desktop_ocr_screen` - Recognize text from the entire screen
 //! - `desktop_ocr_region` - Recognize text from a specific region
 //! - `desktop_ocr_image` - Recognize text from a base64 encoded image
+//!
+//! ## Accessibility
+//! - `desktop_ax_tree` - Get a native application's accessibility tree
+//! - `desktop_ax_find` - Find UI elements by role and name
+//!
+//! ## Image Matching
+//! - `desktop_find_image` - Find a template image, such as an icon, on screen
+//! - `desktop_click_image` - Click a template image found on screen
 
+mod accessibility;
+mod announce;
+mod ax_tools;
 mod clipboard;
 mod extension;
 mod input;
+mod interlock;
 mod ocr;
 mod ocr_tools;
+mod privacy;
+mod recording;
 mod screenshot;
+mod template;
+mod template_tools;
 mod tools;
 mod window;
 mod window_tools;
 
+pub use accessibility::{AxController, AxElement, AxError, AxNode, AxQuery, AxTarget};
+pub use ax_tools::*;
 pub use clipboard::{ClipboardController, ClipboardError};
 pub use extension::DesktopToolsExtension;
 pub use input::{InputController, InputError, MouseButton};
+pub use interlock::{
+    set_input_limits, ActivityMonitor, InterlockConfig, ScreenCorner, INPUT_RESOURCE,
+};
 pub use ocr::{OcrController, OcrError, OcrResult, TextBlock};
 pub use ocr_tools::*;
+pub use privacy::set_privacy_masks;
+pub use recording::{
+    is_recording, start_recording, stop_recording, RecordingError, RecordingFormat, RecordingInfo,
+    RecordingOptions,
+};
 pub use screenshot::{
-    capture_region, capture_screen, get_screen_size, list_monitors, MonitorInfo, Screenshot,
-    ScreenshotError,
+    capture_monitor, capture_monitor_region, capture_region, capture_screen, get_screen_size,
+    list_monitors, to_global, MonitorInfo, Screenshot, ScreenshotError,
 };
+pub use template::{find_on_screen, match_template, ImageMatch, TemplateError, TemplateMatch};
+pub use template_tools::*;
 pub use tools::*;
 pub use window::{WindowController, WindowError, WindowInfo};
 pub use window_tools::*;
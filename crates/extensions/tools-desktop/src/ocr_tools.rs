@@ -37,7 +37,8 @@ impl OcrScreenTool {
                 "desktop_ocr_screen",
                 "Desktop OCR Screen",
                 "Recognize text from the entire screen using OCR",
-            ),
+            )
+            .with_read_only(),
         }
     }
 }
@@ -106,7 +107,8 @@ impl OcrRegionTool {
                 "desktop_ocr_region",
                 "Desktop OCR Region",
                 "Recognize text from a specific region of the screen using OCR",
-            ),
+            )
+            .with_read_only(),
         }
     }
 }
@@ -183,7 +185,8 @@ impl OcrImageTool {
                 "desktop_ocr_image",
                 "Desktop OCR Image",
                 "Recognize text from a base64 encoded image using OCR",
-            ),
+            )
+            .with_read_only(),
         }
     }
 }
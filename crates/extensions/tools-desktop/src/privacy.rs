@@ -0,0 +1,90 @@
+//! Privacy masks of desktop screenshots.
+//!
+//! Every capture, including those taken for OCR, has the configured
+//! regions and the windows with a masked title blacked out before it is
+//! encoded, so they never reach a provider, a transcript or an artifact.
+//! Windows are masked over their whole frame, even where other windows
+//! cover them. If the windows cannot be listed, the whole capture is
+//! blacked out.
+
+use parking_lot::RwLock;
+use screenshots::image::{Rgba, RgbaImage};
+use tracing::warn;
+
+use autohands_protocols::types::{MaskRegion, PrivacyMasks};
+
+use crate::window::{WindowController, WindowInfo};
+
+static PRIVACY_MASKS: RwLock<PrivacyMasks> = parking_lot::const_rwlock(PrivacyMasks {
+    regions: Vec::new(),
+    window_titles: Vec::new(),
+});
+
+/// Set the masks applied to screenshots.
+pub fn set_privacy_masks(masks: PrivacyMasks) {
+    *PRIVACY_MASKS.write() = masks;
+}
+
+/// Black out the masked parts of `image`, a capture of `area` of the
+/// screen.
+pub(crate) fn apply_privacy_masks(image: &mut RgbaImage, area: MaskRegion) {
+    let masks = PRIVACY_MASKS.read().clone();
+    if masks.is_empty() {
+        return;
+    }
+    let windows = if masks.window_titles.is_empty() {
+        Vec::new()
+    } else {
+        match WindowController::new().and_then(|controller| controller.list_windows()) {
+            Ok(windows) => windows,
+            Err(e) => {
+                warn!("Cannot list windows to mask, blacking out the screenshot: {}", e);
+                black_out(image, area, MaskRegion::new(0, 0, area.width, area.height));
+                return;
+            }
+        }
+    };
+    for region in masked_regions(&masks, &windows, area) {
+        black_out(image, area, region);
+    }
+}
+
+/// Masked parts of `area`, relative to it.
+fn masked_regions(
+    masks: &PrivacyMasks,
+    windows: &[WindowInfo],
+    area: MaskRegion,
+) -> Vec<MaskRegion> {
+    let windows = windows
+        .iter()
+        .filter(|w| !w.is_minimized && masks.masks_title(&w.title))
+        .map(|w| MaskRegion::new(w.x, w.y, w.width, w.height));
+    masks
+        .regions
+        .iter()
+        .copied()
+        .chain(windows)
+        .filter_map(|region| region.within(&area))
+        .collect()
+}
+
+/// Fill `region` of `image` with black. The image may have more pixels
+/// than `area` has points, on scaled displays.
+fn black_out(image: &mut RgbaImage, area: MaskRegion, region: MaskRegion) {
+    let scale_x = image.width() as f64 / area.width.max(1) as f64;
+    let scale_y = image.height() as f64 / area.height.max(1) as f64;
+    let left = (region.x as f64 * scale_x).floor() as u32;
+    let top = (region.y as f64 * scale_y).floor() as u32;
+    let right = ((region.x as f64 + region.width as f64) * scale_x).ceil() as u32;
+    let bottom = ((region.y as f64 + region.height as f64) * scale_y).ceil() as u32;
+    let (right, bottom) = (right.min(image.width()), bottom.min(image.height()));
+    for y in top..bottom {
+        for x in left..right {
+            image.put_pixel(x, y, Rgba([0, 0, 0, 255]));
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "privacy_tests.rs"]
+mod tests;
@@ -0,0 +1,55 @@
+use super::*;
+
+fn window(title: &str, x: i32, y: i32, is_minimized: bool) -> WindowInfo {
+    WindowInfo {
+        id: 1,
+        title: title.to_string(),
+        app_name: String::new(),
+        pid: 0,
+        x,
+        y,
+        width: 100,
+        height: 100,
+        is_minimized,
+        is_maximized: false,
+        is_focused: false,
+    }
+}
+
+#[test]
+fn test_masked_regions() {
+    let masks = PrivacyMasks {
+        regions: vec![MaskRegion::new(0, 0, 50, 20)],
+        window_titles: vec!["1password".to_string()],
+    };
+    let windows = vec![
+        window("Vault - 1Password", 500, 300, false),
+        window("1Password", 0, 0, true),
+        window("Terminal", 0, 0, false),
+    ];
+
+    let screen = MaskRegion::new(0, 0, 1920, 1080);
+    assert_eq!(
+        masked_regions(&masks, &windows, screen),
+        vec![MaskRegion::new(0, 0, 50, 20), MaskRegion::new(500, 300, 100, 100)]
+    );
+
+    // Region captures get the masks relative to the region
+    let area = MaskRegion::new(550, 350, 200, 200);
+    assert_eq!(
+        masked_regions(&masks, &windows, area),
+        vec![MaskRegion::new(0, 0, 50, 50)]
+    );
+}
+
+#[test]
+fn test_black_out_scaled_capture() {
+    // A 2x capture of a 10x10 area
+    let mut image = RgbaImage::from_pixel(20, 20, Rgba([255, 255, 255, 255]));
+    let area = MaskRegion::new(0, 0, 10, 10);
+    black_out(&mut image, area, MaskRegion::new(5, 5, 10, 10));
+
+    assert_eq!(*image.get_pixel(9, 9), Rgba([255, 255, 255, 255]));
+    assert_eq!(*image.get_pixel(10, 10), Rgba([0, 0, 0, 255]));
+    assert_eq!(*image.get_pixel(19, 19), Rgba([0, 0, 0, 255]));
+}
@@ -0,0 +1,454 @@
+//! Screen recording.
+//!
+//! A background thread captures the primary screen, a monitor or a region
+//! at a set frame rate, with the privacy masks applied, and encodes frames
+//! as they come: GIFs in process, MP4s by piping raw frames to `ffmpeg`.
+//! One recording runs at a time. It ends when stopped or after its maximum
+//! duration, and its file is complete either way.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use screenshots::image::codecs::gif::{GifEncoder, Repeat};
+use screenshots::image::imageops::{self, FilterType};
+use screenshots::image::{Delay, Frame, RgbaImage};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{info, warn};
+
+use crate::screenshot::{self, ScreenshotError};
+
+/// Highest frame rate.
+pub const MAX_FPS: u32 = 30;
+
+/// Longest recording.
+pub const MAX_DURATION: Duration = Duration::from_secs(600);
+
+/// Widest GIF frame; wider captures are scaled down, as GIF encoding is
+/// slow and its files large.
+const GIF_MAX_WIDTH: u32 = 960;
+
+/// GIF quantization speed, from 1 (best quality) to 30 (fastest).
+const GIF_SPEED: i32 = 10;
+
+/// Recording errors.
+#[derive(Debug, Error)]
+pub enum RecordingError {
+    #[error("A recording is already in progress")]
+    AlreadyRecording,
+
+    #[error("No recording in progress")]
+    NotRecording,
+
+    #[error("Encoder not available: {0}")]
+    EncoderNotAvailable(String),
+
+    #[error("Encoding failed: {0}")]
+    EncodingFailed(String),
+
+    #[error(transparent)]
+    Screenshot(#[from] ScreenshotError),
+}
+
+/// Format of a recording.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecordingFormat {
+    /// Animated GIF, encoded in process.
+    #[default]
+    Gif,
+    /// H.264 MP4, encoded by `ffmpeg`.
+    Mp4,
+}
+
+impl RecordingFormat {
+    /// File extension.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Gif => "gif",
+            Self::Mp4 => "mp4",
+        }
+    }
+}
+
+/// What and how to record.
+#[derive(Debug, Clone)]
+pub struct RecordingOptions {
+    pub format: RecordingFormat,
+    /// Frames per second, up to [`MAX_FPS`].
+    pub fps: u32,
+    /// Duration after which the recording stops by itself, up to
+    /// [`MAX_DURATION`].
+    pub max_duration: Duration,
+    /// Monitor to record, by index in [`screenshot::list_monitors`]; the
+    /// region is then relative to its top-left corner. Defaults to the
+    /// primary monitor, with the region in global coordinates.
+    pub monitor: Option<usize>,
+    /// Region to record as `(x, y, width, height)`; the whole monitor if
+    /// `None`.
+    pub region: Option<(i32, i32, u32, u32)>,
+    /// File to write.
+    pub path: PathBuf,
+}
+
+/// A finished recording.
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingInfo {
+    pub path: PathBuf,
+    pub format: RecordingFormat,
+    pub frames: u64,
+    pub duration_ms: u64,
+    /// Frame width, after any scaling.
+    pub width: u32,
+    /// Frame height, after any scaling.
+    pub height: u32,
+}
+
+struct Recording {
+    stop: Arc<AtomicBool>,
+    handle: JoinHandle<Result<RecordingInfo, RecordingError>>,
+}
+
+static RECORDING: Mutex<Option<Recording>> = parking_lot::const_mutex(None);
+
+/// Start recording in the background.
+///
+/// Fails if a recording is in progress. A recording that reached its
+/// maximum duration but was not stopped is discarded, its file kept.
+pub fn start_recording(options: RecordingOptions) -> Result<(), RecordingError> {
+    let mut recording = RECORDING.lock();
+    if recording.as_ref().is_some_and(|r| !r.handle.is_finished()) {
+        return Err(RecordingError::AlreadyRecording);
+    }
+
+    // Capture and encoder errors surface here rather than on stop
+    let source = Source::new(options.monitor, options.region)?;
+    let first = source.capture()?;
+    let fps = options.fps.clamp(1, MAX_FPS);
+    let encoder = Encoder::new(options.format, &options.path, first.dimensions(), fps)?;
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let max_duration = options.max_duration.min(MAX_DURATION);
+    let handle = {
+        let stop = stop.clone();
+        thread::spawn(move || {
+            let result = record(&source, first, encoder, fps, max_duration, &stop);
+            match &result {
+                Ok(info) => info!("Recorded {} frames to {}", info.frames, info.path.display()),
+                Err(e) => warn!("Recording failed: {}", e),
+            }
+            result
+        })
+    };
+    *recording = Some(Recording { stop, handle });
+    Ok(())
+}
+
+/// Stop the recording, waiting for its file to be finished.
+pub fn stop_recording() -> Result<RecordingInfo, RecordingError> {
+    let recording = RECORDING
+        .lock()
+        .take()
+        .ok_or(RecordingError::NotRecording)?;
+    recording.stop.store(true, Ordering::Relaxed);
+    recording
+        .handle
+        .join()
+        .map_err(|_| RecordingError::EncodingFailed("recording thread panicked".to_string()))?
+}
+
+/// Whether a recording is running.
+pub fn is_recording() -> bool {
+    RECORDING
+        .lock()
+        .as_ref()
+        .is_some_and(|r| !r.handle.is_finished())
+}
+
+/// Capture frames until stopped or `max_duration` has passed.
+fn record(
+    source: &Source,
+    first: RgbaImage,
+    mut encoder: Encoder,
+    fps: u32,
+    max_duration: Duration,
+    stop: &AtomicBool,
+) -> Result<RecordingInfo, RecordingError> {
+    let interval = Duration::from_secs(1) / fps;
+    let started = Instant::now();
+    let mut frame = first;
+    let mut at = Duration::ZERO;
+    let mut frames: u32 = 0;
+    loop {
+        encoder.push(frame, at)?;
+        frames += 1;
+
+        let next = interval * frames;
+        if next >= max_duration {
+            break;
+        }
+        // Stopping takes at most one interval
+        if let Some(wait) = next.checked_sub(started.elapsed()) {
+            thread::sleep(wait);
+        }
+        if stop.load(Ordering::Relaxed) {
+            break;
+        }
+
+        at = started.elapsed();
+        frame = match source.capture() {
+            Ok(frame) => frame,
+            Err(e) => {
+                warn!("Recording stopped, capture failed: {}", e);
+                break;
+            }
+        };
+    }
+
+    let duration = started.elapsed().min(max_duration);
+    encoder.finish(duration)
+}
+
+/// Area of the screen recorded.
+struct Source {
+    /// Index of the monitor, looked up again for each frame.
+    monitor: usize,
+    /// Region relative to the monitor's top-left corner.
+    region: Option<(i32, i32, u32, u32)>,
+}
+
+impl Source {
+    fn new(
+        monitor: Option<usize>,
+        region: Option<(i32, i32, u32, u32)>,
+    ) -> Result<Self, ScreenshotError> {
+        let (monitor, region) = match (region, monitor) {
+            (Some((x, y, width, height)), None) => {
+                let (index, _, x, y) = screenshot::screen_at(x, y)?;
+                (index, Some((x, y, width, height)))
+            }
+            (region, monitor) => (screenshot::find_screen(monitor)?.0, region),
+        };
+        Ok(Self { monitor, region })
+    }
+
+    fn capture(&self) -> Result<RgbaImage, ScreenshotError> {
+        let (_, screen) = screenshot::find_screen(Some(self.monitor))?;
+        match self.region {
+            Some((x, y, width, height)) => {
+                screenshot::capture_area_image(&screen, x, y, width, height)
+            }
+            None => screenshot::capture_image(&screen),
+        }
+    }
+}
+
+/// Encodes frames into a file as they are captured.
+pub(crate) struct Encoder {
+    path: PathBuf,
+    format: RecordingFormat,
+    fps: u32,
+    /// Size every frame is scaled to.
+    size: (u32, u32),
+    /// Frames written.
+    frames: u64,
+    sink: Sink,
+}
+
+enum Sink {
+    Gif {
+        encoder: GifEncoder<BufWriter<File>>,
+        /// Latest frame, written once the next one gives its delay.
+        pending: Option<(RgbaImage, Duration)>,
+    },
+    Mp4(Child),
+}
+
+impl Encoder {
+    /// Start a file of frames of `size`, scaled down for a GIF.
+    pub(crate) fn new(
+        format: RecordingFormat,
+        path: &Path,
+        size: (u32, u32),
+        fps: u32,
+    ) -> Result<Self, RecordingError> {
+        let failed = |e: std::io::Error| RecordingError::EncodingFailed(e.to_string());
+        let (size, sink) = match format {
+            RecordingFormat::Gif => {
+                let file = File::create(path).map_err(failed)?;
+                let mut encoder = GifEncoder::new_with_speed(BufWriter::new(file), GIF_SPEED);
+                encoder
+                    .set_repeat(Repeat::Infinite)
+                    .map_err(|e| RecordingError::EncodingFailed(e.to_string()))?;
+                let sink = Sink::Gif {
+                    encoder,
+                    pending: None,
+                };
+                (gif_size(size), sink)
+            }
+            RecordingFormat::Mp4 => {
+                let child = Command::new("ffmpeg")
+                    .args([
+                        "-y",
+                        "-loglevel",
+                        "error",
+                        "-f",
+                        "rawvideo",
+                        "-pix_fmt",
+                        "rgba",
+                    ])
+                    .arg("-s")
+                    .arg(format!("{}x{}", size.0, size.1))
+                    .arg("-r")
+                    .arg(fps.to_string())
+                    .args(["-i", "-", "-vf", "scale=trunc(iw/2)*2:trunc(ih/2)*2"])
+                    .args([
+                        "-c:v",
+                        "libx264",
+                        "-pix_fmt",
+                        "yuv420p",
+                        "-movflags",
+                        "+faststart",
+                    ])
+                    .arg(path)
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::piped())
+                    .spawn()
+                    .map_err(|e| match e.kind() {
+                        std::io::ErrorKind::NotFound => RecordingError::EncoderNotAvailable(
+                            "ffmpeg is not installed; install it or record a GIF".to_string(),
+                        ),
+                        _ => failed(e),
+                    })?;
+                (size, Sink::Mp4(child))
+            }
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            format,
+            fps,
+            size,
+            frames: 0,
+            sink,
+        })
+    }
+
+    /// Add a frame captured `at` after the start.
+    pub(crate) fn push(&mut self, frame: RgbaImage, at: Duration) -> Result<(), RecordingError> {
+        let frame = if frame.dimensions() == self.size {
+            frame
+        } else {
+            imageops::resize(&frame, self.size.0, self.size.1, FilterType::Triangle)
+        };
+        match &mut self.sink {
+            Sink::Gif { encoder, pending } => {
+                if let Some((previous, previous_at)) = pending.replace((frame, at)) {
+                    write_gif_frame(encoder, previous, at.saturating_sub(previous_at))?;
+                    self.frames += 1;
+                }
+            }
+            Sink::Mp4(child) => {
+                // Frames captured late are repeated, keeping the video in
+                // step with the time they were captured at
+                let due = frames_due(at, self.fps);
+                let stdin = child.stdin.as_mut().ok_or_else(|| {
+                    RecordingError::EncodingFailed("ffmpeg input closed".to_string())
+                })?;
+                loop {
+                    stdin
+                        .write_all(frame.as_raw())
+                        .map_err(|e| RecordingError::EncodingFailed(format!("ffmpeg: {}", e)))?;
+                    self.frames += 1;
+                    if self.frames >= due {
+                        break;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Finish the file, `duration` after the start.
+    pub(crate) fn finish(self, duration: Duration) -> Result<RecordingInfo, RecordingError> {
+        let mut frames = self.frames;
+        match self.sink {
+            Sink::Gif {
+                mut encoder,
+                pending,
+            } => {
+                if let Some((frame, at)) = pending {
+                    let delay = duration
+                        .saturating_sub(at)
+                        .max(Duration::from_secs(1) / self.fps);
+                    write_gif_frame(&mut encoder, frame, delay)?;
+                    frames += 1;
+                }
+                // Dropping the encoder writes the trailer
+                drop(encoder);
+            }
+            Sink::Mp4(mut child) => {
+                drop(child.stdin.take());
+                let output = child
+                    .wait_with_output()
+                    .map_err(|e| RecordingError::EncodingFailed(e.to_string()))?;
+                if !output.status.success() {
+                    return Err(RecordingError::EncodingFailed(format!(
+                        "ffmpeg: {}",
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    )));
+                }
+            }
+        }
+        Ok(RecordingInfo {
+            path: self.path,
+            format: self.format,
+            frames,
+            duration_ms: duration.as_millis() as u64,
+            width: self.size.0,
+            height: self.size.1,
+        })
+    }
+}
+
+fn write_gif_frame(
+    encoder: &mut GifEncoder<BufWriter<File>>,
+    frame: RgbaImage,
+    delay: Duration,
+) -> Result<(), RecordingError> {
+    encoder
+        .encode_frame(Frame::from_parts(
+            frame,
+            0,
+            0,
+            Delay::from_saturating_duration(delay),
+        ))
+        .map_err(|e| RecordingError::EncodingFailed(e.to_string()))
+}
+
+/// Size of GIF frames for captures of `size`: at most [`GIF_MAX_WIDTH`]
+/// wide, keeping the aspect ratio.
+pub(crate) fn gif_size((width, height): (u32, u32)) -> (u32, u32) {
+    if width <= GIF_MAX_WIDTH {
+        return (width, height);
+    }
+    let scaled = u64::from(height) * u64::from(GIF_MAX_WIDTH) / u64::from(width);
+    (GIF_MAX_WIDTH, (scaled as u32).max(1))
+}
+
+/// Frames of a video at `fps` due once a frame captured `at` after the
+/// start is written.
+pub(crate) fn frames_due(at: Duration, fps: u32) -> u64 {
+    (at.as_secs_f64() * f64::from(fps)).floor() as u64 + 1
+}
+
+#[cfg(test)]
+#[path = "recording_tests.rs"]
+mod tests;
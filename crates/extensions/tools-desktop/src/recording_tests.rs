@@ -0,0 +1,85 @@
+use super::*;
+use screenshots::image::codecs::gif::GifDecoder;
+use screenshots::image::{AnimationDecoder, Rgba};
+
+fn solid(width: u32, height: u32, shade: u8) -> RgbaImage {
+    RgbaImage::from_pixel(width, height, Rgba([shade, 255 - shade, 128, 255]))
+}
+
+#[test]
+fn test_gif_size() {
+    assert_eq!(gif_size((800, 600)), (800, 600));
+    assert_eq!(gif_size((960, 540)), (960, 540));
+    assert_eq!(gif_size((1920, 1080)), (960, 540));
+    assert_eq!(gif_size((3840, 1)), (960, 1));
+}
+
+#[test]
+fn test_frames_due() {
+    assert_eq!(frames_due(Duration::ZERO, 10), 1);
+    assert_eq!(frames_due(Duration::from_millis(99), 10), 1);
+    assert_eq!(frames_due(Duration::from_millis(100), 10), 2);
+    assert_eq!(frames_due(Duration::from_millis(350), 10), 4);
+}
+
+#[test]
+fn test_recording_format() {
+    assert_eq!(RecordingFormat::default(), RecordingFormat::Gif);
+    assert_eq!(RecordingFormat::Mp4.extension(), "mp4");
+    let format: RecordingFormat = serde_json::from_value(serde_json::json!("mp4")).unwrap();
+    assert_eq!(format, RecordingFormat::Mp4);
+    assert_eq!(serde_json::to_value(RecordingFormat::Gif).unwrap(), "gif");
+}
+
+#[test]
+fn test_gif_encoder() {
+    let path = std::env::temp_dir().join(format!(
+        "autohands-recording-test-{}.gif",
+        std::process::id()
+    ));
+    let mut encoder = Encoder::new(RecordingFormat::Gif, &path, (40, 30), 10).unwrap();
+    encoder.push(solid(40, 30, 0), Duration::ZERO).unwrap();
+    encoder
+        .push(solid(40, 30, 100), Duration::from_millis(100))
+        .unwrap();
+    // Frames of another size are scaled to the first one's
+    encoder
+        .push(solid(80, 60, 200), Duration::from_millis(250))
+        .unwrap();
+    let info = encoder.finish(Duration::from_millis(400)).unwrap();
+    assert_eq!(info.frames, 3);
+    assert_eq!((info.width, info.height), (40, 30));
+    assert_eq!(info.duration_ms, 400);
+
+    let file = File::open(&path).unwrap();
+    let frames = GifDecoder::new(file)
+        .unwrap()
+        .into_frames()
+        .collect_frames()
+        .unwrap();
+    std::fs::remove_file(&path).ok();
+    let delays: Vec<Duration> = frames.iter().map(|f| Duration::from(f.delay())).collect();
+    assert_eq!(
+        delays,
+        [
+            Duration::from_millis(100),
+            Duration::from_millis(150),
+            Duration::from_millis(150)
+        ]
+    );
+    assert_eq!(frames[2].buffer().dimensions(), (40, 30));
+}
+
+#[test]
+fn test_stop_without_recording() {
+    assert!(matches!(
+        stop_recording(),
+        Err(RecordingError::NotRecording)
+    ));
+}
+
+#[test]
+fn test_recording_error_display() {
+    let err = RecordingError::EncoderNotAvailable("ffmpeg".to_string());
+    assert_eq!(err.to_string(), "Encoder not available: ffmpeg");
+}
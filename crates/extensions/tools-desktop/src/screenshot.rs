@@ -1,11 +1,22 @@
 //! Screenshot capture utilities.
+//!
+//! Captures have the privacy masks blacked out, see [`crate::privacy`].
+//!
+//! Coordinates are global virtual-desktop coordinates, where the primary
+//! monitor's top-left corner is usually the origin and other monitors sit
+//! around it, or are relative to the top-left corner of a monitor given by
+//! its index in [`list_monitors`].
 
 use std::io::Cursor;
 
-use screenshots::image::ImageOutputFormat;
+use screenshots::image::{ImageOutputFormat, RgbaImage};
 use screenshots::Screen;
 use thiserror::Error;
 
+use autohands_protocols::types::MaskRegion;
+
+use crate::privacy::apply_privacy_masks;
+
 /// Screenshot errors.
 #[derive(Debug, Error)]
 pub enum ScreenshotError {
@@ -17,6 +28,9 @@ pub enum ScreenshotError {
 
     #[error("No monitor found")]
     NoMonitor,
+
+    #[error("Monitor {0} not found")]
+    MonitorNotFound(usize),
 }
 
 /// Screenshot result.
@@ -45,22 +59,60 @@ impl Screenshot {
 
 /// Capture the entire screen (primary monitor).
 pub fn capture_screen() -> Result<Screenshot, ScreenshotError> {
-    let screens = Screen::all().map_err(|e| ScreenshotError::CaptureFailed(e.to_string()))?;
+    let (_, screen) = find_screen(None)?;
+    encode_png(&capture_image(&screen)?)
+}
 
-    let screen = screens
-        .into_iter()
-        .find(|s| s.display_info.is_primary)
-        .or_else(|| Screen::all().ok()?.into_iter().next())
-        .ok_or(ScreenshotError::NoMonitor)?;
+/// Capture a monitor by index.
+pub fn capture_monitor(index: usize) -> Result<Screenshot, ScreenshotError> {
+    let (_, screen) = find_screen(Some(index))?;
+    encode_png(&capture_image(&screen)?)
+}
 
-    capture_screen_impl(&screen)
+/// Capture a monitor, the primary one if `None`, with the monitor's info.
+pub(crate) fn capture_monitor_image(
+    monitor: Option<usize>,
+) -> Result<(RgbaImage, MonitorInfo), ScreenshotError> {
+    let (index, screen) = find_screen(monitor)?;
+    Ok((capture_image(&screen)?, monitor_info(index, &screen)))
 }
 
-fn capture_screen_impl(screen: &Screen) -> Result<Screenshot, ScreenshotError> {
-    let image = screen
+/// Screen at `index` in [`list_monitors`], or the primary one if `None`,
+/// with its index.
+pub(crate) fn find_screen(monitor: Option<usize>) -> Result<(usize, Screen), ScreenshotError> {
+    let screens = Screen::all().map_err(|e| ScreenshotError::CaptureFailed(e.to_string()))?;
+
+    match monitor {
+        Some(index) => screens
+            .into_iter()
+            .nth(index)
+            .map(|screen| (index, screen))
+            .ok_or(ScreenshotError::MonitorNotFound(index)),
+        None => {
+            let primary = screens
+                .iter()
+                .position(|s| s.display_info.is_primary)
+                .unwrap_or(0);
+            screens
+                .into_iter()
+                .nth(primary)
+                .map(|screen| (primary, screen))
+                .ok_or(ScreenshotError::NoMonitor)
+        }
+    }
+}
+
+/// Capture a whole screen, with the privacy masks applied.
+pub(crate) fn capture_image(screen: &Screen) -> Result<RgbaImage, ScreenshotError> {
+    let mut image = screen
         .capture()
         .map_err(|e| ScreenshotError::CaptureFailed(e.to_string()))?;
+    let info = &screen.display_info;
+    apply_privacy_masks(&mut image, MaskRegion::new(info.x, info.y, info.width, info.height));
+    Ok(image)
+}
 
+fn encode_png(image: &RgbaImage) -> Result<Screenshot, ScreenshotError> {
     let width = image.width();
     let height = image.height();
 
@@ -77,50 +129,75 @@ fn capture_screen_impl(screen: &Screen) -> Result<Screenshot, ScreenshotError> {
     })
 }
 
-/// Capture a region of the screen.
+/// Capture a region of the screen, in global coordinates, from the
+/// monitor its top-left corner is on (the primary one if it is on none).
 pub fn capture_region(
     x: i32,
     y: i32,
     width: u32,
     height: u32,
 ) -> Result<Screenshot, ScreenshotError> {
-    let screens = Screen::all().map_err(|e| ScreenshotError::CaptureFailed(e.to_string()))?;
+    let (_, screen, x, y) = screen_at(x, y)?;
+    capture_area_impl(&screen, x, y, width, height)
+}
 
-    let screen = screens
+/// Screen a point in global coordinates is on (the primary one if it is
+/// on none) and its index, with the point relative to the screen's
+/// top-left corner.
+pub(crate) fn screen_at(x: i32, y: i32) -> Result<(usize, Screen, i32, i32), ScreenshotError> {
+    let monitor = list_monitors()?
         .into_iter()
-        .find(|s| s.display_info.is_primary)
-        .or_else(|| Screen::all().ok()?.into_iter().next())
-        .ok_or(ScreenshotError::NoMonitor)?;
+        .find(|m| m.contains(x, y))
+        .map(|m| m.index);
 
-    let image = screen
-        .capture_area(x, y, width, height)
-        .map_err(|e| ScreenshotError::CaptureFailed(e.to_string()))?;
+    let (index, screen) = find_screen(monitor)?;
+    let info = &screen.display_info;
+    let (x, y) = (x - info.x, y - info.y);
+    Ok((index, screen, x, y))
+}
 
-    let img_width = image.width();
-    let img_height = image.height();
+/// Capture a region of a monitor, relative to its top-left corner.
+pub fn capture_monitor_region(
+    index: usize,
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+) -> Result<Screenshot, ScreenshotError> {
+    let (_, screen) = find_screen(Some(index))?;
+    capture_area_impl(&screen, x, y, width, height)
+}
 
-    // Encode to PNG
-    let mut buffer = Cursor::new(Vec::new());
-    image
-        .write_to(&mut buffer, ImageOutputFormat::Png)
-        .map_err(|e| ScreenshotError::EncodingFailed(e.to_string()))?;
+fn capture_area_impl(
+    screen: &Screen,
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+) -> Result<Screenshot, ScreenshotError> {
+    encode_png(&capture_area_image(screen, x, y, width, height)?)
+}
 
-    Ok(Screenshot {
-        data: buffer.into_inner(),
-        width: img_width,
-        height: img_height,
-    })
+/// Capture a region of a screen, relative to its top-left corner, with the
+/// privacy masks applied.
+pub(crate) fn capture_area_image(
+    screen: &Screen,
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+) -> Result<RgbaImage, ScreenshotError> {
+    let mut image = screen
+        .capture_area(x, y, width, height)
+        .map_err(|e| ScreenshotError::CaptureFailed(e.to_string()))?;
+    let info = &screen.display_info;
+    apply_privacy_masks(&mut image, MaskRegion::new(info.x + x, info.y + y, width, height));
+    Ok(image)
 }
 
 /// Get screen dimensions.
 pub fn get_screen_size() -> Result<(u32, u32), ScreenshotError> {
-    let screens = Screen::all().map_err(|e| ScreenshotError::CaptureFailed(e.to_string()))?;
-
-    let screen = screens
-        .into_iter()
-        .find(|s| s.display_info.is_primary)
-        .or_else(|| Screen::all().ok()?.into_iter().next())
-        .ok_or(ScreenshotError::NoMonitor)?;
+    let (_, screen) = find_screen(None)?;
 
     Ok((screen.display_info.width, screen.display_info.height))
 }
@@ -130,24 +207,45 @@ pub fn list_monitors() -> Result<Vec<MonitorInfo>, ScreenshotError> {
     let screens = Screen::all().map_err(|e| ScreenshotError::CaptureFailed(e.to_string()))?;
 
     Ok(screens
-        .into_iter()
+        .iter()
         .enumerate()
-        .map(|(idx, s)| MonitorInfo {
-            id: s.display_info.id,
-            name: format!("Monitor {}", idx + 1),
-            x: s.display_info.x,
-            y: s.display_info.y,
-            width: s.display_info.width,
-            height: s.display_info.height,
-            is_primary: s.display_info.is_primary,
-            scale_factor: s.display_info.scale_factor,
-        })
+        .map(|(idx, s)| monitor_info(idx, s))
         .collect())
 }
 
+fn monitor_info(index: usize, screen: &Screen) -> MonitorInfo {
+    let info = &screen.display_info;
+    MonitorInfo {
+        index,
+        id: info.id,
+        name: format!("Monitor {}", index + 1),
+        x: info.x,
+        y: info.y,
+        width: info.width,
+        height: info.height,
+        is_primary: info.is_primary,
+        scale_factor: info.scale_factor,
+    }
+}
+
+/// Convert coordinates relative to a monitor's top-left corner to global
+/// ones; without a monitor, they are global already.
+pub fn to_global(monitor: Option<usize>, x: i32, y: i32) -> Result<(i32, i32), ScreenshotError> {
+    let Some(index) = monitor else {
+        return Ok((x, y));
+    };
+    let monitors = list_monitors()?;
+    let monitor = monitors
+        .get(index)
+        .ok_or(ScreenshotError::MonitorNotFound(index))?;
+    Ok(monitor.to_global(x, y))
+}
+
 /// Monitor information.
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct MonitorInfo {
+    /// Index to pick the monitor by in the desktop tools.
+    pub index: usize,
     pub id: u32,
     pub name: String,
     pub x: i32,
@@ -158,6 +256,22 @@ pub struct MonitorInfo {
     pub scale_factor: f32,
 }
 
+impl MonitorInfo {
+    /// Whether a point in global coordinates is on this monitor.
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x
+            && y >= self.y
+            && i64::from(x) < i64::from(self.x) + i64::from(self.width)
+            && i64::from(y) < i64::from(self.y) + i64::from(self.height)
+    }
+
+    /// Global coordinates of a point relative to this monitor's top-left
+    /// corner.
+    pub fn to_global(&self, x: i32, y: i32) -> (i32, i32) {
+        (self.x + x, self.y + y)
+    }
+}
+
 #[cfg(test)]
 #[path = "screenshot_tests.rs"]
 mod tests;
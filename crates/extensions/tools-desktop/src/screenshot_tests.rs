@@ -3,6 +3,7 @@ use super::*;
 #[test]
 fn test_monitor_info_serialize() {
     let info = MonitorInfo {
+        index: 0,
         id: 1,
         name: "Main".to_string(),
         x: 0,
@@ -22,6 +23,7 @@ fn test_monitor_info_serialize() {
 #[test]
 fn test_monitor_info_fields() {
     let info = MonitorInfo {
+        index: 1,
         id: 2,
         name: "Secondary".to_string(),
         x: 1920,
@@ -85,6 +87,7 @@ fn test_screenshot_error_debug() {
 #[test]
 fn test_monitor_info_debug() {
     let info = MonitorInfo {
+        index: 0,
         id: 1,
         name: "Test".to_string(),
         x: 0,
@@ -102,6 +105,7 @@ fn test_monitor_info_debug() {
 #[test]
 fn test_monitor_info_clone() {
     let info = MonitorInfo {
+        index: 0,
         id: 1,
         name: "Test".to_string(),
         x: 100,
@@ -163,6 +167,7 @@ fn test_screenshot_large_data() {
 #[test]
 fn test_monitor_info_negative_coordinates() {
     let info = MonitorInfo {
+        index: 0,
         id: 1,
         name: "Negative".to_string(),
         x: -1920,
@@ -179,6 +184,7 @@ fn test_monitor_info_negative_coordinates() {
 #[test]
 fn test_monitor_info_serialization_all_fields() {
     let info = MonitorInfo {
+        index: 4,
         id: 5,
         name: "Ultra Wide".to_string(),
         x: 2560,
@@ -189,6 +195,7 @@ fn test_monitor_info_serialization_all_fields() {
         scale_factor: 1.25,
     };
     let json = serde_json::to_string(&info).unwrap();
+    assert!(json.contains("\"index\":4"));
     assert!(json.contains("\"id\":5"));
     assert!(json.contains("Ultra Wide"));
     assert!(json.contains("\"x\":2560"));
@@ -241,3 +248,34 @@ fn test_list_monitors() {
     // At least one should be primary
     assert!(monitors.iter().any(|m| m.is_primary));
 }
+
+#[test]
+fn test_monitor_info_coordinates() {
+    let info = MonitorInfo {
+        index: 1,
+        id: 2,
+        name: "Monitor 2".to_string(),
+        x: -1280,
+        y: 0,
+        width: 1280,
+        height: 720,
+        is_primary: false,
+        scale_factor: 1.0,
+    };
+    assert!(info.contains(-1280, 0));
+    assert!(info.contains(-1, 719));
+    assert!(!info.contains(0, 0));
+    assert!(!info.contains(-640, 720));
+    assert_eq!(info.to_global(100, 50), (-1180, 50));
+}
+
+#[test]
+fn test_to_global_without_monitor() {
+    assert_eq!(to_global(None, 2500, 300).unwrap(), (2500, 300));
+}
+
+#[test]
+fn test_monitor_not_found_display() {
+    let err = ScreenshotError::MonitorNotFound(3);
+    assert_eq!(err.to_string(), "Monitor 3 not found");
+}
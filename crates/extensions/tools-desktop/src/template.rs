@@ -0,0 +1,346 @@
+//! Image template matching.
+//!
+//! Finds where a small image, such as an icon cropped from an earlier
+//! screenshot, appears on screen, for UI elements without text to OCR.
+//! Grayscale pixels are compared by zero-mean normalized cross-correlation
+//! (OpenCV's `TM_CCOEFF_NORMED`), which scores 1.0 for a perfect match and
+//! tolerates changes in brightness and contrast, but not in scale: the
+//! template must be cropped at the screen's resolution.
+//!
+//! Templates large enough are first searched for on downscaled copies, and
+//! the best candidates refined at full resolution.
+
+use screenshots::image::imageops::{self, FilterType};
+use screenshots::image::GrayImage;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::screenshot::{self, MonitorInfo, ScreenshotError};
+
+/// Smallest side a template is downscaled to for the coarse search.
+const MIN_COARSE_SIDE: u32 = 6;
+
+/// Most the coarse search downscales by.
+const MAX_COARSE_FACTOR: u32 = 8;
+
+/// Candidates of the coarse search refined at full resolution.
+const CANDIDATES: usize = 5;
+
+/// Template matching errors.
+#[derive(Debug, Error)]
+pub enum TemplateError {
+    #[error("Invalid template image: {0}")]
+    InvalidImage(String),
+
+    #[error("Template ({0}x{1}) is larger than the screen")]
+    TooLarge(u32, u32),
+
+    #[error("Template is a single flat color and would match anywhere")]
+    Flat,
+
+    #[error(transparent)]
+    Screenshot(#[from] ScreenshotError),
+}
+
+/// Best match of a template in an image, in the image's pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TemplateMatch {
+    /// Left edge of the match.
+    pub x: u32,
+    /// Top edge of the match.
+    pub y: u32,
+    /// Correlation, from -1.0 to 1.0.
+    pub score: f32,
+}
+
+/// Where a template was found on screen, in global coordinates.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImageMatch {
+    /// X of the match's center.
+    pub x: i32,
+    /// Y of the match's center.
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    /// Correlation, 1.0 for a perfect match.
+    pub confidence: f32,
+    /// Index of the monitor the match is on.
+    pub monitor: usize,
+}
+
+/// Find a template, an encoded image, on a monitor, or on every monitor if
+/// `None`, and return the best match however good it is.
+pub fn find_on_screen(
+    template: &[u8],
+    monitor: Option<usize>,
+) -> Result<ImageMatch, TemplateError> {
+    let template = screenshots::image::load_from_memory(template)
+        .map_err(|e| TemplateError::InvalidImage(e.to_string()))?
+        .to_luma8();
+
+    let monitors = match monitor {
+        Some(index) => vec![index],
+        None => screenshot::list_monitors()?
+            .into_iter()
+            .map(|m| m.index)
+            .collect(),
+    };
+
+    let mut best: Option<ImageMatch> = None;
+    let mut error = None;
+    for index in monitors {
+        let (image, info) = screenshot::capture_monitor_image(Some(index))?;
+        let image = imageops::grayscale(&image);
+        // A template can be too large for one monitor but not another
+        let found = match match_template(&image, &template) {
+            Ok(found) => found,
+            Err(e @ TemplateError::TooLarge(..)) => {
+                error = Some(e);
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        let found = to_screen(found, template.dimensions(), image.dimensions(), &info);
+        if best
+            .as_ref()
+            .is_none_or(|b| found.confidence > b.confidence)
+        {
+            best = Some(found);
+        }
+    }
+    best.ok_or_else(|| error.unwrap_or_else(|| ScreenshotError::NoMonitor.into()))
+}
+
+/// Map a match in a capture of `monitor` to global coordinates, which
+/// differ from the capture's pixels on scaled displays.
+pub(crate) fn to_screen(
+    found: TemplateMatch,
+    (template_width, template_height): (u32, u32),
+    (image_width, image_height): (u32, u32),
+    monitor: &MonitorInfo,
+) -> ImageMatch {
+    let scale_x = f64::from(image_width) / f64::from(monitor.width.max(1));
+    let scale_y = f64::from(image_height) / f64::from(monitor.height.max(1));
+    let center_x = f64::from(found.x) + f64::from(template_width) / 2.0;
+    let center_y = f64::from(found.y) + f64::from(template_height) / 2.0;
+    ImageMatch {
+        x: monitor.x + (center_x / scale_x).round() as i32,
+        y: monitor.y + (center_y / scale_y).round() as i32,
+        width: (f64::from(template_width) / scale_x).round() as u32,
+        height: (f64::from(template_height) / scale_y).round() as u32,
+        confidence: found.score,
+        monitor: monitor.index,
+    }
+}
+
+/// Find the best match of `template` in `image`.
+pub fn match_template(
+    image: &GrayImage,
+    template: &GrayImage,
+) -> Result<TemplateMatch, TemplateError> {
+    let (width, height) = template.dimensions();
+    if width == 0 || height == 0 {
+        return Err(TemplateError::InvalidImage("empty image".to_string()));
+    }
+    if width > image.width() || height > image.height() {
+        return Err(TemplateError::TooLarge(width, height));
+    }
+    let full = Template::new(template).ok_or(TemplateError::Flat)?;
+    let sums = Sums::new(image);
+
+    let factor = coarse_factor(width, height);
+    let coarse = if factor > 1 {
+        let small_template = downscale(template, factor);
+        // Detail can be lost in downscaling, leaving only the full search
+        Template::new(&small_template).map(|t| (t, downscale(image, factor)))
+    } else {
+        None
+    };
+    let Some((small_template, small_image)) = coarse else {
+        return Ok(best_matches(image, &sums, &full, 1)[0]);
+    };
+
+    let small_sums = Sums::new(&small_image);
+    let mut best: Option<TemplateMatch> = None;
+    for candidate in best_matches(&small_image, &small_sums, &small_template, CANDIDATES) {
+        let x = candidate.x * factor;
+        let y = candidate.y * factor;
+        let max_x = (x + factor).min(image.width() - width);
+        let max_y = (y + factor).min(image.height() - height);
+        for y in y.saturating_sub(factor).min(max_y)..=max_y {
+            for x in x.saturating_sub(factor).min(max_x)..=max_x {
+                let score = full.score(image, &sums, x, y);
+                if best.is_none_or(|b| score > b.score) {
+                    best = Some(TemplateMatch { x, y, score });
+                }
+            }
+        }
+    }
+    Ok(best.unwrap_or(TemplateMatch {
+        x: 0,
+        y: 0,
+        score: 0.0,
+    }))
+}
+
+/// Factor to downscale by for the coarse search: the largest power of two
+/// keeping the template's sides at least [`MIN_COARSE_SIDE`].
+fn coarse_factor(width: u32, height: u32) -> u32 {
+    let side = width.min(height);
+    let mut factor = 1;
+    while factor < MAX_COARSE_FACTOR && side / (factor * 2) >= MIN_COARSE_SIDE {
+        factor *= 2;
+    }
+    factor
+}
+
+fn downscale(image: &GrayImage, factor: u32) -> GrayImage {
+    let width = (image.width() / factor).max(1);
+    let height = (image.height() / factor).max(1);
+    imageops::resize(image, width, height, FilterType::Triangle)
+}
+
+/// Up to `count` best matches, at least half a template apart.
+fn best_matches(
+    image: &GrayImage,
+    sums: &Sums,
+    template: &Template,
+    count: usize,
+) -> Vec<TemplateMatch> {
+    let mut scores = Vec::new();
+    for y in 0..=image.height() - template.height {
+        for x in 0..=image.width() - template.width {
+            scores.push(TemplateMatch {
+                x,
+                y,
+                score: template.score(image, sums, x, y),
+            });
+        }
+    }
+    if count == 1 {
+        return scores
+            .into_iter()
+            .max_by(|a, b| a.score.total_cmp(&b.score))
+            .into_iter()
+            .collect();
+    }
+    scores.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    let mut matches: Vec<TemplateMatch> = Vec::with_capacity(count);
+    for found in scores {
+        if matches.len() == count {
+            break;
+        }
+        let near = matches.iter().any(|m| {
+            m.x.abs_diff(found.x) < template.width.div_ceil(2)
+                && m.y.abs_diff(found.y) < template.height.div_ceil(2)
+        });
+        if !near {
+            matches.push(found);
+        }
+    }
+    matches
+}
+
+/// A template's pixels less their mean, for correlating.
+struct Template {
+    width: u32,
+    height: u32,
+    values: Vec<f32>,
+    /// Square root of the sum of the squared values.
+    norm: f64,
+}
+
+impl Template {
+    /// `None` for a flat template, which correlates with nothing.
+    fn new(image: &GrayImage) -> Option<Self> {
+        let pixels = image.as_raw();
+        let mean = pixels.iter().map(|&p| f64::from(p)).sum::<f64>() / pixels.len() as f64;
+        let values: Vec<f32> = pixels
+            .iter()
+            .map(|&p| (f64::from(p) - mean) as f32)
+            .collect();
+        let norm = values.iter().map(|&v| f64::from(v * v)).sum::<f64>().sqrt();
+        (norm > 1e-3).then_some(Self {
+            width: image.width(),
+            height: image.height(),
+            values,
+            norm,
+        })
+    }
+
+    /// Correlation with the image at `(x, y)`; 0.0 where the image is flat.
+    fn score(&self, image: &GrayImage, sums: &Sums, x: u32, y: u32) -> f32 {
+        let (sum, sum_sq) = sums.window(x, y, self.width, self.height);
+        let count = f64::from(self.width * self.height);
+        let variance = sum_sq - sum * sum / count;
+        if variance <= 1e-3 {
+            return 0.0;
+        }
+
+        // The template sums to zero, so the window's mean drops out
+        let stride = image.width() as usize;
+        let width = self.width as usize;
+        let pixels = image.as_raw();
+        let mut dot = 0.0;
+        for row in 0..self.height as usize {
+            let start = (y as usize + row) * stride + x as usize;
+            let values = &self.values[row * width..][..width];
+            dot += f64::from(
+                pixels[start..start + width]
+                    .iter()
+                    .zip(values)
+                    .map(|(&p, &v)| f32::from(p) * v)
+                    .sum::<f32>(),
+            );
+        }
+        (dot / (self.norm * variance.sqrt())) as f32
+    }
+}
+
+/// Summed-area tables of an image's pixels and squared pixels.
+struct Sums {
+    stride: usize,
+    sum: Vec<f64>,
+    sum_sq: Vec<f64>,
+}
+
+impl Sums {
+    fn new(image: &GrayImage) -> Self {
+        let stride = image.width() as usize + 1;
+        let rows = image.height() as usize + 1;
+        let mut sum = vec![0.0; stride * rows];
+        let mut sum_sq = vec![0.0; stride * rows];
+        for (y, row) in image.as_raw().chunks(image.width() as usize).enumerate() {
+            let (mut row_sum, mut row_sq) = (0.0, 0.0);
+            for (x, &p) in row.iter().enumerate() {
+                let p = f64::from(p);
+                row_sum += p;
+                row_sq += p * p;
+                let at = (y + 1) * stride + x + 1;
+                sum[at] = sum[at - stride] + row_sum;
+                sum_sq[at] = sum_sq[at - stride] + row_sq;
+            }
+        }
+        Self {
+            stride,
+            sum,
+            sum_sq,
+        }
+    }
+
+    /// Sum and sum of squares of the pixels in a window.
+    fn window(&self, x: u32, y: u32, width: u32, height: u32) -> (f64, f64) {
+        let (x, y) = (x as usize, y as usize);
+        let (x2, y2) = (x + width as usize, y + height as usize);
+        let at = |table: &[f64]| {
+            table[y2 * self.stride + x2] - table[y * self.stride + x2] - table[y2 * self.stride + x]
+                + table[y * self.stride + x]
+        };
+        (at(&self.sum), at(&self.sum_sq))
+    }
+}
+
+#[cfg(test)]
+#[path = "template_tests.rs"]
+mod tests;
@@ -0,0 +1,103 @@
+use super::*;
+use screenshots::image::Luma;
+
+/// Smoothed noise, so downscaled copies keep some detail.
+fn noise(width: u32, height: u32, seed: u32) -> GrayImage {
+    let mut state = seed;
+    let raw = GrayImage::from_fn(width, height, |_, _| {
+        state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        Luma([(state >> 24) as u8])
+    });
+    screenshots::image::imageops::blur(&raw, 1.0)
+}
+
+fn crop(image: &GrayImage, x: u32, y: u32, width: u32, height: u32) -> GrayImage {
+    imageops::crop_imm(image, x, y, width, height).to_image()
+}
+
+#[test]
+fn test_match_template_exact() {
+    let image = noise(200, 120, 1);
+    let template = crop(&image, 123, 67, 30, 20);
+    let found = match_template(&image, &template).unwrap();
+    assert_eq!((found.x, found.y), (123, 67));
+    assert!(found.score > 0.99);
+}
+
+#[test]
+fn test_match_template_small() {
+    let image = noise(100, 80, 2);
+    let template = crop(&image, 41, 9, 8, 8);
+    let found = match_template(&image, &template).unwrap();
+    assert_eq!((found.x, found.y), (41, 9));
+    assert!(found.score > 0.99);
+}
+
+#[test]
+fn test_match_template_contrast() {
+    let image = noise(160, 160, 3);
+    let mut template = crop(&image, 70, 90, 40, 40);
+    for pixel in template.pixels_mut() {
+        pixel.0[0] = pixel.0[0] / 2 + 60;
+    }
+    let found = match_template(&image, &template).unwrap();
+    assert_eq!((found.x, found.y), (70, 90));
+    assert!(found.score > 0.95);
+}
+
+#[test]
+fn test_match_template_absent() {
+    let image = noise(120, 120, 4);
+    let template = noise(24, 24, 5);
+    let found = match_template(&image, &template).unwrap();
+    assert!(found.score < 0.8);
+}
+
+#[test]
+fn test_match_template_errors() {
+    let image = noise(50, 50, 6);
+    let flat = GrayImage::from_pixel(10, 10, Luma([128]));
+    assert!(matches!(
+        match_template(&image, &flat),
+        Err(TemplateError::Flat)
+    ));
+    let large = noise(60, 10, 7);
+    assert!(matches!(
+        match_template(&image, &large),
+        Err(TemplateError::TooLarge(60, 10))
+    ));
+}
+
+#[test]
+fn test_coarse_factor() {
+    assert_eq!(coarse_factor(8, 8), 1);
+    assert_eq!(coarse_factor(12, 40), 2);
+    assert_eq!(coarse_factor(30, 20), 2);
+    assert_eq!(coarse_factor(48, 48), 8);
+    assert_eq!(coarse_factor(400, 400), 8);
+}
+
+#[test]
+fn test_to_screen_scaled() {
+    let monitor = MonitorInfo {
+        index: 1,
+        id: 2,
+        name: "Monitor 2".to_string(),
+        x: 1440,
+        y: 0,
+        width: 1280,
+        height: 800,
+        is_primary: false,
+        scale_factor: 2.0,
+    };
+    let found = TemplateMatch {
+        x: 200,
+        y: 100,
+        score: 0.97,
+    };
+    let m = to_screen(found, (40, 20), (2560, 1600), &monitor);
+    assert_eq!((m.x, m.y), (1440 + 110, 55));
+    assert_eq!((m.width, m.height), (20, 10));
+    assert_eq!(m.monitor, 1);
+    assert_eq!(m.confidence, 0.97);
+}
@@ -0,0 +1,258 @@
+//! Image template matching tools.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use tracing::debug;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{PathAccess, Tool, ToolContext, ToolDefinition, ToolResult};
+
+use crate::input::MouseButton;
+use crate::interlock::{self, INPUT_RESOURCE};
+use crate::template::{self, ImageMatch};
+
+// Helper to run blocking code
+async fn run_blocking<F, T>(f: F) -> Result<T, ToolError>
+where
+    F: FnOnce() -> Result<T, String> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?
+        .map_err(ToolError::ExecutionFailed)
+}
+
+/// Template image to look for, given either way.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TemplateSource {
+    /// Base64 encoded image data.
+    pub image_base64: Option<String>,
+    /// Path to an image file.
+    pub path: Option<String>,
+}
+
+impl TemplateSource {
+    /// Encoded image data of the template; a path is resolved through the
+    /// tool's sandbox.
+    pub fn load(&self, ctx: &ToolContext) -> Result<Vec<u8>, ToolError> {
+        match (&self.image_base64, &self.path) {
+            (Some(data), None) => {
+                use base64::Engine;
+                base64::engine::general_purpose::STANDARD
+                    .decode(data)
+                    .map_err(|e| ToolError::ExecutionFailed(format!("Invalid base64: {}", e)))
+            }
+            (None, Some(path)) => {
+                let resolved = ctx.resolve_path(path, PathAccess::Read)?;
+                std::fs::read(&resolved).map_err(|e| {
+                    ToolError::ExecutionFailed(format!("Failed to read {}: {}", path, e))
+                })
+            }
+            _ => Err(ToolError::ExecutionFailed(
+                "Invalid params: exactly one of image_base64 and path is required".to_string(),
+            )),
+        }
+    }
+}
+
+fn default_threshold() -> f32 {
+    0.8
+}
+
+/// Find a template on screen; the best match is returned as `Err` when it
+/// is below `threshold`.
+async fn find(
+    source: &TemplateSource,
+    ctx: &ToolContext,
+    monitor: Option<usize>,
+    threshold: f32,
+) -> Result<Result<ImageMatch, ImageMatch>, ToolError> {
+    let data = source.load(ctx)?;
+    let found =
+        run_blocking(move || template::find_on_screen(&data, monitor).map_err(|e| e.to_string()))
+            .await?;
+    debug!(
+        "Best template match at ({}, {}) with confidence {:.3}",
+        found.x, found.y, found.confidence
+    );
+    Ok(if found.confidence >= threshold {
+        Ok(found)
+    } else {
+        Err(found)
+    })
+}
+
+// ============================================================================
+// Find Image Tool
+// ============================================================================
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FindImageParams {
+    #[serde(flatten)]
+    pub template: TemplateSource,
+    /// Monitor to search, by index in `desktop_screen_info`; every monitor
+    /// if unset.
+    pub monitor: Option<usize>,
+    /// Lowest confidence, from 0.0 to 1.0, accepted as a match.
+    #[serde(default = "default_threshold")]
+    pub threshold: f32,
+}
+
+/// Find an image on screen.
+pub struct FindImageTool {
+    definition: ToolDefinition,
+}
+
+impl FindImageTool {
+    pub fn new() -> Self {
+        Self {
+            definition: ToolDefinition::new(
+                "desktop_find_image",
+                "Desktop Find Image",
+                "Find where a template image (base64 or file path), such as an icon, appears \
+                 on screen, returning the center of the best match in global coordinates \
+                 with its confidence",
+            )
+            .with_read_only(),
+        }
+    }
+}
+
+impl Default for FindImageTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Tool for FindImageTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: FindImageParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::ExecutionFailed(format!("Invalid params: {}", e)))?;
+
+        match find(&params.template, &ctx, params.monitor, params.threshold).await? {
+            Ok(found) => {
+                let json = serde_json::to_string_pretty(&found)
+                    .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
+                Ok(ToolResult::success(json)
+                    .with_metadata("found", serde_json::json!(true))
+                    .with_metadata("confidence", serde_json::json!(found.confidence)))
+            }
+            Err(best) => Ok(ToolResult::success(format!(
+                "Image not found (best match {:.2} at ({}, {}), below threshold {:.2})",
+                best.confidence, best.x, best.y, params.threshold
+            ))
+            .with_metadata("found", serde_json::json!(false))
+            .with_metadata("confidence", serde_json::json!(best.confidence))),
+        }
+    }
+}
+
+// ============================================================================
+// Click Image Tool
+// ============================================================================
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClickImageParams {
+    #[serde(flatten)]
+    pub template: TemplateSource,
+    /// Monitor to search, by index in `desktop_screen_info`; every monitor
+    /// if unset.
+    pub monitor: Option<usize>,
+    /// Lowest confidence, from 0.0 to 1.0, accepted as a match.
+    #[serde(default = "default_threshold")]
+    pub threshold: f32,
+    #[serde(default = "default_button")]
+    pub button: MouseButton,
+    #[serde(default)]
+    pub double_click: bool,
+}
+
+fn default_button() -> MouseButton {
+    MouseButton::Left
+}
+
+/// Click the center of an image found on screen.
+pub struct ClickImageTool {
+    definition: ToolDefinition,
+}
+
+impl ClickImageTool {
+    pub fn new() -> Self {
+        Self {
+            definition: ToolDefinition::new(
+                "desktop_click_image",
+                "Desktop Click Image",
+                "Find a template image (base64 or file path), such as an icon, on screen and \
+                 click the center of the best match",
+            )
+            .with_exclusive_resource(INPUT_RESOURCE),
+        }
+    }
+}
+
+impl Default for ClickImageTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Tool for ClickImageTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: ClickImageParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::ExecutionFailed(format!("Invalid params: {}", e)))?;
+
+        let found = find(&params.template, &ctx, params.monitor, params.threshold)
+            .await?
+            .map_err(|best| {
+                ToolError::ExecutionFailed(format!(
+                    "Image not found (best match {:.2}, below threshold {:.2})",
+                    best.confidence, params.threshold
+                ))
+            })?;
+
+        let (x, y) = (found.x, found.y);
+        run_blocking(move || {
+            interlock::agent_input(|controller| {
+                controller.mouse_move(x, y)?;
+                if params.double_click {
+                    controller.mouse_double_click(params.button)
+                } else {
+                    controller.mouse_click(params.button)
+                }
+            })
+        })
+        .await?;
+
+        debug!("Clicked image at ({}, {})", x, y);
+        Ok(ToolResult::success(format!(
+            "Clicked image at ({}, {}) with confidence {:.2}",
+            x, y, found.confidence
+        ))
+        .with_metadata("x", serde_json::json!(x))
+        .with_metadata("y", serde_json::json!(y))
+        .with_metadata("confidence", serde_json::json!(found.confidence)))
+    }
+}
+
+#[cfg(test)]
+#[path = "template_tools_tests.rs"]
+mod tests;
@@ -0,0 +1,103 @@
+use super::*;
+
+#[test]
+fn test_find_image_tool_definition() {
+    let tool = FindImageTool::new();
+    assert_eq!(tool.definition().id, "desktop_find_image");
+}
+
+#[test]
+fn test_click_image_tool_definition() {
+    let tool = ClickImageTool::new();
+    assert_eq!(tool.definition().id, "desktop_click_image");
+}
+
+#[test]
+fn test_find_image_params_defaults() {
+    let json = serde_json::json!({
+        "image_base64": "aGVsbG8="
+    });
+    let params: FindImageParams = serde_json::from_value(json).unwrap();
+    assert_eq!(params.template.image_base64.as_deref(), Some("aGVsbG8="));
+    assert!(params.template.path.is_none());
+    assert!(params.monitor.is_none());
+    assert_eq!(params.threshold, 0.8);
+}
+
+#[test]
+fn test_click_image_params() {
+    let json = serde_json::json!({
+        "path": "/tmp/icon.png",
+        "monitor": 1,
+        "threshold": 0.9,
+        "button": "right",
+        "double_click": true
+    });
+    let params: ClickImageParams = serde_json::from_value(json).unwrap();
+    assert_eq!(params.template.path.as_deref(), Some("/tmp/icon.png"));
+    assert_eq!(params.monitor, Some(1));
+    assert_eq!(params.threshold, 0.9);
+    assert!(matches!(params.button, MouseButton::Right));
+    assert!(params.double_click);
+}
+
+#[test]
+fn test_template_source_load() {
+    let ctx = ToolContext::new("test", std::env::temp_dir());
+    let source = TemplateSource {
+        image_base64: Some("aGVsbG8=".to_string()),
+        path: None,
+    };
+    assert_eq!(source.load(&ctx).unwrap(), b"hello");
+
+    let neither = TemplateSource {
+        image_base64: None,
+        path: None,
+    };
+    assert!(neither.load(&ctx).is_err());
+
+    let both = TemplateSource {
+        image_base64: Some("aGVsbG8=".to_string()),
+        path: Some("/tmp/icon.png".to_string()),
+    };
+    assert!(both.load(&ctx).is_err());
+}
+
+#[test]
+fn test_template_path_confined() {
+    let ctx = ToolContext::new("test", std::env::temp_dir());
+    let outside = TemplateSource {
+        image_base64: None,
+        path: Some("/etc/hostname".to_string()),
+    };
+    let err = outside.load(&ctx).unwrap_err();
+    assert!(err.to_string().contains("Path traversal denied"), "{}", err);
+
+    let sandbox = autohands_protocols::tool::SandboxPolicy {
+        denied: vec!["*.png".to_string()],
+        ..Default::default()
+    };
+    let ctx = ctx.with_sandbox(std::sync::Arc::new(sandbox));
+    let denied = TemplateSource {
+        image_base64: None,
+        path: Some("icon.png".to_string()),
+    };
+    let err = denied.load(&ctx).unwrap_err();
+    assert!(err.to_string().contains("denied by the sandbox"), "{}", err);
+}
+
+#[tokio::test]
+async fn test_find_image_path_confined() {
+    let ctx = ToolContext::new("test", std::env::temp_dir());
+    let err = FindImageTool::new()
+        .execute(serde_json::json!({"path": "/etc/hostname"}), ctx)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Path traversal denied"), "{}", err);
+}
+
+#[test]
+fn test_tools_default_impl() {
+    let _ = FindImageTool::default();
+    let _ = ClickImageTool::default();
+}
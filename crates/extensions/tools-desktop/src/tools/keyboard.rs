@@ -7,7 +7,7 @@ use tracing::debug;
 use autohands_protocols::error::ToolError;
 use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
 
-use crate::input::InputController;
+use crate::interlock::{self, INPUT_RESOURCE};
 
 use super::run_blocking;
 
@@ -32,7 +32,8 @@ impl KeyboardTypeTool {
                 "desktop_keyboard_type",
                 "Desktop Keyboard Type",
                 "Type text using the keyboard",
-            ),
+            )
+            .with_exclusive_resource(INPUT_RESOURCE),
         }
     }
 }
@@ -60,8 +61,7 @@ impl Tool for KeyboardTypeTool {
         let len = params.text.len();
 
         run_blocking(move || {
-            let mut controller = InputController::new().map_err(|e| e.to_string())?;
-            controller.type_text(&params.text).map_err(|e| e.to_string())
+            interlock::agent_input(|controller| controller.type_text(&params.text))
         })
         .await?;
 
@@ -91,7 +91,8 @@ impl KeyboardKeyTool {
                 "desktop_keyboard_key",
                 "Desktop Keyboard Key",
                 "Press a single key (e.g., 'enter', 'tab', 'escape', 'f1')",
-            ),
+            )
+            .with_exclusive_resource(INPUT_RESOURCE),
         }
     }
 }
@@ -119,8 +120,7 @@ impl Tool for KeyboardKeyTool {
         let key = params.key.clone();
 
         run_blocking(move || {
-            let mut controller = InputController::new().map_err(|e| e.to_string())?;
-            controller.key_press(&params.key).map_err(|e| e.to_string())
+            interlock::agent_input(|controller| controller.key_press(&params.key))
         })
         .await?;
 
@@ -150,7 +150,8 @@ impl KeyboardHotkeyTool {
                 "desktop_keyboard_hotkey",
                 "Desktop Keyboard Hotkey",
                 "Press a key combination (e.g., ['ctrl', 'c'] for copy)",
-            ),
+            )
+            .with_exclusive_resource(INPUT_RESOURCE),
         }
     }
 }
@@ -178,9 +179,8 @@ impl Tool for KeyboardHotkeyTool {
         let keys_str = params.keys.join("+");
 
         run_blocking(move || {
-            let mut controller = InputController::new().map_err(|e| e.to_string())?;
             let keys: Vec<&str> = params.keys.iter().map(|s| s.as_str()).collect();
-            controller.hotkey(&keys).map_err(|e| e.to_string())
+            interlock::agent_input(|controller| controller.hotkey(&keys))
         })
         .await?;
 
@@ -3,11 +3,13 @@
 mod clipboard;
 mod keyboard;
 mod mouse;
+mod recording;
 mod screenshot;
 
 pub use clipboard::*;
 pub use keyboard::*;
 pub use mouse::*;
+pub use recording::*;
 pub use screenshot::*;
 
 #[cfg(test)]
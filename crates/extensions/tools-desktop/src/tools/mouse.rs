@@ -7,7 +7,9 @@ use tracing::debug;
 use autohands_protocols::error::ToolError;
 use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
 
-use crate::input::{InputController, MouseButton};
+use crate::input::MouseButton;
+use crate::interlock::{self, INPUT_RESOURCE};
+use crate::screenshot;
 
 use super::run_blocking;
 
@@ -21,6 +23,9 @@ pub struct MouseMoveParams {
     pub y: i32,
     #[serde(default)]
     pub relative: bool,
+    /// Monitor the position is relative to, by index in
+    /// `desktop_screen_info`; global coordinates if unset.
+    pub monitor: Option<usize>,
 }
 
 /// Move the mouse cursor.
@@ -34,8 +39,10 @@ impl MouseMoveTool {
             definition: ToolDefinition::new(
                 "desktop_mouse_move",
                 "Desktop Mouse Move",
-                "Move the mouse cursor to a position (absolute or relative)",
-            ),
+                "Move the mouse cursor to a position (absolute or relative), in global \
+                 coordinates or relative to a monitor",
+            )
+            .with_exclusive_resource(INPUT_RESOURCE),
         }
     }
 }
@@ -60,18 +67,28 @@ impl Tool for MouseMoveTool {
         let params: MouseMoveParams = serde_json::from_value(params)
             .map_err(|e| ToolError::ExecutionFailed(format!("Invalid params: {}", e)))?;
 
-        let x = params.x;
-        let y = params.y;
         let relative = params.relative;
+        if relative && params.monitor.is_some() {
+            return Err(ToolError::ExecutionFailed(
+                "Invalid params: monitor does not apply to relative moves".to_string(),
+            ));
+        }
 
-        run_blocking(move || {
-            let mut controller = InputController::new().map_err(|e| e.to_string())?;
-            if relative {
-                controller.mouse_move_relative(x, y)
+        let (x, y) = run_blocking(move || {
+            let (x, y) = if relative {
+                (params.x, params.y)
             } else {
-                controller.mouse_move(x, y)
-            }
-            .map_err(|e| e.to_string())
+                screenshot::to_global(params.monitor, params.x, params.y)
+                    .map_err(|e| e.to_string())?
+            };
+            interlock::agent_input(|controller| {
+                if relative {
+                    controller.mouse_move_relative(x, y)
+                } else {
+                    controller.mouse_move(x, y)
+                }
+            })?;
+            Ok((x, y))
         })
         .await?;
 
@@ -92,6 +109,9 @@ pub struct MouseClickParams {
     pub double_click: bool,
     pub x: Option<i32>,
     pub y: Option<i32>,
+    /// Monitor `x` and `y` are relative to, by index in
+    /// `desktop_screen_info`; global coordinates if unset.
+    pub monitor: Option<usize>,
 }
 
 fn default_button() -> MouseButton {
@@ -109,8 +129,10 @@ impl MouseClickTool {
             definition: ToolDefinition::new(
                 "desktop_mouse_click",
                 "Desktop Mouse Click",
-                "Click the mouse (left, right, or middle button)",
-            ),
+                "Click the mouse (left, right, or middle button), optionally at a position \
+                 in global coordinates or relative to a monitor",
+            )
+            .with_exclusive_resource(INPUT_RESOURCE),
         }
     }
 }
@@ -136,18 +158,23 @@ impl Tool for MouseClickTool {
             .map_err(|e| ToolError::ExecutionFailed(format!("Invalid params: {}", e)))?;
 
         run_blocking(move || {
-            let mut controller = InputController::new().map_err(|e| e.to_string())?;
-
-            if let (Some(x), Some(y)) = (params.x, params.y) {
-                controller.mouse_move(x, y).map_err(|e| e.to_string())?;
-            }
-
-            if params.double_click {
-                controller.mouse_double_click(params.button)
-            } else {
-                controller.mouse_click(params.button)
-            }
-            .map_err(|e| e.to_string())
+            let position = match (params.x, params.y) {
+                (Some(x), Some(y)) => {
+                    Some(screenshot::to_global(params.monitor, x, y).map_err(|e| e.to_string())?)
+                }
+                _ => None,
+            };
+            interlock::agent_input(|controller| {
+                if let Some((x, y)) = position {
+                    controller.mouse_move(x, y)?;
+                }
+
+                if params.double_click {
+                    controller.mouse_double_click(params.button)
+                } else {
+                    controller.mouse_click(params.button)
+                }
+            })
         })
         .await?;
 
@@ -179,7 +206,8 @@ impl MouseScrollTool {
                 "desktop_mouse_scroll",
                 "Desktop Mouse Scroll",
                 "Scroll the mouse wheel (vertical or horizontal)",
-            ),
+            )
+            .with_exclusive_resource(INPUT_RESOURCE),
         }
     }
 }
@@ -207,10 +235,9 @@ impl Tool for MouseScrollTool {
         let delta = params.delta;
 
         run_blocking(move || {
-            let mut controller = InputController::new().map_err(|e| e.to_string())?;
-            controller
-                .mouse_scroll(params.delta, params.horizontal)
-                .map_err(|e| e.to_string())
+            interlock::agent_input(|controller| {
+                controller.mouse_scroll(params.delta, params.horizontal)
+            })
         })
         .await?;
 
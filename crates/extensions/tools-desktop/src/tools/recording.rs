@@ -0,0 +1,178 @@
+//! Screen recording tools.
+
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use tracing::debug;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{PathAccess, Tool, ToolContext, ToolDefinition, ToolResult};
+
+use crate::recording::{self, RecordingFormat, RecordingOptions};
+
+use super::{run_blocking, RegionParams};
+
+// ============================================================================
+// Record Start Tool
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct RecordStartParams {
+    #[serde(default)]
+    pub format: RecordingFormat,
+    /// Frames per second, up to 30.
+    #[serde(default = "default_fps")]
+    pub fps: u32,
+    /// Seconds after which the recording stops by itself, up to 600.
+    #[serde(default = "default_max_duration_secs")]
+    pub max_duration_secs: u64,
+    pub region: Option<RegionParams>,
+    /// Monitor to record, by index in `desktop_screen_info`; the region is
+    /// then relative to its top-left corner. Defaults to the primary
+    /// monitor, with the region in global coordinates.
+    pub monitor: Option<usize>,
+    /// File to write, relative to the working directory; a file in the
+    /// system temp directory if unset.
+    pub path: Option<String>,
+}
+
+fn default_fps() -> u32 {
+    5
+}
+
+fn default_max_duration_secs() -> u64 {
+    60
+}
+
+/// Start recording the screen.
+pub struct RecordStartTool {
+    definition: ToolDefinition,
+}
+
+impl RecordStartTool {
+    pub fn new() -> Self {
+        Self {
+            definition: ToolDefinition::new(
+                "desktop_record_start",
+                "Desktop Record Start",
+                "Start recording the primary screen, a monitor, or a region to a GIF or MP4 \
+                 file, until desktop_record_stop or the maximum duration",
+            ),
+        }
+    }
+}
+
+impl Default for RecordStartTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Tool for RecordStartTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: RecordStartParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::ExecutionFailed(format!("Invalid params: {}", e)))?;
+
+        let path = match &params.path {
+            Some(path) => ctx.resolve_path(path, PathAccess::Write)?,
+            None => default_path(params.format),
+        };
+        let options = RecordingOptions {
+            format: params.format,
+            fps: params.fps,
+            max_duration: Duration::from_secs(params.max_duration_secs),
+            monitor: params.monitor,
+            region: params.region.map(|r| (r.x, r.y, r.width, r.height)),
+            path: path.clone(),
+        };
+
+        run_blocking(move || {
+            if let Some(parent) = options.path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            recording::start_recording(options).map_err(|e| e.to_string())
+        })
+        .await?;
+
+        debug!("Recording started to {}", path.display());
+        Ok(
+            ToolResult::success(format!("Recording started to {}", path.display()))
+                .with_metadata("path", serde_json::json!(path)),
+        )
+    }
+}
+
+/// File in the system temp directory, named after the current time.
+fn default_path(format: RecordingFormat) -> PathBuf {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or_default();
+    std::env::temp_dir().join(format!(
+        "autohands-recording-{}.{}",
+        millis,
+        format.extension()
+    ))
+}
+
+// ============================================================================
+// Record Stop Tool
+// ============================================================================
+
+/// Stop recording the screen.
+pub struct RecordStopTool {
+    definition: ToolDefinition,
+}
+
+impl RecordStopTool {
+    pub fn new() -> Self {
+        Self {
+            definition: ToolDefinition::new(
+                "desktop_record_stop",
+                "Desktop Record Stop",
+                "Stop the screen recording and return the path of the finished file",
+            ),
+        }
+    }
+}
+
+impl Default for RecordStopTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Tool for RecordStopTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        _params: serde_json::Value,
+        _ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let info = run_blocking(|| recording::stop_recording().map_err(|e| e.to_string())).await?;
+
+        let json = serde_json::to_string_pretty(&info)
+            .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
+
+        debug!(
+            "Recording stopped: {} frames in {} ms",
+            info.frames, info.duration_ms
+        );
+        Ok(ToolResult::success(json).with_metadata("path", serde_json::json!(info.path)))
+    }
+}
@@ -14,6 +14,10 @@ use super::run_blocking;
 #[derive(Debug, Deserialize)]
 pub struct ScreenshotParams {
     pub region: Option<RegionParams>,
+    /// Monitor to capture, by index in `desktop_screen_info`; the region is
+    /// then relative to its top-left corner. Defaults to the primary
+    /// monitor, with the region in global coordinates.
+    pub monitor: Option<usize>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -35,8 +39,9 @@ impl DesktopScreenshotTool {
             definition: ToolDefinition::new(
                 "desktop_screenshot",
                 "Desktop Screenshot",
-                "Take a screenshot of the entire screen or a region",
-            ),
+                "Take a screenshot of the primary screen, a monitor, or a region",
+            )
+            .with_read_only(),
         }
     }
 }
@@ -61,11 +66,21 @@ impl Tool for DesktopScreenshotTool {
         let params: ScreenshotParams = serde_json::from_value(params)
             .map_err(|e| ToolError::ExecutionFailed(format!("Invalid params: {}", e)))?;
 
+        let monitor = params.monitor;
         let screenshot = run_blocking(move || {
-            let result = if let Some(region) = params.region {
-                screenshot::capture_region(region.x, region.y, region.width, region.height)
-            } else {
-                screenshot::capture_screen()
+            let result = match (params.region, monitor) {
+                (Some(region), Some(index)) => screenshot::capture_monitor_region(
+                    index,
+                    region.x,
+                    region.y,
+                    region.width,
+                    region.height,
+                ),
+                (Some(region), None) => {
+                    screenshot::capture_region(region.x, region.y, region.width, region.height)
+                }
+                (None, Some(index)) => screenshot::capture_monitor(index),
+                (None, None) => screenshot::capture_screen(),
             };
             result.map_err(|e| e.to_string())
         })
@@ -78,13 +93,17 @@ impl Tool for DesktopScreenshotTool {
             screenshot.data.len()
         );
 
-        Ok(ToolResult::success(format!(
+        let mut result = ToolResult::success(format!(
             "Screenshot captured: {}x{}",
             screenshot.width, screenshot.height
         ))
         .with_metadata("base64", serde_json::json!(screenshot.to_base64()))
         .with_metadata("width", serde_json::json!(screenshot.width))
-        .with_metadata("height", serde_json::json!(screenshot.height)))
+        .with_metadata("height", serde_json::json!(screenshot.height));
+        if let Some(index) = monitor {
+            result = result.with_metadata("monitor", serde_json::json!(index));
+        }
+        Ok(result)
     }
 }
 
@@ -99,8 +118,10 @@ impl ScreenInfoTool {
             definition: ToolDefinition::new(
                 "desktop_screen_info",
                 "Desktop Screen Info",
-                "Get information about screens/monitors",
-            ),
+                "Get information about screens/monitors, with the index other desktop tools \
+                 take to pick one",
+            )
+            .with_read_only(),
         }
     }
 }
@@ -7,6 +7,7 @@ fn test_screenshot_params() {
     let json = serde_json::json!({});
     let params: ScreenshotParams = serde_json::from_value(json).unwrap();
     assert!(params.region.is_none());
+    assert!(params.monitor.is_none());
 }
 
 #[test]
@@ -61,6 +62,7 @@ fn test_mouse_click_params_defaults() {
     assert!(matches!(params.button, MouseButton::Left));
     assert!(params.x.is_none());
     assert!(params.y.is_none());
+    assert!(params.monitor.is_none());
 }
 
 #[test]
@@ -69,13 +71,15 @@ fn test_mouse_click_params_full() {
         "button": "right",
         "double_click": true,
         "x": 500,
-        "y": 600
+        "y": 600,
+        "monitor": 1
     });
     let params: MouseClickParams = serde_json::from_value(json).unwrap();
     assert!(params.double_click);
     assert!(matches!(params.button, MouseButton::Right));
     assert_eq!(params.x, Some(500));
     assert_eq!(params.y, Some(600));
+    assert_eq!(params.monitor, Some(1));
 }
 
 #[test]
@@ -228,3 +232,52 @@ fn test_tools_default_impl() {
     let _ = ClipboardGetTool::default();
     let _ = ClipboardSetTool::default();
 }
+
+#[test]
+fn test_record_start_params_defaults() {
+    let params: RecordStartParams = serde_json::from_value(serde_json::json!({})).unwrap();
+    assert_eq!(params.format, crate::recording::RecordingFormat::Gif);
+    assert_eq!(params.fps, 5);
+    assert_eq!(params.max_duration_secs, 60);
+    assert!(params.region.is_none());
+    assert!(params.monitor.is_none());
+    assert!(params.path.is_none());
+}
+
+#[test]
+fn test_record_start_params() {
+    let json = serde_json::json!({
+        "format": "mp4",
+        "fps": 10,
+        "max_duration_secs": 30,
+        "region": {"x": 0, "y": 0, "width": 640, "height": 480},
+        "monitor": 1,
+        "path": "repro.mp4"
+    });
+    let params: RecordStartParams = serde_json::from_value(json).unwrap();
+    assert_eq!(params.format, crate::recording::RecordingFormat::Mp4);
+    assert_eq!(params.fps, 10);
+    assert_eq!(params.max_duration_secs, 30);
+    assert_eq!(params.region.unwrap().width, 640);
+    assert_eq!(params.monitor, Some(1));
+    assert_eq!(params.path.as_deref(), Some("repro.mp4"));
+}
+
+#[test]
+fn test_record_tool_definitions() {
+    assert_eq!(RecordStartTool::new().definition().id, "desktop_record_start");
+    assert_eq!(RecordStopTool::new().definition().id, "desktop_record_stop");
+    let _ = RecordStartTool::default();
+    let _ = RecordStopTool::default();
+}
+
+#[tokio::test]
+async fn test_record_start_path_confined() {
+    let work_dir = std::env::temp_dir();
+    let ctx = autohands_protocols::tool::ToolContext::new("test", work_dir);
+    let err = RecordStartTool::new()
+        .execute(serde_json::json!({"path": "/etc/autohands-repro.gif"}), ctx)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Path traversal denied"), "{}", err);
+}
@@ -7,6 +7,7 @@ use tracing::debug;
 use autohands_protocols::error::ToolError;
 use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
 
+use crate::screenshot;
 use crate::window::WindowController;
 
 use super::run_blocking;
@@ -27,7 +28,8 @@ impl WindowListTool {
                 "desktop_window_list",
                 "Desktop Window List",
                 "List all visible windows with their IDs, titles, and positions",
-            ),
+            )
+            .with_read_only(),
         }
     }
 }
@@ -135,6 +137,9 @@ pub struct WindowMoveParams {
     pub x: i32,
     /// New Y position.
     pub y: i32,
+    /// Monitor the position is relative to, by index in
+    /// `desktop_screen_info`; global coordinates if unset.
+    pub monitor: Option<usize>,
 }
 
 /// Move a window.
@@ -148,7 +153,7 @@ impl WindowMoveTool {
             definition: ToolDefinition::new(
                 "desktop_window_move",
                 "Desktop Window Move",
-                "Move a window to a new position",
+                "Move a window to a new position, in global coordinates or relative to a monitor",
             ),
         }
     }
@@ -175,12 +180,15 @@ impl Tool for WindowMoveTool {
             .map_err(|e| ToolError::ExecutionFailed(format!("Invalid params: {}", e)))?;
 
         let id = params.id;
-        let x = params.x;
-        let y = params.y;
 
-        run_blocking(move || {
+        let (x, y) = run_blocking(move || {
+            let (x, y) = screenshot::to_global(params.monitor, params.x, params.y)
+                .map_err(|e| e.to_string())?;
             let controller = WindowController::new().map_err(|e| e.to_string())?;
-            controller.move_window(id, x, y).map_err(|e| e.to_string())
+            controller
+                .move_window(id, x, y)
+                .map_err(|e| e.to_string())?;
+            Ok((x, y))
         })
         .await?;
 
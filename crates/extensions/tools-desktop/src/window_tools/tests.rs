@@ -57,6 +57,7 @@ fn test_window_move_params() {
     assert_eq!(params.id, 123);
     assert_eq!(params.x, 100);
     assert_eq!(params.y, 200);
+    assert!(params.monitor.is_none());
 }
 
 #[test]
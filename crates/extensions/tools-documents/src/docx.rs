@@ -0,0 +1,223 @@
+//! Word (.docx) text, structure and properties.
+
+use std::collections::BTreeMap;
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::Serialize;
+
+use crate::ooxml;
+
+/// A block of a Word document.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Block {
+    Heading {
+        level: u8,
+        text: String,
+    },
+    Paragraph {
+        text: String,
+    },
+    ListItem {
+        text: String,
+    },
+    /// Cells row by row; paragraphs within a cell are joined by newlines.
+    Table {
+        rows: Vec<Vec<String>>,
+    },
+}
+
+impl Block {
+    /// The block as Markdown-like text.
+    pub fn render(&self) -> String {
+        match self {
+            Self::Heading { level, text } => {
+                format!("{} {}", "#".repeat(*level as usize), text)
+            }
+            Self::Paragraph { text } => text.clone(),
+            Self::ListItem { text } => format!("- {}", text),
+            Self::Table { rows } => rows
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|cell| cell.replace('\n', " "))
+                        .collect::<Vec<_>>()
+                        .join(" | ")
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+}
+
+/// Blocks and properties of a Word document.
+#[derive(Debug)]
+pub struct DocxContent {
+    pub blocks: Vec<Block>,
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// Read a .docx file's body and core properties.
+pub fn extract(bytes: Vec<u8>) -> Result<DocxContent, String> {
+    let mut package = ooxml::open(bytes)?;
+    let document = ooxml::part(&mut package, "word/document.xml")?
+        .ok_or_else(|| "Not a Word document: word/document.xml is missing".to_string())?;
+    let blocks = parse_document(&document)?;
+    let metadata = ooxml::part(&mut package, "docProps/core.xml")?
+        .map(|xml| ooxml::core_properties(&xml))
+        .unwrap_or_default();
+    Ok(DocxContent { blocks, metadata })
+}
+
+/// A paragraph being read.
+#[derive(Default)]
+struct Paragraph {
+    text: String,
+    style: Option<String>,
+    /// Whether it has numbering, as list items do.
+    numbered: bool,
+}
+
+impl Paragraph {
+    fn into_block(self, text: String) -> Block {
+        let style = self.style.unwrap_or_default().to_ascii_lowercase();
+        let level = match style.strip_prefix("heading") {
+            Some(level) => level.parse().ok(),
+            None if style == "title" => Some(1),
+            None => None,
+        };
+        match level {
+            Some(level) => Block::Heading { level, text },
+            None if self.numbered || style.starts_with("list") => Block::ListItem { text },
+            None => Block::Paragraph { text },
+        }
+    }
+}
+
+/// A table being read.
+#[derive(Default)]
+struct Table {
+    rows: Vec<Vec<String>>,
+    cell: Option<String>,
+}
+
+/// Blocks of `word/document.xml`.
+pub fn parse_document(xml: &str) -> Result<Vec<Block>, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut blocks = Vec::new();
+    let mut paragraph: Option<Paragraph> = None;
+    let mut tables: Vec<Table> = Vec::new();
+    let mut in_text = false;
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| format!("Invalid word/document.xml: {}", e))?;
+        match event {
+            Event::Start(e) => match e.local_name().as_ref() {
+                b"p" => paragraph = Some(Paragraph::default()),
+                b"t" => in_text = true,
+                b"numPr" => {
+                    if let Some(paragraph) = paragraph.as_mut() {
+                        paragraph.numbered = true;
+                    }
+                }
+                b"tbl" => tables.push(Table::default()),
+                b"tr" => {
+                    if let Some(table) = tables.last_mut() {
+                        table.rows.push(Vec::new());
+                    }
+                }
+                b"tc" => {
+                    if let Some(table) = tables.last_mut() {
+                        table.cell = Some(String::new());
+                    }
+                }
+                _ => {}
+            },
+            Event::Empty(e) => {
+                let Some(paragraph) = paragraph.as_mut() else {
+                    continue;
+                };
+                match e.local_name().as_ref() {
+                    b"pStyle" => paragraph.style = attribute(&e, b"w:val"),
+                    // Tab stops in paragraph properties carry a w:val
+                    b"tab" if attribute(&e, b"w:val").is_none() => paragraph.text.push('\t'),
+                    b"br" | b"cr" => paragraph.text.push('\n'),
+                    _ => {}
+                }
+            }
+            Event::Text(text) if in_text => {
+                if let Some(paragraph) = paragraph.as_mut() {
+                    let text = text
+                        .unescape()
+                        .map_err(|e| format!("Invalid word/document.xml: {}", e))?;
+                    paragraph.text.push_str(&text);
+                }
+            }
+            Event::End(e) => match e.local_name().as_ref() {
+                b"t" => in_text = false,
+                b"p" => {
+                    let Some(finished) = paragraph.take() else {
+                        continue;
+                    };
+                    let text = finished.text.trim().to_string();
+                    if text.is_empty() {
+                        continue;
+                    }
+                    match tables.last_mut().and_then(|table| table.cell.as_mut()) {
+                        Some(cell) => append_line(cell, &text),
+                        None => blocks.push(finished.into_block(text)),
+                    }
+                }
+                b"tc" => {
+                    if let Some(table) = tables.last_mut() {
+                        if let (Some(cell), Some(row)) = (table.cell.take(), table.rows.last_mut())
+                        {
+                            row.push(cell);
+                        }
+                    }
+                }
+                b"tbl" => {
+                    let Some(table) = tables.pop() else {
+                        continue;
+                    };
+                    // A nested table becomes text of the cell holding it
+                    match tables.last_mut().and_then(|outer| outer.cell.as_mut()) {
+                        Some(cell) => {
+                            append_line(cell, &Block::Table { rows: table.rows }.render())
+                        }
+                        None if !table.rows.is_empty() => {
+                            blocks.push(Block::Table { rows: table.rows })
+                        }
+                        None => {}
+                    }
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(blocks)
+}
+
+fn append_line(cell: &mut String, text: &str) {
+    if !cell.is_empty() {
+        cell.push('\n');
+    }
+    cell.push_str(text);
+}
+
+fn attribute(element: &BytesStart, name: &[u8]) -> Option<String> {
+    element
+        .try_get_attribute(name)
+        .ok()
+        .flatten()
+        .and_then(|attr| attr.unescape_value().ok())
+        .map(|value| value.into_owned())
+}
+
+#[cfg(test)]
+#[path = "docx_tests.rs"]
+mod tests;
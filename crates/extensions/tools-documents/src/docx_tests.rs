@@ -0,0 +1,101 @@
+use std::io::{Cursor, Write};
+
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use super::*;
+
+const DOCUMENT: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+  <w:body>
+    <w:p><w:pPr><w:pStyle w:val="Heading1"/></w:pPr><w:r><w:t>Quarterly report</w:t></w:r></w:p>
+    <w:p>
+      <w:pPr><w:tabs><w:tab w:val="left" w:pos="720"/></w:tabs></w:pPr>
+      <w:r><w:t xml:space="preserve">Sales grew </w:t></w:r><w:r><w:t>12%</w:t><w:tab/><w:t>&amp; more</w:t></w:r>
+    </w:p>
+    <w:p><w:pPr><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t>First point</w:t></w:r></w:p>
+    <w:p/>
+    <w:tbl>
+      <w:tr><w:tc><w:p><w:r><w:t>Region</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>Total</w:t></w:r></w:p></w:tc></w:tr>
+      <w:tr><w:tc><w:p><w:r><w:t>North</w:t></w:r></w:p><w:p><w:r><w:t>incl. islands</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>40</w:t></w:r></w:p></w:tc></w:tr>
+    </w:tbl>
+  </w:body>
+</w:document>"#;
+
+const CORE: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:dcterms="http://purl.org/dc/terms/">
+  <dc:title>Q3 Report</dc:title>
+  <dc:creator>Ada</dc:creator>
+  <dcterms:created>2024-10-01T09:00:00Z</dcterms:created>
+  <dc:subject></dc:subject>
+</cp:coreProperties>"#;
+
+/// A .docx package with the given document and core properties.
+fn docx(document: &str, core: Option<&str>) -> Vec<u8> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+    writer.start_file("word/document.xml", options).unwrap();
+    writer.write_all(document.as_bytes()).unwrap();
+    if let Some(core) = core {
+        writer.start_file("docProps/core.xml", options).unwrap();
+        writer.write_all(core.as_bytes()).unwrap();
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+#[test]
+fn test_parse_document() {
+    let blocks = parse_document(DOCUMENT).unwrap();
+    assert_eq!(
+        blocks,
+        vec![
+            Block::Heading {
+                level: 1,
+                text: "Quarterly report".to_string()
+            },
+            Block::Paragraph {
+                text: "Sales grew 12%\t& more".to_string()
+            },
+            Block::ListItem {
+                text: "First point".to_string()
+            },
+            Block::Table {
+                rows: vec![
+                    vec!["Region".to_string(), "Total".to_string()],
+                    vec!["North\nincl. islands".to_string(), "40".to_string()],
+                ]
+            },
+        ]
+    );
+    assert_eq!(blocks[0].render(), "# Quarterly report");
+    assert_eq!(
+        blocks[3].render(),
+        "Region | Total\nNorth incl. islands | 40"
+    );
+}
+
+#[test]
+fn test_extract_with_properties() {
+    let content = extract(docx(DOCUMENT, Some(CORE))).unwrap();
+    assert_eq!(content.blocks.len(), 4);
+    assert_eq!(content.metadata["title"], "Q3 Report");
+    assert_eq!(content.metadata["author"], "Ada");
+    assert_eq!(content.metadata["created"], "2024-10-01T09:00:00Z");
+    assert!(!content.metadata.contains_key("subject"));
+
+    let content = extract(docx(DOCUMENT, None)).unwrap();
+    assert!(content.metadata.is_empty());
+}
+
+#[test]
+fn test_extract_rejects_other_files() {
+    assert!(extract(b"plain text".to_vec())
+        .unwrap_err()
+        .contains("Not an Office document"));
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    writer
+        .start_file("xl/workbook.xml", SimpleFileOptions::default())
+        .unwrap();
+    let bytes = writer.finish().unwrap().into_inner();
+    assert!(extract(bytes).unwrap_err().contains("word/document.xml"));
+}
@@ -0,0 +1,84 @@
+//! Document tools extension definition.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::debug;
+
+use autohands_protocols::error::ExtensionError;
+use autohands_protocols::extension::{Extension, ExtensionContext, ExtensionManifest, Provides};
+use autohands_protocols::types::Version;
+
+use crate::tools::{DocxExtractTool, PdfExtractTextTool, XlsxExtractTool};
+
+/// Document tools extension.
+pub struct DocumentToolsExtension {
+    manifest: ExtensionManifest,
+}
+
+impl DocumentToolsExtension {
+    pub fn new() -> Self {
+        let mut manifest =
+            ExtensionManifest::new("tools-documents", "Document Tools", Version::new(0, 1, 0));
+        manifest.description =
+            "Text and metadata extraction from PDF, Word and Excel files".to_string();
+        manifest.provides = Provides {
+            tools: vec![
+                "pdf_extract_text".to_string(),
+                "docx_extract".to_string(),
+                "xlsx_extract".to_string(),
+            ],
+            ..Default::default()
+        };
+
+        Self { manifest }
+    }
+}
+
+impl Default for DocumentToolsExtension {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Extension for DocumentToolsExtension {
+    fn manifest(&self) -> &ExtensionManifest {
+        &self.manifest
+    }
+
+    async fn initialize(&mut self, ctx: ExtensionContext) -> Result<(), ExtensionError> {
+        debug!("Document tools initialized");
+
+        ctx.tool_registry
+            .register_tool(Arc::new(PdfExtractTextTool::new()))?;
+        ctx.tool_registry
+            .register_tool(Arc::new(DocxExtractTool::new()))?;
+        ctx.tool_registry
+            .register_tool(Arc::new(XlsxExtractTool::new()))?;
+
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extension_manifest() {
+        let ext = DocumentToolsExtension::new();
+        assert_eq!(ext.manifest().id, "tools-documents");
+        assert_eq!(ext.manifest().name, "Document Tools");
+        assert_eq!(ext.manifest().provides.tools.len(), 3);
+    }
+}
@@ -0,0 +1,23 @@
+//! Document tools for AutoHands.
+//!
+//! Provides:
+//! - `pdf_extract_text`: text of all or some pages of a PDF, with the
+//!   document info; pages with almost no text layer are rendered with
+//!   `pdftoppm` and read with the desktop extension's OCR
+//! - `docx_extract`: headings, paragraphs, list items and tables of a Word
+//!   document, with its core properties
+//! - `xlsx_extract`: cell values of Excel and OpenDocument spreadsheets,
+//!   sheet by sheet
+//!
+//! Each call returns at most [`MAX_TEXT_CHARS`] characters of text.
+
+mod docx;
+mod extension;
+mod ooxml;
+mod pdf;
+mod tools;
+mod xlsx;
+
+pub use docx::Block;
+pub use extension::DocumentToolsExtension;
+pub use tools::{DocxExtractTool, PdfExtractTextTool, XlsxExtractTool, MAX_TEXT_CHARS};
@@ -0,0 +1,69 @@
+//! Parts shared by the Office Open XML formats (.docx, .xlsx).
+
+use std::collections::BTreeMap;
+use std::io::{Cursor, Read};
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use zip::ZipArchive;
+
+/// Core properties reported, by element name, with their metadata keys.
+const CORE_PROPERTIES: &[(&str, &str)] = &[
+    ("title", "title"),
+    ("subject", "subject"),
+    ("creator", "author"),
+    ("keywords", "keywords"),
+    ("description", "description"),
+    ("lastModifiedBy", "last_modified_by"),
+    ("revision", "revision"),
+    ("created", "created"),
+    ("modified", "modified"),
+];
+
+/// An Office document's package.
+pub type Package = ZipArchive<Cursor<Vec<u8>>>;
+
+pub fn open(bytes: Vec<u8>) -> Result<Package, String> {
+    ZipArchive::new(Cursor::new(bytes)).map_err(|e| format!("Not an Office document: {}", e))
+}
+
+/// A part of the package as text, if it exists.
+pub fn part(package: &mut Package, name: &str) -> Result<Option<String>, String> {
+    let mut file = match package.by_name(name) {
+        Ok(file) => file,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(format!("Cannot read {}: {}", name, e)),
+    };
+    let mut text = String::new();
+    file.read_to_string(&mut text)
+        .map_err(|e| format!("Cannot read {}: {}", name, e))?;
+    Ok(Some(text))
+}
+
+/// Document properties from `docProps/core.xml`.
+pub fn core_properties(xml: &str) -> BTreeMap<String, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut properties = BTreeMap::new();
+    let mut current = None;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                current = CORE_PROPERTIES
+                    .iter()
+                    .find(|(element, _)| element.as_bytes() == e.local_name().as_ref())
+                    .map(|(_, key)| *key);
+            }
+            Ok(Event::Text(text)) => {
+                if let (Some(key), Ok(value)) = (current, text.unescape()) {
+                    if !value.trim().is_empty() {
+                        properties.insert(key.to_string(), value.trim().to_string());
+                    }
+                }
+            }
+            Ok(Event::End(_)) => current = None,
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    properties
+}
@@ -0,0 +1,161 @@
+//! PDF text, document info and page rendering.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::process::Command;
+
+use lopdf::{Document, Object};
+
+use autohands_tools_desktop::OcrController;
+
+/// Info dictionary entries reported, with their metadata keys.
+const INFO_KEYS: &[(&str, &str)] = &[
+    ("Title", "title"),
+    ("Author", "author"),
+    ("Subject", "subject"),
+    ("Keywords", "keywords"),
+    ("Creator", "creator"),
+    ("Producer", "producer"),
+    ("CreationDate", "created"),
+    ("ModDate", "modified"),
+];
+
+/// Non-blank characters below which a page is taken to be a scan.
+const MIN_TEXT_CHARS: usize = 16;
+
+/// Resolution pages are rendered at for OCR.
+const OCR_DPI: u32 = 200;
+
+/// Text of a PDF, one entry per page, and its document info.
+#[derive(Debug)]
+pub struct PdfText {
+    pub pages: Vec<String>,
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// Extract the text layer of every page.
+pub fn extract(bytes: &[u8]) -> Result<PdfText, String> {
+    let pages = pdf_extract::extract_text_from_mem_by_pages(bytes)
+        .map_err(|e| format!("Cannot read PDF: {}", e))?;
+    let metadata = Document::load_mem(bytes)
+        .map(|doc| metadata(&doc))
+        .unwrap_or_default();
+    Ok(PdfText { pages, metadata })
+}
+
+/// Entries of the document info dictionary.
+fn metadata(doc: &Document) -> BTreeMap<String, String> {
+    let info = match doc.trailer.get(b"Info") {
+        Ok(Object::Reference(id)) => doc.get_dictionary(*id).ok(),
+        Ok(Object::Dictionary(dict)) => Some(dict),
+        _ => None,
+    };
+    let mut metadata = BTreeMap::new();
+    let Some(info) = info else {
+        return metadata;
+    };
+    for (key, name) in INFO_KEYS {
+        if let Ok(Object::String(bytes, _)) = info.get(key.as_bytes()) {
+            let value = decode_text_string(bytes);
+            let value = value.trim();
+            if !value.is_empty() {
+                metadata.insert(name.to_string(), value.to_string());
+            }
+        }
+    }
+    metadata
+}
+
+/// A PDF text string: UTF-16BE or UTF-8 after a byte order mark, else
+/// PDFDocEncoding, read as Latin-1.
+pub fn decode_text_string(bytes: &[u8]) -> String {
+    if let Some(utf16) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        let units: Vec<u16> = utf16
+            .chunks_exact(2)
+            .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+            .collect();
+        return String::from_utf16_lossy(&units);
+    }
+    if let Some(utf8) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        return String::from_utf8_lossy(utf8).into_owned();
+    }
+    bytes.iter().map(|&b| b as char).collect()
+}
+
+/// Page numbers of a spec such as `1-3,7,10-`, in order, for a document
+/// of `count` pages.
+pub fn parse_pages(spec: &str, count: usize) -> Result<Vec<usize>, String> {
+    let mut pages = BTreeSet::new();
+    for part in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (start, end) = match part.split_once('-') {
+            Some((start, end)) if end.trim().is_empty() => (page_number(start)?, count),
+            Some((start, end)) => (page_number(start)?, page_number(end)?),
+            None => {
+                let page = page_number(part)?;
+                (page, page)
+            }
+        };
+        if start == 0 || start > end || end > count {
+            return Err(format!(
+                "Page range '{}' does not fit a document of {} pages",
+                part, count
+            ));
+        }
+        pages.extend(start..=end);
+    }
+    if pages.is_empty() {
+        return Err("No pages given".to_string());
+    }
+    Ok(pages.into_iter().collect())
+}
+
+fn page_number(s: &str) -> Result<usize, String> {
+    s.trim()
+        .parse()
+        .map_err(|_| format!("Invalid page number '{}'", s.trim()))
+}
+
+/// Whether a page's text layer is too thin to be its real content.
+pub fn needs_ocr(text: &str) -> bool {
+    text.chars().filter(|c| !c.is_whitespace()).count() < MIN_TEXT_CHARS
+}
+
+/// Recognize the text of a page rendered as an image.
+pub fn ocr_page(path: &Path, page: usize) -> Result<String, String> {
+    let png = render_page(path, page)?;
+    let controller = OcrController::new().map_err(|e| e.to_string())?;
+    let result = controller
+        .recognize_image(&png)
+        .map_err(|e| e.to_string())?;
+    Ok(result.text)
+}
+
+/// A page as PNG, rendered by poppler's `pdftoppm`.
+fn render_page(path: &Path, page: usize) -> Result<Vec<u8>, String> {
+    let dir = tempfile::tempdir().map_err(|e| e.to_string())?;
+    let prefix = dir.path().join("page");
+    let page = page.to_string();
+    let output = Command::new("pdftoppm")
+        .args(["-f", &page, "-l", &page])
+        .args(["-r", &OCR_DPI.to_string(), "-png", "-singlefile"])
+        .arg(path)
+        .arg(&prefix)
+        .output()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => {
+                "pdftoppm is not installed (install poppler-utils)".to_string()
+            }
+            _ => format!("Cannot run pdftoppm: {}", e),
+        })?;
+    if !output.status.success() {
+        return Err(format!(
+            "pdftoppm failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    std::fs::read(prefix.with_extension("png")).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+#[path = "pdf_tests.rs"]
+mod tests;
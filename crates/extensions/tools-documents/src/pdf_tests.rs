@@ -0,0 +1,31 @@
+use super::*;
+
+#[test]
+fn test_parse_pages() {
+    assert_eq!(parse_pages("1-3, 5", 10).unwrap(), vec![1, 2, 3, 5]);
+    assert_eq!(parse_pages("8-", 10).unwrap(), vec![8, 9, 10]);
+    assert_eq!(parse_pages("3,1,3", 3).unwrap(), vec![1, 3]);
+    assert!(parse_pages("0", 3).is_err());
+    assert!(parse_pages("2-1", 3).is_err());
+    assert!(parse_pages("4", 3).unwrap_err().contains("3 pages"));
+    assert!(parse_pages("one", 3).unwrap_err().contains("'one'"));
+    assert!(parse_pages(" , ", 3).is_err());
+}
+
+#[test]
+fn test_decode_text_string() {
+    assert_eq!(decode_text_string(b"Report"), "Report");
+    assert_eq!(
+        decode_text_string(&[0xFE, 0xFF, 0x00, 0x48, 0x00, 0xE9]),
+        "Hé"
+    );
+    assert_eq!(decode_text_string(&[0xEF, 0xBB, 0xBF, b'o', b'k']), "ok");
+    assert_eq!(decode_text_string(&[0xE9]), "é");
+}
+
+#[test]
+fn test_needs_ocr() {
+    assert!(needs_ocr(""));
+    assert!(needs_ocr("  \n 12 \x0c"));
+    assert!(!needs_ocr("Quarterly report for the sales team"));
+}
@@ -0,0 +1,107 @@
+//! Word document extraction tool.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::types::RiskLevel;
+
+use super::{input_file, run_blocking, TextBudget, MAX_TEXT_CHARS};
+use crate::docx;
+
+/// Parameters for docx_extract.
+#[derive(Debug, Deserialize)]
+struct DocxParams {
+    /// Word file.
+    path: String,
+}
+
+/// Tool extracting the text, headings and tables of a Word document.
+pub struct DocxExtractTool {
+    definition: ToolDefinition,
+}
+
+impl DocxExtractTool {
+    /// Create a new Word extraction tool.
+    pub fn new() -> Self {
+        let definition = ToolDefinition::new(
+            "docx_extract",
+            "Extract Word Document",
+            "Extract the text of a Word (.docx) document as headings, paragraphs, list items and tables, with its title, author and dates",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Word (.docx) file"
+                }
+            },
+            "required": ["path"]
+        }))
+        .with_risk_level(RiskLevel::Low)
+        .with_read_only();
+
+        Self { definition }
+    }
+}
+
+impl Default for DocxExtractTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Tool for DocxExtractTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: DocxParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
+        let path = input_file(&ctx, &params.path)?;
+
+        let content = run_blocking(move || {
+            let bytes = std::fs::read(&path)?;
+            docx::extract(bytes).map_err(ToolError::ExecutionFailed)
+        })
+        .await?;
+
+        let mut budget = TextBudget::new();
+        let mut text: Vec<String> = content
+            .metadata
+            .iter()
+            .map(|(key, value)| format!("{}: {}", key, value))
+            .collect();
+        let mut blocks = Vec::new();
+        for block in content.blocks {
+            if budget.exhausted {
+                break;
+            }
+            text.push(budget.take(&block.render()));
+            blocks.push(block);
+        }
+        if budget.exhausted {
+            text.push(format!("[Text cut at {} characters]", MAX_TEXT_CHARS));
+        }
+        if blocks.is_empty() {
+            text.push("The document has no text".to_string());
+        }
+
+        Ok(ToolResult::success_json(
+            text.join("\n\n"),
+            serde_json::json!({
+                "metadata": content.metadata,
+                "blocks": blocks,
+                "truncated": budget.exhausted,
+            }),
+        ))
+    }
+}
@@ -0,0 +1,73 @@
+//! Document tool implementations.
+
+mod docx;
+mod pdf;
+mod xlsx;
+
+pub use docx::DocxExtractTool;
+pub use pdf::PdfExtractTextTool;
+pub use xlsx::XlsxExtractTool;
+
+use std::path::PathBuf;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{PathAccess, ToolContext};
+
+/// Characters of document text one call returns at most.
+pub const MAX_TEXT_CHARS: usize = 100_000;
+
+/// Room left for text in one result.
+struct TextBudget {
+    left: usize,
+    /// Whether text has been cut to fit.
+    exhausted: bool,
+}
+
+impl TextBudget {
+    fn new() -> Self {
+        Self {
+            left: MAX_TEXT_CHARS,
+            exhausted: false,
+        }
+    }
+
+    /// `text`, cut to the room left.
+    fn take(&mut self, text: &str) -> String {
+        let count = text.chars().count();
+        if count <= self.left {
+            self.left -= count;
+            return text.to_string();
+        }
+        self.exhausted = true;
+        let cut = text.chars().take(self.left).collect();
+        self.left = 0;
+        cut
+    }
+}
+
+/// Run document parsing off the async runtime; a parser panicking on a
+/// malformed file becomes an error.
+async fn run_blocking<F, T>(f: F) -> Result<T, ToolError>
+where
+    F: FnOnce() -> Result<T, ToolError> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| ToolError::ExecutionFailed(format!("Document parser failed: {}", e)))?
+}
+
+/// An existing file to read.
+fn input_file(ctx: &ToolContext, path: &str) -> Result<PathBuf, ToolError> {
+    let resolved = ctx.resolve_path(path, PathAccess::Read)?;
+    if !resolved.is_file() {
+        return Err(ToolError::ResourceNotFound(format!(
+            "File not found: {}",
+            resolved.display()
+        )));
+    }
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests;
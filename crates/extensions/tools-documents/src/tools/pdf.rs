@@ -0,0 +1,223 @@
+//! PDF text extraction tool.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::types::RiskLevel;
+
+use super::{input_file, run_blocking, TextBudget, MAX_TEXT_CHARS};
+use crate::pdf::{self, needs_ocr, parse_pages};
+
+/// When pages are OCR'd.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum OcrMode {
+    /// Pages with hardly any text layer, such as scans.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+/// Parameters for pdf_extract_text.
+#[derive(Debug, Deserialize)]
+struct PdfParams {
+    /// PDF file.
+    path: String,
+    /// Pages to read, e.g. `1-3,7`.
+    #[serde(default)]
+    pages: Option<String>,
+    #[serde(default)]
+    ocr: OcrMode,
+}
+
+/// Text of one page.
+#[derive(Debug, Serialize)]
+struct PageText {
+    page: usize,
+    text: String,
+    /// Whether the text was recognized from the rendered page.
+    ocr: bool,
+}
+
+/// Pages read from a PDF.
+#[derive(Debug, Serialize)]
+struct PdfExtraction {
+    page_count: usize,
+    metadata: BTreeMap<String, String>,
+    pages: Vec<PageText>,
+    warnings: Vec<String>,
+    truncated: bool,
+}
+
+impl PdfExtraction {
+    fn render(&self) -> String {
+        let mut out = vec![format!("PDF with {} pages", self.page_count)];
+        out.extend(
+            self.metadata
+                .iter()
+                .map(|(key, value)| format!("{}: {}", key, value)),
+        );
+        for page in &self.pages {
+            let source = if page.ocr { " (OCR)" } else { "" };
+            out.push(format!(
+                "\n--- Page {}{} ---\n{}",
+                page.page, source, page.text
+            ));
+        }
+        if self.truncated {
+            out.push(format!(
+                "\n[Text cut at {} characters; ask for fewer pages]",
+                MAX_TEXT_CHARS
+            ));
+        }
+        out.extend(self.warnings.iter().map(|w| format!("Note: {}", w)));
+        out.join("\n")
+    }
+}
+
+/// Tool extracting the text of PDF pages.
+pub struct PdfExtractTextTool {
+    definition: ToolDefinition,
+}
+
+impl PdfExtractTextTool {
+    /// Create a new PDF text tool.
+    pub fn new() -> Self {
+        let definition = ToolDefinition::new(
+            "pdf_extract_text",
+            "Extract PDF Text",
+            "Extract the text of a PDF, page by page, with its title, author and dates. Scanned pages without a text layer are read with OCR (needs pdftoppm and an OCR engine)",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "PDF file"
+                },
+                "pages": {
+                    "type": "string",
+                    "description": "Pages to read, e.g. \"1-3,7\" or \"10-\" (default: all)"
+                },
+                "ocr": {
+                    "type": "string",
+                    "enum": ["auto", "always", "never"],
+                    "description": "OCR pages: auto (pages with almost no text, the default), always, or never"
+                }
+            },
+            "required": ["path"]
+        }))
+        .with_risk_level(RiskLevel::Low)
+        .with_read_only()
+        .with_example(
+            "Read the first pages of a contract",
+            serde_json::json!({"path": "docs/contract.pdf", "pages": "1-3"}),
+        );
+
+        Self { definition }
+    }
+}
+
+impl Default for PdfExtractTextTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Tool for PdfExtractTextTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: PdfParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
+        let path = input_file(&ctx, &params.path)?;
+
+        let extraction =
+            run_blocking(move || extract_pages(&path, params.pages.as_deref(), params.ocr)).await?;
+
+        Ok(ToolResult::success_json(
+            extraction.render(),
+            serde_json::to_value(&extraction).unwrap_or_default(),
+        ))
+    }
+}
+
+/// Read the selected pages, OCR'ing those `ocr` calls for.
+fn extract_pages(
+    path: &Path,
+    pages: Option<&str>,
+    ocr: OcrMode,
+) -> Result<PdfExtraction, ToolError> {
+    let bytes = std::fs::read(path)?;
+    let text = pdf::extract(&bytes).map_err(ToolError::ExecutionFailed)?;
+    let page_count = text.pages.len();
+    let selected = match pages {
+        Some(spec) => parse_pages(spec, page_count).map_err(ToolError::InvalidParameters)?,
+        None => (1..=page_count).collect(),
+    };
+
+    let mut extraction = PdfExtraction {
+        page_count,
+        metadata: text.metadata,
+        pages: Vec::new(),
+        warnings: Vec::new(),
+        truncated: false,
+    };
+    let mut budget = TextBudget::new();
+    // Once OCR fails in auto mode, later pages keep their text layer
+    let mut ocr_failed = false;
+    for page in selected {
+        if budget.exhausted {
+            break;
+        }
+        let mut content = text.pages[page - 1].trim().to_string();
+        let wanted = match ocr {
+            OcrMode::Always => true,
+            OcrMode::Auto => !ocr_failed && needs_ocr(&content),
+            OcrMode::Never => false,
+        };
+        let mut recognized = false;
+        if wanted {
+            match pdf::ocr_page(path, page) {
+                Ok(ocr_text) => {
+                    recognized = true;
+                    if !ocr_text.trim().is_empty() {
+                        content = ocr_text.trim().to_string();
+                    }
+                }
+                Err(e) if ocr == OcrMode::Always => {
+                    return Err(ToolError::ExecutionFailed(format!(
+                        "OCR of page {} failed: {}",
+                        page, e
+                    )))
+                }
+                Err(e) => {
+                    ocr_failed = true;
+                    extraction
+                        .warnings
+                        .push(format!("pages without a text layer were not OCR'd: {}", e));
+                }
+            }
+        }
+        extraction.pages.push(PageText {
+            page,
+            text: budget.take(&content),
+            ocr: recognized,
+        });
+    }
+    extraction.truncated = budget.exhausted;
+    Ok(extraction)
+}
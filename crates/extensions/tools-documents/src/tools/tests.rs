@@ -0,0 +1,101 @@
+//! Tests for the document tools.
+
+use std::io::{Cursor, Write};
+
+use serde_json::json;
+use tempfile::TempDir;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{Tool, ToolContext};
+
+use super::*;
+
+fn ctx(dir: &TempDir) -> ToolContext {
+    ToolContext::new("test", dir.path().to_path_buf())
+}
+
+/// Write a .docx whose body is `body` to `report.docx`.
+fn write_docx(dir: &TempDir, body: &str) {
+    let document = format!(
+        r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>{}</w:body></w:document>"#,
+        body
+    );
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    writer
+        .start_file("word/document.xml", SimpleFileOptions::default())
+        .unwrap();
+    writer.write_all(document.as_bytes()).unwrap();
+    let bytes = writer.finish().unwrap().into_inner();
+    std::fs::write(dir.path().join("report.docx"), bytes).unwrap();
+}
+
+#[tokio::test]
+async fn test_docx_extract() {
+    let dir = TempDir::new().unwrap();
+    write_docx(
+        &dir,
+        r#"<w:p><w:pPr><w:pStyle w:val="Heading2"/></w:pPr><w:r><w:t>Summary</w:t></w:r></w:p><w:p><w:r><w:t>All good.</w:t></w:r></w:p>"#,
+    );
+
+    let result = DocxExtractTool::new()
+        .execute(json!({"path": "report.docx"}), ctx(&dir))
+        .await
+        .unwrap();
+    assert_eq!(result.content, "## Summary\n\nAll good.");
+    let output = result.structured_output.unwrap();
+    assert_eq!(output["blocks"][0]["type"], "heading");
+    assert_eq!(output["blocks"][0]["level"], 2);
+    assert_eq!(output["truncated"], false);
+}
+
+#[tokio::test]
+async fn test_docx_extract_cuts_long_text() {
+    let dir = TempDir::new().unwrap();
+    let paragraph = format!("<w:p><w:r><w:t>{}</w:t></w:r></w:p>", "x".repeat(60_000));
+    write_docx(&dir, &paragraph.repeat(3));
+
+    let result = DocxExtractTool::new()
+        .execute(json!({"path": "report.docx"}), ctx(&dir))
+        .await
+        .unwrap();
+    let output = result.structured_output.unwrap();
+    assert_eq!(output["truncated"], true);
+    assert_eq!(output["blocks"].as_array().unwrap().len(), 2);
+    assert!(result.content.ends_with("[Text cut at 100000 characters]"));
+}
+
+#[tokio::test]
+async fn test_missing_and_invalid_files() {
+    let dir = TempDir::new().unwrap();
+    let err = PdfExtractTextTool::new()
+        .execute(json!({"path": "missing.pdf"}), ctx(&dir))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ToolError::ResourceNotFound(_)));
+
+    std::fs::write(dir.path().join("notes.txt"), "plain text").unwrap();
+    for tool in [
+        Box::new(PdfExtractTextTool::new()) as Box<dyn Tool>,
+        Box::new(DocxExtractTool::new()),
+        Box::new(XlsxExtractTool::new()),
+    ] {
+        let err = tool
+            .execute(json!({"path": "notes.txt"}), ctx(&dir))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ToolError::ExecutionFailed(_)), "{:?}", err);
+    }
+}
+
+#[test]
+fn test_text_budget() {
+    let mut budget = TextBudget::new();
+    assert_eq!(budget.take("abc"), "abc");
+    assert!(!budget.exhausted);
+    let rest = budget.take(&"y".repeat(MAX_TEXT_CHARS));
+    assert_eq!(rest.len(), MAX_TEXT_CHARS - 3);
+    assert!(budget.exhausted);
+    assert_eq!(budget.take("more"), "");
+}
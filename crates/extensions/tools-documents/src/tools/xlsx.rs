@@ -0,0 +1,145 @@
+//! Spreadsheet extraction tool.
+
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::types::RiskLevel;
+
+use super::{input_file, run_blocking, TextBudget, MAX_TEXT_CHARS};
+use crate::{ooxml, xlsx};
+
+/// Rows read per sheet without `max_rows`.
+const DEFAULT_MAX_ROWS: usize = 200;
+
+/// Most rows read per sheet.
+const MAX_ROWS: usize = 5000;
+
+/// Parameters for xlsx_extract.
+#[derive(Debug, Deserialize)]
+struct XlsxParams {
+    /// Workbook file.
+    path: String,
+    /// Sheet to read; all when absent.
+    #[serde(default)]
+    sheet: Option<String>,
+    #[serde(default)]
+    max_rows: Option<usize>,
+}
+
+/// Tool extracting the cells of spreadsheet sheets.
+pub struct XlsxExtractTool {
+    definition: ToolDefinition,
+}
+
+impl XlsxExtractTool {
+    /// Create a new spreadsheet extraction tool.
+    pub fn new() -> Self {
+        let definition = ToolDefinition::new(
+            "xlsx_extract",
+            "Extract Spreadsheet",
+            "Extract the cell values of an Excel (.xlsx, .xlsm, .xls) or OpenDocument (.ods) workbook, sheet by sheet, with the sheet sizes and document properties. Formulas are read as their last computed values",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Workbook file"
+                },
+                "sheet": {
+                    "type": "string",
+                    "description": "Sheet to read (default: all sheets)"
+                },
+                "max_rows": {
+                    "type": "integer",
+                    "description": "Rows to read per sheet (default 200, at most 5000)"
+                }
+            },
+            "required": ["path"]
+        }))
+        .with_risk_level(RiskLevel::Low)
+        .with_read_only();
+
+        Self { definition }
+    }
+}
+
+impl Default for XlsxExtractTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Tool for XlsxExtractTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: XlsxParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
+        let path = input_file(&ctx, &params.path)?;
+        let max_rows = params
+            .max_rows
+            .unwrap_or(DEFAULT_MAX_ROWS)
+            .clamp(1, MAX_ROWS);
+        let names: Vec<String> = params.sheet.into_iter().collect();
+
+        let (sheets, metadata) = run_blocking(move || {
+            let sheets =
+                xlsx::read_sheets(&path, &names, max_rows).map_err(ToolError::ExecutionFailed)?;
+            Ok((sheets, properties(&path)))
+        })
+        .await?;
+
+        let mut budget = TextBudget::new();
+        let mut text: Vec<String> = metadata
+            .iter()
+            .map(|(key, value)| format!("{}: {}", key, value))
+            .collect();
+        for sheet in &sheets {
+            if budget.exhausted {
+                break;
+            }
+            text.push(budget.take(&sheet.render()));
+        }
+        if budget.exhausted {
+            text.push(format!(
+                "[Text cut at {} characters; read one sheet or fewer rows]",
+                MAX_TEXT_CHARS
+            ));
+        }
+
+        Ok(ToolResult::success_json(
+            text.join("\n\n"),
+            serde_json::json!({
+                "metadata": metadata,
+                "sheets": sheets,
+                "truncated": budget.exhausted,
+            }),
+        ))
+    }
+}
+
+/// Core properties of an Office Open XML workbook; none for other formats.
+fn properties(path: &std::path::Path) -> BTreeMap<String, String> {
+    std::fs::read(path)
+        .ok()
+        .and_then(|bytes| ooxml::open(bytes).ok())
+        .and_then(|mut package| {
+            ooxml::part(&mut package, "docProps/core.xml")
+                .ok()
+                .flatten()
+        })
+        .map(|xml| ooxml::core_properties(&xml))
+        .unwrap_or_default()
+}
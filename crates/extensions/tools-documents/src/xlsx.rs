@@ -0,0 +1,111 @@
+//! Spreadsheet (.xlsx, .xlsm, .xls, .ods) cells.
+
+use std::path::Path;
+
+use calamine::{open_workbook_auto, Data, DataType, Reader};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Cells of one sheet, up to a row limit.
+#[derive(Debug, Serialize)]
+pub struct Sheet {
+    pub name: String,
+    /// Rows of the used range.
+    pub total_rows: usize,
+    /// Columns of the used range.
+    pub columns: usize,
+    pub rows: Vec<Vec<Value>>,
+    /// Whether rows were left out at the limit.
+    pub truncated: bool,
+}
+
+impl Sheet {
+    /// The sheet as a heading and one line per row.
+    pub fn render(&self) -> String {
+        let mut out = vec![format!(
+            "## {} ({} rows x {} columns)",
+            self.name, self.total_rows, self.columns
+        )];
+        out.extend(self.rows.iter().map(|row| {
+            row.iter()
+                .map(cell_text)
+                .collect::<Vec<_>>()
+                .join(" | ")
+                .trim_end()
+                .to_string()
+        }));
+        if self.truncated {
+            out.push(format!(
+                "({} of {} rows shown)",
+                self.rows.len(),
+                self.total_rows
+            ));
+        }
+        out.join("\n")
+    }
+}
+
+/// The named sheets (all when `names` is empty), each cut to `max_rows`
+/// rows.
+pub fn read_sheets(path: &Path, names: &[String], max_rows: usize) -> Result<Vec<Sheet>, String> {
+    let mut workbook =
+        open_workbook_auto(path).map_err(|e| format!("Cannot read workbook: {}", e))?;
+    let available = workbook.sheet_names();
+    for name in names {
+        if !available.contains(name) {
+            return Err(format!(
+                "No sheet '{}' (sheets: {})",
+                name,
+                available.join(", ")
+            ));
+        }
+    }
+    let wanted = if names.is_empty() { &available } else { names };
+    let mut sheets = Vec::new();
+    for name in wanted {
+        let range = workbook
+            .worksheet_range(name)
+            .map_err(|e| format!("Cannot read sheet '{}': {}", name, e))?;
+        let (total_rows, columns) = range.get_size();
+        sheets.push(Sheet {
+            name: name.clone(),
+            total_rows,
+            columns,
+            rows: range
+                .rows()
+                .take(max_rows)
+                .map(|row| row.iter().map(cell_value).collect())
+                .collect(),
+            truncated: total_rows > max_rows,
+        });
+    }
+    Ok(sheets)
+}
+
+/// A cell as JSON; dates become ISO 8601 text.
+pub fn cell_value(cell: &Data) -> Value {
+    match cell {
+        Data::Empty => Value::Null,
+        Data::Int(i) => Value::from(*i),
+        Data::Float(f) => Value::from(*f),
+        Data::Bool(b) => Value::from(*b),
+        Data::String(s) | Data::DateTimeIso(s) | Data::DurationIso(s) => Value::from(s.as_str()),
+        Data::DateTime(_) => match cell.as_datetime() {
+            Some(datetime) => Value::from(datetime.format("%Y-%m-%dT%H:%M:%S").to_string()),
+            None => Value::from(cell.to_string()),
+        },
+        Data::Error(_) => Value::from(cell.to_string()),
+    }
+}
+
+fn cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.replace('\n', " "),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+#[path = "xlsx_tests.rs"]
+mod tests;
@@ -0,0 +1,40 @@
+use calamine::CellErrorType;
+
+use super::*;
+
+#[test]
+fn test_cell_value() {
+    assert_eq!(cell_value(&Data::Empty), Value::Null);
+    assert_eq!(cell_value(&Data::Int(7)), Value::from(7));
+    assert_eq!(cell_value(&Data::Float(2.5)), Value::from(2.5));
+    assert_eq!(cell_value(&Data::Bool(true)), Value::from(true));
+    assert_eq!(cell_value(&Data::String("a".to_string())), Value::from("a"));
+    assert_eq!(
+        cell_value(&Data::Error(CellErrorType::Div0)),
+        Value::from("#DIV/0!")
+    );
+}
+
+#[test]
+fn test_render() {
+    let sheet = Sheet {
+        name: "Sales".to_string(),
+        total_rows: 3,
+        columns: 2,
+        rows: vec![
+            vec![Value::from("Region"), Value::from("Total")],
+            vec![Value::from("North"), Value::Null],
+        ],
+        truncated: true,
+    };
+    assert_eq!(
+        sheet.render(),
+        "## Sales (3 rows x 2 columns)\nRegion | Total\nNorth |\n(2 of 3 rows shown)"
+    );
+}
+
+#[test]
+fn test_missing_workbook() {
+    let err = read_sheets(Path::new("/nonexistent/book.xlsx"), &[], 10).unwrap_err();
+    assert!(err.contains("Cannot read workbook"));
+}
@@ -1,19 +1,31 @@
 //! Filesystem extension definition.
 
 use std::any::Any;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use serde::Deserialize;
 
 use autohands_protocols::error::ExtensionError;
 use autohands_protocols::extension::{Extension, ExtensionContext, ExtensionManifest, Provides};
 use autohands_protocols::types::Version;
 
 use crate::tools::{
-    CreateDirectoryTool, DeleteFileTool, EditFileTool, ListDirectoryTool, MoveFileTool,
-    ReadFileTool, WriteFileTool,
+    ApplyPatchTool, CreateDirectoryTool, DeleteFileTool, EditFileTool, ListDirectoryTool,
+    MoveFileTool, ReadFileTool, SnapshotStore, WorkspaceCommitTool, WorkspaceDiffTool,
+    WorkspaceUndoTool, WriteFileTool,
 };
 
+/// Settings of the extension (`[extensions.config.tools-filesystem]`).
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct FilesystemConfig {
+    /// Where the originals of changed files are kept for `workspace_undo`;
+    /// `~/.autohands/snapshots` by default.
+    pub snapshot_dir: Option<PathBuf>,
+}
+
 /// Filesystem extension providing file operation tools.
 pub struct FilesystemExtension {
     manifest: ExtensionManifest,
@@ -27,16 +39,20 @@ impl FilesystemExtension {
             Version::new(0, 1, 0),
         );
         manifest.description =
-            "File system operations: read, write, edit, list, create, delete, move".to_string();
+            "File system operations: read, write, edit, patch, list, create, delete, move, and undoing changes".to_string();
         manifest.provides = Provides {
             tools: vec![
                 "read_file".to_string(),
                 "write_file".to_string(),
                 "edit_file".to_string(),
+                "apply_patch".to_string(),
                 "list_directory".to_string(),
                 "create_directory".to_string(),
                 "delete_file".to_string(),
                 "move_file".to_string(),
+                "workspace_diff".to_string(),
+                "workspace_undo".to_string(),
+                "workspace_commit".to_string(),
             ],
             ..Default::default()
         };
@@ -58,21 +74,45 @@ impl Extension for FilesystemExtension {
     }
 
     async fn initialize(&mut self, ctx: ExtensionContext) -> Result<(), ExtensionError> {
+        let config: FilesystemConfig = if ctx.config.is_null() {
+            FilesystemConfig::default()
+        } else {
+            serde_json::from_value(ctx.config.clone()).map_err(|e| {
+                ExtensionError::InitializationFailed(format!(
+                    "Invalid tools-filesystem config: {}",
+                    e
+                ))
+            })?
+        };
+        // Tools changing files snapshot them first, for workspace_undo
+        let snapshots = Arc::new(match config.snapshot_dir {
+            Some(dir) => SnapshotStore::new(dir),
+            None => SnapshotStore::default(),
+        });
+
         // Register tools
         ctx.tool_registry
             .register_tool(Arc::new(ReadFileTool::new()))?;
         ctx.tool_registry
-            .register_tool(Arc::new(WriteFileTool::new()))?;
+            .register_tool(Arc::new(WriteFileTool::new().with_snapshots(snapshots.clone())))?;
         ctx.tool_registry
-            .register_tool(Arc::new(EditFileTool::new()))?;
+            .register_tool(Arc::new(EditFileTool::new().with_snapshots(snapshots.clone())))?;
+        ctx.tool_registry
+            .register_tool(Arc::new(ApplyPatchTool::new().with_snapshots(snapshots.clone())))?;
         ctx.tool_registry
             .register_tool(Arc::new(ListDirectoryTool::new()))?;
         ctx.tool_registry
             .register_tool(Arc::new(CreateDirectoryTool::new()))?;
         ctx.tool_registry
-            .register_tool(Arc::new(DeleteFileTool::new()))?;
+            .register_tool(Arc::new(DeleteFileTool::new().with_snapshots(snapshots.clone())))?;
+        ctx.tool_registry
+            .register_tool(Arc::new(MoveFileTool::new().with_snapshots(snapshots.clone())))?;
+        ctx.tool_registry
+            .register_tool(Arc::new(WorkspaceDiffTool::new(snapshots.clone())))?;
+        ctx.tool_registry
+            .register_tool(Arc::new(WorkspaceUndoTool::new(snapshots.clone())))?;
         ctx.tool_registry
-            .register_tool(Arc::new(MoveFileTool::new()))?;
+            .register_tool(Arc::new(WorkspaceCommitTool::new(snapshots)))?;
 
         Ok(())
     }
@@ -109,14 +149,16 @@ mod tests {
         let ext = FilesystemExtension::new();
         let tools = &ext.manifest().provides.tools;
 
-        assert_eq!(tools.len(), 7);
+        assert_eq!(tools.len(), 11);
         assert!(tools.contains(&"read_file".to_string()));
         assert!(tools.contains(&"write_file".to_string()));
         assert!(tools.contains(&"edit_file".to_string()));
+        assert!(tools.contains(&"apply_patch".to_string()));
         assert!(tools.contains(&"list_directory".to_string()));
         assert!(tools.contains(&"create_directory".to_string()));
         assert!(tools.contains(&"delete_file".to_string()));
         assert!(tools.contains(&"move_file".to_string()));
+        assert!(tools.contains(&"workspace_undo".to_string()));
     }
 
     #[test]
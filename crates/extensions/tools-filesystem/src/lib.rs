@@ -1,9 +1,10 @@
 //! File system tools for AutoHands.
 //!
-//! Provides tools for reading, writing, editing, and managing files.
+//! Provides tools for reading, writing, editing, patching, and managing files,
+//! and for reviewing and undoing the changes they made to a workspace.
 
 mod tools;
 mod extension;
 
-pub use extension::FilesystemExtension;
+pub use extension::{FilesystemConfig, FilesystemExtension};
 pub use tools::*;
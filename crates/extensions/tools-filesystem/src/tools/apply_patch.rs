@@ -0,0 +1,443 @@
+//! Apply patch tool (unified diffs and search/replace blocks).
+
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{PathAccess, Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::types::RiskLevel;
+
+use super::patch::{self, ChangeStats, Patch, PatchError};
+use super::snapshot::SnapshotStore;
+
+/// Parameters for apply_patch tool.
+#[derive(Debug, Deserialize)]
+struct ApplyPatchParams {
+    /// Unified diff or search/replace blocks.
+    patch: String,
+    /// File for search/replace blocks that name none.
+    #[serde(default)]
+    path: Option<String>,
+    /// Copy changed files to the backup directory first.
+    #[serde(default = "default_backup")]
+    backup: bool,
+}
+
+fn default_backup() -> bool {
+    true
+}
+
+/// A file changed by the patch.
+struct FileChange {
+    /// Path as written in the patch.
+    name: String,
+    path: PathBuf,
+    /// Content before the patch; `None` if the file does not exist.
+    original: Option<String>,
+    /// Content after the patch; `None` if the file is deleted.
+    patched: Option<String>,
+    stats: ChangeStats,
+}
+
+/// Apply patch tool implementation.
+///
+/// Every hunk is matched against its file before anything is written, so
+/// a patch applies completely or not at all. Changed files are copied to
+/// a per-call directory under the backup directory first.
+pub struct ApplyPatchTool {
+    definition: ToolDefinition,
+    backup_dir: PathBuf,
+    snapshots: Option<Arc<SnapshotStore>>,
+}
+
+impl ApplyPatchTool {
+    pub fn new() -> Self {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "patch": {
+                    "type": "string",
+                    "description": "A unified diff (as from `git diff`), or search/replace blocks: the file path on its own line, then `<<<<<<< SEARCH`, the exact text to find, `=======`, the replacement and `>>>>>>> REPLACE`"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "File for search/replace blocks not preceded by a path"
+                },
+                "backup": {
+                    "type": "boolean",
+                    "description": "Back up changed files before writing (default: true)"
+                }
+            },
+            "required": ["patch"]
+        });
+
+        Self {
+            definition: ToolDefinition::new(
+                "apply_patch",
+                "Apply Patch",
+                "Apply a multi-hunk patch to one or more files atomically, failing on conflicts",
+            )
+            .with_parameters_schema(schema)
+            .with_risk_level(RiskLevel::Medium),
+            backup_dir: dirs::home_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join(".autohands")
+                .join("backups")
+                .join("patches"),
+            snapshots: None,
+        }
+    }
+
+    /// Keep backups under `dir` instead of `~/.autohands/backups/patches`.
+    pub fn with_backup_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.backup_dir = dir.into();
+        self
+    }
+
+    /// Snapshot the patched files, so `workspace_undo` can revert the patch.
+    pub fn with_snapshots(mut self, snapshots: Arc<SnapshotStore>) -> Self {
+        self.snapshots = Some(snapshots);
+        self
+    }
+
+    /// Work out the new content of every file the patch touches.
+    async fn plan(
+        &self,
+        patch: Patch,
+        default_path: Option<&str>,
+        ctx: &ToolContext,
+    ) -> Result<Vec<FileChange>, ToolError> {
+        let mut plan = Plan::default();
+        match patch {
+            Patch::Unified(files) => {
+                for file in files {
+                    let source = file.old_path.as_deref().unwrap_or(file.path()).to_string();
+                    let index = plan.open(&source, ctx).await?;
+                    let content = match (&file.old_path, plan.changes[index].patched.clone()) {
+                        (Some(_), None) => return Err(conflict(&source, "file does not exist")),
+                        (None, Some(content)) if !content.is_empty() => {
+                            return Err(conflict(&source, "file to create already exists"));
+                        }
+                        (_, content) => content.unwrap_or_default(),
+                    };
+                    let (patched, stats) = patch::apply_hunks(&content, &file.hunks)
+                        .map_err(|e| in_file(&source, e))?;
+
+                    let target = match &file.new_path {
+                        None => {
+                            if !patched.is_empty() {
+                                return Err(conflict(&source, "file to delete has other content"));
+                            }
+                            plan.changes[index].patched = None;
+                            index
+                        }
+                        // A rename writes the new path and deletes the old one
+                        Some(new) if file.old_path.as_ref().is_some_and(|old| old != new) => {
+                            let target = plan.open(new, ctx).await?;
+                            if plan.changes[target].patched.is_some() {
+                                return Err(conflict(new, "rename target already exists"));
+                            }
+                            plan.changes[index].patched = None;
+                            plan.changes[target].patched = Some(patched);
+                            target
+                        }
+                        Some(_) => {
+                            plan.changes[index].patched = Some(patched);
+                            index
+                        }
+                    };
+                    add_stats(&mut plan.changes[target].stats, stats);
+                }
+            }
+            Patch::SearchReplace(blocks) => {
+                for block in blocks {
+                    let name = block
+                        .path
+                        .as_deref()
+                        .or(default_path)
+                        .ok_or_else(|| {
+                            ToolError::InvalidParameters(
+                                "SEARCH block names no file; put its path on the line before it or pass `path`".to_string(),
+                            )
+                        })?
+                        .to_string();
+                    let index = plan.open(&name, ctx).await?;
+                    let change = &mut plan.changes[index];
+                    if change.patched.is_none() && !block.search.is_empty() {
+                        return Err(conflict(&name, "file does not exist"));
+                    }
+                    let content = change.patched.clone().unwrap_or_default();
+                    let (patched, stats) = patch::apply_search_replace(&content, &block)
+                        .map_err(|e| in_file(&name, e))?;
+                    change.patched = Some(patched);
+                    add_stats(&mut change.stats, stats);
+                }
+            }
+        }
+        Ok(plan
+            .changes
+            .into_iter()
+            .filter(|change| change.original != change.patched)
+            .collect())
+    }
+
+    /// Copy the files about to change into a new backup directory.
+    async fn back_up(&self, changes: &[FileChange]) -> Result<Option<PathBuf>, ToolError> {
+        if changes.iter().all(|change| change.original.is_none()) {
+            return Ok(None);
+        }
+        let millis = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        tokio::fs::create_dir_all(&self.backup_dir).await?;
+        let mut dir = self.backup_dir.join(millis.to_string());
+        let mut attempt = 0;
+        // Another call may have started in the same millisecond
+        while let Err(e) = tokio::fs::create_dir(&dir).await {
+            if e.kind() != std::io::ErrorKind::AlreadyExists {
+                return Err(e.into());
+            }
+            attempt += 1;
+            dir = self.backup_dir.join(format!("{}-{}", millis, attempt));
+        }
+        for change in changes {
+            if let Some(original) = &change.original {
+                let backup = dir.join(relative(&change.path));
+                if let Some(parent) = backup.parent() {
+                    tokio::fs::create_dir_all(parent).await?;
+                }
+                tokio::fs::write(&backup, original).await?;
+            }
+        }
+        Ok(Some(dir))
+    }
+}
+
+impl Default for ApplyPatchTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Files opened while planning a patch, in patch order.
+#[derive(Default)]
+struct Plan {
+    changes: Vec<FileChange>,
+    by_path: HashMap<PathBuf, usize>,
+}
+
+impl Plan {
+    /// Index of the change to a file, reading it the first time.
+    async fn open(&mut self, name: &str, ctx: &ToolContext) -> Result<usize, ToolError> {
+        let path = ctx.resolve_path(name, PathAccess::Write)?;
+        if let Some(&index) = self.by_path.get(&path) {
+            return Ok(index);
+        }
+        let original = match tokio::fs::read_to_string(&path).await {
+            Ok(content) => Some(content),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => {
+                return Err(ToolError::ExecutionFailed(format!(
+                    "Cannot read {}: {}",
+                    name, e
+                )))
+            }
+        };
+        self.changes.push(FileChange {
+            name: name.to_string(),
+            path: path.clone(),
+            patched: original.clone(),
+            original,
+            stats: ChangeStats::default(),
+        });
+        self.by_path.insert(path, self.changes.len() - 1);
+        Ok(self.changes.len() - 1)
+    }
+}
+
+fn add_stats(total: &mut ChangeStats, stats: ChangeStats) {
+    total.hunks += stats.hunks;
+    total.added += stats.added;
+    total.removed += stats.removed;
+}
+
+fn conflict(name: &str, reason: &str) -> ToolError {
+    ToolError::ExecutionFailed(format!(
+        "Patch conflict in {}: {}. No files were changed.",
+        name, reason
+    ))
+}
+
+fn in_file(name: &str, error: PatchError) -> ToolError {
+    match error {
+        PatchError::Invalid(_) => ToolError::InvalidParameters(format!("{} ({})", error, name)),
+        PatchError::Conflict(reason) => conflict(name, &reason),
+    }
+}
+
+/// `path` without its root, for mirroring it under a backup directory.
+fn relative(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|c| matches!(c, Component::Normal(_)))
+        .collect()
+}
+
+/// Write a file by renaming a temporary file over it.
+async fn write_atomic(path: &Path, content: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let tmp = path.with_file_name(format!(".{}.patch-tmp", name));
+    tokio::fs::write(&tmp, content).await?;
+    if let Err(e) = tokio::fs::rename(&tmp, path).await {
+        let _ = tokio::fs::remove_file(&tmp).await;
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Write one change.
+async fn write_change(change: &FileChange) -> std::io::Result<()> {
+    match &change.patched {
+        Some(content) => write_atomic(&change.path, content).await,
+        None => tokio::fs::remove_file(&change.path).await,
+    }
+}
+
+/// Put a file back as it was before the patch.
+async fn restore(change: &FileChange) -> std::io::Result<()> {
+    match &change.original {
+        Some(content) => write_atomic(&change.path, content).await,
+        None => tokio::fs::remove_file(&change.path).await,
+    }
+}
+
+#[async_trait]
+impl Tool for ApplyPatchTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: ApplyPatchParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
+
+        let patch = patch::parse(&params.patch).map_err(|e| match e {
+            PatchError::Invalid(_) => ToolError::InvalidParameters(e.to_string()),
+            PatchError::Conflict(_) => ToolError::ExecutionFailed(e.to_string()),
+        })?;
+        let changes = self.plan(patch, params.path.as_deref(), &ctx).await?;
+        if changes.is_empty() {
+            return Ok(ToolResult::success("Patch applied: no changes"));
+        }
+
+        let backup_dir = if params.backup {
+            self.back_up(&changes).await?
+        } else {
+            None
+        };
+
+        if let Some(snapshots) = &self.snapshots {
+            for change in &changes {
+                snapshots.track(&ctx.work_dir, &change.path).await?;
+            }
+        }
+
+        for (i, change) in changes.iter().enumerate() {
+            if let Err(e) = write_change(change).await {
+                // Undo the files already written
+                for written in changes[..i].iter().rev() {
+                    if let Err(e) = restore(written).await {
+                        tracing::warn!("Failed to restore {}: {}", written.path.display(), e);
+                    }
+                }
+                return Err(ToolError::ExecutionFailed(format!(
+                    "Failed to write {}: {}. Files already written were restored.",
+                    change.name, e
+                )));
+            }
+        }
+
+        let (mut content, mut output) = summarize(&changes, "Applied");
+        if let Some(dir) = &backup_dir {
+            content.push_str(&format!("\nBackup: {}", dir.display()));
+        }
+        output["backup_dir"] = serde_json::json!(backup_dir);
+
+        Ok(ToolResult::success_json(content, output))
+    }
+
+    async fn simulate(
+        &self,
+        params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: ApplyPatchParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
+
+        let patch = patch::parse(&params.patch).map_err(|e| match e {
+            PatchError::Invalid(_) => ToolError::InvalidParameters(e.to_string()),
+            PatchError::Conflict(_) => ToolError::ExecutionFailed(e.to_string()),
+        })?;
+        let changes = self.plan(patch, params.path.as_deref(), &ctx).await?;
+        let (content, output) = summarize(&changes, "Would apply");
+        let result = ToolResult::simulated(format!("{}\nNothing was changed.", content));
+        Ok(result.with_metadata("patch", output))
+    }
+}
+
+/// Summary of the changes, as text headed by `verb` and as JSON.
+fn summarize(changes: &[FileChange], verb: &str) -> (String, serde_json::Value) {
+    let hunks: usize = changes.iter().map(|change| change.stats.hunks).sum();
+    let mut content = format!(
+        "{} {} hunk{} to {} file{}:",
+        verb,
+        hunks,
+        if hunks == 1 { "" } else { "s" },
+        changes.len(),
+        if changes.len() == 1 { "" } else { "s" }
+    );
+    let mut files = Vec::new();
+    for change in changes {
+        let action = match (&change.original, &change.patched) {
+            (None, _) => "created",
+            (_, None) => "deleted",
+            _ => "modified",
+        };
+        content.push_str(&format!(
+            "\n  {} ({}, {} hunk{}, +{} -{})",
+            change.name,
+            action,
+            change.stats.hunks,
+            if change.stats.hunks == 1 { "" } else { "s" },
+            change.stats.added,
+            change.stats.removed
+        ));
+        files.push(serde_json::json!({
+            "path": change.path,
+            "action": action,
+            "hunks": change.stats.hunks,
+            "added": change.stats.added,
+            "removed": change.stats.removed,
+        }));
+    }
+    let output = serde_json::json!({ "hunks": hunks, "files": files });
+    (content, output)
+}
+
+#[cfg(test)]
+#[path = "apply_patch_tests.rs"]
+mod tests;
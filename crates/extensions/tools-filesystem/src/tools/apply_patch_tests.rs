@@ -0,0 +1,169 @@
+use super::*;
+use tempfile::TempDir;
+
+fn setup() -> (TempDir, ApplyPatchTool, ToolContext) {
+    let temp_dir = TempDir::new().unwrap();
+    let tool = ApplyPatchTool::new().with_backup_dir(temp_dir.path().join("backups"));
+    let ctx = ToolContext::new("test", temp_dir.path().to_path_buf());
+    (temp_dir, tool, ctx)
+}
+
+#[test]
+fn test_tool_definition() {
+    let tool = ApplyPatchTool::new();
+    assert_eq!(tool.definition().id, "apply_patch");
+    assert_eq!(tool.definition().risk_level, RiskLevel::Medium);
+}
+
+#[tokio::test]
+async fn test_apply_unified_diff() {
+    let (temp_dir, tool, ctx) = setup();
+    std::fs::write(temp_dir.path().join("a.txt"), "one\ntwo\nthree\n").unwrap();
+    std::fs::write(temp_dir.path().join("old.txt"), "gone\n").unwrap();
+    let patch = "\
+--- a/a.txt
++++ b/a.txt
+@@ -1,3 +1,3 @@
+ one
+-two
++2
+ three
+--- /dev/null
++++ b/new/b.txt
+@@ -0,0 +1,2 @@
++hello
++world
+--- a/old.txt
++++ /dev/null
+@@ -1 +0,0 @@
+-gone
+";
+
+    let result = tool
+        .execute(serde_json::json!({ "patch": patch }), ctx)
+        .await
+        .unwrap();
+    assert!(result.success);
+    assert!(result.content.starts_with("Applied 3 hunks to 3 files"));
+    assert!(result.content.contains("a.txt (modified, 1 hunk, +1 -1)"));
+    assert!(result.content.contains("new/b.txt (created"));
+    assert!(result.content.contains("old.txt (deleted"));
+
+    let read = |name: &str| std::fs::read_to_string(temp_dir.path().join(name));
+    assert_eq!(read("a.txt").unwrap(), "one\n2\nthree\n");
+    assert_eq!(read("new/b.txt").unwrap(), "hello\nworld\n");
+    assert!(read("old.txt").is_err());
+
+    // The originals were backed up
+    let backup = PathBuf::from(
+        result.structured_output.unwrap()["backup_dir"]
+            .as_str()
+            .unwrap(),
+    );
+    let canon = temp_dir.path().canonicalize().unwrap();
+    let backed_up = |name: &str| std::fs::read_to_string(backup.join(relative(&canon.join(name))));
+    assert_eq!(backed_up("a.txt").unwrap(), "one\ntwo\nthree\n");
+    assert_eq!(backed_up("old.txt").unwrap(), "gone\n");
+}
+
+#[tokio::test]
+async fn test_conflict_changes_nothing() {
+    let (temp_dir, tool, ctx) = setup();
+    std::fs::write(temp_dir.path().join("a.txt"), "one\ntwo\n").unwrap();
+    std::fs::write(temp_dir.path().join("b.txt"), "changed since\n").unwrap();
+    let patch = "\
+--- a/a.txt
++++ b/a.txt
+@@ -1,2 +1,2 @@
+-one
++1
+ two
+--- a/b.txt
++++ b/b.txt
+@@ -1 +1 @@
+-original
++edited
+";
+
+    let err = tool
+        .execute(serde_json::json!({ "patch": patch }), ctx)
+        .await
+        .unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("Patch conflict in b.txt"), "{}", message);
+    assert!(message.contains("No files were changed"));
+    assert_eq!(
+        std::fs::read_to_string(temp_dir.path().join("a.txt")).unwrap(),
+        "one\ntwo\n"
+    );
+    assert!(!temp_dir.path().join("backups").exists());
+}
+
+#[tokio::test]
+async fn test_apply_search_replace_blocks() {
+    let (temp_dir, tool, ctx) = setup();
+    let file = temp_dir.path().join("main.rs");
+    std::fs::write(&file, "fn a() {}\nfn b() {}\n").unwrap();
+    let patch = "\
+<<<<<<< SEARCH
+fn a() {}
+=======
+fn alpha() {}
+>>>>>>> REPLACE
+<<<<<<< SEARCH
+fn b() {}
+=======
+fn beta() {}
+>>>>>>> REPLACE
+";
+
+    // Blocks without a path need one
+    let err = tool
+        .execute(serde_json::json!({ "patch": patch }), ctx.clone())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ToolError::InvalidParameters(_)));
+
+    let params = serde_json::json!({ "patch": patch, "path": "main.rs", "backup": false });
+    let result = tool.execute(params, ctx).await.unwrap();
+    assert!(result
+        .content
+        .contains("main.rs (modified, 2 hunks, +2 -2)"));
+    assert!(!result.content.contains("Backup"));
+    assert_eq!(
+        std::fs::read_to_string(&file).unwrap(),
+        "fn alpha() {}\nfn beta() {}\n"
+    );
+}
+
+#[tokio::test]
+async fn test_invalid_patch() {
+    let (_temp_dir, tool, ctx) = setup();
+    let err = tool
+        .execute(serde_json::json!({ "patch": "not a patch" }), ctx)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ToolError::InvalidParameters(_)));
+}
+
+#[tokio::test]
+async fn test_simulate_changes_nothing() {
+    let (temp_dir, tool, ctx) = setup();
+    std::fs::write(temp_dir.path().join("a.txt"), "one\ntwo\n").unwrap();
+    let patch = "--- a/a.txt\n+++ b/a.txt\n@@ -1,2 +1,2 @@\n-one\n+1\n two\n";
+
+    let result = tool
+        .simulate(serde_json::json!({ "patch": patch }), ctx)
+        .await
+        .unwrap();
+    assert!(result.is_simulated());
+    assert!(result
+        .content
+        .starts_with("[dry run] Would apply 1 hunk to 1 file:"));
+    assert_eq!(result.metadata["patch"]["hunks"], 1);
+    assert_eq!(
+        std::fs::read_to_string(temp_dir.path().join("a.txt")).unwrap(),
+        "one\ntwo\n"
+    );
+    assert!(!temp_dir.path().join("backups").exists());
+}
@@ -4,11 +4,9 @@ use async_trait::async_trait;
 use serde::Deserialize;
 
 use autohands_protocols::error::ToolError;
-use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::tool::{PathAccess, Tool, ToolContext, ToolDefinition, ToolResult};
 use autohands_protocols::types::RiskLevel;
 
-use super::resolve_path_safe;
-
 /// Parameters for create_directory tool.
 #[derive(Debug, Deserialize)]
 struct CreateDirParams {
@@ -77,7 +75,7 @@ impl Tool for CreateDirectoryTool {
         let params: CreateDirParams = serde_json::from_value(params)
             .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
 
-        let path = resolve_path_safe(&params.path, &ctx.work_dir)?;
+        let path = ctx.resolve_path(&params.path, PathAccess::Write)?;
 
         if path.exists() {
             return Ok(ToolResult::success(format!(
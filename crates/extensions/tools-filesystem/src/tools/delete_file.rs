@@ -1,13 +1,15 @@
 //! Delete file tool.
 
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use serde::Deserialize;
 
 use autohands_protocols::error::ToolError;
-use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::tool::{PathAccess, Tool, ToolContext, ToolDefinition, ToolResult};
 use autohands_protocols::types::RiskLevel;
 
-use super::resolve_path_safe;
+use super::snapshot::SnapshotStore;
 
 /// Parameters for delete_file tool.
 #[derive(Debug, Deserialize)]
@@ -22,6 +24,7 @@ struct DeleteFileParams {
 /// Delete file tool implementation.
 pub struct DeleteFileTool {
     definition: ToolDefinition,
+    snapshots: Option<Arc<SnapshotStore>>,
 }
 
 impl DeleteFileTool {
@@ -49,8 +52,15 @@ impl DeleteFileTool {
             )
             .with_parameters_schema(schema)
             .with_risk_level(RiskLevel::High),
+            snapshots: None,
         }
     }
+
+    /// Snapshot what is deleted, so `workspace_undo` can bring it back.
+    pub fn with_snapshots(mut self, snapshots: Arc<SnapshotStore>) -> Self {
+        self.snapshots = Some(snapshots);
+        self
+    }
 }
 
 impl Default for DeleteFileTool {
@@ -73,7 +83,7 @@ impl Tool for DeleteFileTool {
         let params: DeleteFileParams = serde_json::from_value(params)
             .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
 
-        let path = resolve_path_safe(&params.path, &ctx.work_dir)?;
+        let path = ctx.resolve_path(&params.path, PathAccess::Write)?;
 
         if !path.exists() {
             return Err(ToolError::ResourceNotFound(format!(
@@ -83,6 +93,9 @@ impl Tool for DeleteFileTool {
         }
 
         let metadata = tokio::fs::metadata(&path).await?;
+        if let Some(snapshots) = &self.snapshots {
+            snapshots.track(&ctx.work_dir, &path).await?;
+        }
 
         if metadata.is_dir() {
             if params.recursive {
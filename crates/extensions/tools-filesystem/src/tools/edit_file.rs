@@ -1,13 +1,15 @@
 //! Edit file tool (search/replace).
 
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use serde::Deserialize;
 
 use autohands_protocols::error::ToolError;
-use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::tool::{PathAccess, Tool, ToolContext, ToolDefinition, ToolResult};
 use autohands_protocols::types::RiskLevel;
 
-use super::resolve_path_safe;
+use super::snapshot::SnapshotStore;
 
 /// Parameters for edit_file tool.
 #[derive(Debug, Deserialize)]
@@ -26,6 +28,7 @@ struct EditFileParams {
 /// Edit file tool implementation (search/replace).
 pub struct EditFileTool {
     definition: ToolDefinition,
+    snapshots: Option<Arc<SnapshotStore>>,
 }
 
 impl EditFileTool {
@@ -61,8 +64,15 @@ impl EditFileTool {
             )
             .with_parameters_schema(schema)
             .with_risk_level(RiskLevel::Medium),
+            snapshots: None,
         }
     }
+
+    /// Snapshot the file before editing it.
+    pub fn with_snapshots(mut self, snapshots: Arc<SnapshotStore>) -> Self {
+        self.snapshots = Some(snapshots);
+        self
+    }
 }
 
 impl Default for EditFileTool {
@@ -85,7 +95,7 @@ impl Tool for EditFileTool {
         let params: EditFileParams = serde_json::from_value(params)
             .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
 
-        let path = resolve_path_safe(&params.path, &ctx.work_dir)?;
+        let path = ctx.resolve_path(&params.path, PathAccess::Write)?;
 
         if !path.exists() {
             return Err(ToolError::ResourceNotFound(format!(
@@ -119,6 +129,9 @@ impl Tool for EditFileTool {
             content.replacen(&params.old_string, &params.new_string, 1)
         };
 
+        if let Some(snapshots) = &self.snapshots {
+            snapshots.track(&ctx.work_dir, &path).await?;
+        }
         tokio::fs::write(&path, &new_content).await?;
 
         Ok(ToolResult::success(format!(
@@ -4,11 +4,9 @@ use async_trait::async_trait;
 use serde::Deserialize;
 
 use autohands_protocols::error::ToolError;
-use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::tool::{PathAccess, Tool, ToolContext, ToolDefinition, ToolResult};
 use autohands_protocols::types::RiskLevel;
 
-use super::resolve_path_safe;
-
 /// Parameters for list_directory tool.
 #[derive(Debug, Deserialize)]
 struct ListDirectoryParams {
@@ -52,7 +50,8 @@ impl ListDirectoryTool {
                 "List contents of a directory",
             )
             .with_parameters_schema(schema)
-            .with_risk_level(RiskLevel::Low),
+            .with_risk_level(RiskLevel::Low)
+            .with_read_only(),
         }
     }
 }
@@ -77,7 +76,7 @@ impl Tool for ListDirectoryTool {
         let params: ListDirectoryParams = serde_json::from_value(params)
             .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
 
-        let path = resolve_path_safe(&params.path, &ctx.work_dir)?;
+        let path = ctx.resolve_path(&params.path, PathAccess::Read)?;
 
         if !path.exists() {
             return Err(ToolError::ResourceNotFound(format!(
@@ -1,13 +1,15 @@
 //! Move/rename file tool.
 
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use serde::Deserialize;
 
 use autohands_protocols::error::ToolError;
-use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::tool::{PathAccess, Tool, ToolContext, ToolDefinition, ToolResult};
 use autohands_protocols::types::RiskLevel;
 
-use super::resolve_path_safe;
+use super::snapshot::SnapshotStore;
 
 /// Parameters for move_file tool.
 #[derive(Debug, Deserialize)]
@@ -24,6 +26,7 @@ struct MoveFileParams {
 /// Move/rename file tool implementation.
 pub struct MoveFileTool {
     definition: ToolDefinition,
+    snapshots: Option<Arc<SnapshotStore>>,
 }
 
 impl MoveFileTool {
@@ -55,8 +58,15 @@ impl MoveFileTool {
             )
             .with_parameters_schema(schema)
             .with_risk_level(RiskLevel::Medium),
+            snapshots: None,
         }
     }
+
+    /// Snapshot both ends of a move, so `workspace_undo` can move it back.
+    pub fn with_snapshots(mut self, snapshots: Arc<SnapshotStore>) -> Self {
+        self.snapshots = Some(snapshots);
+        self
+    }
 }
 
 impl Default for MoveFileTool {
@@ -79,8 +89,8 @@ impl Tool for MoveFileTool {
         let params: MoveFileParams = serde_json::from_value(params)
             .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
 
-        let source = resolve_path_safe(&params.source, &ctx.work_dir)?;
-        let destination = resolve_path_safe(&params.destination, &ctx.work_dir)?;
+        let source = ctx.resolve_path(&params.source, PathAccess::Write)?;
+        let destination = ctx.resolve_path(&params.destination, PathAccess::Write)?;
 
         if !source.exists() {
             return Err(ToolError::ResourceNotFound(format!(
@@ -96,6 +106,12 @@ impl Tool for MoveFileTool {
             )));
         }
 
+        if let Some(snapshots) = &self.snapshots {
+            snapshots
+                .track_move(&ctx.work_dir, &source, &destination)
+                .await?;
+        }
+
         // Create parent directory if needed
         if let Some(parent) = destination.parent() {
             if !parent.exists() {
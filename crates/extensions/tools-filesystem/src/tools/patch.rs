@@ -0,0 +1,635 @@
+//! Parsing and applying patches.
+//!
+//! Two formats are understood: unified diffs (`git diff`, `diff -u`) and
+//! search/replace blocks naming the file on the line before each block:
+//!
+//! ```text
+//! src/main.rs
+//! <<<<<<< SEARCH
+//! fn old() {}
+//! =======
+//! fn new() {}
+//! >>>>>>> REPLACE
+//! ```
+//!
+//! Patches are applied to file contents in memory; a hunk whose lines are
+//! not found is a conflict and fails the whole patch. Unified diffs between
+//! two contents can be generated too, in the form applying them expects.
+
+use std::fmt;
+
+const SEARCH_MARKER: &str = "<<<<<<< SEARCH";
+const DIVIDER_MARKER: &str = "=======";
+const REPLACE_MARKER: &str = ">>>>>>> REPLACE";
+
+/// Unchanged lines shown around the changes of a generated diff.
+const DIFF_CONTEXT: usize = 3;
+
+/// Line comparisons beyond which a changed region is diffed as replaced
+/// in full.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// A patch to a set of files.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Patch {
+    Unified(Vec<FilePatch>),
+    SearchReplace(Vec<SearchReplace>),
+}
+
+/// Hunks of a unified diff for one file.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FilePatch {
+    /// Path before the change; `None` for a new file.
+    pub old_path: Option<String>,
+    /// Path after the change; `None` for a deleted file.
+    pub new_path: Option<String>,
+    pub hunks: Vec<Hunk>,
+}
+
+impl FilePatch {
+    /// Path of the file the patch applies to.
+    pub fn path(&self) -> &str {
+        self.new_path
+            .as_deref()
+            .or(self.old_path.as_deref())
+            .unwrap_or_default()
+    }
+}
+
+/// One `@@` section of a unified diff.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Hunk {
+    /// First line of the hunk in the original file, from 1 (0 when empty).
+    pub old_start: usize,
+    pub lines: Vec<HunkLine>,
+    /// The new side ends without a newline.
+    pub no_newline: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum HunkLine {
+    Context(String),
+    Remove(String),
+    Add(String),
+}
+
+impl Hunk {
+    fn old_lines(&self) -> Vec<&str> {
+        self.lines
+            .iter()
+            .filter_map(|line| match line {
+                HunkLine::Context(s) | HunkLine::Remove(s) => Some(s.as_str()),
+                HunkLine::Add(_) => None,
+            })
+            .collect()
+    }
+
+    fn new_lines(&self) -> Vec<String> {
+        self.lines
+            .iter()
+            .filter_map(|line| match line {
+                HunkLine::Context(s) | HunkLine::Add(s) => Some(s.clone()),
+                HunkLine::Remove(_) => None,
+            })
+            .collect()
+    }
+}
+
+/// A search/replace block.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SearchReplace {
+    /// File named before the block, if any.
+    pub path: Option<String>,
+    pub search: String,
+    pub replace: String,
+}
+
+/// Lines added and removed by applying a patch to one file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct ChangeStats {
+    pub hunks: usize,
+    pub added: usize,
+    pub removed: usize,
+}
+
+/// Why a patch could not be applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PatchError {
+    /// The patch is malformed.
+    Invalid(String),
+    /// A hunk does not match the file.
+    Conflict(String),
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(msg) => write!(f, "Invalid patch: {}", msg),
+            Self::Conflict(msg) => write!(f, "Patch conflict: {}", msg),
+        }
+    }
+}
+
+/// Parse a patch in either format.
+pub(crate) fn parse(patch: &str) -> Result<Patch, PatchError> {
+    if patch.lines().any(|line| line.trim_end() == SEARCH_MARKER) {
+        parse_search_replace(patch).map(Patch::SearchReplace)
+    } else if patch.lines().any(|line| line.starts_with("@@ ")) {
+        parse_unified(patch).map(Patch::Unified)
+    } else {
+        Err(PatchError::Invalid(
+            "expected a unified diff or SEARCH/REPLACE blocks".to_string(),
+        ))
+    }
+}
+
+/// Parse a unified diff.
+pub(crate) fn parse_unified(patch: &str) -> Result<Vec<FilePatch>, PatchError> {
+    let lines: Vec<&str> = patch.lines().collect();
+    let mut files: Vec<FilePatch> = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        if let Some(old) = line.strip_prefix("--- ") {
+            let new = lines
+                .get(i + 1)
+                .and_then(|next| next.strip_prefix("+++ "))
+                .ok_or_else(|| {
+                    PatchError::Invalid(format!("'---' without '+++' at line {}", i + 1))
+                })?;
+            files.push(FilePatch {
+                old_path: header_path(old),
+                new_path: header_path(new),
+                hunks: Vec::new(),
+            });
+            i += 2;
+        } else if line.starts_with("@@ ") {
+            let file = files.last_mut().ok_or_else(|| {
+                PatchError::Invalid(format!("hunk without file header at line {}", i + 1))
+            })?;
+            let (hunk, next) = parse_hunk(&lines, i)?;
+            file.hunks.push(hunk);
+            i = next;
+        } else {
+            // `diff --git`, `index` and other extended headers
+            i += 1;
+        }
+    }
+    if files.is_empty() {
+        return Err(PatchError::Invalid("no file headers".to_string()));
+    }
+    if let Some(file) = files
+        .iter()
+        .find(|f| f.old_path.is_none() && f.new_path.is_none())
+    {
+        return Err(PatchError::Invalid(format!(
+            "no path in file header of {:?}",
+            file
+        )));
+    }
+    Ok(files)
+}
+
+/// Path of a `---`/`+++` header, without its `a/`/`b/` prefix or timestamp.
+fn header_path(header: &str) -> Option<String> {
+    let path = header.split('\t').next().unwrap_or_default().trim();
+    if path == "/dev/null" || path.is_empty() {
+        return None;
+    }
+    let path = path
+        .strip_prefix("a/")
+        .or_else(|| path.strip_prefix("b/"))
+        .unwrap_or(path);
+    Some(path.to_string())
+}
+
+/// Parse the hunk starting at `lines[start]`, returning it and the index
+/// of the line after it.
+fn parse_hunk(lines: &[&str], start: usize) -> Result<(Hunk, usize), PatchError> {
+    let header = lines[start];
+    let invalid = || PatchError::Invalid(format!("bad hunk header '{}'", header));
+    let ranges = header
+        .strip_prefix("@@ ")
+        .and_then(|rest| rest.split(" @@").next())
+        .ok_or_else(invalid)?;
+    let mut ranges = ranges.split_whitespace();
+    let (old_start, mut old_count) = ranges
+        .next()
+        .and_then(|r| r.strip_prefix('-'))
+        .and_then(parse_range)
+        .ok_or_else(invalid)?;
+    let (_, mut new_count) = ranges
+        .next()
+        .and_then(|r| r.strip_prefix('+'))
+        .and_then(parse_range)
+        .ok_or_else(invalid)?;
+
+    let mut hunk = Hunk {
+        old_start,
+        lines: Vec::new(),
+        no_newline: false,
+    };
+    let mut i = start + 1;
+    while i < lines.len() && (old_count > 0 || new_count > 0) {
+        let line = lines[i];
+        let take = |count: &mut usize| {
+            if *count == 0 {
+                return Err(PatchError::Invalid(format!(
+                    "hunk '{}' is longer than its header says",
+                    header
+                )));
+            }
+            *count -= 1;
+            Ok(())
+        };
+        match line.chars().next() {
+            Some('+') => {
+                take(&mut new_count)?;
+                hunk.lines.push(HunkLine::Add(line[1..].to_string()));
+            }
+            Some('-') => {
+                take(&mut old_count)?;
+                hunk.lines.push(HunkLine::Remove(line[1..].to_string()));
+            }
+            Some('\\') => {}
+            // Some tools drop the space of empty context lines
+            Some(' ') | None => {
+                take(&mut old_count)?;
+                take(&mut new_count)?;
+                hunk.lines.push(HunkLine::Context(
+                    line.get(1..).unwrap_or_default().to_string(),
+                ));
+            }
+            Some(_) => break,
+        }
+        i += 1;
+    }
+    if old_count > 0 || new_count > 0 {
+        return Err(PatchError::Invalid(format!(
+            "hunk '{}' is shorter than its header says",
+            header
+        )));
+    }
+    // "\ No newline at end of file" after the last line of the new side
+    if lines.get(i).is_some_and(|line| line.starts_with('\\')) {
+        hunk.no_newline = !matches!(hunk.lines.last(), Some(HunkLine::Remove(_)));
+        i += 1;
+    }
+    Ok((hunk, i))
+}
+
+/// `start,count` or `start` of a hunk header.
+fn parse_range(range: &str) -> Option<(usize, usize)> {
+    match range.split_once(',') {
+        Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+        None => Some((range.parse().ok()?, 1)),
+    }
+}
+
+/// Parse search/replace blocks.
+pub(crate) fn parse_search_replace(patch: &str) -> Result<Vec<SearchReplace>, PatchError> {
+    let mut blocks = Vec::new();
+    let mut lines = patch.lines().enumerate().peekable();
+    let mut path: Option<String> = None;
+    while let Some((n, line)) = lines.next() {
+        let trimmed = line.trim_end();
+        if trimmed != SEARCH_MARKER {
+            // The last other line before a block names its file
+            let candidate = trimmed.trim();
+            if !candidate.is_empty() && !candidate.starts_with("```") {
+                path = Some(candidate.to_string());
+            }
+            continue;
+        }
+        let mut search = Vec::new();
+        let mut replace = Vec::new();
+        let mut in_replace = false;
+        let mut closed = false;
+        for (_, line) in lines.by_ref() {
+            match line.trim_end() {
+                DIVIDER_MARKER if !in_replace => in_replace = true,
+                REPLACE_MARKER if in_replace => {
+                    closed = true;
+                    break;
+                }
+                _ if in_replace => replace.push(line),
+                _ => search.push(line),
+            }
+        }
+        if !closed {
+            return Err(PatchError::Invalid(format!(
+                "SEARCH block at line {} is not closed with '{}'",
+                n + 1,
+                REPLACE_MARKER
+            )));
+        }
+        blocks.push(SearchReplace {
+            path: path.clone(),
+            search: join_block(&search),
+            replace: join_block(&replace),
+        });
+    }
+    Ok(blocks)
+}
+
+fn join_block(lines: &[&str]) -> String {
+    if lines.is_empty() {
+        String::new()
+    } else {
+        let mut text = lines.join("\n");
+        text.push('\n');
+        text
+    }
+}
+
+/// Apply the hunks of a unified diff to a file's content.
+///
+/// Each hunk is looked for at the line its header names, shifted by the
+/// hunks before it, then at the nearest place its lines match, ignoring
+/// trailing whitespace if they match nowhere exactly.
+pub(crate) fn apply_hunks(
+    content: &str,
+    hunks: &[Hunk],
+) -> Result<(String, ChangeStats), PatchError> {
+    let crlf = content.contains("\r\n");
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let mut trailing_newline = content.is_empty() || content.ends_with('\n');
+    let mut stats = ChangeStats::default();
+    let mut offset: isize = 0;
+    let mut floor = 0;
+
+    for (index, hunk) in hunks.iter().enumerate() {
+        let old = hunk.old_lines();
+        // A hunk removing nothing inserts after its start line
+        let start = if old.is_empty() {
+            hunk.old_start
+        } else {
+            hunk.old_start.saturating_sub(1)
+        };
+        let expected = (start as isize + offset).max(0) as usize;
+        let position = find_lines(&lines, &old, expected, floor, |a, b| a == b)
+            .or_else(|| {
+                find_lines(&lines, &old, expected, floor, |a, b| {
+                    a.trim_end() == b.trim_end()
+                })
+            })
+            .ok_or_else(|| {
+                PatchError::Conflict(format!(
+                    "hunk {} (at line {}) does not match the file",
+                    index + 1,
+                    hunk.old_start
+                ))
+            })?;
+
+        let new = hunk.new_lines();
+        let end = position + old.len();
+        if end == lines.len() && hunk.lines.iter().any(|l| !matches!(l, HunkLine::Remove(_))) {
+            trailing_newline = !hunk.no_newline;
+        }
+        stats.hunks += 1;
+        stats.added += new.len() - count_context(hunk);
+        stats.removed += old.len() - count_context(hunk);
+        offset += new.len() as isize - old.len() as isize;
+        floor = position + new.len();
+        lines.splice(position..end, new);
+    }
+
+    let newline = if crlf { "\r\n" } else { "\n" };
+    let mut result = lines.join(newline);
+    if trailing_newline && !lines.is_empty() {
+        result.push_str(newline);
+    }
+    Ok((result, stats))
+}
+
+fn count_context(hunk: &Hunk) -> usize {
+    hunk.lines
+        .iter()
+        .filter(|line| matches!(line, HunkLine::Context(_)))
+        .count()
+}
+
+/// Position at or after `floor` where `needle` matches, nearest `expected`.
+fn find_lines(
+    lines: &[String],
+    needle: &[&str],
+    expected: usize,
+    floor: usize,
+    eq: impl Fn(&str, &str) -> bool,
+) -> Option<usize> {
+    if needle.is_empty() {
+        return Some(expected.max(floor).min(lines.len()));
+    }
+    if lines.len() < needle.len() {
+        return None;
+    }
+    let matches = |pos: usize| {
+        needle
+            .iter()
+            .zip(&lines[pos..pos + needle.len()])
+            .all(|(a, b)| eq(a, b))
+    };
+    (floor..=lines.len() - needle.len())
+        .filter(|&pos| matches(pos))
+        .min_by_key(|&pos| pos.abs_diff(expected))
+}
+
+/// Apply a search/replace block to a file's content.
+///
+/// The search text must occur exactly once; an empty search text replaces
+/// an empty file.
+pub(crate) fn apply_search_replace(
+    content: &str,
+    block: &SearchReplace,
+) -> Result<(String, ChangeStats), PatchError> {
+    let crlf = content.contains("\r\n");
+    let (search, replace) = if crlf {
+        (
+            block.search.replace('\n', "\r\n"),
+            block.replace.replace('\n', "\r\n"),
+        )
+    } else {
+        (block.search.clone(), block.replace.clone())
+    };
+    let stats = ChangeStats {
+        hunks: 1,
+        added: block.replace.lines().count(),
+        removed: block.search.lines().count(),
+    };
+
+    if search.is_empty() {
+        if !content.is_empty() {
+            return Err(PatchError::Conflict(
+                "empty SEARCH block for a file that is not empty".to_string(),
+            ));
+        }
+        return Ok((replace, stats));
+    }
+    match content.matches(&search).count() {
+        1 => Ok((content.replacen(&search, &replace, 1), stats)),
+        0 => Err(PatchError::Conflict(format!(
+            "SEARCH text not found:\n{}",
+            block.search
+        ))),
+        n => Err(PatchError::Conflict(format!(
+            "SEARCH text found {} times; add context to make it unique:\n{}",
+            n, block.search
+        ))),
+    }
+}
+
+/// One step of a line diff, with indexes into the old and new lines.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DiffOp {
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+/// Unified diff turning `old` into `new`, named `path`; `None` is a file
+/// that does not exist. Empty if the contents are the same.
+pub(crate) fn unified_diff(path: &str, old: Option<&str>, new: Option<&str>) -> String {
+    let old_lines = diff_lines(old.unwrap_or_default());
+    let new_lines = diff_lines(new.unwrap_or_default());
+    let ops = diff_ops(&old_lines, &new_lines);
+    if ops.iter().all(|op| matches!(op, DiffOp::Equal(..))) {
+        return String::new();
+    }
+
+    // Line numbers before each step
+    let mut positions = Vec::with_capacity(ops.len() + 1);
+    let (mut old_pos, mut new_pos) = (0, 0);
+    for op in &ops {
+        positions.push((old_pos, new_pos));
+        match op {
+            DiffOp::Equal(..) => {
+                old_pos += 1;
+                new_pos += 1;
+            }
+            DiffOp::Delete(_) => old_pos += 1,
+            DiffOp::Insert(_) => new_pos += 1,
+        }
+    }
+    positions.push((old_pos, new_pos));
+
+    let mut out = format!(
+        "--- {}\n+++ {}\n",
+        old.map_or("/dev/null".to_string(), |_| format!("a/{}", path)),
+        new.map_or("/dev/null".to_string(), |_| format!("b/{}", path)),
+    );
+    let mut next = 0;
+    while let Some(first) = ops[next..]
+        .iter()
+        .position(|op| !matches!(op, DiffOp::Equal(..)))
+        .map(|i| i + next)
+    {
+        // Changes closer than twice the context share a hunk
+        let mut last = first;
+        let mut i = first;
+        while i < ops.len() && i - last <= 2 * DIFF_CONTEXT + 1 {
+            if !matches!(ops[i], DiffOp::Equal(..)) {
+                last = i;
+            }
+            i += 1;
+        }
+        let start = first.saturating_sub(DIFF_CONTEXT).max(next);
+        let end = (last + 1 + DIFF_CONTEXT).min(ops.len());
+        let (old_from, new_from) = positions[start];
+        let (old_to, new_to) = positions[end];
+        out.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(old_from, old_to - old_from),
+            hunk_range(new_from, new_to - new_from)
+        ));
+        for op in &ops[start..end] {
+            let (marker, (line, no_newline)) = match *op {
+                DiffOp::Equal(i, _) => (' ', old_lines[i]),
+                DiffOp::Delete(i) => ('-', old_lines[i]),
+                DiffOp::Insert(j) => ('+', new_lines[j]),
+            };
+            out.push(marker);
+            out.push_str(line);
+            out.push('\n');
+            if no_newline {
+                out.push_str("\\ No newline at end of file\n");
+            }
+        }
+        next = end;
+    }
+    out
+}
+
+/// Lines of a content, each with whether it ends without a newline.
+fn diff_lines(content: &str) -> Vec<(&str, bool)> {
+    let mut lines: Vec<(&str, bool)> = content.lines().map(|line| (line, false)).collect();
+    if !content.ends_with('\n') {
+        if let Some(last) = lines.last_mut() {
+            last.1 = true;
+        }
+    }
+    lines
+}
+
+/// `start,count` of a hunk header, from the lines before the range.
+fn hunk_range(before: usize, count: usize) -> String {
+    if count == 0 {
+        format!("{},0", before)
+    } else {
+        format!("{},{}", before + 1, count)
+    }
+}
+
+/// Steps turning `old` into `new`, keeping their longest common subsequence.
+fn diff_ops<T: PartialEq>(old: &[T], new: &[T]) -> Vec<DiffOp> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let a = &old[prefix..old.len() - suffix];
+    let b = &new[prefix..new.len() - suffix];
+
+    let mut ops: Vec<DiffOp> = (0..prefix).map(|i| DiffOp::Equal(i, i)).collect();
+    if a.len().saturating_mul(b.len()) > MAX_DIFF_CELLS {
+        ops.extend((0..a.len()).map(|i| DiffOp::Delete(prefix + i)));
+        ops.extend((0..b.len()).map(|j| DiffOp::Insert(prefix + j)));
+    } else {
+        // lengths[i * width + j]: longest common subsequence of a[i..] and b[j..]
+        let width = b.len() + 1;
+        let mut lengths = vec![0u32; (a.len() + 1) * width];
+        for i in (0..a.len()).rev() {
+            for j in (0..b.len()).rev() {
+                lengths[i * width + j] = if a[i] == b[j] {
+                    lengths[(i + 1) * width + j + 1] + 1
+                } else {
+                    lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() || j < b.len() {
+            if i < a.len() && j < b.len() && a[i] == b[j] {
+                ops.push(DiffOp::Equal(prefix + i, prefix + j));
+                i += 1;
+                j += 1;
+            } else if i < a.len()
+                && (j == b.len() || lengths[(i + 1) * width + j] >= lengths[i * width + j + 1])
+            {
+                ops.push(DiffOp::Delete(prefix + i));
+                i += 1;
+            } else {
+                ops.push(DiffOp::Insert(prefix + j));
+                j += 1;
+            }
+        }
+    }
+    let (old_end, new_end) = (old.len() - suffix, new.len() - suffix);
+    ops.extend((0..suffix).map(|k| DiffOp::Equal(old_end + k, new_end + k)));
+    ops
+}
+
+#[cfg(test)]
+#[path = "patch_tests.rs"]
+mod tests;
@@ -0,0 +1,170 @@
+use super::*;
+
+const DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,4 +1,4 @@
+ fn one() {}
+-fn two() {}
++fn deux() {}
+ fn three() {}
+ fn four() {}
+@@ -8,3 +8,4 @@ fn seven() {}
+ fn eight() {}
+ fn nine() {}
+ fn ten() {}
++fn eleven() {}
+";
+
+fn numbered(n: usize) -> String {
+    let names = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    ];
+    names[..n]
+        .iter()
+        .map(|name| format!("fn {}() {{}}\n", name))
+        .collect()
+}
+
+#[test]
+fn test_parse_unified() {
+    let files = parse_unified(DIFF).unwrap();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].path(), "src/lib.rs");
+    assert_eq!(files[0].hunks.len(), 2);
+    assert_eq!(files[0].hunks[1].old_start, 8);
+    assert_eq!(
+        files[0].hunks[1].lines.last(),
+        Some(&HunkLine::Add("fn eleven() {}".to_string()))
+    );
+
+    let created = parse_unified("--- /dev/null\n+++ b/new.txt\n@@ -0,0 +1 @@\n+hello\n").unwrap();
+    assert_eq!(created[0].old_path, None);
+    assert_eq!(created[0].path(), "new.txt");
+
+    assert!(matches!(
+        parse_unified("--- a/x\n+++ b/x\n@@ -1,2 +1,2 @@\n a\n"),
+        Err(PatchError::Invalid(_))
+    ));
+    assert!(matches!(parse("just text"), Err(PatchError::Invalid(_))));
+}
+
+#[test]
+fn test_apply_hunks() {
+    let files = parse_unified(DIFF).unwrap();
+    let (patched, stats) = apply_hunks(&numbered(10), &files[0].hunks).unwrap();
+    assert!(patched.contains("fn deux() {}\nfn three"));
+    assert!(patched.ends_with("fn ten() {}\nfn eleven() {}\n"));
+    assert_eq!(
+        stats,
+        ChangeStats {
+            hunks: 2,
+            added: 2,
+            removed: 1
+        }
+    );
+
+    // Hunks are found away from the lines their headers name
+    let shifted = format!("// header\n// comment\n{}", numbered(10));
+    let (patched, _) = apply_hunks(&shifted, &files[0].hunks).unwrap();
+    assert!(patched.starts_with("// header\n// comment\nfn one() {}\nfn deux"));
+
+    // Lines that are not there are a conflict
+    let err = apply_hunks(&numbered(9), &files[0].hunks).unwrap_err();
+    assert_eq!(
+        err,
+        PatchError::Conflict("hunk 2 (at line 8) does not match the file".to_string())
+    );
+}
+
+#[test]
+fn test_apply_hunks_line_endings() {
+    let diff = "--- a/x\n+++ b/x\n@@ -1,2 +1,2 @@\n a\n-b\n+c\n\\ No newline at end of file\n";
+    let hunks = &parse_unified(diff).unwrap()[0].hunks;
+    assert!(hunks[0].no_newline);
+    assert_eq!(apply_hunks("a\nb\n", hunks).unwrap().0, "a\nc");
+    assert_eq!(apply_hunks("a\r\nb\r\n", hunks).unwrap().0, "a\r\nc");
+
+    let created = parse_unified("--- /dev/null\n+++ b/x\n@@ -0,0 +1,2 @@\n+a\n+b\n").unwrap();
+    assert_eq!(apply_hunks("", &created[0].hunks).unwrap().0, "a\nb\n");
+}
+
+#[test]
+fn test_search_replace() {
+    let patch = "\
+src/a.rs
+<<<<<<< SEARCH
+fn old() {}
+=======
+fn new() {}
+>>>>>>> REPLACE
+
+```
+src/b.rs
+<<<<<<< SEARCH
+=======
+created
+>>>>>>> REPLACE
+```
+";
+    let blocks = parse_search_replace(patch).unwrap();
+    assert_eq!(blocks.len(), 2);
+    assert_eq!(blocks[0].path.as_deref(), Some("src/a.rs"));
+    assert_eq!(blocks[0].search, "fn old() {}\n");
+    assert_eq!(blocks[1].path.as_deref(), Some("src/b.rs"));
+    assert_eq!(blocks[1].search, "");
+
+    let (patched, stats) = apply_search_replace("x\nfn old() {}\ny\n", &blocks[0]).unwrap();
+    assert_eq!(patched, "x\nfn new() {}\ny\n");
+    assert_eq!(stats.added, 1);
+    assert_eq!(apply_search_replace("", &blocks[1]).unwrap().0, "created\n");
+
+    assert!(matches!(
+        apply_search_replace("nothing here", &blocks[0]),
+        Err(PatchError::Conflict(_))
+    ));
+    let twice = "fn old() {}\nfn old() {}\n";
+    let err = apply_search_replace(twice, &blocks[0]).unwrap_err();
+    assert!(err.to_string().contains("found 2 times"));
+
+    assert!(matches!(
+        parse_search_replace("<<<<<<< SEARCH\na\n=======\n"),
+        Err(PatchError::Invalid(_))
+    ));
+}
+
+#[test]
+fn test_unified_diff() {
+    let old = numbered(10);
+    let new = old
+        .replace("fn two() {}\n", "fn deux() {}\n")
+        .replace("fn nine() {}\n", "");
+    let diff = unified_diff("src/lib.rs", Some(&old), Some(&new));
+    assert!(diff.starts_with("--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,10 +1,9 @@\n"));
+
+    // Applying the diff gives the new content back
+    let files = parse_unified(&diff).unwrap();
+    let (patched, stats) = apply_hunks(&old, &files[0].hunks).unwrap();
+    assert_eq!(patched, new);
+    assert_eq!((stats.added, stats.removed), (1, 2));
+
+    // Far apart changes get their own hunks
+    let far = format!("{}{}", numbered(10), numbered(10)).replacen("fn one", "fn uno", 1);
+    let far = far.replacen("fn ten() {}\n", "", 1);
+    let old = format!("{}{}", numbered(10), numbered(10));
+    let files = parse_unified(&unified_diff("x", Some(&old), Some(&far))).unwrap();
+    assert_eq!(files[0].hunks.len(), 2);
+    assert_eq!(apply_hunks(&old, &files[0].hunks).unwrap().0, far);
+
+    assert_eq!(unified_diff("x", Some("same\n"), Some("same\n")), "");
+    assert_eq!(
+        unified_diff("x", None, Some("a\nb")),
+        "--- /dev/null\n+++ b/x\n@@ -0,0 +1,2 @@\n+a\n+b\n\\ No newline at end of file\n"
+    );
+    assert_eq!(
+        unified_diff("x", Some("a\n"), None),
+        "--- a/x\n+++ /dev/null\n@@ -1,1 +0,0 @@\n-a\n"
+    );
+}
@@ -1,13 +1,45 @@
 //! Read file tool.
+//!
+//! Files are streamed line by line, so a line range of a large log costs
+//! only what it returns. Files over [`MAX_READ_BYTES`] are refused unless
+//! read by range, head or tail, and binary files are shown as a hexdump of
+//! their first bytes.
+
+use std::collections::VecDeque;
+use std::path::Path;
 
 use async_trait::async_trait;
 use serde::Deserialize;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncSeekExt, BufReader};
 
 use autohands_protocols::error::ToolError;
-use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::tool::{PathAccess, Tool, ToolContext, ToolDefinition, ToolResult};
 use autohands_protocols::types::RiskLevel;
 
-use super::resolve_path_safe;
+/// Largest file read whole, and most content returned by one call.
+pub const MAX_READ_BYTES: u64 = 256 * 1024;
+
+/// Lines returned by head and tail reads without a limit.
+const DEFAULT_EDGE_LINES: usize = 100;
+
+/// Bytes inspected to tell text from binary.
+const SNIFF_BYTES: usize = 8 * 1024;
+
+/// Bytes of a binary file shown in its hexdump.
+const HEXDUMP_BYTES: usize = 256;
+
+/// Which lines to read.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ReadMode {
+    /// `limit` lines from `offset`, or the whole file.
+    #[default]
+    Range,
+    /// The first `limit` lines.
+    Head,
+    /// The last `limit` lines.
+    Tail,
+}
 
 /// Parameters for read_file tool.
 #[derive(Debug, Deserialize)]
@@ -20,6 +52,22 @@ struct ReadFileParams {
     /// Number of lines to read (optional).
     #[serde(default)]
     limit: Option<usize>,
+    /// Range, head or tail.
+    #[serde(default)]
+    mode: ReadMode,
+}
+
+/// Lines read from a file.
+#[derive(Debug, Default)]
+struct Page {
+    /// Line numbers (1-based) and contents.
+    lines: Vec<(usize, String)>,
+    /// Lines in the file, when it was read to the end.
+    total_lines: Option<usize>,
+    /// First line not returned, when lines follow.
+    next_offset: Option<usize>,
+    /// Whether [`MAX_READ_BYTES`] cut the page short.
+    clipped: bool,
 }
 
 /// Read file tool implementation.
@@ -42,16 +90,34 @@ impl ReadFileTool {
                 },
                 "limit": {
                     "type": "integer",
-                    "description": "Number of lines to read"
+                    "description": "Number of lines to read (head and tail default to 100)"
+                },
+                "mode": {
+                    "type": "string",
+                    "enum": ["range", "head", "tail"],
+                    "description": "range (default): `limit` lines from `offset`; head: the first `limit` lines; tail: the last `limit` lines"
                 }
             },
             "required": ["path"]
         });
 
         Self {
-            definition: ToolDefinition::new("read_file", "Read File", "Read contents of a file")
-                .with_parameters_schema(schema)
-                .with_risk_level(RiskLevel::Low),
+            definition: ToolDefinition::new(
+                "read_file",
+                "Read File",
+                "Read contents of a file. Files over 256 KiB must be read in line ranges (offset/limit) or with mode head/tail; binary files are shown as a hexdump",
+            )
+            .with_parameters_schema(schema)
+            .with_risk_level(RiskLevel::Low)
+            .with_read_only()
+            .with_example(
+                "Read the first 50 lines of a file",
+                serde_json::json!({"path": "/project/src/main.rs", "limit": 50}),
+            )
+            .with_example(
+                "Read the end of a large log",
+                serde_json::json!({"path": "/var/log/app.log", "mode": "tail", "limit": 200}),
+            ),
         }
     }
 }
@@ -76,7 +142,7 @@ impl Tool for ReadFileTool {
         let params: ReadFileParams = serde_json::from_value(params)
             .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
 
-        let path = resolve_path_safe(&params.path, &ctx.work_dir)?;
+        let path = ctx.resolve_path(&params.path, PathAccess::Read)?;
 
         if !path.exists() {
             return Err(ToolError::ResourceNotFound(format!(
@@ -84,24 +150,270 @@ impl Tool for ReadFileTool {
                 path.display()
             )));
         }
+        let metadata = tokio::fs::metadata(&path).await?;
+        if metadata.is_dir() {
+            return Err(ToolError::InvalidParameters(format!(
+                "{} is a directory; use list_directory",
+                path.display()
+            )));
+        }
+        let size = metadata.len();
+
+        let mut file = tokio::fs::File::open(&path).await?;
+        let mut sample = Vec::with_capacity(SNIFF_BYTES);
+        (&mut file)
+            .take(SNIFF_BYTES as u64)
+            .read_to_end(&mut sample)
+            .await?;
+        if is_binary(&sample) {
+            return Ok(binary_preview(size, &sample));
+        }
+        let whole =
+            params.mode == ReadMode::Range && params.offset.is_none() && params.limit.is_none();
+        if whole && size > MAX_READ_BYTES {
+            return Ok(too_large(&path, size));
+        }
+
+        file.rewind().await?;
+        let reader = BufReader::new(file);
+        let page = match params.mode {
+            ReadMode::Range => {
+                let offset = params.offset.unwrap_or(1).max(1);
+                read_range(reader, offset, params.limit.unwrap_or(usize::MAX)).await?
+            }
+            ReadMode::Head => {
+                read_range(reader, 1, params.limit.unwrap_or(DEFAULT_EDGE_LINES)).await?
+            }
+            ReadMode::Tail => read_tail(reader, params.limit.unwrap_or(DEFAULT_EDGE_LINES)).await?,
+        };
+
+        let info = serde_json::json!({
+            "size_bytes": size,
+            "start_line": page.lines.first().map(|(n, _)| n),
+            "end_line": page.lines.last().map(|(n, _)| n),
+            "total_lines": page.total_lines,
+            "next_offset": page.next_offset,
+        });
+        Ok(ToolResult::success_json(render(&page), info))
+    }
+}
+
+/// Whether a sample of a file's start looks binary: it has NUL bytes or
+/// is not UTF-8.
+fn is_binary(sample: &[u8]) -> bool {
+    if sample.contains(&0) {
+        return true;
+    }
+    match std::str::from_utf8(sample) {
+        Ok(_) => false,
+        // A character cut off at the end of the sample is still text
+        Err(e) => e.error_len().is_some(),
+    }
+}
+
+/// Read the next line, without its line ending.
+async fn next_line<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    buf: &mut Vec<u8>,
+) -> std::io::Result<Option<String>> {
+    buf.clear();
+    if reader.read_until(b'\n', buf).await? == 0 {
+        return Ok(None);
+    }
+    if buf.last() == Some(&b'\n') {
+        buf.pop();
+        if buf.last() == Some(&b'\r') {
+            buf.pop();
+        }
+    }
+    Ok(Some(String::from_utf8_lossy(buf).into_owned()))
+}
 
-        let content = tokio::fs::read_to_string(&path).await?;
+/// Up to `limit` lines from line `offset` (1-based).
+async fn read_range<R: AsyncBufRead + Unpin>(
+    mut reader: R,
+    offset: usize,
+    limit: usize,
+) -> std::io::Result<Page> {
+    let mut page = Page::default();
+    if limit == 0 {
+        return Ok(page);
+    }
+    let mut buf = Vec::new();
+    let mut bytes = 0;
+    let mut number = 0;
+    while let Some(mut line) = next_line(&mut reader, &mut buf).await? {
+        number += 1;
+        if number < offset {
+            continue;
+        }
+        if page.lines.len() == limit {
+            page.next_offset = Some(number);
+            return Ok(page);
+        }
+        if bytes + line.len() as u64 > MAX_READ_BYTES {
+            page.clipped = true;
+            if page.lines.is_empty() {
+                // A single line over the limit is cut
+                truncate(&mut line, MAX_READ_BYTES as usize);
+                page.lines.push((number, line));
+                number += 1;
+            }
+            page.next_offset = Some(number);
+            return Ok(page);
+        }
+        bytes += line.len() as u64 + 1;
+        page.lines.push((number, line));
+    }
+    page.total_lines = Some(number);
+    Ok(page)
+}
 
-        // Apply offset and limit
-        let lines: Vec<&str> = content.lines().collect();
-        let offset = params.offset.unwrap_or(1).saturating_sub(1);
-        let limit = params.limit.unwrap_or(lines.len());
+/// The last `limit` lines.
+async fn read_tail<R: AsyncBufRead + Unpin>(mut reader: R, limit: usize) -> std::io::Result<Page> {
+    let mut lines = VecDeque::with_capacity(limit.min(DEFAULT_EDGE_LINES * 10));
+    let mut buf = Vec::new();
+    let mut number = 0;
+    while let Some(line) = next_line(&mut reader, &mut buf).await? {
+        number += 1;
+        if limit == 0 {
+            continue;
+        }
+        if lines.len() == limit {
+            lines.pop_front();
+        }
+        lines.push_back((number, line));
+    }
 
-        let selected: Vec<String> = lines
-            .iter()
-            .skip(offset)
-            .take(limit)
-            .enumerate()
-            .map(|(i, line)| format!("{:>6}→{}", offset + i + 1, line))
-            .collect();
+    // Keep the lines nearest the end within the output limit
+    let mut page = Page {
+        total_lines: Some(number),
+        ..Page::default()
+    };
+    let mut bytes = 0;
+    while let Some((n, mut line)) = lines.pop_back() {
+        if bytes + line.len() as u64 > MAX_READ_BYTES {
+            page.clipped = true;
+            if page.lines.is_empty() {
+                truncate(&mut line, MAX_READ_BYTES as usize);
+                page.lines.push((n, line));
+            }
+            break;
+        }
+        bytes += line.len() as u64 + 1;
+        page.lines.push((n, line));
+    }
+    page.lines.reverse();
+    Ok(page)
+}
 
-        Ok(ToolResult::success(selected.join("\n")))
+/// Cut `line` to at most `max` bytes on a character boundary.
+fn truncate(line: &mut String, max: usize) {
+    let mut end = max.min(line.len());
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+    line.truncate(end);
+}
+
+/// Numbered lines, with a note on how to read on.
+fn render(page: &Page) -> String {
+    let mut content = page
+        .lines
+        .iter()
+        .map(|(n, line)| format!("{:>6}→{}", n, line))
+        .collect::<Vec<_>>()
+        .join("\n");
+    if page.clipped {
+        content.push_str(&format!(
+            "\n… output limit of {} KiB reached",
+            MAX_READ_BYTES / 1024
+        ));
+    }
+    if let Some(next) = page.next_offset {
+        content.push_str(&format!(
+            "\n… more lines follow; continue with offset={}",
+            next
+        ));
+    } else if let (Some(first), Some(total)) = (page.lines.first(), page.total_lines) {
+        if first.0 > 1 {
+            content.push_str(&format!(
+                "\n… showing lines {}-{} of {}",
+                first.0, total, total
+            ));
+        }
     }
+    content
+}
+
+/// A file too large to read whole: an error saying how to read it.
+fn too_large(path: &Path, size: u64) -> ToolResult {
+    let mut result = ToolResult::error(format!(
+        "File too large to read whole: {} is {} bytes (limit {}). Read it in line ranges with offset/limit, or with mode \"head\" or \"tail\"",
+        path.display(),
+        size,
+        MAX_READ_BYTES
+    ));
+    result.structured_output = Some(serde_json::json!({
+        "error": "file_too_large",
+        "path": path,
+        "size_bytes": size,
+        "max_bytes": MAX_READ_BYTES,
+        "suggestions": [
+            {"mode": "head", "limit": DEFAULT_EDGE_LINES},
+            {"mode": "tail", "limit": DEFAULT_EDGE_LINES},
+            {"offset": 1, "limit": 1000},
+        ],
+    }));
+    result
+}
+
+/// A binary file: its size and a hexdump of its first bytes.
+fn binary_preview(size: u64, sample: &[u8]) -> ToolResult {
+    let preview = &sample[..sample.len().min(HEXDUMP_BYTES)];
+    ToolResult::success_json(
+        format!(
+            "Binary file, {} bytes; first {} bytes:\n{}",
+            size,
+            preview.len(),
+            hexdump(preview)
+        ),
+        serde_json::json!({
+            "binary": true,
+            "size_bytes": size,
+            "preview_bytes": preview.len(),
+        }),
+    )
+}
+
+/// `hexdump -C` style lines: offset, 16 bytes in hex and as ASCII.
+fn hexdump(bytes: &[u8]) -> String {
+    bytes
+        .chunks(16)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let hex: Vec<String> = chunk.iter().map(|b| format!("{:02x}", b)).collect();
+            let (left, right) = hex.split_at(hex.len().min(8));
+            let ascii: String = chunk
+                .iter()
+                .map(|&b| {
+                    if b.is_ascii_graphic() || b == b' ' {
+                        b as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            format!(
+                "{:08x}  {:<23}  {:<23}  |{}|",
+                i * 16,
+                left.join(" "),
+                right.join(" "),
+                ascii
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 #[cfg(test)]
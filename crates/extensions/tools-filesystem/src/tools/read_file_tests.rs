@@ -200,3 +200,117 @@ async fn test_read_file_limit_zero() {
     assert!(result.success);
     assert!(result.content.is_empty());
 }
+
+#[tokio::test]
+async fn test_read_file_reports_next_offset() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.txt");
+    std::fs::write(&file_path, "line1\nline2\nline3\nline4\nline5").unwrap();
+
+    let tool = ReadFileTool::new();
+    let ctx = create_test_context(temp_dir.path().to_path_buf());
+    let params = serde_json::json!({"path": "test.txt", "offset": 2, "limit": 2});
+
+    let result = tool.execute(params, ctx).await.unwrap();
+    assert!(result.content.ends_with("continue with offset=4"));
+    let info = result.structured_output.unwrap();
+    assert_eq!(info["start_line"], 2);
+    assert_eq!(info["end_line"], 3);
+    assert_eq!(info["next_offset"], 4);
+}
+
+#[tokio::test]
+async fn test_read_file_head_and_tail() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("log.txt");
+    let content: String = (1..=500).map(|i| format!("entry {}\r\n", i)).collect();
+    std::fs::write(&file_path, content).unwrap();
+
+    let tool = ReadFileTool::new();
+    let ctx = create_test_context(temp_dir.path().to_path_buf());
+    let result = tool
+        .execute(serde_json::json!({"path": "log.txt", "mode": "head"}), ctx.clone())
+        .await
+        .unwrap();
+    let info = result.structured_output.unwrap();
+    assert_eq!(info["end_line"], 100);
+    assert_eq!(info["next_offset"], 101);
+    assert!(!result.content.contains('\r'));
+
+    let result = tool
+        .execute(
+            serde_json::json!({"path": "log.txt", "mode": "tail", "limit": 3}),
+            ctx,
+        )
+        .await
+        .unwrap();
+    assert!(result.content.starts_with("   498→entry 498\n   499→entry 499\n   500→entry 500"));
+    assert!(result.content.ends_with("showing lines 498-500 of 500"));
+    assert_eq!(result.structured_output.unwrap()["total_lines"], 500);
+}
+
+#[tokio::test]
+async fn test_read_large_file_needs_range() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("big.log");
+    let line = "x".repeat(99);
+    let content: String = (0..3000).map(|_| format!("{}\n", line)).collect();
+    std::fs::write(&file_path, content).unwrap();
+
+    let tool = ReadFileTool::new();
+    let ctx = create_test_context(temp_dir.path().to_path_buf());
+    let result = tool
+        .execute(serde_json::json!({"path": "big.log"}), ctx.clone())
+        .await
+        .unwrap();
+    assert!(!result.success);
+    assert!(result.error.unwrap().contains("offset/limit"));
+    let info = result.structured_output.unwrap();
+    assert_eq!(info["error"], "file_too_large");
+    assert_eq!(info["size_bytes"], 300_000);
+
+    // A range is read even when it exceeds the output limit
+    let result = tool
+        .execute(
+            serde_json::json!({"path": "big.log", "offset": 1, "limit": 3000}),
+            ctx,
+        )
+        .await
+        .unwrap();
+    assert!(result.success);
+    assert!(result.content.contains("output limit of 256 KiB reached"));
+    let next = result.structured_output.unwrap()["next_offset"].as_u64().unwrap();
+    assert!(next > 2000 && next < 3000);
+}
+
+#[tokio::test]
+async fn test_read_binary_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("app.bin");
+    let mut content = b"\x7fELF\x02\x01\x01\x00".to_vec();
+    content.extend(std::iter::repeat(0xAB).take(1000));
+    std::fs::write(&file_path, &content).unwrap();
+
+    let tool = ReadFileTool::new();
+    let ctx = create_test_context(temp_dir.path().to_path_buf());
+    let result = tool
+        .execute(serde_json::json!({"path": "app.bin"}), ctx)
+        .await
+        .unwrap();
+    assert!(result.success);
+    assert!(result.content.starts_with("Binary file, 1008 bytes; first 256 bytes:"));
+    assert!(result
+        .content
+        .contains("00000000  7f 45 4c 46 02 01 01 00  ab ab ab ab ab ab ab ab  |.ELF............|"));
+    assert_eq!(result.structured_output.unwrap()["binary"], true);
+}
+
+#[test]
+fn test_is_binary() {
+    assert!(!is_binary(b"plain text\n"));
+    assert!(!is_binary("caf\u{e9}".as_bytes()));
+    // A multi-byte character cut by the sample boundary
+    assert!(!is_binary(&"caf\u{e9}".as_bytes()[..4]));
+    assert!(is_binary(b"text\0more"));
+    assert!(is_binary(b"\xff\xfe\x41"));
+}
@@ -0,0 +1,364 @@
+//! Workspace snapshots for undoing file changes.
+//!
+//! Before a filesystem tool changes a file, the file's content is saved in
+//! a content-addressed store, once per file until the workspace's changes
+//! are committed or undone. The originals are kept on disk, so a bad run
+//! can still be reverted after a restart:
+//!
+//! ```text
+//! ~/.autohands/snapshots/
+//!   objects/<sha256>            original file contents
+//!   workspaces/<sha256>.json    files changed in a workspace, with the
+//!                               hash of their original content
+//! ```
+//!
+//! Only files are tracked: directories an undo leaves empty are removed,
+//! but empty directories a tool deleted are not recreated.
+
+use std::collections::{BTreeMap, HashSet};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::sync::Mutex;
+
+use super::patch::unified_diff;
+
+/// Files changed in one workspace since its last commit.
+#[derive(Debug, Serialize, Deserialize)]
+struct Changeset {
+    workspace: PathBuf,
+    /// Hash of each file's original content; `None` if it did not exist.
+    files: BTreeMap<PathBuf, Option<String>>,
+}
+
+/// How a file differs from its snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Modified,
+    Deleted,
+}
+
+impl ChangeKind {
+    /// One-letter status, as `git status --short` shows it.
+    pub fn letter(&self) -> char {
+        match self {
+            Self::Added => 'A',
+            Self::Modified => 'M',
+            Self::Deleted => 'D',
+        }
+    }
+}
+
+/// A file changed since its snapshot.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WorkspaceChange {
+    pub path: PathBuf,
+    pub kind: ChangeKind,
+    /// Unified diff from the snapshot; `None` for binary files.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+}
+
+/// Snapshots of the files tools change, per workspace.
+pub struct SnapshotStore {
+    root: PathBuf,
+    /// Serializes reads and writes of the changesets.
+    lock: Mutex<()>,
+}
+
+impl SnapshotStore {
+    /// Keep snapshots under `root`.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Snapshot `path` before it changes, or every file under it if it is
+    /// a directory. A file already tracked keeps its first snapshot.
+    pub async fn track(&self, workspace: &Path, path: &Path) -> io::Result<()> {
+        self.record(workspace, files_under(path)).await
+    }
+
+    /// Snapshot the files moving `source` to `destination` changes: those
+    /// under both paths and the ones the moved files will replace.
+    pub async fn track_move(
+        &self,
+        workspace: &Path,
+        source: &Path,
+        destination: &Path,
+    ) -> io::Result<()> {
+        let sources = files_under(source);
+        let mut paths: Vec<PathBuf> = sources
+            .iter()
+            .filter_map(|path| path.strip_prefix(source).ok())
+            .map(|relative| destination.join(relative))
+            .collect();
+        paths.extend(sources);
+        paths.extend(files_under(destination));
+        self.record(workspace, paths).await
+    }
+
+    /// Files of the workspace that differ from their snapshots.
+    pub async fn changes(&self, workspace: &Path) -> io::Result<Vec<WorkspaceChange>> {
+        let _guard = self.lock.lock().await;
+        let changeset = self.load(workspace).await?;
+        self.compare(&changeset).await
+    }
+
+    /// Put every changed file of the workspace back as it was snapshotted,
+    /// removing files that did not exist, and forget the snapshots.
+    ///
+    /// Returns the files restored.
+    pub async fn undo(&self, workspace: &Path) -> io::Result<Vec<PathBuf>> {
+        let _guard = self.lock.lock().await;
+        let mut changeset = self.load(workspace).await?;
+        let mut restored = Vec::new();
+        for (path, original) in &changeset.files {
+            let current = read_if_exists(path).await?;
+            match original {
+                Some(hash) => {
+                    let content = self.read_object(hash).await?;
+                    if current.as_ref() == Some(&content) {
+                        continue;
+                    }
+                    if let Some(parent) = path.parent() {
+                        tokio::fs::create_dir_all(parent).await?;
+                    }
+                    tokio::fs::write(path, &content).await?;
+                }
+                None => {
+                    if current.is_none() {
+                        continue;
+                    }
+                    tokio::fs::remove_file(path).await?;
+                    remove_empty_parents(path, &changeset.workspace).await;
+                }
+            }
+            restored.push(path.clone());
+        }
+        changeset.files.clear();
+        self.save(&changeset).await?;
+        self.prune().await?;
+        Ok(restored)
+    }
+
+    /// Accept the workspace's changes and forget their snapshots.
+    ///
+    /// Returns the changes accepted.
+    pub async fn commit(&self, workspace: &Path) -> io::Result<Vec<WorkspaceChange>> {
+        let _guard = self.lock.lock().await;
+        let mut changeset = self.load(workspace).await?;
+        let changes = self.compare(&changeset).await?;
+        changeset.files.clear();
+        self.save(&changeset).await?;
+        self.prune().await?;
+        Ok(changes)
+    }
+
+    async fn record(&self, workspace: &Path, paths: Vec<PathBuf>) -> io::Result<()> {
+        let _guard = self.lock.lock().await;
+        let mut changeset = self.load(workspace).await?;
+        let mut recorded = false;
+        for path in paths {
+            if changeset.files.contains_key(&path) {
+                continue;
+            }
+            let original = match read_if_exists(&path).await? {
+                Some(content) => Some(self.write_object(&content).await?),
+                None => None,
+            };
+            changeset.files.insert(path, original);
+            recorded = true;
+        }
+        if recorded {
+            self.save(&changeset).await?;
+        }
+        Ok(())
+    }
+
+    async fn compare(&self, changeset: &Changeset) -> io::Result<Vec<WorkspaceChange>> {
+        let mut changes = Vec::new();
+        for (path, original) in &changeset.files {
+            let old = match original {
+                Some(hash) => Some(self.read_object(hash).await?),
+                None => None,
+            };
+            let new = read_if_exists(path).await?;
+            let kind = match (&old, &new) {
+                (None, Some(_)) => ChangeKind::Added,
+                (Some(_), None) => ChangeKind::Deleted,
+                (Some(old), Some(new)) if old != new => ChangeKind::Modified,
+                _ => continue,
+            };
+            let name = path
+                .strip_prefix(&changeset.workspace)
+                .unwrap_or(path)
+                .display()
+                .to_string();
+            let diff = match (as_text(&old), as_text(&new)) {
+                (Some(old), Some(new)) => Some(unified_diff(&name, old, new)),
+                _ => None,
+            };
+            changes.push(WorkspaceChange {
+                path: path.clone(),
+                kind,
+                diff,
+            });
+        }
+        Ok(changes)
+    }
+
+    fn changeset_path(&self, workspace: &Path) -> PathBuf {
+        let key = hash(workspace.to_string_lossy().as_bytes());
+        self.root.join("workspaces").join(format!("{}.json", key))
+    }
+
+    async fn load(&self, workspace: &Path) -> io::Result<Changeset> {
+        // Paths resolved by the tools are canonical
+        let workspace = tokio::fs::canonicalize(workspace)
+            .await
+            .unwrap_or_else(|_| workspace.to_path_buf());
+        match tokio::fs::read(self.changeset_path(&workspace)).await {
+            Ok(content) => serde_json::from_slice(&content)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Changeset {
+                workspace,
+                files: BTreeMap::new(),
+            }),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn save(&self, changeset: &Changeset) -> io::Result<()> {
+        let path = self.changeset_path(&changeset.workspace);
+        if changeset.files.is_empty() {
+            return match tokio::fs::remove_file(&path).await {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+                _ => Ok(()),
+            };
+        }
+        let content = serde_json::to_vec_pretty(changeset)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        write_atomic(&path, &content).await
+    }
+
+    async fn write_object(&self, content: &[u8]) -> io::Result<String> {
+        let key = hash(content);
+        let path = self.root.join("objects").join(&key);
+        if !tokio::fs::try_exists(&path).await? {
+            write_atomic(&path, content).await?;
+        }
+        Ok(key)
+    }
+
+    async fn read_object(&self, key: &str) -> io::Result<Vec<u8>> {
+        tokio::fs::read(self.root.join("objects").join(key)).await
+    }
+
+    /// Remove the objects no workspace refers to any more.
+    async fn prune(&self) -> io::Result<()> {
+        let mut referenced = HashSet::new();
+        match tokio::fs::read_dir(self.root.join("workspaces")).await {
+            Ok(mut changesets) => {
+                while let Some(entry) = changesets.next_entry().await? {
+                    let content = tokio::fs::read(entry.path()).await?;
+                    if let Ok(changeset) = serde_json::from_slice::<Changeset>(&content) {
+                        referenced.extend(changeset.files.into_values().flatten());
+                    }
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+
+        let mut objects = match tokio::fs::read_dir(self.root.join("objects")).await {
+            Ok(dir) => dir,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        while let Some(entry) = objects.next_entry().await? {
+            if !referenced.contains(entry.file_name().to_string_lossy().as_ref()) {
+                tokio::fs::remove_file(entry.path()).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for SnapshotStore {
+    /// Store under `~/.autohands/snapshots`.
+    fn default() -> Self {
+        Self::new(
+            dirs::home_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join(".autohands")
+                .join("snapshots"),
+        )
+    }
+}
+
+fn hash(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+/// `path` itself, or the files under it if it is a directory.
+fn files_under(path: &Path) -> Vec<PathBuf> {
+    if !path.is_dir() {
+        return vec![path.to_path_buf()];
+    }
+    walkdir::WalkDir::new(path)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .collect()
+}
+
+async fn read_if_exists(path: &Path) -> io::Result<Option<Vec<u8>>> {
+    match tokio::fs::read(path).await {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Content as text, `Some(None)` for a missing file; `None` if binary.
+fn as_text(content: &Option<Vec<u8>>) -> Option<Option<&str>> {
+    match content {
+        Some(bytes) => std::str::from_utf8(bytes).ok().map(Some),
+        None => Some(None),
+    }
+}
+
+async fn write_atomic(path: &Path, content: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let tmp = path.with_extension("tmp");
+    tokio::fs::write(&tmp, content).await?;
+    tokio::fs::rename(&tmp, path).await
+}
+
+/// Remove the directories above `path` left empty, up to the workspace.
+async fn remove_empty_parents(path: &Path, workspace: &Path) {
+    let mut current = path;
+    while let Some(dir) = current.parent() {
+        if dir == workspace || !dir.starts_with(workspace) {
+            break;
+        }
+        if tokio::fs::remove_dir(dir).await.is_err() {
+            break;
+        }
+        current = dir;
+    }
+}
+
+#[cfg(test)]
+#[path = "snapshot_tests.rs"]
+mod tests;
@@ -0,0 +1,107 @@
+use super::*;
+use tempfile::TempDir;
+
+fn setup() -> (TempDir, PathBuf, SnapshotStore) {
+    let temp_dir = TempDir::new().unwrap();
+    let workspace = temp_dir.path().join("repo");
+    std::fs::create_dir_all(workspace.join("src")).unwrap();
+    std::fs::write(workspace.join("src/lib.rs"), "fn one() {}\n").unwrap();
+    std::fs::write(workspace.join("README"), "hello\n").unwrap();
+    let store = SnapshotStore::new(temp_dir.path().join("snapshots"));
+    (temp_dir, workspace, store)
+}
+
+#[tokio::test]
+async fn test_changes_and_undo() {
+    let (temp_dir, workspace, store) = setup();
+    let lib = workspace.join("src/lib.rs");
+    let readme = workspace.join("README");
+    let new = workspace.join("docs/guide.md");
+
+    store.track(&workspace, &lib).await.unwrap();
+    std::fs::write(&lib, "fn uno() {}\n").unwrap();
+    store.track(&workspace, &new).await.unwrap();
+    std::fs::create_dir_all(new.parent().unwrap()).unwrap();
+    std::fs::write(&new, "guide\n").unwrap();
+    store.track(&workspace, &readme).await.unwrap();
+    std::fs::remove_file(&readme).unwrap();
+    // Later changes keep the first snapshot
+    store.track(&workspace, &lib).await.unwrap();
+    std::fs::write(&lib, "fn eins() {}\n").unwrap();
+
+    let changes = store.changes(&workspace).await.unwrap();
+    let kinds: Vec<_> = changes.iter().map(|c| (c.kind, c.path.clone())).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            (ChangeKind::Deleted, readme.clone()),
+            (ChangeKind::Added, new.clone()),
+            (ChangeKind::Modified, lib.clone()),
+        ]
+    );
+    let diff = changes[2].diff.as_deref().unwrap();
+    assert!(diff.starts_with("--- a/src/lib.rs\n+++ b/src/lib.rs\n"));
+    assert!(diff.contains("-fn one() {}\n+fn eins() {}\n"));
+
+    let restored = store.undo(&workspace).await.unwrap();
+    assert_eq!(restored.len(), 3);
+    assert_eq!(std::fs::read_to_string(&lib).unwrap(), "fn one() {}\n");
+    assert_eq!(std::fs::read_to_string(&readme).unwrap(), "hello\n");
+    assert!(!workspace.join("docs").exists());
+
+    // Nothing is left to undo, and the stored originals are gone
+    assert!(store.changes(&workspace).await.unwrap().is_empty());
+    let objects = temp_dir.path().join("snapshots/objects");
+    assert_eq!(std::fs::read_dir(objects).unwrap().count(), 0);
+}
+
+#[tokio::test]
+async fn test_commit_forgets_snapshots() {
+    let (_temp_dir, workspace, store) = setup();
+    let lib = workspace.join("src/lib.rs");
+    store.track(&workspace, &lib).await.unwrap();
+    std::fs::write(&lib, "fn two() {}\n").unwrap();
+
+    let committed = store.commit(&workspace).await.unwrap();
+    assert_eq!(committed.len(), 1);
+    assert!(store.undo(&workspace).await.unwrap().is_empty());
+    assert_eq!(std::fs::read_to_string(&lib).unwrap(), "fn two() {}\n");
+}
+
+#[tokio::test]
+async fn test_track_directory_move() {
+    let (_temp_dir, workspace, store) = setup();
+    let source = workspace.join("src");
+    let destination = workspace.join("lib/src");
+    store
+        .track_move(&workspace, &source, &destination)
+        .await
+        .unwrap();
+    std::fs::create_dir_all(destination.parent().unwrap()).unwrap();
+    std::fs::rename(&source, &destination).unwrap();
+
+    let changes = store.changes(&workspace).await.unwrap();
+    assert_eq!(changes.len(), 2);
+
+    store.undo(&workspace).await.unwrap();
+    assert_eq!(
+        std::fs::read_to_string(source.join("lib.rs")).unwrap(),
+        "fn one() {}\n"
+    );
+    assert!(!workspace.join("lib").exists());
+}
+
+#[tokio::test]
+async fn test_snapshots_survive_restart() {
+    let (temp_dir, workspace, store) = setup();
+    let readme = workspace.join("README");
+    store.track(&workspace, &readme).await.unwrap();
+    std::fs::write(&readme, [0xff, 0xfe]).unwrap();
+
+    let store = SnapshotStore::new(temp_dir.path().join("snapshots"));
+    let changes = store.changes(&workspace).await.unwrap();
+    assert_eq!(changes[0].kind, ChangeKind::Modified);
+    assert_eq!(changes[0].diff, None);
+    store.undo(&workspace).await.unwrap();
+    assert_eq!(std::fs::read_to_string(&readme).unwrap(), "hello\n");
+}
@@ -0,0 +1,217 @@
+//! Workspace diff, undo and commit tools.
+//!
+//! They work on the snapshots the filesystem tools take before changing
+//! files, for the working directory of the call.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{PathAccess, Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::types::RiskLevel;
+
+use super::snapshot::{SnapshotStore, WorkspaceChange};
+
+/// Parameters for workspace_diff tool.
+#[derive(Debug, Deserialize)]
+struct WorkspaceDiffParams {
+    /// Only show changes under this path.
+    path: Option<String>,
+}
+
+/// Show the changes made to the workspace since its last commit.
+pub struct WorkspaceDiffTool {
+    definition: ToolDefinition,
+    snapshots: Arc<SnapshotStore>,
+}
+
+impl WorkspaceDiffTool {
+    pub fn new(snapshots: Arc<SnapshotStore>) -> Self {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Only show changes to this file or under this directory"
+                }
+            }
+        });
+
+        Self {
+            definition: ToolDefinition::new(
+                "workspace_diff",
+                "Workspace Diff",
+                "Show the files changed in the workspace since the last workspace_commit, as a unified diff",
+            )
+            .with_parameters_schema(schema)
+            .with_risk_level(RiskLevel::Low)
+            .with_read_only(),
+            snapshots,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for WorkspaceDiffTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: WorkspaceDiffParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
+
+        let mut changes = self.snapshots.changes(&ctx.work_dir).await?;
+        if let Some(path) = &params.path {
+            let path = ctx.resolve_path(path, PathAccess::Read)?;
+            changes.retain(|change| change.path.starts_with(&path));
+        }
+        if changes.is_empty() {
+            return Ok(ToolResult::success(
+                "No changes since the last workspace commit",
+            ));
+        }
+
+        let mut content = summary(&changes);
+        for change in &changes {
+            content.push('\n');
+            match &change.diff {
+                Some(diff) => content.push_str(diff),
+                None => {
+                    content.push_str(&format!("Binary file {} changed\n", change.path.display()))
+                }
+            }
+        }
+        Ok(ToolResult::success_json(
+            content,
+            serde_json::json!({ "changes": changes }),
+        ))
+    }
+}
+
+/// Revert the workspace to its last commit.
+pub struct WorkspaceUndoTool {
+    definition: ToolDefinition,
+    snapshots: Arc<SnapshotStore>,
+}
+
+impl WorkspaceUndoTool {
+    pub fn new(snapshots: Arc<SnapshotStore>) -> Self {
+        Self {
+            definition: ToolDefinition::new(
+                "workspace_undo",
+                "Workspace Undo",
+                "Revert every file changed in the workspace since the last workspace_commit, removing files created since",
+            )
+            .with_parameters_schema(serde_json::json!({ "type": "object", "properties": {} }))
+            .with_risk_level(RiskLevel::Medium),
+            snapshots,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for WorkspaceUndoTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        _params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let restored = self.snapshots.undo(&ctx.work_dir).await?;
+        if restored.is_empty() {
+            return Ok(ToolResult::success("Nothing to undo"));
+        }
+        let mut content = format!("Restored {} file(s):", restored.len());
+        for path in &restored {
+            content.push_str(&format!("\n  {}", path.display()));
+        }
+        Ok(ToolResult::success_json(
+            content,
+            serde_json::json!({ "restored": restored }),
+        ))
+    }
+
+    async fn simulate(
+        &self,
+        _params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let changes = self.snapshots.changes(&ctx.work_dir).await?;
+        Ok(ToolResult::simulated(format!(
+            "Would revert {} file(s); nothing was changed.\n{}",
+            changes.len(),
+            summary(&changes)
+        )))
+    }
+}
+
+/// Accept the workspace's changes, so they can no longer be undone.
+pub struct WorkspaceCommitTool {
+    definition: ToolDefinition,
+    snapshots: Arc<SnapshotStore>,
+}
+
+impl WorkspaceCommitTool {
+    pub fn new(snapshots: Arc<SnapshotStore>) -> Self {
+        Self {
+            definition: ToolDefinition::new(
+                "workspace_commit",
+                "Workspace Commit",
+                "Accept the changes made to the workspace, dropping their snapshots so workspace_undo no longer reverts them",
+            )
+            .with_parameters_schema(serde_json::json!({ "type": "object", "properties": {} }))
+            .with_risk_level(RiskLevel::Low),
+            snapshots,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for WorkspaceCommitTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        _params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let changes = self.snapshots.commit(&ctx.work_dir).await?;
+        if changes.is_empty() {
+            return Ok(ToolResult::success("Nothing to commit"));
+        }
+        Ok(ToolResult::success(format!(
+            "Committed {} changed file(s):\n{}",
+            changes.len(),
+            summary(&changes)
+        )))
+    }
+}
+
+/// One `A`/`M`/`D` line per changed file.
+fn summary(changes: &[WorkspaceChange]) -> String {
+    let mut summary = String::new();
+    for change in changes {
+        summary.push_str(&format!(
+            "{} {}\n",
+            change.kind.letter(),
+            change.path.display()
+        ));
+    }
+    summary
+}
+
+#[cfg(test)]
+#[path = "workspace_tests.rs"]
+mod tests;
@@ -0,0 +1,95 @@
+use super::*;
+use crate::tools::WriteFileTool;
+use tempfile::TempDir;
+
+fn setup() -> (TempDir, Arc<SnapshotStore>, ToolContext) {
+    let temp_dir = TempDir::new().unwrap();
+    let workspace = temp_dir.path().join("repo");
+    std::fs::create_dir_all(&workspace).unwrap();
+    std::fs::write(workspace.join("a.txt"), "one\n").unwrap();
+    let snapshots = Arc::new(SnapshotStore::new(temp_dir.path().join("snapshots")));
+    let ctx = ToolContext::new("test", workspace);
+    (temp_dir, snapshots, ctx)
+}
+
+async fn write(snapshots: &Arc<SnapshotStore>, ctx: &ToolContext, path: &str, content: &str) {
+    WriteFileTool::new()
+        .with_snapshots(snapshots.clone())
+        .execute(
+            serde_json::json!({ "path": path, "content": content }),
+            ctx.clone(),
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_diff_and_undo() {
+    let (_temp_dir, snapshots, ctx) = setup();
+    write(&snapshots, &ctx, "a.txt", "two\n").await;
+    write(&snapshots, &ctx, "b.txt", "new\n").await;
+
+    let diff = WorkspaceDiffTool::new(snapshots.clone());
+    let result = diff
+        .execute(serde_json::json!({}), ctx.clone())
+        .await
+        .unwrap();
+    assert!(result.content.starts_with("M "));
+    assert!(result.content.contains("-one\n+two\n"));
+    assert!(result.content.contains("--- /dev/null\n+++ b/b.txt\n"));
+    let output = result.structured_output.unwrap();
+    assert_eq!(output["changes"][1]["kind"], "added");
+
+    let result = diff
+        .execute(serde_json::json!({ "path": "b.txt" }), ctx.clone())
+        .await
+        .unwrap();
+    assert!(!result.content.contains("a.txt"));
+
+    let undo = WorkspaceUndoTool::new(snapshots.clone());
+    let result = undo
+        .execute(serde_json::json!({}), ctx.clone())
+        .await
+        .unwrap();
+    assert!(result.content.starts_with("Restored 2 file(s)"));
+    assert_eq!(
+        std::fs::read_to_string(ctx.work_dir.join("a.txt")).unwrap(),
+        "one\n"
+    );
+    assert!(!ctx.work_dir.join("b.txt").exists());
+
+    let result = undo.execute(serde_json::json!({}), ctx).await.unwrap();
+    assert_eq!(result.content, "Nothing to undo");
+}
+
+#[tokio::test]
+async fn test_commit() {
+    let (_temp_dir, snapshots, ctx) = setup();
+    write(&snapshots, &ctx, "a.txt", "two\n").await;
+
+    let result = WorkspaceCommitTool::new(snapshots.clone())
+        .execute(serde_json::json!({}), ctx.clone())
+        .await
+        .unwrap();
+    assert!(result.content.starts_with("Committed 1 changed file(s)"));
+
+    let result = WorkspaceDiffTool::new(snapshots)
+        .execute(serde_json::json!({}), ctx)
+        .await
+        .unwrap();
+    assert_eq!(result.content, "No changes since the last workspace commit");
+}
+
+#[test]
+fn test_tool_definitions() {
+    let snapshots = Arc::new(SnapshotStore::default());
+    assert!(
+        WorkspaceDiffTool::new(snapshots.clone())
+            .definition()
+            .read_only
+    );
+    assert_eq!(
+        WorkspaceUndoTool::new(snapshots).definition().risk_level,
+        RiskLevel::Medium
+    );
+}
@@ -1,13 +1,15 @@
 //! Write file tool.
 
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use serde::Deserialize;
 
 use autohands_protocols::error::ToolError;
-use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::tool::{PathAccess, Tool, ToolContext, ToolDefinition, ToolResult};
 use autohands_protocols::types::RiskLevel;
 
-use super::resolve_path_safe;
+use super::snapshot::SnapshotStore;
 
 /// Parameters for write_file tool.
 #[derive(Debug, Deserialize)]
@@ -21,6 +23,7 @@ struct WriteFileParams {
 /// Write file tool implementation.
 pub struct WriteFileTool {
     definition: ToolDefinition,
+    snapshots: Option<Arc<SnapshotStore>>,
 }
 
 impl WriteFileTool {
@@ -48,8 +51,15 @@ impl WriteFileTool {
             )
             .with_parameters_schema(schema)
             .with_risk_level(RiskLevel::Medium),
+            snapshots: None,
         }
     }
+
+    /// Snapshot the file before writing it, so `workspace_undo` can restore it.
+    pub fn with_snapshots(mut self, snapshots: Arc<SnapshotStore>) -> Self {
+        self.snapshots = Some(snapshots);
+        self
+    }
 }
 
 impl Default for WriteFileTool {
@@ -72,7 +82,11 @@ impl Tool for WriteFileTool {
         let params: WriteFileParams = serde_json::from_value(params)
             .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
 
-        let path = resolve_path_safe(&params.path, &ctx.work_dir)?;
+        let path = ctx.resolve_path(&params.path, PathAccess::Write)?;
+
+        if let Some(snapshots) = &self.snapshots {
+            snapshots.track(&ctx.work_dir, &path).await?;
+        }
 
         // Create parent directories if needed
         if let Some(parent) = path.parent() {
@@ -87,6 +101,26 @@ impl Tool for WriteFileTool {
             path.display()
         )))
     }
+
+    async fn simulate(
+        &self,
+        params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: WriteFileParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
+
+        let path = ctx.resolve_path(&params.path, PathAccess::Write)?;
+        let action = match tokio::fs::metadata(&path).await {
+            Ok(metadata) => format!("overwrite {} ({} bytes)", path.display(), metadata.len()),
+            Err(_) => format!("create {}", path.display()),
+        };
+        Ok(ToolResult::simulated(format!(
+            "Would {} with {} bytes; nothing was changed.",
+            action,
+            params.content.len()
+        )))
+    }
 }
 
 #[cfg(test)]
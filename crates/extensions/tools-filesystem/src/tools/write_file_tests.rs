@@ -175,3 +175,20 @@ async fn test_write_file_unicode_content() {
     let content = std::fs::read_to_string(&file_path).unwrap();
     assert_eq!(content, "你好世界 🌍");
 }
+
+#[tokio::test]
+async fn test_write_file_read_only_sandbox() {
+    let temp_dir = TempDir::new().unwrap();
+    let sandbox = autohands_protocols::tool::SandboxPolicy {
+        read_only: true,
+        ..Default::default()
+    };
+    let tool = WriteFileTool::new();
+    let ctx = create_test_context(temp_dir.path().to_path_buf())
+        .with_sandbox(std::sync::Arc::new(sandbox));
+    let params = serde_json::json!({"path": "out.txt", "content": "x"});
+
+    let err = tool.execute(params, ctx).await.unwrap_err();
+    assert!(err.to_string().contains("read-only"));
+    assert!(!temp_dir.path().join("out.txt").exists());
+}
@@ -0,0 +1,99 @@
+//! Git tools extension definition.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::debug;
+
+use autohands_protocols::error::ExtensionError;
+use autohands_protocols::extension::{Extension, ExtensionContext, ExtensionManifest, Provides};
+use autohands_protocols::types::Version;
+
+use crate::tools::{
+    GitBranchTool, GitCommitTool, GitDiffTool, GitLogTool, GitPushTool, GitStatusTool,
+};
+
+/// Git tools extension.
+pub struct GitToolsExtension {
+    manifest: ExtensionManifest,
+}
+
+impl GitToolsExtension {
+    pub fn new() -> Self {
+        let mut manifest = ExtensionManifest::new("tools-git", "Git Tools", Version::new(0, 1, 0));
+        manifest.description =
+            "Git operations: status, diff, commit, branches, history and push".to_string();
+        manifest.provides = Provides {
+            tools: vec![
+                "git_status".to_string(),
+                "git_diff".to_string(),
+                "git_commit".to_string(),
+                "git_branch".to_string(),
+                "git_log".to_string(),
+                "git_push".to_string(),
+            ],
+            ..Default::default()
+        };
+
+        Self { manifest }
+    }
+}
+
+impl Default for GitToolsExtension {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Extension for GitToolsExtension {
+    fn manifest(&self) -> &ExtensionManifest {
+        &self.manifest
+    }
+
+    async fn initialize(&mut self, ctx: ExtensionContext) -> Result<(), ExtensionError> {
+        debug!("Git tools initialized");
+
+        ctx.tool_registry
+            .register_tool(Arc::new(GitStatusTool::new()))?;
+        ctx.tool_registry
+            .register_tool(Arc::new(GitDiffTool::new()))?;
+        ctx.tool_registry
+            .register_tool(Arc::new(GitCommitTool::new()))?;
+        ctx.tool_registry
+            .register_tool(Arc::new(GitBranchTool::new()))?;
+        ctx.tool_registry
+            .register_tool(Arc::new(GitLogTool::new()))?;
+        ctx.tool_registry
+            .register_tool(Arc::new(GitPushTool::new()))?;
+
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extension_manifest() {
+        let ext = GitToolsExtension::new();
+        assert_eq!(ext.manifest().id, "tools-git");
+        assert_eq!(ext.manifest().name, "Git Tools");
+        assert_eq!(ext.manifest().provides.tools.len(), 6);
+        assert!(ext
+            .manifest()
+            .provides
+            .tools
+            .contains(&"git_push".to_string()));
+    }
+}
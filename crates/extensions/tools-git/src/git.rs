@@ -0,0 +1,350 @@
+//! Running git and parsing its machine-readable output.
+//!
+//! Commands run with `git -C <repo>`, never prompting for credentials, and
+//! ask for porcelain or custom-delimited formats so results can be returned
+//! as structured data instead of screen-oriented text.
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use serde::Serialize;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{PathAccess, ToolContext};
+
+/// Field separator of custom formats (ASCII unit separator).
+const FIELD: char = '\u{1f}';
+
+/// Record separator of custom formats (ASCII record separator).
+const RECORD: char = '\u{1e}';
+
+/// Repository a call works on: `repo` if given, else the working directory.
+pub(crate) fn repo_dir(
+    ctx: &ToolContext,
+    repo: Option<&str>,
+    access: PathAccess,
+) -> Result<PathBuf, ToolError> {
+    ctx.resolve_path(repo.unwrap_or("."), access)
+}
+
+/// Run git in `repo`, returning its standard output.
+pub(crate) async fn git(repo: &Path, args: &[&str]) -> Result<String, ToolError> {
+    let output = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(args)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .env("GIT_PAGER", "cat")
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| ToolError::ExecutionFailed(format!("Failed to run git: {}", e)))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let message = if stderr.trim().is_empty() {
+            stdout.trim()
+        } else {
+            stderr.trim()
+        };
+        return Err(ToolError::ExecutionFailed(format!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            message
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Reject a revision, branch or remote name git would read as an option.
+pub(crate) fn check_name(value: &str, what: &str) -> Result<(), ToolError> {
+    if value.is_empty() || value.starts_with('-') || value.contains(['\0', '\n']) {
+        return Err(ToolError::InvalidParameters(format!(
+            "Invalid {}: '{}'",
+            what, value
+        )));
+    }
+    Ok(())
+}
+
+/// Reject a remote name that is not a plain name: `+` and `:` would make a
+/// push refspec of it.
+pub(crate) fn check_remote(value: &str) -> Result<(), ToolError> {
+    check_name(value, "remote")?;
+    if value.contains(['+', ':']) {
+        return Err(ToolError::InvalidParameters(format!("Invalid remote: '{}'", value)));
+    }
+    Ok(())
+}
+
+/// Reject a branch name git would not accept as a branch, or that would
+/// make a push refspec (`+main` forces, `:main` deletes the remote branch).
+pub(crate) async fn check_branch(repo: &Path, value: &str) -> Result<(), ToolError> {
+    check_name(value, "branch")?;
+    let invalid = || ToolError::InvalidParameters(format!("Invalid branch: '{}'", value));
+    if value.contains(['+', ':']) {
+        return Err(invalid());
+    }
+    git(repo, &["check-ref-format", "--branch", value])
+        .await
+        .map_err(|_| invalid())?;
+    Ok(())
+}
+
+/// How a file differs, in the index or the working tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileChange {
+    Modified,
+    Added,
+    Deleted,
+    Renamed,
+    Copied,
+    TypeChanged,
+    Untracked,
+    Conflicted,
+}
+
+impl FileChange {
+    fn from_code(code: char) -> Option<Self> {
+        match code {
+            'M' => Some(Self::Modified),
+            'A' => Some(Self::Added),
+            'D' => Some(Self::Deleted),
+            'R' => Some(Self::Renamed),
+            'C' => Some(Self::Copied),
+            'T' => Some(Self::TypeChanged),
+            _ => None,
+        }
+    }
+}
+
+/// A changed file of `git status`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileStatus {
+    pub path: String,
+    /// Path before a rename or copy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub original_path: Option<String>,
+    /// Change staged in the index.
+    pub staged: Option<FileChange>,
+    /// Change in the working tree, not staged.
+    pub unstaged: Option<FileChange>,
+}
+
+/// Branch state and changed files of a repository.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct StatusReport {
+    /// Current branch; `None` when HEAD is detached.
+    pub branch: Option<String>,
+    /// Commit HEAD points at; `None` before the first commit.
+    pub head: Option<String>,
+    pub upstream: Option<String>,
+    /// Commits on the branch and not on its upstream.
+    pub ahead: u32,
+    /// Commits on the upstream and not on the branch.
+    pub behind: u32,
+    pub files: Vec<FileStatus>,
+}
+
+/// Parse `git status --porcelain=v2 --branch -z`.
+pub(crate) fn parse_status(output: &str) -> StatusReport {
+    let mut report = StatusReport::default();
+    let mut entries = output.split('\0').filter(|entry| !entry.is_empty());
+    while let Some(entry) = entries.next() {
+        if let Some(header) = entry.strip_prefix("# ") {
+            let (key, value) = header.split_once(' ').unwrap_or((header, ""));
+            match key {
+                "branch.oid" if value != "(initial)" => report.head = Some(value.to_string()),
+                "branch.head" if value != "(detached)" => report.branch = Some(value.to_string()),
+                "branch.upstream" => report.upstream = Some(value.to_string()),
+                "branch.ab" => {
+                    for count in value.split_whitespace() {
+                        if let Some(ahead) = count.strip_prefix('+') {
+                            report.ahead = ahead.parse().unwrap_or_default();
+                        } else if let Some(behind) = count.strip_prefix('-') {
+                            report.behind = behind.parse().unwrap_or_default();
+                        }
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        let codes = |fields: &[&str]| {
+            let mut xy = fields.get(1).copied().unwrap_or_default().chars();
+            (
+                xy.next().and_then(FileChange::from_code),
+                xy.next().and_then(FileChange::from_code),
+            )
+        };
+        let file = match entry.chars().next() {
+            Some('1') => {
+                let fields: Vec<&str> = entry.splitn(9, ' ').collect();
+                let (staged, unstaged) = codes(&fields);
+                fields.get(8).map(|path| FileStatus {
+                    path: path.to_string(),
+                    original_path: None,
+                    staged,
+                    unstaged,
+                })
+            }
+            Some('2') => {
+                // The original path follows as the next entry
+                let fields: Vec<&str> = entry.splitn(10, ' ').collect();
+                let (staged, unstaged) = codes(&fields);
+                let original_path = entries.next().map(str::to_string);
+                fields.get(9).map(|path| FileStatus {
+                    path: path.to_string(),
+                    original_path,
+                    staged,
+                    unstaged,
+                })
+            }
+            Some('u') => entry.splitn(11, ' ').nth(10).map(|path| FileStatus {
+                path: path.to_string(),
+                original_path: None,
+                staged: None,
+                unstaged: Some(FileChange::Conflicted),
+            }),
+            Some('?') => entry.get(2..).map(|path| FileStatus {
+                path: path.to_string(),
+                original_path: None,
+                staged: None,
+                unstaged: Some(FileChange::Untracked),
+            }),
+            _ => None,
+        };
+        report.files.extend(file);
+    }
+    report
+}
+
+/// Custom `git log` format parsed by [`parse_log`].
+pub(crate) fn log_format() -> String {
+    format!(
+        "--format=%H{f}%h{f}%an{f}%ae{f}%aI{f}%s{r}",
+        f = "%x1f",
+        r = "%x1e"
+    )
+}
+
+/// A commit of `git log`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Commit {
+    pub hash: String,
+    pub short_hash: String,
+    pub author: String,
+    pub email: String,
+    /// Author date, RFC 3339.
+    pub date: String,
+    pub subject: String,
+}
+
+/// Parse `git log` output in [`log_format`].
+pub(crate) fn parse_log(output: &str) -> Vec<Commit> {
+    output
+        .split(RECORD)
+        .filter_map(|record| {
+            let mut fields = record.trim_start_matches('\n').split(FIELD);
+            Some(Commit {
+                hash: fields.next().filter(|hash| !hash.is_empty())?.to_string(),
+                short_hash: fields.next()?.to_string(),
+                author: fields.next()?.to_string(),
+                email: fields.next()?.to_string(),
+                date: fields.next()?.to_string(),
+                subject: fields.next()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Lines added and removed in one file of a diff.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiffStat {
+    pub path: String,
+    /// `None` for binary files.
+    pub added: Option<u64>,
+    pub removed: Option<u64>,
+}
+
+/// Parse `git diff --numstat`.
+pub(crate) fn parse_numstat(output: &str) -> Vec<DiffStat> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            let added = fields.next()?.parse().ok();
+            let removed = fields.next()?.parse().ok();
+            Some(DiffStat {
+                path: fields.next()?.to_string(),
+                added,
+                removed,
+            })
+        })
+        .collect()
+}
+
+/// Custom `git branch` format parsed by [`parse_branches`].
+pub(crate) const BRANCH_FORMAT: &str =
+    "--format=%(HEAD)%09%(refname:short)%09%(objectname:short)%09%(upstream:short)%09%(upstream:track,nobracket)";
+
+/// A local branch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Branch {
+    pub name: String,
+    pub current: bool,
+    /// Commit the branch points at, abbreviated.
+    pub commit: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upstream: Option<String>,
+    /// How the branch compares to its upstream, e.g. `ahead 1, behind 2`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tracking: Option<String>,
+}
+
+/// Parse `git branch` output in [`BRANCH_FORMAT`].
+pub(crate) fn parse_branches(output: &str) -> Vec<Branch> {
+    let non_empty = |field: Option<&str>| field.filter(|f| !f.is_empty()).map(str::to_string);
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let current = fields.next()? == "*";
+            let name = fields.next()?.to_string();
+            let commit = fields.next()?.to_string();
+            Some(Branch {
+                name,
+                current,
+                commit,
+                upstream: non_empty(fields.next()),
+                tracking: non_empty(fields.next()),
+            })
+        })
+        .collect()
+}
+
+/// Cut `text` to at most `max_chars` bytes on a character boundary, noting
+/// the cut.
+pub(crate) fn truncate(text: String, max_chars: usize, what: &str) -> String {
+    if text.len() <= max_chars {
+        return text;
+    }
+    let mut end = max_chars;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!(
+        "{}\n… {} truncated at {} characters",
+        &text[..end],
+        what,
+        max_chars
+    )
+}
+
+#[cfg(test)]
+#[path = "git_tests.rs"]
+mod tests;
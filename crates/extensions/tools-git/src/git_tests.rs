@@ -0,0 +1,92 @@
+use super::*;
+
+#[test]
+fn test_parse_status() {
+    let output = [
+        "# branch.oid 1f2e3d4c5b6a79881f2e3d4c5b6a79881f2e3d4c",
+        "# branch.head main",
+        "# branch.upstream origin/main",
+        "# branch.ab +2 -1",
+        "1 M. N... 100644 100644 100644 aaaa bbbb src/lib.rs",
+        "1 .D N... 100644 100644 000000 aaaa aaaa old notes.txt",
+        "2 R. N... 100644 100644 100644 aaaa aaaa R100 src/new.rs",
+        "src/old.rs",
+        "u UU N... 100644 100644 100644 100644 aaaa bbbb cccc Cargo.lock",
+        "? scratch.txt",
+        "",
+    ]
+    .join("\0");
+
+    let report = parse_status(&output);
+    assert_eq!(report.branch.as_deref(), Some("main"));
+    assert_eq!(report.upstream.as_deref(), Some("origin/main"));
+    assert_eq!((report.ahead, report.behind), (2, 1));
+    assert_eq!(report.files.len(), 5);
+
+    assert_eq!(report.files[0].path, "src/lib.rs");
+    assert_eq!(report.files[0].staged, Some(FileChange::Modified));
+    assert_eq!(report.files[0].unstaged, None);
+    assert_eq!(report.files[1].path, "old notes.txt");
+    assert_eq!(report.files[1].unstaged, Some(FileChange::Deleted));
+    assert_eq!(report.files[2].path, "src/new.rs");
+    assert_eq!(report.files[2].original_path.as_deref(), Some("src/old.rs"));
+    assert_eq!(report.files[2].staged, Some(FileChange::Renamed));
+    assert_eq!(report.files[3].unstaged, Some(FileChange::Conflicted));
+    assert_eq!(report.files[4].path, "scratch.txt");
+    assert_eq!(report.files[4].unstaged, Some(FileChange::Untracked));
+}
+
+#[test]
+fn test_parse_status_detached_initial() {
+    let report = parse_status("# branch.oid (initial)\0# branch.head (detached)\0");
+    assert_eq!(report, StatusReport::default());
+}
+
+#[test]
+fn test_parse_log() {
+    let output = "abc123\u{1f}abc\u{1f}Ada\u{1f}ada@example.com\u{1f}2024-05-01T10:00:00+02:00\u{1f}Fix: a | b\u{1e}\n\
+                  def456\u{1f}def\u{1f}Bob\u{1f}bob@example.com\u{1f}2024-04-30T09:00:00Z\u{1f}Initial\u{1e}\n";
+    let commits = parse_log(output);
+    assert_eq!(commits.len(), 2);
+    assert_eq!(commits[0].hash, "abc123");
+    assert_eq!(commits[0].subject, "Fix: a | b");
+    assert_eq!(commits[1].author, "Bob");
+    assert!(parse_log("").is_empty());
+}
+
+#[test]
+fn test_parse_numstat_and_branches() {
+    let stats = parse_numstat("3\t1\tsrc/lib.rs\n-\t-\tlogo.png\n");
+    assert_eq!(stats[0].added, Some(3));
+    assert_eq!(stats[1].path, "logo.png");
+    assert_eq!(stats[1].added, None);
+
+    let branches = parse_branches("*\tmain\tabc1234\torigin/main\tahead 1\n \tfix\tdef5678\t\t\n");
+    assert!(branches[0].current);
+    assert_eq!(branches[0].tracking.as_deref(), Some("ahead 1"));
+    assert_eq!(branches[1].name, "fix");
+    assert_eq!(branches[1].upstream, None);
+}
+
+#[test]
+fn test_check_name() {
+    assert!(check_name("feature/x", "branch").is_ok());
+    assert!(check_name("--upload-pack=evil", "remote").is_err());
+    assert!(check_name("", "branch").is_err());
+}
+
+#[test]
+fn test_check_remote() {
+    assert!(check_remote("origin").is_ok());
+    assert!(check_remote("+origin").is_err());
+    assert!(check_remote("origin:main").is_err());
+}
+
+#[tokio::test]
+async fn test_check_branch() {
+    let dir = tempfile::TempDir::new().unwrap();
+    assert!(check_branch(dir.path(), "feature/x").await.is_ok());
+    for name in ["+main", ":main", "main:main", "a..b", "-main", "feature/"] {
+        assert!(check_branch(dir.path(), name).await.is_err(), "{}", name);
+    }
+}
@@ -0,0 +1,16 @@
+//! Git tools for AutoHands.
+//!
+//! Provides git_status, git_diff, git_commit, git_branch, git_log and
+//! git_push, so coding agents work with repositories through structured
+//! results instead of raw shell commands. The tools run the `git` binary
+//! and parse its machine-readable output.
+
+mod extension;
+mod git;
+mod tools;
+
+pub use extension::GitToolsExtension;
+pub use git::{Branch, Commit, DiffStat, FileChange, FileStatus, StatusReport};
+pub use tools::{
+    GitBranchTool, GitCommitTool, GitDiffTool, GitLogTool, GitPushTool, GitStatusTool,
+};
@@ -0,0 +1,179 @@
+//! Branch tool.
+
+use std::path::Path;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{PathAccess, Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::types::RiskLevel;
+
+use crate::git::{check_branch, check_name, git, parse_branches, repo_dir, BRANCH_FORMAT};
+
+/// What git_branch does.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum BranchAction {
+    #[default]
+    List,
+    Create,
+    Switch,
+    Delete,
+}
+
+/// Parameters for git_branch.
+#[derive(Debug, Deserialize)]
+struct BranchParams {
+    /// Repository directory; the working directory by default.
+    #[serde(default)]
+    repo: Option<String>,
+    #[serde(default)]
+    action: BranchAction,
+    /// Branch to create, switch to or delete.
+    #[serde(default)]
+    name: Option<String>,
+    /// Revision a created branch starts at; HEAD by default.
+    #[serde(default)]
+    start_point: Option<String>,
+    /// Switch to the branch after creating it.
+    #[serde(default)]
+    switch: bool,
+}
+
+/// Tool listing, creating, switching and deleting local branches.
+pub struct GitBranchTool {
+    definition: ToolDefinition,
+}
+
+impl GitBranchTool {
+    /// Create a new branch tool.
+    pub fn new() -> Self {
+        let definition = ToolDefinition::new(
+            "git_branch",
+            "Git Branch",
+            "List local branches, or create, switch to or delete one. Deleting refuses branches that are not merged",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "repo": {
+                    "type": "string",
+                    "description": "Repository directory (default: working directory)"
+                },
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "create", "switch", "delete"],
+                    "description": "What to do (default: list)"
+                },
+                "name": {
+                    "type": "string",
+                    "description": "Branch name, required unless listing"
+                },
+                "start_point": {
+                    "type": "string",
+                    "description": "Revision a new branch starts at (default: HEAD)"
+                },
+                "switch": {
+                    "type": "boolean",
+                    "description": "Switch to the new branch after creating it (default: false)"
+                }
+            }
+        }))
+        .with_risk_level(RiskLevel::Medium);
+
+        Self { definition }
+    }
+}
+
+impl Default for GitBranchTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Tool for GitBranchTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: BranchParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
+
+        let access = match params.action {
+            BranchAction::List => PathAccess::Read,
+            _ => PathAccess::Write,
+        };
+        let repo = repo_dir(&ctx, params.repo.as_deref(), access)?;
+        let name = match (&params.action, params.name.as_deref()) {
+            (BranchAction::List, _) => return list_branches(&repo).await,
+            (_, Some(name)) => name,
+            (_, None) => {
+                return Err(ToolError::InvalidParameters(
+                    "name is required to create, switch or delete".to_string(),
+                ))
+            }
+        };
+        check_branch(&repo, name).await?;
+
+        let content = match params.action {
+            BranchAction::Create => {
+                let mut args = if params.switch {
+                    vec!["switch", "--create", name]
+                } else {
+                    vec!["branch", name]
+                };
+                if let Some(start_point) = &params.start_point {
+                    check_name(start_point, "start point")?;
+                    args.push(start_point);
+                }
+                git(&repo, &args).await?;
+                if params.switch {
+                    format!("Created and switched to branch {}", name)
+                } else {
+                    format!("Created branch {}", name)
+                }
+            }
+            BranchAction::Delete => {
+                git(&repo, &["branch", "--delete", name]).await?;
+                format!("Deleted branch {}", name)
+            }
+            BranchAction::List | BranchAction::Switch => {
+                git(&repo, &["switch", name]).await?;
+                format!("Switched to branch {}", name)
+            }
+        };
+        Ok(ToolResult::success(content))
+    }
+}
+
+async fn list_branches(repo: &Path) -> Result<ToolResult, ToolError> {
+    let branches = parse_branches(&git(repo, &["branch", "--list", BRANCH_FORMAT]).await?);
+    let mut content = String::new();
+    for branch in &branches {
+        content.push_str(&format!(
+            "{} {} {}",
+            if branch.current { '*' } else { ' ' },
+            branch.name,
+            branch.commit
+        ));
+        if let Some(upstream) = &branch.upstream {
+            content.push_str(&format!(" [{}", upstream));
+            if let Some(tracking) = &branch.tracking {
+                content.push_str(&format!(": {}", tracking));
+            }
+            content.push(']');
+        }
+        content.push('\n');
+    }
+    Ok(ToolResult::success_json(
+        content,
+        serde_json::json!({ "branches": branches }),
+    ))
+}
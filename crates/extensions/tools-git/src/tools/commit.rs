@@ -0,0 +1,123 @@
+//! Commit tool.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{PathAccess, Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::types::RiskLevel;
+
+use crate::git::{git, log_format, parse_log, repo_dir};
+
+/// Parameters for git_commit.
+#[derive(Debug, Deserialize)]
+struct CommitParams {
+    /// Repository directory; the working directory by default.
+    #[serde(default)]
+    repo: Option<String>,
+    /// Commit message.
+    message: String,
+    /// Files or directories to stage before committing.
+    #[serde(default)]
+    paths: Vec<String>,
+    /// Stage every change, including untracked files, before committing.
+    #[serde(default)]
+    all: bool,
+}
+
+/// Tool recording staged changes as a commit.
+pub struct GitCommitTool {
+    definition: ToolDefinition,
+}
+
+impl GitCommitTool {
+    /// Create a new commit tool.
+    pub fn new() -> Self {
+        let definition = ToolDefinition::new(
+            "git_commit",
+            "Git Commit",
+            "Commit the staged changes, after staging the given paths or, with all=true, every change. Returns the new commit",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "repo": {
+                    "type": "string",
+                    "description": "Repository directory (default: working directory)"
+                },
+                "message": {
+                    "type": "string",
+                    "description": "Commit message"
+                },
+                "paths": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Files or directories to stage, including deletions, before committing"
+                },
+                "all": {
+                    "type": "boolean",
+                    "description": "Stage every change, untracked files included (default: false)"
+                }
+            },
+            "required": ["message"]
+        }))
+        .with_risk_level(RiskLevel::Medium);
+
+        Self { definition }
+    }
+}
+
+impl Default for GitCommitTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Tool for GitCommitTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: CommitParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
+        if params.message.trim().is_empty() {
+            return Err(ToolError::InvalidParameters(
+                "Commit message is empty".to_string(),
+            ));
+        }
+        let repo = repo_dir(&ctx, params.repo.as_deref(), PathAccess::Write)?;
+
+        if params.all {
+            git(&repo, &["add", "--all"]).await?;
+        } else if !params.paths.is_empty() {
+            let paths = params
+                .paths
+                .iter()
+                .map(|path| {
+                    ctx.resolve_path(path, PathAccess::Write)
+                        .map(|p| p.display().to_string())
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut args = vec!["add", "--all", "--"];
+            args.extend(paths.iter().map(String::as_str));
+            git(&repo, &args).await?;
+        }
+
+        git(&repo, &["commit", "--quiet", "-m", &params.message]).await?;
+        let commit = parse_log(&git(&repo, &["log", "-1", &log_format()]).await?)
+            .into_iter()
+            .next()
+            .ok_or_else(|| ToolError::ExecutionFailed("Commit not found".to_string()))?;
+
+        Ok(ToolResult::success_json(
+            format!("Committed {}: {}", commit.short_hash, commit.subject),
+            serde_json::to_value(&commit).unwrap_or_default(),
+        ))
+    }
+}
@@ -0,0 +1,141 @@
+//! Diff tool.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{PathAccess, Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::types::RiskLevel;
+
+use crate::git::{check_name, git, parse_numstat, repo_dir, truncate};
+
+/// Default cap on the diff returned, in characters.
+const DEFAULT_MAX_CHARS: usize = 60_000;
+
+/// Parameters for git_diff.
+#[derive(Debug, Deserialize)]
+struct DiffParams {
+    /// Repository directory; the working directory by default.
+    #[serde(default)]
+    repo: Option<String>,
+    /// Diff the index against HEAD instead of the working tree against the
+    /// index.
+    #[serde(default)]
+    staged: bool,
+    /// Revision to compare the working tree (or index) with.
+    #[serde(default)]
+    base: Option<String>,
+    /// Only diff these files or directories.
+    #[serde(default)]
+    paths: Vec<String>,
+    /// Cap on the diff returned, in characters.
+    #[serde(default = "default_max_chars")]
+    max_chars: usize,
+}
+
+fn default_max_chars() -> usize {
+    DEFAULT_MAX_CHARS
+}
+
+/// Tool showing uncommitted changes as a unified diff.
+pub struct GitDiffTool {
+    definition: ToolDefinition,
+}
+
+impl GitDiffTool {
+    /// Create a new diff tool.
+    pub fn new() -> Self {
+        let definition = ToolDefinition::new(
+            "git_diff",
+            "Git Diff",
+            "Show uncommitted changes as a unified diff with per-file line counts: unstaged changes by default, staged ones with staged=true, or everything since a base revision",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "repo": {
+                    "type": "string",
+                    "description": "Repository directory (default: working directory)"
+                },
+                "staged": {
+                    "type": "boolean",
+                    "description": "Show changes staged for the next commit (default: false)"
+                },
+                "base": {
+                    "type": "string",
+                    "description": "Revision to diff against, e.g. main or HEAD~3"
+                },
+                "paths": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Only diff these files or directories"
+                },
+                "max_chars": {
+                    "type": "integer",
+                    "description": "Cap on the diff returned (default: 60000)"
+                }
+            }
+        }))
+        .with_risk_level(RiskLevel::Low)
+        .with_read_only();
+
+        Self { definition }
+    }
+}
+
+impl Default for GitDiffTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Tool for GitDiffTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: DiffParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
+        let repo = repo_dir(&ctx, params.repo.as_deref(), PathAccess::Read)?;
+        let paths = params
+            .paths
+            .iter()
+            .map(|path| ctx.resolve_path(path, PathAccess::Read))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut args = vec!["diff", "--no-color", "--no-ext-diff"];
+        if params.staged {
+            args.push("--cached");
+        }
+        if let Some(base) = &params.base {
+            check_name(base, "base revision")?;
+            args.push(base);
+        }
+        let paths: Vec<String> = paths.iter().map(|p| p.display().to_string()).collect();
+        let mut stat_args = args.clone();
+        stat_args.push("--numstat");
+        for args in [&mut args, &mut stat_args] {
+            args.push("--");
+            args.extend(paths.iter().map(String::as_str));
+        }
+
+        let files = parse_numstat(&git(&repo, &stat_args).await?);
+        let diff = git(&repo, &args).await?;
+        if files.is_empty() {
+            return Ok(ToolResult::success("No changes"));
+        }
+        let truncated = diff.len() > params.max_chars;
+        let content = truncate(diff, params.max_chars, "diff");
+
+        Ok(ToolResult::success_json(
+            content,
+            serde_json::json!({ "files": files, "truncated": truncated }),
+        ))
+    }
+}
@@ -0,0 +1,144 @@
+//! History tool.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{PathAccess, Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::types::RiskLevel;
+
+use crate::git::{check_name, git, log_format, parse_log, repo_dir};
+
+/// Default number of commits listed.
+const DEFAULT_MAX_COUNT: usize = 20;
+
+/// Parameters for git_log.
+#[derive(Debug, Deserialize)]
+struct LogParams {
+    /// Repository directory; the working directory by default.
+    #[serde(default)]
+    repo: Option<String>,
+    /// Number of commits to list.
+    #[serde(default = "default_max_count")]
+    max_count: usize,
+    /// Revision or range to list, e.g. `main..HEAD`.
+    #[serde(default)]
+    revision: Option<String>,
+    /// Only commits touching this file or directory.
+    #[serde(default)]
+    path: Option<String>,
+    /// Only commits whose author matches this pattern.
+    #[serde(default)]
+    author: Option<String>,
+}
+
+fn default_max_count() -> usize {
+    DEFAULT_MAX_COUNT
+}
+
+/// Tool listing commits.
+pub struct GitLogTool {
+    definition: ToolDefinition,
+}
+
+impl GitLogTool {
+    /// Create a new log tool.
+    pub fn new() -> Self {
+        let definition = ToolDefinition::new(
+            "git_log",
+            "Git Log",
+            "List recent commits with hash, author, date and subject, optionally for a revision range, a path or an author",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "repo": {
+                    "type": "string",
+                    "description": "Repository directory (default: working directory)"
+                },
+                "max_count": {
+                    "type": "integer",
+                    "description": "Number of commits to list (default: 20)"
+                },
+                "revision": {
+                    "type": "string",
+                    "description": "Revision or range, e.g. main..HEAD (default: HEAD)"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Only commits touching this file or directory"
+                },
+                "author": {
+                    "type": "string",
+                    "description": "Only commits whose author name or email matches"
+                }
+            }
+        }))
+        .with_risk_level(RiskLevel::Low)
+        .with_read_only();
+
+        Self { definition }
+    }
+}
+
+impl Default for GitLogTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Tool for GitLogTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: LogParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
+        let repo = repo_dir(&ctx, params.repo.as_deref(), PathAccess::Read)?;
+
+        let max_count = format!("--max-count={}", params.max_count);
+        let format = log_format();
+        let author = params.author.as_ref().map(|a| format!("--author={}", a));
+        let mut args = vec!["log", max_count.as_str(), format.as_str()];
+        if let Some(author) = &author {
+            args.push(author);
+        }
+        if let Some(revision) = &params.revision {
+            check_name(revision, "revision")?;
+            args.push(revision);
+        }
+        let path = match &params.path {
+            Some(path) => Some(
+                ctx.resolve_path(path, PathAccess::Read)?
+                    .display()
+                    .to_string(),
+            ),
+            None => None,
+        };
+        args.push("--");
+        args.extend(path.as_deref());
+
+        let commits = parse_log(&git(&repo, &args).await?);
+        if commits.is_empty() {
+            return Ok(ToolResult::success("No commits"));
+        }
+        let mut content = String::new();
+        for commit in &commits {
+            content.push_str(&format!(
+                "{} {} {} {}\n",
+                commit.short_hash, commit.date, commit.author, commit.subject
+            ));
+        }
+
+        Ok(ToolResult::success_json(
+            content,
+            serde_json::json!({ "commits": commits }),
+        ))
+    }
+}
@@ -0,0 +1,18 @@
+//! Git tool implementations.
+
+mod branch;
+mod commit;
+mod diff;
+mod log;
+mod push;
+mod status;
+
+pub use branch::GitBranchTool;
+pub use commit::GitCommitTool;
+pub use diff::GitDiffTool;
+pub use log::GitLogTool;
+pub use push::GitPushTool;
+pub use status::GitStatusTool;
+
+#[cfg(test)]
+mod tests;
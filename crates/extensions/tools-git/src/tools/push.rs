@@ -0,0 +1,133 @@
+//! Push tool.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{PathAccess, Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::types::RiskLevel;
+
+use crate::git::{check_branch, check_remote, git, repo_dir};
+
+/// Parameters for git_push.
+#[derive(Debug, Deserialize)]
+struct PushParams {
+    /// Repository directory; the working directory by default.
+    #[serde(default)]
+    repo: Option<String>,
+    #[serde(default = "default_remote")]
+    remote: String,
+    /// Branch to push; the current one by default.
+    #[serde(default)]
+    branch: Option<String>,
+    /// Make the remote branch the upstream of the local one.
+    #[serde(default)]
+    set_upstream: bool,
+    /// Overwrite the remote branch, unless it changed since last fetched.
+    #[serde(default)]
+    force_with_lease: bool,
+}
+
+fn default_remote() -> String {
+    "origin".to_string()
+}
+
+/// Tool pushing a branch to a remote.
+///
+/// It is high risk: a push publishes commits beyond the machine. A plain
+/// `--force` is never used.
+pub struct GitPushTool {
+    definition: ToolDefinition,
+}
+
+impl GitPushTool {
+    /// Create a new push tool.
+    pub fn new() -> Self {
+        let definition = ToolDefinition::new(
+            "git_push",
+            "Git Push",
+            "Push a branch to a remote repository. Rewriting the remote branch requires force_with_lease, which fails if someone else pushed to it",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "repo": {
+                    "type": "string",
+                    "description": "Repository directory (default: working directory)"
+                },
+                "remote": {
+                    "type": "string",
+                    "description": "Remote to push to (default: origin)"
+                },
+                "branch": {
+                    "type": "string",
+                    "description": "Local branch to push (default: current branch)"
+                },
+                "set_upstream": {
+                    "type": "boolean",
+                    "description": "Track the pushed branch as upstream (default: false)"
+                },
+                "force_with_lease": {
+                    "type": "boolean",
+                    "description": "Allow rewriting the remote branch if it has not changed since last fetched (default: false)"
+                }
+            }
+        }))
+        .with_risk_level(RiskLevel::High);
+
+        Self { definition }
+    }
+}
+
+impl Default for GitPushTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Tool for GitPushTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: PushParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
+        let repo = repo_dir(&ctx, params.repo.as_deref(), PathAccess::Write)?;
+        check_remote(&params.remote)?;
+        let branch = match params.branch {
+            Some(branch) => branch,
+            None => git(&repo, &["symbolic-ref", "--short", "HEAD"])
+                .await
+                .map_err(|_| {
+                    ToolError::ExecutionFailed(
+                        "HEAD is detached; name the branch to push".to_string(),
+                    )
+                })?
+                .trim()
+                .to_string(),
+        };
+        check_branch(&repo, &branch).await?;
+
+        let mut args = vec!["push", "--porcelain"];
+        if params.set_upstream {
+            args.push("--set-upstream");
+        }
+        if params.force_with_lease {
+            args.push("--force-with-lease");
+        }
+        args.push(&params.remote);
+        args.push(&branch);
+        let output = git(&repo, &args).await?;
+
+        Ok(ToolResult::success_json(
+            format!("Pushed {} to {}\n{}", branch, params.remote, output.trim()),
+            serde_json::json!({ "remote": params.remote, "branch": branch }),
+        ))
+    }
+}
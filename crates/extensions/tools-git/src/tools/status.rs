@@ -0,0 +1,129 @@
+//! Repository status tool.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{PathAccess, Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::types::RiskLevel;
+
+use crate::git::{git, parse_status, repo_dir, FileChange};
+
+/// Parameters for git_status.
+#[derive(Debug, Deserialize)]
+struct StatusParams {
+    /// Repository directory; the working directory by default.
+    #[serde(default)]
+    repo: Option<String>,
+}
+
+/// Tool reporting the branch and changed files of a repository.
+pub struct GitStatusTool {
+    definition: ToolDefinition,
+}
+
+impl GitStatusTool {
+    /// Create a new status tool.
+    pub fn new() -> Self {
+        let definition = ToolDefinition::new(
+            "git_status",
+            "Git Status",
+            "Show the current branch, how it compares to its upstream, and the staged, unstaged and untracked files",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "repo": {
+                    "type": "string",
+                    "description": "Repository directory (default: working directory)"
+                }
+            }
+        }))
+        .with_risk_level(RiskLevel::Low)
+        .with_read_only();
+
+        Self { definition }
+    }
+}
+
+impl Default for GitStatusTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Tool for GitStatusTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: StatusParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
+        let repo = repo_dir(&ctx, params.repo.as_deref(), PathAccess::Read)?;
+
+        let output = git(
+            &repo,
+            &[
+                "status",
+                "--porcelain=v2",
+                "--branch",
+                "-z",
+                "--untracked-files=all",
+            ],
+        )
+        .await?;
+        let report = parse_status(&output);
+
+        let mut content = match &report.branch {
+            Some(branch) => format!("On branch {}", branch),
+            None => "HEAD detached".to_string(),
+        };
+        if let Some(upstream) = &report.upstream {
+            content.push_str(&format!(
+                " (upstream {}: {} ahead, {} behind)",
+                upstream, report.ahead, report.behind
+            ));
+        }
+        if report.files.is_empty() {
+            content.push_str("\nNothing to commit, working tree clean");
+        }
+        for file in &report.files {
+            let path = match &file.original_path {
+                Some(original) => format!("{} -> {}", original, file.path),
+                None => file.path.clone(),
+            };
+            content.push_str(&format!(
+                "\n{}{} {}",
+                letter(file.staged),
+                letter(file.unstaged),
+                path
+            ));
+        }
+
+        Ok(ToolResult::success_json(
+            content,
+            serde_json::to_value(&report).unwrap_or_default(),
+        ))
+    }
+}
+
+/// One-letter code of a change, as `git status --short` shows it.
+fn letter(change: Option<FileChange>) -> char {
+    match change {
+        None => ' ',
+        Some(FileChange::Modified) => 'M',
+        Some(FileChange::Added) => 'A',
+        Some(FileChange::Deleted) => 'D',
+        Some(FileChange::Renamed) => 'R',
+        Some(FileChange::Copied) => 'C',
+        Some(FileChange::TypeChanged) => 'T',
+        Some(FileChange::Untracked) => '?',
+        Some(FileChange::Conflicted) => 'U',
+    }
+}
@@ -0,0 +1,188 @@
+//! Tests for the git tools, against real repositories.
+
+use std::path::Path;
+use std::process::Command;
+
+use serde_json::json;
+use tempfile::TempDir;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{Tool, ToolContext};
+use autohands_protocols::types::RiskLevel;
+
+use super::*;
+
+fn run(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .status()
+        .unwrap();
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+/// A repository with one commit of `README.md` on `main`.
+fn repo() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    run(dir.path(), &["init", "--quiet", "--initial-branch=main"]);
+    run(dir.path(), &["config", "user.name", "Test"]);
+    run(dir.path(), &["config", "user.email", "test@example.com"]);
+    std::fs::write(dir.path().join("README.md"), "hello\n").unwrap();
+    run(dir.path(), &["add", "README.md"]);
+    run(dir.path(), &["commit", "--quiet", "-m", "Initial commit"]);
+    dir
+}
+
+fn ctx(dir: &TempDir) -> ToolContext {
+    ToolContext::new("test", dir.path().to_path_buf())
+}
+
+#[tokio::test]
+async fn test_status_and_diff() {
+    let dir = repo();
+    std::fs::write(dir.path().join("README.md"), "hello\nworld\n").unwrap();
+    std::fs::write(dir.path().join("new.txt"), "new\n").unwrap();
+
+    let result = GitStatusTool::new()
+        .execute(json!({}), ctx(&dir))
+        .await
+        .unwrap();
+    let status = result.structured_output.unwrap();
+    assert_eq!(status["branch"], "main");
+    assert_eq!(status["files"][0]["path"], "README.md");
+    assert_eq!(status["files"][0]["unstaged"], "modified");
+    assert_eq!(status["files"][1]["unstaged"], "untracked");
+
+    let result = GitDiffTool::new()
+        .execute(json!({}), ctx(&dir))
+        .await
+        .unwrap();
+    assert!(result.content.contains("+world"));
+    let stats = result.structured_output.unwrap();
+    assert_eq!(stats["files"][0]["added"], 1);
+
+    let result = GitDiffTool::new()
+        .execute(json!({"staged": true}), ctx(&dir))
+        .await
+        .unwrap();
+    assert_eq!(result.content, "No changes");
+}
+
+#[tokio::test]
+async fn test_commit_and_log() {
+    let dir = repo();
+    std::fs::write(dir.path().join("a.txt"), "a\n").unwrap();
+    std::fs::write(dir.path().join("b.txt"), "b\n").unwrap();
+
+    let result = GitCommitTool::new()
+        .execute(json!({"message": "Add a", "paths": ["a.txt"]}), ctx(&dir))
+        .await
+        .unwrap();
+    assert!(result.content.contains("Add a"));
+
+    let result = GitStatusTool::new()
+        .execute(json!({}), ctx(&dir))
+        .await
+        .unwrap();
+    let status = result.structured_output.unwrap();
+    assert_eq!(status["files"].as_array().unwrap().len(), 1);
+    assert_eq!(status["files"][0]["path"], "b.txt");
+
+    let result = GitLogTool::new()
+        .execute(json!({"max_count": 5}), ctx(&dir))
+        .await
+        .unwrap();
+    let commits = &result.structured_output.unwrap()["commits"];
+    assert_eq!(commits[0]["subject"], "Add a");
+    assert_eq!(commits[1]["subject"], "Initial commit");
+
+    let result = GitLogTool::new()
+        .execute(json!({"path": "README.md"}), ctx(&dir))
+        .await
+        .unwrap();
+    assert_eq!(
+        result.structured_output.unwrap()["commits"]
+            .as_array()
+            .unwrap()
+            .len(),
+        1
+    );
+
+    let err = GitCommitTool::new()
+        .execute(json!({"message": "Nothing"}), ctx(&dir))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ToolError::ExecutionFailed(_)));
+}
+
+#[tokio::test]
+async fn test_branches() {
+    let dir = repo();
+    let tool = GitBranchTool::new();
+    tool.execute(
+        json!({"action": "create", "name": "fix", "switch": true}),
+        ctx(&dir),
+    )
+    .await
+    .unwrap();
+
+    let result = tool.execute(json!({}), ctx(&dir)).await.unwrap();
+    let branches = &result.structured_output.unwrap()["branches"];
+    assert_eq!(branches[0]["name"], "fix");
+    assert_eq!(branches[0]["current"], true);
+
+    tool.execute(json!({"action": "switch", "name": "main"}), ctx(&dir))
+        .await
+        .unwrap();
+    tool.execute(json!({"action": "delete", "name": "fix"}), ctx(&dir))
+        .await
+        .unwrap();
+
+    let err = tool
+        .execute(json!({"action": "create", "name": "-D"}), ctx(&dir))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ToolError::InvalidParameters(_)));
+}
+
+#[tokio::test]
+async fn test_push() {
+    let remote = TempDir::new().unwrap();
+    run(remote.path(), &["init", "--quiet", "--bare"]);
+    let dir = repo();
+    run(
+        dir.path(),
+        &["remote", "add", "origin", remote.path().to_str().unwrap()],
+    );
+
+    let tool = GitPushTool::new();
+    assert_eq!(tool.definition().risk_level, RiskLevel::High);
+    tool.execute(json!({"set_upstream": true}), ctx(&dir))
+        .await
+        .unwrap();
+
+    let result = GitStatusTool::new()
+        .execute(json!({}), ctx(&dir))
+        .await
+        .unwrap();
+    assert_eq!(result.structured_output.unwrap()["upstream"], "origin/main");
+}
+
+#[tokio::test]
+async fn test_push_rejects_refspecs() {
+    let dir = repo();
+    let tool = GitPushTool::new();
+    for branch in ["+main", ":main", "main:other"] {
+        let err = tool
+            .execute(json!({"branch": branch}), ctx(&dir))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ToolError::InvalidParameters(_)), "{}", branch);
+    }
+    let err = tool
+        .execute(json!({"remote": "+origin", "branch": "main"}), ctx(&dir))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ToolError::InvalidParameters(_)));
+}
@@ -0,0 +1,149 @@
+//! Minimal GitHub REST API client.
+
+use std::time::Duration;
+
+use reqwest::{Client, Response};
+use serde_json::Value;
+
+use autohands_protocols::error::ToolError;
+
+/// Public GitHub API.
+pub const DEFAULT_API_URL: &str = "https://api.github.com";
+
+/// GitHub REST API client authenticated with a token.
+///
+/// Configured from `GITHUB_TOKEN` and, for GitHub Enterprise,
+/// `GITHUB_API_URL`.
+pub struct GitHubClient {
+    http: Client,
+    api_url: String,
+    token: Option<String>,
+}
+
+impl GitHubClient {
+    /// Create a client for an API URL.
+    pub fn new(api_url: impl Into<String>, token: Option<String>) -> Self {
+        let http = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .user_agent("AutoHands/0.1")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self {
+            http,
+            api_url: api_url.into().trim_end_matches('/').to_string(),
+            token,
+        }
+    }
+
+    /// Create a client from the environment.
+    pub fn from_env() -> Self {
+        Self::new(
+            std::env::var("GITHUB_API_URL").unwrap_or_else(|_| DEFAULT_API_URL.to_string()),
+            std::env::var("GITHUB_TOKEN").ok().filter(|t| !t.is_empty()),
+        )
+    }
+
+    /// POST a JSON body to an API path and return the response JSON.
+    pub async fn post(&self, path: &str, body: &Value) -> Result<Value, ToolError> {
+        let Some(token) = &self.token else {
+            return Err(ToolError::PermissionDenied(
+                "GitHub token not configured. Set GITHUB_TOKEN.".to_string(),
+            ));
+        };
+
+        let resp = self
+            .http
+            .post(format!("{}{}", self.api_url, path))
+            .bearer_auth(token)
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("GitHub request failed: {}", e)))?;
+
+        let status = resp.status();
+        let data: Value = resp.json().await.unwrap_or_default();
+        if !status.is_success() {
+            return Err(api_error(status.as_u16(), &data));
+        }
+        Ok(data)
+    }
+
+    /// GET an API path and return the response JSON.
+    pub async fn get(&self, path: &str) -> Result<Value, ToolError> {
+        let resp = self.send_get(path, "application/vnd.github+json").await?;
+        Ok(resp.json().await.unwrap_or_default())
+    }
+
+    /// GET an API path as text in a media type such as
+    /// `application/vnd.github.diff`.
+    pub async fn get_text(&self, path: &str, accept: &str) -> Result<String, ToolError> {
+        let resp = self.send_get(path, accept).await?;
+        resp.text()
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("GitHub request failed: {}", e)))
+    }
+
+    /// Public repositories are readable without a token.
+    async fn send_get(&self, path: &str, accept: &str) -> Result<Response, ToolError> {
+        let mut request = self
+            .http
+            .get(format!("{}{}", self.api_url, path))
+            .header("Accept", accept)
+            .header("X-GitHub-Api-Version", "2022-11-28");
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        let resp = request
+            .send()
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("GitHub request failed: {}", e)))?;
+
+        let status = resp.status();
+        if !status.is_success() {
+            let data: Value = resp.json().await.unwrap_or_default();
+            return Err(api_error(status.as_u16(), &data));
+        }
+        Ok(resp)
+    }
+}
+
+fn api_error(status: u16, data: &Value) -> ToolError {
+    let message = data["message"].as_str().unwrap_or("unknown error");
+    ToolError::ExecutionFailed(format!("GitHub API error {}: {}", status, message))
+}
+
+/// Repository and number of a pull request URL such as
+/// `https://github.com/owner/name/pull/42`.
+pub(crate) fn parse_pr_url(url: &str) -> Option<(String, u64)> {
+    let path = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let mut parts = path.split('/').skip(1);
+    let owner = parts.next()?;
+    let name = parts.next()?;
+    if parts.next()? != "pull" {
+        return None;
+    }
+    let number = parts.next()?.split(['#', '?']).next()?.parse().ok()?;
+    Some((format!("{}/{}", owner, name), number))
+}
+
+/// Check a repository name has the form `owner/name`.
+pub(crate) fn check_repo(repo: &str) -> Result<(), ToolError> {
+    let valid = repo.split_once('/').is_some_and(|(owner, name)| {
+        let part_ok = |s: &str| {
+            !s.is_empty()
+                && s.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        };
+        part_ok(owner) && part_ok(name)
+    });
+    if valid {
+        Ok(())
+    } else {
+        Err(ToolError::InvalidParameters(format!(
+            "Invalid repository '{}': expected owner/name",
+            repo
+        )))
+    }
+}
@@ -0,0 +1,96 @@
+//! GitHub tools extension definition.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::debug;
+
+use autohands_protocols::error::ExtensionError;
+use autohands_protocols::extension::{Extension, ExtensionContext, ExtensionManifest, Provides};
+use autohands_protocols::types::Version;
+
+use crate::client::GitHubClient;
+use crate::tools::{GitHubCommentTool, GitHubCreatePrTool, GitHubPrDiffTool, GitHubReviewTool};
+
+/// GitHub tools extension.
+pub struct GitHubToolsExtension {
+    manifest: ExtensionManifest,
+}
+
+impl GitHubToolsExtension {
+    pub fn new() -> Self {
+        let mut manifest = ExtensionManifest::new(
+            "tools-github",
+            "GitHub Tools",
+            Version::new(0, 1, 0),
+        );
+        manifest.description =
+            "GitHub automation: comment on issues, open, diff and review pull requests"
+                .to_string();
+        manifest.provides = Provides {
+            tools: vec![
+                "github_comment".to_string(),
+                "github_create_pr".to_string(),
+                "github_pr_diff".to_string(),
+                "github_review".to_string(),
+            ],
+            ..Default::default()
+        };
+
+        Self { manifest }
+    }
+}
+
+impl Default for GitHubToolsExtension {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Extension for GitHubToolsExtension {
+    fn manifest(&self) -> &ExtensionManifest {
+        &self.manifest
+    }
+
+    async fn initialize(&mut self, ctx: ExtensionContext) -> Result<(), ExtensionError> {
+        // Tools are registered without a token too; they fail with a clear
+        // error when called.
+        let client = Arc::new(GitHubClient::from_env());
+        debug!("GitHub tools initialized");
+
+        ctx.tool_registry
+            .register_tool(Arc::new(GitHubCommentTool::new(client.clone())))?;
+        ctx.tool_registry
+            .register_tool(Arc::new(GitHubCreatePrTool::new(client.clone())))?;
+        ctx.tool_registry
+            .register_tool(Arc::new(GitHubPrDiffTool::new(client.clone())))?;
+        ctx.tool_registry
+            .register_tool(Arc::new(GitHubReviewTool::new(client)))?;
+
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extension_manifest() {
+        let ext = GitHubToolsExtension::new();
+        assert_eq!(ext.manifest().id, "tools-github");
+        assert_eq!(ext.manifest().name, "GitHub Tools");
+        assert_eq!(ext.manifest().provides.tools.len(), 4);
+        assert!(ext.manifest().provides.tools.contains(&"github_review".to_string()));
+    }
+}
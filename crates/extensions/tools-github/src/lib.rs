@@ -0,0 +1,16 @@
+//! GitHub tools for AutoHands.
+//!
+//! Provides github_comment, github_create_pr, github_pr_diff and
+//! github_review so an agent triggered by a GitHub event can act on the
+//! repository. Authenticates with `GITHUB_TOKEN`.
+
+mod client;
+mod extension;
+mod tools;
+
+pub use client::{GitHubClient, DEFAULT_API_URL};
+pub use extension::GitHubToolsExtension;
+pub use tools::{
+    GitHubCommentTool, GitHubCreatePrTool, GitHubPrDiffTool, GitHubReviewTool, ReviewFinding,
+    Severity,
+};
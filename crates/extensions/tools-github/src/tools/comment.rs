@@ -0,0 +1,93 @@
+//! Issue and pull request comment tool.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::types::RiskLevel;
+
+use crate::client::{check_repo, GitHubClient};
+
+/// Parameters for github_comment.
+#[derive(Debug, Deserialize)]
+struct CommentParams {
+    /// Repository as `owner/name`.
+    repo: String,
+    /// Issue or pull request number.
+    number: u64,
+    /// Comment text (markdown).
+    body: String,
+}
+
+/// Tool for commenting on an issue or pull request.
+pub struct GitHubCommentTool {
+    definition: ToolDefinition,
+    client: Arc<GitHubClient>,
+}
+
+impl GitHubCommentTool {
+    /// Create a new comment tool.
+    pub fn new(client: Arc<GitHubClient>) -> Self {
+        let definition = ToolDefinition::new(
+            "github_comment",
+            "GitHub Comment",
+            "Comment on a GitHub issue or pull request",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "repo": {
+                    "type": "string",
+                    "description": "Repository as owner/name"
+                },
+                "number": {
+                    "type": "integer",
+                    "description": "Issue or pull request number"
+                },
+                "body": {
+                    "type": "string",
+                    "description": "Comment text (markdown)"
+                }
+            },
+            "required": ["repo", "number", "body"]
+        }))
+        .with_risk_level(RiskLevel::Medium);
+
+        Self { definition, client }
+    }
+}
+
+#[async_trait]
+impl Tool for GitHubCommentTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        _ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: CommentParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
+        check_repo(&params.repo)?;
+        if params.body.trim().is_empty() {
+            return Err(ToolError::InvalidParameters("Comment body is empty".to_string()));
+        }
+
+        let path = format!("/repos/{}/issues/{}/comments", params.repo, params.number);
+        let data = self
+            .client
+            .post(&path, &serde_json::json!({ "body": params.body }))
+            .await?;
+
+        let url = data["html_url"].as_str().unwrap_or_default().to_string();
+        Ok(ToolResult::success_json(
+            format!("Commented on {}#{}: {}", params.repo, params.number, url),
+            serde_json::json!({ "id": data["id"], "url": url }),
+        ))
+    }
+}
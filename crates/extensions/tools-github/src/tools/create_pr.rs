@@ -0,0 +1,124 @@
+//! Pull request creation tool.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::types::RiskLevel;
+
+use crate::client::{check_repo, GitHubClient};
+
+/// Parameters for github_create_pr.
+#[derive(Debug, Deserialize)]
+struct CreatePrParams {
+    /// Repository as `owner/name`.
+    repo: String,
+    /// Pull request title.
+    title: String,
+    /// Branch with the changes.
+    head: String,
+    /// Branch to merge into.
+    base: String,
+    /// Description (markdown).
+    #[serde(default)]
+    body: Option<String>,
+    /// Open as a draft.
+    #[serde(default)]
+    draft: bool,
+}
+
+/// Tool for opening a pull request.
+pub struct GitHubCreatePrTool {
+    definition: ToolDefinition,
+    client: Arc<GitHubClient>,
+}
+
+impl GitHubCreatePrTool {
+    /// Create a new pull request tool.
+    pub fn new(client: Arc<GitHubClient>) -> Self {
+        let definition = ToolDefinition::new(
+            "github_create_pr",
+            "GitHub Create Pull Request",
+            "Open a pull request from a pushed branch",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "repo": {
+                    "type": "string",
+                    "description": "Repository as owner/name"
+                },
+                "title": {
+                    "type": "string",
+                    "description": "Pull request title"
+                },
+                "head": {
+                    "type": "string",
+                    "description": "Branch with the changes (owner:branch for forks)"
+                },
+                "base": {
+                    "type": "string",
+                    "description": "Branch to merge into, e.g. main"
+                },
+                "body": {
+                    "type": "string",
+                    "description": "Pull request description (markdown)"
+                },
+                "draft": {
+                    "type": "boolean",
+                    "description": "Open as a draft (default: false)"
+                }
+            },
+            "required": ["repo", "title", "head", "base"]
+        }))
+        .with_risk_level(RiskLevel::Medium);
+
+        Self { definition, client }
+    }
+}
+
+#[async_trait]
+impl Tool for GitHubCreatePrTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        _ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: CreatePrParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
+        check_repo(&params.repo)?;
+        if params.head == params.base {
+            return Err(ToolError::InvalidParameters(
+                "head and base must be different branches".to_string(),
+            ));
+        }
+
+        let data = self
+            .client
+            .post(
+                &format!("/repos/{}/pulls", params.repo),
+                &serde_json::json!({
+                    "title": params.title,
+                    "head": params.head,
+                    "base": params.base,
+                    "body": params.body,
+                    "draft": params.draft,
+                }),
+            )
+            .await?;
+
+        let number = data["number"].as_u64().unwrap_or_default();
+        let url = data["html_url"].as_str().unwrap_or_default().to_string();
+        Ok(ToolResult::success_json(
+            format!("Opened pull request {}#{}: {}", params.repo, number, url),
+            serde_json::json!({ "number": number, "url": url }),
+        ))
+    }
+}
@@ -0,0 +1,14 @@
+//! GitHub tool implementations.
+
+mod comment;
+mod create_pr;
+mod pr_diff;
+mod review;
+
+pub use comment::GitHubCommentTool;
+pub use create_pr::GitHubCreatePrTool;
+pub use pr_diff::GitHubPrDiffTool;
+pub use review::{GitHubReviewTool, ReviewFinding, Severity};
+
+#[cfg(test)]
+mod tests;
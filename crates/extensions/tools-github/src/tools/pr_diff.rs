@@ -0,0 +1,157 @@
+//! Pull request diff tool.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::types::RiskLevel;
+
+use crate::client::{check_repo, parse_pr_url, GitHubClient};
+
+/// Default cap on the diff returned, in characters.
+const DEFAULT_MAX_CHARS: usize = 60_000;
+
+/// Parameters for github_pr_diff.
+#[derive(Debug, Deserialize)]
+struct PrDiffParams {
+    /// Pull request URL, instead of `repo` and `number`.
+    #[serde(default)]
+    url: Option<String>,
+    /// Repository as `owner/name`.
+    #[serde(default)]
+    repo: Option<String>,
+    /// Pull request number.
+    #[serde(default)]
+    number: Option<u64>,
+    /// Cap on the diff returned, in characters.
+    #[serde(default = "default_max_chars")]
+    max_chars: usize,
+}
+
+fn default_max_chars() -> usize {
+    DEFAULT_MAX_CHARS
+}
+
+/// Tool fetching a pull request with its unified diff.
+pub struct GitHubPrDiffTool {
+    definition: ToolDefinition,
+    client: Arc<GitHubClient>,
+}
+
+impl GitHubPrDiffTool {
+    /// Create a new diff tool.
+    pub fn new(client: Arc<GitHubClient>) -> Self {
+        let definition = ToolDefinition::new(
+            "github_pr_diff",
+            "GitHub Pull Request Diff",
+            "Fetch a pull request's title, description, branches and unified diff",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "url": {
+                    "type": "string",
+                    "description": "Pull request URL, e.g. https://github.com/owner/name/pull/42"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository as owner/name (instead of url)"
+                },
+                "number": {
+                    "type": "integer",
+                    "description": "Pull request number (instead of url)"
+                },
+                "max_chars": {
+                    "type": "integer",
+                    "description": "Cap on the diff returned (default: 60000)"
+                }
+            }
+        }))
+        .with_risk_level(RiskLevel::Low)
+        .with_read_only();
+
+        Self { definition, client }
+    }
+}
+
+#[async_trait]
+impl Tool for GitHubPrDiffTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        _ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: PrDiffParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
+        let (repo, number) = match (&params.url, params.repo, params.number) {
+            (Some(url), _, _) => parse_pr_url(url).ok_or_else(|| {
+                ToolError::InvalidParameters(format!("Not a pull request URL: {}", url))
+            })?,
+            (None, Some(repo), Some(number)) => (repo, number),
+            _ => {
+                return Err(ToolError::InvalidParameters(
+                    "Either url or repo and number are required".to_string(),
+                ))
+            }
+        };
+        check_repo(&repo)?;
+
+        let path = format!("/repos/{}/pulls/{}", repo, number);
+        let pr = self.client.get(&path).await?;
+        let diff = self
+            .client
+            .get_text(&path, "application/vnd.github.diff")
+            .await?;
+
+        let truncated = diff.len() > params.max_chars;
+        let diff = if truncated {
+            let mut end = params.max_chars;
+            while !diff.is_char_boundary(end) {
+                end -= 1;
+            }
+            format!("{}\n… diff truncated at {} characters", &diff[..end], params.max_chars)
+        } else {
+            diff
+        };
+
+        let title = pr["title"].as_str().unwrap_or_default();
+        let head = pr["head"]["ref"].as_str().unwrap_or_default();
+        let base = pr["base"]["ref"].as_str().unwrap_or_default();
+        let mut content = format!(
+            "{}#{}: {}\n{} → {} ({} files, +{} -{})\n",
+            repo,
+            number,
+            title,
+            head,
+            base,
+            pr["changed_files"].as_u64().unwrap_or_default(),
+            pr["additions"].as_u64().unwrap_or_default(),
+            pr["deletions"].as_u64().unwrap_or_default(),
+        );
+        if let Some(body) = pr["body"].as_str().filter(|b| !b.trim().is_empty()) {
+            content.push_str(&format!("\n{}\n", body.trim()));
+        }
+        content.push_str(&format!("\n{}", diff));
+
+        Ok(ToolResult::success_json(
+            content,
+            serde_json::json!({
+                "repo": repo,
+                "number": number,
+                "title": title,
+                "head": head,
+                "base": base,
+                "head_sha": pr["head"]["sha"],
+                "url": pr["html_url"],
+                "truncated": truncated,
+            }),
+        ))
+    }
+}
@@ -0,0 +1,249 @@
+//! Pull request review tool.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::types::RiskLevel;
+
+use crate::client::{check_repo, GitHubClient};
+
+/// Review verdict.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ReviewEvent {
+    Approve,
+    RequestChanges,
+    #[default]
+    Comment,
+}
+
+impl ReviewEvent {
+    fn api_name(self) -> &'static str {
+        match self {
+            Self::Approve => "APPROVE",
+            Self::RequestChanges => "REQUEST_CHANGES",
+            Self::Comment => "COMMENT",
+        }
+    }
+}
+
+/// A comment on a line of the diff.
+#[derive(Debug, Serialize, Deserialize)]
+struct LineComment {
+    /// File path in the repository.
+    path: String,
+    /// Line in the new version of the file.
+    line: u64,
+    /// Comment text.
+    body: String,
+}
+
+/// Severity of a review finding, most severe first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Critical,
+    Major,
+    Minor,
+    Nit,
+}
+
+impl Severity {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Critical => "critical",
+            Self::Major => "major",
+            Self::Minor => "minor",
+            Self::Nit => "nit",
+        }
+    }
+}
+
+/// A structured review finding, posted as a line comment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewFinding {
+    /// File path in the repository.
+    pub file: String,
+    /// Line in the new version of the file.
+    pub line: u64,
+    pub severity: Severity,
+    /// What is wrong and how to fix it.
+    pub suggestion: String,
+}
+
+impl ReviewFinding {
+    fn comment(&self) -> LineComment {
+        LineComment {
+            path: self.file.clone(),
+            line: self.line,
+            body: format!("**{}**: {}", self.severity.name(), self.suggestion),
+        }
+    }
+}
+
+/// Summary of findings, e.g. "3 findings: 1 critical, 2 minor".
+fn findings_summary(findings: &[ReviewFinding]) -> String {
+    let mut counts: Vec<String> = Vec::new();
+    for severity in [Severity::Critical, Severity::Major, Severity::Minor, Severity::Nit] {
+        let count = findings.iter().filter(|f| f.severity == severity).count();
+        if count > 0 {
+            counts.push(format!("{} {}", count, severity.name()));
+        }
+    }
+    let noun = if findings.len() == 1 { "finding" } else { "findings" };
+    format!("{} {}: {}", findings.len(), noun, counts.join(", "))
+}
+
+/// Parameters for github_review.
+#[derive(Debug, Deserialize)]
+struct ReviewParams {
+    /// Repository as `owner/name`.
+    repo: String,
+    /// Pull request number.
+    number: u64,
+    /// Verdict (default: comment).
+    #[serde(default)]
+    event: ReviewEvent,
+    /// Review summary (markdown).
+    #[serde(default)]
+    body: String,
+    /// Line comments.
+    #[serde(default)]
+    comments: Vec<LineComment>,
+    /// Structured findings, posted as line comments.
+    #[serde(default)]
+    findings: Vec<ReviewFinding>,
+}
+
+/// Tool for reviewing a pull request.
+pub struct GitHubReviewTool {
+    definition: ToolDefinition,
+    client: Arc<GitHubClient>,
+}
+
+impl GitHubReviewTool {
+    /// Create a new review tool.
+    pub fn new(client: Arc<GitHubClient>) -> Self {
+        let definition = ToolDefinition::new(
+            "github_review",
+            "GitHub Review",
+            "Review a pull request: approve, request changes or comment, optionally on specific lines",
+        )
+        .with_parameters_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "repo": {
+                    "type": "string",
+                    "description": "Repository as owner/name"
+                },
+                "number": {
+                    "type": "integer",
+                    "description": "Pull request number"
+                },
+                "event": {
+                    "type": "string",
+                    "enum": ["approve", "request_changes", "comment"],
+                    "description": "Review verdict (default: comment)"
+                },
+                "body": {
+                    "type": "string",
+                    "description": "Review summary (markdown)"
+                },
+                "comments": {
+                    "type": "array",
+                    "description": "Comments on specific lines of the diff",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "path": { "type": "string" },
+                            "line": { "type": "integer" },
+                            "body": { "type": "string" }
+                        },
+                        "required": ["path", "line", "body"]
+                    }
+                },
+                "findings": {
+                    "type": "array",
+                    "description": "Review findings, posted as line comments; \
+                        they make up the summary when body is empty",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "file": { "type": "string" },
+                            "line": { "type": "integer" },
+                            "severity": {
+                                "type": "string",
+                                "enum": ["critical", "major", "minor", "nit"]
+                            },
+                            "suggestion": { "type": "string" }
+                        },
+                        "required": ["file", "line", "severity", "suggestion"]
+                    }
+                }
+            },
+            "required": ["repo", "number"]
+        }))
+        .with_risk_level(RiskLevel::Medium);
+
+        Self { definition, client }
+    }
+}
+
+#[async_trait]
+impl Tool for GitHubReviewTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        _ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let mut params: ReviewParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
+        check_repo(&params.repo)?;
+        if !params.findings.is_empty() {
+            if params.body.trim().is_empty() {
+                params.body = findings_summary(&params.findings);
+            }
+            params.comments.extend(params.findings.iter().map(ReviewFinding::comment));
+        }
+        // GitHub rejects these without a summary
+        if !matches!(params.event, ReviewEvent::Approve) && params.body.trim().is_empty() {
+            return Err(ToolError::InvalidParameters(format!(
+                "A body is required for a '{}' review",
+                params.event.api_name().to_lowercase()
+            )));
+        }
+
+        let path = format!("/repos/{}/pulls/{}/reviews", params.repo, params.number);
+        let data = self
+            .client
+            .post(
+                &path,
+                &serde_json::json!({
+                    "event": params.event.api_name(),
+                    "body": params.body,
+                    "comments": params.comments,
+                }),
+            )
+            .await?;
+
+        let url = data["html_url"].as_str().unwrap_or_default().to_string();
+        Ok(ToolResult::success_json(
+            format!(
+                "Submitted {} review on {}#{}: {}",
+                params.event.api_name(),
+                params.repo,
+                params.number,
+                url
+            ),
+            serde_json::json!({ "id": data["id"], "state": data["state"], "url": url }),
+        ))
+    }
+}
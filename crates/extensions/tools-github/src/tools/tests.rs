@@ -0,0 +1,246 @@
+//! Tests for the GitHub tools.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use serde_json::json;
+use wiremock::matchers::{body_partial_json, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use autohands_protocols::error::ToolError;
+use autohands_protocols::tool::{Tool, ToolContext};
+
+use super::*;
+use crate::client::{parse_pr_url, GitHubClient};
+
+fn ctx() -> ToolContext {
+    ToolContext::new("test", PathBuf::from("/tmp"))
+}
+
+fn client(server: &MockServer) -> Arc<GitHubClient> {
+    Arc::new(GitHubClient::new(server.uri(), Some("token".to_string())))
+}
+
+#[tokio::test]
+async fn test_comment() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/repos/acme/app/issues/42/comments"))
+        .and(header("authorization", "Bearer token"))
+        .and(body_partial_json(json!({"body": "Thanks!"})))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "id": 1,
+            "html_url": "https://github.com/acme/app/issues/42#issuecomment-1",
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let tool = GitHubCommentTool::new(client(&server));
+    let result = tool
+        .execute(json!({"repo": "acme/app", "number": 42, "body": "Thanks!"}), ctx())
+        .await
+        .unwrap();
+    assert!(result.success);
+    assert!(result.content.contains("acme/app#42"));
+}
+
+#[tokio::test]
+async fn test_create_pr() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/repos/acme/app/pulls"))
+        .and(body_partial_json(json!({"head": "fix", "base": "main", "draft": true})))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "number": 9,
+            "html_url": "https://github.com/acme/app/pull/9",
+        })))
+        .mount(&server)
+        .await;
+
+    let tool = GitHubCreatePrTool::new(client(&server));
+    let result = tool
+        .execute(
+            json!({"repo": "acme/app", "title": "Fix", "head": "fix", "base": "main", "draft": true}),
+            ctx(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.structured_output.unwrap()["number"], 9);
+
+    let err = tool
+        .execute(json!({"repo": "acme/app", "title": "x", "head": "main", "base": "main"}), ctx())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ToolError::InvalidParameters(_)));
+}
+
+#[tokio::test]
+async fn test_review() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/repos/acme/app/pulls/9/reviews"))
+        .and(body_partial_json(json!({
+            "event": "REQUEST_CHANGES",
+            "comments": [{"path": "src/lib.rs", "line": 3, "body": "Typo"}],
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 5,
+            "state": "CHANGES_REQUESTED",
+        })))
+        .mount(&server)
+        .await;
+
+    let tool = GitHubReviewTool::new(client(&server));
+    let result = tool
+        .execute(
+            json!({
+                "repo": "acme/app",
+                "number": 9,
+                "event": "request_changes",
+                "body": "See comments",
+                "comments": [{"path": "src/lib.rs", "line": 3, "body": "Typo"}],
+            }),
+            ctx(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.structured_output.unwrap()["state"], "CHANGES_REQUESTED");
+
+    // Only approvals may omit the body
+    let err = tool
+        .execute(json!({"repo": "acme/app", "number": 9}), ctx())
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("'comment' review"));
+}
+
+#[tokio::test]
+async fn test_review_findings() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/repos/acme/app/pulls/9/reviews"))
+        .and(body_partial_json(json!({
+            "event": "COMMENT",
+            "body": "2 findings: 1 critical, 1 nit",
+            "comments": [
+                {"path": "src/db.rs", "line": 12, "body": "**critical**: Bind the parameter"},
+                {"path": "src/lib.rs", "line": 3, "body": "**nit**: Typo"},
+            ],
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": 6})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let tool = GitHubReviewTool::new(client(&server));
+    tool.execute(
+        json!({
+            "repo": "acme/app",
+            "number": 9,
+            "findings": [
+                {"file": "src/db.rs", "line": 12, "severity": "critical",
+                 "suggestion": "Bind the parameter"},
+                {"file": "src/lib.rs", "line": 3, "severity": "nit", "suggestion": "Typo"},
+            ],
+        }),
+        ctx(),
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_pr_diff() {
+    let server = MockServer::start().await;
+    let diff = "diff --git a/src/lib.rs b/src/lib.rs\n+fn added() {}\n";
+    Mock::given(method("GET"))
+        .and(path("/repos/acme/app/pulls/9"))
+        .and(header("accept", "application/vnd.github.diff"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(diff))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/repos/acme/app/pulls/9"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "title": "Add a function",
+            "body": "Adds it.",
+            "head": {"ref": "feature", "sha": "abc123"},
+            "base": {"ref": "main"},
+            "changed_files": 1,
+            "additions": 1,
+            "deletions": 0,
+            "html_url": "https://github.com/acme/app/pull/9",
+        })))
+        .mount(&server)
+        .await;
+
+    let tool = GitHubPrDiffTool::new(client(&server));
+    let result = tool
+        .execute(json!({"url": "https://github.com/acme/app/pull/9/files"}), ctx())
+        .await
+        .unwrap();
+    assert!(result.content.starts_with("acme/app#9: Add a function\nfeature → main"));
+    assert!(result.content.contains("Adds it."));
+    assert!(result.content.ends_with(diff));
+    let output = result.structured_output.unwrap();
+    assert_eq!(output["head_sha"], "abc123");
+    assert_eq!(output["truncated"], false);
+
+    let result = tool
+        .execute(json!({"repo": "acme/app", "number": 9, "max_chars": 10}), ctx())
+        .await
+        .unwrap();
+    assert!(result.content.ends_with("diff --git\n… diff truncated at 10 characters"));
+
+    let err = tool.execute(json!({"repo": "acme/app"}), ctx()).await.unwrap_err();
+    assert!(matches!(err, ToolError::InvalidParameters(_)));
+}
+
+#[test]
+fn test_parse_pr_url() {
+    assert_eq!(
+        parse_pr_url("https://github.com/acme/app/pull/42"),
+        Some(("acme/app".to_string(), 42))
+    );
+    assert_eq!(
+        parse_pr_url("https://github.example.com/acme/app/pull/7#discussion_r1"),
+        Some(("acme/app".to_string(), 7))
+    );
+    assert_eq!(parse_pr_url("https://github.com/acme/app/issues/42"), None);
+    assert_eq!(parse_pr_url("acme/app"), None);
+}
+
+#[tokio::test]
+async fn test_api_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({"message": "Not Found"})))
+        .mount(&server)
+        .await;
+
+    let tool = GitHubCommentTool::new(client(&server));
+    let err = tool
+        .execute(json!({"repo": "acme/app", "number": 1, "body": "hi"}), ctx())
+        .await
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Tool execution failed: GitHub API error 404: Not Found");
+}
+
+#[tokio::test]
+async fn test_validation() {
+    let tool = GitHubCommentTool::new(Arc::new(GitHubClient::new("http://unused", None)));
+    for repo in ["acme", "acme/", "/app", "acme/app/x", "acme/a b"] {
+        let err = tool
+            .execute(json!({"repo": repo, "number": 1, "body": "hi"}), ctx())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ToolError::InvalidParameters(_)), "{}", repo);
+    }
+
+    let err = tool
+        .execute(json!({"repo": "acme/app", "number": 1, "body": "hi"}), ctx())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ToolError::PermissionDenied(_)));
+}
@@ -36,7 +36,8 @@ impl ImageInfoTool {
             "image_info",
             "Image Info",
             "Get metadata about an image (dimensions, format, color type, size).",
-        );
+        )
+        .with_read_only();
         definition.parameters_schema = Some(serde_json::json!({
             "type": "object",
             "properties": {
@@ -71,7 +71,8 @@ impl MemorySearchTool {
                 "Search long-term memory for relevant information. Use this to recall past conversations, user preferences, decisions, and facts.",
             )
             .with_parameters_schema(schema)
-            .with_risk_level(RiskLevel::Low),
+            .with_risk_level(RiskLevel::Low)
+            .with_read_only(),
             backend,
         }
     }
@@ -181,7 +182,8 @@ impl MemoryGetTool {
                 "Retrieve a specific memory entry by its ID.",
             )
             .with_parameters_schema(schema)
-            .with_risk_level(RiskLevel::Low),
+            .with_risk_level(RiskLevel::Low)
+            .with_read_only(),
             backend,
         }
     }
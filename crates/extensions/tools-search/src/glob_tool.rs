@@ -2,10 +2,9 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::path::PathBuf;
 
 use autohands_protocols::error::ToolError;
-use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::tool::{PathAccess, Tool, ToolContext, ToolDefinition, ToolResult};
 use autohands_protocols::types::RiskLevel;
 
 #[derive(Debug, Deserialize)]
@@ -40,7 +39,8 @@ impl GlobTool {
         Self {
             definition: ToolDefinition::new("glob", "Glob Search", "Find files matching a pattern")
                 .with_parameters_schema(schema)
-                .with_risk_level(RiskLevel::Low),
+                .with_risk_level(RiskLevel::Low)
+                .with_read_only(),
         }
     }
 }
@@ -65,10 +65,7 @@ impl Tool for GlobTool {
         let params: GlobParams = serde_json::from_value(params)
             .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
 
-        let base_path = params
-            .path
-            .map(PathBuf::from)
-            .unwrap_or_else(|| ctx.work_dir.clone());
+        let base_path = ctx.resolve_path(params.path.as_deref().unwrap_or("."), PathAccess::Read)?;
 
         let full_pattern = base_path.join(&params.pattern);
         let pattern_str = full_pattern.to_string_lossy();
@@ -79,6 +76,10 @@ impl Tool for GlobTool {
         {
             match entry {
                 Ok(path) => {
+                    // The pattern may lead out of the base path (`../*`)
+                    if ctx.resolve_path(&path.to_string_lossy(), PathAccess::Read).is_err() {
+                        continue;
+                    }
                     let relative = path
                         .strip_prefix(&base_path)
                         .unwrap_or(&path)
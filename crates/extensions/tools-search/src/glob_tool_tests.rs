@@ -130,3 +130,22 @@ async fn test_glob_invalid_params() {
     let result = tool.execute(params, ctx).await;
     assert!(result.is_err());
 }
+
+#[tokio::test]
+async fn test_glob_confined_to_work_dir() {
+    let temp = TempDir::new().unwrap();
+    let work = temp.path().join("work");
+    tokio::fs::create_dir(&work).await.unwrap();
+    tokio::fs::write(temp.path().join("secret.rs"), "content").await.unwrap();
+
+    let tool = GlobTool::new();
+    let ctx = ToolContext::new("test", work.clone());
+    let result = tool
+        .execute(serde_json::json!({"pattern": "../*.rs"}), ctx.clone())
+        .await
+        .unwrap();
+    assert!(!result.content.contains("secret.rs"));
+
+    let params = serde_json::json!({"pattern": "*.rs", "path": temp.path().to_string_lossy()});
+    assert!(tool.execute(params, ctx).await.is_err());
+}
@@ -3,11 +3,10 @@
 use async_trait::async_trait;
 use regex::Regex;
 use serde::Deserialize;
-use std::path::PathBuf;
 use walkdir::WalkDir;
 
 use autohands_protocols::error::ToolError;
-use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
+use autohands_protocols::tool::{PathAccess, Tool, ToolContext, ToolDefinition, ToolResult};
 use autohands_protocols::types::RiskLevel;
 
 #[derive(Debug, Deserialize)]
@@ -64,7 +63,8 @@ impl GrepTool {
         Self {
             definition: ToolDefinition::new("grep", "Content Search", "Search file contents")
                 .with_parameters_schema(schema)
-                .with_risk_level(RiskLevel::Low),
+                .with_risk_level(RiskLevel::Low)
+                .with_read_only(),
         }
     }
 }
@@ -98,10 +98,7 @@ impl Tool for GrepTool {
         let regex = Regex::new(&pattern)
             .map_err(|e| ToolError::InvalidParameters(format!("Invalid regex: {}", e)))?;
 
-        let search_path = params
-            .path
-            .map(PathBuf::from)
-            .unwrap_or_else(|| ctx.work_dir.clone());
+        let search_path = ctx.resolve_path(params.path.as_deref().unwrap_or("."), PathAccess::Read)?;
 
         let mut results = Vec::new();
         let glob_pattern = params.glob.as_deref();
@@ -110,6 +107,9 @@ impl Tool for GrepTool {
             if !entry.file_type().is_file() {
                 continue;
             }
+            if ctx.sandbox.as_ref().is_some_and(|s| s.check_denied(entry.path()).is_err()) {
+                continue;
+            }
 
             if let Some(glob) = glob_pattern {
                 if !matches_glob(entry.path(), glob) {
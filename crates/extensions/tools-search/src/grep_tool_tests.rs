@@ -257,3 +257,18 @@ async fn test_grep_in_subdirectories() {
     let result = tool.execute(params, ctx).await.unwrap();
     assert!(result.content.contains("file.txt"));
 }
+
+#[tokio::test]
+async fn test_grep_confined_to_work_dir() {
+    let temp = TempDir::new().unwrap();
+    let outside = TempDir::new().unwrap();
+    tokio::fs::write(outside.path().join("secret.txt"), "foo").await.unwrap();
+
+    let tool = GrepTool::new();
+    let ctx = ToolContext::new("test", temp.path().to_path_buf());
+    let params = serde_json::json!({
+        "pattern": "foo",
+        "path": outside.path().to_string_lossy()
+    });
+    assert!(tool.execute(params, ctx).await.is_err());
+}
@@ -1,18 +1,118 @@
 //! Background process management.
+//!
+//! Processes may be started with [`ResourceLimits`]. Memory and CPU time
+//! are capped with rlimits (`ulimit -v`, `ulimit -t`) and the CPU share
+//! with `nice`, on Unix. A watchdog thread per limited process kills it
+//! once it runs longer than its maximum runtime or its resident memory
+//! exceeds its cap, and records its peak resident memory (on Linux).
+//!
+//! A process started by an agent is bound to the run's [`AbortSignal`]:
+//! it is terminated when the session is terminated, and every process is
+//! killed when the extension shuts down.
 
 use std::collections::HashMap;
 use std::process::{Child, Command, ExitStatus, Stdio};
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
 
 use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
 use uuid::Uuid;
 
+use autohands_protocols::tool::AbortSignal;
+
+/// How often the watchdog of a limited process checks it.
+const WATCH_INTERVAL: Duration = Duration::from_millis(200);
+
+/// How often a process bound to a session checks whether it has ended.
+const BIND_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Signal sent by the kernel when the CPU time rlimit is reached.
+#[cfg(unix)]
+const SIGXCPU: i32 = 24;
+
 /// Background process status.
 #[derive(Debug, Clone)]
 pub enum ProcessStatus {
     Running,
     Completed(i32),
     Failed(String),
+    /// Killed for breaching a resource limit, with its session or on
+    /// shutdown.
+    Terminated(String),
+}
+
+/// Resource limits of a background process.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceLimits {
+    /// Memory cap in megabytes: the address space rlimit, and the process
+    /// is killed once its resident memory exceeds it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_mb: Option<u64>,
+    /// CPU time in seconds, after which the kernel stops the process.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_secs: Option<u64>,
+    /// Wall-clock time in seconds after which the process is killed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_runtime_secs: Option<u64>,
+    /// Niceness from 0 to 19; higher values get a smaller CPU share.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nice: Option<u8>,
+}
+
+impl ResourceLimits {
+    /// Whether no limit is set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether the process needs a watchdog.
+    fn is_watched(&self) -> bool {
+        self.memory_mb.is_some() || self.max_runtime_secs.is_some()
+    }
+
+    /// Shell script applying the rlimits and niceness, then running the
+    /// command passed as `$1`.
+    pub(crate) fn wrapper_script(&self) -> String {
+        let mut script = String::new();
+        if let Some(mb) = self.memory_mb {
+            script.push_str(&format!("ulimit -v {} && ", mb * 1024));
+        }
+        if let Some(secs) = self.cpu_secs {
+            // SIGXCPU at the soft limit, SIGKILL a second later
+            let secs = secs.max(1);
+            script.push_str(&format!("ulimit -S -t {} && ulimit -H -t {} && ", secs, secs + 1));
+        }
+        script.push_str("exec ");
+        if let Some(nice) = self.nice {
+            script.push_str(&format!("nice -n {} ", nice.min(19)));
+        }
+        script.push_str("sh -c \"$1\"");
+        script
+    }
+}
+
+/// Status and resource usage of a background process.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessReport {
+    pub id: String,
+    pub command: String,
+    #[serde(skip)]
+    pub status: ProcessStatus,
+    /// `running`, `completed`, `failed` or `terminated`.
+    pub state: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    /// Why the process failed or was terminated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Wall-clock time so far, or until the process ended.
+    pub runtime_secs: f64,
+    /// Highest resident memory seen, in kilobytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peak_memory_kb: Option<u64>,
+    pub limits: ResourceLimits,
 }
 
 /// Information about a background process.
@@ -21,59 +121,149 @@ pub struct ProcessInfo {
     pub id: String,
     pub command: String,
     pub status: ProcessStatus,
+    pub limits: ResourceLimits,
+    /// Highest resident memory seen, in kilobytes.
+    pub peak_memory_kb: Option<u64>,
+    started: Instant,
+    runtime: Option<Duration>,
     child: Option<Child>,
 }
 
 impl ProcessInfo {
-    fn new(id: String, command: String, child: Child) -> Self {
+    fn new(id: String, command: String, child: Child, limits: ResourceLimits) -> Self {
         Self {
             id,
             command,
             status: ProcessStatus::Running,
+            limits,
+            peak_memory_kb: None,
+            started: Instant::now(),
+            runtime: None,
             child: Some(child),
         }
     }
 
-    /// Check and update process status.
+    /// Check and update process status, enforcing its limits.
     fn update_status(&mut self) {
         if let Some(child) = &mut self.child {
             match child.try_wait() {
                 Ok(Some(status)) => {
-                    self.status = ProcessStatus::Completed(status.code().unwrap_or(-1));
-                    self.child = None;
-                }
-                Ok(None) => {}
-                Err(e) => {
-                    self.status = ProcessStatus::Failed(e.to_string());
-                    self.child = None;
+                    let status = self.exit_status(status);
+                    self.finish(status);
                 }
+                Ok(None) => self.enforce_limits(),
+                Err(e) => self.finish(ProcessStatus::Failed(e.to_string())),
             }
         }
     }
 
+    /// Record memory usage and kill the process if it breached a limit.
+    fn enforce_limits(&mut self) {
+        let Some(child) = &mut self.child else {
+            return;
+        };
+        let usage = memory_usage(child.id());
+        if let Some((_, peak_kb)) = usage {
+            self.peak_memory_kb = Some(self.peak_memory_kb.unwrap_or(0).max(peak_kb));
+        }
+
+        let runtime = self.started.elapsed();
+        let breach = match (self.limits.max_runtime_secs, self.limits.memory_mb, usage) {
+            (Some(secs), _, _) if runtime >= Duration::from_secs(secs) => {
+                Some(format!("exceeded max runtime of {}s", secs))
+            }
+            (_, Some(mb), Some((rss_kb, _))) if rss_kb > mb * 1024 => {
+                Some(format!("exceeded memory limit of {} MB", mb))
+            }
+            _ => None,
+        };
+        if let Some(reason) = breach {
+            warn!("Killing background process {}: {}", self.id, reason);
+            let _ = child.kill();
+            let _ = child.wait();
+            self.finish(ProcessStatus::Terminated(reason));
+        }
+    }
+
+    /// Status of an exited process.
+    fn exit_status(&self, status: ExitStatus) -> ProcessStatus {
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt;
+            if let (Some(secs), Some(SIGXCPU)) = (self.limits.cpu_secs, status.signal()) {
+                return ProcessStatus::Terminated(format!("exceeded CPU time limit of {}s", secs));
+            }
+        }
+        ProcessStatus::Completed(status.code().unwrap_or(-1))
+    }
+
+    fn finish(&mut self, status: ProcessStatus) {
+        self.status = status;
+        self.runtime = Some(self.started.elapsed());
+        self.child = None;
+    }
+
     /// Kill the process if running.
     fn kill(&mut self) -> Result<(), String> {
+        self.kill_with(ProcessStatus::Completed(-9))
+    }
+
+    /// Kill the process if running, recording `status`.
+    fn kill_with(&mut self, status: ProcessStatus) -> Result<(), String> {
         if let Some(child) = &mut self.child {
             child.kill().map_err(|e| e.to_string())?;
-            self.status = ProcessStatus::Completed(-9);
-            self.child = None;
+            let _ = child.wait();
+            self.finish(status);
         }
         Ok(())
     }
 
-    /// Wait for process to complete.
-    fn wait(&mut self) -> Result<ExitStatus, String> {
-        if let Some(child) = &mut self.child {
-            let status = child.wait().map_err(|e| e.to_string())?;
-            self.status = ProcessStatus::Completed(status.code().unwrap_or(-1));
-            self.child = None;
-            Ok(status)
-        } else {
-            Err("Process not running".to_string())
+    fn report(&self) -> ProcessReport {
+        let (state, exit_code, reason) = match &self.status {
+            ProcessStatus::Running => ("running", None, None),
+            ProcessStatus::Completed(code) => ("completed", Some(*code), None),
+            ProcessStatus::Failed(msg) => ("failed", None, Some(msg.clone())),
+            ProcessStatus::Terminated(msg) => ("terminated", None, Some(msg.clone())),
+        };
+        ProcessReport {
+            id: self.id.clone(),
+            command: self.command.clone(),
+            status: self.status.clone(),
+            state,
+            exit_code,
+            reason,
+            runtime_secs: self.runtime.unwrap_or_else(|| self.started.elapsed()).as_secs_f64(),
+            peak_memory_kb: self.peak_memory_kb,
+            limits: self.limits.clone(),
         }
     }
 }
 
+/// Resident and peak resident memory of a process, in kilobytes.
+#[cfg(target_os = "linux")]
+fn memory_usage(pid: u32) -> Option<(u64, u64)> {
+    let status = std::fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    parse_memory_usage(&status)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn memory_usage(_pid: u32) -> Option<(u64, u64)> {
+    None
+}
+
+/// `VmRSS` and `VmHWM` of a `/proc/<pid>/status` file.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_memory_usage(status: &str) -> Option<(u64, u64)> {
+    let field = |name: &str| {
+        status
+            .lines()
+            .find_map(|line| line.strip_prefix(name))
+            .and_then(|rest| rest.trim().trim_end_matches("kB").trim().parse::<u64>().ok())
+    };
+    let rss = field("VmRSS:")?;
+    Some((rss, field("VmHWM:").unwrap_or(rss)))
+}
+
 /// Manager for background processes.
 pub struct BackgroundManager {
     processes: Arc<Mutex<HashMap<String, ProcessInfo>>>,
@@ -89,17 +279,32 @@ impl BackgroundManager {
 
     /// Start a background process.
     pub fn spawn(&self, command: &str, cwd: Option<&str>) -> Result<String, String> {
-        let (shell, flag) = if cfg!(target_os = "windows") {
-            ("cmd", "/C")
+        self.spawn_with_limits(command, cwd, ResourceLimits::default())
+    }
+
+    /// Start a background process under resource limits.
+    ///
+    /// Only the runtime and memory watchdog applies on Windows.
+    pub fn spawn_with_limits(
+        &self,
+        command: &str,
+        cwd: Option<&str>,
+        limits: ResourceLimits,
+    ) -> Result<String, String> {
+        let mut cmd = if cfg!(target_os = "windows") {
+            let mut cmd = Command::new("cmd");
+            cmd.arg("/C").arg(command);
+            cmd
+        } else if limits.memory_mb.is_some() || limits.cpu_secs.is_some() || limits.nice.is_some() {
+            let mut cmd = Command::new("sh");
+            cmd.arg("-c").arg(limits.wrapper_script()).arg("sh").arg(command);
+            cmd
         } else {
-            ("sh", "-c")
+            let mut cmd = Command::new("sh");
+            cmd.arg("-c").arg(command);
+            cmd
         };
-
-        let mut cmd = Command::new(shell);
-        cmd.arg(flag)
-            .arg(command)
-            .stdout(Stdio::null())
-            .stderr(Stdio::null());
+        cmd.stdout(Stdio::null()).stderr(Stdio::null());
 
         if let Some(dir) = cwd {
             cmd.current_dir(dir);
@@ -108,14 +313,26 @@ impl BackgroundManager {
         let child = cmd.spawn().map_err(|e| e.to_string())?;
         let id = Uuid::new_v4().to_string();
 
+        let watched = limits.is_watched();
         self.processes.lock().insert(
             id.clone(),
-            ProcessInfo::new(id.clone(), command.to_string(), child),
+            ProcessInfo::new(id.clone(), command.to_string(), child, limits),
         );
+        if watched {
+            watch(Arc::downgrade(&self.processes), id.clone());
+        }
 
         Ok(id)
     }
 
+    /// Status and resource usage of a process.
+    pub fn report(&self, id: &str) -> Option<ProcessReport> {
+        let mut processes = self.processes.lock();
+        let info = processes.get_mut(id)?;
+        info.update_status();
+        Some(info.report())
+    }
+
     /// Get process status.
     pub fn status(&self, id: &str) -> Option<ProcessStatus> {
         let mut processes = self.processes.lock();
@@ -149,14 +366,86 @@ impl BackgroundManager {
         }
     }
 
-    /// Wait for a process to complete.
-    pub fn wait(&self, id: &str) -> Result<i32, String> {
+    /// Terminate a process once `signal` is aborted.
+    ///
+    /// Must be called within a Tokio runtime. Stops watching when the
+    /// process ends or the manager is dropped.
+    pub fn bind(&self, id: &str, signal: Arc<AbortSignal>) {
+        let processes = Arc::downgrade(&self.processes);
+        let id = id.to_string();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = signal.aborted() => break,
+                    _ = tokio::time::sleep(BIND_CHECK_INTERVAL) => {}
+                }
+                let Some(processes) = processes.upgrade() else {
+                    return;
+                };
+                let mut processes = processes.lock();
+                let Some(info) = processes.get_mut(&id) else {
+                    return;
+                };
+                info.update_status();
+                if !matches!(info.status, ProcessStatus::Running) {
+                    return;
+                }
+            }
+            let Some(processes) = processes.upgrade() else {
+                return;
+            };
+            if let Some(info) = processes.lock().get_mut(&id) {
+                info.update_status();
+                if matches!(info.status, ProcessStatus::Running) {
+                    info!("Terminating background process {}: session cancelled", id);
+                    let reason = "session cancelled".to_string();
+                    if let Err(e) = info.kill_with(ProcessStatus::Terminated(reason)) {
+                        warn!("Failed to terminate background process {}: {}", id, e);
+                    }
+                }
+            }
+        });
+    }
+
+    /// Kill every running process, returning the IDs of those killed.
+    pub fn kill_all(&self) -> Vec<String> {
         let mut processes = self.processes.lock();
-        if let Some(info) = processes.get_mut(id) {
-            let status = info.wait()?;
-            Ok(status.code().unwrap_or(-1))
-        } else {
-            Err(format!("Process not found: {}", id))
+        let mut killed = Vec::new();
+        for info in processes.values_mut() {
+            info.update_status();
+            if !matches!(info.status, ProcessStatus::Running) {
+                continue;
+            }
+            let reason = "shutting down".to_string();
+            match info.kill_with(ProcessStatus::Terminated(reason)) {
+                Ok(()) => killed.push(info.id.clone()),
+                Err(e) => warn!("Failed to kill background process {}: {}", info.id, e),
+            }
+        }
+        killed
+    }
+
+    /// Wait for a process to complete, returning its exit code.
+    ///
+    /// The manager is not locked while waiting, so limits stay enforced.
+    pub fn wait(&self, id: &str) -> Result<i32, String> {
+        loop {
+            {
+                let mut processes = self.processes.lock();
+                let info = processes
+                    .get_mut(id)
+                    .ok_or_else(|| format!("Process not found: {}", id))?;
+                info.update_status();
+                match &info.status {
+                    ProcessStatus::Running => {}
+                    ProcessStatus::Completed(code) => return Ok(*code),
+                    ProcessStatus::Failed(msg) => return Err(msg.clone()),
+                    ProcessStatus::Terminated(reason) => {
+                        return Err(format!("Process terminated: {}", reason))
+                    }
+                }
+            }
+            std::thread::sleep(Duration::from_millis(20));
         }
     }
 
@@ -182,6 +471,24 @@ impl BackgroundManager {
     }
 }
 
+/// Check a limited process until it ends or the manager is dropped.
+fn watch(processes: Weak<Mutex<HashMap<String, ProcessInfo>>>, id: String) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(WATCH_INTERVAL);
+        let Some(processes) = processes.upgrade() else {
+            return;
+        };
+        let mut processes = processes.lock();
+        let Some(info) = processes.get_mut(&id) else {
+            return;
+        };
+        info.update_status();
+        if !matches!(info.status, ProcessStatus::Running) {
+            return;
+        }
+    });
+}
+
 impl Default for BackgroundManager {
     fn default() -> Self {
         Self::new()
@@ -166,3 +166,103 @@ fn test_spawn_invalid_command_cwd() {
     // but the command might fail - either way we test the path
     let _ = result;
 }
+
+#[test]
+fn test_resource_limits_wrapper_script() {
+    assert!(ResourceLimits::default().is_empty());
+    let limits = ResourceLimits {
+        memory_mb: Some(64),
+        cpu_secs: Some(5),
+        nice: Some(30),
+        ..Default::default()
+    };
+    assert!(!limits.is_empty());
+    assert_eq!(
+        limits.wrapper_script(),
+        "ulimit -v 65536 && ulimit -S -t 5 && ulimit -H -t 6 && exec nice -n 19 sh -c \"$1\""
+    );
+}
+
+#[test]
+fn test_parse_memory_usage() {
+    let status = "Name:\tsleep\nVmHWM:\t    2048 kB\nVmRSS:\t    1024 kB\n";
+    assert_eq!(parse_memory_usage(status), Some((1024, 2048)));
+    assert_eq!(parse_memory_usage("Name:\tzombie\n"), None);
+}
+
+#[test]
+fn test_spawn_with_limits_runs_command() {
+    let manager = BackgroundManager::new();
+    let limits = ResourceLimits {
+        memory_mb: Some(256),
+        nice: Some(10),
+        ..Default::default()
+    };
+    let id = manager.spawn_with_limits("exit 3", None, limits.clone()).unwrap();
+    assert_eq!(manager.wait(&id), Ok(3));
+
+    let report = manager.report(&id).unwrap();
+    assert_eq!(report.state, "completed");
+    assert_eq!(report.exit_code, Some(3));
+    assert_eq!(report.limits, limits);
+}
+
+#[test]
+fn test_max_runtime_terminates_process() {
+    let manager = BackgroundManager::new();
+    let limits = ResourceLimits {
+        max_runtime_secs: Some(1),
+        ..Default::default()
+    };
+    let id = manager.spawn_with_limits("sleep 60", None, limits).unwrap();
+
+    // The watchdog kills it without anyone asking for its status
+    std::thread::sleep(std::time::Duration::from_millis(1500));
+    let report = manager.report(&id).unwrap();
+    assert_eq!(report.state, "terminated");
+    assert_eq!(report.reason.as_deref(), Some("exceeded max runtime of 1s"));
+    assert!(report.runtime_secs >= 1.0 && report.runtime_secs < 60.0);
+    assert_eq!(manager.running_count(), 0);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_cpu_limit_terminates_process() {
+    let manager = BackgroundManager::new();
+    let limits = ResourceLimits {
+        cpu_secs: Some(1),
+        max_runtime_secs: Some(30),
+        ..Default::default()
+    };
+    let id = manager.spawn_with_limits("while :; do :; done", None, limits).unwrap();
+
+    let err = manager.wait(&id).unwrap_err();
+    assert_eq!(err, "Process terminated: exceeded CPU time limit of 1s");
+    assert!(matches!(manager.status(&id), Some(ProcessStatus::Terminated(_))));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_bind_terminates_with_session() {
+    let manager = BackgroundManager::new();
+    let session = Arc::new(AbortSignal::new());
+    let id = manager.spawn("sleep 60", None).unwrap();
+    manager.bind(&id, session.child());
+    let other = manager.spawn("sleep 60", None).unwrap();
+
+    session.abort();
+    for _ in 0..100 {
+        if !matches!(manager.status(&id), Some(ProcessStatus::Running)) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    match manager.status(&id) {
+        Some(ProcessStatus::Terminated(reason)) => assert_eq!(reason, "session cancelled"),
+        status => panic!("unexpected status {:?}", status),
+    }
+    // Unbound processes keep running until shutdown
+    assert!(matches!(manager.status(&other), Some(ProcessStatus::Running)));
+    assert_eq!(manager.kill_all(), vec![other.clone()]);
+    assert!(matches!(manager.status(&other), Some(ProcessStatus::Terminated(_))));
+}
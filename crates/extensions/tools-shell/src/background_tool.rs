@@ -9,7 +9,7 @@ use autohands_protocols::error::ToolError;
 use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
 use autohands_protocols::types::RiskLevel;
 
-use crate::background::{BackgroundManager, ProcessStatus};
+use crate::background::{BackgroundManager, ProcessStatus, ResourceLimits};
 
 /// Parameters for background tool.
 #[derive(Debug, Deserialize)]
@@ -25,6 +25,9 @@ struct BackgroundParams {
     /// Working directory (for spawn)
     #[serde(default)]
     cwd: Option<String>,
+    /// Resource limits (for spawn)
+    #[serde(flatten)]
+    limits: ResourceLimits,
 }
 
 /// Background process management tool.
@@ -54,6 +57,27 @@ impl BackgroundTool {
                 "cwd": {
                     "type": "string",
                     "description": "Working directory (for spawn)"
+                },
+                "memory_mb": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Memory cap in MB; the process is killed above it (for spawn)"
+                },
+                "cpu_secs": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "CPU time limit in seconds (for spawn)"
+                },
+                "max_runtime_secs": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Wall-clock time after which the process is killed (for spawn)"
+                },
+                "nice": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "maximum": 19,
+                    "description": "Niceness; higher values get a smaller CPU share (for spawn)"
                 }
             },
             "required": ["action"]
@@ -76,6 +100,7 @@ impl BackgroundTool {
             ProcessStatus::Running => "Running".to_string(),
             ProcessStatus::Completed(code) => format!("Completed (exit code: {})", code),
             ProcessStatus::Failed(msg) => format!("Failed: {}", msg),
+            ProcessStatus::Terminated(reason) => format!("Terminated: {}", reason),
         }
     }
 }
@@ -89,7 +114,7 @@ impl Tool for BackgroundTool {
     async fn execute(
         &self,
         params: serde_json::Value,
-        _ctx: ToolContext,
+        ctx: ToolContext,
     ) -> Result<ToolResult, ToolError> {
         let params: BackgroundParams = serde_json::from_value(params)
             .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
@@ -100,10 +125,14 @@ impl Tool for BackgroundTool {
                     .command
                     .ok_or_else(|| ToolError::InvalidParameters("command required".into()))?;
 
+                // Background processes run on the host, which a sandbox cannot confine
+                let cwd = ctx.check_host_command(params.cwd.as_deref())?;
+
                 let id = self
                     .manager
-                    .spawn(&command, params.cwd.as_deref())
+                    .spawn_with_limits(&command, Some(&cwd.to_string_lossy()), params.limits)
                     .map_err(|e| ToolError::ExecutionFailed(e))?;
+                self.manager.bind(&id, ctx.abort_signal.clone());
 
                 Ok(ToolResult::success(format!(
                     "Background process started: {}",
@@ -115,11 +144,23 @@ impl Tool for BackgroundTool {
                     .process_id
                     .ok_or_else(|| ToolError::InvalidParameters("process_id required".into()))?;
 
-                let status = self.manager.status(&id).ok_or_else(|| {
+                let report = self.manager.report(&id).ok_or_else(|| {
                     ToolError::ResourceNotFound(format!("Process not found: {}", id))
                 })?;
 
-                Ok(ToolResult::success(Self::format_status(&status)))
+                let mut content = format!(
+                    "{} (runtime: {:.1}s",
+                    Self::format_status(&report.status),
+                    report.runtime_secs
+                );
+                if let Some(kb) = report.peak_memory_kb {
+                    content.push_str(&format!(", peak memory: {} KB", kb));
+                }
+                content.push(')');
+                Ok(ToolResult::success_json(
+                    content,
+                    serde_json::to_value(&report).unwrap_or_default(),
+                ))
             }
             "list" => {
                 let processes = self.manager.list();
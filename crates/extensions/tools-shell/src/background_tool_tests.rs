@@ -36,6 +36,22 @@ fn test_background_params_with_process_id() {
     assert_eq!(params.process_id, Some("proc_123".to_string()));
 }
 
+#[test]
+fn test_background_params_limits() {
+    let json = serde_json::json!({
+        "action": "spawn",
+        "command": "make test",
+        "memory_mb": 512,
+        "max_runtime_secs": 600,
+        "nice": 10
+    });
+    let params: BackgroundParams = serde_json::from_value(json).unwrap();
+    assert_eq!(params.limits.memory_mb, Some(512));
+    assert_eq!(params.limits.max_runtime_secs, Some(600));
+    assert_eq!(params.limits.nice, Some(10));
+    assert!(params.limits.cpu_secs.is_none());
+}
+
 #[test]
 fn test_tool_definition() {
     let manager = Arc::new(BackgroundManager::new());
@@ -65,6 +81,15 @@ fn test_format_status_failed() {
     assert_eq!(BackgroundTool::format_status(&status), "Failed: error message");
 }
 
+#[test]
+fn test_format_status_terminated() {
+    let status = ProcessStatus::Terminated("exceeded max runtime of 5s".to_string());
+    assert_eq!(
+        BackgroundTool::format_status(&status),
+        "Terminated: exceeded max runtime of 5s"
+    );
+}
+
 #[tokio::test]
 async fn test_list_empty() {
     let manager = Arc::new(BackgroundManager::new());
@@ -210,3 +235,44 @@ async fn test_invalid_params() {
     let result = tool.execute(params, ctx).await;
     assert!(result.is_err());
 }
+
+#[tokio::test]
+async fn test_status_reports_usage() {
+    let manager = Arc::new(BackgroundManager::new());
+    let tool = BackgroundTool::new(manager.clone());
+    let ctx = ToolContext::new("test", std::env::current_dir().unwrap());
+
+    let id = manager
+        .spawn_with_limits(
+            "exit 0",
+            None,
+            ResourceLimits {
+                max_runtime_secs: Some(60),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    manager.wait(&id).unwrap();
+
+    let params = serde_json::json!({"action": "status", "process_id": id});
+    let result = tool.execute(params, ctx).await.unwrap();
+    assert!(result.content.starts_with("Completed (exit code: 0) (runtime: "));
+    let report = result.structured_output.unwrap();
+    assert_eq!(report["state"], "completed");
+    assert_eq!(report["exit_code"], 0);
+    assert_eq!(report["limits"]["max_runtime_secs"], 60);
+}
+
+#[tokio::test]
+async fn test_spawn_refused_under_sandbox() {
+    let manager = Arc::new(BackgroundManager::new());
+    let tool = BackgroundTool::new(manager.clone());
+    let sandbox = autohands_protocols::tool::SandboxPolicy::default();
+    let ctx =
+        ToolContext::new("test", std::env::current_dir().unwrap()).with_sandbox(Arc::new(sandbox));
+
+    let params = serde_json::json!({"action": "spawn", "command": "sleep 10"});
+    let err = tool.execute(params, ctx).await.unwrap_err();
+    assert!(matches!(err, ToolError::PermissionDenied(_)));
+    assert!(manager.list().is_empty());
+}
@@ -0,0 +1,81 @@
+//! Running shell commands in an ephemeral container.
+//!
+//! Under a sandbox with a [`ContainerSandbox`], `exec` runs its command
+//! with `docker run --rm` (or Podman) instead of on the host. The working
+//! directory and the sandbox's allowed roots are mounted at their host
+//! paths, CPU and memory are limited, and the container has no network
+//! unless the sandbox allows it. Host environment variables are not
+//! passed in.
+
+use std::path::{Path, PathBuf};
+
+use tokio::process::Command;
+use tracing::warn;
+
+use autohands_protocols::tool::ContainerSandbox;
+
+/// A command to run in a container.
+pub(crate) struct ContainerRun<'a> {
+    pub sandbox: &'a ContainerSandbox,
+    /// Container name, used to remove it when the command times out.
+    pub name: String,
+    /// Host directories mounted at the same path.
+    pub mounts: Vec<PathBuf>,
+    /// Directory the command runs in.
+    pub cwd: &'a Path,
+    pub command: &'a str,
+}
+
+impl ContainerRun<'_> {
+    /// Arguments of the engine's `run` command.
+    pub(crate) fn args(&self) -> Vec<String> {
+        let mut args = vec![
+            "run".to_string(),
+            "--rm".to_string(),
+            "--name".to_string(),
+            self.name.clone(),
+            "--cpus".to_string(),
+            self.sandbox.cpus.to_string(),
+            "--memory".to_string(),
+            self.sandbox.memory.clone(),
+        ];
+        if !self.sandbox.network {
+            args.extend(["--network".to_string(), "none".to_string()]);
+        }
+        for mount in &self.mounts {
+            let mount = mount.display();
+            args.extend(["-v".to_string(), format!("{}:{}", mount, mount)]);
+        }
+        args.extend([
+            "-w".to_string(),
+            self.cwd.display().to_string(),
+            self.sandbox.image.clone(),
+            "sh".to_string(),
+            "-c".to_string(),
+            self.command.to_string(),
+        ]);
+        args
+    }
+
+    /// The engine command running the container.
+    pub(crate) fn command(&self) -> Command {
+        let mut cmd = Command::new(&self.sandbox.engine);
+        cmd.args(self.args()).kill_on_drop(true);
+        cmd
+    }
+
+    /// Remove the container, which outlives a killed engine client.
+    pub(crate) async fn remove(&self) {
+        let removed = Command::new(&self.sandbox.engine)
+            .args(["rm", "-f", &self.name])
+            .output()
+            .await;
+        if let Err(e) = removed {
+            warn!("Failed to remove container {}: {}", self.name, e);
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "container_tests.rs"]
+mod tests;
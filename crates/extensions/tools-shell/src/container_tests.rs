@@ -0,0 +1,42 @@
+use super::*;
+
+fn run<'a>(sandbox: &'a ContainerSandbox, cwd: &'a Path) -> ContainerRun<'a> {
+    ContainerRun {
+        sandbox,
+        name: "autohands-exec-1".to_string(),
+        mounts: vec![PathBuf::from("/work"), PathBuf::from("/shared")],
+        cwd,
+        command: "cargo test && echo done",
+    }
+}
+
+#[test]
+fn test_args_without_network() {
+    let sandbox = ContainerSandbox::default();
+    let args = run(&sandbox, Path::new("/work/crate")).args();
+    assert_eq!(
+        args,
+        [
+            "run", "--rm", "--name", "autohands-exec-1", "--cpus", "1", "--memory", "1g",
+            "--network", "none", "-v", "/work:/work", "-v", "/shared:/shared", "-w",
+            "/work/crate", "debian:bookworm-slim", "sh", "-c", "cargo test && echo done",
+        ]
+    );
+}
+
+#[test]
+fn test_args_with_network_and_limits() {
+    let sandbox = ContainerSandbox {
+        engine: "podman".to_string(),
+        image: "rust:1".to_string(),
+        cpus: 2.5,
+        memory: "4g".to_string(),
+        network: true,
+    };
+    let args = run(&sandbox, Path::new("/work")).args();
+    assert!(!args.contains(&"--network".to_string()));
+    assert!(args.windows(2).any(|w| w == ["--cpus", "2.5"]));
+    assert!(args.windows(2).any(|w| w == ["--memory", "4g"]));
+    assert!(args.contains(&"rust:1".to_string()));
+    assert_eq!(run(&sandbox, Path::new("/work")).command().as_std().get_program(), "podman");
+}
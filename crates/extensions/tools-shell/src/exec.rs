@@ -1,17 +1,27 @@
 //! Shell command execution tool.
+//!
+//! Commands run on the host, or under a sandbox only in its container
+//! (see [`crate::container`]). Long-running commands can stream
+//! their output (see [`crate::stream`]). Host commands run in the shell
+//! and environment profile chosen per call or configured (see
+//! [`crate::shell`]).
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::process::Stdio;
+use std::process::{ExitStatus, Stdio};
+use std::sync::Arc;
 use std::time::Duration;
 
-use tokio::process::Command;
 use tokio::time::timeout;
 
 use autohands_protocols::error::ToolError;
 use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
 use autohands_protocols::types::RiskLevel;
 
+use crate::container::ContainerRun;
+use crate::shell::{Shell, ShellConfig};
+use crate::stream::{run_streaming, DEFAULT_TAIL_BYTES};
+
 /// Parameters for exec tool.
 #[derive(Debug, Deserialize)]
 struct ExecParams {
@@ -23,15 +33,35 @@ struct ExecParams {
     /// Working directory (optional).
     #[serde(default)]
     cwd: Option<String>,
+    /// Stream output while the command runs.
+    #[serde(default)]
+    stream: bool,
+    /// Bytes of output kept in the result when streaming.
+    #[serde(default = "default_tail_bytes")]
+    tail_bytes: usize,
+    /// Shell to run the command in.
+    #[serde(default)]
+    shell: Option<Shell>,
+    /// Environment profile to run the command with.
+    #[serde(default)]
+    profile: Option<String>,
+    /// Run a login shell, sourcing the user's shell profile.
+    #[serde(default)]
+    login: Option<bool>,
 }
 
 fn default_timeout() -> u64 {
     120_000
 }
 
+fn default_tail_bytes() -> usize {
+    DEFAULT_TAIL_BYTES
+}
+
 /// Shell command execution tool.
 pub struct ExecTool {
     definition: ToolDefinition,
+    config: Arc<ShellConfig>,
 }
 
 impl ExecTool {
@@ -50,6 +80,27 @@ impl ExecTool {
                 "cwd": {
                     "type": "string",
                     "description": "Working directory for the command"
+                },
+                "stream": {
+                    "type": "boolean",
+                    "description": "Stream stdout/stderr while the command runs and return only the end of the output; use for long builds and test runs"
+                },
+                "tail_bytes": {
+                    "type": "integer",
+                    "description": "Bytes of output returned when streaming (default: 16384)"
+                },
+                "shell": {
+                    "type": "string",
+                    "enum": ["sh", "bash", "zsh", "fish", "pwsh", "cmd"],
+                    "description": "Shell to run the command in (default: configured, else sh or cmd on Windows)"
+                },
+                "profile": {
+                    "type": "string",
+                    "description": "Configured environment profile (variables, PATH additions, working directory)"
+                },
+                "login": {
+                    "type": "boolean",
+                    "description": "Run a login shell so the user's shell profile is sourced"
                 }
             },
             "required": ["command"]
@@ -62,9 +113,20 @@ impl ExecTool {
                 "Execute a shell command",
             )
             .with_parameters_schema(schema)
-            .with_risk_level(RiskLevel::High),
+            .with_risk_level(RiskLevel::High)
+            .with_example(
+                "Run the test suite in a project",
+                serde_json::json!({"command": "cargo test", "cwd": "/project"}),
+            ),
+            config: Arc::new(ShellConfig::default()),
         }
     }
+
+    /// Use configured shell settings and environment profiles.
+    pub fn with_config(mut self, config: Arc<ShellConfig>) -> Self {
+        self.config = config;
+        self
+    }
 }
 
 impl Default for ExecTool {
@@ -87,31 +149,71 @@ impl Tool for ExecTool {
         let params: ExecParams = serde_json::from_value(params)
             .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
 
-        let cwd = params
-            .cwd
-            .map(std::path::PathBuf::from)
-            .unwrap_or_else(|| ctx.work_dir.clone());
-
-        // Determine shell based on platform
-        let (shell, flag) = if cfg!(target_os = "windows") {
-            ("cmd", "/C")
-        } else {
-            ("sh", "-c")
+        let invocation = self
+            .config
+            .resolve(params.shell, params.profile.as_deref(), params.login)
+            .map_err(ToolError::InvalidParameters)?;
+        let profile_cwd = invocation.cwd.as_ref().map(|dir| dir.display().to_string());
+        let cwd = ctx.check_command(params.cwd.as_deref().or(profile_cwd.as_deref()))?;
+        let host_command = || {
+            let mut cmd = invocation.command(&params.command);
+            cmd.current_dir(&cwd);
+            cmd
         };
 
-        let mut cmd = Command::new(shell);
-        cmd.arg(flag)
-            .arg(&params.command)
-            .current_dir(&cwd)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
-
         let duration = Duration::from_millis(params.timeout);
+        let container = ctx.sandbox.as_deref().and_then(|sandbox| {
+            let sandbox_container = sandbox.container.as_ref()?;
+            let mut mounts = vec![ctx.work_dir.canonicalize().unwrap_or(ctx.work_dir.clone())];
+            mounts.extend(sandbox.allowed_root_paths());
+            Some(ContainerRun {
+                sandbox: sandbox_container,
+                name: format!("autohands-exec-{}", uuid::Uuid::new_v4()),
+                mounts,
+                cwd: &cwd,
+                command: &params.command,
+            })
+        });
 
-        let output = timeout(duration, cmd.output())
-            .await
-            .map_err(|_| ToolError::Timeout(params.timeout / 1000))?
-            .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
+        if params.stream {
+            let cmd = match &container {
+                Some(container) => container.command(),
+                None => host_command(),
+            };
+            let streamed = run_streaming(cmd, &ctx, duration, params.tail_bytes)
+                .await
+                .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
+            let Some(streamed) = streamed else {
+                if let Some(container) = &container {
+                    container.remove().await;
+                }
+                return Err(ToolError::Timeout(params.timeout / 1000));
+            };
+            return Ok(finish(streamed.status, streamed.output));
+        }
+
+        let output = match &container {
+            Some(container) => {
+                let mut cmd = container.command();
+                cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+                match timeout(duration, cmd.output()).await {
+                    Ok(output) => output,
+                    Err(_) => {
+                        container.remove().await;
+                        return Err(ToolError::Timeout(params.timeout / 1000));
+                    }
+                }
+            }
+            None => {
+                let mut cmd = host_command();
+                cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+
+                timeout(duration, cmd.output())
+                    .await
+                    .map_err(|_| ToolError::Timeout(params.timeout / 1000))?
+            }
+        }
+        .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
 
         let stdout = String::from_utf8_lossy(&output.stdout);
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -129,15 +231,42 @@ impl Tool for ExecTool {
             result.push_str(&stderr);
         }
 
-        if output.status.success() {
-            Ok(ToolResult::success(result))
-        } else {
-            let code = output.status.code().unwrap_or(-1);
-            Ok(ToolResult::error(format!(
-                "Command failed with exit code {}\n{}",
-                code, result
-            )))
-        }
+        Ok(finish(output.status, result))
+    }
+
+    async fn simulate(
+        &self,
+        params: serde_json::Value,
+        ctx: ToolContext,
+    ) -> Result<ToolResult, ToolError> {
+        let params: ExecParams = serde_json::from_value(params)
+            .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
+
+        let invocation = self
+            .config
+            .resolve(params.shell, params.profile.as_deref(), params.login)
+            .map_err(ToolError::InvalidParameters)?;
+        let profile_cwd = invocation.cwd.as_ref().map(|dir| dir.display().to_string());
+        let cwd = ctx.check_command(params.cwd.as_deref().or(profile_cwd.as_deref()))?;
+        Ok(ToolResult::simulated(format!(
+            "Would run `{}` with {} in {}; nothing was run.",
+            params.command,
+            invocation.shell.program(),
+            cwd.display()
+        )))
+    }
+}
+
+/// The tool result for a finished command and its output.
+fn finish(status: ExitStatus, output: String) -> ToolResult {
+    if status.success() {
+        ToolResult::success(output)
+    } else {
+        let code = status.code().unwrap_or(-1);
+        ToolResult::error(format!(
+            "Command failed with exit code {}\n{}",
+            code, output
+        ))
     }
 }
 
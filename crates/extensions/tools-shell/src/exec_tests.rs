@@ -1,4 +1,6 @@
 use super::*;
+use autohands_protocols::tool::{OutputStream, SandboxPolicy, ToolOutputChunk};
+use std::sync::Arc;
 use tempfile::TempDir;
 
 fn create_test_context(work_dir: std::path::PathBuf) -> ToolContext {
@@ -129,3 +131,154 @@ async fn test_exec_multiline_output() {
     assert!(result.content.contains("line2"));
     assert!(result.content.contains("line3"));
 }
+
+#[tokio::test]
+async fn test_exec_under_sandbox() {
+    let temp_dir = TempDir::new().unwrap();
+    let sandbox = SandboxPolicy {
+        denied: vec!["secret.txt".to_string()],
+        ..Default::default()
+    };
+    let ctx = create_test_context(temp_dir.path().to_path_buf()).with_sandbox(Arc::new(sandbox));
+    let tool = ExecTool::new();
+
+    // Without a container, no command runs on the host
+    for command in ["echo ok", "cat $HOME/.ssh/id_rsa", "cd ~ && cat .ssh/x"] {
+        let err = tool
+            .execute(serde_json::json!({"command": command}), ctx.clone())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ToolError::PermissionDenied(_)));
+    }
+
+    // With one, only from inside the sandbox
+    let sandbox = SandboxPolicy {
+        container: Some(autohands_protocols::tool::ContainerSandbox {
+            engine: "autohands-missing-engine".to_string(),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let ctx = create_test_context(temp_dir.path().to_path_buf()).with_sandbox(Arc::new(sandbox));
+    let err = tool
+        .execute(serde_json::json!({"command": "ls", "cwd": "/"}), ctx)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Path traversal denied"));
+}
+
+#[tokio::test]
+async fn test_exec_in_container_runs_engine() {
+    let temp_dir = TempDir::new().unwrap();
+    let sandbox = SandboxPolicy {
+        container: Some(autohands_protocols::tool::ContainerSandbox {
+            engine: "autohands-missing-engine".to_string(),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let ctx = create_test_context(temp_dir.path().to_path_buf()).with_sandbox(Arc::new(sandbox));
+
+    let err = ExecTool::new()
+        .execute(serde_json::json!({"command": "echo ok"}), ctx)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ToolError::ExecutionFailed(_)));
+}
+
+#[tokio::test]
+async fn test_exec_streams_output() {
+    let temp_dir = TempDir::new().unwrap();
+    let chunks = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let sink = chunks.clone();
+    let output = move |chunk: ToolOutputChunk| sink.lock().unwrap().push(chunk);
+    let ctx = create_test_context(temp_dir.path().to_path_buf()).with_output(Arc::new(output));
+    let params = serde_json::json!({
+        "command": "echo building; sleep 0.1; echo warning >&2; printf '%0200d' 0",
+        "stream": true,
+        "tail_bytes": 100
+    });
+
+    let result = ExecTool::new().execute(params, ctx).await.unwrap();
+    assert!(result.success);
+    assert!(result.content.starts_with("[... "));
+    assert!(result.content.ends_with(&"0".repeat(100)));
+
+    let chunks = chunks.lock().unwrap();
+    assert_eq!(chunks[0].content, "building\n");
+    assert!(chunks
+        .iter()
+        .any(|c| c.stream == OutputStream::Stderr && c.content == "warning\n"));
+}
+
+#[tokio::test]
+async fn test_exec_stream_timeout_and_failure() {
+    let temp_dir = TempDir::new().unwrap();
+    let tool = ExecTool::new();
+    let ctx = create_test_context(temp_dir.path().to_path_buf());
+
+    let err = tool
+        .execute(
+            serde_json::json!({"command": "sleep 10", "stream": true, "timeout": 100}),
+            ctx.clone(),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ToolError::Timeout(_)));
+
+    let result = tool
+        .execute(
+            serde_json::json!({"command": "echo oops; exit 3", "stream": true}),
+            ctx,
+        )
+        .await
+        .unwrap();
+    assert!(!result.success);
+    assert_eq!(
+        result.error.as_deref(),
+        Some("Command failed with exit code 3\noops\n")
+    );
+}
+
+#[tokio::test]
+async fn test_exec_with_profile() {
+    let temp_dir = TempDir::new().unwrap();
+    let subdir = temp_dir.path().join("app");
+    std::fs::create_dir(&subdir).unwrap();
+    let config: ShellConfig = serde_json::from_value(serde_json::json!({
+        "profiles": {
+            "app": {
+                "env": { "APP_MODE": "test" },
+                "path": ["/opt/app-tools"],
+                "cwd": subdir.to_str().unwrap()
+            }
+        }
+    }))
+    .unwrap();
+    let tool = ExecTool::new().with_config(Arc::new(config));
+    let ctx = create_test_context(temp_dir.path().to_path_buf());
+
+    let result = tool
+        .execute(
+            serde_json::json!({
+                "command": "echo $APP_MODE; pwd; echo $PATH",
+                "profile": "app"
+            }),
+            ctx.clone(),
+        )
+        .await
+        .unwrap();
+    let lines: Vec<&str> = result.content.lines().collect();
+    assert_eq!(lines[0], "test");
+    assert!(lines[1].ends_with("app"));
+    assert!(lines[2].starts_with("/opt/app-tools:"));
+
+    let err = tool
+        .execute(
+            serde_json::json!({"command": "true", "profile": "missing"}),
+            ctx,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ToolError::InvalidParameters(_)));
+}
@@ -14,6 +14,7 @@ use crate::background_tool::BackgroundTool;
 use crate::exec::ExecTool;
 use crate::session::SessionManager;
 use crate::session_tool::SessionTool;
+use crate::shell::ShellConfig;
 
 /// Shell extension providing command execution tools.
 pub struct ShellExtension {
@@ -58,14 +59,40 @@ impl Extension for ShellExtension {
     }
 
     async fn initialize(&mut self, ctx: ExtensionContext) -> Result<(), ExtensionError> {
-        ctx.tool_registry.register_tool(Arc::new(ExecTool::new()))?;
+        // Shell choice and environment profiles
+        let config: ShellConfig = if ctx.config.is_null() {
+            ShellConfig::default()
+        } else {
+            serde_json::from_value(ctx.config.clone()).map_err(|e| {
+                ExtensionError::InitializationFailed(format!("Invalid tools-shell config: {}", e))
+            })?
+        };
+        let config = Arc::new(config);
+
         ctx.tool_registry
-            .register_tool(Arc::new(SessionTool::new(self.session_manager.clone())))?;
+            .register_tool(Arc::new(ExecTool::new().with_config(config.clone())))?;
+        ctx.tool_registry.register_tool(Arc::new(
+            SessionTool::new(self.session_manager.clone()).with_config(config),
+        ))?;
         ctx.tool_registry
             .register_tool(Arc::new(BackgroundTool::new(self.background_manager.clone())))?;
         Ok(())
     }
 
+    async fn shutdown(&self) -> Result<(), ExtensionError> {
+        let killed = self.background_manager.kill_all();
+        let sessions = self.session_manager.kill_all().await;
+        if !killed.is_empty() || sessions > 0 {
+            tracing::warn!(
+                "Shell tools shutdown: killed {} background processes ({}) and {} sessions",
+                killed.len(),
+                killed.join(", "),
+                sessions
+            );
+        }
+        Ok(())
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
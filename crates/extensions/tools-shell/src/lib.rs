@@ -1,20 +1,28 @@
 //! Shell execution tools for AutoHands.
 //!
 //! This extension provides:
-//! - `exec`: Execute shell commands
+//! - `exec`: Execute shell commands, on the host or in a container, optionally
+//!   streaming their output
 //! - `shell_session`: Manage persistent shell sessions
-//! - `background`: Manage background processes
+//! - `background`: Manage background processes, optionally under resource limits
+//!
+//! `exec` and `shell_session` run in a configurable shell (bash, zsh, fish,
+//! PowerShell or cmd) with named environment profiles.
 
 mod background;
 mod background_tool;
+mod container;
 mod exec;
 mod extension;
 mod session;
 mod session_tool;
+mod shell;
+mod stream;
 
-pub use background::BackgroundManager;
+pub use background::{BackgroundManager, ProcessReport, ProcessStatus, ResourceLimits};
 pub use background_tool::BackgroundTool;
 pub use exec::ExecTool;
 pub use extension::ShellExtension;
 pub use session::SessionManager;
 pub use session_tool::SessionTool;
+pub use shell::{EnvProfile, Shell, ShellConfig, ShellInvocation};
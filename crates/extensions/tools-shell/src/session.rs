@@ -34,7 +34,12 @@ pub struct ShellSession {
 impl ShellSession {
     /// Create a new shell session.
     pub fn spawn(shell: &str) -> Result<Self, SessionError> {
-        let mut child = Command::new(shell)
+        Self::spawn_command(Command::new(shell))
+    }
+
+    /// Create a session from a prepared shell command.
+    pub fn spawn_command(mut cmd: Command) -> Result<Self, SessionError> {
+        let mut child = cmd
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -153,9 +158,16 @@ impl SessionManager {
             "bash"
         });
 
-        let session = ShellSession::spawn(shell)?;
-        let id = session.id().to_string();
+        self.insert(ShellSession::spawn(shell)?).await
+    }
+
+    /// Create a session from a prepared shell command.
+    pub async fn create_session_with(&self, cmd: Command) -> Result<String, SessionError> {
+        self.insert(ShellSession::spawn_command(cmd)?).await
+    }
 
+    async fn insert(&self, session: ShellSession) -> Result<String, SessionError> {
+        let id = session.id().to_string();
         self.sessions.lock().await.insert(id.clone(), session);
         Ok(id)
     }
@@ -190,6 +202,17 @@ impl SessionManager {
         Ok(())
     }
 
+    /// Kill every session, returning how many were still alive.
+    pub async fn kill_all(&self) -> usize {
+        let mut sessions = self.sessions.lock().await;
+        let alive = sessions.values_mut().filter(|s| s.is_alive()).count();
+        for session in sessions.values_mut() {
+            let _ = session.kill();
+        }
+        sessions.clear();
+        alive
+    }
+
     /// Clean up dead sessions.
     pub async fn cleanup(&self) {
         let mut sessions = self.sessions.lock().await;
@@ -10,6 +10,7 @@ use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
 use autohands_protocols::types::RiskLevel;
 
 use crate::session::SessionManager;
+use crate::shell::{Shell, ShellConfig};
 
 /// Parameters for session tool.
 #[derive(Debug, Deserialize)]
@@ -25,6 +26,15 @@ struct SessionParams {
     /// Timeout in milliseconds (for execute action)
     #[serde(default = "default_timeout")]
     timeout: u64,
+    /// Shell to start (for create)
+    #[serde(default)]
+    shell: Option<Shell>,
+    /// Environment profile (for create)
+    #[serde(default)]
+    profile: Option<String>,
+    /// Start a login shell (for create)
+    #[serde(default)]
+    login: Option<bool>,
 }
 
 fn default_timeout() -> u64 {
@@ -35,6 +45,7 @@ fn default_timeout() -> u64 {
 pub struct SessionTool {
     definition: ToolDefinition,
     manager: Arc<SessionManager>,
+    config: Arc<ShellConfig>,
 }
 
 impl SessionTool {
@@ -58,6 +69,19 @@ impl SessionTool {
                 "timeout": {
                     "type": "integer",
                     "description": "Timeout in milliseconds (default: 30000)"
+                },
+                "shell": {
+                    "type": "string",
+                    "enum": ["sh", "bash", "zsh", "fish", "pwsh", "cmd"],
+                    "description": "Shell to start (for create; default: configured, else bash or cmd on Windows)"
+                },
+                "profile": {
+                    "type": "string",
+                    "description": "Configured environment profile (for create)"
+                },
+                "login": {
+                    "type": "boolean",
+                    "description": "Start a login shell (for create)"
                 }
             },
             "required": ["action"]
@@ -72,8 +96,15 @@ impl SessionTool {
             .with_parameters_schema(schema)
             .with_risk_level(RiskLevel::High),
             manager,
+            config: Arc::new(ShellConfig::default()),
         }
     }
+
+    /// Use configured shell settings and environment profiles.
+    pub fn with_config(mut self, config: Arc<ShellConfig>) -> Self {
+        self.config = config;
+        self
+    }
 }
 
 #[async_trait]
@@ -85,16 +116,34 @@ impl Tool for SessionTool {
     async fn execute(
         &self,
         params: serde_json::Value,
-        _ctx: ToolContext,
+        ctx: ToolContext,
     ) -> Result<ToolResult, ToolError> {
         let params: SessionParams = serde_json::from_value(params)
             .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
 
         match params.action.as_str() {
             "create" => {
+                // Sessions run on the host, which a sandbox cannot confine,
+                // and keep their directory between commands
+                ctx.check_host_command(None)?;
+                // Sessions default to bash rather than sh
+                let fallback = if cfg!(target_os = "windows") {
+                    Shell::Cmd
+                } else {
+                    Shell::Bash
+                };
+                let invocation = self
+                    .config
+                    .resolve_or(
+                        params.shell,
+                        params.profile.as_deref(),
+                        params.login,
+                        fallback,
+                    )
+                    .map_err(ToolError::InvalidParameters)?;
                 let id = self
                     .manager
-                    .create_session(None)
+                    .create_session_with(invocation.session_command())
                     .await
                     .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
                 Ok(ToolResult::success(format!("Session created: {}", id)))
@@ -106,6 +155,7 @@ impl Tool for SessionTool {
                 let command = params
                     .command
                     .ok_or_else(|| ToolError::InvalidParameters("command required".into()))?;
+                ctx.check_host_command(None)?;
 
                 let output = self
                     .manager
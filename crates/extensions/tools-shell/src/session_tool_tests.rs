@@ -143,3 +143,27 @@ async fn test_invalid_params() {
     let result = tool.execute(params, ctx).await;
     assert!(result.is_err());
 }
+
+#[tokio::test]
+async fn test_sessions_refused_under_sandbox() {
+    let manager = Arc::new(SessionManager::new());
+    let tool = SessionTool::new(manager);
+    let sandbox = autohands_protocols::tool::SandboxPolicy::default();
+    let ctx =
+        ToolContext::new("test", std::env::current_dir().unwrap()).with_sandbox(Arc::new(sandbox));
+
+    let err = tool
+        .execute(serde_json::json!({"action": "create"}), ctx.clone())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ToolError::PermissionDenied(_)));
+
+    // A session keeps its directory, so a `cd /` would outlive any check
+    let params = serde_json::json!({
+        "action": "execute",
+        "session_id": "session_1",
+        "command": "cd /"
+    });
+    let err = tool.execute(params, ctx).await.unwrap_err();
+    assert!(matches!(err, ToolError::PermissionDenied(_)));
+}
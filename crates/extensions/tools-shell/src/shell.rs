@@ -0,0 +1,249 @@
+//! Shell selection and environment profiles.
+//!
+//! Commands run in `sh` (`cmd` on Windows) unless a shell is chosen, per
+//! call or in the extension's configuration. Environment profiles are named
+//! sets of variables, PATH additions and a working directory, so a skill
+//! can run with the same tools on macOS, Linux and Windows deployments:
+//!
+//! ```toml
+//! [extensions.config.tools-shell]
+//! shell = "bash"
+//! login = true                  # source the shell's login profile
+//! default_profile = "node"
+//!
+//! [extensions.config.tools-shell.profiles.node]
+//! env = { NODE_ENV = "test" }
+//! path = ["~/.volta/bin"]
+//! cwd = "~/projects/web"
+//! ```
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// A shell commands can run in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Shell {
+    Sh,
+    Bash,
+    Zsh,
+    Fish,
+    Pwsh,
+    Cmd,
+}
+
+impl Shell {
+    /// `cmd` on Windows, `sh` elsewhere.
+    pub fn platform_default() -> Self {
+        if cfg!(target_os = "windows") {
+            Self::Cmd
+        } else {
+            Self::Sh
+        }
+    }
+
+    /// Executable name.
+    pub fn program(&self) -> &'static str {
+        match self {
+            Self::Sh => "sh",
+            Self::Bash => "bash",
+            Self::Zsh => "zsh",
+            Self::Fish => "fish",
+            Self::Pwsh => "pwsh",
+            Self::Cmd => "cmd",
+        }
+    }
+
+    /// Arguments starting the shell, as a login shell if `login`.
+    ///
+    /// `cmd` has no login profile; PowerShell loads its profile only for
+    /// login shells.
+    pub fn startup_args(&self, login: bool) -> Vec<&'static str> {
+        match self {
+            Self::Sh | Self::Bash | Self::Zsh | Self::Fish if login => vec!["-l"],
+            Self::Pwsh if login => vec!["-NoLogo"],
+            Self::Pwsh => vec!["-NoLogo", "-NoProfile"],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Arguments running a single command.
+    pub fn command_args(&self, command: &str, login: bool) -> Vec<String> {
+        let mut args: Vec<String> = self
+            .startup_args(login)
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        match self {
+            Self::Pwsh => args.extend(["-NonInteractive".to_string(), "-Command".to_string()]),
+            Self::Cmd => args.push("/C".to_string()),
+            _ => args.push("-c".to_string()),
+        }
+        args.push(command.to_string());
+        args
+    }
+}
+
+/// A named set of environment settings.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnvProfile {
+    /// Variables set for the command.
+    pub env: HashMap<String, String>,
+    /// Directories put in front of `PATH`.
+    pub path: Vec<String>,
+    /// Working directory used when a call names none.
+    pub cwd: Option<String>,
+    /// Shell used instead of the configured one.
+    pub shell: Option<Shell>,
+    /// Whether to run a login shell, overriding the configuration.
+    pub login: Option<bool>,
+}
+
+/// Shell settings of the extension (`[extensions.config.tools-shell]`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShellConfig {
+    /// Shell used when a call names none; the platform default otherwise.
+    pub shell: Option<Shell>,
+    /// Run login shells, sourcing the user's shell profile.
+    pub login: bool,
+    /// Profile used when a call names none.
+    pub default_profile: Option<String>,
+    /// Environment profiles by name.
+    pub profiles: HashMap<String, EnvProfile>,
+}
+
+impl ShellConfig {
+    /// Settings of a call naming a shell, profile or login mode.
+    pub fn resolve(
+        &self,
+        shell: Option<Shell>,
+        profile: Option<&str>,
+        login: Option<bool>,
+    ) -> Result<ShellInvocation, String> {
+        self.resolve_or(shell, profile, login, Shell::platform_default())
+    }
+
+    /// Like [`resolve`](Self::resolve), with `fallback` used when neither
+    /// the call, the profile nor the configuration names a shell.
+    pub fn resolve_or(
+        &self,
+        shell: Option<Shell>,
+        profile: Option<&str>,
+        login: Option<bool>,
+        fallback: Shell,
+    ) -> Result<ShellInvocation, String> {
+        let profile = match profile.or(self.default_profile.as_deref()) {
+            Some(name) => Some(self.profiles.get(name).ok_or_else(|| {
+                let mut known: Vec<_> = self.profiles.keys().map(String::as_str).collect();
+                known.sort();
+                format!(
+                    "Unknown environment profile '{}' (known: {})",
+                    name,
+                    known.join(", ")
+                )
+            })?),
+            None => None,
+        };
+        let default = EnvProfile::default();
+        let profile = profile.unwrap_or(&default);
+        Ok(ShellInvocation {
+            shell: shell.or(profile.shell).or(self.shell).unwrap_or(fallback),
+            login: login.or(profile.login).unwrap_or(self.login),
+            env: profile.env.clone(),
+            path: profile
+                .path
+                .iter()
+                .map(String::as_str)
+                .map(expand_home)
+                .collect(),
+            cwd: profile.cwd.as_deref().map(expand_home),
+        })
+    }
+}
+
+/// How one command is run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShellInvocation {
+    pub shell: Shell,
+    pub login: bool,
+    pub env: HashMap<String, String>,
+    /// Directories put in front of `PATH`.
+    pub path: Vec<PathBuf>,
+    /// Working directory from the profile.
+    pub cwd: Option<PathBuf>,
+}
+
+impl ShellInvocation {
+    /// Variables to set, `PATH` included when the profile extends it.
+    pub fn env_vars(&self) -> Vec<(OsString, OsString)> {
+        let mut vars: Vec<(OsString, OsString)> = self
+            .env
+            .iter()
+            .filter(|(name, _)| !name.eq_ignore_ascii_case("PATH") || self.path.is_empty())
+            .map(|(name, value)| (name.into(), value.into()))
+            .collect();
+        if !self.path.is_empty() {
+            let current = self
+                .env
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case("PATH"))
+                .map(|(_, value)| OsString::from(value))
+                .or_else(|| std::env::var_os("PATH"))
+                .unwrap_or_default();
+            let dirs = self
+                .path
+                .iter()
+                .cloned()
+                .chain(std::env::split_paths(&current));
+            if let Ok(path) = std::env::join_paths(dirs) {
+                vars.push(("PATH".into(), path));
+            }
+        }
+        vars
+    }
+
+    /// Command running `command` in the shell.
+    pub fn command(&self, command: &str) -> tokio::process::Command {
+        let mut cmd = tokio::process::Command::new(self.shell.program());
+        cmd.args(self.shell.command_args(command, self.login))
+            .envs(self.env_vars());
+        cmd
+    }
+
+    /// Command starting an interactive session of the shell.
+    pub fn session_command(&self) -> std::process::Command {
+        let mut cmd = std::process::Command::new(self.shell.program());
+        cmd.args(self.shell.startup_args(self.login))
+            .envs(self.env_vars());
+        if let Some(cwd) = &self.cwd {
+            cmd.current_dir(cwd);
+        }
+        cmd
+    }
+}
+
+/// Replace a leading `~` with the home directory.
+fn expand_home(path: &str) -> PathBuf {
+    let home = || {
+        std::env::var_os("HOME")
+            .or_else(|| std::env::var_os("USERPROFILE"))
+            .map(PathBuf::from)
+    };
+    match path.strip_prefix('~') {
+        Some("") => home().unwrap_or_else(|| PathBuf::from(path)),
+        Some(rest) if rest.starts_with('/') || rest.starts_with('\\') => match home() {
+            Some(home) => home.join(&rest[1..]),
+            None => PathBuf::from(path),
+        },
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+#[path = "shell_tests.rs"]
+mod tests;
@@ -0,0 +1,102 @@
+use super::*;
+
+fn config() -> ShellConfig {
+    serde_json::from_value(serde_json::json!({
+        "shell": "bash",
+        "profiles": {
+            "node": {
+                "env": { "NODE_ENV": "test" },
+                "path": ["/opt/node/bin"],
+                "cwd": "/srv/web",
+                "login": true
+            },
+            "ps": { "shell": "pwsh" }
+        }
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_shell_args() {
+    assert_eq!(Shell::Bash.command_args("ls", false), ["-c", "ls"]);
+    assert_eq!(Shell::Zsh.command_args("ls", true), ["-l", "-c", "ls"]);
+    assert_eq!(Shell::Cmd.command_args("dir", true), ["/C", "dir"]);
+    assert_eq!(
+        Shell::Pwsh.command_args("Get-ChildItem", false),
+        [
+            "-NoLogo",
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            "Get-ChildItem"
+        ]
+    );
+    assert_eq!(Shell::Fish.startup_args(true), ["-l"]);
+}
+
+#[test]
+fn test_resolve_precedence() {
+    let config = config();
+
+    let plain = config.resolve(None, None, None).unwrap();
+    assert_eq!(plain.shell, Shell::Bash);
+    assert!(!plain.login);
+    assert!(plain.env.is_empty());
+
+    let node = config.resolve(None, Some("node"), None).unwrap();
+    assert!(node.login);
+    assert_eq!(node.env["NODE_ENV"], "test");
+    assert_eq!(node.cwd, Some(PathBuf::from("/srv/web")));
+
+    // The call wins over the profile, the profile over the configuration
+    let ps = config
+        .resolve(Some(Shell::Zsh), Some("ps"), Some(false))
+        .unwrap();
+    assert_eq!(ps.shell, Shell::Zsh);
+    assert_eq!(
+        config.resolve(None, Some("ps"), None).unwrap().shell,
+        Shell::Pwsh
+    );
+
+    let err = config.resolve(None, Some("python"), None).unwrap_err();
+    assert_eq!(
+        err,
+        "Unknown environment profile 'python' (known: node, ps)"
+    );
+}
+
+#[test]
+fn test_default_profile_and_fallback() {
+    let config = ShellConfig {
+        default_profile: Some("node".to_string()),
+        shell: None,
+        ..config()
+    };
+    let invocation = config.resolve_or(None, None, None, Shell::Fish).unwrap();
+    assert_eq!(invocation.shell, Shell::Fish);
+    assert_eq!(invocation.env["NODE_ENV"], "test");
+}
+
+#[test]
+fn test_env_vars_extend_path() {
+    let invocation = ShellInvocation {
+        shell: Shell::Sh,
+        login: false,
+        env: HashMap::from([
+            ("PATH".to_string(), "/usr/bin".to_string()),
+            ("CI".to_string(), "1".to_string()),
+        ]),
+        path: vec![PathBuf::from("/opt/tools")],
+        cwd: None,
+    };
+    let mut vars = invocation.env_vars();
+    vars.sort();
+    let expected = std::env::join_paths(["/opt/tools", "/usr/bin"]).unwrap();
+    assert_eq!(
+        vars,
+        [
+            (OsString::from("CI"), OsString::from("1")),
+            (OsString::from("PATH"), expected),
+        ]
+    );
+}
@@ -0,0 +1,150 @@
+//! Streaming a command's output while it runs.
+//!
+//! With `stream: true`, `exec` forwards stdout and stderr chunks to the
+//! caller as they are written (see [`ToolContext::emit_output`]), so a long
+//! build or test run can be watched live. Its result then keeps only the
+//! last bytes of the output, stdout and stderr interleaved as they arrived.
+
+use std::process::{ExitStatus, Stdio};
+use std::time::Duration;
+
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::process::Command;
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+
+use autohands_protocols::tool::{OutputStream, ToolContext};
+
+/// Bytes of output kept in the result by default.
+pub(crate) const DEFAULT_TAIL_BYTES: usize = 16 * 1024;
+
+/// Size of a single read from a pipe.
+const CHUNK_BYTES: usize = 8 * 1024;
+
+/// Last bytes of a command's output.
+pub(crate) struct OutputTail {
+    output: String,
+    limit: usize,
+    omitted: usize,
+}
+
+impl OutputTail {
+    pub(crate) fn new(limit: usize) -> Self {
+        Self {
+            output: String::new(),
+            limit,
+            omitted: 0,
+        }
+    }
+
+    /// Append output, dropping the oldest beyond the limit.
+    pub(crate) fn push(&mut self, text: &str) {
+        self.output.push_str(text);
+        if self.output.len() > self.limit {
+            let mut start = self.output.len() - self.limit;
+            while !self.output.is_char_boundary(start) {
+                start += 1;
+            }
+            self.output.drain(..start);
+            self.omitted += start;
+        }
+    }
+
+    /// The kept output, noting how much was dropped.
+    pub(crate) fn into_string(self) -> String {
+        if self.omitted == 0 {
+            self.output
+        } else {
+            format!("[... {} bytes omitted ...]\n{}", self.omitted, self.output)
+        }
+    }
+}
+
+/// Exit status and output tail of a streamed command.
+pub(crate) struct StreamedOutput {
+    pub status: ExitStatus,
+    pub output: String,
+}
+
+/// Run a command, emitting its output to the context as it is written.
+///
+/// Returns `None` if the command did not finish within `duration`; it is
+/// killed then.
+pub(crate) async fn run_streaming(
+    mut cmd: Command,
+    ctx: &ToolContext,
+    duration: Duration,
+    tail_bytes: usize,
+) -> std::io::Result<Option<StreamedOutput>> {
+    cmd.stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let mut child = cmd.spawn()?;
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    if let Some(stdout) = child.stdout.take() {
+        tokio::spawn(forward(stdout, OutputStream::Stdout, tx.clone()));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        tokio::spawn(forward(stderr, OutputStream::Stderr, tx));
+    }
+
+    let run = async move {
+        let mut tail = OutputTail::new(tail_bytes);
+        while let Some((stream, text)) = rx.recv().await {
+            tail.push(&text);
+            ctx.emit_output(stream, text);
+        }
+        let status = child.wait().await?;
+        Ok::<_, std::io::Error>(StreamedOutput {
+            status,
+            output: tail.into_string(),
+        })
+    };
+    match timeout(duration, run).await {
+        Ok(result) => result.map(Some),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Send a pipe's output as text until it closes.
+async fn forward(
+    mut pipe: impl AsyncRead + Unpin,
+    stream: OutputStream,
+    tx: mpsc::UnboundedSender<(OutputStream, String)>,
+) {
+    let mut buf = vec![0; CHUNK_BYTES];
+    // Bytes of a character split across reads
+    let mut pending = Vec::new();
+    loop {
+        let n = match pipe.read(&mut buf).await {
+            Ok(0) | Err(_) => break,
+            Ok(n) => n,
+        };
+        pending.extend_from_slice(&buf[..n]);
+        let text = decode(&mut pending);
+        if !text.is_empty() && tx.send((stream, text)).is_err() {
+            return;
+        }
+    }
+    if !pending.is_empty() {
+        let _ = tx.send((stream, String::from_utf8_lossy(&pending).into_owned()));
+    }
+}
+
+/// Decode the bytes read so far, keeping an incomplete trailing character.
+pub(crate) fn decode(pending: &mut Vec<u8>) -> String {
+    let complete = match std::str::from_utf8(pending) {
+        Ok(_) => pending.len(),
+        Err(e) if e.error_len().is_none() => e.valid_up_to(),
+        Err(_) => pending.len(),
+    };
+    let rest = pending.split_off(complete);
+    let text = String::from_utf8_lossy(pending).into_owned();
+    *pending = rest;
+    text
+}
+
+#[cfg(test)]
+#[path = "stream_tests.rs"]
+mod tests;
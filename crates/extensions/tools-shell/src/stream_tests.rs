@@ -0,0 +1,28 @@
+use super::*;
+
+#[test]
+fn test_output_tail_keeps_last_bytes() {
+    let mut tail = OutputTail::new(8);
+    tail.push("line 1\n");
+    assert_eq!(tail.omitted, 0);
+    tail.push("line 2\n");
+    assert_eq!(tail.into_string(), "[... 6 bytes omitted ...]\n\nline 2\n");
+
+    // Never cut inside a character
+    let mut tail = OutputTail::new(3);
+    tail.push("ééé");
+    assert_eq!(tail.output, "é");
+    assert_eq!(tail.omitted, 4);
+}
+
+#[test]
+fn test_decode_keeps_split_character() {
+    let bytes = "hé".as_bytes();
+    let mut pending = bytes[..2].to_vec();
+    assert_eq!(decode(&mut pending), "h");
+    assert_eq!(pending, vec![bytes[1]]);
+
+    pending.push(bytes[2]);
+    assert_eq!(decode(&mut pending), "é");
+    assert!(pending.is_empty());
+}
@@ -10,6 +10,7 @@ use autohands_protocols::error::ExtensionError;
 use autohands_protocols::extension::{Extension, ExtensionContext, ExtensionManifest, Provides};
 use autohands_protocols::skill::SkillLoader;
 use autohands_protocols::types::Version;
+use autohands_skills_dynamic::SkillRegistry;
 
 use crate::{SkillListTool, SkillLoadTool, SkillReadTool};
 
@@ -19,9 +20,13 @@ use crate::{SkillListTool, SkillLoadTool, SkillReadTool};
 /// - `skill_list`: List available skills
 /// - `skill_load`: Load a skill's expert guidance
 /// - `skill_read`: Read files from within a skill directory
+///
+/// With a registry, `skill_list` and `skill_load` honour the skills
+/// disabled in it.
 pub struct SkillToolsExtension {
     manifest: ExtensionManifest,
     loader: Arc<RwLock<dyn SkillLoader>>,
+    registry: Option<Arc<SkillRegistry>>,
 }
 
 impl SkillToolsExtension {
@@ -41,7 +46,13 @@ impl SkillToolsExtension {
             ..Default::default()
         };
 
-        Self { manifest, loader }
+        Self { manifest, loader, registry: None }
+    }
+
+    /// Honour the skills disabled in `registry`.
+    pub fn with_registry(mut self, registry: Arc<SkillRegistry>) -> Self {
+        self.registry = Some(registry);
+        self
     }
 }
 
@@ -52,10 +63,14 @@ impl Extension for SkillToolsExtension {
     }
 
     async fn initialize(&mut self, ctx: ExtensionContext) -> Result<(), ExtensionError> {
-        ctx.tool_registry
-            .register_tool(Arc::new(SkillListTool::new(self.loader.clone())))?;
-        ctx.tool_registry
-            .register_tool(Arc::new(SkillLoadTool::new(self.loader.clone())))?;
+        let mut list = SkillListTool::new(self.loader.clone());
+        let mut load = SkillLoadTool::new(self.loader.clone());
+        if let Some(registry) = &self.registry {
+            list = list.with_registry(registry.clone());
+            load = load.with_registry(registry.clone());
+        }
+        ctx.tool_registry.register_tool(Arc::new(list))?;
+        ctx.tool_registry.register_tool(Arc::new(load))?;
         ctx.tool_registry
             .register_tool(Arc::new(SkillReadTool::new(self.loader.clone())))?;
 
@@ -10,6 +10,7 @@ use autohands_protocols::error::ToolError;
 use autohands_protocols::skill::SkillLoader;
 use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
 use autohands_protocols::types::RiskLevel;
+use autohands_skills_dynamic::SkillRegistry;
 
 #[derive(Debug, Deserialize)]
 struct SkillListParams {
@@ -28,6 +29,7 @@ struct SkillListParams {
 pub struct SkillListTool {
     definition: ToolDefinition,
     loader: Arc<RwLock<dyn SkillLoader>>,
+    registry: Option<Arc<SkillRegistry>>,
 }
 
 impl SkillListTool {
@@ -53,10 +55,18 @@ impl SkillListTool {
                 "List available skills that can enhance your capabilities. Use this to discover what expert knowledge is available for the current task.",
             )
             .with_parameters_schema(schema)
-            .with_risk_level(RiskLevel::Low),
+            .with_risk_level(RiskLevel::Low)
+            .with_read_only(),
             loader,
+            registry: None,
         }
     }
+
+    /// Leave out the skills disabled in `registry`.
+    pub fn with_registry(mut self, registry: Arc<SkillRegistry>) -> Self {
+        self.registry = Some(registry);
+        self
+    }
 }
 
 #[async_trait]
@@ -79,8 +89,19 @@ impl Tool for SkillListTool {
             .await
             .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
 
+        // Leave out disabled skills
+        let mut enabled = Vec::with_capacity(skills.len());
+        for skill in skills {
+            if let Some(registry) = &self.registry {
+                if !registry.is_enabled(&skill.id).await {
+                    continue;
+                }
+            }
+            enabled.push(skill);
+        }
+
         // Filter skills
-        let filtered: Vec<_> = skills
+        let filtered: Vec<_> = enabled
             .into_iter()
             .filter(|s| {
                 // Filter by tag
@@ -85,3 +85,19 @@ async fn test_skill_list_filter_by_category() {
     assert!(result.content.contains("Code Review Expert"));
     assert!(!result.content.contains("Security Audit"));
 }
+
+#[tokio::test]
+async fn test_skill_list_leaves_out_disabled() {
+    let loader: Arc<RwLock<dyn SkillLoader>> = Arc::new(RwLock::new(MockLoader::new()));
+    let registry = Arc::new(SkillRegistry::new());
+    for skill in loader.read().await.skills.clone() {
+        registry.register(skill).await;
+    }
+    registry.set_enabled("security-audit", false).await;
+    let tool = SkillListTool::new(loader).with_registry(registry);
+    let ctx = ToolContext::new("test", PathBuf::from("."));
+
+    let result = tool.execute(serde_json::json!({}), ctx).await.unwrap();
+    assert!(result.content.contains("Code Review Expert"));
+    assert!(!result.content.contains("Security Audit"));
+}
@@ -10,6 +10,7 @@ use autohands_protocols::error::ToolError;
 use autohands_protocols::skill::SkillLoader;
 use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
 use autohands_protocols::types::RiskLevel;
+use autohands_skills_dynamic::SkillRegistry;
 
 #[derive(Debug, Deserialize)]
 struct SkillLoadParams {
@@ -26,6 +27,7 @@ struct SkillLoadParams {
 pub struct SkillLoadTool {
     definition: ToolDefinition,
     loader: Arc<RwLock<dyn SkillLoader>>,
+    registry: Option<Arc<SkillRegistry>>,
 }
 
 impl SkillLoadTool {
@@ -48,10 +50,18 @@ impl SkillLoadTool {
                 "Load a skill's expert guidance to enhance your capabilities for the current task. The loaded content contains expert instructions, workflows, and best practices that you should follow.",
             )
             .with_parameters_schema(schema)
-            .with_risk_level(RiskLevel::Low),
+            .with_risk_level(RiskLevel::Low)
+            .with_read_only(),
             loader,
+            registry: None,
         }
     }
+
+    /// Refuse the skills disabled in `registry`.
+    pub fn with_registry(mut self, registry: Arc<SkillRegistry>) -> Self {
+        self.registry = Some(registry);
+        self
+    }
 }
 
 #[async_trait]
@@ -68,6 +78,15 @@ impl Tool for SkillLoadTool {
         let params: SkillLoadParams = serde_json::from_value(params)
             .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
 
+        if let Some(registry) = &self.registry {
+            if !registry.is_enabled(&params.skill_id).await {
+                return Err(ToolError::ExecutionFailed(format!(
+                    "Skill '{}' is disabled",
+                    params.skill_id
+                )));
+            }
+        }
+
         let loader = self.loader.read().await;
         let skill = loader
             .load(&params.skill_id)
@@ -90,6 +90,23 @@ You are an expert code reviewer. Follow these steps:
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_skill_load_disabled_skill() {
+        let loader: Arc<RwLock<dyn SkillLoader>> = Arc::new(RwLock::new(MockLoader::new()));
+        let registry = Arc::new(SkillRegistry::new());
+        registry.register(loader.read().await.load("code-review").await.unwrap()).await;
+        registry.set_enabled("code-review", false).await;
+        let tool = SkillLoadTool::new(loader).with_registry(registry);
+        let ctx = ToolContext::new("test", PathBuf::from("."));
+
+        let err = tool
+            .execute(serde_json::json!({"skill_id": "code-review"}), ctx)
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("disabled"));
+    }
+
     #[tokio::test]
     async fn test_skill_load_missing_param() {
         let loader: Arc<RwLock<dyn SkillLoader>> = Arc::new(RwLock::new(MockLoader::new()));
@@ -54,7 +54,8 @@ impl SkillReadTool {
                 "Read a file from within a skill's directory. Use this to access templates, examples, or reference documentation that comes with a skill.",
             )
             .with_parameters_schema(schema)
-            .with_risk_level(RiskLevel::Low),
+            .with_risk_level(RiskLevel::Low)
+            .with_read_only(),
             loader,
         }
     }
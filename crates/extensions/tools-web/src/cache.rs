@@ -0,0 +1,92 @@
+//! On-disk cache of fetched pages, revalidated with ETags.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// A page as last fetched.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedPage {
+    pub url: String,
+    pub status: u16,
+    /// Response headers, names lowercase.
+    pub headers: HashMap<String, String>,
+    pub body: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    /// Unix time of the fetch, in seconds.
+    pub fetched_at: u64,
+}
+
+impl CachedPage {
+    /// A page fetched now, taking its validators from `headers`.
+    pub fn new(url: &str, status: u16, headers: HashMap<String, String>, body: String) -> Self {
+        let fetched_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Self {
+            url: url.to_string(),
+            status,
+            etag: headers.get("etag").cloned(),
+            last_modified: headers.get("last-modified").cloned(),
+            headers,
+            body,
+            fetched_at,
+        }
+    }
+
+    /// Whether the page is worth keeping: a full response the server can
+    /// revalidate and did not mark `no-store`.
+    pub fn is_storable(&self) -> bool {
+        let no_store = self
+            .headers
+            .get("cache-control")
+            .is_some_and(|value| value.to_ascii_lowercase().contains("no-store"));
+        self.status == 200 && (self.etag.is_some() || self.last_modified.is_some()) && !no_store
+    }
+}
+
+/// Pages cached in a directory, one JSON file per URL.
+#[derive(Debug, Clone)]
+pub struct PageCache {
+    dir: PathBuf,
+}
+
+impl PageCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, url: &str) -> PathBuf {
+        self.dir
+            .join(format!("{:x}.json", Sha256::digest(url.as_bytes())))
+    }
+
+    /// The cached copy of `url`; unreadable entries count as missing.
+    pub async fn get(&self, url: &str) -> Option<CachedPage> {
+        let data = tokio::fs::read(self.path(url)).await.ok()?;
+        let page: CachedPage = serde_json::from_slice(&data).ok()?;
+        (page.url == url).then_some(page)
+    }
+
+    /// Store `page`, replacing any older copy.
+    pub async fn put(&self, page: &CachedPage) -> std::io::Result<()> {
+        tokio::fs::create_dir_all(&self.dir).await?;
+        let path = self.path(&page.url);
+        // Write aside and rename, so readers never see half a file
+        let partial = path.with_extension(format!("{}.tmp", std::process::id()));
+        tokio::fs::write(&partial, serde_json::to_vec(page)?).await?;
+        tokio::fs::rename(&partial, &path).await
+    }
+}
+
+#[cfg(test)]
+#[path = "cache_tests.rs"]
+mod tests;
@@ -0,0 +1,63 @@
+use super::*;
+
+fn page(url: &str, headers: &[(&str, &str)]) -> CachedPage {
+    let headers = headers
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    CachedPage::new(url, 200, headers, "<p>Hello</p>".to_string())
+}
+
+#[test]
+fn test_validators_from_headers() {
+    let page = page(
+        "https://example.com/",
+        &[
+            ("etag", "\"v1\""),
+            ("last-modified", "Tue, 01 Sep 2026 10:00:00 GMT"),
+        ],
+    );
+    assert_eq!(page.etag.as_deref(), Some("\"v1\""));
+    assert!(page.last_modified.is_some());
+    assert!(page.fetched_at > 0);
+}
+
+#[test]
+fn test_is_storable() {
+    assert!(page("https://example.com/", &[("etag", "\"v1\"")]).is_storable());
+    assert!(!page("https://example.com/", &[]).is_storable());
+    assert!(!page(
+        "https://example.com/",
+        &[("etag", "\"v1\""), ("cache-control", "private, No-Store")]
+    )
+    .is_storable());
+
+    let mut missing = page("https://example.com/", &[("etag", "\"v1\"")]);
+    missing.status = 404;
+    assert!(!missing.is_storable());
+}
+
+#[tokio::test]
+async fn test_put_and_get() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = PageCache::new(dir.path().join("web"));
+    let url = "https://example.com/a?b=1";
+    assert!(cache.get(url).await.is_none());
+
+    let stored = page(url, &[("etag", "\"v1\"")]);
+    cache.put(&stored).await.unwrap();
+    assert_eq!(cache.get(url).await, Some(stored));
+    assert!(cache.get("https://example.com/a").await.is_none());
+
+    let files: Vec<_> = std::fs::read_dir(cache.dir()).unwrap().collect();
+    assert_eq!(files.len(), 1);
+}
+
+#[tokio::test]
+async fn test_corrupt_entry_is_missing() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = PageCache::new(dir.path());
+    let url = "https://example.com/";
+    std::fs::write(cache.path(url), "not json").unwrap();
+    assert!(cache.get(url).await.is_none());
+}
@@ -0,0 +1,43 @@
+//! Web tools configuration (`[extensions.config.tools-web]`).
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cache::PageCache;
+use crate::search::SearchConfig;
+
+/// Settings of the web tools extension.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebConfig {
+    /// Page cache of `web_fetch`.
+    pub fetch: FetchConfig,
+    /// Backend of `web_search`.
+    pub search: SearchConfig,
+}
+
+/// Settings of `web_fetch`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FetchConfig {
+    /// Keep fetched pages on disk and revalidate them with ETags.
+    pub cache: bool,
+    /// Where cached pages go; `autohands/web` in the user cache directory
+    /// by default.
+    pub cache_dir: Option<PathBuf>,
+}
+
+impl FetchConfig {
+    /// The page cache, when enabled and a directory is known.
+    pub fn page_cache(&self) -> Option<PageCache> {
+        if !self.cache {
+            return None;
+        }
+        let dir = self
+            .cache_dir
+            .clone()
+            .or_else(|| dirs::cache_dir().map(|dir| dir.join("autohands").join("web")))?;
+        Some(PageCache::new(dir))
+    }
+}
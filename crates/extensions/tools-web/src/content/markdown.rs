@@ -0,0 +1,383 @@
+//! HTML to Markdown conversion.
+
+use scraper::{ElementRef, Html, Node};
+use url::Url;
+
+use super::readability::is_unlikely;
+
+/// Elements never rendered.
+const SKIPPED_TAGS: &[&str] = &[
+    "script", "style", "noscript", "template", "svg", "canvas", "iframe", "object", "embed",
+    "head", "select", "option", "button", "input", "textarea",
+];
+
+/// Elements dropped as boilerplate when extracting an article.
+const BOILERPLATE_TAGS: &[&str] = &["nav", "aside", "footer", "form", "dialog", "menu"];
+
+/// Elements set apart from their surroundings by blank lines.
+const BLOCK_TAGS: &[&str] = &[
+    "p",
+    "div",
+    "section",
+    "article",
+    "main",
+    "header",
+    "footer",
+    "nav",
+    "aside",
+    "figure",
+    "figcaption",
+    "details",
+    "summary",
+    "dl",
+    "dt",
+    "dd",
+    "address",
+    "form",
+    "fieldset",
+    "body",
+];
+
+/// Convert a whole page's body to Markdown, keeping navigation and other
+/// page furniture.
+pub fn html_to_markdown(html: &str, base: Option<&Url>) -> String {
+    let document = Html::parse_document(html);
+    Converter::new(base, false).convert([body(&document)])
+}
+
+/// The `<body>` of a document, or its root element.
+pub(super) fn body(document: &Html) -> ElementRef<'_> {
+    let root = document.root_element();
+    root.descendants()
+        .filter_map(ElementRef::wrap)
+        .find(|element| element.value().name() == "body")
+        .unwrap_or(root)
+}
+
+/// Whether `element` is left out of the Markdown.
+pub(super) fn skipped(element: ElementRef<'_>, strip_boilerplate: bool) -> bool {
+    let value = element.value();
+    if SKIPPED_TAGS.contains(&value.name())
+        || value.attr("hidden").is_some()
+        || value.attr("aria-hidden") == Some("true")
+    {
+        return true;
+    }
+    strip_boilerplate && (BOILERPLATE_TAGS.contains(&value.name()) || is_unlikely(element))
+}
+
+/// Renders elements as Markdown.
+pub(super) struct Converter<'u> {
+    /// URL relative links and images are resolved against.
+    base: Option<&'u Url>,
+    /// Also drop elements that look like boilerplate.
+    strip_boilerplate: bool,
+}
+
+impl<'u> Converter<'u> {
+    pub(super) fn new(base: Option<&'u Url>, strip_boilerplate: bool) -> Self {
+        Self {
+            base,
+            strip_boilerplate,
+        }
+    }
+
+    pub(super) fn convert<'a>(&self, elements: impl IntoIterator<Item = ElementRef<'a>>) -> String {
+        let mut out = String::new();
+        for element in elements {
+            self.element(element, &mut out);
+            block_break(&mut out);
+        }
+        tidy(&out)
+    }
+
+    fn children(&self, element: ElementRef<'_>, out: &mut String) {
+        for child in element.children() {
+            match child.value() {
+                Node::Text(text) => push_text(out, text),
+                Node::Element(_) => {
+                    if let Some(child) = ElementRef::wrap(child) {
+                        self.element(child, out);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Markdown of an element's children on their own.
+    fn inner(&self, element: ElementRef<'_>) -> String {
+        let mut out = String::new();
+        self.children(element, &mut out);
+        out
+    }
+
+    fn element(&self, element: ElementRef<'_>, out: &mut String) {
+        if skipped(element, self.strip_boilerplate) {
+            return;
+        }
+        let name = element.value().name();
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let text = collapse(&self.inner(element));
+                if !text.is_empty() {
+                    block_break(out);
+                    let level = name[1..].parse().unwrap_or(1);
+                    out.push_str(&format!("{} {}", "#".repeat(level), text));
+                    block_break(out);
+                }
+            }
+            "br" => out.push('\n'),
+            "hr" => {
+                block_break(out);
+                out.push_str("---");
+                block_break(out);
+            }
+            "pre" => {
+                let code: String = element.text().collect();
+                block_break(out);
+                out.push_str(&format!("```\n{}\n```", code.trim_matches('\n')));
+                block_break(out);
+            }
+            "code" | "kbd" | "samp" => {
+                let code = collapse(&element.text().collect::<String>());
+                if !code.is_empty() {
+                    let fence = if code.contains('`') { "``" } else { "`" };
+                    push_inline(out, &format!("{}{}{}", fence, code, fence), "");
+                }
+            }
+            "strong" | "b" => self.wrap(element, "**", out),
+            "em" | "i" => self.wrap(element, "*", out),
+            "del" | "s" => self.wrap(element, "~~", out),
+            "a" => self.link(element, out),
+            "img" => self.image(element, out),
+            "ul" | "ol" => self.list(element, name == "ol", out),
+            "blockquote" => {
+                let quote = tidy(&self.inner(element));
+                if !quote.is_empty() {
+                    block_break(out);
+                    out.push_str(&prefix_lines(&quote, "> "));
+                    block_break(out);
+                }
+            }
+            "table" => self.table(element, out),
+            _ if BLOCK_TAGS.contains(&name) => {
+                block_break(out);
+                self.children(element, out);
+                block_break(out);
+            }
+            _ => self.children(element, out),
+        }
+    }
+
+    /// Inline content between `marker`s, as in `**bold**`.
+    fn wrap(&self, element: ElementRef<'_>, marker: &str, out: &mut String) {
+        let inner = self.inner(element);
+        let text = collapse(&inner);
+        if !text.is_empty() {
+            push_inline(out, &format!("{}{}{}", marker, text, marker), &inner);
+        }
+    }
+
+    fn link(&self, element: ElementRef<'_>, out: &mut String) {
+        let inner = self.inner(element);
+        let text = collapse(&inner);
+        let href = element
+            .value()
+            .attr("href")
+            .and_then(|href| self.resolve(href));
+        match href {
+            Some(href) if !text.is_empty() => {
+                push_inline(out, &format!("[{}]({})", text, href), &inner)
+            }
+            _ => push_text(out, &inner),
+        }
+    }
+
+    fn image(&self, element: ElementRef<'_>, out: &mut String) {
+        let value = element.value();
+        let src = value
+            .attr("src")
+            .or_else(|| value.attr("data-src"))
+            .filter(|src| !src.starts_with("data:"))
+            .and_then(|src| self.resolve(src));
+        if let Some(src) = src {
+            let alt = collapse(value.attr("alt").unwrap_or_default());
+            push_inline(out, &format!("![{}]({})", alt, src), "");
+        }
+    }
+
+    fn list(&self, element: ElementRef<'_>, ordered: bool, out: &mut String) {
+        // Only a line break before, so nested lists stay tight
+        let end = out.trim_end_matches(' ').len();
+        out.truncate(end);
+        if !out.is_empty() && !out.ends_with('\n') {
+            out.push('\n');
+        }
+        let items = element
+            .children()
+            .filter_map(ElementRef::wrap)
+            .filter(|child| child.value().name() == "li");
+        let mut number = 1;
+        for item in items {
+            let text = tidy(&self.inner(item));
+            if text.is_empty() {
+                continue;
+            }
+            let marker = if ordered {
+                format!("{}. ", number)
+            } else {
+                "- ".to_string()
+            };
+            number += 1;
+            let indent = " ".repeat(marker.len());
+            let mut lines = text.lines();
+            out.push_str(&marker);
+            out.push_str(lines.next().unwrap_or_default());
+            for line in lines {
+                out.push('\n');
+                if !line.is_empty() {
+                    out.push_str(&indent);
+                    out.push_str(line);
+                }
+            }
+            out.push('\n');
+        }
+        block_break(out);
+    }
+
+    fn table(&self, table: ElementRef<'_>, out: &mut String) {
+        // Rows of this table, not of tables nested in its cells
+        let rows: Vec<Vec<String>> = table
+            .descendants()
+            .filter_map(ElementRef::wrap)
+            .filter(|row| row.value().name() == "tr")
+            .filter(|row| {
+                row.ancestors()
+                    .filter_map(ElementRef::wrap)
+                    .find(|ancestor| ancestor.value().name() == "table")
+                    .is_some_and(|owner| owner.id() == table.id())
+            })
+            .map(|row| {
+                row.children()
+                    .filter_map(ElementRef::wrap)
+                    .filter(|cell| matches!(cell.value().name(), "td" | "th"))
+                    .map(|cell| collapse(&self.inner(cell)).replace('|', "\\|"))
+                    .collect::<Vec<_>>()
+            })
+            .filter(|row| row.iter().any(|cell| !cell.is_empty()))
+            .collect();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        block_break(out);
+        if rows.len() < 2 || width < 2 {
+            // A layout table: keep its text
+            for cell in rows.iter().flatten().filter(|cell| !cell.is_empty()) {
+                out.push_str(cell);
+                block_break(out);
+            }
+            return;
+        }
+        for (i, row) in rows.iter().enumerate() {
+            let cells: Vec<&str> = (0..width)
+                .map(|j| row.get(j).map_or("", String::as_str))
+                .collect();
+            out.push_str(&format!("| {} |\n", cells.join(" | ")));
+            if i == 0 {
+                out.push_str(&format!("|{}\n", " --- |".repeat(width)));
+            }
+        }
+        block_break(out);
+    }
+
+    /// An absolute URL for `href`; `None` for script and in-page links.
+    fn resolve(&self, href: &str) -> Option<String> {
+        let href = href.trim();
+        if href.is_empty() || href.starts_with('#') || href.starts_with("javascript:") {
+            return None;
+        }
+        let url = match self.base {
+            Some(base) => base.join(href).ok()?,
+            None => Url::parse(href).ok()?,
+        };
+        Some(url.to_string())
+    }
+}
+
+/// Text with whitespace runs collapsed, keeping a space at either end
+/// where the source had whitespace.
+fn push_text(out: &mut String, text: &str) {
+    let words = collapse(text);
+    let at_line_start = out.is_empty() || out.ends_with([' ', '\n']);
+    if text.starts_with(char::is_whitespace) && !at_line_start {
+        out.push(' ');
+    }
+    out.push_str(&words);
+    if !words.is_empty() && text.ends_with(char::is_whitespace) {
+        out.push(' ');
+    }
+}
+
+/// Markup standing for `source`, keeping the spaces around it.
+fn push_inline(out: &mut String, markup: &str, source: &str) {
+    if source.starts_with(char::is_whitespace) && !out.is_empty() && !out.ends_with([' ', '\n']) {
+        out.push(' ');
+    }
+    out.push_str(markup);
+    if source.ends_with(char::is_whitespace) {
+        out.push(' ');
+    }
+}
+
+/// End the current block with a blank line.
+fn block_break(out: &mut String) {
+    let end = out.trim_end_matches(' ').len();
+    out.truncate(end);
+    if out.is_empty() {
+        return;
+    }
+    while !out.ends_with("\n\n") {
+        out.push('\n');
+    }
+}
+
+fn collapse(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn prefix_lines(text: &str, prefix: &str) -> String {
+    text.lines()
+        .map(|line| {
+            if line.is_empty() {
+                prefix.trim_end().to_string()
+            } else {
+                format!("{}{}", prefix, line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Trailing spaces removed and runs of blank lines merged, outside code
+/// blocks.
+fn tidy(markdown: &str) -> String {
+    let mut out = String::new();
+    let mut in_code = false;
+    let mut blank = false;
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+        }
+        let line = if in_code { line } else { line.trim_end() };
+        if line.is_empty() && !in_code {
+            if blank {
+                continue;
+            }
+            blank = true;
+        } else {
+            blank = false;
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    out.trim_matches('\n').to_string()
+}
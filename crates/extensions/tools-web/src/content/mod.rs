@@ -0,0 +1,66 @@
+//! Page content extraction for `web_fetch`.
+//!
+//! HTML is converted to Markdown, either whole ([`html_to_markdown`]) or
+//! reduced to its main content first ([`extract_article`]), a port of the
+//! core of Mozilla's Readability: paragraphs score their ancestors, the
+//! best-scoring element and its related siblings are kept, and navigation,
+//! sidebars, comments and ads are dropped.
+
+mod markdown;
+mod readability;
+
+pub use markdown::html_to_markdown;
+pub use readability::{extract_article, Article};
+
+use scraper::ElementRef;
+
+/// Characters per token assumed when budgeting text.
+pub const CHARS_PER_TOKEN: usize = 4;
+
+/// Tokens of `text`, estimated.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// `text` cut to about `max_tokens` tokens, at a paragraph or line break
+/// when one is near; `None` when it already fits.
+pub fn truncate_to_tokens(text: &str, max_tokens: usize) -> Option<String> {
+    let max_chars = max_tokens.saturating_mul(CHARS_PER_TOKEN);
+    let end = text.char_indices().nth(max_chars).map(|(i, _)| i)?;
+    let head = &text[..end];
+    // Prefer a clean break in the last quarter of the budget
+    let floor = head.len() * 3 / 4;
+    let cut = head
+        .rfind("\n\n")
+        .filter(|&i| i >= floor)
+        .or_else(|| head.rfind('\n').filter(|&i| i >= floor))
+        .unwrap_or(end);
+    Some(head[..cut].trim_end().to_string())
+}
+
+/// Text of an element with whitespace collapsed.
+fn inner_text(element: ElementRef<'_>) -> String {
+    let mut text = String::new();
+    for chunk in element.text() {
+        for word in chunk.split_whitespace() {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            text.push_str(word);
+        }
+    }
+    text
+}
+
+/// Whether an HTML page is declared or looks like one.
+pub fn is_html(content_type: Option<&str>, body: &str) -> bool {
+    if let Some(content_type) = content_type {
+        return content_type.contains("html");
+    }
+    let start: String = body.trim_start().chars().take(15).collect();
+    let start = start.to_ascii_lowercase();
+    start.starts_with("<!doctype html") || start.starts_with("<html")
+}
+
+#[cfg(test)]
+mod tests;
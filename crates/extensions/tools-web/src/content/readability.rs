@@ -0,0 +1,285 @@
+//! Main content detection.
+
+use std::collections::HashMap;
+
+use scraper::{ElementRef, Html};
+use url::Url;
+
+use super::inner_text;
+use super::markdown::{body, skipped, Converter};
+
+/// Elements whose text scores their ancestors.
+const SCORED_TAGS: &[&str] = &["p", "pre", "td", "blockquote"];
+
+/// Shortest text that counts as a paragraph, in characters.
+const MIN_PARAGRAPH_CHARS: usize = 25;
+
+/// Class and id fragments of page furniture.
+const UNLIKELY_NAMES: &[&str] = &[
+    "-ad-",
+    "ad-break",
+    "agegate",
+    "banner",
+    "breadcrumb",
+    "combx",
+    "comment",
+    "community",
+    "cookie",
+    "disqus",
+    "extra",
+    "footer",
+    "gdpr",
+    "header",
+    "legends",
+    "menu",
+    "modal",
+    "newsletter",
+    "pager",
+    "pagination",
+    "popup",
+    "related",
+    "remark",
+    "replies",
+    "rss",
+    "share",
+    "shoutbox",
+    "sidebar",
+    "skyscraper",
+    "social",
+    "sponsor",
+    "subscribe",
+    "supplemental",
+];
+
+/// Class and id fragments that keep an unlikely-looking element.
+const MAYBE_NAMES: &[&str] = &[
+    "and", "article", "body", "column", "content", "main", "shadow",
+];
+
+/// Class and id fragments that raise an element's score.
+const POSITIVE_NAMES: &[&str] = &[
+    "article", "blog", "body", "content", "entry", "h-entry", "hentry", "main", "page", "post",
+    "story", "text",
+];
+
+/// Class and id fragments that lower an element's score.
+const NEGATIVE_NAMES: &[&str] = &[
+    "banner",
+    "combx",
+    "comment",
+    "com-",
+    "contact",
+    "foot",
+    "gdpr",
+    "hidden",
+    "masthead",
+    "media",
+    "meta",
+    "outbrain",
+    "promo",
+    "related",
+    "scroll",
+    "share",
+    "shopping",
+    "shoutbox",
+    "sidebar",
+    "skyscraper",
+    "sponsor",
+    "tags",
+    "widget",
+];
+
+/// Roles of landmarks that are never the main content.
+const UNLIKELY_ROLES: &[&str] = &[
+    "alert",
+    "banner",
+    "complementary",
+    "contentinfo",
+    "dialog",
+    "menu",
+    "navigation",
+];
+
+/// The main content of a page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Article {
+    pub title: Option<String>,
+    pub markdown: String,
+}
+
+/// The main content of `html` as Markdown, with links and images resolved
+/// against `base`.
+pub fn extract_article(html: &str, base: Option<&Url>) -> Article {
+    let document = Html::parse_document(html);
+    let content = main_content(body(&document));
+    Article {
+        title: title(&document),
+        markdown: Converter::new(base, true).convert(content),
+    }
+}
+
+/// Whether an element looks like navigation, ads or other furniture by its
+/// role, class or id.
+pub(super) fn is_unlikely(element: ElementRef<'_>) -> bool {
+    let value = element.value();
+    if matches!(value.name(), "html" | "body" | "article" | "main") {
+        return false;
+    }
+    if value
+        .attr("role")
+        .is_some_and(|role| UNLIKELY_ROLES.contains(&role))
+    {
+        return true;
+    }
+    let names = class_and_id(element);
+    contains_any(&names, UNLIKELY_NAMES) && !contains_any(&names, MAYBE_NAMES)
+}
+
+/// The best-scoring element and its siblings that belong with it; `body`
+/// itself when nothing scores.
+fn main_content(body: ElementRef<'_>) -> Vec<ElementRef<'_>> {
+    let mut candidates = HashMap::new();
+    for paragraph in body.descendants().filter_map(ElementRef::wrap) {
+        if !SCORED_TAGS.contains(&paragraph.value().name()) {
+            continue;
+        }
+        let text = inner_text(paragraph);
+        let length = text.chars().count();
+        if length < MIN_PARAGRAPH_CHARS || in_boilerplate(paragraph) {
+            continue;
+        }
+        let score = 1.0 + text.matches(',').count() as f64 + (length / 100).min(3) as f64;
+        let ancestors = paragraph.ancestors().filter_map(ElementRef::wrap).take(3);
+        for (level, ancestor) in ancestors.enumerate() {
+            let divider = match level {
+                0 => 1.0,
+                1 => 2.0,
+                level => level as f64 * 3.0,
+            };
+            candidates
+                .entry(ancestor.id())
+                .or_insert_with(|| (ancestor, initial_score(ancestor)))
+                .1 += score / divider;
+        }
+    }
+
+    // Text that is mostly links is navigation, however long
+    let scores: HashMap<_, _> = candidates
+        .into_iter()
+        .map(|(id, (element, score))| (id, (element, score * (1.0 - link_density(element)))))
+        .collect();
+    let Some(&(top, top_score)) = scores.values().max_by(|a, b| a.1.total_cmp(&b.1)) else {
+        return vec![body];
+    };
+    let Some(parent) = top.parent().and_then(ElementRef::wrap) else {
+        return vec![top];
+    };
+
+    let threshold = (top_score * 0.2).max(10.0);
+    parent
+        .children()
+        .filter_map(ElementRef::wrap)
+        .filter(|sibling| {
+            if sibling.id() == top.id() {
+                return true;
+            }
+            if scores
+                .get(&sibling.id())
+                .is_some_and(|(_, score)| *score >= threshold)
+            {
+                return true;
+            }
+            if sibling.value().name() != "p" {
+                return false;
+            }
+            let text = inner_text(*sibling);
+            let length = text.chars().count();
+            let density = link_density(*sibling);
+            (length > 80 && density < 0.25) || (length > 0 && density == 0.0 && text.ends_with('.'))
+        })
+        .collect()
+}
+
+/// Score an element starts with, from its tag and names.
+fn initial_score(element: ElementRef<'_>) -> f64 {
+    let tag = match element.value().name() {
+        "div" | "article" | "main" => 5.0,
+        "pre" | "td" | "blockquote" => 3.0,
+        "address" | "ol" | "ul" | "dl" | "dd" | "dt" | "li" | "form" => -3.0,
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "th" => -5.0,
+        _ => 0.0,
+    };
+    let names = class_and_id(element);
+    let mut weight = 0.0;
+    if contains_any(&names, POSITIVE_NAMES) {
+        weight += 25.0;
+    }
+    if contains_any(&names, NEGATIVE_NAMES) {
+        weight -= 25.0;
+    }
+    tag + weight
+}
+
+/// Share of an element's text inside links.
+fn link_density(element: ElementRef<'_>) -> f64 {
+    let total = inner_text(element).len();
+    if total == 0 {
+        return 0.0;
+    }
+    let linked: usize = element
+        .descendants()
+        .filter_map(ElementRef::wrap)
+        .filter(|e| e.value().name() == "a")
+        .map(|a| inner_text(a).len())
+        .sum();
+    (linked as f64 / total as f64).min(1.0)
+}
+
+/// Whether an element or one of its ancestors is dropped as boilerplate.
+fn in_boilerplate(element: ElementRef<'_>) -> bool {
+    std::iter::once(element)
+        .chain(element.ancestors().filter_map(ElementRef::wrap))
+        .any(|e| skipped(e, true))
+}
+
+/// Lowercase class and id of an element.
+fn class_and_id(element: ElementRef<'_>) -> String {
+    let value = element.value();
+    format!(
+        "{} {}",
+        value.attr("class").unwrap_or_default(),
+        value.attr("id").unwrap_or_default()
+    )
+    .to_lowercase()
+}
+
+fn contains_any(names: &str, fragments: &[&str]) -> bool {
+    fragments.iter().any(|fragment| names.contains(fragment))
+}
+
+/// Title from Open Graph metadata, `<title>` or the first `<h1>`.
+fn title(document: &Html) -> Option<String> {
+    let elements = || {
+        document
+            .root_element()
+            .descendants()
+            .filter_map(ElementRef::wrap)
+    };
+    let og_title = elements()
+        .find(|e| e.value().name() == "meta" && e.value().attr("property") == Some("og:title"))
+        .and_then(|meta| meta.value().attr("content"))
+        .map(|content| content.split_whitespace().collect::<Vec<_>>().join(" "));
+    og_title
+        .into_iter()
+        .chain(
+            elements()
+                .find(|e| e.value().name() == "title")
+                .map(inner_text),
+        )
+        .chain(
+            elements()
+                .find(|e| e.value().name() == "h1")
+                .map(inner_text),
+        )
+        .find(|title| !title.is_empty())
+}
@@ -0,0 +1,121 @@
+//! Tests for content extraction.
+
+use url::Url;
+
+use super::*;
+
+fn base() -> Url {
+    Url::parse("https://example.com/guide/").unwrap()
+}
+
+#[test]
+fn test_inline_markup_and_links() {
+    let html = r##"<html><body><h1>Title</h1>
+        <p>Read the <a href="../docs">docs</a>, then <b>build</b> with <code>cargo</code>.</p>
+        <p>Skip <a href="javascript:void(0)">this</a> and <a href="#top">that</a>.
+        <img src="img/logo.png" alt="Logo"></p>
+        <script>alert(1)</script></body></html>"##;
+    assert_eq!(
+        html_to_markdown(html, Some(&base())),
+        "# Title\n\n\
+         Read the [docs](https://example.com/docs), then **build** with `cargo`.\n\n\
+         Skip this and that. ![Logo](https://example.com/guide/img/logo.png)"
+    );
+}
+
+#[test]
+fn test_lists_tables_and_code() {
+    let html = r#"<ul><li>One</li><li>Two<ol><li>Inner</li></ol></li></ul>
+        <table><tr><th>Name</th><th>Qty</th></tr><tr><td>Apple</td><td>3 | 4</td></tr></table>
+        <pre><code>fn main() {
+    run();
+}
+</code></pre>
+        <blockquote><p>Quoted</p><p>twice</p></blockquote>"#;
+    assert_eq!(
+        html_to_markdown(html, None),
+        "- One\n- Two\n  1. Inner\n\n\
+         | Name | Qty |\n| --- | --- |\n| Apple | 3 \\| 4 |\n\n\
+         ```\nfn main() {\n    run();\n}\n```\n\n\
+         > Quoted\n>\n> twice"
+    );
+}
+
+#[test]
+fn test_layout_table_keeps_text() {
+    let html = "<table><tr><td>Only cell</td></tr></table>";
+    assert_eq!(html_to_markdown(html, None), "Only cell");
+}
+
+#[test]
+fn test_extract_article_drops_boilerplate() {
+    let paragraph = "Rust gives you control over memory, without a garbage collector, \
+                     and its compiler catches whole classes of bugs before they ship.";
+    let html = format!(
+        r#"<html><head><title>Why Rust</title></head><body>
+        <nav><a href="/">Home</a> <a href="/blog">Blog</a></nav>
+        <div class="sidebar"><p>Subscribe to our newsletter for weekly updates, tips and more.</p></div>
+        <div class="post-content">
+          <h2>Safety</h2>
+          <p>{paragraph}</p>
+          <p>{paragraph}</p>
+          <p>See the <a href="/book">book</a>.</p>
+        </div>
+        <div id="comments"><p>Great post, thanks for writing it, really helpful stuff!</p></div>
+        <footer>Copyright Example, all rights reserved, since forever.</footer>
+        </body></html>"#
+    );
+    let article = extract_article(&html, Some(&base()));
+    assert_eq!(article.title.as_deref(), Some("Why Rust"));
+    assert!(article
+        .markdown
+        .starts_with("## Safety\n\nRust gives you control"));
+    assert!(article
+        .markdown
+        .contains("[book](https://example.com/book)"));
+    assert!(!article.markdown.contains("Home"));
+    assert!(!article.markdown.contains("newsletter"));
+    assert!(!article.markdown.contains("Great post"));
+    assert!(!article.markdown.contains("Copyright"));
+}
+
+#[test]
+fn test_extract_article_without_paragraphs() {
+    let html = r#"<html><head><meta property="og:title" content="Status"></head>
+        <body><span>All systems go</span></body></html>"#;
+    let article = extract_article(html, None);
+    assert_eq!(article.title.as_deref(), Some("Status"));
+    assert_eq!(article.markdown, "All systems go");
+}
+
+#[test]
+fn test_estimate_tokens() {
+    assert_eq!(estimate_tokens(""), 0);
+    assert_eq!(estimate_tokens("abc"), 1);
+    assert_eq!(estimate_tokens("abcdefgh"), 2);
+}
+
+#[test]
+fn test_truncate_to_tokens() {
+    assert_eq!(truncate_to_tokens("short", 10), None);
+
+    let para = "x".repeat(40);
+    let text = format!("{para}\n\n{para}\n\n{para}");
+    assert_eq!(
+        truncate_to_tokens(&text, 25),
+        Some(format!("{para}\n\n{para}"))
+    );
+
+    // No break near the end: cut at the budget
+    let text = "y".repeat(200);
+    assert_eq!(truncate_to_tokens(&text, 10), Some("y".repeat(40)));
+}
+
+#[test]
+fn test_is_html() {
+    assert!(is_html(Some("text/html; charset=utf-8"), ""));
+    assert!(is_html(Some("application/xhtml+xml"), ""));
+    assert!(!is_html(Some("application/json"), "<html>"));
+    assert!(is_html(None, "  <!DOCTYPE html><html>"));
+    assert!(!is_html(None, "{\"a\": 1}"));
+}
@@ -9,6 +9,7 @@ use autohands_protocols::error::ExtensionError;
 use autohands_protocols::extension::{Extension, ExtensionContext, ExtensionManifest, Provides};
 use autohands_protocols::types::Version;
 
+use crate::config::WebConfig;
 use crate::tools::{WebFetchTool, WebSearchTool};
 
 /// Web tools extension.
@@ -46,11 +47,32 @@ impl Extension for WebToolsExtension {
     }
 
     async fn initialize(&mut self, ctx: ExtensionContext) -> Result<(), ExtensionError> {
+        let config: WebConfig = if ctx.config.is_null() {
+            WebConfig::default()
+        } else {
+            serde_json::from_value(ctx.config.clone()).map_err(|e| {
+                ExtensionError::InitializationFailed(format!("Invalid tools-web config: {}", e))
+            })?
+        };
+        let (provider, fallback) = config
+            .search
+            .build()
+            .map_err(ExtensionError::InitializationFailed)?;
+        tracing::debug!("web_search uses {}", provider.name());
+
+        let mut fetch = WebFetchTool::new();
+        if let Some(cache) = config.fetch.page_cache() {
+            tracing::debug!("web_fetch caches pages in {}", cache.dir().display());
+            fetch = fetch.with_cache(cache);
+        }
+
         // Register tools
-        ctx.tool_registry
-            .register_tool(Arc::new(WebFetchTool::new()))?;
-        ctx.tool_registry
-            .register_tool(Arc::new(WebSearchTool::new()))?;
+        ctx.tool_registry.register_tool(Arc::new(fetch))?;
+        ctx.tool_registry.register_tool(Arc::new(
+            WebSearchTool::new()
+                .with_provider(provider)
+                .with_fallback(fallback),
+        ))?;
 
         Ok(())
     }
@@ -1,9 +1,18 @@
 //! Web tools for AutoHands.
 //!
-//! Provides web_fetch and web_search tools.
+//! Provides web_fetch and web_search tools. web_fetch can reduce pages
+//! to Markdown of their main content and cache them on disk. web_search
+//! queries Brave, SerpAPI, Tavily, a SearXNG instance or DuckDuckGo, as
+//! configured.
 
+mod cache;
+mod config;
+pub mod content;
 mod extension;
+pub mod search;
 mod tools;
 
+pub use cache::{CachedPage, PageCache};
+pub use config::{FetchConfig, WebConfig};
 pub use extension::WebToolsExtension;
 pub use tools::{WebFetchTool, WebSearchTool};
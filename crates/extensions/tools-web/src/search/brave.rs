@@ -0,0 +1,93 @@
+//! Brave Search API.
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+
+use super::{normalize, send_json, SearchError, SearchProvider, SearchResult};
+
+const ENDPOINT: &str = "https://api.search.brave.com/res/v1/web/search";
+
+/// Most results one request returns.
+const MAX_COUNT: usize = 20;
+
+#[derive(Debug, Deserialize)]
+struct Response {
+    #[serde(default)]
+    web: Option<WebResults>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WebResults {
+    #[serde(default)]
+    results: Vec<Hit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Hit {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    age: Option<String>,
+}
+
+/// Search through the Brave Search API (`X-Subscription-Token` key).
+pub struct BraveProvider {
+    client: Client,
+    api_key: String,
+    endpoint: String,
+}
+
+impl BraveProvider {
+    pub fn new(client: Client, api_key: impl Into<String>) -> Self {
+        Self {
+            client,
+            api_key: api_key.into(),
+            endpoint: ENDPOINT.to_string(),
+        }
+    }
+
+    /// Send requests to `endpoint` instead of the public API.
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into();
+        self
+    }
+}
+
+#[async_trait]
+impl SearchProvider for BraveProvider {
+    fn name(&self) -> &'static str {
+        "brave"
+    }
+
+    async fn search(
+        &self,
+        query: &str,
+        max_results: usize,
+    ) -> Result<Vec<SearchResult>, SearchError> {
+        let count = max_results.min(MAX_COUNT).to_string();
+        let request = self
+            .client
+            .get(&self.endpoint)
+            .header("X-Subscription-Token", &self.api_key)
+            .header("Accept", "application/json")
+            .query(&[("q", query), ("count", count.as_str())]);
+        let response: Response = send_json(self.name(), request).await?;
+
+        let hits = response.web.map(|web| web.results).unwrap_or_default();
+        let results = hits
+            .into_iter()
+            .map(|hit| SearchResult {
+                title: hit.title,
+                url: hit.url,
+                snippet: hit.description,
+                published: hit.age,
+            })
+            .collect();
+        Ok(normalize(results, max_results))
+    }
+}
@@ -0,0 +1,180 @@
+//! Search provider selection (`[extensions.config.tools-web.search]`).
+//!
+//! ```toml
+//! [extensions.config.tools-web.search]
+//! provider = "brave"      # brave, serpapi, tavily, searxng or duckduckgo
+//!
+//! [extensions.config.tools-web.search.brave]
+//! api_key = "${BRAVE_API_KEY}"
+//! ```
+//!
+//! Without `provider`, the first configured of Brave, Tavily, SerpAPI and
+//! SearXNG is used, else DuckDuckGo.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    http_client, BraveProvider, DuckDuckGoProvider, SearchProvider, SearxngProvider,
+    SerpApiProvider, TavilyProvider,
+};
+
+/// Seconds a search request may take by default.
+pub const DEFAULT_TIMEOUT_SECS: u64 = 15;
+
+/// A search backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderKind {
+    Brave,
+    SerpApi,
+    Tavily,
+    Searxng,
+    DuckDuckGo,
+}
+
+/// An API key.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct KeyConfig {
+    pub api_key: String,
+}
+
+/// SerpAPI settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SerpApiConfig {
+    pub api_key: String,
+    /// Engine queried through SerpAPI.
+    #[serde(default = "default_serpapi_engine")]
+    pub engine: String,
+}
+
+fn default_serpapi_engine() -> String {
+    "google".to_string()
+}
+
+/// Tavily settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TavilyConfig {
+    pub api_key: String,
+    /// `basic` or `advanced`.
+    #[serde(default = "default_tavily_depth")]
+    pub search_depth: String,
+}
+
+fn default_tavily_depth() -> String {
+    "basic".to_string()
+}
+
+/// SearXNG settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearxngConfig {
+    /// Base URL of the instance.
+    pub url: String,
+}
+
+/// Search settings of the web tools.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchConfig {
+    /// Provider to use; picked from the configured ones when absent.
+    pub provider: Option<ProviderKind>,
+    /// Retry with DuckDuckGo when the provider fails.
+    pub fallback: bool,
+    /// Seconds a search request may take.
+    pub timeout_secs: u64,
+    pub brave: Option<KeyConfig>,
+    pub serpapi: Option<SerpApiConfig>,
+    pub tavily: Option<TavilyConfig>,
+    pub searxng: Option<SearxngConfig>,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self {
+            provider: None,
+            fallback: true,
+            timeout_secs: DEFAULT_TIMEOUT_SECS,
+            brave: None,
+            serpapi: None,
+            tavily: None,
+            searxng: None,
+        }
+    }
+}
+
+impl SearchConfig {
+    /// The provider to use: the named one, or the first configured.
+    pub fn selected(&self) -> ProviderKind {
+        self.provider.unwrap_or(if self.brave.is_some() {
+            ProviderKind::Brave
+        } else if self.tavily.is_some() {
+            ProviderKind::Tavily
+        } else if self.serpapi.is_some() {
+            ProviderKind::SerpApi
+        } else if self.searxng.is_some() {
+            ProviderKind::Searxng
+        } else {
+            ProviderKind::DuckDuckGo
+        })
+    }
+
+    /// The selected provider and, with `fallback`, DuckDuckGo to retry with.
+    pub fn build(
+        &self,
+    ) -> Result<(Arc<dyn SearchProvider>, Option<Arc<dyn SearchProvider>>), String> {
+        let client = http_client(Duration::from_secs(self.timeout_secs.max(1)));
+        let missing = |name: &str| {
+            format!(
+                "Search provider '{}' needs a [extensions.config.tools-web.search.{}] section",
+                name, name
+            )
+        };
+        let key = |key: &str, name: &str| {
+            if key.trim().is_empty() {
+                Err(format!("Search provider '{}' has an empty api_key", name))
+            } else {
+                Ok(key.to_string())
+            }
+        };
+
+        let kind = self.selected();
+        let primary: Arc<dyn SearchProvider> = match kind {
+            ProviderKind::Brave => {
+                let config = self.brave.as_ref().ok_or_else(|| missing("brave"))?;
+                Arc::new(BraveProvider::new(
+                    client.clone(),
+                    key(&config.api_key, "brave")?,
+                ))
+            }
+            ProviderKind::SerpApi => {
+                let config = self.serpapi.as_ref().ok_or_else(|| missing("serpapi"))?;
+                Arc::new(SerpApiProvider::new(
+                    client.clone(),
+                    key(&config.api_key, "serpapi")?,
+                    &config.engine,
+                ))
+            }
+            ProviderKind::Tavily => {
+                let config = self.tavily.as_ref().ok_or_else(|| missing("tavily"))?;
+                Arc::new(TavilyProvider::new(
+                    client.clone(),
+                    key(&config.api_key, "tavily")?,
+                    &config.search_depth,
+                ))
+            }
+            ProviderKind::Searxng => {
+                let config = self.searxng.as_ref().ok_or_else(|| missing("searxng"))?;
+                Arc::new(SearxngProvider::new(client.clone(), &config.url))
+            }
+            ProviderKind::DuckDuckGo => {
+                return Ok((Arc::new(DuckDuckGoProvider::new(client)), None))
+            }
+        };
+        let fallback = self
+            .fallback
+            .then(|| Arc::new(DuckDuckGoProvider::new(client)) as Arc<dyn SearchProvider>);
+        Ok((primary, fallback))
+    }
+}
@@ -0,0 +1,137 @@
+//! DuckDuckGo's HTML results page, which needs no key.
+
+use async_trait::async_trait;
+use reqwest::Client;
+use url::Url;
+
+use super::{decode_entities, normalize, send, SearchError, SearchProvider, SearchResult};
+
+const ENDPOINT: &str = "https://html.duckduckgo.com/html/";
+
+/// Class of a result's title link.
+const TITLE_CLASS: &str = "class=\"result__a\"";
+
+/// Class of a result's snippet.
+const SNIPPET_CLASS: &str = "class=\"result__snippet\"";
+
+/// Search by scraping DuckDuckGo's HTML page; the fallback when no other
+/// provider is configured or the configured one fails.
+pub struct DuckDuckGoProvider {
+    client: Client,
+    endpoint: String,
+}
+
+impl DuckDuckGoProvider {
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            endpoint: ENDPOINT.to_string(),
+        }
+    }
+
+    /// Send requests to `endpoint` instead of DuckDuckGo.
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into();
+        self
+    }
+}
+
+#[async_trait]
+impl SearchProvider for DuckDuckGoProvider {
+    fn name(&self) -> &'static str {
+        "duckduckgo"
+    }
+
+    async fn search(
+        &self,
+        query: &str,
+        max_results: usize,
+    ) -> Result<Vec<SearchResult>, SearchError> {
+        let request = self.client.get(&self.endpoint).query(&[("q", query)]);
+        let html = send(self.name(), request)
+            .await?
+            .text()
+            .await
+            .map_err(|e| SearchError::Response {
+                provider: self.name(),
+                message: e.to_string(),
+            })?;
+        Ok(normalize(parse_results(&html), max_results))
+    }
+}
+
+/// Results of a DuckDuckGo HTML page, ads left out.
+pub(super) fn parse_results(html: &str) -> Vec<SearchResult> {
+    let mut results = Vec::new();
+    let mut rest = html;
+    while let Some(class) = rest.find(TITLE_CLASS) {
+        let Some(tag_start) = rest[..class].rfind('<') else {
+            break;
+        };
+        let link = &rest[tag_start..];
+        let (Some(tag_end), Some(close)) = (link.find('>'), link.find("</a>")) else {
+            break;
+        };
+        if close < tag_end {
+            break;
+        }
+        let title = &link[tag_end + 1..close];
+        let href = attribute(&link[..tag_end], "href");
+        rest = &link[close..];
+
+        // The snippet sits between this title and the next one
+        let block = &rest[..rest.find(TITLE_CLASS).unwrap_or(rest.len())];
+        let snippet = block
+            .find(SNIPPET_CLASS)
+            .and_then(|i| element_text(&block[i..]))
+            .unwrap_or_default();
+
+        if let Some(url) = href.as_deref().and_then(target_url) {
+            results.push(SearchResult {
+                title: title.to_string(),
+                url,
+                snippet: snippet.to_string(),
+                published: None,
+            });
+        }
+    }
+    results
+}
+
+/// Value of attribute `name` in an opening tag.
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let marker = format!("{}=\"", name);
+    let start = tag.find(&marker)? + marker.len();
+    let end = tag[start..].find('"')?;
+    Some(decode_entities(&tag[start..start + end]))
+}
+
+/// Content of the element whose opening tag `s` is inside.
+fn element_text(s: &str) -> Option<&str> {
+    let start = s.find('>')? + 1;
+    let end = ["</a>", "</div>", "</td>"]
+        .iter()
+        .filter_map(|close| s[start..].find(close))
+        .min()?;
+    Some(&s[start..start + end])
+}
+
+/// The page a result link leads to: DuckDuckGo wraps it in a `/l/`
+/// redirect, and ads go through `/y.js`.
+fn target_url(href: &str) -> Option<String> {
+    let absolute = if href.starts_with("//") {
+        format!("https:{}", href)
+    } else {
+        href.to_string()
+    };
+    let url = Url::parse(&absolute).ok()?;
+    if !url.host_str()?.ends_with("duckduckgo.com") {
+        return Some(url.to_string());
+    }
+    if url.path() != "/l/" {
+        return None;
+    }
+    url.query_pairs()
+        .find(|(key, _)| key == "uddg")
+        .map(|(_, target)| target.into_owned())
+}
@@ -0,0 +1,210 @@
+//! Web search backends.
+//!
+//! `web_search` queries one [`SearchProvider`], chosen in the extension's
+//! configuration, and falls back to DuckDuckGo's HTML page when it fails.
+//! Every provider's results are normalized to plain-text [`SearchResult`]s.
+
+mod brave;
+mod config;
+mod duckduckgo;
+mod searxng;
+mod serpapi;
+mod tavily;
+
+pub use brave::BraveProvider;
+pub use config::{
+    KeyConfig, ProviderKind, SearchConfig, SearxngConfig, SerpApiConfig, TavilyConfig,
+    DEFAULT_TIMEOUT_SECS,
+};
+pub use duckduckgo::DuckDuckGoProvider;
+pub use searxng::SearxngProvider;
+pub use serpapi::SerpApiProvider;
+pub use tavily::TavilyProvider;
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Errors from a search backend.
+#[derive(Debug, Error)]
+pub enum SearchError {
+    #[error("{provider} request failed: {message}")]
+    Request {
+        provider: &'static str,
+        message: String,
+    },
+
+    #[error("{provider} returned HTTP {status}: {body}")]
+    Status {
+        provider: &'static str,
+        status: u16,
+        body: String,
+    },
+
+    #[error("{provider} returned an unexpected response: {message}")]
+    Response {
+        provider: &'static str,
+        message: String,
+    },
+}
+
+/// A search hit, the same for every provider.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchResult {
+    pub title: String,
+    pub url: String,
+    pub snippet: String,
+    /// Publication date or age, as the provider gives it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published: Option<String>,
+}
+
+/// A web search backend.
+#[async_trait]
+pub trait SearchProvider: Send + Sync {
+    /// Name shown with the results.
+    fn name(&self) -> &'static str;
+
+    /// Up to `max_results` results for `query`, best first.
+    async fn search(
+        &self,
+        query: &str,
+        max_results: usize,
+    ) -> Result<Vec<SearchResult>, SearchError>;
+}
+
+/// User agent of search requests; DuckDuckGo turns away clients without
+/// a browser-like one.
+const USER_AGENT: &str = "Mozilla/5.0 (compatible; AutoHands/0.1)";
+
+/// HTTP client for search requests.
+pub fn http_client(timeout: Duration) -> Client {
+    Client::builder()
+        .timeout(timeout)
+        .user_agent(USER_AGENT)
+        .build()
+        .unwrap_or_default()
+}
+
+/// Send a request and decode its JSON body, mapping failures to
+/// [`SearchError`].
+async fn send_json<T: serde::de::DeserializeOwned>(
+    provider: &'static str,
+    request: reqwest::RequestBuilder,
+) -> Result<T, SearchError> {
+    let response = send(provider, request).await?;
+    response.json().await.map_err(|e| SearchError::Response {
+        provider,
+        message: e.to_string(),
+    })
+}
+
+/// Send a request, failing on a non-success status.
+async fn send(
+    provider: &'static str,
+    request: reqwest::RequestBuilder,
+) -> Result<reqwest::Response, SearchError> {
+    let response = request.send().await.map_err(|e| SearchError::Request {
+        provider,
+        message: e.to_string(),
+    })?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(SearchError::Status {
+            provider,
+            status: status.as_u16(),
+            body: body.chars().take(300).collect(),
+        });
+    }
+    Ok(response)
+}
+
+/// Clean up raw results: plain-text titles and snippets, no entries
+/// without a URL, no duplicate URLs, at most `max_results`.
+pub fn normalize(results: Vec<SearchResult>, max_results: usize) -> Vec<SearchResult> {
+    let mut seen = HashSet::new();
+    results
+        .into_iter()
+        .filter_map(|result| {
+            let url = result.url.trim().to_string();
+            if url.is_empty() || !seen.insert(url.clone()) {
+                return None;
+            }
+            let title = plain_text(&result.title);
+            Some(SearchResult {
+                title: if title.is_empty() { url.clone() } else { title },
+                url,
+                snippet: plain_text(&result.snippet),
+                published: result
+                    .published
+                    .map(|p| p.trim().to_string())
+                    .filter(|p| !p.is_empty()),
+            })
+        })
+        .take(max_results)
+        .collect()
+}
+
+/// `html` without tags and entities, whitespace collapsed.
+pub fn plain_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    let text = decode_entities(&text);
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Decode the HTML entities search snippets use.
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest.find(';').filter(|&end| end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,75 @@
+//! SearXNG, a self-hosted metasearch engine.
+//!
+//! The instance must allow the JSON format (`search.formats` in its
+//! `settings.yml`).
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+
+use super::{normalize, send_json, SearchError, SearchProvider, SearchResult};
+
+#[derive(Debug, Deserialize)]
+struct Response {
+    #[serde(default)]
+    results: Vec<Hit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Hit {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    content: String,
+    #[serde(default, rename = "publishedDate")]
+    published_date: Option<String>,
+}
+
+/// Search through a SearXNG instance.
+pub struct SearxngProvider {
+    client: Client,
+    /// Base URL of the instance.
+    url: String,
+}
+
+impl SearxngProvider {
+    pub fn new(client: Client, url: impl Into<String>) -> Self {
+        Self {
+            client,
+            url: url.into().trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl SearchProvider for SearxngProvider {
+    fn name(&self) -> &'static str {
+        "searxng"
+    }
+
+    async fn search(
+        &self,
+        query: &str,
+        max_results: usize,
+    ) -> Result<Vec<SearchResult>, SearchError> {
+        let request = self
+            .client
+            .get(format!("{}/search", self.url))
+            .query(&[("q", query), ("format", "json")]);
+        let response: Response = send_json(self.name(), request).await?;
+
+        let results = response
+            .results
+            .into_iter()
+            .map(|hit| SearchResult {
+                title: hit.title,
+                url: hit.url,
+                snippet: hit.content,
+                published: hit.published_date,
+            })
+            .collect();
+        Ok(normalize(results, max_results))
+    }
+}
@@ -0,0 +1,100 @@
+//! SerpAPI, which returns the results of Google and other engines.
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+
+use super::{normalize, send_json, SearchError, SearchProvider, SearchResult};
+
+const ENDPOINT: &str = "https://serpapi.com/search.json";
+
+#[derive(Debug, Deserialize)]
+struct Response {
+    #[serde(default)]
+    organic_results: Vec<Hit>,
+    /// Set instead of results when the search failed.
+    #[serde(default)]
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Hit {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    link: String,
+    #[serde(default)]
+    snippet: String,
+    #[serde(default)]
+    date: Option<String>,
+}
+
+/// Search through SerpAPI (`api_key` parameter).
+pub struct SerpApiProvider {
+    client: Client,
+    api_key: String,
+    engine: String,
+    endpoint: String,
+}
+
+impl SerpApiProvider {
+    /// A provider querying `engine` (`google`, `bing`, ...).
+    pub fn new(client: Client, api_key: impl Into<String>, engine: impl Into<String>) -> Self {
+        Self {
+            client,
+            api_key: api_key.into(),
+            engine: engine.into(),
+            endpoint: ENDPOINT.to_string(),
+        }
+    }
+
+    /// Send requests to `endpoint` instead of the public API.
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into();
+        self
+    }
+}
+
+#[async_trait]
+impl SearchProvider for SerpApiProvider {
+    fn name(&self) -> &'static str {
+        "serpapi"
+    }
+
+    async fn search(
+        &self,
+        query: &str,
+        max_results: usize,
+    ) -> Result<Vec<SearchResult>, SearchError> {
+        let num = max_results.to_string();
+        let request = self.client.get(&self.endpoint).query(&[
+            ("engine", self.engine.as_str()),
+            ("q", query),
+            ("num", num.as_str()),
+            ("api_key", self.api_key.as_str()),
+        ]);
+        let response: Response = send_json(self.name(), request).await?;
+        if let Some(error) = response.error {
+            // "Google hasn't returned any results" is an empty page, not a failure
+            if error.contains("hasn't returned any results") {
+                return Ok(Vec::new());
+            }
+            return Err(SearchError::Response {
+                provider: self.name(),
+                message: error,
+            });
+        }
+
+        let results = response
+            .organic_results
+            .into_iter()
+            .map(|hit| SearchResult {
+                title: hit.title,
+                url: hit.link,
+                snippet: hit.snippet,
+                published: hit.date,
+            })
+            .collect();
+        Ok(normalize(results, max_results))
+    }
+}
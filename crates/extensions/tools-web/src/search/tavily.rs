@@ -0,0 +1,96 @@
+//! Tavily, a search API built for agents.
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+
+use super::{normalize, send_json, SearchError, SearchProvider, SearchResult};
+
+const ENDPOINT: &str = "https://api.tavily.com/search";
+
+/// Most results one request returns.
+const MAX_COUNT: usize = 20;
+
+#[derive(Debug, Deserialize)]
+struct Response {
+    #[serde(default)]
+    results: Vec<Hit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Hit {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    content: String,
+    #[serde(default)]
+    published_date: Option<String>,
+}
+
+/// Search through the Tavily API (bearer key).
+pub struct TavilyProvider {
+    client: Client,
+    api_key: String,
+    /// `basic` or `advanced`.
+    search_depth: String,
+    endpoint: String,
+}
+
+impl TavilyProvider {
+    pub fn new(
+        client: Client,
+        api_key: impl Into<String>,
+        search_depth: impl Into<String>,
+    ) -> Self {
+        Self {
+            client,
+            api_key: api_key.into(),
+            search_depth: search_depth.into(),
+            endpoint: ENDPOINT.to_string(),
+        }
+    }
+
+    /// Send requests to `endpoint` instead of the public API.
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into();
+        self
+    }
+}
+
+#[async_trait]
+impl SearchProvider for TavilyProvider {
+    fn name(&self) -> &'static str {
+        "tavily"
+    }
+
+    async fn search(
+        &self,
+        query: &str,
+        max_results: usize,
+    ) -> Result<Vec<SearchResult>, SearchError> {
+        let request = self
+            .client
+            .post(&self.endpoint)
+            .bearer_auth(&self.api_key)
+            .json(&serde_json::json!({
+                "query": query,
+                "max_results": max_results.min(MAX_COUNT),
+                "search_depth": self.search_depth,
+            }));
+        let response: Response = send_json(self.name(), request).await?;
+
+        let results = response
+            .results
+            .into_iter()
+            .map(|hit| SearchResult {
+                title: hit.title,
+                url: hit.url,
+                snippet: hit.content,
+                published: hit.published_date,
+            })
+            .collect();
+        Ok(normalize(results, max_results))
+    }
+}
@@ -0,0 +1,259 @@
+//! Tests for the search providers, against mock servers.
+
+use reqwest::Client;
+use serde_json::json;
+use wiremock::matchers::{body_partial_json, header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use super::duckduckgo::parse_results;
+use super::*;
+
+fn hit(title: &str, url: &str, snippet: &str) -> SearchResult {
+    SearchResult {
+        title: title.to_string(),
+        url: url.to_string(),
+        snippet: snippet.to_string(),
+        published: None,
+    }
+}
+
+#[test]
+fn test_plain_text() {
+    assert_eq!(
+        plain_text("The <strong>Rust</strong>  language &amp; its\n tools"),
+        "The Rust language & its tools"
+    );
+    assert_eq!(
+        plain_text("&quot;a&quot; &#39;b&#x27; &lt;c&gt;"),
+        "\"a\" 'b' <c>"
+    );
+    assert_eq!(plain_text("R&D &unknown; 5 & 6"), "R&D &unknown; 5 & 6");
+}
+
+#[test]
+fn test_normalize() {
+    let results = normalize(
+        vec![
+            hit("<b>One</b>", "https://a.example/", "first"),
+            hit("Again", "https://a.example/", "duplicate"),
+            hit("No URL", " ", "dropped"),
+            hit("", "https://b.example/", "untitled"),
+            hit("Three", "https://c.example/", ""),
+        ],
+        2,
+    );
+    assert_eq!(
+        results,
+        vec![
+            hit("One", "https://a.example/", "first"),
+            hit("https://b.example/", "https://b.example/", "untitled"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_brave() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/search"))
+        .and(header("X-Subscription-Token", "key"))
+        .and(query_param("q", "rust"))
+        .and(query_param("count", "5"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "web": {"results": [
+                {"title": "Rust", "url": "https://www.rust-lang.org/", "description": "A <strong>language</strong>", "age": "2 days ago"}
+            ]}
+        })))
+        .mount(&server)
+        .await;
+
+    let provider =
+        BraveProvider::new(Client::new(), "key").with_endpoint(format!("{}/search", server.uri()));
+    let results = provider.search("rust", 5).await.unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].snippet, "A language");
+    assert_eq!(results[0].published.as_deref(), Some("2 days ago"));
+}
+
+#[tokio::test]
+async fn test_serpapi() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(query_param("engine", "bing"))
+        .and(query_param("api_key", "key"))
+        .and(query_param("q", "rust"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "organic_results": [
+                {"title": "Rust", "link": "https://www.rust-lang.org/", "snippet": "Fast", "date": "Jan 1, 2024"}
+            ]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(query_param("q", "nothing"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "error": "Google hasn't returned any results for this query."
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(query_param("q", "broken"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "error": "Invalid API key."
+        })))
+        .mount(&server)
+        .await;
+
+    let provider = SerpApiProvider::new(Client::new(), "key", "bing").with_endpoint(server.uri());
+    let results = provider.search("rust", 10).await.unwrap();
+    assert_eq!(results[0].url, "https://www.rust-lang.org/");
+    assert_eq!(results[0].published.as_deref(), Some("Jan 1, 2024"));
+    assert!(provider.search("nothing", 10).await.unwrap().is_empty());
+    let err = provider.search("broken", 10).await.unwrap_err();
+    assert!(err.to_string().contains("Invalid API key"));
+}
+
+#[tokio::test]
+async fn test_tavily() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(header("Authorization", "Bearer key"))
+        .and(body_partial_json(json!({"query": "rust", "max_results": 3, "search_depth": "advanced"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "results": [
+                {"title": "Rust", "url": "https://www.rust-lang.org/", "content": "Reliable", "published_date": null}
+            ]
+        })))
+        .mount(&server)
+        .await;
+
+    let provider =
+        TavilyProvider::new(Client::new(), "key", "advanced").with_endpoint(server.uri());
+    let results = provider.search("rust", 3).await.unwrap();
+    assert_eq!(
+        results,
+        vec![hit("Rust", "https://www.rust-lang.org/", "Reliable")]
+    );
+}
+
+#[tokio::test]
+async fn test_searxng() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/search"))
+        .and(query_param("format", "json"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "results": [
+                {"title": "Rust", "url": "https://www.rust-lang.org/", "content": "Productive", "publishedDate": "2024-01-01T00:00:00"}
+            ]
+        })))
+        .mount(&server)
+        .await;
+
+    let provider = SearxngProvider::new(Client::new(), format!("{}/", server.uri()));
+    let results = provider.search("rust", 3).await.unwrap();
+    assert_eq!(results[0].snippet, "Productive");
+    assert_eq!(results[0].published.as_deref(), Some("2024-01-01T00:00:00"));
+}
+
+#[tokio::test]
+async fn test_http_errors() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(401).set_body_string("bad token"))
+        .mount(&server)
+        .await;
+
+    let provider = BraveProvider::new(Client::new(), "key").with_endpoint(server.uri());
+    let err = provider.search("rust", 3).await.unwrap_err();
+    assert!(matches!(err, SearchError::Status { status: 401, .. }));
+    assert_eq!(err.to_string(), "brave returned HTTP 401: bad token");
+}
+
+const DUCKDUCKGO_PAGE: &str = r#"
+<div class="result results_links results_links_deep result--ad">
+  <h2 class="result__title"><a rel="nofollow" class="result__a" href="https://duckduckgo.com/y.js?ad_domain=ads.example">Sponsored</a></h2>
+  <a class="result__snippet" href="https://duckduckgo.com/y.js?ad_domain=ads.example">Buy now</a>
+</div>
+<div class="result results_links results_links_deep web-result">
+  <h2 class="result__title"><a rel="nofollow" class="result__a" href="//duckduckgo.com/l/?uddg=https%3A%2F%2Fwww.rust%2Dlang.org%2F&amp;rut=abc">Rust Programming <b>Language</b></a></h2>
+  <a class="result__snippet" href="//duckduckgo.com/l/?uddg=x">A language empowering <b>everyone</b> to build reliable software.</a>
+</div>
+<div class="result results_links results_links_deep web-result">
+  <h2 class="result__title"><a rel="nofollow" class="result__a" href="https://doc.rust-lang.org/book/">The Book</a></h2>
+</div>
+"#;
+
+#[test]
+fn test_parse_duckduckgo_page() {
+    let results = parse_results(DUCKDUCKGO_PAGE);
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].url, "https://www.rust-lang.org/");
+    assert_eq!(
+        normalize(results, 10),
+        vec![
+            hit(
+                "Rust Programming Language",
+                "https://www.rust-lang.org/",
+                "A language empowering everyone to build reliable software."
+            ),
+            hit("The Book", "https://doc.rust-lang.org/book/", ""),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_duckduckgo() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(query_param("q", "rust"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(DUCKDUCKGO_PAGE))
+        .mount(&server)
+        .await;
+
+    let provider = DuckDuckGoProvider::new(Client::new()).with_endpoint(server.uri());
+    let results = provider.search("rust", 1).await.unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].title, "Rust Programming Language");
+}
+
+#[test]
+fn test_config_selection() {
+    let config = SearchConfig::default();
+    assert_eq!(config.selected(), ProviderKind::DuckDuckGo);
+    let (provider, fallback) = config.build().unwrap();
+    assert_eq!(provider.name(), "duckduckgo");
+    assert!(fallback.is_none());
+
+    let config: SearchConfig = serde_json::from_value(json!({
+        "searxng": {"url": "http://localhost:8888"},
+        "tavily": {"api_key": "key"}
+    }))
+    .unwrap();
+    assert_eq!(config.selected(), ProviderKind::Tavily);
+    let (provider, fallback) = config.build().unwrap();
+    assert_eq!(provider.name(), "tavily");
+    assert_eq!(fallback.unwrap().name(), "duckduckgo");
+
+    let config: SearchConfig = serde_json::from_value(json!({
+        "provider": "searxng",
+        "fallback": false,
+        "tavily": {"api_key": "key"},
+        "searxng": {"url": "http://localhost:8888"}
+    }))
+    .unwrap();
+    let (provider, fallback) = config.build().unwrap();
+    assert_eq!(provider.name(), "searxng");
+    assert!(fallback.is_none());
+}
+
+#[test]
+fn test_config_errors() {
+    let config: SearchConfig = serde_json::from_value(json!({"provider": "brave"})).unwrap();
+    assert!(config.build().err().unwrap().contains("search.brave"));
+
+    let config: SearchConfig = serde_json::from_value(json!({"serpapi": {"api_key": ""}})).unwrap();
+    assert!(config.build().err().unwrap().contains("empty api_key"));
+
+    assert!(serde_json::from_value::<SearchConfig>(json!({"provider": "bing"})).is_err());
+}
@@ -9,6 +9,11 @@ use autohands_protocols::error::ToolError;
 use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
 use autohands_protocols::types::RiskLevel;
 
+use crate::cache::{CachedPage, PageCache};
+use crate::content::{
+    estimate_tokens, extract_article, html_to_markdown, is_html, truncate_to_tokens,
+};
+
 /// Parameters for web_fetch tool.
 #[derive(Debug, Deserialize)]
 struct FetchParams {
@@ -29,6 +34,31 @@ struct FetchParams {
     /// Timeout in seconds.
     #[serde(default = "default_timeout")]
     timeout: u64,
+
+    /// What to return (default: raw).
+    #[serde(default)]
+    mode: FetchMode,
+
+    /// Token budget of markdown and article output.
+    #[serde(default = "default_max_tokens")]
+    max_tokens: usize,
+
+    /// Whether to use the page cache, when one is configured.
+    #[serde(default = "default_cache")]
+    cache: bool,
+}
+
+/// Output of web_fetch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum FetchMode {
+    /// Status, headers and body as JSON.
+    #[default]
+    Raw,
+    /// The whole page as Markdown.
+    Markdown,
+    /// The page's main content as Markdown.
+    Article,
 }
 
 fn default_method() -> String {
@@ -39,6 +69,14 @@ fn default_timeout() -> u64 {
     30
 }
 
+fn default_max_tokens() -> usize {
+    8000
+}
+
+fn default_cache() -> bool {
+    true
+}
+
 /// Result from web fetch.
 #[derive(Debug, Serialize)]
 struct FetchResult {
@@ -52,6 +90,7 @@ struct FetchResult {
 pub struct WebFetchTool {
     definition: ToolDefinition,
     client: Client,
+    cache: Option<PageCache>,
 }
 
 impl WebFetchTool {
@@ -66,7 +105,7 @@ impl WebFetchTool {
         let definition = ToolDefinition::new(
             "web_fetch",
             "Web Fetch",
-            "Fetch content from a URL",
+            "Fetch content from a URL. Use mode 'article' to read a page's main content as Markdown, or 'markdown' for the whole page",
         )
         .with_parameters_schema(serde_json::json!({
             "type": "object",
@@ -92,13 +131,96 @@ impl WebFetchTool {
                     "type": "integer",
                     "description": "Request timeout in seconds",
                     "default": 30
+                },
+                "mode": {
+                    "type": "string",
+                    "enum": ["raw", "markdown", "article"],
+                    "description": "raw: status, headers and body as JSON; markdown: the page as Markdown; article: the main content as Markdown, without navigation, sidebars and ads",
+                    "default": "raw"
+                },
+                "max_tokens": {
+                    "type": "integer",
+                    "description": "Approximate token budget of markdown and article output",
+                    "default": 8000
+                },
+                "cache": {
+                    "type": "boolean",
+                    "description": "Use the page cache for GET requests, when configured",
+                    "default": true
                 }
             },
             "required": ["url"]
         }))
-        .with_risk_level(RiskLevel::Medium);
+        .with_risk_level(RiskLevel::Medium)
+        .with_read_only();
 
-        Self { definition, client }
+        Self {
+            definition,
+            client,
+            cache: None,
+        }
+    }
+
+    /// Cache GET responses in `cache`, revalidating them with ETag and
+    /// Last-Modified.
+    pub fn with_cache(mut self, cache: PageCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// The page as Markdown within the token budget.
+    fn extract(
+        &self,
+        page: &CachedPage,
+        mode: FetchMode,
+        max_tokens: usize,
+        cached: bool,
+    ) -> ToolResult {
+        let content_type = page.headers.get("content-type").map(String::as_str);
+        let base = url::Url::parse(&page.url).ok();
+        let (title, text) = if !is_html(content_type, &page.body) {
+            (None, page.body.clone())
+        } else if mode == FetchMode::Article {
+            let article = extract_article(&page.body, base.as_ref());
+            (article.title, article.markdown)
+        } else {
+            (None, html_to_markdown(&page.body, base.as_ref()))
+        };
+
+        let tokens = estimate_tokens(&text);
+        let (text, truncated) = match truncate_to_tokens(&text, max_tokens) {
+            Some(cut) => (cut, true),
+            None => (text, false),
+        };
+
+        let mut content = String::new();
+        if let Some(title) = &title {
+            content.push_str(&format!("# {}\n\n", title));
+        }
+        content.push_str(&format!("Source: {}", page.url));
+        if page.status >= 400 {
+            content.push_str(&format!(" (HTTP {})", page.status));
+        }
+        content.push_str("\n\n");
+        content.push_str(&text);
+        if truncated {
+            content.push_str(&format!(
+                "\n\n[Truncated to about {} of {} tokens; raise max_tokens to read more]",
+                max_tokens, tokens
+            ));
+        }
+
+        ToolResult::success_json(
+            content,
+            serde_json::json!({
+                "url": page.url,
+                "status": page.status,
+                "title": title,
+                "tokens": tokens,
+                "truncated": truncated,
+                "cached": cached,
+            }),
+        )
     }
 }
 
@@ -125,6 +247,11 @@ impl Tool for WebFetchTool {
         // Validate URL
         let url = url::Url::parse(&params.url)
             .map_err(|e| ToolError::InvalidParameters(format!("Invalid URL: {}", e)))?;
+        if params.max_tokens == 0 {
+            return Err(ToolError::InvalidParameters(
+                "max_tokens must be at least 1".to_string(),
+            ));
+        }
 
         // Build request
         let mut request = match params.method.to_uppercase().as_str() {
@@ -141,6 +268,11 @@ impl Tool for WebFetchTool {
         // Set timeout
         request = request.timeout(Duration::from_secs(params.timeout));
 
+        // Pages fetched with custom headers may be personal: leave them out
+        let cache = self.cache.as_ref().filter(|_| {
+            params.cache && params.method.eq_ignore_ascii_case("GET") && params.headers.is_empty()
+        });
+
         // Add headers
         for (key, value) in params.headers {
             request = request.header(&key, &value);
@@ -151,6 +283,20 @@ impl Tool for WebFetchTool {
             request = request.body(body);
         }
 
+        // Revalidate a cached copy instead of downloading it again
+        let cached = match cache {
+            Some(cache) => cache.get(&params.url).await,
+            None => None,
+        };
+        if let Some(page) = &cached {
+            if let Some(etag) = &page.etag {
+                request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &page.last_modified {
+                request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
         // Execute request
         let response = request.send().await
             .map_err(|e| ToolError::ExecutionFailed(format!("Request failed: {}", e)))?;
@@ -165,11 +311,28 @@ impl Tool for WebFetchTool {
         let body = response.text().await
             .map_err(|e| ToolError::ExecutionFailed(format!("Failed to read body: {}", e)))?;
 
+        let fetched = CachedPage::new(&params.url, status, headers, body);
+        let (page, from_cache) = match cached {
+            Some(page) if status == 304 => (page, true),
+            _ => {
+                if let Some(cache) = cache.filter(|_| fetched.is_storable()) {
+                    if let Err(e) = cache.put(&fetched).await {
+                        tracing::warn!("Failed to cache {}: {}", params.url, e);
+                    }
+                }
+                (fetched, false)
+            }
+        };
+
+        if params.mode != FetchMode::Raw {
+            return Ok(self.extract(&page, params.mode, params.max_tokens, from_cache));
+        }
+
         let result = FetchResult {
-            status,
-            headers,
-            body,
-            url: params.url,
+            status: page.status,
+            headers: page.headers,
+            body: page.body,
+            url: page.url,
         };
 
         Ok(ToolResult::success(serde_json::to_string_pretty(&result).unwrap()))
@@ -1,7 +1,7 @@
 use super::*;
 use std::path::PathBuf;
 use wiremock::{Mock, MockServer, ResponseTemplate};
-use wiremock::matchers::{method, path};
+use wiremock::matchers::{header, method, path};
 
 #[test]
 fn test_tool_definition() {
@@ -243,3 +243,176 @@ async fn test_fetch_404() {
     let result = tool.execute(params, ctx).await.unwrap();
     assert!(result.content.contains("404"));
 }
+
+#[test]
+fn test_fetch_params_content_defaults() {
+    let params: FetchParams =
+        serde_json::from_value(serde_json::json!({"url": "https://example.com"})).unwrap();
+    assert_eq!(params.mode, FetchMode::Raw);
+    assert_eq!(params.max_tokens, default_max_tokens());
+    assert!(params.cache);
+
+    let params: FetchParams = serde_json::from_value(
+        serde_json::json!({"url": "https://example.com", "mode": "article", "max_tokens": 100}),
+    )
+    .unwrap();
+    assert_eq!(params.mode, FetchMode::Article);
+    assert_eq!(params.max_tokens, 100);
+}
+
+const ARTICLE_PAGE: &str = r#"<html><head><title>Release notes</title></head><body>
+    <nav><a href="/">Home</a> <a href="/docs">Docs</a></nav>
+    <article>
+      <p>This release makes the scheduler faster, fairer and easier to configure, with new defaults.</p>
+      <p>Upgrading takes one step: see the <a href="/upgrade">upgrade guide</a> for the details.</p>
+      <table><tr><th>Version</th><th>Date</th></tr><tr><td>2.0</td><td>March</td></tr></table>
+    </article>
+    <footer>Copyright Example Corp, all rights reserved.</footer>
+    </body></html>"#;
+
+async fn serve_page(server: &MockServer, route: &str, body: &str) {
+    Mock::given(method("GET"))
+        .and(path(route))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Content-Type", "text/html; charset=utf-8")
+                .set_body_string(body),
+        )
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_fetch_article() {
+    let mock_server = MockServer::start().await;
+    serve_page(&mock_server, "/notes", ARTICLE_PAGE).await;
+
+    let tool = WebFetchTool::new();
+    let ctx = ToolContext::new("test", PathBuf::from("."));
+    let params = serde_json::json!({
+        "url": format!("{}/notes", mock_server.uri()),
+        "mode": "article"
+    });
+
+    let result = tool.execute(params, ctx).await.unwrap();
+    assert!(result.content.starts_with("# Release notes\n\nSource: "));
+    assert!(result.content.contains("scheduler faster"));
+    assert!(result
+        .content
+        .contains(&format!("[upgrade guide]({}/upgrade)", mock_server.uri())));
+    assert!(result.content.contains("| Version | Date |\n| --- | --- |\n| 2.0 | March |"));
+    assert!(!result.content.contains("Home"));
+    assert!(!result.content.contains("Copyright"));
+
+    let output = result.structured_output.unwrap();
+    assert_eq!(output["title"], "Release notes");
+    assert_eq!(output["truncated"], false);
+    assert_eq!(output["cached"], false);
+}
+
+#[tokio::test]
+async fn test_fetch_markdown_truncated() {
+    let mock_server = MockServer::start().await;
+    let paragraphs: String = (0..50)
+        .map(|i| format!("<p>Paragraph {} of a long page about nothing much.</p>", i))
+        .collect();
+    serve_page(&mock_server, "/long", &format!("<html><body>{}</body></html>", paragraphs)).await;
+
+    let tool = WebFetchTool::new();
+    let ctx = ToolContext::new("test", PathBuf::from("."));
+    let params = serde_json::json!({
+        "url": format!("{}/long", mock_server.uri()),
+        "mode": "markdown",
+        "max_tokens": 50
+    });
+
+    let result = tool.execute(params, ctx).await.unwrap();
+    assert!(result.content.contains("Paragraph 0 of"));
+    assert!(!result.content.contains("Paragraph 49"));
+    assert!(result.content.contains("[Truncated to about 50 of"));
+    let output = result.structured_output.unwrap();
+    assert_eq!(output["truncated"], true);
+    assert!(output["tokens"].as_u64().unwrap() > 50);
+}
+
+#[tokio::test]
+async fn test_fetch_markdown_keeps_plain_text() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/data.json"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Content-Type", "application/json")
+                .set_body_string("{\"a\": \"<b>1</b>\"}"),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let tool = WebFetchTool::new();
+    let ctx = ToolContext::new("test", PathBuf::from("."));
+    let params = serde_json::json!({
+        "url": format!("{}/data.json", mock_server.uri()),
+        "mode": "markdown"
+    });
+
+    let result = tool.execute(params, ctx).await.unwrap();
+    assert!(result.content.ends_with("{\"a\": \"<b>1</b>\"}"));
+}
+
+#[tokio::test]
+async fn test_fetch_zero_max_tokens() {
+    let tool = WebFetchTool::new();
+    let ctx = ToolContext::new("test", PathBuf::from("."));
+    let params = serde_json::json!({
+        "url": "https://example.com",
+        "mode": "article",
+        "max_tokens": 0
+    });
+
+    let result = tool.execute(params, ctx).await;
+    assert!(matches!(result, Err(ToolError::InvalidParameters(_))));
+}
+
+#[tokio::test]
+async fn test_fetch_cache_revalidates_with_etag() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/cached"))
+        .and(header("If-None-Match", "\"v1\""))
+        .respond_with(ResponseTemplate::new(304))
+        .with_priority(1)
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/cached"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Content-Type", "text/html")
+                .insert_header("ETag", "\"v1\"")
+                .set_body_string(ARTICLE_PAGE),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let tool = WebFetchTool::new().with_cache(PageCache::new(dir.path()));
+    let params = serde_json::json!({
+        "url": format!("{}/cached", mock_server.uri()),
+        "mode": "article"
+    });
+
+    let first = tool
+        .execute(params.clone(), ToolContext::new("test", PathBuf::from(".")))
+        .await
+        .unwrap();
+    assert_eq!(first.structured_output.unwrap()["cached"], false);
+
+    let second = tool
+        .execute(params, ToolContext::new("test", PathBuf::from(".")))
+        .await
+        .unwrap();
+    assert_eq!(second.structured_output.unwrap()["cached"], true);
+    assert!(second.content.contains("scheduler faster"));
+}
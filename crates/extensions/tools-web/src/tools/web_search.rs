@@ -1,12 +1,22 @@
 //! Web search tool implementation.
 
+use std::sync::Arc;
+use std::time::Duration;
+
 use async_trait::async_trait;
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
 
 use autohands_protocols::error::ToolError;
 use autohands_protocols::tool::{Tool, ToolContext, ToolDefinition, ToolResult};
 use autohands_protocols::types::RiskLevel;
 
+use crate::search::{
+    http_client, DuckDuckGoProvider, SearchProvider, SearchResult, DEFAULT_TIMEOUT_SECS,
+};
+
+/// Most results one search returns.
+const MAX_RESULTS: u32 = 20;
+
 /// Parameters for web_search tool.
 #[derive(Debug, Deserialize)]
 struct SearchParams {
@@ -22,27 +32,21 @@ fn default_max_results() -> u32 {
     10
 }
 
-/// A search result.
-#[derive(Debug, Serialize)]
-struct SearchResult {
-    title: String,
-    url: String,
-    snippet: String,
-}
-
-/// Tool for web search.
-/// Note: In production, this would integrate with a search API.
+/// Tool for web search through a configured [`SearchProvider`].
 pub struct WebSearchTool {
     definition: ToolDefinition,
+    provider: Arc<dyn SearchProvider>,
+    /// Provider retried when `provider` fails.
+    fallback: Option<Arc<dyn SearchProvider>>,
 }
 
 impl WebSearchTool {
-    /// Create a new web search tool.
+    /// Create a web search tool using DuckDuckGo.
     pub fn new() -> Self {
         let definition = ToolDefinition::new(
             "web_search",
             "Web Search",
-            "Search the web for information",
+            "Search the web and get result titles, URLs and snippets; fetch a result with web_fetch to read it",
         )
         .with_parameters_schema(serde_json::json!({
             "type": "object",
@@ -53,42 +57,55 @@ impl WebSearchTool {
                 },
                 "max_results": {
                     "type": "integer",
-                    "description": "Maximum number of results to return",
+                    "description": "Maximum number of results to return (at most 20)",
                     "default": 10
                 }
             },
             "required": ["query"]
         }))
-        .with_risk_level(RiskLevel::Low);
+        .with_risk_level(RiskLevel::Low)
+        .with_read_only();
 
-        Self { definition }
+        Self {
+            definition,
+            provider: Arc::new(DuckDuckGoProvider::new(http_client(Duration::from_secs(
+                DEFAULT_TIMEOUT_SECS,
+            )))),
+            fallback: None,
+        }
     }
 
-    /// Perform the search (stub implementation).
-    async fn search(&self, query: &str, max_results: u32) -> Vec<SearchResult> {
-        // This is a stub. In production, integrate with:
-        // - Google Custom Search API
-        // - Bing Search API
-        // - DuckDuckGo API
-        // - SerpAPI
-        // etc.
-
-        // Return mock results for demonstration
-        vec![
-            SearchResult {
-                title: format!("Result 1 for: {}", query),
-                url: format!("https://example.com/1?q={}", urlencoding::encode(query)),
-                snippet: format!("This is a mock search result for '{}'", query),
-            },
-            SearchResult {
-                title: format!("Result 2 for: {}", query),
-                url: format!("https://example.com/2?q={}", urlencoding::encode(query)),
-                snippet: format!("Another mock result about '{}'", query),
-            },
-        ]
-        .into_iter()
-        .take(max_results as usize)
-        .collect()
+    /// Search with `provider` instead.
+    pub fn with_provider(mut self, provider: Arc<dyn SearchProvider>) -> Self {
+        self.provider = provider;
+        self
+    }
+
+    /// Retry failed searches with `fallback`.
+    pub fn with_fallback(mut self, fallback: Option<Arc<dyn SearchProvider>>) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// Search with the provider, then the fallback if the provider fails.
+    async fn search(
+        &self,
+        query: &str,
+        max_results: usize,
+    ) -> Result<(&'static str, Vec<SearchResult>, Option<String>), ToolError> {
+        let error = match self.provider.search(query, max_results).await {
+            Ok(results) => return Ok((self.provider.name(), results, None)),
+            Err(e) => e,
+        };
+        let Some(fallback) = &self.fallback else {
+            return Err(ToolError::ExecutionFailed(error.to_string()));
+        };
+        tracing::warn!("{}; retrying with {}", error, fallback.name());
+        let results = fallback
+            .search(query, max_results)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("{}; {}", error, e)))?;
+        Ok((fallback.name(), results, Some(error.to_string())))
     }
 }
 
@@ -107,33 +124,57 @@ impl Tool for WebSearchTool {
     async fn execute(
         &self,
         params: serde_json::Value,
-        _ctx: ToolContext,
+        ctx: ToolContext,
     ) -> Result<ToolResult, ToolError> {
-        tracing::warn!("web_search is using stub implementation, results are mock data");
-
         let params: SearchParams = serde_json::from_value(params)
             .map_err(|e| ToolError::InvalidParameters(e.to_string()))?;
 
         if params.query.trim().is_empty() {
-            return Err(ToolError::InvalidParameters("Query cannot be empty".to_string()));
+            return Err(ToolError::InvalidParameters(
+                "Query cannot be empty".to_string(),
+            ));
         }
+        let max_results = params.max_results.clamp(1, MAX_RESULTS) as usize;
 
-        let results = self.search(&params.query, params.max_results).await;
+        let (provider, results, provider_error) = tokio::select! {
+            found = self.search(params.query.trim(), max_results) => found?,
+            _ = ctx.abort_signal.aborted() => return Err(ToolError::Cancelled),
+        };
+
+        let mut content = if results.is_empty() {
+            format!("No results for '{}'", params.query)
+        } else {
+            results
+                .iter()
+                .enumerate()
+                .map(|(i, result)| {
+                    let mut entry = format!("{}. {}\n   {}", i + 1, result.title, result.url);
+                    if let Some(published) = &result.published {
+                        entry.push_str(&format!(" ({})", published));
+                    }
+                    if !result.snippet.is_empty() {
+                        entry.push_str(&format!("\n   {}", result.snippet));
+                    }
+                    entry
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        if let Some(error) = &provider_error {
+            content.push_str(&format!(
+                "\n\n(Results from {} because {})",
+                provider, error
+            ));
+        }
 
         let output = serde_json::json!({
             "query": params.query,
+            "provider": provider,
             "results": results,
             "count": results.len()
         });
 
-        Ok(ToolResult::success(serde_json::to_string_pretty(&output).unwrap()))
-    }
-}
-
-/// URL encoding helper.
-mod urlencoding {
-    pub fn encode(input: &str) -> String {
-        url::form_urlencoded::byte_serialize(input.as_bytes()).collect()
+        Ok(ToolResult::success_json(content, output))
     }
 }
 
@@ -142,6 +183,55 @@ mod tests {
     use super::*;
     use std::path::PathBuf;
 
+    use crate::search::SearchError;
+
+    /// Provider returning numbered results, or failing.
+    struct FakeProvider {
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl SearchProvider for FakeProvider {
+        fn name(&self) -> &'static str {
+            if self.fail {
+                "broken"
+            } else {
+                "fake"
+            }
+        }
+
+        async fn search(
+            &self,
+            query: &str,
+            max_results: usize,
+        ) -> Result<Vec<SearchResult>, SearchError> {
+            if self.fail {
+                return Err(SearchError::Status {
+                    provider: "broken",
+                    status: 429,
+                    body: "rate limited".to_string(),
+                });
+            }
+            Ok((1..=30)
+                .map(|i| SearchResult {
+                    title: format!("Result {} for: {}", i, query),
+                    url: format!("https://example.com/{}", i),
+                    snippet: format!("About '{}'", query),
+                    published: None,
+                })
+                .take(max_results)
+                .collect())
+        }
+    }
+
+    fn tool(fail: bool, fallback: bool) -> WebSearchTool {
+        WebSearchTool::new()
+            .with_provider(Arc::new(FakeProvider { fail }))
+            .with_fallback(
+                fallback.then(|| Arc::new(FakeProvider { fail: false }) as Arc<dyn SearchProvider>),
+            )
+    }
+
     #[test]
     fn test_tool_definition() {
         let tool = WebSearchTool::new();
@@ -151,29 +241,35 @@ mod tests {
 
     #[tokio::test]
     async fn test_search() {
-        let tool = WebSearchTool::new();
         let ctx = ToolContext::new("test", PathBuf::from("."));
         let params = serde_json::json!({
             "query": "rust programming"
         });
 
-        let result = tool.execute(params, ctx).await.unwrap();
-        assert!(result.content.contains("rust programming"));
-        assert!(result.content.contains("results"));
+        let result = tool(false, false).execute(params, ctx).await.unwrap();
+        assert!(result
+            .content
+            .starts_with("1. Result 1 for: rust programming"));
+        let output = result.structured_output.unwrap();
+        assert_eq!(output["provider"], "fake");
+        assert_eq!(output["count"], 10);
     }
 
     #[tokio::test]
     async fn test_search_max_results() {
-        let tool = WebSearchTool::new();
         let ctx = ToolContext::new("test", PathBuf::from("."));
         let params = serde_json::json!({
             "query": "test",
             "max_results": 1
         });
 
-        let result = tool.execute(params, ctx).await.unwrap();
-        let parsed: serde_json::Value = serde_json::from_str(&result.content).unwrap();
-        assert_eq!(parsed["count"], 1);
+        let result = tool(false, false).execute(params, ctx).await.unwrap();
+        assert_eq!(result.structured_output.unwrap()["count"], 1);
+
+        let ctx = ToolContext::new("test", PathBuf::from("."));
+        let params = serde_json::json!({"query": "test", "max_results": 100});
+        let result = tool(false, false).execute(params, ctx).await.unwrap();
+        assert_eq!(result.structured_output.unwrap()["count"], MAX_RESULTS);
     }
 
     #[tokio::test]
@@ -188,9 +284,23 @@ mod tests {
         assert!(result.is_err());
     }
 
-    #[test]
-    fn test_urlencoding() {
-        assert_eq!(urlencoding::encode("hello world"), "hello+world");
-        assert_eq!(urlencoding::encode("a=b&c=d"), "a%3Db%26c%3Dd");
+    #[tokio::test]
+    async fn test_search_fallback() {
+        let ctx = ToolContext::new("test", PathBuf::from("."));
+        let result = tool(true, true)
+            .execute(serde_json::json!({"query": "test"}), ctx)
+            .await
+            .unwrap();
+        assert!(result
+            .content
+            .ends_with("(Results from fake because broken returned HTTP 429: rate limited)"));
+        assert_eq!(result.structured_output.unwrap()["provider"], "fake");
+
+        let ctx = ToolContext::new("test", PathBuf::from("."));
+        let err = tool(true, false)
+            .execute(serde_json::json!({"query": "test"}), ctx)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ToolError::ExecutionFailed(m) if m.contains("429")));
     }
 }
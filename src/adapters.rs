@@ -2,10 +2,31 @@
 
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Instant;
 
+use futures::StreamExt;
+
+use std::collections::HashMap;
+
+use autohands_api::{BackupJob, BackupRunner, PageFetcher, TemplateStore};
+use autohands_channel_web::{AdminBackend, MetricsSource, TemplateSummary, ToolSummary};
 use autohands_checkpoint::CheckpointManager;
+use autohands_config::ConfigLoader;
+use autohands_core::registry::{ProviderRegistry, ToolRegistry};
+use autohands_daemon::backup::{backup_file_name, create_backup, prune_backups};
 use autohands_monitor::metrics::MetricsRegistry;
-use autohands_runtime::{CheckpointData, CheckpointSupport};
+use autohands_monitor::DashboardStats;
+use autohands_protocols::error::ProviderError;
+use autohands_protocols::provider::{
+    CompletionRequest, CompletionResponse, CompletionStream, LLMProvider, ModelDefinition,
+    ProviderCapabilities,
+};
+use autohands_protocols::skill::{Skill, SkillDefinition};
+use autohands_protocols::types::Message;
+use autohands_runloop::{CallbackDispatcher, CallbackEventKind, RunLoop};
+use autohands_runtime::feature_flags::{provider_flag, WORKSPACE_METADATA};
+use autohands_runtime::{AgentRuntime, CheckpointData, CheckpointSupport, FeatureFlags, TurnObserver};
+use autohands_skills_dynamic::SkillRegistry;
 
 /// Get the default PID file path.
 pub(crate) fn default_pid_file() -> PathBuf {
@@ -21,6 +42,11 @@ pub(crate) fn autohands_dir() -> PathBuf {
         .unwrap_or_else(|| PathBuf::from(".autohands"))
 }
 
+/// Directory of YAML workflow definitions (`~/.autohands/workflows/`).
+pub(crate) fn workflows_dir() -> PathBuf {
+    autohands_dir().join("workflows")
+}
+
 /// Adapter: bridges CheckpointManager to CheckpointSupport trait.
 ///
 /// CheckpointManager stores messages as serde_json::Value, while CheckpointSupport
@@ -66,25 +92,54 @@ impl CheckpointSupport for CheckpointAdapter {
     }
 }
 
+/// Adapter: posts a `turn_completed` event to the callback of the task
+/// running the session.
+pub(crate) struct CallbackTurnObserver {
+    pub callbacks: Arc<CallbackDispatcher>,
+}
+
+#[async_trait::async_trait]
+impl TurnObserver for CallbackTurnObserver {
+    async fn on_turn_completed(&self, session_id: &str, turn: u32, message: &Message) {
+        self.callbacks.emit_for_session(
+            session_id,
+            CallbackEventKind::TurnCompleted,
+            serde_json::json!({
+                "turn": turn,
+                "text": message.content.text(),
+            }),
+        );
+    }
+}
+
 /// Wraps an AgentEventHandler to add metrics instrumentation.
 pub(crate) struct MetricsWrappedHandler {
     pub inner: Arc<autohands_runloop::RuntimeAgentEventHandler>,
     pub metrics: Arc<MetricsRegistry>,
+    pub stats: Arc<DashboardStats>,
     pub active_count: std::sync::atomic::AtomicU64,
 }
 
 impl MetricsWrappedHandler {
     /// Record task outcome: distinguishes Ok(AgentResult { error: Some }) as failed.
-    async fn record_outcome(&self, result: &autohands_runloop::RunLoopResult<autohands_runloop::AgentResult>) {
+    async fn record_outcome(
+        &self,
+        task: &autohands_runloop::Task,
+        result: &autohands_runloop::RunLoopResult<autohands_runloop::AgentResult>,
+    ) {
         match result {
             Ok(agent_result) if agent_result.error.is_some() => {
                 self.metrics.inc_counter("autohands_tasks_failed").await;
+                if let Some(ref error) = agent_result.error {
+                    self.stats.record_failure(&task.task_type, error);
+                }
             }
             Ok(_) => {
                 self.metrics.inc_counter("autohands_tasks_completed").await;
             }
-            Err(_) => {
+            Err(e) => {
                 self.metrics.inc_counter("autohands_tasks_failed").await;
+                self.stats.record_failure(&task.task_type, e.to_string());
             }
         }
     }
@@ -107,7 +162,7 @@ impl autohands_runloop::AgentEventHandler for MetricsWrappedHandler {
 
         let active = self.active_count.fetch_sub(1, Ordering::SeqCst) - 1;
         self.metrics.set_gauge("autohands_active_sessions", active).await;
-        self.record_outcome(&result).await;
+        self.record_outcome(task, &result).await;
         result
     }
 
@@ -118,7 +173,7 @@ impl autohands_runloop::AgentEventHandler for MetricsWrappedHandler {
     ) -> autohands_runloop::RunLoopResult<autohands_runloop::AgentResult> {
         self.metrics.inc_counter("autohands_requests_total").await;
         let result = self.inner.handle_subtask(task, injector).await;
-        self.record_outcome(&result).await;
+        self.record_outcome(task, &result).await;
         result
     }
 
@@ -129,7 +184,478 @@ impl autohands_runloop::AgentEventHandler for MetricsWrappedHandler {
     ) -> autohands_runloop::RunLoopResult<autohands_runloop::AgentResult> {
         self.metrics.inc_counter("autohands_requests_total").await;
         let result = self.inner.handle_delayed(task, injector).await;
-        self.record_outcome(&result).await;
+        self.record_outcome(task, &result).await;
         result
     }
 }
+
+/// Agent handler of a server without LLM providers: every run fails with
+/// setup instructions, which are sent back to the channel it came from.
+pub(crate) struct NoProviderHandler;
+
+impl NoProviderHandler {
+    fn refuse() -> autohands_runloop::RunLoopResult<autohands_runloop::AgentResult> {
+        Ok(autohands_runloop::AgentResult {
+            response: Some(format!(
+                "{}. {}.",
+                autohands_api::NO_PROVIDER_ERROR,
+                autohands_api::PROVIDER_SETUP
+            )),
+            tasks: Vec::new(),
+            is_complete: true,
+            error: Some(autohands_api::NO_PROVIDER_ERROR.to_string()),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl autohands_runloop::AgentEventHandler for NoProviderHandler {
+    async fn handle_execute(
+        &self,
+        _task: &autohands_runloop::Task,
+        _injector: &autohands_runloop::AgentTaskInjector,
+    ) -> autohands_runloop::RunLoopResult<autohands_runloop::AgentResult> {
+        Self::refuse()
+    }
+
+    async fn handle_subtask(
+        &self,
+        _task: &autohands_runloop::Task,
+        _injector: &autohands_runloop::AgentTaskInjector,
+    ) -> autohands_runloop::RunLoopResult<autohands_runloop::AgentResult> {
+        Self::refuse()
+    }
+
+    async fn handle_delayed(
+        &self,
+        _task: &autohands_runloop::Task,
+        _injector: &autohands_runloop::AgentTaskInjector,
+    ) -> autohands_runloop::RunLoopResult<autohands_runloop::AgentResult> {
+        Self::refuse()
+    }
+}
+
+/// Adapter: exposes the skill and tool registries and the task template store
+/// to the web channel admin pages.
+pub(crate) struct WebAdminAdapter {
+    pub tool_registry: Arc<ToolRegistry>,
+    pub skill_registry: Arc<SkillRegistry>,
+    pub template_store: Arc<dyn TemplateStore>,
+}
+
+#[async_trait::async_trait]
+impl AdminBackend for WebAdminAdapter {
+    async fn list_skills(&self) -> Vec<SkillDefinition> {
+        self.skill_registry.list().await
+    }
+
+    async fn get_skill(&self, id: &str) -> Option<Skill> {
+        self.skill_registry.get(id).await
+    }
+
+    async fn set_skill_enabled(&self, id: &str, enabled: bool) -> bool {
+        self.skill_registry.set_enabled(id, enabled).await
+    }
+
+    async fn list_tools(&self) -> Vec<ToolSummary> {
+        self.tool_registry
+            .list_all()
+            .into_iter()
+            .map(|def| {
+                let usage = self.tool_registry.usage(&def.id);
+                ToolSummary {
+                    enabled: self.tool_registry.is_enabled(&def.id),
+                    id: def.id,
+                    name: def.name,
+                    description: def.description,
+                    risk_level: def.risk_level,
+                    calls: usage.calls,
+                    errors: usage.errors,
+                    last_used: usage.last_used,
+                }
+            })
+            .collect()
+    }
+
+    async fn set_tool_enabled(&self, id: &str, enabled: bool) -> bool {
+        self.tool_registry.set_enabled(id, enabled).is_ok()
+    }
+
+    async fn list_templates(&self) -> Vec<TemplateSummary> {
+        self.template_store
+            .load_all()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|t| TemplateSummary {
+                id: t.id,
+                name: t.name,
+                description: t.description,
+                prompt: t.prompt,
+                variables: t.variables,
+                agent: t.agent,
+            })
+            .collect()
+    }
+
+    async fn render_template(
+        &self,
+        id: &str,
+        variables: &HashMap<String, String>,
+    ) -> Option<Result<String, String>> {
+        let template = self.template_store.load(id).await.ok()??;
+        Some(template.render(variables).map_err(|e| e.to_string()))
+    }
+
+    async fn delete_template(&self, id: &str) -> bool {
+        self.template_store.delete(id).await.unwrap_or(false)
+    }
+}
+
+/// Wraps an LLM provider to record latency and token usage for the dashboard.
+pub(crate) struct MeteredProvider {
+    inner: Arc<dyn LLMProvider>,
+    stats: Arc<DashboardStats>,
+}
+
+impl MeteredProvider {
+    /// Replace every registered provider with a metered wrapper.
+    pub(crate) fn instrument_all(registry: &ProviderRegistry, stats: &Arc<DashboardStats>) {
+        for id in registry.list_ids() {
+            let Some(inner) = registry.get(&id) else { continue };
+            let metered = Arc::new(MeteredProvider { inner, stats: stats.clone() });
+            if registry.unregister(&id).is_ok() {
+                let _ = registry.register(metered);
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl LLMProvider for MeteredProvider {
+    fn id(&self) -> &str {
+        self.inner.id()
+    }
+
+    fn models(&self) -> &[ModelDefinition] {
+        self.inner.models()
+    }
+
+    fn capabilities(&self) -> &ProviderCapabilities {
+        self.inner.capabilities()
+    }
+
+    async fn complete(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<CompletionResponse, ProviderError> {
+        let started = Instant::now();
+        let result = self.inner.complete(request).await;
+        self.stats.record_provider_latency(self.id(), started.elapsed(), result.is_ok());
+        match &result {
+            Ok(response) => self
+                .stats
+                .record_tokens(response.usage.prompt_tokens, response.usage.completion_tokens),
+            Err(e) => self.stats.record_failure(self.id(), e.to_string()),
+        }
+        result
+    }
+
+    /// Latency for streams is the time until the stream is established.
+    async fn complete_stream(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<CompletionStream, ProviderError> {
+        let started = Instant::now();
+        let result = self.inner.complete_stream(request).await;
+        self.stats.record_provider_latency(self.id(), started.elapsed(), result.is_ok());
+        let stream = match result {
+            Ok(stream) => stream,
+            Err(e) => {
+                self.stats.record_failure(self.id(), e.to_string());
+                return Err(e);
+            }
+        };
+        let stats = self.stats.clone();
+        Ok(Box::pin(stream.inspect(move |chunk| {
+            if let Ok(chunk) = chunk
+                && let Some(ref usage) = chunk.usage
+            {
+                stats.record_tokens(usage.prompt_tokens, usage.completion_tokens);
+            }
+        })))
+    }
+
+    async fn count_tokens(&self, messages: &[Message], model: &str) -> Result<u32, ProviderError> {
+        self.inner.count_tokens(messages, model).await
+    }
+}
+
+/// Wraps an LLM provider to refuse requests while its `provider:<id>` feature
+/// flag is off for the requesting workspace.
+pub(crate) struct FeatureGatedProvider {
+    inner: Arc<dyn LLMProvider>,
+    flags: Arc<FeatureFlags>,
+}
+
+impl FeatureGatedProvider {
+    /// Replace every registered provider with a gated wrapper.
+    pub(crate) fn gate_all(registry: &ProviderRegistry, flags: &Arc<FeatureFlags>) {
+        for id in registry.list_ids() {
+            let Some(inner) = registry.get(&id) else { continue };
+            let gated = Arc::new(FeatureGatedProvider { inner, flags: flags.clone() });
+            if registry.unregister(&id).is_ok() {
+                let _ = registry.register(gated);
+            }
+        }
+    }
+
+    fn check(&self, request: &CompletionRequest) -> Result<(), ProviderError> {
+        let workspace = request.metadata.get(WORKSPACE_METADATA).and_then(|v| v.as_str());
+        let flag = provider_flag(self.id());
+        if self.flags.is_enabled(&flag, workspace) {
+            Ok(())
+        } else {
+            Err(ProviderError::NotFound(format!(
+                "{} (disabled by feature flag {})",
+                self.id(),
+                flag
+            )))
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl LLMProvider for FeatureGatedProvider {
+    fn id(&self) -> &str {
+        self.inner.id()
+    }
+
+    fn models(&self) -> &[ModelDefinition] {
+        self.inner.models()
+    }
+
+    fn capabilities(&self) -> &ProviderCapabilities {
+        self.inner.capabilities()
+    }
+
+    async fn complete(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<CompletionResponse, ProviderError> {
+        self.check(&request)?;
+        self.inner.complete(request).await
+    }
+
+    async fn complete_stream(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<CompletionStream, ProviderError> {
+        self.check(&request)?;
+        self.inner.complete_stream(request).await
+    }
+
+    async fn count_tokens(&self, messages: &[Message], model: &str) -> Result<u32, ProviderError> {
+        self.inner.count_tokens(messages, model).await
+    }
+}
+
+/// Adapter: takes the backups of backup jobs.
+///
+/// Backups are written to the job's directory, or `~/.autohands/backups/`,
+/// keeping the job's most recent ones. Encrypted backups use the passphrase
+/// in `AUTOHANDS_BACKUP_PASSPHRASE`.
+pub(crate) struct StateBackupRunner;
+
+#[async_trait::async_trait]
+impl BackupRunner for StateBackupRunner {
+    async fn backup(&self, _job_id: &str, backup: &BackupJob) -> Result<String, String> {
+        let dir = backup
+            .dir
+            .as_deref()
+            .map(|dir| PathBuf::from(ConfigLoader::expand_path(dir)))
+            .unwrap_or_else(crate::cmd_backup::backups_dir);
+        let passphrase = if backup.encrypt {
+            let passphrase = std::env::var(crate::cmd_backup::BACKUP_PASSPHRASE_ENV)
+                .ok()
+                .filter(|p| !p.is_empty())
+                .ok_or_else(|| {
+                    format!("{} is not set", crate::cmd_backup::BACKUP_PASSPHRASE_ENV)
+                })?;
+            Some(passphrase)
+        } else {
+            None
+        };
+        let options = autohands_daemon::BackupOptions {
+            exclude: backup.exclude.clone(),
+            passphrase,
+            config_file: None,
+        };
+        let keep = backup.keep;
+
+        tokio::task::spawn_blocking(move || {
+            let output = dir.join(backup_file_name(chrono::Utc::now()));
+            let summary = create_backup(&autohands_dir(), &output, &options)?;
+            let pruned = if keep > 0 { prune_backups(&dir, keep)? } else { 0 };
+            Ok::<_, autohands_daemon::DaemonError>(format!(
+                "Backed up {} file(s) to {} ({} old backup(s) pruned)",
+                summary.files,
+                output.display(),
+                pruned
+            ))
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+}
+
+/// Adapter: feeds the web channel dashboard.
+pub(crate) struct DashboardAdapter {
+    pub stats: Arc<DashboardStats>,
+    pub run_loop: Arc<RunLoop>,
+    pub agent_runtime: Arc<AgentRuntime>,
+}
+
+#[async_trait::async_trait]
+impl MetricsSource for DashboardAdapter {
+    async fn snapshot(&self) -> serde_json::Value {
+        let mut snapshot = serde_json::to_value(self.stats.snapshot()).unwrap_or_default();
+        if let Some(obj) = snapshot.as_object_mut() {
+            obj.insert("queue_depth".to_string(), self.run_loop.pending_task_count().await.into());
+            obj.insert(
+                "queue_depths".to_string(),
+                serde_json::to_value(self.run_loop.metrics().queue_depths()).unwrap_or_default(),
+            );
+            obj.insert(
+                "source_stats".to_string(),
+                serde_json::to_value(self.run_loop.metrics().source_stats()).unwrap_or_default(),
+            );
+            obj.insert(
+                "active_sessions".to_string(),
+                self.agent_runtime.running_sessions().len().into(),
+            );
+        }
+        snapshot
+    }
+}
+
+/// Skill metadata (Progressive Disclosure L1) as a section of the refreshed
+/// agent context.
+pub(crate) struct SkillContextSection {
+    pub injector: autohands_skills_dynamic::SkillMetadataInjector,
+}
+
+#[async_trait::async_trait]
+impl autohands_runtime::ContextSection for SkillContextSection {
+    fn name(&self) -> &str {
+        "skills"
+    }
+
+    async fn render(&self) -> Result<String, String> {
+        Ok(self.injector.generate_system_prompt_section().await.trim().to_string())
+    }
+}
+
+/// Adapter: lists the dynamic skills in the capability manifest.
+pub(crate) struct SkillCatalogAdapter {
+    pub skill_registry: Arc<SkillRegistry>,
+}
+
+#[async_trait::async_trait]
+impl autohands_api::SkillCatalog for SkillCatalogAdapter {
+    async fn list_skills(&self) -> Vec<SkillDefinition> {
+        self.skill_registry.list().await
+    }
+}
+
+/// Adapter: lists the sub-agents spawned through the agent tools in task
+/// trees.
+pub(crate) struct SpawnedAgentDirectory {
+    pub manager: Arc<autohands_tools_agent::AgentManager>,
+}
+
+impl autohands_api::SubAgentDirectory for SpawnedAgentDirectory {
+    fn spawned_by(&self, session_id: &str) -> Vec<autohands_api::SubAgentNode> {
+        let mut agents = self.manager.list_by_parent(session_id);
+        agents.sort_by_key(|agent| agent.spawned_at);
+        agents
+            .into_iter()
+            .map(|agent| autohands_api::SubAgentNode {
+                status: serde_json::to_value(agent.status)
+                    .ok()
+                    .and_then(|status| status.as_str().map(str::to_string))
+                    .unwrap_or_default(),
+                id: agent.id,
+                agent_id: agent.agent_id,
+                session_id: agent.session_id,
+                task: agent.task,
+                created_at: agent.spawned_at,
+                finished_at: agent.completed_at,
+                error: agent.error,
+            })
+            .collect()
+    }
+}
+
+/// Adapter: feeds the web channel's task trees page.
+pub(crate) struct TaskTreeAdapter {
+    pub run_loop: Arc<RunLoop>,
+    pub agent_runtime: Arc<AgentRuntime>,
+    pub transcript_manager: Arc<autohands_runtime::TranscriptManager>,
+    pub sub_agents: Option<Arc<dyn autohands_api::SubAgentDirectory>>,
+}
+
+#[async_trait::async_trait]
+impl autohands_channel_web::TaskTreeSource for TaskTreeAdapter {
+    async fn recent(&self, limit: usize) -> Vec<serde_json::Value> {
+        self.run_loop
+            .task_graph()
+            .roots(limit)
+            .into_iter()
+            .map(|node| serde_json::to_value(node).unwrap_or_default())
+            .collect()
+    }
+
+    async fn tree(&self, id: &str) -> Option<serde_json::Value> {
+        let graph = self.run_loop.task_graph();
+        let tree = autohands_api::build_task_tree(
+            &autohands_api::TaskTreeSources {
+                graph: &graph,
+                runtime: &self.agent_runtime,
+                transcripts: &self.transcript_manager,
+                sub_agents: self.sub_agents.as_ref(),
+            },
+            id,
+        )?;
+        serde_json::to_value(tree).ok()
+    }
+}
+
+/// Fetches the pages of watch jobs over HTTP.
+pub(crate) struct HttpPageFetcher {
+    client: reqwest::Client,
+}
+
+impl HttpPageFetcher {
+    pub(crate) fn new() -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .user_agent(concat!("AutoHands/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .unwrap_or_default();
+        Self { client }
+    }
+}
+
+#[async_trait::async_trait]
+impl PageFetcher for HttpPageFetcher {
+    async fn fetch(&self, url: &str) -> Result<String, String> {
+        let response = self.client.get(url).send().await.map_err(|e| e.to_string())?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("{} returned HTTP {}", url, status));
+        }
+        response.text().await.map_err(|e| e.to_string())
+    }
+}
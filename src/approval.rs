@@ -0,0 +1,183 @@
+//! Human approval wiring: the approval service and its notifiers.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tracing::{info, warn};
+
+use autohands_config::ApprovalConfig;
+use autohands_core::ApprovalService;
+use autohands_protocols::approval::{
+    ApprovalNotifier, ApprovalRequest, ApprovalTimeoutPolicy, ToolApprovalPolicy,
+};
+use autohands_protocols::error::ChannelError;
+use autohands_protocols::types::RiskLevel;
+use autohands_runloop::{CallbackDispatcher, CallbackEventKind};
+
+/// Posts each new approval request as JSON to a webhook.
+pub(crate) struct WebhookApprovalNotifier {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookApprovalNotifier {
+    pub(crate) fn new(url: impl Into<String>) -> Self {
+        Self { client: reqwest::Client::new(), url: url.into() }
+    }
+}
+
+#[async_trait::async_trait]
+impl ApprovalNotifier for WebhookApprovalNotifier {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    async fn notify(&self, request: &ApprovalRequest) -> Result<(), ChannelError> {
+        post(&self.client, &self.url, &serde_json::json!(request)).await
+    }
+}
+
+/// Announces new approval requests in Slack through an incoming webhook.
+///
+/// Incoming webhooks cannot carry interactive buttons, so the message links
+/// to the approvals page where the request is decided.
+pub(crate) struct SlackApprovalNotifier {
+    client: reqwest::Client,
+    url: String,
+    approvals_page: String,
+}
+
+impl SlackApprovalNotifier {
+    pub(crate) fn new(url: impl Into<String>, approvals_page: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: url.into(),
+            approvals_page: approvals_page.into(),
+        }
+    }
+
+    fn message(&self, request: &ApprovalRequest) -> String {
+        let mut text = format!(
+            ":raised_hand: *Approval needed:* {}\nSource: `{}` (risk: {:?})",
+            request.title, request.source, request.risk_level
+        );
+        if let Some(description) = &request.description {
+            text.push_str(&format!("\n{}", description));
+        }
+        if let Some(secs) = request.timeout_secs {
+            text.push_str(&format!(
+                "\nTimes out in {}s ({:?} on timeout)",
+                secs, request.on_timeout
+            ));
+        }
+        text.push_str(&format!(
+            "\nDecide at {} (request `{}`)",
+            self.approvals_page, request.id
+        ));
+        text
+    }
+}
+
+#[async_trait::async_trait]
+impl ApprovalNotifier for SlackApprovalNotifier {
+    fn name(&self) -> &str {
+        "slack"
+    }
+
+    async fn notify(&self, request: &ApprovalRequest) -> Result<(), ChannelError> {
+        let body = serde_json::json!({"text": self.message(request)});
+        post(&self.client, &self.url, &body).await
+    }
+}
+
+/// Posts a `needs_approval` event to the callback of the task whose agent
+/// session asked for approval.
+pub(crate) struct CallbackApprovalNotifier {
+    callbacks: Arc<CallbackDispatcher>,
+}
+
+impl CallbackApprovalNotifier {
+    pub(crate) fn new(callbacks: Arc<CallbackDispatcher>) -> Self {
+        Self { callbacks }
+    }
+}
+
+#[async_trait::async_trait]
+impl ApprovalNotifier for CallbackApprovalNotifier {
+    fn name(&self) -> &str {
+        "task_callback"
+    }
+
+    async fn notify(&self, request: &ApprovalRequest) -> Result<(), ChannelError> {
+        if let Some(session_id) = &request.session_id {
+            self.callbacks.emit_for_session(
+                session_id,
+                CallbackEventKind::NeedsApproval,
+                serde_json::json!(request),
+            );
+        }
+        Ok(())
+    }
+}
+
+async fn post(
+    client: &reqwest::Client,
+    url: &str,
+    body: &serde_json::Value,
+) -> Result<(), ChannelError> {
+    let resp = client
+        .post(url)
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| ChannelError::SendFailed(e.to_string()))?;
+    if !resp.status().is_success() {
+        return Err(ChannelError::SendFailed(format!("HTTP {}", resp.status())));
+    }
+    Ok(())
+}
+
+/// Build the approval service from config.
+///
+/// `approvals_page` is the web UI address linked from Slack messages.
+/// Requests from agent sessions are also posted to their task's callback.
+pub(crate) fn build_approval_service(
+    config: &ApprovalConfig,
+    approvals_page: &str,
+    callbacks: Arc<CallbackDispatcher>,
+) -> ApprovalService {
+    let policy = serde_json::from_value::<ApprovalTimeoutPolicy>(serde_json::Value::String(
+        config.on_timeout.clone(),
+    ))
+    .unwrap_or_default();
+    let mut service = ApprovalService::new()
+        .with_default_timeout(Duration::from_secs(config.timeout_secs))
+        .with_timeout_policy(policy)
+        .with_notifier(Arc::new(CallbackApprovalNotifier::new(callbacks)));
+    if let Some(url) = &config.webhook_url {
+        service = service.with_notifier(Arc::new(WebhookApprovalNotifier::new(url)));
+        info!("Approval requests will be posted to webhook");
+    }
+    if let Some(url) = &config.slack_webhook_url {
+        service = service.with_notifier(Arc::new(SlackApprovalNotifier::new(url, approvals_page)));
+        info!("Approval requests will be announced in Slack");
+    }
+    service
+}
+
+/// Policy of tool calls, if any: `[approval.tool_policy]`, or approval of
+/// those at or above `tool_risk_threshold`.
+pub(crate) fn tool_approval_policy(config: &ApprovalConfig) -> Option<ToolApprovalPolicy> {
+    if let Some(policy) = &config.tool_policy {
+        // A policy that does not parse must not let everything run
+        let parsed = serde_json::to_value(policy).and_then(serde_json::from_value);
+        return Some(parsed.unwrap_or_else(|e| {
+            warn!("Invalid [approval.tool_policy] ({}), using the default policy", e);
+            ToolApprovalPolicy::default()
+        }));
+    }
+    let level = config.tool_risk_threshold.as_ref()?;
+    let min_risk: RiskLevel =
+        serde_json::from_value(serde_json::Value::String(level.clone())).ok()?;
+    Some(ToolApprovalPolicy::threshold(min_risk))
+}
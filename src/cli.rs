@@ -2,7 +2,7 @@
 
 use std::path::PathBuf;
 
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 
 /// AutoHands CLI.
 #[derive(Parser)]
@@ -14,10 +14,20 @@ pub(crate) struct Cli {
     #[arg(short, long, default_value = "config/default.toml", global = true)]
     pub config: PathBuf,
 
+    /// Configuration profile (`[profile.<name>]` of the file) to apply
+    /// (default: AUTOHANDS_PROFILE)
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
+
     /// Working directory
     #[arg(short, long, global = true)]
     pub work_dir: Option<PathBuf>,
 
+    /// Override a configuration key, e.g. `--set server.port=9000`
+    /// (repeatable; takes precedence over the file and AUTOHANDS_* variables)
+    #[arg(long = "set", value_name = "KEY=VALUE", value_parser = autohands_config::parse_override, global = true)]
+    pub set: Vec<(String, String)>,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -26,17 +36,33 @@ pub(crate) struct Cli {
 pub(crate) enum Commands {
     /// Run the server in foreground (default)
     Run {
-        /// Server host
-        #[arg(long, default_value = "127.0.0.1")]
-        host: String,
+        /// Server host (default: from config)
+        #[arg(long)]
+        host: Option<String>,
 
-        /// Server port (API)
-        #[arg(long, default_value_t = 8080)]
-        port: u16,
+        /// Server port (API, default: from config)
+        #[arg(long)]
+        port: Option<u16>,
 
         /// Web channel port (WebSocket UI)
         #[arg(long, default_value_t = 8081)]
         web_port: u16,
+
+        /// Run every tool call without approval (trusted environments only)
+        #[arg(long)]
+        yolo: bool,
+    },
+
+    /// Configuration commands
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+
+    /// Encrypted secrets file commands
+    Secrets {
+        #[command(subcommand)]
+        action: SecretsAction,
     },
 
     /// Daemon management commands
@@ -50,6 +76,388 @@ pub(crate) enum Commands {
         #[command(subcommand)]
         action: SkillAction,
     },
+
+    /// Task template commands
+    Template {
+        #[command(subcommand)]
+        action: TemplateAction,
+    },
+
+    /// Workflow commands
+    Workflow {
+        #[command(subcommand)]
+        action: WorkflowAction,
+    },
+
+    /// Session export and import
+    Session {
+        #[command(subcommand)]
+        action: SessionAction,
+    },
+
+    /// Desktop automation interlock: stop or allow the mouse and keyboard
+    /// tools of a running server
+    Desktop {
+        #[command(subcommand)]
+        action: DesktopAction,
+    },
+
+    /// RunLoop diagnostics of a running server
+    Runloop {
+        #[command(subcommand)]
+        action: RunloopAction,
+    },
+
+    /// Update to the latest release (checksum and signature verified)
+    SelfUpdate(SelfUpdateArgs),
+
+    /// Backup and restore of the state directory (~/.autohands)
+    Backup {
+        #[command(subcommand)]
+        action: BackupAction,
+    },
+
+    /// Remove orphaned browser profiles, stale temp files, expired
+    /// artifacts and old debug logs
+    Gc {
+        /// Only report what would be removed
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Output format (table, json)
+        #[arg(long, default_value = "table")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub(crate) enum ConfigAction {
+    /// Print the configuration with secrets redacted
+    Show {
+        /// Print the effective configuration: defaults, file, AUTOHANDS_*
+        /// environment variables and --set overrides merged
+        #[arg(long)]
+        resolved: bool,
+    },
+    /// Check a configuration file for syntax errors, unknown keys and
+    /// invalid values
+    Validate {
+        /// File to check (defaults to --config)
+        file: Option<PathBuf>,
+    },
+    /// Print the JSON Schema of the configuration file
+    Schema,
+}
+
+#[derive(Subcommand)]
+pub(crate) enum SecretsAction {
+    /// Store a secret read from stdin, referenced as `secret://file/<NAME>`
+    Set {
+        /// Secret name
+        name: String,
+
+        /// Passphrase of the secrets file
+        #[arg(long, env = autohands_config::SECRETS_PASSPHRASE_ENV, hide_env_values = true)]
+        passphrase: String,
+    },
+
+    /// Remove a secret
+    Remove {
+        /// Secret name
+        name: String,
+
+        /// Passphrase of the secrets file
+        #[arg(long, env = autohands_config::SECRETS_PASSPHRASE_ENV, hide_env_values = true)]
+        passphrase: String,
+    },
+
+    /// List secret names
+    List {
+        /// Passphrase of the secrets file
+        #[arg(long, env = autohands_config::SECRETS_PASSPHRASE_ENV, hide_env_values = true)]
+        passphrase: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub(crate) enum BackupAction {
+    /// Snapshot the state directory and configuration into one file
+    Create {
+        /// Output file (default: ~/.autohands/backups/autohands-<timestamp>.backup)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Glob pattern of state paths to leave out (repeatable), e.g. "sessions"
+        #[arg(long)]
+        exclude: Vec<String>,
+
+        /// Encrypt with this passphrase
+        #[arg(long, env = "AUTOHANDS_BACKUP_PASSPHRASE", hide_env_values = true)]
+        passphrase: Option<String>,
+    },
+
+    /// Restore a backup into the state directory
+    Restore {
+        /// Backup file
+        file: PathBuf,
+
+        /// Replace existing files
+        #[arg(long)]
+        overwrite: bool,
+
+        /// Passphrase of an encrypted backup
+        #[arg(long, env = "AUTOHANDS_BACKUP_PASSPHRASE", hide_env_values = true)]
+        passphrase: Option<String>,
+
+        /// Do not restore the configuration file
+        #[arg(long)]
+        skip_config: bool,
+
+        /// PID file of the daemon, which must not be running
+        #[arg(long)]
+        pid_file: Option<PathBuf>,
+    },
+}
+
+#[derive(Args)]
+pub(crate) struct SelfUpdateArgs {
+    /// Only report whether an update is available
+    #[arg(long)]
+    pub check: bool,
+
+    /// Install the latest release even if it is not newer
+    #[arg(long)]
+    pub force: bool,
+
+    /// Release manifest URL
+    #[arg(
+        long,
+        env = "AUTOHANDS_UPDATE_MANIFEST",
+        default_value = crate::cmd_update::DEFAULT_MANIFEST_URL
+    )]
+    pub manifest_url: String,
+
+    /// Base64 Ed25519 release public key, instead of the built-in one
+    #[arg(long, env = "AUTOHANDS_UPDATE_PUBLIC_KEY")]
+    pub public_key: Option<String>,
+
+    /// Install with only the checksum verified when there is no public key
+    #[arg(long)]
+    pub allow_unsigned: bool,
+
+    /// Drain the running server, then restart the daemon on the new binary
+    #[arg(long)]
+    pub restart: bool,
+
+    /// PID file path of the daemon to restart
+    #[arg(long)]
+    pub pid_file: Option<PathBuf>,
+
+    /// Server URL to drain (default: from config)
+    #[arg(long)]
+    pub server: Option<String>,
+}
+
+#[derive(Subcommand)]
+pub(crate) enum SessionAction {
+    /// Export a session bundle (transcript, artifacts, memories)
+    Export {
+        /// Session ID
+        session_id: String,
+
+        /// Output file (default: session-<id>.json)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Server URL (default: from config)
+        #[arg(long)]
+        server: Option<String>,
+    },
+
+    /// Import a session bundle
+    Import {
+        /// Bundle file
+        file: PathBuf,
+
+        /// Replace an existing session with the same ID
+        #[arg(long)]
+        overwrite: bool,
+
+        /// Server URL (default: from config)
+        #[arg(long)]
+        server: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub(crate) enum RunloopAction {
+    /// Dump the event journal: source triggers, phase transitions and
+    /// dispatch decisions (requires runloop.journal = true)
+    Events {
+        /// Only events since this time: RFC 3339, or an age such as 30s,
+        /// 15m, 2h or 1d
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Only events of this kind (e.g. dispatched, rejected, wakeup)
+        #[arg(long)]
+        kind: Option<String>,
+
+        /// Only the most recent events, this many
+        #[arg(long)]
+        limit: Option<usize>,
+
+        /// Output format (table, json)
+        #[arg(long, default_value = "table")]
+        format: String,
+
+        /// Server URL (default: from config)
+        #[arg(long)]
+        server: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub(crate) enum DesktopAction {
+    /// Stop all mouse and keyboard tools until resumed
+    Suspend {
+        /// Why, shown to agents whose calls are refused
+        #[arg(long)]
+        reason: Option<String>,
+
+        /// Server URL (default: from config)
+        #[arg(long)]
+        server: Option<String>,
+    },
+
+    /// Panic button: stop all mouse and keyboard tools and cancel the
+    /// agent runs using them; for binding to a global hotkey
+    Abort {
+        /// Why, logged and shown in the status
+        #[arg(long)]
+        reason: Option<String>,
+
+        /// Server URL (default: from config)
+        #[arg(long)]
+        server: Option<String>,
+    },
+
+    /// Let the mouse and keyboard tools run again
+    Resume {
+        /// Server URL (default: from config)
+        #[arg(long)]
+        server: Option<String>,
+    },
+
+    /// Suspend if running, resume if suspended; for binding to a global
+    /// hotkey or menu-bar item
+    Toggle {
+        /// Server URL (default: from config)
+        #[arg(long)]
+        server: Option<String>,
+    },
+
+    /// Show whether the mouse and keyboard tools are suspended
+    Status {
+        /// Server URL (default: from config)
+        #[arg(long)]
+        server: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub(crate) enum WorkflowAction {
+    /// List workflow files and registered workflows
+    List {
+        /// Output format (table, json)
+        #[arg(long, default_value = "table")]
+        format: String,
+    },
+
+    /// Validate workflow YAML files
+    Validate {
+        /// Files or directories (default: ~/.autohands/workflows)
+        paths: Vec<PathBuf>,
+    },
+
+    /// Run a workflow on a running server
+    Run {
+        /// Workflow ID, or path to a YAML file to register first
+        workflow: String,
+
+        /// Input value as KEY=VALUE (repeatable; JSON values are parsed)
+        #[arg(long = "input")]
+        inputs: Vec<String>,
+
+        /// Server URL (default: from config)
+        #[arg(long)]
+        server: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub(crate) enum TemplateAction {
+    /// List saved templates
+    List {
+        /// Output format (table, json)
+        #[arg(long, default_value = "table")]
+        format: String,
+    },
+
+    /// Show a template
+    Show {
+        /// Template ID
+        template_id: String,
+    },
+
+    /// Save a template (replaces an existing one with the same ID)
+    Add {
+        /// Template ID
+        template_id: String,
+
+        /// Prompt with {variable} placeholders
+        prompt: String,
+
+        /// Display name (default: the ID)
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// Default agent profile
+        #[arg(short, long)]
+        agent: Option<String>,
+
+        /// Description
+        #[arg(short, long)]
+        description: Option<String>,
+
+        /// Required variable (repeatable; default: all placeholders)
+        #[arg(long = "var")]
+        variables: Vec<String>,
+    },
+
+    /// Delete a template
+    Delete {
+        /// Template ID
+        template_id: String,
+    },
+
+    /// Submit a template to a running server
+    Run {
+        /// Template ID
+        template_id: String,
+
+        /// Variable value as KEY=VALUE (repeatable)
+        #[arg(long = "var")]
+        variables: Vec<String>,
+
+        /// Agent to use instead of the template's default
+        #[arg(short, long)]
+        agent: Option<String>,
+
+        /// Server URL (default: from config)
+        #[arg(long)]
+        server: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
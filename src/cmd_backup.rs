@@ -0,0 +1,74 @@
+//! Backup subcommand handlers for AutoHands.
+
+use std::path::{Path, PathBuf};
+
+use autohands_daemon::backup::{backup_file_name, create_backup, restore_backup};
+use autohands_daemon::{BackupOptions, PidFile, RestoreOptions};
+
+use crate::adapters::{autohands_dir, default_pid_file};
+use crate::cli::BackupAction;
+
+/// Environment variable holding the passphrase of encrypted backups.
+pub(crate) const BACKUP_PASSPHRASE_ENV: &str = "AUTOHANDS_BACKUP_PASSPHRASE";
+
+/// Directory backups are written to by default (`~/.autohands/backups/`).
+pub(crate) fn backups_dir() -> PathBuf {
+    autohands_dir().join("backups")
+}
+
+/// Handle backup subcommands.
+pub(crate) async fn handle_backup_command(
+    action: BackupAction,
+    config_file: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match action {
+        BackupAction::Create { output, exclude, passphrase } => {
+            let output =
+                output.unwrap_or_else(|| backups_dir().join(backup_file_name(chrono::Utc::now())));
+            let options = BackupOptions {
+                exclude,
+                passphrase,
+                config_file: Some(config_file.to_path_buf()),
+            };
+            let summary = create_backup(&autohands_dir(), &output, &options)?;
+            println!(
+                "Backed up {} file(s) ({} bytes){}{} to {}",
+                summary.files,
+                summary.bytes,
+                if summary.config { " and the configuration" } else { "" },
+                if summary.encrypted { ", encrypted," } else { "" },
+                output.display()
+            );
+            Ok(())
+        }
+        BackupAction::Restore { file, overwrite, passphrase, skip_config, pid_file } => {
+            let pid_file = PidFile::new(pid_file.unwrap_or_else(default_pid_file));
+            if let Ok(Some(pid)) = pid_file.read_pid() {
+                if PidFile::is_process_running(pid) {
+                    return Err(format!(
+                        "The daemon is running (PID {}); stop it before restoring",
+                        pid
+                    )
+                    .into());
+                }
+            }
+
+            let state_dir = autohands_dir();
+            let options = RestoreOptions {
+                passphrase,
+                config_file: (!skip_config).then(|| config_file.to_path_buf()),
+                overwrite,
+            };
+            let summary = restore_backup(&file, &state_dir, &options)?;
+            println!(
+                "Restored {} file(s) to {}",
+                summary.files,
+                state_dir.display()
+            );
+            if summary.config {
+                println!("Restored the configuration to {}", config_file.display());
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,88 @@
+//! Configuration commands for AutoHands.
+
+use std::path::Path;
+
+use autohands_config::{redacted_file, Config, ConfigLayers, ConfigValidator, Diagnostic};
+
+use crate::cli::ConfigAction;
+
+/// Handle `autohands config` subcommands.
+pub(crate) fn handle_config_command(
+    action: ConfigAction,
+    layers: &ConfigLayers,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match action {
+        ConfigAction::Show { resolved: true } => {
+            // Unlike startup, an invalid layer is an error here
+            let config = layers.resolve()?;
+            match (layers.file(), layers.profile()) {
+                (Some(path), Some(profile)) => println!(
+                    "# Effective configuration: defaults < {} (profile {}) < AUTOHANDS_* < --set",
+                    path.display(),
+                    profile
+                ),
+                (Some(path), None) => println!(
+                    "# Effective configuration: defaults < {} < AUTOHANDS_* < --set",
+                    path.display()
+                ),
+                (None, _) => println!("# Effective configuration: defaults < AUTOHANDS_* < --set"),
+            }
+            print!("{}", config.to_redacted_toml()?);
+        }
+        ConfigAction::Show { resolved: false } => {
+            let Some(path) = layers.file() else {
+                println!("No configuration file; use --resolved for the effective configuration");
+                return Ok(());
+            };
+            println!("# {}", path.display());
+            print!("{}", redacted_file(path)?);
+        }
+        ConfigAction::Validate { file } => {
+            let Some(path) = file.as_deref().or(layers.file()) else {
+                return Err("No configuration file to validate".into());
+            };
+            let validation = ConfigValidator::validate_file(path)?;
+            for diagnostic in &validation.diagnostics {
+                print_diagnostic(path, diagnostic);
+            }
+            if !validation.is_valid() {
+                return Err(format!(
+                    "{}: {} error(s), {} warning(s)",
+                    path.display(),
+                    validation.error_count(),
+                    validation.warning_count()
+                )
+                .into());
+            }
+            println!(
+                "{}: valid ({} warning(s))",
+                path.display(),
+                validation.warning_count()
+            );
+        }
+        ConfigAction::Schema => {
+            println!("{}", serde_json::to_string_pretty(&Config::json_schema())?);
+        }
+    }
+    Ok(())
+}
+
+/// Print a diagnostic with the line it points at.
+fn print_diagnostic(path: &Path, diagnostic: &Diagnostic) {
+    if diagnostic.path.is_empty() {
+        println!("{}: {}", diagnostic.severity, diagnostic.message);
+    } else {
+        println!("{}: {}: {}", diagnostic.severity, diagnostic.path, diagnostic.message);
+    }
+    let (Some(line), Some(column), Some(text)) =
+        (diagnostic.line, diagnostic.column, &diagnostic.source_line)
+    else {
+        println!("  --> {}", path.display());
+        return;
+    };
+    let gutter = " ".repeat(line.to_string().len());
+    println!("{}--> {}:{}:{}", gutter, path.display(), line, column);
+    println!("{} |", gutter);
+    println!("{} | {}", line, text);
+    println!("{} | {}^", gutter, " ".repeat(column - 1));
+}
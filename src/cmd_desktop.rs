@@ -0,0 +1,123 @@
+//! Desktop interlock subcommand handlers for AutoHands.
+//!
+//! The mouse and keyboard tools hold the `desktop-input` resource; these
+//! commands suspend, abort and resume it on a running server.
+
+use autohands_config::Config;
+use autohands_tools_desktop::INPUT_RESOURCE;
+
+use crate::cli::DesktopAction;
+
+/// Handle desktop subcommands.
+pub(crate) async fn handle_desktop_command(
+    action: DesktopAction,
+    config: &Config,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let default_server = || format!("http://{}:{}", config.server.host, config.server.port);
+    let client = reqwest::Client::new();
+    match action {
+        DesktopAction::Suspend { reason, server } => {
+            let server = server.unwrap_or_else(default_server);
+            suspend(&client, &server, reason).await
+        }
+        DesktopAction::Abort { reason, server } => {
+            let server = server.unwrap_or_else(default_server);
+            abort(&client, &server, reason).await
+        }
+        DesktopAction::Resume { server } => {
+            let server = server.unwrap_or_else(default_server);
+            resume(&client, &server).await
+        }
+        DesktopAction::Toggle { server } => {
+            let server = server.unwrap_or_else(default_server);
+            match suspension(&client, &server).await? {
+                Some(_) => resume(&client, &server).await,
+                None => suspend(&client, &server, None).await,
+            }
+        }
+        DesktopAction::Status { server } => {
+            let server = server.unwrap_or_else(default_server);
+            match suspension(&client, &server).await? {
+                Some(suspension) => println!(
+                    "Desktop input suspended since {}: {}",
+                    suspension["since"].as_str().unwrap_or("-"),
+                    suspension["reason"].as_str().unwrap_or("-")
+                ),
+                None => println!("Desktop input enabled"),
+            }
+            Ok(())
+        }
+    }
+}
+
+fn resource_url(server: &str, path: &str) -> String {
+    format!("{}/admin/resources{}", server.trim_end_matches('/'), path)
+}
+
+/// The suspension of the input resource, if suspended.
+async fn suspension(
+    client: &reqwest::Client,
+    server: &str,
+) -> Result<Option<serde_json::Value>, Box<dyn std::error::Error>> {
+    let resp = client.get(resource_url(server, "")).send().await?;
+    let status = resp.status();
+    if !status.is_success() {
+        return Err(format!("Failed to query desktop input ({})", status).into());
+    }
+    let data: serde_json::Value = resp.json().await?;
+    let suspended = data["suspended"].as_array().cloned().unwrap_or_default();
+    Ok(suspended
+        .into_iter()
+        .find(|s| s["resource"] == INPUT_RESOURCE))
+}
+
+async fn suspend(
+    client: &reqwest::Client,
+    server: &str,
+    reason: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let reason = reason.unwrap_or_else(|| "suspended by the user".to_string());
+    let url = resource_url(server, &format!("/{}/suspend", INPUT_RESOURCE));
+    let resp = client
+        .post(&url)
+        .json(&serde_json::json!({ "reason": reason }))
+        .send()
+        .await?;
+    let status = resp.status();
+    if !status.is_success() {
+        return Err(format!("Failed to suspend desktop input ({})", status).into());
+    }
+    println!("Desktop input suspended: mouse and keyboard tools are refused");
+    Ok(())
+}
+
+async fn abort(
+    client: &reqwest::Client,
+    server: &str,
+    reason: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let reason = reason.unwrap_or_else(|| "aborted by the user".to_string());
+    let url = resource_url(server, &format!("/{}/abort", INPUT_RESOURCE));
+    let resp = client
+        .post(&url)
+        .json(&serde_json::json!({ "reason": reason }))
+        .send()
+        .await?;
+    let status = resp.status();
+    if !status.is_success() {
+        return Err(format!("Failed to abort desktop input ({})", status).into());
+    }
+    println!("Desktop input aborted: agent runs using the mouse or keyboard are cancelled");
+    Ok(())
+}
+
+async fn resume(client: &reqwest::Client, server: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let url = resource_url(server, &format!("/{}/resume", INPUT_RESOURCE));
+    let resp = client.post(&url).send().await?;
+    match resp.status() {
+        status if status.is_success() => println!("Desktop input resumed"),
+        reqwest::StatusCode::NOT_FOUND => println!("Desktop input was not suspended"),
+        status => return Err(format!("Failed to resume desktop input ({})", status).into()),
+    }
+    Ok(())
+}
@@ -0,0 +1,48 @@
+//! Garbage collection command for AutoHands.
+
+use autohands_daemon::gc::collect_garbage;
+use autohands_daemon::GcOptions;
+
+use crate::adapters::autohands_dir;
+
+/// Handle `autohands gc`.
+pub(crate) fn handle_gc(dry_run: bool, format: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let options = GcOptions {
+        dry_run,
+        ..GcOptions::new(autohands_dir())
+    };
+    let report = collect_garbage(&options);
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    if report.items.is_empty() {
+        println!("Nothing to collect");
+    } else {
+        println!("{:<16} {:>10}  {:<28} PATH", "KIND", "BYTES", "REASON");
+        println!("{}", "-".repeat(80));
+        for item in &report.items {
+            let kind = serde_json::to_value(item.kind)?;
+            println!(
+                "{:<16} {:>10}  {:<28} {}",
+                kind.as_str().unwrap_or_default(),
+                item.bytes,
+                item.reason,
+                item.path.display()
+            );
+        }
+        println!();
+        println!(
+            "{} {} item(s), {} bytes",
+            if dry_run { "Would remove" } else { "Removed" },
+            report.items.len(),
+            report.bytes
+        );
+    }
+    for error in &report.errors {
+        eprintln!("Failed to remove {}", error);
+    }
+    Ok(())
+}
@@ -0,0 +1,139 @@
+//! RunLoop subcommand handlers for AutoHands.
+
+use autohands_config::Config;
+use chrono::{DateTime, Duration, Utc};
+
+use crate::cli::RunloopAction;
+
+/// Handle runloop subcommands.
+pub(crate) async fn handle_runloop_command(
+    action: RunloopAction,
+    config: &Config,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match action {
+        RunloopAction::Events {
+            since,
+            kind,
+            limit,
+            format,
+            server,
+        } => {
+            let server = server
+                .unwrap_or_else(|| format!("http://{}:{}", config.server.host, config.server.port));
+            let mut query = Vec::new();
+            if let Some(since) = since {
+                query.push(("since", parse_since(&since, Utc::now())?.to_rfc3339()));
+            }
+            if let Some(kind) = kind {
+                query.push(("kind", kind));
+            }
+            if let Some(limit) = limit {
+                query.push(("limit", limit.to_string()));
+            }
+
+            let url = format!("{}/v1/runloop/events", server.trim_end_matches('/'));
+            let resp = reqwest::Client::new()
+                .get(&url)
+                .query(&query)
+                .send()
+                .await?;
+            let status = resp.status();
+            let data: serde_json::Value = resp.json().await.unwrap_or_default();
+            if !status.is_success() {
+                let error = data["error"].as_str().unwrap_or("request failed");
+                return Err(
+                    format!("Failed to read RunLoop events ({}): {}", status, error).into(),
+                );
+            }
+
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&data["events"])?);
+                return Ok(());
+            }
+            print_events(
+                data["events"]
+                    .as_array()
+                    .map(Vec::as_slice)
+                    .unwrap_or_default(),
+            );
+            Ok(())
+        }
+    }
+}
+
+/// `--since` as a time: RFC 3339, or an age such as `15m` before `now`.
+fn parse_since(value: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Ok(at.with_timezone(&Utc));
+    }
+    let invalid = || {
+        format!(
+            "Invalid --since '{}': expected RFC 3339 or an age such as 30s, 15m, 2h or 1d",
+            value
+        )
+    };
+    let split = value.len().saturating_sub(1);
+    let amount: i64 = value
+        .get(..split)
+        .and_then(|n| n.parse().ok())
+        .ok_or_else(invalid)?;
+    let age = match value.get(split..).unwrap_or_default() {
+        "s" => Duration::seconds(amount),
+        "m" => Duration::minutes(amount),
+        "h" => Duration::hours(amount),
+        "d" => Duration::days(amount),
+        _ => return Err(invalid()),
+    };
+    Ok(now - age)
+}
+
+fn print_events(events: &[serde_json::Value]) {
+    if events.is_empty() {
+        println!("No events");
+        return;
+    }
+    println!("{:>8}  {:<24} {:<18} DETAIL", "SEQ", "TIME", "KIND");
+    println!("{}", "-".repeat(80));
+    for event in events {
+        let at = event["at"]
+            .as_str()
+            .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+            .map(|at| {
+                at.with_timezone(&Utc)
+                    .format("%Y-%m-%d %H:%M:%S%.3f")
+                    .to_string()
+            })
+            .unwrap_or_default();
+        println!(
+            "{:>8}  {:<24} {:<18} {}",
+            event["seq"],
+            at,
+            event["kind"].as_str().unwrap_or_default(),
+            detail(event)
+        );
+    }
+}
+
+/// The fields of an event other than its position, time and kind.
+fn detail(event: &serde_json::Value) -> String {
+    let Some(fields) = event.as_object() else {
+        return String::new();
+    };
+    fields
+        .iter()
+        .filter(|(key, _)| !matches!(key.as_str(), "seq" | "at" | "kind"))
+        .map(|(key, value)| match value {
+            serde_json::Value::String(s) => format!("{}={}", key, s),
+            // Tasks are shown by type and id
+            serde_json::Value::Object(task) if key == "task" => format!(
+                "task={}:{}",
+                task.get("task_type")
+                    .and_then(|t| t.as_str())
+                    .unwrap_or_default(),
+                task.get("id").and_then(|t| t.as_str()).unwrap_or_default()
+            ),
+            value => format!("{}={}", key, value),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
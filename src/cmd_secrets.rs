@@ -0,0 +1,44 @@
+//! Encrypted secrets file commands for AutoHands.
+
+use std::io::Read;
+
+use autohands_config::{Config, ConfigLoader, EncryptedFileStore};
+
+use crate::cli::SecretsAction;
+
+/// Handle `autohands secrets` subcommands.
+pub(crate) fn handle_secrets_command(
+    action: SecretsAction,
+    config: &Config,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let path = ConfigLoader::expand_path(&config.secrets.file.to_string_lossy());
+    match action {
+        SecretsAction::Set { name, passphrase } => {
+            let mut value = String::new();
+            std::io::stdin().read_to_string(&mut value)?;
+            let value = value.strip_suffix('\n').unwrap_or(&value);
+            if value.is_empty() {
+                return Err("No secret on stdin".into());
+            }
+            EncryptedFileStore::new(&path, passphrase).set(&name, value)?;
+            println!("Stored secret://file/{} in {}", name, path);
+        }
+        SecretsAction::Remove { name, passphrase } => {
+            if EncryptedFileStore::new(&path, passphrase).remove(&name)? {
+                println!("Removed {}", name);
+            } else {
+                println!("No secret named {}", name);
+            }
+        }
+        SecretsAction::List { passphrase } => {
+            let secrets = EncryptedFileStore::new(&path, passphrase).load()?;
+            if secrets.is_empty() {
+                println!("No secrets in {}", path);
+            }
+            for name in secrets.keys() {
+                println!("secret://file/{}", name);
+            }
+        }
+    }
+    Ok(())
+}
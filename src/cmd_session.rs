@@ -0,0 +1,98 @@
+//! Session subcommand handlers for AutoHands.
+
+use std::path::{Path, PathBuf};
+
+use autohands_config::Config;
+
+use crate::cli::SessionAction;
+
+/// Handle session subcommands.
+pub(crate) async fn handle_session_command(
+    action: SessionAction,
+    config: &Config,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let default_server = || format!("http://{}:{}", config.server.host, config.server.port);
+    match action {
+        SessionAction::Export { session_id, output, server } => {
+            let server = server.unwrap_or_else(default_server);
+            session_export(&session_id, output, &server).await
+        }
+        SessionAction::Import { file, overwrite, server } => {
+            let server = server.unwrap_or_else(default_server);
+            session_import(&file, overwrite, &server).await
+        }
+    }
+}
+
+/// Download a session bundle from a running server.
+async fn session_export(
+    session_id: &str,
+    output: Option<PathBuf>,
+    server: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let url = format!("{}/admin/sessions/{}/export", server.trim_end_matches('/'), session_id);
+    let resp = reqwest::get(&url).await?;
+    let status = resp.status();
+    let data: serde_json::Value = resp.json().await?;
+
+    if !status.is_success() {
+        let error = data["error"].as_str().unwrap_or("unknown error");
+        return Err(format!("Export of session '{}' failed ({}): {}", session_id, status, error).into());
+    }
+
+    let output = output.unwrap_or_else(|| PathBuf::from(format!("session-{}.json", session_id)));
+    std::fs::write(&output, serde_json::to_string_pretty(&data)?)?;
+    println!(
+        "Exported session '{}' to {} ({} transcript entries, {} artifacts, {} memories)",
+        session_id,
+        output.display(),
+        array_len(&data["transcript"]),
+        array_len(&data["artifacts"]),
+        array_len(&data["memories"])
+    );
+    Ok(())
+}
+
+/// Upload a session bundle to a running server.
+async fn session_import(
+    file: &Path,
+    overwrite: bool,
+    server: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let bundle: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(file)?)
+        .map_err(|e| format!("{} is not a session bundle: {}", file.display(), e))?;
+
+    let url = format!("{}/admin/sessions/import", server.trim_end_matches('/'));
+    let resp = reqwest::Client::new()
+        .post(&url)
+        .query(&[("overwrite", overwrite)])
+        .json(&bundle)
+        .send()
+        .await?;
+    let status = resp.status();
+    let data: serde_json::Value = resp.json().await.unwrap_or_default();
+
+    if !status.is_success() {
+        let error = data["error"].as_str().unwrap_or("unknown error");
+        return Err(format!("Import of {} failed ({}): {}", file.display(), status, error).into());
+    }
+
+    println!(
+        "Imported session '{}' ({} transcript entries, {} memories)",
+        data["session_id"].as_str().unwrap_or("-"),
+        data["transcript_entries"],
+        data["memories"]
+    );
+    if data["artifacts"].as_u64().unwrap_or(0) > 0 {
+        println!(
+            "Restored {} artifact(s) to {}",
+            data["artifacts"],
+            data["artifact_dir"].as_str().unwrap_or("-")
+        );
+    }
+    Ok(())
+}
+
+fn array_len(value: &serde_json::Value) -> usize {
+    value.as_array().map_or(0, Vec::len)
+}
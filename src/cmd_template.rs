@@ -0,0 +1,163 @@
+//! Task template subcommand handlers for AutoHands.
+
+use std::collections::HashMap;
+
+use autohands_api::template::placeholders;
+use autohands_api::{FileTemplateStore, TaskTemplate, TemplateStore};
+use autohands_config::Config;
+
+use crate::adapters::autohands_dir;
+use crate::cli::TemplateAction;
+
+/// Handle template subcommands.
+pub(crate) async fn handle_template_command(
+    action: TemplateAction,
+    config: &Config,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let store = FileTemplateStore::new(autohands_dir()).await?;
+    match action {
+        TemplateAction::List { format } => template_list(&store, &format).await,
+        TemplateAction::Show { template_id } => template_show(&store, &template_id).await,
+        TemplateAction::Add { template_id, prompt, name, agent, description, variables } => {
+            let name = name.unwrap_or_else(|| template_id.clone());
+            let mut template = TaskTemplate::new(template_id, name, prompt);
+            if let Some(agent) = agent {
+                template = template.with_agent(agent);
+            }
+            if let Some(description) = description {
+                template = template.with_description(description);
+            }
+            if !variables.is_empty() {
+                template = template.with_variables(variables);
+            }
+            store.save(&template).await?;
+            println!("Saved template '{}'", template.id);
+            Ok(())
+        }
+        TemplateAction::Delete { template_id } => {
+            if !store.delete(&template_id).await? {
+                return Err(format!("Template not found: {}", template_id).into());
+            }
+            println!("Deleted template '{}'", template_id);
+            Ok(())
+        }
+        TemplateAction::Run { template_id, variables, agent, server } => {
+            let server = server.unwrap_or_else(|| {
+                format!("http://{}:{}", config.server.host, config.server.port)
+            });
+            template_run(&template_id, &variables, agent, &server).await
+        }
+    }
+}
+
+/// List saved templates.
+async fn template_list(
+    store: &FileTemplateStore,
+    format: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut templates = store.load_all().await?;
+    templates.sort_by(|a, b| a.id.cmp(&b.id));
+
+    if templates.is_empty() {
+        println!("No templates found.");
+        return Ok(());
+    }
+
+    match format {
+        "json" => println!("{}", serde_json::to_string_pretty(&templates)?),
+        _ => {
+            println!("{:<20} {:<30} {:<15} VARIABLES", "ID", "NAME", "AGENT");
+            println!("{}", "-".repeat(80));
+            for template in templates {
+                let agent = template.agent.as_deref().unwrap_or("-");
+                println!(
+                    "{:<20} {:<30} {:<15} {}",
+                    template.id,
+                    template.name,
+                    agent,
+                    template.variables.join(", ")
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Show a template.
+async fn template_show(
+    store: &FileTemplateStore,
+    template_id: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let template = store
+        .load(template_id)
+        .await?
+        .ok_or_else(|| format!("Template not found: {}", template_id))?;
+
+    println!("Template: {}", template.name);
+    println!("{}", "=".repeat(50));
+    println!("ID:          {}", template.id);
+    if let Some(description) = &template.description {
+        println!("Description: {}", description);
+    }
+    if let Some(agent) = &template.agent {
+        println!("Agent:       {}", agent);
+    }
+    if !template.variables.is_empty() {
+        println!("Variables:   {}", template.variables.join(", "));
+    }
+    let optional: Vec<_> = placeholders(&template.prompt)
+        .into_iter()
+        .filter(|p| !template.variables.contains(p))
+        .collect();
+    if !optional.is_empty() {
+        println!("Optional:    {}", optional.join(", "));
+    }
+    println!("\nPrompt:");
+    println!("{}", "-".repeat(50));
+    println!("{}", template.prompt);
+
+    Ok(())
+}
+
+/// Submit a template to a running server.
+async fn template_run(
+    template_id: &str,
+    variables: &[String],
+    agent: Option<String>,
+    server: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let variables = parse_variables(variables)?;
+    let body = serde_json::json!({
+        "variables": variables,
+        "agent_id": agent,
+    });
+
+    let url = format!("{}/templates/{}/run", server.trim_end_matches('/'), template_id);
+    let resp = reqwest::Client::new().post(&url).json(&body).send().await?;
+    let status = resp.status();
+    let data: serde_json::Value = resp.json().await?;
+
+    if !status.is_success() {
+        let error = data["error"].as_str().unwrap_or("unknown error");
+        return Err(format!("Server rejected template run ({}): {}", status, error).into());
+    }
+
+    println!(
+        "Queued template '{}' (session {})",
+        template_id,
+        data["session_id"].as_str().unwrap_or("-")
+    );
+    Ok(())
+}
+
+/// Parse `KEY=VALUE` arguments.
+fn parse_variables(args: &[String]) -> Result<HashMap<String, String>, String> {
+    args.iter()
+        .map(|arg| {
+            arg.split_once('=')
+                .map(|(k, v)| (k.trim().to_string(), v.to_string()))
+                .ok_or_else(|| format!("Invalid variable '{}': expected KEY=VALUE", arg))
+        })
+        .collect()
+}
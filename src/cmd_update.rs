@@ -0,0 +1,295 @@
+//! Self-update command for AutoHands.
+//!
+//! Fetches the release manifest, downloads the binary built for this
+//! platform, checks its SHA-256 checksum and Ed25519 signature, and swaps it
+//! in place of the running executable. With `--restart`, the running server
+//! is drained first and the daemon is restarted on the new binary.
+//!
+//! The signature covers the version, the platform and the checksum (see
+//! [`signed_message`]), so a signed binary cannot be passed off as another
+//! release or platform. Release builds embed the public key from
+//! `AUTOHANDS_RELEASE_PUBLIC_KEY` at compile time; `--public-key` overrides
+//! it.
+//!
+//! The manifest is JSON:
+//!
+//! ```json
+//! {
+//!   "version": "0.2.0",
+//!   "notes": "Bug fixes",
+//!   "platforms": {
+//!     "linux-x86_64": {
+//!       "url": "https://.../autohands-linux-x86_64",
+//!       "sha256": "<hex digest of the binary>",
+//!       "signature": "<base64 Ed25519 signature of the signed message>"
+//!     }
+//!   }
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use base64::Engine;
+use ring::signature::{UnparsedPublicKey, ED25519};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+
+use autohands_config::Config;
+
+use crate::cli::SelfUpdateArgs;
+
+/// Manifest of the latest release.
+pub(crate) const DEFAULT_MANIFEST_URL: &str =
+    "https://github.com/autohands/autohands/releases/latest/download/manifest.json";
+
+/// Base64 Ed25519 key the releases are signed with, embedded by release
+/// builds.
+const RELEASE_PUBLIC_KEY: Option<&str> = option_env!("AUTOHANDS_RELEASE_PUBLIC_KEY");
+
+/// Seconds the running server gets to finish its tasks before a restart.
+const DRAIN_WAIT_SECONDS: u64 = 60;
+
+/// Release manifest.
+#[derive(Debug, Deserialize)]
+struct ReleaseManifest {
+    /// Released version.
+    version: String,
+    /// Release notes.
+    #[serde(default)]
+    notes: Option<String>,
+    /// Binaries by platform (`<os>-<arch>`).
+    platforms: HashMap<String, ReleaseAsset>,
+}
+
+/// Binary of a release for one platform.
+#[derive(Debug, Deserialize)]
+struct ReleaseAsset {
+    /// Download URL.
+    url: String,
+    /// Hex SHA-256 digest of the binary.
+    sha256: String,
+    /// Base64 Ed25519 signature of the [`signed_message`] of the binary.
+    #[serde(default)]
+    signature: Option<String>,
+}
+
+/// Handle `autohands self-update`.
+pub(crate) async fn handle_self_update(
+    args: SelfUpdateArgs,
+    config: &Config,
+    cli_config: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let current = env!("CARGO_PKG_VERSION");
+    let platform = platform();
+
+    let client = reqwest::Client::new();
+    let resp = client.get(&args.manifest_url).send().await?;
+    if !resp.status().is_success() {
+        return Err(format!(
+            "Failed to fetch release manifest {} ({})",
+            args.manifest_url,
+            resp.status()
+        )
+        .into());
+    }
+    let manifest: ReleaseManifest = resp
+        .json()
+        .await
+        .map_err(|e| format!("Invalid release manifest: {}", e))?;
+
+    let newer = is_newer(&manifest.version, current);
+    if args.check {
+        if newer {
+            println!("Update available: {} -> {}", current, manifest.version);
+            if let Some(notes) = &manifest.notes {
+                println!("\n{}", notes);
+            }
+        } else {
+            println!("AutoHands {} is up to date", current);
+        }
+        return Ok(());
+    }
+    if !newer && !args.force {
+        println!("AutoHands {} is up to date (latest: {})", current, manifest.version);
+        return Ok(());
+    }
+
+    let asset = manifest
+        .platforms
+        .get(&platform)
+        .ok_or_else(|| format!("Release {} has no binary for {}", manifest.version, platform))?;
+    let public_key = args.public_key.as_deref().or(RELEASE_PUBLIC_KEY);
+    if public_key.is_none() && !args.allow_unsigned {
+        return Err("This build has no release public key; set --public-key \
+                    (or AUTOHANDS_UPDATE_PUBLIC_KEY), or pass --allow-unsigned"
+            .into());
+    }
+
+    println!("Downloading AutoHands {} for {}...", manifest.version, platform);
+    let resp = client.get(&asset.url).send().await?;
+    if !resp.status().is_success() {
+        return Err(format!("Failed to download {} ({})", asset.url, resp.status()).into());
+    }
+    let binary = resp.bytes().await?;
+
+    verify_checksum(&binary, &asset.sha256)?;
+    match public_key {
+        Some(public_key) => {
+            let signature = asset
+                .signature
+                .as_deref()
+                .ok_or_else(|| format!("Release {} is not signed", manifest.version))?;
+            let message = signed_message(&manifest.version, &platform, &asset.sha256);
+            verify_signature(message.as_bytes(), signature, public_key)?;
+            info!("Signature verified");
+        }
+        None => warn!("Installing without a signature check (--allow-unsigned)"),
+    }
+
+    let exe = std::env::current_exe()?;
+    install_binary(&exe, &binary)?;
+    println!("Updated {} to {}", exe.display(), manifest.version);
+
+    if args.restart {
+        let server = args
+            .server
+            .unwrap_or_else(|| format!("http://{}:{}", config.server.host, config.server.port));
+        drain_server(&client, &server).await;
+        restart_daemon(&exe, cli_config, args.pid_file.as_deref())?;
+    }
+    Ok(())
+}
+
+/// Platform key of the running binary, e.g. `linux-x86_64`.
+fn platform() -> String {
+    format!("{}-{}", std::env::consts::OS, std::env::consts::ARCH)
+}
+
+/// Numeric components of a version, ignoring a `v` prefix and any
+/// pre-release or build suffix.
+fn parse_version(version: &str) -> Option<Vec<u64>> {
+    let core = version.trim().trim_start_matches('v').split(['-', '+']).next()?;
+    core.split('.').map(|part| part.parse().ok()).collect()
+}
+
+/// Whether `candidate` is a later version than `current`.
+fn is_newer(candidate: &str, current: &str) -> bool {
+    match (parse_version(candidate), parse_version(current)) {
+        (Some(mut candidate), Some(mut current)) => {
+            let len = candidate.len().max(current.len());
+            candidate.resize(len, 0);
+            current.resize(len, 0);
+            candidate > current
+        }
+        _ => false,
+    }
+}
+
+/// Check the binary against the manifest's SHA-256 digest.
+fn verify_checksum(binary: &[u8], expected: &str) -> Result<(), String> {
+    let actual = hex::encode(Sha256::digest(binary));
+    if actual.eq_ignore_ascii_case(expected.trim()) {
+        Ok(())
+    } else {
+        Err(format!("Checksum mismatch: expected {}, got {}", expected, actual))
+    }
+}
+
+/// What a release signature signs for one binary: its version, platform
+/// and SHA-256 digest, one per line.
+fn signed_message(version: &str, platform: &str, sha256: &str) -> String {
+    format!(
+        "autohands-release\n{}\n{}\n{}\n",
+        version.trim(),
+        platform,
+        sha256.trim().to_ascii_lowercase()
+    )
+}
+
+/// Check an Ed25519 signature of `message` against the release public key.
+fn verify_signature(message: &[u8], signature: &str, public_key: &str) -> Result<(), String> {
+    let engine = base64::engine::general_purpose::STANDARD;
+    let key = engine
+        .decode(public_key.trim())
+        .map_err(|e| format!("Invalid public key: {}", e))?;
+    let signature = engine
+        .decode(signature.trim())
+        .map_err(|e| format!("Invalid signature: {}", e))?;
+    UnparsedPublicKey::new(&ED25519, key)
+        .verify(message, &signature)
+        .map_err(|_| "Signature verification failed".to_string())
+}
+
+/// Replace the executable at `exe` with `binary`.
+///
+/// The binary is written next to the executable and renamed over it, so the
+/// swap is atomic: a crash leaves either the old or the new binary in place.
+/// The running process keeps executing the old one.
+fn install_binary(exe: &Path, binary: &[u8]) -> std::io::Result<()> {
+    let staged = exe.with_extension("new");
+    {
+        use std::io::Write;
+        let mut file = std::fs::File::create(&staged)?;
+        file.write_all(binary)?;
+        file.sync_all()?;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o755))?;
+    }
+    std::fs::rename(&staged, exe).inspect_err(|_| {
+        let _ = std::fs::remove_file(&staged);
+    })
+}
+
+/// Drain the running server so in-flight tasks finish before the restart.
+///
+/// Best effort: a server that is not running or does not answer is skipped.
+async fn drain_server(client: &reqwest::Client, server: &str) {
+    let url = format!("{}/v1/runloop/drain", server.trim_end_matches('/'));
+    println!("Draining {} (up to {}s)...", server, DRAIN_WAIT_SECONDS);
+    let resp = client
+        .post(&url)
+        .query(&[("wait_seconds", DRAIN_WAIT_SECONDS)])
+        .send()
+        .await;
+    match resp {
+        Ok(resp) if resp.status().is_success() => {
+            let status: serde_json::Value = resp.json().await.unwrap_or_default();
+            if status["drained"].as_bool() != Some(true) {
+                warn!(
+                    "Server not drained in time ({} queued, {} running); restarting anyway",
+                    status["queued_tasks"], status["active_agent_tasks"]
+                );
+            }
+        }
+        Ok(resp) => warn!("Drain of {} failed ({}); restarting anyway", server, resp.status()),
+        Err(e) => warn!("Could not reach {} to drain it: {}", server, e),
+    }
+}
+
+/// Restart the daemon by running `daemon restart` with the new binary.
+fn restart_daemon(
+    exe: &Path,
+    cli_config: &Path,
+    pid_file: Option<&Path>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut command = std::process::Command::new(exe);
+    command.arg("--config").arg(cli_config).args(["daemon", "restart"]);
+    if let Some(pid_file) = pid_file {
+        command.arg("--pid-file").arg(pid_file);
+    }
+    println!("Restarting daemon...");
+    let status = command.status()?;
+    if !status.success() {
+        return Err(format!("Daemon restart failed ({})", status).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "cmd_update_tests.rs"]
+mod tests;
@@ -0,0 +1,63 @@
+use super::*;
+use ring::rand::SystemRandom;
+use ring::signature::{Ed25519KeyPair, KeyPair};
+
+/// A key pair and its base64 public key.
+fn key_pair() -> (Ed25519KeyPair, String) {
+    let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+    let pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+    let public = base64::engine::general_purpose::STANDARD.encode(pair.public_key().as_ref());
+    (pair, public)
+}
+
+fn sign(pair: &Ed25519KeyPair, message: &str) -> String {
+    base64::engine::general_purpose::STANDARD.encode(pair.sign(message.as_bytes()).as_ref())
+}
+
+#[test]
+fn test_is_newer() {
+    assert!(is_newer("0.2.0", "0.1.9"));
+    assert!(is_newer("v1.0", "0.9.9"));
+    assert!(is_newer("1.0.1", "1.0"));
+    assert!(is_newer("1.10.0", "1.9.0"));
+    assert!(!is_newer("1.0.0", "1.0.0"));
+    assert!(!is_newer("1.0.0-rc1", "1.0.0"));
+    assert!(!is_newer("0.1.0", "0.2.0"));
+    assert!(!is_newer("latest", "0.1.0"));
+}
+
+#[test]
+fn test_verify_checksum() {
+    let digest = hex::encode(Sha256::digest(b"binary"));
+    assert!(verify_checksum(b"binary", &digest).is_ok());
+    assert!(verify_checksum(b"binary", &format!(" {} ", digest.to_uppercase())).is_ok());
+    let err = verify_checksum(b"tampered", &digest).unwrap_err();
+    assert!(err.contains("Checksum mismatch"));
+}
+
+#[test]
+fn test_verify_signature() {
+    let (pair, public) = key_pair();
+    let message = signed_message("0.2.0", "linux-x86_64", "ABCDEF");
+    let signature = sign(&pair, &message);
+
+    assert!(verify_signature(message.as_bytes(), &signature, &public).is_ok());
+    // The digest is compared in lower case
+    let same = signed_message("0.2.0", "linux-x86_64", "abcdef");
+    assert!(verify_signature(same.as_bytes(), &signature, &public).is_ok());
+
+    // The signature does not carry over to another version or platform
+    for other in [
+        signed_message("0.3.0", "linux-x86_64", "abcdef"),
+        signed_message("0.2.0", "macos-aarch64", "abcdef"),
+        signed_message("0.2.0", "linux-x86_64", "123456"),
+    ] {
+        let err = verify_signature(other.as_bytes(), &signature, &public).unwrap_err();
+        assert_eq!(err, "Signature verification failed");
+    }
+
+    let (_, other_key) = key_pair();
+    assert!(verify_signature(message.as_bytes(), &signature, &other_key).is_err());
+    let err = verify_signature(message.as_bytes(), "not base64!", &public).unwrap_err();
+    assert!(err.contains("Invalid signature"));
+}
@@ -0,0 +1,195 @@
+//! Workflow subcommand handlers for AutoHands.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use autohands_api::workflow::{is_workflow_file, load_workflow_dir, load_workflow_file};
+use autohands_api::{SqliteWorkflowStore, Workflow, WorkflowStore};
+use autohands_config::Config;
+
+use crate::adapters::{autohands_dir, workflows_dir};
+use crate::cli::WorkflowAction;
+
+/// Handle workflow subcommands.
+pub(crate) async fn handle_workflow_command(
+    action: WorkflowAction,
+    config: &Config,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match action {
+        WorkflowAction::List { format } => workflow_list(&format).await,
+        WorkflowAction::Validate { paths } => workflow_validate(paths),
+        WorkflowAction::Run { workflow, inputs, server } => {
+            let server = server.unwrap_or_else(|| {
+                format!("http://{}:{}", config.server.host, config.server.port)
+            });
+            workflow_run(&workflow, &inputs, &server).await
+        }
+    }
+}
+
+/// List workflow files and workflows registered with the server's store.
+async fn workflow_list(format: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let dir = workflows_dir();
+    let mut rows: Vec<(String, String, usize, String)> = Vec::new();
+    let mut from_files = Vec::new();
+
+    for (path, result) in load_workflow_dir(&dir) {
+        let file = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        match result {
+            Ok(workflow) => {
+                rows.push(row(&workflow, file));
+                from_files.push(workflow);
+            }
+            Err(_) => rows.push(("-".to_string(), "(invalid)".to_string(), 0, file)),
+        }
+    }
+
+    let store = SqliteWorkflowStore::open(autohands_dir().join("workflows.db")).await?;
+    let registered: Vec<Workflow> = store
+        .load_all()
+        .await?
+        .into_iter()
+        .filter(|w| !from_files.iter().any(|f| f.id == w.id))
+        .collect();
+    for workflow in &registered {
+        rows.push(row(workflow, "(api)".to_string()));
+    }
+
+    if rows.is_empty() {
+        println!("No workflows found in {:?}.", dir);
+        return Ok(());
+    }
+
+    match format {
+        "json" => {
+            let workflows: Vec<&Workflow> = from_files.iter().chain(&registered).collect();
+            println!("{}", serde_json::to_string_pretty(&workflows)?);
+        }
+        _ => {
+            println!("{:<20} {:<30} {:<6} SOURCE", "ID", "NAME", "STEPS");
+            println!("{}", "-".repeat(80));
+            for (id, name, steps, source) in rows {
+                println!("{:<20} {:<30} {:<6} {}", id, name, steps, source);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn row(workflow: &Workflow, source: String) -> (String, String, usize, String) {
+    (
+        workflow.id.clone(),
+        workflow.name.clone(),
+        workflow.root.step_ids().len(),
+        source,
+    )
+}
+
+/// Validate workflow files, reporting every error.
+fn workflow_validate(paths: Vec<PathBuf>) -> Result<(), Box<dyn std::error::Error>> {
+    let paths = if paths.is_empty() { vec![workflows_dir()] } else { paths };
+
+    let mut results = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            results.extend(load_workflow_dir(&path));
+        } else {
+            let result = load_workflow_file(&path);
+            results.push((path, result));
+        }
+    }
+
+    if results.is_empty() {
+        println!("No workflow files found.");
+        return Ok(());
+    }
+
+    let mut invalid = 0;
+    for (path, result) in &results {
+        match result {
+            Ok(workflow) => println!("ok      {} ({})", path.display(), workflow.id),
+            Err(e) => {
+                invalid += 1;
+                println!("invalid {}: {}", path.display(), e);
+            }
+        }
+    }
+
+    if invalid > 0 {
+        return Err(format!("{} of {} workflow file(s) invalid", invalid, results.len()).into());
+    }
+    Ok(())
+}
+
+/// Run a workflow on a running server, registering it first if given a file.
+async fn workflow_run(
+    workflow: &str,
+    inputs: &[String],
+    server: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let inputs = parse_inputs(inputs)?;
+    let server = server.trim_end_matches('/');
+    let client = reqwest::Client::new();
+
+    let path = Path::new(workflow);
+    let workflow_id = if is_workflow_file(path) && path.is_file() {
+        let definition = load_workflow_file(path)?;
+        let resp = client
+            .post(format!("{}/workflows", server))
+            .json(&definition)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let data: serde_json::Value = resp.json().await.unwrap_or_default();
+            let error = data["error"].as_str().unwrap_or("unknown error");
+            return Err(format!("Server rejected workflow ({}): {}", status, error).into());
+        }
+        definition.id
+    } else {
+        workflow.to_string()
+    };
+
+    let url = format!("{}/workflows/{}/run", server, workflow_id);
+    let resp = client
+        .post(&url)
+        .json(&serde_json::json!({ "inputs": inputs }))
+        .send()
+        .await?;
+    let status = resp.status();
+    let data: serde_json::Value = resp.json().await?;
+
+    if !status.is_success() {
+        let error = data["error"].as_str().unwrap_or("unknown error");
+        return Err(format!("Workflow '{}' failed ({}): {}", workflow_id, status, error).into());
+    }
+
+    println!(
+        "Workflow '{}' finished: {} (execution {})",
+        workflow_id,
+        data["status"].as_str().unwrap_or("-"),
+        data["execution_id"].as_str().unwrap_or("-")
+    );
+    if let Some(results) = data["step_results"].as_object() {
+        for (step_id, result) in results {
+            let mark = if result["success"].as_bool().unwrap_or(false) { "ok" } else { "failed" };
+            println!("  {:<20} {}", step_id, mark);
+        }
+    }
+    Ok(())
+}
+
+/// Parse `KEY=VALUE` arguments; values that parse as JSON keep their type.
+fn parse_inputs(args: &[String]) -> Result<HashMap<String, serde_json::Value>, String> {
+    args.iter()
+        .map(|arg| {
+            let (key, value) = arg
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid input '{}': expected KEY=VALUE", arg))?;
+            let value = serde_json::from_str(value)
+                .unwrap_or_else(|_| serde_json::Value::String(value.to_string()));
+            Ok((key.trim().to_string(), value))
+        })
+        .collect()
+}
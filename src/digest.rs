@@ -0,0 +1,82 @@
+//! Scheduled daily memory digest.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{Local, NaiveTime};
+use tracing::{error, info, warn};
+
+use autohands_config::DigestConfig;
+use autohands_core::registry::ChannelRegistry;
+use autohands_protocols::channel::{OutboundMessage, ReplyAddress};
+use autohands_protocols::memory::MemoryBackend;
+use autohands_runtime::{DailyDigest, StrategySummarizer, Summarizer, TranscriptManager};
+
+/// Start the daily digest job if enabled.
+pub(crate) fn spawn_daily_digest(
+    config: &DigestConfig,
+    transcripts: Arc<TranscriptManager>,
+    summarizer: Arc<dyn Summarizer>,
+    memory: Option<Arc<dyn MemoryBackend>>,
+    channels: Arc<ChannelRegistry>,
+) {
+    if !config.enabled {
+        return;
+    }
+    let Ok(at) = NaiveTime::parse_from_str(&config.time, "%H:%M") else {
+        warn!("Invalid memory.digest.time '{}', daily digest disabled", config.time);
+        return;
+    };
+    let strategy = config.strategy.parse().unwrap_or_else(|e: String| {
+        warn!("{}, using default", e);
+        Default::default()
+    });
+    let mut digest = DailyDigest::new(
+        transcripts,
+        Arc::new(StrategySummarizer::new(summarizer, strategy)),
+    );
+    if let Some(memory) = memory {
+        digest = digest.with_memory(memory);
+    }
+    let delivery = match (&config.channel, &config.target) {
+        (Some(channel), Some(target)) => Some(ReplyAddress::new(channel, target)),
+        _ => None,
+    };
+
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(until(at)).await;
+            let today = Local::now().date_naive();
+            let report = match digest.run(today).await {
+                Ok(Some(report)) => report,
+                Ok(None) => continue,
+                Err(e) => {
+                    error!("Daily digest for {} failed: {}", today, e);
+                    continue;
+                }
+            };
+            if let Some(ref address) = delivery {
+                let text = format!(
+                    "Daily digest for {} ({} sessions)\n\n{}",
+                    report.date,
+                    report.sessions.len(),
+                    report.summary
+                );
+                if let Err(e) = channels.send(address, OutboundMessage::text(text)).await {
+                    warn!("Failed to deliver daily digest to {}: {}", address.channel_id, e);
+                }
+            }
+        }
+    });
+    info!("Daily digest scheduled at {} (strategy={})", at.format("%H:%M"), strategy);
+}
+
+/// Time from now until the next local occurrence of `at`.
+fn until(at: NaiveTime) -> Duration {
+    let now = Local::now().naive_local();
+    let mut next = now.date().and_time(at);
+    if next <= now {
+        next += chrono::Duration::days(1);
+    }
+    (next - now).to_std().unwrap_or_default()
+}
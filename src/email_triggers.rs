@@ -0,0 +1,73 @@
+//! Email triggers declared under `[[triggers.email]]`.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use tracing::info;
+
+use autohands_api::{WorkflowExecutor, WorkflowStore};
+use autohands_config::{ConfigLoader, EmailTriggerConfig, TriggersConfig};
+use autohands_runloop::{EmailConfig, EmailInjector, ImapFetcher, RunLoop};
+
+use crate::file_triggers::StoredWorkflowLauncher;
+
+/// Injector settings of a configured email trigger.
+fn email_config(trigger: &EmailTriggerConfig) -> EmailConfig {
+    EmailConfig {
+        id: trigger.id.clone(),
+        host: trigger.host.clone(),
+        port: trigger.port,
+        tls: trigger.tls,
+        username: trigger.username.clone(),
+        password: trigger.password.clone(),
+        mailbox: trigger.mailbox.clone(),
+        poll_interval_secs: trigger.poll_interval_secs,
+        from: trigger.from.clone(),
+        subject: trigger.subject.clone(),
+        agent: trigger.agent.clone(),
+        prompt: trigger.prompt.clone(),
+        workflow: trigger.workflow.clone(),
+        archive_mailbox: trigger.archive_mailbox.clone(),
+        archive_from: trigger.archive_from.clone(),
+        archive_subject: trigger.archive_subject.clone(),
+        mark_seen: trigger.mark_seen,
+        attachment_dir: trigger
+            .attachment_dir
+            .as_ref()
+            .map(|dir| PathBuf::from(ConfigLoader::expand_path(&dir.to_string_lossy()))),
+        enabled: trigger.enabled,
+    }
+}
+
+/// Start polling the configured mailboxes.
+///
+/// Each returned injector polls until stopped.
+pub(crate) fn start_email_triggers(
+    config: &TriggersConfig,
+    run_loop: Arc<RunLoop>,
+    workflow_store: Arc<dyn WorkflowStore>,
+    workflow_executor: Arc<WorkflowExecutor>,
+) -> Vec<Arc<EmailInjector>> {
+    let launcher = Arc::new(StoredWorkflowLauncher {
+        store: workflow_store,
+        executor: workflow_executor,
+    });
+    let injectors: Vec<_> = config
+        .email
+        .iter()
+        .filter(|t| t.enabled)
+        .map(|trigger| {
+            let config = email_config(trigger);
+            let fetcher = Arc::new(ImapFetcher::new(config.clone()));
+            let injector = Arc::new(
+                EmailInjector::new(config, fetcher, run_loop.clone()).with_workflow_launcher(launcher.clone()),
+            );
+            injector.start();
+            injector
+        })
+        .collect();
+    if !injectors.is_empty() {
+        info!("Started {} email trigger(s)", injectors.len());
+    }
+    injectors
+}
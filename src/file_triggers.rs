@@ -0,0 +1,104 @@
+//! Watch-and-act file triggers declared under `[[triggers.file_watchers]]`.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::{error, info, warn};
+
+use autohands_api::{WorkflowExecution, WorkflowExecutor, WorkflowStore};
+use autohands_config::{ConfigLoader, FileWatcherTriggerConfig, TriggersConfig};
+use autohands_runloop::{FilePipeline, FileWatcherConfig, FileWatcherManager, RunLoop, WorkflowLauncher};
+
+/// Runs stored workflows for file and email triggers, in the background.
+pub(crate) struct StoredWorkflowLauncher {
+    pub(crate) store: Arc<dyn WorkflowStore>,
+    pub(crate) executor: Arc<WorkflowExecutor>,
+}
+
+#[async_trait]
+impl WorkflowLauncher for StoredWorkflowLauncher {
+    async fn launch(&self, workflow_id: &str, inputs: HashMap<String, serde_json::Value>) -> Result<(), String> {
+        let workflow = self
+            .store
+            .load(workflow_id)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("Workflow '{}' not found", workflow_id))?;
+        let executor = self.executor.clone();
+        tokio::spawn(async move {
+            let mut execution = WorkflowExecution::new(&workflow.id);
+            if let Err(e) = executor
+                .execute_workflow_with_inputs(&workflow, &mut execution, &inputs)
+                .await
+            {
+                error!("Workflow {} ({}) failed: {}", workflow.id, execution.id, e);
+            }
+        });
+        Ok(())
+    }
+}
+
+/// Watcher settings of a configured file trigger.
+fn watcher_config(trigger: &FileWatcherTriggerConfig) -> FileWatcherConfig {
+    let events = trigger
+        .events
+        .iter()
+        .filter_map(|event| {
+            serde_json::from_value(serde_json::Value::String(event.clone()))
+                .inspect_err(|_| warn!("File watcher {}: unknown change kind '{}'", trigger.id, event))
+                .ok()
+        })
+        .collect();
+    FileWatcherConfig {
+        id: trigger.id.clone(),
+        paths: trigger
+            .paths
+            .iter()
+            .map(|path| PathBuf::from(ConfigLoader::expand_path(&path.to_string_lossy())))
+            .collect(),
+        patterns: trigger.patterns.clone(),
+        exclude: trigger.exclude.clone(),
+        respect_gitignore: trigger.respect_gitignore,
+        agent: trigger.agent.clone(),
+        prompt: trigger.prompt.clone(),
+        workflow: trigger.workflow.clone(),
+        events,
+        per_file: trigger.per_file,
+        enabled: trigger.enabled,
+        debounce_ms: trigger.debounce_ms,
+        max_wait_ms: 5000,
+    }
+}
+
+/// Start the configured file triggers.
+///
+/// The returned manager owns the watchers; they stop when it is dropped.
+pub(crate) async fn start_file_triggers(
+    config: &TriggersConfig,
+    run_loop: Arc<RunLoop>,
+    workflow_store: Arc<dyn WorkflowStore>,
+    workflow_executor: Arc<WorkflowExecutor>,
+) -> Arc<FileWatcherManager> {
+    let manager = Arc::new(FileWatcherManager::new());
+    for trigger in config.file_watchers.iter().filter(|t| t.enabled) {
+        if let Err(e) = manager.register(watcher_config(trigger)).await {
+            warn!("Failed to register file watcher {}: {}", trigger.id, e);
+        }
+    }
+    if config.file_watchers.is_empty() {
+        return manager;
+    }
+
+    let launcher = Arc::new(StoredWorkflowLauncher {
+        store: workflow_store,
+        executor: workflow_executor,
+    });
+    FilePipeline::new(manager.clone(), run_loop)
+        .with_workflow_launcher(launcher)
+        .start()
+        .await;
+    info!("Started {} file watcher trigger(s)", manager.list().await.len());
+    manager
+}
@@ -0,0 +1,57 @@
+//! Secret redaction of log output.
+//!
+//! Tracing is set up before the configuration is read, so log lines go
+//! through [`LOG_REDACTOR`]: it starts with the built-in credential formats
+//! and the server replaces it with the configured redactor.
+
+use std::borrow::Cow;
+use std::io::{self, Write};
+use std::sync::{Arc, LazyLock};
+
+use tracing_subscriber::fmt::MakeWriter;
+
+use autohands_core::redact::{Redactor, SecretRedactor, SharedRedactor};
+
+/// Redactor of console and file log output.
+pub(crate) static LOG_REDACTOR: LazyLock<SharedRedactor> =
+    LazyLock::new(|| SharedRedactor::new(Arc::new(SecretRedactor::default())));
+
+/// Writers redacting what another `MakeWriter`'s writers are given.
+pub(crate) struct RedactingMakeWriter<M> {
+    inner: M,
+}
+
+impl<M> RedactingMakeWriter<M> {
+    pub(crate) fn new(inner: M) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for RedactingMakeWriter<M> {
+    type Writer = RedactingWriter<M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactingWriter {
+            inner: self.inner.make_writer(),
+        }
+    }
+}
+
+/// Writer redacting each write, which holds a whole formatted event.
+pub(crate) struct RedactingWriter<W> {
+    inner: W,
+}
+
+impl<W: Write> Write for RedactingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match std::str::from_utf8(buf).map(|text| LOG_REDACTOR.redact(text)) {
+            Ok(Cow::Owned(redacted)) => self.inner.write_all(redacted.as_bytes())?,
+            _ => self.inner.write_all(buf)?,
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
@@ -3,16 +3,36 @@
 //! Main entry point for the AutoHands CLI and server.
 
 mod adapters;
+mod approval;
 mod cli;
+mod cmd_backup;
+mod cmd_config;
 mod cmd_daemon;
+mod cmd_desktop;
+mod cmd_gc;
+mod cmd_runloop;
+mod cmd_secrets;
+mod cmd_session;
 mod cmd_skill;
+mod cmd_template;
+mod cmd_update;
+mod cmd_workflow;
+mod digest;
+mod email_triggers;
+mod file_triggers;
+mod log_redaction;
+mod mqtt_triggers;
 mod register;
 mod server;
+#[cfg(feature = "otel")]
+mod telemetry;
+
+use std::path::Path;
 
 use clap::Parser;
 use tracing::{info, warn};
 
-use autohands_config::{ConfigLoader, Config};
+use autohands_config::{Config, ConfigError, ConfigLayers, PROFILE_KEY};
 use cli::{Cli, Commands};
 
 #[tokio::main]
@@ -22,34 +42,103 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let cli = Cli::parse();
 
-    // Load configuration from file (with env var expansion fallback)
-    let config = ConfigLoader::load(&cli.config).unwrap_or_else(|e| {
-        warn!("Failed to load config from {:?}: {}, using defaults", cli.config, e);
-        Config::default()
-    });
-    info!("Configuration loaded: server={}:{}", config.server.host, config.server.port);
+    // Layered configuration: defaults < file < AUTOHANDS_* env < CLI flags
+    let mut layers = ConfigLayers::new().with_env(std::env::vars());
+    if cli.config.exists() {
+        layers = layers.with_file(&cli.config);
+    } else {
+        warn!("Config file {:?} not found, using defaults", cli.config);
+    }
+    if let Some(profile) = &cli.profile {
+        layers = layers.with_profile(profile);
+    }
+    if let Some(Commands::Run { host, port, yolo, .. }) = &cli.command {
+        if let Some(host) = host {
+            layers = layers.with_override("server.host", host);
+        }
+        if let Some(port) = port {
+            layers = layers.with_override("server.port", port.to_string());
+        }
+        if *yolo {
+            layers = layers.with_override("approval.yolo", "true");
+        }
+    }
+    for (key, value) in &cli.set {
+        layers = layers.with_override(key, value);
+    }
+    let resolved = if matches!(cli.command, None | Some(Commands::Run { .. })) {
+        // Only the server needs what secret:// references name; Vault is
+        // queried with blocking requests
+        tokio::task::block_in_place(|| layers.resolve_with_secrets())
+    } else {
+        layers.resolve()
+    };
+    let config = match resolved {
+        Ok(config) => config,
+        Err(e @ ConfigError::Secret(_)) => return Err(e.into()),
+        // Running a deployment's profile on defaults would be a surprise
+        Err(ConfigError::InvalidValue { field, message }) if field.starts_with(PROFILE_KEY) => {
+            return Err(ConfigError::InvalidValue { field, message }.into())
+        }
+        Err(e) => {
+            warn!("Failed to load config from {:?}: {}, using defaults", cli.config, e);
+            Config::default()
+        }
+    };
+    match layers.profile() {
+        Some(profile) => info!(
+            "Configuration loaded (profile {}): server={}:{}",
+            profile, config.server.host, config.server.port
+        ),
+        None => info!("Configuration loaded: server={}:{}", config.server.host, config.server.port),
+    }
 
     let work_dir = cli
         .work_dir
         .unwrap_or_else(|| std::env::current_dir().expect("Failed to get current directory"));
 
-    match cli.command {
+    let result = match cli.command {
         None => {
             // Default: run server with config
-            server::run_server(work_dir, config).await
+            server::run_server(work_dir, config, layers.file().map(Path::to_path_buf)).await
         }
-        Some(Commands::Run { host, port, web_port: _ }) => {
-            // CLI args override config values
-            let mut config = config;
-            config.server.host = host;
-            config.server.port = port;
-            server::run_server(work_dir, config).await
+        Some(Commands::Run { .. }) => {
+            // --host, --port and --yolo are applied as configuration overrides
+            server::run_server(work_dir, config, layers.file().map(Path::to_path_buf)).await
         }
+        Some(Commands::Config { action }) => cmd_config::handle_config_command(action, &layers),
+        Some(Commands::Secrets { action }) => cmd_secrets::handle_secrets_command(action, &config),
         Some(Commands::Daemon { action }) => {
             cmd_daemon::handle_daemon_command(action, work_dir).await
         }
         Some(Commands::Skill { action }) => {
             cmd_skill::handle_skill_command(action).await
         }
-    }
+        Some(Commands::Template { action }) => {
+            cmd_template::handle_template_command(action, &config).await
+        }
+        Some(Commands::Workflow { action }) => {
+            cmd_workflow::handle_workflow_command(action, &config).await
+        }
+        Some(Commands::Session { action }) => {
+            cmd_session::handle_session_command(action, &config).await
+        }
+        Some(Commands::Desktop { action }) => {
+            cmd_desktop::handle_desktop_command(action, &config).await
+        }
+        Some(Commands::Runloop { action }) => {
+            cmd_runloop::handle_runloop_command(action, &config).await
+        }
+        Some(Commands::SelfUpdate(args)) => {
+            cmd_update::handle_self_update(args, &config, &cli.config).await
+        }
+        Some(Commands::Backup { action }) => {
+            cmd_backup::handle_backup_command(action, &cli.config).await
+        }
+        Some(Commands::Gc { dry_run, format }) => cmd_gc::handle_gc(dry_run, &format),
+    };
+
+    #[cfg(feature = "otel")]
+    telemetry::shutdown();
+    result
 }
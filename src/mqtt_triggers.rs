@@ -0,0 +1,54 @@
+//! MQTT brokers declared under `[[triggers.mqtt]]`.
+
+use tracing::{info, warn};
+
+use autohands_config::{MqttTriggerConfig, TriggersConfig};
+use autohands_core::registry::ChannelRegistry;
+use autohands_runloop::{MqttConfig, MqttSource1, MqttSubscription, RunLoop};
+
+/// Source settings of a configured broker.
+fn mqtt_config(broker: &MqttTriggerConfig) -> MqttConfig {
+    MqttConfig {
+        id: broker.id.clone(),
+        host: broker.host.clone(),
+        port: broker.port,
+        client_id: broker.client_id.clone(),
+        username: broker.username.clone(),
+        password: broker.password.clone(),
+        keep_alive_secs: broker.keep_alive_secs,
+        subscriptions: broker
+            .subscriptions
+            .iter()
+            .map(|subscription| MqttSubscription {
+                topic: subscription.topic.clone(),
+                qos: subscription.qos,
+                agent: subscription.agent.clone(),
+                prompt: subscription.prompt.clone(),
+                reply_topic: subscription.reply_topic.clone(),
+                reply_format: serde_json::from_value(serde_json::Value::String(subscription.reply_format.clone()))
+                    .inspect_err(|_| {
+                        warn!("MQTT {}: unknown reply format '{}'", broker.id, subscription.reply_format)
+                    })
+                    .unwrap_or_default(),
+            })
+            .collect(),
+        publish_qos: broker.publish_qos,
+        retain: broker.retain,
+        enabled: broker.enabled,
+    }
+}
+
+/// Connect to the configured brokers.
+///
+/// Subscribed messages feed the RunLoop as Source1 messages, and each broker
+/// is registered as a channel so results can be published to its topics.
+pub(crate) async fn start_mqtt(config: &TriggersConfig, run_loop: &RunLoop, channels: &ChannelRegistry) {
+    for broker in config.mqtt.iter().filter(|b| b.enabled) {
+        let (receiver, channel) = MqttSource1::new(mqtt_config(broker)).connect();
+        if let Err(e) = channels.register(channel) {
+            warn!("Failed to register MQTT channel {}: {}", broker.id, e);
+        }
+        run_loop.add_source1(receiver).await;
+        info!("MQTT {} connecting to {}:{}", broker.id, broker.host, broker.port);
+    }
+}
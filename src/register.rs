@@ -1,16 +1,21 @@
 //! Extension and provider registration for AutoHands.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
 use tracing::{error, info, warn};
 
 use autohands_config::{Config, ConfigLoader};
 use autohands_core::registry::{ProviderRegistry, ToolRegistry};
+use autohands_core::{BootstrapReport, ExtensionCatalog, ExtensionKind, ExtensionSelection, Kernel};
 use autohands_provider_anthropic::AnthropicProvider;
 use autohands_provider_gemini::GeminiProvider;
 use autohands_provider_openai::OpenAIProvider;
-use autohands_runtime::AgentRuntime;
+use autohands_runtime::{
+    AgentRuntime, ContextRefresher, FailoverProvider, LivePrompt, MemorySection, ProviderHealthTable,
+    RedactingProvider, TemplateSection,
+};
 
 // Memory extensions
 use autohands_memory_sqlite::SqliteMemoryExtension;
@@ -21,10 +26,15 @@ use autohands_tools_memory::MemoryToolsExtension;
 
 // Tool extensions
 use autohands_tools_browser::BrowserToolsExtension;
-use autohands_tools_code::{AnalyzeCodeTool, FindSymbolTool};
-use autohands_tools_cron::CronToolsExtension;
+use autohands_tools_code::{AnalyzeCodeTool, FindSymbolTool, RepoMapTool};
+use autohands_tools_cron::{CronLimits, CronToolsExtension};
+use autohands_tools_data::DataToolsExtension;
+use autohands_tools_database::DatabaseToolsExtension;
 use autohands_tools_desktop::DesktopToolsExtension;
+use autohands_tools_documents::DocumentToolsExtension;
 use autohands_tools_filesystem::FilesystemExtension;
+use autohands_tools_git::GitToolsExtension;
+use autohands_tools_github::GitHubToolsExtension;
 use autohands_tools_image::ImageToolsExtension;
 use autohands_tools_notify::NotifyToolsExtension;
 use autohands_tools_search::SearchExtension;
@@ -33,269 +43,202 @@ use autohands_tools_skill::SkillToolsExtension;
 use autohands_tools_web::WebToolsExtension;
 
 // Agent extensions
-use autohands_agent_general::GeneralAgent;
+use autohands_agent_general::{review_agent_config, GeneralAgent, REVIEW_AGENT_ID, REVIEW_TOOLS};
 use autohands_tools_agent::AgentToolsExtension;
 
 // Protocols for extension context
 use autohands_protocols::agent::AgentConfig;
-use autohands_protocols::extension::Extension;
+use autohands_protocols::tool::{ContainerSandbox, SandboxPolicy};
 
 // Skills progressive disclosure
 use autohands_skills_dynamic::SkillMetadataInjector;
 
-use crate::adapters::autohands_dir;
+use crate::adapters::{autohands_dir, SkillContextSection};
 use crate::cmd_skill::create_skill_loader_for_server;
 
-/// Register available tools and return (skill registry, optional memory backend, agent tools extension).
+/// Every extension ID the server knows, for `[extensions]` to name.
+const BUILT_IN_EXTENSIONS: &[&str] = &[
+    "tools-filesystem",
+    "tools-shell",
+    "tools-browser",
+    "tools-desktop",
+    "tools-search",
+    "tools-web",
+    "tools-code",
+    "tools-cron",
+    "tools-notify",
+    "tools-github",
+    "tools-git",
+    "tools-data",
+    "tools-database",
+    "tools-documents",
+    "tools-image",
+    "tools-memory",
+    "tools-agent",
+    "tools-skill",
+    "memory-sqlite",
+    "memory-markdown",
+    "memory-vector",
+    "memory-hybrid",
+    "provider-anthropic",
+    "provider-openai",
+    "provider-gemini",
+    "provider-ark",
+    "channel-web",
+];
+
+/// Extensions enabled by the `[extensions]` config section.
+pub(crate) fn extension_selection(config: &Config) -> ExtensionSelection {
+    let mut selection = ExtensionSelection::all()
+        .with_enabled(config.extensions.enabled.iter().cloned())
+        .with_disabled(config.extensions.disabled.iter().cloned());
+    for (id, ext_config) in &config.extensions.config {
+        selection = selection.with_config(id.clone(), ext_config.clone());
+    }
+    selection
+}
+
+/// Memory path from config, or a default under ~/.autohands.
+fn memory_path(config: &Config, default: &str) -> PathBuf {
+    config.memory.path.clone()
+        .map(|p| {
+            let expanded = ConfigLoader::expand_path(&p.to_string_lossy());
+            PathBuf::from(expanded)
+        })
+        .unwrap_or_else(|| autohands_dir().join(default))
+}
+
+/// Log the tools of the extensions a bootstrap loaded.
+fn log_loaded_tools(kernel: &Kernel, report: &BootstrapReport) {
+    for id in &report.loaded {
+        if let Some(ext) = kernel.extension(id) {
+            info!("Registered {} tools: {:?}", id, ext.manifest().provides.tools);
+        }
+    }
+}
+
+/// Load the tool and memory extensions enabled by `[extensions]` through
+/// the kernel and return (skill registry, optional memory backend).
 pub(crate) async fn register_tools_with_skill_registry(
-    tool_registry: Arc<ToolRegistry>,
-    provider_registry: Arc<ProviderRegistry>,
+    kernel: &Kernel,
+    job_store: Arc<dyn autohands_api::JobStore>,
+    question_gate: Arc<dyn autohands_protocols::question::QuestionGate>,
     work_dir: &PathBuf,
     config: &Config,
 ) -> (
     Arc<autohands_skills_dynamic::SkillRegistry>,
     Option<Arc<dyn autohands_protocols::memory::MemoryBackend>>,
-    Option<AgentToolsExtension>,
 ) {
-    use autohands_core::registry::MemoryRegistry;
-    use autohands_protocols::extension::ExtensionContext;
-
-    // Create extension context for initializing extensions
-    // Note: task_submitter is None since we're not running within a RunLoop here
-    let memory_registry = Arc::new(MemoryRegistry::new());
-
-    let ctx = ExtensionContext::new(
-        serde_json::Value::Null,
-        None, // task_submitter - not needed for tool registration
-        tool_registry.clone() as Arc<dyn autohands_protocols::extension::ToolRegistryAccess>,
-        provider_registry.clone() as Arc<dyn autohands_protocols::extension::ProviderRegistryAccess>,
-        memory_registry.clone() as Arc<dyn autohands_protocols::extension::MemoryRegistryAccess>,
-        work_dir.clone(),
-    );
-
-    // Register Filesystem tools
-    let mut fs_ext = FilesystemExtension::new();
-    match fs_ext.initialize(ctx.clone()).await {
-        Ok(()) => {
-            let tools = fs_ext.manifest().provides.tools.clone();
-            info!("Registered filesystem tools: {:?}", tools);
-        }
-        Err(e) => {
-            warn!("Failed to initialize filesystem extension: {}", e);
-        }
-    }
-
-    // Register Shell tools
-    let mut shell_ext = ShellExtension::new();
-    match shell_ext.initialize(ctx.clone()).await {
-        Ok(()) => {
-            let tools = shell_ext.manifest().provides.tools.clone();
-            info!("Registered shell tools: {:?}", tools);
-        }
-        Err(e) => {
-            warn!("Failed to initialize shell extension: {}", e);
-        }
-    }
-
-    // Register Browser tools - Chrome will be auto-launched on first use
-    // Profile persisted at ~/.autohands/browser-profile for login state
-    let mut browser_ext = BrowserToolsExtension::new();
-    match browser_ext.initialize(ctx.clone()).await {
-        Ok(()) => {
-            let tools = browser_ext.manifest().provides.tools.clone();
-            info!("Registered browser tools: {:?}", tools);
-        }
-        Err(e) => {
-            warn!("Failed to initialize browser extension: {}", e);
-        }
-    }
-
-    // Register Desktop tools
-    let mut desktop_ext = DesktopToolsExtension::new();
-    match desktop_ext.initialize(ctx.clone()).await {
-        Ok(()) => {
-            let tools = desktop_ext.manifest().provides.tools.clone();
-            info!("Registered desktop tools: {:?}", tools);
-        }
-        Err(e) => {
-            warn!("Failed to initialize desktop extension: {}", e);
-        }
-    }
-
-    // Register Search tools (glob, grep)
-    let mut search_ext = SearchExtension::new();
-    match search_ext.initialize(ctx.clone()).await {
-        Ok(()) => {
-            let tools = search_ext.manifest().provides.tools.clone();
-            info!("Registered search tools: {:?}", tools);
-        }
-        Err(e) => {
-            warn!("Failed to initialize search extension: {}", e);
-        }
-    }
-
-    // Register Web tools (web_fetch, web_search)
-    let mut web_ext = WebToolsExtension::new();
-    match web_ext.initialize(ctx.clone()).await {
-        Ok(()) => {
-            let tools = web_ext.manifest().provides.tools.clone();
-            info!("Registered web tools: {:?}", tools);
-        }
-        Err(e) => {
-            warn!("Failed to initialize web extension: {}", e);
-        }
-    }
-
-    // Register Code tools (analyze_code, find_symbol) - no Extension, register directly
-    if let Err(e) = tool_registry.register(Arc::new(AnalyzeCodeTool::new())) {
-        warn!("Failed to register analyze_code tool: {}", e);
-    } else {
-        info!("Registered analyze_code tool");
-    }
-    if let Err(e) = tool_registry.register(Arc::new(FindSymbolTool::new())) {
-        warn!("Failed to register find_symbol tool: {}", e);
-    } else {
-        info!("Registered find_symbol tool");
-    }
-
-    // Register Memory backend based on config
+    let tool_registry = kernel.tool_registry().clone();
+    let memory_registry = kernel.memory_registry().clone();
+    let selection = extension_selection(config);
+
+    // Extensions built from configuration alone. Chrome is auto-launched on
+    // first browser use, its profile persisted at ~/.autohands/browser-profile
+    let cron_limits = CronLimits {
+        max_jobs: config.scheduler.max_agent_jobs,
+        ..CronLimits::default()
+    };
+    let mut catalog = ExtensionCatalog::new()
+        .add("tools-filesystem", ExtensionKind::Tool, || Box::new(FilesystemExtension::new()))
+        .add("tools-shell", ExtensionKind::Tool, || Box::new(ShellExtension::new()))
+        .add("tools-browser", ExtensionKind::Tool, || Box::new(BrowserToolsExtension::new()))
+        .add("tools-desktop", ExtensionKind::Tool, || Box::new(DesktopToolsExtension::new()))
+        .add("tools-search", ExtensionKind::Tool, || Box::new(SearchExtension::new()))
+        .add("tools-web", ExtensionKind::Tool, || Box::new(WebToolsExtension::new()))
+        .add("tools-cron", ExtensionKind::Tool, move || {
+            Box::new(CronToolsExtension::new().with_store(job_store.clone()).with_limits(cron_limits))
+        })
+        .add("tools-notify", ExtensionKind::Tool, || Box::new(NotifyToolsExtension::new()))
+        .add("tools-github", ExtensionKind::Tool, || Box::new(GitHubToolsExtension::new()))
+        .add("tools-git", ExtensionKind::Tool, || Box::new(GitToolsExtension::new()))
+        .add("tools-data", ExtensionKind::Tool, || Box::new(DataToolsExtension::new()))
+        .add("tools-database", ExtensionKind::Tool, || Box::new(DatabaseToolsExtension::new()))
+        .add("tools-documents", ExtensionKind::Tool, || Box::new(DocumentToolsExtension::new()))
+        .add("tools-image", ExtensionKind::Tool, || Box::new(ImageToolsExtension::new()));
+
+    // Memory backend based on config
     match config.memory.backend.as_str() {
         "sqlite" => {
-            let path = config.memory.path.clone()
-                .map(|p| {
-                    let expanded = ConfigLoader::expand_path(&p.to_string_lossy());
-                    PathBuf::from(expanded)
-                })
-                .unwrap_or_else(|| autohands_dir().join("memory.db"));
-            let mut sqlite_ext = SqliteMemoryExtension::new().with_path(&path);
-            match sqlite_ext.initialize(ctx.clone()).await {
-                Ok(()) => {
-                    info!("Registered SQLite memory backend (path={})", path.display());
-                }
-                Err(e) => {
-                    warn!("Failed to initialize SQLite memory backend: {}", e);
-                }
-            }
+            let path = memory_path(config, "memory.db");
+            info!("SQLite memory backend at {}", path.display());
+            catalog = catalog.add("memory-sqlite", ExtensionKind::Memory, move || {
+                Box::new(SqliteMemoryExtension::new().with_path(&path))
+            });
         }
         "markdown" => {
-            let path = config.memory.path.clone()
-                .map(|p| {
-                    let expanded = ConfigLoader::expand_path(&p.to_string_lossy());
-                    PathBuf::from(expanded)
-                })
-                .unwrap_or_else(|| autohands_dir().join("memory"));
-            let mut md_ext = MarkdownMemoryExtension::new().with_path(&path);
-            match md_ext.initialize(ctx.clone()).await {
-                Ok(()) => {
-                    info!("Registered markdown memory backend (path={})", path.display());
-                }
-                Err(e) => {
-                    warn!("Failed to initialize markdown memory backend: {}", e);
-                }
-            }
+            let path = memory_path(config, "memory");
+            info!("Markdown memory backend at {}", path.display());
+            catalog = catalog.add("memory-markdown", ExtensionKind::Memory, move || {
+                Box::new(MarkdownMemoryExtension::new().with_path(&path))
+            });
         }
         "vector" => {
-            let mut vector_ext = VectorMemoryExtension::new();
-            match vector_ext.initialize(ctx.clone()).await {
-                Ok(()) => {
-                    info!("Registered vector memory backend");
-                }
-                Err(e) => {
-                    warn!("Failed to initialize vector memory backend: {}", e);
-                }
-            }
+            catalog = catalog.add("memory-vector", ExtensionKind::Memory, || Box::new(VectorMemoryExtension::new()));
         }
         "hybrid" => {
-            let mut hybrid_ext = HybridMemoryExtension::new();
-            match hybrid_ext.initialize(ctx.clone()).await {
-                Ok(()) => {
-                    info!("Registered hybrid memory backend");
-                }
-                Err(e) => {
-                    warn!("Failed to initialize hybrid memory backend: {}", e);
-                }
-            }
+            catalog = catalog.add("memory-hybrid", ExtensionKind::Memory, || Box::new(HybridMemoryExtension::new()));
         }
         other => {
             info!("Using '{}' memory backend (no additional registration needed)", other);
         }
     }
 
-    // Register Memory tools if a memory backend is available
-    let memory_backend: Option<Arc<dyn autohands_protocols::memory::MemoryBackend>> = {
-        let ids = memory_registry.list_ids();
-        if let Some(first_id) = ids.first() {
-            memory_registry.get(first_id)
-        } else {
-            None
-        }
-    };
-    if let Some(ref backend) = memory_backend {
-        let mut memory_tools_ext = MemoryToolsExtension::new(backend.clone());
-        match memory_tools_ext.initialize(ctx.clone()).await {
-            Ok(()) => {
-                let tools = memory_tools_ext.manifest().provides.tools.clone();
-                info!("Registered memory tools: {:?}", tools);
-            }
-            Err(e) => {
-                warn!("Failed to initialize memory tools extension: {}", e);
-            }
-        }
-    }
+    let mut report = kernel.bootstrap(&catalog, &selection).await;
+    log_loaded_tools(kernel, &report);
 
-    // Register Cron tools
-    let mut cron_ext = CronToolsExtension::new();
-    match cron_ext.initialize(ctx.clone()).await {
-        Ok(()) => {
-            let tools = cron_ext.manifest().provides.tools.clone();
-            info!("Registered cron tools: {:?}", tools);
-        }
-        Err(e) => {
-            warn!("Failed to initialize cron tools extension: {}", e);
-        }
+    // Third-party plugins from the [extensions] paths
+    let plugin_paths: Vec<PathBuf> = config
+        .extensions
+        .paths
+        .iter()
+        .map(|path| PathBuf::from(ConfigLoader::expand_path(&path.to_string_lossy())))
+        .collect();
+    let plugins = kernel.load_plugins(&plugin_paths, &selection).await;
+    log_loaded_tools(kernel, &plugins);
+    let plugin_ids: Vec<&str> = plugins.loaded.iter().chain(&plugins.skipped).map(String::as_str).collect();
+    let unknown = selection.unknown(BUILT_IN_EXTENSIONS.iter().copied().chain(plugin_ids));
+    if !unknown.is_empty() {
+        warn!("[extensions] names unknown extensions: {:?}", unknown);
     }
+    report.merge(plugins);
 
-    // Register Notify tools
-    let mut notify_ext = NotifyToolsExtension::new();
-    match notify_ext.initialize(ctx.clone()).await {
-        Ok(()) => {
-            let tools = notify_ext.manifest().provides.tools.clone();
-            info!("Registered notify tools: {:?}", tools);
-        }
-        Err(e) => {
-            warn!("Failed to initialize notify tools extension: {}", e);
+    // Register Code tools (analyze_code, find_symbol, repo_map) - no Extension, register directly
+    if selection.is_enabled("tools-code") {
+        if let Err(e) = tool_registry.register(Arc::new(AnalyzeCodeTool::new())) {
+            warn!("Failed to register analyze_code tool: {}", e);
+        } else {
+            info!("Registered analyze_code tool");
         }
-    }
-
-    // Register Image tools
-    let mut image_ext = ImageToolsExtension::new();
-    match image_ext.initialize(ctx.clone()).await {
-        Ok(()) => {
-            let tools = image_ext.manifest().provides.tools.clone();
-            info!("Registered image tools: {:?}", tools);
+        if let Err(e) = tool_registry.register(Arc::new(FindSymbolTool::new())) {
+            warn!("Failed to register find_symbol tool: {}", e);
+        } else {
+            info!("Registered find_symbol tool");
         }
-        Err(e) => {
-            warn!("Failed to initialize image tools extension: {}", e);
+        if let Err(e) = tool_registry.register(Arc::new(RepoMapTool::new())) {
+            warn!("Failed to register repo_map tool: {}", e);
+        } else {
+            info!("Registered repo_map tool");
         }
     }
 
-    // Register Agent tools (agent_spawn, agent_status, agent_message, etc.)
-    let agent_tools_ext = {
-        let mut ext = AgentToolsExtension::new();
-        match ext.initialize(ctx.clone()).await {
-            Ok(()) => {
-                info!("Registered agent tools: {:?}", ext.manifest().provides.tools);
-                Some(ext)
-            }
-            Err(e) => {
-                warn!("Failed to initialize agent tools extension: {}", e);
-                None
-            }
+    // The first memory backend, for the memory tools
+    let memory_backend: Option<Arc<dyn autohands_protocols::memory::MemoryBackend>> = {
+        let ids = memory_registry.list_ids();
+        if let Some(first_id) = ids.first() {
+            memory_registry.get(first_id)
+        } else {
+            None
         }
     };
 
-    // Create skill registry and loader
-    let skill_registry = Arc::new(autohands_skills_dynamic::SkillRegistry::new());
+    // Create skill registry and loader. The registry is shared by the
+    // admin API and the skill tools, and keeps disabled skills in
+    // skills.json
+    let skill_registry = Arc::new(
+        autohands_skills_dynamic::SkillRegistry::new()
+            .with_state_file(autohands_dir().join("skills.json")),
+    );
     let skill_loader = create_skill_loader_for_server(work_dir).await;
 
     // Load skills into registry
@@ -327,26 +270,94 @@ pub(crate) async fn register_tools_with_skill_registry(
         }
     }
 
-    // Register Skill tools with the loader
+    // Extensions built on the memory backend, the question gate and the
+    // skill loader: memory tools, agent tools (agent_spawn, agent_status,
+    // agent_message, etc.) with ask_user, and skill tools
     let skill_loader: Arc<tokio::sync::RwLock<dyn autohands_protocols::skill::SkillLoader>> =
         Arc::new(tokio::sync::RwLock::new(skill_loader));
-
-    let mut skill_ext = SkillToolsExtension::new(skill_loader);
-    match skill_ext.initialize(ctx.clone()).await {
-        Ok(()) => {
-            let tools = skill_ext.manifest().provides.tools.clone();
-            info!("Registered skill tools: {:?}", tools);
-        }
-        Err(e) => {
-            warn!("Failed to initialize skill tools extension: {}", e);
-        }
+    let mut catalog = ExtensionCatalog::new();
+    if let Some(ref backend) = memory_backend {
+        let backend = backend.clone();
+        catalog = catalog.add("tools-memory", ExtensionKind::Tool, move || {
+            Box::new(MemoryToolsExtension::new(backend.clone()))
+        });
     }
+    let tools_registry = skill_registry.clone();
+    let catalog = catalog
+        .add("tools-agent", ExtensionKind::Tool, move || {
+            Box::new(AgentToolsExtension::new().with_question_gate(question_gate.clone()))
+        })
+        .add("tools-skill", ExtensionKind::Tool, move || {
+            Box::new(
+                SkillToolsExtension::new(skill_loader.clone())
+                    .with_registry(tools_registry.clone()),
+            )
+        });
+    let dependent = kernel.bootstrap(&catalog, &selection).await;
+    log_loaded_tools(kernel, &dependent);
+    report.merge(dependent);
 
     // Log total registered tools
     let total_tools = tool_registry.list().len();
-    info!("Total registered tools: {}", total_tools);
+    info!(
+        "Total registered tools: {} ({} extension(s) loaded, {} disabled, {} failed)",
+        total_tools,
+        report.loaded.len(),
+        report.skipped.len(),
+        report.failed.len()
+    );
 
-    (skill_registry, memory_backend, agent_tools_ext)
+    (skill_registry, memory_backend)
+}
+
+/// Built-in system prompt, used while no prompt template file exists.
+const BASE_SYSTEM_PROMPT: &str = r#"You are AutoHands, an omnipotent autonomous agent capable of executing any task.
+
+You have access to various tools for:
+- File operations (read, write, edit, glob, grep)
+- Shell commands
+- Browser automation
+- Desktop control (mouse, keyboard, screenshots, OCR)
+- Web fetching and searching
+- Code analysis
+- Long-term memory (memory_search, memory_get, memory_store)
+
+## Memory
+You have long-term memory capabilities. When answering questions about past conversations,
+user preferences, or historical decisions, use memory_search first to find relevant memories.
+When you learn important information (user preferences, key decisions, facts, action items),
+use memory_store to persist them for future reference.
+
+Execute tasks efficiently and thoroughly."#;
+
+/// Build the refresher of the agents' system prompt: the template (or the
+/// built-in prompt), skill metadata (Progressive Disclosure L1) and standing
+/// memories.
+pub(crate) fn build_context_refresher(
+    config: &Config,
+    skill_registry: Arc<autohands_skills_dynamic::SkillRegistry>,
+    memory_backend: Option<Arc<dyn autohands_protocols::memory::MemoryBackend>>,
+    config_file: Option<&Path>,
+) -> Arc<ContextRefresher> {
+    let refresh = &config.agent.context_refresh;
+    let mut refresher = ContextRefresher::new(Arc::new(LivePrompt::default()))
+        .with_section(Arc::new(TemplateSection::new(refresh.prompt_file.clone(), BASE_SYSTEM_PROMPT)))
+        .with_section(Arc::new(SkillContextSection {
+            injector: SkillMetadataInjector::new(skill_registry),
+        }))
+        .with_check_interval(Duration::from_secs(refresh.file_check_secs.max(1)));
+    if let (Some(query), Some(backend)) = (&refresh.memory_query, memory_backend) {
+        refresher = refresher.with_section(Arc::new(MemorySection::new(backend, query, refresh.memory_limit)));
+    }
+    if refresh.interval_secs > 0 {
+        refresher = refresher.with_interval(Duration::from_secs(refresh.interval_secs));
+    }
+    if refresh.on_file_change {
+        for path in config_file.into_iter().chain(refresh.prompt_file.as_deref()) {
+            refresher = refresher.watch(path);
+        }
+    }
+    Arc::new(refresher)
 }
 
 /// Register available agents with skill metadata injected into system prompt.
@@ -355,6 +366,9 @@ pub(crate) async fn register_agents(
     provider_registry: Arc<ProviderRegistry>,
     tool_registry: Arc<ToolRegistry>,
     skill_registry: Arc<autohands_skills_dynamic::SkillRegistry>,
+    provider_health: Arc<ProviderHealthTable>,
+    context: &Arc<ContextRefresher>,
+    config: &Config,
 ) {
     // Get first available provider for the default agent
     let provider_ids = provider_registry.list_ids();
@@ -372,6 +386,26 @@ pub(crate) async fn register_agents(
         }
     };
 
+    // Fail over to the other providers, each on its configured default model
+    let provider: Arc<dyn autohands_protocols::provider::LLMProvider> = if provider_ids.len() > 1 {
+        let mut failover = FailoverProvider::new(provider, provider_health);
+        for id in &provider_ids[1..] {
+            if let Some(fallback) = provider_registry.get(id) {
+                let model = config.providers.get(id).and_then(|p| p.default_model.clone());
+                failover = failover.with_fallback(fallback, model);
+            }
+        }
+        info!("Provider '{}' fails over to {:?}", default_provider_id, &provider_ids[1..]);
+        Arc::new(failover)
+    } else {
+        provider
+    };
+    // Scrub secrets from the context sent to providers
+    let provider: Arc<dyn autohands_protocols::provider::LLMProvider> = match agent_runtime.redactor() {
+        Some(redactor) => Arc::new(RedactingProvider::new(provider, redactor.clone())),
+        None => provider,
+    };
+
     // Use doubao-seed-1-8-251228 as the default model
     // Note: For Ark platform, you may need to use your endpoint ID instead
     let default_model = "doubao-seed-1-8-251228".to_string();
@@ -383,38 +417,19 @@ pub(crate) async fn register_agents(
         .filter_map(|def| tool_registry.get(&def.id))
         .collect();
 
-    // Generate skill metadata section for system prompt (Progressive Disclosure L1)
-    let skill_injector = SkillMetadataInjector::new(skill_registry.clone());
-    let skill_section = skill_injector.generate_system_prompt_section().await;
-
     // Create general agent config with skill metadata in system prompt
     let mut agent_config = AgentConfig::new("general", "General Agent", &default_model);
+    if let Some(sandbox) = sandbox_policy(config, "general") {
+        agent_config = agent_config.with_sandbox(sandbox);
+    }
 
-    // Build system prompt with skill metadata
-    let base_prompt = r#"You are AutoHands, an omnipotent autonomous agent capable of executing any task.
-
-You have access to various tools for:
-- File operations (read, write, edit, glob, grep)
-- Shell commands
-- Browser automation
-- Desktop control (mouse, keyboard, screenshots, OCR)
-- Web fetching and searching
-- Code analysis
-- Long-term memory (memory_search, memory_get, memory_store)
-
-## Memory
-You have long-term memory capabilities. When answering questions about past conversations,
-user preferences, or historical decisions, use memory_search first to find relevant memories.
-When you learn important information (user preferences, key decisions, facts, action items),
-use memory_store to persist them for future reference.
-
-Execute tasks efficiently and thoroughly."#;
-
-    agent_config.system_prompt = if skill_section.is_empty() {
-        Some(base_prompt.to_string())
-    } else {
-        Some(format!("{}\n{}", base_prompt, skill_section))
-    };
+    // The system prompt is rebuilt while the agent runs
+    let refresh = &config.agent.context_refresh;
+    context.refresh().await;
+    agent_config.system_prompt = context.prompt().get();
+    if refresh.interval_secs > 0 || refresh.on_file_change {
+        context.clone().start();
+    }
 
     // Log skill injection status
     let skill_count = skill_registry.len().await;
@@ -425,22 +440,59 @@ Execute tasks efficiently and thoroughly."#;
         );
     }
 
+    // The review agent gets only the review tools that are registered
+    let review_tools: Vec<Arc<dyn autohands_protocols::tool::Tool>> = tools
+        .iter()
+        .filter(|t| REVIEW_TOOLS.contains(&t.definition().id.as_str()))
+        .cloned()
+        .collect();
+    let mut review_config = review_agent_config(&default_model);
+    review_config.tools = review_tools.iter().map(|t| t.definition().id.clone()).collect();
+    if let Some(sandbox) = sandbox_policy(config, REVIEW_AGENT_ID) {
+        review_config = review_config.with_sandbox(sandbox);
+    }
+
     // Create and register general agent
-    let general_agent = GeneralAgent::new(agent_config, provider.clone(), tools);
+    let general_agent = GeneralAgent::new(agent_config, provider.clone(), tools)
+        .with_streaming(config.agent.stream_turns)
+        .with_live_prompt(context.prompt().clone());
     agent_runtime.register_agent(Arc::new(general_agent));
+    let review_agent = GeneralAgent::new(review_config, provider.clone(), review_tools)
+        .with_streaming(config.agent.stream_turns);
+    agent_runtime.register_agent(Arc::new(review_agent));
 
-    info!("Registered general agent with model: {}", default_model);
+    info!("Registered general and review agents with model: {}", default_model);
     info!("Total registered agents: {}", agent_runtime.list_agents().len());
 }
 
-/// Register available LLM providers based on config and environment variables.
+/// Sandbox of an agent from `[agent.sandbox]` and `[agent.sandboxes]`.
+pub(crate) fn sandbox_policy(config: &Config, agent_id: &str) -> Option<SandboxPolicy> {
+    config.agent.sandbox_for(agent_id).map(|sandbox| SandboxPolicy {
+        allowed_roots: sandbox.allowed_roots.clone(),
+        denied: sandbox.denied.clone(),
+        read_only: sandbox.read_only,
+        container: sandbox.container.as_ref().map(|c| ContainerSandbox {
+            engine: c.engine.clone(),
+            image: c.image.clone(),
+            cpus: c.cpus,
+            memory: c.memory.clone(),
+            network: c.network,
+        }),
+    })
+}
+
+/// Register available LLM providers based on config.
+///
+/// API keys from `<NAME>_API_KEY` variables are already part of the
+/// resolved config.
 pub(crate) async fn register_providers(registry: &ProviderRegistry, config: &Config) {
-    // Iterate over configured providers, falling back to env vars for API keys
+    let selection = extension_selection(config);
     for (name, provider_config) in &config.providers {
-        let api_key = provider_config.api_key.clone()
-            .or_else(|| std::env::var(format!("{}_API_KEY", name.to_uppercase())).ok());
-
-        let Some(api_key) = api_key else {
+        if !selection.is_enabled(&format!("provider-{}", name)) {
+            info!("Skipping provider '{}': disabled in [extensions]", name);
+            continue;
+        }
+        let Some(api_key) = provider_config.api_key.clone() else {
             info!("Skipping provider '{}': no API key configured or in environment", name);
             continue;
         };
@@ -488,29 +540,12 @@ pub(crate) async fn register_providers(registry: &ProviderRegistry, config: &Con
         }
     }
 
-    // Fallback: register from env vars if no providers configured
-    if config.providers.is_empty() {
-        if let Ok(api_key) = std::env::var("ANTHROPIC_API_KEY") {
-            let provider = AnthropicProvider::new(api_key);
-            if let Err(e) = registry.register(Arc::new(provider)) {
-                warn!("Failed to register Anthropic provider: {}", e);
-            } else {
-                info!("Registered Anthropic provider (from env)");
-            }
-        }
-        if let Ok(api_key) = std::env::var("ARK_API_KEY") {
-            let provider = autohands_provider_ark::ArkProvider::new(api_key);
-            if let Err(e) = registry.register(Arc::new(provider)) {
-                warn!("Failed to register Ark provider: {}", e);
-            } else {
-                info!("Registered Ark provider (from env)");
-            }
-        }
-    }
-
     let provider_ids = registry.list_ids();
     if provider_ids.is_empty() {
-        warn!("No LLM providers registered. Configure providers in config or set API key environment variables.");
+        warn!(
+            "No LLM providers registered; running in degraded mode (tools, jobs and the API work, agent runs are refused). {}",
+            autohands_api::PROVIDER_SETUP
+        );
     } else {
         info!("Registered providers: {:?}", provider_ids);
     }
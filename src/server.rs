@@ -9,20 +9,40 @@ use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, Env
 
 use autohands_api::{AppState, InterfaceConfig};
 use autohands_protocols::Channel;
+use autohands_tools_agent::AgentToolsExtension;
 use autohands_channel_web::{WebChannel, WebChannelConfig};
 use autohands_checkpoint::{CheckpointConfig as CpConfig, CheckpointManager, FileCheckpointStore};
-use autohands_config::{Config, ConfigLoader};
+use autohands_config::{Config, ConfigLoader, RedactionConfig};
 use autohands_core::registry::{ChannelRegistry, ProviderRegistry, ToolRegistry};
-use autohands_core::Kernel;
+use autohands_core::redact::{Redactor, RedactorChain, SecretRedactor};
+use autohands_core::{Kernel, QuestionService};
 use autohands_monitor::metrics::MetricsRegistry;
-use autohands_runtime::{AgentLoopConfig, AgentRuntime, AgentRuntimeConfig};
-
-use crate::adapters::{autohands_dir, CheckpointAdapter, MetricsWrappedHandler};
-use crate::register::{register_agents, register_providers, register_tools_with_skill_registry};
+use autohands_monitor::DashboardStats;
+use autohands_runtime::{
+    AgentLoopConfig, AgentRuntime, AgentRuntimeConfig, FeatureFlags, OverridePolicy, ProbeConfig,
+    ProjectDetector, ProviderHealthTable, ProviderProber,
+};
+
+use crate::approval::{build_approval_service, tool_approval_policy};
+use crate::adapters::{
+    autohands_dir, CallbackTurnObserver, CheckpointAdapter, DashboardAdapter, FeatureGatedProvider, MeteredProvider,
+    MetricsWrappedHandler, NoProviderHandler, SkillCatalogAdapter,
+    WebAdminAdapter, workflows_dir,
+};
+use crate::log_redaction::{RedactingMakeWriter, LOG_REDACTOR};
+use crate::register::{
+    build_context_refresher, extension_selection, register_agents, register_providers,
+    register_tools_with_skill_registry, sandbox_policy,
+};
+
+/// How long in-flight agent runs get to stop on shutdown.
+const SHUTDOWN_GRACE: std::time::Duration = std::time::Duration::from_secs(5);
 
 /// Initialize tracing with console and file output.
 ///
 /// Log files are written to ~/.autohands/debug/ with daily rotation and 100MB max size.
+/// Secrets are redacted from both outputs by [`LOG_REDACTOR`].
+/// With the `otel` feature, spans are also exported over OTLP.
 pub(crate) fn init_tracing() -> Result<(), Box<dyn std::error::Error>> {
     // Create log directory
     let log_dir = autohands_dir().join("debug");
@@ -51,29 +71,106 @@ pub(crate) fn init_tracing() -> Result<(), Box<dyn std::error::Error>> {
     let env_filter = EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| EnvFilter::new("info"));
 
-    tracing_subscriber::registry()
+    let subscriber = tracing_subscriber::registry()
         .with(env_filter)
         // Console layer (human-readable text format with colors)
         .with(
             fmt::layer()
+                .with_writer(RedactingMakeWriter::new(std::io::stdout))
                 .with_target(true)
                 .with_ansi(true)
         )
         // File layer (text format without colors)
         .with(
             fmt::layer()
-                .with_writer(non_blocking)
+                .with_writer(RedactingMakeWriter::new(non_blocking))
                 .with_ansi(false)
-        )
-        .init();
+        );
+    // Span export over OTLP, when built with the `otel` feature
+    #[cfg(feature = "otel")]
+    let subscriber = subscriber.with(crate::telemetry::otlp_layer()?);
+    subscriber.init();
 
     Ok(())
 }
 
+/// Redactor of the built-in secret formats and `[redaction]` patterns, or
+/// `None` when redaction is disabled.
+fn build_redactor(
+    config: &RedactionConfig,
+) -> Result<Option<Arc<dyn Redactor>>, Box<dyn std::error::Error>> {
+    if !config.enabled {
+        return Ok(None);
+    }
+    let mut redactor = SecretRedactor::default();
+    for pattern in &config.patterns {
+        redactor = redactor.with_pattern(pattern)?;
+    }
+    Ok(Some(Arc::new(redactor)))
+}
+
+/// Channel bridge settings from the `[channels]` config section.
+fn channel_bridge_config(config: &Config) -> autohands_runloop::ChannelBridgeConfig {
+    use autohands_runloop::{CommandRoute, DeliveryTarget, InboundFilter, RateLimit};
+
+    let channels = config
+        .channels
+        .iter()
+        .map(|(id, channel)| {
+            let commands = channel
+                .commands
+                .iter()
+                .map(|(name, command)| {
+                    let route = CommandRoute {
+                        task_type: command.task_type.clone().unwrap_or_else(|| CommandRoute::default().task_type),
+                        agent: command.agent.clone(),
+                        prompt: command.prompt.clone(),
+                    };
+                    (name.clone(), route)
+                })
+                .collect();
+            let deliver_to = channel
+                .deliver_to
+                .iter()
+                .map(|delivery| DeliveryTarget {
+                    format: serde_json::from_value(serde_json::Value::String(delivery.format.clone()))
+                        .inspect_err(|_| {
+                            warn!("Channel {}: unknown delivery format '{}'", id, delivery.format)
+                        })
+                        .unwrap_or_default(),
+                    file_name: delivery.file_name.clone(),
+                    ..DeliveryTarget::new(&delivery.channel_id, &delivery.target)
+                })
+                .collect();
+            let filter = InboundFilter {
+                mentions: channel.mentions.clone(),
+                command_prefix: channel.command_prefix.clone(),
+                commands,
+                allowed_senders: channel.allowed_senders.clone(),
+                rate_limit: RateLimit {
+                    cooldown_secs: channel.cooldown_secs,
+                    max_tasks_per_hour: channel.max_tasks_per_hour,
+                    message: channel
+                        .throttle_message
+                        .clone()
+                        .unwrap_or_else(|| RateLimit::default().message),
+                },
+                deliver_to,
+            };
+            (id.clone(), filter)
+        })
+        .collect();
+    autohands_runloop::ChannelBridgeConfig {
+        channels,
+        ..Default::default()
+    }
+}
+
 /// Run the server in foreground.
 pub(crate) async fn run_server(
     work_dir: PathBuf,
     config: Config,
+    config_file: Option<PathBuf>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let host = config.server.host.clone();
     let port = config.server.port;
@@ -83,22 +180,66 @@ pub(crate) async fn run_server(
     info!("Starting AutoHands v{}", env!("CARGO_PKG_VERSION"));
     info!("Working directory: {}", work_dir.display());
 
-    // Initialize kernel
-    let kernel = Arc::new(Kernel::new(work_dir.clone()));
-    info!("Kernel initialized");
-
     // Initialize registries
     let provider_registry = Arc::new(ProviderRegistry::new());
     let tool_registry = Arc::new(ToolRegistry::new());
     let channel_registry = Arc::new(ChannelRegistry::new());
 
+    // Initialize kernel; extensions it loads register into the shared registries
+    let kernel = Arc::new(
+        Kernel::new(work_dir.clone()).with_registries(tool_registry.clone(), provider_registry.clone()),
+    );
+    info!("Kernel initialized");
+
     // Register providers based on config and available API keys
     register_providers(&provider_registry, &config).await;
 
-    // Register tools and get skill registry + memory backend + agent tools extension
-    let (skill_registry, memory_backend, agent_tools_ext) = register_tools_with_skill_registry(
-        tool_registry.clone(),
-        provider_registry.clone(),
+    // Record provider latency and token spend for the web dashboard
+    let dashboard_stats = Arc::new(DashboardStats::new());
+    MeteredProvider::instrument_all(&provider_registry, &dashboard_stats);
+
+    // Feature flags from config, overridable at runtime via /admin/features;
+    // providers are refused while their `provider:<id>` flag is off
+    let feature_flags = Arc::new(FeatureFlags::from_config(
+        config.features.flags.clone(),
+        config.features.workspaces.clone(),
+    ));
+    FeatureGatedProvider::gate_all(&provider_registry, &feature_flags);
+
+    // Provider health from periodic one-token probes and real requests;
+    // agents fail over away from unhealthy providers and /health reports it
+    let provider_health = Arc::new(ProviderHealthTable::new(config.monitor.provider_unhealthy_after));
+    if config.monitor.provider_probe_interval_secs > 0 {
+        ProviderProber::new(
+            provider_registry.clone(),
+            provider_health.clone(),
+            ProbeConfig {
+                interval: std::time::Duration::from_secs(config.monitor.provider_probe_interval_secs),
+                timeout: std::time::Duration::from_secs(config.monitor.provider_probe_timeout_secs),
+                models: config.monitor.provider_probe_models.clone(),
+            },
+        )
+        .start();
+    }
+
+    // Jobs, their next run times and run history persist under ~/.autohands/jobs;
+    // the cron tools and the job scheduler share the store
+    let job_store: Arc<dyn autohands_api::JobStore> =
+        Arc::new(autohands_api::FileJobStore::new(autohands_dir()).await?);
+
+    // Questions agents ask through ask_user go to the conversation of their
+    // session; pending ones survive restarts in ~/.autohands/questions.json
+    let question_service = Arc::new(
+        QuestionService::new()
+            .with_channels(channel_registry.clone())
+            .with_store(autohands_dir().join("questions.json")),
+    );
+
+    // Load the extensions enabled by [extensions] and get skill registry + memory backend
+    let (skill_registry, memory_backend) = register_tools_with_skill_registry(
+        &kernel,
+        job_store.clone(),
+        question_service.clone(),
         &work_dir,
         &config,
     ).await;
@@ -130,11 +271,22 @@ pub(crate) async fn run_server(
         info!("Checkpoint system disabled");
         None
     };
+    // Secrets are scrubbed from logs, transcripts, tool results and provider context
+    let redactor = build_redactor(&config.redaction)?;
+    match &redactor {
+        Some(redactor) => LOG_REDACTOR.set(redactor.clone()),
+        None => {
+            LOG_REDACTOR.set(Arc::new(RedactorChain::default()));
+            warn!("Secret redaction disabled");
+        }
+    }
+
     // Create AgentRuntime with config-driven values and optional checkpoint support
     let runtime_config = AgentRuntimeConfig {
         max_concurrent: 10,
         default_loop_config: AgentLoopConfig {
             checkpoint_enabled: config.checkpoint.enabled,
+            context_fallback_model: config.agent.context_fallback_model.clone(),
             ..Default::default()
         },
     };
@@ -142,7 +294,88 @@ pub(crate) async fn run_server(
         provider_registry.clone(),
         tool_registry.clone(),
         runtime_config,
-    );
+    )
+    .with_feature_flags(feature_flags)
+    .with_override_policy(OverridePolicy {
+        enabled: config.agent.overrides.enabled,
+        allowed_models: config.agent.overrides.allowed_models.clone(),
+        max_tokens: config.agent.overrides.max_tokens,
+    });
+    if config.agent.project_brief.enabled {
+        let detector = ProjectDetector::new(config.agent.project_brief.max_tokens);
+        agent_runtime = agent_runtime.with_project_detector(Arc::new(detector));
+    }
+
+    // Lifecycle events of tasks submitted with a callback URL; agent turns and
+    // approval requests are routed to them by session
+    let mut runloop_config = autohands_runloop::RunLoopConfig::default();
+    // Switch to AgentProcessing mode while agents run, and to Background mode when idle
+    runloop_config.mode_policy.enabled = config.runloop.mode_policy;
+    runloop_config.mode_policy.background_after_idle_secs = config.runloop.background_after_idle_secs;
+    runloop_config.metrics_enabled = config.runloop.metrics_enabled;
+    runloop_config.callbacks.max_attempts = config.runloop.callback_max_attempts;
+    runloop_config.callbacks.timeout_secs = config.runloop.callback_timeout_secs;
+    runloop_config.callbacks.allowed_hosts = config.runloop.callback_allowed_hosts.clone();
+    runloop_config.journal.enabled = config.runloop.journal;
+    runloop_config.journal.capacity = config.runloop.journal_capacity;
+    let callbacks = Arc::new(autohands_runloop::CallbackDispatcher::new(
+        runloop_config.callbacks.clone(),
+    ));
+    agent_runtime = agent_runtime.with_turn_observer(Arc::new(CallbackTurnObserver {
+        callbacks: callbacks.clone(),
+    }));
+
+    // Hash-chained record of tool calls, approvals and agent runs
+    let audit_log = if config.audit.enabled {
+        let path = config.audit.path
+            .clone()
+            .map(|p| PathBuf::from(ConfigLoader::expand_path(&p.to_string_lossy())))
+            .unwrap_or_else(|| autohands_dir().join("audit.jsonl"));
+        let mut redaction = autohands_core::RedactionRules::default();
+        for key in &config.audit.redact_keys {
+            redaction = redaction.with_key(key.clone());
+        }
+        for pattern in &config.audit.redact_patterns {
+            redaction = redaction.with_pattern(pattern)?;
+        }
+        if config.redaction.enabled {
+            for pattern in &config.redaction.patterns {
+                redaction = redaction.with_pattern(pattern)?;
+            }
+        }
+        let log = Arc::new(autohands_core::AuditLog::open(&path)?.with_redaction(redaction));
+        info!("Audit log: {}", path.display());
+        agent_runtime = agent_runtime.with_audit_log(log.clone());
+        Some(log)
+    } else {
+        info!("Audit log disabled");
+        None
+    };
+
+    // Human approvals for workflow steps and, optionally, risky tool calls
+    let approvals_page = format!("http://{}:{}/approvals", host, web_port);
+    let mut approval_service =
+        build_approval_service(&config.approval, &approvals_page, callbacks.clone())
+            .with_questions(question_service.clone());
+    if let Some(log) = &audit_log {
+        approval_service = approval_service.with_audit_log(log.clone());
+    }
+    let approval_service = Arc::new(approval_service);
+    if config.approval.yolo {
+        warn!("Tool approval is bypassed (--yolo): every tool call runs unattended");
+    } else if let Some(policy) = tool_approval_policy(&config.approval) {
+        info!(
+            "Tool approval policy: low={:?}, medium={:?}, high={:?}, {} tool override(s)",
+            policy.low,
+            policy.medium,
+            policy.high,
+            policy.tools.len()
+        );
+        agent_runtime = agent_runtime.with_approval(autohands_runtime::ToolApproval {
+            gate: approval_service.clone(),
+            policy,
+        });
+    }
 
     if let Some(ref cp_manager) = checkpoint_manager {
         let adapter = Arc::new(CheckpointAdapter { manager: cp_manager.clone() });
@@ -157,34 +390,72 @@ pub(crate) async fn run_server(
     }
 
     // Create HistoryCompressor for context length recovery
+    let mut base_summarizer: Option<Arc<dyn autohands_runtime::Summarizer>> = None;
     {
-        use autohands_runtime::{HistoryCompressor, LLMSummarizer, SummarizerConfig};
+        use autohands_runtime::{HistoryCompressor, LLMSummarizer, StrategySummarizer, SummarizerConfig};
         let provider_ids = provider_registry.list_ids();
         if !provider_ids.is_empty() {
-            if let Some(provider) = provider_registry.get(&provider_ids[0]) {
+            if let Some(mut provider) = provider_registry.get(&provider_ids[0]) {
+                if let Some(ref redactor) = redactor {
+                    provider = Arc::new(autohands_runtime::RedactingProvider::new(provider, redactor.clone()));
+                }
                 let summarizer_config = SummarizerConfig::default();
                 let summarizer = Arc::new(LLMSummarizer::new(provider, summarizer_config.clone()));
-                let compressor = Arc::new(HistoryCompressor::new(summarizer, summarizer_config));
+                let settings = &config.memory.summarizer;
+                let strategy = settings.strategy.parse().unwrap_or_else(|e: String| {
+                    warn!("{}, using default", e);
+                    Default::default()
+                });
+                let strategy_summarizer = Arc::new(
+                    StrategySummarizer::new(summarizer.clone(), strategy)
+                        .with_chunk_size(settings.chunk_size),
+                );
+                let compressor = Arc::new(HistoryCompressor::new(strategy_summarizer, summarizer_config));
                 agent_runtime = agent_runtime.with_compressor(compressor);
-                info!("HistoryCompressor wired into AgentRuntime");
+                base_summarizer = Some(summarizer);
+                info!("HistoryCompressor wired into AgentRuntime (strategy={})", strategy);
             }
         }
     }
 
+    if let Some(ref redactor) = redactor {
+        agent_runtime = agent_runtime.with_redactor(redactor.clone());
+    }
+    // Direct invocations (API, workflow steps) run under the default agent's sandbox
+    if let Some(sandbox) = sandbox_policy(&config, "general") {
+        agent_runtime = agent_runtime.with_invoke_sandbox(sandbox);
+    }
     let agent_runtime = Arc::new(agent_runtime);
 
     // Inject AgentRuntime into tools-agent extension (post-initialization)
-    if let Some(ref ext) = agent_tools_ext {
+    let agent_tools_ext = kernel.extension("tools-agent");
+    let agent_tools_ext = agent_tools_ext.as_ref().and_then(|ext| ext.as_any().downcast_ref::<AgentToolsExtension>());
+    if let Some(ext) = agent_tools_ext {
         ext.set_runtime(agent_runtime.clone());
         info!("AgentRuntime injected into tools-agent extension");
     }
+    // Sub-agents spawned through the agent tools appear in task trees
+    let sub_agents = agent_tools_ext.and_then(AgentToolsExtension::manager).map(|manager| {
+        Arc::new(crate::adapters::SpawnedAgentDirectory { manager })
+            as Arc<dyn autohands_api::SubAgentDirectory>
+    });
 
-    // Register agents with skill metadata injected into system prompt
+    // Register agents with skill metadata injected into system prompt, kept
+    // fresh while they run
+    let context_refresher = build_context_refresher(
+        &config,
+        skill_registry.clone(),
+        memory_backend.clone(),
+        config_file.as_deref(),
+    );
     register_agents(
         &agent_runtime,
         provider_registry.clone(),
         tool_registry.clone(),
-        skill_registry,
+        skill_registry.clone(),
+        provider_health.clone(),
+        &context_refresher,
+        &config,
     ).await;
 
     // Initialize monitor system
@@ -204,53 +475,117 @@ pub(crate) async fn run_server(
     std::fs::create_dir_all(&transcript_dir)?;
     info!("Session transcripts will be saved to: {}", transcript_dir.display());
 
+    // Idempotency keys persist so client retries across restarts stay deduplicated
+    let idempotency_store = Arc::new(
+        autohands_api::FileIdempotencyStore::new(
+            autohands_dir(),
+            std::time::Duration::from_secs(config.server.idempotency_ttl_secs),
+        )
+        .await?,
+    );
+
     // Create app state
-    let state = Arc::new(AppState::new(
+    let mut state = AppState::new(
         provider_registry.clone(),
         tool_registry.clone(),
         kernel.clone(),
         agent_runtime.clone(),
         transcript_dir,
-    ));
+    )
+    .with_idempotency_store(idempotency_store)
+    .with_provider_required()
+    .with_provider_health(provider_health)
+    .with_context_refresher(context_refresher)
+    .with_channels(channel_registry.clone())
+    .with_skill_catalog(Arc::new(SkillCatalogAdapter {
+        skill_registry: skill_registry.clone(),
+    }));
+    if let Some(ref redactor) = redactor {
+        state = state.with_transcript_redactor(redactor.clone());
+    }
+    let state = Arc::new(state);
 
     // Create and start RunLoop
-    use autohands_runloop::{ChannelBridge, RunLoop, RunLoopConfig, RunLoopMode};
+    use autohands_runloop::{ChannelBridge, RunLoop, RunLoopMode};
     use autohands_api::RunLoopState;
     use std::time::Duration;
 
-    let runloop_config = RunLoopConfig::default();
-    let run_loop = Arc::new(RunLoop::new(runloop_config));
+    // The RunLoop shares the runtime's trace log, so task timelines include
+    // agent turns, provider calls and tools
+    let run_loop = Arc::new(
+        RunLoop::new(runloop_config)
+            .with_callback_dispatcher(callbacks)
+            .with_trace_log(agent_runtime.trace_log().clone()),
+    );
 
     // Create RunLoop state for HTTP API
     let runloop_state = Arc::new(RunLoopState::from_runloop(run_loop.clone()));
 
+    // Task templates live in ~/.autohands/templates, shared with the CLI
+    let template_store: Arc<dyn autohands_api::TemplateStore> =
+        Arc::new(autohands_api::FileTemplateStore::new(autohands_dir()).await?);
+
     // Initialize Web Channel
     let web_channel_config = WebChannelConfig {
         host: host.clone(),
         port: web_port,
     };
-    let web_channel = Arc::new(WebChannel::new("web", web_channel_config));
-    channel_registry.register(web_channel.clone())?;
+    let web_admin = Arc::new(WebAdminAdapter {
+        tool_registry: tool_registry.clone(),
+        skill_registry: skill_registry.clone(),
+        template_store: template_store.clone(),
+    });
+    let web_metrics = Arc::new(DashboardAdapter {
+        stats: dashboard_stats.clone(),
+        run_loop: run_loop.clone(),
+        agent_runtime: agent_runtime.clone(),
+    });
+    let web_tasks = Arc::new(crate::adapters::TaskTreeAdapter {
+        run_loop: run_loop.clone(),
+        agent_runtime: agent_runtime.clone(),
+        transcript_manager: state.transcript_manager.clone(),
+        sub_agents: sub_agents.clone(),
+    });
+    let web_channel = Arc::new(
+        WebChannel::new("web", web_channel_config)
+            .with_admin_backend(web_admin)
+            .with_metrics_source(web_metrics)
+            .with_approval_gate(approval_service.clone())
+            .with_task_tree_source(web_tasks),
+    );
+    if extension_selection(&config).is_enabled("channel-web") {
+        channel_registry.register(web_channel.clone())?;
+        info!("Web Channel at http://{}:{}", host, web_port);
+    } else {
+        info!("Web Channel disabled in [extensions]");
+    }
+    crate::mqtt_triggers::start_mqtt(&config.triggers, &run_loop, &channel_registry).await;
 
     // Start all channels
     channel_registry.start_all().await?;
-    info!("Web Channel started at http://{}:{}", host, web_port);
 
     // Create and start channel bridge (connects channels to RunLoop)
     let channel_bridge = ChannelBridge::new(
         channel_registry.clone(),
         run_loop.clone(),
-    );
+    )
+    .with_config(channel_bridge_config(&config))
+    .with_questions(question_service.clone());
     channel_bridge.start().await;
     info!("ChannelBridge started, listening on {} channel(s)", channel_registry.list_ids().len());
 
     // Configure RunLoop with handler (optionally wrapped with metrics) and channel registry
     use autohands_runloop::RuntimeAgentEventHandler;
     let inner_handler = Arc::new(RuntimeAgentEventHandler::new(agent_runtime.clone(), &config.agent.default));
-    let handler: Arc<dyn autohands_runloop::AgentEventHandler> = if config.monitor.enabled {
+    let handler: Arc<dyn autohands_runloop::AgentEventHandler> = if provider_registry.list_ids().is_empty() {
+        // Degraded mode: channel messages and jobs get setup instructions
+        warn!("No LLM provider: agent runs are refused until one is configured");
+        Arc::new(NoProviderHandler)
+    } else if config.monitor.enabled {
         Arc::new(MetricsWrappedHandler {
             inner: inner_handler,
             metrics: metrics_registry.clone(),
+            stats: dashboard_stats.clone(),
             active_count: std::sync::atomic::AtomicU64::new(0),
         })
     } else {
@@ -260,6 +595,15 @@ pub(crate) async fn run_server(
     run_loop.set_channel_registry(channel_registry.clone()).await;
     info!("RunLoop configured with agent handler and channel registry");
 
+    // Garbage-collect orphaned browser profiles, stale temp files, expired
+    // artifacts and old debug logs at startup and then hourly
+    let gc = autohands_runloop::ResourceCleanupObserver::new(|| {
+        let options = autohands_daemon::GcOptions::new(autohands_dir());
+        tokio::task::spawn_blocking(move || autohands_daemon::gc::collect_garbage(&options));
+    })
+    .with_interval(Duration::from_secs(60 * 60));
+    run_loop.add_observer("resource_gc", Arc::new(gc)).await;
+
     // Start RunLoop in background (run for 100 years = effectively forever)
     let run_loop_handle = run_loop.clone();
     tokio::spawn(async move {
@@ -280,7 +624,80 @@ pub(crate) async fn run_server(
     api_ws_channel.start().await?;
     info!("API WebSocket Channel registered for response routing");
 
-    let hybrid_state = Arc::new(autohands_api::HybridAppState::new(state.clone(), runloop_state, api_ws_channel));
+    // Persist workflows and their executions so interrupted runs resume after restart
+    let workflow_store = Arc::new(
+        autohands_api::SqliteWorkflowStore::open(autohands_dir().join("workflows.db")).await?,
+    );
+    if let Err(e) =
+        autohands_api::workflow::register_builtin_workflows(workflow_store.as_ref()).await
+    {
+        warn!("Failed to register built-in workflows: {}", e);
+    }
+    match autohands_api::workflow::register_workflow_dir(workflow_store.as_ref(), &workflows_dir())
+        .await
+    {
+        Ok(0) => {}
+        Ok(n) => info!("Loaded {} workflow(s) from {:?}", n, workflows_dir()),
+        Err(e) => warn!("Failed to load workflow files: {}", e),
+    }
+    let (job_cancel, job_cancel_rx) = tokio::sync::watch::channel(false);
+    {
+        let scheduler = Arc::new(
+            autohands_api::JobScheduler::new(job_store.clone(), runloop_state.clone())
+                .with_run_tracker(agent_runtime.clone())
+                .with_backup_runner(Arc::new(crate::adapters::StateBackupRunner))
+                .with_page_watcher(Arc::new(autohands_api::PageWatcher::new(
+                    Arc::new(crate::adapters::HttpPageFetcher::new()),
+                    autohands_dir().join("watch"),
+                )))
+                .with_channels(channel_registry.clone())
+                .with_max_concurrent(config.scheduler.max_concurrent_jobs),
+        );
+        tokio::spawn(scheduler.run(job_cancel_rx));
+    }
+    let step_executor = Arc::new(autohands_api::workflow::RuntimeAgentExecutor::new(
+        agent_runtime.clone(),
+    ));
+    let mut hybrid_state =
+        autohands_api::HybridAppState::new(state.clone(), runloop_state, api_ws_channel)
+            .with_job_store(job_store)
+            .with_workflow_store(workflow_store)
+            .with_agent_executor(step_executor.clone())
+            .with_tool_executor(step_executor)
+            .with_template_store(template_store)
+            .with_approval_gate(approval_service)
+            .with_question_gate(question_service);
+    if let Some(sub_agents) = sub_agents {
+        hybrid_state = hybrid_state.with_sub_agents(sub_agents);
+    }
+    if let Some(audit_log) = audit_log {
+        hybrid_state = hybrid_state.with_audit_log(audit_log);
+    }
+    let hybrid_state = Arc::new(hybrid_state);
+    {
+        let executor = hybrid_state.workflow_executor.clone();
+        tokio::spawn(async move {
+            match executor.resume_incomplete().await {
+                Ok(0) => {}
+                Ok(n) => info!("Resumed {} interrupted workflow execution(s)", n),
+                Err(e) => warn!("Failed to resume workflow executions: {}", e),
+            }
+        });
+    }
+    // Watch-and-act file triggers; the watchers run as long as the server
+    let _file_watchers = crate::file_triggers::start_file_triggers(
+        &config.triggers,
+        run_loop.clone(),
+        hybrid_state.workflow_store.clone(),
+        hybrid_state.workflow_executor.clone(),
+    )
+    .await;
+    let _email_triggers = crate::email_triggers::start_email_triggers(
+        &config.triggers,
+        run_loop.clone(),
+        hybrid_state.workflow_store.clone(),
+        hybrid_state.workflow_executor.clone(),
+    );
     let base_router = autohands_api::create_router_with_hybrid_state(hybrid_state);
 
     // Monitor routes (/health, /metrics) are already built into the API router
@@ -295,17 +712,32 @@ pub(crate) async fn run_server(
     info!("  POST /tasks          - 提交任务");
     info!("  GET  /tasks/{{id}}     - 查询状态");
     info!("  POST /webhook/{{id}}   - 触发 Webhook");
+    info!("  POST /templates/{{id}}/run - 运行任务模板");
     info!("  GET  /ws             - WebSocket");
     if config.monitor.enabled {
         info!("  GET  {}       - 健康检查", config.monitor.health_endpoint);
         info!("  GET  {}      - Prometheus 指标", config.monitor.metrics_endpoint);
     }
 
+    // Summarize each day's sessions into one memory entry
+    match base_summarizer {
+        Some(summarizer) => crate::digest::spawn_daily_digest(
+            &config.memory.digest,
+            state.transcript_manager.clone(),
+            summarizer,
+            memory_backend.clone(),
+            channel_registry.clone(),
+        ),
+        None if config.memory.digest.enabled => warn!("No LLM provider, daily digest disabled"),
+        None => {}
+    }
+
     // Spawn periodic cleanup task for session, history, and transcript memory management (#6, #16)
     {
         let session_mgr = agent_runtime.session_manager().clone();
         let history_mgr = agent_runtime.history_manager().clone();
         let transcript_mgr = state.transcript_manager.clone();
+        let idempotency_store = state.idempotency_store.clone();
         let agent_runtime_clone = agent_runtime.clone();
         tokio::spawn(async move {
             let cleanup_interval = std::time::Duration::from_secs(10 * 60); // 10 minutes
@@ -319,6 +751,11 @@ pub(crate) async fn run_server(
                     history_mgr.remove(session_id);
                     transcript_mgr.remove_writer(session_id).await;
                 }
+                match idempotency_store.purge_expired().await {
+                    Ok(0) => {}
+                    Ok(n) => info!("Periodic cleanup: removed {} expired idempotency key(s)", n),
+                    Err(e) => warn!("Failed to purge idempotency keys: {}", e),
+                }
                 if !expired.is_empty() {
                     info!(
                         "Periodic cleanup: removed {} idle session(s), remaining sessions={}, histories={}",
@@ -339,6 +776,7 @@ pub(crate) async fn run_server(
 
     // Clone run_loop and shutdown_notify for use in the shutdown signal handler
     let shutdown_run_loop = run_loop.clone();
+    let shutdown_runtime = agent_runtime.clone();
     let shutdown_notify = state.shutdown_notify.clone();
     let shutdown_signal = async move {
         let ctrl_c = tokio::signal::ctrl_c();
@@ -390,9 +828,20 @@ pub(crate) async fn run_server(
         // Stop RunLoop so in-flight agents can flush checkpoints
         shutdown_run_loop.stop();
 
-        // Grace period: allow in-flight tasks to complete
-        info!("Waiting 5s grace period for in-flight tasks...");
-        tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+        // Cancel running agents and everything they spawned, giving them a
+        // grace period to stop
+        info!("Waiting up to {:?} for in-flight agents...", SHUTDOWN_GRACE);
+        let report = shutdown_runtime.shutdown(SHUTDOWN_GRACE).await;
+        if report.is_clean() {
+            info!("Cancelled {} in-flight agent runs", report.cancelled.len());
+        } else {
+            warn!(
+                "{} of {} agent runs did not stop in time: {}",
+                report.forced.len(),
+                report.cancelled.len(),
+                report.forced.join(", ")
+            );
+        }
     };
 
     axum::serve(listener, app)
@@ -400,5 +849,13 @@ pub(crate) async fn run_server(
         .await?;
 
     info!("Shutting down...");
+    let _ = job_cancel.send(true);
+
+    // Extensions clean up what outlived the runs, such as background processes
+    for manifest in kernel.list_extensions().iter().rev() {
+        if let Err(e) = kernel.unload_extension(&manifest.id).await {
+            warn!("Failed to shut down extension {}: {}", manifest.id, e);
+        }
+    }
     Ok(())
 }
@@ -0,0 +1,57 @@
+//! OpenTelemetry span export (`otel` feature).
+//!
+//! When `OTEL_EXPORTER_OTLP_ENDPOINT` (or `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT`)
+//! is set, tracing spans are exported over OTLP/HTTP to a collector such as
+//! Jaeger or Tempo: RunLoop phases, task dispatch, agent runs and turns,
+//! provider calls with their token counts, and tool executions. The usual
+//! `OTEL_*` variables (headers, timeout, `OTEL_SERVICE_NAME`) apply.
+
+use std::sync::OnceLock;
+
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::SpanExporter;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use opentelemetry_sdk::Resource;
+use tracing::Subscriber;
+use tracing_opentelemetry::OpenTelemetryLayer;
+use tracing_subscriber::registry::LookupSpan;
+
+/// Service name reported when `OTEL_SERVICE_NAME` is unset.
+const DEFAULT_SERVICE_NAME: &str = "autohands";
+
+static PROVIDER: OnceLock<SdkTracerProvider> = OnceLock::new();
+
+/// Tracing layer exporting spans over OTLP, if an endpoint is configured.
+pub(crate) fn otlp_layer<S>(
+) -> Result<Option<OpenTelemetryLayer<S, opentelemetry_sdk::trace::Tracer>>, Box<dyn std::error::Error>>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    let configured = ["OTEL_EXPORTER_OTLP_ENDPOINT", "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT"]
+        .iter()
+        .any(|var| std::env::var(var).is_ok_and(|v| !v.is_empty()));
+    if !configured {
+        return Ok(None);
+    }
+
+    let exporter = SpanExporter::builder().with_http().build()?;
+    let service_name = std::env::var("OTEL_SERVICE_NAME")
+        .unwrap_or_else(|_| DEFAULT_SERVICE_NAME.to_string());
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(Resource::builder().with_service_name(service_name).build())
+        .build();
+    let tracer = provider.tracer(DEFAULT_SERVICE_NAME);
+    let _ = PROVIDER.set(provider);
+
+    Ok(Some(tracing_opentelemetry::layer().with_tracer(tracer)))
+}
+
+/// Flush spans not yet exported.
+pub(crate) fn shutdown() {
+    if let Some(provider) = PROVIDER.get() {
+        if let Err(e) = provider.shutdown() {
+            eprintln!("Failed to flush OpenTelemetry spans: {}", e);
+        }
+    }
+}